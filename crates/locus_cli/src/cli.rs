@@ -43,12 +43,30 @@ pub enum Command {
         /// Show the setup wizard first (configure API keys). Use when no keys are set or to test.
         #[arg(long)]
         onboarding: bool,
+        /// Mirror the chat as plain text to .locus/live-transcript.txt (also LOCUS_TRANSCRIPT=1)
+        #[arg(long)]
+        transcript: bool,
     },
     /// Inspect and call ToolBus tools
     Toolbus {
         #[command(subcommand)]
         action: ToolbusAction,
     },
+    /// Browse and restore captured versions of a file
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// List and restore per-turn git checkpoints (enable with LOCUS_CHECKPOINTS=1)
+    Checkpoints {
+        #[command(subcommand)]
+        action: CheckpointsAction,
+    },
+    /// Browse, export and delete saved agent sessions
+    Sessions {
+        #[command(subcommand)]
+        action: SessionsAction,
+    },
     /// Inspect and test LLM providers
     Providers {
         #[command(subcommand)]
@@ -64,11 +82,54 @@ pub enum Command {
         #[command(subcommand)]
         action: McpAction,
     },
+    /// Serve locus as an ACP (Agent Client Protocol) agent on stdio, for editors like Zed
+    Acp {
+        /// Working directory for settings (default: current directory; sessions use the editor's cwd)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
     /// LocusGraph cache and event queue
     Graph {
         #[command(subcommand)]
         action: GraphAction,
     },
+    /// git blame with the locus session and turn that added each line
+    Blame {
+        /// File to blame
+        file: String,
+        /// Repository root (default: enclosing git repo)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
+    /// Markdown standup report from session turns, locus co-authored commits and completed tasks
+    Journal {
+        /// Start of the window: today, yesterday, week, <N>h, <N>d, <N>w or YYYY-MM-DD
+        #[arg(long, default_value = "yesterday")]
+        since: String,
+        /// Repository root (default: enclosing git repo)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
+    /// Plans and tasks the agent tracks with task_list, from .locus/locus.db
+    Tasks {
+        /// Plan to show (default: list every plan)
+        plan: Option<String>,
+        /// Repository root (default: enclosing git repo)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
+    /// Guided demo session on a bundled sample repo, with a scripted model (no API key needed)
+    Tutorial {
+        /// Directory to create the sample repo in, kept afterwards (default: a temporary directory)
+        #[arg(long)]
+        dir: Option<String>,
+    },
+    /// Report the optional features compiled into this build and the programs found on PATH
+    Features {
+        /// Repository root for language server config (default: enclosing git repo)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
     /// Start interactive agent session
     Run {
         /// Model to use (e.g. claude-sonnet-4-20250514, glm-5)
@@ -87,8 +148,49 @@ pub enum Command {
         #[arg(long)]
         max_tokens: Option<u32>,
         /// Initial message/prompt to start with
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "command")]
         prompt: Option<String>,
+        /// Run a custom command from .locus/commands/<name>.md as the prompt
+        #[arg(long, value_name = "NAME")]
+        command: Option<String>,
+        /// Arguments for --command (its $ARGUMENTS)
+        #[arg(requires = "command", trailing_var_arg = true)]
+        args: Vec<String>,
+    },
+    /// Finish a turn cut off by a crash, from its recovery checkpoint
+    Resume {
+        /// Session id, id prefix or slug
+        #[arg(required_unless_present = "last", conflicts_with = "last")]
+        session: Option<String>,
+        /// The most recently interrupted session
+        #[arg(long)]
+        last: bool,
+        /// Model to use (default: the session's)
+        #[arg(long)]
+        model: Option<String>,
+        /// Provider to use (default: the session's)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Working directory (default: current directory)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
+    /// Work on an issue: branch, planned session, then push and open a PR (needs `gh`)
+    Work {
+        /// Issue URL, `#123` or `123`
+        issue: String,
+        /// Model to use
+        #[arg(long)]
+        model: Option<String>,
+        /// Provider to use (anthropic, zai)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Working directory (default: current directory)
+        #[arg(long)]
+        workdir: Option<String>,
+        /// Maximum turns per session
+        #[arg(long)]
+        max_turns: Option<u32>,
     },
 }
 
@@ -118,6 +220,16 @@ pub enum ConfigAction {
         #[arg(long)]
         from_env: bool,
     },
+    /// Set the TUI language (LOCUS_LOCALE); lists available locales when no code is given
+    Locale {
+        /// Locale code (e.g. en, de); `keys` shows message keys instead of text
+        code: Option<String>,
+    },
+    /// Set the TUI theme (LOCUS_THEME); lists the themes when no name is given
+    Theme {
+        /// Theme name (e.g. light, high-contrast, colorblind); `custom` uses .locus/theme.toml
+        name: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -126,6 +238,22 @@ pub enum GraphAction {
     ClearQueue,
     /// Remove the LocusGraph cache and queue DB (same as clear-queue). Path: LOCUSGRAPH_DB_PATH or ~/.locus/locus_graph_cache.db
     Clean,
+    /// Send the events still queued for the LocusGraph server and report how many are pending
+    Flush,
+    /// Delete local memories past their TTL and merge near-duplicates (LOCUSGRAPH_RETENTION sets TTLs)
+    Prune {
+        /// Report what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Search memory as the agent recalls it and print the memories, most relevant first
+    Search {
+        /// What to look up
+        query: String,
+        /// Most memories to return
+        #[arg(long, default_value = "10")]
+        limit: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -145,6 +273,89 @@ pub enum ToolbusAction {
         #[arg(short, long)]
         args: String,
     },
+    /// Per-tool call counts, latency percentiles and failure rates from .locus/locus.db
+    Stats {
+        /// Start of the window: today, yesterday, week, <N>h, <N>d, <N>w or YYYY-MM-DD (default: all calls)
+        #[arg(long)]
+        since: Option<String>,
+        /// Repository root (default: enclosing git repo)
+        #[arg(long)]
+        workdir: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HistoryAction {
+    /// List every captured version of a file
+    List {
+        /// File path (relative to repo root)
+        path: String,
+    },
+    /// Show one version as a diff against the previous version
+    Show {
+        /// File path (relative to repo root)
+        path: String,
+        /// Version number (0 = before the first edit)
+        version: usize,
+    },
+    /// Restore a file to a captured version (undoable with undo_edit)
+    Restore {
+        /// File path (relative to repo root)
+        path: String,
+        /// Version number (0 = before the first edit)
+        version: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CheckpointsAction {
+    /// List checkpoints, newest first
+    List {
+        /// Only show checkpoints from sessions whose id starts with this
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Restore the working tree to a checkpoint (the current state is checkpointed first)
+    Restore {
+        /// Checkpoint id (commit hash prefix) or `<session>/<turn>`
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SessionsAction {
+    /// List saved sessions, most recent first
+    List,
+    /// Print a session's transcript
+    Show {
+        /// Session id, id prefix or slug
+        session: String,
+    },
+    /// Write a session's transcript as markdown or JSON
+    Export {
+        /// Session id, id prefix or slug
+        session: String,
+        /// Transcript format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ExportFormat,
+        /// File to write (default: stdout)
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Delete a saved session and its session log
+    Delete {
+        /// Session id, id prefix or slug
+        session: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Readable transcript: messages, tool calls and results
+    #[default]
+    Markdown,
+    /// The saved session as stored (turns, blocks, token usage)
+    Json,
 }
 
 #[derive(Subcommand)]
@@ -193,7 +404,7 @@ pub enum McpAction {
         /// Environment variables (KEY=VALUE format)
         #[arg(short = 'e', long)]
         env: Vec<String>,
-        /// Authentication type (bearer, basic, api_key)
+        /// Authentication type (bearer, basic, api_key, oauth)
         #[arg(long)]
         auth_type: Option<String>,
         /// Authentication token (can use $ENV_VAR for env var references)
@@ -241,6 +452,31 @@ pub enum McpAction {
         server_id: String,
     },
 
+    /// Log in to a remote server that uses OAuth (tokens go to the OS keyring)
+    Login {
+        /// Server ID to log in to
+        server_id: String,
+        /// Use the device code flow (enter a code on another device) instead of a browser
+        #[arg(long)]
+        device: bool,
+    },
+
+    /// Forget the OAuth tokens for a server
+    Logout {
+        /// Server ID to log out of
+        server_id: String,
+    },
+
+    /// Serve locus's own tools to another agent or editor over stdio MCP
+    Serve {
+        /// Tools to expose, comma-separated (default: all, plus `task` when an LLM is configured)
+        #[arg(long, value_delimiter = ',')]
+        tools: Vec<String>,
+        /// Repository the tools work in (default: current directory)
+        #[arg(short, long)]
+        workdir: Option<String>,
+    },
+
     /// Call an MCP tool directly
     Call {
         /// Tool name (format: server_id.tool_name or mcp.server_id.tool_name)
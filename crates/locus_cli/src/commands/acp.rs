@@ -0,0 +1,25 @@
+//! `locus acp` command - serve locus as an ACP agent for editors.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use locus_runtime::{LocusAcpAgent, RuntimeConfig};
+use locus_toolbus::acp::AcpConnection;
+
+pub async fn handle(workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(w) => PathBuf::from(w),
+        None => std::env::current_dir()?,
+    };
+    // Each session runs in the cwd the editor gives it; this is only the template.
+    let config = RuntimeConfig::from_env(repo_root);
+
+    // stdout carries JSON-RPC, so everything for humans goes to stderr.
+    let connection = AcpConnection::new();
+    let agent = Arc::new(LocusAcpAgent::from_env(connection.client(), config).await?);
+    eprintln!("locus: ACP agent ready on stdio");
+    connection.serve_stdio(agent.clone()).await?;
+    agent.shutdown().await;
+    Ok(())
+}
@@ -0,0 +1,260 @@
+//! `locus blame` — git blame with the locus session and turn behind each line.
+//!
+//! Lines come from `git blame --porcelain` on the working tree (untracked files
+//! show as not committed). Each line that a recorded agent edit added is tagged
+//! `<session>#<turn>`, from the `file_changes` table the runtime writes to
+//! `.locus/locus.db`, and the prompt of every tagged turn is listed below the
+//! file so reviewers can trace machine-written lines back to the request.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{Local, TimeZone};
+use console::style;
+use locus_core::db::{self, FileChange};
+use serde::Serialize;
+
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+/// Hash git blame reports for lines that are not committed yet.
+const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Debug, Serialize)]
+struct Blame {
+    path: String,
+    lines: Vec<BlameLine>,
+    turns: Vec<BlameTurn>,
+}
+
+#[derive(Debug, Serialize)]
+struct BlameLine {
+    line: usize,
+    /// None when the line is not committed.
+    commit: Option<String>,
+    author: Option<String>,
+    /// Index into `turns` of the turn that added the line.
+    turn: Option<usize>,
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct BlameTurn {
+    label: String,
+    session_id: String,
+    session: String,
+    turn: u32,
+    ts: i64,
+    prompt: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct GitLine {
+    commit: Option<String>,
+    author: Option<String>,
+}
+
+pub async fn handle(file: String, workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(dir) => PathBuf::from(dir),
+        None => find_repo_root()?,
+    };
+    let relative = repo_relative(&repo_root, &file)?;
+
+    let blame = tokio::task::spawn_blocking(move || -> Result<Blame> {
+        let text = std::fs::read_to_string(repo_root.join(&relative))
+            .with_context(|| format!("Failed to read {}", relative))?;
+        let git = git_blame(&repo_root, &relative);
+        let changes = db::file_changes_for(&repo_root, &relative)?;
+        Ok(build_blame(relative, &text, &git, &changes))
+    })
+    .await??;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::to_value(&blame)?);
+    } else {
+        print_blame(&blame);
+    }
+    Ok(())
+}
+
+/// `file` (absolute, or relative to the current directory or else the repo root)
+/// as a `/`-separated path relative to the repo root.
+fn repo_relative(repo_root: &Path, file: &str) -> Result<String> {
+    let mut full = std::env::current_dir()?.join(file);
+    if !full.exists() {
+        full = repo_root.join(file);
+    }
+    let full = full
+        .canonicalize()
+        .with_context(|| format!("File not found: {}", file))?;
+    let root = repo_root
+        .canonicalize()
+        .with_context(|| format!("Repository not found: {}", repo_root.display()))?;
+    let relative = full
+        .strip_prefix(&root)
+        .map_err(|_| anyhow!("{} is outside the repository {}", file, root.display()))?;
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Commit and author per line, from `git blame --porcelain`. Empty when git
+/// fails (no repo, untracked file), in which case every line is uncommitted.
+fn git_blame(repo_root: &Path, path: &str) -> Vec<GitLine> {
+    let output = Command::new("git")
+        .args(["blame", "--porcelain", "--", path])
+        .current_dir(repo_root)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn parse_porcelain(porcelain: &str) -> Vec<GitLine> {
+    let mut authors: HashMap<&str, &str> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut commit = "";
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            lines.push(if commit == UNCOMMITTED {
+                GitLine::default()
+            } else {
+                GitLine {
+                    commit: Some(commit.chars().take(8).collect()),
+                    author: authors.get(commit).map(|a| a.to_string()),
+                }
+            });
+        } else if let Some(author) = line.strip_prefix("author ") {
+            authors.insert(commit, author);
+        } else if let Some(hash) = line.split(' ').next()
+            && hash.len() == 40
+            && hash.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            commit = hash;
+        }
+    }
+    lines
+}
+
+fn build_blame(path: String, text: &str, git: &[GitLine], changes: &[FileChange]) -> Blame {
+    let owners = db::attribute_lines(text, changes);
+
+    // One entry per turn that still owns a line, numbered in file order.
+    let mut turns: Vec<BlameTurn> = Vec::new();
+    let mut turn_index: HashMap<(String, u32), usize> = HashMap::new();
+    let mut lines = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let turn = owners[i].map(|owner| {
+            let change = &changes[owner];
+            let key = (change.session_id.clone(), change.turn);
+            *turn_index.entry(key).or_insert_with(|| {
+                turns.push(blame_turn(change));
+                turns.len() - 1
+            })
+        });
+        let git_line = git.get(i).cloned().unwrap_or_default();
+        lines.push(BlameLine {
+            line: i + 1,
+            commit: git_line.commit,
+            author: git_line.author,
+            turn,
+            text: line.to_string(),
+        });
+    }
+    Blame { path, lines, turns }
+}
+
+fn blame_turn(change: &FileChange) -> BlameTurn {
+    let session = if change.session_slug.is_empty() {
+        change.session_id.chars().take(8).collect()
+    } else {
+        change.session_slug.clone()
+    };
+    BlameTurn {
+        label: format!("{}#{}", session, change.turn),
+        session_id: change.session_id.clone(),
+        session,
+        turn: change.turn,
+        ts: change.ts,
+        prompt: change.prompt.clone(),
+    }
+}
+
+fn print_blame(blame: &Blame) {
+    let number_width = blame.lines.len().to_string().len();
+    let author_width = blame
+        .lines
+        .iter()
+        .filter_map(|l| l.author.as_deref())
+        .map(|a| a.chars().count().min(16))
+        .max()
+        .unwrap_or(0)
+        .max("(new)".len());
+    let turn_width = blame
+        .turns
+        .iter()
+        .map(|t| t.label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for line in &blame.lines {
+        let commit = line.commit.as_deref().unwrap_or("--------");
+        let author: String = line
+            .author
+            .as_deref()
+            .unwrap_or("(new)")
+            .chars()
+            .take(16)
+            .collect();
+        let turn = line
+            .turn
+            .map(|t| blame.turns[t].label.as_str())
+            .unwrap_or("");
+        let turn = format!("{:<turn_width$}", turn);
+        let turn = if line.turn.is_some() {
+            style(turn).magenta().to_string()
+        } else {
+            turn
+        };
+        println!(
+            "{} {} {:<author_width$} {} {}",
+            style(format!("{:>number_width$}", line.line)).dim(),
+            style(commit).yellow(),
+            author,
+            turn,
+            line.text
+        );
+    }
+
+    if blame.turns.is_empty() {
+        println!();
+        output::dim(&format!(
+            "No lines in {} from recorded locus turns.",
+            blame.path
+        ));
+        return;
+    }
+    println!();
+    output::header("Turns");
+    for turn in &blame.turns {
+        let when = Local
+            .timestamp_opt(turn.ts, 0)
+            .single()
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        println!(
+            "  {} {} {}",
+            style(format!("{:<turn_width$}", turn.label)).magenta(),
+            style(when).dim(),
+            turn.prompt.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! `locus checkpoints` subcommands — list and restore per-turn git checkpoints.
+//!
+//! Checkpoints are written by the runtime when `LOCUS_CHECKPOINTS=1` is set: one commit per
+//! editing turn under `refs/locus/checkpoints/<session>/<turn>`, outside the branch history.
+
+use anyhow::Result;
+use comfy_table::Cell;
+use locus_runtime::Checkpoints;
+
+use crate::cli::CheckpointsAction;
+use crate::commands::history::format_age;
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+pub async fn handle(action: CheckpointsAction) -> Result<()> {
+    let checkpoints = Checkpoints::new(find_repo_root()?);
+
+    match action {
+        CheckpointsAction::List { session } => list(&checkpoints, session.as_deref()).await,
+        CheckpointsAction::Restore { id } => restore(&checkpoints, &id).await,
+    }
+}
+
+async fn list(checkpoints: &Checkpoints, session: Option<&str>) -> Result<()> {
+    let all = checkpoints.list().await?;
+    let shown: Vec<_> = all
+        .iter()
+        .filter(|c| session.is_none_or(|s| c.session_id.starts_with(s)))
+        .collect();
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({ "checkpoints": shown }));
+        return Ok(());
+    }
+
+    output::header("Checkpoints");
+    if shown.is_empty() {
+        output::dim("No checkpoints yet. Enable with LOCUS_CHECKPOINTS=1.");
+        return Ok(());
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut table = output::table();
+    table.set_header(vec!["Id", "When", "Session", "Turn", "Summary"]);
+    for checkpoint in &shown {
+        let age = now.saturating_sub(checkpoint.timestamp.max(0) as u64);
+        table.add_row(vec![
+            Cell::new(&checkpoint.id),
+            Cell::new(format_age(age)),
+            Cell::new(short_session(&checkpoint.session_id)),
+            Cell::new(&checkpoint.turn),
+            Cell::new(&checkpoint.summary),
+        ]);
+    }
+    output::table_print(&table, &[]);
+    output::dim("locus checkpoints restore <id>");
+
+    Ok(())
+}
+
+async fn restore(checkpoints: &Checkpoints, id: &str) -> Result<()> {
+    let outcome = checkpoints.restore(id).await?;
+    let checkpoint = &outcome.checkpoint;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::to_value(&outcome)?);
+        return Ok(());
+    }
+
+    if outcome.changed.is_empty() {
+        output::success(&format!(
+            "Working tree already matches checkpoint {}",
+            checkpoint.id
+        ));
+        return Ok(());
+    }
+    for path in &outcome.changed {
+        output::kv("restored", path);
+    }
+    if let Some(backup) = &outcome.backup {
+        output::dim(&format!(
+            "Previous state saved as checkpoint {} (locus checkpoints restore {})",
+            backup.id, backup.id
+        ));
+    }
+    output::success(&format!(
+        "Restored {} file(s) to checkpoint {} (turn {})",
+        outcome.changed.len(),
+        checkpoint.id,
+        checkpoint.turn
+    ));
+
+    Ok(())
+}
+
+/// First eight characters of a session id; enough to tell sessions apart in a table.
+fn short_session(session_id: &str) -> &str {
+    session_id
+        .char_indices()
+        .nth(8)
+        .map_or(session_id, |(i, _)| &session_id[..i])
+}
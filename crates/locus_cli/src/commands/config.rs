@@ -35,6 +35,8 @@ pub async fn handle(action: ConfigAction) -> Result<()> {
                 configure_graph(url, graph_id).await
             }
         }
+        ConfigAction::Locale { code } => configure_locale(code).await,
+        ConfigAction::Theme { name } => configure_theme(name).await,
     }
 }
 
@@ -224,6 +226,69 @@ async fn configure_graph(url: Option<String>, graph_id: Option<String>) -> Resul
     Ok(())
 }
 
+async fn configure_locale(code: Option<String>) -> Result<()> {
+    use locus_tui::i18n;
+
+    let Some(code) = code else {
+        output::header("TUI locale");
+        println!("  Current: {}", i18n::current_locale());
+        let builtin: Vec<&str> = i18n::BUILTIN_LOCALES.iter().map(|(c, _)| *c).collect();
+        println!("  Built in: {}", builtin.join(", "));
+        if let Some(dir) = i18n::user_locales_dir() {
+            output::dim(&format!("Custom catalogs: {}/<code>.json", dir.display()));
+        }
+        return Ok(());
+    };
+
+    if !i18n::is_available(&code) {
+        return Err(anyhow!(
+            "No catalog for locale '{}'. Add {}/{}.json or pick a built-in locale.",
+            code,
+            i18n::user_locales_dir()
+                .map(|d| d.display().to_string())
+                .unwrap_or_else(|| "~/.locus/locales".to_string()),
+            code
+        ));
+    }
+
+    let locus_dir = get_global_locus_dir()?;
+    save_config_key(&locus_dir, i18n::LOCALE_ENV, &code)?;
+    output::success(&format!("TUI locale set to {}", code));
+    Ok(())
+}
+
+async fn configure_theme(name: Option<String>) -> Result<()> {
+    use locus_tui::theme::{BUILTIN_THEMES, CUSTOM_THEME, THEME_FILE};
+    use locus_tui::theme_picker::THEME_ENV;
+
+    let Some(name) = name else {
+        output::header("TUI theme");
+        let current = env::var(THEME_ENV).unwrap_or_else(|_| "follows the terminal".to_string());
+        println!("  Current: {}", current);
+        println!("  Built in: {}", BUILTIN_THEMES.join(", "));
+        output::dim(&format!(
+            "Project theme: {} (as '{}')",
+            THEME_FILE, CUSTOM_THEME
+        ));
+        return Ok(());
+    };
+
+    if name != CUSTOM_THEME && !BUILTIN_THEMES.contains(&name.as_str()) {
+        return Err(anyhow!(
+            "No theme '{}'. Pick one of {} or '{}' for {}.",
+            name,
+            BUILTIN_THEMES.join(", "),
+            CUSTOM_THEME,
+            THEME_FILE
+        ));
+    }
+
+    let locus_dir = get_global_locus_dir()?;
+    save_config_key(&locus_dir, THEME_ENV, &name)?;
+    output::success(&format!("TUI theme set to {}", name));
+    Ok(())
+}
+
 fn select_provider() -> Result<&'static (&'static str, &'static str, &'static str)> {
     println!("Select a provider to configure:\n");
 
@@ -0,0 +1,74 @@
+//! `locus features` — what this build and this machine support.
+//!
+//! Lists the cargo features compiled in (`syntax`, `semantic`, `web`, `otel`;
+//! a `--no-default-features` build has none) and the external programs the
+//! tools use, found on PATH, so a minimal CI build can be told apart from a
+//! full desktop one.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use comfy_table::{Attribute, Cell, Color};
+use locus_toolbus::{Capability, CapabilityKind};
+
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+pub async fn handle(workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(dir) => PathBuf::from(dir),
+        None => find_repo_root().or_else(|_| std::env::current_dir())?,
+    };
+    let mut report =
+        tokio::task::spawn_blocking(move || locus_toolbus::capabilities(&repo_root)).await?;
+    // OTLP export lives in the observability crate; listed after the ToolBus features.
+    let features = report
+        .iter()
+        .take_while(|c| c.kind == CapabilityKind::Feature)
+        .count();
+    report.insert(features, otel_capability());
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "capabilities": report,
+        }));
+        return Ok(());
+    }
+
+    output::header(&format!("locus {}", env!("CARGO_PKG_VERSION")));
+    let mut table = output::table();
+    table.set_header(
+        ["Capability", "Kind", "Status", "Detail"]
+            .map(|h| Cell::new(h).fg(Color::Cyan).add_attribute(Attribute::Bold)),
+    );
+    for capability in &report {
+        let kind = match capability.kind {
+            CapabilityKind::Feature => "feature",
+            CapabilityKind::Program => "program",
+        };
+        let status = match (capability.compiled, capability.available) {
+            (_, true) => Cell::new("available").fg(Color::Green),
+            (false, _) => Cell::new("not built").fg(Color::DarkGrey),
+            (true, false) => Cell::new("missing").fg(Color::Yellow),
+        };
+        table.add_row(vec![
+            Cell::new(&capability.name),
+            Cell::new(kind),
+            status,
+            Cell::new(&capability.detail),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn otel_capability() -> Capability {
+    let detail = match std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .or_else(|_| std::env::var("TEMPO_ENDPOINT"))
+    {
+        Ok(endpoint) if !endpoint.is_empty() => format!("OTLP span export to {}", endpoint),
+        _ => "OTLP span export (set OTEL_EXPORTER_OTLP_ENDPOINT)".to_string(),
+    };
+    Capability::feature("otel", locusgraph_observability::OTEL_ENABLED, detail)
+}
@@ -2,12 +2,18 @@
 //!
 //! Cache/queue DB path: `LOCUSGRAPH_DB_PATH` env, or `~/.locus/locus_graph_cache.db`, or
 //! `$TMPDIR/locus_graph_cache.db`. Use a project-local path by setting e.g.
-//! `LOCUSGRAPH_DB_PATH=.locus/locus_graph_cache.db`.
+//! `LOCUSGRAPH_DB_PATH=.locus/locus_graph_cache.db`. Events not sent yet are
+//! queued in the local memory DB next to it (`locus_memory.db`), which
+//! `prune` trims by the retention policy.
 
 use std::fs;
 
-use anyhow::Result;
-use locus_graph::default_db_path;
+use anyhow::{Result, bail};
+use locus_graph::local::LOCAL_DB;
+use locus_graph::{
+    LocalStore, LocusGraphClient, LocusGraphConfig, RetrieveOptions, default_db_path,
+};
+use locus_runtime::memory::memory_items;
 
 use crate::cli::GraphAction;
 use crate::output;
@@ -16,14 +22,130 @@ pub async fn handle(action: GraphAction) -> Result<()> {
     match action {
         GraphAction::ClearQueue => clear_queue().await,
         GraphAction::Clean => clean_cache().await,
+        GraphAction::Flush => flush().await,
+        GraphAction::Prune { dry_run } => prune(dry_run),
+        GraphAction::Search { query, limit } => search(&query, limit).await,
     }
 }
 
 /// Clear the event queue and cache (same as clean; kept for backward compatibility).
+/// Queued events stay in the local memory DB but are no longer sent.
 async fn clear_queue() -> Result<()> {
+    let local = default_db_path().with_file_name(LOCAL_DB);
+    if local.exists() {
+        let dropped = LocalStore::open(&local)?.abandon_unsynced()?;
+        if dropped > 0 {
+            output::success(&format!("Dropped {} queued events.", dropped));
+        }
+    }
     clean_cache().await
 }
 
+/// Print the memories recalled for `query`, most relevant first.
+async fn search(query: &str, limit: u64) -> Result<()> {
+    let client = LocusGraphClient::new(LocusGraphConfig::from_env()?).await?;
+    let options = RetrieveOptions::new().limit(limit);
+    let result = client.retrieve_memories(query, Some(options)).await?;
+    let memories = memory_items(&result.memories);
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({
+            "query": query,
+            "items_found": result.items_found,
+            "degraded": result.degraded,
+            "offline": client.is_offline(),
+            "memories": memories,
+            "confidence": result.confidence,
+        }));
+        return Ok(());
+    }
+    output::header(&format!("Memories for \"{}\"", query));
+    if result.degraded {
+        output::warning("LocusGraph could not be searched.");
+    } else if memories.is_empty() {
+        output::dim("Nothing remembered for this query.");
+    }
+    for (rank, memory) in memories.iter().enumerate() {
+        let memory = memory.trim_start_matches(['-', '*', '+']).trim_start();
+        println!("{:>3}. {}", rank + 1, memory.replace('\n', "\n     "));
+    }
+    if client.is_offline() && !memories.is_empty() {
+        output::dim("Offline: searched the local memory store.");
+    }
+    Ok(())
+}
+
+/// Send the queued events now and report what is still pending.
+async fn flush() -> Result<()> {
+    let config = LocusGraphConfig::from_env()?;
+    let offline = config.offline;
+    let client = LocusGraphClient::new(config).await?;
+    let pending = client.queued_events_count()?;
+    if offline {
+        bail!(
+            "LocusGraph is offline (LOCUSGRAPH_AGENT_SECRET unset or LOCUSGRAPH_OFFLINE=1): {} events pending",
+            pending
+        );
+    }
+    let result = client.sync().await;
+    let left = client.queued_events_count()?;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({
+            "pending_before": pending,
+            "sent": pending.saturating_sub(left),
+            "pending": left,
+            "error": result.as_ref().err().map(|e| e.to_string()),
+        }));
+        return Ok(());
+    }
+    let sent = pending.saturating_sub(left);
+    match result {
+        Ok(_) if left == 0 => {
+            output::success(&format!("Sent {} queued events; none pending.", sent))
+        }
+        Ok(_) => output::warning(&format!(
+            "Sent {} queued events; {} still pending.",
+            sent, left
+        )),
+        Err(e) => bail!("Sent {} queued events; {} still pending: {}", sent, left, e),
+    }
+    Ok(())
+}
+
+/// Apply the retention policy to the local memory DB. Copies already on the
+/// server are not affected.
+fn prune(dry_run: bool) -> Result<()> {
+    let config = LocusGraphConfig::from_env()?;
+    let path = config.local_db_path();
+    if !path.exists() {
+        output::dim(&format!("No local memory at {}.", path.display()));
+        return Ok(());
+    }
+    let report = LocalStore::open(&path)?
+        .with_retention(config.retention)
+        .prune(dry_run)?;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({
+            "path": path.display().to_string(),
+            "dry_run": dry_run,
+            "expired": report.expired,
+            "duplicates": report.duplicates,
+        }));
+        return Ok(());
+    }
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    output::success(&format!(
+        "{} {} expired and {} duplicate memories from {}.",
+        verb,
+        report.expired,
+        report.duplicates,
+        path.display()
+    ));
+    Ok(())
+}
+
 /// Remove the LocusGraph cache/queue DB so old failing events stop retrying and cache is fresh.
 async fn clean_cache() -> Result<()> {
     let path = default_db_path();
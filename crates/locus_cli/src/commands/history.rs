@@ -0,0 +1,127 @@
+//! `locus history` subcommands — browse and restore file versions from edit history.
+//!
+//! Versions come from the `file_history` tool (backed by `.locus/locus.db` edit history):
+//! version 0 is the content before the first retained edit, each later version is one edit.
+
+use anyhow::{Result, anyhow};
+use comfy_table::Cell;
+use locus_toolbus::ToolBus;
+use serde_json::{Value as JsonValue, json};
+
+use crate::cli::HistoryAction;
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+pub async fn handle(action: HistoryAction) -> Result<()> {
+    let bus = ToolBus::new(find_repo_root()?);
+
+    match action {
+        HistoryAction::List { path } => list(&bus, &path).await,
+        HistoryAction::Show { path, version } => show(&bus, &path, version).await,
+        HistoryAction::Restore { path, version } => restore(&bus, &path, version).await,
+    }
+}
+
+async fn list(bus: &ToolBus, path: &str) -> Result<()> {
+    let (result, _) = bus.call("file_history", json!({ "path": path })).await?;
+    let versions = result["versions"]
+        .as_array()
+        .ok_or_else(|| anyhow!("Unexpected file_history result"))?;
+
+    output::header(&format!("History: {}", path));
+
+    let mut table = output::table();
+    table.set_header(vec!["Version", "When", "Lines", "Change"]);
+    let items: Vec<(String, String)> = versions
+        .iter()
+        .map(|v| {
+            let version = v["version"].as_u64().unwrap_or(0).to_string();
+            let change = match v["version"].as_u64() {
+                Some(0) => "original".to_string(),
+                _ => format!(
+                    "+{} -{}",
+                    v["added"].as_u64().unwrap_or(0),
+                    v["removed"].as_u64().unwrap_or(0)
+                ),
+            };
+            table.add_row(vec![
+                Cell::new(&version),
+                Cell::new(format_ts(&v["ts"])),
+                Cell::new(v["lines"].as_u64().unwrap_or(0)),
+                Cell::new(&change),
+            ]);
+            (version, change)
+        })
+        .collect();
+    let items: Vec<(&str, &str)> = items
+        .iter()
+        .map(|(v, c)| (v.as_str(), c.as_str()))
+        .collect();
+    output::table_print(&table, &items);
+    output::dim("locus history show <path> <version> · locus history restore <path> <version>");
+
+    Ok(())
+}
+
+async fn show(bus: &ToolBus, path: &str, version: usize) -> Result<()> {
+    let (result, _) = bus
+        .call(
+            "file_history",
+            json!({ "path": path, "action": "diff", "version": version }),
+        )
+        .await?;
+
+    output::header(&format!(
+        "{} · version {} ({})",
+        path,
+        version,
+        format_ts(&result["ts"])
+    ));
+    output::diff(
+        result["old_content"].as_str().unwrap_or(""),
+        result["new_content"].as_str().unwrap_or(""),
+    );
+
+    Ok(())
+}
+
+async fn restore(bus: &ToolBus, path: &str, version: usize) -> Result<()> {
+    let (result, _) = bus
+        .call(
+            "file_history",
+            json!({ "path": path, "action": "restore", "version": version }),
+        )
+        .await?;
+
+    output::diff(
+        result["old_content"].as_str().unwrap_or(""),
+        result["new_content"].as_str().unwrap_or(""),
+    );
+    output::success(&format!("Restored {} to version {}", path, version));
+
+    Ok(())
+}
+
+/// Edit timestamp (seconds since epoch) as a relative age; version 0 has no timestamp.
+fn format_ts(ts: &JsonValue) -> String {
+    match ts.as_u64() {
+        Some(0) | None => "—".to_string(),
+        Some(secs) => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            format_age(now.saturating_sub(secs))
+        }
+    }
+}
+
+/// Short relative age: "12s ago", "5m ago", "3h ago", "2d ago".
+pub(crate) fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s ago", s),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
@@ -0,0 +1,246 @@
+//! `locus journal` — markdown standup report for a time window.
+//!
+//! Built from three local sources:
+//! - Session turns recorded by the runtime in `.locus/locus.db` (`session_log`)
+//! - Git commits whose message has a `Co-authored-by:` trailer mentioning locus
+//! - Task list items marked done in the window
+//!
+//! `--since` accepts `today`, `yesterday`, `week`, `<N>h`, `<N>d`, `<N>w` or a
+//! `YYYY-MM-DD` date; day-based values start at local midnight.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use locus_core::db::{self, CompletedTask, SessionLogEntry};
+use serde::Serialize;
+
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+/// Substring (case-insensitive) a `Co-authored-by:` trailer must contain.
+const CO_AUTHOR_MARKER: &str = "locus";
+
+#[derive(Debug, Serialize)]
+struct Journal {
+    since: i64,
+    sessions: Vec<SessionGroup>,
+    commits: Vec<JournalCommit>,
+    tasks: Vec<CompletedTask>,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionGroup {
+    session_id: String,
+    name: String,
+    turns: Vec<SessionLogEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct JournalCommit {
+    hash: String,
+    author: String,
+    ts: i64,
+    subject: String,
+}
+
+pub async fn handle(since: String, workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(dir) => PathBuf::from(dir),
+        None => find_repo_root()?,
+    };
+    let start = parse_since(&since, Local::now())?;
+    let since_ts = start.timestamp();
+
+    let journal = tokio::task::spawn_blocking(move || -> Result<Journal> {
+        Ok(Journal {
+            since: since_ts,
+            sessions: group_sessions(db::turns_since(&repo_root, since_ts)?),
+            commits: locus_commits(&repo_root, since_ts),
+            tasks: db::done_since(&repo_root, since_ts)?,
+        })
+    })
+    .await??;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::to_value(&journal)?);
+    } else {
+        print!("{}", render_markdown(&journal, start));
+    }
+    Ok(())
+}
+
+/// Start of the window for a `--since` value, relative to `now`.
+pub(crate) fn parse_since(value: &str, now: DateTime<Local>) -> Result<DateTime<Local>> {
+    let value = value.trim().to_lowercase();
+    let today = now.date_naive();
+    let days_ago = |n: i64| local_midnight(today - Duration::days(n));
+    match value.as_str() {
+        "today" => return days_ago(0),
+        "yesterday" => return days_ago(1),
+        "week" => return days_ago(7),
+        _ => {}
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return local_midnight(date);
+    }
+    let (count, unit) = value.split_at(value.len().saturating_sub(1));
+    let count: i64 = count
+        .parse()
+        .map_err(|_| anyhow!("Invalid --since value: {}", value))?;
+    match unit {
+        "h" => Ok(now - Duration::hours(count)),
+        "d" => days_ago(count),
+        "w" => days_ago(count * 7),
+        _ => Err(anyhow!("Invalid --since value: {}", value)),
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> Result<DateTime<Local>> {
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .ok_or_else(|| anyhow!("No local midnight on {}", date))
+}
+
+/// Group turns by session, in order of each session's first turn.
+fn group_sessions(turns: Vec<SessionLogEntry>) -> Vec<SessionGroup> {
+    let mut groups: Vec<SessionGroup> = Vec::new();
+    for turn in turns {
+        match groups.iter_mut().find(|g| g.session_id == turn.session_id) {
+            Some(group) => group.turns.push(turn),
+            None => groups.push(SessionGroup {
+                session_id: turn.session_id.clone(),
+                name: if turn.session_slug.is_empty() {
+                    turn.title.clone()
+                } else {
+                    turn.session_slug.clone()
+                },
+                turns: vec![turn],
+            }),
+        }
+    }
+    groups
+}
+
+/// Commits since `since` co-authored by locus. Empty when git is unavailable.
+fn locus_commits(repo_root: &Path, since: i64) -> Vec<JournalCommit> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            &format!("--since=@{}", since),
+            "--format=%h%x1f%an%x1f%at%x1f%s%x1f%b%x1e",
+        ])
+        .current_dir(repo_root)
+        .output();
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim().splitn(5, '\x1f').collect();
+            let [hash, author, ts, subject, body] = fields[..] else {
+                return None;
+            };
+            let co_authored = body.lines().any(|line| {
+                let line = line.trim().to_lowercase();
+                line.starts_with("co-authored-by:") && line.contains(CO_AUTHOR_MARKER)
+            });
+            co_authored.then(|| JournalCommit {
+                hash: hash.to_string(),
+                author: author.to_string(),
+                ts: ts.parse().unwrap_or(0),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn render_markdown(journal: &Journal, start: DateTime<Local>) -> String {
+    let mut out = format!(
+        "# Work journal since {}\n",
+        start.format("%a %Y-%m-%d %H:%M")
+    );
+
+    out.push_str(&format!("\n## Sessions ({})\n", journal.sessions.len()));
+    if journal.sessions.is_empty() {
+        out.push_str("\n_None._\n");
+    }
+    for group in &journal.sessions {
+        let first = group.turns.first().map(|t| t.ts).unwrap_or_default();
+        let last = group.turns.last().map(|t| t.ts).unwrap_or_default();
+        out.push_str(&format!(
+            "\n### {} ({}–{}, {} turn{})\n\n",
+            group.name,
+            local_time(first, "%m-%d %H:%M"),
+            local_time(last, "%H:%M"),
+            group.turns.len(),
+            if group.turns.len() == 1 { "" } else { "s" }
+        ));
+        for turn in &group.turns {
+            let outcome = one_line(&turn.outcome);
+            if outcome.is_empty() {
+                out.push_str(&format!("- **{}**\n", one_line(&turn.title)));
+            } else {
+                out.push_str(&format!("- **{}**: {}\n", one_line(&turn.title), outcome));
+            }
+        }
+        let files: BTreeSet<&str> = group
+            .turns
+            .iter()
+            .flat_map(|t| t.files_modified.iter().map(String::as_str))
+            .collect();
+        if !files.is_empty() {
+            let files: Vec<String> = files.iter().map(|f| format!("`{}`", f)).collect();
+            out.push_str(&format!("\nFiles: {}\n", files.join(", ")));
+        }
+    }
+
+    out.push_str(&format!("\n## Commits ({})\n\n", journal.commits.len()));
+    if journal.commits.is_empty() {
+        out.push_str("_None._\n");
+    }
+    for commit in &journal.commits {
+        out.push_str(&format!(
+            "- `{}` {} ({}, {})\n",
+            commit.hash,
+            commit.subject,
+            commit.author,
+            local_time(commit.ts, "%m-%d %H:%M")
+        ));
+    }
+
+    out.push_str(&format!(
+        "\n## Completed tasks ({})\n\n",
+        journal.tasks.len()
+    ));
+    if journal.tasks.is_empty() {
+        out.push_str("_None._\n");
+    }
+    for done in &journal.tasks {
+        out.push_str(&format!(
+            "- [x] {} (plan `{}`)\n",
+            one_line(&done.task.title),
+            done.plan_id
+        ));
+    }
+    out
+}
+
+fn local_time(ts: i64, format: &str) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|dt| dt.format(format).to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -1,23 +1,30 @@
 //! MCP server management commands.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::{Result, anyhow};
-use locus_toolbus::mcp::{McpAuthConfig, McpManager, McpServerConfig, TransportType};
+use locus_runtime::{Runtime, RuntimeConfig, TaskTool};
+use locus_toolbus::ToolBus;
+use locus_toolbus::mcp::{
+    AuthPrompt, McpAuthConfig, McpManager, McpServer, McpServerConfig, McpServersConfig,
+    OAuthConfig, OAuthFlow, TransportType,
+};
 
 use crate::cli::McpAction;
 use crate::output;
 
 /// Returns the default MCP configuration path.
 fn config_path() -> PathBuf {
-    dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("locus")
-        .join("mcp_servers.toml")
+    McpServersConfig::default_path()
 }
 
 /// Handles MCP CLI actions.
 pub async fn handle(action: McpAction) -> Result<()> {
+    // Serving speaks JSON-RPC on stdout and needs no server configuration.
+    if let McpAction::Serve { tools, workdir } = action {
+        return serve(tools, workdir).await;
+    }
     let manager = McpManager::load(config_path()).await?;
 
     match action {
@@ -45,7 +52,10 @@ pub async fn handle(action: McpAction) -> Result<()> {
         McpAction::Stop { server_id } => stop_server(manager, server_id).await,
         McpAction::Test { server_id } => test_server(manager, server_id).await,
         McpAction::Info { server_id } => show_server_info(manager, server_id).await,
+        McpAction::Login { server_id, device } => login(manager, server_id, device).await,
+        McpAction::Logout { server_id } => logout(manager, server_id).await,
         McpAction::Call { tool, args } => call_mcp_tool(manager, tool, args).await,
+        McpAction::Serve { .. } => unreachable!("handled above"),
     }
 }
 
@@ -93,6 +103,16 @@ async fn add_server(
 
     // Create auth config if provided
     let auth = match (auth_type, auth_token) {
+        (Some(auth_type), None) if auth_type == "oauth" => {
+            if url.is_none() {
+                return Err(anyhow!(
+                    "OAuth is only supported for remote servers (--url)"
+                ));
+            }
+            // Client ID and scopes go in [servers.auth.oauth]; by default the
+            // client registers itself.
+            Some(McpAuthConfig::oauth(OAuthConfig::default()))
+        }
         (Some(auth_type), Some(token)) => Some(McpAuthConfig::new(auth_type, token)),
         (None, Some(_)) => {
             return Err(anyhow!(
@@ -145,9 +165,13 @@ async fn add_server(
         config
     };
 
+    let needs_login = config.auth.as_ref().is_some_and(|a| a.is_oauth());
     manager.add_server(config).await?;
 
     output::success(&format!("Added MCP server: {} ({})", name, id));
+    if needs_login {
+        output::dim(&format!("Log in with: locus mcp login {}", id));
+    }
     output::dim(&format!("Start it with: locus mcp start {}", id));
 
     Ok(())
@@ -302,6 +326,17 @@ async fn show_server_info(manager: McpManager, server_id: String) -> Result<()>
         output::kv("Authentication:", "");
         output::kv("  Type:", &auth.auth_type);
         output::kv("  Header:", auth.header_name());
+        if auth.is_oauth() {
+            let tokens = manager.oauth_session(&server_id).await?.tokens().await;
+            output::kv(
+                "  Logged in:",
+                if tokens.is_some() {
+                    "yes"
+                } else {
+                    "no (run `locus mcp login`)"
+                },
+            );
+        }
     }
 
     // Show tools if running
@@ -323,6 +358,69 @@ async fn show_server_info(manager: McpManager, server_id: String) -> Result<()>
     Ok(())
 }
 
+async fn login(manager: McpManager, server_id: String, device: bool) -> Result<()> {
+    let session = manager.oauth_session(&server_id).await?;
+    let flow = if device {
+        OAuthFlow::Device
+    } else {
+        session.flow()
+    };
+    let prompt = |prompt: AuthPrompt| match prompt {
+        AuthPrompt::OpenBrowser { url } => {
+            output::dim("Opening your browser to log in. If it does not open, visit:");
+            println!("  {}", url);
+            open_browser(&url);
+        }
+        AuthPrompt::EnterCode {
+            verification_uri,
+            user_code,
+            verification_uri_complete,
+        } => {
+            output::kv("Visit:", &verification_uri);
+            output::kv("Enter code:", &user_code);
+            if let Some(complete) = verification_uri_complete {
+                output::dim(&format!("Or open: {}", complete));
+            }
+        }
+    };
+
+    output::dim("Waiting for authorization...");
+    let tokens = session.login(flow, &prompt).await?;
+    output::success(&format!("Logged in to MCP server: {}", server_id));
+    if let Some(scope) = tokens.scope {
+        output::kv("Scope:", &scope);
+    }
+    Ok(())
+}
+
+async fn logout(manager: McpManager, server_id: String) -> Result<()> {
+    let session = manager.oauth_session(&server_id).await?;
+    if session.logout().await? {
+        output::success(&format!("Logged out of MCP server: {}", server_id));
+    } else {
+        output::warning(&format!("Not logged in to MCP server: {}", server_id));
+    }
+    Ok(())
+}
+
+/// Best effort; the URL is printed either way.
+fn open_browser(url: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let _ = command
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+}
+
 async fn call_mcp_tool(manager: McpManager, tool: String, args: String) -> Result<()> {
     // Parse the tool name (format: server_id.tool_name or mcp.server_id.tool_name)
     let parts: Vec<&str> = tool.split('.').collect();
@@ -377,3 +475,53 @@ async fn call_mcp_tool(manager: McpManager, tool: String, args: String) -> Resul
 
     Ok(())
 }
+
+/// Serve the ToolBus over stdio until the client disconnects.
+///
+/// `task` needs an agent runtime (LLM and LocusGraph); when one cannot be built
+/// the other tools are still served. Everything but protocol messages goes to
+/// stderr.
+async fn serve(tools: Vec<String>, workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(w) => PathBuf::from(w),
+        None => std::env::current_dir()?,
+    };
+    let wants_task = tools.is_empty() || tools.iter().any(|t| t == "task");
+
+    let mut runtime = None;
+    if wants_task {
+        // No session log, and no MCP servers of our own: a client should not
+        // reach other servers through us.
+        let mut config = RuntimeConfig::from_env(repo_root.clone()).with_session_log(false);
+        config.mcp_config = None;
+        let (event_tx, _) = tokio::sync::mpsc::channel(1);
+        match Runtime::new(config, event_tx).await {
+            Ok(rt) => {
+                rt.toolbus.add_tool(Arc::new(TaskTool::new(&rt)));
+                runtime = Some(rt);
+            }
+            Err(e) => eprintln!("locus: serving without `task`: {}", e),
+        }
+    }
+    let toolbus = match &runtime {
+        Some(rt) => Arc::clone(&rt.toolbus),
+        None => {
+            let toolbus = ToolBus::new(repo_root);
+            toolbus.load_extensions().await;
+            Arc::new(toolbus)
+        }
+    };
+
+    let mut server = McpServer::new(toolbus);
+    if !tools.is_empty() {
+        server = server.with_tools(tools);
+    }
+    let served: Vec<String> = server.tools().into_iter().map(|t| t.name).collect();
+    eprintln!("locus: serving {} over stdio MCP", served.join(", "));
+    server.serve_stdio().await?;
+
+    if let Some(mut rt) = runtime {
+        let _ = rt.shutdown().await;
+    }
+    Ok(())
+}
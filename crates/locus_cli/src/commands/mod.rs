@@ -1,12 +1,23 @@
 //! Command dispatch.
 
+pub mod acp;
+pub mod blame;
+pub mod checkpoints;
 pub mod config;
+pub mod features;
 pub mod graph;
+pub mod history;
+pub mod journal;
 pub mod mcp;
 pub mod providers;
+pub mod resume;
 pub mod run;
+pub mod sessions;
+pub mod tasks;
 pub mod toolbus;
 pub mod tui;
+pub mod tutorial;
+pub mod work;
 
 use crate::cli::{Cli, Command};
 use anyhow::Result;
@@ -17,18 +28,29 @@ pub async fn handle(cli: Cli) -> Result<()> {
         provider: None,
         model: None,
         onboarding: false,
+        transcript: false,
     }) {
         Command::Tui {
             workdir,
             provider,
             model,
             onboarding,
-        } => tui::handle(workdir, provider, model, onboarding).await,
+            transcript,
+        } => tui::handle(workdir, provider, model, onboarding, transcript).await,
         Command::Toolbus { action } => toolbus::handle(action).await,
+        Command::History { action } => history::handle(action).await,
+        Command::Checkpoints { action } => checkpoints::handle(action).await,
+        Command::Sessions { action } => sessions::handle(action).await,
         Command::Providers { action } => providers::handle(action).await,
         Command::Config { action } => config::handle(action).await,
         Command::Graph { action } => graph::handle(action).await,
         Command::Mcp { action } => mcp::handle(action).await,
+        Command::Acp { workdir } => acp::handle(workdir).await,
+        Command::Blame { file, workdir } => blame::handle(file, workdir).await,
+        Command::Journal { since, workdir } => journal::handle(since, workdir).await,
+        Command::Tasks { plan, workdir } => tasks::handle(plan, workdir).await,
+        Command::Features { workdir } => features::handle(workdir).await,
+        Command::Tutorial { dir } => tutorial::handle(dir).await,
         Command::Run {
             model,
             provider,
@@ -36,6 +58,27 @@ pub async fn handle(cli: Cli) -> Result<()> {
             max_turns,
             max_tokens,
             prompt,
-        } => run::handle(model, provider, workdir, max_turns, max_tokens, prompt).await,
+            command,
+            args,
+        } => {
+            run::handle(
+                model, provider, workdir, max_turns, max_tokens, prompt, command, args,
+            )
+            .await
+        }
+        Command::Resume {
+            session,
+            last: _,
+            model,
+            provider,
+            workdir,
+        } => resume::handle(session, model, provider, workdir).await,
+        Command::Work {
+            issue,
+            model,
+            provider,
+            workdir,
+            max_turns,
+        } => work::handle(issue, model, provider, workdir, max_turns).await,
     }
 }
@@ -0,0 +1,112 @@
+//! `locus resume` — finish a turn that was cut off when locus crashed or was killed.
+//!
+//! While a run is going the runtime keeps a recovery checkpoint of the turn in
+//! `.locus/locus.db` (see `locus_core::db::RunCheckpoint`) and drops it when the
+//! run ends, so a checkpoint left behind marks an interrupted turn. Resuming
+//! replays it with `Runtime::recover`: read-only calls that had not returned run
+//! again, others are reported to the model as interrupted.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use locus_core::SessionEvent;
+use locus_core::db::{self, RunCheckpoint};
+use locus_runtime::{LlmProvider, Runtime, RuntimeConfig};
+use locusgraph_observability::{init_from_env, shutdown};
+use tokio::sync::mpsc;
+
+use crate::commands::run::spawn_event_printer;
+use crate::output;
+
+pub async fn handle(
+    session: Option<String>,
+    model: Option<String>,
+    provider: Option<String>,
+    workdir: Option<String>,
+) -> Result<()> {
+    let repo_root = match workdir {
+        Some(w) => PathBuf::from(w),
+        None => std::env::current_dir()?,
+    };
+
+    let checkpoint = {
+        let repo_root = repo_root.clone();
+        tokio::task::spawn_blocking(move || find_checkpoint(&repo_root, session.as_deref()))
+            .await??
+    };
+
+    // Keep the session's model unless told otherwise.
+    let session_config = &checkpoint.session.config;
+    let llm_provider = provider
+        .as_deref()
+        .unwrap_or(&session_config.provider)
+        .parse::<LlmProvider>()
+        .unwrap_or_default();
+    let config = RuntimeConfig::from_env(repo_root.clone())
+        .with_provider(llm_provider)
+        .with_model(model.unwrap_or_else(|| session_config.model.clone()));
+
+    if let Err(e) = init_from_env() {
+        output::warning(&format!("Observability init failed (continuing): {}", e));
+    }
+
+    let label = if checkpoint.slug.is_empty() {
+        checkpoint.session.id.as_str().to_string()
+    } else {
+        checkpoint.slug.clone()
+    };
+    output::header("Resuming interrupted turn");
+    println!("  Session: {} ({})", label, checkpoint.session.id);
+    println!("  Repository: {}", repo_root.display());
+    println!("  Model: {}", config.model);
+    if !checkpoint.finished_tools.is_empty() || !checkpoint.pending_tools.is_empty() {
+        println!(
+            "  Tool calls: {} finished, {} interrupted",
+            checkpoint.finished_tools.len(),
+            checkpoint.pending_tools.len()
+        );
+    }
+    println!();
+
+    let (event_tx, event_rx) = mpsc::channel::<SessionEvent>(256);
+    let event_handle = spawn_event_printer(event_rx);
+
+    let mut runtime = match Runtime::new(config, event_tx).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            event_handle.abort();
+            shutdown();
+            return Err(anyhow!("Failed to start runtime: {}", e));
+        }
+    };
+    let result = runtime.recover(checkpoint, None).await;
+    let _ = runtime.shutdown().await;
+    event_handle.abort();
+
+    println!();
+    match result {
+        Ok(status) => output::success(&format!("Session completed: {:?}", status)),
+        Err(e) => output::error(&format!("Runtime error: {}", e)),
+    }
+    output::session_summary(&runtime.session.build_summary());
+
+    shutdown();
+    Ok(())
+}
+
+/// The checkpoint of the session named by `query`, or the latest one.
+fn find_checkpoint(repo_root: &std::path::Path, query: Option<&str>) -> Result<RunCheckpoint> {
+    let Some(query) = query else {
+        return db::load_run_checkpoint(repo_root, None)?
+            .ok_or_else(|| anyhow!("No interrupted turn to resume"));
+    };
+    let id = db::find_session(repo_root, query)?
+        .map(|record| record.id)
+        .unwrap_or_else(|| query.to_string());
+    db::load_run_checkpoint(repo_root, Some(&id))?.ok_or_else(|| {
+        anyhow!(
+            "Session {} has no interrupted turn; continue it from the TUI (Ctrl+S)",
+            query
+        )
+    })
+}
@@ -1,17 +1,19 @@
 //! `locus run` command - start the runtime agent.
 
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use tokio::sync::mpsc;
 
-use locus_core::SessionEvent;
+use locus_core::custom_command::COMMANDS_DIR;
+use locus_core::{SessionEvent, load_custom_commands};
 use locus_runtime::{Runtime, RuntimeConfig};
 use locusgraph_observability::{init_from_env, shutdown};
 
 use crate::output;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle(
     model: Option<String>,
     provider: Option<String>,
@@ -19,6 +21,8 @@ pub async fn handle(
     max_turns: Option<u32>,
     max_tokens: Option<u32>,
     prompt: Option<String>,
+    command: Option<String>,
+    args: Vec<String>,
 ) -> Result<()> {
     // Determine working directory
     let repo_root = match workdir {
@@ -26,6 +30,12 @@ pub async fn handle(
         None => std::env::current_dir()?,
     };
 
+    // `--command name args…` runs `.locus/commands/name.md`; the runtime expands it.
+    let prompt = match command {
+        Some(name) => Some(command_prompt(&repo_root, &name, &args)?),
+        None => prompt,
+    };
+
     // Parse provider
     let llm_provider = provider
         .as_deref()
@@ -78,67 +88,9 @@ pub async fn handle(
     }
 
     // Create event channel
-    let (event_tx, mut event_rx) = mpsc::channel::<SessionEvent>(256);
+    let (event_tx, event_rx) = mpsc::channel::<SessionEvent>(256);
 
-    // Spawn event handler
-    let event_handle = tokio::spawn(async move {
-        use console::style;
-        while let Some(event) = event_rx.recv().await {
-            match event {
-                SessionEvent::TurnStart { role } => {
-                    let role_str = match role {
-                        locus_core::Role::User => style("User").cyan(),
-                        locus_core::Role::Assistant => style("Assistant").green(),
-                        locus_core::Role::Tool => style("Tool").yellow(),
-                        locus_core::Role::System => style("System").magenta(),
-                    };
-                    println!("\n[{}]", role_str);
-                }
-                SessionEvent::TextDelta { text } => {
-                    print!("{}", text);
-                    use std::io::Write;
-                    std::io::stdout().flush().ok();
-                }
-                SessionEvent::ThinkingDelta { thinking } => {
-                    print!("{}", style(thinking).dim());
-                    use std::io::Write;
-                    std::io::stdout().flush().ok();
-                }
-                SessionEvent::ToolStart { tool_use } => {
-                    println!(
-                        "\n  {} {}",
-                        style("Tool:").yellow(),
-                        style(&tool_use.name).bold()
-                    );
-                }
-                SessionEvent::ToolDone { result, .. } => {
-                    let preview = if result.is_error {
-                        format!("Error: {}", result.output)
-                    } else {
-                        let content_str = result.output.to_string();
-                        if content_str.len() > 200 {
-                            format!("{}...", &content_str[..200])
-                        } else {
-                            content_str
-                        }
-                    };
-                    println!("  {} {}", style("Result:").dim(), preview);
-                }
-                SessionEvent::Error { error } => {
-                    eprintln!("\n{} {}", style("Error:").red(), error);
-                }
-                SessionEvent::Status { message } => {
-                    output::dim(&format!("  {}", message));
-                }
-                SessionEvent::MemoryRecall { items_found, .. } => {
-                    if items_found > 0 {
-                        output::dim(&format!("  Recalled {} memories", items_found));
-                    }
-                }
-                _ => {}
-            }
-        }
-    });
+    let event_handle = spawn_event_printer(event_rx);
 
     // Create and run runtime
     output::dim("Starting runtime...\n");
@@ -192,63 +144,8 @@ pub async fn handle(
                     break;
                 }
 
-                let (next_tx, mut next_rx) = mpsc::channel::<SessionEvent>(256);
-                let event_handle_next = tokio::spawn(async move {
-                    use console::style;
-                    while let Some(event) = next_rx.recv().await {
-                        match event {
-                            SessionEvent::TurnStart { role } => {
-                                let role_str = match role {
-                                    locus_core::Role::User => style("User").cyan(),
-                                    locus_core::Role::Assistant => style("Assistant").green(),
-                                    locus_core::Role::Tool => style("Tool").yellow(),
-                                    locus_core::Role::System => style("System").magenta(),
-                                };
-                                println!("\n[{}]", role_str);
-                            }
-                            SessionEvent::TextDelta { text } => {
-                                print!("{}", text);
-                                std::io::stdout().flush().ok();
-                            }
-                            SessionEvent::ThinkingDelta { thinking } => {
-                                print!("{}", style(thinking).dim());
-                                std::io::stdout().flush().ok();
-                            }
-                            SessionEvent::ToolStart { tool_use } => {
-                                println!(
-                                    "\n  {} {}",
-                                    style("Tool:").yellow(),
-                                    style(&tool_use.name).bold()
-                                );
-                            }
-                            SessionEvent::ToolDone { result, .. } => {
-                                let preview = if result.is_error {
-                                    format!("Error: {}", result.output)
-                                } else {
-                                    let s = result.output.to_string();
-                                    if s.len() > 200 {
-                                        format!("{}...", &s[..200])
-                                    } else {
-                                        s
-                                    }
-                                };
-                                println!("  {} {}", style("Result:").dim(), preview);
-                            }
-                            SessionEvent::Error { error } => {
-                                eprintln!("\n{} {}", style("Error:").red(), error)
-                            }
-                            SessionEvent::Status { message } => {
-                                output::dim(&format!("  {}", message))
-                            }
-                            SessionEvent::MemoryRecall { items_found, .. } => {
-                                if items_found > 0 {
-                                    output::dim(&format!("  Recalled {} memories", items_found));
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                });
+                let (next_tx, next_rx) = mpsc::channel::<SessionEvent>(256);
+                let event_handle_next = spawn_event_printer(next_rx);
 
                 let prev_session = &runtime.session;
                 let config = runtime.config.clone();
@@ -306,3 +203,160 @@ pub async fn handle(
     shutdown();
     Ok(())
 }
+
+/// The `/name args…` prompt for a custom command, once it exists.
+fn command_prompt(repo_root: &Path, name: &str, args: &[String]) -> Result<String> {
+    let name = name.trim_start_matches('/');
+    let commands = load_custom_commands(repo_root);
+    if !commands.iter().any(|c| c.name == name) {
+        let known: Vec<String> = commands.iter().map(|c| format!("/{}", c.name)).collect();
+        bail!(
+            "No custom command {} in {} (available: {})",
+            name,
+            COMMANDS_DIR,
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        );
+    }
+    Ok(format!("/{} {}", name, args.join(" "))
+        .trim_end()
+        .to_string())
+}
+
+/// Print runtime events to the terminal until the channel closes.
+pub(crate) fn spawn_event_printer(
+    mut event_rx: mpsc::Receiver<SessionEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        use console::style;
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                SessionEvent::TurnStart { role } => {
+                    let role_str = match role {
+                        locus_core::Role::User => style("User").cyan(),
+                        locus_core::Role::Assistant => style("Assistant").green(),
+                        locus_core::Role::Tool => style("Tool").yellow(),
+                        locus_core::Role::System => style("System").magenta(),
+                    };
+                    println!("\n[{}]", role_str);
+                }
+                SessionEvent::TextDelta { text } => {
+                    print!("{}", text);
+                    std::io::stdout().flush().ok();
+                }
+                SessionEvent::ThinkingDelta { thinking } => {
+                    print!("{}", style(thinking).dim());
+                    std::io::stdout().flush().ok();
+                }
+                SessionEvent::ToolStart { tool_use } => {
+                    println!(
+                        "\n  {} {}",
+                        style("Tool:").yellow(),
+                        style(&tool_use.name).bold()
+                    );
+                }
+                SessionEvent::ToolDone { result, .. } => {
+                    let preview = if result.is_error {
+                        format!("Error: {}", result.output)
+                    } else {
+                        let content_str = result.output.to_string();
+                        if content_str.len() > 200 {
+                            format!("{}...", &content_str[..200])
+                        } else {
+                            content_str
+                        }
+                    };
+                    println!("  {} {}", style("Result:").dim(), preview);
+                }
+                SessionEvent::Error { error } => {
+                    eprintln!("\n{} {}", style("Error:").red(), error);
+                }
+                SessionEvent::GuardrailViolation {
+                    violations,
+                    reverted,
+                } => {
+                    let outcome = if reverted {
+                        "edits reverted"
+                    } else {
+                        "run stopped"
+                    };
+                    eprintln!(
+                        "\n{} {}",
+                        style(format!("Guardrail violated ({}):", outcome)).red(),
+                        violations.join("; ")
+                    );
+                }
+                SessionEvent::SecretsRedacted { secrets } => {
+                    let listed: Vec<String> = secrets
+                        .iter()
+                        .map(|s| format!("{} as {}", s.kind, s.placeholder))
+                        .collect();
+                    output::warning(&format!(
+                        "Secrets hidden from the model: {}",
+                        listed.join(", ")
+                    ));
+                }
+                SessionEvent::SimilarWork { work } => {
+                    let matched = &work.matched;
+                    let session = if matched.session_slug.is_empty() {
+                        &matched.session_id
+                    } else {
+                        &matched.session_slug
+                    };
+                    output::warning(&format!(
+                        "Similar to session {} (\"{}\"), which changed: {}",
+                        session,
+                        matched.title,
+                        work.files_modified.join(", ")
+                    ));
+                }
+                SessionEvent::Status { message } => {
+                    output::dim(&format!("  {}", message));
+                }
+                SessionEvent::ToolProgress { message, .. } => {
+                    output::dim(&format!("    {}", message));
+                }
+                SessionEvent::ToolTimedOut {
+                    tool_name,
+                    timeout_secs,
+                    ..
+                } => {
+                    output::warning(&format!(
+                        "{} timed out after {}s and was cancelled",
+                        tool_name, timeout_secs
+                    ));
+                }
+                SessionEvent::Throttled {
+                    wait_ms, reason, ..
+                } => {
+                    output::dim(&format!("  Throttled for {}s: {}", wait_ms / 1000, reason));
+                }
+                SessionEvent::MemoryRecall { items_found, .. } => {
+                    if items_found > 0 {
+                        output::dim(&format!("  Recalled {} memories", items_found));
+                    }
+                }
+                SessionEvent::MemoryBudget {
+                    budget_tokens,
+                    used_tokens,
+                    items,
+                } => {
+                    let left_out = items.iter().filter(|i| !i.included).count();
+                    if left_out > 0 {
+                        output::dim(&format!(
+                            "  Memories: {}/{} tokens, {} of {} left out",
+                            used_tokens,
+                            budget_tokens,
+                            left_out,
+                            items.len()
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    })
+}
@@ -0,0 +1,244 @@
+//! `locus sessions` — browse, export and delete saved agent sessions.
+//!
+//! The runtime saves every session to `.locus/locus.db` after each turn (see
+//! `locus_core::db::save_session`); the TUI lists the same sessions with Ctrl+S.
+//! Sessions are named by id, a unique id prefix or slug. Cost is shown as tokens.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::{Local, TimeZone};
+use comfy_table::Cell;
+use locus_core::db::{self, SessionRecord};
+use locus_core::{ContentBlock, Role, Session};
+
+use crate::cli::{ExportFormat, SessionsAction};
+use crate::commands::history::format_age;
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+/// Longest tool argument summary in a markdown transcript.
+const MAX_ARGS_CHARS: usize = 160;
+
+pub async fn handle(action: SessionsAction) -> Result<()> {
+    let repo_root = find_repo_root()?;
+
+    match action {
+        SessionsAction::List => list(repo_root).await,
+        SessionsAction::Show { session } => show(repo_root, &session).await,
+        SessionsAction::Export {
+            session,
+            format,
+            file,
+        } => export(repo_root, &session, format, file).await,
+        SessionsAction::Delete { session } => delete(repo_root, &session).await,
+    }
+}
+
+async fn list(repo_root: PathBuf) -> Result<()> {
+    let sessions = tokio::task::spawn_blocking(move || db::list_sessions(&repo_root)).await??;
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({ "sessions": sessions }));
+        return Ok(());
+    }
+    if sessions.is_empty() {
+        output::dim("No saved sessions yet. Sessions are saved after every turn.");
+        return Ok(());
+    }
+
+    output::header("Sessions");
+    let now = chrono::Utc::now().timestamp();
+    let mut table = output::table();
+    table.set_header(vec!["Session", "Id", "Updated", "Turns", "Tokens", "Model"]);
+    for session in &sessions {
+        table.add_row(vec![
+            Cell::new(session.label()),
+            Cell::new(short_id(&session.id)),
+            Cell::new(format_age(
+                now.saturating_sub(session.updated_at).max(0) as u64
+            )),
+            Cell::new(session.turns),
+            Cell::new(format_tokens(session.total_tokens())),
+            Cell::new(&session.model),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+async fn show(repo_root: PathBuf, query: &str) -> Result<()> {
+    let (record, session) = load(repo_root, query).await?;
+    if output::is_json() {
+        output::json_pretty(&serde_json::to_value(&session)?);
+    } else {
+        print!("{}", render_markdown(&record, &session));
+    }
+    Ok(())
+}
+
+async fn export(
+    repo_root: PathBuf,
+    query: &str,
+    format: ExportFormat,
+    file: Option<String>,
+) -> Result<()> {
+    let (record, session) = load(repo_root, query).await?;
+    let text = match format {
+        ExportFormat::Markdown => render_markdown(&record, &session),
+        ExportFormat::Json => format!("{}\n", serde_json::to_string_pretty(&session)?),
+    };
+    match file {
+        Some(file) => {
+            std::fs::write(&file, text).map_err(|e| anyhow!("Failed to write {}: {}", file, e))?;
+            output::success(&format!("Exported {} to {}", record.label(), file));
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+async fn delete(repo_root: PathBuf, query: &str) -> Result<()> {
+    let query = query.to_string();
+    let record = tokio::task::spawn_blocking(move || -> Result<SessionRecord> {
+        let record = find(&repo_root, &query)?;
+        db::delete_session(&repo_root, &record.id)?;
+        Ok(record)
+    })
+    .await??;
+    output::success(&format!(
+        "Deleted session {} ({})",
+        record.label(),
+        short_id(&record.id)
+    ));
+    Ok(())
+}
+
+async fn load(repo_root: PathBuf, query: &str) -> Result<(SessionRecord, Session)> {
+    let query = query.to_string();
+    tokio::task::spawn_blocking(move || {
+        let record = find(&repo_root, &query)?;
+        let session = db::load_session(&repo_root, &record.id)?
+            .ok_or_else(|| anyhow!("Session not found: {}", query))?;
+        Ok((record, session))
+    })
+    .await?
+}
+
+fn find(repo_root: &Path, query: &str) -> Result<SessionRecord> {
+    db::find_session(repo_root, query)?.ok_or_else(|| anyhow!("Session not found: {}", query))
+}
+
+/// Readable transcript: a header with the session's details, then each message.
+/// Thinking is left out; tool calls show their arguments and how they ended.
+fn render_markdown(record: &SessionRecord, session: &Session) -> String {
+    let title = if record.title.is_empty() {
+        record.label()
+    } else {
+        &record.title
+    };
+    let mut out = format!("# {}\n\n", title);
+    out.push_str(&format!("- Session: `{}`", record.id));
+    if !record.slug.is_empty() {
+        out.push_str(&format!(" ({})", record.slug));
+    }
+    out.push('\n');
+    out.push_str(&format!(
+        "- Started: {} · Updated: {}\n",
+        format_date(record.created_at),
+        format_date(record.updated_at)
+    ));
+    out.push_str(&format!(
+        "- Model: {} / {}\n",
+        record.provider, record.model
+    ));
+    out.push_str(&format!(
+        "- Tokens: {} in · {} out\n",
+        format_tokens(record.prompt_tokens),
+        format_tokens(record.completion_tokens)
+    ));
+
+    for turn in &session.turns {
+        let heading = match turn.role {
+            Role::User => Some("You"),
+            Role::Assistant => Some("Assistant"),
+            Role::System => Some("System"),
+            Role::Tool => None,
+        };
+        if let Some(heading) = heading {
+            out.push_str(&format!(
+                "\n## {} · {}\n",
+                heading,
+                turn.timestamp.with_timezone(&Local).format("%H:%M")
+            ));
+        }
+        for block in &turn.blocks {
+            match block {
+                ContentBlock::Text { text } => {
+                    out.push('\n');
+                    out.push_str(text.trim_end());
+                    out.push('\n');
+                }
+                ContentBlock::Thinking { .. } => {}
+                ContentBlock::ToolUse { tool_use } => {
+                    out.push_str(&format!(
+                        "\n- **{}** `{}`\n",
+                        tool_use.name,
+                        truncate(&tool_use.args.to_string(), MAX_ARGS_CHARS)
+                    ));
+                }
+                ContentBlock::ToolResult { tool_result } => {
+                    if tool_result.is_error {
+                        // Tool turns wrap the output: {"tool_use_id", "result": {"error"}, ...}.
+                        let output = &tool_result.output;
+                        let error = ["result", "error"]
+                            .iter()
+                            .try_fold(output, |v, key| v.get(key))
+                            .or_else(|| output.get("result"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                            .unwrap_or_else(|| output.to_string());
+                        out.push_str(&format!(
+                            "  - failed: {}\n",
+                            truncate(error.lines().next().unwrap_or_default(), MAX_ARGS_CHARS)
+                        ));
+                    } else {
+                        out.push_str(&format!("  - done in {} ms\n", tool_result.duration_ms));
+                    }
+                }
+                ContentBlock::Error { error } => {
+                    out.push_str(&format!("\n> Error: {}\n", error));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+fn format_date(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
+
+/// `950`, `12.3k` or `1.2M`.
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", cut)
+}
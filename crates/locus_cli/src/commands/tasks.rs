@@ -0,0 +1,91 @@
+//! `locus tasks` — the task_list plans stored in `.locus/locus.db`.
+//!
+//! The agent's `task_list` tool writes the same table, so plans made in one
+//! session (or by `locus work`) show up here and in later sessions.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use comfy_table::Cell;
+use locus_core::db::{self, TaskItem, TaskStatus};
+
+use crate::commands::toolbus::find_repo_root;
+use crate::output;
+
+pub async fn handle(plan: Option<String>, workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(dir) => PathBuf::from(dir),
+        None => find_repo_root()?,
+    };
+    match plan {
+        Some(plan_id) => show(repo_root, plan_id).await,
+        None => list(repo_root).await,
+    }
+}
+
+async fn list(repo_root: PathBuf) -> Result<()> {
+    let plans = tokio::task::spawn_blocking(move || db::plans(&repo_root)).await??;
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({ "plans": plans }));
+        return Ok(());
+    }
+    if plans.is_empty() {
+        output::dim("No task lists yet. The agent creates them with the task_list tool.");
+        return Ok(());
+    }
+
+    output::header("Task lists");
+    let mut table = output::table();
+    table.set_header(vec!["Plan", "Tasks", "In progress", "Done"]);
+    for plan in &plans {
+        table.add_row(vec![
+            Cell::new(&plan.plan_id),
+            Cell::new(plan.tasks),
+            Cell::new(plan.in_progress),
+            Cell::new(format!("{}/{}", plan.done, plan.tasks)),
+        ]);
+    }
+    println!("{table}");
+    output::dim("locus tasks <plan>");
+    Ok(())
+}
+
+async fn show(repo_root: PathBuf, plan_id: String) -> Result<()> {
+    let id = plan_id.clone();
+    let result = tokio::task::spawn_blocking(move || db::list(&repo_root, &id)).await??;
+    let tasks: Vec<TaskItem> = serde_json::from_value(result["tasks"].clone())?;
+    if tasks.is_empty() {
+        return Err(anyhow!("No tasks in plan '{}'", plan_id));
+    }
+    if output::is_json() {
+        output::json_pretty(&result);
+        return Ok(());
+    }
+
+    output::header(&format!("Plan: {}", plan_id));
+    let mut table = output::table();
+    table.set_header(vec!["Id", "Status", "Task", "Criteria"]);
+    for task in &tasks {
+        let criteria = match task.criteria.len() {
+            0 => String::new(),
+            n => format!("{}/{}", n - task.unmet_criteria().count(), n),
+        };
+        table.add_row(vec![
+            Cell::new(task.id.as_deref().unwrap_or("")),
+            Cell::new(status_label(task.status)),
+            Cell::new(&task.title),
+            Cell::new(criteria),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "pending",
+        TaskStatus::InProgress => "in progress",
+        TaskStatus::Done => "done",
+        TaskStatus::Cancelled => "cancelled",
+    }
+}
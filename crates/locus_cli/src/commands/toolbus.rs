@@ -3,24 +3,33 @@
 use std::path::PathBuf;
 
 use anyhow::{Result, anyhow};
+use chrono::Local;
+use comfy_table::Cell;
+use locus_core::db::{self, ToolStats};
 use locus_toolbus::ToolBus;
 use serde_json::Value as JsonValue;
 
 use crate::cli::ToolbusAction;
+use crate::commands::journal::parse_since;
 use crate::output;
 
 pub async fn handle(action: ToolbusAction) -> Result<()> {
+    if let ToolbusAction::Stats { since, workdir } = action {
+        return stats(since, workdir).await;
+    }
     let repo_root = find_repo_root()?;
     let bus = ToolBus::new(repo_root);
+    bus.load_extensions().await;
 
     match action {
         ToolbusAction::List => list(&bus),
         ToolbusAction::Info { tool } => info(&bus, &tool),
         ToolbusAction::Call { tool, args } => call(&bus, &tool, &args).await,
+        ToolbusAction::Stats { .. } => unreachable!("handled above"),
     }
 }
 
-fn find_repo_root() -> Result<PathBuf> {
+pub(crate) fn find_repo_root() -> Result<PathBuf> {
     let cwd = std::env::current_dir()?;
     let mut path = cwd.as_path();
 
@@ -95,3 +104,57 @@ async fn call(bus: &ToolBus, tool_name: &str, args_str: &str) -> Result<()> {
 
     Ok(())
 }
+
+async fn stats(since: Option<String>, workdir: Option<String>) -> Result<()> {
+    let repo_root = match workdir {
+        Some(dir) => PathBuf::from(dir),
+        None => find_repo_root()?,
+    };
+    let since_ts = match since {
+        Some(since) => parse_since(&since, Local::now())?.timestamp(),
+        None => 0,
+    };
+    let stats = tokio::task::spawn_blocking(move || db::tool_stats(&repo_root, since_ts)).await??;
+
+    if output::is_json() {
+        output::json_pretty(&serde_json::json!({ "tools": stats }));
+        return Ok(());
+    }
+    if stats.is_empty() {
+        output::dim("No tool calls recorded yet.");
+        return Ok(());
+    }
+
+    output::header("Tool Usage");
+    let mut table = output::table();
+    table.set_header(vec![
+        "Tool", "Calls", "Failed", "p50", "p90", "p99", "Max", "Total",
+    ]);
+    for tool in &stats {
+        table.add_row(row(tool));
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn row(tool: &ToolStats) -> Vec<Cell> {
+    vec![
+        Cell::new(&tool.tool),
+        Cell::new(tool.calls),
+        Cell::new(format!("{:.1}%", tool.failure_rate * 100.0)),
+        Cell::new(format_ms(tool.p50_ms)),
+        Cell::new(format_ms(tool.p90_ms)),
+        Cell::new(format_ms(tool.p99_ms)),
+        Cell::new(format_ms(tool.max_ms)),
+        Cell::new(format_ms(tool.total_ms)),
+    ]
+}
+
+/// `850ms`, `12.3s` or `4m05s`.
+fn format_ms(ms: u64) -> String {
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m{:02}s", ms / 60_000, ms % 60_000 / 1000),
+    }
+}
@@ -1,29 +1,133 @@
 //! `locus tui` — run the interactive TUI with runtime integration.
 
+use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Result;
-use locus_runtime::{LlmProvider, Runtime, RuntimeConfig};
-use locusgraph_observability::{ObservabilityConfig, init};
+use locus_runtime::{ApprovalBroker, LlmProvider, Runtime, RuntimeConfig, RuntimeError};
+use locusgraph_observability::{LogBuffer, ObservabilityConfig, init};
 use tokio::sync::{RwLock, mpsc};
 use tokio_util::sync::CancellationToken;
 
-use locus_core::SessionEvent;
-use locus_tui::run_tui_with_runtime;
-use locus_tui::theme::Appearance;
+use locus_core::{
+    ApprovalResponse, ConflictResponse, CustomCommand, ModelCommand, PlanCommand, SessionEvent,
+    ToolAnnotation, load_custom_commands, parse_memory_command, parse_model_command,
+    parse_pin_command, parse_plan_command, parse_reject_hunk, parse_rewind,
+};
+use locus_tui::logs::MAX_LOG_LINES;
+use locus_tui::theme::detect_term;
+use locus_tui::{SessionSwitch, TranscriptMirror, run_tui_with_runtime};
 
 use crate::output;
 
-async fn run_runtime_loop(
-    config: RuntimeConfig,
-    provider_locked: bool,
-    model_locked: bool,
+/// Keep runtime logs for the TUI logs screen (Ctrl+D) instead of the console.
+pub(crate) fn init_logging() -> LogBuffer {
+    let logs = LogBuffer::new(MAX_LOG_LINES);
+
+    // Init tracing without console; keep logs in the TUI's buffer. Include locus.trace=debug so
+    // LocusGraph, LLM, and tool traces show in the Runtime logs screen (Ctrl+D).
+    let mut obs_config = ObservabilityConfig::from_env()
+        .with_console(false)
+        .with_log_buffer(logs.clone());
+    if obs_config.log_level.is_none() {
+        obs_config = obs_config.with_log_level("info,locus.trace=debug");
+    }
+    if let Err(e) = init(obs_config) {
+        output::warning(&format!("Observability init failed (continuing): {}", e));
+    }
+
+    logs
+}
+
+/// What [run_with_runtime] runs besides the runtime's events.
+pub(crate) struct TuiOptions {
+    /// Runtime logs for the logs screen (Ctrl+D).
+    pub logs: Option<LogBuffer>,
+    pub transcript: Option<TranscriptMirror>,
+    pub show_setup: bool,
+    /// Sent as the first user message when the TUI starts.
+    pub first_message: Option<String>,
+    /// Project whose saved sessions the session switcher (Ctrl+S) lists; None turns
+    /// the switcher off.
+    pub sessions_root: Option<PathBuf>,
+    /// Custom commands offered for `/name` completion.
+    pub custom_commands: Vec<CustomCommand>,
+}
+
+/// Run the TUI against runtimes made by `start`, which is called for the first
+/// message, again after each new session (Ctrl+N), and for each resumed session (Ctrl+S).
+pub(crate) async fn run_with_runtime<S, F>(start: S, options: TuiOptions) -> Result<()>
+where
+    S: Fn(mpsc::Sender<SessionEvent>) -> F + Send + 'static,
+    F: Future<Output = Result<Runtime, RuntimeError>> + Send + 'static,
+{
+    let (event_tx, event_rx) = mpsc::channel(256);
+    let (user_msg_tx, user_msg_rx) = mpsc::channel::<String>(64);
+    let (new_session_tx, new_session_rx) = mpsc::channel::<()>(4);
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>(4);
+    let (annotation_tx, annotation_rx) = mpsc::channel::<ToolAnnotation>(16);
+    let (approval_tx, approval_rx) = mpsc::channel::<ApprovalResponse>(16);
+    let (conflict_tx, conflict_rx) = mpsc::channel::<ConflictResponse>(16);
+    let (resume_tx, resume_rx) = mpsc::channel::<String>(4);
+    let session_switch = options
+        .sessions_root
+        .clone()
+        .map(|repo_root| SessionSwitch {
+            repo_root,
+            resume_tx,
+        });
+
+    tokio::spawn(run_runtime_loop(
+        start,
+        options.sessions_root,
+        event_tx,
+        user_msg_rx,
+        new_session_rx,
+        cancel_rx,
+        annotation_rx,
+        approval_rx,
+        conflict_rx,
+        resume_rx,
+    ));
+    if let Some(message) = options.first_message {
+        let _ = user_msg_tx.try_send(message);
+    }
+
+    run_tui_with_runtime(
+        event_rx,
+        user_msg_tx,
+        options.logs,
+        Some(new_session_tx),
+        Some(cancel_tx),
+        Some(annotation_tx),
+        Some(approval_tx),
+        Some(conflict_tx),
+        options.transcript,
+        session_switch,
+        options.custom_commands,
+        detect_term().unwrap_or_default(),
+        options.show_setup,
+    )?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_runtime_loop<S, F>(
+    start: S,
+    sessions_root: Option<PathBuf>,
     event_tx: mpsc::Sender<SessionEvent>,
     mut user_msg_rx: mpsc::Receiver<String>,
     mut new_session_rx: mpsc::Receiver<()>,
     mut cancel_rx: mpsc::Receiver<()>,
-) {
+    mut annotation_rx: mpsc::Receiver<ToolAnnotation>,
+    mut approval_rx: mpsc::Receiver<ApprovalResponse>,
+    mut conflict_rx: mpsc::Receiver<ConflictResponse>,
+    mut resume_rx: mpsc::Receiver<String>,
+) where
+    S: Fn(mpsc::Sender<SessionEvent>) -> F,
+    F: Future<Output = Result<Runtime, RuntimeError>>,
+{
     let current_cancel_token: Arc<RwLock<Option<CancellationToken>>> = Arc::new(RwLock::new(None));
     let token_guard = Arc::clone(&current_cancel_token);
     tokio::spawn(async move {
@@ -34,6 +138,33 @@ async fn run_runtime_loop(
         }
     });
 
+    // Answers arrive while a run is blocked on the approval, so resolve them off the main loop.
+    let broker = Arc::new(ApprovalBroker::new(event_tx.clone()));
+    let resolver = Arc::clone(&broker);
+    tokio::spawn(async move {
+        while let Some(response) = approval_rx.recv().await {
+            resolver.resolve(response);
+        }
+    });
+    let resolver = Arc::clone(&broker);
+    tokio::spawn(async move {
+        while let Some(response) = conflict_rx.recv().await {
+            resolver.resolve_conflict(response);
+        }
+    });
+
+    // Notes are written during runs too, so hand them to the runtime's own queue.
+    let current_annotations: Arc<RwLock<Option<mpsc::Sender<ToolAnnotation>>>> =
+        Arc::new(RwLock::new(None));
+    let annotations = Arc::clone(&current_annotations);
+    tokio::spawn(async move {
+        while let Some(annotation) = annotation_rx.recv().await {
+            if let Some(tx) = annotations.read().await.as_ref() {
+                let _ = tx.send(annotation).await;
+            }
+        }
+    });
+
     let mut runtime_opt: Option<Runtime> = None;
     loop {
         tokio::select! {
@@ -42,10 +173,78 @@ async fn run_runtime_loop(
                     Some(m) => m,
                     None => break,
                 };
-                let active_config = refreshed_runtime_config(&config, provider_locked, model_locked);
+                // `:rewind N` acts on the current session; it never starts one.
+                if let Some(turns) = parse_rewind(&msg) {
+                    let rewound = match (turns, runtime_opt.as_mut()) {
+                        (Ok(turns), Some(rt)) => {
+                            rt.rewind(turns).await.map(|_| ()).map_err(|e| e.to_string())
+                        }
+                        (Ok(_), None) => Err("Nothing to rewind".to_string()),
+                        (Err(usage), _) => Err(usage),
+                    };
+                    if let Err(e) = rewound {
+                        let _ = event_tx.send(SessionEvent::error(e)).await;
+                    }
+                    continue;
+                }
+                // `:reject-hunk T N <path>` comes from the diff review; it acts on the current session.
+                if let Some(target) = parse_reject_hunk(&msg) {
+                    let rejected = match (target, runtime_opt.as_mut()) {
+                        (Ok(target), Some(rt)) => {
+                            rt.reject_hunk(target).await.map_err(|e| e.to_string())
+                        }
+                        (Ok(_), None) => Err("Nothing to review".to_string()),
+                        (Err(usage), _) => Err(usage),
+                    };
+                    if let Err(e) = rejected {
+                        let _ = event_tx.send(SessionEvent::error(e)).await;
+                    }
+                    continue;
+                }
+                // `:plan <task>` plans with read-only tools; `:execute-plan` carries it out.
+                let plan_command = match parse_plan_command(&msg) {
+                    Some(Ok(command)) => Some(command),
+                    Some(Err(usage)) => {
+                        let _ = event_tx.send(SessionEvent::error(usage)).await;
+                        continue;
+                    }
+                    None => None,
+                };
+                // `:pin <path>` / `:unpin <path>` change what every request carries.
+                let pin_command = match parse_pin_command(&msg) {
+                    Some(Ok(command)) => Some(command),
+                    Some(Err(usage)) => {
+                        let _ = event_tx.send(SessionEvent::error(usage)).await;
+                        continue;
+                    }
+                    None => None,
+                };
+                // `:model` lists the providers' models; `:model <provider>/<model>` switches.
+                let model_command = match parse_model_command(&msg) {
+                    Some(Ok(command)) => Some(command),
+                    Some(Err(usage)) => {
+                        let _ = event_tx.send(SessionEvent::error(usage)).await;
+                        continue;
+                    }
+                    None => None,
+                };
+                // `:memory <query>` shows what recall would find, without a turn.
+                let memory_query = match parse_memory_command(&msg) {
+                    Some(Ok(query)) => Some(query),
+                    Some(Err(usage)) => {
+                        let _ = event_tx.send(SessionEvent::error(usage)).await;
+                        continue;
+                    }
+                    None => None,
+                };
                 let mut rt = match runtime_opt.take() {
-                    None => match Runtime::new(active_config.clone(), event_tx.clone()).await {
-                        Ok(r) => r,
+                    None => match start(event_tx.clone()).await {
+                        Ok(r) => {
+                            r.set_approver(broker.clone());
+                            r.set_conflict_resolver(broker.clone());
+                            *current_annotations.write().await = Some(r.annotation_sender());
+                            r
+                        }
                         Err(e) => {
                             output::error(&format!("Runtime failed to start: {}", e));
                             continue;
@@ -53,9 +252,46 @@ async fn run_runtime_loop(
                     },
                     Some(prev) => prev,
                 };
+                if let Some(command) = pin_command {
+                    if let Err(e) = rt.pin(command).await {
+                        let _ = event_tx.send(SessionEvent::error(e.to_string())).await;
+                    }
+                    runtime_opt = Some(rt);
+                    continue;
+                }
+                if let Some(command) = model_command {
+                    let switched = match command {
+                        ModelCommand::List => {
+                            rt.list_models().await;
+                            Ok(())
+                        }
+                        ModelCommand::Switch { provider, model } => {
+                            rt.switch_model(&provider, &model).await
+                        }
+                    };
+                    if let Err(e) = switched {
+                        let _ = event_tx.send(SessionEvent::error(e.to_string())).await;
+                    }
+                    runtime_opt = Some(rt);
+                    continue;
+                }
+                if let Some(query) = memory_query {
+                    if let Err(e) = rt.search_memories(&query).await {
+                        let _ = event_tx.send(SessionEvent::error(e.to_string())).await;
+                    }
+                    runtime_opt = Some(rt);
+                    continue;
+                }
                 let token = CancellationToken::new();
                 *current_cancel_token.write().await = Some(token.clone());
-                if let Err(e) = rt.run(msg, Some(token)).await {
+                let result = match plan_command {
+                    Some(PlanCommand::Plan(task)) => rt.plan(task, Some(token)).await,
+                    Some(PlanCommand::Execute(path)) => {
+                        rt.execute_plan(path.as_deref(), Some(token)).await
+                    }
+                    None => rt.run(msg, Some(token)).await,
+                };
+                if let Err(e) = result {
                     // Runtime already sends SessionEvent::error + turn_end to TUI; also log to stderr
                     output::error(&format!("Run failed: {}", e));
                 }
@@ -74,6 +310,50 @@ async fn run_runtime_loop(
                     None => break,
                 }
             }
+            Some(session_id) = resume_rx.recv() => {
+                let Some(repo_root) = sessions_root.clone() else {
+                    continue;
+                };
+                if let Some(mut rt) = runtime_opt.take()
+                    && let Err(e) = rt.shutdown().await
+                {
+                    output::warning(&format!("Runtime shutdown: {}", e));
+                }
+                let loaded = tokio::task::spawn_blocking(move || {
+                    let record = locus_core::db::find_session(&repo_root, &session_id)?;
+                    let session = match &record {
+                        Some(record) => locus_core::db::load_session(&repo_root, &record.id)?,
+                        None => None,
+                    };
+                    anyhow::Ok(record.zip(session))
+                })
+                .await;
+                let (record, session) = match loaded {
+                    Ok(Ok(Some(loaded))) => loaded,
+                    Ok(Ok(None)) => {
+                        output::error("Session to resume no longer exists");
+                        continue;
+                    }
+                    Ok(Err(e)) => {
+                        output::error(&format!("Session failed to load: {}", e));
+                        continue;
+                    }
+                    Err(e) => {
+                        output::error(&format!("Session failed to load: {}", e));
+                        continue;
+                    }
+                };
+                match start(event_tx.clone()).await {
+                    Ok(mut rt) => {
+                        rt.set_approver(broker.clone());
+                        rt.set_conflict_resolver(broker.clone());
+                        *current_annotations.write().await = Some(rt.annotation_sender());
+                        rt.resume_session(session, record.slug).await;
+                        runtime_opt = Some(rt);
+                    }
+                    Err(e) => output::error(&format!("Runtime failed to start: {}", e)),
+                }
+            }
         }
     }
 
@@ -89,29 +369,33 @@ pub async fn handle(
     provider: Option<String>,
     model: Option<String>,
     onboarding: bool,
+    transcript: bool,
 ) -> Result<()> {
     let repo_root = workdir
         .map(PathBuf::from)
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
-    // Channel for runtime logs → TUI debug traces screen (Ctrl+D)
-    let (log_tx, log_rx) = mpsc::channel::<String>(512);
-    let log_sink: Arc<dyn Fn(String) + Send + Sync> = Arc::new(move |line| {
-        let _ = log_tx.try_send(line);
-    });
+    let logs = init_logging();
 
-    // Init tracing without console; send logs to TUI sink. Include locus.trace=debug so
-    // LocusGraph, LLM, and tool traces show in the Runtime logs screen (Ctrl+D).
-    let mut obs_config = ObservabilityConfig::from_env()
-        .with_console(false)
-        .with_log_sink(log_sink);
-    if obs_config.log_level.is_none() {
-        obs_config = obs_config.with_log_level("info,locus.trace=debug");
-    }
-    if let Err(e) = init(obs_config) {
-        output::warning(&format!("Observability init failed (continuing): {}", e));
-    }
+    let transcript = if transcript || TranscriptMirror::enabled_by_env() {
+        match TranscriptMirror::for_repo(&repo_root) {
+            Ok(mirror) => {
+                output::dim(&format!(
+                    "Mirroring transcript to {}",
+                    mirror.path().display()
+                ));
+                Some(mirror)
+            }
+            Err(e) => {
+                output::warning(&format!("Transcript mirror disabled: {}", e));
+                None
+            }
+        }
+    } else {
+        None
+    };
 
+    let config_repo_root = repo_root.clone();
     let mut config = RuntimeConfig::from_env(repo_root);
     let provider_locked = provider.is_some();
     let model_locked = model.is_some();
@@ -123,34 +407,27 @@ pub async fn handle(
     if let Some(m) = model {
         config = config.with_model(m);
     }
-    let (event_tx, event_rx) = mpsc::channel(256);
-    let (user_msg_tx, user_msg_rx) = mpsc::channel::<String>(64);
-    let (new_session_tx, new_session_rx) = mpsc::channel::<()>(4);
-    let (cancel_tx, cancel_rx) = mpsc::channel::<()>(4);
-
     // Show setup when no LLM key is set, or when user passes --onboarding.
     let show_setup = onboarding || !has_any_llm_key();
 
-    tokio::spawn(run_runtime_loop(
-        config,
-        provider_locked,
-        model_locked,
-        event_tx,
-        user_msg_rx,
-        new_session_rx,
-        cancel_rx,
-    ));
-
-    run_tui_with_runtime(
-        event_rx,
-        user_msg_tx,
-        Some(log_rx),
-        Some(new_session_tx),
-        Some(cancel_tx),
-        Appearance::Dark,
-        show_setup,
-    )?;
-    Ok(())
+    let start = move |event_tx| {
+        Runtime::new(
+            refreshed_runtime_config(&config, provider_locked, model_locked),
+            event_tx,
+        )
+    };
+    run_with_runtime(
+        start,
+        TuiOptions {
+            logs: Some(logs),
+            transcript,
+            show_setup,
+            first_message: None,
+            custom_commands: load_custom_commands(&config_repo_root),
+            sessions_root: Some(config_repo_root),
+        },
+    )
+    .await
 }
 
 /// True if at least one LLM provider API key is saved in the global config DB.
@@ -190,6 +467,7 @@ fn refreshed_runtime_config(
     refreshed.tool_token_budget = base.tool_token_budget;
     refreshed.max_tokens = base.max_tokens;
     refreshed.sandbox = base.sandbox.clone();
+    refreshed.checkpoints = base.checkpoints;
 
     if provider_locked {
         refreshed = refreshed.with_provider(base.provider);
@@ -0,0 +1,136 @@
+//! `locus tutorial` — a guided session on a bundled sample repo.
+//!
+//! Copies the sample repo in `tutorial/repo` into a fresh directory (committed
+//! with git when it is available) and runs the real TUI and runtime there, with
+//! the scripted [MockProvider] answering from `tutorial/cassette.json` and an
+//! offline LocusGraph. The script walks through prompts, approvals (the sample
+//! repo asks before edits), diff review, the task board and memory; any message
+//! past its end gets a closing note. No API key or network is needed.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use locus_graph::{LocusGraphClient, LocusGraphConfig};
+use locus_llms::testing::{Cassette, MockProvider};
+use locus_runtime::{Runtime, RuntimeConfig};
+use locus_toolbus::ToolBus;
+
+use crate::commands::tui::{TuiOptions, init_logging, run_with_runtime};
+use crate::output;
+
+/// The sample repo, as (path, content).
+const SAMPLE_REPO: &[(&str, &str)] = &[
+    ("README.md", include_str!("../../tutorial/repo/README.md")),
+    (".gitignore", include_str!("../../tutorial/repo/.gitignore")),
+    (
+        ".locus/tools.toml",
+        include_str!("../../tutorial/repo/.locus/tools.toml"),
+    ),
+    (
+        "shop/__init__.py",
+        include_str!("../../tutorial/repo/shop/__init__.py"),
+    ),
+    (
+        "shop/cart.py",
+        include_str!("../../tutorial/repo/shop/cart.py"),
+    ),
+    (
+        "tests/test_cart.py",
+        include_str!("../../tutorial/repo/tests/test_cart.py"),
+    ),
+];
+
+const CASSETTE: &str = include_str!("../../tutorial/cassette.json");
+
+/// Sent for the user when the TUI opens, so the session starts with the welcome.
+const FIRST_MESSAGE: &str = "Start the tutorial";
+
+pub async fn handle(dir: Option<String>) -> Result<()> {
+    let keep = dir.is_some();
+    let repo_root = match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::temp_dir().join(format!("locus-tutorial-{}", std::process::id())),
+    };
+    write_sample_repo(&repo_root)?;
+    let logs = init_logging();
+
+    let provider = Arc::new(MockProvider::new(Cassette::from_json(CASSETTE)?).with_id("tutorial"));
+    let graph_config = LocusGraphConfig::new("http://127.0.0.1:1", "tutorial", "locus-tutorial")
+        .db_path(repo_root.join(".locus").join("locus_graph_cache.db"))
+        .cache_reads(false);
+    let locus_graph = Arc::new(LocusGraphClient::new(graph_config).await?);
+    let toolbus = Arc::new(ToolBus::new(repo_root.clone()));
+    let config = RuntimeConfig::new(repo_root.clone()).with_model("tutorial");
+
+    // Ctrl+N starts a new session on the same script, so it picks up where it was.
+    let start = move |event_tx| {
+        Runtime::new_with_shared(
+            config.clone(),
+            event_tx,
+            toolbus.clone(),
+            locus_graph.clone(),
+            provider.clone(),
+        )
+    };
+    let result = run_with_runtime(
+        start,
+        TuiOptions {
+            logs: Some(logs),
+            transcript: None,
+            show_setup: false,
+            first_message: Some(FIRST_MESSAGE.to_string()),
+            sessions_root: None,
+            custom_commands: Vec::new(),
+        },
+    )
+    .await;
+
+    if keep {
+        output::dim(&format!(
+            "The tutorial repo is kept at {}",
+            repo_root.display()
+        ));
+    } else if let Err(e) = std::fs::remove_dir_all(&repo_root) {
+        output::warning(&format!("Could not remove {}: {}", repo_root.display(), e));
+    }
+    result
+}
+
+/// Write the sample repo into `root`, which must be missing or empty, and commit
+/// it so the session can show diffs against git.
+fn write_sample_repo(root: &Path) -> Result<()> {
+    if root.exists() && std::fs::read_dir(root)?.next().is_some() {
+        bail!(
+            "{} is not empty; pass an empty or new directory to --dir",
+            root.display()
+        );
+    }
+    for (path, content) in SAMPLE_REPO {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content).with_context(|| format!("write {}", path.display()))?;
+    }
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args([
+                "-c",
+                "user.name=locus",
+                "-c",
+                "user.email=tutorial@locus.codes",
+            ])
+            .args(args)
+            .current_dir(root)
+            .output()
+            .is_ok_and(|o| o.status.success())
+    };
+    if !(git(&["init", "-q"]) && git(&["add", "-A"]) && git(&["commit", "-q", "-m", "Sample shop"]))
+    {
+        output::warning("git is not available; the tutorial repo is not a git repository");
+    }
+    Ok(())
+}
@@ -0,0 +1,320 @@
+//! `locus work <issue>` — issue-to-branch workflow.
+//!
+//! 1. Fetch the issue with the GitHub CLI (`gh issue view`)
+//! 2. Create (or switch to) `locus/<number>-<slug>` from the current branch
+//! 3. Run a session seeded with the issue, asking the agent to plan in
+//!    task_list (plan `issue-<number>`) before implementing and committing
+//! 4. Offer to push the branch and open a PR whose description is built from
+//!    the session log, the plan and the new commits
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+use locus_core::SessionEvent;
+use locus_core::db::{self, TaskItem, TaskStatus};
+use locus_runtime::{Runtime, RuntimeConfig};
+use locusgraph_observability::{init_from_env, shutdown};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+use crate::commands::run::spawn_event_printer;
+use crate::output;
+
+/// Branch name prefix for issue branches.
+const BRANCH_PREFIX: &str = "locus/";
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
+}
+
+pub async fn handle(
+    issue: String,
+    model: Option<String>,
+    provider: Option<String>,
+    workdir: Option<String>,
+    max_turns: Option<u32>,
+) -> Result<()> {
+    let repo_root = match workdir {
+        Some(w) => PathBuf::from(w),
+        None => std::env::current_dir()?,
+    };
+
+    let issue = fetch_issue(&repo_root, &issue)?;
+    let base = git(&repo_root, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    let branch = branch_name(&issue);
+    switch_branch(&repo_root, &branch)?;
+
+    let llm_provider = provider
+        .as_deref()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default();
+    let mut config = RuntimeConfig::from_env(repo_root.clone()).with_provider(llm_provider);
+    if let Some(m) = &model {
+        config = config.with_model(m);
+    }
+    if let Some(max) = max_turns {
+        config = config.with_max_turns(max);
+    }
+
+    if let Err(e) = init_from_env() {
+        output::warning(&format!("Observability init failed (continuing): {}", e));
+    }
+
+    output::header(&format!("Issue #{}: {}", issue.number, issue.title));
+    output::kv("Branch", &format!("{} (from {})", branch, base));
+    output::kv("Plan", &plan_id(&issue));
+    println!();
+
+    let (event_tx, event_rx) = mpsc::channel::<SessionEvent>(256);
+    let event_handle = spawn_event_printer(event_rx);
+    let started = chrono::Utc::now().timestamp();
+
+    let mut runtime = match Runtime::new(config, event_tx).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            event_handle.abort();
+            output::error(&format!("Failed to start runtime: {}", e));
+            output::dim("Make sure LocusGraph is configured: locus config graph");
+            shutdown();
+            return Ok(());
+        }
+    };
+    let result = runtime.run(session_prompt(&issue, &branch), None).await;
+    let _ = runtime.shutdown().await;
+    event_handle.abort();
+    println!();
+    match &result {
+        Ok(status) => output::success(&format!("Session completed: {:?}", status)),
+        Err(e) => output::error(&format!("Runtime error: {}", e)),
+    }
+    output::session_summary(&runtime.session.build_summary());
+    let session_id = runtime.session.id.as_str().to_string();
+    shutdown();
+
+    offer_pull_request(&repo_root, &issue, &base, &branch, &session_id, started)
+}
+
+/// `gh issue view` for a URL, `#123` or `123`.
+fn fetch_issue(repo_root: &Path, issue: &str) -> Result<Issue> {
+    let issue = issue.trim().trim_start_matches('#');
+    let output = Command::new("gh")
+        .args([
+            "issue",
+            "view",
+            issue,
+            "--json",
+            "number,title,body,url,labels",
+        ])
+        .current_dir(repo_root)
+        .output()
+        .context("Failed to run `gh`; install the GitHub CLI and run `gh auth login`")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Could not fetch issue {}: {}",
+            issue,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).context("Unexpected `gh issue view` output")
+}
+
+/// `locus/<number>-<slug of the title>`, at most 40 slug characters.
+fn branch_name(issue: &Issue) -> String {
+    let mut slug = String::new();
+    for word in issue
+        .title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + word.len() + 1 > 40 {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    slug.truncate(40);
+    if slug.is_empty() {
+        format!("{}{}", BRANCH_PREFIX, issue.number)
+    } else {
+        format!("{}{}-{}", BRANCH_PREFIX, issue.number, slug)
+    }
+}
+
+fn plan_id(issue: &Issue) -> String {
+    format!("issue-{}", issue.number)
+}
+
+/// Create `branch` from HEAD, or switch to it when it already exists.
+fn switch_branch(repo_root: &Path, branch: &str) -> Result<()> {
+    if git(repo_root, &["switch", "-c", branch]).is_ok() {
+        output::success(&format!("Created branch {}", branch));
+        return Ok(());
+    }
+    git(repo_root, &["switch", branch])?;
+    output::dim(&format!("Switched to existing branch {}", branch));
+    Ok(())
+}
+
+fn session_prompt(issue: &Issue, branch: &str) -> String {
+    let mut prompt = format!("Work on issue #{}: {}\n", issue.number, issue.title);
+    if !issue.url.is_empty() {
+        prompt.push_str(&format!("{}\n", issue.url));
+    }
+    if !issue.labels.is_empty() {
+        let labels: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+        prompt.push_str(&format!("Labels: {}\n", labels.join(", ")));
+    }
+    if !issue.body.trim().is_empty() {
+        prompt.push_str(&format!("\n{}\n", issue.body.trim()));
+    }
+    prompt.push_str(&format!(
+        "\nBefore changing code, use task_list to create plan `{}` with one task per step \
+         of the work. Then implement the plan on branch `{}` (already checked out), marking \
+         tasks done as you finish them, run the relevant tests, and commit the finished work \
+         with messages that reference #{}. Do not push.",
+        plan_id(issue),
+        branch,
+        issue.number
+    ));
+    prompt
+}
+
+fn offer_pull_request(
+    repo_root: &Path,
+    issue: &Issue,
+    base: &str,
+    branch: &str,
+    session_id: &str,
+    started: i64,
+) -> Result<()> {
+    if !git(repo_root, &["status", "--porcelain"])?.is_empty() {
+        output::warning("Uncommitted changes are not part of the branch yet.");
+    }
+    let range = format!("{}..{}", base, branch);
+    let commits = git(repo_root, &["log", "--reverse", "--format=%h %s", &range])?;
+    if commits.is_empty() {
+        output::warning(&format!("No commits on {}; nothing to push.", branch));
+        return Ok(());
+    }
+
+    println!();
+    output::header(&format!("Commits on {}", branch));
+    for line in commits.lines() {
+        println!("  {}", line);
+    }
+    output::dim("Push the branch and open a pull request? (y/n):");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+        output::dim(&format!("Left {} unpushed.", branch));
+        return Ok(());
+    }
+
+    git(repo_root, &["push", "-u", "origin", branch])?;
+    let body = pr_description(repo_root, issue, &commits, session_id, started);
+    let output = Command::new("gh")
+        .args(["pr", "create", "--base", base, "--head", branch])
+        .args(["--title", &issue.title, "--body", &body])
+        .current_dir(repo_root)
+        .output()
+        .context("Failed to run `gh pr create`")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "gh pr create failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    output::success(&format!(
+        "Opened {}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    ));
+    Ok(())
+}
+
+/// PR body: issue link, turn outcomes from the session log, the plan and the commits.
+fn pr_description(
+    repo_root: &Path,
+    issue: &Issue,
+    commits: &str,
+    session_id: &str,
+    started: i64,
+) -> String {
+    let mut body = format!("Closes #{}\n", issue.number);
+
+    let turns: Vec<_> = db::turns_since(repo_root, started)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|t| t.session_id == session_id)
+        .collect();
+    if !turns.is_empty() {
+        body.push_str("\n## Summary\n\n");
+        for turn in &turns {
+            let outcome = turn
+                .outcome
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if outcome.is_empty() {
+                body.push_str(&format!("- {}\n", turn.title));
+            } else {
+                body.push_str(&format!("- {}: {}\n", turn.title, outcome));
+            }
+        }
+    }
+
+    let tasks: Vec<TaskItem> = db::list(repo_root, &plan_id(issue))
+        .ok()
+        .and_then(|plan| serde_json::from_value(plan["tasks"].clone()).ok())
+        .unwrap_or_default();
+    if !tasks.is_empty() {
+        body.push_str("\n## Plan\n\n");
+        for task in &tasks {
+            let mark = if task.status == TaskStatus::Done {
+                "x"
+            } else {
+                " "
+            };
+            body.push_str(&format!("- [{}] {}\n", mark, task.title));
+        }
+    }
+
+    body.push_str("\n## Commits\n\n");
+    for line in commits.lines() {
+        body.push_str(&format!("- {}\n", line));
+    }
+    body
+}
+
+/// Run git in `repo_root` and return trimmed stdout; fails with git's stderr.
+fn git(repo_root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -25,7 +25,7 @@ pub fn init(format: OutputFormat) {
     }
 }
 
-fn is_json() -> bool {
+pub fn is_json() -> bool {
     JSON_MODE.load(Ordering::Relaxed)
 }
 
@@ -168,6 +168,35 @@ pub fn session_summary(summary: &locus_core::SessionSummary) {
     }
 }
 
+// ── Diffs ──────────────────────────────────────────────────────────
+
+/// Print a line diff between two texts (JSON mode emits both texts instead).
+pub fn diff(old: &str, new: &str) {
+    if is_json() {
+        let data = serde_json::json!({ "old_content": old, "new_content": new });
+        emit_json("diff", "", Some(&data));
+        return;
+    }
+    use locus_tui::diff::{ChangeType, line_diff_with_numbers};
+    for row in line_diff_with_numbers(old, new) {
+        let line_no = row
+            .new_line_no
+            .or(row.old_line_no)
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        let gutter = style(format!("{:>5} │", line_no)).dim();
+        match row.change {
+            ChangeType::Added => {
+                println!("{} {}", gutter, style(format!("+ {}", row.text)).green())
+            }
+            ChangeType::Removed => {
+                println!("{} {}", gutter, style(format!("- {}", row.text)).red())
+            }
+            ChangeType::Unchanged => println!("{}   {}", gutter, style(&row.text).dim()),
+        }
+    }
+}
+
 // ── Tables ─────────────────────────────────────────────────────────
 
 /// Create a styled table for listing items.
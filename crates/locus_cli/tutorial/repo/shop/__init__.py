@@ -0,0 +1,3 @@
+from shop.cart import Item, cart_total
+
+__all__ = ["Item", "cart_total"]
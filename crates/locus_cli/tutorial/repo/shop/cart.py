@@ -0,0 +1,16 @@
+"""Shopping cart totals."""
+
+from dataclasses import dataclass
+
+
+@dataclass
+class Item:
+    name: str
+    price_cents: int
+    quantity: int = 1
+
+
+def cart_total(items: list[Item], discount_percent: int = 0) -> int:
+    """Total price in cents, after taking `discount_percent` off."""
+    subtotal = sum(item.price_cents * item.quantity for item in items)
+    return subtotal
@@ -0,0 +1,11 @@
+from shop import Item, cart_total
+
+
+def test_total_adds_quantities():
+    items = [Item("pen", 150, quantity=2), Item("notebook", 400)]
+    assert cart_total(items) == 700
+
+
+def test_total_applies_discount():
+    items = [Item("notebook", 400)]
+    assert cart_total(items, discount_percent=25) == 300
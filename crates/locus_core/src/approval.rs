@@ -0,0 +1,173 @@
+//! Tool approval: the permission a tool needs and the user's answer.
+//!
+//! The ToolBus asks for approval when a tool's permission rule is `ask`. The
+//! runtime forwards the [ApprovalRequest] to the TUI as
+//! [crate::SessionEvent::ApprovalRequested]; the TUI answers with an
+//! [ApprovalResponse] carrying the same id. File writes come with an
+//! [ApprovalPreview] of the change, and a denial can carry a message for the model.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// What a tool can do to the machine it runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Reads files or project state only.
+    Read,
+    /// Creates, edits or restores files.
+    Write,
+    /// Runs shell commands or other processes.
+    Execute,
+    /// Talks to the network.
+    Network,
+    /// Changes git state: commits, branches.
+    GitWrite,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Execute => "execute",
+            Permission::Network => "network",
+            Permission::GitWrite => "git_write",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The file change a write call would make, shown before it is allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalPreview {
+    /// Path as the tool was given it.
+    pub path: String,
+    /// Current content; empty when the file does not exist yet.
+    pub before: String,
+    pub after: String,
+}
+
+/// A pending tool call waiting for the user to allow or deny it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub tool_name: String,
+    pub permission: Permission,
+    pub args: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<ApprovalPreview>,
+}
+
+impl ApprovalRequest {
+    pub fn new(
+        tool_name: impl Into<String>,
+        permission: Permission,
+        args: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool_name: tool_name.into(),
+            permission,
+            args,
+            preview: None,
+        }
+    }
+
+    pub fn with_preview(mut self, preview: ApprovalPreview) -> Self {
+        self.preview = Some(preview);
+        self
+    }
+}
+
+/// The user's answer to an [ApprovalRequest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// Run this call.
+    Allow,
+    /// Run this call and stop asking for this tool for the rest of the session.
+    AllowAlways,
+    /// Do not run this call; the model gets an error result instead, with the
+    /// response's message when the user wrote one.
+    Deny,
+}
+
+/// Decision for the request with `id`, sent from the TUI back to the runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalResponse {
+    pub id: String,
+    pub decision: ApprovalDecision,
+    /// What the user told the agent along with a denial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ApprovalResponse {
+    pub fn new(id: impl Into<String>, decision: ApprovalDecision) -> Self {
+        Self {
+            id: id.into(),
+            decision,
+            message: None,
+        }
+    }
+
+    /// Deny with a message that is passed on to the model.
+    pub fn deny_with_message(id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            message: Some(message.into()),
+            ..Self::new(id, ApprovalDecision::Deny)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_serde() {
+        assert_eq!(
+            serde_json::to_string(&Permission::Execute).unwrap(),
+            "\"execute\""
+        );
+        let p: Permission = serde_json::from_str("\"network\"").unwrap();
+        assert_eq!(p, Permission::Network);
+        assert_eq!(Permission::Write.to_string(), "write");
+        assert_eq!(
+            serde_json::to_string(&Permission::GitWrite).unwrap(),
+            format!("\"{}\"", Permission::GitWrite)
+        );
+    }
+
+    #[test]
+    fn test_approval_request_ids_are_unique() {
+        let a = ApprovalRequest::new("bash", Permission::Execute, serde_json::json!({}));
+        let b = ApprovalRequest::new("bash", Permission::Execute, serde_json::json!({}));
+        assert_ne!(a.id, b.id);
+        assert_eq!(a.tool_name, "bash");
+    }
+
+    #[test]
+    fn test_approval_response_message_is_optional() {
+        let plain: ApprovalResponse =
+            serde_json::from_str(r#"{"id": "1", "decision": "deny"}"#).unwrap();
+        assert_eq!(plain.message, None);
+        let denied = ApprovalResponse::deny_with_message("1", "use the builder instead");
+        assert_eq!(denied.decision, ApprovalDecision::Deny);
+        let json = serde_json::to_value(&denied).unwrap();
+        assert_eq!(json["message"], "use the builder instead");
+    }
+
+    #[test]
+    fn test_approval_decision_serde() {
+        let d: ApprovalDecision = serde_json::from_str("\"allow_always\"").unwrap();
+        assert_eq!(d, ApprovalDecision::AllowAlways);
+    }
+}
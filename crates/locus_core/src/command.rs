@@ -0,0 +1,23 @@
+//! Shared parsing for the built-in `:` chat commands.
+
+/// What follows `command` in `input`, when `input` starts with it as a word.
+pub(crate) fn command_args<'a>(input: &'a str, command: &str) -> Option<&'a str> {
+    let rest = input.strip_prefix(command)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_args() {
+        assert_eq!(command_args(":pin", ":pin"), Some(""));
+        assert_eq!(command_args(":pin  a.rs ", ":pin"), Some("a.rs"));
+        assert_eq!(command_args(":pinned", ":pin"), None);
+        assert_eq!(command_args("pin a.rs", ":pin"), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! Edit conflicts: the user changed a file the agent is about to edit.
+//!
+//! The ToolBus remembers each file as the agent last read or wrote it (the base).
+//! When an edit targets a file whose content no longer matches its base, the
+//! ToolBus builds a [ConflictRequest] holding the base, the user's version and
+//! the agent's intended version, plus a three-way [merge3] of both changes. The
+//! runtime forwards it to the TUI as [crate::SessionEvent::ConflictDetected];
+//! the TUI answers with a [ConflictResponse] carrying the same id.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::file_change::matching_lines;
+
+/// Marker lines around the two sides of a conflicting hunk in [merge3] output.
+pub const MARKER_USER: &str = "<<<<<<< user";
+pub const MARKER_SPLIT: &str = "=======";
+pub const MARKER_AGENT: &str = ">>>>>>> agent";
+
+/// An edit waiting for the user to decide how it meets their changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConflictRequest {
+    pub id: String,
+    pub tool_name: String,
+    /// Path relative to the repo root.
+    pub path: String,
+    /// The file when the agent last read or wrote it.
+    pub base: String,
+    /// The file on disk now, with the user's changes.
+    pub user: String,
+    /// What the agent's edit makes of `base`.
+    pub agent: String,
+    /// Both changes applied to `base`; what [ConflictDecision::Rebase] writes.
+    pub merged: String,
+    /// Hunks both sides changed differently, left in `merged` between markers.
+    pub conflicts: usize,
+}
+
+impl ConflictRequest {
+    pub fn new(
+        tool_name: impl Into<String>,
+        path: impl Into<String>,
+        base: impl Into<String>,
+        user: impl Into<String>,
+        agent: impl Into<String>,
+    ) -> Self {
+        let (base, user, agent) = (base.into(), user.into(), agent.into());
+        let merge = merge3(&base, &user, &agent);
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool_name: tool_name.into(),
+            path: path.into(),
+            base,
+            user,
+            agent,
+            merged: merge.content,
+            conflicts: merge.conflicts,
+        }
+    }
+}
+
+/// The user's answer to a [ConflictRequest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictDecision {
+    /// Write the merge of both changes (with markers where they clash).
+    Rebase,
+    /// Write the agent's version, dropping the user's changes.
+    Overwrite,
+    /// Leave the file as the user has it; the model gets an error result.
+    Abort,
+}
+
+/// Decision for the conflict with `id`, sent from the TUI back to the runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictResponse {
+    pub id: String,
+    pub decision: ConflictDecision,
+}
+
+impl ConflictResponse {
+    pub fn new(id: impl Into<String>, decision: ConflictDecision) -> Self {
+        Self {
+            id: id.into(),
+            decision,
+        }
+    }
+}
+
+/// Result of [merge3].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    pub content: String,
+    /// Number of hunks written between conflict markers.
+    pub conflicts: usize,
+}
+
+/// Line-based three-way merge of the `user` and `agent` changes to `base`.
+///
+/// Hunks only one side changed take that side; hunks both changed the same way
+/// are taken once; hunks changed differently keep both, user first, between
+/// [MARKER_USER], [MARKER_SPLIT] and [MARKER_AGENT] lines.
+pub fn merge3(base: &str, user: &str, agent: &str) -> Merge {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let user_lines: Vec<&str> = user.split_inclusive('\n').collect();
+    let agent_lines: Vec<&str> = agent.split_inclusive('\n').collect();
+    let to_user = line_map(&base_lines, &user_lines);
+    let to_agent = line_map(&base_lines, &agent_lines);
+
+    let mut content = String::new();
+    let mut conflicts = 0;
+    let (mut b, mut u, mut a) = (0, 0, 0);
+    loop {
+        // The next base line both sides kept ends the current hunk.
+        let sync = (b..base_lines.len()).find_map(|i| Some((i, to_user[i]?, to_agent[i]?)));
+        let (b_end, u_end, a_end) =
+            sync.unwrap_or((base_lines.len(), user_lines.len(), agent_lines.len()));
+        let old = &base_lines[b..b_end];
+        let theirs = &user_lines[u..u_end];
+        let ours = &agent_lines[a..a_end];
+        if theirs == old || theirs == ours {
+            push_lines(&mut content, ours);
+        } else if ours == old {
+            push_lines(&mut content, theirs);
+        } else {
+            conflicts += 1;
+            push_block(&mut content, MARKER_USER, theirs);
+            push_block(&mut content, MARKER_SPLIT, ours);
+            content.push_str(MARKER_AGENT);
+            content.push('\n');
+        }
+        match sync {
+            Some((i, u_next, a_next)) => {
+                content.push_str(base_lines[i]);
+                b = i + 1;
+                u = u_next + 1;
+                a = a_next + 1;
+            }
+            None => break,
+        }
+    }
+    Merge { content, conflicts }
+}
+
+/// For each `base` line, the `other` line it matches in a line diff.
+fn line_map(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut map = vec![None; base.len()];
+    for (b, o) in matching_lines(base, other) {
+        map[b] = Some(o);
+    }
+    map
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+    }
+}
+
+/// `marker` then `lines`, ending on a line break so the next marker starts a line.
+fn push_block(out: &mut String, marker: &str, lines: &[&str]) {
+    out.push_str(marker);
+    out.push('\n');
+    push_lines(out, lines);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str =
+        "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+
+    #[test]
+    fn test_merge3_combines_separate_changes() {
+        let user = BASE.replace("let a = 1;", "let a = 10;");
+        let agent = BASE.replace("println!(\"{}\", a + b);", "println!(\"sum: {}\", a + b);");
+        let merge = merge3(BASE, &user, &agent);
+        assert_eq!(merge.conflicts, 0);
+        assert_eq!(
+            merge.content,
+            "fn main() {\n    let a = 10;\n    let b = 2;\n    println!(\"sum: {}\", a + b);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_merge3_takes_identical_changes_once() {
+        let both = BASE.replace("let b = 2;", "let b = 3;");
+        let merge = merge3(BASE, &both, &both);
+        assert_eq!(merge.conflicts, 0);
+        assert_eq!(merge.content, both);
+    }
+
+    #[test]
+    fn test_merge3_marks_clashing_changes() {
+        let user = BASE.replace("let b = 2;", "let b = 20;");
+        let agent = BASE.replace("let b = 2;", "let b = 200;");
+        let merge = merge3(BASE, &user, &agent);
+        assert_eq!(merge.conflicts, 1);
+        assert_eq!(
+            merge.content,
+            "fn main() {\n    let a = 1;\n<<<<<<< user\n    let b = 20;\n=======\n    let b = 200;\n>>>>>>> agent\n    println!(\"{}\", a + b);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_merge3_handles_appends_without_trailing_newline() {
+        let merge = merge3("a\nb", "a\nb\nuser", "a\nb\nagent");
+        assert_eq!(merge.conflicts, 1);
+        assert_eq!(
+            merge.content,
+            "a\n<<<<<<< user\nb\nuser\n=======\nb\nagent\n>>>>>>> agent\n"
+        );
+        assert_eq!(merge3("", "", "new file\n").content, "new file\n");
+    }
+
+    #[test]
+    fn test_conflict_request_carries_merge() {
+        let request = ConflictRequest::new("edit_file", "src/main.rs", "a\n", "b\n", "a\n");
+        assert_eq!(request.merged, "b\n");
+        assert_eq!(request.conflicts, 0);
+        let decision: ConflictDecision = serde_json::from_str("\"overwrite\"").unwrap();
+        assert_eq!(decision, ConflictDecision::Overwrite);
+    }
+}
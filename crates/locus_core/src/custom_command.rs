@@ -0,0 +1,186 @@
+//! Custom slash commands: markdown prompt templates under `.locus/commands/`.
+//!
+//! `.locus/commands/review.md` is run by typing `/review src/lib.rs`; the
+//! file's text becomes the prompt, with `$ARGUMENTS` replaced by what follows
+//! the name. `@path` mentions in the template or the arguments attach those
+//! files as in any prompt. An optional front matter block sets the one-line
+//! description shown in completions:
+//!
+//! ```text
+//! ---
+//! description: Review a file for bugs
+//! ---
+//! Review $ARGUMENTS for bugs and missing tests.
+//! ```
+
+use std::path::Path;
+
+/// Custom commands, relative to the repo root.
+pub const COMMANDS_DIR: &str = ".locus/commands";
+
+/// Placeholder for the command's arguments in a template.
+pub const ARGUMENTS_PLACEHOLDER: &str = "$ARGUMENTS";
+
+/// Longest description taken from a template's first line.
+const MAX_DESCRIPTION_CHARS: usize = 80;
+
+/// One `.locus/commands/<name>.md` template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomCommand {
+    /// Typed as `/<name>`.
+    pub name: String,
+    /// From the front matter, or the template's first line.
+    pub description: String,
+    pub template: String,
+}
+
+impl CustomCommand {
+    /// The prompt for this command with `args`. A template without
+    /// `$ARGUMENTS` gets non-empty arguments appended on their own line.
+    pub fn expand(&self, args: &str) -> String {
+        let args = args.trim();
+        if self.template.contains(ARGUMENTS_PLACEHOLDER) {
+            return self.template.replace(ARGUMENTS_PLACEHOLDER, args);
+        }
+        if args.is_empty() {
+            self.template.clone()
+        } else {
+            format!("{}\n\n{}", self.template, args)
+        }
+    }
+}
+
+/// The commands in `repo_root`'s [COMMANDS_DIR], by name. Unreadable files
+/// and names that cannot be typed as one word are skipped.
+pub fn load_custom_commands(repo_root: &Path) -> Vec<CustomCommand> {
+    let Ok(entries) = std::fs::read_dir(repo_root.join(COMMANDS_DIR)) else {
+        return Vec::new();
+    };
+    let mut commands: Vec<CustomCommand> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "md") {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+            let text = std::fs::read_to_string(&path).ok()?;
+            let (description, template) = split_front_matter(&text);
+            Some(CustomCommand {
+                description: description.unwrap_or_else(|| first_line(template)),
+                template: template.trim().to_string(),
+                name,
+            })
+        })
+        .collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+/// The command name and arguments in `input`, when it starts with `/name`.
+pub fn parse_slash_command(input: &str) -> Option<(&str, &str)> {
+    let rest = input.trim_start().strip_prefix('/')?;
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some((name, args.trim()))
+}
+
+/// `input` expanded when it runs one of `commands`, with the command's name.
+pub fn expand_slash_command(commands: &[CustomCommand], input: &str) -> Option<(String, String)> {
+    let (name, args) = parse_slash_command(input)?;
+    let command = commands.iter().find(|c| c.name == name)?;
+    Some((command.name.clone(), command.expand(args)))
+}
+
+/// The `description:` from a leading `---` block, and the text after it.
+fn split_front_matter(text: &str) -> (Option<String>, &str) {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return (None, text);
+    };
+    let Some((front, body)) = rest.split_once("\n---") else {
+        return (None, text);
+    };
+    let description = front.lines().find_map(|line| {
+        let value = line.strip_prefix("description:")?.trim();
+        Some(value.trim_matches('"').to_string())
+    });
+    (description, body.strip_prefix('\n').unwrap_or(body))
+}
+
+/// The template's first non-empty line, without heading marks, shortened.
+fn first_line(template: &str) -> String {
+    let line = template
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_DESCRIPTION_CHARS {
+        let cut: String = line.chars().take(MAX_DESCRIPTION_CHARS).collect();
+        format!("{}…", cut)
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn loads_and_expands_commands() {
+        let dir = TempDir::new().unwrap();
+        let commands_dir = dir.path().join(COMMANDS_DIR);
+        std::fs::create_dir_all(&commands_dir).unwrap();
+        std::fs::write(
+            commands_dir.join("review.md"),
+            "---\ndescription: Review a file for bugs\n---\nReview @$ARGUMENTS for bugs.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            commands_dir.join("changelog.md"),
+            "# Update the changelog\n\nFrom git log.",
+        )
+        .unwrap();
+        std::fs::write(commands_dir.join("notes.txt"), "not a command").unwrap();
+
+        let commands = load_custom_commands(dir.path());
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["changelog", "review"]);
+        assert_eq!(commands[0].description, "Update the changelog");
+        assert_eq!(commands[1].description, "Review a file for bugs");
+
+        assert_eq!(
+            expand_slash_command(&commands, "/review src/lib.rs"),
+            Some((
+                "review".to_string(),
+                "Review @src/lib.rs for bugs.".to_string()
+            ))
+        );
+        assert_eq!(
+            expand_slash_command(&commands, "/changelog since v1.2")
+                .unwrap()
+                .1,
+            "# Update the changelog\n\nFrom git log.\n\nsince v1.2"
+        );
+        assert_eq!(expand_slash_command(&commands, "/deploy now"), None);
+        assert_eq!(expand_slash_command(&commands, "/etc/hosts is wrong"), None);
+        assert!(load_custom_commands(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn parses_slash_commands() {
+        assert_eq!(
+            parse_slash_command("/review  a b "),
+            Some(("review", "a b"))
+        );
+        assert_eq!(parse_slash_command("/review"), Some(("review", "")));
+        assert_eq!(parse_slash_command("review"), None);
+        assert_eq!(parse_slash_command("/"), None);
+    }
+}
@@ -0,0 +1,188 @@
+//! Files changed by agent turns (file_changes table), for `locus blame`.
+//!
+//! Each row keeps one file's content before and after one round of tool calls,
+//! with the session and turn that made the change. [`attribute_lines`] maps the
+//! lines of a file as it is now back to the change that introduced them.
+
+use anyhow::Result;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::open_db;
+
+/// Line diffs over this many cells (old × new lines, after trimming the common
+/// head and tail) match nothing in the middle instead of running the LCS.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// One file changed by an agent turn.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileChange {
+    pub session_id: String,
+    #[serde(default)]
+    pub session_slug: String,
+    /// Turn number within the session (1-based).
+    pub turn: u32,
+    /// Unix seconds when the change was recorded.
+    pub ts: i64,
+    /// Path relative to the repo root.
+    pub path: String,
+    /// The user message that started the turn (cut to 200 chars).
+    pub prompt: String,
+    /// Content before the change; None when the turn created the file.
+    pub before: Option<String>,
+    /// Content after the change; None when the turn deleted the file.
+    pub after: Option<String>,
+}
+
+/// Append a file change.
+pub fn record_file_change(repo_root: &Path, change: &FileChange) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    conn.execute(
+        "INSERT INTO file_changes (session_id, session_slug, turn, ts, path, prompt, before_content, after_content) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            change.session_id,
+            change.session_slug,
+            change.turn,
+            change.ts,
+            change.path,
+            change.prompt,
+            change.before,
+            change.after,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Recorded changes to `path` (relative to the repo root), oldest first.
+pub fn file_changes_for(repo_root: &Path, path: &str) -> Result<Vec<FileChange>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT session_id, session_slug, turn, ts, path, prompt, before_content, after_content FROM file_changes WHERE path = ?1 ORDER BY ts, id",
+    )?;
+    let rows = stmt.query_map(params![path], |row| {
+        Ok(FileChange {
+            session_id: row.get(0)?,
+            session_slug: row.get(1)?,
+            turn: row.get(2)?,
+            ts: row.get(3)?,
+            path: row.get(4)?,
+            prompt: row.get(5)?,
+            before: row.get(6)?,
+            after: row.get(7)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Changes a session's turns after `after_turn` made, oldest first.
+pub fn session_file_changes(
+    repo_root: &Path,
+    session_id: &str,
+    after_turn: u32,
+) -> Result<Vec<FileChange>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT session_id, session_slug, turn, ts, path, prompt, before_content, after_content FROM file_changes WHERE session_id = ?1 AND turn > ?2 ORDER BY ts, id",
+    )?;
+    let rows = stmt.query_map(params![session_id, after_turn], |row| {
+        Ok(FileChange {
+            session_id: row.get(0)?,
+            session_slug: row.get(1)?,
+            turn: row.get(2)?,
+            ts: row.get(3)?,
+            path: row.get(4)?,
+            prompt: row.get(5)?,
+            before: row.get(6)?,
+            after: row.get(7)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Forget the changes of a session's turns after `after_turn` (they were rewound).
+pub fn delete_session_file_changes(
+    repo_root: &Path,
+    session_id: &str,
+    after_turn: u32,
+) -> Result<usize> {
+    let conn = open_db(repo_root)?;
+    Ok(conn.execute(
+        "DELETE FROM file_changes WHERE session_id = ?1 AND turn > ?2",
+        params![session_id, after_turn],
+    )?)
+}
+
+/// For each line of `current`, the index in `changes` (oldest first) of the change
+/// that added it, or None for lines no recorded change added.
+///
+/// Changes are walked newest first. A line belongs to a change when it matches a
+/// line that change added (by line diff of its before and after content) and no
+/// newer change already claimed it. Lines edited by hand after the change no
+/// longer match and stay unattributed.
+pub fn attribute_lines(current: &str, changes: &[FileChange]) -> Vec<Option<usize>> {
+    let current: Vec<&str> = current.lines().collect();
+    let mut owners = vec![None; current.len()];
+    for (index, change) in changes.iter().enumerate().rev() {
+        let Some(after) = change.after.as_deref() else {
+            continue;
+        };
+        let after: Vec<&str> = after.lines().collect();
+        let before: Vec<&str> = change.before.as_deref().unwrap_or("").lines().collect();
+        let kept: HashSet<usize> = matching_lines(&before, &after)
+            .into_iter()
+            .map(|(_, a)| a)
+            .collect();
+        for (a, c) in matching_lines(&after, &current) {
+            if !kept.contains(&a) && owners[c].is_none() {
+                owners[c] = Some(index);
+            }
+        }
+    }
+    owners
+}
+
+/// Pairs of (old index, new index) for lines a longest-common-subsequence diff keeps.
+pub(crate) fn matching_lines(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n > 0 && m > 0 && n * m <= MAX_DIFF_CELLS {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..].
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                pairs.push((prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    pairs.extend((0..suffix).map(|k| (old.len() - suffix + k, new.len() - suffix + k)));
+    pairs
+}
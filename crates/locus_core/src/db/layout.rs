@@ -1,7 +1,7 @@
 //! `.locus/` directory layout (Crush-style).
 //!
 //! - `locus.db` + WAL: main project DB (edit history, config, task list).
-//! - `logs/`, `commands/`: subdirs for logs and command data.
+//! - `logs/`, `commands/`: subdirs for logs and custom slash commands.
 //! - LocusGraph uses a separate `.locus/locus_graph_cache.db`.
 //! - `env`: optional file synced from config table for `source .locus/env`.
 
@@ -14,7 +14,8 @@ pub const LOCUS_DB: &str = "locus.db";
 pub const ENV_FILE: &str = "env";
 /// Subdir for log files.
 pub const LOGS_DIR: &str = "logs";
-/// Subdir for command history / saved commands.
+/// Subdir for custom slash commands (`<name>.md` prompt templates, see
+/// [crate::custom_command]).
 pub const COMMANDS_DIR: &str = "commands";
 
 /// Ensures `locus_dir`, `locus_dir/logs`, `locus_dir/commands` exist; returns path to locus.db.
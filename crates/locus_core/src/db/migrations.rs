@@ -29,15 +29,126 @@ CREATE TABLE IF NOT EXISTS task_list (
     status TEXT NOT NULL,
     description TEXT,
     sort_order INTEGER NOT NULL,
+    criteria TEXT,
     PRIMARY KEY (plan_id, task_id)
 );
 CREATE INDEX IF NOT EXISTS idx_task_list_plan_order ON task_list(plan_id, sort_order);
 ";
 
+/// Session log: one row per finished agent turn (summary for reports).
+pub const SESSION_LOG: &str = "
+CREATE TABLE IF NOT EXISTS session_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    session_slug TEXT NOT NULL,
+    ts INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    files_modified TEXT,
+    tool_calls INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_session_log_ts ON session_log(ts);
+";
+
+/// File changes: one row per file changed by an agent turn (before/after content for blame).
+pub const FILE_CHANGES: &str = "
+CREATE TABLE IF NOT EXISTS file_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    session_slug TEXT NOT NULL,
+    turn INTEGER NOT NULL,
+    ts INTEGER NOT NULL,
+    path TEXT NOT NULL,
+    prompt TEXT NOT NULL,
+    before_content TEXT,
+    after_content TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_file_changes_path ON file_changes(path);
+";
+
+/// Tool usage: one row per finished ToolBus call (timings for `locus toolbus stats`).
+pub const TOOL_USAGE: &str = "
+CREATE TABLE IF NOT EXISTS tool_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tool TEXT NOT NULL,
+    session_id TEXT,
+    ts INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    success INTEGER NOT NULL,
+    bytes INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_tool_usage_ts ON tool_usage(ts);
+";
+
+/// Saved sessions: the full session as JSON, plus the columns it is listed by.
+pub const SESSIONS: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    slug TEXT NOT NULL,
+    title TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    turns INTEGER NOT NULL DEFAULT 0,
+    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sessions_updated ON sessions(updated_at);
+";
+
+/// Crash recovery: the state of each session's turn in progress.
+pub const RUN_CHECKPOINTS: &str = "
+CREATE TABLE IF NOT EXISTS run_checkpoints (
+    session_id TEXT PRIMARY KEY,
+    saved_at INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+";
+
+/// Prompt history: one row per prompt sent from the TUI (recalled with Up/Down, Ctrl+R).
+pub const PROMPT_HISTORY: &str = "
+CREATE TABLE IF NOT EXISTS prompt_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts INTEGER NOT NULL,
+    prompt TEXT NOT NULL
+);
+";
+
 /// Run all migrations on an open connection.
 pub fn run_all(conn: &rusqlite::Connection) -> anyhow::Result<()> {
     conn.execute_batch(EDIT_HISTORY)?;
     conn.execute_batch(CONFIG)?;
     conn.execute_batch(TASK_LIST)?;
+    conn.execute_batch(SESSION_LOG)?;
+    conn.execute_batch(FILE_CHANGES)?;
+    conn.execute_batch(TOOL_USAGE)?;
+    conn.execute_batch(SESSIONS)?;
+    conn.execute_batch(RUN_CHECKPOINTS)?;
+    conn.execute_batch(PROMPT_HISTORY)?;
+    add_column_if_missing(conn, "task_list", "criteria", "TEXT")?;
+    add_column_if_missing(conn, "task_list", "done_at", "INTEGER")?;
+    Ok(())
+}
+
+/// Add a column to a table created by an older schema (CREATE IF NOT EXISTS won't).
+fn add_column_if_missing(
+    conn: &rusqlite::Connection,
+    table: &str,
+    column: &str,
+    decl: &str,
+) -> anyhow::Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {};",
+            table, column, decl
+        ))?;
+    }
     Ok(())
 }
@@ -1,25 +1,48 @@
 //! Project SQLite DB under `.locus/` (Crush-style layout).
 //!
-//! - `locus.db` + WAL: main project DB (edit history, config, task list).
+//! - `locus.db` + WAL: main project DB (edit history, config, task list, session log,
+//!   file changes, tool usage, saved sessions, crash recovery checkpoints, prompt history).
 //! - `logs/`, `commands/`: directories for logs and command data.
 //! - LocusGraph uses a separate `.locus/locus_graph_cache.db`.
 //! - `env`: optional file synced from config table for `source .locus/env`.
 
 mod config;
 mod connection;
+pub(crate) mod file_change;
 mod layout;
 mod migrations;
+mod prompt_history;
+mod run_checkpoints;
+mod session_log;
+mod sessions;
 mod task_list;
+mod tool_usage;
 
 pub use config::{
     clear_config, delete_config, get_config, get_config_value, set_config, sync_env_file,
 };
 pub use connection::{open_db, open_db_at};
+pub use file_change::{
+    attribute_lines, delete_session_file_changes, file_changes_for, record_file_change,
+    session_file_changes, FileChange,
+};
 pub use layout::{
     ensure_locus_dir, ensure_locus_dir_at, COMMANDS_DIR, ENV_FILE, LOCUS_DB, LOGS_DIR,
 };
 pub use migrations::run_all as run_migrations;
-pub use task_list::{add, create, get, list, remove, reorder, update, TaskItem, TaskStatus};
+pub use prompt_history::{recent_prompts, record_prompt, MAX_PROMPT_HISTORY};
+pub use run_checkpoints::{
+    clear_run_checkpoint, load_run_checkpoint, save_run_checkpoint, RunCheckpoint,
+};
+pub use session_log::{find_similar_work, record_turn, turns_since, SessionLogEntry, SimilarWork};
+pub use sessions::{
+    delete_session, find_session, list_sessions, load_session, save_session, SessionRecord,
+};
+pub use task_list::{
+    add, create, done_since, ensure_criterion_ids, get, list, plans, remove, reorder, tasks,
+    update, AcceptanceCriterion, CompletedTask, PlanSummary, TaskItem, TaskStatus,
+};
+pub use tool_usage::{record_tool_call, tool_stats, ToolCallRecord, ToolStats};
 
 #[cfg(test)]
 mod tests {
@@ -35,4 +58,383 @@ mod tests {
         assert!(repo.join(".locus").join(LOGS_DIR).is_dir());
         assert!(repo.join(".locus").join(COMMANDS_DIR).is_dir());
     }
+
+    #[test]
+    fn migrations_add_criteria_column_to_old_task_list() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE task_list (plan_id TEXT NOT NULL, task_id TEXT NOT NULL, title TEXT NOT NULL, \
+             status TEXT NOT NULL, description TEXT, sort_order INTEGER NOT NULL, PRIMARY KEY (plan_id, task_id));",
+        )
+        .unwrap();
+        run_migrations(&conn).unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute(
+            "UPDATE task_list SET criteria = '[]' WHERE plan_id = 'p'",
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn session_log_and_done_tasks_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let turn = |ts, title: &str| SessionLogEntry {
+            session_id: "s1".into(),
+            session_slug: "fix-login".into(),
+            ts,
+            title: title.into(),
+            outcome: "Done".into(),
+            files_modified: vec!["src/login.rs".into()],
+            tool_calls: 3,
+        };
+        record_turn(repo, &turn(100, "old")).unwrap();
+        record_turn(repo, &turn(200, "new")).unwrap();
+        let turns = turns_since(repo, 150).unwrap();
+        assert_eq!(turns, vec![turn(200, "new")]);
+
+        let task = |title: &str| TaskItem {
+            id: None,
+            title: title.into(),
+            status: TaskStatus::Pending,
+            description: None,
+            criteria: Vec::new(),
+        };
+        create(repo, "p", vec![task("a"), task("b")]).unwrap();
+        assert!(done_since(repo, 0).unwrap().is_empty());
+        update(repo, "p", "t2", Some(TaskStatus::Done), None, None, None).unwrap();
+        let done = done_since(repo, 0).unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].plan_id, "p");
+        assert_eq!(done[0].task.title, "b");
+        let done_at = done[0].done_at;
+
+        // Marking done again keeps the first completion time; reopening clears it.
+        update(repo, "p", "t2", Some(TaskStatus::Done), None, None, None).unwrap();
+        assert_eq!(done_since(repo, 0).unwrap()[0].done_at, done_at);
+        update(
+            repo,
+            "p",
+            "t2",
+            Some(TaskStatus::InProgress),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(done_since(repo, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plans_count_tasks_by_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        assert!(plans(repo).unwrap().is_empty());
+        let task = |title: &str, status| TaskItem {
+            id: None,
+            title: title.into(),
+            status,
+            description: None,
+            criteria: Vec::new(),
+        };
+        create(
+            repo,
+            "issue-7",
+            vec![
+                task("a", TaskStatus::Done),
+                task("b", TaskStatus::InProgress),
+                task("c", TaskStatus::Pending),
+            ],
+        )
+        .unwrap();
+        create(repo, "default", vec![task("x", TaskStatus::Pending)]).unwrap();
+        let summary = |plan_id: &str, tasks, in_progress, done| PlanSummary {
+            plan_id: plan_id.into(),
+            tasks,
+            in_progress,
+            done,
+        };
+        assert_eq!(
+            plans(repo).unwrap(),
+            vec![summary("default", 1, 0, 0), summary("issue-7", 3, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn find_similar_work_matches_other_editing_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let turn = |session: &str, ts, title: &str, files: &[&str]| SessionLogEntry {
+            session_id: session.into(),
+            session_slug: format!("slug-{}", session),
+            ts,
+            title: title.into(),
+            outcome: "Done".into(),
+            files_modified: files.iter().map(|f| f.to_string()).collect(),
+            tool_calls: 1,
+        };
+        record_turn(
+            repo,
+            &turn(
+                "s1",
+                100,
+                "Add retry logic to the HTTP client",
+                &["src/http.rs"],
+            ),
+        )
+        .unwrap();
+        record_turn(
+            repo,
+            &turn(
+                "s1",
+                110,
+                "Also cover timeouts",
+                &["src/http.rs", "tests/http.rs"],
+            ),
+        )
+        .unwrap();
+        record_turn(
+            repo,
+            &turn("s2", 200, "Explain retry logic in the HTTP client", &[]),
+        )
+        .unwrap();
+        record_turn(
+            repo,
+            &turn("s3", 300, "add retry logic to http client", &[]),
+        )
+        .unwrap();
+
+        let work = find_similar_work(repo, "Please add retry logic to our HTTP client", "s3", 0.6)
+            .unwrap()
+            .unwrap();
+        assert_eq!(work.matched.session_id, "s1");
+        assert_eq!(work.matched.ts, 100);
+        assert_eq!(work.files_modified, vec!["src/http.rs", "tests/http.rs"]);
+        assert_eq!(work.turns.len(), 2);
+
+        assert!(
+            find_similar_work(repo, "add retry logic to http client", "s1", 0.6)
+                .unwrap()
+                .is_none()
+        );
+        assert!(find_similar_work(repo, "fix the build", "s3", 0.1)
+            .unwrap()
+            .is_none());
+        assert!(
+            find_similar_work(repo, "rename the database migration helpers", "s3", 0.6)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn file_changes_attribute_lines_to_the_turn_that_added_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let change = |turn, ts, before: Option<&str>, after: &str| FileChange {
+            session_id: "s1".into(),
+            session_slug: "add-retry".into(),
+            turn,
+            ts,
+            path: "src/http.rs".into(),
+            prompt: "add retry logic".into(),
+            before: before.map(String::from),
+            after: Some(after.into()),
+        };
+        record_file_change(repo, &change(2, 200, Some("a\nb\n"), "a\nretry\nb\n")).unwrap();
+        record_file_change(repo, &change(1, 100, None, "a\nb\n")).unwrap();
+        let changes = file_changes_for(repo, "src/http.rs").unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.turn).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(file_changes_for(repo, "src/other.rs").unwrap().is_empty());
+
+        // "b" was edited by hand after turn 1, and "c" was never recorded.
+        let owners = attribute_lines("a\nretry\nB\nc\n", &changes);
+        assert_eq!(owners, vec![Some(0), Some(1), None, None]);
+    }
+
+    #[test]
+    fn session_file_changes_select_and_forget_later_turns() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let change = |session: &str, turn, path: &str| FileChange {
+            session_id: session.into(),
+            session_slug: String::new(),
+            turn,
+            ts: turn as i64,
+            path: path.into(),
+            prompt: String::new(),
+            before: None,
+            after: Some("x".into()),
+        };
+        record_file_change(repo, &change("s1", 1, "a.rs")).unwrap();
+        record_file_change(repo, &change("s1", 2, "b.rs")).unwrap();
+        record_file_change(repo, &change("s1", 3, "a.rs")).unwrap();
+        record_file_change(repo, &change("s2", 3, "c.rs")).unwrap();
+
+        let later = session_file_changes(repo, "s1", 1).unwrap();
+        assert_eq!(later.iter().map(|c| c.turn).collect::<Vec<_>>(), vec![2, 3]);
+
+        assert_eq!(delete_session_file_changes(repo, "s1", 1).unwrap(), 2);
+        assert!(session_file_changes(repo, "s1", 1).unwrap().is_empty());
+        assert_eq!(session_file_changes(repo, "s1", 0).unwrap().len(), 1);
+        assert_eq!(session_file_changes(repo, "s2", 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn saved_sessions_list_find_load_and_delete() {
+        use crate::session::{Session, SessionConfig};
+        use crate::turn::{ContentBlock, Turn};
+
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let new_session = |message: &str| {
+            let mut session = Session::new(
+                repo.to_path_buf(),
+                SessionConfig::new("claude-sonnet", "anthropic"),
+            );
+            session.add_turn(Turn::user().with_block(ContentBlock::text(message)));
+            session.add_turn(Turn::assistant().with_block(ContentBlock::text("Done.")));
+            session.add_llm_usage(1200, 300);
+            session
+        };
+        let first = new_session("\nFix the flaky test\nin ci");
+        let second = new_session("Add a README");
+        save_session(repo, "fix-the-flaky-test", &first).unwrap();
+        save_session(repo, "", &second).unwrap();
+        // Saving again updates in place.
+        save_session(repo, "add-a-readme", &second).unwrap();
+
+        let sessions = list_sessions(repo).unwrap();
+        assert_eq!(sessions.len(), 2);
+        let record = sessions.iter().find(|s| s.id == first.id.as_str()).unwrap();
+        assert_eq!(record.title, "Fix the flaky test");
+        assert_eq!((record.turns, record.total_tokens()), (1, 1500));
+        assert_eq!(record.model, "claude-sonnet");
+
+        let by_slug = find_session(repo, "add-a-readme").unwrap().unwrap();
+        assert_eq!(by_slug.id, second.id.as_str());
+        let prefix = &first.id.as_str()[..8];
+        assert_eq!(
+            find_session(repo, prefix).unwrap().unwrap().label(),
+            "fix-the-flaky-test"
+        );
+        assert!(find_session(repo, "nope").unwrap().is_none());
+
+        let loaded = load_session(repo, first.id.as_str()).unwrap().unwrap();
+        assert_eq!(loaded.turns.len(), 2);
+        assert_eq!(loaded.total_prompt_tokens, 1200);
+
+        assert!(delete_session(repo, first.id.as_str()).unwrap());
+        assert!(!delete_session(repo, first.id.as_str()).unwrap());
+        assert!(load_session(repo, first.id.as_str()).unwrap().is_none());
+        assert_eq!(list_sessions(repo).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn run_checkpoints_keep_the_latest_turn_in_progress() {
+        use crate::session::{Session, SessionConfig};
+        use crate::tool_call::{ToolResultData, ToolUse};
+
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        assert!(load_run_checkpoint(repo, None).unwrap().is_none());
+
+        let session = || Session::new(repo.to_path_buf(), SessionConfig::new("m", "anthropic"));
+        let read = ToolUse::new("t1", "read", serde_json::json!({"path": "a.rs"}));
+        let bash = ToolUse::new("t2", "bash", serde_json::json!({"command": "make"}));
+        let older = RunCheckpoint {
+            session: session(),
+            slug: "older".into(),
+            pending_tools: vec![],
+            finished_tools: vec![],
+            saved_at: 100,
+        };
+        let mut latest = RunCheckpoint {
+            session: session(),
+            slug: "latest".into(),
+            pending_tools: vec![read.clone(), bash.clone()],
+            finished_tools: vec![],
+            saved_at: 200,
+        };
+        save_run_checkpoint(repo, &older).unwrap();
+        save_run_checkpoint(repo, &latest).unwrap();
+        // A later step replaces the session's checkpoint.
+        latest.pending_tools = vec![bash];
+        latest.finished_tools = vec![(read, ToolResultData::success(serde_json::json!("x"), 5))];
+        latest.saved_at = 201;
+        save_run_checkpoint(repo, &latest).unwrap();
+
+        let found = load_run_checkpoint(repo, None).unwrap().unwrap();
+        assert_eq!(found.slug, "latest");
+        assert_eq!(found.pending_tools.len(), 1);
+        assert_eq!(found.finished_tools[0].0.id, "t1");
+        let by_id = load_run_checkpoint(repo, Some(older.session.id.as_str()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_id.slug, "older");
+
+        clear_run_checkpoint(repo, latest.session.id.as_str()).unwrap();
+        assert_eq!(
+            load_run_checkpoint(repo, None).unwrap().unwrap().slug,
+            "older"
+        );
+    }
+
+    #[test]
+    fn prompt_history_skips_repeats_and_lists_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        assert!(recent_prompts(repo, 10).unwrap().is_empty());
+
+        record_prompt(repo, "fix the add test", 100).unwrap();
+        record_prompt(repo, "fix the add test", 101).unwrap();
+        record_prompt(repo, "run cargo test", 102).unwrap();
+        record_prompt(repo, "fix the add test", 103).unwrap();
+        assert_eq!(
+            recent_prompts(repo, 10).unwrap(),
+            vec!["fix the add test", "run cargo test", "fix the add test"]
+        );
+        assert_eq!(recent_prompts(repo, 1).unwrap(), vec!["fix the add test"]);
+    }
+
+    #[test]
+    fn tool_stats_rank_tools_by_total_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let call = |tool: &str, ts, duration_ms, success, bytes| ToolCallRecord {
+            tool: tool.into(),
+            session_id: Some("s1".into()),
+            ts,
+            duration_ms,
+            success,
+            bytes,
+        };
+        for ms in 1..=100 {
+            record_tool_call(repo, &call("read", 100, ms, true, 1000)).unwrap();
+        }
+        record_tool_call(repo, &call("bash", 100, 9000, true, 300)).unwrap();
+        record_tool_call(repo, &call("bash", 100, 4000, false, 0)).unwrap();
+        record_tool_call(repo, &call("grep", 10, 50_000, true, 10)).unwrap();
+
+        let stats = tool_stats(repo, 50).unwrap();
+        let tools: Vec<&str> = stats.iter().map(|s| s.tool.as_str()).collect();
+        assert_eq!(tools, vec!["bash", "read"]);
+
+        let bash = &stats[0];
+        assert_eq!((bash.calls, bash.failures, bash.failure_rate), (2, 1, 0.5));
+        assert_eq!(
+            (bash.p50_ms, bash.max_ms, bash.total_ms),
+            (4000, 9000, 13000)
+        );
+        assert_eq!(bash.avg_bytes, 300);
+
+        let read = &stats[1];
+        assert_eq!((read.p50_ms, read.p90_ms, read.p99_ms), (50, 90, 99));
+        assert_eq!((read.total_ms, read.failure_rate), (5050, 0.0));
+        assert!(tool_stats(repo, 200).unwrap().is_empty());
+    }
 }
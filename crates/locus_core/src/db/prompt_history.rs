@@ -0,0 +1,47 @@
+//! Prompt history (prompt_history table): what was typed into the TUI prompt,
+//! recalled with Up/Down and Ctrl+R across sessions. Only the latest
+//! [MAX_PROMPT_HISTORY] are kept.
+
+use anyhow::Result;
+use rusqlite::params;
+use std::path::Path;
+
+use super::open_db;
+
+/// Prompts kept; older ones are dropped as new ones come.
+pub const MAX_PROMPT_HISTORY: usize = 1000;
+
+/// Append `prompt` sent at `ts` (unix seconds), unless it repeats the last one.
+pub fn record_prompt(repo_root: &Path, prompt: &str, ts: i64) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    let last: Option<String> = conn
+        .query_row(
+            "SELECT prompt FROM prompt_history ORDER BY id DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+    if last.as_deref() == Some(prompt) {
+        return Ok(());
+    }
+    conn.execute(
+        "INSERT INTO prompt_history (ts, prompt) VALUES (?1, ?2)",
+        params![ts, prompt],
+    )?;
+    conn.execute(
+        "DELETE FROM prompt_history WHERE id <= (SELECT MAX(id) FROM prompt_history) - ?1",
+        params![MAX_PROMPT_HISTORY as i64],
+    )?;
+    Ok(())
+}
+
+/// The latest `limit` prompts, oldest first.
+pub fn recent_prompts(repo_root: &Path, limit: usize) -> Result<Vec<String>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare("SELECT prompt FROM prompt_history ORDER BY id DESC LIMIT ?1")?;
+    let mut prompts = stmt
+        .query_map(params![limit as i64], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    prompts.reverse();
+    Ok(prompts)
+}
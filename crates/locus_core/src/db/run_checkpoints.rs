@@ -0,0 +1,78 @@
+//! Crash recovery checkpoints (run_checkpoints table): the state of a turn in
+//! progress, saved by the runtime after each step and dropped when the run ends.
+//! A checkpoint left behind means the process died mid-turn; `locus resume`
+//! picks the turn up from it.
+
+use anyhow::Result;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::open_db;
+use crate::session::Session;
+use crate::tool_call::{ToolResultData, ToolUse};
+
+/// A turn in progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCheckpoint {
+    /// The session as of the last step: turns so far and token totals.
+    pub session: Session,
+    pub slug: String,
+    /// Calls of the last assistant turn that had not returned.
+    pub pending_tools: Vec<ToolUse>,
+    /// Calls of the last assistant turn that returned, with their results, in call order.
+    pub finished_tools: Vec<(ToolUse, ToolResultData)>,
+    /// Unix seconds.
+    pub saved_at: i64,
+}
+
+/// Insert or replace the checkpoint of `checkpoint.session`.
+pub fn save_run_checkpoint(repo_root: &Path, checkpoint: &RunCheckpoint) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    conn.execute(
+        "INSERT INTO run_checkpoints (session_id, saved_at, data) VALUES (?1, ?2, ?3)
+         ON CONFLICT(session_id) DO UPDATE SET saved_at = ?2, data = ?3",
+        params![
+            checkpoint.session.id.as_str(),
+            checkpoint.saved_at,
+            serde_json::to_string(checkpoint)?,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Drop the checkpoint of a session whose run ended.
+pub fn clear_run_checkpoint(repo_root: &Path, session_id: &str) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    conn.execute(
+        "DELETE FROM run_checkpoints WHERE session_id = ?1",
+        params![session_id],
+    )?;
+    Ok(())
+}
+
+/// The checkpoint of `session_id`, or the most recent one when None.
+pub fn load_run_checkpoint(
+    repo_root: &Path,
+    session_id: Option<&str>,
+) -> Result<Option<RunCheckpoint>> {
+    let conn = open_db(repo_root)?;
+    let data: Option<String> = match session_id {
+        Some(id) => conn
+            .query_row(
+                "SELECT data FROM run_checkpoints WHERE session_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?,
+        None => conn
+            .query_row(
+                "SELECT data FROM run_checkpoints ORDER BY saved_at DESC, rowid DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?,
+    };
+    data.map(|data| serde_json::from_str(&data).map_err(Into::into))
+        .transpose()
+}
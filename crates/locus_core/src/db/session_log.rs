@@ -0,0 +1,162 @@
+//! Turn summaries in the project DB (session_log table), kept next to the
+//! LocusGraph copy so reports like `locus journal` work offline.
+
+use anyhow::Result;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+use super::open_db;
+
+/// One finished turn of an agent session.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionLogEntry {
+    pub session_id: String,
+    /// Session slug (kebab-case from the first message); empty for unnamed sessions.
+    #[serde(default)]
+    pub session_slug: String,
+    /// Unix seconds when the turn ended.
+    pub ts: i64,
+    pub title: String,
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files_modified: Vec<String>,
+    #[serde(default)]
+    pub tool_calls: u32,
+}
+
+/// Append a turn summary.
+pub fn record_turn(repo_root: &Path, entry: &SessionLogEntry) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    conn.execute(
+        "INSERT INTO session_log (session_id, session_slug, ts, title, outcome, files_modified, tool_calls) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            entry.session_id,
+            entry.session_slug,
+            entry.ts,
+            entry.title,
+            entry.outcome,
+            serde_json::to_string(&entry.files_modified)?,
+            entry.tool_calls,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Turns that ended at or after `since` (unix seconds), oldest first.
+pub fn turns_since(repo_root: &Path, since: i64) -> Result<Vec<SessionLogEntry>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT session_id, session_slug, ts, title, outcome, files_modified, tool_calls FROM session_log WHERE ts >= ?1 ORDER BY ts, id",
+    )?;
+    let rows = stmt.query_map(params![since], |row| {
+        Ok(SessionLogEntry {
+            session_id: row.get(0)?,
+            session_slug: row.get(1)?,
+            ts: row.get(2)?,
+            title: row.get(3)?,
+            outcome: row.get(4)?,
+            files_modified: row
+                .get::<_, Option<String>>(5)?
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            tool_calls: row.get(6)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Requests with fewer significant words than this are too vague to compare.
+const MIN_SIGNIFICANT_WORDS: usize = 3;
+
+/// Filler words that say nothing about what a request is about.
+const STOP_WORDS: &[&str] = &[
+    "and", "are", "can", "could", "for", "from", "into", "its", "our", "please", "should", "that",
+    "the", "then", "this", "was", "when", "with", "would", "you",
+];
+
+/// A past session that worked on a request close to a new one.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimilarWork {
+    /// Word overlap between the two requests (0.0–1.0).
+    pub score: f64,
+    /// The past turn whose request matched.
+    pub matched: SessionLogEntry,
+    /// Files the past session changed, in first-changed order.
+    pub files_modified: Vec<String>,
+    /// Every turn of the past session, oldest first.
+    pub turns: Vec<SessionLogEntry>,
+}
+
+/// Find the past session (other than `exclude_session`) whose request best
+/// matches `request`. Only sessions that changed files count, and the word
+/// overlap must reach `min_score`; ties go to the most recent turn.
+pub fn find_similar_work(
+    repo_root: &Path,
+    request: &str,
+    exclude_session: &str,
+    min_score: f64,
+) -> Result<Option<SimilarWork>> {
+    let words = significant_words(request);
+    if words.len() < MIN_SIGNIFICANT_WORDS {
+        return Ok(None);
+    }
+    let turns = turns_since(repo_root, 0)?;
+    let editing_sessions: HashSet<&str> = turns
+        .iter()
+        .filter(|t| !t.files_modified.is_empty())
+        .map(|t| t.session_id.as_str())
+        .collect();
+
+    let mut best: Option<(f64, &SessionLogEntry)> = None;
+    for turn in &turns {
+        if turn.session_id == exclude_session
+            || !editing_sessions.contains(turn.session_id.as_str())
+        {
+            continue;
+        }
+        let score = word_overlap(&words, &significant_words(&turn.title));
+        if score >= min_score && best.is_none_or(|(best_score, _)| score >= best_score) {
+            best = Some((score, turn));
+        }
+    }
+
+    Ok(best.map(|(score, matched)| {
+        let session: Vec<SessionLogEntry> = turns
+            .iter()
+            .filter(|t| t.session_id == matched.session_id)
+            .cloned()
+            .collect();
+        let mut files_modified: Vec<String> = Vec::new();
+        for file in session.iter().flat_map(|t| &t.files_modified) {
+            if !files_modified.contains(file) {
+                files_modified.push(file.clone());
+            }
+        }
+        SimilarWork {
+            score,
+            matched: matched.clone(),
+            files_modified,
+            turns: session,
+        }
+    }))
+}
+
+/// Lowercase words of three or more characters, minus stop words.
+fn significant_words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Jaccard similarity of two word sets.
+fn word_overlap(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
@@ -0,0 +1,172 @@
+//! Saved agent sessions (sessions table): the whole [Session] as JSON plus the
+//! columns `locus sessions` and the TUI session switcher list by. The runtime
+//! saves the session after every turn, so an interrupted session can be resumed.
+
+use anyhow::{anyhow, Result};
+use rusqlite::{params, OptionalExtension, Row};
+use serde::Serialize;
+use std::path::Path;
+
+use super::open_db;
+use crate::session::Session;
+use crate::turn::{ContentBlock, Role};
+
+/// Longest title kept for a session.
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Listing columns of a saved session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionRecord {
+    pub id: String,
+    /// Kebab-case slug from the first message; empty before the first turn.
+    pub slug: String,
+    /// First line of the first user message.
+    pub title: String,
+    /// Unix seconds.
+    pub created_at: i64,
+    /// Unix seconds of the last save.
+    pub updated_at: i64,
+    pub provider: String,
+    pub model: String,
+    /// User messages sent.
+    pub turns: u32,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl SessionRecord {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+
+    /// `slug` (or the id when the session has none) for display.
+    pub fn label(&self) -> &str {
+        if self.slug.is_empty() {
+            &self.id
+        } else {
+            &self.slug
+        }
+    }
+}
+
+const RECORD_COLUMNS: &str = "id, slug, title, created_at, updated_at, provider, model, turns, prompt_tokens, completion_tokens";
+
+fn record_from_row(row: &Row<'_>) -> rusqlite::Result<SessionRecord> {
+    Ok(SessionRecord {
+        id: row.get(0)?,
+        slug: row.get(1)?,
+        title: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+        provider: row.get(5)?,
+        model: row.get(6)?,
+        turns: row.get(7)?,
+        prompt_tokens: row.get::<_, i64>(8)? as u64,
+        completion_tokens: row.get::<_, i64>(9)? as u64,
+    })
+}
+
+/// Insert or replace the saved copy of `session`.
+pub fn save_session(repo_root: &Path, slug: &str, session: &Session) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    let user_turns: Vec<_> = session
+        .turns
+        .iter()
+        .filter(|t| t.role == Role::User)
+        .collect();
+    let title = user_turns
+        .first()
+        .and_then(|turn| {
+            turn.blocks.iter().find_map(|block| match block {
+                ContentBlock::Text { text } => text.lines().find(|l| !l.trim().is_empty()),
+                _ => None,
+            })
+        })
+        .map(|line| truncate(line.trim(), MAX_TITLE_CHARS))
+        .unwrap_or_default();
+    conn.execute(
+        "INSERT INTO sessions (id, slug, title, created_at, updated_at, provider, model, turns, prompt_tokens, completion_tokens, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+         ON CONFLICT(id) DO UPDATE SET slug = ?2, title = ?3, updated_at = ?5, provider = ?6, model = ?7,
+             turns = ?8, prompt_tokens = ?9, completion_tokens = ?10, data = ?11",
+        params![
+            session.id.as_str(),
+            slug,
+            title,
+            session.created_at.timestamp(),
+            chrono::Utc::now().timestamp(),
+            session.config.provider,
+            session.config.model,
+            user_turns.len() as u32,
+            session.total_prompt_tokens as i64,
+            session.total_completion_tokens as i64,
+            serde_json::to_string(session)?,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Saved sessions, most recently updated first.
+pub fn list_sessions(repo_root: &Path) -> Result<Vec<SessionRecord>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM sessions ORDER BY updated_at DESC, created_at DESC",
+        RECORD_COLUMNS
+    ))?;
+    let rows = stmt.query_map([], record_from_row)?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// The session named by `query`: its id, a unique id prefix, or its slug (the
+/// latest one when several sessions share it). Errors when a prefix is ambiguous.
+pub fn find_session(repo_root: &Path, query: &str) -> Result<Option<SessionRecord>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(None);
+    }
+    let sessions = list_sessions(repo_root)?;
+    if let Some(record) = sessions.iter().find(|s| s.id == query || s.slug == query) {
+        return Ok(Some(record.clone()));
+    }
+    let mut matches = sessions.into_iter().filter(|s| s.id.starts_with(query));
+    let first = matches.next();
+    if matches.next().is_some() {
+        return Err(anyhow!(
+            "Session id prefix '{}' is ambiguous; use more characters",
+            query
+        ));
+    }
+    Ok(first)
+}
+
+/// The saved session with `id`.
+pub fn load_session(repo_root: &Path, id: &str) -> Result<Option<Session>> {
+    let conn = open_db(repo_root)?;
+    let data: Option<String> = conn
+        .query_row(
+            "SELECT data FROM sessions WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    data.map(|data| serde_json::from_str(&data).map_err(Into::into))
+        .transpose()
+}
+
+/// Delete a saved session and its session log turns. Returns whether it existed.
+pub fn delete_session(repo_root: &Path, id: &str) -> Result<bool> {
+    let mut conn = open_db(repo_root)?;
+    let tx = conn.transaction()?;
+    let deleted = tx.execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+    tx.execute("DELETE FROM session_log WHERE session_id = ?1", params![id])?;
+    tx.commit()?;
+    Ok(deleted > 0)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars - 3).collect();
+    format!("{}...", cut)
+}
@@ -18,6 +18,19 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+/// One acceptance criterion on a task, checked off with evidence
+/// (tool call ids, diffs, test runs) that shows it holds.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AcceptanceCriterion {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub met: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<String>,
+}
+
 /// A single task in a plan.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TaskItem {
@@ -28,10 +41,50 @@ pub struct TaskItem {
     pub status: TaskStatus,
     #[serde(default)]
     pub description: Option<String>,
+    /// Acceptance checklist (stored as JSON in the `criteria` column).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criteria: Vec<AcceptanceCriterion>,
+}
+
+impl TaskItem {
+    /// Criteria not yet checked off.
+    pub fn unmet_criteria(&self) -> impl Iterator<Item = &AcceptanceCriterion> {
+        self.criteria.iter().filter(|c| !c.met)
+    }
+}
+
+/// A task marked done, with its plan and when it was finished.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletedTask {
+    pub plan_id: String,
+    pub task: TaskItem,
+    /// Unix seconds when the task was marked done.
+    pub done_at: i64,
+}
+
+/// Task counts for one plan, for listing plans.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanSummary {
+    pub plan_id: String,
+    pub tasks: usize,
+    pub in_progress: usize,
+    pub done: usize,
 }
 
 const NEXT_ID_KEY_PREFIX: &str = "task_list:next_id:";
 
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// `done_at` for a task inserted with `status`.
+fn done_at_for(status: TaskStatus) -> Option<i64> {
+    (status == TaskStatus::Done).then(now_secs)
+}
+
 fn status_to_str(s: TaskStatus) -> &'static str {
     match s {
         TaskStatus::Pending => "pending",
@@ -70,10 +123,50 @@ fn ensure_next_ids(
             let id = next_id(conn, plan_id)?;
             item.id = Some(format!("t{}", id));
         }
+        ensure_criterion_ids(&mut item.criteria);
     }
     Ok(())
 }
 
+/// Give criteria without an id a per-task id (`c1`, `c2`, ...) that is not taken yet.
+pub fn ensure_criterion_ids(criteria: &mut [AcceptanceCriterion]) {
+    let mut n = 1;
+    for i in 0..criteria.len() {
+        if criteria[i].id.as_ref().is_none_or(|s| s.is_empty()) {
+            while criteria
+                .iter()
+                .any(|c| c.id.as_deref() == Some(&format!("c{}", n)))
+            {
+                n += 1;
+            }
+            criteria[i].id = Some(format!("c{}", n));
+        }
+    }
+}
+
+fn criteria_to_db(criteria: &[AcceptanceCriterion]) -> Option<String> {
+    if criteria.is_empty() {
+        None
+    } else {
+        serde_json::to_string(criteria).ok()
+    }
+}
+
+fn criteria_from_db(raw: Option<String>) -> Vec<AcceptanceCriterion> {
+    raw.and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn task_from_row(row: &rusqlite::Row) -> rusqlite::Result<TaskItem> {
+    Ok(TaskItem {
+        id: Some(row.get::<_, String>(0)?),
+        title: row.get(1)?,
+        status: str_to_status(&row.get::<_, String>(2)?),
+        description: row.get(3)?,
+        criteria: criteria_from_db(row.get(4)?),
+    })
+}
+
 /// Create a plan with the given tasks; returns the created tasks and JSON response.
 pub fn create(
     repo_root: &Path,
@@ -85,7 +178,7 @@ pub fn create(
     let mut sort_order: i64 = 0;
     for item in &tasks {
         conn.execute(
-            "INSERT INTO task_list (plan_id, task_id, title, status, description, sort_order) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO task_list (plan_id, task_id, title, status, description, sort_order, criteria, done_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 plan_id,
                 item.id.as_deref().unwrap_or(""),
@@ -93,6 +186,8 @@ pub fn create(
                 status_to_str(item.status),
                 item.description,
                 sort_order,
+                criteria_to_db(&item.criteria),
+                done_at_for(item.status),
             ],
         )?;
         sort_order += 1;
@@ -101,21 +196,19 @@ pub fn create(
     Ok((tasks, out))
 }
 
-/// List all tasks for a plan.
-pub fn list(repo_root: &Path, plan_id: &str) -> Result<serde_json::Value> {
+/// All tasks for a plan, in order.
+pub fn tasks(repo_root: &Path, plan_id: &str) -> Result<Vec<TaskItem>> {
     let conn = open_db(repo_root)?;
     let mut stmt = conn.prepare(
-        "SELECT task_id, title, status, description, sort_order FROM task_list WHERE plan_id = ?1 ORDER BY sort_order",
+        "SELECT task_id, title, status, description, criteria FROM task_list WHERE plan_id = ?1 ORDER BY sort_order",
     )?;
-    let rows = stmt.query_map(params![plan_id], |row| {
-        Ok(TaskItem {
-            id: Some(row.get::<_, String>(0)?),
-            title: row.get(1)?,
-            status: str_to_status(&row.get::<_, String>(2)?),
-            description: row.get(3)?,
-        })
-    })?;
-    let tasks: Vec<TaskItem> = rows.collect::<Result<Vec<_>, _>>()?;
+    let rows = stmt.query_map(params![plan_id], task_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// List all tasks for a plan.
+pub fn list(repo_root: &Path, plan_id: &str) -> Result<serde_json::Value> {
+    let tasks = tasks(repo_root, plan_id)?;
     Ok(serde_json::json!({ "plan_id": plan_id, "tasks": tasks }))
 }
 
@@ -123,22 +216,17 @@ pub fn list(repo_root: &Path, plan_id: &str) -> Result<serde_json::Value> {
 pub fn get(repo_root: &Path, plan_id: &str, task_id: &str) -> Result<Option<TaskItem>> {
     let conn = open_db(repo_root)?;
     let mut stmt = conn.prepare(
-        "SELECT task_id, title, status, description FROM task_list WHERE plan_id = ?1 AND task_id = ?2",
+        "SELECT task_id, title, status, description, criteria FROM task_list WHERE plan_id = ?1 AND task_id = ?2",
     )?;
     let mut rows = stmt.query(params![plan_id, task_id])?;
     let row = match rows.next()? {
         Some(r) => r,
         None => return Ok(None),
     };
-    Ok(Some(TaskItem {
-        id: Some(row.get::<_, String>(0)?),
-        title: row.get(1)?,
-        status: str_to_status(&row.get::<_, String>(2)?),
-        description: row.get(3)?,
-    }))
+    Ok(Some(task_from_row(row)?))
 }
 
-/// Update a task; returns the updated task if found.
+/// Update a task; `criteria` replaces the whole checklist. Returns the updated task if found.
 pub fn update(
     repo_root: &Path,
     plan_id: &str,
@@ -146,12 +234,14 @@ pub fn update(
     status: Option<TaskStatus>,
     title: Option<String>,
     description: Option<String>,
+    criteria: Option<Vec<AcceptanceCriterion>>,
 ) -> Result<Option<TaskItem>> {
     let conn = open_db(repo_root)?;
     if let Some(s) = status {
+        // Keep the first completion time when a done task is marked done again.
         conn.execute(
-            "UPDATE task_list SET status = ?1 WHERE plan_id = ?2 AND task_id = ?3",
-            params![status_to_str(s), plan_id, task_id],
+            "UPDATE task_list SET status = ?1, done_at = CASE WHEN ?1 = 'done' THEN COALESCE(done_at, ?4) ELSE NULL END WHERE plan_id = ?2 AND task_id = ?3",
+            params![status_to_str(s), plan_id, task_id, now_secs()],
         )?;
     }
     if let Some(ref t) = title {
@@ -166,6 +256,13 @@ pub fn update(
             params![d, plan_id, task_id],
         )?;
     }
+    if let Some(mut c) = criteria {
+        ensure_criterion_ids(&mut c);
+        conn.execute(
+            "UPDATE task_list SET criteria = ?1 WHERE plan_id = ?2 AND task_id = ?3",
+            params![criteria_to_db(&c), plan_id, task_id],
+        )?;
+    }
     get(repo_root, plan_id, task_id)
 }
 
@@ -184,7 +281,7 @@ pub fn add(
     )?;
     for item in &new_tasks {
         conn.execute(
-            "INSERT INTO task_list (plan_id, task_id, title, status, description, sort_order) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO task_list (plan_id, task_id, title, status, description, sort_order, criteria, done_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 plan_id,
                 item.id.as_deref().unwrap_or(""),
@@ -192,6 +289,8 @@ pub fn add(
                 status_to_str(item.status),
                 item.description,
                 sort_order,
+                criteria_to_db(&item.criteria),
+                done_at_for(item.status),
             ],
         )?;
         sort_order += 1;
@@ -199,6 +298,39 @@ pub fn add(
     list(repo_root, plan_id)
 }
 
+/// Every plan with its task counts, by plan id.
+pub fn plans(repo_root: &Path) -> Result<Vec<PlanSummary>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT plan_id, COUNT(*), SUM(status = 'in_progress'), SUM(status = 'done') FROM task_list GROUP BY plan_id ORDER BY plan_id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(PlanSummary {
+            plan_id: row.get(0)?,
+            tasks: row.get::<_, i64>(1)? as usize,
+            in_progress: row.get::<_, i64>(2)? as usize,
+            done: row.get::<_, i64>(3)? as usize,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Tasks in any plan marked done at or after `since` (unix seconds), oldest first.
+pub fn done_since(repo_root: &Path, since: i64) -> Result<Vec<CompletedTask>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT task_id, title, status, description, criteria, plan_id, done_at FROM task_list WHERE status = 'done' AND done_at >= ?1 ORDER BY done_at, plan_id, sort_order",
+    )?;
+    let rows = stmt.query_map(params![since], |row| {
+        Ok(CompletedTask {
+            task: task_from_row(row)?,
+            plan_id: row.get(5)?,
+            done_at: row.get(6)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
 /// Remove a task; returns list JSON on success, or Null if task not found.
 pub fn remove(repo_root: &Path, plan_id: &str, task_id: &str) -> Result<serde_json::Value> {
     let conn = open_db(repo_root)?;
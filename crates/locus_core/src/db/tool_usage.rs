@@ -0,0 +1,123 @@
+//! Per-call tool timings (tool_usage table), recorded by the ToolBus for
+//! `locus toolbus stats`.
+//!
+//! [`tool_stats`] summarizes them per tool: call count, failure rate, latency
+//! percentiles and total time, most total time first, so the tools that cost a
+//! session the most are at the top.
+
+use anyhow::Result;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::open_db;
+
+/// One finished `ToolBus` call.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ToolCallRecord {
+    pub tool: String,
+    /// Session that made the call; None for calls outside a session (`locus toolbus call`).
+    pub session_id: Option<String>,
+    /// Unix seconds when the call finished.
+    pub ts: i64,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Size of the JSON result returned (0 for failed calls).
+    pub bytes: u64,
+}
+
+/// Usage summary for one tool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolStats {
+    pub tool: String,
+    pub calls: u64,
+    pub failures: u64,
+    /// `failures / calls`, 0 to 1.
+    pub failure_rate: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    pub total_ms: u64,
+    /// Mean result size of successful calls.
+    pub avg_bytes: u64,
+}
+
+/// Append a call.
+pub fn record_tool_call(repo_root: &Path, record: &ToolCallRecord) -> Result<()> {
+    let conn = open_db(repo_root)?;
+    conn.execute(
+        "INSERT INTO tool_usage (tool, session_id, ts, duration_ms, success, bytes) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            record.tool,
+            record.session_id,
+            record.ts,
+            record.duration_ms as i64,
+            record.success,
+            record.bytes as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Per-tool summaries of calls that finished at or after `since` (unix seconds),
+/// most total time first.
+pub fn tool_stats(repo_root: &Path, since: i64) -> Result<Vec<ToolStats>> {
+    let conn = open_db(repo_root)?;
+    let mut stmt = conn.prepare(
+        "SELECT tool, duration_ms, success, bytes FROM tool_usage WHERE ts >= ?1 ORDER BY tool, duration_ms",
+    )?;
+    let rows = stmt.query_map(params![since], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, bool>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+
+    // Durations arrive sorted per tool.
+    let mut calls: BTreeMap<String, (Vec<u64>, u64, u64)> = BTreeMap::new();
+    for row in rows {
+        let (tool, duration_ms, success, bytes) = row?;
+        let (durations, failures, success_bytes) = calls.entry(tool).or_default();
+        durations.push(duration_ms.max(0) as u64);
+        if success {
+            *success_bytes += bytes.max(0) as u64;
+        } else {
+            *failures += 1;
+        }
+    }
+
+    let mut stats: Vec<ToolStats> = calls
+        .into_iter()
+        .map(|(tool, (durations, failures, success_bytes))| {
+            let count = durations.len() as u64;
+            let successes = count - failures;
+            ToolStats {
+                tool,
+                calls: count,
+                failures,
+                failure_rate: failures as f64 / count as f64,
+                p50_ms: percentile(&durations, 50),
+                p90_ms: percentile(&durations, 90),
+                p99_ms: percentile(&durations, 99),
+                max_ms: durations.last().copied().unwrap_or_default(),
+                total_ms: durations.iter().sum(),
+                avg_bytes: success_bytes.checked_div(successes).unwrap_or(0),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then(a.tool.cmp(&b.tool)));
+    Ok(stats)
+}
+
+/// Nearest-rank percentile of `sorted`.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
@@ -0,0 +1,325 @@
+//! Diff review: the files an agent turn changed, cut into hunks the user can
+//! approve or reject. Rejecting a hunk (`:reject-hunk`) puts back the lines it
+//! replaced and leaves the rest of the turn's changes in place.
+//!
+//! Diffs come from the file change log (see [crate::db::FileChange]): a file's
+//! content before the turn's first change to it against its content after the
+//! last one. Hunks carry [CONTEXT] unchanged lines around their changes, so a
+//! hunk can be found and reverted even after other hunks of the file were.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::db::file_change::matching_lines;
+use crate::db::FileChange;
+
+/// The chat command, typed as `:reject-hunk <turn> <hunk> <path>`.
+pub const REJECT_HUNK_COMMAND: &str = ":reject-hunk";
+
+/// Unchanged lines kept around each change; changes closer than twice this
+/// share a hunk.
+pub const CONTEXT: usize = 3;
+
+/// What a line of a hunk does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HunkLineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+/// One line of a hunk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HunkLine {
+    pub kind: HunkLineKind,
+    pub text: String,
+}
+
+/// A run of changed lines with the unchanged lines around them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hunk {
+    /// Line of the old content the hunk starts at (0-based).
+    pub old_start: usize,
+    /// Line of the new content the hunk starts at (0-based).
+    pub new_start: usize,
+    pub lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    /// The hunk's lines as they were: context and removed lines.
+    pub fn old_lines(&self) -> Vec<&str> {
+        self.side(HunkLineKind::Removed)
+    }
+
+    /// The hunk's lines as the turn left them: context and added lines.
+    pub fn new_lines(&self) -> Vec<&str> {
+        self.side(HunkLineKind::Added)
+    }
+
+    fn side(&self, changed: HunkLineKind) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == HunkLineKind::Context || line.kind == changed)
+            .map(|line| line.text.as_str())
+            .collect()
+    }
+}
+
+/// One file a turn changed, as hunks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Path relative to the repo root.
+    pub path: String,
+    /// The turn created the file.
+    pub created: bool,
+    /// The turn deleted the file.
+    pub deleted: bool,
+    pub hunks: Vec<Hunk>,
+}
+
+/// A hunk to reject: the `hunk`-th hunk of `path` in turn `turn`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkRef {
+    pub turn: u32,
+    pub hunk: usize,
+    pub path: String,
+}
+
+/// The hunk to reject when `input` is the reject command. Err holds the usage
+/// line when the arguments are missing or not numbers.
+pub fn parse_reject_hunk(input: &str) -> Option<Result<HunkRef, String>> {
+    let rest = input.trim().strip_prefix(REJECT_HUNK_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let usage = || format!("Usage: {} <turn> <hunk> <path>", REJECT_HUNK_COMMAND);
+    let mut parts = rest.trim().splitn(3, char::is_whitespace);
+    let turn = parts.next().and_then(|t| t.parse().ok());
+    let hunk = parts.next().and_then(|h| h.parse().ok());
+    let path = parts.next().map(str::trim).filter(|p| !p.is_empty());
+    Some(match (turn, hunk, path) {
+        (Some(turn), Some(hunk), Some(path)) => Ok(HunkRef {
+            turn,
+            hunk,
+            path: path.to_string(),
+        }),
+        _ => Err(usage()),
+    })
+}
+
+/// The files turn `turn` changed, by path, from `changes` (oldest first).
+/// Files the turn left as they were are skipped.
+pub fn turn_diffs(changes: &[FileChange], turn: u32) -> Vec<FileDiff> {
+    // path -> (content before the first change, content after the last)
+    let mut files: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for change in changes.iter().filter(|c| c.turn == turn) {
+        files
+            .entry(change.path.as_str())
+            .and_modify(|(_, after)| *after = change.after.as_deref())
+            .or_insert((change.before.as_deref(), change.after.as_deref()));
+    }
+    files
+        .into_iter()
+        .map(|(path, (before, after))| FileDiff {
+            path: path.to_string(),
+            created: before.is_none(),
+            deleted: after.is_none(),
+            hunks: hunks(before.unwrap_or(""), after.unwrap_or("")),
+        })
+        .filter(|file| !file.hunks.is_empty())
+        .collect()
+}
+
+/// Hunks turning `before` into `after`.
+pub fn hunks(before: &str, after: &str) -> Vec<Hunk> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // Every line of both sides in order, as (kind, old index, new index)
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    let pairs = matching_lines(&old, &new);
+    for (oi, nj) in pairs.into_iter().chain([(old.len(), new.len())]) {
+        ops.extend((i..oi).map(|o| (HunkLineKind::Removed, o, j)));
+        ops.extend((j..nj).map(|n| (HunkLineKind::Added, oi, n)));
+        if oi < old.len() && nj < new.len() {
+            ops.push((HunkLineKind::Context, oi, nj));
+        }
+        i = oi + 1;
+        j = nj + 1;
+    }
+
+    // Ranges of ops to show: each change with CONTEXT lines around it, merged
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (k, (kind, _, _)) in ops.iter().enumerate() {
+        if *kind == HunkLineKind::Context {
+            continue;
+        }
+        let start = k.saturating_sub(CONTEXT);
+        let end = (k + 1 + CONTEXT).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let (_, old_start, new_start) = ops[start];
+            Hunk {
+                old_start,
+                new_start,
+                lines: ops[start..end]
+                    .iter()
+                    .map(|&(kind, o, n)| HunkLine {
+                        kind,
+                        text: match kind {
+                            HunkLineKind::Removed => old[o],
+                            _ => new[n],
+                        }
+                        .to_string(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// `current` with `hunk` undone: its new lines, context included, replaced by
+/// its old ones. The match closest to where the hunk was is used. None when
+/// the new lines are no longer in `current`.
+pub fn revert_hunk(current: &str, hunk: &Hunk) -> Option<String> {
+    let lines: Vec<&str> = current.lines().collect();
+    let new = hunk.new_lines();
+    let start = if new.is_empty() {
+        // Nothing to match on: only a file that is still empty can take it
+        lines.is_empty().then_some(0)?
+    } else {
+        (0..=lines.len().saturating_sub(new.len()))
+            .filter(|&p| lines.len() >= new.len() && lines[p..p + new.len()] == new[..])
+            .min_by_key(|&p| p.abs_diff(hunk.new_start))?
+    };
+    let mut reverted: Vec<&str> = lines[..start].to_vec();
+    reverted.extend(hunk.old_lines());
+    reverted.extend(&lines[start + new.len()..]);
+    let mut out = reverted.join("\n");
+    if !out.is_empty() && (current.ends_with('\n') || current.is_empty()) {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(n: usize) -> String {
+        (1..=n).map(|i| format!("line {}\n", i)).collect()
+    }
+
+    #[test]
+    fn parses_the_reject_command() {
+        assert_eq!(
+            parse_reject_hunk(":reject-hunk 2 0 src/my file.rs"),
+            Some(Ok(HunkRef {
+                turn: 2,
+                hunk: 0,
+                path: "src/my file.rs".to_string(),
+            }))
+        );
+        assert!(matches!(parse_reject_hunk(":reject-hunk 2"), Some(Err(_))));
+        assert!(matches!(
+            parse_reject_hunk(":reject-hunk x 0 a.rs"),
+            Some(Err(_))
+        ));
+        assert_eq!(parse_reject_hunk(":reject-hunks 1 0 a.rs"), None);
+    }
+
+    #[test]
+    fn changes_far_apart_get_their_own_hunks() {
+        let before = numbered(20);
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+        let hunks = hunks(&before, &after);
+        assert_eq!(hunks.len(), 2);
+
+        assert_eq!(hunks[0].old_start, 0);
+        assert_eq!(
+            hunks[0].old_lines(),
+            ["line 1", "line 2", "line 3", "line 4", "line 5"]
+        );
+        assert_eq!(
+            hunks[0].new_lines(),
+            ["line 1", "line two", "line 3", "line 4", "line 5"]
+        );
+        assert_eq!(hunks[1].old_start, 14);
+        assert_eq!(hunks[1].new_start, 14);
+        assert!(hunks[1]
+            .lines
+            .iter()
+            .any(|l| l.kind == HunkLineKind::Removed && l.text == "line 18"));
+
+        // Close changes share a hunk
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 6\n", "line six\n");
+        assert_eq!(super::hunks(&before, &after).len(), 1);
+    }
+
+    #[test]
+    fn reverts_one_hunk_and_keeps_the_other() {
+        let before = numbered(20);
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+        let hunks = hunks(&before, &after);
+
+        let reverted = revert_hunk(&after, &hunks[1]).unwrap();
+        assert!(reverted.contains("line two\n"));
+        assert!(reverted.contains("line 18\n"));
+
+        // The first hunk still applies with the second one undone
+        let both = revert_hunk(&reverted, &hunks[0]).unwrap();
+        assert_eq!(both, before);
+
+        // Lines edited since no longer match
+        assert_eq!(
+            revert_hunk(&after.replace("line two", "line 2!"), &hunks[0]),
+            None
+        );
+    }
+
+    #[test]
+    fn diffs_a_turns_files() {
+        let change = |turn, path: &str, before: Option<&str>, after: Option<&str>| FileChange {
+            session_id: "s".into(),
+            session_slug: String::new(),
+            turn,
+            ts: 0,
+            path: path.into(),
+            prompt: String::new(),
+            before: before.map(String::from),
+            after: after.map(String::from),
+        };
+        let changes = vec![
+            change(1, "a.rs", Some("a\n"), Some("b\n")),
+            change(2, "a.rs", Some("b\n"), Some("c\n")),
+            change(2, "a.rs", Some("c\n"), Some("d\n")),
+            change(2, "new.rs", None, Some("fn main() {}\n")),
+            change(2, "same.rs", Some("x\n"), Some("x\n")),
+        ];
+        let diffs = turn_diffs(&changes, 2);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].path, "a.rs");
+        assert_eq!(diffs[0].hunks[0].old_lines(), ["b"]);
+        assert_eq!(diffs[0].hunks[0].new_lines(), ["d"]);
+        assert!(diffs[1].created);
+        assert_eq!(
+            revert_hunk("fn main() {}\n", &diffs[1].hunks[0]).unwrap(),
+            ""
+        );
+    }
+}
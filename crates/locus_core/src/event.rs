@@ -1,5 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use crate::approval::ApprovalRequest;
+use crate::conflict::ConflictRequest;
+use crate::db::{SimilarWork, TaskItem};
+use crate::diff_review::FileDiff;
+use crate::inspect::ProviderCall;
+use crate::plan::Plan;
+use crate::rewind::RewindSummary;
+use crate::secrets::RedactedSecret;
 use crate::session::SessionStatus;
 use crate::tool_call::{ToolResultData, ToolUse};
 use crate::turn::Role;
@@ -33,6 +41,47 @@ pub enum SessionEvent {
         items_found: u64,
     },
 
+    /// Recalled memories measured against the prompt's memory token budget.
+    MemoryBudget {
+        /// Tokens allowed for memories (0 = no limit).
+        budget_tokens: u64,
+        /// Tokens of the memories that went into the prompt.
+        used_tokens: u64,
+        /// Every recalled memory, most relevant first.
+        items: Vec<MemoryUsage>,
+    },
+
+    /// Results of a memory search (`:memory <query>`), most relevant first.
+    MemorySearch {
+        query: String,
+        memories: Vec<String>,
+        /// True when LocusGraph could not be searched.
+        degraded: bool,
+    },
+
+    /// One memory retrieval before an LLM call, for the memory trace.
+    MemoriesRetrieved {
+        /// User turns in the session so far; retrievals with the same number
+        /// belong to the same request.
+        turn: usize,
+        query: String,
+        /// Every recalled memory, most relevant first.
+        memories: Vec<RetrievedMemory>,
+        /// How long the retrieval took.
+        duration_ms: u64,
+        /// True when LocusGraph could not be searched.
+        degraded: bool,
+    },
+
+    /// What LocusGraph knows about the project — common failures and
+    /// conventions — fetched when a session starts.
+    ProjectInsights {
+        insight: String,
+        recommendation: String,
+        /// How sure LocusGraph is, from 0 to 1.
+        confidence: f64,
+    },
+
     MemoryStore {
         context_id: String,
         event_kind: String,
@@ -43,6 +92,144 @@ pub enum SessionEvent {
         message: String,
     },
 
+    /// A running tool reported what it is doing.
+    ToolProgress {
+        tool_use_id: String,
+        message: String,
+    },
+
+    /// A tool call ran past its time limit and was cancelled (likely hung).
+    ToolTimedOut {
+        tool_use_id: String,
+        tool_name: String,
+        timeout_secs: u64,
+    },
+
+    /// A sub-agent (task tool) is held back because the provider's rate-limit budget is low.
+    Throttled {
+        tool_use_id: String,
+        wait_ms: u64,
+        reason: String,
+    },
+
+    /// A sub-agent of the turn was queued, started, finished or reported what
+    /// it is doing; `agents` is every sub-agent of the turn, in call order.
+    SubAgents {
+        agents: Vec<SubAgentProgress>,
+    },
+
+    /// Something a sub-agent streamed or called (its text, tool calls and
+    /// errors), for its transcript in the agents view.
+    SubAgentOutput {
+        tool_use_id: String,
+        event: Box<SessionEvent>,
+    },
+
+    /// Tokens an LLM call used, with the session's totals so far.
+    TokenUsage {
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        total_prompt_tokens: u64,
+        total_completion_tokens: u64,
+    },
+
+    /// The provider and model a turn's LLM calls go to, and the context
+    /// window they have; sent when a turn starts.
+    ModelInUse {
+        provider: String,
+        model: String,
+        context_limit: u64,
+    },
+
+    /// The models of each provider with an API key (`:model`).
+    ModelList {
+        providers: Vec<ProviderModels>,
+    },
+
+    /// The prompts of the running MCP servers, runnable as `/mcp.<server>.<prompt>`;
+    /// sent when they change.
+    McpPrompts {
+        prompts: Vec<McpPromptInfo>,
+    },
+
+    /// A finished provider request with its raw stream events (only when inspection is enabled).
+    ProviderCall {
+        call: Box<ProviderCall>,
+        /// How many recent calls the viewer should keep.
+        keep: usize,
+    },
+
+    /// A tool call is waiting for the user to allow or deny it.
+    ApprovalRequested {
+        request: ApprovalRequest,
+    },
+
+    /// An edit targets a file the user changed since the agent last saw it.
+    ConflictDetected {
+        request: ConflictRequest,
+    },
+
+    /// Edits in the last turn broke a `.locus/guardrails.toml` assertion.
+    GuardrailViolation {
+        violations: Vec<String>,
+        /// True when the turn's edits were rolled back; false when the run was stopped instead.
+        reverted: bool,
+    },
+
+    /// Secrets were replaced with placeholders before a request went to the
+    /// model; lists each secret the first time it is seen.
+    SecretsRedacted {
+        secrets: Vec<RedactedSecret>,
+    },
+
+    /// A past session worked on a request close to the one just sent.
+    SimilarWork {
+        work: Box<SimilarWork>,
+    },
+
+    /// The task_list tool changed a plan; `tasks` is the whole plan after the change.
+    TaskListUpdated {
+        plan_id: String,
+        tasks: Vec<TaskItem>,
+    },
+
+    /// The session's last turns were dropped (`:rewind`) and the files they changed put back.
+    Rewound {
+        summary: RewindSummary,
+    },
+
+    /// The files a turn changed, as hunks for the diff review.
+    TurnDiff {
+        /// User turns in the session so far.
+        turn: u32,
+        files: Vec<FileDiff>,
+    },
+
+    /// A hunk of a turn's diff was rejected (`:reject-hunk`) and its lines put back.
+    HunkReverted {
+        turn: u32,
+        path: String,
+        /// Index of the hunk among the file's hunks in the turn's diff.
+        hunk: usize,
+    },
+
+    /// The files pinned into every turn's context (`:pin`), after a change
+    /// or when asked for.
+    PinnedFiles {
+        files: Vec<String>,
+    },
+
+    /// Project instruction files (`AGENTS.md`, `CLAUDE.md`, `.locus/instructions.md`)
+    /// were found and added to the system prompt, listed by repo-relative path.
+    InstructionsLoaded {
+        files: Vec<String>,
+    },
+
+    /// A plan mode run (`:plan`) finished and its plan was saved under `.locus/plans/`.
+    PlanReady {
+        plan: Plan,
+    },
+
     TurnEnd,
 
     Error {
@@ -60,6 +247,98 @@ pub enum SessionEvent {
     },
 }
 
+/// A provider and the models it offers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderModels {
+    pub provider: String,
+    pub models: Vec<String>,
+}
+
+/// A prompt an MCP server offers, as the `/` command popup lists it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPromptInfo {
+    /// `mcp.<server>.<prompt>`, typed after `/`.
+    pub name: String,
+    /// Its arguments, e.g. `<path> [focus]`; empty when it takes none.
+    pub usage: String,
+    pub description: String,
+}
+
+/// One recalled memory and what it costs in the prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// First line of the memory, cut to 80 characters.
+    pub summary: String,
+    /// Estimated tokens (~4 characters each).
+    pub tokens: u64,
+    /// False when it did not fit in the budget and was left out.
+    pub included: bool,
+}
+
+/// One memory of a retrieval, with how relevant it was and whether it made
+/// it into the prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievedMemory {
+    /// First line of the memory, cut to 80 characters.
+    pub summary: String,
+    /// Relevance from 0 to 1, when the search reports one.
+    pub relevance: Option<f64>,
+    /// Estimated tokens (~4 characters each).
+    pub tokens: u64,
+    /// False when it did not fit in the memory budget and was left out.
+    pub included: bool,
+}
+
+/// Where one sub-agent (`task` call) is, as shown in the agents view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubAgentProgress {
+    pub tool_use_id: String,
+    /// The task description the model gave it.
+    pub description: String,
+    pub state: SubAgentState,
+    /// What it is doing: its last status line or tool call (empty until it starts).
+    #[serde(default)]
+    pub activity: String,
+    /// Time since it started (0 while queued).
+    #[serde(default)]
+    pub elapsed_ms: u64,
+    /// The tool it is running, if any.
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// The last line of text it streamed.
+    #[serde(default)]
+    pub last_line: String,
+    /// Tokens its LLM calls used so far, prompt and completion.
+    #[serde(default)]
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubAgentState {
+    /// Waiting for a free slot (see `max_parallel_subagents`).
+    Queued,
+    Running,
+    /// Held back while the provider's rate-limit budget is low (see
+    /// [SessionEvent::Throttled]); runs once the wait is over.
+    Throttled,
+    Done,
+    Failed,
+    /// Stopped after running past its time limit.
+    TimedOut,
+    Cancelled,
+}
+
+impl SubAgentState {
+    /// Whether the sub-agent has stopped, one way or another.
+    pub fn is_finished(self) -> bool {
+        !matches!(
+            self,
+            SubAgentState::Queued | SubAgentState::Running | SubAgentState::Throttled
+        )
+    }
+}
+
 impl SessionEvent {
     pub fn turn_start(role: Role) -> Self {
         SessionEvent::TurnStart { role }
@@ -93,6 +372,50 @@ impl SessionEvent {
         }
     }
 
+    pub fn memory_budget(budget_tokens: u64, used_tokens: u64, items: Vec<MemoryUsage>) -> Self {
+        SessionEvent::MemoryBudget {
+            budget_tokens,
+            used_tokens,
+            items,
+        }
+    }
+
+    pub fn memory_search(query: impl Into<String>, memories: Vec<String>, degraded: bool) -> Self {
+        SessionEvent::MemorySearch {
+            query: query.into(),
+            memories,
+            degraded,
+        }
+    }
+
+    pub fn memories_retrieved(
+        turn: usize,
+        query: impl Into<String>,
+        memories: Vec<RetrievedMemory>,
+        duration_ms: u64,
+        degraded: bool,
+    ) -> Self {
+        SessionEvent::MemoriesRetrieved {
+            turn,
+            query: query.into(),
+            memories,
+            duration_ms,
+            degraded,
+        }
+    }
+
+    pub fn project_insights(
+        insight: impl Into<String>,
+        recommendation: impl Into<String>,
+        confidence: f64,
+    ) -> Self {
+        SessionEvent::ProjectInsights {
+            insight: insight.into(),
+            recommendation: recommendation.into(),
+            confidence,
+        }
+    }
+
     pub fn memory_store(
         context_id: impl Into<String>,
         event_kind: impl Into<String>,
@@ -111,6 +434,149 @@ impl SessionEvent {
         }
     }
 
+    pub fn tool_progress(tool_use_id: impl Into<String>, message: impl Into<String>) -> Self {
+        SessionEvent::ToolProgress {
+            tool_use_id: tool_use_id.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn tool_timed_out(
+        tool_use_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        timeout_secs: u64,
+    ) -> Self {
+        SessionEvent::ToolTimedOut {
+            tool_use_id: tool_use_id.into(),
+            tool_name: tool_name.into(),
+            timeout_secs,
+        }
+    }
+
+    pub fn throttled(
+        tool_use_id: impl Into<String>,
+        wait_ms: u64,
+        reason: impl Into<String>,
+    ) -> Self {
+        SessionEvent::Throttled {
+            tool_use_id: tool_use_id.into(),
+            wait_ms,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn sub_agents(agents: Vec<SubAgentProgress>) -> Self {
+        SessionEvent::SubAgents { agents }
+    }
+
+    pub fn sub_agent_output(tool_use_id: impl Into<String>, event: SessionEvent) -> Self {
+        SessionEvent::SubAgentOutput {
+            tool_use_id: tool_use_id.into(),
+            event: Box::new(event),
+        }
+    }
+
+    pub fn token_usage(
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        total_prompt_tokens: u64,
+        total_completion_tokens: u64,
+    ) -> Self {
+        SessionEvent::TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_prompt_tokens,
+            total_completion_tokens,
+        }
+    }
+
+    pub fn model_in_use(
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        context_limit: u64,
+    ) -> Self {
+        SessionEvent::ModelInUse {
+            provider: provider.into(),
+            model: model.into(),
+            context_limit,
+        }
+    }
+
+    pub fn model_list(providers: Vec<ProviderModels>) -> Self {
+        SessionEvent::ModelList { providers }
+    }
+
+    pub fn provider_call(call: ProviderCall, keep: usize) -> Self {
+        SessionEvent::ProviderCall {
+            call: Box::new(call),
+            keep,
+        }
+    }
+
+    pub fn approval_requested(request: ApprovalRequest) -> Self {
+        SessionEvent::ApprovalRequested { request }
+    }
+
+    pub fn conflict_detected(request: ConflictRequest) -> Self {
+        SessionEvent::ConflictDetected { request }
+    }
+
+    pub fn guardrail_violation(violations: Vec<String>, reverted: bool) -> Self {
+        SessionEvent::GuardrailViolation {
+            violations,
+            reverted,
+        }
+    }
+
+    pub fn secrets_redacted(secrets: Vec<RedactedSecret>) -> Self {
+        SessionEvent::SecretsRedacted { secrets }
+    }
+
+    pub fn similar_work(work: SimilarWork) -> Self {
+        SessionEvent::SimilarWork {
+            work: Box::new(work),
+        }
+    }
+
+    pub fn turn_diff(turn: u32, files: Vec<FileDiff>) -> Self {
+        SessionEvent::TurnDiff { turn, files }
+    }
+
+    pub fn hunk_reverted(turn: u32, path: impl Into<String>, hunk: usize) -> Self {
+        SessionEvent::HunkReverted {
+            turn,
+            path: path.into(),
+            hunk,
+        }
+    }
+
+    pub fn task_list_updated(plan_id: impl Into<String>, tasks: Vec<TaskItem>) -> Self {
+        SessionEvent::TaskListUpdated {
+            plan_id: plan_id.into(),
+            tasks,
+        }
+    }
+
+    pub fn rewound(summary: RewindSummary) -> Self {
+        SessionEvent::Rewound { summary }
+    }
+
+    pub fn pinned_files(files: Vec<String>) -> Self {
+        SessionEvent::PinnedFiles { files }
+    }
+
+    pub fn mcp_prompts(prompts: Vec<McpPromptInfo>) -> Self {
+        SessionEvent::McpPrompts { prompts }
+    }
+
+    pub fn instructions_loaded(files: Vec<String>) -> Self {
+        SessionEvent::InstructionsLoaded { files }
+    }
+
+    pub fn plan_ready(plan: Plan) -> Self {
+        SessionEvent::PlanReady { plan }
+    }
+
     pub fn turn_end() -> Self {
         SessionEvent::TurnEnd
     }
@@ -199,6 +665,23 @@ mod tests {
         assert!(json.contains(r#""items_found":5"#));
     }
 
+    #[test]
+    fn test_memory_budget() {
+        let event = SessionEvent::memory_budget(
+            500,
+            120,
+            vec![MemoryUsage {
+                summary: "JWT refresh uses the auth client".into(),
+                tokens: 120,
+                included: true,
+            }],
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"memory_budget"#));
+        assert!(json.contains(r#""used_tokens":120"#));
+        assert!(json.contains(r#""included":true"#));
+    }
+
     #[test]
     fn test_memory_store() {
         let event = SessionEvent::memory_store(
@@ -221,6 +704,93 @@ mod tests {
         assert!(json.contains("compressing context..."));
     }
 
+    #[test]
+    fn test_tool_progress() {
+        let event = SessionEvent::tool_progress("t1", "Filling the search form");
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"tool_progress"#));
+        assert!(json.contains(r#""message":"Filling the search form""#));
+    }
+
+    #[test]
+    fn test_tool_timed_out() {
+        let event = SessionEvent::tool_timed_out("t1", "mcp.search.query", 600);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"tool_timed_out"#));
+        assert!(json.contains(r#""timeout_secs":600"#));
+    }
+
+    #[test]
+    fn test_throttled() {
+        let event = SessionEvent::throttled("task-1", 12_000, "3% of request budget left");
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"throttled"#));
+        assert!(json.contains(r#""wait_ms":12000"#));
+    }
+
+    #[test]
+    fn test_sub_agents() {
+        let event = SessionEvent::sub_agents(vec![SubAgentProgress {
+            tool_use_id: "task-1".into(),
+            description: "Audit the parser".into(),
+            state: SubAgentState::TimedOut,
+            activity: String::new(),
+            elapsed_ms: 600_000,
+            tool: None,
+            last_line: String::new(),
+            tokens: 0,
+        }]);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"sub_agents"#));
+        assert!(json.contains(r#""state":"timed_out"#));
+        assert!(SubAgentState::TimedOut.is_finished());
+        assert!(!SubAgentState::Queued.is_finished());
+    }
+
+    #[test]
+    fn test_provider_call() {
+        let call = ProviderCall::new("zai", "glm-5", serde_json::json!({"messages": []}));
+        let event = SessionEvent::provider_call(call, 10);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"provider_call"#));
+        assert!(json.contains(r#""keep":10"#));
+        assert!(json.contains("glm-5"));
+    }
+
+    #[test]
+    fn test_approval_requested() {
+        let request = ApprovalRequest::new(
+            "bash",
+            crate::approval::Permission::Execute,
+            serde_json::json!({"command": "cargo test"}),
+        );
+        let event = SessionEvent::approval_requested(request);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"approval_requested"#));
+        assert!(json.contains(r#""permission":"execute"#));
+    }
+
+    #[test]
+    fn test_conflict_detected() {
+        let request = ConflictRequest::new("edit_file", "src/lib.rs", "a\n", "b\n", "c\n");
+        let event = SessionEvent::conflict_detected(request);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"conflict_detected"#));
+        assert!(json.contains(r#""conflicts":1"#));
+    }
+
+    #[test]
+    fn test_guardrail_violation() {
+        let event = SessionEvent::guardrail_violation(
+            vec!["edited protected file Cargo.lock".into()],
+            true,
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"guardrail_violation"#));
+        assert!(json.contains(r#""reverted":true"#));
+        assert!(json.contains("Cargo.lock"));
+    }
+
     #[test]
     fn test_turn_end() {
         let event = SessionEvent::turn_end();
@@ -263,8 +833,71 @@ mod tests {
             SessionEvent::tool_start(ToolUse::new("t1", "bash", serde_json::json!({}))),
             SessionEvent::tool_done("t1", ToolResultData::success(serde_json::json!({}), 0)),
             SessionEvent::memory_recall("q", 0),
+            SessionEvent::memory_budget(0, 0, Vec::new()),
             SessionEvent::memory_store("ctx:123", "observation", "stored intent"),
+            SessionEvent::project_insights("tests need a DB", "run migrations first", 0.7),
             SessionEvent::status("status"),
+            SessionEvent::tool_progress("t1", "navigating"),
+            SessionEvent::tool_timed_out("t1", "bash", 60),
+            SessionEvent::throttled("t1", 1000, "low budget"),
+            SessionEvent::sub_agent_output("t1", SessionEvent::text_delta("Reading")),
+            SessionEvent::token_usage(1200, 80, 3400, 240),
+            SessionEvent::provider_call(
+                ProviderCall::new("anthropic", "m", serde_json::json!({})),
+                5,
+            ),
+            SessionEvent::approval_requested(ApprovalRequest::new(
+                "bash",
+                crate::approval::Permission::Execute,
+                serde_json::json!({}),
+            )),
+            SessionEvent::conflict_detected(ConflictRequest::new("edit_file", "a", "", "", "")),
+            SessionEvent::guardrail_violation(vec!["check failed".into()], false),
+            SessionEvent::secrets_redacted(vec![RedactedSecret {
+                placeholder: "[SECRET_1]".into(),
+                kind: "`sk-` token".into(),
+            }]),
+            SessionEvent::similar_work(crate::db::SimilarWork {
+                score: 0.8,
+                matched: crate::db::SessionLogEntry {
+                    session_id: "s1".into(),
+                    session_slug: "add-retries".into(),
+                    ts: 100,
+                    title: "add retries".into(),
+                    outcome: "Done".into(),
+                    files_modified: vec!["src/http.rs".into()],
+                    tool_calls: 2,
+                },
+                files_modified: vec!["src/http.rs".into()],
+                turns: Vec::new(),
+            }),
+            SessionEvent::task_list_updated(
+                "default",
+                vec![crate::db::TaskItem {
+                    id: Some("1".into()),
+                    title: "write tests".into(),
+                    status: crate::db::TaskStatus::InProgress,
+                    description: None,
+                    criteria: Vec::new(),
+                }],
+            ),
+            SessionEvent::turn_diff(
+                1,
+                crate::diff_review::turn_diffs(
+                    &[crate::db::FileChange {
+                        session_id: "s1".into(),
+                        session_slug: String::new(),
+                        turn: 1,
+                        ts: 100,
+                        path: "src/http.rs".into(),
+                        prompt: "add retries".into(),
+                        before: Some("a\n".into()),
+                        after: Some("b\n".into()),
+                    }],
+                    1,
+                ),
+            ),
+            SessionEvent::hunk_reverted(1, "src/http.rs", 0),
             SessionEvent::turn_end(),
             SessionEvent::error("err"),
             SessionEvent::session_end(SessionStatus::Completed),
@@ -0,0 +1,425 @@
+//! Provider call inspection: what was sent to the model and what came back.
+//!
+//! When inspection is enabled, the runtime records one [ProviderCall] per LLM
+//! request (the full request body plus the raw stream events) and emits it as
+//! [crate::SessionEvent::ProviderCall] so the TUI can show the last few calls.
+//! Everything is passed through a [RedactionPolicy] before it leaves the runtime.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Stream events kept per call; text deltas arrive one per token, so long
+/// answers are cut here and the rest is only counted.
+pub const MAX_PROVIDER_CALL_EVENTS: usize = 2000;
+
+const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderCall {
+    pub provider: String,
+    pub model: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Request body as sent: messages (system prompt included, after compaction),
+    /// options and tool schemas.
+    pub request: serde_json::Value,
+    /// Raw stream events in arrival order, one JSON object per entry.
+    pub events: Vec<String>,
+    /// Events received after [MAX_PROVIDER_CALL_EVENTS] was reached.
+    #[serde(default)]
+    pub dropped_events: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProviderCall {
+    pub fn new(
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        request: serde_json::Value,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            model: model.into(),
+            started_at: Utc::now(),
+            duration_ms: 0,
+            request,
+            events: Vec::new(),
+            dropped_events: 0,
+            error: None,
+        }
+    }
+
+    pub fn push_event(&mut self, event: impl Into<String>) {
+        if self.events.len() < MAX_PROVIDER_CALL_EVENTS {
+            self.events.push(event.into());
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+
+    pub fn finish(mut self, duration_ms: u64, error: Option<String>) -> Self {
+        self.duration_ms = duration_ms;
+        self.error = error;
+        self
+    }
+
+    /// Apply `policy` to the request, events and error.
+    pub fn redacted(mut self, policy: &RedactionPolicy) -> Self {
+        policy.redact_value(&mut self.request);
+        for event in &mut self.events {
+            *event = policy.redact_text(event);
+        }
+        self.error = self.error.map(|e| policy.redact_text(&e));
+        self
+    }
+}
+
+/// What to scrub from inspected payloads before they are shown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionPolicy {
+    /// Object keys whose values are always replaced (case-insensitive; `-` matches `_`).
+    pub secret_keys: Vec<String>,
+    /// Prefixes of credential-shaped tokens scrubbed from any string (e.g. `sk-`).
+    pub secret_prefixes: Vec<String>,
+    /// Literal values scrubbed wherever they appear (e.g. API keys loaded from env).
+    pub secret_values: Vec<String>,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            secret_keys: owned(&[
+                "api_key",
+                "apikey",
+                "x_api_key",
+                "authorization",
+                "password",
+                "secret",
+                "client_secret",
+                "access_token",
+                "refresh_token",
+            ]),
+            secret_prefixes: owned(&["sk-", "ghp_", "github_pat_", "xoxb-", "xoxp-", "AKIA"]),
+            secret_values: Vec::new(),
+        }
+    }
+}
+
+impl RedactionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_secret_value(mut self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        if !value.trim().is_empty() {
+            self.secret_values.push(value);
+        }
+        self
+    }
+
+    /// Also scrub the values of environment variables that look like credentials
+    /// (`*_API_KEY`, `*_SECRET`, `*_TOKEN`).
+    pub fn with_env_secrets(mut self) -> Self {
+        for (name, value) in std::env::vars() {
+            let is_secret = ["_API_KEY", "_SECRET", "_TOKEN"]
+                .iter()
+                .any(|suffix| name.ends_with(suffix));
+            // Short values are more likely flags than credentials and would over-redact.
+            if is_secret && value.len() >= 8 {
+                self = self.with_secret_value(value);
+            }
+        }
+        self
+    }
+
+    pub(crate) fn is_secret_key(&self, key: &str) -> bool {
+        let key = key.to_lowercase().replace('-', "_");
+        self.secret_keys.contains(&key)
+    }
+
+    pub fn redact_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.redact_text(s),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.redact_value(item);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    if self.is_secret_key(key) && !v.is_null() {
+                        *v = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_value(v);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn redact_text(&self, text: &str) -> String {
+        replace_spans(text, &self.secret_spans(text), |_| REDACTED.to_string())
+    }
+
+    /// Where secrets sit in `text`, in order and without overlaps: literal
+    /// values, credential-shaped tokens and values assigned to secret-looking
+    /// names (`OPENAI_API_KEY=…`, `"password": "…"`).
+    pub fn secret_spans(&self, text: &str) -> Vec<SecretSpan> {
+        let mut spans = Vec::new();
+        for value in &self.secret_values {
+            for (start, _) in text.match_indices(value.as_str()) {
+                spans.push(SecretSpan {
+                    start,
+                    end: start + value.len(),
+                    kind: "environment secret".to_string(),
+                });
+            }
+        }
+        for prefix in &self.secret_prefixes {
+            spans.extend(prefixed_token_spans(text, prefix));
+        }
+        spans.extend(self.assignment_spans(text));
+
+        // Earlier first, longer first on ties; drop anything overlapping a kept span.
+        spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        let mut kept: Vec<SecretSpan> = Vec::with_capacity(spans.len());
+        for span in spans {
+            if kept.last().is_none_or(|last| span.start >= last.end) {
+                kept.push(span);
+            }
+        }
+        kept
+    }
+
+    /// Names whose assigned value is a secret: the policy's keys, plus anything
+    /// ending in one of [SECRET_NAME_SUFFIXES].
+    fn is_secret_name(&self, name: &str) -> bool {
+        let upper = name.to_uppercase().replace('-', "_");
+        self.is_secret_key(name)
+            || SECRET_NAME_SUFFIXES
+                .iter()
+                .any(|suffix| upper.ends_with(suffix))
+    }
+
+    /// Values in `NAME=value`, `NAME: value` and `"name": "value"` where NAME
+    /// looks like a secret and the value looks like a credential.
+    fn assignment_spans(&self, text: &str) -> Vec<SecretSpan> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+        let bytes = text.as_bytes();
+        let mut spans = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            if !is_name_char(bytes[i] as char) || (i > 0 && is_name_char(bytes[i - 1] as char)) {
+                i += 1;
+                continue;
+            }
+            let name_end = text[i..]
+                .find(|c: char| !is_name_char(c))
+                .map_or(text.len(), |n| i + n);
+            let name = &text[i..name_end];
+            i = name_end;
+            if !self.is_secret_name(name) {
+                continue;
+            }
+            let rest = &text[name_end..];
+            let after_name = rest.trim_start_matches(['"', '\'']);
+            let after_space = after_name.trim_start_matches([' ', '\t']);
+            let Some(after_op) = after_space
+                .strip_prefix('=')
+                .or_else(|| after_space.strip_prefix(':'))
+            else {
+                continue;
+            };
+            let mut value_start = text.len() - after_op.len();
+            value_start += after_op.len() - after_op.trim_start_matches([' ', '\t']).len();
+            let mut value = value_end(&text[value_start..]);
+            // `Authorization: Bearer <token>`: the token is the secret.
+            if matches!(value, "Bearer" | "Basic" | "Token") {
+                value_start += value.len();
+                value_start +=
+                    text[value_start..].len() - text[value_start..].trim_start_matches(' ').len();
+                value = value_end(&text[value_start..]);
+            }
+            let quote_len = value.len() - value.trim_start_matches(['"', '\'']).len();
+            let value = value.trim_matches(['"', '\'']);
+            if looks_like_credential(value) {
+                let start = value_start + quote_len;
+                spans.push(SecretSpan {
+                    start,
+                    end: start + value.len(),
+                    kind: format!("{} value", name.trim_matches('-')),
+                });
+            }
+        }
+        spans
+    }
+}
+
+/// Name endings that mark a variable or field as holding a secret.
+const SECRET_NAME_SUFFIXES: &[&str] = &[
+    "_API_KEY",
+    "_SECRET",
+    "_SECRET_KEY",
+    "_TOKEN",
+    "_PASSWORD",
+    "PASSWD",
+];
+
+/// One secret found in text: its byte range and what matched it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretSpan {
+    pub start: usize,
+    pub end: usize,
+    /// What matched, e.g. "`sk-` token" or "DATABASE_PASSWORD value".
+    pub kind: String,
+}
+
+/// Replace each span (sorted, non-overlapping) with `replacement(secret)`.
+pub(crate) fn replace_spans(
+    text: &str,
+    spans: &[SecretSpan],
+    mut replacement: impl FnMut(&SecretSpan) -> String,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for span in spans {
+        out.push_str(&text[last..span.start]);
+        out.push_str(&replacement(span));
+        last = span.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// The value at the start of `text`: up to whitespace or a separator, a quoted
+/// string when it starts with a quote.
+fn value_end(text: &str) -> &str {
+    if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let body = &text[1..];
+        return match body.find(quote) {
+            Some(end) => &text[..end + 2],
+            None => text,
+        };
+    }
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '}' | ']' | ')' | '"' | '\''))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// At least eight characters mixing two kinds (lower, upper, digit, symbol),
+/// and not a reference such as `${VAR}`, `process.env.KEY` or `<your-key>`.
+fn looks_like_credential(value: &str) -> bool {
+    if value.len() < 8 || value.contains(char::is_whitespace) || value.contains(REDACTED) {
+        return false;
+    }
+    if value.starts_with(['$', '{', '<', '[', '%']) || value.contains('(') {
+        return false;
+    }
+    let dotted_name = value.contains('.')
+        && !value.contains(|c: char| c.is_ascii_digit())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '_' || c == '.');
+    if dotted_name {
+        return false;
+    }
+    let kinds = [
+        value.contains(|c: char| c.is_ascii_lowercase()),
+        value.contains(|c: char| c.is_ascii_uppercase()),
+        value.contains(|c: char| c.is_ascii_digit()),
+        value.contains(|c: char| !c.is_ascii_alphanumeric()),
+    ];
+    kinds.iter().filter(|k| **k).count() >= 2
+}
+
+/// Tokens that start with `prefix` at a word boundary and carry at least eight
+/// more token characters, so `task-list` survives but `sk-abc123def456` does not.
+fn prefixed_token_spans(text: &str, prefix: &str) -> Vec<SecretSpan> {
+    let is_token_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find(prefix) {
+        let start = offset + pos;
+        let at_boundary = !matches!(text[..start].chars().next_back(), Some(c) if is_token_char(c));
+        let after = &text[start + prefix.len()..];
+        let body_len = after.find(|c| !is_token_char(c)).unwrap_or(after.len());
+        let end = start + prefix.len() + body_len;
+        if at_boundary && body_len >= 8 {
+            spans.push(SecretSpan {
+                start,
+                end,
+                kind: format!("`{}` token", prefix),
+            });
+        }
+        offset = end;
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_secret_keys() {
+        let mut value = serde_json::json!({
+            "model": "glm-5",
+            "max_tokens": 8192,
+            "headers": { "Authorization": "Bearer abc", "X-Api-Key": "k" },
+            "tools": [{ "name": "bash", "password": null }]
+        });
+        RedactionPolicy::default().redact_value(&mut value);
+
+        assert_eq!(value["model"], "glm-5");
+        assert_eq!(value["max_tokens"], 8192);
+        assert_eq!(value["headers"]["Authorization"], REDACTED);
+        assert_eq!(value["headers"]["X-Api-Key"], REDACTED);
+        assert!(value["tools"][0]["password"].is_null());
+    }
+
+    #[test]
+    fn test_redacts_prefixed_tokens_at_word_boundary() {
+        let policy = RedactionPolicy::default();
+        assert_eq!(
+            policy.redact_text("export KEY=sk-ant-api03abcdef end"),
+            "export KEY=[REDACTED] end"
+        );
+        assert_eq!(policy.redact_text("run the task-list"), "run the task-list");
+        assert_eq!(policy.redact_text("sk-short"), "sk-short");
+    }
+
+    #[test]
+    fn test_redacts_literal_values() {
+        let policy = RedactionPolicy::new()
+            .with_secret_value("hunter2-graph-secret")
+            .with_secret_value("  ");
+        assert_eq!(policy.secret_values.len(), 1);
+        assert_eq!(
+            policy.redact_text(r#"{"data":"token hunter2-graph-secret"}"#),
+            r#"{"data":"token [REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn test_provider_call_caps_events_and_redacts() {
+        let mut call = ProviderCall::new("zai", "glm-5", serde_json::json!({ "api_key": "x" }));
+        for i in 0..MAX_PROVIDER_CALL_EVENTS + 3 {
+            call.push_event(format!(r#"{{"n":{}}}"#, i));
+        }
+        call.push_event("ignored");
+        let call = call
+            .finish(42, Some("bad key sk-0123456789abcdef".to_string()))
+            .redacted(&RedactionPolicy::default());
+
+        assert_eq!(call.events.len(), MAX_PROVIDER_CALL_EVENTS);
+        assert_eq!(call.dropped_events, 4);
+        assert_eq!(call.duration_ms, 42);
+        assert_eq!(call.request["api_key"], REDACTED);
+        assert_eq!(call.error.as_deref(), Some("bad key [REDACTED]"));
+    }
+}
@@ -1,17 +1,51 @@
+pub mod approval;
+mod command;
+pub mod conflict;
+pub mod custom_command;
 pub mod db;
+pub mod diff_review;
 pub mod error;
 pub mod event;
+pub mod inspect;
 pub mod memory;
+pub mod memory_search;
+pub mod mention;
+pub mod model;
+pub mod pin;
+pub mod plan;
+pub mod pricing;
+pub mod rewind;
+pub mod secrets;
 pub mod session;
 pub mod tool_call;
 pub mod turn;
 
+pub use approval::{
+    ApprovalDecision, ApprovalPreview, ApprovalRequest, ApprovalResponse, Permission,
+};
+pub use conflict::{merge3, ConflictDecision, ConflictRequest, ConflictResponse, Merge};
+pub use custom_command::{
+    expand_slash_command, load_custom_commands, parse_slash_command, CustomCommand,
+};
+pub use diff_review::{parse_reject_hunk, FileDiff, Hunk, HunkLine, HunkLineKind, HunkRef};
 pub use error::{LocusError, Result};
-pub use event::SessionEvent;
+pub use event::{
+    McpPromptInfo, MemoryUsage, ProviderModels, RetrievedMemory, SessionEvent, SubAgentProgress,
+    SubAgentState,
+};
+pub use inspect::{ProviderCall, RedactionPolicy};
 pub use memory::{ContextScope, EventKind, MemoryEvent};
+pub use memory_search::parse_memory_command;
+pub use mention::{find_mentions, Mention};
+pub use model::{parse_model_command, ModelCommand};
+pub use pin::{parse_pin_command, PinCommand};
+pub use plan::{parse_plan_command, Plan, PlanCommand};
+pub use pricing::{model_price, ModelPrice};
+pub use rewind::{parse_rewind, RewindSummary};
+pub use secrets::{RedactedSecret, SecretScrubber};
 pub use session::{
     ParentSessionId, SandboxPolicy, Session, SessionConfig, SessionId, SessionStatus,
     SessionSummary,
 };
-pub use tool_call::{ToolResultData, ToolStatus, ToolUse};
+pub use tool_call::{ToolAnnotation, ToolResultData, ToolStatus, ToolUse};
 pub use turn::{ContentBlock, Role, TokenUsage, Turn};
@@ -0,0 +1,34 @@
+//! `:memory <query>`: look up what LocusGraph remembers about the repo for a
+//! query, without asking the model anything.
+
+/// Chat command to search memory.
+pub const MEMORY_COMMAND: &str = ":memory";
+
+/// The query in `input`, when it is `:memory`. Err holds the usage line for
+/// `:memory` without a query.
+pub fn parse_memory_command(input: &str) -> Option<Result<String, String>> {
+    let rest = input.trim().strip_prefix(MEMORY_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    match rest.trim() {
+        "" => Some(Err(format!("Usage: {} <query>", MEMORY_COMMAND))),
+        query => Some(Ok(query.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_command() {
+        assert_eq!(
+            parse_memory_command(" :memory how are errors reported "),
+            Some(Ok("how are errors reported".to_string()))
+        );
+        assert!(matches!(parse_memory_command(":memory"), Some(Err(_))));
+        assert_eq!(parse_memory_command(":memorize this"), None);
+        assert_eq!(parse_memory_command("what is in :memory"), None);
+    }
+}
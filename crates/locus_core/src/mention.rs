@@ -0,0 +1,83 @@
+//! `@path` mentions in user prompts: `@src/lib.rs` attaches that file to the
+//! prompt, `@docs/` a listing of that folder.
+//!
+//! Finding mentions is shared by the runtime, which attaches what they name,
+//! and the TUI, which highlights them in the chat.
+
+use std::ops::Range;
+
+/// Start of a context block holding a mentioned file or folder, so views that
+/// replay a session can tell attachments from what the user typed.
+pub const ATTACHMENT_PREFIX: &str = "[Attached @";
+
+/// One `@path` in a prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// The path as written, without the `@`.
+    pub path: String,
+    /// Byte range of the mention in the prompt, `@` included.
+    pub range: Range<usize>,
+}
+
+/// Characters that end a sentence around a mention rather than belong to it.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '"', '\'', '`'];
+
+/// The `@path` mentions in `text`, in order. A mention starts at an `@` at the
+/// start of the text or after whitespace or an opening bracket or quote (so
+/// `user@example.com` is not one) and runs to the next whitespace, less any
+/// trailing punctuation.
+pub fn find_mentions(text: &str) -> Vec<Mention> {
+    let mut mentions = Vec::new();
+    let mut prev: Option<char> = None;
+    for (at, c) in text.char_indices() {
+        let starts = c == '@'
+            && prev.is_none_or(|p| p.is_whitespace() || matches!(p, '(' | '[' | '"' | '\'' | '`'));
+        prev = Some(c);
+        if !starts {
+            continue;
+        }
+        let rest = &text[at + 1..];
+        let word = rest.split(char::is_whitespace).next().unwrap_or_default();
+        let path = word.trim_end_matches(TRAILING_PUNCTUATION);
+        if path.is_empty() || path.contains('@') {
+            continue;
+        }
+        mentions.push(Mention {
+            path: path.to_string(),
+            range: at..at + 1 + path.len(),
+        });
+    }
+    mentions
+}
+
+/// Whether a user-turn text block is a mention attachment rather than typed text.
+pub fn is_attachment(text: &str) -> bool {
+    text.starts_with(ATTACHMENT_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(text: &str) -> Vec<String> {
+        find_mentions(text).into_iter().map(|m| m.path).collect()
+    }
+
+    #[test]
+    fn finds_paths_after_at_signs() {
+        assert_eq!(
+            paths("@src/lib.rs is slow, see (@docs/) and @Makefile."),
+            ["src/lib.rs", "docs/", "Makefile"]
+        );
+        let text = "fix @src/main.rs: it panics";
+        let mention = &find_mentions(text)[0];
+        assert_eq!(&text[mention.range.clone()], "@src/main.rs");
+    }
+
+    #[test]
+    fn ignores_emails_and_bare_at_signs() {
+        assert!(paths("mail me@example.com or @ here, @@x").is_empty());
+        assert!(is_attachment("[Attached @src/lib.rs]\n…"));
+        assert!(!is_attachment("look at @src/lib.rs"));
+    }
+}
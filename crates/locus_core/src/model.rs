@@ -0,0 +1,68 @@
+//! `:model`: switch the provider and model of the session. Bare, it lists
+//! the models of each provider with an API key; the switch applies from the
+//! next turn on and is kept in the session's config.
+
+use crate::command::command_args;
+
+/// Chat command to list the models, or switch to one.
+pub const MODEL_COMMAND: &str = ":model";
+
+/// A parsed model chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelCommand {
+    /// List the providers and their models.
+    List,
+    /// Use this model of this provider from the next turn on.
+    Switch { provider: String, model: String },
+}
+
+/// The command in `input`, when it is `:model`, `:model <provider>/<model>`
+/// or `:model <provider> <model>`. Err holds the usage line for a model
+/// without a provider.
+pub fn parse_model_command(input: &str) -> Option<Result<ModelCommand, String>> {
+    let rest = command_args(input.trim(), MODEL_COMMAND)?;
+    if rest.is_empty() {
+        return Some(Ok(ModelCommand::List));
+    }
+    let split = rest
+        .split_once(char::is_whitespace)
+        .or_else(|| rest.split_once('/'));
+    Some(match split {
+        Some((provider, model)) if !model.trim().is_empty() => Ok(ModelCommand::Switch {
+            provider: provider.trim().to_lowercase(),
+            model: model.trim().to_string(),
+        }),
+        _ => Err(format!(
+            "Usage: {} <provider>/<model>  ({} alone lists them)",
+            MODEL_COMMAND, MODEL_COMMAND
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model_command() {
+        assert_eq!(parse_model_command(":model"), Some(Ok(ModelCommand::List)));
+        let switch = Some(Ok(ModelCommand::Switch {
+            provider: "anthropic".to_string(),
+            model: "claude-sonnet-4-20250514".to_string(),
+        }));
+        assert_eq!(
+            parse_model_command(" :model anthropic/claude-sonnet-4-20250514 "),
+            switch
+        );
+        assert_eq!(
+            parse_model_command(":model Anthropic claude-sonnet-4-20250514"),
+            switch
+        );
+        assert!(matches!(
+            parse_model_command(":model glm-4.5"),
+            Some(Err(_))
+        ));
+        assert_eq!(parse_model_command(":models"), None);
+        assert_eq!(parse_model_command("model this"), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! `:pin <path>`: keep a file in the context of every turn, re-read whenever
+//! it changes, for things like a project's conventions or an API spec.
+
+use crate::command::command_args;
+
+/// Chat command to pin a file, or to list the pinned files when bare.
+pub const PIN_COMMAND: &str = ":pin";
+
+/// Chat command to stop pinning a file.
+pub const UNPIN_COMMAND: &str = ":unpin";
+
+/// A parsed pin chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinCommand {
+    /// Pin this file (repo-relative).
+    Pin(String),
+    Unpin(String),
+    /// Say which files are pinned.
+    List,
+}
+
+/// The command in `input`, when it is `:pin` or `:unpin`. Err holds the usage
+/// line for `:unpin` without a path.
+pub fn parse_pin_command(input: &str) -> Option<Result<PinCommand, String>> {
+    let input = input.trim();
+    if let Some(rest) = command_args(input, UNPIN_COMMAND) {
+        if rest.is_empty() {
+            return Some(Err(format!(
+                "Usage: {} <path>  ({} lists pinned files)",
+                UNPIN_COMMAND, PIN_COMMAND
+            )));
+        }
+        return Some(Ok(PinCommand::Unpin(rest.to_string())));
+    }
+    let rest = command_args(input, PIN_COMMAND)?;
+    if rest.is_empty() {
+        return Some(Ok(PinCommand::List));
+    }
+    Some(Ok(PinCommand::Pin(rest.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pin_command() {
+        assert_eq!(
+            parse_pin_command(" :pin docs/CONVENTIONS.md "),
+            Some(Ok(PinCommand::Pin("docs/CONVENTIONS.md".to_string())))
+        );
+        assert_eq!(parse_pin_command(":pin"), Some(Ok(PinCommand::List)));
+        assert_eq!(
+            parse_pin_command(":unpin api.yaml"),
+            Some(Ok(PinCommand::Unpin("api.yaml".to_string())))
+        );
+        assert!(matches!(parse_pin_command(":unpin"), Some(Err(_))));
+        assert_eq!(parse_pin_command(":pinned"), None);
+        assert_eq!(parse_pin_command("pin this"), None);
+    }
+}
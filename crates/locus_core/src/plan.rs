@@ -0,0 +1,180 @@
+//! Plan mode: `:plan <task>` runs the agent with read-only tools and saves its
+//! answer as a plan under `.locus/plans/`; `:execute-plan` runs it with every
+//! tool, the plan passed to the model as context.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::command::command_args;
+
+/// Chat command to plan a task, typed as `:plan <task>`.
+pub const PLAN_COMMAND: &str = ":plan";
+/// Chat command to carry out a plan, typed as `:execute-plan [path]` (the newest
+/// plan when no path is given).
+pub const EXECUTE_PLAN_COMMAND: &str = ":execute-plan";
+/// Saved plans, relative to the repo root.
+pub const PLANS_DIR: &str = ".locus/plans";
+
+/// Longest task slug in a plan file name.
+const MAX_SLUG_CHARS: usize = 40;
+
+/// A parsed plan mode chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanCommand {
+    /// Plan this task without changing anything.
+    Plan(String),
+    /// Carry out the plan at this path (repo-relative), or the newest one.
+    Execute(Option<String>),
+}
+
+/// A saved plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Plan {
+    /// File under [PLANS_DIR], relative to the repo root.
+    pub path: String,
+    pub task: String,
+    /// The plan as the model wrote it (markdown).
+    pub text: String,
+}
+
+/// The command in `input`, when it is one of the plan mode commands. Err holds
+/// the usage line for `:plan` without a task.
+pub fn parse_plan_command(input: &str) -> Option<Result<PlanCommand, String>> {
+    let input = input.trim();
+    if let Some(rest) = command_args(input, EXECUTE_PLAN_COMMAND) {
+        let path = (!rest.is_empty()).then(|| rest.to_string());
+        return Some(Ok(PlanCommand::Execute(path)));
+    }
+    let rest = command_args(input, PLAN_COMMAND)?;
+    if rest.is_empty() {
+        return Some(Err(format!(
+            "Usage: {} <task>  (then {} to carry it out)",
+            PLAN_COMMAND, EXECUTE_PLAN_COMMAND
+        )));
+    }
+    Some(Ok(PlanCommand::Plan(rest.to_string())))
+}
+
+/// Write a plan for `task` to a new file under [PLANS_DIR], named by time and task.
+pub fn save_plan(repo_root: &Path, task: &str, text: &str) -> std::io::Result<Plan> {
+    let dir = repo_root.join(PLANS_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let slug = slug(task);
+    let mut name = format!("{}-{}.md", stamp, slug);
+    let mut n = 2;
+    while dir.join(&name).exists() {
+        name = format!("{}-{}-{}.md", stamp, slug, n);
+        n += 1;
+    }
+    let plan = Plan {
+        path: format!("{}/{}", PLANS_DIR, name),
+        task: task.to_string(),
+        text: text.trim().to_string(),
+    };
+    std::fs::write(
+        dir.join(&name),
+        format!("# Plan: {}\n\n{}\n", plan.task, plan.text),
+    )?;
+    Ok(plan)
+}
+
+/// Read the plan at `path` (repo-relative), or the newest under [PLANS_DIR].
+/// `Ok(None)` when no path is given and there are no plans yet.
+pub fn load_plan(repo_root: &Path, path: Option<&str>) -> std::io::Result<Option<Plan>> {
+    let file = match path {
+        Some(path) => repo_root.join(path),
+        None => match newest_plan(&repo_root.join(PLANS_DIR))? {
+            Some(file) => file,
+            None => return Ok(None),
+        },
+    };
+    let content = std::fs::read_to_string(&file)?;
+    let (task, text) = match content.strip_prefix("# Plan: ") {
+        Some(rest) => rest.split_once('\n').unwrap_or((rest, "")),
+        None => ("", content.as_str()),
+    };
+    let path = file
+        .strip_prefix(repo_root)
+        .unwrap_or(&file)
+        .to_string_lossy()
+        .replace('\\', "/");
+    Ok(Some(Plan {
+        path,
+        task: task.trim().to_string(),
+        text: text.trim().to_string(),
+    }))
+}
+
+/// The most recently written plan; file names (which start with the time) break ties.
+fn newest_plan(dir: &Path) -> std::io::Result<Option<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    let newest = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+        .max_by_key(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            (modified, entry.path().file_stem().map(|s| s.to_owned()))
+        });
+    Ok(newest.map(|entry| entry.path()))
+}
+
+fn slug(task: &str) -> String {
+    let slug = task
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug: String = slug.chars().take(MAX_SLUG_CHARS).collect();
+    match slug.trim_end_matches('-') {
+        "" => "plan".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_plan_command() {
+        assert_eq!(
+            parse_plan_command(":plan  fix the flaky test "),
+            Some(Ok(PlanCommand::Plan("fix the flaky test".to_string())))
+        );
+        assert!(matches!(parse_plan_command(":plan"), Some(Err(_))));
+        assert_eq!(
+            parse_plan_command(":execute-plan"),
+            Some(Ok(PlanCommand::Execute(None)))
+        );
+        assert_eq!(
+            parse_plan_command(":execute-plan .locus/plans/a.md"),
+            Some(Ok(PlanCommand::Execute(Some(
+                ".locus/plans/a.md".to_string()
+            ))))
+        );
+        assert_eq!(parse_plan_command(":planet"), None);
+        assert_eq!(parse_plan_command("plan the release"), None);
+    }
+
+    #[test]
+    fn test_saved_plans_load_back() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(load_plan(dir.path(), None).unwrap(), None);
+
+        let first = save_plan(dir.path(), "Fix `add`!", "1. Edit src/lib.rs\n").unwrap();
+        assert!(first.path.starts_with(".locus/plans/"));
+        assert!(first.path.ends_with("-fix-add.md"));
+        let second = save_plan(dir.path(), "Fix `add`!", "1. Something else").unwrap();
+        assert_ne!(first.path, second.path);
+
+        let loaded = load_plan(dir.path(), Some(&first.path)).unwrap().unwrap();
+        assert_eq!(loaded, first);
+        assert_eq!(loaded.text, "1. Edit src/lib.rs");
+        assert_eq!(load_plan(dir.path(), None).unwrap().unwrap(), second);
+    }
+}
@@ -0,0 +1,100 @@
+//! List prices of the models locus talks to, for showing what a session
+//! costs. Prices are per million tokens in USD and only an estimate: cached
+//! prompt tokens, batch discounts and long-context surcharges are ignored.
+
+/// What a model costs per million prompt (input) and completion (output) tokens, in USD.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    const fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+        }
+    }
+
+    /// Cost in USD of a call with these token counts.
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.input_per_mtok
+            + completion_tokens as f64 * self.output_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Model name prefixes and their prices; the first prefix a model name
+/// starts with wins, so more specific prefixes come first.
+const PRICES: &[(&str, ModelPrice)] = &[
+    // Anthropic
+    ("claude-opus-4-5", ModelPrice::new(5.0, 25.0)),
+    ("claude-opus-4", ModelPrice::new(15.0, 75.0)),
+    ("claude-3-opus", ModelPrice::new(15.0, 75.0)),
+    ("claude-sonnet-4", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-haiku-4-5", ModelPrice::new(1.0, 5.0)),
+    ("claude-3-5-haiku", ModelPrice::new(0.8, 4.0)),
+    ("claude-3-haiku", ModelPrice::new(0.25, 1.25)),
+    // OpenAI
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.6)),
+    ("gpt-4o", ModelPrice::new(2.5, 10.0)),
+    ("gpt-4.1-nano", ModelPrice::new(0.1, 0.4)),
+    ("gpt-4.1-mini", ModelPrice::new(0.4, 1.6)),
+    ("gpt-4.1", ModelPrice::new(2.0, 8.0)),
+    ("o4-mini", ModelPrice::new(1.1, 4.4)),
+    ("o3", ModelPrice::new(2.0, 8.0)),
+    // Z.AI
+    ("glm-4.5-air", ModelPrice::new(0.2, 1.1)),
+    ("glm-4.5", ModelPrice::new(0.6, 2.2)),
+    ("glm-4.6", ModelPrice::new(0.6, 2.2)),
+];
+
+/// The price of `model` from `provider`; free for local Ollama models, None
+/// when the model is not in the table.
+pub fn model_price(provider: &str, model: &str) -> Option<ModelPrice> {
+    if provider == "ollama" {
+        return Some(ModelPrice::new(0.0, 0.0));
+    }
+    let model = model.to_ascii_lowercase();
+    // Router and Bedrock style ids: "openai/gpt-4o", "anthropic.claude-sonnet-4-…"
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    let name = name.strip_prefix("anthropic.").unwrap_or(name);
+    PRICES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, price)| *price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_by_longest_known_prefix() {
+        let sonnet = model_price("anthropic", "claude-sonnet-4-20250514").unwrap();
+        assert_eq!(sonnet, ModelPrice::new(3.0, 15.0));
+        assert_eq!(
+            model_price("openai", "gpt-4o-mini-2024-07-18"),
+            Some(ModelPrice::new(0.15, 0.6))
+        );
+        assert_eq!(
+            model_price("zai", "glm-4.5-air"),
+            Some(ModelPrice::new(0.2, 1.1))
+        );
+        assert_eq!(
+            model_price("ollama", "llama3.1").unwrap().cost(1000, 1000),
+            0.0
+        );
+        assert_eq!(model_price("anthropic", "some-new-model"), None);
+    }
+
+    #[test]
+    fn cost_of_a_call() {
+        let price = ModelPrice::new(3.0, 15.0);
+        let cost = price.cost(100_000, 2_000);
+        assert!((cost - 0.33).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,150 @@
+//! `:rewind N`: drop the last N turns of a session and put back the files those
+//! turns changed, so the conversation and the working tree agree again.
+//!
+//! File contents come from the file change log (see [crate::db::FileChange]),
+//! which holds each file's content before and after every agent turn that wrote it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::db::FileChange;
+
+/// The chat command, typed as `:rewind` (one turn) or `:rewind N`.
+pub const REWIND_COMMAND: &str = ":rewind";
+
+/// What a rewind dropped and put back.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewindSummary {
+    /// Turns dropped from the session.
+    pub turns: u32,
+    /// Files set back to their content from before the dropped turns.
+    pub restored: Vec<String>,
+    /// Files the dropped turns created, now deleted.
+    pub removed: Vec<String>,
+    /// Files changed by someone else since the dropped turns wrote them; left as they are.
+    pub kept: Vec<String>,
+}
+
+/// Turns to rewind when `input` is the rewind command. Err holds the usage line
+/// when the count is not a positive number.
+pub fn parse_rewind(input: &str) -> Option<Result<u32, String>> {
+    let rest = input.trim().strip_prefix(REWIND_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let usage = || {
+        format!(
+            "Usage: {} [N]  (N = turns to drop, default 1)",
+            REWIND_COMMAND
+        )
+    };
+    match rest.trim() {
+        "" => Some(Ok(1)),
+        count => Some(count.parse().ok().filter(|&n| n > 0).ok_or_else(usage)),
+    }
+}
+
+/// Put the files in `changes` (the dropped turns' changes, oldest first) back to
+/// their content before the first of them. A file whose content is no longer what
+/// the last change left is kept, so edits made since are not lost.
+pub fn rewind_files(
+    repo_root: &Path,
+    turns: u32,
+    changes: &[FileChange],
+) -> std::io::Result<RewindSummary> {
+    // path -> (content before the first change, content after the last)
+    let mut files: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for change in changes {
+        files
+            .entry(change.path.as_str())
+            .and_modify(|(_, after)| *after = change.after.as_deref())
+            .or_insert((change.before.as_deref(), change.after.as_deref()));
+    }
+
+    let mut summary = RewindSummary {
+        turns,
+        ..Default::default()
+    };
+    for (path, (before, after)) in files {
+        let full = repo_root.join(path);
+        let current = std::fs::read_to_string(&full).ok();
+        if current.as_deref() != after {
+            summary.kept.push(path.to_string());
+            continue;
+        }
+        match before {
+            Some(content) => {
+                if let Some(parent) = full.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&full, content)?;
+                summary.restored.push(path.to_string());
+            }
+            None => {
+                if current.is_some() {
+                    std::fs::remove_file(&full)?;
+                }
+                summary.removed.push(path.to_string());
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_rewind_command() {
+        assert_eq!(parse_rewind(":rewind"), Some(Ok(1)));
+        assert_eq!(parse_rewind("  :rewind 3 "), Some(Ok(3)));
+        assert!(matches!(parse_rewind(":rewind 0"), Some(Err(_))));
+        assert!(matches!(parse_rewind(":rewind two"), Some(Err(_))));
+        assert_eq!(parse_rewind(":rewinder"), None);
+        assert_eq!(parse_rewind("please :rewind"), None);
+    }
+
+    #[test]
+    fn restores_created_edited_and_keeps_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let change = |turn, path: &str, before: Option<&str>, after: Option<&str>| FileChange {
+            session_id: "s1".into(),
+            session_slug: String::new(),
+            turn,
+            ts: turn as i64,
+            path: path.into(),
+            prompt: String::new(),
+            before: before.map(Into::into),
+            after: after.map(Into::into),
+        };
+        std::fs::create_dir(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/lib.rs"), "v3").unwrap();
+        std::fs::write(repo.join("src/new.rs"), "new").unwrap();
+        std::fs::write(repo.join("notes.md"), "edited by hand").unwrap();
+        let changes = [
+            change(2, "src/lib.rs", Some("v1"), Some("v2")),
+            change(2, "src/new.rs", None, Some("new")),
+            change(3, "src/lib.rs", Some("v2"), Some("v3")),
+            change(3, "notes.md", Some("old"), Some("agent")),
+        ];
+
+        let summary = rewind_files(repo, 2, &changes).unwrap();
+
+        assert_eq!(summary.turns, 2);
+        assert_eq!(summary.restored, ["src/lib.rs"]);
+        assert_eq!(summary.removed, ["src/new.rs"]);
+        assert_eq!(summary.kept, ["notes.md"]);
+        assert_eq!(
+            std::fs::read_to_string(repo.join("src/lib.rs")).unwrap(),
+            "v1"
+        );
+        assert!(!repo.join("src/new.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(repo.join("notes.md")).unwrap(),
+            "edited by hand"
+        );
+    }
+}
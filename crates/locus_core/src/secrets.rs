@@ -0,0 +1,285 @@
+//! Secret scrubbing for what is sent to the model.
+//!
+//! [SecretScrubber] replaces every secret a [RedactionPolicy] finds with a
+//! placeholder such as `[SECRET_1]`. Each distinct secret keeps its number for
+//! the scrubber's lifetime, so the same key reads the same in every request
+//! and the model can still tell two secrets apart. The placeholders in the
+//! file content an `edit_file` or `create_file` call writes are restored
+//! before it runs, so an edit of a line holding a secret keeps the secret.
+//! Nothing else gets them back: a command, URL or path the model wrote never
+//! carries a secret it didn't see, and stored calls keep the placeholders.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::inspect::{replace_spans, RedactionPolicy};
+
+/// A secret replaced for the first time, reported to the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactedSecret {
+    /// What the model sees instead, e.g. `[SECRET_1]`.
+    pub placeholder: String,
+    /// What matched, e.g. "`ghp_` token" or "DATABASE_PASSWORD value".
+    pub kind: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SecretScrubber {
+    policy: RedactionPolicy,
+    /// Secret value → placeholder.
+    placeholders: HashMap<String, String>,
+}
+
+impl SecretScrubber {
+    pub fn new(policy: RedactionPolicy) -> Self {
+        Self {
+            policy,
+            placeholders: HashMap::new(),
+        }
+    }
+
+    /// Distinct secrets replaced so far.
+    pub fn len(&self) -> usize {
+        self.placeholders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.placeholders.is_empty()
+    }
+
+    /// Placeholder for `secret`, numbering it (and adding it to `found`) the
+    /// first time it is seen.
+    fn placeholder(&mut self, secret: &str, kind: &str, found: &mut Vec<RedactedSecret>) -> String {
+        if let Some(placeholder) = self.placeholders.get(secret) {
+            return placeholder.clone();
+        }
+        let placeholder = format!("[SECRET_{}]", self.placeholders.len() + 1);
+        self.placeholders
+            .insert(secret.to_string(), placeholder.clone());
+        found.push(RedactedSecret {
+            placeholder: placeholder.clone(),
+            kind: kind.to_string(),
+        });
+        placeholder
+    }
+
+    /// `text` with each secret replaced by its placeholder.
+    pub fn scrub_text(&mut self, text: &str, found: &mut Vec<RedactedSecret>) -> String {
+        let spans = self.policy.secret_spans(text);
+        if spans.is_empty() {
+            return text.to_string();
+        }
+        replace_spans(text, &spans, |span| {
+            self.placeholder(&text[span.start..span.end], &span.kind, found)
+        })
+    }
+
+    /// Scrub every string in `value`; string values under secret keys
+    /// (`api_key`, `password`, …) are replaced whole.
+    pub fn scrub_value(&mut self, value: &mut serde_json::Value, found: &mut Vec<RedactedSecret>) {
+        match value {
+            serde_json::Value::String(s) => *s = self.scrub_text(s, found),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.scrub_value(item, found);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    match v {
+                        serde_json::Value::String(s)
+                            if self.policy.is_secret_key(key) && !is_placeholder(s) =>
+                        {
+                            let kind = format!("{} field", key);
+                            *s = self.placeholder(s, &kind, found);
+                        }
+                        _ => self.scrub_value(v, found),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The arguments to run a `tool_name` call with: the file content an
+    /// `edit_file` or `create_file` call writes gets its placeholders put back
+    /// to their secrets; every other argument and tool is left as written.
+    pub fn restore_file_content(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+    ) -> serde_json::Value {
+        let mut args = args.clone();
+        let fields: &[&str] = match tool_name {
+            "edit_file" => &["old_string", "new_string"],
+            "create_file" => &["content"],
+            _ => return args,
+        };
+        if self.placeholders.is_empty() {
+            return args;
+        }
+        let restore = |object: &mut serde_json::Value| {
+            for field in fields {
+                if let Some(serde_json::Value::String(s)) = object.get_mut(*field) {
+                    *s = self.restore_text(s);
+                }
+            }
+        };
+        restore(&mut args);
+        if let Some(serde_json::Value::Array(edits)) = args.get_mut("edits") {
+            edits.iter_mut().for_each(restore);
+        }
+        args
+    }
+
+    /// `text` with each placeholder this scrubber handed out put back to its
+    /// secret. Unknown placeholders are left as they are.
+    fn restore_text(&self, text: &str) -> String {
+        let mut restored = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("[SECRET_") {
+            restored.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let secret = rest
+                .find(']')
+                .and_then(|end| Some((self.secret(&rest[..=end])?, end)));
+            match secret {
+                Some((secret, end)) => {
+                    restored.push_str(secret);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    restored.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        restored.push_str(rest);
+        restored
+    }
+
+    /// The secret behind `placeholder`.
+    fn secret(&self, placeholder: &str) -> Option<&str> {
+        self.placeholders
+            .iter()
+            .find(|(_, p)| *p == placeholder)
+            .map(|(secret, _)| secret.as_str())
+    }
+}
+
+fn is_placeholder(s: &str) -> bool {
+    s.is_empty() || (s.starts_with("[SECRET_") && s.ends_with(']'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_secret_keeps_its_placeholder() {
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+        let mut found = Vec::new();
+        let text = "token ghp_abcdef1234567890 and sk-live0123456789 then ghp_abcdef1234567890";
+        assert_eq!(
+            scrubber.scrub_text(text, &mut found),
+            "token [SECRET_1] and [SECRET_2] then [SECRET_1]"
+        );
+        assert_eq!(
+            found,
+            [
+                RedactedSecret {
+                    placeholder: "[SECRET_1]".into(),
+                    kind: "`ghp_` token".into()
+                },
+                RedactedSecret {
+                    placeholder: "[SECRET_2]".into(),
+                    kind: "`sk-` token".into()
+                },
+            ]
+        );
+
+        // Seen before: same placeholder, nothing new to report.
+        let mut again = Vec::new();
+        assert_eq!(
+            scrubber.scrub_text("sk-live0123456789", &mut again),
+            "[SECRET_2]"
+        );
+        assert!(again.is_empty());
+        assert_eq!(scrubber.len(), 2);
+    }
+
+    #[test]
+    fn scrubs_env_files_and_secret_fields() {
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+        let mut found = Vec::new();
+        let env = "DATABASE_URL=postgres://localhost/app\nSTRIPE_SECRET_KEY=rk_test_51Hx9Yz\nAPI_TOKEN=\"Zq8v2LmN4pX\"\nDEBUG=true\n";
+        assert_eq!(
+            scrubber.scrub_text(env, &mut found),
+            "DATABASE_URL=postgres://localhost/app\nSTRIPE_SECRET_KEY=[SECRET_1]\nAPI_TOKEN=\"[SECRET_2]\"\nDEBUG=true\n"
+        );
+        assert_eq!(found[0].kind, "STRIPE_SECRET_KEY value");
+
+        let mut value = serde_json::json!({
+            "path": ".env",
+            "config": { "password": "hunter2hunter2", "retries": 3 },
+            "content": "Authorization: Bearer abcDEF123456789",
+        });
+        scrubber.scrub_value(&mut value, &mut found);
+        assert_eq!(value["config"]["password"], "[SECRET_3]");
+        assert_eq!(value["config"]["retries"], 3);
+        assert_eq!(value["content"], "Authorization: Bearer [SECRET_4]");
+        assert_eq!(value["path"], ".env");
+    }
+
+    #[test]
+    fn restores_placeholders_only_in_written_file_content() {
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+        let mut found = Vec::new();
+        let shown = scrubber.scrub_text(
+            "api_key: sk-live0123456789\ntoken: ghp_abcdef1234567890\n",
+            &mut found,
+        );
+        assert_eq!(shown, "api_key: [SECRET_1]\ntoken: [SECRET_2]\n");
+
+        let written = serde_json::json!({
+            "path": "config/[SECRET_1].yaml",
+            "old_string": "api_key: [SECRET_1]",
+            "new_string": "openai_api_key: [SECRET_1] # was [SECRET_9]",
+            "edits": [{ "old_string": "[SECRET_2]]", "new_string": "[SECRET_" }],
+        });
+        let args = scrubber.restore_file_content("edit_file", &written);
+        assert_eq!(args["old_string"], "api_key: sk-live0123456789");
+        assert_eq!(
+            args["new_string"],
+            "openai_api_key: sk-live0123456789 # was [SECRET_9]"
+        );
+        assert_eq!(args["edits"][0]["old_string"], "ghp_abcdef1234567890]");
+        assert_eq!(args["edits"][0]["new_string"], "[SECRET_");
+        assert_eq!(args["path"], "config/[SECRET_1].yaml");
+        // The call as written is left alone.
+        assert_eq!(written["old_string"], "api_key: [SECRET_1]");
+
+        let args = scrubber.restore_file_content(
+            "create_file",
+            &serde_json::json!({ "path": "a.env", "content": "KEY=[SECRET_2]" }),
+        );
+        assert_eq!(args["content"], "KEY=ghp_abcdef1234567890");
+
+        // Commands, URLs and other tools never get a secret back.
+        let command = serde_json::json!({ "command": "curl https://x/?k=[SECRET_1]" });
+        assert_eq!(scrubber.restore_file_content("bash", &command), command);
+        let fetch =
+            serde_json::json!({ "url": "https://x/?k=[SECRET_1]", "content": "[SECRET_1]" });
+        assert_eq!(scrubber.restore_file_content("web_fetch", &fetch), fetch);
+    }
+
+    #[test]
+    fn leaves_references_and_plain_words() {
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+        let mut found = Vec::new();
+        let code = "const API_KEY = process.env.OPENAI_API_KEY;\nGITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}\npassword: required\nMY_TOKEN=<your-token-here>\n";
+        assert_eq!(scrubber.scrub_text(code, &mut found), code);
+        assert!(found.is_empty());
+    }
+}
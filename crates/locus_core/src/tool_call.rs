@@ -67,6 +67,30 @@ impl ToolResultData {
     }
 }
 
+/// A user correction attached to a finished tool result (e.g. "this grep missed the
+/// generated code dir"). Sent from the TUI; the runtime adds it to the conversation and
+/// stores it as memory feedback.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAnnotation {
+    pub tool_use_id: String,
+    pub tool_name: String,
+    pub note: String,
+}
+
+impl ToolAnnotation {
+    pub fn new(
+        tool_use_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            tool_use_id: tool_use_id.into(),
+            tool_name: tool_name.into(),
+            note: note.into(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,16 @@
 //! LocusGraph client — wraps locus-proxy with higher-level API.
 //!
-//! Provides semantic search, memory retrieval, and event storage.
+//! Provides semantic search, memory retrieval, and event storage. Stored
+//! events also go to the local store ([crate::local]), which answers
+//! retrieval when there is no server or it cannot be reached. Its unsynced
+//! events are the outgoing queue: they survive restarts and are sent again
+//! on the next start or by [LocusGraphClient::sync]. With `queue_stores`,
+//! stores only add to that queue and a background flush sends it once
+//! 50 events are waiting or every 2 seconds.
 
 use crate::config::LocusGraphConfig;
-use crate::error::Result;
+use crate::error::{LocusGraphError, Result};
+use crate::local::{memories_markdown, LocalStore};
 use crate::types::{
     BatchContextResult, BatchResolveResult, Context, ContextDetail, ContextRelationship,
     ContextResult, ContextType, CreateEventRequest, InsightResult, InsightsOptions, LinkInfo,
@@ -18,8 +25,19 @@ use locus_proxy::{
     ResolveRequest as ProxyResolveRequest, RetrieveContextRequest, SearchContextsRequest,
     StoreEventRequest,
 };
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Memories returned by a local search when the options set no limit.
+const DEFAULT_LOCAL_LIMIT: usize = 10;
+/// Unsynced events read from the local store at a time while syncing.
+const SYNC_BATCH: usize = 100;
+/// Queued events that make the background flush send at once.
+const FLUSH_SIZE: usize = 50;
+/// Longest a queued event waits for the background flush.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Backend allows only lowercase, digits, underscore, hyphen, colon. Enforce type:name (one colon).
 fn sanitize_context_id(s: &str) -> String {
@@ -80,33 +98,80 @@ fn sanitize_context_id_list(list: Vec<String>) -> Vec<String> {
 /// - Automatic graph_id injection
 /// - Fire-and-forget storage
 /// - Memory retrieval before LLM calls
+/// - A local store that keeps memory working offline
 #[derive(Clone)]
 pub struct LocusGraphClient {
-    proxy: Arc<locus_proxy::LocusProxyClient>,
+    /// None in offline mode.
+    proxy: Option<Arc<locus_proxy::LocusProxyClient>>,
+    local: LocalStore,
+    /// Set while the local store may hold events the server has not accepted.
+    needs_sync: Arc<AtomicBool>,
+    /// Held while sending unsynced events, so no event is sent twice.
+    sync_lock: Arc<tokio::sync::Mutex<()>>,
+    flusher: Arc<Flusher>,
     config: LocusGraphConfig,
 }
 
 impl LocusGraphClient {
     /// Create a new client with the given configuration.
-    /// Ensures the parent directory of the DB path exists (e.g. project .locus).
+    /// Ensures the parent directory of the DB path exists (e.g. project .locus)
+    /// and opens the local store; online, events stored while the server was
+    /// unreachable are sent in the background.
     pub async fn new(config: LocusGraphConfig) -> Result<Self> {
         if let Some(parent) = config.db_path.parent() {
             let _ = tokio::fs::create_dir_all(parent).await;
         }
-        let proxy_config = locus_proxy::LocusProxyConfig::new(
-            config.grpc_endpoint.clone(),
-            config.agent_secret.clone(),
-            config.db_path.clone(),
-        )
-        .cache_reads(config.cache_reads)
-        .queue_stores(config.queue_stores);
-
-        let proxy = locus_proxy::LocusProxyClient::new(proxy_config).await?;
-
-        Ok(Self {
-            proxy: Arc::new(proxy),
-            config,
+        let local_path = config.local_db_path();
+        let retention = config.retention.clone();
+        let (local, last_id) = blocking(move || {
+            let local = LocalStore::open(&local_path)?.with_retention(retention);
+            let last_id = local.last_id()?;
+            Ok((local, last_id))
         })
+        .await?;
+
+        let proxy = if config.offline {
+            info!(
+                "LocusGraph offline: memory stays in {}",
+                local.path().display()
+            );
+            None
+        } else {
+            let proxy_config = locus_proxy::LocusProxyConfig::new(
+                config.grpc_endpoint.clone(),
+                config.agent_secret.clone(),
+                config.db_path.clone(),
+            )
+            .cache_reads(config.cache_reads)
+            // Stores are queued in the local store, which outlives the process
+            .queue_stores(false);
+            Some(Arc::new(
+                locus_proxy::LocusProxyClient::new(proxy_config).await?,
+            ))
+        };
+
+        let client = Self {
+            proxy,
+            local,
+            needs_sync: Arc::new(AtomicBool::new(true)),
+            sync_lock: Arc::new(tokio::sync::Mutex::new(())),
+            flusher: Arc::new(Flusher::default()),
+            config,
+        };
+        client.sync_in_background(last_id + 1);
+        if client.proxy.is_some() && client.config.queue_stores {
+            tokio::spawn(client.clone().flush_loop());
+        }
+        Ok(client)
+    }
+
+    /// True when there is no server and memory is local only.
+    pub fn is_offline(&self) -> bool {
+        self.proxy.is_none()
+    }
+
+    fn proxy(&self) -> Result<&locus_proxy::LocusProxyClient> {
+        self.proxy.as_deref().ok_or(LocusGraphError::Offline)
     }
 
     /// Get the graph ID for this client.
@@ -123,8 +188,7 @@ impl LocusGraphClient {
             target: "locus.trace",
             message = %format!("LocusGraph store_event\n{}", body)
         );
-        let request = self.build_store_request(event);
-        match self.proxy.store_event(request).await {
+        match self.store_event_result(event).await {
             Ok(_) => {
                 debug!("Event stored successfully");
                 true
@@ -136,11 +200,154 @@ impl LocusGraphClient {
         }
     }
 
-    /// Store a memory event and return the result.
+    /// Store a memory event and return its id: the server's, or `local-<n>`
+    /// while only the local store has it (queued with `queue_stores`, offline,
+    /// or the server could not be reached; it is sent later by [Self::sync]).
     pub async fn store_event_result(&self, event: CreateEventRequest) -> Result<String> {
         let request = self.build_store_request(event);
-        let response = self.proxy.store_event(request).await?;
-        Ok(response.event_id)
+        let local = self.local.clone();
+        let (inserted, request) = blocking(move || {
+            let inserted = local.insert(&request, false)?;
+            Ok((inserted, request))
+        })
+        .await?;
+        let id = inserted.id;
+        if inserted.duplicate {
+            debug!("Event repeats stored event {}; not sent again", id);
+            return Ok(format!("local-{}", id));
+        }
+        let Some(proxy) = &self.proxy else {
+            return Ok(format!("local-{}", id));
+        };
+        if self.config.queue_stores {
+            self.queued(1);
+            return Ok(format!("local-{}", id));
+        }
+        match proxy.store_event(request).await {
+            Ok(response) => {
+                let local = self.local.clone();
+                blocking(move || local.mark_synced(id)).await?;
+                self.sync_in_background(id);
+                Ok(response.event_id)
+            }
+            Err(e) => {
+                warn!("LocusGraph unreachable, event kept locally: {}", e);
+                self.needs_sync.store(true, Ordering::SeqCst);
+                Ok(format!("local-{}", id))
+            }
+        }
+    }
+
+    /// Store several memory events at once: one local write, then queued for
+    /// the background flush (or sent now without `queue_stores`), in order.
+    ///
+    /// Returns `true` if the events were stored/queued, `false` on failure.
+    pub async fn store_events_batch(&self, events: Vec<CreateEventRequest>) -> bool {
+        if events.is_empty() {
+            return true;
+        }
+        let count = events.len();
+        let requests: Vec<StoreEventRequest> = events
+            .into_iter()
+            .map(|event| self.build_store_request(event))
+            .collect();
+        let local = self.local.clone();
+        if let Err(e) = blocking(move || local.insert_many(&requests)).await {
+            warn!("Failed to store {} events: {}", count, e);
+            return false;
+        }
+        debug!("Stored {} events", count);
+        if self.proxy.is_none() {
+            return true;
+        }
+        if self.config.queue_stores {
+            self.queued(count);
+        } else if let Err(e) = self.sync().await {
+            warn!("LocusGraph unreachable, events kept locally: {}", e);
+            self.needs_sync.store(true, Ordering::SeqCst);
+        }
+        true
+    }
+
+    /// Note `count` newly queued events, waking the flush once enough wait.
+    fn queued(&self, count: usize) {
+        self.needs_sync.store(true, Ordering::SeqCst);
+        let waiting = self.flusher.queued.fetch_add(count, Ordering::SeqCst) + count;
+        if waiting >= FLUSH_SIZE {
+            self.flusher.wake.notify_one();
+        }
+    }
+
+    /// Send the queue every [FLUSH_INTERVAL], or sooner when [Self::queued]
+    /// wakes it, until the last other clone of the client is dropped.
+    async fn flush_loop(self) {
+        loop {
+            let _ = tokio::time::timeout(FLUSH_INTERVAL, self.flusher.wake.notified()).await;
+            if Arc::strong_count(&self.flusher) == 1 {
+                return;
+            }
+            self.flusher.queued.store(0, Ordering::SeqCst);
+            if !self.needs_sync.swap(false, Ordering::SeqCst) {
+                continue;
+            }
+            match self.sync().await {
+                Ok(sent) => debug!("LocusGraph: flushed {} queued events", sent),
+                Err(e) => {
+                    debug!("LocusGraph flush stopped: {}", e);
+                    self.needs_sync.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+    }
+
+    /// Send the events the server has not accepted yet (stored offline or
+    /// while it was down), oldest first, stopping at the first failure.
+    /// Returns how many were sent.
+    pub async fn sync(&self) -> Result<usize> {
+        self.sync_before(i64::MAX).await
+    }
+
+    /// [Self::sync] for events stored before local id `before`, so a store
+    /// still in flight is not sent twice.
+    async fn sync_before(&self, before: i64) -> Result<usize> {
+        let proxy = self.proxy()?;
+        let _sending = self.sync_lock.lock().await;
+        let mut sent = 0;
+        loop {
+            let local = self.local.clone();
+            let batch = blocking(move || local.unsynced(SYNC_BATCH)).await?;
+            let last_batch = batch.len() < SYNC_BATCH;
+            for (id, request) in batch {
+                if id >= before {
+                    return Ok(sent);
+                }
+                proxy.store_event(request).await?;
+                let local = self.local.clone();
+                blocking(move || local.mark_synced(id)).await?;
+                sent += 1;
+            }
+            if last_batch {
+                return Ok(sent);
+            }
+        }
+    }
+
+    /// Run [Self::sync_before] on a background task when a sync is due.
+    fn sync_in_background(&self, before: i64) {
+        if self.proxy.is_none() || !self.needs_sync.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        let client = self.clone();
+        tokio::spawn(async move {
+            match client.sync_before(before).await {
+                Ok(0) => {}
+                Ok(sent) => info!("LocusGraph: synced {} locally stored events", sent),
+                Err(e) => {
+                    debug!("LocusGraph sync stopped: {}", e);
+                    client.needs_sync.store(true, Ordering::SeqCst);
+                }
+            }
+        });
     }
 
     fn build_store_request(&self, event: CreateEventRequest) -> StoreEventRequest {
@@ -160,8 +367,9 @@ impl LocusGraphClient {
 
     /// Semantic search — returns memories relevant to a query.
     ///
-    /// Called BEFORE every LLM call to inject context.
-    /// On failure, returns empty context (agent works without memory).
+    /// Called BEFORE every LLM call to inject context. Offline, or when the
+    /// server cannot be reached, the local store is searched instead; if that
+    /// fails too, returns empty context (agent works without memory).
     pub async fn retrieve_memories(
         &self,
         query: &str,
@@ -169,30 +377,78 @@ impl LocusGraphClient {
     ) -> Result<ContextResult> {
         let opts = options.unwrap_or_default();
 
-        let request = RetrieveContextRequest {
-            graph_id: self.config.graph_id.clone(),
-            query: query.to_string(),
-            limit: opts.limit,
-            context_ids: opts.context_ids,
-            context_types: opts
-                .context_types
-                .into_iter()
-                .map(|(k, v)| (k, ContextTypeFilter { names: v.names }))
-                .collect(),
-        };
+        if let Some(proxy) = &self.proxy {
+            let request = RetrieveContextRequest {
+                graph_id: self.config.graph_id.clone(),
+                query: query.to_string(),
+                limit: opts.limit,
+                context_ids: opts.context_ids.clone(),
+                context_types: opts
+                    .context_types
+                    .iter()
+                    .map(|(k, v)| {
+                        (
+                            k.clone(),
+                            ContextTypeFilter {
+                                names: v.names.clone(),
+                            },
+                        )
+                    })
+                    .collect(),
+            };
+            match proxy.retrieve_context(request).await {
+                Ok(response) => {
+                    return Ok(ContextResult {
+                        memories: response.memories,
+                        items_found: response.items_found,
+                        degraded: false,
+                        scores: Vec::new(),
+                        confidence: Vec::new(),
+                    })
+                }
+                Err(e) => warn!("Failed to retrieve memories, searching locally: {}", e),
+            }
+        }
+        if !self.config.local_reads {
+            return Ok(ContextResult {
+                memories: String::new(),
+                items_found: 0,
+                degraded: true,
+                scores: Vec::new(),
+                confidence: Vec::new(),
+            });
+        }
 
-        match self.proxy.retrieve_context(request).await {
-            Ok(response) => Ok(ContextResult {
-                memories: response.memories,
-                items_found: response.items_found,
+        let local = self.local.clone();
+        let graph_id = self.config.graph_id.clone();
+        let query = query.to_string();
+        let limit = opts.limit.map_or(DEFAULT_LOCAL_LIMIT, |l| l as usize);
+        let found = blocking(move || {
+            local.search(
+                &graph_id,
+                &query,
+                limit,
+                &opts.context_ids,
+                &opts.context_types,
+            )
+        })
+        .await;
+        match found {
+            Ok(memories) => Ok(ContextResult {
+                memories: memories_markdown(&memories),
+                items_found: memories.len() as u64,
                 degraded: false,
+                scores: memories.iter().map(|m| m.score).collect(),
+                confidence: memories.iter().map(|m| m.confidence).collect(),
             }),
             Err(e) => {
-                warn!("Failed to retrieve memories: {}", e);
+                warn!("Failed to search local memories: {}", e);
                 Ok(ContextResult {
                     memories: String::new(),
                     items_found: 0,
                     degraded: true,
+                    scores: Vec::new(),
+                    confidence: Vec::new(),
                 })
             }
         }
@@ -219,7 +475,7 @@ impl LocusGraphClient {
                 .collect(),
         };
 
-        let response = self.proxy.generate_insights(request).await?;
+        let response = self.proxy()?.generate_insights(request).await?;
 
         // Parse confidence from string
         let confidence = response
@@ -247,7 +503,7 @@ impl LocusGraphClient {
             page_size,
         };
 
-        let response = self.proxy.list_context_types(request).await?;
+        let response = self.proxy()?.list_context_types(request).await?;
 
         Ok(response
             .context_types
@@ -273,7 +529,7 @@ impl LocusGraphClient {
             page_size,
         };
 
-        let response = self.proxy.list_contexts_by_type(request).await?;
+        let response = self.proxy()?.list_contexts_by_type(request).await?;
 
         Ok(self.parse_contexts_response(response))
     }
@@ -295,7 +551,7 @@ impl LocusGraphClient {
             page_size,
         };
 
-        let response = self.proxy.search_contexts(request).await?;
+        let response = self.proxy()?.search_contexts(request).await?;
 
         Ok(self.parse_contexts_response(response))
     }
@@ -307,7 +563,7 @@ impl LocusGraphClient {
             graph_id: self.config.graph_id.clone(),
             context_id: context_id.to_string(),
         };
-        let response = self.proxy.get_context(request).await?;
+        let response = self.proxy()?.get_context(request).await?;
         Ok(self.parse_context_detail(response))
     }
 
@@ -322,7 +578,7 @@ impl LocusGraphClient {
             context_name: context_name.to_string(),
             context_type: context_type.map(|s| s.to_string()),
         };
-        let response = self.proxy.get_context_by_name(request).await?;
+        let response = self.proxy()?.get_context_by_name(request).await?;
         Ok(self.parse_context_detail(response))
     }
 
@@ -332,7 +588,7 @@ impl LocusGraphClient {
             graph_id: self.config.graph_id.clone(),
             context_ids,
         };
-        let response = self.proxy.batch_get_context(request).await?;
+        let response = self.proxy()?.batch_get_context(request).await?;
         Ok(BatchContextResult {
             contexts: response
                 .contexts
@@ -364,7 +620,7 @@ impl LocusGraphClient {
             page,
             page_size,
         };
-        let response = self.proxy.get_context_relationships(request).await?;
+        let response = self.proxy()?.get_context_relationships(request).await?;
         Ok(response
             .relationships
             .into_iter()
@@ -390,7 +646,7 @@ impl LocusGraphClient {
             context_id: context_id.to_string(),
             locus_id: locus_id.to_string(),
         };
-        let response = self.proxy.resolve(request).await?;
+        let response = self.proxy()?.resolve(request).await?;
         Ok(ResolveResult {
             context_id: response.context_id,
             locus_id: response.locus_id,
@@ -414,7 +670,7 @@ impl LocusGraphClient {
                 })
                 .collect(),
         };
-        let response = self.proxy.batch_resolve(request).await?;
+        let response = self.proxy()?.batch_resolve(request).await?;
         Ok(BatchResolveResult {
             results: response
                 .results
@@ -435,7 +691,7 @@ impl LocusGraphClient {
         let request = GetUnresolvedOverviewRequest {
             graph_id: self.config.graph_id.clone(),
         };
-        let response = self.proxy.get_unresolved_overview(request).await?;
+        let response = self.proxy()?.get_unresolved_overview(request).await?;
         Ok(UnresolvedOverview {
             total_unresolved_links: response.total_unresolved_links,
             unique_context_ids: response.unique_context_ids,
@@ -457,7 +713,7 @@ impl LocusGraphClient {
             graph_id: self.config.graph_id.clone(),
             context_id: context_id.to_string(),
         };
-        let response = self.proxy.get_unresolved_links(request).await?;
+        let response = self.proxy()?.get_unresolved_links(request).await?;
         Ok(UnresolvedLinks {
             context_id: response.context_id,
             links_count: response.links_count,
@@ -558,9 +814,14 @@ impl LocusGraphClient {
             .collect())
     }
 
-    /// Get the number of queued events waiting to be sent.
+    /// Get the number of queued events waiting to be sent: the proxy's queue
+    /// and the events only the local store has.
     pub fn queued_events_count(&self) -> Result<usize> {
-        self.proxy.queued_events_count().map_err(Into::into)
+        let queued = match &self.proxy {
+            Some(proxy) => proxy.queued_events_count()?,
+            None => 0,
+        };
+        Ok(queued + self.local.unsynced_count()?)
     }
 
     fn parse_contexts_response(&self, response: ListContextsResponse) -> Vec<Context> {
@@ -594,3 +855,64 @@ impl LocusGraphClient {
         }
     }
 }
+
+/// Wakes the background flush of queued stores.
+#[derive(Default)]
+struct Flusher {
+    /// Events queued since the last flush.
+    queued: AtomicUsize,
+    wake: tokio::sync::Notify,
+}
+
+/// Run blocking local store work off the async runtime.
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| LocusGraphError::Local(format!("task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventKind;
+    use tempfile::TempDir;
+
+    fn fact(context_id: &str, value: &str) -> CreateEventRequest {
+        CreateEventRequest::new(EventKind::Fact, serde_json::json!({ "value": value }))
+            .context_id(context_id)
+    }
+
+    #[tokio::test]
+    async fn offline_client_stores_batches_locally() {
+        let dir = TempDir::new().unwrap();
+        let config = LocusGraphConfig::new("http://127.0.0.1:1", "", "g")
+            .db_path(dir.path().join("cache.db"))
+            .offline(true);
+        let client = LocusGraphClient::new(config).await.unwrap();
+        assert!(client.is_offline());
+
+        assert!(
+            client
+                .store_events_batch(vec![
+                    fact("fact:build", "build with cargo xtask dist"),
+                    fact("fact:lint", "clippy runs with deny warnings"),
+                ])
+                .await
+        );
+        assert_eq!(
+            client
+                .store_event_result(fact("fact:ci", "ci runs clippy"))
+                .await
+                .unwrap(),
+            "local-3"
+        );
+
+        let found = client.retrieve_memories("clippy", None).await.unwrap();
+        assert_eq!(found.items_found, 2);
+        assert!(found.memories.contains("[fact:lint]"));
+        assert!(!found.degraded);
+        assert!(matches!(client.sync().await, Err(LocusGraphError::Offline)));
+    }
+}
@@ -0,0 +1,74 @@
+//! Confidence — how far a memory can be trusted, from 0 to 1.
+//!
+//! A memory starts at [BASE]. Each time it is confirmed — stored again (see
+//! [crate::retention]) or reinforced by another event — it gains confidence;
+//! each later event that contradicts it cuts it down. Confidence then decays
+//! with the time since the memory was last confirmed, halving every
+//! [HALF_LIFE_DAYS]. The local store ranks results by relevance weighted by
+//! confidence and reports it with each memory; [label] turns it into the word
+//! the runtime puts in front of the memory in the prompt.
+
+/// Confidence of a memory seen once, today, never contradicted.
+pub const BASE: f64 = 0.5;
+/// Days for an unconfirmed memory's confidence to halve.
+pub const HALF_LIFE_DAYS: f64 = 180.0;
+/// From this confidence on a memory is "confirmed".
+pub const CONFIRMED: f64 = 0.75;
+/// Below this confidence a memory is "uncertain".
+pub const LIKELY: f64 = 0.4;
+
+/// Share of the remaining doubt each confirmation removes.
+const CONFIRMATION_WEIGHT: f64 = 0.4;
+/// Share of the confidence each later contradiction keeps.
+const CONTRADICTION_KEEP: f64 = 0.3;
+
+/// Confidence of a memory confirmed `confirmations` times (not counting its
+/// first store), contradicted by `contradictions` later events and last
+/// confirmed `age_secs` ago.
+pub fn confidence(confirmations: u32, contradictions: u32, age_secs: i64) -> f64 {
+    let doubt = (1.0 - BASE) * (1.0 - CONFIRMATION_WEIGHT).powi(confirmations as i32);
+    let contradicted = CONTRADICTION_KEEP.powi(contradictions as i32);
+    let age_days = age_secs.max(0) as f64 / 86_400.0;
+    let decay = 0.5_f64.powf(age_days / HALF_LIFE_DAYS);
+    ((1.0 - doubt) * contradicted * decay).clamp(0.0, 1.0)
+}
+
+/// How to phrase a memory of `confidence` to the model.
+pub fn label(confidence: f64) -> &'static str {
+    if confidence >= CONFIRMED {
+        "confirmed"
+    } else if confidence >= LIKELY {
+        "likely"
+    } else {
+        "uncertain"
+    }
+}
+
+/// Search relevance weighted by confidence, for ranking.
+pub fn weighted(relevance: f64, confidence: f64) -> f64 {
+    relevance * (0.5 + 0.5 * confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmations_boost_and_contradictions_cut() {
+        let fresh = confidence(0, 0, 0);
+        assert_eq!(fresh, BASE);
+        assert_eq!(label(fresh), "likely");
+
+        let confirmed = confidence(2, 0, 0);
+        assert!(confirmed > fresh);
+        assert_eq!(label(confirmed), "confirmed");
+
+        let contradicted = confidence(2, 1, 0);
+        assert!(contradicted < fresh);
+        assert_eq!(label(contradicted), "uncertain");
+
+        let year_old = confidence(0, 0, 2 * 180 * 86_400);
+        assert!((year_old - BASE / 4.0).abs() < 1e-9);
+        assert!(weighted(0.8, confirmed) > weighted(0.8, fresh));
+    }
+}
@@ -4,6 +4,8 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::retention::RetentionPolicy;
+
 /// Default path for cache/queue DB when not overridden by LOCUSGRAPH_DB_PATH.
 /// Prefers repo .locus when running inside a git repo (walk up from cwd for .git),
 /// else ~/.locus/locus_graph_cache.db, else temp dir.
@@ -53,20 +55,34 @@ pub struct LocusGraphConfig {
     pub db_path: PathBuf,
     /// Whether to use cache for read operations
     pub cache_reads: bool,
-    /// Whether to queue store_event and send in background
+    /// Whether store_event only queues the event in the local store and
+    /// sends it in the background (the queue survives restarts)
     pub queue_stores: bool,
+    /// Whether retrieve_memories searches the local store when the server
+    /// is offline or fails
+    pub local_reads: bool,
+    /// No server: memories are stored and searched only in the local store
+    /// (see [crate::local])
+    pub offline: bool,
+    /// How long local memories of each kind are kept (see [crate::retention])
+    pub retention: RetentionPolicy,
 }
 
 impl LocusGraphConfig {
     /// Create config from environment variables.
     ///
-    /// Required: `LOCUSGRAPH_AGENT_SECRET`
+    /// Optional: `LOCUSGRAPH_AGENT_SECRET` (unset: offline, local memory only)
     /// Optional: `LOCUSGRAPH_SERVER_URL` (default: https://grpc-dev.locusgraph.com:443)
     /// Optional: `LOCUSGRAPH_GRAPH_ID` (default: locus-agent)
+    /// Optional: `LOCUSGRAPH_OFFLINE=1` (local memory only, even with a secret)
+    /// Optional: `LOCUSGRAPH_RETENTION` (TTL overrides, e.g. `action=7d,observation=never`)
     pub fn from_env() -> Result<Self, crate::error::LocusGraphError> {
-        let agent_secret = std::env::var("LOCUSGRAPH_AGENT_SECRET").map_err(|_| {
-            crate::error::LocusGraphError::Config("LOCUSGRAPH_AGENT_SECRET not set".into())
-        })?;
+        let agent_secret = std::env::var("LOCUSGRAPH_AGENT_SECRET")
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let offline = agent_secret.is_empty()
+            || std::env::var("LOCUSGRAPH_OFFLINE").is_ok_and(|v| v == "1" || v == "true");
 
         let grpc_endpoint = std::env::var("LOCUSGRAPH_SERVER_URL")
             .unwrap_or_else(|_| "https://grpc-dev.locusgraph.com:443".to_string());
@@ -76,6 +92,11 @@ impl LocusGraphConfig {
 
         let db_path = default_db_path();
 
+        let retention = match std::env::var("LOCUSGRAPH_RETENTION") {
+            Ok(spec) => RetentionPolicy::parse(&spec)?,
+            Err(_) => RetentionPolicy::default(),
+        };
+
         Ok(Self {
             grpc_endpoint,
             agent_secret,
@@ -83,6 +104,9 @@ impl LocusGraphConfig {
             db_path,
             cache_reads: true,
             queue_stores: true,
+            local_reads: true,
+            offline,
+            retention,
         })
     }
 
@@ -99,6 +123,9 @@ impl LocusGraphConfig {
             db_path: default_db_path(),
             cache_reads: true,
             queue_stores: true,
+            local_reads: true,
+            offline: false,
+            retention: RetentionPolicy::default(),
         }
     }
 
@@ -119,4 +146,27 @@ impl LocusGraphConfig {
         self.queue_stores = on;
         self
     }
+
+    /// Search the local store when the server cannot answer (default: on).
+    pub fn local_reads(mut self, on: bool) -> Self {
+        self.local_reads = on;
+        self
+    }
+
+    /// Set how long local memories of each kind are kept.
+    pub fn retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention = policy;
+        self
+    }
+
+    /// Keep memory local only, without a server.
+    pub fn offline(mut self, on: bool) -> Self {
+        self.offline = on;
+        self
+    }
+
+    /// Path of the local memory DB: [crate::local::LOCAL_DB] next to `db_path`.
+    pub fn local_db_path(&self) -> PathBuf {
+        self.db_path.with_file_name(crate::local::LOCAL_DB)
+    }
 }
@@ -17,6 +17,14 @@ pub enum LocusGraphError {
     #[error("Serialization error: {0}")]
     Serde(#[from] serde_json::Error),
 
+    /// No server is configured (offline mode) and the call needs one
+    #[error("LocusGraph is offline: no server configured")]
+    Offline,
+
+    /// Error from the local memory store
+    #[error("Local memory store: {0}")]
+    Local(String),
+
     /// Generic error with message
     #[error("{0}")]
     Other(String),
@@ -1,5 +1,69 @@
-//! Context ID helpers for LocusGraph are defined in `locus_runtime::memory`.
+//! Context ID helpers for LocusGraph.
 //!
 //! Backend requires format `type:name` (e.g. fact:redis_caching). Type is aligned
 //! with `event_kind`. Runtime-specific helpers like
-//! `tool_anchor:{project_name}_{repo_hash}` live in the runtime crate.
+//! `tool_anchor:{project_name}_{repo_hash}` live in `locus_runtime::memory`.
+//!
+//! Files get a context of their own, `file:{path}` (see [file_context_id]).
+//! Events about a file — an edit, a failed call on it — are linked to it with
+//! [anchor_to_files], so a search scoped to the file finds them in later
+//! sessions, whatever the query.
+
+use crate::types::CreateEventRequest;
+
+/// Context ID of a repo-relative file path, e.g. `file:src_auth_jwt_rs` for
+/// `src/auth/jwt.rs`.
+pub fn file_context_id(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("file:{}", name)
+}
+
+/// Link `event` to the contexts of `paths` (repo-relative), keeping the links
+/// it already has.
+pub fn anchor_to_files(mut event: CreateEventRequest, paths: &[String]) -> CreateEventRequest {
+    if paths.is_empty() {
+        return event;
+    }
+    let related = event.related_to.get_or_insert_with(Vec::new);
+    for id in paths.iter().map(|path| file_context_id(path)) {
+        if !related.contains(&id) {
+            related.push(id);
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventKind;
+
+    #[test]
+    fn anchors_events_to_file_contexts() {
+        assert_eq!(file_context_id("src/auth/jwt.rs"), "file:src_auth_jwt_rs");
+        assert_eq!(file_context_id("./src\\Main.rs"), "file:src_main_rs");
+
+        let event = CreateEventRequest::new(EventKind::Action, serde_json::json!({}))
+            .related_to(vec!["fact:auth".to_string()]);
+        let paths = vec![
+            "src/auth/jwt.rs".to_string(),
+            "./src/auth/jwt.rs".to_string(),
+        ];
+        let event = anchor_to_files(event, &paths);
+        assert_eq!(
+            event.related_to.unwrap(),
+            ["fact:auth".to_string(), "file:src_auth_jwt_rs".to_string()]
+        );
+    }
+}
@@ -14,6 +14,12 @@
 //! - **Learning** — the AI improves across sessions by recalling past context
 //! - **Cross-session** — start a new session, still remember project patterns
 //! - **Semantic recall** — "how do we handle auth?" → relevant memories injected
+//! - **Offline** — with no server configured, memory lives in a local SQLite/FTS5
+//!   store under `.locus/` and is synced once a server is set up (see [local])
+//! - **Retention** — old tool-call events expire and near-duplicates are not
+//!   stored twice (see [retention])
+//! - **Confidence** — confirmed memories rank higher, contradicted ones lower,
+//!   and each carries a confidence (see [confidence])
 //!
 //! # Quick Start
 //!
@@ -49,15 +55,20 @@
 //! ```
 
 pub mod client;
+pub mod confidence;
 pub mod config;
 pub mod error;
 pub mod hooks;
+pub mod local;
+pub mod retention;
 pub mod types;
 
 // Re-export main types at crate root
 pub use client::LocusGraphClient;
 pub use config::{default_db_path, LocusGraphConfig};
 pub use error::{LocusGraphError, Result};
+pub use local::{Inserted, LocalMemory, LocalStore};
+pub use retention::{PruneReport, RetentionPolicy};
 pub use types::{
     BatchContextResult, BatchResolveResult, Context, ContextDetail, ContextRelationship,
     ContextResult, ContextType, ContextTypeFilter, CreateEventRequest, EventKind, EventLinks,
@@ -65,5 +76,5 @@ pub use types::{
     RetrieveOptions, TurnSummary, UnresolvedContextStats, UnresolvedLinks, UnresolvedOverview,
 };
 
-// Context ID helpers live in locus_runtime::memory; no context constants are
-// re-exported from this crate.
+// Runtime context ID helpers live in locus_runtime::memory; the file ones are
+// used through `hooks` and not re-exported from this crate.
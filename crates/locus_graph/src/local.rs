@@ -0,0 +1,934 @@
+//! Local memory store — SQLite + FTS5 next to the cache DB.
+//!
+//! Every stored event is written here first, so memory works with no server
+//! at all (offline mode) and survives the server being down. Events the
+//! server has not accepted yet are kept unsynced and pushed by
+//! [crate::LocusGraphClient::sync] once it is reachable again.
+//!
+//! Retrieval is full-text search ranked by BM25, scoped like the server's:
+//! an event is in scope when its context, or a context it links to, is one
+//! of the requested contexts or was pulled in by one (up to [SCOPE_DEPTH]
+//! links away). Events past their kind's TTL are not returned, and an event
+//! repeating a stored one is merged into it (see [crate::retention]). With a
+//! query, results are ranked by relevance weighted by confidence (see
+//! [crate::confidence]).
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use locus_proxy::StoreEventRequest;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::confidence;
+use crate::error::{LocusGraphError, Result};
+use crate::retention::{fingerprint, PruneReport, RetentionPolicy};
+use crate::types::ContextTypeFilter;
+
+/// Basename of the local memory DB, next to the cache DB.
+pub const LOCAL_DB: &str = "locus_memory.db";
+
+/// Links followed from the requested contexts when scoping a search.
+const SCOPE_DEPTH: usize = 3;
+/// Full-text matches considered before scope filtering.
+const MAX_CANDIDATES: usize = 500;
+/// Characters of a memory's text kept in recalled markdown.
+const MAX_MEMORY_CHARS: usize = 400;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY,
+    graph_id TEXT NOT NULL,
+    event_kind TEXT NOT NULL,
+    context_id TEXT,
+    source TEXT,
+    payload_json TEXT NOT NULL,
+    related_to TEXT NOT NULL DEFAULT '[]',
+    extends TEXT NOT NULL DEFAULT '[]',
+    reinforces TEXT NOT NULL DEFAULT '[]',
+    contradicts TEXT NOT NULL DEFAULT '[]',
+    timestamp TEXT,
+    created_at INTEGER NOT NULL,
+    synced INTEGER NOT NULL DEFAULT 0,
+    fingerprint TEXT,
+    seen INTEGER NOT NULL DEFAULT 1
+);
+CREATE INDEX IF NOT EXISTS events_unsynced ON events(synced) WHERE synced = 0;
+CREATE INDEX IF NOT EXISTS events_context ON events(graph_id, context_id);
+CREATE INDEX IF NOT EXISTS events_fingerprint ON events(graph_id, fingerprint);
+CREATE VIRTUAL TABLE IF NOT EXISTS events_fts USING fts5(
+    context_id, text, tokenize = 'porter unicode61'
+);
+";
+
+/// A memory found by [LocalStore::search].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalMemory {
+    pub context_id: Option<String>,
+    pub event_kind: String,
+    pub text: String,
+    /// Relevance to the query, 0 to 1; 0 when no query was given.
+    pub score: f64,
+    /// How far the memory can be trusted, 0 to 1 (see [crate::confidence]).
+    pub confidence: f64,
+}
+
+/// The outcome of [LocalStore::insert].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inserted {
+    /// Local id of the event, or of the stored event it repeats.
+    pub id: i64,
+    /// The event repeated a stored one and was merged into it, so there is
+    /// nothing new to send.
+    pub duplicate: bool,
+}
+
+/// The local memory DB. Cheap to clone; calls block, so async callers run
+/// them with `spawn_blocking`.
+#[derive(Clone)]
+pub struct LocalStore {
+    conn: Arc<Mutex<Connection>>,
+    path: PathBuf,
+    retention: RetentionPolicy,
+}
+
+impl LocalStore {
+    /// Open (creating if needed) the store at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| local_error("create dir", e))?;
+        }
+        let conn = Connection::open(path).map_err(|e| local_error("open", e))?;
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")
+            .map_err(|e| local_error("configure", e))?;
+        // Stores created before retention lack these columns
+        let has_fingerprint = conn
+            .prepare("SELECT fingerprint FROM events LIMIT 0")
+            .is_ok();
+        if !has_fingerprint && conn.prepare("SELECT id FROM events LIMIT 0").is_ok() {
+            conn.execute_batch(
+                "ALTER TABLE events ADD COLUMN fingerprint TEXT;
+                 ALTER TABLE events ADD COLUMN seen INTEGER NOT NULL DEFAULT 1;",
+            )
+            .map_err(|e| local_error("migrate", e))?;
+        }
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| local_error("create schema", e))?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            path: path.to_path_buf(),
+            retention: RetentionPolicy::default(),
+        })
+    }
+
+    /// Use `policy` to decide which events have expired.
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention = policy;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Save `request`, or merge it into the stored event it repeats. `synced`
+    /// marks it as already accepted by the server.
+    pub fn insert(&self, request: &StoreEventRequest, synced: bool) -> Result<Inserted> {
+        insert_event(&*self.lock()?, request, synced)
+    }
+
+    /// Save `requests` in one transaction, not yet synced; returns their
+    /// local ids in order (a repeated event gets the id it was merged into).
+    pub fn insert_many(&self, requests: &[StoreEventRequest]) -> Result<Vec<i64>> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| local_error("begin insert", e))?;
+        let ids = requests
+            .iter()
+            .map(|request| insert_event(&tx, request, false).map(|inserted| inserted.id))
+            .collect::<Result<Vec<_>>>()?;
+        tx.commit().map_err(|e| local_error("commit insert", e))?;
+        Ok(ids)
+    }
+
+    /// Up to `limit` events the server has not accepted yet, oldest first.
+    pub fn unsynced(&self, limit: usize) -> Result<Vec<(i64, StoreEventRequest)>> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, graph_id, event_kind, context_id, source, payload_json,
+                     related_to, extends, reinforces, contradicts, timestamp
+                 FROM events WHERE synced = 0 ORDER BY id LIMIT ?1",
+            )
+            .map_err(|e| local_error("read unsynced", e))?;
+        let rows = stmt
+            .query_map([limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    StoreEventRequest {
+                        graph_id: row.get(1)?,
+                        event_kind: row.get(2)?,
+                        context_id: row.get(3)?,
+                        source: row.get(4)?,
+                        payload_json: row.get(5)?,
+                        related_to: from_json(&row.get::<_, String>(6)?),
+                        extends: from_json(&row.get::<_, String>(7)?),
+                        reinforces: from_json(&row.get::<_, String>(8)?),
+                        contradicts: from_json(&row.get::<_, String>(9)?),
+                        timestamp: row.get(10)?,
+                    },
+                ))
+            })
+            .map_err(|e| local_error("read unsynced", e))?;
+        rows.collect::<std::result::Result<_, _>>()
+            .map_err(|e| local_error("read unsynced", e))
+    }
+
+    /// Record that the server accepted event `id`.
+    pub fn mark_synced(&self, id: i64) -> Result<()> {
+        self.lock()?
+            .execute("UPDATE events SET synced = 1 WHERE id = ?1", [id])
+            .map_err(|e| local_error("mark synced", e))?;
+        Ok(())
+    }
+
+    /// Stop sending the events the server has not accepted; they stay in
+    /// the store and are still searched. Returns how many there were.
+    pub fn abandon_unsynced(&self) -> Result<usize> {
+        self.lock()?
+            .execute("UPDATE events SET synced = 1 WHERE synced = 0", [])
+            .map_err(|e| local_error("abandon unsynced", e))
+    }
+
+    /// Id of the newest event; 0 when there are none.
+    pub fn last_id(&self) -> Result<i64> {
+        self.lock()?
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM events", [], |row| {
+                row.get(0)
+            })
+            .map_err(|e| local_error("read last id", e))
+    }
+
+    /// Events not accepted by the server yet.
+    pub fn unsynced_count(&self) -> Result<usize> {
+        self.lock()?
+            .query_row("SELECT COUNT(*) FROM events WHERE synced = 0", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+            .map_err(|e| local_error("count unsynced", e))
+    }
+
+    /// Memories of `graph_id` matching `query`, best first. `context_ids` and
+    /// `context_types` narrow the scope; both empty searches everything. An
+    /// empty query returns the newest memories in scope.
+    pub fn search(
+        &self,
+        graph_id: &str,
+        query: &str,
+        limit: usize,
+        context_ids: &[String],
+        context_types: &HashMap<String, ContextTypeFilter>,
+    ) -> Result<Vec<LocalMemory>> {
+        let conn = self.lock()?;
+        let scope = if context_ids.is_empty() {
+            None
+        } else {
+            Some(expand_scope(&conn, graph_id, context_ids)?)
+        };
+        let in_scope = |context_id: &Option<String>| {
+            let Some(id) = context_id else {
+                return scope.is_none() && context_types.is_empty();
+            };
+            let by_id = scope.as_ref().is_some_and(|scope| scope.contains(id));
+            let by_type = id.split_once(':').is_some_and(|(kind, name)| {
+                context_types
+                    .get(kind)
+                    .is_some_and(|f| f.names.is_empty() || f.names.iter().any(|n| n == name))
+            });
+            (scope.is_none() && context_types.is_empty()) || by_id || by_type
+        };
+
+        let match_query = fts_query(query);
+        let sql = if match_query.is_empty() {
+            "SELECT e.context_id, e.event_kind, f.text, 0.0, e.created_at, e.id, e.seen
+             FROM events e JOIN events_fts f ON f.rowid = e.id
+             WHERE e.graph_id = ?1 ORDER BY e.id DESC LIMIT ?2"
+        } else {
+            "SELECT e.context_id, e.event_kind, f.text, bm25(events_fts), e.created_at,
+                 e.id, e.seen
+             FROM events_fts f JOIN events e ON e.id = f.rowid
+             WHERE events_fts MATCH ?3 AND e.graph_id = ?1
+             ORDER BY bm25(events_fts), e.id DESC LIMIT ?2"
+        };
+        let mut stmt = conn.prepare(sql).map_err(|e| local_error("search", e))?;
+        let map = |row: &rusqlite::Row<'_>| {
+            let memory = LocalMemory {
+                context_id: row.get(0)?,
+                event_kind: row.get(1)?,
+                text: row.get(2)?,
+                score: relevance(row.get(3)?),
+                confidence: 0.0,
+            };
+            let created_at: i64 = row.get(4)?;
+            let id: i64 = row.get(5)?;
+            let seen: i64 = row.get(6)?;
+            Ok((memory, created_at, id, seen))
+        };
+        let rows = if match_query.is_empty() {
+            stmt.query_map(params![graph_id, MAX_CANDIDATES as i64], map)
+        } else {
+            stmt.query_map(params![graph_id, MAX_CANDIDATES as i64, match_query], map)
+        }
+        .map_err(|e| local_error("search", e))?;
+
+        let now = unix_now();
+        let links = LinkCounts::read(&conn, graph_id)?;
+        let mut memories = Vec::new();
+        for row in rows {
+            let (mut memory, created_at, id, seen) = row.map_err(|e| local_error("search", e))?;
+            if self
+                .retention
+                .is_expired(&memory.event_kind, created_at, now)
+                || !in_scope(&memory.context_id)
+            {
+                continue;
+            }
+            let (reinforced, contradicted) = links.of(memory.context_id.as_deref(), id);
+            memory.confidence = confidence::confidence(
+                (seen.max(1) - 1) as u32 + reinforced,
+                contradicted,
+                now - created_at,
+            );
+            memories.push(memory);
+        }
+        if !match_query.is_empty() {
+            memories.sort_by(|a, b| {
+                confidence::weighted(b.score, b.confidence)
+                    .total_cmp(&confidence::weighted(a.score, a.confidence))
+            });
+        }
+        memories.truncate(limit);
+        Ok(memories)
+    }
+
+    /// Delete the events past their kind's TTL and merge near-duplicates into
+    /// the newest of them. An expired event that others extend is kept, so
+    /// their scope stays intact. With `dry_run` nothing changes and the report
+    /// says what would be removed.
+    pub fn prune(&self, dry_run: bool) -> Result<PruneReport> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| local_error("begin prune", e))?;
+        let mut report = PruneReport::default();
+
+        // Fingerprint events stored before retention
+        let unfingerprinted: Vec<(i64, String, Option<String>, String)> = {
+            let mut stmt = tx
+                .prepare(
+                    "SELECT e.id, e.event_kind, e.context_id, f.text FROM events e
+                     JOIN events_fts f ON f.rowid = e.id WHERE e.fingerprint IS NULL",
+                )
+                .map_err(|e| local_error("prune", e))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })
+                .and_then(|rows| rows.collect::<std::result::Result<_, _>>())
+                .map_err(|e| local_error("prune", e))?;
+            rows
+        };
+        for (id, event_kind, context_id, text) in unfingerprinted {
+            tx.execute(
+                "UPDATE events SET fingerprint = ?2 WHERE id = ?1",
+                params![id, fingerprint(&event_kind, context_id.as_deref(), &text)],
+            )
+            .map_err(|e| local_error("prune", e))?;
+        }
+
+        // Older copies of a fingerprint, each with the newest copy's id
+        let repeats: Vec<(i64, i64)> = {
+            let mut stmt = tx
+                .prepare(
+                    "SELECT e.id, n.id FROM events e JOIN events n
+                     ON n.graph_id = e.graph_id AND n.fingerprint = e.fingerprint
+                     WHERE n.id = (SELECT MAX(id) FROM events m
+                         WHERE m.graph_id = e.graph_id AND m.fingerprint = e.fingerprint)
+                     AND e.id < n.id ORDER BY e.id",
+                )
+                .map_err(|e| local_error("prune", e))?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .and_then(|rows| rows.collect::<std::result::Result<_, _>>())
+                .map_err(|e| local_error("prune", e))?;
+            rows
+        };
+        for (old, newest) in repeats {
+            let links = read_links(&tx, old)?;
+            merge_links(&tx, newest, &links)?;
+            tx.execute(
+                "UPDATE events SET seen = seen + (SELECT seen FROM events WHERE id = ?1)
+                 WHERE id = ?2",
+                params![old, newest],
+            )
+            .map_err(|e| local_error("prune", e))?;
+            tx.execute("DELETE FROM events WHERE id = ?1", [old])
+                .map_err(|e| local_error("prune", e))?;
+            report.duplicates += 1;
+        }
+
+        let now = unix_now();
+        for kind in ["fact", "action", "decision", "observation", "feedback"] {
+            let Some(ttl) = self.retention.ttl_of(kind) else {
+                continue;
+            };
+            report.expired += tx
+                .execute(
+                    "DELETE FROM events WHERE event_kind = ?1 AND created_at + ?2 <= ?3
+                     AND (context_id IS NULL OR NOT EXISTS (
+                         SELECT 1 FROM events o, json_each(o.extends) l
+                         WHERE l.value = events.context_id AND o.id != events.id))",
+                    params![kind, ttl.as_secs() as i64, now],
+                )
+                .map_err(|e| local_error("prune", e))?;
+        }
+        tx.execute(
+            "DELETE FROM events_fts WHERE rowid NOT IN (SELECT id FROM events)",
+            [],
+        )
+        .map_err(|e| local_error("prune", e))?;
+
+        if dry_run { tx.rollback() } else { tx.commit() }
+            .map_err(|e| local_error("finish prune", e))?;
+        Ok(report)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| LocusGraphError::Local("local memory store lock poisoned".into()))
+    }
+}
+
+/// Which contexts other events reinforce or contradict.
+struct LinkCounts {
+    /// Context id → events reinforcing it.
+    reinforced: HashMap<String, u32>,
+    /// Context id → ids of the events contradicting it.
+    contradicted: HashMap<String, Vec<i64>>,
+}
+
+impl LinkCounts {
+    fn read(conn: &Connection, graph_id: &str) -> Result<Self> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, reinforces, contradicts FROM events
+                 WHERE graph_id = ?1 AND (reinforces != '[]' OR contradicts != '[]')",
+            )
+            .map_err(|e| local_error("read links", e))?;
+        let rows = stmt
+            .query_map([graph_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    from_json(&row.get::<_, String>(1)?),
+                    from_json(&row.get::<_, String>(2)?),
+                ))
+            })
+            .map_err(|e| local_error("read links", e))?;
+        let mut counts = Self {
+            reinforced: HashMap::new(),
+            contradicted: HashMap::new(),
+        };
+        for row in rows {
+            let (id, reinforces, contradicts) = row.map_err(|e| local_error("read links", e))?;
+            for context_id in reinforces {
+                *counts.reinforced.entry(context_id).or_default() += 1;
+            }
+            for context_id in contradicts {
+                counts.contradicted.entry(context_id).or_default().push(id);
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Events reinforcing `context_id`, and those contradicting it stored
+    /// after event `id`.
+    fn of(&self, context_id: Option<&str>, id: i64) -> (u32, u32) {
+        let Some(context_id) = context_id else {
+            return (0, 0);
+        };
+        let reinforced = self.reinforced.get(context_id).copied().unwrap_or(0);
+        let contradicted = self
+            .contradicted
+            .get(context_id)
+            .map_or(0, |ids| ids.iter().filter(|&&by| by > id).count() as u32);
+        (reinforced, contradicted)
+    }
+}
+
+/// `memories` as the markdown list the server returns from a retrieve.
+pub fn memories_markdown(memories: &[LocalMemory]) -> String {
+    memories
+        .iter()
+        .map(|m| {
+            // One line per memory, so each stays one list item
+            let flat = m.text.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+            let mut text: String = flat.chars().take(MAX_MEMORY_CHARS).collect();
+            if text.len() < flat.len() {
+                text.push('…');
+            }
+            match &m.context_id {
+                Some(id) => format!("- [{}] {}", id, text),
+                None => format!("- {}", text),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A bm25 rank (negative, lower is better) as a relevance from 0 to 1.
+fn relevance(bm25: f64) -> f64 {
+    let score = (-bm25).max(0.0);
+    score / (1.0 + score)
+}
+
+/// `context_ids` plus the contexts that link to them, [SCOPE_DEPTH] deep.
+fn expand_scope(
+    conn: &Connection,
+    graph_id: &str,
+    context_ids: &[String],
+) -> Result<HashSet<String>> {
+    let mut scope: HashSet<String> = context_ids.iter().cloned().collect();
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT context_id FROM events
+             WHERE graph_id = ?1 AND context_id IS NOT NULL AND EXISTS (
+                 SELECT 1 FROM json_each(related_to) l
+                 WHERE l.value IN (SELECT value FROM json_each(?2))
+                 UNION ALL SELECT 1 FROM json_each(extends) l
+                 WHERE l.value IN (SELECT value FROM json_each(?2))
+                 UNION ALL SELECT 1 FROM json_each(reinforces) l
+                 WHERE l.value IN (SELECT value FROM json_each(?2))
+             )",
+        )
+        .map_err(|e| local_error("scope", e))?;
+    for _ in 0..SCOPE_DEPTH {
+        let current = serde_json::to_string(&scope.iter().collect::<Vec<_>>())?;
+        let linked: Vec<String> = stmt
+            .query_map(params![graph_id, current], |row| row.get(0))
+            .and_then(|rows| rows.collect())
+            .map_err(|e| local_error("scope", e))?;
+        let before = scope.len();
+        scope.extend(linked);
+        if scope.len() == before {
+            break;
+        }
+    }
+    Ok(scope)
+}
+
+/// An FTS5 query matching any word of `query`, each quoted so punctuation
+/// cannot break the syntax.
+fn fts_query(query: &str) -> String {
+    query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| format!("\"{}\"", word))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+/// The searchable text of a payload: its `kind`, then its values as
+/// `key: value` pairs.
+fn payload_text(payload_json: &str) -> String {
+    fn collect(key: &str, value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (k, v) in map {
+                    if k != "kind" {
+                        collect(k, v, out);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter().for_each(|v| collect(key, v, out)),
+            serde_json::Value::String(s) if !s.is_empty() => out.push(format!("{}: {}", key, s)),
+            serde_json::Value::Number(n) => out.push(format!("{}: {}", key, n)),
+            serde_json::Value::Bool(b) => out.push(format!("{}: {}", key, b)),
+            _ => {}
+        }
+    }
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(payload_json) else {
+        return payload_json.to_string();
+    };
+    let mut parts = Vec::new();
+    collect("value", &payload, &mut parts);
+    let text = parts.join("; ");
+    match payload.get("kind").and_then(|k| k.as_str()) {
+        Some(kind) => format!("{}: {}", kind, text),
+        None => text,
+    }
+}
+
+/// Insert one event and its search text, or merge it into the stored event
+/// with the same fingerprint: that one gets its links, is counted as seen
+/// again and starts its TTL over.
+fn insert_event(conn: &Connection, request: &StoreEventRequest, synced: bool) -> Result<Inserted> {
+    let now = unix_now();
+    let text = payload_text(&request.payload_json);
+    let fingerprint = fingerprint(&request.event_kind, request.context_id.as_deref(), &text);
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM events WHERE graph_id = ?1 AND fingerprint = ?2
+             ORDER BY id DESC LIMIT 1",
+            params![request.graph_id, fingerprint],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| local_error("find duplicate", e))?;
+    if let Some(id) = existing {
+        let links = [
+            request.related_to.clone(),
+            request.extends.clone(),
+            request.reinforces.clone(),
+            request.contradicts.clone(),
+        ];
+        merge_links(conn, id, &links)?;
+        conn.execute(
+            "UPDATE events SET seen = seen + 1, created_at = ?2 WHERE id = ?1",
+            params![id, now],
+        )
+        .map_err(|e| local_error("merge duplicate", e))?;
+        return Ok(Inserted {
+            id,
+            duplicate: true,
+        });
+    }
+
+    conn.execute(
+        "INSERT INTO events (graph_id, event_kind, context_id, source, payload_json,
+             related_to, extends, reinforces, contradicts, timestamp, created_at, synced,
+             fingerprint)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            request.graph_id,
+            request.event_kind,
+            request.context_id,
+            request.source,
+            request.payload_json,
+            to_json(&request.related_to),
+            to_json(&request.extends),
+            to_json(&request.reinforces),
+            to_json(&request.contradicts),
+            request.timestamp,
+            now,
+            synced,
+            fingerprint,
+        ],
+    )
+    .map_err(|e| local_error("insert", e))?;
+    let id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO events_fts (rowid, context_id, text) VALUES (?1, ?2, ?3)",
+        params![id, request.context_id.as_deref().unwrap_or_default(), text],
+    )
+    .map_err(|e| local_error("index", e))?;
+    Ok(Inserted {
+        id,
+        duplicate: false,
+    })
+}
+
+/// The related_to, extends, reinforces and contradicts links of event `id`.
+fn read_links(conn: &Connection, id: i64) -> Result<[Vec<String>; 4]> {
+    conn.query_row(
+        "SELECT related_to, extends, reinforces, contradicts FROM events WHERE id = ?1",
+        [id],
+        |row| {
+            Ok([
+                from_json(&row.get::<_, String>(0)?),
+                from_json(&row.get::<_, String>(1)?),
+                from_json(&row.get::<_, String>(2)?),
+                from_json(&row.get::<_, String>(3)?),
+            ])
+        },
+    )
+    .map_err(|e| local_error("read links", e))
+}
+
+/// Add `links` (as from [read_links]) to those of event `id`.
+fn merge_links(conn: &Connection, id: i64, links: &[Vec<String>; 4]) -> Result<()> {
+    let mut merged = read_links(conn, id)?;
+    for (list, new) in merged.iter_mut().zip(links) {
+        for link in new {
+            if !list.contains(link) {
+                list.push(link.clone());
+            }
+        }
+    }
+    conn.execute(
+        "UPDATE events SET related_to = ?2, extends = ?3, reinforces = ?4, contradicts = ?5
+         WHERE id = ?1",
+        params![
+            id,
+            to_json(&merged[0]),
+            to_json(&merged[1]),
+            to_json(&merged[2]),
+            to_json(&merged[3])
+        ],
+    )
+    .map_err(|e| local_error("merge links", e))?;
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn to_json(list: &[String]) -> String {
+    serde_json::to_string(list).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn from_json(text: &str) -> Vec<String> {
+    serde_json::from_str(text).unwrap_or_default()
+}
+
+fn local_error(action: &str, e: impl std::fmt::Display) -> LocusGraphError {
+    LocusGraphError::Local(format!("{}: {}", action, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventKind;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn event(context_id: &str, extends: &[&str], payload: serde_json::Value) -> StoreEventRequest {
+        StoreEventRequest {
+            graph_id: "g".to_string(),
+            event_kind: "fact".to_string(),
+            context_id: Some(context_id.to_string()),
+            source: Some("agent".to_string()),
+            payload_json: payload.to_string(),
+            related_to: Vec::new(),
+            extends: extends.iter().map(|s| s.to_string()).collect(),
+            reinforces: Vec::new(),
+            contradicts: Vec::new(),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn searches_stored_events_in_scope() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalStore::open(&dir.path().join(LOCAL_DB)).unwrap();
+        let auth = serde_json::json!({
+            "kind": "technical_fact",
+            "data": { "topic": "auth", "value": "we use JWT tokens" }
+        });
+        store
+            .insert(
+                &event("session:fix-auth", &["project:demo"], serde_json::json!({})),
+                true,
+            )
+            .unwrap();
+        store
+            .insert(&event("fact:auth", &["session:fix-auth"], auth), false)
+            .unwrap();
+        store
+            .insert(
+                &event(
+                    "fact:ci",
+                    &[],
+                    serde_json::json!({ "value": "CI runs tokens check" }),
+                ),
+                false,
+            )
+            .unwrap();
+
+        let none = HashMap::new();
+        let found = store
+            .search("g", "how do we handle JWT auth?", 5, &[], &none)
+            .unwrap();
+        assert_eq!(found[0].context_id.as_deref(), Some("fact:auth"));
+        assert_eq!(
+            found[0].text,
+            "technical_fact: topic: auth; value: we use JWT tokens"
+        );
+        assert!(found[0].score > 0.0 && found[0].score < 1.0);
+        assert!(found.windows(2).all(|w| w[0].score >= w[1].score));
+
+        // fact:auth extends a session that extends the project; fact:ci does not.
+        let scoped = store
+            .search("g", "tokens", 5, &["project:demo".to_string()], &none)
+            .unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(
+            memories_markdown(&scoped),
+            "- [fact:auth] technical_fact: topic: auth; value: we use JWT tokens"
+        );
+        assert!(store
+            .search("other", "tokens", 5, &[], &none)
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(store.unsynced_count().unwrap(), 2);
+        let pending = store.unsynced(10).unwrap();
+        assert_eq!(pending[0].1.extends, ["session:fix-auth"]);
+        store.mark_synced(pending[0].0).unwrap();
+        assert_eq!(store.unsynced_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn unsynced_events_outlive_the_store() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCAL_DB);
+        let store = LocalStore::open(&path).unwrap();
+        let note = |value: &str| serde_json::json!({ "value": value });
+        store
+            .insert(&event("fact:a", &[], note("queued first")), true)
+            .unwrap();
+        let queued = store
+            .insert(&event("fact:b", &[], note("queued second")), false)
+            .unwrap()
+            .id;
+        drop(store);
+
+        let reopened = LocalStore::open(&path).unwrap();
+        let pending = reopened.unsynced(10).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, queued);
+        assert_eq!(pending[0].1.context_id.as_deref(), Some("fact:b"));
+
+        assert_eq!(reopened.abandon_unsynced().unwrap(), 1);
+        assert_eq!(reopened.unsynced_count().unwrap(), 0);
+        assert_eq!(
+            reopened
+                .search("g", "queued", 5, &[], &HashMap::new())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn merges_repeats_and_prunes_expired_events() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalStore::open(&dir.path().join(LOCAL_DB)).unwrap();
+        let call = |ms: u64| serde_json::json!({ "kind": "tool_call", "data": { "tool": "read", "duration_ms": ms } });
+        let mut first = event("action:ab12_1_003", &["turn:1"], call(12));
+        first.event_kind = "action".into();
+        let mut again = event("action:ab12_2_007", &["turn:2"], call(40));
+        again.event_kind = "action".into();
+
+        let stored = store.insert(&first, false).unwrap();
+        let repeat = store.insert(&again, false).unwrap();
+        assert!(!stored.duplicate);
+        assert_eq!(
+            repeat,
+            Inserted {
+                id: stored.id,
+                duplicate: true
+            }
+        );
+        let pending = store.unsynced(10).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].1.extends, ["turn:1", "turn:2"]);
+
+        // Nothing is old enough to expire yet; with a zero TTL everything is.
+        let none = HashMap::new();
+        assert_eq!(store.prune(false).unwrap(), PruneReport::default());
+        let store = store.with_retention(
+            RetentionPolicy::keep_all().ttl(EventKind::Action, Some(Duration::ZERO)),
+        );
+        assert!(store.search("g", "read", 5, &[], &none).unwrap().is_empty());
+        let report = store.prune(true).unwrap();
+        assert_eq!(report.expired, 1);
+        assert_eq!(store.last_id().unwrap(), stored.id);
+        store.prune(false).unwrap();
+        assert_eq!(store.last_id().unwrap(), 0);
+    }
+
+    #[test]
+    fn facts_that_differ_in_a_number_are_both_kept() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalStore::open(&dir.path().join(LOCAL_DB)).unwrap();
+        let fact = |value: &str| serde_json::json!({ "value": value });
+        let ten = store
+            .insert(
+                &event("fact:limits", &[], fact("max upload is 10 MB")),
+                false,
+            )
+            .unwrap();
+        let twenty_five = store
+            .insert(
+                &event("fact:limits", &[], fact("max upload is 25 MB")),
+                false,
+            )
+            .unwrap();
+        assert!(!twenty_five.duplicate);
+        assert_ne!(ten.id, twenty_five.id);
+        // Same text about another context is another memory too.
+        let other = store
+            .insert(
+                &event("fact:avatars", &[], fact("max upload is 10 MB")),
+                false,
+            )
+            .unwrap();
+        assert!(!other.duplicate);
+        // A true repeat still merges.
+        let again = store
+            .insert(
+                &event("fact:limits", &[], fact("Max upload is  10 MB")),
+                false,
+            )
+            .unwrap();
+        assert_eq!(again.id, ten.id);
+        assert!(again.duplicate);
+
+        assert_eq!(store.prune(false).unwrap(), PruneReport::default());
+        let found = store
+            .search("g", "max upload", 5, &[], &HashMap::new())
+            .unwrap();
+        assert_eq!(found.len(), 3);
+    }
+
+    #[test]
+    fn contradicted_memories_rank_below_confirmed_ones() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalStore::open(&dir.path().join(LOCAL_DB)).unwrap();
+        let fact = |value: &str| serde_json::json!({ "value": value });
+        store
+            .insert(
+                &event("fact:old-cache", &[], fact("the cache uses redis")),
+                false,
+            )
+            .unwrap();
+        store
+            .insert(
+                &event("fact:new-cache", &[], fact("the cache uses memcached")),
+                false,
+            )
+            .unwrap();
+        let mut moved = event("decision:cache", &[], fact("moved from redis to memcached"));
+        moved.contradicts = vec!["fact:old-cache".to_string()];
+        moved.reinforces = vec!["fact:new-cache".to_string()];
+        store.insert(&moved, false).unwrap();
+
+        let found = store
+            .search("g", "cache uses", 5, &[], &HashMap::new())
+            .unwrap();
+        assert_eq!(found[0].context_id.as_deref(), Some("fact:new-cache"));
+        let old = found
+            .iter()
+            .find(|m| m.context_id.as_deref() == Some("fact:old-cache"))
+            .unwrap();
+        assert!(old.confidence < found[0].confidence);
+        assert_eq!(crate::confidence::label(old.confidence), "uncertain");
+    }
+}
@@ -0,0 +1,222 @@
+//! Retention — how long memories are kept, and which ones repeat others.
+//!
+//! A long-lived graph collects thousands of tool-call events that say the same
+//! thing. Two policies keep retrieval useful:
+//!
+//! - **TTL per [EventKind]**: actions and observations (tool calls, LLM calls,
+//!   errors) expire after a while; facts, decisions and feedback are kept. The
+//!   local store stops returning expired events at once and `locus graph
+//!   prune` deletes them.
+//! - **Near-duplicates**: an event whose text matches a stored one once
+//!   numbers, case and spacing are ignored (see [fingerprint]) is not stored
+//!   or sent again; the stored one is marked as seen again instead.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::error::{LocusGraphError, Result};
+use crate::types::EventKind;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long events of each kind are kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetentionPolicy {
+    /// TTL by event kind (`EventKind::as_str`); kinds not listed never expire.
+    ttl: HashMap<String, Duration>,
+}
+
+impl Default for RetentionPolicy {
+    /// Actions expire after 30 days, observations after 14; the rest are kept.
+    fn default() -> Self {
+        Self::keep_all()
+            .ttl(EventKind::Action, Some(DAY * 30))
+            .ttl(EventKind::Observation, Some(DAY * 14))
+    }
+}
+
+impl RetentionPolicy {
+    /// A policy under which nothing expires.
+    pub fn keep_all() -> Self {
+        Self {
+            ttl: HashMap::new(),
+        }
+    }
+
+    /// Keep events of `kind` for `ttl`; None keeps them forever.
+    pub fn ttl(mut self, kind: EventKind, ttl: Option<Duration>) -> Self {
+        match ttl {
+            Some(ttl) => self.ttl.insert(kind.as_str().to_string(), ttl),
+            None => self.ttl.remove(kind.as_str()),
+        };
+        self
+    }
+
+    /// TTL of events of `event_kind`, if they expire.
+    pub fn ttl_of(&self, event_kind: &str) -> Option<Duration> {
+        self.ttl.get(event_kind).copied()
+    }
+
+    /// Whether an event of `event_kind` stored at `created_at` (Unix seconds)
+    /// has expired at `now`.
+    pub fn is_expired(&self, event_kind: &str, created_at: i64, now: i64) -> bool {
+        self.ttl_of(event_kind)
+            .is_some_and(|ttl| created_at + ttl.as_secs() as i64 <= now)
+    }
+
+    /// The default policy changed by `spec`, a comma-separated list of
+    /// `kind=duration` where duration is `<N>d`, `<N>h` or `never`, e.g.
+    /// `action=7d,observation=never`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut policy = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || LocusGraphError::Config(format!("invalid retention '{}'", entry));
+            let (kind, duration) = entry.split_once('=').ok_or_else(invalid)?;
+            let kind = match kind.trim() {
+                "fact" => EventKind::Fact,
+                "action" => EventKind::Action,
+                "decision" => EventKind::Decision,
+                "observation" => EventKind::Observation,
+                "feedback" => EventKind::Feedback,
+                _ => return Err(invalid()),
+            };
+            let duration = duration.trim();
+            let ttl = if duration == "never" {
+                None
+            } else {
+                let (count, unit) = duration.split_at(duration.len().saturating_sub(1));
+                let count: u32 = count.parse().map_err(|_| invalid())?;
+                match unit {
+                    "d" => Some(DAY * count),
+                    "h" => Some(Duration::from_secs(60 * 60) * count),
+                    _ => return Err(invalid()),
+                }
+            };
+            policy = policy.ttl(kind, ttl);
+        }
+        Ok(policy)
+    }
+}
+
+/// What [crate::LocalStore::prune] removed (or would remove).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Events past their kind's TTL.
+    pub expired: usize,
+    /// Events repeating a newer one, merged into it.
+    pub duplicates: usize,
+}
+
+/// Event kinds whose repeats differ only in numbers (timings, counters).
+const NUMBER_BLIND_KINDS: &[&str] = &["action", "observation"];
+
+/// Key under which near-duplicate events match: a hash of the event kind, its
+/// context and its searchable text with case and spacing ignored. For actions
+/// and observations numbers are ignored too, so two calls of the same tool in
+/// a session that differ only in timings or counters collide (their context
+/// ids differ only in the turn and sequence numbers). A fact or decision that
+/// changes a number is a different memory.
+pub fn fingerprint(event_kind: &str, context_id: Option<&str>, text: &str) -> String {
+    let number_blind = NUMBER_BLIND_KINDS.contains(&event_kind);
+    let context_id = context_id.unwrap_or_default();
+    let mut normalized = String::with_capacity(event_kind.len() + context_id.len() + text.len());
+    normalized.push_str(event_kind);
+    normalized.push('\n');
+    let mut last = ' ';
+    for c in context_id
+        .chars()
+        .chain(['\n'])
+        .chain(text.chars())
+        .flat_map(char::to_lowercase)
+    {
+        let c = if number_blind && c.is_ascii_digit() {
+            '#'
+        } else if c.is_whitespace() {
+            ' '
+        } else {
+            c
+        };
+        // One `#` per number and one space per run of spaces
+        if !(((c == '#' && number_blind) || c == ' ') && c == last) {
+            normalized.push(c);
+        }
+        last = c;
+    }
+    // FNV-1a: stable across builds, unlike std's hasher
+    let hash = normalized
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ttl_overrides() {
+        let policy = RetentionPolicy::parse("action=7d, observation=never,fact=12h").unwrap();
+        assert_eq!(policy.ttl_of("action"), Some(DAY * 7));
+        assert_eq!(policy.ttl_of("observation"), None);
+        assert_eq!(
+            policy.ttl_of("fact"),
+            Some(Duration::from_secs(12 * 60 * 60))
+        );
+        assert!(policy.is_expired("action", 0, 7 * 86_400));
+        assert!(!policy.is_expired("decision", 0, i64::MAX / 2));
+        assert!(RetentionPolicy::parse("action=soon").is_err());
+        assert!(RetentionPolicy::parse("tool=1d").is_err());
+    }
+
+    #[test]
+    fn fingerprints_ignore_spacing_and_the_numbers_of_actions() {
+        let call = |context: &str, text: &str| fingerprint("action", Some(context), text);
+        let a = call(
+            "action:ab12_1_003",
+            "tool_call: tool: read; duration_ms: 12",
+        );
+        let b = call(
+            "action:ab12_4_017",
+            "Tool_call:  tool: read; duration_ms: 3051",
+        );
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            call(
+                "action:ab12_1_003",
+                "tool_call: tool: grep; duration_ms: 12"
+            )
+        );
+        assert_ne!(
+            a,
+            call(
+                "action:cd34_1_003",
+                "tool_call: tool: read; duration_ms: 12"
+            )
+        );
+        assert_ne!(
+            a,
+            fingerprint(
+                "observation",
+                Some("action:ab12_1_003"),
+                "tool_call: tool: read; duration_ms: 12"
+            )
+        );
+
+        let fact = |context: &str, text: &str| fingerprint("fact", Some(context), text);
+        assert_eq!(
+            fact("fact:limits", "Max  upload is 10 MB"),
+            fact("fact:limits", "max upload is 10 mb")
+        );
+        assert_ne!(
+            fact("fact:limits", "max upload is 10 MB"),
+            fact("fact:limits", "max upload is 25 MB")
+        );
+        assert_ne!(
+            fact("fact:api", "uses JWT tokens"),
+            fact("fact:web", "uses JWT tokens")
+        );
+    }
+}
@@ -131,6 +131,14 @@ pub struct ContextResult {
     pub items_found: u64,
     /// True if the server was unreachable and we returned empty results
     pub degraded: bool,
+    /// Relevance of each memory, 0 to 1 and in order, when the search
+    /// reports one (the local store does; the server does not)
+    #[serde(default)]
+    pub scores: Vec<f64>,
+    /// How far each memory can be trusted, 0 to 1 and in order, when the
+    /// search reports it (see [crate::confidence])
+    #[serde(default)]
+    pub confidence: Vec<f64>,
 }
 
 /// Result from generate_insights operation.
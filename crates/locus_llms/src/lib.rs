@@ -34,6 +34,9 @@ pub mod provider;
 pub mod providers;
 pub mod types;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 #[cfg(test)]
 mod tests;
 
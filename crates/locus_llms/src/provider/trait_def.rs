@@ -1,7 +1,7 @@
 //! Provider trait definition
 
 use crate::error::Result;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers, RateLimitSnapshot};
 use async_trait::async_trait;
 
 /// Trait for AI provider implementations
@@ -20,6 +20,11 @@ pub trait Provider: Send + Sync {
     /// Generate a streaming response
     async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream>;
 
+    /// Latest rate-limit budget reported by the provider (optional)
+    fn rate_limit(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
+
     /// List available models (optional)
     async fn list_models(&self) -> Result<Vec<String>> {
         Ok(vec![])
@@ -5,15 +5,18 @@ use super::stream::create_stream;
 use super::types::{AnthropicConfig, AnthropicResponse};
 use crate::error::{Error, Result};
 use crate::provider::Provider;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::types::{
+    GenerateRequest, GenerateResponse, GenerateStream, Headers, RateLimitSnapshot, RateLimitTracker,
+};
 use async_trait::async_trait;
+use eventsource_stream::Eventsource;
 use reqwest::Client;
-use reqwest_eventsource::EventSource;
 
 /// Anthropic provider
 pub struct AnthropicProvider {
     config: AnthropicConfig,
     client: Client,
+    rate_limits: RateLimitTracker,
 }
 
 impl AnthropicProvider {
@@ -30,7 +33,11 @@ impl AnthropicProvider {
         }
 
         let client = Client::new();
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            client,
+            rate_limits: RateLimitTracker::new(),
+        })
     }
 
     /// Create provider from environment
@@ -57,6 +64,10 @@ impl Provider for AnthropicProvider {
         self.build_headers_with_cache(custom_headers, false)
     }
 
+    fn rate_limit(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.latest()
+    }
+
     async fn list_models(&self) -> Result<Vec<String>> {
         let url = format!("{}models", self.config.base_url);
         let headers = self.build_headers(None);
@@ -111,6 +122,7 @@ impl Provider for AnthropicProvider {
             .json(&conversion_result.request)
             .send()
             .await?;
+        self.rate_limits.record(response.headers());
 
         if !response.status().is_success() {
             let status = response.status();
@@ -134,18 +146,27 @@ impl Provider for AnthropicProvider {
             conversion_result.has_cache_control,
         );
 
-        let req_builder = self
+        let response = self
             .client
             .post(&url)
             .headers(headers.to_reqwest_headers())
-            .json(&conversion_result.request);
+            .json(&conversion_result.request)
+            .send()
+            .await?;
+        self.rate_limits.record(response.headers());
 
-        let event_source = EventSource::new(req_builder)
-            .map_err(|e| Error::stream_error(format!("Failed to create event source: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::provider_error(format!(
+                "Anthropic API error {}: {}",
+                status, error_text
+            )));
+        }
 
         // Note: Streaming doesn't return warnings in the current implementation
         // Warnings would need to be communicated via the stream events
-        create_stream(event_source).await
+        create_stream(response.bytes_stream().eventsource()).await
     }
 }
 
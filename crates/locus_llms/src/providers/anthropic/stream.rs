@@ -8,8 +8,8 @@
 use super::types::{AnthropicContent, AnthropicStreamEvent};
 use crate::error::{Error, Result};
 use crate::types::{FinishReason, FinishReasonKind, GenerateStream, StreamEvent, Usage};
-use futures::stream::StreamExt;
-use reqwest_eventsource::{Event, EventSource};
+use eventsource_stream::{Event, EventStreamError};
+use futures::stream::{Stream, StreamExt};
 
 /// Track state for each content block during streaming
 #[derive(Debug, Clone)]
@@ -23,20 +23,20 @@ enum ContentBlock {
     },
 }
 
-/// Create a stream from Anthropic EventSource
-pub async fn create_stream(mut event_source: EventSource) -> Result<GenerateStream> {
+/// Create a stream from Anthropic server-sent events (HTTP status already checked by the caller)
+pub async fn create_stream<S>(events: S) -> Result<GenerateStream>
+where
+    S: Stream<Item = std::result::Result<Event, EventStreamError<reqwest::Error>>> + Send + 'static,
+{
     let stream = async_stream::stream! {
+        let mut events = Box::pin(events);
         let mut accumulated_usage = Usage::default();
         // Track content blocks by index - stores both ID and accumulated input
         let mut content_blocks: std::collections::HashMap<u32, ContentBlock> = std::collections::HashMap::new();
 
-        while let Some(event) = event_source.next().await {
+        while let Some(event) = events.next().await {
             match event {
-                Ok(Event::Open) => {
-                    // Connection opened
-                    continue;
-                }
-                Ok(Event::Message(message)) => {
+                Ok(message) => {
                     // Parse the event
                     if message.data == "[DONE]" {
                         break;
@@ -54,30 +54,12 @@ pub async fn create_stream(mut event_source: EventSource) -> Result<GenerateStre
                         }
                     }
                 }
-                Err(reqwest_eventsource::Error::StreamEnded) => {
-                    // Stream ended normally without message_stop - this is OK
-                    break;
-                }
-                Err(reqwest_eventsource::Error::InvalidStatusCode(status, response)) => {
-                    // HTTP error - try to get error body for better message
-                    let error_body = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Unable to read error body".to_string());
-                    yield Err(Error::provider_error(format!(
-                        "Anthropic API error {}: {}",
-                        status, error_body
-                    )));
-                    break;
-                }
                 Err(e) => {
                     yield Err(Error::stream_error(format!("Stream error: {}", e)));
                     break;
                 }
             }
         }
-
-        event_source.close();
     };
 
     Ok(GenerateStream::new(Box::pin(stream)))
@@ -5,10 +5,12 @@ use super::stream::create_stream;
 use super::types::{ZaiConfig, ZaiResponse};
 use crate::error::{Error, Result};
 use crate::provider::Provider;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::types::{
+    GenerateRequest, GenerateResponse, GenerateStream, Headers, RateLimitSnapshot, RateLimitTracker,
+};
 use async_trait::async_trait;
+use eventsource_stream::Eventsource;
 use reqwest::Client;
-use reqwest_eventsource::EventSource;
 
 /// Max retries for transient 429 rate-limit errors
 const MAX_RETRIES: u32 = 3;
@@ -19,6 +21,7 @@ const BASE_RETRY_DELAY_MS: u64 = 1000;
 pub struct ZaiProvider {
     config: ZaiConfig,
     client: Client,
+    rate_limits: RateLimitTracker,
 }
 
 impl ZaiProvider {
@@ -32,7 +35,11 @@ impl ZaiProvider {
         }
 
         let client = Client::new();
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            client,
+            rate_limits: RateLimitTracker::new(),
+        })
     }
 
     /// Create provider from environment
@@ -62,6 +69,10 @@ impl Provider for ZaiProvider {
         headers
     }
 
+    fn rate_limit(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.latest()
+    }
+
     async fn list_models(&self) -> Result<Vec<String>> {
         // Z.AI doesn't have a models endpoint, return known models
         Ok(vec![
@@ -88,6 +99,7 @@ impl Provider for ZaiProvider {
                 .json(&zai_request)
                 .send()
                 .await?;
+            self.rate_limits.record(response.headers());
 
             if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
                 let error_text = response.text().await.unwrap_or_default();
@@ -143,43 +155,54 @@ impl Provider for ZaiProvider {
         let headers = self.build_headers(request.options.headers.as_ref());
 
         for attempt in 0..=MAX_RETRIES {
-            let req_builder = self
+            let response = self
                 .client
                 .post(&url)
                 .headers(headers.to_reqwest_headers())
-                .json(&zai_request);
-
-            let event_source = EventSource::new(req_builder).map_err(|e| {
-                Error::stream_error(format!("Failed to create event source: {}", e))
-            })?;
-
-            match create_stream(event_source).await {
-                Ok(stream) => return Ok(stream),
-                Err(Error::ProviderError(ref msg)) if msg.contains("429") => {
-                    // Check if it's a balance error (not a transient rate limit)
-                    if msg.contains("balance") || msg.contains("recharge") || msg.contains("1113") {
-                        return Err(Error::InsufficientBalance(msg.clone()));
-                    }
-
-                    if attempt < MAX_RETRIES {
-                        let delay = BASE_RETRY_DELAY_MS * 2u64.pow(attempt);
-                        eprintln!(
-                            "[ZAI] Rate limited (429), retrying in {}ms (attempt {}/{})",
-                            delay,
-                            attempt + 1,
-                            MAX_RETRIES
-                        );
-                        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
-                        continue;
-                    }
-
-                    return Err(Error::RateLimitExceeded(format!(
-                        "Z.AI rate limit exceeded: {}",
-                        msg
-                    )));
+                .json(&zai_request)
+                .send()
+                .await?;
+            self.rate_limits.record(response.headers());
+
+            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                let error_text = response.text().await.unwrap_or_default();
+
+                // Check if it's a balance error (not a transient rate limit)
+                if error_text.contains("balance")
+                    || error_text.contains("recharge")
+                    || error_text.contains("1113")
+                {
+                    return Err(Error::InsufficientBalance(error_text));
                 }
-                other => return other,
+
+                if attempt < MAX_RETRIES {
+                    let delay = BASE_RETRY_DELAY_MS * 2u64.pow(attempt);
+                    eprintln!(
+                        "[ZAI] Rate limited (429), retrying in {}ms (attempt {}/{})",
+                        delay,
+                        attempt + 1,
+                        MAX_RETRIES
+                    );
+                    tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                    continue;
+                }
+
+                return Err(Error::RateLimitExceeded(format!(
+                    "Z.AI rate limit exceeded: {}",
+                    error_text
+                )));
             }
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(Error::provider_error(format!(
+                    "Z.AI API error {}: {}",
+                    status, error_text
+                )));
+            }
+
+            return create_stream(response.bytes_stream().eventsource()).await;
         }
 
         Err(Error::RateLimitExceeded(
@@ -10,8 +10,8 @@ use super::convert::parse_finish_reason;
 use super::types::ZaiStreamChunk;
 use crate::error::{Error, Result};
 use crate::types::{GenerateStream, StreamEvent, Usage};
-use futures::stream::StreamExt;
-use reqwest_eventsource::{Event, EventSource};
+use eventsource_stream::{Event, EventStreamError};
+use futures::stream::{Stream, StreamExt};
 
 /// Track accumulated tool call state during streaming
 #[derive(Debug, Clone)]
@@ -21,16 +21,19 @@ struct AccumulatedToolCall {
     arguments: String,
 }
 
-/// Create a stream from Z.AI EventSource
-pub async fn create_stream(mut event_source: EventSource) -> Result<GenerateStream> {
+/// Create a stream from Z.AI server-sent events (HTTP status already checked by the caller)
+pub async fn create_stream<S>(events: S) -> Result<GenerateStream>
+where
+    S: Stream<Item = std::result::Result<Event, EventStreamError<reqwest::Error>>> + Send + 'static,
+{
     let stream = async_stream::stream! {
+        let mut events = Box::pin(events);
         let mut tool_calls: std::collections::HashMap<u32, AccumulatedToolCall> =
             std::collections::HashMap::new();
 
-        while let Some(event) = event_source.next().await {
+        while let Some(event) = events.next().await {
             match event {
-                Ok(Event::Open) => continue,
-                Ok(Event::Message(message)) => {
+                Ok(message) => {
                     if message.data == "[DONE]" {
                         break;
                     }
@@ -49,25 +52,12 @@ pub async fn create_stream(mut event_source: EventSource) -> Result<GenerateStre
                         }
                     }
                 }
-                Err(reqwest_eventsource::Error::StreamEnded) => break,
-                Err(reqwest_eventsource::Error::InvalidStatusCode(status, response)) => {
-                    let error_body = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Unable to read error body".to_string());
-                    yield Err(Error::provider_error(format!(
-                        "Z.AI API error {}: {}", status, error_body
-                    )));
-                    break;
-                }
                 Err(e) => {
                     yield Err(Error::stream_error(format!("Stream error: {}", e)));
                     break;
                 }
             }
         }
-
-        event_source.close();
     };
 
     Ok(GenerateStream::new(Box::pin(stream)))
@@ -0,0 +1,279 @@
+//! A scripted provider for tests: replays recorded responses and keeps the requests it got.
+//!
+//! Built into this crate's own tests and, for other crates, behind the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! locus-llms = { path = "../locus_llms", features = ["testing"] }
+//! ```
+//!
+//! A [`Cassette`] is a JSON file with one entry per LLM call, in call order. Each entry is
+//! either shorthand text and tool calls, or the raw [`StreamEvent`]s to send:
+//!
+//! ```json
+//! {
+//!   "responses": [
+//!     { "tool_calls": [{ "name": "read", "arguments": { "path": "src/lib.rs" } }] },
+//!     { "text": "Done." },
+//!     { "events": [{ "type": "error", "message": "overloaded" }] }
+//!   ],
+//!   "fallback": { "text": "Nothing more scripted." }
+//! }
+//! ```
+//!
+//! `fallback`, when present, answers every call after the last response.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::provider::Provider;
+use crate::types::{
+    FinishReason, FinishReasonKind, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ResponseContent, StreamEvent, ToolCall, Usage,
+};
+
+/// Recorded responses, replayed one per call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub responses: Vec<MockResponse>,
+    /// Answer for calls after the last response; without one they fail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<MockResponse>,
+}
+
+impl Cassette {
+    pub fn new(responses: Vec<MockResponse>) -> Self {
+        Self {
+            responses,
+            fallback: None,
+        }
+    }
+
+    /// Answer calls past the end of the script with `response`.
+    pub fn with_fallback(mut self, response: MockResponse) -> Self {
+        self.fallback = Some(response);
+        self
+    }
+
+    /// Parse a cassette from JSON text, e.g. one bundled with `include_str!`.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Read a cassette from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::ConfigError(format!("read cassette {}: {}", path.display(), e)))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+/// One scripted LLM response.
+///
+/// With `events` set, those are streamed as is; otherwise the text and tool calls are
+/// turned into a start, deltas, tool call and finish events.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MockResponse {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<MockToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<StreamEvent>>,
+}
+
+/// A tool call in a [`MockResponse`]; the id defaults to `call_<response>_<index>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockToolCall {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl MockResponse {
+    /// A plain text answer.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Exactly these stream events.
+    pub fn events(events: Vec<StreamEvent>) -> Self {
+        Self {
+            events: Some(events),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_tool_call(mut self, name: impl Into<String>, arguments: Value) -> Self {
+        self.tool_calls.push(MockToolCall {
+            id: None,
+            name: name.into(),
+            arguments,
+        });
+        self
+    }
+
+    /// The stream events for this response, as the `index`-th call (0-based).
+    pub fn to_events(&self, index: usize) -> Vec<StreamEvent> {
+        if let Some(events) = &self.events {
+            return events.clone();
+        }
+        let id = format!("mock_{}", index);
+        let mut events = vec![StreamEvent::start(&id)];
+        if !self.text.is_empty() {
+            events.push(StreamEvent::text_delta(&id, &self.text));
+        }
+        for (i, call) in self.tool_calls.iter().enumerate() {
+            let call_id = call
+                .id
+                .clone()
+                .unwrap_or_else(|| format!("call_{}_{}", index, i));
+            events.push(StreamEvent::tool_call_start(&call_id, &call.name));
+            events.push(StreamEvent::tool_call_end(
+                call_id,
+                &call.name,
+                call.arguments.clone(),
+            ));
+        }
+        let reason = if self.tool_calls.is_empty() {
+            FinishReasonKind::Stop
+        } else {
+            FinishReasonKind::ToolCalls
+        };
+        events.push(StreamEvent::finish(
+            Usage::new(100, 20),
+            FinishReason::new(reason),
+        ));
+        events
+    }
+}
+
+/// A [`Provider`] that answers each call with the next [`MockResponse`].
+///
+/// Every request is kept for assertions on prompt construction. Calls after the last
+/// response get the cassette's fallback, or fail with [`Error::Other`] so a loop that
+/// runs longer than scripted shows up as an error instead of hanging.
+pub struct MockProvider {
+    id: String,
+    responses: Mutex<VecDeque<MockResponse>>,
+    fallback: Option<MockResponse>,
+    requests: Mutex<Vec<GenerateRequest>>,
+}
+
+impl MockProvider {
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            id: "mock".to_string(),
+            responses: Mutex::new(cassette.responses.into()),
+            fallback: cassette.fallback,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Load the cassette at `path`; panics when it is missing or invalid.
+    pub fn from_cassette(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        Self::new(
+            Cassette::load(path).unwrap_or_else(|e| panic!("cassette {}: {}", path.display(), e)),
+        )
+    }
+
+    /// Report `id` as the provider id (default `mock`).
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Requests received so far, in order.
+    pub fn requests(&self) -> Vec<GenerateRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Responses not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+
+    /// Record `request` and return the events of the next response.
+    fn next_events(&self, request: GenerateRequest) -> Result<Vec<StreamEvent>> {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push(request);
+        let index = requests.len() - 1;
+        let next = self.responses.lock().unwrap().pop_front();
+        let response = next.or_else(|| self.fallback.clone()).ok_or_else(|| {
+            Error::Other(format!(
+                "mock provider has no response for call {}",
+                index + 1
+            ))
+        })?;
+        Ok(response.to_events(index))
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    fn provider_id(&self) -> &str {
+        &self.id
+    }
+
+    fn build_headers(&self, _custom_headers: Option<&Headers>) -> Headers {
+        Headers::new()
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let mut text = String::new();
+        let mut content = Vec::new();
+        let mut usage = Usage::default();
+        let mut finish_reason = FinishReason::new(FinishReasonKind::Stop);
+        for event in self.next_events(request)? {
+            match event {
+                StreamEvent::TextDelta { delta, .. } => text.push_str(&delta),
+                StreamEvent::ToolCallEnd {
+                    id,
+                    name,
+                    arguments,
+                } => content.push(ResponseContent::ToolCall(ToolCall {
+                    id,
+                    name,
+                    arguments,
+                })),
+                StreamEvent::Finish { usage: u, reason } => {
+                    usage = u;
+                    finish_reason = reason;
+                }
+                StreamEvent::Error { message } => return Err(Error::ProviderError(message)),
+                _ => {}
+            }
+        }
+        if !text.is_empty() {
+            content.insert(0, ResponseContent::Text { text });
+        }
+        Ok(GenerateResponse {
+            content,
+            usage,
+            finish_reason,
+            metadata: None,
+            warnings: None,
+        })
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let events = self.next_events(request)?;
+        Ok(GenerateStream::new(Box::pin(
+            futures::stream::iter(events).map(Ok),
+        )))
+    }
+}
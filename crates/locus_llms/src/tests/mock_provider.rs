@@ -0,0 +1,84 @@
+use crate::provider::Provider;
+use crate::testing::{Cassette, MockProvider, MockResponse};
+use crate::types::{FinishReasonKind, GenerateRequest, Message, Role, StreamEvent};
+use futures::StreamExt;
+use serde_json::json;
+
+fn request(text: &str) -> GenerateRequest {
+    GenerateRequest::new("mock-model", vec![Message::new(Role::User, text)])
+}
+
+async fn collect(provider: &MockProvider, text: &str) -> Vec<StreamEvent> {
+    let stream = provider.stream(request(text)).await.expect("stream");
+    stream.map(|e| e.expect("event")).collect().await
+}
+
+#[tokio::test]
+async fn test_replays_responses_in_order_and_records_requests() {
+    let provider = MockProvider::new(Cassette::new(vec![
+        MockResponse::default().with_tool_call("read", json!({"path": "a.rs"})),
+        MockResponse::text("done"),
+    ]));
+
+    let first = collect(&provider, "read a.rs").await;
+    assert!(matches!(
+        &first[1],
+        StreamEvent::ToolCallStart { id, name } if id == "call_0_0" && name == "read"
+    ));
+    assert!(matches!(
+        &first[2],
+        StreamEvent::ToolCallEnd { arguments, .. } if arguments["path"] == "a.rs"
+    ));
+    assert!(matches!(
+        first.last(),
+        Some(StreamEvent::Finish { reason, .. }) if reason.unified == FinishReasonKind::ToolCalls
+    ));
+
+    let second = collect(&provider, "next").await;
+    assert!(matches!(&second[1], StreamEvent::TextDelta { delta, .. } if delta == "done"));
+
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].messages[0].text(), Some("next".to_string()));
+    assert_eq!(provider.remaining(), 0);
+    assert!(provider.stream(request("more")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_cassette_json_with_raw_events() {
+    let cassette: Cassette = serde_json::from_value(json!({
+        "responses": [
+            { "text": "hi" },
+            { "events": [{ "type": "error", "message": "overloaded" }] }
+        ]
+    }))
+    .unwrap();
+    let provider = MockProvider::new(cassette);
+
+    let response = provider.generate(request("hello")).await.unwrap();
+    assert_eq!(response.text(), "hi");
+    assert_eq!(response.finish_reason.unified, FinishReasonKind::Stop);
+
+    let events = collect(&provider, "again").await;
+    assert!(matches!(&events[..], [StreamEvent::Error { message }] if message == "overloaded"));
+}
+
+#[tokio::test]
+async fn test_fallback_answers_past_the_script() {
+    let cassette: Cassette = Cassette::from_json(
+        r#"{ "responses": [{ "text": "first" }], "fallback": { "text": "that's all" } }"#,
+    )
+    .unwrap();
+    let provider = MockProvider::new(cassette);
+
+    assert_eq!(
+        provider.generate(request("a")).await.unwrap().text(),
+        "first"
+    );
+    for _ in 0..2 {
+        let response = provider.generate(request("more")).await.unwrap();
+        assert_eq!(response.text(), "that's all");
+    }
+    assert_eq!(provider.remaining(), 0);
+    assert_eq!(provider.requests().len(), 3);
+}
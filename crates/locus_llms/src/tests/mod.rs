@@ -1,2 +1,4 @@
 #[cfg(test)]
+mod mock_provider;
+#[cfg(test)]
 mod provider_registry;
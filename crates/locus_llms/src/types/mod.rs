@@ -5,6 +5,7 @@ mod cache_validator;
 mod headers;
 mod message;
 mod options;
+mod rate_limit;
 mod request;
 mod response;
 mod stream;
@@ -27,6 +28,9 @@ pub use options::{
     AnthropicToolOptions, GenerateOptions, Tool, ToolChoice, ToolFunction, ToolProviderOptions,
 };
 
+// Rate-limit types
+pub use rate_limit::{RateLimitSnapshot, RateLimitTracker};
+
 // Request types
 pub use request::{
     AnthropicOptions, GenerateRequest, GoogleOptions, OpenAIOptions, ProviderOptions,
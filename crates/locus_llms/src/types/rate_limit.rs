@@ -0,0 +1,261 @@
+//! Rate-limit budget reported by providers in response headers
+//!
+//! - **Anthropic**: `anthropic-ratelimit-{requests,tokens}-{limit,remaining,reset}`
+//!   (reset is an RFC 3339 timestamp)
+//! - **OpenAI-compatible** (Z.AI): `x-ratelimit-{limit,remaining,reset}-{requests,tokens}`
+//!   (reset is a duration such as `1s` or `6m0s`)
+//! - `retry-after` (seconds) on 429 responses for both
+
+use reqwest::header::HeaderMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Latest rate-limit budget seen from a provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitSnapshot {
+    /// Requests allowed per window
+    pub requests_limit: Option<u64>,
+    /// Requests left in the current window
+    pub requests_remaining: Option<u64>,
+    /// Tokens allowed per window
+    pub tokens_limit: Option<u64>,
+    /// Tokens left in the current window
+    pub tokens_remaining: Option<u64>,
+    /// Time until the budget refills, measured from `observed_at`
+    pub reset_after: Option<Duration>,
+    /// Server-requested backoff from `retry-after`, measured from `observed_at`
+    pub retry_after: Option<Duration>,
+    /// When the headers were received
+    pub observed_at: Instant,
+}
+
+impl RateLimitSnapshot {
+    /// Parse rate-limit headers. Returns None when the response carries none.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let num = |names: &[&str]| names.iter().find_map(|n| header_u64(headers, n));
+        let reset = |names: &[&str]| names.iter().find_map(|n| header_reset(headers, n));
+
+        let snapshot = Self {
+            requests_limit: num(&[
+                "anthropic-ratelimit-requests-limit",
+                "x-ratelimit-limit-requests",
+            ]),
+            requests_remaining: num(&[
+                "anthropic-ratelimit-requests-remaining",
+                "x-ratelimit-remaining-requests",
+            ]),
+            tokens_limit: num(&[
+                "anthropic-ratelimit-tokens-limit",
+                "x-ratelimit-limit-tokens",
+            ]),
+            tokens_remaining: num(&[
+                "anthropic-ratelimit-tokens-remaining",
+                "x-ratelimit-remaining-tokens",
+            ]),
+            reset_after: [
+                reset(&[
+                    "anthropic-ratelimit-requests-reset",
+                    "x-ratelimit-reset-requests",
+                ]),
+                reset(&[
+                    "anthropic-ratelimit-tokens-reset",
+                    "x-ratelimit-reset-tokens",
+                ]),
+            ]
+            .into_iter()
+            .flatten()
+            .max(),
+            retry_after: header_u64(headers, "retry-after").map(Duration::from_secs),
+            observed_at: Instant::now(),
+        };
+
+        let empty = snapshot.requests_remaining.is_none()
+            && snapshot.tokens_remaining.is_none()
+            && snapshot.retry_after.is_none();
+        (!empty).then_some(snapshot)
+    }
+
+    /// Smallest remaining fraction across requests and tokens (0.0 = exhausted).
+    pub fn remaining_fraction(&self) -> Option<f64> {
+        let fraction = |remaining: Option<u64>, limit: Option<u64>| match (remaining, limit) {
+            (Some(r), Some(l)) if l > 0 => Some(r as f64 / l as f64),
+            _ => None,
+        };
+        [
+            fraction(self.requests_remaining, self.requests_limit),
+            fraction(self.tokens_remaining, self.tokens_limit),
+        ]
+        .into_iter()
+        .flatten()
+        .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// How long to wait from now before the budget is usable again, if the
+    /// provider told us. Prefers `retry-after`, falls back to the reset time.
+    pub fn wait_hint(&self) -> Option<Duration> {
+        let wait = self.retry_after.or(self.reset_after)?;
+        Some(wait.saturating_sub(self.observed_at.elapsed()))
+    }
+}
+
+/// Shared holder for the latest [RateLimitSnapshot]; providers record into it on every response.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitTracker {
+    latest: Arc<Mutex<Option<RateLimitSnapshot>>>,
+}
+
+impl RateLimitTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the budget from response headers (ignored if they carry none).
+    pub fn record(&self, headers: &HeaderMap) {
+        if let Some(snapshot) = RateLimitSnapshot::from_headers(headers)
+            && let Ok(mut latest) = self.latest.lock()
+        {
+            *latest = Some(snapshot);
+        }
+    }
+
+    /// Latest snapshot, if any response has carried rate-limit headers.
+    pub fn latest(&self) -> Option<RateLimitSnapshot> {
+        self.latest.lock().ok().and_then(|l| l.clone())
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    header_str(headers, name)?.parse().ok()
+}
+
+/// Reset header as a duration from now: RFC 3339 timestamp, bare seconds, or `1m30s` style.
+fn header_reset(headers: &HeaderMap, name: &str) -> Option<Duration> {
+    let value = header_str(headers, name)?;
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(value) {
+        let ms = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_milliseconds();
+        return Some(Duration::from_millis(ms.max(0) as u64));
+    }
+    if let Ok(secs) = value.parse::<f64>() {
+        return Some(Duration::from_secs_f64(secs.max(0.0)));
+    }
+    parse_compound_duration(value)
+}
+
+/// Parse Go-style durations used by OpenAI-compatible APIs: `20ms`, `1.5s`, `6m0s`, `1h2m`.
+fn parse_compound_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let num_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let amount: f64 = rest[..num_end].parse().ok()?;
+        rest = &rest[num_end..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        total += amount
+            * match &rest[..unit_end] {
+                "ms" => 0.001,
+                "s" => 1.0,
+                "m" => 60.0,
+                "h" => 3600.0,
+                _ => return None,
+            };
+        rest = &rest[unit_end..];
+    }
+    Some(Duration::from_secs_f64(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in pairs {
+            map.insert(
+                HeaderName::from_static(k),
+                HeaderValue::from_str(v).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn test_no_rate_limit_headers() {
+        let map = headers(&[("content-type", "application/json")]);
+        assert!(RateLimitSnapshot::from_headers(&map).is_none());
+    }
+
+    #[test]
+    fn test_anthropic_headers() {
+        let reset = (chrono::Utc::now() + chrono::Duration::seconds(30)).to_rfc3339();
+        let map = headers(&[
+            ("anthropic-ratelimit-requests-limit", "50"),
+            ("anthropic-ratelimit-requests-remaining", "5"),
+            ("anthropic-ratelimit-requests-reset", &reset),
+            ("anthropic-ratelimit-tokens-limit", "40000"),
+            ("anthropic-ratelimit-tokens-remaining", "20000"),
+        ]);
+        let snapshot = RateLimitSnapshot::from_headers(&map).unwrap();
+
+        assert_eq!(snapshot.requests_remaining, Some(5));
+        assert_eq!(snapshot.tokens_limit, Some(40000));
+        assert_eq!(snapshot.remaining_fraction(), Some(0.1));
+        let wait = snapshot.wait_hint().unwrap();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_openai_style_headers() {
+        let map = headers(&[
+            ("x-ratelimit-limit-tokens", "1000"),
+            ("x-ratelimit-remaining-tokens", "750"),
+            ("x-ratelimit-reset-tokens", "1m30s"),
+        ]);
+        let snapshot = RateLimitSnapshot::from_headers(&map).unwrap();
+
+        assert_eq!(snapshot.remaining_fraction(), Some(0.75));
+        assert_eq!(snapshot.reset_after, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_retry_after_preferred() {
+        let map = headers(&[("retry-after", "12"), ("x-ratelimit-reset-requests", "1s")]);
+        let snapshot = RateLimitSnapshot::from_headers(&map).unwrap();
+        assert!(snapshot.wait_hint().unwrap() > Duration::from_secs(11));
+    }
+
+    #[test]
+    fn test_compound_duration() {
+        assert_eq!(
+            parse_compound_duration("20ms"),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(
+            parse_compound_duration("1h2m"),
+            Some(Duration::from_secs(3720))
+        );
+        assert_eq!(parse_compound_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_tracker_keeps_latest() {
+        let tracker = RateLimitTracker::new();
+        assert!(tracker.latest().is_none());
+        tracker.record(&headers(&[("retry-after", "3")]));
+        tracker.record(&headers(&[("content-type", "text/plain")]));
+        assert_eq!(
+            tracker.latest().unwrap().retry_after,
+            Some(Duration::from_secs(3))
+        );
+    }
+}
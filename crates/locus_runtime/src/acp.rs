@@ -0,0 +1,679 @@
+//! ACP agent backed by runtime sessions (`locus acp`).
+//!
+//! Each ACP session is a [Runtime] on its own ToolBus rooted at the session's
+//! `cwd`, sharing the LocusGraph and LLM clients. A prompt runs one
+//! [Runtime::run]; its events go to the editor as `session/update`s while it
+//! runs: text and thinking chunks, tool calls (edits carry their diff), tool
+//! results and task_list plans. `ask` permission rules and edit conflicts become
+//! `session/request_permission` requests on the tool call they belong to.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use locus_core::db::TaskStatus;
+use locus_core::{
+    ApprovalDecision, ApprovalRequest, ApprovalResponse, ConflictDecision, ConflictRequest,
+    SessionEvent, SessionStatus, ToolUse,
+};
+use locus_graph::{LocusGraphClient, LocusGraphConfig};
+use locus_llms::Provider;
+use locus_toolbus::acp::protocol::{
+    AgentCapabilities, CancelNotification, ContentBlock as AcpContent, InitializeRequest,
+    InitializeResponse, NewSessionRequest, NewSessionResponse, PermissionOption,
+    PermissionOptionKind, PlanEntry, PlanEntryPriority, PlanEntryStatus, PromptCapabilities,
+    PromptRequest, PromptResponse, RequestPermissionOutcome, RequestPermissionRequest,
+    SessionNotification, SessionUpdate, StopReason, ToolCall, ToolCallContent, ToolCallLocation,
+    ToolCallStatus, ToolCallUpdate, ToolKind, PROTOCOL_VERSION,
+};
+use locus_toolbus::acp::{AcpAgent, AcpClient, AcpError};
+use locus_toolbus::{Approver, ConflictResolver, ToolBus};
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::RuntimeConfig;
+use crate::error::RuntimeError;
+use crate::runtime::Runtime;
+
+/// Tool output longer than this is cut in the text shown in the editor; the
+/// full output still goes out as `rawOutput`.
+const MAX_RESULT_TEXT: usize = 4000;
+
+/// Serves ACP sessions from runtimes.
+pub struct LocusAcpAgent {
+    client: Arc<AcpClient>,
+    /// Template for each session's runtime; `repo_root` becomes the session's cwd.
+    config: RuntimeConfig,
+    locus_graph: Arc<LocusGraphClient>,
+    llm_client: Arc<dyn Provider>,
+    sessions: Mutex<HashMap<String, Arc<AcpSession>>>,
+}
+
+struct AcpSession {
+    id: String,
+    cwd: PathBuf,
+    /// Held for the length of a prompt, so prompts to one session run one at a time.
+    state: tokio::sync::Mutex<SessionState>,
+    cancel: Mutex<Option<CancellationToken>>,
+    /// Running tool calls by tool name, so permission requests land on the right call.
+    running_tools: Arc<Mutex<HashMap<String, String>>>,
+}
+
+struct SessionState {
+    runtime: Runtime,
+    events: mpsc::Receiver<SessionEvent>,
+}
+
+impl LocusAcpAgent {
+    pub fn new(
+        client: Arc<AcpClient>,
+        config: RuntimeConfig,
+        locus_graph: Arc<LocusGraphClient>,
+        llm_client: Arc<dyn Provider>,
+    ) -> Self {
+        Self {
+            client,
+            config,
+            locus_graph,
+            llm_client,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Agent with the LocusGraph client and LLM provider configured by the environment.
+    pub async fn from_env(
+        client: Arc<AcpClient>,
+        config: RuntimeConfig,
+    ) -> Result<Self, RuntimeError> {
+        let graph_config = LocusGraphConfig::from_env()
+            .map_err(|e| RuntimeError::ConfigError(format!("LocusGraph config: {}", e)))?;
+        let locus_graph = LocusGraphClient::new(graph_config)
+            .await
+            .map_err(|e| RuntimeError::MemoryFailed(format!("LocusGraph client: {}", e)))?;
+        let llm_client = Runtime::create_provider(&config.provider)?;
+        Ok(Self::new(client, config, Arc::new(locus_graph), llm_client))
+    }
+
+    /// End every session, as `locus run` does when its session finishes.
+    pub async fn shutdown(&self) {
+        let sessions: Vec<Arc<AcpSession>> = match self.sessions.lock() {
+            Ok(mut sessions) => sessions.drain().map(|(_, session)| session).collect(),
+            Err(_) => return,
+        };
+        for session in sessions {
+            let mut state = session.state.lock().await;
+            if let Err(e) = state.runtime.shutdown().await {
+                warn!("ACP session {} shutdown failed: {}", session.id, e);
+            }
+        }
+    }
+
+    fn session(&self, session_id: &str) -> Result<Arc<AcpSession>, AcpError> {
+        self.sessions
+            .lock()
+            .ok()
+            .and_then(|sessions| sessions.get(session_id).cloned())
+            .ok_or_else(|| AcpError::invalid_params(format!("Unknown session: {}", session_id)))
+    }
+
+    fn send(&self, session_id: &str, update: SessionUpdate) {
+        let notification = SessionNotification {
+            session_id: session_id.to_string(),
+            update,
+        };
+        if let Err(e) = self.client.session_update(notification) {
+            warn!("ACP session update not sent: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl AcpAgent for LocusAcpAgent {
+    async fn initialize(&self, request: InitializeRequest) -> Result<InitializeResponse, AcpError> {
+        Ok(InitializeResponse {
+            protocol_version: request.protocol_version.min(PROTOCOL_VERSION),
+            agent_capabilities: AgentCapabilities {
+                load_session: false,
+                prompt_capabilities: PromptCapabilities {
+                    embedded_context: true,
+                    ..Default::default()
+                },
+            },
+            auth_methods: Vec::new(),
+        })
+    }
+
+    async fn new_session(
+        &self,
+        request: NewSessionRequest,
+    ) -> Result<NewSessionResponse, AcpError> {
+        if !request.cwd.is_dir() {
+            return Err(AcpError::invalid_params(format!(
+                "Not a directory: {}",
+                request.cwd.display()
+            )));
+        }
+        if !request.mcp_servers.is_empty() {
+            info!(
+                "Ignoring {} MCP servers from the client; locus uses its own configuration",
+                request.mcp_servers.len()
+            );
+        }
+        let mut config = self.config.clone();
+        config.repo_root = request.cwd.clone();
+        let toolbus = Arc::new(ToolBus::with_backend(
+            request.cwd.clone(),
+            config.execution_backend.clone(),
+        ));
+        toolbus.load_extensions().await;
+        let (event_tx, events) = mpsc::channel(256);
+        let runtime = Runtime::new_with_shared(
+            config,
+            event_tx,
+            toolbus,
+            Arc::clone(&self.locus_graph),
+            Arc::clone(&self.llm_client),
+        )
+        .await
+        .map_err(|e| AcpError::internal(e.to_string()))?;
+
+        let id = runtime.session.id.as_str().to_string();
+        let running_tools = Arc::new(Mutex::new(HashMap::new()));
+        let permissions = Arc::new(AcpPermissions {
+            client: Arc::clone(&self.client),
+            session_id: id.clone(),
+            cwd: request.cwd.clone(),
+            running_tools: Arc::clone(&running_tools),
+        });
+        runtime.set_approver(permissions.clone());
+        runtime.set_conflict_resolver(permissions);
+
+        let session = Arc::new(AcpSession {
+            id: id.clone(),
+            cwd: request.cwd,
+            state: tokio::sync::Mutex::new(SessionState { runtime, events }),
+            cancel: Mutex::new(None),
+            running_tools,
+        });
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.insert(id.clone(), session);
+        }
+        Ok(NewSessionResponse { session_id: id })
+    }
+
+    async fn prompt(&self, request: PromptRequest) -> Result<PromptResponse, AcpError> {
+        let session = self.session(&request.session_id)?;
+        let message = prompt_text(&request.prompt);
+        if message.trim().is_empty() {
+            return Err(AcpError::invalid_params("The prompt has no text"));
+        }
+
+        let mut state = session.state.lock().await;
+        let token = CancellationToken::new();
+        if let Ok(mut cancel) = session.cancel.lock() {
+            *cancel = Some(token.clone());
+        }
+        let mut updates = EventUpdates::new(&session.cwd, Arc::clone(&session.running_tools));
+        let SessionState { runtime, events } = &mut *state;
+
+        // Events first, so a tool call reaches the editor (and the running-tools
+        // map) before the tool asks for permission.
+        let result = {
+            let run = runtime.run(message, Some(token));
+            tokio::pin!(run);
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = events.recv() => {
+                        for update in updates.updates(&event) {
+                            self.send(&session.id, update);
+                        }
+                    }
+                    result = &mut run => break result,
+                }
+            }
+        };
+        while let Ok(event) = events.try_recv() {
+            for update in updates.updates(&event) {
+                self.send(&session.id, update);
+            }
+        }
+        if let Ok(mut cancel) = session.cancel.lock() {
+            *cancel = None;
+        }
+
+        let stop_reason = match result {
+            Ok(SessionStatus::Cancelled) | Err(RuntimeError::Cancelled) => StopReason::Cancelled,
+            Ok(SessionStatus::Failed { error }) => return Err(AcpError::internal(error)),
+            Ok(_) => StopReason::EndTurn,
+            Err(e) => return Err(AcpError::internal(e.to_string())),
+        };
+        Ok(PromptResponse { stop_reason })
+    }
+
+    async fn cancel(&self, notification: CancelNotification) {
+        let Ok(session) = self.session(&notification.session_id) else {
+            return;
+        };
+        let token = session.cancel.lock().ok().and_then(|cancel| cancel.clone());
+        if let Some(token) = token {
+            token.cancel();
+        }
+    }
+}
+
+/// The prompt as one user message. Embedded files are appended in full; linked
+/// files are named so the model can read them.
+fn prompt_text(prompt: &[AcpContent]) -> String {
+    let mut parts = Vec::new();
+    for block in prompt {
+        match block {
+            AcpContent::Text { text } => parts.push(text.clone()),
+            AcpContent::ResourceLink { uri, name } => {
+                parts.push(format!("[{}]({})", name, file_path(uri)))
+            }
+            AcpContent::Resource { resource } => {
+                if let Some(text) = &resource.text {
+                    parts.push(format!(
+                        "<file path=\"{}\">\n{}\n</file>",
+                        file_path(&resource.uri),
+                        text
+                    ));
+                }
+            }
+            AcpContent::Other => {}
+        }
+    }
+    parts.join("\n\n")
+}
+
+fn file_path(uri: &str) -> &str {
+    uri.strip_prefix("file://").unwrap_or(uri)
+}
+
+/// Turns the runtime's events for one prompt into ACP session updates.
+struct EventUpdates {
+    cwd: PathBuf,
+    /// Tool calls started in this prompt.
+    tools: HashMap<String, ToolUse>,
+    running_tools: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl EventUpdates {
+    fn new(cwd: &Path, running_tools: Arc<Mutex<HashMap<String, String>>>) -> Self {
+        Self {
+            cwd: cwd.to_path_buf(),
+            tools: HashMap::new(),
+            running_tools,
+        }
+    }
+
+    fn updates(&mut self, event: &SessionEvent) -> Vec<SessionUpdate> {
+        match event {
+            SessionEvent::TextDelta { text } => vec![SessionUpdate::AgentMessageChunk {
+                content: AcpContent::text(text.clone()),
+            }],
+            SessionEvent::ThinkingDelta { thinking } => vec![SessionUpdate::AgentThoughtChunk {
+                content: AcpContent::text(thinking.clone()),
+            }],
+            SessionEvent::ToolStart { tool_use } => {
+                if let Ok(mut running) = self.running_tools.lock() {
+                    running.insert(tool_use.name.clone(), tool_use.id.clone());
+                }
+                self.tools.insert(tool_use.id.clone(), tool_use.clone());
+                vec![SessionUpdate::ToolCall(ToolCall {
+                    tool_call_id: tool_use.id.clone(),
+                    title: tool_title(&tool_use.name, &tool_use.args),
+                    kind: tool_kind(&tool_use.name),
+                    status: ToolCallStatus::InProgress,
+                    content: edit_diffs(&self.cwd, &tool_use.name, &tool_use.args),
+                    locations: tool_locations(&self.cwd, &tool_use.args),
+                    raw_input: Some(tool_use.args.clone()),
+                })]
+            }
+            SessionEvent::ToolDone {
+                tool_use_id,
+                result,
+            } => {
+                let tool = self.tools.remove(tool_use_id);
+                if let (Some(tool), Ok(mut running)) = (&tool, self.running_tools.lock()) {
+                    if running.get(&tool.name) == Some(tool_use_id) {
+                        running.remove(&tool.name);
+                    }
+                }
+                // A successful edit keeps its diff; everything else shows its output.
+                let shows_diff = tool
+                    .as_ref()
+                    .is_some_and(|tool| !edit_diffs(&self.cwd, &tool.name, &tool.args).is_empty());
+                let content = if shows_diff && !result.is_error {
+                    None
+                } else {
+                    Some(vec![ToolCallContent::Content {
+                        content: AcpContent::text(result_text(&result.output)),
+                    }])
+                };
+                vec![SessionUpdate::ToolCallUpdate(ToolCallUpdate {
+                    tool_call_id: tool_use_id.clone(),
+                    status: Some(if result.is_error {
+                        ToolCallStatus::Failed
+                    } else {
+                        ToolCallStatus::Completed
+                    }),
+                    content,
+                    raw_output: Some(result.output.clone()),
+                    ..Default::default()
+                })]
+            }
+            SessionEvent::TaskListUpdated { tasks, .. } => vec![SessionUpdate::Plan {
+                entries: tasks
+                    .iter()
+                    .filter_map(|task| {
+                        let status = match task.status {
+                            TaskStatus::Pending => PlanEntryStatus::Pending,
+                            TaskStatus::InProgress => PlanEntryStatus::InProgress,
+                            TaskStatus::Done => PlanEntryStatus::Completed,
+                            TaskStatus::Cancelled => return None,
+                        };
+                        Some(PlanEntry {
+                            content: task.title.clone(),
+                            priority: PlanEntryPriority::Medium,
+                            status,
+                        })
+                    })
+                    .collect(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn tool_kind(name: &str) -> ToolKind {
+    match name {
+        "read" | "tree" | "file_history" | "git_status" | "git_diff" | "git_log" => ToolKind::Read,
+        "edit_file" | "create_file" | "undo_edit" | "format_code" | "lsp_rename" => ToolKind::Edit,
+        "grep" | "glob" | "finder" | "semantic_search" | "ast_search" | "code_nav" => {
+            ToolKind::Search
+        }
+        "bash" | "handoff" | "lint" | "git_commit" | "git_branch" => ToolKind::Execute,
+        "web_fetch" | "web_automation" => ToolKind::Fetch,
+        "task" | "task_list" | "notes" => ToolKind::Think,
+        _ => ToolKind::Other,
+    }
+}
+
+/// "bash: cargo test", "edit_file: src/lib.rs": the tool and what it works on.
+fn tool_title(name: &str, args: &JsonValue) -> String {
+    let subject = ["command", "path", "pattern", "query", "url", "description"]
+        .iter()
+        .find_map(|key| args.get(*key).and_then(|v| v.as_str()));
+    match subject {
+        Some(subject) => format!("{}: {}", name, subject),
+        None => name.to_string(),
+    }
+}
+
+fn tool_locations(cwd: &Path, args: &JsonValue) -> Vec<ToolCallLocation> {
+    args.get("path")
+        .and_then(|p| p.as_str())
+        .map(|path| ToolCallLocation {
+            path: cwd.join(path),
+            line: args
+                .get("line")
+                .or_else(|| args.get("start_line"))
+                .and_then(|l| l.as_u64())
+                .map(|l| l as u32),
+        })
+        .into_iter()
+        .collect()
+}
+
+/// The change an `edit_file` or `create_file` call proposes, as ACP diffs.
+/// Replacements show the replaced text; whole-file writes show the file as it is
+/// on disk before the call runs.
+fn edit_diffs(cwd: &Path, name: &str, args: &JsonValue) -> Vec<ToolCallContent> {
+    let Some(path) = args.get("path").and_then(|p| p.as_str()) else {
+        return Vec::new();
+    };
+    let path = cwd.join(path);
+    let current = || std::fs::read_to_string(&path).ok();
+    let text = |key: &str, args: &JsonValue| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    match name {
+        "create_file" => vec![ToolCallContent::Diff {
+            path: path.clone(),
+            old_text: current(),
+            new_text: text("content", args),
+        }],
+        "edit_file" => match args.get("edits").and_then(|e| e.as_array()) {
+            Some(edits) => edits
+                .iter()
+                .map(|edit| ToolCallContent::Diff {
+                    path: path.clone(),
+                    old_text: Some(text("old_string", edit)),
+                    new_text: text("new_string", edit),
+                })
+                .collect(),
+            None => {
+                let old = text("old_string", args);
+                let old_text = if old.is_empty() { current() } else { Some(old) };
+                vec![ToolCallContent::Diff {
+                    path: path.clone(),
+                    old_text,
+                    new_text: text("new_string", args),
+                }]
+            }
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn result_text(output: &JsonValue) -> String {
+    let text = match output {
+        JsonValue::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    };
+    if text.len() <= MAX_RESULT_TEXT {
+        return text;
+    }
+    let mut end = MAX_RESULT_TEXT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n… ({} bytes more)", &text[..end], text.len() - end)
+}
+
+/// Answers ToolBus approvals and edit conflicts by asking the editor's user.
+struct AcpPermissions {
+    client: Arc<AcpClient>,
+    session_id: String,
+    cwd: PathBuf,
+    running_tools: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl AcpPermissions {
+    /// The tool call the question is about: the running call of that tool, or a
+    /// new entry when the runtime has not reported one.
+    async fn tool_call(
+        &self,
+        tool_name: &str,
+        fallback_id: &str,
+        args: &JsonValue,
+    ) -> ToolCallUpdate {
+        // The runtime sends ToolStart and calls the ToolBus in the same poll. Yield
+        // so the prompt loop forwards the call first: the editor sees it before the
+        // question about it, and it is in `running_tools` when we look.
+        tokio::task::yield_now().await;
+        let tool_call_id = self
+            .running_tools
+            .lock()
+            .ok()
+            .and_then(|running| running.get(tool_name).cloned())
+            .unwrap_or_else(|| fallback_id.to_string());
+        ToolCallUpdate {
+            tool_call_id,
+            title: Some(tool_title(tool_name, args)),
+            kind: Some(tool_kind(tool_name)),
+            status: Some(ToolCallStatus::Pending),
+            raw_input: Some(args.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Ask, and return the chosen option ID (None when cancelled or unanswered).
+    async fn ask(
+        &self,
+        tool_call: ToolCallUpdate,
+        options: Vec<PermissionOption>,
+    ) -> Option<String> {
+        let request = RequestPermissionRequest {
+            session_id: self.session_id.clone(),
+            tool_call,
+            options,
+        };
+        match self.client.request_permission(request).await {
+            Ok(response) => match response.outcome {
+                RequestPermissionOutcome::Selected { option_id } => Some(option_id),
+                RequestPermissionOutcome::Cancelled => None,
+            },
+            Err(e) => {
+                warn!("ACP permission request failed: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Approver for AcpPermissions {
+    async fn approve(&self, request: ApprovalRequest) -> ApprovalResponse {
+        let tool_call = self
+            .tool_call(&request.tool_name, &request.id, &request.args)
+            .await;
+        let options = vec![
+            PermissionOption::new("allow", "Allow", PermissionOptionKind::AllowOnce),
+            PermissionOption::new(
+                "allow_always",
+                format!("Always allow {}", request.tool_name),
+                PermissionOptionKind::AllowAlways,
+            ),
+            PermissionOption::new("deny", "Deny", PermissionOptionKind::RejectOnce),
+        ];
+        let decision = match self.ask(tool_call, options).await.as_deref() {
+            Some("allow") => ApprovalDecision::Allow,
+            Some("allow_always") => ApprovalDecision::AllowAlways,
+            _ => ApprovalDecision::Deny,
+        };
+        ApprovalResponse::new(request.id, decision)
+    }
+}
+
+#[async_trait]
+impl ConflictResolver for AcpPermissions {
+    async fn resolve(&self, request: ConflictRequest) -> ConflictDecision {
+        let mut tool_call = self
+            .tool_call(
+                &request.tool_name,
+                &request.id,
+                &serde_json::json!({ "path": request.path }),
+            )
+            .await;
+        tool_call.title = Some(format!(
+            "{} changed since the agent read it ({} conflicting hunks)",
+            request.path, request.conflicts
+        ));
+        // What "Merge" would write, against the file as the user has it.
+        tool_call.content = Some(vec![ToolCallContent::Diff {
+            path: self.cwd.join(&request.path),
+            old_text: Some(request.user.clone()),
+            new_text: request.merged.clone(),
+        }]);
+        let options = vec![
+            PermissionOption::new(
+                "rebase",
+                "Merge both changes",
+                PermissionOptionKind::AllowOnce,
+            ),
+            PermissionOption::new(
+                "overwrite",
+                "Use the agent's version",
+                PermissionOptionKind::AllowOnce,
+            ),
+            PermissionOption::new("abort", "Keep my version", PermissionOptionKind::RejectOnce),
+        ];
+        match self.ask(tool_call, options).await.as_deref() {
+            Some("rebase") => ConflictDecision::Rebase,
+            Some("overwrite") => ConflictDecision::Overwrite,
+            _ => ConflictDecision::Abort,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::ToolResultData;
+    use serde_json::json;
+
+    #[test]
+    fn test_prompt_text_includes_embedded_files() {
+        let prompt: Vec<AcpContent> = serde_json::from_value(json!([
+            {"type": "text", "text": "Why does this fail?"},
+            {"type": "resource_link", "uri": "file:///repo/src/a.rs", "name": "a.rs"},
+            {"type": "resource", "resource": {"uri": "file:///repo/src/b.rs", "text": "fn b() {}"}},
+        ]))
+        .unwrap();
+        assert_eq!(
+            prompt_text(&prompt),
+            "Why does this fail?\n\n[a.rs](/repo/src/a.rs)\n\n<file path=\"/repo/src/b.rs\">\nfn b() {}\n</file>"
+        );
+    }
+
+    #[test]
+    fn test_edit_becomes_tool_call_with_diff() {
+        let running = Arc::new(Mutex::new(HashMap::new()));
+        let mut updates = EventUpdates::new(Path::new("/repo"), Arc::clone(&running));
+        let args = json!({"path": "src/lib.rs", "old_string": "a - b", "new_string": "a + b"});
+        let start = updates.updates(&SessionEvent::ToolStart {
+            tool_use: ToolUse::new("call_1", "edit_file", args),
+        });
+        let SessionUpdate::ToolCall(call) = &start[0] else {
+            panic!("expected a tool call, got {:?}", start);
+        };
+        assert_eq!(call.kind, ToolKind::Edit);
+        assert_eq!(call.title, "edit_file: src/lib.rs");
+        assert_eq!(
+            call.content,
+            vec![ToolCallContent::Diff {
+                path: PathBuf::from("/repo/src/lib.rs"),
+                old_text: Some("a - b".to_string()),
+                new_text: "a + b".to_string(),
+            }]
+        );
+        assert_eq!(running.lock().unwrap()["edit_file"], "call_1");
+
+        let done = updates.updates(&SessionEvent::ToolDone {
+            tool_use_id: "call_1".to_string(),
+            result: ToolResultData::success(json!({"replacements": 1}), 3),
+        });
+        let SessionUpdate::ToolCallUpdate(update) = &done[0] else {
+            panic!("expected a tool call update, got {:?}", done);
+        };
+        assert_eq!(update.status, Some(ToolCallStatus::Completed));
+        assert_eq!(update.content, None);
+        assert!(running.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_long_results_are_cut() {
+        let text = result_text(&JsonValue::String("é".repeat(MAX_RESULT_TEXT)));
+        assert!(text.len() < MAX_RESULT_TEXT + 40);
+        assert!(text.ends_with("bytes more)"));
+    }
+}
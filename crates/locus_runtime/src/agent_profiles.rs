@@ -0,0 +1,249 @@
+//! Named sub-agent profiles for the `task` tool.
+//!
+//! A `task` call may name a `profile`; its sub-agent then gets only that
+//! profile's tools (calls to any other are refused) and, optionally, another
+//! model and turn limit. Three are built in:
+//!
+//! - `searcher`: read-only tools on the provider's cheap model
+//! - `tester`: `bash` plus reading and searching files
+//! - `reviewer`: reading files plus `git_diff`, `git_log` and `git_status`
+//!
+//! `.locus/agents.toml` adds profiles or replaces built-in ones by name:
+//!
+//! ```toml
+//! [docs]
+//! description = "Reads and edits markdown"
+//! tools = ["read", "glob", "edit_file", "create_file"]
+//! model = "cheap"        # the provider's cheap model; or a model name
+//! max_turns = 15
+//!
+//! [searcher]
+//! read_only = true       # every tool that only reads, plus `tools`
+//! tools = ["web_fetch"]
+//! ```
+//!
+//! A `task` call without a profile runs a full clone of the parent, as before.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use crate::config::LlmProvider;
+
+/// Profiles file, relative to the repo root.
+pub const AGENT_PROFILES_FILE: &str = ".locus/agents.toml";
+
+/// `model` value for the provider's cheap model (see [LlmProvider::cheap_model]).
+pub const CHEAP_MODEL: &str = "cheap";
+
+/// What a sub-agent run with this profile may use.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentProfile {
+    /// What the profile is for, shown to the model with the profile's name.
+    #[serde(default)]
+    pub description: String,
+    /// Tool names the sub-agent gets.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Also give it every registered tool that only reads.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Model for the sub-agent: a name, or [CHEAP_MODEL]; the parent's when unset.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub max_turns: Option<u32>,
+}
+
+impl AgentProfile {
+    /// The model a sub-agent with this profile uses under `provider`.
+    /// [CHEAP_MODEL] falls back to `parent_model` for providers without one.
+    pub fn model_for(&self, provider: LlmProvider, parent_model: &str) -> String {
+        match self.model.as_deref() {
+            None => parent_model.to_string(),
+            Some(CHEAP_MODEL) => provider.cheap_model().unwrap_or(parent_model).to_string(),
+            Some(model) => model.to_string(),
+        }
+    }
+}
+
+/// The built-in profiles with [AGENT_PROFILES_FILE] laid over them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentProfiles {
+    profiles: BTreeMap<String, AgentProfile>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AgentProfilesError {
+    #[error("Failed to read agent profiles file: {0}")]
+    Read(#[from] std::io::Error),
+
+    #[error("Failed to parse agent profiles file: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+impl Default for AgentProfiles {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl AgentProfiles {
+    /// `searcher`, `tester` and `reviewer`.
+    pub fn builtin() -> Self {
+        let profile = |description: &str, tools: &[&str]| AgentProfile {
+            description: description.to_string(),
+            tools: tools.iter().map(|t| t.to_string()).collect(),
+            ..AgentProfile::default()
+        };
+        let profiles = BTreeMap::from([
+            (
+                "searcher".to_string(),
+                AgentProfile {
+                    read_only: true,
+                    model: Some(CHEAP_MODEL.to_string()),
+                    ..profile("Finds code and answers questions about it; read-only", &[])
+                },
+            ),
+            (
+                "tester".to_string(),
+                profile(
+                    "Runs commands and tests and reports the results",
+                    &["bash", "read", "grep", "glob"],
+                ),
+            ),
+            (
+                "reviewer".to_string(),
+                profile(
+                    "Reviews changes in the working tree and history",
+                    &["read", "grep", "git_diff", "git_log", "git_status"],
+                ),
+            ),
+        ]);
+        Self { profiles }
+    }
+
+    /// The built-in profiles plus those in `.locus/agents.toml` under
+    /// `repo_root`, which win on a name clash. A missing file adds none.
+    pub fn load(repo_root: &Path) -> Result<Self, AgentProfilesError> {
+        let mut profiles = Self::builtin();
+        let path = repo_root.join(AGENT_PROFILES_FILE);
+        if path.exists() {
+            let custom: BTreeMap<String, AgentProfile> =
+                toml::from_str(&std::fs::read_to_string(path)?)?;
+            profiles.profiles.extend(custom);
+        }
+        Ok(profiles)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AgentProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Profile names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// The profile a `task` call's `args` name, if any. Err says which
+    /// profiles there are when the name is unknown.
+    pub fn requested(&self, args: &JsonValue) -> Result<Option<(&str, &AgentProfile)>, String> {
+        let Some(name) = args.get("profile").and_then(|v| v.as_str()) else {
+            return Ok(None);
+        };
+        match self.profiles.get_key_value(name) {
+            Some((name, profile)) => Ok(Some((name.as_str(), profile))),
+            None => Err(format!(
+                "Unknown agent profile '{}'; known profiles: {}",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+
+    /// One line per profile for the `task` tool's `profile` parameter.
+    pub fn describe(&self) -> String {
+        let lines: Vec<String> = self
+            .profiles
+            .iter()
+            .map(|(name, profile)| {
+                if profile.description.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}: {}", name, profile.description)
+                }
+            })
+            .collect();
+        format!(
+            "Optional agent profile; the sub-agent gets only that profile's tools. Omit for a full agent. {}",
+            lines.join("; ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn file_profiles_add_to_and_replace_builtin_ones() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            AgentProfiles::load(dir.path()).unwrap(),
+            AgentProfiles::builtin()
+        );
+
+        std::fs::create_dir_all(dir.path().join(".locus")).unwrap();
+        std::fs::write(
+            dir.path().join(AGENT_PROFILES_FILE),
+            r#"
+            [docs]
+            description = "Edits markdown"
+            tools = ["read", "edit_file"]
+            max_turns = 5
+
+            [tester]
+            tools = ["bash"]
+            model = "my-model"
+            "#,
+        )
+        .unwrap();
+        let profiles = AgentProfiles::load(dir.path()).unwrap();
+        let names: Vec<&str> = profiles.names().collect();
+        assert_eq!(names, ["docs", "reviewer", "searcher", "tester"]);
+        assert_eq!(profiles.get("docs").unwrap().max_turns, Some(5));
+        assert_eq!(profiles.get("tester").unwrap().tools, ["bash"]);
+        assert!(profiles.describe().contains("docs: Edits markdown"));
+        let (name, _) = profiles
+            .requested(&serde_json::json!({ "profile": "docs" }))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "docs");
+        assert_eq!(profiles.requested(&serde_json::json!({})), Ok(None));
+        assert!(profiles
+            .requested(&serde_json::json!({ "profile": "nope" }))
+            .unwrap_err()
+            .contains("docs, reviewer, searcher, tester"));
+
+        std::fs::write(dir.path().join(AGENT_PROFILES_FILE), "[x]\ntool = []\n").unwrap();
+        assert!(AgentProfiles::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn cheap_model_follows_the_provider() {
+        let profiles = AgentProfiles::builtin();
+        let searcher = profiles.get("searcher").unwrap();
+        assert!(searcher.read_only);
+        assert_eq!(
+            searcher.model_for(LlmProvider::Anthropic, "claude-sonnet-4-20250514"),
+            LlmProvider::Anthropic.cheap_model().unwrap()
+        );
+        assert_eq!(searcher.model_for(LlmProvider::Ollama, "llama3"), "llama3");
+        let tester = profiles.get("tester").unwrap();
+        assert_eq!(tester.model_for(LlmProvider::ZAI, "glm-5"), "glm-5");
+    }
+}
@@ -0,0 +1,215 @@
+//! Bridges ToolBus approval requests and edit conflicts to the TUI.
+//!
+//! [ApprovalBroker] implements [Approver]: each request is sent to the TUI as
+//! [SessionEvent::ApprovalRequested] and the tool call waits until the matching
+//! [ApprovalResponse] is passed to [ApprovalBroker::resolve]; a denial's message
+//! goes back with it. If the UI goes away before answering, the call is denied.
+//!
+//! It implements [ConflictResolver] the same way: conflicts go out as
+//! [SessionEvent::ConflictDetected] and wait for a [ConflictResponse] passed to
+//! [ApprovalBroker::resolve_conflict]; without an answer the edit is aborted.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use locus_core::{
+    ApprovalDecision, ApprovalRequest, ApprovalResponse, ConflictDecision, ConflictRequest,
+    ConflictResponse, SessionEvent,
+};
+use locus_toolbus::{Approver, ConflictResolver};
+use tokio::sync::{mpsc, oneshot};
+use tracing::warn;
+
+pub struct ApprovalBroker {
+    event_tx: mpsc::Sender<SessionEvent>,
+    pending: Mutex<HashMap<String, oneshot::Sender<ApprovalResponse>>>,
+    pending_conflicts: Mutex<HashMap<String, oneshot::Sender<ConflictDecision>>>,
+}
+
+impl ApprovalBroker {
+    pub fn new(event_tx: mpsc::Sender<SessionEvent>) -> Self {
+        Self {
+            event_tx,
+            pending: Mutex::new(HashMap::new()),
+            pending_conflicts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Deliver the user's decision. Returns false if no request with that id is waiting.
+    pub fn resolve(&self, response: ApprovalResponse) -> bool {
+        let sender = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&response.id));
+        match sender {
+            Some(tx) => tx.send(response).is_ok(),
+            None => {
+                warn!("Approval response for unknown request {}", response.id);
+                false
+            }
+        }
+    }
+
+    /// Deliver the user's conflict decision. Returns false if no conflict with that id is waiting.
+    pub fn resolve_conflict(&self, response: ConflictResponse) -> bool {
+        let sender = self
+            .pending_conflicts
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&response.id));
+        match sender {
+            Some(tx) => tx.send(response.decision).is_ok(),
+            None => {
+                warn!("Conflict response for unknown request {}", response.id);
+                false
+            }
+        }
+    }
+
+    fn forget(&self, id: &str) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(id);
+        }
+    }
+
+    fn forget_conflict(&self, id: &str) {
+        if let Ok(mut pending) = self.pending_conflicts.lock() {
+            pending.remove(id);
+        }
+    }
+}
+
+#[async_trait]
+impl Approver for ApprovalBroker {
+    async fn approve(&self, request: ApprovalRequest) -> ApprovalResponse {
+        let id = request.id.clone();
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id.clone(), tx);
+        }
+
+        if self
+            .event_tx
+            .send(SessionEvent::approval_requested(request))
+            .await
+            .is_err()
+        {
+            self.forget(&id);
+            return ApprovalResponse::new(id, ApprovalDecision::Deny);
+        }
+
+        let response = rx
+            .await
+            .unwrap_or_else(|_| ApprovalResponse::new(&id, ApprovalDecision::Deny));
+        self.forget(&id);
+        response
+    }
+}
+
+#[async_trait]
+impl ConflictResolver for ApprovalBroker {
+    async fn resolve(&self, request: ConflictRequest) -> ConflictDecision {
+        let id = request.id.clone();
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending_conflicts.lock() {
+            pending.insert(id.clone(), tx);
+        }
+
+        if self
+            .event_tx
+            .send(SessionEvent::conflict_detected(request))
+            .await
+            .is_err()
+        {
+            self.forget_conflict(&id);
+            return ConflictDecision::Abort;
+        }
+
+        let decision = rx.await.unwrap_or(ConflictDecision::Abort);
+        self.forget_conflict(&id);
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::Permission;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_request_is_forwarded_and_resolved() {
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let broker = Arc::new(ApprovalBroker::new(event_tx));
+
+        let asking = Arc::clone(&broker);
+        let handle = tokio::spawn(async move {
+            asking
+                .approve(ApprovalRequest::new(
+                    "bash",
+                    Permission::Execute,
+                    serde_json::json!({"command": "ls"}),
+                ))
+                .await
+        });
+
+        let id = match event_rx.recv().await.unwrap() {
+            SessionEvent::ApprovalRequested { request } => request.id,
+            other => panic!("expected approval request, got {:?}", other),
+        };
+        let denied = ApprovalResponse::deny_with_message(&id, "list src only");
+        assert!(broker.resolve(denied.clone()));
+        assert_eq!(handle.await.unwrap(), denied);
+        assert!(!broker.resolve(ApprovalResponse::new(id, ApprovalDecision::Allow)));
+    }
+
+    #[tokio::test]
+    async fn test_conflict_is_forwarded_and_resolved() {
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let broker = Arc::new(ApprovalBroker::new(event_tx));
+
+        let asking = Arc::clone(&broker);
+        let handle = tokio::spawn(async move {
+            ConflictResolver::resolve(
+                asking.as_ref(),
+                ConflictRequest::new("edit_file", "src/lib.rs", "a\n", "b\n", "c\n"),
+            )
+            .await
+        });
+
+        let id = match event_rx.recv().await.unwrap() {
+            SessionEvent::ConflictDetected { request } => request.id,
+            other => panic!("expected conflict, got {:?}", other),
+        };
+        assert!(broker.resolve_conflict(ConflictResponse::new(&id, ConflictDecision::Rebase)));
+        assert_eq!(handle.await.unwrap(), ConflictDecision::Rebase);
+        assert!(broker.pending_conflicts.lock().unwrap().is_empty());
+
+        drop(event_rx);
+        let decision = ConflictResolver::resolve(
+            broker.as_ref(),
+            ConflictRequest::new("create_file", "a", "", "x", "y"),
+        )
+        .await;
+        assert_eq!(decision, ConflictDecision::Abort);
+    }
+
+    #[tokio::test]
+    async fn test_denies_when_ui_is_gone() {
+        let (event_tx, event_rx) = mpsc::channel(4);
+        drop(event_rx);
+        let broker = ApprovalBroker::new(event_tx);
+
+        let response = broker
+            .approve(ApprovalRequest::new(
+                "web_automation",
+                Permission::Network,
+                serde_json::json!({}),
+            ))
+            .await;
+        assert_eq!(response.decision, ApprovalDecision::Deny);
+        assert!(broker.pending.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,478 @@
+//! Git checkpoints: one snapshot commit per agent turn that changed files.
+//!
+//! Opt-in via [crate::RuntimeConfig::checkpoints] (`LOCUS_CHECKPOINTS=1`). At the end
+//! of each run the working tree — untracked files included, ignored files not — is
+//! written into a commit through a temporary index, so the user's branch, index and
+//! stash are never touched. Commits live under
+//! `refs/locus/checkpoints/<session>/<turn>`; each one's parent is the previous
+//! checkpoint of the session (HEAD for the first), and a turn whose tree matches
+//! its parent's is skipped.
+//!
+//! `locus checkpoints list|restore` browses them. Restoring first snapshots the
+//! current tree under `refs/locus/checkpoints/restore/<unix time>`, so a restore
+//! can itself be restored.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Ref namespace holding all checkpoints.
+pub const CHECKPOINT_REF_PREFIX: &str = "refs/locus/checkpoints";
+/// Session name used for the snapshot taken before a restore.
+pub const RESTORE_SESSION: &str = "restore";
+
+const CHECKPOINT_AUTHOR: &str = "locus";
+const CHECKPOINT_EMAIL: &str = "locus@localhost";
+const MAX_SUMMARY_CHARS: usize = 72;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("Not a git repository: {0}")]
+    NotARepository(String),
+
+    #[error("{command} failed: {stderr}")]
+    Git { command: String, stderr: String },
+
+    #[error("Checkpoint not found: {0}")]
+    NotFound(String),
+
+    #[error("Checkpoint id '{0}' matches more than one checkpoint")]
+    Ambiguous(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// One checkpoint commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Checkpoint {
+    /// Short commit hash, used as the id on the command line.
+    pub id: String,
+    pub commit: String,
+    pub session_id: String,
+    pub turn: String,
+    /// Commit time, seconds since epoch.
+    pub timestamp: i64,
+    /// First line of the user message that started the turn.
+    pub summary: String,
+}
+
+/// Result of [Checkpoints::restore].
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreOutcome {
+    pub checkpoint: Checkpoint,
+    /// Snapshot of the tree as it was before the restore (None when nothing changed).
+    pub backup: Option<Checkpoint>,
+    /// Paths that were rewritten or removed.
+    pub changed: Vec<String>,
+}
+
+/// Checkpoint store for one repository.
+pub struct Checkpoints {
+    repo_root: PathBuf,
+}
+
+impl Checkpoints {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+
+    /// Snapshot the working tree for `session_id`/`turn`. Returns None when nothing
+    /// changed since the session's previous checkpoint (or HEAD).
+    pub async fn create(
+        &self,
+        session_id: &str,
+        turn: &str,
+        summary: &str,
+    ) -> Result<Option<Checkpoint>, CheckpointError> {
+        let tree = self.snapshot_tree().await?;
+        let parent = match self.latest(session_id).await? {
+            Some(previous) => Some(previous.commit),
+            None => self.git_opt(&["rev-parse", "--verify", "-q", "HEAD"]).await,
+        };
+        if let Some(parent) = &parent {
+            let parent_tree = self
+                .git(&["rev-parse", &format!("{}^{{tree}}", parent)])
+                .await?;
+            if parent_tree.trim() == tree {
+                return Ok(None);
+            }
+        }
+
+        let summary = summary_line(summary);
+        let message = format!("{}\n\nlocus checkpoint {}/{}", summary, session_id, turn);
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", message.as_str()];
+        if let Some(parent) = &parent {
+            args.extend(["-p", parent.as_str()]);
+        }
+        let commit = self.git(&args).await?.trim().to_string();
+        let reference = format!("{}/{}/{}", CHECKPOINT_REF_PREFIX, session_id, turn);
+        self.git(&["update-ref", &reference, &commit]).await?;
+
+        self.find(&commit).await.map(Some)
+    }
+
+    /// All checkpoints, newest first.
+    pub async fn list(&self) -> Result<Vec<Checkpoint>, CheckpointError> {
+        let output = self
+            .git(&[
+                "for-each-ref",
+                "--sort=-creatordate",
+                "--format=%(refname)%1f%(objectname)%1f%(creatordate:unix)%1f%(contents:subject)",
+                CHECKPOINT_REF_PREFIX,
+            ])
+            .await?;
+        Ok(output.lines().filter_map(parse_ref_line).collect())
+    }
+
+    /// Newest checkpoint of one session.
+    pub async fn latest(&self, session_id: &str) -> Result<Option<Checkpoint>, CheckpointError> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .find(|c| c.session_id == session_id))
+    }
+
+    /// Look up a checkpoint by commit hash prefix (4+ chars) or `<session>/<turn>`.
+    pub async fn find(&self, id: &str) -> Result<Checkpoint, CheckpointError> {
+        let id = id.trim();
+        let mut matches: Vec<Checkpoint> = self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|c| {
+                (id.len() >= 4 && c.commit.starts_with(id))
+                    || format!("{}/{}", c.session_id, c.turn) == id
+            })
+            .collect();
+        matches.dedup_by(|a, b| a.commit == b.commit);
+        match matches.len() {
+            0 => Err(CheckpointError::NotFound(id.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => Err(CheckpointError::Ambiguous(id.to_string())),
+        }
+    }
+
+    /// Put the working tree back to a checkpoint. Files created after it are
+    /// removed; the index and HEAD are left alone.
+    pub async fn restore(&self, id: &str) -> Result<RestoreOutcome, CheckpointError> {
+        let checkpoint = self.find(id).await?;
+        let current = self.snapshot_tree().await?;
+        let changed = self
+            .git(&[
+                "diff",
+                "--name-status",
+                "--no-renames",
+                "-z",
+                &checkpoint.commit,
+                &current,
+            ])
+            .await?;
+        let changed = parse_name_status(&changed);
+        if changed.is_empty() {
+            return Ok(RestoreOutcome {
+                checkpoint,
+                backup: None,
+                changed: Vec::new(),
+            });
+        }
+
+        let stamp = chrono::Utc::now().timestamp_millis().to_string();
+        let backup = self
+            .create(
+                RESTORE_SESSION,
+                &stamp,
+                &format!("Before restoring {}", checkpoint.id),
+            )
+            .await?;
+
+        for (status, path) in &changed {
+            if status == "A" {
+                let path = self.repo_root.join(path);
+                if path.exists() {
+                    tokio::fs::remove_file(&path).await?;
+                }
+            }
+        }
+        let index = self.temp_index().await?;
+        let envs = [("GIT_INDEX_FILE", index.as_os_str())];
+        let result = async {
+            self.git_with_env(&["read-tree", &checkpoint.commit], &envs)
+                .await?;
+            self.git_with_env(&["checkout-index", "-a", "-f"], &envs)
+                .await
+        }
+        .await;
+        let _ = tokio::fs::remove_file(&index).await;
+        result?;
+
+        Ok(RestoreOutcome {
+            checkpoint,
+            backup,
+            changed: changed.into_iter().map(|(_, path)| path).collect(),
+        })
+    }
+
+    /// Write the working tree into a tree object through a throwaway index.
+    async fn snapshot_tree(&self) -> Result<String, CheckpointError> {
+        let index = self.temp_index().await?;
+        // Start from the real index so unchanged files keep their stat cache.
+        // The copy keeps the index's mtime: git checks entries changed in the
+        // same second as the index by content, and a newer mtime would hide
+        // same-size edits made just after the index was written.
+        if let Some(real) = self.git_opt(&["rev-parse", "--git-path", "index"]).await {
+            let real = self.repo_root.join(real);
+            if real.exists() {
+                tokio::fs::copy(&real, &index).await?;
+                let modified = tokio::fs::metadata(&real).await?.modified()?;
+                std::fs::File::options()
+                    .write(true)
+                    .open(&index)?
+                    .set_modified(modified)?;
+            }
+        }
+        let envs = [("GIT_INDEX_FILE", index.as_os_str())];
+        let result = async {
+            self.git_with_env(&["add", "-A", "--", "."], &envs).await?;
+            self.git_with_env(&["write-tree"], &envs).await
+        }
+        .await;
+        let _ = tokio::fs::remove_file(&index).await;
+        Ok(result?.trim().to_string())
+    }
+
+    async fn temp_index(&self) -> Result<PathBuf, CheckpointError> {
+        let path = self
+            .git(&[
+                "rev-parse",
+                "--git-path",
+                &format!("locus-checkpoint-index-{}", std::process::id()),
+            ])
+            .await?;
+        Ok(self.repo_root.join(path.trim()))
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String, CheckpointError> {
+        self.git_with_env(args, &[]).await
+    }
+
+    /// Like [Self::git] but None on failure, for lookups that may legitimately miss.
+    async fn git_opt(&self, args: &[&str]) -> Option<String> {
+        self.git(args)
+            .await
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    async fn git_with_env(
+        &self,
+        args: &[&str],
+        envs: &[(&str, &std::ffi::OsStr)],
+    ) -> Result<String, CheckpointError> {
+        let output = Command::new("git")
+            .args(["-c", "core.quotepath=off"])
+            .args(args)
+            .current_dir(&self.repo_root)
+            .env("GIT_AUTHOR_NAME", CHECKPOINT_AUTHOR)
+            .env("GIT_AUTHOR_EMAIL", CHECKPOINT_EMAIL)
+            .env("GIT_COMMITTER_NAME", CHECKPOINT_AUTHOR)
+            .env("GIT_COMMITTER_EMAIL", CHECKPOINT_EMAIL)
+            .envs(envs.iter().copied())
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if stderr.contains("not a git repository") {
+            return Err(CheckpointError::NotARepository(
+                self.repo_root.display().to_string(),
+            ));
+        }
+        Err(CheckpointError::Git {
+            command: format!("git {}", args.join(" ")),
+            stderr,
+        })
+    }
+}
+
+/// `refname \x1f hash \x1f unix time \x1f subject` → Checkpoint.
+fn parse_ref_line(line: &str) -> Option<Checkpoint> {
+    let mut fields = line.split('\x1f');
+    let reference = fields.next()?;
+    let commit = fields.next()?.to_string();
+    let timestamp = fields.next()?.parse().unwrap_or(0);
+    let summary = fields.next().unwrap_or_default().to_string();
+    let rest = reference
+        .strip_prefix(CHECKPOINT_REF_PREFIX)?
+        .strip_prefix('/')?;
+    let (session_id, turn) = rest.rsplit_once('/')?;
+    Some(Checkpoint {
+        id: commit.chars().take(7).collect(),
+        commit,
+        session_id: session_id.to_string(),
+        turn: turn.to_string(),
+        timestamp,
+        summary,
+    })
+}
+
+/// `git diff --name-status -z` → (status letter, path) pairs.
+fn parse_name_status(output: &str) -> Vec<(String, String)> {
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    let mut entries = Vec::new();
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        entries.push((status.to_string(), path.to_string()));
+    }
+    entries
+}
+
+/// First non-empty line of `text`, cut to fit a commit subject.
+fn summary_line(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("agent turn");
+    if line.chars().count() <= MAX_SUMMARY_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    format!("{}…", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Repo with one commit, or None when git is unavailable.
+    fn create_test_repo() -> Option<TempDir> {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn a() {}\n").unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        let ok = git(dir.path(), &["init", "-q"])
+            && git(dir.path(), &["add", "-A"])
+            && git(dir.path(), &["commit", "-q", "-m", "Initial commit"]);
+        ok.then_some(dir)
+    }
+
+    #[test]
+    fn test_parse_ref_line() {
+        let line = "refs/locus/checkpoints/abc-123/002\x1fdeadbeefcafe\x1f1700000000\x1fFix login";
+        let checkpoint = parse_ref_line(line).unwrap();
+        assert_eq!(checkpoint.id, "deadbee");
+        assert_eq!(checkpoint.session_id, "abc-123");
+        assert_eq!(checkpoint.turn, "002");
+        assert_eq!(checkpoint.timestamp, 1_700_000_000);
+        assert_eq!(checkpoint.summary, "Fix login");
+        assert!(parse_ref_line("refs/heads/main\x1fabc\x1f0\x1fx").is_none());
+    }
+
+    #[test]
+    fn test_summary_line() {
+        assert_eq!(summary_line("\n  fix the bug \nmore"), "fix the bug");
+        assert_eq!(summary_line(""), "agent turn");
+        let long = "x".repeat(100);
+        assert_eq!(summary_line(&long).chars().count(), MAX_SUMMARY_CHARS);
+    }
+
+    #[tokio::test]
+    async fn test_create_skips_unchanged_and_chains() {
+        let Some(dir) = create_test_repo() else {
+            return;
+        };
+        let store = Checkpoints::new(dir.path().to_path_buf());
+        assert!(store
+            .create("s1", "001", "nothing")
+            .await
+            .unwrap()
+            .is_none());
+
+        fs::write(dir.path().join("lib.rs"), "fn b() {}\n").unwrap();
+        fs::write(dir.path().join("new.rs"), "fn c() {}\n").unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target/out"), "build\n").unwrap();
+        let first = store
+            .create("s1", "002", "Rename a to b")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.turn, "002");
+        assert_eq!(first.summary, "Rename a to b");
+        assert!(store.create("s1", "003", "again").await.unwrap().is_none());
+
+        fs::write(dir.path().join("new.rs"), "fn d() {}\n").unwrap();
+        let second = store
+            .create("s1", "004", "Edit new")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(git(
+            dir.path(),
+            &["merge-base", "--is-ancestor", &first.commit, &second.commit]
+        ));
+
+        // The user's index, HEAD and ignored files are untouched.
+        assert!(git(dir.path(), &["diff", "--cached", "--quiet"]));
+        let listed: Vec<String> = store
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.turn)
+            .collect();
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&"002".to_string()) && listed.contains(&"004".to_string()));
+        assert_eq!(store.find("s1/004").await.unwrap(), second);
+        assert!(matches!(
+            store.find("zzzz").await,
+            Err(CheckpointError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_rewrites_and_removes_files() {
+        let Some(dir) = create_test_repo() else {
+            return;
+        };
+        let root = dir.path();
+        let store = Checkpoints::new(root.to_path_buf());
+        fs::write(root.join("lib.rs"), "fn b() {}\n").unwrap();
+        let checkpoint = store.create("s1", "001", "Edit").await.unwrap().unwrap();
+
+        fs::write(root.join("lib.rs"), "fn broken(\n").unwrap();
+        fs::write(root.join("extra.rs"), "junk\n").unwrap();
+        let outcome = store.restore(&checkpoint.id).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "fn b() {}\n"
+        );
+        assert!(!root.join("extra.rs").exists());
+        assert_eq!(outcome.changed.len(), 2);
+
+        let backup = outcome.backup.unwrap();
+        assert_eq!(backup.session_id, RESTORE_SESSION);
+        let again = store.restore(&backup.id).await.unwrap();
+        assert!(again.backup.is_some());
+        assert_eq!(fs::read_to_string(root.join("extra.rs")).unwrap(), "junk\n");
+
+        assert!(store.restore(&backup.id).await.unwrap().changed.is_empty());
+    }
+}
@@ -2,8 +2,11 @@
 
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use locus_core::SandboxPolicy;
+use locus_toolbus::mcp::McpServersConfig;
+use locus_toolbus::{DevcontainerBackend, DockerBackend, ExecutionBackend, SshBackend};
 
 /// LLM provider selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -24,6 +27,17 @@ impl LlmProvider {
             LlmProvider::ZAI => "zai",
         }
     }
+
+    /// A smaller, cheaper model for sub-agents that only search or summarize
+    /// (None when the provider has no such default, e.g. local models).
+    pub fn cheap_model(&self) -> Option<&'static str> {
+        match self {
+            LlmProvider::Anthropic => Some("claude-3-5-haiku-20241022"),
+            LlmProvider::OpenAI => Some("gpt-4o-mini"),
+            LlmProvider::ZAI => Some("glm-4.5-air"),
+            LlmProvider::Ollama => None,
+        }
+    }
 }
 
 impl FromStr for LlmProvider {
@@ -59,6 +73,8 @@ pub struct RuntimeConfig {
     pub context_limit: u64,
     /// Maximum memories to retrieve per query
     pub memory_limit: u8,
+    /// Tokens of recalled memories put in the prompt, most relevant first (0 = no limit)
+    pub memory_token_budget: usize,
     /// Maximum tokens to spend on tool schemas per LLM call
     pub tool_token_budget: u32,
     /// Maximum tokens for LLM response generation
@@ -67,6 +83,41 @@ pub struct RuntimeConfig {
     pub sandbox: SandboxPolicy,
     /// Repository root directory
     pub repo_root: PathBuf,
+    /// Provider calls kept for the inspection view (0 = inspection off)
+    pub inspect_calls: usize,
+    /// Record turn summaries in the project DB session log, save the session
+    /// there for `locus sessions`, and checkpoint turns in progress for
+    /// `locus resume` (off for sub-agents)
+    pub session_log: bool,
+    /// Where bash/handoff run: on the host, in a Docker container or over SSH
+    pub execution_backend: ExecutionBackend,
+    /// Snapshot the working tree into a git checkpoint after each run that changed files
+    pub checkpoints: bool,
+    /// Bytes of an earlier file read kept in context before it is packed to the relevant functions (0 = off)
+    pub file_pack_budget: usize,
+    /// Tokens of ranked files and top-level symbols in the system prompt (0 = no repo map)
+    pub repo_map_tokens: usize,
+    /// Add `AGENTS.md`, `CLAUDE.md` and `.locus/instructions.md` to the system prompt
+    pub project_instructions: bool,
+    /// Replace API keys, tokens and passwords with placeholders before each LLM call
+    pub redact_secrets: bool,
+    /// MCP servers config; its servers' tools follow the file for the session (None = no MCP)
+    pub mcp_config: Option<PathBuf>,
+    /// Sub-agents (`task` calls) of one turn run at the same time; the rest wait
+    pub max_parallel_subagents: usize,
+    /// Seconds a sub-agent may run before it is stopped (0 = no limit)
+    pub subagent_timeout_secs: u64,
+    /// Characters of sub-agent output passed back as written; longer output is
+    /// condensed by the cheap model first (0 = never condense)
+    pub subagent_summary_chars: usize,
+    /// Files (repo-relative) whose current content goes into every turn's context
+    pub pinned_files: Vec<PathBuf>,
+    /// How long one request may keep the agent working before it stops and asks
+    /// whether to go on (None = no limit)
+    pub max_turn_duration: Option<Duration>,
+    /// Tool calls one request may make before the agent stops and asks whether
+    /// to go on (None = no limit)
+    pub max_tool_calls_per_turn: Option<u32>,
 }
 
 impl RuntimeConfig {
@@ -77,10 +128,26 @@ impl RuntimeConfig {
             max_turns: None,
             context_limit: 200_000,
             memory_limit: 10,
+            memory_token_budget: 2_000,
             tool_token_budget: 3800,
             max_tokens: 8192,
             sandbox: SandboxPolicy::default(),
             repo_root,
+            inspect_calls: 0,
+            session_log: true,
+            execution_backend: ExecutionBackend::default(),
+            checkpoints: false,
+            file_pack_budget: 8_000,
+            repo_map_tokens: 1_024,
+            project_instructions: true,
+            redact_secrets: true,
+            mcp_config: None,
+            max_parallel_subagents: 4,
+            subagent_timeout_secs: 600,
+            subagent_summary_chars: 1_500,
+            pinned_files: Vec::new(),
+            max_turn_duration: Some(Duration::from_secs(30 * 60)),
+            max_tool_calls_per_turn: Some(100),
         }
     }
 
@@ -112,6 +179,11 @@ impl RuntimeConfig {
         self
     }
 
+    pub fn with_memory_token_budget(mut self, tokens: usize) -> Self {
+        self.memory_token_budget = tokens;
+        self
+    }
+
     pub fn with_tool_token_budget(mut self, budget: u32) -> Self {
         self.tool_token_budget = budget;
         self
@@ -127,6 +199,71 @@ impl RuntimeConfig {
         self
     }
 
+    pub fn with_inspect_calls(mut self, calls: usize) -> Self {
+        self.inspect_calls = calls;
+        self
+    }
+
+    pub fn with_session_log(mut self, enabled: bool) -> Self {
+        self.session_log = enabled;
+        self
+    }
+
+    pub fn with_execution_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.execution_backend = backend;
+        self
+    }
+
+    pub fn with_checkpoints(mut self, enabled: bool) -> Self {
+        self.checkpoints = enabled;
+        self
+    }
+
+    pub fn with_file_pack_budget(mut self, budget: usize) -> Self {
+        self.file_pack_budget = budget;
+        self
+    }
+
+    pub fn with_repo_map_tokens(mut self, tokens: usize) -> Self {
+        self.repo_map_tokens = tokens;
+        self
+    }
+
+    pub fn with_project_instructions(mut self, enabled: bool) -> Self {
+        self.project_instructions = enabled;
+        self
+    }
+
+    pub fn with_max_parallel_subagents(mut self, max: usize) -> Self {
+        self.max_parallel_subagents = max;
+        self
+    }
+
+    pub fn with_subagent_timeout_secs(mut self, secs: u64) -> Self {
+        self.subagent_timeout_secs = secs;
+        self
+    }
+
+    pub fn with_max_turn_duration(mut self, max: Option<Duration>) -> Self {
+        self.max_turn_duration = max;
+        self
+    }
+
+    pub fn with_max_tool_calls_per_turn(mut self, max: Option<u32>) -> Self {
+        self.max_tool_calls_per_turn = max;
+        self
+    }
+
+    pub fn with_subagent_summary_chars(mut self, chars: usize) -> Self {
+        self.subagent_summary_chars = chars;
+        self
+    }
+
+    pub fn with_pinned_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.pinned_files = files;
+        self
+    }
+
     /// Load configuration from environment variables
     pub fn from_env(repo_root: PathBuf) -> Self {
         let mut config = Self::new(repo_root);
@@ -167,6 +304,12 @@ impl RuntimeConfig {
             }
         }
 
+        if let Ok(tokens) = std::env::var("LOCUS_MEMORY_TOKENS") {
+            if let Ok(val) = tokens.parse::<usize>() {
+                config.memory_token_budget = val;
+            }
+        }
+
         if let Ok(budget) = std::env::var("LOCUS_TOOL_BUDGET") {
             if let Ok(val) = budget.parse::<u32>() {
                 config.tool_token_budget = val;
@@ -179,6 +322,88 @@ impl RuntimeConfig {
             }
         }
 
+        if let Ok(calls) = std::env::var("LOCUS_INSPECT_CALLS") {
+            if let Ok(val) = calls.parse::<usize>() {
+                config.inspect_calls = val;
+            }
+        }
+
+        if let Ok(budget) = std::env::var("LOCUS_FILE_PACK_BUDGET") {
+            if let Ok(val) = budget.parse::<usize>() {
+                config.file_pack_budget = val;
+            }
+        }
+
+        if let Ok(tokens) = std::env::var("LOCUS_REPO_MAP_TOKENS") {
+            if let Ok(val) = tokens.parse::<usize>() {
+                config.repo_map_tokens = val;
+            }
+        }
+
+        if let Ok(max) = std::env::var("LOCUS_MAX_SUBAGENTS") {
+            if let Ok(val) = max.parse::<usize>() {
+                config.max_parallel_subagents = val.max(1);
+            }
+        }
+
+        if let Ok(secs) = std::env::var("LOCUS_SUBAGENT_TIMEOUT") {
+            if let Ok(val) = secs.parse::<u64>() {
+                config.subagent_timeout_secs = val;
+            }
+        }
+
+        if let Ok(secs) = std::env::var("LOCUS_MAX_TURN_SECS") {
+            if let Ok(val) = secs.parse::<u64>() {
+                config.max_turn_duration = (val > 0).then(|| Duration::from_secs(val));
+            }
+        }
+
+        if let Ok(calls) = std::env::var("LOCUS_MAX_TOOL_CALLS") {
+            if let Ok(val) = calls.parse::<u32>() {
+                config.max_tool_calls_per_turn = (val > 0).then_some(val);
+            }
+        }
+
+        if let Ok(files) = std::env::var("LOCUS_PINNED_FILES") {
+            config.pinned_files = files
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(PathBuf::from)
+                .collect();
+        }
+
+        if let Ok(chars) = std::env::var("LOCUS_SUBAGENT_SUMMARY_CHARS") {
+            if let Ok(val) = chars.parse::<usize>() {
+                config.subagent_summary_chars = val;
+            }
+        }
+
+        if let Ok(checkpoints) = std::env::var("LOCUS_CHECKPOINTS") {
+            config.checkpoints = matches!(checkpoints.trim().to_lowercase().as_str(), "1" | "true");
+        }
+
+        if let Ok(instructions) = std::env::var("LOCUS_INSTRUCTIONS") {
+            config.project_instructions =
+                !matches!(instructions.trim().to_lowercase().as_str(), "0" | "false");
+        }
+
+        if let Ok(redact) = std::env::var("LOCUS_REDACT_SECRETS") {
+            config.redact_secrets = !matches!(redact.trim().to_lowercase().as_str(), "0" | "false");
+        }
+
+        if !std::env::var("LOCUS_MCP").is_ok_and(|v| matches!(v.trim(), "0" | "false")) {
+            config.mcp_config = Some(McpServersConfig::default_path());
+        }
+
+        if let Some(docker) = DockerBackend::from_env() {
+            config.execution_backend = ExecutionBackend::Docker(docker);
+        } else if let Some(devcontainer) = DevcontainerBackend::from_env() {
+            config.execution_backend = ExecutionBackend::Devcontainer(devcontainer);
+        } else if let Some(ssh) = SshBackend::from_env() {
+            config.execution_backend = ExecutionBackend::Ssh(ssh);
+        }
+
         config
     }
 }
@@ -211,8 +436,21 @@ mod tests {
         assert_eq!(config.max_turns, None);
         assert_eq!(config.context_limit, 200_000);
         assert_eq!(config.memory_limit, 10);
+        assert_eq!(config.memory_token_budget, 2_000);
         assert_eq!(config.tool_token_budget, 3800);
         assert_eq!(config.max_tokens, 8192);
+        assert_eq!(config.inspect_calls, 0);
+        assert_eq!(config.execution_backend, ExecutionBackend::Local);
+        assert!(!config.checkpoints);
+        assert_eq!(config.file_pack_budget, 8_000);
+        assert_eq!(config.max_parallel_subagents, 4);
+        assert_eq!(config.subagent_timeout_secs, 600);
+        assert_eq!(config.subagent_summary_chars, 1_500);
+        assert!(config.pinned_files.is_empty());
+        assert_eq!(config.max_turn_duration, Some(Duration::from_secs(1_800)));
+        assert_eq!(config.max_tool_calls_per_turn, Some(100));
+        assert_eq!(config.repo_map_tokens, 1_024);
+        assert!(config.project_instructions);
     }
 
     #[test]
@@ -224,7 +462,9 @@ mod tests {
             .with_context_limit(100_000)
             .with_memory_limit(5)
             .with_tool_token_budget(2000)
-            .with_max_tokens(16384);
+            .with_max_tokens(16384)
+            .with_inspect_calls(5)
+            .with_execution_backend(ExecutionBackend::Docker(DockerBackend::new("rust:1.85")));
 
         assert_eq!(config.model, "gpt-4");
         assert_eq!(config.provider, LlmProvider::OpenAI);
@@ -233,5 +473,10 @@ mod tests {
         assert_eq!(config.memory_limit, 5);
         assert_eq!(config.tool_token_budget, 2000);
         assert_eq!(config.max_tokens, 16384);
+        assert_eq!(config.inspect_calls, 5);
+        assert!(matches!(
+            config.execution_backend,
+            ExecutionBackend::Docker(ref docker) if docker.image == "rust:1.85"
+        ));
     }
 }
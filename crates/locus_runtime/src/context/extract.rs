@@ -1,5 +1,6 @@
-//! File path extraction from session turns.
+//! File path and identifier extraction from session turns.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use locus_core::{ContentBlock, Session};
@@ -31,6 +32,31 @@ pub(crate) fn extract_recent_files(session: &Session) -> Vec<String> {
     files
 }
 
+/// Identifiers the recent conversation is about: words from the last few
+/// turns' text and tool arguments (tool output is skipped, it names too much).
+pub(crate) fn extract_focus_terms(session: &Session) -> HashSet<String> {
+    let mut terms = HashSet::new();
+    let mut add = |text: &str| {
+        for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+            if word.len() >= 3 && !word.starts_with(|c: char| c.is_ascii_digit()) {
+                terms.insert(word.to_string());
+            }
+        }
+    };
+
+    for turn in session.turns.iter().rev().take(6) {
+        for block in &turn.blocks {
+            match block {
+                ContentBlock::Text { text } => add(text),
+                ContentBlock::ToolUse { tool_use } => add(&tool_use.args.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    terms
+}
+
 /// Try to extract a file path from a line of text.
 fn extract_path_from_line(line: &str) -> Option<String> {
     let parts: Vec<&str> = line.split_whitespace().collect();
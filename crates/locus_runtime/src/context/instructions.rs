@@ -0,0 +1,199 @@
+//! Project instruction files merged into the system prompt.
+//!
+//! locus learns conventions from what happens in sessions, but rules a team
+//! has written down are honored from the first turn: `AGENTS.md`, `CLAUDE.md`
+//! and `.locus/instructions.md` at the repo root apply everywhere, and an
+//! `AGENTS.md` or `CLAUDE.md` in a nested package applies to the files under
+//! its directory. Files are read once per session, so the prompt prefix stays
+//! stable across calls.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use tracing::warn;
+
+/// Instruction files looked for at the repo root, in prompt order.
+pub const ROOT_INSTRUCTION_FILES: &[&str] = &["AGENTS.md", "CLAUDE.md", ".locus/instructions.md"];
+
+/// Instruction files looked for in nested packages.
+const NESTED_INSTRUCTION_FILES: &[&str] = &["AGENTS.md", "CLAUDE.md"];
+
+/// Directory levels below the root searched for nested instruction files.
+const NESTED_MAX_DEPTH: usize = 4;
+
+/// Bytes of one instruction file kept; longer files are cut here.
+const MAX_FILE_BYTES: usize = 16_000;
+
+/// Bytes of all instruction files together; files past this are left out.
+const MAX_TOTAL_BYTES: usize = 48_000;
+
+/// One instruction file as it goes into the prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionFile {
+    /// Repo-relative path, `/`-separated.
+    pub path: String,
+    pub content: String,
+    pub truncated: bool,
+}
+
+impl InstructionFile {
+    /// The directory it applies to; empty for the repo root.
+    fn scope(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((dir, _)) if !self.path.starts_with(".locus/") => dir,
+            _ => "",
+        }
+    }
+}
+
+/// The instruction files in `repo_root`: the root ones first, then nested
+/// ones in path order. Empty files and copies of a file already loaded (a
+/// `CLAUDE.md` symlinked to `AGENTS.md`, say) are skipped.
+pub fn load_instructions(repo_root: &Path) -> Vec<InstructionFile> {
+    let mut paths: Vec<String> = ROOT_INSTRUCTION_FILES
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    paths.extend(nested_instruction_paths(repo_root));
+
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    let mut total = 0;
+    for path in paths {
+        let Ok(content) = std::fs::read_to_string(repo_root.join(&path)) else {
+            continue;
+        };
+        let content = content.trim().to_string();
+        if content.is_empty() || !seen.insert(content.clone()) {
+            continue;
+        }
+        let (content, truncated) = cut(content, MAX_FILE_BYTES);
+        if total + content.len() > MAX_TOTAL_BYTES {
+            warn!("Instruction file {} left out: over the size limit", path);
+            continue;
+        }
+        total += content.len();
+        files.push(InstructionFile {
+            path,
+            content,
+            truncated,
+        });
+    }
+    files
+}
+
+/// The system prompt section for `files`; empty when there are none.
+pub fn instructions_section(files: &[InstructionFile]) -> String {
+    if files.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from(
+        "\n## Project Instructions\nRules this project's maintainers wrote for agents. Follow \
+         them over your own defaults (but not over the safety rules). Rules from a nested \
+         file apply only to files under its directory.\n",
+    );
+    for file in files {
+        match file.scope() {
+            "" => out.push_str(&format!("\n### {}\n", file.path)),
+            dir => out.push_str(&format!("\n### {} (applies to {}/)\n", file.path, dir)),
+        }
+        out.push_str(&file.content);
+        out.push('\n');
+        if file.truncated {
+            out.push_str(&format!("(Cut at {} bytes)\n", MAX_FILE_BYTES));
+        }
+    }
+    out
+}
+
+/// Nested `AGENTS.md` / `CLAUDE.md` files, skipping ignored and hidden directories.
+fn nested_instruction_paths(repo_root: &Path) -> Vec<String> {
+    let walker = ignore::WalkBuilder::new(repo_root)
+        .hidden(true)
+        .git_ignore(true)
+        .git_exclude(true)
+        .git_global(false)
+        .require_git(false)
+        .max_depth(Some(NESTED_MAX_DEPTH + 1))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    let mut paths: Vec<String> = walker
+        .flatten()
+        .filter(|entry| entry.depth() > 1 && entry.file_type().is_some_and(|t| t.is_file()))
+        .filter(|entry| {
+            NESTED_INSTRUCTION_FILES
+                .iter()
+                .any(|name| entry.file_name() == *name)
+        })
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(repo_root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// `content` cut to at most `max` bytes on a character boundary.
+fn cut(mut content: String, max: usize) -> (String, bool) {
+    if content.len() <= max {
+        return (content, false);
+    }
+    let mut end = max;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content.truncate(end);
+    (content, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn loads_root_and_nested_instructions() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".locus")).unwrap();
+        std::fs::create_dir_all(root.join("crates/api")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::write(root.join(".gitignore"), "/target\n").unwrap();
+        std::fs::write(root.join("AGENTS.md"), "Run cargo fmt.\n").unwrap();
+        std::fs::write(root.join("CLAUDE.md"), "Run cargo fmt.\n").unwrap();
+        std::fs::write(root.join(".locus/instructions.md"), "No unwrap.\n").unwrap();
+        std::fs::write(root.join("crates/api/AGENTS.md"), "Keep handlers thin.").unwrap();
+        std::fs::write(root.join("target/debug/AGENTS.md"), "built").unwrap();
+
+        let files = load_instructions(root);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "AGENTS.md",
+                ".locus/instructions.md",
+                "crates/api/AGENTS.md"
+            ]
+        );
+
+        let section = instructions_section(&files);
+        assert!(section.contains("## Project Instructions"));
+        assert!(section.contains("### AGENTS.md\nRun cargo fmt.\n"));
+        assert!(section.contains("### .locus/instructions.md\nNo unwrap.\n"));
+        assert!(section
+            .contains("### crates/api/AGENTS.md (applies to crates/api/)\nKeep handlers thin.\n"));
+        assert!(instructions_section(&[]).is_empty());
+    }
+
+    #[test]
+    fn long_instructions_are_cut() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("AGENTS.md"), "é".repeat(MAX_FILE_BYTES)).unwrap();
+
+        let files = load_instructions(dir.path());
+        assert!(files[0].truncated);
+        assert_eq!(files[0].content.len(), MAX_FILE_BYTES);
+        assert!(instructions_section(&files).contains("(Cut at 16000 bytes)"));
+    }
+}
@@ -0,0 +1,180 @@
+//! Files and folders a prompt mentions with `@path`, attached to its user turn.
+//!
+//! Mentions are looked up through the tool bus (`read` for files, `glob` for
+//! folders), so they stay inside the repo and skip protected paths exactly as
+//! the model's own calls would. A mention that names nothing readable is left
+//! as plain text and reported in the status line.
+
+use std::collections::HashSet;
+
+use locus_core::mention::{find_mentions, ATTACHMENT_PREFIX};
+use locus_toolbus::ToolBus;
+use serde_json::json;
+
+/// Entries listed for a mentioned folder, nested ones included.
+const MAX_FOLDER_ENTRIES: usize = 200;
+
+/// What the mentions in a prompt came to.
+#[derive(Debug, Default)]
+pub struct Attachments {
+    /// One context block per mention that could be attached.
+    pub blocks: Vec<String>,
+    /// Mentions attached, as written.
+    pub attached: Vec<String>,
+    /// Mentions left as text, with why.
+    pub skipped: Vec<(String, String)>,
+}
+
+impl Attachments {
+    /// Status line for the user; None when the prompt mentions nothing.
+    pub fn status(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if !self.attached.is_empty() {
+            let names: Vec<String> = self.attached.iter().map(|p| format!("@{}", p)).collect();
+            parts.push(format!("Attached {}", names.join(", ")));
+        }
+        for (path, reason) in &self.skipped {
+            parts.push(format!("@{} not attached: {}", path, reason));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+}
+
+/// Attach the files and folders `prompt` mentions, each once.
+pub async fn attach_mentions(toolbus: &ToolBus, prompt: &str) -> Attachments {
+    let mut attachments = Attachments::default();
+    let mut seen = HashSet::new();
+    for mention in find_mentions(prompt) {
+        if !seen.insert(mention.path.clone()) {
+            continue;
+        }
+        match attach(toolbus, &mention.path).await {
+            Ok(block) => {
+                attachments.blocks.push(block);
+                attachments.attached.push(mention.path);
+            }
+            Err(reason) => attachments.skipped.push((mention.path, reason)),
+        }
+    }
+    attachments
+}
+
+/// The context block for one mentioned path.
+async fn attach(toolbus: &ToolBus, path: &str) -> Result<String, String> {
+    let (output, _) = toolbus
+        .call("read", json!({ "path": path }))
+        .await
+        .map_err(|e| e.to_string())?;
+    if output["type"] == "directory" {
+        return folder_block(toolbus, path).await;
+    }
+    let content = output["content"].as_str().unwrap_or_default();
+    let mut block = format!("{}{}]\n```\n{}", ATTACHMENT_PREFIX, path, content);
+    if !content.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str("```");
+    if output["truncated"] == true {
+        block.push_str(&format!(
+            "\n(Only the start of the file; it is {} bytes)",
+            output["size_bytes"]
+        ));
+    }
+    Ok(block)
+}
+
+/// A mentioned folder as an indented tree of what `glob` finds under it.
+async fn folder_block(toolbus: &ToolBus, path: &str) -> Result<String, String> {
+    let dir = path.trim_end_matches('/');
+    let (output, _) = toolbus
+        .call(
+            "glob",
+            json!({
+                "pattern": "*",
+                "path": dir,
+                "include_dirs": true,
+                "max_results": MAX_FOLDER_ENTRIES,
+            }),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut entries: Vec<&str> = output["files"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|f| f.as_str())
+        .collect();
+    entries.sort_unstable();
+
+    let mut block = format!("{}{}/] (folder)\n", ATTACHMENT_PREFIX, dir);
+    for entry in &entries {
+        let relative = entry
+            .strip_prefix(dir)
+            .unwrap_or(entry)
+            .trim_start_matches('/');
+        let depth = relative.matches('/').count();
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        let slash = if toolbus.repo_root().join(entry).is_dir() {
+            "/"
+        } else {
+            ""
+        };
+        block.push_str(&format!("{}{}{}\n", "  ".repeat(depth), name, slash));
+    }
+    if entries.is_empty() {
+        block.push_str("(empty)\n");
+    }
+    if output["truncated"] == true {
+        block.push_str(&format!(
+            "… only the first {} entries\n",
+            MAX_FOLDER_ENTRIES
+        ));
+    }
+    Ok(block.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn attaches_files_and_folders_and_skips_the_rest() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/util")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub mod util;\n").unwrap();
+        std::fs::write(dir.path().join("src/util/mod.rs"), "").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=x\n").unwrap();
+        let toolbus = ToolBus::new(dir.path().to_path_buf());
+
+        let attachments = attach_mentions(
+            &toolbus,
+            "Why is @src/lib.rs so short? See @src/ and @missing.rs, @.env, @src/lib.rs",
+        )
+        .await;
+
+        assert_eq!(attachments.attached, ["src/lib.rs", "src/"]);
+        assert_eq!(
+            attachments.blocks[0],
+            "[Attached @src/lib.rs]\n```\npub mod util;\n```"
+        );
+        assert_eq!(
+            attachments.blocks[1],
+            "[Attached @src/] (folder)\nlib.rs\nutil/\n  mod.rs"
+        );
+        let skipped: Vec<&str> = attachments
+            .skipped
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect();
+        assert_eq!(skipped, ["missing.rs", ".env"]);
+        assert!(attachments
+            .status()
+            .unwrap()
+            .starts_with("Attached @src/lib.rs, @src/;"));
+        assert!(attach_mentions(&toolbus, "no mentions")
+            .await
+            .status()
+            .is_none());
+    }
+}
@@ -1,10 +1,15 @@
 //! Session-to-LLM message conversion and request building.
 
+use std::collections::HashSet;
+
 use locus_core::{ContentBlock, Session, Turn};
 use locus_llms::types::{GenerateOptions, Message, Role as LlmRole, Tool, ToolChoice};
 use locus_toolbus::ToolInfo;
+use serde_json::Value as JsonValue;
 
-use super::extract::extract_recent_files;
+use super::extract::{extract_focus_terms, extract_recent_files};
+use super::pack::pack_file;
+use super::reads::ReadTracker;
 
 /// Build session context string for the prompt.
 ///
@@ -37,8 +42,19 @@ pub fn build_session_context(session: &Session) -> String {
 /// Build messages array for the LLM request.
 ///
 /// Converts session turns into the message format expected by the LLM,
-/// prepending system prompt with session context and memories.
-pub fn build_messages(system_prompt: &str, session: &Session, memories: &str) -> Vec<Message> {
+/// prepending system prompt with session context, memories and the pinned
+/// files section (see [super::PinnedFiles::section]). File reads
+/// from earlier turns that exceed `file_budget` bytes are packed down to the
+/// functions the conversation is about (0 keeps every read whole). A read
+/// that repeats an earlier whole read of the same content is sent as a short
+/// "unchanged since turn N" note, and a changed one carries a freshness hint.
+pub fn build_messages(
+    system_prompt: &str,
+    session: &Session,
+    memories: &str,
+    pinned: &str,
+    file_budget: usize,
+) -> Vec<Message> {
     let mut messages = Vec::new();
 
     // System message with prompt
@@ -54,11 +70,20 @@ pub fn build_messages(system_prompt: &str, session: &Session, memories: &str) ->
         system_content.push_str(memories);
     }
 
+    if !pinned.is_empty() {
+        system_content.push_str("\n\n");
+        system_content.push_str(pinned);
+    }
+
     messages.push(Message::new(LlmRole::System, system_content));
 
-    // Convert previous turns to messages
-    for turn in &session.turns {
-        if let Some(msg) = turn_to_message(turn) {
+    // Convert previous turns to messages; the newest turn is always sent whole.
+    let focus = extract_focus_terms(session);
+    let last = session.turns.len().saturating_sub(1);
+    let mut reads = ReadTracker::new();
+    for (i, turn) in session.turns.iter().enumerate() {
+        let packing = (file_budget > 0 && i < last).then_some((&focus, file_budget));
+        if let Some(msg) = turn_to_message(turn, i + 1, packing, &mut reads) {
             messages.push(msg);
         }
     }
@@ -103,11 +128,46 @@ pub fn build_generate_request(
     }
 }
 
+/// Replace the content of a `read` result with its packed form, when packing
+/// shrinks it. Returns the output and whether it was packed.
+fn pack_read_result(
+    output: &JsonValue,
+    focus: &HashSet<String>,
+    budget: usize,
+) -> (JsonValue, bool) {
+    let result = &output["result"];
+    if output["tool_name"] != "read" || result["type"] != "file" {
+        return (output.clone(), false);
+    }
+    let (Some(path), Some(content)) = (result["path"].as_str(), result["content"].as_str()) else {
+        return (output.clone(), false);
+    };
+    let Some(packed) = pack_file(path, content, focus, budget) else {
+        return (output.clone(), false);
+    };
+
+    let mut output = output.clone();
+    output["result"]["content"] = packed.content.into();
+    output["result"]["packed"] = serde_json::json!({
+        "elided_lines": packed.elided_lines,
+        "note": "Functions unrelated to the conversation are elided; read the file again for the full text.",
+    });
+    (output, true)
+}
+
 /// Convert a Turn to an LLM Message.
 ///
 /// Returns None if the turn has no meaningful content.
 /// Preserves structured tool calls and results for proper API compatibility.
-fn turn_to_message(turn: &Turn) -> Option<Message> {
+/// `index` is the turn's 1-based position; file reads are checked against
+/// those already in `reads`, and with `packing` set large ones are packed (see
+/// [`build_messages`]).
+fn turn_to_message(
+    turn: &Turn,
+    index: usize,
+    packing: Option<(&HashSet<String>, usize)>,
+    reads: &mut ReadTracker,
+) -> Option<Message> {
     use locus_llms::types::ContentPart;
 
     let role = match turn.role {
@@ -148,10 +208,11 @@ fn turn_to_message(turn: &Turn) -> Option<Message> {
                     .get("tool_use_id")
                     .and_then(|v| v.as_str())
                     .unwrap_or("");
-                parts.push(ContentPart::tool_result(
-                    tool_use_id,
-                    tool_result.output.clone(),
-                ));
+                let output = reads.apply(index, &tool_result.output, |output| match packing {
+                    Some((focus, budget)) => pack_read_result(output, focus, budget),
+                    None => (output.clone(), false),
+                });
+                parts.push(ContentPart::tool_result(tool_use_id, output));
             }
         }
     }
@@ -187,7 +248,7 @@ mod tests {
         let system_prompt = "You are a helpful assistant.";
         let memories = "Previous context...";
 
-        let messages = build_messages(system_prompt, &session, memories);
+        let messages = build_messages(system_prompt, &session, memories, "", 0);
 
         assert_eq!(messages.len(), 1); // System only
         assert!(matches!(messages[0].role, LlmRole::System));
@@ -201,16 +262,54 @@ mod tests {
         session.add_turn(Turn::user().with_block(ContentBlock::text("First message")));
         session.add_turn(Turn::assistant().with_block(ContentBlock::text("First response")));
 
-        let messages = build_messages("System prompt", &session, "");
+        let messages = build_messages("System prompt", &session, "", "", 0);
 
         assert_eq!(messages.len(), 3); // System + 2 turns
     }
 
+    #[test]
+    fn test_build_messages_packs_earlier_reads() {
+        let config = SessionConfig::new("claude-sonnet-4", "anthropic");
+        let mut session = Session::new(std::path::PathBuf::from("/repo"), config);
+        let body = "    let x = 1;\n".repeat(20);
+        let content = format!("fn wanted() {{\n{body}}}\n\nfn other() {{\n{body}}}\n");
+        let read = |id: &str| {
+            Turn::new(locus_core::Role::Tool).with_block(ContentBlock::tool_result(
+                locus_core::ToolResultData::success(
+                    serde_json::json!({
+                        "tool_use_id": id,
+                        "tool_name": "read",
+                        "result": { "type": "file", "path": "src/lib.rs", "content": content },
+                    }),
+                    0,
+                ),
+            ))
+        };
+        session.add_turn(Turn::user().with_block(ContentBlock::text("Why does wanted fail?")));
+        session.add_turn(read("t1"));
+        session.add_turn(Turn::user().with_block(ContentBlock::text("Fix wanted")));
+        session.add_turn(read("t2"));
+
+        let output = |message: &Message| serde_json::to_string(message).unwrap();
+        let messages = build_messages("System prompt", &session, "", "", 200);
+        let earlier = output(&messages[2]);
+        assert!(earlier.contains("fn other() {\\n    … 21 lines elided"));
+        assert!(earlier.contains("elided_lines"));
+        assert!(!output(&messages[4]).contains("elided"));
+
+        let messages = build_messages("System prompt", &session, "", "", 0);
+        assert!(!output(&messages[2]).contains("elided"));
+        // The earlier read is now whole, so the identical one repeats it as a note.
+        let again = output(&messages[4]);
+        assert!(again.contains("Unchanged since your read in turn 2 (tool call t1)"));
+        assert!(!again.contains("fn wanted"));
+    }
+
     #[test]
     fn test_turn_to_message() {
         let turn = Turn::user().with_block(ContentBlock::text("Hello world"));
 
-        let msg = turn_to_message(&turn);
+        let msg = turn_to_message(&turn, 1, None, &mut ReadTracker::new());
 
         assert!(msg.is_some());
         let msg = msg.unwrap();
@@ -223,7 +322,7 @@ mod tests {
     fn test_turn_to_message_empty() {
         let turn = Turn::user();
 
-        let msg = turn_to_message(&turn);
+        let msg = turn_to_message(&turn, 1, None, &mut ReadTracker::new());
 
         assert!(msg.is_none());
     }
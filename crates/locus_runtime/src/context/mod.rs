@@ -1,16 +1,34 @@
 //! Context and prompt building for the Runtime.
 //!
 //! Split into focused submodules:
-//! - **prompt** — system prompt construction and tool formatting
+//! - **prompt** — system prompt construction, tool formatting and the repo map
 //! - **messages** — session-to-LLM message conversion and request building
 //! - **window** — context window management (token estimation, compression)
-//! - **extract** — file path extraction from session turns
+//! - **extract** — file path and focus identifier extraction from session turns
+//! - **pack** — tree-sitter packing of large file reads to the relevant functions
+//! - **reads** — duplicate-read suppression and freshness hints for file reads
+//! - **redact** — secret placeholders in outgoing messages
+//! - **mentions** — files and folders a prompt names with `@path`
+//! - **pinned** — files kept in every request's context (`:pin`)
+//! - **instructions** — `AGENTS.md` and other project instruction files
 
 mod extract;
+mod instructions;
+mod mentions;
 mod messages;
+mod pack;
+mod pinned;
 mod prompt;
+mod reads;
+mod redact;
 mod window;
 
+pub use instructions::{
+    instructions_section, load_instructions, InstructionFile, ROOT_INSTRUCTION_FILES,
+};
+pub use mentions::{attach_mentions, Attachments};
 pub use messages::{build_generate_request, build_messages, build_session_context};
-pub use prompt::build_system_prompt;
+pub use pinned::PinnedFiles;
+pub use prompt::{build_repo_map, build_system_prompt, PLAN_MODE_PROMPT};
+pub use redact::redact_messages;
 pub use window::{compress_context, near_context_limit};
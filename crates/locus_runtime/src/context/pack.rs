@@ -0,0 +1,293 @@
+//! Language-aware packing of file reads kept in the context.
+//!
+//! Once the model has seen a file, later requests do not need all of it.
+//! Files over the per-file budget are parsed with tree-sitter and cut down to
+//! the functions the conversation is about, plus their callers and callees.
+//! Every other function keeps its first line and an elision marker, and code
+//! outside functions (imports, types, constants) is kept as is.
+
+use std::collections::HashSet;
+
+use locus_toolbus::tools::syntax::language_for;
+use tree_sitter::{Node, Parser};
+
+/// One function-like definition in a parsed file.
+struct Item {
+    name: String,
+    /// First and last line (0-based, inclusive).
+    start: usize,
+    end: usize,
+    /// Identifiers used inside the definition (callees, fields, locals).
+    idents: HashSet<String>,
+}
+
+/// A file read reduced to the spans that matter for the conversation.
+#[derive(Debug, PartialEq)]
+pub(crate) struct PackedFile {
+    pub content: String,
+    pub elided_lines: usize,
+}
+
+/// Node kinds that define a function or method, across the supported grammars.
+fn is_function(node: &Node) -> bool {
+    match node.kind() {
+        "function_item"
+        | "function_definition"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "method_definition"
+        | "method_declaration" => true,
+        // `const handler = (req) => { ... }`
+        "variable_declarator" => node.child_by_field_name("value").is_some_and(|v| {
+            matches!(
+                v.kind(),
+                "arrow_function" | "function_expression" | "function"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// Collect top-most function definitions; nested functions stay part of their parent.
+fn collect_items(node: Node, source: &[u8], items: &mut Vec<Item>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if !is_function(&child) {
+            collect_items(child, source, items);
+            continue;
+        }
+        let Some(name) = child
+            .child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+        else {
+            continue;
+        };
+        let mut idents = HashSet::new();
+        collect_idents(child, source, &mut idents);
+        items.push(Item {
+            name: name.to_string(),
+            start: child.start_position().row,
+            end: child.end_position().row,
+            idents,
+        });
+    }
+}
+
+fn collect_idents(node: Node, source: &[u8], idents: &mut HashSet<String>) {
+    if node.child_count() == 0 {
+        if node.kind().ends_with("identifier") {
+            if let Ok(text) = node.utf8_text(source) {
+                idents.insert(text.to_string());
+            }
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_idents(child, source, idents);
+    }
+}
+
+/// Pack `content` into roughly `budget` bytes, keeping functions named in
+/// `focus` and the functions they call or are called by.
+///
+/// Returns `None` when the file already fits, its language is not supported,
+/// or packing would not make it smaller.
+pub(crate) fn pack_file(
+    path: &str,
+    content: &str,
+    focus: &HashSet<String>,
+    budget: usize,
+) -> Option<PackedFile> {
+    if content.len() <= budget {
+        return None;
+    }
+    let mut parser = Parser::new();
+    parser.set_language(&language_for(path)?).ok()?;
+    let tree = parser.parse(content, None)?;
+
+    let mut items = Vec::new();
+    collect_items(tree.root_node(), content.as_bytes(), &mut items);
+    // One-liners cost as much as their marker; always keep them.
+    items.retain(|item| item.end > item.start + 1);
+    if items.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let span_len = |item: &Item| -> usize {
+        lines[item.start..=item.end.min(lines.len() - 1)]
+            .iter()
+            .map(|l| l.len() + 1)
+            .sum()
+    };
+
+    let seeds: Vec<usize> = (0..items.len())
+        .filter(|&i| focus.contains(&items[i].name))
+        .collect();
+    let related: Vec<usize> = (0..items.len())
+        .filter(|i| !seeds.contains(i))
+        .filter(|&i| {
+            seeds.iter().any(|&s| {
+                items[s].idents.contains(&items[i].name) || items[i].idents.contains(&items[s].name)
+            })
+        })
+        .collect();
+
+    // Everything outside the elidable spans, plus a first line and marker per item.
+    let mut used = content
+        .len()
+        .saturating_sub(items.iter().map(span_len).sum::<usize>())
+        + items
+            .iter()
+            .map(|item| lines[item.start].len() + 40)
+            .sum::<usize>();
+    let mut keep = vec![false; items.len()];
+    for (rank, &i) in seeds.iter().chain(related.iter()).enumerate() {
+        let cost = span_len(&items[i]);
+        // The first focused function is kept even when it alone exceeds the budget.
+        if rank == 0 || used + cost <= budget {
+            keep[i] = true;
+            used += cost;
+        }
+    }
+
+    let mut out = String::with_capacity(budget);
+    let mut elided_lines = 0;
+    let mut row = 0;
+    let mut next = items.iter().zip(&keep).peekable();
+    while row < lines.len() {
+        while next.peek().is_some_and(|(item, _)| item.start < row) {
+            next.next();
+        }
+        match next.peek() {
+            Some((item, false)) if item.start == row => {
+                let end = item.end.min(lines.len() - 1);
+                let indent: String = lines[row]
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect();
+                out.push_str(lines[row]);
+                out.push('\n');
+                out.push_str(&format!(
+                    "{}    … {} lines elided ({}-{})\n",
+                    indent,
+                    end - row,
+                    row + 2,
+                    end + 1
+                ));
+                elided_lines += end - row;
+                row = end + 1;
+            }
+            _ => {
+                out.push_str(lines[row]);
+                out.push('\n');
+                row += 1;
+            }
+        }
+    }
+
+    (out.len() < content.len()).then_some(PackedFile {
+        content: out,
+        elided_lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use std::fs;
+
+fn parse(input: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for line in input.lines() {
+        out.push(normalize(line));
+    }
+    out
+}
+
+fn normalize(line: &str) -> String {
+    let trimmed = line.trim();
+    let lower = trimmed.to_lowercase();
+    lower
+}
+
+fn render(items: &[String]) -> String {
+    let mut out = String::new();
+    for item in items {
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+        out.push_str(item);
+    }
+    out
+}
+
+fn load(path: &str) -> Vec<String> {
+    let text = fs::read_to_string(path).unwrap();
+    parse(&text)
+}
+"#;
+
+    fn focus(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn small_or_unknown_files_are_left_alone() {
+        assert!(pack_file("src/lib.rs", SOURCE, &focus(&["parse"]), 100_000).is_none());
+        assert!(pack_file("notes.txt", SOURCE, &focus(&["parse"]), 10).is_none());
+    }
+
+    #[test]
+    fn keeps_focus_with_callers_and_callees() {
+        let packed = pack_file("src/lib.rs", SOURCE, &focus(&["parse"]), 800).unwrap();
+        // parse itself, its callee normalize and its caller load stay whole.
+        assert!(packed.content.contains("out.push(normalize(line));"));
+        assert!(packed
+            .content
+            .contains("let lower = trimmed.to_lowercase();"));
+        assert!(packed.content.contains("parse(&text)"));
+        // render is unrelated and reduced to its signature.
+        assert!(packed
+            .content
+            .contains("fn render(items: &[String]) -> String {\n"));
+        assert!(packed.content.contains("… 21 lines elided (18-38)"));
+        assert!(!packed.content.contains("out.push_str(item);"));
+        assert!(packed.content.starts_with("use std::fs;\n"));
+        assert_eq!(packed.elided_lines, 21);
+    }
+
+    #[test]
+    fn budget_limits_related_functions() {
+        let packed = pack_file("src/lib.rs", SOURCE, &focus(&["parse"]), 560).unwrap();
+        assert!(packed.content.contains("out.push(normalize(line));"));
+        assert!(!packed.content.contains("parse(&text)"));
+        assert!(!packed
+            .content
+            .contains("let lower = trimmed.to_lowercase();"));
+    }
+
+    #[test]
+    fn packs_python_methods() {
+        let source = "class Store:\n    def get(self, key):\n        value = self.data[key]\n        return value\n\n    def put(self, key, value):\n        self.data[key] = value\n        self.get(key)\n";
+        let packed = pack_file("store.py", source, &focus(&["nothing"]), 10).unwrap();
+        assert!(packed
+            .content
+            .contains("    def get(self, key):\n        … 2 lines elided (3-4)\n"));
+        assert_eq!(packed.elided_lines, 4);
+    }
+}
@@ -0,0 +1,203 @@
+//! Files pinned into every turn's context (`:pin`, `RuntimeConfig::pinned_files`).
+//!
+//! Before each LLM call the pinned files are checked for changes (size and
+//! mtime) and the changed ones re-read through the bus's `read` tool, so the
+//! system prompt carries their current content rather than a stale copy, and
+//! protected or out-of-repo paths are refused as for the model's own reads. A
+//! file that changed since the previous call is marked so in the prompt.
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use locus_toolbus::ToolBus;
+use serde_json::json;
+
+/// Bytes of one pinned file sent; longer files are cut here.
+const MAX_PINNED_BYTES: u64 = 32_000;
+
+/// The pinned files and their content as of the last refresh.
+#[derive(Debug, Default)]
+pub struct PinnedFiles {
+    files: Vec<PinnedFile>,
+}
+
+#[derive(Debug)]
+struct PinnedFile {
+    path: String,
+    /// Modified time and size when last read; None until read or while missing.
+    stamp: Option<(SystemTime, u64)>,
+    /// Content when last read, or why it could not be.
+    content: Result<String, String>,
+    truncated: bool,
+    /// Content differs from the previous refresh.
+    changed: bool,
+}
+
+impl PinnedFiles {
+    /// Pin `paths` (repo-relative); they are read on the first refresh.
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let mut pinned = Self::default();
+        for path in paths {
+            pinned.add(path.to_string_lossy().to_string());
+        }
+        pinned
+    }
+
+    /// Pinned paths, in the order they were pinned.
+    pub fn paths(&self) -> Vec<String> {
+        self.files.iter().map(|f| f.path.clone()).collect()
+    }
+
+    /// Pin `path` once it reads as a file through the bus.
+    pub async fn pin(&mut self, toolbus: &ToolBus, path: &str) -> Result<(), String> {
+        let path = path.trim_start_matches("./");
+        if self.files.iter().any(|f| f.path == path) {
+            return Ok(());
+        }
+        let (content, truncated) = read_pinned(toolbus, path).await?;
+        self.add(path.to_string());
+        let file = self.files.last_mut().expect("just pinned");
+        file.stamp = stamp(toolbus, path);
+        file.content = Ok(content);
+        file.truncated = truncated;
+        Ok(())
+    }
+
+    /// Stop pinning `path`; false when it was not pinned.
+    pub fn unpin(&mut self, path: &str) -> bool {
+        let path = path.trim_start_matches("./");
+        let before = self.files.len();
+        self.files.retain(|f| f.path != path);
+        self.files.len() != before
+    }
+
+    /// Re-read the pinned files that changed on disk since the last refresh.
+    pub async fn refresh(&mut self, toolbus: &ToolBus) {
+        for file in &mut self.files {
+            file.changed = false;
+            let now = stamp(toolbus, &file.path);
+            if now.is_some() && now == file.stamp {
+                continue;
+            }
+            let read = read_pinned(toolbus, &file.path).await;
+            let had_content = file.stamp.is_some() && file.content.is_ok();
+            file.stamp = now.filter(|_| read.is_ok());
+            match read {
+                Ok((content, truncated)) => {
+                    file.changed = had_content && file.content.as_ref() != Ok(&content);
+                    file.content = Ok(content);
+                    file.truncated = truncated;
+                }
+                Err(e) => file.content = Err(e),
+            }
+        }
+    }
+
+    /// The system prompt section for the pinned files; empty when none are.
+    pub fn section(&self) -> String {
+        if self.files.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from(
+            "## Pinned Files\nThe user pinned these files; this is their current content, \
+             re-read before every request.\n",
+        );
+        for file in &self.files {
+            let note = if file.changed {
+                " (changed since the previous request)"
+            } else {
+                ""
+            };
+            out.push_str(&format!("\n### {}{}\n", file.path, note));
+            match &file.content {
+                Ok(content) => {
+                    out.push_str("```\n");
+                    out.push_str(content);
+                    if !content.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n");
+                    if file.truncated {
+                        out.push_str(&format!("(Cut at {} bytes)\n", MAX_PINNED_BYTES));
+                    }
+                }
+                Err(e) => out.push_str(&format!("(Not readable: {})\n", e)),
+            }
+        }
+        out.trim_end().to_string()
+    }
+
+    fn add(&mut self, path: String) {
+        self.files.push(PinnedFile {
+            path,
+            stamp: None,
+            content: Err("not read yet".to_string()),
+            truncated: false,
+            changed: false,
+        });
+    }
+}
+
+/// Modified time and size of `path` under the bus's repo root.
+fn stamp(toolbus: &ToolBus, path: &str) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(toolbus.repo_root().join(path)).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// `path`'s content through the bus, and whether it was cut.
+async fn read_pinned(toolbus: &ToolBus, path: &str) -> Result<(String, bool), String> {
+    let (output, _) = toolbus
+        .call(
+            "read",
+            json!({ "path": path, "max_bytes": MAX_PINNED_BYTES }),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    if output["type"] != "file" {
+        return Err(format!("{} is not a file", path));
+    }
+    Ok((
+        output["content"].as_str().unwrap_or_default().to_string(),
+        output["truncated"] == true,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn pinned_files_follow_their_content_on_disk() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs/STYLE.md"), "Use tabs.\n").unwrap();
+        let toolbus = ToolBus::new(dir.path().to_path_buf());
+
+        let mut pinned = PinnedFiles::new(&[PathBuf::from("missing.md")]);
+        assert!(pinned.pin(&toolbus, "docs").await.is_err());
+        assert!(pinned.pin(&toolbus, "nope.md").await.is_err());
+        pinned.pin(&toolbus, "./docs/STYLE.md").await.unwrap();
+        assert_eq!(pinned.paths(), ["missing.md", "docs/STYLE.md"]);
+
+        pinned.refresh(&toolbus).await;
+        let section = pinned.section();
+        assert!(section.contains("### docs/STYLE.md\n```\nUse tabs.\n```"));
+        assert!(section.contains("### missing.md\n(Not readable:"));
+
+        std::fs::write(dir.path().join("docs/STYLE.md"), "Use spaces, four.\n").unwrap();
+        pinned.refresh(&toolbus).await;
+        let section = pinned.section();
+        assert!(section.contains("### docs/STYLE.md (changed since the previous request)"));
+        assert!(section.contains("Use spaces, four."));
+        assert!(!section.contains("Use tabs."));
+
+        pinned.refresh(&toolbus).await;
+        assert!(!pinned.section().contains("changed since"));
+
+        assert!(pinned.unpin("docs/STYLE.md"));
+        assert!(!pinned.unpin("docs/STYLE.md"));
+        assert!(pinned.unpin("missing.md"));
+        assert!(pinned.section().is_empty());
+    }
+}
@@ -1,14 +1,56 @@
-//! System prompt construction and tool formatting.
+//! System prompt construction, tool formatting and the repo map.
 
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use locus_toolbus::tools::syntax::language_for;
 use locus_toolbus::ToolInfo;
+use tree_sitter::{Node, Parser};
+
+/// Files the repo map looks at; larger repos are mapped from the first ones walked.
+const REPO_MAP_MAX_FILES: usize = 2_000;
+/// Larger files (generated code, bundles) are left out of the repo map.
+const REPO_MAP_MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Symbols listed per file; the rest are counted in a `… N more` line.
+const REPO_MAP_SYMBOLS_PER_FILE: usize = 12;
+/// Longest symbol line in the repo map, in characters.
+const REPO_MAP_LINE_CHARS: usize = 100;
+
+/// Appended to the system prompt in plan mode (`:plan`).
+pub const PLAN_MODE_PROMPT: &str = r#"
+## Plan Mode
+You are planning, not implementing. Only read-only tools (plus `task_list`) are
+available and calls to anything else are refused; do not try to edit files or
+run commands that change the project. Investigate what you need, then reply with
+the plan itself as markdown: the goal, the files to change and how, numbered
+steps, and how to verify the result. The plan is saved and carried out later
+with every tool available, so make each step concrete enough to follow as is.
+"#;
 
-/// Build the system prompt with tool descriptions and graph map.
+/// Build the system prompt with tool descriptions, graph map, repo map and
+/// project instructions.
 ///
-/// Includes the agent identity, capabilities, safety rules, and
-/// a structural map of the LocusGraph hierarchy (if available).
-pub fn build_system_prompt(tools: &[ToolInfo], graph_map: &str) -> String {
+/// Includes the agent identity, capabilities, safety rules, a structural map
+/// of the LocusGraph hierarchy, a ranked map of the repo's files and
+/// top-level symbols and the instruction section from
+/// [instructions_section](super::instructions_section) (each if available).
+pub fn build_system_prompt(
+    tools: &[ToolInfo],
+    graph_map: &str,
+    repo_map: &str,
+    instructions: &str,
+) -> String {
     let tools_desc = format_tools(tools);
 
+    let repo_section = if repo_map.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n## Repo Map\nMain files and their top-level symbols, most referenced first. Use it to go straight to the right file instead of searching:\n```\n{}\n```\n",
+            repo_map
+        )
+    };
+
     let graph_section = if graph_map.is_empty() {
         String::new()
     } else {
@@ -42,13 +84,13 @@ You have access to memories from previous sessions. Use them to:
 - Learn from errors and solutions
 - Remember project conventions and patterns
 - Track user preferences
-{graph_section}
+{graph_section}{repo_section}
 ## Behavior
 - Be concise and direct
 - Make autonomous decisions when clear
 - Ask for clarification only when truly ambiguous
 - Store important decisions and outcomes to memory
-"#
+{instructions}"#
     )
 }
 
@@ -71,6 +113,205 @@ pub(crate) fn format_tools(tools: &[ToolInfo]) -> String {
         .join("\n")
 }
 
+/// A top-level definition in a mapped file.
+struct MapSymbol {
+    name: String,
+    /// First line of the definition, trimmed.
+    line: String,
+}
+
+struct MapFile {
+    path: String,
+    symbols: Vec<MapSymbol>,
+    /// Identifiers used anywhere in the file.
+    idents: HashSet<String>,
+}
+
+/// Compact map of the repo's source files and their top-level symbols, within
+/// about `budget_tokens` tokens (~4 characters each). Empty when the budget is 0
+/// or no file has a supported grammar.
+///
+/// Files are ranked by how many other files use their symbols, so the map leads
+/// with the code the rest of the repo is built on; ties go to shallower paths.
+pub fn build_repo_map(repo_root: &Path, budget_tokens: usize) -> String {
+    if budget_tokens == 0 {
+        return String::new();
+    }
+    let files = map_files(repo_root);
+
+    // In how many files each identifier appears.
+    let mut ident_files: HashMap<&str, usize> = HashMap::new();
+    for file in &files {
+        for ident in &file.idents {
+            *ident_files.entry(ident.as_str()).or_default() += 1;
+        }
+    }
+    // Uses of a symbol in other files (its own file counts once).
+    let references = |symbol: &MapSymbol| {
+        ident_files
+            .get(symbol.name.as_str())
+            .map_or(0, |n| n.saturating_sub(1))
+    };
+
+    let mut ranked: Vec<(usize, &MapFile)> = files
+        .iter()
+        .filter(|f| !f.symbols.is_empty())
+        .map(|f| (f.symbols.iter().map(references).sum(), f))
+        .collect();
+    ranked.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| {
+                a.path
+                    .matches('/')
+                    .count()
+                    .cmp(&b.path.matches('/').count())
+            })
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let budget_chars = budget_tokens.saturating_mul(4);
+    let mut out = String::new();
+    for (shown, (_, file)) in ranked.iter().enumerate() {
+        let mut block = format!("{}\n", file.path);
+        let mut keep: Vec<usize> = (0..file.symbols.len()).collect();
+        if keep.len() > REPO_MAP_SYMBOLS_PER_FILE {
+            keep.sort_by_key(|&i| std::cmp::Reverse(references(&file.symbols[i])));
+            keep.truncate(REPO_MAP_SYMBOLS_PER_FILE);
+            keep.sort_unstable();
+        }
+        for &i in &keep {
+            block.push_str(&format!("  {}\n", file.symbols[i].line));
+        }
+        if keep.len() < file.symbols.len() {
+            block.push_str(&format!("  … {} more\n", file.symbols.len() - keep.len()));
+        }
+        if out.len() + block.len() > budget_chars {
+            out.push_str(&format!("… {} more files\n", ranked.len() - shown));
+            break;
+        }
+        out.push_str(&block);
+    }
+    out.trim_end().to_string()
+}
+
+/// Parse every source file with a bundled grammar, in path order.
+fn map_files(repo_root: &Path) -> Vec<MapFile> {
+    let walker = ignore::WalkBuilder::new(repo_root)
+        .hidden(true)
+        .git_ignore(true)
+        .git_exclude(true)
+        .git_global(false)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != ".git")
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker.flatten() {
+        if files.len() >= REPO_MAP_MAX_FILES {
+            break;
+        }
+        if !entry.file_type().is_some_and(|t| t.is_file())
+            || entry
+                .metadata()
+                .map_or(true, |m| m.len() > REPO_MAP_MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(repo_root) else {
+            continue;
+        };
+        let path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if is_test_path(&path) {
+            continue;
+        }
+        let Some(language) = language_for(&path) else {
+            continue;
+        };
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let mut parser = Parser::new();
+        if parser.set_language(&language).is_err() {
+            continue;
+        }
+        let Some(tree) = parser.parse(&text, None) else {
+            continue;
+        };
+        files.push(MapFile {
+            path,
+            symbols: top_level_symbols(tree.root_node(), &text),
+            idents: text
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|w| !w.is_empty())
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+    files
+}
+
+/// Test files and directories, which the map leaves out.
+fn is_test_path(path: &str) -> bool {
+    let mut parts = path.split('/');
+    let file = parts.next_back().unwrap_or_default();
+    let stem = file.split('.').next().unwrap_or_default();
+    parts.any(|dir| matches!(dir, "test" | "tests" | "__tests__"))
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || file.contains(".test.")
+        || file.contains(".spec.")
+}
+
+/// Named top-level definitions, in source order. Rust `mod` declarations are
+/// left out: the file paths already show the module tree.
+fn top_level_symbols(root: Node, text: &str) -> Vec<MapSymbol> {
+    let mut symbols = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if node.kind() == "mod_item" {
+            continue;
+        }
+        let mut item = node;
+        // `export function f` and `@decorated def f` wrap the definition.
+        if matches!(node.kind(), "export_statement" | "decorated_definition") {
+            if let Some(inner) = node
+                .child_by_field_name("declaration")
+                .or_else(|| node.child_by_field_name("definition"))
+            {
+                item = inner;
+            }
+        }
+        let Some(name) = item
+            .child_by_field_name("name")
+            .or_else(|| item.child_by_field_name("type"))
+            .and_then(|n| n.utf8_text(text.as_bytes()).ok())
+        else {
+            continue;
+        };
+        let first_line = text[item.start_byte()..].lines().next().unwrap_or_default();
+        let mut line = first_line
+            .trim()
+            .trim_end_matches('{')
+            .trim_end()
+            .to_string();
+        if line.chars().count() > REPO_MAP_LINE_CHARS {
+            line = line.chars().take(REPO_MAP_LINE_CHARS).collect();
+            line.push('…');
+        }
+        symbols.push(MapSymbol {
+            name: name.to_string(),
+            line,
+        });
+    }
+    symbols
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,17 +331,18 @@ mod tests {
             },
         ];
 
-        let prompt = build_system_prompt(&tools, "");
+        let prompt = build_system_prompt(&tools, "", "", "");
 
         assert!(prompt.contains("locus.codes"));
         assert!(prompt.contains("bash"));
         assert!(prompt.contains("read"));
         assert!(prompt.contains("Safety Rules"));
         assert!(!prompt.contains("Graph Map"));
+        assert!(!prompt.contains("Repo Map"));
     }
 
     #[test]
-    fn test_build_system_prompt_with_graph_map() {
+    fn test_build_system_prompt_with_graph_and_repo_map() {
         let tools = vec![ToolInfo {
             name: "bash".to_string(),
             description: "Execute commands".to_string(),
@@ -108,11 +350,13 @@ mod tests {
         }];
 
         let graph_map = "project:myproject_abc123\n  └── tool_anchor:myproject_abc123";
-        let prompt = build_system_prompt(&tools, graph_map);
+        let prompt = build_system_prompt(&tools, graph_map, "src/lib.rs\n  pub fn run()", "");
 
         assert!(prompt.contains("## Graph Map"));
         assert!(prompt.contains("project:myproject_abc123"));
         assert!(prompt.contains("tool_anchor:myproject_abc123"));
+        assert!(prompt.contains("## Repo Map"));
+        assert!(prompt.contains("  pub fn run()"));
     }
 
     #[test]
@@ -136,4 +380,48 @@ mod tests {
 
         assert!(formatted.contains("No tools available"));
     }
+
+    #[test]
+    fn test_build_repo_map_ranks_by_references() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let write = |path: &str, text: &str| {
+            let full = root.join(path);
+            std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+            std::fs::write(full, text).unwrap();
+        };
+        write(
+            "src/main.rs",
+            "use crate::db::Pool;\n\nfn main() {\n    let pool = Pool::open(\"db\");\n}\n",
+        );
+        write(
+            "src/api/handlers.rs",
+            "/// Serves one request.\npub async fn handle(pool: &Pool) -> Response {\n    todo!()\n}\n",
+        );
+        write(
+            "src/db.rs",
+            "pub struct Pool {\n    url: String,\n}\n\nimpl Pool {\n    pub fn open(url: &str) -> Self {\n        Self { url: url.into() }\n    }\n}\n",
+        );
+        write("README.md", "# Service\n");
+        write(
+            "tests/db.rs",
+            "fn opens_pool() {\n    Pool::open(\"x\");\n}\n",
+        );
+        write("src/db_test.rs", "fn pool_test() {}\n");
+
+        let map = build_repo_map(root, 1024);
+        assert_eq!(
+            map,
+            "src/db.rs\n  pub struct Pool\n  impl Pool\n\
+             src/main.rs\n  fn main()\n\
+             src/api/handlers.rs\n  pub async fn handle(pool: &Pool) -> Response"
+        );
+
+        let small = build_repo_map(root, 10);
+        assert_eq!(
+            small,
+            "src/db.rs\n  pub struct Pool\n  impl Pool\n… 2 more files"
+        );
+        assert!(build_repo_map(root, 0).is_empty());
+    }
 }
@@ -0,0 +1,160 @@
+//! Duplicate-read suppression for file reads sent to the LLM.
+//!
+//! The session keeps every `read` result whole; while the request is built, a
+//! read whose content matches an earlier read of the same file that is still
+//! sent whole becomes a short "unchanged since turn N" note, and a read whose
+//! content differs from the earlier one carries a freshness hint.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value as JsonValue};
+
+/// The last read of a file seen so far in the request.
+struct SeenRead {
+    /// 1-based position of the turn holding the read.
+    turn: usize,
+    tool_use_id: String,
+    content: String,
+    /// Sent with its full content, so later duplicates may point at it.
+    whole: bool,
+}
+
+/// File reads already in the request, keyed by path.
+#[derive(Default)]
+pub(crate) struct ReadTracker {
+    seen: HashMap<String, SeenRead>,
+}
+
+impl ReadTracker {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// `output` of a tool result in `turn` (1-based) as it goes to the LLM.
+    ///
+    /// Reads that repeat an earlier whole read are replaced by a note; other
+    /// reads go through `pack`, which returns the output to send and whether it
+    /// was cut down.
+    pub(crate) fn apply(
+        &mut self,
+        turn: usize,
+        output: &JsonValue,
+        pack: impl FnOnce(&JsonValue) -> (JsonValue, bool),
+    ) -> JsonValue {
+        let result = &output["result"];
+        let (Some(path), Some(content)) = (result["path"].as_str(), result["content"].as_str())
+        else {
+            return pack(output).0;
+        };
+        if output["tool_name"] != "read" || result["type"] != "file" {
+            return pack(output).0;
+        }
+        let key = path.trim_start_matches("./").to_string();
+
+        let earlier = self.seen.get(&key);
+        if let Some(earlier) = earlier.filter(|e| e.whole && e.content == content) {
+            let mut output = output.clone();
+            output["result"] = json!({
+                "type": "file",
+                "path": path,
+                "unchanged": true,
+                "note": format!(
+                    "Unchanged since your read in turn {} (tool call {}); that content is current.",
+                    earlier.turn, earlier.tool_use_id
+                ),
+            });
+            return output;
+        }
+
+        let freshness = earlier.filter(|e| e.content != content).map(|e| {
+            format!(
+                "Changed since your read in turn {} (tool call {}); this is the current content.",
+                e.turn, e.tool_use_id
+            )
+        });
+        let (mut sent, packed) = pack(output);
+        if let Some(freshness) = freshness {
+            sent["result"]["freshness"] = freshness.into();
+        }
+        self.seen.insert(
+            key,
+            SeenRead {
+                turn,
+                tool_use_id: output["tool_use_id"].as_str().unwrap_or("").to_string(),
+                content: content.to_string(),
+                whole: !packed,
+            },
+        );
+        sent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(id: &str, path: &str, content: &str) -> JsonValue {
+        json!({
+            "tool_use_id": id,
+            "tool_name": "read",
+            "result": { "type": "file", "path": path, "content": content, "truncated": false },
+        })
+    }
+
+    fn whole(output: &JsonValue) -> (JsonValue, bool) {
+        (output.clone(), false)
+    }
+
+    #[test]
+    fn test_repeated_read_becomes_unchanged_note() {
+        let mut reads = ReadTracker::new();
+        let first = reads.apply(2, &read("t1", "src/lib.rs", "fn a() {}\n"), whole);
+        assert_eq!(first["result"]["content"], "fn a() {}\n");
+
+        let again = reads.apply(4, &read("t2", "./src/lib.rs", "fn a() {}\n"), whole);
+        assert_eq!(again["tool_use_id"], "t2");
+        assert_eq!(again["result"]["unchanged"], true);
+        assert!(again["result"].get("content").is_none());
+        let note = again["result"]["note"].as_str().unwrap();
+        assert!(note.contains("turn 2 (tool call t1)"));
+    }
+
+    #[test]
+    fn test_changed_read_carries_freshness_hint() {
+        let mut reads = ReadTracker::new();
+        reads.apply(2, &read("t1", "src/lib.rs", "fn a() {}\n"), whole);
+        let changed = reads.apply(4, &read("t2", "src/lib.rs", "fn b() {}\n"), whole);
+        assert_eq!(changed["result"]["content"], "fn b() {}\n");
+        let hint = changed["result"]["freshness"].as_str().unwrap();
+        assert!(hint.contains("Changed since your read in turn 2"));
+
+        // Later duplicates point at the newest version.
+        let again = reads.apply(6, &read("t3", "src/lib.rs", "fn b() {}\n"), whole);
+        assert!(again["result"]["note"].as_str().unwrap().contains("turn 4"));
+    }
+
+    #[test]
+    fn test_read_after_packed_copy_is_sent_whole() {
+        let mut reads = ReadTracker::new();
+        reads.apply(2, &read("t1", "src/lib.rs", "fn a() {}\n"), |o| {
+            (o.clone(), true)
+        });
+        let again = reads.apply(4, &read("t2", "src/lib.rs", "fn a() {}\n"), whole);
+        assert_eq!(again["result"]["content"], "fn a() {}\n");
+        assert!(again["result"].get("freshness").is_none());
+    }
+
+    #[test]
+    fn test_other_results_pass_through() {
+        let mut reads = ReadTracker::new();
+        let grep = json!({"tool_use_id": "t1", "tool_name": "grep", "result": {"matches": []}});
+        assert_eq!(reads.apply(2, &grep, whole), grep);
+        let dir = json!({
+            "tool_use_id": "t2",
+            "tool_name": "read",
+            "result": {"type": "directory", "path": "src", "entries": []},
+        });
+        assert_eq!(reads.apply(3, &dir, whole), dir);
+        assert_eq!(reads.apply(4, &dir, whole), dir);
+    }
+}
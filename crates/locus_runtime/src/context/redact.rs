@@ -0,0 +1,112 @@
+//! Secret redaction of outgoing LLM messages.
+
+use locus_core::{RedactedSecret, SecretScrubber};
+use locus_llms::types::{ContentPart, Message, MessageContent};
+
+/// Replace secrets in every message (text, tool calls and tool results) with
+/// the scrubber's placeholders. Returns the secrets seen for the first time.
+pub fn redact_messages(
+    messages: &mut [Message],
+    scrubber: &mut SecretScrubber,
+) -> Vec<RedactedSecret> {
+    let mut found = Vec::new();
+    for message in messages {
+        match &mut message.content {
+            MessageContent::Text(text) => *text = scrubber.scrub_text(text, &mut found),
+            MessageContent::Parts(parts) => {
+                for part in parts {
+                    match part {
+                        ContentPart::Text { text, .. } => {
+                            *text = scrubber.scrub_text(text, &mut found)
+                        }
+                        ContentPart::ToolCall { arguments, .. } => {
+                            scrubber.scrub_value(arguments, &mut found)
+                        }
+                        ContentPart::ToolResult { content, .. } => {
+                            scrubber.scrub_value(content, &mut found)
+                        }
+                        ContentPart::Image { .. } => {}
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::RedactionPolicy;
+    use locus_llms::types::Role as LlmRole;
+
+    #[test]
+    fn test_redacts_tool_results_with_stable_placeholders() {
+        let read = serde_json::json!({
+            "tool_name": "read",
+            "result": { "path": ".env", "content": "OPENAI_API_KEY=sk-proj0123456789abc\n" },
+        });
+        let mut messages = vec![
+            Message::new(LlmRole::System, "You are a coding agent."),
+            Message::new(
+                LlmRole::Tool,
+                vec![ContentPart::tool_result("call_1", read.clone())],
+            ),
+        ];
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+
+        let found = redact_messages(&mut messages, &mut scrubber);
+        assert_eq!(found.len(), 1);
+        let sent = serde_json::to_string(&messages).unwrap();
+        assert!(sent.contains("OPENAI_API_KEY=[SECRET_1]"));
+        assert!(!sent.contains("sk-proj"));
+
+        // The next request carries the same read: same text, nothing new to report.
+        messages.push(Message::new(
+            LlmRole::Tool,
+            vec![ContentPart::tool_result("call_2", read)],
+        ));
+        assert!(redact_messages(&mut messages, &mut scrubber).is_empty());
+        assert_eq!(
+            serde_json::to_string(&messages[1]).unwrap(),
+            serde_json::to_string(&messages[2])
+                .unwrap()
+                .replace("call_2", "call_1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edit_with_placeholders_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = "model: gpt-4o\napi_key: sk-live0123456789abc\n";
+        std::fs::write(dir.path().join("config.yaml"), config).unwrap();
+        let toolbus = locus_toolbus::ToolBus::new(dir.path().to_path_buf());
+        let read = toolbus
+            .call("read", serde_json::json!({ "path": "config.yaml" }))
+            .await
+            .unwrap();
+
+        let mut messages = vec![Message::new(
+            LlmRole::Tool,
+            vec![ContentPart::tool_result("call_1", read)],
+        )];
+        let mut scrubber = SecretScrubber::new(RedactionPolicy::default());
+        assert_eq!(redact_messages(&mut messages, &mut scrubber).len(), 1);
+        assert!(serde_json::to_string(&messages)
+            .unwrap()
+            .contains("api_key: [SECRET_1]"));
+
+        // The model edits the line it was shown, placeholder and all.
+        let args = serde_json::json!({
+            "path": "config.yaml",
+            "old_string": "api_key: [SECRET_1]",
+            "new_string": "openai_api_key: [SECRET_1]",
+        });
+        let run_args = scrubber.restore_file_content("edit_file", &args);
+        toolbus.call("edit_file", run_args).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("config.yaml")).unwrap(),
+            "model: gpt-4o\nopenai_api_key: sk-live0123456789abc\n"
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Guardrail assertions enforced after each editing turn.
+//!
+//! `.locus/guardrails.toml` sets hard limits that hold whatever the prompt says:
+//!
+//! ```toml
+//! on_violation = "revert"               # or "block"
+//! protected = ["Cargo.lock", "migrations/**"]
+//! max_diff_lines = 400                  # added + removed lines per turn, all files
+//!
+//! [[checks]]
+//! name = "tests"
+//! command = "cargo test -q"
+//! timeout_secs = 300
+//! ```
+//!
+//! A turn is an editing turn when a write tool (`edit_file`, `create_file`,
+//! `undo_edit`, `file_history`) touched a file. Each path is snapshotted before
+//! its first write in the turn; once all tool calls ran, the assertions are checked
+//! in order — protected files, diff size, then commands (only when the file
+//! assertions pass). On violation the runtime restores the snapshots (`revert`) or
+//! keeps the changes and stops the run for the user (`block`). Files changed by
+//! shell commands are not tracked.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use glob::{MatchOptions, Pattern};
+use locus_toolbus::ExecutionBackend;
+use serde::Deserialize;
+
+/// Guardrails file, relative to the repo root.
+pub const GUARDRAILS_FILE: &str = ".locus/guardrails.toml";
+
+const DEFAULT_CHECK_TIMEOUT_SECS: u64 = 300;
+/// How much of a failing check's output goes into the violation message.
+const CHECK_OUTPUT_TAIL_BYTES: usize = 2000;
+
+/// What happens to a turn that breaks an assertion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationAction {
+    /// Restore the files edited in the turn and let the agent try again.
+    #[default]
+    Revert,
+    /// Keep the edits and stop the run until the user replies.
+    Block,
+}
+
+/// A command that must exit 0 after every editing turn.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GuardrailCheck {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub command: String,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl GuardrailCheck {
+    fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.command)
+    }
+}
+
+/// Assertions loaded from [GUARDRAILS_FILE]. The default has none.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Guardrails {
+    #[serde(default)]
+    pub on_violation: ViolationAction,
+    /// Glob patterns, relative to the repo root. A pattern without `/` also
+    /// matches by file name anywhere in the tree.
+    #[serde(default)]
+    pub protected: Vec<String>,
+    #[serde(default)]
+    pub max_diff_lines: Option<usize>,
+    #[serde(default)]
+    pub checks: Vec<GuardrailCheck>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GuardrailsError {
+    #[error("Failed to read guardrails file: {0}")]
+    Read(#[from] std::io::Error),
+
+    #[error("Failed to parse guardrails file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Invalid protected pattern '{pattern}': {message}")]
+    Pattern { pattern: String, message: String },
+}
+
+impl Guardrails {
+    /// Load `.locus/guardrails.toml` under `repo_root`. A missing file yields no assertions.
+    pub fn load(repo_root: &Path) -> Result<Self, GuardrailsError> {
+        let path = repo_root.join(GUARDRAILS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let guardrails: Self = toml::from_str(&std::fs::read_to_string(path)?)?;
+        for pattern in &guardrails.protected {
+            Pattern::new(pattern).map_err(|e| GuardrailsError::Pattern {
+                pattern: pattern.clone(),
+                message: e.msg.to_string(),
+            })?;
+        }
+        Ok(guardrails)
+    }
+
+    /// True when there is nothing to enforce.
+    pub fn is_empty(&self) -> bool {
+        self.protected.is_empty() && self.max_diff_lines.is_none() && self.checks.is_empty()
+    }
+
+    /// Whether `rel_path` (relative to the repo root) matches a protected pattern.
+    pub fn is_protected(&self, rel_path: &Path) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let file_name = rel_path.file_name().map(Path::new);
+        self.protected.iter().any(|raw| {
+            let Ok(pattern) = Pattern::new(raw) else {
+                return false;
+            };
+            pattern.matches_path_with(rel_path, options)
+                || (!raw.contains('/')
+                    && file_name.is_some_and(|n| pattern.matches_path_with(n, options)))
+        })
+    }
+
+    /// Check the turn's edits; returns one message per broken assertion.
+    pub async fn evaluate(
+        &self,
+        repo_root: &Path,
+        edits: &TurnEdits,
+        backend: &ExecutionBackend,
+    ) -> Vec<String> {
+        let changes = edits.changes(repo_root);
+        if changes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut violations: Vec<String> = changes
+            .iter()
+            .filter(|(path, _)| self.is_protected(path))
+            .map(|(path, _)| format!("edited protected file {}", path.display()))
+            .collect();
+
+        if let Some(max) = self.max_diff_lines {
+            let total: usize = changes.iter().map(|(_, lines)| lines).sum();
+            if total > max {
+                violations.push(format!(
+                    "turn changed {} lines, over the limit of {}",
+                    total, max
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            for check in &self.checks {
+                if let Some(failure) = run_check(check, repo_root, backend).await {
+                    violations.push(failure);
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// Run one check command; returns the violation message when it fails.
+async fn run_check(
+    check: &GuardrailCheck,
+    repo_root: &Path,
+    backend: &ExecutionBackend,
+) -> Option<String> {
+    let mut prepared =
+        match backend.command(repo_root, Some(repo_root), ("sh", "-c"), &check.command) {
+            Ok(prepared) => prepared,
+            Err(e) => return Some(format!("check `{}` could not start: {}", check.label(), e)),
+        };
+    prepared.command.kill_on_drop(true);
+
+    let timeout = Duration::from_secs(check.timeout_secs.unwrap_or(DEFAULT_CHECK_TIMEOUT_SECS));
+    let output = match tokio::time::timeout(timeout, prepared.command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Some(format!("check `{}` could not start: {}", check.label(), e)),
+        Err(_) => {
+            backend.cleanup(prepared.container.as_deref()).await;
+            return Some(format!(
+                "check `{}` timed out after {}s",
+                check.label(),
+                timeout.as_secs()
+            ));
+        }
+    };
+    if output.status.success() {
+        return None;
+    }
+
+    let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+    let combined = combined.trim();
+    let mut start = combined.len().saturating_sub(CHECK_OUTPUT_TAIL_BYTES);
+    while !combined.is_char_boundary(start) {
+        start += 1;
+    }
+    let code = output
+        .status
+        .code()
+        .map_or_else(|| "signal".to_string(), |c| c.to_string());
+    Some(format!(
+        "check `{}` failed (exit {}):\n{}",
+        check.label(),
+        code,
+        &combined[start..]
+    ))
+}
+
+/// Files written during one turn, with their content before the first write.
+#[derive(Debug, Default)]
+pub struct TurnEdits {
+    /// Relative path → original content (None when the file did not exist).
+    originals: BTreeMap<PathBuf, Option<String>>,
+}
+
+impl TurnEdits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember `path` as it is now, unless it was already captured this turn.
+    /// Paths outside the repo root are ignored.
+    pub fn snapshot(&mut self, repo_root: &Path, path: &str) {
+        let Some(rel) = relative_path(repo_root, path) else {
+            return;
+        };
+        let abs = repo_root.join(&rel);
+        self.originals
+            .entry(rel)
+            .or_insert_with(|| std::fs::read_to_string(abs).ok());
+    }
+
+    /// Remember `path` as `content`, what a tool reported it held just before
+    /// writing it, unless it was already captured this turn. Paths outside the
+    /// repo root are ignored.
+    pub fn record(&mut self, repo_root: &Path, path: &str, content: String) {
+        let Some(rel) = relative_path(repo_root, path) else {
+            return;
+        };
+        self.originals.entry(rel).or_insert(Some(content));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+
+    /// Files whose content differs from the snapshot, with added + removed line counts.
+    pub fn changes(&self, repo_root: &Path) -> Vec<(PathBuf, usize)> {
+        self.originals
+            .iter()
+            .filter_map(|(rel, original)| {
+                let current = std::fs::read_to_string(repo_root.join(rel)).ok();
+                if &current == original {
+                    return None;
+                }
+                let lines = changed_lines(
+                    original.as_deref().unwrap_or_default(),
+                    current.as_deref().unwrap_or_default(),
+                );
+                Some((rel.clone(), lines))
+            })
+            .collect()
+    }
+
+    /// Files whose content differs from the snapshot, with their content before
+    /// and after (None when the file did not exist).
+    pub fn contents(&self, repo_root: &Path) -> Vec<(PathBuf, Option<String>, Option<String>)> {
+        self.originals
+            .iter()
+            .filter_map(|(rel, original)| {
+                let current = std::fs::read_to_string(repo_root.join(rel)).ok();
+                (&current != original).then(|| (rel.clone(), original.clone(), current))
+            })
+            .collect()
+    }
+
+    /// Put every captured file back as it was; files created in the turn are removed.
+    pub fn revert(&self, repo_root: &Path) -> std::io::Result<()> {
+        for (rel, original) in &self.originals {
+            let path = repo_root.join(rel);
+            match original {
+                Some(content) => std::fs::write(&path, content)?,
+                None if path.exists() => std::fs::remove_file(&path)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `path` relative to `repo_root`, or None if it points outside it.
+fn relative_path(repo_root: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    let rel = if path.is_absolute() {
+        path.strip_prefix(repo_root).ok()?.to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
+    let normal = rel
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    (normal && rel.components().next().is_some()).then_some(rel)
+}
+
+/// Added + removed lines after trimming the unchanged head and tail.
+fn changed_lines(old: &str, new: &str) -> usize {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (old.len() - prefix - suffix) + (new.len() - prefix - suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn guardrails(toml: &str) -> Guardrails {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_load_missing_and_invalid() {
+        let dir = TempDir::new().unwrap();
+        assert!(Guardrails::load(dir.path()).unwrap().is_empty());
+
+        fs::create_dir(dir.path().join(".locus")).unwrap();
+        fs::write(dir.path().join(GUARDRAILS_FILE), "protected = [\"[\"]\n").unwrap();
+        assert!(matches!(
+            Guardrails::load(dir.path()),
+            Err(GuardrailsError::Pattern { .. })
+        ));
+
+        fs::write(dir.path().join(GUARDRAILS_FILE), "max_diff = 3\n").unwrap();
+        assert!(matches!(
+            Guardrails::load(dir.path()),
+            Err(GuardrailsError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_full_file() {
+        let g = guardrails(
+            r#"
+            on_violation = "block"
+            protected = ["Cargo.lock"]
+            max_diff_lines = 10
+
+            [[checks]]
+            name = "tests"
+            command = "cargo test"
+            "#,
+        );
+        assert_eq!(g.on_violation, ViolationAction::Block);
+        assert_eq!(g.max_diff_lines, Some(10));
+        assert_eq!(g.checks[0].label(), "tests");
+        assert!(!g.is_empty());
+    }
+
+    #[test]
+    fn test_protected_patterns() {
+        let g = guardrails(r#"protected = ["Cargo.lock", "migrations/**", "src/*.sql"]"#);
+        assert!(g.is_protected(Path::new("Cargo.lock")));
+        assert!(g.is_protected(Path::new("crates/app/Cargo.lock")));
+        assert!(g.is_protected(Path::new("migrations/2024/001.sql")));
+        assert!(g.is_protected(Path::new("src/schema.sql")));
+        assert!(!g.is_protected(Path::new("src/db/schema.sql")));
+        assert!(!g.is_protected(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_changed_lines() {
+        assert_eq!(changed_lines("a\nb\nc\n", "a\nb\nc\n"), 0);
+        assert_eq!(changed_lines("a\nb\nc\n", "a\nx\nc\n"), 2);
+        assert_eq!(changed_lines("a\n", "a\nb\nc\n"), 2);
+        assert_eq!(changed_lines("", "a\nb\n"), 2);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let root = Path::new("/repo");
+        assert_eq!(
+            relative_path(root, "/repo/src/a.rs"),
+            Some(PathBuf::from("src/a.rs"))
+        );
+        assert_eq!(
+            relative_path(root, "src/a.rs"),
+            Some(PathBuf::from("src/a.rs"))
+        );
+        assert_eq!(relative_path(root, "/elsewhere/a.rs"), None);
+        assert_eq!(relative_path(root, "../a.rs"), None);
+    }
+
+    #[test]
+    fn test_snapshot_changes_and_revert() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.rs"), "one\ntwo\n").unwrap();
+
+        let mut edits = TurnEdits::new();
+        edits.snapshot(root, "a.rs");
+        edits.snapshot(root, "new.rs");
+        fs::write(root.join("a.rs"), "one\nTWO\n").unwrap();
+        // A second snapshot of the same path keeps the original content.
+        edits.snapshot(root, "a.rs");
+        fs::write(root.join("new.rs"), "fresh\n").unwrap();
+
+        let changes = edits.changes(root);
+        assert_eq!(
+            changes,
+            vec![(PathBuf::from("a.rs"), 2), (PathBuf::from("new.rs"), 1)]
+        );
+        assert_eq!(
+            edits.contents(root),
+            vec![
+                (
+                    PathBuf::from("a.rs"),
+                    Some("one\ntwo\n".to_string()),
+                    Some("one\nTWO\n".to_string())
+                ),
+                (PathBuf::from("new.rs"), None, Some("fresh\n".to_string())),
+            ]
+        );
+
+        edits.revert(root).unwrap();
+        assert_eq!(fs::read_to_string(root.join("a.rs")).unwrap(), "one\ntwo\n");
+        assert!(!root.join("new.rs").exists());
+        assert!(edits.changes(root).is_empty());
+        assert!(edits.contents(root).is_empty());
+    }
+
+    #[test]
+    fn test_record_reported_content() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/b.rs"), "new\n").unwrap();
+
+        let mut edits = TurnEdits::new();
+        edits.record(root, "src/b.rs", "old\n".to_string());
+        // A later report or snapshot of the same path keeps the first content.
+        edits.record(root, "src/b.rs", "newer\n".to_string());
+        edits.snapshot(root, "src/b.rs");
+
+        assert_eq!(edits.changes(root), vec![(PathBuf::from("src/b.rs"), 2)]);
+        edits.revert(root).unwrap();
+        assert_eq!(fs::read_to_string(root.join("src/b.rs")).unwrap(), "old\n");
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_reports_violations() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Cargo.lock"), "v1\n").unwrap();
+        let mut edits = TurnEdits::new();
+        edits.snapshot(root, "Cargo.lock");
+        fs::write(root.join("Cargo.lock"), "v2\nv3\n").unwrap();
+
+        let backend = ExecutionBackend::default();
+        let g = guardrails("protected = [\"Cargo.lock\"]\nmax_diff_lines = 2\n");
+        let violations = g.evaluate(root, &edits, &backend).await;
+        assert_eq!(
+            violations,
+            vec![
+                "edited protected file Cargo.lock".to_string(),
+                "turn changed 3 lines, over the limit of 2".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_runs_checks() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut edits = TurnEdits::new();
+        edits.snapshot(root, "a.txt");
+        fs::write(root.join("a.txt"), "x\n").unwrap();
+
+        let backend = ExecutionBackend::default();
+        let passing = guardrails("[[checks]]\ncommand = \"test -f a.txt\"\n");
+        assert!(passing.evaluate(root, &edits, &backend).await.is_empty());
+
+        let failing =
+            guardrails("[[checks]]\nname = \"lint\"\ncommand = \"echo bad style; exit 3\"\n");
+        let violations = failing.evaluate(root, &edits, &backend).await;
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].starts_with("check `lint` failed (exit 3)"));
+        assert!(violations[0].contains("bad style"));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_ignores_unchanged_turn() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Cargo.lock"), "v1\n").unwrap();
+        let mut edits = TurnEdits::new();
+        edits.snapshot(root, "Cargo.lock");
+
+        let g = guardrails("protected = [\"Cargo.lock\"]\n[[checks]]\ncommand = \"false\"\n");
+        let violations = g.evaluate(root, &edits, &ExecutionBackend::default()).await;
+        assert!(violations.is_empty());
+    }
+}
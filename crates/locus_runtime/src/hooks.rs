@@ -0,0 +1,383 @@
+//! User hooks: shell commands run around tool calls and at the end of a turn.
+//!
+//! `.locus/hooks.toml` lists them by when they run:
+//!
+//! ```toml
+//! [[pre_tool]]
+//! name = "no pushes"
+//! tools = ["bash"]                  # omit (or "*") for every tool
+//! command = "! grep -q 'git push'"  # exit non-zero to block the call
+//!
+//! [[post_tool]]
+//! tools = ["edit_file", "create_file"]
+//! command = "cargo fmt"
+//!
+//! [[turn_end]]
+//! command = "cargo check -q --message-format short"
+//! feed_back = true                  # pass the output to the model
+//! timeout_secs = 120
+//! ```
+//!
+//! Each hook runs with `sh -c` in the repo root on the host (not the execution
+//! backend), like a git hook. It gets a JSON payload on stdin — `event`, `tool`,
+//! `args`, and for `post_tool` the `result` and `is_error` — and `LOCUS_HOOK_EVENT`
+//! and `LOCUS_TOOL_NAME` in its environment.
+//!
+//! - `pre_tool`: a non-zero exit blocks the call; the model gets an error result
+//!   with the hook's output instead
+//! - `post_tool`: runs after the call; with `feed_back`, the output is added to
+//!   the result the model sees under `hooks`
+//! - `turn_end`: runs once the run ends; with `feed_back`, the output is passed
+//!   to the model with the next message
+//!
+//! Failing `post_tool` and `turn_end` hooks are reported to the user either way.
+//! Hooks apply to the calls the agent makes itself, not to `task` sub-agents.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Hooks file, relative to the repo root.
+pub const HOOKS_FILE: &str = ".locus/hooks.toml";
+
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+/// How much of a hook's output is kept for the model and the user.
+const HOOK_OUTPUT_TAIL_BYTES: usize = 2000;
+
+/// One command from [HOOKS_FILE].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hook {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub command: String,
+    /// Tool names the hook runs for; empty or `"*"` means every tool. Ignored
+    /// for `turn_end`.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Pass the hook's output on to the model.
+    #[serde(default)]
+    pub feed_back: bool,
+}
+
+impl Hook {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.command)
+    }
+
+    fn applies_to(&self, tool_name: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|t| t == "*" || t == tool_name)
+    }
+}
+
+/// Hooks loaded from [HOOKS_FILE]. The default has none.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hooks {
+    #[serde(default)]
+    pub pre_tool: Vec<Hook>,
+    #[serde(default)]
+    pub post_tool: Vec<Hook>,
+    #[serde(default)]
+    pub turn_end: Vec<Hook>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HooksError {
+    #[error("Failed to read hooks file: {0}")]
+    Read(#[from] std::io::Error),
+
+    #[error("Failed to parse hooks file: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// How one hook run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookRun {
+    pub label: String,
+    pub success: bool,
+    /// Exit code, or why there is none ("timed out", "signal", a spawn error).
+    pub status: String,
+    /// Tail of stdout and stderr, trimmed.
+    pub output: String,
+    pub feed_back: bool,
+}
+
+impl HookRun {
+    /// One-line summary for the user.
+    pub fn describe(&self) -> String {
+        let first = self.output.lines().next().unwrap_or_default();
+        if first.is_empty() {
+            format!("hook `{}` failed ({})", self.label, self.status)
+        } else {
+            format!("hook `{}` failed ({}): {}", self.label, self.status, first)
+        }
+    }
+}
+
+impl Hooks {
+    /// Load `.locus/hooks.toml` under `repo_root`. A missing file yields no hooks.
+    pub fn load(repo_root: &Path) -> Result<Self, HooksError> {
+        let path = repo_root.join(HOOKS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre_tool.is_empty() && self.post_tool.is_empty() && self.turn_end.is_empty()
+    }
+
+    /// Run the `pre_tool` hooks for a call, stopping at the first that fails:
+    /// that one blocks the call.
+    pub async fn before_tool(
+        &self,
+        repo_root: &Path,
+        tool_name: &str,
+        args: &JsonValue,
+    ) -> Vec<HookRun> {
+        let payload = json!({ "event": "pre_tool", "tool": tool_name, "args": args });
+        let mut runs = Vec::new();
+        for hook in self.pre_tool.iter().filter(|h| h.applies_to(tool_name)) {
+            let run = run_hook(hook, repo_root, "pre_tool", Some(tool_name), &payload).await;
+            let blocked = !run.success;
+            runs.push(run);
+            if blocked {
+                break;
+            }
+        }
+        runs
+    }
+
+    /// Run the `post_tool` hooks for a finished call.
+    pub async fn after_tool(
+        &self,
+        repo_root: &Path,
+        tool_name: &str,
+        args: &JsonValue,
+        result: &JsonValue,
+        is_error: bool,
+    ) -> Vec<HookRun> {
+        let payload = json!({
+            "event": "post_tool",
+            "tool": tool_name,
+            "args": args,
+            "result": result,
+            "is_error": is_error,
+        });
+        let mut runs = Vec::new();
+        for hook in self.post_tool.iter().filter(|h| h.applies_to(tool_name)) {
+            runs.push(run_hook(hook, repo_root, "post_tool", Some(tool_name), &payload).await);
+        }
+        runs
+    }
+
+    /// Run the `turn_end` hooks.
+    pub async fn at_turn_end(&self, repo_root: &Path) -> Vec<HookRun> {
+        let payload = json!({ "event": "turn_end" });
+        let mut runs = Vec::new();
+        for hook in &self.turn_end {
+            runs.push(run_hook(hook, repo_root, "turn_end", None, &payload).await);
+        }
+        runs
+    }
+}
+
+/// Output of the hooks that feed back, for a tool result or a note to the model.
+pub fn feedback(runs: &[HookRun]) -> Vec<JsonValue> {
+    runs.iter()
+        .filter(|run| run.feed_back && !run.output.is_empty())
+        .map(|run| json!({ "hook": run.label, "status": run.status, "output": run.output }))
+        .collect()
+}
+
+async fn run_hook(
+    hook: &Hook,
+    repo_root: &Path,
+    event: &str,
+    tool_name: Option<&str>,
+    payload: &JsonValue,
+) -> HookRun {
+    let finished = |success: bool, status: String, output: String| HookRun {
+        label: hook.label().to_string(),
+        success,
+        status,
+        output,
+        feed_back: hook.feed_back,
+    };
+
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(&hook.command)
+        .current_dir(repo_root)
+        .env("LOCUS_HOOK_EVENT", event)
+        .env("LOCUS_TOOL_NAME", tool_name.unwrap_or_default())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return finished(false, format!("could not start: {}", e), String::new()),
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that doesn't read its input closes the pipe early; that's fine.
+        let _ = stdin.write_all(payload.to_string().as_bytes()).await;
+    }
+
+    let timeout = Duration::from_secs(hook.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS));
+    let output = match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return finished(false, format!("failed: {}", e), String::new()),
+        Err(_) => {
+            return finished(
+                false,
+                format!("timed out after {}s", timeout.as_secs()),
+                String::new(),
+            )
+        }
+    };
+
+    let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+    let combined = combined.trim();
+    let mut start = combined.len().saturating_sub(HOOK_OUTPUT_TAIL_BYTES);
+    while !combined.is_char_boundary(start) {
+        start += 1;
+    }
+    let status = output
+        .status
+        .code()
+        .map_or_else(|| "signal".to_string(), |c| format!("exit {}", c));
+    finished(
+        output.status.success(),
+        status,
+        combined[start..].to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hooks(toml: &str) -> Hooks {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn parses_hooks_and_matches_tools() {
+        let hooks = hooks(
+            r#"
+            [[pre_tool]]
+            tools = ["bash"]
+            command = "true"
+
+            [[post_tool]]
+            command = "cargo fmt"
+
+            [[turn_end]]
+            name = "check"
+            command = "cargo check"
+            feed_back = true
+            "#,
+        );
+        assert!(hooks.pre_tool[0].applies_to("bash"));
+        assert!(!hooks.pre_tool[0].applies_to("edit_file"));
+        assert!(hooks.post_tool[0].applies_to("edit_file"));
+        assert_eq!(hooks.post_tool[0].label(), "cargo fmt");
+        assert_eq!(hooks.turn_end[0].label(), "check");
+        assert!(Hooks::default().is_empty());
+        assert!(toml::from_str::<Hooks>("[[on_start]]\ncommand = \"x\"").is_err());
+    }
+
+    #[tokio::test]
+    async fn failing_pre_tool_hook_blocks_and_stops() {
+        let dir = TempDir::new().unwrap();
+        let hooks = hooks(
+            r#"
+            [[pre_tool]]
+            name = "no pushes"
+            command = "if grep -q 'git push'; then echo 'pushing is not allowed'; exit 1; fi"
+
+            [[pre_tool]]
+            command = "echo second"
+            "#,
+        );
+
+        let runs = hooks
+            .before_tool(dir.path(), "bash", &json!({"command": "ls"}))
+            .await;
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|r| r.success));
+
+        let runs = hooks
+            .before_tool(dir.path(), "bash", &json!({"command": "git push"}))
+            .await;
+        assert_eq!(runs.len(), 1);
+        assert!(!runs[0].success);
+        assert_eq!(runs[0].status, "exit 1");
+        assert_eq!(runs[0].output, "pushing is not allowed");
+    }
+
+    #[tokio::test]
+    async fn post_tool_hooks_see_the_result_and_feed_back() {
+        let dir = TempDir::new().unwrap();
+        let hooks = hooks(
+            r#"
+            [[post_tool]]
+            tools = ["edit_file"]
+            command = "echo $LOCUS_HOOK_EVENT $LOCUS_TOOL_NAME; grep -o '\"is_error\":false'"
+            feed_back = true
+
+            [[post_tool]]
+            tools = ["edit_file"]
+            command = "echo quiet"
+            "#,
+        );
+
+        let runs = hooks
+            .after_tool(
+                dir.path(),
+                "edit_file",
+                &json!({"path": "a.rs"}),
+                &json!({"ok": true}),
+                false,
+            )
+            .await;
+        assert_eq!(
+            feedback(&runs),
+            vec![json!({
+                "hook": runs[0].label,
+                "status": "exit 0",
+                "output": "post_tool edit_file\n\"is_error\":false",
+            })]
+        );
+        assert!(hooks
+            .after_tool(dir.path(), "bash", &json!({}), &json!({}), false)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn slow_hooks_time_out() {
+        let dir = TempDir::new().unwrap();
+        let hooks = hooks("[[turn_end]]\ncommand = \"sleep 5\"\ntimeout_secs = 0\n");
+        let runs = hooks.at_turn_end(dir.path()).await;
+        assert!(!runs[0].success);
+        assert_eq!(runs[0].status, "timed out after 0s");
+        assert_eq!(
+            runs[0].describe(),
+            "hook `sleep 5` failed (timed out after 0s)"
+        );
+    }
+}
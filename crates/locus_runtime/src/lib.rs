@@ -1,10 +1,22 @@
+pub mod acp;
+pub mod agent_profiles;
+pub mod approval;
+pub mod checkpoint;
 pub mod config;
 pub mod context;
 pub mod error;
+pub mod guardrails;
+pub mod hooks;
 pub mod memory;
 pub mod runtime;
 pub mod tool_handler;
 
+pub use acp::LocusAcpAgent;
+pub use agent_profiles::{AgentProfile, AgentProfiles};
+pub use approval::ApprovalBroker;
+pub use checkpoint::{Checkpoint, CheckpointError, Checkpoints};
 pub use config::{LlmProvider, RuntimeConfig};
 pub use error::{Result, RuntimeError};
-pub use runtime::Runtime;
+pub use guardrails::{Guardrails, ViolationAction};
+pub use hooks::{Hook, Hooks};
+pub use runtime::{Runtime, TaskTool};
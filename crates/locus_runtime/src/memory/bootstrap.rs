@@ -44,7 +44,7 @@ pub fn bootstrap_tools(
         .extends(vec![project_anchor.clone()])
         .source("validator");
 
-        locus_graph.store_event(master_event).await;
+        let mut events = vec![master_event];
 
         // Step 3: Individual tool events
         for tool in &tools {
@@ -62,7 +62,7 @@ pub fn bootstrap_tools(
             .related_to(vec![project_anchor.clone()])
             .source("validator");
 
-            locus_graph.store_event(tool_event).await;
+            events.push(tool_event);
         }
 
         // Step 4: Meta-tool events
@@ -81,7 +81,9 @@ pub fn bootstrap_tools(
             .related_to(vec![project_anchor.clone()])
             .source("validator");
 
-            locus_graph.store_event(meta_event).await;
+            events.push(meta_event);
         }
+
+        locus_graph.store_events_batch(events).await;
     });
 }
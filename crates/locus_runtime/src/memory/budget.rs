@@ -0,0 +1,150 @@
+//! Token budget for recalled memories.
+//!
+//! LocusGraph returns recalled memories as one markdown block, most relevant
+//! first. The block is split into items (top-level list entries or paragraphs;
+//! headings stay with the items under them), and items are taken in order while
+//! they fit the budget. Items that do not fit are skipped, so a smaller, less
+//! relevant one can still use the room that is left.
+
+use locus_core::MemoryUsage;
+
+/// Characters of a memory shown in the accounting event.
+const SUMMARY_CHARS: usize = 80;
+
+/// Memories chosen for the prompt, with what each one costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySelection {
+    /// Markdown of the included memories.
+    pub memories: String,
+    /// Tokens of the included memories.
+    pub used_tokens: u64,
+    /// Every recalled memory, most relevant first.
+    pub items: Vec<MemoryUsage>,
+}
+
+/// A heading and the memory items under it.
+struct Section<'a> {
+    heading: Option<&'a str>,
+    items: Vec<String>,
+}
+
+/// Pick memories from `markdown` in order until `budget_tokens` is spent
+/// (0 = no limit).
+pub fn select_memories(markdown: &str, budget_tokens: usize) -> MemorySelection {
+    let sections = split_memories(markdown);
+    let separator = if markdown.contains("\n\n") {
+        "\n\n"
+    } else {
+        "\n"
+    };
+
+    let mut used = 0;
+    let mut items = Vec::new();
+    let mut parts = Vec::new();
+    for section in &sections {
+        let mut kept = Vec::new();
+        for item in &section.items {
+            let tokens = estimate_tokens(item);
+            let included = budget_tokens == 0 || used + tokens <= budget_tokens;
+            if included {
+                used += tokens;
+                kept.push(item.as_str());
+            }
+            items.push(MemoryUsage {
+                summary: summarize(item),
+                tokens: tokens as u64,
+                included,
+            });
+        }
+        if kept.is_empty() {
+            continue;
+        }
+        if let Some(heading) = section.heading {
+            parts.push(heading.to_string());
+        }
+        parts.extend(kept.into_iter().map(str::to_string));
+    }
+
+    MemorySelection {
+        memories: parts.join(separator),
+        used_tokens: used as u64,
+        items,
+    }
+}
+
+/// The memory items in `markdown`, most relevant first, without headings.
+pub fn memory_items(markdown: &str) -> Vec<String> {
+    split_memories(markdown)
+        .into_iter()
+        .flat_map(|section| section.items)
+        .collect()
+}
+
+/// Split the markdown into sections of items. A new item starts at a top-level
+/// list marker, or at unindented text after a blank line.
+fn split_memories(markdown: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![Section {
+        heading: None,
+        items: Vec::new(),
+    }];
+    let mut current: Vec<&str> = Vec::new();
+    let mut after_blank = false;
+
+    fn flush(current: &mut Vec<&str>, sections: &mut [Section]) {
+        let text = current.join("\n");
+        let text = text.trim();
+        if !text.is_empty() {
+            if let Some(section) = sections.last_mut() {
+                section.items.push(text.to_string());
+            }
+        }
+        current.clear();
+    }
+
+    for line in markdown.lines() {
+        if line.trim().is_empty() {
+            after_blank = true;
+            current.push(line);
+            continue;
+        }
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        if line.starts_with('#') {
+            flush(&mut current, &mut sections);
+            sections.push(Section {
+                heading: Some(line.trim_end()),
+                items: Vec::new(),
+            });
+        } else if (!indented && (after_blank || is_list_item(line))) || current.is_empty() {
+            flush(&mut current, &mut sections);
+            current.push(line);
+        } else {
+            current.push(line);
+        }
+        after_blank = false;
+    }
+    flush(&mut current, &mut sections);
+    sections
+}
+
+fn is_list_item(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && (line[digits..].starts_with(". ") || line[digits..].starts_with(") "))
+}
+
+/// Rough token count: ~4 characters per token, rounded up.
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+fn summarize(item: &str) -> String {
+    let line = item.lines().next().unwrap_or_default();
+    let line = line.trim_start_matches(['-', '*', '+']).trim();
+    if line.chars().count() <= SUMMARY_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(SUMMARY_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
@@ -5,6 +5,7 @@
 
 mod anchors;
 mod bootstrap;
+mod budget;
 mod graph_map;
 mod recall;
 mod session;
@@ -16,6 +17,7 @@ pub use anchors::{
     session_context_id, tool_anchor_id,
 };
 pub use bootstrap::bootstrap_tools;
+pub use budget::{memory_items, select_memories, MemorySelection};
 pub use graph_map::build_graph_map;
 pub use recall::{
     build_context_ids, fetch_session_turns, get_active_tools, recall_memories, CORE_TOOLS,
@@ -24,8 +26,8 @@ pub use session::{store_session_end, store_session_start};
 pub use utils::simple_hash;
 
 pub use turns::{
-    build_action_event, build_error_event, build_intent_event, build_llm_event, build_turn_end,
-    build_turn_start,
+    build_action_event, build_error_event, build_feedback_event, build_intent_event,
+    build_llm_event, build_turn_end, build_turn_start,
 };
 
 pub(crate) use utils::safe_context_name;
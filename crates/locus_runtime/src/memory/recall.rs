@@ -1,28 +1,43 @@
-use locus_core::SessionEvent;
+use std::time::Instant;
+
+use locus_core::{RetrievedMemory, SessionEvent};
+use locus_graph::confidence;
+use locus_graph::hooks::file_context_id;
 use locus_graph::{ContextResult, LocusGraphClient, RetrieveOptions};
 use locus_toolbus::ToolInfo;
 use tokio::sync::mpsc;
 use tracing::warn;
 
+use super::budget::{memory_items, select_memories};
 use super::{session_anchor_id, session_context_id, tool_anchor_id};
 
 /// Recall relevant memories before LLM call.
 ///
-/// Queries LocusGraph for memories relevant to the query and emits
-/// a MemoryRecall event to notify the TUI.
+/// Queries LocusGraph for memories relevant to the query, adds those anchored
+/// to `files` (the files the request is working on, see
+/// [locus_graph::hooks]), marks each with how sure the graph is of it, keeps
+/// the most relevant ones that fit in
+/// `memory_tokens` (0 = no limit) and emits MemoryRecall and MemoryBudget
+/// events to notify the TUI, then MemoriesRetrieved for the memory trace of
+/// user turn `turn`.
+#[allow(clippy::too_many_arguments)]
 pub async fn recall_memories(
     locus_graph: &LocusGraphClient,
     event_tx: &mpsc::Sender<SessionEvent>,
     query: &str,
     memory_limit: u8,
     context_ids: &[String],
+    files: &[String],
+    memory_tokens: usize,
+    turn: usize,
 ) -> ContextResult {
+    let started = Instant::now();
     let mut options = RetrieveOptions::new().limit(memory_limit as u64);
     for id in context_ids {
         options = options.context_id(id.clone());
     }
 
-    let result = locus_graph
+    let mut result = locus_graph
         .retrieve_memories(query, Some(options))
         .await
         .unwrap_or_else(|e| {
@@ -31,21 +46,123 @@ pub async fn recall_memories(
                 memories: String::new(),
                 items_found: 0,
                 degraded: true,
+                scores: Vec::new(),
+                confidence: Vec::new(),
             }
         });
 
     if result.degraded {
         warn!("Memory service degraded - operating without memory context");
+    } else if !files.is_empty() {
+        let mut options = RetrieveOptions::new().limit(memory_limit as u64);
+        for path in files {
+            options = options.context_id(file_context_id(path));
+        }
+        match locus_graph
+            .retrieve_memories(&files.join(" "), Some(options))
+            .await
+        {
+            Ok(found) => merge_memories(&mut result, found),
+            Err(e) => warn!("File memory recall failed: {}", e),
+        }
     }
+    label_confidence(&mut result);
 
     // Notify TUI about memory recall
     let _ = event_tx
         .send(SessionEvent::memory_recall(query, result.items_found))
         .await;
 
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let mut retrieved = Vec::new();
+    if !result.memories.is_empty() {
+        let selection = select_memories(&result.memories, memory_tokens);
+        // Scores are per memory the search returned; use them only when
+        // they line up with the items the budget split out.
+        let scores = (result.scores.len() == selection.items.len()).then_some(&result.scores);
+        retrieved = selection
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| RetrievedMemory {
+                summary: item.summary.clone(),
+                relevance: scores.map(|s| s[i]),
+                tokens: item.tokens,
+                included: item.included,
+            })
+            .collect();
+        let _ = event_tx
+            .send(SessionEvent::memory_budget(
+                memory_tokens as u64,
+                selection.used_tokens,
+                selection.items,
+            ))
+            .await;
+        result.memories = selection.memories;
+    }
+    let _ = event_tx
+        .send(SessionEvent::memories_retrieved(
+            turn,
+            query,
+            retrieved,
+            duration_ms,
+            result.degraded,
+        ))
+        .await;
+
     result
 }
 
+/// Add the memories of `found` that `result` does not have yet, after its own.
+pub(super) fn merge_memories(result: &mut ContextResult, found: ContextResult) {
+    let have = memory_items(&result.memories);
+    let found_items = memory_items(&found.memories);
+    let scored = result.scores.len() == have.len() && found.scores.len() == found_items.len();
+    let rated =
+        result.confidence.len() == have.len() && found.confidence.len() == found_items.len();
+    for (i, item) in found_items.into_iter().enumerate() {
+        if have.contains(&item) {
+            continue;
+        }
+        if !result.memories.is_empty() {
+            result.memories.push('\n');
+        }
+        result.memories.push_str(&item);
+        result.items_found += 1;
+        if scored {
+            result.scores.push(found.scores[i]);
+        }
+        if rated {
+            result.confidence.push(found.confidence[i]);
+        }
+    }
+    if !scored {
+        result.scores.clear();
+    }
+    if !rated {
+        result.confidence.clear();
+    }
+}
+
+/// Put the confidence of each memory in front of it — "(confirmed)",
+/// "(likely)" or "(uncertain)" — so the model can tell settled facts from
+/// guesses. Left alone unless the search rated every memory.
+pub(super) fn label_confidence(result: &mut ContextResult) {
+    let items = memory_items(&result.memories);
+    if items.is_empty() || result.confidence.len() != items.len() {
+        return;
+    }
+    result.memories = items
+        .iter()
+        .zip(&result.confidence)
+        .map(|(item, rating)| {
+            let text = item.strip_prefix("- ").unwrap_or(item);
+            format!("- ({}) {}", confidence::label(*rating), text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
 /// Tools always available in every LLM call.
 /// These are cheap, universally useful, and don't need discovery.
 pub const CORE_TOOLS: &[&str] = &[
@@ -56,6 +173,8 @@ pub const CORE_TOOLS: &[&str] = &[
     "glob",
     "grep",
     "finder",
+    "code_nav",
+    "tree",
     "tool_search",
     "tool_explain",
 ];
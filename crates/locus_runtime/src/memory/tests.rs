@@ -1,3 +1,4 @@
+use super::recall;
 use super::turns;
 use super::*;
 use serde_json::json;
@@ -131,3 +132,111 @@ fn test_build_llm_event() {
     let data = event.payload.get("data").unwrap();
     assert_eq!(data.get("total_tokens").unwrap(), 1200);
 }
+
+#[test]
+fn test_build_feedback_event() {
+    let event = turns::build_feedback_event(
+        "feedback:a1b2c3d4_001_004",
+        "turn:fix-jwt_validate-token",
+        "grep",
+        "toolu_01",
+        "this grep missed the generated code dir",
+    );
+    assert_eq!(event.event_kind, locus_graph::EventKind::Feedback);
+    assert_eq!(event.source.as_deref(), Some("user"));
+    let data = event.payload.get("data").unwrap();
+    assert_eq!(data.get("tool").unwrap(), "grep");
+    assert_eq!(data.get("tool_use_id").unwrap(), "toolu_01");
+}
+
+#[test]
+fn test_select_memories_skips_items_over_budget() {
+    let long = format!("- {}", "retry logic details ".repeat(20).trim_end());
+    let markdown = format!(
+        "- JWT refresh lives in src/auth.rs\n{}\n- Tests use the mock client\n  with fixtures in tests/data",
+        long
+    );
+
+    let selection = select_memories(&markdown, 30);
+    assert_eq!(
+        selection.memories,
+        "- JWT refresh lives in src/auth.rs\n- Tests use the mock client\n  with fixtures in tests/data"
+    );
+    let accounting: Vec<_> = selection
+        .items
+        .iter()
+        .map(|i| (i.summary.as_str(), i.tokens, i.included))
+        .collect();
+    assert_eq!(
+        accounting,
+        vec![
+            ("JWT refresh lives in src/auth.rs", 9, true),
+            (
+                "retry logic details retry logic details retry logic details retry logic details…",
+                101,
+                false
+            ),
+            ("Tests use the mock client", 15, true),
+        ]
+    );
+    assert_eq!(selection.used_tokens, 24);
+
+    let unlimited = select_memories(&markdown, 0);
+    assert_eq!(unlimited.memories, markdown);
+    assert!(unlimited.items.iter().all(|i| i.included));
+}
+
+#[test]
+fn test_select_memories_keeps_headings_with_their_items() {
+    let markdown = "## Facts\n\nThe API uses snake_case.\n\n## Turns\n\nRewrote the whole HTTP client and its tests.";
+
+    let selection = select_memories(markdown, 8);
+    assert_eq!(selection.memories, "## Facts\n\nThe API uses snake_case.");
+    assert_eq!(selection.items.len(), 2);
+    assert!(!selection.items[1].included);
+}
+
+#[test]
+fn test_memory_items_drops_headings() {
+    let markdown =
+        "## Facts\n\n- The API uses snake_case.\n- Errors go through AppError\n  in src/error.rs";
+    assert_eq!(
+        memory_items(markdown),
+        [
+            "- The API uses snake_case.",
+            "- Errors go through AppError\n  in src/error.rs"
+        ]
+    );
+}
+
+#[test]
+fn test_merge_memories_appends_new_file_memories() {
+    let mut result = locus_graph::ContextResult {
+        memories: "- [fact:auth] we use JWT".to_string(),
+        items_found: 1,
+        degraded: false,
+        scores: vec![0.9],
+        confidence: vec![0.8],
+    };
+    let found = locus_graph::ContextResult {
+        memories: "- [fact:auth] we use JWT\n- [action:edit] edited src/auth.rs".to_string(),
+        items_found: 2,
+        degraded: false,
+        scores: vec![0.5, 0.4],
+        confidence: vec![0.8, 0.3],
+    };
+    recall::merge_memories(&mut result, found);
+    assert_eq!(
+        result.memories,
+        "- [fact:auth] we use JWT\n- [action:edit] edited src/auth.rs"
+    );
+    assert_eq!(result.items_found, 2);
+    assert_eq!(result.scores, [0.9, 0.4]);
+    assert_eq!(result.confidence, [0.8, 0.3]);
+
+    recall::label_confidence(&mut result);
+    assert_eq!(
+        result.memories,
+        "- (confirmed) [fact:auth] we use JWT\n- (uncertain) [action:edit] edited src/auth.rs"
+    );
+}
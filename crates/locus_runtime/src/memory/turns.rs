@@ -170,6 +170,30 @@ pub fn build_error_event(
     .source("executor")
 }
 
+/// Build a feedback event for a user annotation on a tool result.
+pub fn build_feedback_event(
+    event_ctx: &str,
+    turn_ctx: &str,
+    tool_name: &str,
+    tool_use_id: &str,
+    note: &str,
+) -> CreateEventRequest {
+    CreateEventRequest::new(
+        EventKind::Feedback,
+        json!({
+            "kind": "tool_annotation",
+            "data": {
+                "tool": tool_name,
+                "tool_use_id": tool_use_id,
+                "note": truncate(note, 1000),
+            }
+        }),
+    )
+    .context_id(event_ctx)
+    .extends(vec![turn_ctx.to_string()])
+    .source("user")
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
@@ -0,0 +1,126 @@
+//! Sub-agent admission control.
+//!
+//! Before a task tool spawns a sub-agent, the provider's latest rate-limit
+//! snapshot is checked. When the request or token budget is nearly spent (or
+//! the provider asked us to back off), the sub-agent waits for the budget to
+//! refill instead of starting and failing with a 429 halfway through a plan.
+
+use std::time::Duration;
+
+use locus_llms::types::RateLimitSnapshot;
+
+/// Throttle when less than this fraction of the request or token budget is left.
+const LOW_BUDGET_FRACTION: f64 = 0.1;
+/// Wait used when the budget is low but the provider did not say when it resets.
+const DEFAULT_THROTTLE_WAIT: Duration = Duration::from_secs(5);
+/// Upper bound on a single throttle so a bad reset header cannot stall the plan.
+const MAX_THROTTLE_WAIT: Duration = Duration::from_secs(60);
+
+/// Whether a sub-agent may start now.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Admission {
+    Admit,
+    Throttle { wait: Duration, reason: String },
+}
+
+/// Decide whether a new sub-agent can start given the latest rate-limit snapshot.
+pub(crate) fn admit(snapshot: Option<&RateLimitSnapshot>) -> Admission {
+    let Some(snapshot) = snapshot else {
+        return Admission::Admit;
+    };
+
+    if snapshot.retry_after.is_some() {
+        if let Some(wait) = snapshot.wait_hint().filter(|w| !w.is_zero()) {
+            return Admission::Throttle {
+                wait: wait.min(MAX_THROTTLE_WAIT),
+                reason: "provider asked to retry later".to_string(),
+            };
+        }
+    }
+
+    match snapshot.remaining_fraction() {
+        Some(fraction) if fraction < LOW_BUDGET_FRACTION => {
+            let wait = match snapshot.wait_hint() {
+                Some(w) if w.is_zero() => return Admission::Admit,
+                Some(w) => w,
+                None => DEFAULT_THROTTLE_WAIT,
+            };
+            Admission::Throttle {
+                wait: wait.min(MAX_THROTTLE_WAIT),
+                reason: format!("{:.0}% of rate-limit budget left", fraction * 100.0),
+            }
+        }
+        _ => Admission::Admit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn snapshot(remaining: u64, reset_after: Option<Duration>) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            requests_limit: Some(100),
+            requests_remaining: Some(remaining),
+            tokens_limit: None,
+            tokens_remaining: None,
+            reset_after,
+            retry_after: None,
+            observed_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_admit_without_snapshot() {
+        assert_eq!(admit(None), Admission::Admit);
+    }
+
+    #[test]
+    fn test_admit_with_healthy_budget() {
+        let s = snapshot(50, Some(Duration::from_secs(30)));
+        assert_eq!(admit(Some(&s)), Admission::Admit);
+    }
+
+    #[test]
+    fn test_throttle_until_reset_when_budget_low() {
+        let s = snapshot(5, Some(Duration::from_secs(20)));
+        match admit(Some(&s)) {
+            Admission::Throttle { wait, reason } => {
+                assert!(wait > Duration::from_secs(19) && wait <= Duration::from_secs(20));
+                assert!(reason.contains("5%"));
+            }
+            other => panic!("expected throttle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_throttle_capped_and_defaulted() {
+        let long = snapshot(0, Some(Duration::from_secs(600)));
+        assert!(matches!(
+            admit(Some(&long)),
+            Admission::Throttle { wait, .. } if wait == MAX_THROTTLE_WAIT
+        ));
+
+        let unknown_reset = snapshot(1, None);
+        assert!(matches!(
+            admit(Some(&unknown_reset)),
+            Admission::Throttle { wait, .. } if wait == DEFAULT_THROTTLE_WAIT
+        ));
+    }
+
+    #[test]
+    fn test_admit_once_reset_has_passed() {
+        let s = snapshot(0, Some(Duration::ZERO));
+        assert_eq!(admit(Some(&s)), Admission::Admit);
+    }
+
+    #[test]
+    fn test_retry_after_throttles() {
+        let s = RateLimitSnapshot {
+            retry_after: Some(Duration::from_secs(8)),
+            ..snapshot(50, None)
+        };
+        assert!(matches!(admit(Some(&s)), Admission::Throttle { .. }));
+    }
+}
@@ -2,24 +2,34 @@
 
 use std::time::Instant;
 
-use locus_core::{ContentBlock, Role, SessionEvent, SessionStatus, Turn};
+use locus_core::custom_command::COMMANDS_DIR;
+use locus_core::db::RunCheckpoint;
+use locus_core::{
+    expand_slash_command, load_custom_commands, ContentBlock, McpPromptInfo, Role, SessionEvent,
+    SessionStatus, ToolAnnotation, Turn,
+};
 use locus_llms::types::GenerateRequest;
 use locusgraph_observability::{agent_span, record_error};
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{debug, info, warn};
 
+use crate::checkpoint::{CheckpointError, Checkpoints};
 use crate::context::{self, near_context_limit};
 use crate::error::RuntimeError;
 use crate::memory;
 
 use super::Runtime;
 
+/// Ends the assistant turn of a run the user cancelled.
+const CANCELLED_MARKER: &str = "[Cancelled by the user]";
+
 impl Runtime {
     /// Main entry point — run the agent with an initial message.
     ///
     /// This processes the initial message and runs the agent loop until
     /// the session ends (completed, failed, cancelled, or max turns reached).
-    /// If `cancel` is provided and is triggered, streaming stops and returns `Ok(SessionStatus::Cancelled)`.
+    /// If `cancel` is provided and is triggered, streaming or the running tool call stops
+    /// and this returns `Ok(SessionStatus::Cancelled)`.
     pub async fn run(
         &mut self,
         initial_message: String,
@@ -40,41 +50,87 @@ impl Runtime {
             .send(SessionEvent::status("Session started"))
             .await;
 
-        // Process the initial message (streaming; can be cancelled)
-        match self.process_message(initial_message, cancel).await {
-            Err(RuntimeError::Cancelled) => {
-                self.session.set_status(SessionStatus::Cancelled);
-                self.session
-                    .finish_run(Some(run_start.elapsed().as_millis() as u64));
-                let _ = self.event_tx.send(SessionEvent::turn_end()).await;
-                let _ = self
-                    .event_tx
-                    .send(SessionEvent::session_end_with_tokens(
-                        SessionStatus::Cancelled,
-                        self.session.total_prompt_tokens,
-                        self.session.total_completion_tokens,
-                    ))
-                    .await;
-                return Ok(SessionStatus::Cancelled);
-            }
-            Err(e) => {
-                record_error(&e);
-                self.session
-                    .finish_run(Some(run_start.elapsed().as_millis() as u64));
-                let _ = self.event_tx.send(SessionEvent::turn_end()).await;
-                let _ = self.event_tx.send(SessionEvent::error(e.to_string())).await;
-                return Err(e);
-            }
-            Ok(()) => {}
+        // Process the initial message (streaming; can be cancelled), then run the agent loop
+        let result = match self.process_message(initial_message, cancel.clone()).await {
+            Ok(()) => self.agent_loop(cancel).await,
+            Err(e) => Err(e),
+        };
+        self.end_run(run_start, result).await
+    }
+
+    /// Finish a turn that was in progress when the process died, from its crash
+    /// recovery checkpoint (see [locus_core::db::RunCheckpoint]), then run the
+    /// agent loop as [Self::run] would.
+    ///
+    /// Calls that had returned keep their results. Calls that had not are run
+    /// again when they only read; anything that may have changed files or run
+    /// commands is answered as interrupted instead, so the model can check and
+    /// decide. A turn cut off while the model was answering asks it again.
+    pub async fn recover(
+        &mut self,
+        checkpoint: RunCheckpoint,
+        cancel: Option<CancellationToken>,
+    ) -> Result<SessionStatus, RuntimeError> {
+        let RunCheckpoint {
+            session,
+            slug,
+            pending_tools,
+            finished_tools,
+            ..
+        } = checkpoint;
+        self.resume_session(session, slug).await;
+        if let Some(message) = self.last_user_message() {
+            self.turn_slug = Self::slugify_turn(&message);
         }
 
-        // Run the agent loop
-        let status = match self.agent_loop().await {
-            Ok(s) => s,
+        let run_start = Instant::now();
+        self.session.start_run();
+        self.session.set_status(SessionStatus::Running);
+        info!(
+            "Recovering turn {}: {} finished and {} pending tool calls",
+            self.turn_sequence,
+            finished_tools.len(),
+            pending_tools.len()
+        );
+        let _ = self
+            .event_tx
+            .send(SessionEvent::status("Recovering interrupted turn"))
+            .await;
+
+        let result = match self
+            .finish_interrupted_calls(pending_tools, finished_tools, cancel.clone())
+            .await
+        {
+            Ok(()) if self.session.turns.last().map(|t| t.role) == Some(Role::User) => {
+                match self.process_tool_results(cancel.clone()).await {
+                    Ok(()) => self.agent_loop(cancel).await,
+                    Err(e) => Err(e),
+                }
+            }
+            Ok(()) => self.agent_loop(cancel).await,
+            Err(e) => Err(e),
+        };
+        self.end_run(run_start, result).await
+    }
+
+    /// Wrap up a run: send its diff for review, git checkpoint, save the
+    /// session, drop its crash recovery checkpoint and tell the UI how it ended.
+    async fn end_run(
+        &mut self,
+        run_start: Instant,
+        result: Result<SessionStatus, RuntimeError>,
+    ) -> Result<SessionStatus, RuntimeError> {
+        self.send_turn_diff().await;
+        let status = match result {
+            Ok(status) => status,
+            Err(RuntimeError::Cancelled) => return Ok(self.finish_cancelled(run_start).await),
             Err(e) => {
                 record_error(&e);
                 self.session
                     .finish_run(Some(run_start.elapsed().as_millis() as u64));
+                self.checkpoint_turn().await;
+                self.save_session().await;
+                self.clear_run_checkpoint().await;
                 let _ = self.event_tx.send(SessionEvent::turn_end()).await;
                 let _ = self.event_tx.send(SessionEvent::error(e.to_string())).await;
                 return Err(e);
@@ -83,6 +139,10 @@ impl Runtime {
 
         self.session
             .finish_run(Some(run_start.elapsed().as_millis() as u64));
+        self.checkpoint_turn().await;
+        self.save_session().await;
+        self.clear_run_checkpoint().await;
+        self.run_turn_end_hooks().await;
         // Emit session end event with token usage
         let _ = self
             .event_tx
@@ -96,6 +156,61 @@ impl Runtime {
         Ok(status)
     }
 
+    /// Run the `turn_end` hooks: failures are reported to the user and output
+    /// from `feed_back` hooks goes to the model with the next message.
+    async fn run_turn_end_hooks(&mut self) {
+        if self.hooks.turn_end.is_empty() {
+            return;
+        }
+        let runs = self.hooks.at_turn_end(self.toolbus.repo_root()).await;
+        for run in &runs {
+            if !run.success {
+                warn!("Turn end: {}", run.describe());
+                let _ = self
+                    .event_tx
+                    .send(SessionEvent::status(run.describe()))
+                    .await;
+            }
+            if run.feed_back && !run.output.is_empty() {
+                self.pending_notes.push(format!(
+                    "[Hook `{}` after the last turn, {}]\n{}",
+                    run.label, run.status, run.output
+                ));
+            }
+        }
+    }
+
+    /// End a run the user cancelled: an assistant turn with whatever the model
+    /// had streamed marks the cancel (so the next message follows an assistant
+    /// turn and the model knows it was stopped), the session is left
+    /// `Cancelled` and the TUI gets turn and session end events.
+    async fn finish_cancelled(&mut self, run_start: Instant) -> SessionStatus {
+        let partial = std::mem::take(&mut self.cancelled_reply);
+        let marker = if partial.trim().is_empty() {
+            CANCELLED_MARKER.to_string()
+        } else {
+            format!("{}\n\n{}", partial.trim_end(), CANCELLED_MARKER)
+        };
+        self.session
+            .add_turn(Turn::assistant().with_block(ContentBlock::text(marker)));
+        self.session.set_status(SessionStatus::Cancelled);
+        self.session
+            .finish_run(Some(run_start.elapsed().as_millis() as u64));
+        self.checkpoint_turn().await;
+        self.save_session().await;
+        self.clear_run_checkpoint().await;
+        let _ = self.event_tx.send(SessionEvent::turn_end()).await;
+        let _ = self
+            .event_tx
+            .send(SessionEvent::session_end_with_tokens(
+                SessionStatus::Cancelled,
+                self.session.total_prompt_tokens,
+                self.session.total_completion_tokens,
+            ))
+            .await;
+        SessionStatus::Cancelled
+    }
+
     /// The core agent loop.
     ///
     /// Repeatedly:
@@ -107,8 +222,15 @@ impl Runtime {
     /// 6. Handles tool calls
     /// 7. Stores decisions
     /// 8. Compresses context if needed
-    /// 9. Checks termination conditions
-    pub async fn agent_loop(&mut self) -> Result<SessionStatus, RuntimeError> {
+    /// 9. Checks termination conditions, including the turn limits (see
+    ///    [super::watchdog])
+    ///
+    /// If `cancel` is triggered, the LLM stream or running tool call stops and
+    /// this returns `Err(RuntimeError::Cancelled)`.
+    pub async fn agent_loop(
+        &mut self,
+        cancel: Option<CancellationToken>,
+    ) -> Result<SessionStatus, RuntimeError> {
         loop {
             // Check termination conditions
             if !self.session.is_active() {
@@ -133,16 +255,23 @@ impl Runtime {
                 break;
             }
 
+            // Stop a request that has run too long or made too many calls
+            if let Some(limit) = self.turn_limit_reached() {
+                self.stop_at_turn_limit(limit).await;
+                break;
+            }
+
             // We have pending tool results - process them by calling LLM again
             info!("Processing pending tool results");
-            self.process_tool_results().await?;
+            self.process_tool_results(cancel.clone()).await?;
         }
 
         Ok(self.session.status.clone())
     }
 
     /// Prepare a complete LLM request: recall memories, check context limits,
-    /// build prompt + messages, assemble GenerateRequest.
+    /// build prompt + messages, replace secrets with placeholders, assemble
+    /// GenerateRequest.
     ///
     /// Centralizes the shared pipeline used by both `process_message` and
     /// `process_tool_results` to avoid duplication.
@@ -150,13 +279,21 @@ impl Runtime {
         &mut self,
         query: &str,
     ) -> Result<GenerateRequest, RuntimeError> {
-        // Recall memories
+        // Recall memories for the query and the files being worked on
+        let files = self.touched_files();
         let memory_result = memory::recall_memories(
             &self.locus_graph,
             &self.event_tx,
             query,
             self.config.memory_limit,
             &self.context_ids,
+            &files,
+            self.config.memory_token_budget,
+            self.session
+                .turns
+                .iter()
+                .filter(|turn| turn.role == Role::User)
+                .count(),
         )
         .await;
 
@@ -166,9 +303,35 @@ impl Runtime {
         }
 
         // Build request from cached tools
-        let system_prompt = context::build_system_prompt(&self.active_tools, &self.graph_map);
-        let messages =
-            context::build_messages(&system_prompt, &self.session, &memory_result.memories);
+        let repo_map = self.repo_map().await;
+        let instructions = self.project_instructions().await;
+        let mut system_prompt = context::build_system_prompt(
+            &self.active_tools,
+            &self.graph_map,
+            &repo_map,
+            &instructions,
+        );
+        if self.plan_mode {
+            system_prompt.push_str(context::PLAN_MODE_PROMPT);
+        }
+        self.pinned.refresh(&self.toolbus).await;
+        let mut messages = context::build_messages(
+            &system_prompt,
+            &self.session,
+            &memory_result.memories,
+            &self.pinned.section(),
+            self.config.file_pack_budget,
+        );
+        if self.config.redact_secrets {
+            let redacted = context::redact_messages(&mut messages, &mut self.secrets);
+            if !redacted.is_empty() {
+                warn!("Redacted {} secret(s) before the LLM call", redacted.len());
+                let _ = self
+                    .event_tx
+                    .send(SessionEvent::secrets_redacted(redacted))
+                    .await;
+            }
+        }
 
         Ok(context::build_generate_request(
             &self.config.model,
@@ -179,15 +342,52 @@ impl Runtime {
     }
 
     /// Process pending tool results by calling the LLM.
-    async fn process_tool_results(&mut self) -> Result<(), RuntimeError> {
+    async fn process_tool_results(
+        &mut self,
+        cancel: Option<CancellationToken>,
+    ) -> Result<(), RuntimeError> {
+        self.attach_pending_notes().await;
         let query = self.last_user_message().unwrap_or_default();
         let request = self.prepare_llm_call(&query).await?;
 
-        self.stream_llm_response(request, None).await?;
+        self.stream_llm_response(request, cancel).await?;
 
         Ok(())
     }
 
+    /// Snapshot the working tree as this turn's git checkpoint, when enabled.
+    /// Turns that changed nothing are skipped; failures are only logged.
+    async fn checkpoint_turn(&self) {
+        if !self.config.checkpoints {
+            return;
+        }
+        let summary = self.last_user_message().unwrap_or_default();
+        let store = Checkpoints::new(self.config.repo_root.clone());
+        match store
+            .create(self.session.id.as_str(), &self.turn_id(), &summary)
+            .await
+        {
+            Ok(Some(checkpoint)) => {
+                info!(
+                    "Saved checkpoint {} for turn {}",
+                    checkpoint.id, checkpoint.turn
+                );
+                let _ = self
+                    .event_tx
+                    .send(SessionEvent::status(format!(
+                        "Checkpoint {} saved (turn {})",
+                        checkpoint.id, checkpoint.turn
+                    )))
+                    .await;
+            }
+            Ok(None) => {}
+            Err(CheckpointError::NotARepository(path)) => {
+                debug!("Skipping checkpoint, not a git repository: {}", path);
+            }
+            Err(e) => warn!("Checkpoint failed: {}", e),
+        }
+    }
+
     /// Get the last user message from the session.
     fn last_user_message(&self) -> Option<String> {
         self.session
@@ -219,6 +419,30 @@ impl Runtime {
         let span = agent_span!(&session_id, "process_message");
         let _guard = span.enter();
 
+        self.announce_mcp_prompts().await;
+
+        // `/mcp.<server>.<prompt> args` runs a prompt of an MCP server, and
+        // `/name args` the `.locus/commands/name.md` template.
+        let message = match self.expand_mcp_prompt(&message).await? {
+            Some(prompt) => prompt,
+            None => message,
+        };
+        let commands = load_custom_commands(&self.config.repo_root);
+        let message = match expand_slash_command(&commands, &message) {
+            Some((name, prompt)) => {
+                info!("Running custom command /{}", name);
+                let _ = self
+                    .event_tx
+                    .send(SessionEvent::status(format!(
+                        "Running /{} from {}/{}.md",
+                        name, COMMANDS_DIR, name
+                    )))
+                    .await;
+                prompt
+            }
+            None => message,
+        };
+
         info!("Processing user message: {} chars", message.len());
 
         // First message — set slug, create session, fetch existing turns
@@ -235,23 +459,8 @@ impl Runtime {
             )
             .await;
 
-            // Fetch existing turns for this session from LocusGraph
-            let existing_turns = memory::fetch_session_turns(
-                &self.locus_graph,
-                &self.session_slug,
-                self.session.id.as_str(),
-            )
-            .await;
-            info!("Found {} existing turns for session", existing_turns.len());
-
-            // Rebuild context_ids with existing turns
-            self.context_ids = memory::build_context_ids(
-                &self.project_name,
-                &self.repo_hash,
-                &self.session_slug,
-                self.session.id.as_str(),
-                &existing_turns,
-            );
+            self.load_session_context().await;
+            self.fetch_project_insights();
         }
 
         // Start new turn
@@ -283,10 +492,38 @@ impl Runtime {
             .event_tx
             .send(SessionEvent::turn_start(Role::User))
             .await;
+        let _ = self
+            .event_tx
+            .send(SessionEvent::model_in_use(
+                self.config.provider.as_str(),
+                &self.config.model,
+                self.config.context_limit,
+            ))
+            .await;
+
+        if let Some(note) = self.check_similar_work(&message).await {
+            self.pending_notes.push(note);
+        }
 
-        // Create user turn and add to session
-        let user_turn = Turn::user().with_block(ContentBlock::text(&message));
+        self.receive_annotations().await;
+
+        let attachments = context::attach_mentions(&self.toolbus, &message).await;
+        if let Some(status) = attachments.status() {
+            info!("{}", status);
+            let _ = self.event_tx.send(SessionEvent::status(status)).await;
+        }
+
+        // Create user turn (with the files it mentions and any queued
+        // tool-result notes) and add to session
+        let mut user_turn = Turn::user().with_block(ContentBlock::text(&message));
+        for block in attachments.blocks {
+            user_turn = user_turn.with_block(ContentBlock::text(block));
+        }
+        for note in self.pending_notes.drain(..) {
+            user_turn = user_turn.with_block(ContentBlock::text(note));
+        }
         self.session.add_turn(user_turn);
+        self.save_run_checkpoint(&[], &[]).await;
 
         // Build and stream LLM request
         let request = self.prepare_llm_call(&message).await?;
@@ -296,17 +533,129 @@ impl Runtime {
         }
 
         let summary = self.build_turn_summary(&message);
+        self.record_session_log(&summary).await;
+        self.save_session().await;
         let turn_end_event =
             memory::build_turn_end(&turn_ctx, &session_ctx, summary, self.turn_sequence, 0);
         self.buffer_event(turn_end_event);
         self.flush_turn_events().await;
 
         // Emit turn end to TUI
+        self.announce_mcp_prompts().await;
         let _ = self.event_tx.send(SessionEvent::turn_end()).await;
 
         Ok(())
     }
 
+    /// Send the MCP servers' prompts for the `/` popup when they changed since
+    /// last sent; servers start and reload in the background.
+    async fn announce_mcp_prompts(&mut self) {
+        let prompts: Vec<McpPromptInfo> = self.toolbus.list_prompts();
+        if prompts != self.mcp_prompts {
+            self.mcp_prompts = prompts.clone();
+            let _ = self.event_tx.send(SessionEvent::mcp_prompts(prompts)).await;
+        }
+    }
+
+    /// The text of the MCP prompt `message` runs (`/mcp.<server>.<prompt> args`),
+    /// or None when it names no registered prompt.
+    async fn expand_mcp_prompt(&self, message: &str) -> Result<Option<String>, RuntimeError> {
+        let Some(rest) = message.trim().strip_prefix('/') else {
+            return Ok(None);
+        };
+        let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let Some(prompt) = self.toolbus.prompt(name) else {
+            return Ok(None);
+        };
+        info!("Running MCP prompt /{}", name);
+        let _ = self
+            .event_tx
+            .send(SessionEvent::status(format!(
+                "Running MCP prompt /{}",
+                name
+            )))
+            .await;
+        let text = prompt
+            .render(args)
+            .await
+            .map_err(|e| RuntimeError::ToolFailed {
+                tool: name.to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(Some(text))
+    }
+
+    /// Attach a user correction to a finished tool result.
+    ///
+    /// The note is queued and sent with the next LLM call: with the pending
+    /// tool results when a run is going, else with the next user turn. It is
+    /// also stored in LocusGraph as feedback so later recalls pick it up.
+    pub async fn annotate_tool_result(&mut self, annotation: ToolAnnotation) {
+        let note = annotation.note.trim();
+        if note.is_empty() {
+            return;
+        }
+        self.pending_notes.push(format!(
+            "[Note on {} result {}] {}",
+            annotation.tool_name, annotation.tool_use_id, note
+        ));
+
+        let seq = self.next_seq();
+        let event_ctx = self.event_ctx("feedback", seq);
+        let event = memory::build_feedback_event(
+            &event_ctx,
+            &self.turn_ctx(),
+            &annotation.tool_name,
+            &annotation.tool_use_id,
+            note,
+        );
+        if !self.locus_graph.store_event(event).await {
+            record_error(&RuntimeError::MemoryFailed(
+                "Tool annotation feedback failed".to_string(),
+            ));
+        }
+        let _ = self
+            .event_tx
+            .send(SessionEvent::memory_store(
+                event_ctx,
+                "feedback",
+                format!("note on {}", annotation.tool_name),
+            ))
+            .await;
+    }
+
+    /// Queue the notes sent through [Runtime::annotation_sender].
+    async fn receive_annotations(&mut self) {
+        while let Ok(annotation) = self.annotation_rx.try_recv() {
+            self.annotate_tool_result(annotation).await;
+        }
+    }
+
+    /// Add the queued notes to the last pending tool result, so the next LLM
+    /// call in this run sees them.
+    async fn attach_pending_notes(&mut self) {
+        self.receive_annotations().await;
+        if self.pending_notes.is_empty() {
+            return;
+        }
+        let Some(output) = self
+            .session
+            .turns
+            .last_mut()
+            .filter(|turn| turn.role == Role::Tool)
+            .and_then(|turn| {
+                turn.blocks.iter_mut().rev().find_map(|block| match block {
+                    ContentBlock::ToolResult { tool_result } => tool_result.output.as_object_mut(),
+                    _ => None,
+                })
+            })
+        else {
+            return;
+        };
+        let notes: Vec<String> = self.pending_notes.drain(..).collect();
+        output.insert("notes".to_string(), serde_json::json!(notes));
+    }
+
     /// Check if there are pending tool results that need processing.
     fn has_pending_tool_results(&self) -> bool {
         if let Some(last_turn) = self.session.turns.last() {
@@ -0,0 +1,147 @@
+//! Diff review: the files each run changed go to the UI as hunks, and
+//! `:reject-hunk` puts one hunk's lines back.
+
+use locus_core::diff_review::{revert_hunk, turn_diffs};
+use locus_core::{HunkRef, SessionEvent};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::error::RuntimeError;
+
+use super::Runtime;
+
+impl Runtime {
+    /// Send the files this turn changed as [SessionEvent::TurnDiff]. Needs
+    /// `session_log`, which records the changes; nothing is sent when the turn
+    /// changed no files.
+    pub(super) async fn send_turn_diff(&self) {
+        if !self.config.session_log || self.turn_sequence == 0 {
+            return;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let session_id = self.session.id.as_str().to_string();
+        let turn = self.turn_sequence;
+        let diffs = tokio::task::spawn_blocking(move || {
+            locus_core::db::session_file_changes(&repo_root, &session_id, turn - 1)
+                .map(|changes| turn_diffs(&changes, turn))
+        })
+        .await;
+        match diffs {
+            Ok(Ok(files)) if !files.is_empty() => {
+                let _ = self
+                    .event_tx
+                    .send(SessionEvent::turn_diff(turn, files))
+                    .await;
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!("Failed to read the turn's file changes: {}", e),
+            Err(e) => warn!("File change task failed: {}", e),
+        }
+    }
+
+    /// Reject one hunk of a turn's diff: put back the lines it replaced, leave
+    /// the rest of the file as it is, and tell the model with the next message
+    /// so it does not redo the change. Fails when the hunk's lines were changed
+    /// since.
+    pub async fn reject_hunk(&mut self, target: HunkRef) -> Result<(), RuntimeError> {
+        if !self.config.session_log {
+            return Err(RuntimeError::SessionError(
+                "Diff review needs the session log".to_string(),
+            ));
+        }
+        let repo_root = self.config.repo_root.clone();
+        let session_id = self.session.id.as_str().to_string();
+        let request = target.clone();
+        let reversal = tokio::task::spawn_blocking(move || -> anyhow::Result<Reversal> {
+            let changes = locus_core::db::session_file_changes(
+                &repo_root,
+                &session_id,
+                request.turn.saturating_sub(1),
+            )?;
+            let file = turn_diffs(&changes, request.turn)
+                .into_iter()
+                .find(|file| file.path == request.path)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("turn {} did not change {}", request.turn, request.path)
+                })?;
+            let hunk = file
+                .hunks
+                .get(request.hunk)
+                .ok_or_else(|| anyhow::anyhow!("{} has no hunk {}", request.path, request.hunk))?;
+            let full = repo_root.join(&request.path);
+            let exists = full.is_file();
+            let current = std::fs::read_to_string(&full).unwrap_or_default();
+            let content = revert_hunk(&current, hunk).ok_or_else(|| {
+                anyhow::anyhow!("{} changed since; the hunk no longer applies", request.path)
+            })?;
+            let removed: Vec<&str> = hunk
+                .lines
+                .iter()
+                .filter(|line| line.kind == locus_core::HunkLineKind::Added)
+                .map(|line| line.text.as_str())
+                .collect();
+            Ok(Reversal {
+                delete: file.created && content.is_empty(),
+                exists,
+                content,
+                removed: removed.join("\n"),
+            })
+        })
+        .await
+        .map_err(|e| RuntimeError::SessionError(e.to_string()))?
+        .map_err(|e| RuntimeError::SessionError(format!("Reject failed: {}", e)))?;
+
+        // Written by the bus's edit tools, so protected paths and tool policy
+        // apply and `undo_edit` can take the reversal back.
+        let (tool, args) = if reversal.exists {
+            (
+                "edit_file",
+                json!({ "path": target.path, "new_string": reversal.content }),
+            )
+        } else {
+            (
+                "create_file",
+                json!({ "path": target.path, "content": reversal.content }),
+            )
+        };
+        self.toolbus
+            .call(tool, args)
+            .await
+            .map_err(|e| RuntimeError::SessionError(format!("Reject failed: {}", e)))?;
+        if reversal.delete {
+            // No tool deletes files: the file the turn created is emptied
+            // above, with history, then removed.
+            tokio::fs::remove_file(self.config.repo_root.join(&target.path)).await?;
+        }
+
+        info!(
+            "Rejected hunk {} of {} from turn {}",
+            target.hunk, target.path, target.turn
+        );
+        self.pending_notes.push(format!(
+            "[Diff review] The user rejected part of your change to {} and it was reverted. \
+             Do not make it again unless asked. The rejected lines were:\n{}",
+            target.path, reversal.removed
+        ));
+        let _ = self
+            .event_tx
+            .send(SessionEvent::hunk_reverted(
+                target.turn,
+                target.path,
+                target.hunk,
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+/// One rejected hunk, worked out: the file's content without it, and the lines
+/// it had added, for the note to the model.
+struct Reversal {
+    content: String,
+    /// The file is there to edit; otherwise it is created.
+    exists: bool,
+    /// The turn created the file and nothing of it is left.
+    delete: bool,
+    removed: String,
+}
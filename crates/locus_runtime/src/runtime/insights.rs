@@ -0,0 +1,64 @@
+//! What LocusGraph remembers about the project, shown when a session starts.
+
+use locus_core::SessionEvent;
+use locus_graph::InsightsOptions;
+use tracing::{debug, info};
+
+use crate::memory;
+
+use super::Runtime;
+
+/// Task LocusGraph reasons over for the session-start insights.
+const PROJECT_INSIGHTS_TASK: &str = "Summarize what is known about this project for an agent \
+     starting a new session: common failure patterns (commands or tools that fail and why) and \
+     the conventions the project follows.";
+
+/// Memories LocusGraph considers for the insights.
+const PROJECT_INSIGHTS_LIMIT: u64 = 30;
+
+impl Runtime {
+    /// Ask LocusGraph, in the background, for insights about the project from
+    /// past sessions and tool use, and send them as
+    /// [SessionEvent::ProjectInsights]. Nothing is sent when LocusGraph is
+    /// offline or has nothing to say; the session does not wait for it.
+    pub(super) fn fetch_project_insights(&self) {
+        if !self.config.session_log {
+            return;
+        }
+        let options = InsightsOptions::new()
+            .limit(PROJECT_INSIGHTS_LIMIT)
+            .context_id(memory::project_anchor_id(
+                &self.project_name,
+                &self.repo_hash,
+            ))
+            .context_id(memory::tool_anchor_id(&self.project_name, &self.repo_hash))
+            .context_id(memory::session_anchor_id(
+                &self.project_name,
+                &self.repo_hash,
+            ));
+        let locus_graph = self.locus_graph.clone();
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            match locus_graph
+                .generate_insights(PROJECT_INSIGHTS_TASK, Some(options))
+                .await
+            {
+                Ok(result) if !result.insight.trim().is_empty() => {
+                    info!(
+                        "Project insights ({:.0}% confidence)",
+                        result.confidence * 100.0
+                    );
+                    let _ = event_tx
+                        .send(SessionEvent::project_insights(
+                            result.insight,
+                            result.recommendation,
+                            result.confidence,
+                        ))
+                        .await;
+                }
+                Ok(_) => debug!("No project insights"),
+                Err(e) => debug!("Project insights unavailable: {}", e),
+            }
+        });
+    }
+}
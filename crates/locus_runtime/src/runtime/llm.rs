@@ -4,7 +4,9 @@ use std::collections::HashMap;
 use std::time::Instant;
 
 use futures::StreamExt;
-use locus_core::{ContentBlock, Role, SessionEvent, TokenUsage, ToolUse, Turn};
+use locus_core::{
+    ContentBlock, ProviderCall, RedactionPolicy, Role, SessionEvent, TokenUsage, ToolUse, Turn,
+};
 use locus_llms::types::{GenerateRequest, StreamEvent};
 use locusgraph_observability::{record_duration, record_error};
 use tokio_util::sync::CancellationToken;
@@ -43,6 +45,10 @@ impl Runtime {
         }
 
         let model = request.model.clone();
+        let mut inspect = (self.config.inspect_calls > 0).then(|| {
+            let body = serde_json::to_value(&request).unwrap_or(serde_json::Value::Null);
+            ProviderCall::new(self.llm_client.provider_id(), &model, body)
+        });
 
         // Emit turn start for assistant
         let _ = self
@@ -55,6 +61,8 @@ impl Runtime {
         let mut stream = match self.llm_client.stream(request).await {
             Ok(s) => s,
             Err(e) => {
+                self.emit_provider_call(inspect, start, Some(e.to_string()))
+                    .await;
                 let err = RuntimeError::LlmFailed(e.to_string());
                 record_error(&err);
                 return Err(err);
@@ -72,7 +80,12 @@ impl Runtime {
             let event_result = if let Some(c) = cancel.clone() {
                 tokio::select! {
                     biased;
-                    _ = c.cancelled() => return Err(RuntimeError::Cancelled),
+                    _ = c.cancelled() => {
+                        self.emit_provider_call(inspect, start, Some("cancelled".to_string()))
+                            .await;
+                        self.cancelled_reply = text_content;
+                        return Err(RuntimeError::Cancelled);
+                    }
                     ev = stream.next() => ev,
                 }
             } else {
@@ -81,6 +94,11 @@ impl Runtime {
             let Some(event_result) = event_result else {
                 break;
             };
+            if let (Some(call), Ok(event)) = (inspect.as_mut(), &event_result) {
+                call.push_event(
+                    serde_json::to_string(event).unwrap_or_else(|_| format!("{:?}", event)),
+                );
+            }
             match event_result {
                 Ok(event) => match event {
                     StreamEvent::Start { id } => {
@@ -140,6 +158,7 @@ impl Runtime {
                         );
                         self.buffer_event(error_event);
                         let _ = self.event_tx.send(SessionEvent::error(&message)).await;
+                        self.emit_provider_call(inspect, start, Some(message)).await;
                         return Err(err);
                     }
                 },
@@ -155,6 +174,8 @@ impl Runtime {
                         &e.to_string(),
                     );
                     self.buffer_event(error_event);
+                    self.emit_provider_call(inspect, start, Some(e.to_string()))
+                        .await;
                     return Err(err);
                 }
             }
@@ -162,6 +183,7 @@ impl Runtime {
 
         let duration = start.elapsed();
         record_duration("llm.stream_duration_ms", duration);
+        self.emit_provider_call(inspect, start, None).await;
 
         // Store LLM call (fire-and-forget) and session token totals
         let prompt_tokens = usage.as_ref().map(|u| u.prompt_tokens as u64).unwrap_or(0);
@@ -170,6 +192,17 @@ impl Runtime {
             .map(|u| u.completion_tokens as u64)
             .unwrap_or(0);
         self.session.add_llm_usage(prompt_tokens, completion_tokens);
+        if usage.is_some() {
+            let _ = self
+                .event_tx
+                .send(SessionEvent::token_usage(
+                    prompt_tokens,
+                    completion_tokens,
+                    self.session.total_prompt_tokens,
+                    self.session.total_completion_tokens,
+                ))
+                .await;
+        }
 
         // Build assistant turn (with token usage for this turn)
         let turn_usage = TokenUsage::new(prompt_tokens, completion_tokens);
@@ -220,12 +253,34 @@ impl Runtime {
 
         // Add assistant turn to session
         self.session.add_turn(assistant_turn);
+        self.save_run_checkpoint(&tool_uses, &[]).await;
 
         // Execute tool calls if any
         if !tool_uses.is_empty() {
-            self.execute_tool_calls(tool_uses).await?;
+            self.execute_tool_calls(tool_uses, cancel).await?;
         }
 
         Ok(())
     }
+
+    /// Send a recorded provider call to the inspection view, redacted.
+    /// No-op when inspection is off (`call` is None).
+    async fn emit_provider_call(
+        &self,
+        call: Option<ProviderCall>,
+        start: Instant,
+        error: Option<String>,
+    ) {
+        let Some(call) = call else {
+            return;
+        };
+        let policy = RedactionPolicy::default().with_env_secrets();
+        let call = call
+            .finish(start.elapsed().as_millis() as u64, error)
+            .redacted(&policy);
+        let _ = self
+            .event_tx
+            .send(SessionEvent::provider_call(call, self.config.inspect_calls))
+            .await;
+    }
 }
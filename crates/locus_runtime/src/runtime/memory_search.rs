@@ -0,0 +1,37 @@
+//! `:memory <query>`: what LocusGraph recalls about this repo for a query,
+//! shown to the user instead of going into a prompt.
+
+use locus_core::SessionEvent;
+use locus_graph::RetrieveOptions;
+
+use crate::error::RuntimeError;
+use crate::memory;
+
+use super::Runtime;
+
+impl Runtime {
+    /// Search memory as recall before an LLM call would, in the same scope,
+    /// and send the results as [SessionEvent::MemorySearch]. Returns the
+    /// memories, most relevant first.
+    pub async fn search_memories(&self, query: &str) -> Result<Vec<String>, RuntimeError> {
+        let mut options = RetrieveOptions::new().limit(self.config.memory_limit as u64);
+        for id in &self.context_ids {
+            options = options.context_id(id.clone());
+        }
+        let result = self
+            .locus_graph
+            .retrieve_memories(query, Some(options))
+            .await
+            .map_err(|e| RuntimeError::MemoryFailed(e.to_string()))?;
+        let memories = memory::memory_items(&result.memories);
+        let _ = self
+            .event_tx
+            .send(SessionEvent::memory_search(
+                query,
+                memories.clone(),
+                result.degraded,
+            ))
+            .await;
+        Ok(memories)
+    }
+}
@@ -6,25 +6,56 @@
 //! Split into focused submodules:
 //! - **agent_loop** — run, agent loop, message processing, prepare_llm_call
 //! - **llm** — LLM streaming and response handling
-//! - **tools** — tool call execution and sub-agent task spawning
+//! - **tools** — tool call execution, guardrail enforcement and sub-agent task spawning
+//! - **admission** — rate-limit aware admission control for sub-agents
+//! - **rewind** — `:rewind N`: drop recent turns and restore the files they changed
+//! - **plan** — `:plan` / `:execute-plan`: plan with read-only tools, then carry the plan out
+//! - **model** — `:model`: list the providers' models, switch the session to one
+//! - **task_tool** — the `task` tool on a ToolBus, for `locus mcp serve`
 
+mod admission;
 mod agent_loop;
+mod diff_review;
+mod insights;
 mod llm;
+mod memory_search;
+mod model;
+mod pin;
+mod plan;
+mod rewind;
+mod sub_agent_report;
+mod task_tool;
 mod tools;
+mod watchdog;
 
+pub use task_tool::TaskTool;
+
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use locus_core::{ContentBlock, Role, Session, SessionConfig, SessionEvent, SessionStatus, Turn};
+use locus_core::db::RunCheckpoint;
+use locus_core::{
+    ContentBlock, McpPromptInfo, RedactionPolicy, Role, SecretScrubber, Session, SessionConfig,
+    SessionEvent, SessionStatus, ToolAnnotation, ToolResultData, ToolUse, Turn,
+};
 use locus_graph::{CreateEventRequest, LocusGraphClient, LocusGraphConfig, TurnSummary};
 use locus_llms::{AnthropicProvider, Provider, ZaiProvider};
-use locus_toolbus::{ToolBus, ToolInfo};
+use locus_toolbus::mcp::{watch_mcp_config, McpManager};
+use locus_toolbus::{Approver, ConflictResolver, ToolBus, ToolInfo};
 use tokio::sync::mpsc;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::agent_profiles::{AgentProfiles, AGENT_PROFILES_FILE};
 use crate::config::{LlmProvider, RuntimeConfig};
+use crate::context;
 use crate::error::RuntimeError;
+use crate::guardrails::{Guardrails, GUARDRAILS_FILE};
+use crate::hooks::{Hooks, HOOKS_FILE};
 use crate::memory;
 
+/// Word overlap at which a past request counts as the same work.
+const SIMILAR_WORK_THRESHOLD: f64 = 0.6;
+
 /// The main orchestrator for locus.codes.
 ///
 /// Owns all components and runs the agent loop.
@@ -43,8 +74,13 @@ pub struct Runtime {
     pub config: RuntimeConfig,
     /// Cached context IDs for memory queries (updated when new turns are created)
     context_ids: Vec<String>,
-    /// Cached active tools (stable per runtime lifetime)
+    /// Cached active tools (stable per runtime lifetime, narrowed during plan mode)
     active_tools: Vec<ToolInfo>,
+    /// Planning with read-only tools (`:plan`); other tool calls are refused
+    plan_mode: bool,
+    /// Text the model had streamed when the user cancelled, kept for the
+    /// cancelled-turn marker
+    cancelled_reply: String,
     /// Current turn sequence number (1-based, increments per turn)
     turn_sequence: u32,
     /// Event sequence counter within current turn (1-based, resets per turn)
@@ -61,6 +97,34 @@ pub struct Runtime {
     turn_event_buffer: Vec<CreateEventRequest>,
     /// Cached graph map (structural hierarchy, built at startup)
     graph_map: String,
+    /// Cached repo map (ranked files and symbols, built before the first LLM call)
+    repo_map: Option<String>,
+    /// Cached project instructions section (`AGENTS.md` etc., read before the first LLM call)
+    instructions: Option<String>,
+    /// User notes on tool results, sent with the next LLM call
+    pending_notes: Vec<String>,
+    /// Tool result notes sent while a run holds the runtime (see
+    /// [Runtime::annotation_sender])
+    annotation_tx: mpsc::Sender<ToolAnnotation>,
+    annotation_rx: mpsc::Receiver<ToolAnnotation>,
+    /// MCP prompts last sent to the UI (see [SessionEvent::McpPrompts])
+    mcp_prompts: Vec<McpPromptInfo>,
+    /// Files kept in every request's context (`:pin`)
+    pinned: context::PinnedFiles,
+    /// Assertions checked after each editing turn (`.locus/guardrails.toml`)
+    guardrails: Guardrails,
+    /// Shell commands run around tool calls and at turn end (`.locus/hooks.toml`)
+    hooks: Hooks,
+    /// Profiles `task` calls can give their sub-agents (`.locus/agents.toml`)
+    agent_profiles: AgentProfiles,
+    /// The profile this sub-agent runs with; calls to tools outside
+    /// `profile_tools` are refused
+    agent_profile: Option<String>,
+    profile_tools: HashSet<String>,
+    /// Past sessions already flagged as similar work, so each is shown once
+    similar_sessions: HashSet<String>,
+    /// Placeholders for secrets sent to the model, stable for the session
+    secrets: SecretScrubber,
 }
 
 impl Runtime {
@@ -101,7 +165,20 @@ impl Runtime {
             .map_err(|e| RuntimeError::MemoryFailed(format!("LocusGraph client: {}", e)))?;
 
         // Initialize ToolBus
-        let toolbus = Arc::new(ToolBus::new(config.repo_root.clone()));
+        let toolbus = Arc::new(ToolBus::with_backend(
+            config.repo_root.clone(),
+            config.execution_backend.clone(),
+        ));
+        toolbus.load_extensions().await;
+
+        // MCP servers: started in the background and kept in line with the config
+        // file for as long as the ToolBus lives, shared sessions included
+        if let Some(path) = &config.mcp_config {
+            let manager = Arc::new(McpManager::new(path.clone()));
+            if let Err(e) = watch_mcp_config(manager, &toolbus) {
+                warn!("MCP servers unavailable: {}", e);
+            }
+        }
 
         // Initialize LLM provider
         let llm_client = Self::create_provider(&config.provider)?;
@@ -138,14 +215,20 @@ impl Runtime {
         );
 
         // Build graph map (walk hierarchy 2 levels deep from project root)
-        let graph_map =
-            memory::build_graph_map(&locus_graph, &project_name, &repo_hash).await;
+        let graph_map = memory::build_graph_map(&locus_graph, &project_name, &repo_hash).await;
 
         // Cache context IDs and active tools (starts empty for turns, populated at session start)
         let context_ids = memory::build_context_ids(&project_name, &repo_hash, "", "", &[]);
         let mut active_tools = memory::get_active_tools(&toolbus_tools);
         active_tools.extend(meta_tools);
 
+        let guardrails = load_guardrails(&config.repo_root)?;
+        let hooks = load_hooks(&config.repo_root)?;
+        let agent_profiles = load_agent_profiles(&config.repo_root)?;
+        describe_agent_profiles(&mut active_tools, &agent_profiles);
+        let pinned = context::PinnedFiles::new(&config.pinned_files);
+
+        let (annotation_tx, annotation_rx) = mpsc::channel(16);
         Ok(Self {
             session,
             locus_graph,
@@ -155,6 +238,8 @@ impl Runtime {
             config,
             context_ids,
             active_tools,
+            plan_mode: false,
+            cancelled_reply: String::new(),
             turn_sequence: 0,
             event_seq: 0,
             session_slug: String::new(),
@@ -163,6 +248,20 @@ impl Runtime {
             project_name,
             turn_event_buffer: Vec::new(),
             graph_map,
+            repo_map: None,
+            instructions: None,
+            pending_notes: Vec::new(),
+            annotation_tx,
+            annotation_rx,
+            mcp_prompts: Vec::new(),
+            pinned,
+            guardrails,
+            hooks,
+            agent_profiles,
+            agent_profile: None,
+            profile_tools: HashSet::new(),
+            similar_sessions: HashSet::new(),
+            secrets: SecretScrubber::new(RedactionPolicy::default().with_env_secrets()),
         })
     }
 
@@ -204,6 +303,13 @@ impl Runtime {
         let mut active_tools = memory::get_active_tools(&toolbus.list_tools());
         active_tools.extend(locus_toolbus::meta_tool_definitions());
 
+        let guardrails = load_guardrails(&config.repo_root)?;
+        let hooks = load_hooks(&config.repo_root)?;
+        let agent_profiles = load_agent_profiles(&config.repo_root)?;
+        describe_agent_profiles(&mut active_tools, &agent_profiles);
+        let pinned = context::PinnedFiles::new(&config.pinned_files);
+
+        let (annotation_tx, annotation_rx) = mpsc::channel(16);
         Ok(Self {
             session,
             locus_graph,
@@ -213,6 +319,8 @@ impl Runtime {
             config,
             context_ids,
             active_tools,
+            plan_mode: false,
+            cancelled_reply: String::new(),
             turn_sequence: 0,
             event_seq: 0,
             session_slug: String::new(),
@@ -221,6 +329,20 @@ impl Runtime {
             project_name,
             turn_event_buffer: Vec::new(),
             graph_map: String::new(),
+            repo_map: None,
+            instructions: None,
+            pending_notes: Vec::new(),
+            annotation_tx,
+            annotation_rx,
+            mcp_prompts: Vec::new(),
+            pinned,
+            guardrails,
+            hooks,
+            agent_profiles,
+            agent_profile: None,
+            profile_tools: HashSet::new(),
+            similar_sessions: HashSet::new(),
+            secrets: SecretScrubber::new(RedactionPolicy::default().with_env_secrets()),
         })
     }
 
@@ -256,6 +378,13 @@ impl Runtime {
         let mut active_tools = memory::get_active_tools(&toolbus.list_tools());
         active_tools.extend(locus_toolbus::meta_tool_definitions());
 
+        let guardrails = load_guardrails(&config.repo_root)?;
+        let hooks = load_hooks(&config.repo_root)?;
+        let agent_profiles = load_agent_profiles(&config.repo_root)?;
+        describe_agent_profiles(&mut active_tools, &agent_profiles);
+        let pinned = context::PinnedFiles::new(&config.pinned_files);
+
+        let (annotation_tx, annotation_rx) = mpsc::channel(16);
         Ok(Self {
             session,
             locus_graph,
@@ -265,6 +394,8 @@ impl Runtime {
             config,
             context_ids,
             active_tools,
+            plan_mode: false,
+            cancelled_reply: String::new(),
             turn_sequence: 0,
             event_seq: 0,
             session_slug: String::new(),
@@ -273,6 +404,20 @@ impl Runtime {
             project_name,
             turn_event_buffer: Vec::new(),
             graph_map: String::new(),
+            repo_map: None,
+            instructions: None,
+            pending_notes: Vec::new(),
+            annotation_tx,
+            annotation_rx,
+            mcp_prompts: Vec::new(),
+            pinned,
+            guardrails,
+            hooks,
+            agent_profiles,
+            agent_profile: None,
+            profile_tools: HashSet::new(),
+            similar_sessions: HashSet::new(),
+            secrets: SecretScrubber::new(RedactionPolicy::default().with_env_secrets()),
         })
     }
 
@@ -312,9 +457,7 @@ impl Runtime {
         }
         let locus_graph = Arc::clone(&self.locus_graph);
         tokio::spawn(async move {
-            for event in events {
-                let _ = locus_graph.store_event(event).await;
-            }
+            locus_graph.store_events_batch(events).await;
         });
     }
 
@@ -339,6 +482,55 @@ impl Runtime {
         )
     }
 
+    /// The repo map for the system prompt, built on first use and kept for the
+    /// session so the prompt prefix stays stable across calls.
+    async fn repo_map(&mut self) -> String {
+        if let Some(map) = &self.repo_map {
+            return map.clone();
+        }
+        let repo_root = self.config.repo_root.clone();
+        let budget = self.config.repo_map_tokens;
+        let map = tokio::task::spawn_blocking(move || context::build_repo_map(&repo_root, budget))
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Repo map task failed: {}", e);
+                String::new()
+            });
+        self.repo_map = Some(map.clone());
+        map
+    }
+
+    /// The project instructions section for the system prompt, read on first
+    /// use and kept for the session like the repo map. Sends
+    /// [SessionEvent::InstructionsLoaded] when any instruction file was found.
+    async fn project_instructions(&mut self) -> String {
+        if let Some(section) = &self.instructions {
+            return section.clone();
+        }
+        let files = if self.config.project_instructions {
+            let repo_root = self.config.repo_root.clone();
+            tokio::task::spawn_blocking(move || context::load_instructions(&repo_root))
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Loading project instructions failed: {}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+        if !files.is_empty() {
+            let paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+            info!("Loaded project instructions: {}", paths.join(", "));
+            let _ = self
+                .event_tx
+                .send(SessionEvent::instructions_loaded(paths))
+                .await;
+        }
+        let section = context::instructions_section(&files);
+        self.instructions = Some(section.clone());
+        section
+    }
+
     /// Build a TurnSummary from the current round of activity.
     fn build_turn_summary(&self, user_message: &str) -> TurnSummary {
         let mut actions_taken = Vec::new();
@@ -439,6 +631,206 @@ impl Runtime {
         }
     }
 
+    /// Keep a local copy of the turn summary in the project DB for `locus journal`.
+    async fn record_session_log(&self, summary: &TurnSummary) {
+        if !self.config.session_log {
+            return;
+        }
+        let entry = locus_core::db::SessionLogEntry {
+            session_id: self.session.id.as_str().to_string(),
+            session_slug: self.session_slug.clone(),
+            ts: chrono::Utc::now().timestamp(),
+            title: summary.title.clone(),
+            outcome: summary.outcome.clone(),
+            files_modified: summary.files_modified.clone(),
+            tool_calls: summary.actions_taken.len() as u32,
+        };
+        let repo_root = self.config.repo_root.clone();
+        match tokio::task::spawn_blocking(move || locus_core::db::record_turn(&repo_root, &entry))
+            .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to record session log: {}", e),
+            Err(e) => warn!("Session log task failed: {}", e),
+        }
+    }
+
+    /// Save the whole session to the project DB, for `locus sessions` and resuming.
+    async fn save_session(&self) {
+        if !self.config.session_log {
+            return;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let slug = self.session_slug.clone();
+        let session = self.session.clone();
+        match tokio::task::spawn_blocking(move || {
+            locus_core::db::save_session(&repo_root, &slug, &session)
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to save session: {}", e),
+            Err(e) => warn!("Save session task failed: {}", e),
+        }
+    }
+
+    /// Save the turn in progress as the session's crash recovery checkpoint:
+    /// the session so far plus the last assistant turn's calls that are
+    /// `pending` and `finished`. Called after each step of a run.
+    async fn save_run_checkpoint(
+        &self,
+        pending: &[ToolUse],
+        finished: &[(ToolUse, ToolResultData)],
+    ) {
+        if !self.config.session_log {
+            return;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let checkpoint = RunCheckpoint {
+            session: self.session.clone(),
+            slug: self.session_slug.clone(),
+            pending_tools: pending.to_vec(),
+            finished_tools: finished.to_vec(),
+            saved_at: chrono::Utc::now().timestamp(),
+        };
+        match tokio::task::spawn_blocking(move || {
+            locus_core::db::save_run_checkpoint(&repo_root, &checkpoint)
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to save run checkpoint: {}", e),
+            Err(e) => warn!("Run checkpoint task failed: {}", e),
+        }
+    }
+
+    /// Drop the crash recovery checkpoint once the run has ended.
+    async fn clear_run_checkpoint(&self) {
+        if !self.config.session_log {
+            return;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let session_id = self.session.id.as_str().to_string();
+        match tokio::task::spawn_blocking(move || {
+            locus_core::db::clear_run_checkpoint(&repo_root, &session_id)
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to clear run checkpoint: {}", e),
+            Err(e) => warn!("Run checkpoint task failed: {}", e),
+        }
+    }
+
+    /// Continue a saved session (see `locus_core::db::load_session`): its turns
+    /// become the conversation history and its id and slug are kept, so the
+    /// next message is another turn of it. Later turns use this runtime's model.
+    pub async fn resume_session(&mut self, mut session: Session, slug: String) {
+        session.set_status(SessionStatus::Active);
+        session.run_started_at = None;
+        session.config = self.session.config.clone();
+        self.turn_sequence = session
+            .turns
+            .iter()
+            .filter(|t| t.role == Role::User)
+            .count() as u32;
+        self.session = session;
+        self.session_slug = slug;
+        self.similar_sessions.clear();
+        if !self.session_slug.is_empty() {
+            self.load_session_context().await;
+        }
+        info!(
+            "Resumed session {} ({} turns)",
+            self.session.id.as_str(),
+            self.turn_sequence
+        );
+    }
+
+    /// Point memory retrieval at this session's turns stored in LocusGraph.
+    async fn load_session_context(&mut self) {
+        let existing_turns = memory::fetch_session_turns(
+            &self.locus_graph,
+            &self.session_slug,
+            self.session.id.as_str(),
+        )
+        .await;
+        info!("Found {} existing turns for session", existing_turns.len());
+
+        self.context_ids = memory::build_context_ids(
+            &self.project_name,
+            &self.repo_hash,
+            &self.session_slug,
+            self.session.id.as_str(),
+            &existing_turns,
+        );
+    }
+
+    /// Look up a past session that already handled a request like `message`.
+    /// The UI is told about it and the returned note goes to the model, so it
+    /// builds on that work instead of redoing or contradicting it.
+    async fn check_similar_work(&mut self, message: &str) -> Option<String> {
+        if !self.config.session_log {
+            return None;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let request = message.to_string();
+        let session_id = self.session.id.as_str().to_string();
+        let lookup = tokio::task::spawn_blocking(move || {
+            locus_core::db::find_similar_work(
+                &repo_root,
+                &request,
+                &session_id,
+                SIMILAR_WORK_THRESHOLD,
+            )
+        })
+        .await;
+        let work = match lookup {
+            Ok(Ok(work)) => work?,
+            Ok(Err(e)) => {
+                warn!("Similar work lookup failed: {}", e);
+                return None;
+            }
+            Err(e) => {
+                warn!("Similar work task failed: {}", e);
+                return None;
+            }
+        };
+        if !self
+            .similar_sessions
+            .insert(work.matched.session_id.clone())
+        {
+            return None;
+        }
+
+        let matched = &work.matched;
+        let date = chrono::DateTime::from_timestamp(matched.ts, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let session = if matched.session_slug.is_empty() {
+            &matched.session_id
+        } else {
+            &matched.session_slug
+        };
+        info!(
+            "Request resembles session {} ({:.0}% word overlap)",
+            session,
+            work.score * 100.0
+        );
+        let note = format!(
+            "[Similar past work] On {} session {} handled a similar request: \"{}\". \
+             It changed {}. Outcome: \"{}\". \
+             Build on that work rather than redoing it, and say so if this request contradicts it.",
+            date,
+            session,
+            matched.title,
+            work.files_modified.join(", "),
+            matched.outcome
+        );
+        let _ = self.event_tx.send(SessionEvent::similar_work(work)).await;
+        Some(note)
+    }
+
     /// Generate a slug from user message (kebab-case, max 30 chars).
     fn slugify(message: &str) -> String {
         let slug: String = message
@@ -492,7 +884,9 @@ impl Runtime {
     }
 
     /// Create an LLM provider based on configuration.
-    fn create_provider(provider: &LlmProvider) -> Result<Arc<dyn Provider>, RuntimeError> {
+    pub(crate) fn create_provider(
+        provider: &LlmProvider,
+    ) -> Result<Arc<dyn Provider>, RuntimeError> {
         match provider {
             LlmProvider::Anthropic => {
                 let anthropic = AnthropicProvider::from_env()
@@ -542,6 +936,25 @@ impl Runtime {
         Ok(())
     }
 
+    /// Route ToolBus approval requests (`ask` permission rules) to `approver`,
+    /// usually an [crate::ApprovalBroker] answered by the TUI. Sub-agents share
+    /// the ToolBus, so their calls go through the same approver.
+    pub fn set_approver(&self, approver: Arc<dyn Approver>) {
+        self.toolbus.set_approver(approver);
+    }
+
+    /// Sender for notes on tool results. Notes sent while a run is going are
+    /// picked up before its next LLM call; others wait for the next run.
+    pub fn annotation_sender(&self) -> mpsc::Sender<ToolAnnotation> {
+        self.annotation_tx.clone()
+    }
+
+    /// Route edit conflicts (the user changed a file the agent is about to edit)
+    /// to `resolver`, usually the same [crate::ApprovalBroker].
+    pub fn set_conflict_resolver(&self, resolver: Arc<dyn ConflictResolver>) {
+        self.toolbus.set_conflict_resolver(resolver);
+    }
+
     /// Get the current task description.
     pub fn current_task(&self) -> String {
         self.session
@@ -561,6 +974,32 @@ impl Runtime {
     }
 }
 
+/// Load guardrails for `repo_root`. A broken file is an error rather than
+/// silently running without the limits it declares.
+fn load_guardrails(repo_root: &std::path::Path) -> Result<Guardrails, RuntimeError> {
+    Guardrails::load(repo_root)
+        .map_err(|e| RuntimeError::ConfigError(format!("{}: {}", GUARDRAILS_FILE, e)))
+}
+
+/// Load user hooks for `repo_root`; like guardrails, a broken file is an error.
+fn load_hooks(repo_root: &std::path::Path) -> Result<Hooks, RuntimeError> {
+    Hooks::load(repo_root).map_err(|e| RuntimeError::ConfigError(format!("{}: {}", HOOKS_FILE, e)))
+}
+
+/// Load agent profiles for `repo_root`; like guardrails, a broken file is an error.
+fn load_agent_profiles(repo_root: &std::path::Path) -> Result<AgentProfiles, RuntimeError> {
+    AgentProfiles::load(repo_root)
+        .map_err(|e| RuntimeError::ConfigError(format!("{}: {}", AGENT_PROFILES_FILE, e)))
+}
+
+/// Name the profiles in the `task` tool's `profile` parameter, custom ones included.
+fn describe_agent_profiles(tools: &mut [ToolInfo], profiles: &AgentProfiles) {
+    if let Some(task) = tools.iter_mut().find(|tool| tool.name == "task") {
+        task.parameters["properties"]["profile"]["description"] =
+            serde_json::Value::String(profiles.describe());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
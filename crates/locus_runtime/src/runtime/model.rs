@@ -0,0 +1,79 @@
+//! `:model`: the models of each provider with an API key, and switching the
+//! session to one of them between turns.
+
+use locus_core::{ProviderModels, SessionEvent};
+use locus_llms::{AnthropicProvider, ProviderRegistry, ZaiProvider};
+use tracing::{info, warn};
+
+use crate::config::LlmProvider;
+use crate::error::RuntimeError;
+
+use super::Runtime;
+
+impl Runtime {
+    /// The providers whose API key is set.
+    pub fn provider_registry() -> ProviderRegistry {
+        let mut registry = ProviderRegistry::new();
+        if let Ok(provider) = AnthropicProvider::from_env() {
+            registry = registry.register(LlmProvider::Anthropic.as_str(), provider);
+        }
+        if let Ok(provider) = ZaiProvider::from_env() {
+            registry = registry.register(LlmProvider::ZAI.as_str(), provider);
+        }
+        registry
+    }
+
+    /// Ask each registered provider for its models and send them as
+    /// [SessionEvent::ModelList]. A provider that can't be reached lists only
+    /// the model in use, if it is that provider's.
+    pub async fn list_models(&self) -> Vec<ProviderModels> {
+        let registry = Self::provider_registry();
+        let mut ids = registry.list_providers();
+        ids.sort();
+        let mut providers = Vec::new();
+        for id in ids {
+            let mut models = match registry.get_provider(&id) {
+                Ok(provider) => provider.list_models().await.unwrap_or_else(|e| {
+                    warn!("Listing {} models failed: {}", id, e);
+                    Vec::new()
+                }),
+                Err(_) => Vec::new(),
+            };
+            if id == self.config.provider.as_str() && !models.contains(&self.config.model) {
+                models.insert(0, self.config.model.clone());
+            }
+            providers.push(ProviderModels {
+                provider: id,
+                models,
+            });
+        }
+        let _ = self
+            .event_tx
+            .send(SessionEvent::model_list(providers.clone()))
+            .await;
+        providers
+    }
+
+    /// Send the next turns to `model` of `provider`, and keep the choice in the
+    /// session's config. Sends [SessionEvent::ModelInUse].
+    pub async fn switch_model(&mut self, provider: &str, model: &str) -> Result<(), RuntimeError> {
+        let provider: LlmProvider = provider
+            .parse()
+            .map_err(|_| RuntimeError::ProviderNotFound(provider.to_string()))?;
+        self.llm_client = Self::create_provider(&provider)?;
+        self.config.provider = provider;
+        self.config.model = model.to_string();
+        self.session.config.provider = provider.as_str().to_string();
+        self.session.config.model = model.to_string();
+        info!("Switched to {}/{}", provider, model);
+        let _ = self
+            .event_tx
+            .send(SessionEvent::model_in_use(
+                provider.as_str(),
+                model,
+                self.config.context_limit,
+            ))
+            .await;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! `:pin <path>` / `:unpin <path>`: files kept in the context of every request.
+
+use locus_core::{PinCommand, SessionEvent};
+use tracing::info;
+
+use crate::error::RuntimeError;
+
+use super::Runtime;
+
+impl Runtime {
+    /// Pin or unpin a file, or just list the pinned ones. Pinning reads the
+    /// file through the tool bus, so only readable files in the repo can be
+    /// pinned. Sends [SessionEvent::PinnedFiles] and returns the pinned paths.
+    pub async fn pin(&mut self, command: PinCommand) -> Result<Vec<String>, RuntimeError> {
+        match command {
+            PinCommand::Pin(path) => {
+                self.pinned.pin(&self.toolbus, &path).await.map_err(|e| {
+                    RuntimeError::SessionError(format!("Cannot pin {}: {}", path, e))
+                })?;
+                info!("Pinned {}", path);
+            }
+            PinCommand::Unpin(path) => {
+                if !self.pinned.unpin(&path) {
+                    return Err(RuntimeError::SessionError(format!(
+                        "{} is not pinned",
+                        path
+                    )));
+                }
+                info!("Unpinned {}", path);
+            }
+            PinCommand::List => {}
+        }
+        let paths = self.pinned.paths();
+        let _ = self
+            .event_tx
+            .send(SessionEvent::pinned_files(paths.clone()))
+            .await;
+        Ok(paths)
+    }
+}
@@ -0,0 +1,138 @@
+//! `:plan` / `:execute-plan`: plan a task with read-only tools, save the plan
+//! under `.locus/plans/`, then carry it out with every tool.
+
+use locus_core::plan::{load_plan, save_plan};
+use locus_core::{ContentBlock, Role, SessionEvent, SessionStatus};
+use locus_toolbus::Permission;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::error::RuntimeError;
+
+use super::Runtime;
+
+/// Tools plan mode allows besides read-only ones: `task_list` so the plan can
+/// be laid out as tasks, and the meta-tools for finding tools.
+const PLAN_MODE_TOOLS: &[&str] = &["task_list", "tool_search", "tool_explain"];
+/// Offered in plan mode even when they are not among the active tools.
+const PLAN_MODE_EXTRA_TOOLS: &[&str] = &["read", "task_list"];
+
+impl Runtime {
+    /// Run `task` in plan mode: the model only gets read-only tools (plus
+    /// `task_list`), other calls are refused, and its final answer is saved as a
+    /// plan under `.locus/plans/` and sent as [SessionEvent::PlanReady].
+    pub async fn plan(
+        &mut self,
+        task: String,
+        cancel: Option<CancellationToken>,
+    ) -> Result<SessionStatus, RuntimeError> {
+        let mut plan_tools: Vec<_> = self
+            .active_tools
+            .iter()
+            .filter(|tool| self.allowed_in_plan_mode(&tool.name))
+            .cloned()
+            .collect();
+        for tool in self.toolbus.list_tools() {
+            if PLAN_MODE_EXTRA_TOOLS.contains(&tool.name.as_str())
+                && !plan_tools.iter().any(|t| t.name == tool.name)
+            {
+                plan_tools.push(tool);
+            }
+        }
+        let full_tools = std::mem::replace(&mut self.active_tools, plan_tools);
+        self.plan_mode = true;
+        let first_turn = self.session.turns.len();
+        let result = self.run(task.clone(), cancel).await;
+        self.plan_mode = false;
+        self.active_tools = full_tools;
+
+        let status = result?;
+        if status == SessionStatus::Cancelled {
+            return Ok(status);
+        }
+        let text = self.last_assistant_text(first_turn);
+        if text.trim().is_empty() {
+            let _ = self
+                .event_tx
+                .send(SessionEvent::error("Plan mode ended without a plan"))
+                .await;
+            return Ok(status);
+        }
+
+        let repo_root = self.config.repo_root.clone();
+        let plan = tokio::task::spawn_blocking(move || save_plan(&repo_root, &task, &text))
+            .await
+            .map_err(|e| RuntimeError::SessionError(e.to_string()))?
+            .map_err(|e| RuntimeError::SessionError(format!("Failed to save plan: {}", e)))?;
+        info!("Saved plan to {}", plan.path);
+        let _ = self.event_tx.send(SessionEvent::plan_ready(plan)).await;
+        Ok(status)
+    }
+
+    /// Carry out the plan at `path` (the newest one when None) with every tool:
+    /// the plan goes to the model with the message asking it to follow it.
+    pub async fn execute_plan(
+        &mut self,
+        path: Option<&str>,
+        cancel: Option<CancellationToken>,
+    ) -> Result<SessionStatus, RuntimeError> {
+        let plan = match load_plan(&self.config.repo_root, path) {
+            Ok(Some(plan)) => plan,
+            Ok(None) => {
+                let message = "No plan to execute; make one with :plan <task>".to_string();
+                return Err(self.plan_error(message).await);
+            }
+            Err(e) => {
+                let message = format!("Failed to read plan {}: {}", path.unwrap_or_default(), e);
+                return Err(self.plan_error(message).await);
+            }
+        };
+        info!("Executing plan {}", plan.path);
+        self.pending_notes.push(format!(
+            "[Plan to carry out, from {}]\n{}",
+            plan.path, plan.text
+        ));
+        let task = if plan.task.is_empty() {
+            plan.path
+        } else {
+            plan.task
+        };
+        self.run(format!("Carry out the plan for: {}", task), cancel)
+            .await
+    }
+
+    /// Tell the user why a plan can't be carried out; no run was started to do it.
+    async fn plan_error(&self, message: String) -> RuntimeError {
+        let _ = self
+            .event_tx
+            .send(SessionEvent::error(message.clone()))
+            .await;
+        RuntimeError::SessionError(message)
+    }
+
+    /// Whether plan mode lets the model call `tool_name`.
+    pub(crate) fn allowed_in_plan_mode(&self, tool_name: &str) -> bool {
+        PLAN_MODE_TOOLS.contains(&tool_name)
+            || self.toolbus.permission_of(tool_name) == Some(Permission::Read)
+    }
+
+    /// Text of the last assistant turn from `first_turn` on that has any.
+    fn last_assistant_text(&self, first_turn: usize) -> String {
+        self.session.turns[first_turn.min(self.session.turns.len())..]
+            .iter()
+            .rev()
+            .filter(|turn| turn.role == Role::Assistant)
+            .map(|turn| {
+                turn.blocks
+                    .iter()
+                    .filter_map(|block| match block {
+                        ContentBlock::Text { text } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .find(|text| !text.trim().is_empty())
+            .unwrap_or_default()
+    }
+}
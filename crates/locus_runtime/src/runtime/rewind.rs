@@ -0,0 +1,75 @@
+//! `:rewind N`: drop the session's last N turns and restore the files they changed.
+
+use locus_core::rewind::{rewind_files, RewindSummary};
+use locus_core::{Role, SessionEvent};
+use tracing::info;
+
+use crate::error::RuntimeError;
+
+use super::Runtime;
+
+impl Runtime {
+    /// Drop the last `turns` user turns (and everything after the first of them)
+    /// from the session, and put the files those turns changed back the way they
+    /// were. Files changed since by someone else are left alone and reported as
+    /// kept. Asking for more turns than the session has rewinds all of them.
+    ///
+    /// File contents come from the file change log, so this needs `session_log`;
+    /// without it only the conversation is rewound. Changes made through `bash`
+    /// are not in the log and stay as they are.
+    pub async fn rewind(&mut self, turns: u32) -> Result<RewindSummary, RuntimeError> {
+        let user_turns: Vec<usize> = self
+            .session
+            .turns
+            .iter()
+            .enumerate()
+            .filter(|(_, turn)| turn.role == Role::User)
+            .map(|(i, _)| i)
+            .collect();
+        // Older turns may have been compressed away; only those still here count.
+        let turns = turns.min(user_turns.len() as u32);
+        if turns == 0 {
+            return Err(RuntimeError::SessionError("Nothing to rewind".to_string()));
+        }
+        let cut = user_turns[user_turns.len() - turns as usize];
+        let kept_turn = self.turn_sequence.saturating_sub(turns);
+
+        let summary = if self.config.session_log {
+            let repo_root = self.config.repo_root.clone();
+            let session_id = self.session.id.as_str().to_string();
+            tokio::task::spawn_blocking(move || -> anyhow::Result<RewindSummary> {
+                let changes =
+                    locus_core::db::session_file_changes(&repo_root, &session_id, kept_turn)?;
+                let summary = rewind_files(&repo_root, turns, &changes)?;
+                locus_core::db::delete_session_file_changes(&repo_root, &session_id, kept_turn)?;
+                Ok(summary)
+            })
+            .await
+            .map_err(|e| RuntimeError::SessionError(e.to_string()))?
+            .map_err(|e| RuntimeError::SessionError(format!("Rewind failed: {}", e)))?
+        } else {
+            RewindSummary {
+                turns,
+                ..Default::default()
+            }
+        };
+
+        self.session.turns.truncate(cut);
+        self.turn_sequence = kept_turn;
+        self.pending_notes.clear();
+        self.save_session().await;
+        self.clear_run_checkpoint().await;
+        info!(
+            "Rewound {} turns: {} restored, {} removed, {} kept",
+            turns,
+            summary.restored.len(),
+            summary.removed.len(),
+            summary.kept.len()
+        );
+        let _ = self
+            .event_tx
+            .send(SessionEvent::rewound(summary.clone()))
+            .await;
+        Ok(summary)
+    }
+}
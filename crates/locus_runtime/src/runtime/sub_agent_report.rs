@@ -0,0 +1,220 @@
+//! What a finished sub-agent hands back to the parent: its findings, the
+//! artifacts it left (new files, commands it ran) and the files it changed.
+//!
+//! Findings are the sub-agent's last message when everything it wrote fits in
+//! `subagent_summary_chars`. Past that, its messages and calls are condensed
+//! by the provider's cheap model first, so a long sub-agent run costs the
+//! parent a few hundred tokens rather than the whole transcript.
+
+use std::collections::HashSet;
+
+use locus_core::{ContentBlock, Role, SessionStatus};
+use locus_llms::types::{Message, Role as LlmRole};
+use locus_toolbus::Permission;
+use serde_json::Value as JsonValue;
+use tracing::{info, warn};
+
+use crate::context;
+
+use super::Runtime;
+
+/// Output cap for the condensed findings.
+const SUMMARY_MAX_TOKENS: u32 = 600;
+
+/// Characters of one call's arguments shown to the summarizer.
+const ARGS_CHARS: usize = 200;
+
+const SUMMARY_PROMPT: &str = "You condense a coding sub-agent's work for the agent that \
+delegated it. Reply with its findings only: what it found or did, the answers to the task, \
+and anything left undone or failing, as short bullet points. Keep file paths, symbol names, \
+commands and error messages exact. Leave out the steps it took to get there.";
+
+/// A sub-agent's result as the parent sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SubAgentReport {
+    pub findings: String,
+    /// Files it created and commands it ran.
+    pub artifacts: Vec<String>,
+    /// Files it changed, in the order it first changed them.
+    pub files_touched: Vec<String>,
+    /// Whether `findings` were condensed from a longer transcript.
+    pub summarized: bool,
+}
+
+impl SubAgentReport {
+    /// Add the report to a `task` result.
+    pub fn write_to(&self, output: &mut JsonValue) {
+        output["summary"] = serde_json::json!(self.findings);
+        output["artifacts"] = serde_json::json!(self.artifacts);
+        output["files_touched"] = serde_json::json!(self.files_touched);
+        if self.summarized {
+            output["summarized"] = serde_json::json!(true);
+        }
+    }
+}
+
+impl Runtime {
+    /// Report on this sub-agent's run, condensing what it wrote when it is
+    /// longer than `summary_chars` (0 = never) and it was not cancelled. A failed condensing call falls
+    /// back to the start of its last message.
+    pub(crate) async fn sub_agent_report(
+        &mut self,
+        status: &SessionStatus,
+        summary_chars: usize,
+    ) -> SubAgentReport {
+        let (artifacts, files_touched) = self.sub_agent_outputs();
+        let last_message = self.sub_agent_summary(status);
+        let transcript = self.sub_agent_transcript();
+        let mut report = SubAgentReport {
+            findings: last_message,
+            artifacts,
+            files_touched,
+            summarized: false,
+        };
+        if summary_chars == 0
+            || *status == SessionStatus::Cancelled
+            || transcript.chars().count() <= summary_chars
+        {
+            return report;
+        }
+
+        match self.condense(transcript).await {
+            Ok(findings) if !findings.trim().is_empty() => {
+                info!(
+                    "Condensed sub-agent output to {} chars",
+                    findings.chars().count()
+                );
+                report.findings = findings.trim().to_string();
+                report.summarized = true;
+            }
+            Ok(_) => warn!("Sub-agent summary came back empty; passing its last message"),
+            Err(e) => {
+                warn!("Failed to condense sub-agent output: {}", e);
+                if report.findings.chars().count() > summary_chars {
+                    let mut cut: String = report.findings.chars().take(summary_chars).collect();
+                    cut.push('…');
+                    report.findings = cut;
+                }
+            }
+        }
+        report
+    }
+
+    /// Its last assistant text.
+    fn sub_agent_summary(&self, status: &SessionStatus) -> String {
+        self.session
+            .turns
+            .iter()
+            .rev()
+            .find(|t| t.role == Role::Assistant)
+            .and_then(|t| {
+                t.blocks.iter().find_map(|b| {
+                    if let ContentBlock::Text { text } = b {
+                        Some(text.clone())
+                    } else {
+                        None
+                    }
+                })
+            })
+            .unwrap_or_else(|| format!("Task completed: {:?}", status))
+    }
+
+    /// Ask the cheap model for the findings in `transcript`.
+    async fn condense(&mut self, transcript: String) -> Result<String, locus_llms::Error> {
+        let model = self
+            .config
+            .provider
+            .cheap_model()
+            .unwrap_or(&self.config.model)
+            .to_string();
+        let mut messages = vec![
+            Message::new(LlmRole::System, SUMMARY_PROMPT),
+            Message::new(LlmRole::User, transcript),
+        ];
+        if self.config.redact_secrets {
+            context::redact_messages(&mut messages, &mut self.secrets);
+        }
+        let request = context::build_generate_request(&model, messages, &[], SUMMARY_MAX_TOKENS);
+        Ok(self.llm_client.generate(request).await?.text())
+    }
+
+    /// The task, then everything the sub-agent wrote and each call it made.
+    fn sub_agent_transcript(&self) -> String {
+        let mut lines = Vec::new();
+        for turn in &self.session.turns {
+            for block in &turn.blocks {
+                match block {
+                    ContentBlock::Text { text } if turn.role == Role::User => {
+                        lines.push(format!("Task: {}", text));
+                    }
+                    ContentBlock::Text { text } if turn.role == Role::Assistant => {
+                        lines.push(text.clone());
+                    }
+                    ContentBlock::ToolUse { tool_use } => {
+                        let mut args = tool_use.args.to_string();
+                        if args.chars().count() > ARGS_CHARS {
+                            args = args.chars().take(ARGS_CHARS).collect::<String>() + "…";
+                        }
+                        lines.push(format!("→ {} {}", tool_use.name, args));
+                    }
+                    ContentBlock::ToolResult { tool_result } if tool_result.is_error => {
+                        lines.push(format!(
+                            "  failed: {}",
+                            tool_result.output["result"]["error"]
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Files created and commands run, and the files changed, from the
+    /// sub-agent's calls that succeeded.
+    fn sub_agent_outputs(&self) -> (Vec<String>, Vec<String>) {
+        let blocks: Vec<&ContentBlock> = self
+            .session
+            .turns
+            .iter()
+            .flat_map(|turn| &turn.blocks)
+            .collect();
+        let failed: HashSet<&str> = blocks
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolResult { tool_result } if tool_result.is_error => {
+                    tool_result.output["tool_use_id"].as_str()
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut artifacts: Vec<String> = Vec::new();
+        let mut files_touched: Vec<String> = Vec::new();
+        for block in blocks {
+            let ContentBlock::ToolUse { tool_use } = block else {
+                continue;
+            };
+            if failed.contains(tool_use.id.as_str()) {
+                continue;
+            }
+            let arg = |name: &str| tool_use.args.get(name).and_then(|v| v.as_str());
+            if let (Some(command), "bash") = (arg("command"), tool_use.name.as_str()) {
+                artifacts.push(format!("$ {}", command));
+            }
+            let Some(path) = arg("path") else {
+                continue;
+            };
+            if self.toolbus.permission_of(&tool_use.name) != Some(Permission::Write) {
+                continue;
+            }
+            if tool_use.name == "create_file" && !artifacts.iter().any(|a| a == path) {
+                artifacts.push(path.to_string());
+            }
+            if !files_touched.iter().any(|p| p == path) {
+                files_touched.push(path.to_string());
+            }
+        }
+        (artifacts, files_touched)
+    }
+}
@@ -0,0 +1,132 @@
+//! The `task` meta-tool as a ToolBus tool, for callers outside a session.
+//!
+//! In a session the runtime handles `task` itself (see `tools.rs`). `locus mcp
+//! serve` has no agent loop, so it registers a [TaskTool] on the bus it serves:
+//! each call runs a sub-agent on that bus with the serving runtime's LLM and
+//! LocusGraph clients and answers with the sub-agent's final message.
+
+use std::sync::{Arc, Weak};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use locus_core::{SessionEvent, SessionStatus};
+use locus_graph::LocusGraphClient;
+use locus_llms::Provider;
+use locus_toolbus::{
+    task_tool_definition, Permission, Tool, ToolBus, ToolContext, ToolResult, TASK_TOOL_DESCRIPTION,
+};
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+
+use super::tools::sub_agent_config;
+use super::Runtime;
+use crate::agent_profiles::AgentProfiles;
+use crate::config::RuntimeConfig;
+
+/// Runs each call as a sub-agent on the bus it is registered on.
+pub struct TaskTool {
+    /// Weak so the tool does not keep its own bus alive.
+    toolbus: Weak<ToolBus>,
+    locus_graph: Arc<LocusGraphClient>,
+    llm_client: Arc<dyn Provider>,
+    config: RuntimeConfig,
+    agent_profiles: AgentProfiles,
+}
+
+impl TaskTool {
+    /// A task tool sharing `runtime`'s bus, clients and model settings.
+    pub fn new(runtime: &Runtime) -> Self {
+        Self {
+            toolbus: Arc::downgrade(&runtime.toolbus),
+            locus_graph: Arc::clone(&runtime.locus_graph),
+            llm_client: Arc::clone(&runtime.llm_client),
+            config: runtime.config.clone(),
+            agent_profiles: runtime.agent_profiles.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TaskTool {
+    fn name(&self) -> &'static str {
+        "task"
+    }
+
+    fn description(&self) -> &'static str {
+        TASK_TOOL_DESCRIPTION
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        task_tool_definition().parameters
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let prompt = args
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'prompt' argument"))?
+            .to_string();
+        let description = args
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("sub-task")
+            .to_string();
+        let profile = self
+            .agent_profiles
+            .requested(&args)
+            .map_err(|message| anyhow::anyhow!(message))?;
+        let toolbus = self
+            .toolbus
+            .upgrade()
+            .ok_or_else(|| anyhow::anyhow!("The tool bus was shut down"))?;
+        let start = Instant::now();
+
+        // Status lines become progress; everything else is the sub-agent's own business.
+        let (event_tx, mut event_rx) = mpsc::channel::<SessionEvent>(100);
+        let progress = ctx.progress.clone();
+        let forward = tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                if let SessionEvent::Status { message } = event {
+                    progress.report(message);
+                }
+            }
+        });
+
+        let mut sub_runtime = Runtime::new_with_shared(
+            sub_agent_config(&self.config, profile.map(|(_, profile)| profile)),
+            event_tx,
+            toolbus,
+            Arc::clone(&self.locus_graph),
+            Arc::clone(&self.llm_client),
+        )
+        .await?;
+        if let Some((name, profile)) = profile {
+            sub_runtime.restrict_to_profile(name, profile);
+        }
+        ctx.report(format!("Sub-agent: {}", description));
+        let status = Box::pin(sub_runtime.run(prompt, Some(ctx.cancel.child_token()))).await?;
+        if status == SessionStatus::Cancelled {
+            anyhow::bail!("Sub-agent cancelled: {}", description);
+        }
+        let report = sub_runtime
+            .sub_agent_report(&status, self.config.subagent_summary_chars)
+            .await;
+        drop(sub_runtime);
+        forward.abort();
+
+        let mut output = serde_json::json!({
+            "description": description,
+            "duration_ms": start.elapsed().as_millis() as u64,
+        });
+        report.write_to(&mut output);
+        Ok(output)
+    }
+}
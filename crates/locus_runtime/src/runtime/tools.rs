@@ -1,30 +1,52 @@
-//! Tool call execution and sub-agent task spawning.
+//! Tool call execution, plan mode, agent profile and user hook refusals,
+//! guardrail enforcement and the sub-agent scheduler for `task` calls.
 
-use std::sync::Arc;
-use std::time::Instant;
+use std::collections::HashSet;
+use std::path::{Component, Path};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use locus_core::{ContentBlock, Role, SessionEvent, ToolResultData, ToolUse};
+use futures::StreamExt;
+use locus_core::{
+    ContentBlock, Role, SessionEvent, SessionStatus, SubAgentProgress, SubAgentState,
+    ToolResultData, ToolUse,
+};
+use locus_graph::hooks::anchor_to_files;
 use locus_graph::LocusGraphClient;
 use locus_llms::Provider;
-use locus_toolbus::ToolBus;
+use locus_toolbus::{Permission, ToolBus, WriteSink};
 use locusgraph_observability::{agent_span, record_duration, record_error};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
+use crate::agent_profiles::{AgentProfile, AgentProfiles};
 use crate::config::RuntimeConfig;
 use crate::error::RuntimeError;
+use crate::guardrails::{TurnEdits, ViolationAction};
+use crate::hooks;
 use crate::memory;
 use crate::tool_handler;
 
+use super::admission::{self, Admission};
 use super::Runtime;
 
+/// Files whose memories are added to recall, see [Runtime::touched_files].
+const MAX_TOUCHED_FILES: usize = 5;
+
 impl Runtime {
     /// Execute a list of tool calls.
     /// Task tools run in parallel; all others run sequentially.
+    ///
+    /// When `cancel` fires, the running call is stopped and the calls after it
+    /// are answered as cancelled without running; once every call has a result
+    /// in the session this returns `Err(RuntimeError::Cancelled)`.
     pub(crate) async fn execute_tool_calls(
         &mut self,
         tool_uses: Vec<ToolUse>,
+        cancel: Option<CancellationToken>,
     ) -> Result<(), RuntimeError> {
+        let cancel = cancel.unwrap_or_default();
         let span = tracing::info_span!(
             "runtime.execute_tool_calls",
             session.id = %self.session.id.as_str(),
@@ -36,38 +58,66 @@ impl Runtime {
         let mut task_tools = Vec::new();
         let mut regular_tools = Vec::new();
         for tool_use in tool_uses {
-            if tool_use.name == "task" {
+            // Plan mode and profiles without `task` refuse it along with the
+            // other regular calls they refuse.
+            if tool_use.name == "task" && !self.plan_mode && self.profile_allows("task") {
                 task_tools.push(tool_use);
             } else {
                 regular_tools.push(tool_use);
             }
         }
 
-        let mut results = Vec::with_capacity(task_tools.len() + regular_tools.len());
+        // Call order of `results`, for crash recovery checkpoints.
+        let order: Vec<ToolUse> = regular_tools.iter().chain(&task_tools).cloned().collect();
+        let mut results = Vec::with_capacity(order.len());
+        let mut edits = TurnEdits::new();
+        let mut editing_calls = HashSet::new();
 
         // Execute regular tools sequentially
         for tool_use in regular_tools {
-            if tool_handler::requires_confirmation(&tool_use) {
-                warn!(
-                    "Tool {} requires confirmation - auto-approving for now",
-                    tool_use.name
-                );
+            if cancel.is_cancelled() {
+                results.push((tool_use, cancelled_result()));
+                continue;
+            }
+            let refused = self.refuse_call(&tool_use).await;
+            let ran = refused.is_none();
+            if let Some(path) = self.tracked_edit_path(&tool_use).filter(|_| ran) {
+                edits.snapshot(self.toolbus.repo_root(), path);
+                editing_calls.insert(tool_use.id.clone());
             }
 
             let session_id = self.session.id.as_str().to_string();
             let turn_id = self.turn_id();
             let seq = self.next_seq();
-            let result = match tool_handler::handle_tool_call(
-                tool_use.clone(),
-                &self.toolbus,
-                Arc::clone(&self.locus_graph),
-                &self.event_tx,
-                session_id,
-                turn_id,
-                seq,
-            )
-            .await
-            {
+            // Files the tool reports only while it runs (`lsp_rename`, `format_code`)
+            let (writes_tx, mut writes_rx) = mpsc::unbounded_channel();
+            let call = match refused {
+                Some(refused) => Ok(refused),
+                None => {
+                    tool_handler::handle_tool_call(
+                        tool_use.clone(),
+                        self.secrets
+                            .restore_file_content(&tool_use.name, &tool_use.args),
+                        WriteSink::new(writes_tx),
+                        &self.toolbus,
+                        Arc::clone(&self.locus_graph),
+                        &self.event_tx,
+                        &cancel,
+                        session_id,
+                        turn_id,
+                        seq,
+                    )
+                    .await
+                }
+            };
+            while let Ok((path, content)) = writes_rx.try_recv() {
+                if self.tracks_edits() {
+                    edits.record(self.toolbus.repo_root(), &path, content);
+                    editing_calls.insert(tool_use.id.clone());
+                }
+            }
+            let files: Vec<String> = self.touched_file(&tool_use).into_iter().collect();
+            let mut result = match call {
                 Ok(r) => r,
                 Err(e) => {
                     let error_event = memory::build_error_event(
@@ -76,11 +126,14 @@ impl Runtime {
                         &tool_use.name,
                         &e.to_string(),
                     );
-                    self.buffer_event(error_event);
+                    self.buffer_event(anchor_to_files(error_event, &files));
                     record_error(&e);
                     return Err(e);
                 }
             };
+            if ran {
+                self.run_post_tool_hooks(&tool_use, &mut result).await;
+            }
 
             let action_event = memory::build_action_event(
                 &self.event_ctx("action", seq),
@@ -91,7 +144,7 @@ impl Runtime {
                 result.is_error,
                 result.duration_ms,
             );
-            self.buffer_event(action_event);
+            self.buffer_event(anchor_to_files(action_event, &files));
 
             if result.is_error {
                 let err_seq = self.next_seq();
@@ -101,70 +154,542 @@ impl Runtime {
                     &tool_use.name,
                     &result.output.to_string(),
                 );
-                self.buffer_event(error_event);
+                self.buffer_event(anchor_to_files(error_event, &files));
             }
 
             results.push((tool_use, result));
+            self.save_run_checkpoint(&order[results.len()..], &results)
+                .await;
         }
 
-        // Execute task tools (sequentially; parallel spawn would require Runtime to be Send)
-        for tool_use in task_tools {
-            info!(
-                "Running task: {}",
-                tool_use
-                    .args
-                    .get("description")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("sub-task")
-            );
-
-            let session_id = self.session.id.as_str().to_string();
-            let turn_id = self.turn_id();
-            let seq = self.next_seq();
-            let result = match Self::run_task_tool(
-                tool_use.clone(),
-                &self.toolbus,
-                Arc::clone(&self.locus_graph),
-                Arc::clone(&self.llm_client),
-                &self.config,
-                &self.event_tx,
-                session_id,
-                turn_id,
-                seq,
+        // Execute task tools as sub-agents, up to `max_parallel_subagents` at a
+        // time. They run in this task (Runtime is not Send), and results come
+        // back in call order.
+        if !task_tools.is_empty() {
+            let scheduler = SubAgentScheduler::new(self, &task_tools);
+            scheduler.board.publish().await;
+            let mut runs = futures::stream::iter(
+                task_tools
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, tool_use)| scheduler.run(index, tool_use, &cancel)),
             )
-            .await
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    record_error(&e);
-                    return Err(e);
-                }
-            };
+            .buffered(scheduler.max_parallel);
+            while let Some((tool_use, result)) = runs.next().await {
+                let result = match result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        record_error(&e);
+                        return Err(e);
+                    }
+                };
+                results.push((tool_use, result));
+                self.save_run_checkpoint(&order[results.len()..], &results)
+                    .await;
+            }
+        }
 
-            results.push((tool_use, result));
+        if !edits.is_empty() {
+            if !self.guardrails.is_empty() {
+                self.enforce_guardrails(&edits, &editing_calls, &mut results)
+                    .await;
+            }
+            self.record_file_changes(&edits).await;
         }
 
         // Create tool result turn and add to session
         if !results.is_empty() {
             let tool_turn = tool_handler::create_tool_result_turn(&results);
             self.session.add_turn(tool_turn);
+            self.save_run_checkpoint(&[], &[]).await;
         }
 
+        if cancel.is_cancelled() {
+            return Err(RuntimeError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Answer the calls of a turn interrupted by a crash (see [Self::recover]):
+    /// `finished` keep their results, read-only `pending` calls run again and
+    /// the rest are answered as interrupted. Adds the tool result turn.
+    pub(crate) async fn finish_interrupted_calls(
+        &mut self,
+        pending: Vec<ToolUse>,
+        finished: Vec<(ToolUse, ToolResultData)>,
+        cancel: Option<CancellationToken>,
+    ) -> Result<(), RuntimeError> {
+        if pending.is_empty() && finished.is_empty() {
+            return Ok(());
+        }
+        let cancel = cancel.unwrap_or_default();
+        let mut results = finished;
+        for tool_use in pending {
+            let result = if cancel.is_cancelled() {
+                cancelled_result()
+            } else if self.toolbus.permission_of(&tool_use.name) == Some(Permission::Read) {
+                info!("Re-running interrupted read-only call {}", tool_use.name);
+                let session_id = self.session.id.as_str().to_string();
+                let turn_id = self.turn_id();
+                let seq = self.next_seq();
+                tool_handler::handle_tool_call(
+                    tool_use.clone(),
+                    self.secrets
+                        .restore_file_content(&tool_use.name, &tool_use.args),
+                    WriteSink::default(),
+                    &self.toolbus,
+                    Arc::clone(&self.locus_graph),
+                    &self.event_tx,
+                    &cancel,
+                    session_id,
+                    turn_id,
+                    seq,
+                )
+                .await?
+            } else {
+                warn!(
+                    "Not re-running interrupted call {} ({}): it may have had effects",
+                    tool_use.name, tool_use.id
+                );
+                interrupted_result()
+            };
+            results.push((tool_use, result));
+        }
+        self.session
+            .add_turn(tool_handler::create_tool_result_turn(&results));
+        self.save_run_checkpoint(&[], &[]).await;
+        if cancel.is_cancelled() {
+            return Err(RuntimeError::Cancelled);
+        }
         Ok(())
     }
 
-    /// Run a single task tool by spawning a sub-agent runtime.
-    #[allow(clippy::too_many_arguments)]
-    async fn run_task_tool(
+    /// Whether guardrails or the file change log (`locus blame`, `:rewind`,
+    /// diff review) need the content of edited files from before the edit.
+    fn tracks_edits(&self) -> bool {
+        !self.guardrails.is_empty() || self.config.session_log
+    }
+
+    /// Path a file-writing tool call is about to change, when edits are tracked.
+    /// Files a tool finds it has to change only while it runs are reported
+    /// through its [WriteSink] instead.
+    fn tracked_edit_path<'a>(&self, tool_use: &'a ToolUse) -> Option<&'a str> {
+        if !self.tracks_edits()
+            || self.toolbus.permission_of(&tool_use.name) != Some(Permission::Write)
+        {
+            return None;
+        }
+        tool_use.args.get("path").and_then(|p| p.as_str())
+    }
+
+    /// The repo-relative file `tool_use` read or changed, when its `path`
+    /// argument names one in the repo.
+    fn touched_file(&self, tool_use: &ToolUse) -> Option<String> {
+        let path = Path::new(tool_use.args.get("path")?.as_str()?);
+        let repo_root = self.toolbus.repo_root();
+        let relative = if path.is_absolute() {
+            path.strip_prefix(repo_root).ok()?
+        } else {
+            path
+        };
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+            || !repo_root.join(relative).is_file()
+        {
+            return None;
+        }
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        Some(relative.trim_start_matches("./").to_string())
+    }
+
+    /// Files the tool calls since the last user message read or changed, most
+    /// recent first, so recall can add the memories anchored to them.
+    pub(crate) fn touched_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = Vec::new();
+        for turn in self.session.turns.iter().rev() {
+            if turn.role == Role::User {
+                break;
+            }
+            for block in turn.blocks.iter().rev() {
+                if let ContentBlock::ToolUse { tool_use } = block {
+                    if let Some(file) = self.touched_file(tool_use) {
+                        if !files.contains(&file) {
+                            files.push(file);
+                        }
+                    }
+                }
+            }
+        }
+        files.truncate(MAX_TOUCHED_FILES);
+        files
+    }
+
+    /// Record the files this round of tool calls changed, with the session, turn
+    /// and prompt behind them, so `locus blame` can attribute their lines.
+    async fn record_file_changes(&self, edits: &TurnEdits) {
+        if !self.config.session_log {
+            return;
+        }
+        let repo_root = self.config.repo_root.clone();
+        let prompt = self.turn_prompt();
+        let changes: Vec<_> = edits
+            .contents(self.toolbus.repo_root())
+            .into_iter()
+            .map(|(path, before, after)| locus_core::db::FileChange {
+                session_id: self.session.id.as_str().to_string(),
+                session_slug: self.session_slug.clone(),
+                turn: self.turn_sequence,
+                ts: chrono::Utc::now().timestamp(),
+                path: path.to_string_lossy().replace('\\', "/"),
+                prompt: prompt.clone(),
+                before,
+                after,
+            })
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+        let recorded = tokio::task::spawn_blocking(move || {
+            changes
+                .iter()
+                .try_for_each(|change| locus_core::db::record_file_change(&repo_root, change))
+        })
+        .await;
+        match recorded {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to record file changes: {}", e),
+            Err(e) => warn!("File change task failed: {}", e),
+        }
+    }
+
+    /// Text of the user message that started this turn, cut to `PROMPT_CHARS`.
+    fn turn_prompt(&self) -> String {
+        const PROMPT_CHARS: usize = 200;
+        let text = self
+            .session
+            .turns
+            .iter()
+            .rev()
+            .find(|turn| turn.role == Role::User)
+            .and_then(|turn| {
+                turn.blocks.iter().find_map(|block| match block {
+                    ContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+            })
+            .unwrap_or_default()
+            .trim();
+        if text.chars().count() <= PROMPT_CHARS {
+            return text.to_string();
+        }
+        let mut cut: String = text.chars().take(PROMPT_CHARS).collect();
+        cut.push('…');
+        cut
+    }
+
+    /// Why a call must not run: plan mode only allows read-only tools, a
+    /// sub-agent only its profile's tools, and a failing `pre_tool` hook blocks
+    /// the call. When refused, the call is shown as
+    /// started and failed in chat and the returned result replaces running it.
+    async fn refuse_call(&self, tool_use: &ToolUse) -> Option<ToolResultData> {
+        let result = if self.plan_mode && !self.allowed_in_plan_mode(&tool_use.name) {
+            warn!("{} refused in plan mode", tool_use.name);
+            ToolResultData::error(
+                serde_json::json!({
+                    "error": format!(
+                        "Plan mode is read-only: {} is not available until the plan is carried out",
+                        tool_use.name
+                    ),
+                }),
+                0,
+            )
+        } else if !self.profile_allows(&tool_use.name) {
+            let profile = self.agent_profile.as_deref().unwrap_or_default();
+            warn!("{} refused for the {} agent", tool_use.name, profile);
+            ToolResultData::error(
+                serde_json::json!({
+                    "error": format!(
+                        "{} is not available to the {} agent",
+                        tool_use.name, profile
+                    ),
+                }),
+                0,
+            )
+        } else {
+            self.run_pre_tool_hooks(tool_use).await?
+        };
+        let _ = self
+            .event_tx
+            .send(SessionEvent::tool_start(tool_use.clone()))
+            .await;
+        let _ = self
+            .event_tx
+            .send(SessionEvent::tool_done(tool_use.id.clone(), result.clone()))
+            .await;
+        Some(result)
+    }
+
+    /// Run the `pre_tool` hooks for a call; the result of the first that fails
+    /// replaces running it.
+    async fn run_pre_tool_hooks(&self, tool_use: &ToolUse) -> Option<ToolResultData> {
+        if self.hooks.pre_tool.is_empty() {
+            return None;
+        }
+        let runs = self
+            .hooks
+            .before_tool(self.toolbus.repo_root(), &tool_use.name, &tool_use.args)
+            .await;
+        let blocker = runs.into_iter().find(|run| !run.success)?;
+        warn!("{} blocked by {}", tool_use.name, blocker.describe());
+        Some(ToolResultData::error(
+            serde_json::json!({
+                "error": format!("Blocked by hook `{}` ({})", blocker.label, blocker.status),
+                "hook_output": blocker.output,
+            }),
+            0,
+        ))
+    }
+
+    /// Run the `post_tool` hooks for a finished call: failures are reported to
+    /// the user and output from `feed_back` hooks is added to the result.
+    async fn run_post_tool_hooks(&self, tool_use: &ToolUse, result: &mut ToolResultData) {
+        if self.hooks.post_tool.is_empty() {
+            return;
+        }
+        let runs = self
+            .hooks
+            .after_tool(
+                self.toolbus.repo_root(),
+                &tool_use.name,
+                &tool_use.args,
+                &result.output,
+                result.is_error,
+            )
+            .await;
+        for run in runs.iter().filter(|run| !run.success) {
+            warn!("After {}: {}", tool_use.name, run.describe());
+            let _ = self
+                .event_tx
+                .send(SessionEvent::status(run.describe()))
+                .await;
+        }
+        let feedback = hooks::feedback(&runs);
+        if feedback.is_empty() {
+            return;
+        }
+        match result.output.as_object_mut() {
+            Some(output) => {
+                output.insert("hooks".to_string(), feedback.into());
+            }
+            None => {
+                result.output = serde_json::json!({
+                    "result": result.output.take(),
+                    "hooks": feedback,
+                });
+            }
+        }
+    }
+
+    /// Check this turn's file edits against `.locus/guardrails.toml`.
+    ///
+    /// On violation the edit results become errors that explain why, the user
+    /// gets a guardrail event in chat, and the edits are either reverted or kept
+    /// with the run stopped (session left `Waiting` for the user).
+    async fn enforce_guardrails(
+        &mut self,
+        edits: &TurnEdits,
+        editing_calls: &HashSet<String>,
+        results: &mut [(ToolUse, ToolResultData)],
+    ) {
+        let repo_root = self.toolbus.repo_root().clone();
+        let violations = self
+            .guardrails
+            .evaluate(&repo_root, edits, self.toolbus.execution_backend())
+            .await;
+        if violations.is_empty() {
+            return;
+        }
+        // Taken before a revert undoes the changes
+        let files: Vec<String> = edits
+            .changes(&repo_root)
+            .into_iter()
+            .map(|(path, _)| path.to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        let mut reverted = self.guardrails.on_violation == ViolationAction::Revert;
+        if reverted {
+            if let Err(e) = edits.revert(&repo_root) {
+                // Could not roll back; stop instead of continuing on top of the violation.
+                warn!("Guardrail revert failed: {}", e);
+                reverted = false;
+            }
+        }
+        let outcome = if reverted {
+            "this turn's edits were reverted"
+        } else {
+            "the run was stopped for the user to review"
+        };
+        let message = format!(
+            "Guardrail violation ({}): {}",
+            outcome,
+            violations.join("; ")
+        );
+        warn!("{}", message);
+
+        for (tool_use, result) in results.iter_mut() {
+            if editing_calls.contains(&tool_use.id) {
+                result.output = serde_json::json!({
+                    "error": message,
+                    "guardrail_violations": violations,
+                    "reverted": reverted,
+                    "result": result.output.take(),
+                });
+                result.is_error = true;
+            }
+        }
+
+        let seq = self.next_seq();
+        let error_event = memory::build_error_event(
+            &self.event_ctx("error", seq),
+            &self.turn_ctx(),
+            "guardrails",
+            &message,
+        );
+        self.buffer_event(anchor_to_files(error_event, &files));
+
+        if !reverted {
+            self.session.set_status(SessionStatus::Waiting);
+        }
+        let _ = self
+            .event_tx
+            .send(SessionEvent::guardrail_violation(violations, reverted))
+            .await;
+    }
+}
+
+impl Runtime {
+    /// Limit this sub-agent to `profile`'s tools: the model is offered only
+    /// those, and calls to any other are refused.
+    pub(crate) fn restrict_to_profile(&mut self, name: &str, profile: &AgentProfile) {
+        let mut candidates = std::mem::take(&mut self.active_tools);
+        for tool in self.toolbus.list_tools() {
+            if !candidates.iter().any(|t| t.name == tool.name) {
+                candidates.push(tool);
+            }
+        }
+        let allowed: Vec<_> = candidates
+            .into_iter()
+            .filter(|tool| {
+                profile.tools.contains(&tool.name)
+                    || (profile.read_only && self.allowed_in_plan_mode(&tool.name))
+            })
+            .collect();
+        self.profile_tools = allowed.iter().map(|tool| tool.name.clone()).collect();
+        self.active_tools = allowed;
+        self.agent_profile = Some(name.to_string());
+    }
+
+    /// Whether this runtime's profile, if it has one, lets it call `tool_name`.
+    fn profile_allows(&self, tool_name: &str) -> bool {
+        self.agent_profile.is_none() || self.profile_tools.contains(tool_name)
+    }
+}
+
+/// Runs the `task` calls of a turn as sub-agents: at most `max_parallel` at a
+/// time (the rest wait in call order), each stopped when it runs past
+/// `timeout` counted from its admission, with every change published on the
+/// [SubAgentBoard].
+struct SubAgentScheduler {
+    max_parallel: usize,
+    timeout: Option<Duration>,
+    board: SubAgentBoard,
+    toolbus: Arc<ToolBus>,
+    locus_graph: Arc<LocusGraphClient>,
+    llm_client: Arc<dyn Provider>,
+    config: RuntimeConfig,
+    agent_profiles: AgentProfiles,
+    event_tx: mpsc::Sender<SessionEvent>,
+}
+
+impl SubAgentScheduler {
+    fn new(runtime: &Runtime, tasks: &[ToolUse]) -> Self {
+        let config = &runtime.config;
+        Self {
+            max_parallel: config.max_parallel_subagents.max(1),
+            timeout: (config.subagent_timeout_secs > 0)
+                .then(|| Duration::from_secs(config.subagent_timeout_secs)),
+            board: SubAgentBoard::new(tasks, runtime.event_tx.clone()),
+            toolbus: Arc::clone(&runtime.toolbus),
+            locus_graph: Arc::clone(&runtime.locus_graph),
+            llm_client: Arc::clone(&runtime.llm_client),
+            config: config.clone(),
+            agent_profiles: runtime.agent_profiles.clone(),
+            event_tx: runtime.event_tx.clone(),
+        }
+    }
+
+    /// Run the sub-agent for the `index`th task call once a slot frees up.
+    async fn run(
+        &self,
+        index: usize,
+        tool_use: ToolUse,
+        cancel: &CancellationToken,
+    ) -> (ToolUse, Result<ToolResultData, RuntimeError>) {
+        if cancel.is_cancelled() {
+            self.board.set_state(index, SubAgentState::Cancelled).await;
+            return (tool_use, Ok(cancelled_result()));
+        }
+        self.board.set_state(index, SubAgentState::Running).await;
+        let result = self.run_task(tool_use.clone(), index, cancel).await;
+        let state = match &result {
+            Ok(r) if !r.is_error => SubAgentState::Done,
+            Ok(r) if r.output.get("cancelled").is_some() => SubAgentState::Cancelled,
+            Ok(r) if r.output.get("timed_out").is_some() => SubAgentState::TimedOut,
+            _ => SubAgentState::Failed,
+        };
+        self.board.set_state(index, state).await;
+        (tool_use, result)
+    }
+
+    /// Result for a sub-agent stopped at its time limit; dropping its run
+    /// dropped its runtime and killed the commands it had running.
+    async fn timed_out(&self, tool: &ToolUse, limit: Duration) -> ToolResultData {
+        warn!(
+            "Sub-agent {} ran past {}s; stopped",
+            tool.id,
+            limit.as_secs()
+        );
+        let result = ToolResultData::error(
+            serde_json::json!({
+                "error": format!("Sub-agent ran past its {}s limit and was stopped", limit.as_secs()),
+                "description": task_description(tool),
+                "timed_out": true,
+            }),
+            limit.as_millis() as u64,
+        );
+        let _ = self
+            .event_tx
+            .send(SessionEvent::tool_timed_out(
+                tool.id.clone(),
+                "task",
+                limit.as_secs(),
+            ))
+            .await;
+        let _ = self
+            .event_tx
+            .send(SessionEvent::tool_done(tool.id.clone(), result.clone()))
+            .await;
+        result
+    }
+
+    /// Run a single task tool by spawning a sub-agent runtime, once the
+    /// provider's rate limits admit it.
+    async fn run_task(
+        &self,
         tool: ToolUse,
-        toolbus: &Arc<ToolBus>,
-        locus_graph: Arc<LocusGraphClient>,
-        llm_client: Arc<dyn Provider>,
-        config: &RuntimeConfig,
-        event_tx: &mpsc::Sender<SessionEvent>,
-        _session_id: String,
-        _turn_id: String,
-        _seq: u32,
+        index: usize,
+        cancel: &CancellationToken,
     ) -> Result<ToolResultData, RuntimeError> {
         let span = agent_span!("task", "run_task_tool");
         let _guard = span.enter();
@@ -180,72 +705,206 @@ impl Runtime {
             })?
             .to_string();
 
-        let description = tool
-            .args
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("sub-task")
-            .to_string();
+        let description = task_description(&tool);
+        info!("Running task: {}", description);
 
+        let event_tx = &self.event_tx;
         let _ = event_tx.send(SessionEvent::tool_start(tool.clone())).await;
-        let _ = event_tx
-            .send(SessionEvent::status(format!("Sub-agent: {}", description)))
-            .await;
 
+        let profile = match self.agent_profiles.requested(&tool.args) {
+            Ok(profile) => profile,
+            Err(message) => {
+                warn!("Task '{}': {}", description, message);
+                let result = ToolResultData::error(
+                    serde_json::json!({ "error": message, "description": description }),
+                    0,
+                );
+                let _ = event_tx
+                    .send(SessionEvent::tool_done(tool.id.clone(), result.clone()))
+                    .await;
+                return Ok(result);
+            }
+        };
+
+        // Hold the sub-agent back while the provider's rate-limit budget is low
+        if let Admission::Throttle { wait, reason } =
+            admission::admit(self.llm_client.rate_limit().as_ref())
+        {
+            info!(
+                "Throttling sub-agent '{}' for {}ms: {}",
+                description,
+                wait.as_millis(),
+                reason
+            );
+            self.board
+                .update(index, |agent| {
+                    agent.state = SubAgentState::Throttled;
+                    agent.activity = reason.clone();
+                })
+                .await;
+            let _ = event_tx
+                .send(SessionEvent::throttled(
+                    &tool.id,
+                    wait.as_millis() as u64,
+                    reason,
+                ))
+                .await;
+            let admitted = tokio::select! {
+                _ = tokio::time::sleep(wait) => true,
+                _ = cancel.cancelled() => false,
+            };
+            if !admitted {
+                let result = cancelled_result();
+                let _ = event_tx
+                    .send(SessionEvent::tool_done(tool.id.clone(), result.clone()))
+                    .await;
+                return Ok(result);
+            }
+            self.board
+                .update(index, |agent| {
+                    agent.state = SubAgentState::Running;
+                    agent.activity.clear();
+                })
+                .await;
+        }
+
+        // Time spent throttled doesn't count against the limit
+        let run = self.run_admitted(&tool, index, prompt, profile, start, cancel);
+        match self.timeout {
+            Some(limit) => match tokio::time::timeout(limit, run).await {
+                Ok(result) => result,
+                Err(_) => Ok(self.timed_out(&tool, limit).await),
+            },
+            None => run.await,
+        }
+    }
+
+    /// Run the sub-agent for `tool` on `prompt` and answer for it.
+    async fn run_admitted(
+        &self,
+        tool: &ToolUse,
+        index: usize,
+        prompt: String,
+        profile: Option<(&str, &AgentProfile)>,
+        start: Instant,
+        cancel: &CancellationToken,
+    ) -> Result<ToolResultData, RuntimeError> {
+        let description = task_description(tool);
+        let event_tx = &self.event_tx;
+
+        // The sub-agent's own turns and streamed text stay out of the chat; its
+        // status lines, tool calls, last line of text and token use go on the
+        // board, and its text, tool calls and errors to its transcript.
         let (sub_tx, mut sub_rx) = mpsc::channel::<SessionEvent>(100);
         let fwd_tx = event_tx.clone();
+        let board = self.board.clone();
+        let agent_id = tool.id.clone();
         let fwd_handle = tokio::spawn(async move {
+            let mut line = String::new();
             while let Some(event) = sub_rx.recv().await {
-                let _ = fwd_tx.send(event).await;
+                match event {
+                    SessionEvent::TurnStart { .. }
+                    | SessionEvent::ThinkingDelta { .. }
+                    | SessionEvent::TurnEnd
+                    | SessionEvent::SessionEnd { .. } => {}
+                    SessionEvent::TextDelta { text } => {
+                        if let Some(last) = completed_line(&mut line, &text) {
+                            board.update(index, |agent| agent.last_line = last).await;
+                        }
+                        let event = SessionEvent::text_delta(text);
+                        let _ = fwd_tx
+                            .send(SessionEvent::sub_agent_output(&agent_id, event))
+                            .await;
+                    }
+                    SessionEvent::TokenUsage {
+                        total_prompt_tokens,
+                        total_completion_tokens,
+                        ..
+                    } => {
+                        let tokens = total_prompt_tokens + total_completion_tokens;
+                        board.update(index, |agent| agent.tokens = tokens).await;
+                    }
+                    SessionEvent::Status { message } => board.set_activity(index, message).await,
+                    event => {
+                        match &event {
+                            SessionEvent::ToolStart { tool_use } => {
+                                let name = tool_use.name.clone();
+                                board
+                                    .update(index, |agent| {
+                                        agent.activity = name.clone();
+                                        agent.tool = Some(name);
+                                    })
+                                    .await;
+                            }
+                            SessionEvent::ToolDone { .. } => {
+                                board.update(index, |agent| agent.tool = None).await;
+                            }
+                            _ => {}
+                        }
+                        if matches!(
+                            event,
+                            SessionEvent::ToolStart { .. }
+                                | SessionEvent::ToolDone { .. }
+                                | SessionEvent::Error { .. }
+                        ) {
+                            let _ = fwd_tx
+                                .send(SessionEvent::sub_agent_output(&agent_id, event.clone()))
+                                .await;
+                        }
+                        let _ = fwd_tx.send(event).await;
+                    }
+                }
+            }
+            let rest = line.trim();
+            if !rest.is_empty() {
+                let last = rest.to_string();
+                board.update(index, |agent| agent.last_line = last).await;
             }
         });
 
-        let sub_config = RuntimeConfig::new(config.repo_root.clone())
-            .with_model(&config.model)
-            .with_provider(config.provider)
-            .with_max_turns(30)
-            .with_sandbox(config.sandbox.clone());
-
         let mut sub_runtime = Runtime::new_with_shared(
-            sub_config,
+            sub_agent_config(&self.config, profile.map(|(_, profile)| profile)),
             sub_tx,
-            Arc::clone(toolbus),
-            locus_graph.clone(),
-            llm_client,
+            Arc::clone(&self.toolbus),
+            Arc::clone(&self.locus_graph),
+            Arc::clone(&self.llm_client),
         )
         .await?;
+        if let Some((name, profile)) = profile {
+            sub_runtime.restrict_to_profile(name, profile);
+        }
 
-        let status = Box::pin(sub_runtime.run(prompt, None)).await?;
-        fwd_handle.abort();
-
-        let summary = sub_runtime
-            .session
-            .turns
-            .iter()
-            .rev()
-            .find(|t| t.role == Role::Assistant)
-            .and_then(|t| {
-                t.blocks.iter().find_map(|b| {
-                    if let ContentBlock::Text { text } = b {
-                        Some(text.clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-            .unwrap_or_else(|| format!("Task completed: {:?}", status));
+        let status = Box::pin(sub_runtime.run(prompt, Some(cancel.child_token()))).await?;
+        let report = sub_runtime
+            .sub_agent_report(&status, self.config.subagent_summary_chars)
+            .await;
+        // Dropping the sub-agent closes its channel; forward what it sent last
+        // (such as a refused call) before answering for it.
+        drop(sub_runtime);
+        let _ = fwd_handle.await;
 
         let duration = start.elapsed();
         let duration_ms = duration.as_millis() as u64;
         record_duration("task.duration_ms", duration);
 
-        let output = serde_json::json!({
+        let mut output = serde_json::json!({
             "description": description,
-            "summary": summary,
             "duration_ms": duration_ms,
         });
+        report.write_to(&mut output);
+        if let Some((name, _)) = profile {
+            output["profile"] = serde_json::json!(name);
+        }
 
-        let tool_result = ToolResultData::success(output, duration_ms);
+        // A cancelled sub-agent did not finish; say so rather than pass off
+        // its last words as the answer.
+        let tool_result = if status == SessionStatus::Cancelled {
+            output["error"] = serde_json::json!("Cancelled by the user");
+            output["cancelled"] = serde_json::json!(true);
+            ToolResultData::error(output, duration_ms)
+        } else {
+            ToolResultData::success(output, duration_ms)
+        };
         let _ = event_tx
             .send(SessionEvent::tool_done(
                 tool.id.clone(),
@@ -256,3 +915,158 @@ impl Runtime {
         Ok(tool_result)
     }
 }
+
+/// Every sub-agent of a turn and where it is, shared with the tasks that
+/// forward their events; each change is sent as [SessionEvent::SubAgents].
+#[derive(Clone)]
+struct SubAgentBoard {
+    agents: Arc<Mutex<Vec<BoardEntry>>>,
+    event_tx: mpsc::Sender<SessionEvent>,
+}
+
+impl SubAgentBoard {
+    /// All of `tasks` queued.
+    fn new(tasks: &[ToolUse], event_tx: mpsc::Sender<SessionEvent>) -> Self {
+        let agents = tasks
+            .iter()
+            .map(|tool| {
+                let progress = SubAgentProgress {
+                    tool_use_id: tool.id.clone(),
+                    description: task_description(tool),
+                    state: SubAgentState::Queued,
+                    activity: String::new(),
+                    elapsed_ms: 0,
+                    tool: None,
+                    last_line: String::new(),
+                    tokens: 0,
+                };
+                BoardEntry {
+                    progress,
+                    started: None,
+                }
+            })
+            .collect();
+        Self {
+            agents: Arc::new(Mutex::new(agents)),
+            event_tx,
+        }
+    }
+
+    async fn set_state(&self, index: usize, state: SubAgentState) {
+        self.update(index, |agent| agent.state = state).await;
+    }
+
+    async fn set_activity(&self, index: usize, activity: String) {
+        self.update(index, |agent| agent.activity = activity).await;
+    }
+
+    async fn publish(&self) {
+        self.update(usize::MAX, |_| {}).await;
+    }
+
+    /// Change the `index`th sub-agent (none when out of range), then send the board.
+    async fn update(&self, index: usize, change: impl FnOnce(&mut SubAgentProgress)) {
+        let snapshot: Vec<SubAgentProgress> = {
+            let mut agents = self.agents.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(BoardEntry {
+                progress: agent,
+                started,
+            }) = agents.get_mut(index)
+            {
+                let was_running = agent.state == SubAgentState::Running;
+                change(agent);
+                if agent.state == SubAgentState::Running && started.is_none() {
+                    *started = Some(Instant::now());
+                }
+                // Keep the final time of one that just stopped.
+                if was_running && agent.state.is_finished() {
+                    agent.elapsed_ms = started.map_or(0, |at| at.elapsed().as_millis() as u64);
+                }
+            }
+            agents
+                .iter_mut()
+                .map(
+                    |BoardEntry {
+                         progress: agent,
+                         started,
+                     }| {
+                        if agent.state == SubAgentState::Running {
+                            agent.elapsed_ms =
+                                started.map_or(0, |at| at.elapsed().as_millis() as u64);
+                        }
+                        agent.clone()
+                    },
+                )
+                .collect()
+        };
+        let _ = self.event_tx.send(SessionEvent::sub_agents(snapshot)).await;
+    }
+}
+
+struct BoardEntry {
+    progress: SubAgentProgress,
+    /// When it started running.
+    started: Option<Instant>,
+}
+
+/// Add streamed `text` to the line in progress; returns the last non-empty
+/// line `text` completed, if any.
+fn completed_line(line: &mut String, text: &str) -> Option<String> {
+    line.push_str(text);
+    let end = line.rfind('\n')?;
+    let last = line[..end]
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .map(str::to_string);
+    line.drain(..=end);
+    last
+}
+
+/// The description a `task` call gave its sub-agent.
+fn task_description(tool: &ToolUse) -> String {
+    tool.args
+        .get("description")
+        .and_then(|v| v.as_str())
+        .unwrap_or("sub-task")
+        .to_string()
+}
+
+/// Configuration for a sub-agent spawned by the `task` tool, on `profile`'s
+/// model and turn limit when it names them.
+pub(crate) fn sub_agent_config(
+    config: &RuntimeConfig,
+    profile: Option<&AgentProfile>,
+) -> RuntimeConfig {
+    let model = profile
+        .map(|profile| profile.model_for(config.provider, &config.model))
+        .unwrap_or_else(|| config.model.clone());
+    let max_turns = profile.and_then(|profile| profile.max_turns).unwrap_or(30);
+    RuntimeConfig::new(config.repo_root.clone())
+        .with_model(&model)
+        .with_provider(config.provider)
+        .with_max_turns(max_turns)
+        .with_sandbox(config.sandbox.clone())
+        .with_pinned_files(config.pinned_files.clone())
+        .with_session_log(false)
+}
+
+/// Result for a call that may have changed things when the process died during it.
+fn interrupted_result() -> ToolResultData {
+    ToolResultData::error(
+        serde_json::json!({
+            "error": "Interrupted: locus stopped while this call ran, so it may have partly run. \
+                      It was not run again; check its effects before retrying.",
+            "interrupted": true
+        }),
+        0,
+    )
+}
+
+/// Result for a call skipped because the user stopped the turn before it ran.
+fn cancelled_result() -> ToolResultData {
+    ToolResultData::error(
+        serde_json::json!({ "error": "Cancelled by the user before it ran", "cancelled": true }),
+        0,
+    )
+}
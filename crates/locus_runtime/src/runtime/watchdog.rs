@@ -0,0 +1,144 @@
+//! Turn limits: a request that keeps the agent working past
+//! `max_turn_duration` or `max_tool_calls_per_turn` is stopped between steps,
+//! with a summary of what it tried, and the user is asked whether to go on —
+//! so a model looping on a failing test does not run all night.
+//!
+//! The limits are checked before each follow-up LLM call, so a single slow
+//! tool call is bounded by its own timeout rather than by this.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::time::Duration;
+
+use locus_core::{ContentBlock, Role, SessionEvent, SessionStatus, Turn};
+use tracing::warn;
+
+use super::Runtime;
+
+/// Characters of the last error quoted in the summary.
+const ERROR_CHARS: usize = 300;
+
+/// Which limit a request ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TurnLimit {
+    ToolCalls(u32),
+    Duration(Duration),
+}
+
+impl fmt::Display for TurnLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TurnLimit::ToolCalls(max) => write!(f, "its limit of {} tool calls", max),
+            TurnLimit::Duration(max) if max.as_secs() >= 60 => {
+                write!(f, "its time limit of {} minutes", max.as_secs() / 60)
+            }
+            TurnLimit::Duration(max) => write!(f, "its time limit of {} seconds", max.as_secs()),
+        }
+    }
+}
+
+impl Runtime {
+    /// The limit the current request has reached, if any.
+    pub(crate) fn turn_limit_reached(&self) -> Option<TurnLimit> {
+        if let Some(max) = self.config.max_tool_calls_per_turn {
+            if self.tool_calls_this_request() >= max as usize {
+                return Some(TurnLimit::ToolCalls(max));
+            }
+        }
+        let max = self.config.max_turn_duration?;
+        let started = self.session.run_started_at?;
+        let elapsed = (chrono::Utc::now() - started).to_std().unwrap_or_default();
+        (elapsed >= max).then_some(TurnLimit::Duration(max))
+    }
+
+    /// Stop the request at `limit`: an assistant turn sums up the calls it
+    /// made and asks whether to go on, and the session waits for the answer.
+    pub(crate) async fn stop_at_turn_limit(&mut self, limit: TurnLimit) {
+        warn!("Request stopped: reached {}", limit);
+        let summary = format!(
+            "I stopped here: this request reached {}.\n\n{}\n\nReply \"continue\" to keep going, \
+             or tell me what to do differently.",
+            limit,
+            self.attempted_summary()
+        );
+        let _ = self
+            .event_tx
+            .send(SessionEvent::turn_start(Role::Assistant))
+            .await;
+        let _ = self
+            .event_tx
+            .send(SessionEvent::text_delta(summary.clone()))
+            .await;
+        let _ = self.event_tx.send(SessionEvent::turn_end()).await;
+        self.session
+            .add_turn(Turn::assistant().with_block(ContentBlock::text(summary)));
+        self.session.set_status(SessionStatus::Waiting);
+    }
+
+    /// The turns since the last user message.
+    fn request_turns(&self) -> &[Turn] {
+        let start = self
+            .session
+            .turns
+            .iter()
+            .rposition(|turn| turn.role == Role::User)
+            .map_or(0, |i| i + 1);
+        &self.session.turns[start..]
+    }
+
+    fn tool_calls_this_request(&self) -> usize {
+        self.request_turns()
+            .iter()
+            .flat_map(|turn| &turn.blocks)
+            .filter(|block| matches!(block, ContentBlock::ToolUse { .. }))
+            .count()
+    }
+
+    /// The calls this request made, per tool with how many failed, and the
+    /// last error.
+    fn attempted_summary(&self) -> String {
+        let mut calls: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        let mut names = HashMap::new();
+        let mut last_error = None;
+        for block in self.request_turns().iter().flat_map(|turn| &turn.blocks) {
+            match block {
+                ContentBlock::ToolUse { tool_use } => {
+                    names.insert(tool_use.id.as_str(), tool_use.name.as_str());
+                    calls.entry(tool_use.name.as_str()).or_default().0 += 1;
+                }
+                ContentBlock::ToolResult { tool_result } if tool_result.is_error => {
+                    let name = tool_result.output["tool_use_id"]
+                        .as_str()
+                        .and_then(|id| names.get(id));
+                    if let Some(name) = name {
+                        calls.entry(name).or_default().1 += 1;
+                    }
+                    let error = &tool_result.output["result"]["error"];
+                    last_error = Some(
+                        error
+                            .as_str()
+                            .map_or_else(|| error.to_string(), str::to_string),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let mut lines = vec!["What I tried:".to_string()];
+        for (name, (count, failed)) in &calls {
+            let line = match failed {
+                0 => format!("- {} × {}", name, count),
+                _ => format!("- {} × {} ({} failed)", name, count, failed),
+            };
+            lines.push(line);
+        }
+        if let Some(error) = last_error {
+            let mut quoted: String = error.chars().take(ERROR_CHARS).collect();
+            if quoted.len() < error.len() {
+                quoted.push('…');
+            }
+            lines.push(format!("\nLast error: {}", quoted));
+        }
+        lines.join("\n")
+    }
+}
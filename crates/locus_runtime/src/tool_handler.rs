@@ -4,37 +4,50 @@
 //! emit events, and store results to memory.
 //!
 //! Meta-tools `tool_search`, `tool_explain`, and `task` are handled here
-//! before reaching ToolBus (they query LocusGraph or spawn sub-runtimes).
+//! before reaching ToolBus (they read the bus's tool list or spawn sub-runtimes).
 
 use std::sync::Arc;
 use std::time::Instant;
 
 use locus_core::{ContentBlock, SessionEvent, ToolResultData, ToolUse, Turn};
-use locus_graph::{ContextTypeFilter, LocusGraphClient, RetrieveOptions};
-use locus_toolbus::ToolBus;
+use locus_graph::LocusGraphClient;
+use locus_toolbus::{
+    CommandDenied, ProgressSink, ProtectedPathDenied, TaskListAction, TaskListArgs, ToolBus,
+    ToolContext, ToolInterrupted, WriteSink,
+};
 use locusgraph_observability::record_duration;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
 use crate::error::RuntimeError;
 
 /// Handle a single tool call.
 ///
-/// Meta-tools `tool_search` and `tool_explain` are handled here (LocusGraph / cached schema).
+/// Meta-tools `tool_search` and `tool_explain` are handled here (ranked search / cached schema).
 /// The `task` tool is handled in the runtime (spawns sub-agent). All others go to ToolBus.
 ///
 /// This function:
 /// 1. Emits a ToolStart event
 /// 2. Executes the tool (meta-tool or ToolBus)
-/// 3. Emits a ToolDone event
+/// 3. Emits a ToolDone event (and TaskListUpdated when task_list changed a plan)
 /// 4. Returns the result for adding to session
+///
+/// Cancelling `cancel` (the user stopping the turn) stops a running ToolBus call.
+/// `run_args` are what the ToolBus runs `tool` with (its file content with
+/// secrets restored, see [locus_core::SecretScrubber::restore_file_content]);
+/// events and results carry `tool` as the model wrote it. `writes` hears about
+/// the files the tool finds it has to change while it runs.
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_tool_call(
     tool: ToolUse,
+    run_args: serde_json::Value,
+    writes: WriteSink,
     toolbus: &Arc<ToolBus>,
-    locus_graph: Arc<LocusGraphClient>,
+    _locus_graph: Arc<LocusGraphClient>,
     event_tx: &mpsc::Sender<SessionEvent>,
-    _session_id: String,
+    cancel: &CancellationToken,
+    session_id: String,
     _turn_id: String,
     _seq: u32,
 ) -> Result<ToolResultData, RuntimeError> {
@@ -48,9 +61,7 @@ pub async fn handle_tool_call(
 
     // Handle meta-tools directly (don't go through ToolBus)
     match tool.name.as_str() {
-        "tool_search" => {
-            return handle_tool_search(&tool, Arc::clone(&locus_graph), event_tx).await
-        }
+        "tool_search" => return handle_tool_search(&tool, toolbus, event_tx).await,
         "tool_explain" => return handle_tool_explain(&tool, toolbus, event_tx).await,
         _ => {}
     }
@@ -67,13 +78,31 @@ pub async fn handle_tool_call(
         );
     }
 
-    // Execute via ToolBus
+    // Execute via ToolBus, forwarding what the tool reports while it runs
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+    let forward_tx = event_tx.clone();
+    let tool_use_id = tool.id.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Some(message) = progress_rx.recv().await {
+            let _ = forward_tx
+                .send(SessionEvent::tool_progress(tool_use_id.clone(), message))
+                .await;
+        }
+    });
     let start = Instant::now();
-    let result = toolbus.call(&tool.name, tool.args.clone()).await;
+    let ctx = ToolContext::new()
+        .with_progress(ProgressSink::new(progress_tx))
+        .with_cancel(cancel.clone())
+        .with_session(session_id)
+        .with_writes(writes);
+    let result = toolbus.call_with_context(&tool.name, run_args, ctx).await;
     let duration = start.elapsed();
+    // The sink was dropped with the call; deliver what is left before ToolDone.
+    let _ = forwarder.await;
     let duration_ms = duration.as_millis() as u64;
     record_duration("tool.duration_ms", duration);
 
+    let mut timed_out = None;
     let tool_result = match result {
         Ok((output, _duration_from_toolbus)) => {
             info!(
@@ -89,12 +118,30 @@ pub async fn handle_tool_call(
             span.record("error.message", e.to_string());
             error!("Tool {} failed: {}", tool.name, e);
 
-            // Surface error in chat so user can see and fix
-            let _ = event_tx
-                .send(SessionEvent::error(format!("{}: {}", tool.name, e)))
-                .await;
+            // Surface error in chat so user can see and fix; interrupted calls
+            // get their own event (or were stopped by the user).
+            let interrupted = e.downcast_ref::<ToolInterrupted>();
+            if let Some(ToolInterrupted::TimedOut { timeout_secs, .. }) = interrupted {
+                timed_out = Some(*timeout_secs);
+            } else if interrupted.is_none() {
+                let _ = event_tx
+                    .send(SessionEvent::error(format!("{}: {}", tool.name, e)))
+                    .await;
+            }
 
-            ToolResultData::error(serde_json::json!({ "error": e.to_string() }), duration_ms)
+            // Command and protected-path rule violations carry the matched rule,
+            // interrupted calls say why they stopped and other failures get the
+            // bus's hint (closest match, similar paths), so the model can change course.
+            let output = if let Some(denied) = e.downcast_ref::<CommandDenied>() {
+                denied.to_json()
+            } else if let Some(denied) = e.downcast_ref::<ProtectedPathDenied>() {
+                denied.to_json()
+            } else if let Some(interrupted) = interrupted {
+                interrupted.to_json()
+            } else {
+                toolbus.explain_failure(&tool.name, &tool.args, &e)
+            };
+            ToolResultData::error(output, duration_ms)
         }
     };
 
@@ -114,14 +161,55 @@ pub async fn handle_tool_call(
             tool_result.clone(),
         ))
         .await;
+    if let Some(timeout_secs) = timed_out {
+        let _ = event_tx
+            .send(SessionEvent::tool_timed_out(
+                tool.id.clone(),
+                tool.name.clone(),
+                timeout_secs,
+            ))
+            .await;
+    }
+    if !tool_result.is_error {
+        if let Some(event) = task_list_update(&tool, toolbus).await {
+            let _ = event_tx.send(event).await;
+        }
+    }
 
     Ok(tool_result)
 }
 
-/// Handle tool_search meta-tool: query LocusGraph for tools matching the user's intent.
+/// TaskListUpdated with the whole plan after a task_list call that changed it;
+/// None for other tools, read-only actions, or when the plan cannot be loaded.
+async fn task_list_update(tool: &ToolUse, toolbus: &Arc<ToolBus>) -> Option<SessionEvent> {
+    if tool.name != "task_list" {
+        return None;
+    }
+    let args: TaskListArgs = serde_json::from_value(tool.args.clone()).ok()?;
+    if matches!(args.action, TaskListAction::List | TaskListAction::Get) {
+        return None;
+    }
+    let repo_root = toolbus.repo_root().clone();
+    let plan_id = args.plan_id;
+    let loaded = {
+        let plan_id = plan_id.clone();
+        tokio::task::spawn_blocking(move || locus_core::db::tasks(&repo_root, &plan_id)).await
+    };
+    match loaded {
+        Ok(Ok(tasks)) => Some(SessionEvent::task_list_updated(plan_id, tasks)),
+        Ok(Err(e)) => {
+            warn!("Could not load plan {} after task_list: {}", plan_id, e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Handle tool_search meta-tool: rank the registered tools (MCP tools included)
+/// against the model's description of what it wants to do.
 async fn handle_tool_search(
     tool: &ToolUse,
-    locus_graph: Arc<LocusGraphClient>,
+    toolbus: &Arc<ToolBus>,
     event_tx: &mpsc::Sender<SessionEvent>,
 ) -> Result<ToolResultData, RuntimeError> {
     let start = Instant::now();
@@ -139,24 +227,13 @@ async fn handle_tool_search(
 
     let _ = event_tx.send(SessionEvent::tool_start(tool.clone())).await;
 
-    let options = RetrieveOptions::new()
-        .limit(max_results)
-        .context_type("tool", ContextTypeFilter::new())
-        .context_type("meta", ContextTypeFilter::new());
-
-    let result = locus_graph
-        .retrieve_memories(query, Some(options))
-        .await
-        .unwrap_or_else(|_| locus_graph::ContextResult {
-            memories: String::new(),
-            items_found: 0,
-            degraded: true,
-        });
+    let matches = toolbus.search_tools(query, max_results as usize).await;
 
     let duration_ms = start.elapsed().as_millis() as u64;
     let output = serde_json::json!({
-        "results": result.memories,
-        "items_found": result.items_found,
+        "query": query,
+        "count": matches.len(),
+        "tools": matches,
     });
 
     let tool_result = ToolResultData::success(output, duration_ms);
@@ -232,9 +309,12 @@ pub async fn handle_tool_calls(
         seq = seq.saturating_add(1);
         match handle_tool_call(
             tool.clone(),
+            tool.args.clone(),
+            WriteSink::default(),
             toolbus,
             Arc::clone(&locus_graph),
             event_tx,
+            &CancellationToken::new(),
             session_id.clone(),
             turn_id.clone(),
             s,
@@ -293,117 +373,9 @@ pub fn extract_tool_calls(turn: &Turn) -> Vec<ToolUse> {
         .collect()
 }
 
-/// Check if a tool call requires user confirmation.
-///
-/// Destructive operations should be confirmed before execution.
-///
-/// # Security Warning
-/// This is a "seatbelt" check only - it provides basic protection against
-/// obviously destructive commands but is NOT a security boundary. The patterns
-/// can be bypassed via:
-/// - Extra whitespace: `rm  -rf` (multiple spaces)
-/// - Pipes: `echo "rm -rf /" | sh`
-/// - Shell aliases or variable expansion
-/// - Backslash escaping: `\rm`
-///
-/// For real security, implement sandboxing (Docker, user namespace, or seccomp).
-/// Consider using a proper shell parser instead of substring matching for
-/// production use.
-pub fn requires_confirmation(tool: &ToolUse) -> bool {
-    match tool.name.as_str() {
-        "bash" => {
-            // Check for potentially destructive commands
-            if let Some(cmd) = tool.args.get("command").and_then(|c| c.as_str()) {
-                let dangerous_patterns = [
-                    "rm ",
-                    "rm -",
-                    "rmdir",
-                    "git push",
-                    "git reset --hard",
-                    "DROP",
-                    "TRUNCATE",
-                    "DELETE FROM",
-                    ":(){ :|:& };:",
-                    "mkfs",
-                    "dd if=",
-                    "> /dev/",
-                    "chmod -R 777",
-                    "chown -R",
-                ];
-
-                let cmd_lower = cmd.to_lowercase();
-                for pattern in dangerous_patterns {
-                    if cmd_lower.contains(&pattern.to_lowercase()) {
-                        return true;
-                    }
-                }
-            }
-            false
-        }
-        "edit_file" | "create_file" => {
-            // File operations on sensitive paths should be confirmed
-            if let Some(path) = tool.file_path.as_ref() {
-                let path_str = path.to_string_lossy().to_lowercase();
-                let sensitive_paths = [
-                    ".env",
-                    ".ssh",
-                    ".gnupg",
-                    "credentials",
-                    "secrets",
-                    "id_rsa",
-                    "authorized_keys",
-                ];
-
-                for sensitive in sensitive_paths {
-                    if path_str.contains(sensitive) {
-                        return true;
-                    }
-                }
-            }
-            false
-        }
-        _ => false,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
-
-    #[test]
-    fn test_requires_confirmation_bash_rm() {
-        let tool = ToolUse::new(
-            "t1",
-            "bash",
-            serde_json::json!({ "command": "rm -rf /important" }),
-        );
-
-        assert!(requires_confirmation(&tool));
-    }
-
-    #[test]
-    fn test_requires_confirmation_bash_safe() {
-        let tool = ToolUse::new("t1", "bash", serde_json::json!({ "command": "ls -la" }));
-
-        assert!(!requires_confirmation(&tool));
-    }
-
-    #[test]
-    fn test_requires_confirmation_env_file() {
-        let tool = ToolUse::new("t1", "edit_file", serde_json::json!({}))
-            .with_file_path(PathBuf::from("/project/.env"));
-
-        assert!(requires_confirmation(&tool));
-    }
-
-    #[test]
-    fn test_requires_confirmation_normal_file() {
-        let tool = ToolUse::new("t1", "edit_file", serde_json::json!({}))
-            .with_file_path(PathBuf::from("/project/src/main.rs"));
-
-        assert!(!requires_confirmation(&tool));
-    }
 
     #[test]
     fn test_extract_tool_calls() {
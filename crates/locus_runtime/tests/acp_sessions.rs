@@ -0,0 +1,149 @@
+//! `locus acp` end to end: an editor's JSON-RPC messages drive a runtime session.
+//!
+//! The fixture asks before writes, so the cassette's edit has to go through a
+//! `session/request_permission` round trip before it lands.
+//!
+//! Run: `cargo test -p locus-runtime --test acp_sessions`
+
+#[allow(dead_code)]
+mod harness;
+
+use std::sync::Arc;
+
+use harness::{cassette_path, offline_graph, GitRepo};
+use locus_llms::testing::MockProvider;
+use locus_runtime::{LocusAcpAgent, RuntimeConfig};
+use locus_toolbus::acp::AcpConnection;
+use serde_json::{json, Value as JsonValue};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+
+fn fixture() -> GitRepo {
+    GitRepo::new(&[
+        (".gitignore", "/target\n.locus/\n"),
+        (".locus/tools.toml", "[permissions]\nwrite = \"ask\"\n"),
+        (
+            "src/lib.rs",
+            "pub fn add(a: i32, b: i32) -> i32 {\n    a - b\n}\n",
+        ),
+    ])
+}
+
+async fn send(writer: &mut (impl AsyncWrite + Unpin), message: JsonValue) {
+    writer
+        .write_all(format!("{}\n", message).as_bytes())
+        .await
+        .expect("write message");
+}
+
+async fn next(lines: &mut Lines<impl AsyncBufRead + Unpin>) -> JsonValue {
+    let line = lines
+        .next_line()
+        .await
+        .expect("read message")
+        .expect("agent closed the connection");
+    serde_json::from_str(&line).expect("message is JSON")
+}
+
+#[tokio::test]
+async fn prompt_streams_updates_and_asks_before_editing() {
+    let repo = fixture();
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path("fix_add")));
+    let config = RuntimeConfig::new(repo.root().to_path_buf())
+        .with_model("mock-model")
+        .with_session_log(false);
+
+    let connection = AcpConnection::new();
+    let agent = Arc::new(LocusAcpAgent::new(
+        connection.client(),
+        config,
+        Arc::new(offline_graph(&repo).await),
+        provider,
+    ));
+    let (editor, agent_end) = tokio::io::duplex(256 * 1024);
+    let (agent_read, agent_write) = tokio::io::split(agent_end);
+    let serve = tokio::spawn(connection.serve(agent, BufReader::new(agent_read), agent_write));
+    let (editor_read, mut editor_write) = tokio::io::split(editor);
+    let mut lines = BufReader::new(editor_read).lines();
+
+    send(
+        &mut editor_write,
+        json!({"jsonrpc": "2.0", "id": 0, "method": "initialize",
+               "params": {"protocolVersion": 1, "clientCapabilities": {}}}),
+    )
+    .await;
+    assert_eq!(next(&mut lines).await["result"]["protocolVersion"], 1);
+
+    send(
+        &mut editor_write,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "session/new",
+               "params": {"cwd": repo.root(), "mcpServers": []}}),
+    )
+    .await;
+    let session_id = next(&mut lines).await["result"]["sessionId"].clone();
+    assert!(session_id.is_string());
+
+    send(
+        &mut editor_write,
+        json!({"jsonrpc": "2.0", "id": 2, "method": "session/prompt",
+               "params": {"sessionId": session_id,
+                          "prompt": [{"type": "text", "text": "The add function subtracts. Fix it."}]}}),
+    )
+    .await;
+
+    let mut updates = Vec::new();
+    let mut permission_requests = Vec::new();
+    let stop_reason = loop {
+        let message = next(&mut lines).await;
+        match message["method"].as_str() {
+            Some("session/update") => {
+                assert_eq!(message["params"]["sessionId"], session_id);
+                updates.push(message["params"]["update"].clone());
+            }
+            Some("session/request_permission") => {
+                send(
+                    &mut editor_write,
+                    json!({"jsonrpc": "2.0", "id": message["id"],
+                           "result": {"outcome": {"outcome": "selected", "optionId": "allow"}}}),
+                )
+                .await;
+                permission_requests.push(message["params"].clone());
+            }
+            _ => {
+                assert_eq!(message["id"], 2, "unexpected message: {}", message);
+                break message["result"]["stopReason"].clone();
+            }
+        }
+    };
+    assert_eq!(stop_reason, "end_turn");
+
+    let edit = updates
+        .iter()
+        .find(|u| u["sessionUpdate"] == "tool_call" && u["kind"] == "edit")
+        .expect("edit tool call");
+    assert_eq!(
+        edit["content"][0],
+        json!({"type": "diff", "path": repo.root().join("src/lib.rs"),
+               "oldText": "a - b", "newText": "a + b"})
+    );
+    // The permission request is about that same call.
+    assert_eq!(permission_requests.len(), 1);
+    assert_eq!(
+        permission_requests[0]["toolCall"]["toolCallId"],
+        edit["toolCallId"]
+    );
+    assert!(updates
+        .iter()
+        .any(|u| u["sessionUpdate"] == "tool_call_update"
+            && u["toolCallId"] == edit["toolCallId"]
+            && u["status"] == "completed"));
+    assert!(updates
+        .iter()
+        .any(|u| u["sessionUpdate"] == "agent_message_chunk"));
+    assert_eq!(
+        repo.read("src/lib.rs"),
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+    );
+
+    editor_write.shutdown().await.expect("close connection");
+    serve.await.expect("serve task").expect("serve");
+}
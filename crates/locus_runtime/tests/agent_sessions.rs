@@ -0,0 +1,731 @@
+//! Golden-file tests for the agent loop: prompt → tool calls → edits → events.
+//!
+//! Each test replays a cassette from `tests/cassettes/` through a full Runtime session in
+//! a temp git repo and compares the result with `tests/golden/`. A diff in a golden file
+//! means prompt construction, tool dispatch or event emission changed; rerun with
+//! `LOCUS_UPDATE_GOLDEN=1` when the change is intended and review the new files.
+//!
+//! Run: `cargo test -p locus-runtime --test agent_sessions`
+
+mod harness;
+
+use std::time::Duration;
+
+use harness::{
+    annotate_session, cancel_session, golden_path, plan_session, recover_session, review_session,
+    rewind_session, run_session, run_session_with, GitRepo,
+};
+use locus_core::db::TaskStatus;
+use locus_core::{
+    HunkRef, SessionEvent, SessionStatus, SubAgentProgress, SubAgentState, ToolAnnotation,
+};
+use locus_runtime::{LlmProvider, RuntimeError};
+use locus_toolbus::testing::assert_golden;
+
+const LIB_RS: &str = "pub fn add(a: i32, b: i32) -> i32 {\n    a - b\n}\n";
+
+fn fixture() -> GitRepo {
+    GitRepo::new(&[
+        // The runtime keeps its local state in .locus/, as in a real project.
+        (".gitignore", "/target\n.locus/\n"),
+        (
+            "Cargo.toml",
+            "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        ),
+        ("src/lib.rs", LIB_RS),
+    ])
+}
+
+#[tokio::test]
+async fn fix_add_edits_the_file() {
+    let repo = fixture();
+    let run = run_session(&repo, "fix_add", "The add function subtracts. Fix it.").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.tools_called(), ["grep", "edit_file"]);
+    assert_eq!(
+        repo.read("src/lib.rs"),
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+    );
+    // The edit result goes back to the model before its final answer.
+    assert_eq!(run.requests.len(), 3);
+    assert_eq!(run.unused_responses, 0);
+
+    assert_golden(&run.golden(&repo), golden_path("fix_add"));
+}
+
+#[tokio::test]
+async fn create_file_adds_an_untracked_file() {
+    let repo = fixture();
+    let run = run_session(
+        &repo,
+        "create_readme",
+        "Document the crate in docs/README.md",
+    )
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(
+        repo.read("docs/README.md"),
+        "# fixture\n\nAdds two numbers.\n"
+    );
+    assert_eq!(repo.status(), ["?? docs/README.md"]);
+
+    assert_golden(&run.golden(&repo), golden_path("create_readme"));
+}
+
+#[tokio::test]
+async fn note_written_during_a_run_reaches_the_next_request() {
+    let repo = fixture();
+    let note = "Keep the README under docs/, not at the root.";
+    let run = annotate_session(
+        &repo,
+        "annotated_readme",
+        "Document the crate in docs/README.md",
+        ToolAnnotation::new("call_0_0", "create_file", note),
+    )
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.tools_called(), ["create_file", "read"]);
+    assert_eq!(run.requests.len(), 3);
+    // Sent while the first tool turn ran, so the prompt did not carry it; the
+    // request with that turn's result does, and so does every later one.
+    let mentions_note = |i: usize| {
+        serde_json::to_string(&run.requests[i].messages)
+            .unwrap()
+            .contains(note)
+    };
+    assert!(!mentions_note(0));
+    assert!(mentions_note(1));
+    assert!(mentions_note(2));
+}
+
+#[tokio::test]
+async fn failed_edit_is_reported_to_the_model() {
+    let repo = fixture();
+    let run = run_session(&repo, "edit_not_found", "Replace the multiplication in add").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(repo.read("src/lib.rs"), LIB_RS);
+    assert!(repo.status().is_empty());
+    assert!(run.events.iter().any(|event| matches!(
+        event,
+        SessionEvent::ToolDone { result, .. } if result.is_error
+    )));
+    assert_eq!(run.requests.len(), 2);
+
+    assert_golden(&run.golden(&repo), golden_path("edit_not_found"));
+}
+
+#[tokio::test]
+async fn task_list_changes_are_reported_with_the_whole_plan() {
+    let repo = fixture();
+    let run = run_session(&repo, "plan_tasks", "Plan the fix for add").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.tools_called(), ["task_list", "task_list", "task_list"]);
+    // create and update change the plan; list only reads it.
+    let updates: Vec<Vec<TaskStatus>> = run
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::TaskListUpdated { plan_id, tasks } if plan_id == "default" => {
+                Some(tasks.iter().map(|t| t.status).collect())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        updates,
+        [
+            vec![TaskStatus::Pending, TaskStatus::Pending],
+            vec![TaskStatus::InProgress, TaskStatus::Pending],
+        ]
+    );
+
+    assert_golden(&run.golden(&repo), golden_path("plan_tasks"));
+}
+
+#[tokio::test]
+async fn provider_error_ends_the_run() {
+    let repo = fixture();
+    let run = run_session(&repo, "provider_error", "Fix add").await;
+
+    assert!(matches!(run.result, Err(RuntimeError::LlmFailed(_))));
+    assert!(run
+        .events
+        .iter()
+        .any(|event| matches!(event, SessionEvent::Error { error } if error == "overloaded")));
+    assert!(repo.status().is_empty());
+
+    assert_golden(&run.golden(&repo), golden_path("provider_error"));
+}
+
+#[tokio::test]
+async fn system_prompt_is_stable() {
+    let repo = fixture();
+    let run = run_session(
+        &repo,
+        "create_readme",
+        "Document the crate in docs/README.md",
+    )
+    .await;
+
+    assert_golden(&run.system_prompt_lines(), golden_path("system_prompt"));
+}
+
+#[tokio::test]
+async fn tool_search_ranks_the_registered_tools() {
+    let repo = fixture();
+    let toolbus = std::sync::Arc::new(locus_toolbus::ToolBus::new(repo.root().to_path_buf()));
+    let (event_tx, mut events) = tokio::sync::mpsc::channel(16);
+    let search = locus_core::ToolUse::new(
+        "call-1",
+        "tool_search",
+        serde_json::json!({"query": "commit my staged changes", "max_results": 3}),
+    );
+
+    let args = search.args.clone();
+    let result = locus_runtime::tool_handler::handle_tool_call(
+        search,
+        args,
+        locus_toolbus::WriteSink::default(),
+        &toolbus,
+        std::sync::Arc::new(harness::offline_graph(&repo).await),
+        &event_tx,
+        &tokio_util::sync::CancellationToken::new(),
+        "session".to_string(),
+        "turn".to_string(),
+        0,
+    )
+    .await
+    .expect("tool_search");
+
+    assert_eq!(result.output["query"], "commit my staged changes");
+    assert_eq!(result.output["tools"][0]["name"], "git_commit");
+    assert!(result.output["count"].as_u64().unwrap() <= 3);
+    assert!(matches!(
+        events.recv().await,
+        Some(SessionEvent::ToolStart { .. })
+    ));
+}
+
+#[tokio::test]
+async fn recover_reruns_reads_and_reports_interrupted_edits() {
+    use locus_core::db::{load_run_checkpoint, RunCheckpoint};
+    use locus_core::{ContentBlock, Session, SessionConfig, ToolResultData, ToolUse, Turn};
+    use serde_json::json;
+
+    let repo = fixture();
+    // The process died while edit_file ran: grep had returned, read had not started.
+    let grep = ToolUse::new(
+        "call-1",
+        "grep",
+        json!({"pattern": "fn add", "path": "src"}),
+    );
+    let read = ToolUse::new("call-2", "read", json!({"path": "src/lib.rs"}));
+    let edit = ToolUse::new(
+        "call-3",
+        "edit_file",
+        json!({"path": "src/lib.rs", "old_string": "a - b", "new_string": "a + b"}),
+    );
+    let mut session = Session::new(
+        repo.root().to_path_buf(),
+        SessionConfig::new("mock-model", "anthropic"),
+    );
+    session.add_turn(Turn::user().with_block(ContentBlock::text("Fix add")));
+    session.add_turn(
+        Turn::assistant()
+            .with_block(ContentBlock::tool_use(grep.clone()))
+            .with_block(ContentBlock::tool_use(edit.clone()))
+            .with_block(ContentBlock::tool_use(read.clone())),
+    );
+    let checkpoint = RunCheckpoint {
+        session,
+        slug: "fix-add".to_string(),
+        pending_tools: vec![edit, read],
+        finished_tools: vec![(
+            grep,
+            ToolResultData::success(json!({"matches": ["src/lib.rs:1"]}), 3),
+        )],
+        saved_at: 0,
+    };
+
+    let run = recover_session(&repo, "recover_turn", checkpoint).await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    // Only the read-only call runs again; the edit is not repeated.
+    assert_eq!(run.tools_called(), ["read"]);
+    assert_eq!(repo.read("src/lib.rs"), LIB_RS);
+    assert_eq!(run.requests.len(), 1);
+    let messages = serde_json::to_string(&run.requests[0].messages).unwrap();
+    assert!(messages.contains("Interrupted"));
+    assert!(messages.contains("matches"));
+    assert!(load_run_checkpoint(repo.root(), None).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn rewind_drops_turns_and_restores_their_files() {
+    let repo = fixture();
+    let (run, rewound) = rewind_session(
+        &repo,
+        "rewind_turns",
+        &["Fix add", "Add a README"],
+        &[1, 5, 1],
+    )
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    let [readme, fix, nothing] = &rewound[..] else {
+        panic!("three rewinds");
+    };
+
+    // The README the last turn created is deleted.
+    let readme = readme.as_ref().unwrap();
+    assert_eq!(readme.turns, 1);
+    assert_eq!(readme.removed, ["docs/README.md"]);
+    assert!(readme.restored.is_empty());
+    // Only one turn was left, so asking for five rewinds that one.
+    let fix = fix.as_ref().unwrap();
+    assert_eq!(fix.turns, 1);
+    assert_eq!(fix.restored, ["src/lib.rs"]);
+    assert!(matches!(nothing, Err(RuntimeError::SessionError(_))));
+
+    assert_eq!(repo.read("src/lib.rs"), LIB_RS);
+    assert!(repo.status().is_empty(), "{:?}", repo.status());
+    assert_eq!(
+        run.events
+            .iter()
+            .filter(|e| matches!(e, SessionEvent::Rewound { .. }))
+            .count(),
+        2
+    );
+    let saved = locus_core::db::load_session(repo.root(), &run.session_id)
+        .unwrap()
+        .unwrap();
+    assert!(saved.turns.is_empty());
+}
+
+#[tokio::test]
+async fn rejected_hunks_are_reverted() {
+    let repo = fixture();
+    let target = HunkRef {
+        turn: 1,
+        hunk: 0,
+        path: "src/lib.rs".to_string(),
+    };
+    let (run, rejected) = review_session(
+        &repo,
+        "fix_add",
+        "The add function subtracts. Fix it.",
+        &[target.clone(), target],
+    )
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    let diff = run.events.iter().find_map(|e| match e {
+        SessionEvent::TurnDiff { turn, files } => Some((*turn, files)),
+        _ => None,
+    });
+    let (turn, files) = diff.expect("turn diff sent");
+    assert_eq!(turn, 1);
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "src/lib.rs");
+    assert_eq!(files[0].hunks[0].new_lines()[1], "    a + b");
+
+    // The edit is undone; the second reject no longer applies.
+    assert!(rejected[0].is_ok());
+    assert!(matches!(rejected[1], Err(RuntimeError::SessionError(_))));
+    assert_eq!(repo.read("src/lib.rs"), LIB_RS);
+    assert!(repo.status().is_empty(), "{:?}", repo.status());
+    assert_eq!(
+        run.events
+            .iter()
+            .filter(|e| matches!(e, SessionEvent::HunkReverted { .. }))
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn plan_mode_is_read_only_and_its_plan_is_carried_out() {
+    let repo = fixture();
+    let run = plan_session(&repo, "plan_then_execute", "Fix add").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    // Calls in one response may run in any order.
+    let mut planning_calls = run.tools_called()[..2].to_vec();
+    planning_calls.sort();
+    assert_eq!(planning_calls, ["edit_file", "read"]);
+    assert_eq!(run.tools_called()[2..], ["edit_file"]);
+    assert_eq!(
+        repo.read("src/lib.rs"),
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+    );
+
+    // Planning offers only read-only tools and refuses the edit it is asked for.
+    let planning = serde_json::to_value(&run.requests[0].options.tools).unwrap();
+    let planning = planning.to_string();
+    assert!(planning.contains("\"read\""));
+    assert!(!planning.contains("\"edit_file\""));
+    assert!(run
+        .system_prompt_lines()
+        .to_string()
+        .contains("## Plan Mode"));
+    let refused = run
+        .events
+        .iter()
+        .find_map(|event| match event {
+            SessionEvent::ToolDone { result, .. } if result.is_error => Some(result),
+            _ => None,
+        })
+        .unwrap();
+    assert!(refused.output["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Plan mode is read-only"));
+
+    // The plan is saved, and the run that carries it out has every tool and the plan.
+    let plan = run
+        .events
+        .iter()
+        .find_map(|event| match event {
+            SessionEvent::PlanReady { plan } => Some(plan),
+            _ => None,
+        })
+        .unwrap();
+    assert!(plan.path.starts_with(".locus/plans/"));
+    assert_eq!(plan.task, "Fix add");
+    assert!(repo.read(&plan.path).contains("change `a - b` to `a + b`"));
+    let executing = &run.requests[2];
+    let tools = serde_json::to_value(&executing.options.tools)
+        .unwrap()
+        .to_string();
+    assert!(tools.contains("\"edit_file\""));
+    let messages = serde_json::to_value(&executing.messages)
+        .unwrap()
+        .to_string();
+    assert!(messages.contains("Carry out the plan for: Fix add"));
+    assert!(messages.contains("change `a - b` to `a + b`"));
+}
+
+#[tokio::test]
+async fn cancel_kills_the_command_and_leaves_a_resumable_session() {
+    let repo = fixture();
+    let started = std::time::Instant::now();
+    let (run, cancelled) = cancel_session(
+        &repo,
+        "cancel_build",
+        "Run the build",
+        Duration::from_millis(500),
+        "Never mind",
+    )
+    .await;
+
+    assert!(matches!(cancelled, Ok(SessionStatus::Cancelled)));
+    assert!(started.elapsed() < Duration::from_secs(20));
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    assert!(repo.status().is_empty());
+
+    // The killed command is reported as cancelled, and the next request sees
+    // an assistant turn marking the cancel before the follow-up.
+    let killed = run
+        .events
+        .iter()
+        .find_map(|event| match event {
+            SessionEvent::ToolDone { result, .. } => Some(result),
+            _ => None,
+        })
+        .unwrap();
+    assert!(killed.is_error);
+    let messages = serde_json::to_value(&run.requests[1].messages).unwrap();
+    let messages = messages.as_array().unwrap();
+    let marker = &messages[messages.len() - 2];
+    assert_eq!(marker["role"], "assistant");
+    assert!(marker.to_string().contains("[Cancelled by the user]"));
+    assert!(messages[messages.len() - 1]
+        .to_string()
+        .contains("Never mind"));
+}
+
+/// Every sub-agent board the session sent.
+fn sub_agent_boards(run: &harness::SessionRun) -> Vec<&Vec<SubAgentProgress>> {
+    run.events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::SubAgents { agents } => Some(agents),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn sub_agents_run_at_most_max_parallel_at_a_time() {
+    let repo = fixture();
+    let run = run_session_with(&repo, "parallel_tasks", "Look around", |config| {
+        config.with_max_parallel_subagents(2)
+    })
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    let boards = sub_agent_boards(&run);
+    let count = |agents: &[SubAgentProgress], state| {
+        agents.iter().filter(|agent| agent.state == state).count()
+    };
+    assert!(boards
+        .iter()
+        .all(|agents| count(agents, SubAgentState::Running) <= 2));
+    // The third waited for a slot.
+    assert!(boards
+        .iter()
+        .filter(|agents| agents[2].state != SubAgentState::Queued)
+        .all(|agents| agents[0].state.is_finished() || agents[1].state.is_finished()));
+    let last = boards.last().unwrap();
+    let mut descriptions: Vec<&str> = last.iter().map(|a| a.description.as_str()).collect();
+    descriptions.sort();
+    assert_eq!(descriptions, ["Check docs", "Check tests", "Read lib.rs"]);
+    assert_eq!(count(last, SubAgentState::Done), 3);
+    assert!(last
+        .iter()
+        .all(|agent| agent.last_line == "Sub-agent report." && agent.tokens == 120));
+    // Their text goes to their transcripts, not the chat.
+    assert!(run.events.iter().any(|event| matches!(
+        event,
+        SessionEvent::SubAgentOutput { tool_use_id, event }
+            if tool_use_id == &last[0].tool_use_id
+                && matches!(**event, SessionEvent::TextDelta { .. })
+    )));
+
+    // Results reach the model in call order.
+    let messages = serde_json::to_value(&run.requests.last().unwrap().messages)
+        .unwrap()
+        .to_string();
+    let order: Vec<usize> = last
+        .iter()
+        .map(|agent| messages.rfind(&agent.tool_use_id).unwrap())
+        .collect();
+    assert!(order.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[tokio::test]
+async fn sub_agent_past_its_time_limit_is_stopped() {
+    let repo = fixture();
+    let started = std::time::Instant::now();
+    let run = run_session_with(&repo, "task_timeout", "Build it", |config| {
+        config.with_subagent_timeout_secs(1)
+    })
+    .await;
+
+    assert!(started.elapsed() < Duration::from_secs(20));
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    assert!(repo.status().is_empty());
+    let last = sub_agent_boards(&run).pop().unwrap().clone();
+    assert_eq!(last[0].state, SubAgentState::TimedOut);
+    assert!(run.events.iter().any(|event| matches!(
+        event,
+        SessionEvent::ToolDone { result, .. } if result.output["timed_out"] == true
+    )));
+}
+
+#[tokio::test]
+async fn searcher_sub_agent_gets_read_only_tools_on_the_cheap_model() {
+    let repo = fixture();
+    let run = run_session(&repo, "searcher_task", "Where is add broken?").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    assert_eq!(repo.read("src/lib.rs"), LIB_RS);
+    assert!(repo.status().is_empty());
+
+    let sub_request = &run.requests[1];
+    assert_eq!(
+        sub_request.model,
+        LlmProvider::Anthropic.cheap_model().unwrap()
+    );
+    let tools: Vec<&str> = sub_request
+        .options
+        .tools
+        .iter()
+        .flatten()
+        .map(|tool| tool.function.name.as_str())
+        .collect();
+    assert!(tools.contains(&"read") && tools.contains(&"grep"));
+    assert!(!tools
+        .iter()
+        .any(|tool| ["edit_file", "bash", "task"].contains(tool)));
+
+    let outputs: Vec<_> = run
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::ToolDone { result, .. } => Some(&result.output),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        outputs[0]["error"],
+        "edit_file is not available to the searcher agent"
+    );
+    assert_eq!(outputs[1]["profile"], "searcher");
+}
+
+#[tokio::test]
+async fn long_sub_agent_output_is_condensed_before_the_parent_sees_it() {
+    let repo = fixture();
+    let run = run_session_with(&repo, "condensed_task", "Fix add", |config| {
+        config.with_subagent_summary_chars(100)
+    })
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    assert!(repo.read("src/lib.rs").contains("a + b"));
+
+    // The condensing call goes to the cheap model with the transcript and no tools.
+    let condense = &run.requests[3];
+    assert_eq!(
+        condense.model,
+        LlmProvider::Anthropic.cheap_model().unwrap()
+    );
+    assert!(condense.options.tools.is_none());
+    let transcript = serde_json::to_string(&condense.messages).unwrap();
+    assert!(transcript.contains("Task: Fix the add function and check it"));
+    assert!(transcript.contains("→ edit_file"));
+
+    let output = run
+        .events
+        .iter()
+        .rev()
+        .find_map(|event| match event {
+            SessionEvent::ToolDone { result, .. } => Some(&result.output),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        output["summary"],
+        "- `add` in src/lib.rs subtracted; now returns `a + b`"
+    );
+    assert_eq!(output["summarized"], true);
+    assert_eq!(output["files_touched"], serde_json::json!(["src/lib.rs"]));
+    assert_eq!(output["artifacts"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn pinned_file_is_in_every_request_as_it_is_on_disk() {
+    let repo = GitRepo::new(&[
+        (".gitignore", ".locus/\n"),
+        ("CONVENTIONS.md", "Use tabs.\n"),
+        ("src/lib.rs", LIB_RS),
+    ]);
+    let run = run_session_with(
+        &repo,
+        "pinned_conventions",
+        "Switch the conventions to spaces",
+        |config| config.with_pinned_files(vec!["CONVENTIONS.md".into()]),
+    )
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.unused_responses, 0);
+    let messages: Vec<String> = run
+        .requests
+        .iter()
+        .map(|request| serde_json::to_string(&request.messages).unwrap())
+        .collect();
+    assert!(messages[0].contains("### CONVENTIONS.md\\n```\\nUse tabs.\\n```"));
+    // The request after the edit carries the new content, marked as changed.
+    assert!(messages[1].contains("### CONVENTIONS.md (changed since the previous request)"));
+    assert!(messages[1].contains("Use four spaces."));
+    assert!(!messages[1].contains("```\\nUse tabs."));
+}
+
+#[tokio::test]
+async fn project_instructions_are_in_the_system_prompt() {
+    let repo = GitRepo::new(&[
+        (".gitignore", ".locus/\n"),
+        ("AGENTS.md", "Never reformat files you did not change.\n"),
+        ("src/AGENTS.md", "Keep functions under 20 lines.\n"),
+        ("src/lib.rs", LIB_RS),
+    ]);
+    let run = run_session(&repo, "fix_add", "The add function subtracts. Fix it.").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    let loaded = run
+        .events
+        .iter()
+        .find_map(|event| match event {
+            SessionEvent::InstructionsLoaded { files } => Some(files.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(loaded, ["AGENTS.md", "src/AGENTS.md"]);
+    // Loaded once, and in every request's system prompt.
+    assert_eq!(
+        run.events
+            .iter()
+            .filter(|e| matches!(e, SessionEvent::InstructionsLoaded { .. }))
+            .count(),
+        1
+    );
+    for request in &run.requests {
+        let messages = serde_json::to_string(&request.messages).unwrap();
+        assert!(messages.contains("## Project Instructions"));
+        assert!(messages.contains("Never reformat files you did not change."));
+        assert!(messages.contains("### src/AGENTS.md (applies to src/)"));
+    }
+}
+
+#[tokio::test]
+async fn custom_command_is_expanded_into_the_prompt() {
+    let repo = GitRepo::new(&[
+        (".gitignore", ".locus/\n"),
+        (
+            ".locus/commands/fix.md",
+            "---\ndescription: Fix a function\n---\nThe $ARGUMENTS function is wrong. Fix it.\n",
+        ),
+        ("src/lib.rs", LIB_RS),
+    ]);
+    let run = run_session(&repo, "fix_add", "/fix add").await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    let first = serde_json::to_string(&run.requests[0].messages).unwrap();
+    assert!(first.contains("The add function is wrong. Fix it."));
+    assert!(!first.contains("/fix add"));
+    assert!(run.events.iter().any(|event| matches!(
+        event,
+        SessionEvent::Status { message } if message == "Running /fix from .locus/commands/fix.md"
+    )));
+}
+
+#[tokio::test]
+async fn request_stops_at_its_tool_call_limit_and_asks_to_go_on() {
+    let repo = fixture();
+    let run = run_session_with(&repo, "looping_reads", "Find the bug", |config| {
+        config.with_max_tool_calls_per_turn(Some(2))
+    })
+    .await;
+
+    assert!(matches!(run.result, Ok(SessionStatus::Waiting)));
+    assert_eq!(run.tools_called(), ["read", "read"]);
+    // The model is not called again once the limit is reached.
+    assert_eq!(run.requests.len(), 2);
+    assert_eq!(run.unused_responses, 2);
+    let text: String = run
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::TextDelta { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(text.contains("this request reached its limit of 2 tool calls"));
+    assert!(text.contains("- read × 2 (2 failed)"));
+    assert!(text.contains("Last error:"));
+    assert!(text.contains("Reply \"continue\" to keep going"));
+}
@@ -0,0 +1,430 @@
+//! End-to-end harness: full Runtime sessions against a scripted provider in a temp git repo.
+//!
+//! A session test builds a [`GitRepo`] fixture, picks a cassette from `tests/cassettes/`
+//! (format in `locus_llms::testing`) and sends one prompt through [`run_session`]. The
+//! runtime uses the real ToolBus, so tool calls in the cassette edit the fixture. Tests then
+//! check the files and compare [`SessionRun::golden`] — status, requests sent to the
+//! provider, emitted events and `git status` — with `tests/golden/<name>.json`.
+//! Set `LOCUS_UPDATE_GOLDEN=1` to rewrite golden files after an intended change.
+//!
+//! LocusGraph points at an endpoint nothing listens on, so memory calls fail and are
+//! skipped the way they are when the server is down.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use locus_core::db::RunCheckpoint;
+use locus_core::{
+    ApprovalDecision, ApprovalRequest, ApprovalResponse, HunkRef, RewindSummary, SessionEvent,
+    SessionStatus, ToolAnnotation,
+};
+use locus_graph::{LocusGraphClient, LocusGraphConfig};
+use locus_llms::testing::MockProvider;
+use locus_llms::types::{GenerateRequest, Role as LlmRole};
+use locus_runtime::{Runtime, RuntimeConfig, RuntimeError};
+use locus_toolbus::testing::{redact_keys, replace_in_strings};
+use locus_toolbus::{Approver, ToolBus};
+use serde_json::{json, Value as JsonValue};
+use tempfile::TempDir;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Where the per-session part of the system message starts; everything before it is
+/// the static prompt, covered by its own golden file.
+pub const SESSION_CONTEXT_HEADING: &str = "## Current Session";
+
+pub fn cassette_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/cassettes/{}.json", name))
+}
+
+pub fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/golden/{}.json", name))
+}
+
+/// A git repository in a temp dir with one commit of the given files.
+pub struct GitRepo {
+    dir: TempDir,
+    root: PathBuf,
+}
+
+impl GitRepo {
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        let dir = TempDir::new().expect("create temp dir");
+        // A fixed directory name keeps the repository name in the prompt stable.
+        let root = dir.path().join("fixture");
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().expect("file has a parent")).expect("create dir");
+            fs::write(path, content).expect("write fixture file");
+        }
+        let repo = Self { dir, root };
+        repo.git(&["init", "-q"]);
+        repo.git(&["add", "-A"]);
+        repo.git(&["commit", "-q", "-m", "initial"]);
+        repo
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.root.join(path)).unwrap_or_else(|e| panic!("read {}: {}", path, e))
+    }
+
+    /// `git status --porcelain` lines, sorted.
+    pub fn status(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .git(&["status", "--porcelain", "--untracked-files=all"])
+            .lines()
+            .map(str::to_string)
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    fn git(&self, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=locus",
+                "-c",
+                "user.email=locus@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(&self.root)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+}
+
+/// What one session did.
+pub struct SessionRun {
+    pub result: Result<SessionStatus, RuntimeError>,
+    pub events: Vec<SessionEvent>,
+    pub requests: Vec<GenerateRequest>,
+    /// Cassette responses the session never asked for.
+    pub unused_responses: usize,
+    pub session_id: String,
+}
+
+/// A LocusGraph client for `repo` whose server is never reachable.
+pub async fn offline_graph(repo: &GitRepo) -> LocusGraphClient {
+    let graph_config = LocusGraphConfig::new("http://127.0.0.1:1", "test-secret", "locus-test")
+        .db_path(repo.dir.path().join("locus_graph.db"))
+        .cache_reads(false)
+        .queue_stores(false)
+        .local_reads(false);
+    LocusGraphClient::new(graph_config)
+        .await
+        .expect("offline LocusGraph client")
+}
+
+/// Run `prompt` as a new session in `repo`, answering LLM calls from `cassette`.
+pub async fn run_session(repo: &GitRepo, cassette: &str, prompt: &str) -> SessionRun {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), false).await;
+    let result = runtime.run(prompt.to_string(), None).await;
+    finish(runtime, result, collector, &provider).await
+}
+
+/// Finish the turn saved in `checkpoint` (see `Runtime::recover`) in `repo`,
+/// answering LLM calls from `cassette`. The project DB session log is on, as in
+/// a real run, so the checkpoint is dropped afterwards.
+pub async fn recover_session(
+    repo: &GitRepo,
+    cassette: &str,
+    checkpoint: RunCheckpoint,
+) -> SessionRun {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), true).await;
+    let result = runtime.recover(checkpoint, None).await;
+    finish(runtime, result, collector, &provider).await
+}
+
+/// Run each of `prompts` as a turn of one session in `repo`, then `:rewind` by
+/// each count in `rewinds`. The session log is on, so file changes are recorded.
+pub async fn rewind_session(
+    repo: &GitRepo,
+    cassette: &str,
+    prompts: &[&str],
+    rewinds: &[u32],
+) -> (SessionRun, Vec<Result<RewindSummary, RuntimeError>>) {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), true).await;
+    let mut result = Ok(SessionStatus::Active);
+    for prompt in prompts {
+        result = runtime.run(prompt.to_string(), None).await;
+    }
+    let mut rewound = Vec::new();
+    for &turns in rewinds {
+        rewound.push(runtime.rewind(turns).await);
+    }
+    (finish(runtime, result, collector, &provider).await, rewound)
+}
+
+/// Run `prompt` in `repo` with the session log on, then reject each hunk of
+/// `rejects` (`:reject-hunk`) in the same session.
+pub async fn review_session(
+    repo: &GitRepo,
+    cassette: &str,
+    prompt: &str,
+    rejects: &[HunkRef],
+) -> (SessionRun, Vec<Result<(), RuntimeError>>) {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), true).await;
+    let result = runtime.run(prompt.to_string(), None).await;
+    let mut rejected = Vec::new();
+    for target in rejects {
+        rejected.push(runtime.reject_hunk(target.clone()).await);
+    }
+    (
+        finish(runtime, result, collector, &provider).await,
+        rejected,
+    )
+}
+
+/// Plan `task` in plan mode (`:plan`) in `repo`, then carry out the saved plan
+/// (`:execute-plan`) in the same session when the planning run succeeds.
+pub async fn plan_session(repo: &GitRepo, cassette: &str, task: &str) -> SessionRun {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), false).await;
+    let mut result = runtime.plan(task.to_string(), None).await;
+    if result.is_ok() {
+        result = runtime.execute_plan(None, None).await;
+    }
+    finish(runtime, result, collector, &provider).await
+}
+
+/// Run `prompt` in `repo` and cancel it after `cancel_after`, then send
+/// `follow_up` in the same session.
+pub async fn cancel_session(
+    repo: &GitRepo,
+    cassette: &str,
+    prompt: &str,
+    cancel_after: Duration,
+    follow_up: &str,
+) -> (SessionRun, Result<SessionStatus, RuntimeError>) {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), false).await;
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(cancel_after).await;
+        trigger.cancel();
+    });
+    let cancelled = runtime.run(prompt.to_string(), Some(cancel)).await;
+    let result = runtime.run(follow_up.to_string(), None).await;
+    (
+        finish(runtime, result, collector, &provider).await,
+        cancelled,
+    )
+}
+
+/// Run `prompt` in `repo` and send `annotation` while the run is going: when
+/// the first call that needs approval asks for it. Every call is allowed.
+pub async fn annotate_session(
+    repo: &GitRepo,
+    cassette: &str,
+    prompt: &str,
+    annotation: ToolAnnotation,
+) -> SessionRun {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let (mut runtime, collector) = start_runtime(repo, provider.clone(), false).await;
+    runtime.set_approver(Arc::new(AnnotatingApprover {
+        notes: runtime.annotation_sender(),
+        annotation: Mutex::new(Some(annotation)),
+    }));
+    let result = runtime.run(prompt.to_string(), None).await;
+    finish(runtime, result, collector, &provider).await
+}
+
+/// Allows every call, sending its note with the first approval it is asked for.
+struct AnnotatingApprover {
+    notes: mpsc::Sender<ToolAnnotation>,
+    annotation: Mutex<Option<ToolAnnotation>>,
+}
+
+#[async_trait]
+impl Approver for AnnotatingApprover {
+    async fn approve(&self, request: ApprovalRequest) -> ApprovalResponse {
+        let annotation = self.annotation.lock().unwrap().take();
+        if let Some(annotation) = annotation {
+            self.notes
+                .send(annotation)
+                .await
+                .expect("runtime takes notes");
+        }
+        ApprovalResponse::new(request.id, ApprovalDecision::Allow)
+    }
+}
+
+/// Like [run_session], with the runtime config changed by `configure`.
+pub async fn run_session_with(
+    repo: &GitRepo,
+    cassette: &str,
+    prompt: &str,
+    configure: impl FnOnce(RuntimeConfig) -> RuntimeConfig,
+) -> SessionRun {
+    let provider = Arc::new(MockProvider::from_cassette(cassette_path(cassette)));
+    let config = configure(test_config(repo, false));
+    let (mut runtime, collector) = start_runtime_with(repo, provider.clone(), config).await;
+    let result = runtime.run(prompt.to_string(), None).await;
+    finish(runtime, result, collector, &provider).await
+}
+
+fn test_config(repo: &GitRepo, session_log: bool) -> RuntimeConfig {
+    RuntimeConfig::new(repo.root().to_path_buf())
+        .with_model("mock-model")
+        .with_session_log(session_log)
+}
+
+async fn start_runtime(
+    repo: &GitRepo,
+    provider: Arc<MockProvider>,
+    session_log: bool,
+) -> (Runtime, tokio::task::JoinHandle<Vec<SessionEvent>>) {
+    start_runtime_with(repo, provider, test_config(repo, session_log)).await
+}
+
+async fn start_runtime_with(
+    repo: &GitRepo,
+    provider: Arc<MockProvider>,
+    config: RuntimeConfig,
+) -> (Runtime, tokio::task::JoinHandle<Vec<SessionEvent>>) {
+    let root = repo.root().to_path_buf();
+
+    let locus_graph = offline_graph(repo).await;
+
+    let (event_tx, mut event_rx) = mpsc::channel(64);
+    let collector = tokio::spawn(async move {
+        let mut events = Vec::new();
+        while let Some(event) = event_rx.recv().await {
+            events.push(event);
+        }
+        events
+    });
+
+    let runtime = Runtime::new_with_shared(
+        config,
+        event_tx,
+        Arc::new(ToolBus::new(root)),
+        Arc::new(locus_graph),
+        provider,
+    )
+    .await
+    .expect("create runtime");
+    (runtime, collector)
+}
+
+async fn finish(
+    runtime: Runtime,
+    result: Result<SessionStatus, RuntimeError>,
+    collector: tokio::task::JoinHandle<Vec<SessionEvent>>,
+    provider: &MockProvider,
+) -> SessionRun {
+    let session_id = runtime.session.id.as_str().to_string();
+    drop(runtime);
+
+    SessionRun {
+        result,
+        events: collector.await.expect("collect events"),
+        requests: provider.requests(),
+        unused_responses: provider.remaining(),
+        session_id,
+    }
+}
+
+impl SessionRun {
+    /// Names of the tools the session started, in order.
+    pub fn tools_called(&self) -> Vec<String> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                SessionEvent::ToolStart { tool_use } => Some(tool_use.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The static part of the first request's system prompt, one entry per line.
+    pub fn system_prompt_lines(&self) -> JsonValue {
+        let system = system_text(&self.requests[0]);
+        let prompt = system
+            .split(SESSION_CONTEXT_HEADING)
+            .next()
+            .unwrap_or_default();
+        json!(prompt.trim_end().lines().collect::<Vec<_>>())
+    }
+
+    /// Everything worth pinning, with temp paths, session ids and timings normalized.
+    pub fn golden(&self, repo: &GitRepo) -> JsonValue {
+        let status = match &self.result {
+            Ok(status) => json!(status),
+            Err(e) => json!({ "error": e.to_string() }),
+        };
+        let mut value = json!({
+            "status": status,
+            "unused_responses": self.unused_responses,
+            "git_status": repo.status(),
+            "requests": self.requests.iter().map(request_summary).collect::<Vec<_>>(),
+            "events": self.events,
+        });
+        for root in [repo.root().to_path_buf(), canonical(repo.root())] {
+            replace_in_strings(&mut value, &root.to_string_lossy(), "<repo>");
+        }
+        replace_in_strings(&mut value, &self.session_id, "<session>");
+        redact_keys(&mut value, &["duration_ms"]);
+        value
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn system_text(request: &GenerateRequest) -> String {
+    request
+        .messages
+        .iter()
+        .find(|m| m.role == LlmRole::System)
+        .and_then(|m| m.text())
+        .expect("request has a system message")
+}
+
+/// A request with the system message cut down to its per-session part and tools by name.
+fn request_summary(request: &GenerateRequest) -> JsonValue {
+    let mut messages = serde_json::to_value(&request.messages).expect("messages serialize");
+    let system = system_text(request);
+    let session_part = system
+        .find(SESSION_CONTEXT_HEADING)
+        .map_or("", |at| &system[at..]);
+    if let Some(first) = messages.get_mut(0) {
+        first["content"] = json!(session_part);
+    }
+    let tools: Vec<&str> = request
+        .options
+        .tools
+        .iter()
+        .flatten()
+        .map(|tool| tool.function.name.as_str())
+        .collect();
+    json!({
+        "model": request.model,
+        "tools": tools,
+        "messages": messages,
+    })
+}
@@ -6,7 +6,7 @@ use locus_graph::{ContextTypeFilter, LocusGraphClient, LocusGraphConfig, Retriev
 use serde_json::Value;
 
 fn try_config() -> Option<LocusGraphConfig> {
-    LocusGraphConfig::from_env().ok()
+    LocusGraphConfig::from_env().ok().filter(|c| !c.offline)
 }
 
 // ─── Phase 1: Project Root Anchor ───────────────────────────────────────
@@ -0,0 +1,475 @@
+//! The JSON-RPC side of ACP: one message per line in both directions.
+//!
+//! The client (the editor) calls the agent (`initialize`, `session/new`,
+//! `session/prompt`, `session/cancel`); while a prompt runs the agent notifies
+//! the client (`session/update`) and calls it back (`session/request_permission`).
+//! [AcpConnection::serve] routes the first to an [AcpAgent] and hands the agent an
+//! [AcpClient] for the second.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinSet;
+use tracing::debug;
+
+use crate::acp::protocol::{
+    CancelNotification, InitializeRequest, InitializeResponse, NewSessionRequest,
+    NewSessionResponse, PromptRequest, PromptResponse, RequestPermissionRequest,
+    RequestPermissionResponse, SessionNotification,
+};
+use crate::mcp::protocol::{JsonRpcErrorObject, error_codes};
+
+/// Errors from an ACP connection or agent.
+#[derive(Debug, thiserror::Error)]
+pub enum AcpError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// An error the other side should see as a JSON-RPC error.
+    #[error("{}", .0.message)]
+    Rpc(JsonRpcErrorObject),
+
+    #[error("Connection closed")]
+    Closed,
+}
+
+impl AcpError {
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::Rpc(JsonRpcErrorObject::new(
+            error_codes::INVALID_PARAMS,
+            message,
+        ))
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Rpc(JsonRpcErrorObject::new(
+            error_codes::INTERNAL_ERROR,
+            message,
+        ))
+    }
+
+    fn into_object(self) -> JsonRpcErrorObject {
+        match self {
+            Self::Rpc(error) => error,
+            Self::Json(e) => JsonRpcErrorObject::new(error_codes::INVALID_PARAMS, e.to_string()),
+            other => JsonRpcErrorObject::new(error_codes::INTERNAL_ERROR, other.to_string()),
+        }
+    }
+}
+
+/// An agent an ACP client can drive.
+#[async_trait]
+pub trait AcpAgent: Send + Sync {
+    async fn initialize(&self, request: InitializeRequest) -> Result<InitializeResponse, AcpError>;
+
+    /// Only called when [InitializeResponse::auth_methods] offered a method.
+    async fn authenticate(&self, method_id: String) -> Result<(), AcpError> {
+        Err(AcpError::invalid_params(format!(
+            "Unknown authentication method: {}",
+            method_id
+        )))
+    }
+
+    async fn new_session(&self, request: NewSessionRequest)
+    -> Result<NewSessionResponse, AcpError>;
+
+    /// Run one prompt to the end of the turn, reporting progress through the
+    /// [AcpClient] the connection was created with.
+    async fn prompt(&self, request: PromptRequest) -> Result<PromptResponse, AcpError>;
+
+    /// Stop the running prompt; it should still answer, with `cancelled`.
+    async fn cancel(&self, notification: CancelNotification);
+}
+
+type PendingResponse = oneshot::Sender<Result<Value, JsonRpcErrorObject>>;
+
+/// The agent's handle on the client: notifications and requests back to the editor.
+pub struct AcpClient {
+    outgoing: mpsc::UnboundedSender<String>,
+    pending: Mutex<HashMap<u64, PendingResponse>>,
+    next_id: AtomicU64,
+}
+
+impl AcpClient {
+    /// Report progress of a prompt (`session/update`).
+    pub fn session_update(&self, notification: SessionNotification) -> Result<(), AcpError> {
+        self.notify("session/update", &notification)
+    }
+
+    /// Ask the user whether a tool call may run (`session/request_permission`).
+    pub async fn request_permission(
+        &self,
+        request: RequestPermissionRequest,
+    ) -> Result<RequestPermissionResponse, AcpError> {
+        self.request("session/request_permission", &request).await
+    }
+
+    fn notify<P: Serialize>(&self, method: &str, params: &P) -> Result<(), AcpError> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        self.outgoing
+            .send(message.to_string())
+            .map_err(|_| AcpError::Closed)
+    }
+
+    async fn request<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &P,
+    ) -> Result<R, AcpError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id, tx);
+        }
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if self.outgoing.send(message.to_string()).is_err() {
+            self.forget(id);
+            return Err(AcpError::Closed);
+        }
+        let result = rx.await.map_err(|_| AcpError::Closed)?;
+        Ok(serde_json::from_value(result.map_err(AcpError::Rpc)?)?)
+    }
+
+    fn forget(&self, id: u64) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&id);
+        }
+    }
+
+    /// Deliver the client's answer to one of our requests.
+    fn resolve(&self, message: &Value) {
+        let Some(id) = message.get("id").and_then(Value::as_u64) else {
+            return;
+        };
+        let Some(tx) = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&id))
+        else {
+            debug!("ACP response to unknown request {}", id);
+            return;
+        };
+        let result = match message.get("error") {
+            Some(error) => Err(serde_json::from_value(error.clone()).unwrap_or_else(|_| {
+                JsonRpcErrorObject::new(error_codes::INTERNAL_ERROR, error.to_string())
+            })),
+            None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+        };
+        let _ = tx.send(result);
+    }
+}
+
+/// One ACP connection: create it, give [AcpConnection::client] to the agent, then serve.
+pub struct AcpConnection {
+    client: Arc<AcpClient>,
+    outgoing: mpsc::UnboundedReceiver<String>,
+}
+
+impl Default for AcpConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AcpConnection {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            client: Arc::new(AcpClient {
+                outgoing: tx,
+                pending: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(0),
+            }),
+            outgoing: rx,
+        }
+    }
+
+    pub fn client(&self) -> Arc<AcpClient> {
+        Arc::clone(&self.client)
+    }
+
+    /// Serve on stdin and stdout until stdin closes.
+    pub async fn serve_stdio(self, agent: Arc<dyn AcpAgent>) -> Result<(), AcpError> {
+        let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+        self.serve(agent, stdin, tokio::io::stdout()).await
+    }
+
+    /// Route messages from `reader` until it ends. Requests run concurrently, so a
+    /// prompt can wait on a permission answer read by this same loop. Prompts still
+    /// running when the reader ends are dropped.
+    pub async fn serve<R, W>(
+        mut self,
+        agent: Arc<dyn AcpAgent>,
+        reader: R,
+        mut writer: W,
+    ) -> Result<(), AcpError>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = reader.lines();
+        let mut requests = JoinSet::new();
+        loop {
+            tokio::select! {
+                line = lines.next_line() => {
+                    let Some(line) = line? else { break };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    self.route(&line, &agent, &mut requests);
+                }
+                Some(message) = self.outgoing.recv() => {
+                    writer.write_all(message.as_bytes()).await?;
+                    writer.write_all(b"\n").await?;
+                    writer.flush().await?;
+                }
+                Some(_) = requests.join_next(), if !requests.is_empty() => {}
+            }
+        }
+        requests.abort_all();
+        if let Ok(mut pending) = self.client.pending.lock() {
+            pending.clear();
+        }
+        while let Ok(message) = self.outgoing.try_recv() {
+            writer.write_all(message.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+        }
+        writer.flush().await?;
+        Ok(())
+    }
+
+    fn route(&self, line: &str, agent: &Arc<dyn AcpAgent>, requests: &mut JoinSet<()>) {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => {
+                let error = JsonRpcErrorObject::new(
+                    error_codes::PARSE_ERROR,
+                    format!("Parse error: {}", e),
+                );
+                let _ = self.client.outgoing.send(response(Value::Null, Err(error)));
+                return;
+            }
+        };
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            self.client.resolve(&message);
+            return;
+        };
+        let method = method.to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let id = message.get("id").cloned();
+        let agent = Arc::clone(agent);
+        let outgoing = self.client.outgoing.clone();
+        requests.spawn(async move {
+            let result = dispatch(agent.as_ref(), &method, params).await;
+            match id {
+                Some(id) => {
+                    let _ = outgoing.send(response(id, result.map_err(AcpError::into_object)));
+                }
+                None => {
+                    if let Err(e) = result {
+                        debug!("ACP notification {} failed: {}", method, e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+async fn dispatch(agent: &dyn AcpAgent, method: &str, params: Value) -> Result<Value, AcpError> {
+    match method {
+        "initialize" => to_value(agent.initialize(serde_json::from_value(params)?).await?),
+        "authenticate" => {
+            let method_id = params
+                .get("methodId")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            agent.authenticate(method_id).await?;
+            Ok(json!({}))
+        }
+        "session/new" => to_value(agent.new_session(serde_json::from_value(params)?).await?),
+        "session/prompt" => to_value(agent.prompt(serde_json::from_value(params)?).await?),
+        "session/cancel" => {
+            agent.cancel(serde_json::from_value(params)?).await;
+            Ok(Value::Null)
+        }
+        _ => Err(AcpError::Rpc(JsonRpcErrorObject::new(
+            error_codes::METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        ))),
+    }
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, AcpError> {
+    Ok(serde_json::to_value(value)?)
+}
+
+fn response(id: Value, result: Result<Value, JsonRpcErrorObject>) -> String {
+    let message = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    message.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acp::protocol::{
+        AgentCapabilities, PROTOCOL_VERSION, PermissionOption, PermissionOptionKind,
+        RequestPermissionOutcome, SessionUpdate, StopReason, ToolCallUpdate,
+    };
+    use tokio::io::{AsyncReadExt, BufReader};
+
+    /// Echoes the prompt back, asking permission first.
+    struct EchoAgent {
+        client: Arc<AcpClient>,
+    }
+
+    #[async_trait]
+    impl AcpAgent for EchoAgent {
+        async fn initialize(
+            &self,
+            _request: InitializeRequest,
+        ) -> Result<InitializeResponse, AcpError> {
+            Ok(InitializeResponse {
+                protocol_version: PROTOCOL_VERSION,
+                agent_capabilities: AgentCapabilities::default(),
+                auth_methods: vec![],
+            })
+        }
+
+        async fn new_session(
+            &self,
+            _request: NewSessionRequest,
+        ) -> Result<NewSessionResponse, AcpError> {
+            Ok(NewSessionResponse {
+                session_id: "s1".to_string(),
+            })
+        }
+
+        async fn prompt(&self, request: PromptRequest) -> Result<PromptResponse, AcpError> {
+            let answer = self
+                .client
+                .request_permission(RequestPermissionRequest {
+                    session_id: request.session_id.clone(),
+                    tool_call: ToolCallUpdate {
+                        tool_call_id: "call_1".to_string(),
+                        ..Default::default()
+                    },
+                    options: vec![PermissionOption::new(
+                        "allow",
+                        "Allow",
+                        PermissionOptionKind::AllowOnce,
+                    )],
+                })
+                .await?;
+            if answer.outcome == RequestPermissionOutcome::Cancelled {
+                return Ok(PromptResponse {
+                    stop_reason: StopReason::Cancelled,
+                });
+            }
+            for content in request.prompt {
+                self.client.session_update(SessionNotification {
+                    session_id: request.session_id.clone(),
+                    update: SessionUpdate::AgentMessageChunk { content },
+                })?;
+            }
+            Ok(PromptResponse {
+                stop_reason: StopReason::EndTurn,
+            })
+        }
+
+        async fn cancel(&self, _notification: CancelNotification) {}
+    }
+
+    async fn send(writer: &mut (impl AsyncWrite + Unpin), message: Value) {
+        writer
+            .write_all(format!("{}\n", message).as_bytes())
+            .await
+            .unwrap();
+    }
+
+    async fn next(lines: &mut tokio::io::Lines<impl AsyncBufRead + Unpin>) -> Value {
+        serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prompt_round_trip_with_permission_request() {
+        let connection = AcpConnection::new();
+        let agent = Arc::new(EchoAgent {
+            client: connection.client(),
+        });
+        let (client_end, agent_end) = tokio::io::duplex(64 * 1024);
+        let (agent_read, agent_write) = tokio::io::split(agent_end);
+        let serve = tokio::spawn(connection.serve(agent, BufReader::new(agent_read), agent_write));
+
+        let (client_read, mut client_write) = tokio::io::split(client_end);
+        let mut client_lines = BufReader::new(client_read).lines();
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "id": 0, "method": "initialize",
+                    "params": {"protocolVersion": 1, "clientCapabilities": {}}}),
+        )
+        .await;
+        let init: Value = next(&mut client_lines).await;
+        assert_eq!(init["result"]["protocolVersion"], 1);
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "session/prompt",
+                    "params": {"sessionId": "s1", "prompt": [{"type": "text", "text": "hi"}]}}),
+        )
+        .await;
+        let permission: Value = next(&mut client_lines).await;
+        assert_eq!(permission["method"], "session/request_permission");
+        assert_eq!(permission["params"]["toolCall"]["toolCallId"], "call_1");
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "id": permission["id"],
+                    "result": {"outcome": {"outcome": "selected", "optionId": "allow"}}}),
+        )
+        .await;
+        let update: Value = next(&mut client_lines).await;
+        assert_eq!(update["method"], "session/update");
+        assert_eq!(
+            update["params"]["update"],
+            json!({"sessionUpdate": "agent_message_chunk", "content": {"type": "text", "text": "hi"}})
+        );
+        let done: Value = next(&mut client_lines).await;
+        assert_eq!(done["id"], 1);
+        assert_eq!(done["result"]["stopReason"], "end_turn");
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "session/load", "params": {}}),
+        )
+        .await;
+        let unknown: Value = next(&mut client_lines).await;
+        assert_eq!(unknown["error"]["code"], error_codes::METHOD_NOT_FOUND);
+
+        // The read half keeps the stream open, so close our side explicitly.
+        client_write.shutdown().await.unwrap();
+        serve.await.unwrap().unwrap();
+        let mut rest = String::new();
+        client_lines
+            .into_inner()
+            .read_to_string(&mut rest)
+            .await
+            .unwrap();
+        assert!(rest.is_empty());
+    }
+}
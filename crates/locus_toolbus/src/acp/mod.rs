@@ -1,4 +1,19 @@
-//! Agent Communication Protocol (ACP) client — future.
+//! Agent Client Protocol (ACP): lets an editor such as Zed drive locus as its agent.
 //!
-//! External ACP tools will be registered at runtime when servers connect.
-//! Schemas will be stored in LocusGraph for discovery. Not part of `register_defaults()`.
+//! The editor starts `locus acp` and speaks JSON-RPC over stdio: it opens sessions,
+//! sends prompts and cancels them; locus streams the reply, tool calls and file
+//! diffs back as `session/update` notifications and asks before running a tool
+//! with `session/request_permission`.
+//!
+//! This module is the protocol side, independent of the agent loop:
+//! - **protocol**: message types, with the specification's field names
+//! - **connection**: [AcpConnection] routes requests to an [AcpAgent] and gives it
+//!   an [AcpClient] for notifications and permission requests
+//!
+//! The agent itself (sessions backed by the runtime) is `locus_runtime::acp`.
+
+pub mod connection;
+pub mod protocol;
+
+pub use connection::{AcpAgent, AcpClient, AcpConnection, AcpError};
+pub use protocol::PROTOCOL_VERSION;
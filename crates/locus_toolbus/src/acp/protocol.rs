@@ -0,0 +1,401 @@
+//! ACP message types: the subset of the Agent Client Protocol locus speaks.
+//!
+//! Field names follow the specification (camelCase on the wire). Content kinds
+//! and updates locus does not handle deserialize to an `Other` variant instead of
+//! failing the whole message.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// ACP protocol version (an integer, bumped only for breaking changes).
+pub const PROTOCOL_VERSION: u32 = 1;
+
+// =============================================================================
+// Initialization
+// =============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeRequest {
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub client_capabilities: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResponse {
+    pub protocol_version: u32,
+    pub agent_capabilities: AgentCapabilities,
+    /// Ways to authenticate; empty when none is needed.
+    #[serde(default)]
+    pub auth_methods: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCapabilities {
+    #[serde(default)]
+    pub load_session: bool,
+    #[serde(default)]
+    pub prompt_capabilities: PromptCapabilities,
+}
+
+/// Content kinds beyond text and resource links the agent accepts in prompts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptCapabilities {
+    #[serde(default)]
+    pub image: bool,
+    #[serde(default)]
+    pub audio: bool,
+    #[serde(default)]
+    pub embedded_context: bool,
+}
+
+// =============================================================================
+// Sessions and prompts
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSessionRequest {
+    /// Absolute path the session works in.
+    pub cwd: PathBuf,
+    /// MCP servers the client wants the agent to connect to.
+    #[serde(default)]
+    pub mcp_servers: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSessionResponse {
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptRequest {
+    pub session_id: String,
+    pub prompt: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptResponse {
+    pub stop_reason: StopReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    EndTurn,
+    MaxTokens,
+    MaxTurnRequests,
+    Refusal,
+    Cancelled,
+}
+
+/// `session/cancel`: stop the running prompt of a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelNotification {
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    /// A file the user referenced without sending its contents.
+    #[serde(rename_all = "camelCase")]
+    ResourceLink {
+        uri: String,
+        name: String,
+    },
+    /// A file sent along with the prompt.
+    Resource {
+        resource: EmbeddedResource,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl ContentBlock {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    pub uri: String,
+    /// None for binary resources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+// =============================================================================
+// Session updates (agent → client notifications)
+// =============================================================================
+
+/// `session/update`: progress of a running prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionNotification {
+    pub session_id: String,
+    pub update: SessionUpdate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "sessionUpdate", rename_all = "snake_case")]
+pub enum SessionUpdate {
+    AgentMessageChunk {
+        content: ContentBlock,
+    },
+    AgentThoughtChunk {
+        content: ContentBlock,
+    },
+    ToolCall(ToolCall),
+    ToolCallUpdate(ToolCallUpdate),
+    Plan {
+        entries: Vec<PlanEntry>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCall {
+    pub tool_call_id: String,
+    pub title: String,
+    pub kind: ToolKind,
+    pub status: ToolCallStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<ToolCallContent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<ToolCallLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_input: Option<Value>,
+}
+
+/// Changes to a reported tool call; fields left None stay as they were.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallUpdate {
+    pub tool_call_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ToolKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ToolCallStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Vec<ToolCallContent>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Vec<ToolCallLocation>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_input: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_output: Option<Value>,
+}
+
+/// What a tool does, so the client can pick an icon and a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolKind {
+    Read,
+    Edit,
+    Delete,
+    Move,
+    Search,
+    Execute,
+    Think,
+    Fetch,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolCallContent {
+    Content {
+        content: ContentBlock,
+    },
+    /// A proposed or applied file change; `old_text` is None for a new file.
+    #[serde(rename_all = "camelCase")]
+    Diff {
+        path: PathBuf,
+        old_text: Option<String>,
+        new_text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallLocation {
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanEntry {
+    pub content: String,
+    pub priority: PlanEntryPriority,
+    pub status: PlanEntryStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanEntryPriority {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanEntryStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+// =============================================================================
+// Permission requests (agent → client requests)
+// =============================================================================
+
+/// `session/request_permission`: ask the user before a tool call runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPermissionRequest {
+    pub session_id: String,
+    pub tool_call: ToolCallUpdate,
+    pub options: Vec<PermissionOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionOption {
+    pub option_id: String,
+    pub name: String,
+    pub kind: PermissionOptionKind,
+}
+
+impl PermissionOption {
+    pub fn new(
+        option_id: impl Into<String>,
+        name: impl Into<String>,
+        kind: PermissionOptionKind,
+    ) -> Self {
+        Self {
+            option_id: option_id.into(),
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionOptionKind {
+    AllowOnce,
+    AllowAlways,
+    RejectOnce,
+    RejectAlways,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPermissionResponse {
+    pub outcome: RequestPermissionOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RequestPermissionOutcome {
+    /// The prompt was cancelled before the user answered.
+    Cancelled,
+    #[serde(rename_all = "camelCase")]
+    Selected { option_id: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_wire_format() {
+        let update = SessionUpdate::ToolCall(ToolCall {
+            tool_call_id: "call_1".to_string(),
+            title: "edit_file src/lib.rs".to_string(),
+            kind: ToolKind::Edit,
+            status: ToolCallStatus::InProgress,
+            content: vec![ToolCallContent::Diff {
+                path: PathBuf::from("/repo/src/lib.rs"),
+                old_text: Some("a - b".to_string()),
+                new_text: "a + b".to_string(),
+            }],
+            locations: vec![],
+            raw_input: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({
+                "sessionUpdate": "tool_call",
+                "toolCallId": "call_1",
+                "title": "edit_file src/lib.rs",
+                "kind": "edit",
+                "status": "in_progress",
+                "content": [{"type": "diff", "path": "/repo/src/lib.rs",
+                             "oldText": "a - b", "newText": "a + b"}],
+            })
+        );
+
+        let outcome: RequestPermissionResponse = serde_json::from_value(
+            json!({"outcome": {"outcome": "selected", "optionId": "allow"}}),
+        )
+        .unwrap();
+        assert_eq!(
+            outcome.outcome,
+            RequestPermissionOutcome::Selected {
+                option_id: "allow".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_content_is_kept_as_other() {
+        let prompt: PromptRequest = serde_json::from_value(json!({
+            "sessionId": "s1",
+            "prompt": [
+                {"type": "text", "text": "Explain this"},
+                {"type": "image", "data": "…", "mimeType": "image/png"},
+                {"type": "resource", "resource": {"uri": "file:///repo/a.rs", "text": "fn a() {}"}},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(prompt.prompt[0], ContentBlock::text("Explain this"));
+        assert_eq!(prompt.prompt[1], ContentBlock::Other);
+        assert!(matches!(prompt.prompt[2], ContentBlock::Resource { .. }));
+    }
+}
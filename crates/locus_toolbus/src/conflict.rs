@@ -0,0 +1,278 @@
+//! Edit conflicts for [crate::ToolBus::call].
+//!
+//! - The bus keeps each file's content as the agent last read or wrote it (its
+//!   baseline), from `read`, `edit_file`, `create_file`, `undo_edit` and
+//!   `file_history` restores; other tools that may change files (shell commands,
+//!   checkouts, renames) refresh every tracked baseline, since those changes are
+//!   the agent's own
+//! - Before `edit_file` or `create_file` writes a file whose content no longer
+//!   matches its baseline, the user changed it: the bus builds a
+//!   [ConflictRequest] (base, the user's version, the agent's intended version and
+//!   their three-way merge) and asks the [ConflictResolver] set on the bus (the
+//!   TUI conflict dialog)
+//! - [ConflictDecision::Rebase] writes the merge, [ConflictDecision::Overwrite]
+//!   the agent's version, [ConflictDecision::Abort] leaves the file and fails the
+//!   call; without a resolver, clean merges are rebased and clashing ones aborted
+//! - Files the agent never read or wrote are not checked
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+
+pub use locus_core::{ConflictDecision, ConflictRequest, ConflictResponse};
+
+use crate::permission::{ApprovalPreview, Permission};
+use crate::tools::{EditFileArgs, edit_file};
+
+/// Answers edit conflicts; see the module docs.
+#[async_trait]
+pub trait ConflictResolver: Send + Sync {
+    async fn resolve(&self, request: ConflictRequest) -> ConflictDecision;
+}
+
+/// How a conflicting write was settled, added to the tool result as `conflict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    pub decision: ConflictDecision,
+    /// Hunks left between conflict markers (rebase only).
+    pub conflicts: usize,
+}
+
+impl Resolution {
+    pub fn to_json(self) -> JsonValue {
+        let note = match self.decision {
+            ConflictDecision::Overwrite => {
+                "The user had changed this file since you last read it; they chose to overwrite their changes with your edit.".to_string()
+            }
+            _ if self.conflicts > 0 => format!(
+                "The user had changed this file since you last read it. Your edit was merged with their changes; {} hunk(s) clash and were left between '<<<<<<< user' and '>>>>>>> agent' markers. Read the file and resolve them.",
+                self.conflicts
+            ),
+            _ => "The user had changed this file since you last read it. Your edit was merged with their changes; read the file before editing it again.".to_string(),
+        };
+        json!({
+            "resolution": self.decision,
+            "conflicts": self.conflicts,
+            "note": note,
+        })
+    }
+}
+
+/// Baselines plus the resolver asked when an edit meets the user's changes.
+#[derive(Default)]
+pub struct ConflictGuard {
+    baselines: Mutex<HashMap<PathBuf, String>>,
+    resolver: RwLock<Option<Arc<dyn ConflictResolver>>>,
+}
+
+impl ConflictGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_resolver(&self, resolver: Arc<dyn ConflictResolver>) {
+        if let Ok(mut slot) = self.resolver.write() {
+            *slot = Some(resolver);
+        }
+    }
+
+    /// The file as the agent last saw it, if it is tracked.
+    pub fn baseline(&self, path: &Path) -> Option<String> {
+        self.baselines
+            .lock()
+            .ok()
+            .and_then(|b| b.get(path).cloned())
+    }
+
+    fn set_baseline(&self, path: PathBuf, content: Option<String>) {
+        if let Ok(mut baselines) = self.baselines.lock() {
+            match content {
+                Some(content) => baselines.insert(path, content),
+                None => baselines.remove(&path),
+            };
+        }
+    }
+
+    /// Check a call before it runs. Returns the arguments to run it with: as
+    /// given, or rewritten to write the resolved content.
+    pub async fn check(
+        &self,
+        repo_root: &Path,
+        tool_name: &str,
+        args: JsonValue,
+    ) -> Result<(JsonValue, Option<Resolution>)> {
+        if !matches!(tool_name, "edit_file" | "create_file") {
+            return Ok((args, None));
+        }
+        let Some(path) = args
+            .get("path")
+            .and_then(|p| p.as_str())
+            .map(str::to_string)
+        else {
+            return Ok((args, None));
+        };
+        let full = resolve(repo_root, &path);
+        let Some(base) = self.baseline(&full) else {
+            return Ok((args, None));
+        };
+        let Ok(user) = std::fs::read_to_string(&full) else {
+            return Ok((args, None));
+        };
+        if user == base {
+            return Ok((args, None));
+        }
+        // An edit that does not apply to the base is left to fail (or apply) on disk.
+        let Some(agent) = planned_content(tool_name, &base, &args) else {
+            return Ok((args, None));
+        };
+        if agent == user {
+            return Ok((args, None));
+        }
+
+        let relative = full
+            .strip_prefix(normalize(repo_root))
+            .unwrap_or(&full)
+            .to_string_lossy()
+            .to_string();
+        let request = ConflictRequest::new(tool_name, relative, base, user, agent);
+        let resolver = self.resolver.read().ok().and_then(|r| r.clone());
+        let decision = match resolver {
+            Some(resolver) => resolver.resolve(request.clone()).await,
+            None if request.conflicts == 0 => ConflictDecision::Rebase,
+            None => ConflictDecision::Abort,
+        };
+        tracing::info!(
+            "Edit conflict on {}: {:?} ({} clashing hunks)",
+            request.path,
+            decision,
+            request.conflicts
+        );
+
+        let (content, conflicts) = match decision {
+            ConflictDecision::Abort => {
+                return Err(anyhow!(
+                    "{} was changed outside the agent since you last read it, and the edit was aborted to keep those changes. Read the file again and redo the edit on its current content.",
+                    request.path
+                ));
+            }
+            ConflictDecision::Rebase => (request.merged, request.conflicts),
+            ConflictDecision::Overwrite => (request.agent, 0),
+        };
+        let args = match tool_name {
+            "create_file" => {
+                let mut args = args;
+                args["content"] = JsonValue::String(content);
+                args
+            }
+            // Overwrite mode writes the whole file and records it for undo.
+            _ => json!({ "path": path, "new_string": content }),
+        };
+        Ok((
+            args,
+            Some(Resolution {
+                decision,
+                conflicts,
+            }),
+        ))
+    }
+
+    /// Update baselines after a successful call with `path` and `action` arguments:
+    /// files the agent read or wrote take their current content; other tools that
+    /// may change files refresh every tracked file.
+    pub fn observe(
+        &self,
+        repo_root: &Path,
+        tool_name: &str,
+        permission: Permission,
+        path: Option<&str>,
+        action: Option<&str>,
+    ) {
+        let observed = match tool_name {
+            "read" | "edit_file" | "create_file" | "undo_edit" => true,
+            "file_history" => action == Some("restore"),
+            // Commands, checkouts and renames may change any file, as the agent's own edits.
+            _ if permission != Permission::Read && permission != Permission::Network => {
+                self.refresh();
+                false
+            }
+            _ => false,
+        };
+        if let (true, Some(path)) = (observed, path) {
+            let full = resolve(repo_root, path);
+            if full.is_file() {
+                self.set_baseline(full.clone(), std::fs::read_to_string(&full).ok());
+            }
+        }
+    }
+
+    /// Re-read every tracked file; files that are gone are no longer tracked.
+    pub fn refresh(&self) {
+        let paths: Vec<PathBuf> = match self.baselines.lock() {
+            Ok(baselines) => baselines.keys().cloned().collect(),
+            Err(_) => return,
+        };
+        for path in paths {
+            let content = std::fs::read_to_string(&path).ok();
+            self.set_baseline(path, content);
+        }
+    }
+}
+
+/// The change an `edit_file` or `create_file` call would make, for the approval
+/// dialog. None for other tools, or when the edit would fail anyway.
+pub(crate) fn preview_change(
+    repo_root: &Path,
+    tool_name: &str,
+    args: &JsonValue,
+) -> Option<ApprovalPreview> {
+    if !matches!(tool_name, "edit_file" | "create_file") {
+        return None;
+    }
+    let path = args.get("path").and_then(|p| p.as_str())?;
+    let before = std::fs::read_to_string(resolve(repo_root, path)).unwrap_or_default();
+    let after = planned_content(tool_name, &before, args)?;
+    Some(ApprovalPreview {
+        path: path.to_string(),
+        before,
+        after,
+    })
+}
+
+/// What `tool_name` would write given the file holds `base`.
+fn planned_content(tool_name: &str, base: &str, args: &JsonValue) -> Option<String> {
+    match tool_name {
+        "create_file" => args
+            .get("content")
+            .and_then(|c| c.as_str())
+            .map(str::to_string),
+        _ => {
+            let args: EditFileArgs = serde_json::from_value(args.clone()).ok()?;
+            edit_file::apply_edits(base, &args).ok()
+        }
+    }
+}
+
+/// `path` (relative to `repo_root` or absolute) as a normalized absolute path.
+fn resolve(repo_root: &Path, path: &str) -> PathBuf {
+    normalize(&repo_root.join(path))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component.as_os_str());
+                }
+            }
+            _ => normalized.push(component.as_os_str()),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,789 @@
+//! Where `execute` tools run: on the host, inside a Docker container, or on a
+//! remote machine over SSH.
+//!
+//! - [ExecutionBackend::Local] runs `bash` and `handoff` commands directly
+//! - [ExecutionBackend::Docker] runs them with `docker run --rm` in the configured
+//!   image; the repo is bind-mounted at [CONTAINER_WORKDIR] and the working
+//!   directory is mapped into it, so paths outside the repo are refused
+//! - The container has no network unless `network = true`, and runs as the owner
+//!   of the repo root (unix) so created files keep the usual ownership
+//! - With `write_tools = true`, `create_file` and `edit_file` also write through
+//!   the container, so a read-only mount or image policy applies to them too
+//!   (undo and history restore still write on the host)
+//! - A command that times out has its container removed (`docker rm -f`)
+//! - [ExecutionBackend::Ssh] runs them in `remote_dir` on another host while files
+//!   are still edited locally; with `sync = true` (default) the repo is pushed with
+//!   `rsync --delete` before every command, skipping `exclude` (`.git`, `target`,
+//!   `node_modules`), so remote build output survives between commands
+//! - [ExecutionBackend::Devcontainer] runs them in the project's dev container:
+//!   `devcontainer up` before every command (it reuses a running container) and
+//!   then `docker exec` as the configured `remoteUser` in the mapped workspace
+//!   folder; files are edited on the host and reach the container through the
+//!   workspace mount
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Mount point of the repository inside the container.
+pub const CONTAINER_WORKDIR: &str = "/workspace";
+
+static NEXT_CONTAINER_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    #[error("Path is outside the repository mirrored by the execution backend: {0}")]
+    OutsideMount(String),
+
+    #[error("Container command failed: {0}")]
+    ContainerFailed(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Execution backend for shell commands (and optionally file writes).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ExecutionBackend {
+    #[default]
+    Local,
+    Docker(DockerBackend),
+    Ssh(SshBackend),
+    Devcontainer(DevcontainerBackend),
+}
+
+/// `docker run` settings for [ExecutionBackend::Docker].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DockerBackend {
+    /// Image to run commands in (e.g. `rust:1.85`).
+    pub image: String,
+    /// Docker CLI binary (`docker`, `podman`, ...).
+    #[serde(default = "default_docker")]
+    pub docker: String,
+    /// Shell inside the image used for `-c <command>`.
+    #[serde(default = "default_shell")]
+    pub shell: String,
+    /// Allow network access (default: `--network none`).
+    #[serde(default)]
+    pub network: bool,
+    /// Also route `create_file` / `edit_file` writes through the container.
+    #[serde(default)]
+    pub write_tools: bool,
+    /// `--user` value; defaults to the owner of the repo root on unix.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Extra arguments for `docker run`, placed before the image.
+    #[serde(default)]
+    pub run_args: Vec<String>,
+}
+
+fn default_docker() -> String {
+    "docker".to_string()
+}
+
+fn default_shell() -> String {
+    "/bin/sh".to_string()
+}
+
+impl DockerBackend {
+    pub fn new(image: impl Into<String>) -> Self {
+        Self {
+            image: image.into(),
+            docker: default_docker(),
+            shell: default_shell(),
+            network: false,
+            write_tools: false,
+            user: None,
+            run_args: Vec::new(),
+        }
+    }
+
+    pub fn with_docker(mut self, docker: impl Into<String>) -> Self {
+        self.docker = docker.into();
+        self
+    }
+
+    pub fn with_shell(mut self, shell: impl Into<String>) -> Self {
+        self.shell = shell.into();
+        self
+    }
+
+    pub fn with_network(mut self, network: bool) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn with_write_tools(mut self, write_tools: bool) -> Self {
+        self.write_tools = write_tools;
+        self
+    }
+
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn with_run_arg(mut self, arg: impl Into<String>) -> Self {
+        self.run_args.push(arg.into());
+        self
+    }
+
+    /// Backend from `LOCUS_DOCKER_IMAGE` (required), `LOCUS_DOCKER_NETWORK` and
+    /// `LOCUS_DOCKER_WRITE_TOOLS` (`1` / `true`).
+    pub fn from_env() -> Option<Self> {
+        let image = std::env::var("LOCUS_DOCKER_IMAGE").ok()?;
+        if image.trim().is_empty() {
+            return None;
+        }
+        let flag = |key: &str| {
+            std::env::var(key).is_ok_and(|v| {
+                matches!(
+                    v.trim().to_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+        };
+        Some(
+            Self::new(image.trim())
+                .with_network(flag("LOCUS_DOCKER_NETWORK"))
+                .with_write_tools(flag("LOCUS_DOCKER_WRITE_TOOLS")),
+        )
+    }
+
+    /// `docker run` arguments up to and including the image.
+    fn run_args(
+        &self,
+        repo_root: &Path,
+        working_dir: &Path,
+        name: &str,
+        interactive: bool,
+    ) -> Result<Vec<String>, ExecutionError> {
+        let workdir = container_path(repo_root, working_dir)?;
+        let mut args = vec!["run".to_string(), "--rm".to_string()];
+        if interactive {
+            args.push("-i".to_string());
+        }
+        args.extend([
+            "--name".to_string(),
+            name.to_string(),
+            "-v".to_string(),
+            format!("{}:{}", repo_root.display(), CONTAINER_WORKDIR),
+            "-w".to_string(),
+            workdir,
+        ]);
+        if !self.network {
+            args.extend(["--network".to_string(), "none".to_string()]);
+        }
+        if let Some(user) = self.user.clone().or_else(|| repo_owner(repo_root)) {
+            args.extend(["--user".to_string(), user]);
+        }
+        args.extend(self.run_args.iter().cloned());
+        args.push(self.image.clone());
+        Ok(args)
+    }
+}
+
+/// Remote host settings for [ExecutionBackend::Ssh].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SshBackend {
+    /// `host` or `user@host` (an alias from `~/.ssh/config` works too).
+    pub host: String,
+    /// Checkout of the repository on the remote host.
+    pub remote_dir: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Private key passed as `ssh -i`.
+    #[serde(default)]
+    pub identity_file: Option<PathBuf>,
+    /// Push local changes with rsync before each command.
+    #[serde(default = "default_sync")]
+    pub sync: bool,
+    /// Paths rsync leaves alone (neither copied nor deleted).
+    #[serde(default = "default_sync_exclude")]
+    pub exclude: Vec<String>,
+    /// Extra `ssh` arguments (e.g. `-o ConnectTimeout=5`).
+    #[serde(default)]
+    pub ssh_args: Vec<String>,
+}
+
+fn default_sync() -> bool {
+    true
+}
+
+fn default_sync_exclude() -> Vec<String> {
+    [".git", "target", "node_modules"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+impl SshBackend {
+    pub fn new(host: impl Into<String>, remote_dir: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            remote_dir: remote_dir.into(),
+            port: None,
+            identity_file: None,
+            sync: default_sync(),
+            exclude: default_sync_exclude(),
+            ssh_args: Vec::new(),
+        }
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn with_identity_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.identity_file = Some(path.into());
+        self
+    }
+
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    pub fn with_exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    pub fn with_ssh_arg(mut self, arg: impl Into<String>) -> Self {
+        self.ssh_args.push(arg.into());
+        self
+    }
+
+    /// Backend from `LOCUS_SSH_HOST` and `LOCUS_SSH_DIR` (both required),
+    /// `LOCUS_SSH_PORT`, `LOCUS_SSH_KEY` and `LOCUS_SSH_SYNC` (`0` / `false` to skip rsync).
+    pub fn from_env() -> Option<Self> {
+        let var = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let mut ssh = Self::new(var("LOCUS_SSH_HOST")?, var("LOCUS_SSH_DIR")?);
+        if let Some(port) = var("LOCUS_SSH_PORT").and_then(|p| p.parse().ok()) {
+            ssh = ssh.with_port(port);
+        }
+        if let Some(key) = var("LOCUS_SSH_KEY") {
+            ssh = ssh.with_identity_file(key);
+        }
+        if let Some(sync) = var("LOCUS_SSH_SYNC") {
+            ssh = ssh.with_sync(!matches!(
+                sync.to_lowercase().as_str(),
+                "0" | "false" | "no" | "off"
+            ));
+        }
+        Some(ssh)
+    }
+
+    /// `ssh` and its options, without the host.
+    fn ssh_command(&self) -> Vec<String> {
+        let mut args = vec![
+            "ssh".to_string(),
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+        ];
+        if let Some(port) = self.port {
+            args.extend(["-p".to_string(), port.to_string()]);
+        }
+        if let Some(key) = &self.identity_file {
+            args.extend(["-i".to_string(), key.display().to_string()]);
+        }
+        args.extend(self.ssh_args.iter().cloned());
+        args
+    }
+
+    /// Local shell script: optional rsync of `repo_root`, then the command over ssh
+    /// in the remote counterpart of `working_dir`.
+    fn script(
+        &self,
+        repo_root: &Path,
+        working_dir: &Path,
+        shell_command: &str,
+    ) -> Result<String, ExecutionError> {
+        let remote_dir = self.remote_dir.trim_end_matches('/');
+        let workdir = map_path(repo_root, working_dir, remote_dir)?;
+        let ssh = self.ssh_command();
+        let mut script = String::new();
+        if self.sync {
+            let mut rsync = vec![
+                "rsync".to_string(),
+                "-az".to_string(),
+                "--delete".to_string(),
+            ];
+            rsync.extend(self.exclude.iter().map(|e| format!("--exclude={}", e)));
+            rsync.extend([
+                "-e".to_string(),
+                ssh.iter()
+                    .map(|a| shell_quote(a))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                format!("{}/", repo_root.display()),
+                format!("{}:{}/", self.host, remote_dir),
+            ]);
+            script.push_str(&join_quoted(&rsync));
+            script.push_str(" && ");
+        }
+        let remote = format!("cd {} && {}", shell_quote(&workdir), shell_command);
+        let mut run = ssh;
+        run.extend([self.host.clone(), remote]);
+        script.push_str("exec ");
+        script.push_str(&join_quoted(&run));
+        Ok(script)
+    }
+}
+
+/// Dev container settings for [ExecutionBackend::Devcontainer].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevcontainerBackend {
+    /// `devcontainer.json` to use; the CLI finds `.devcontainer/` itself when None.
+    #[serde(default)]
+    pub config: Option<PathBuf>,
+    /// Dev Containers CLI binary.
+    #[serde(default = "default_devcontainer")]
+    pub devcontainer: String,
+    /// Docker CLI binary used for `exec`.
+    #[serde(default = "default_docker")]
+    pub docker: String,
+    /// Shell inside the container used for `-c <command>`.
+    #[serde(default = "default_shell")]
+    pub shell: String,
+}
+
+fn default_devcontainer() -> String {
+    "devcontainer".to_string()
+}
+
+impl Default for DevcontainerBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DevcontainerBackend {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            devcontainer: default_devcontainer(),
+            docker: default_docker(),
+            shell: default_shell(),
+        }
+    }
+
+    pub fn with_config(mut self, config: impl Into<PathBuf>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    pub fn with_devcontainer(mut self, devcontainer: impl Into<String>) -> Self {
+        self.devcontainer = devcontainer.into();
+        self
+    }
+
+    pub fn with_docker(mut self, docker: impl Into<String>) -> Self {
+        self.docker = docker.into();
+        self
+    }
+
+    /// Backend from `LOCUS_DEVCONTAINER`: `1` / `true` uses the CLI's own config
+    /// lookup, any other value is taken as the path to `devcontainer.json`.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var("LOCUS_DEVCONTAINER").ok()?;
+        match value.trim() {
+            "" | "0" | "false" | "no" | "off" => None,
+            "1" | "true" | "yes" | "on" => Some(Self::new()),
+            path => Some(Self::new().with_config(path)),
+        }
+    }
+
+    /// Local shell script: `devcontainer up`, then the command via `docker exec`
+    /// in the container counterpart of `working_dir`.
+    fn script(
+        &self,
+        repo_root: &Path,
+        working_dir: &Path,
+        shell_command: &str,
+    ) -> Result<String, ExecutionError> {
+        // Relative to the workspace folder, which is only known after `up`.
+        let relative = map_path(repo_root, working_dir, "/")?;
+        let mut up = vec![
+            self.devcontainer.clone(),
+            "up".to_string(),
+            "--workspace-folder".to_string(),
+            repo_root.display().to_string(),
+        ];
+        if let Some(config) = &self.config {
+            up.extend(["--config".to_string(), config.display().to_string()]);
+        }
+        let exec = join_quoted(&[
+            self.shell.clone(),
+            "-c".to_string(),
+            shell_command.to_string(),
+        ]);
+        // `up` prints its result as one JSON line: containerId, remoteUser, remoteWorkspaceFolder.
+        Ok(format!(
+            concat!(
+                "up=$({up}) || exit $?\n",
+                r#"field() {{ printf '%s\n' "$up" | tail -n 1 | sed -n "s/.*\"$1\":\"\([^\"]*\)\".*/\1/p"; }}"#,
+                "\n",
+                r#"id=$(field containerId); user=$(field remoteUser); folder=$(field remoteWorkspaceFolder)"#,
+                "\n",
+                r#"[ -n "$id" ] || {{ printf '%s\n' "$up" >&2; exit 1; }}"#,
+                "\n",
+                r#"exec {docker} exec ${{user:+-u "$user"}} -w "$folder"{relative} "$id" {exec}"#,
+            ),
+            up = join_quoted(&up),
+            docker = shell_quote(&self.docker),
+            relative = shell_quote(&relative),
+            exec = exec,
+        ))
+    }
+}
+
+/// `arg` as one POSIX shell word.
+pub(crate) fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+fn join_quoted(args: &[String]) -> String {
+    args.iter()
+        .map(|a| shell_quote(a))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A command prepared by [ExecutionBackend::command].
+pub struct PreparedCommand {
+    pub command: Command,
+    /// Container name when the command runs in Docker, for cleanup.
+    pub container: Option<String>,
+}
+
+impl ExecutionBackend {
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local)
+    }
+
+    /// Docker settings when file writes should go through the container.
+    fn write_container(&self) -> Option<&DockerBackend> {
+        match self {
+            Self::Docker(docker) if docker.write_tools => Some(docker),
+            _ => None,
+        }
+    }
+
+    /// Shell command `shell_command` in `working_dir` (the repo root in a container
+    /// when None). `local_shell` is the host shell and its flag (`/bin/bash -c`).
+    pub fn command(
+        &self,
+        repo_root: &Path,
+        working_dir: Option<&Path>,
+        local_shell: (&str, &str),
+        shell_command: &str,
+    ) -> Result<PreparedCommand, ExecutionError> {
+        match self {
+            Self::Local => {
+                let (shell, flag) = local_shell;
+                let mut command = Command::new(shell);
+                command.arg(flag).arg(shell_command);
+                if let Some(dir) = working_dir {
+                    command.current_dir(dir);
+                }
+                Ok(PreparedCommand {
+                    command,
+                    container: None,
+                })
+            }
+            Self::Docker(docker) => {
+                let name = container_name();
+                let mut command = Command::new(&docker.docker);
+                command
+                    .args(docker.run_args(
+                        repo_root,
+                        working_dir.unwrap_or(repo_root),
+                        &name,
+                        false,
+                    )?)
+                    .arg(&docker.shell)
+                    .arg("-c")
+                    .arg(shell_command)
+                    .stdin(Stdio::null());
+                Ok(PreparedCommand {
+                    command,
+                    container: Some(name),
+                })
+            }
+            Self::Devcontainer(devcontainer) => {
+                let script = devcontainer.script(
+                    repo_root,
+                    working_dir.unwrap_or(repo_root),
+                    shell_command,
+                )?;
+                let mut command = Command::new("/bin/sh");
+                command
+                    .arg("-c")
+                    .arg(script)
+                    .current_dir(repo_root)
+                    .stdin(Stdio::null());
+                Ok(PreparedCommand {
+                    command,
+                    container: None,
+                })
+            }
+            Self::Ssh(ssh) => {
+                let script =
+                    ssh.script(repo_root, working_dir.unwrap_or(repo_root), shell_command)?;
+                let mut command = Command::new("/bin/sh");
+                command
+                    .arg("-c")
+                    .arg(script)
+                    .current_dir(repo_root)
+                    .stdin(Stdio::null());
+                Ok(PreparedCommand {
+                    command,
+                    container: None,
+                })
+            }
+        }
+    }
+
+    /// Remove a container left behind by a command that was cut off.
+    pub async fn cleanup(&self, container: Option<&str>) {
+        if let (Self::Docker(docker), Some(name)) = (self, container) {
+            let _ = Command::new(&docker.docker)
+                .args(["rm", "-f", name])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+        }
+    }
+
+    /// Write `content` to `path` (absolute, inside `repo_root`), through the
+    /// container when `write_tools` is on.
+    pub async fn write_file(
+        &self,
+        repo_root: &Path,
+        path: &Path,
+        content: &[u8],
+    ) -> Result<(), ExecutionError> {
+        let Some(docker) = self.write_container() else {
+            tokio::fs::write(path, content).await?;
+            return Ok(());
+        };
+        let target = container_path(repo_root, path)?;
+        let name = container_name();
+        let mut child = Command::new(&docker.docker)
+            .args(docker.run_args(repo_root, repo_root, &name, true)?)
+            .arg(&docker.shell)
+            .args(["-c", "cat > \"$1\"", "sh", &target])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(ExecutionError::ContainerFailed(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ))
+        }
+    }
+}
+
+fn container_name() -> String {
+    format!(
+        "locus-{}-{}",
+        std::process::id(),
+        NEXT_CONTAINER_ID.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Map a host path inside `repo_root` to its path in the container.
+pub fn container_path(repo_root: &Path, host_path: &Path) -> Result<String, ExecutionError> {
+    map_path(repo_root, host_path, CONTAINER_WORKDIR)
+}
+
+/// Map a host path inside `repo_root` to the same path under `mount`.
+fn map_path(repo_root: &Path, host_path: &Path, mount: &str) -> Result<String, ExecutionError> {
+    let relative = host_path
+        .strip_prefix(repo_root)
+        .or_else(|_| {
+            // Relative working dirs are taken as repo-relative.
+            if host_path.is_relative() {
+                Ok(host_path)
+            } else {
+                Err(())
+            }
+        })
+        .map_err(|_| ExecutionError::OutsideMount(host_path.display().to_string()))?;
+    let mut path = PathBuf::from(mount);
+    for component in relative.components() {
+        match component {
+            std::path::Component::Normal(part) => path.push(part),
+            std::path::Component::CurDir => {}
+            _ => {
+                return Err(ExecutionError::OutsideMount(
+                    host_path.display().to_string(),
+                ));
+            }
+        }
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(unix)]
+fn repo_owner(repo_root: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = std::fs::metadata(repo_root).ok()?;
+    Some(format!("{}:{}", meta.uid(), meta.gid()))
+}
+
+#[cfg(not(unix))]
+fn repo_owner(_repo_root: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_path_maps_into_mount() {
+        let root = Path::new("/home/ada/repo");
+        assert_eq!(container_path(root, root).unwrap(), "/workspace");
+        assert_eq!(
+            container_path(root, &root.join("crates/core")).unwrap(),
+            "/workspace/crates/core"
+        );
+        assert_eq!(
+            container_path(root, Path::new("./src")).unwrap(),
+            "/workspace/src"
+        );
+        assert!(container_path(root, Path::new("/etc")).is_err());
+        assert!(container_path(root, Path::new("../other")).is_err());
+    }
+
+    #[test]
+    fn test_docker_run_args() {
+        let root = Path::new("/home/ada/repo");
+        let docker = DockerBackend::new("rust:1.85")
+            .with_user("1000:1000")
+            .with_run_arg("--memory=2g");
+        let args = docker
+            .run_args(root, &root.join("src"), "locus-test", false)
+            .unwrap();
+        assert_eq!(
+            args,
+            [
+                "run",
+                "--rm",
+                "--name",
+                "locus-test",
+                "-v",
+                "/home/ada/repo:/workspace",
+                "-w",
+                "/workspace/src",
+                "--network",
+                "none",
+                "--user",
+                "1000:1000",
+                "--memory=2g",
+                "rust:1.85",
+            ]
+        );
+
+        let args = docker
+            .with_network(true)
+            .run_args(root, root, "locus-test", true)
+            .unwrap();
+        assert!(args.contains(&"-i".to_string()));
+        assert!(!args.contains(&"--network".to_string()));
+    }
+
+    #[test]
+    fn test_ssh_script_syncs_then_runs_remotely() {
+        let root = Path::new("/home/ada/repo");
+        let ssh = SshBackend::new("ada@gpu-box", "/srv/repo/")
+            .with_port(2222)
+            .with_identity_file("/home/ada/.ssh/id_gpu");
+        let script = ssh
+            .script(root, &root.join("crates/core"), "cargo test 'a b'")
+            .unwrap();
+        assert_eq!(
+            script,
+            concat!(
+                "rsync -az --delete --exclude=.git --exclude=target --exclude=node_modules ",
+                "-e 'ssh -o BatchMode=yes -p 2222 -i /home/ada/.ssh/id_gpu' ",
+                "/home/ada/repo/ ada@gpu-box:/srv/repo/ && ",
+                "exec ssh -o BatchMode=yes -p 2222 -i /home/ada/.ssh/id_gpu ada@gpu-box ",
+                r#"'cd /srv/repo/crates/core && cargo test '\''a b'\'''"#,
+            )
+        );
+
+        let script = ssh.with_sync(false).script(root, root, "make").unwrap();
+        assert!(script.starts_with("exec ssh "));
+        assert!(script.ends_with("'cd /srv/repo && make'"));
+        assert!(
+            SshBackend::new("h", "/srv")
+                .script(root, Path::new("/tmp"), "ls")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_backend_deserializes_tagged() {
+        let backend: ExecutionBackend =
+            serde_json::from_str(r#"{"kind": "docker", "image": "node:22", "write_tools": true}"#)
+                .unwrap();
+        let ExecutionBackend::Docker(docker) = &backend else {
+            panic!("expected docker backend");
+        };
+        assert_eq!(docker.docker, "docker");
+        assert_eq!(docker.shell, "/bin/sh");
+        assert!(docker.write_tools && !docker.network);
+        assert!(backend.write_container().is_some());
+        assert!(ExecutionBackend::default().is_local());
+
+        let backend: ExecutionBackend =
+            serde_json::from_str(r#"{"kind": "ssh", "host": "gpu", "remote_dir": "/srv/repo"}"#)
+                .unwrap();
+        let ExecutionBackend::Ssh(ssh) = &backend else {
+            panic!("expected ssh backend");
+        };
+        assert!(ssh.sync);
+        assert_eq!(ssh.exclude, [".git", "target", "node_modules"]);
+        assert!(backend.write_container().is_none());
+
+        let backend: ExecutionBackend =
+            serde_json::from_str(r#"{"kind": "devcontainer"}"#).unwrap();
+        assert_eq!(
+            backend,
+            ExecutionBackend::Devcontainer(DevcontainerBackend::new())
+        );
+        assert!(backend.write_container().is_none());
+    }
+}
@@ -0,0 +1,165 @@
+//! What this build and this machine support, for `locus features`.
+//!
+//! - Cargo features: `syntax` (tree-sitter grammars), `semantic` (semantic_search
+//!   and its index), `web` (web_fetch, web_automation); all on by default, off in
+//!   minimal builds (`--no-default-features`). Tools behind a missing feature are
+//!   not registered
+//! - Programs the tools shell out to (git, docker, ssh, language servers, ...),
+//!   found on PATH when the report is built
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::lsp::LspConfig;
+use crate::tools::syntax::LANGUAGES;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityKind {
+    /// A cargo feature of this build.
+    Feature,
+    /// An external program found on PATH.
+    Program,
+}
+
+/// One line of the report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capability {
+    pub name: String,
+    pub kind: CapabilityKind,
+    /// Compiled into this build; always true for programs.
+    pub compiled: bool,
+    /// Usable now: compiled in and, for programs, found on PATH.
+    pub available: bool,
+    /// What it enables, or what is missing.
+    pub detail: String,
+}
+
+impl Capability {
+    /// A cargo feature, available whenever it is compiled in.
+    pub fn feature(name: &str, compiled: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: CapabilityKind::Feature,
+            compiled,
+            available: compiled,
+            detail: if compiled {
+                detail.into()
+            } else {
+                "not in this build".to_string()
+            },
+        }
+    }
+
+    /// A program, available when `command` is on PATH.
+    pub fn program(command: &str, detail: impl Into<String>) -> Self {
+        let found = find_program(command);
+        Self {
+            name: command.to_string(),
+            kind: CapabilityKind::Program,
+            compiled: true,
+            available: found.is_some(),
+            detail: match found {
+                Some(path) => format!("{} ({})", detail.into(), path.display()),
+                None => format!("{} (not found on PATH)", detail.into()),
+            },
+        }
+    }
+}
+
+/// The ToolBus features of this build, then the programs its tools use,
+/// including the language servers in the repo's `.locus/lsp.toml`.
+pub fn capabilities(repo_root: &Path) -> Vec<Capability> {
+    let mut report = vec![
+        Capability::feature(
+            "syntax",
+            cfg!(feature = "syntax"),
+            format!(
+                "code_nav, ast_search, read packing and the repo map for {}",
+                LANGUAGES.join(", ")
+            ),
+        ),
+        Capability::feature("semantic", cfg!(feature = "semantic"), semantic_detail()),
+        Capability::feature("web", cfg!(feature = "web"), web_detail()),
+        Capability::program("git", "git tools, checkpoints, blame"),
+        Capability::program("docker", "Docker execution backend"),
+        Capability::program("devcontainer", "dev container execution backend"),
+        Capability::program("ssh", "SSH execution backend"),
+        Capability::program("rsync", "SSH execution backend repo sync"),
+        Capability::program("gh", "locus work pull requests"),
+        Capability::program(
+            crate::plugins::DEFAULT_WASM_RUNTIME,
+            "WASM plugin tools (.locus/plugins)",
+        ),
+        Capability::program("cargo", "lint tool (clippy), deps (cargo tree)"),
+        Capability::program("npx", "lint tool (eslint), format_code (prettier)"),
+        Capability::program("ruff", "lint tool (ruff)"),
+        Capability::program("rustfmt", "format_code (rustfmt)"),
+        Capability::program("black", "format_code (black)"),
+        Capability::program("cargo-audit", "deps audit (cargo)"),
+        Capability::program("cargo-outdated", "deps outdated (cargo)"),
+        Capability::program("npm", "deps (npm audit, outdated, ls)"),
+        Capability::program("pip-audit", "deps audit (pip)"),
+        Capability::program("pipdeptree", "deps tree (pip)"),
+    ];
+    if let Ok(config) = LspConfig::load(repo_root) {
+        for server in &config.servers {
+            report.push(Capability::program(
+                &server.command,
+                format!("{} language server", server.id),
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(feature = "semantic")]
+fn semantic_detail() -> String {
+    let embedder = crate::semantic::embedder_from_env().id();
+    match embedder.strip_prefix("api-") {
+        Some(model) => format!("semantic_search with API embeddings ({})", model),
+        None => format!(
+            "semantic_search with local hash embeddings (set {} for API embeddings)",
+            crate::semantic::embed::EMBEDDINGS_URL_ENV
+        ),
+    }
+}
+
+#[cfg(not(feature = "semantic"))]
+fn semantic_detail() -> String {
+    String::new()
+}
+
+#[cfg(feature = "web")]
+fn web_detail() -> String {
+    let key = crate::tools::web_automation::ENV_API_KEY;
+    if std::env::var(key).is_ok_and(|v| !v.is_empty()) {
+        "web_fetch, web_automation".to_string()
+    } else {
+        format!("web_fetch; web_automation needs {}", key)
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn web_detail() -> String {
+    String::new()
+}
+
+/// `command` as run from PATH: a path is checked as is, a bare name is looked up
+/// in each PATH directory.
+pub fn find_program(command: &str) -> Option<PathBuf> {
+    let command = Path::new(command);
+    if command.components().count() > 1 {
+        return command.is_file().then(|| command.to_path_buf());
+    }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(command);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension("exe");
+        (cfg!(windows) && exe.is_file()).then_some(exe)
+    })
+}
@@ -24,6 +24,18 @@ pub struct WalEntry {
     pub new: String,
 }
 
+/// One captured version of a file, reconstructed from the edit stack.
+///
+/// Version 0 is the content before the oldest retained edit; version `n` is the
+/// content right after edit `n`. The last version matches the file as last edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileVersion {
+    pub version: usize,
+    /// Timestamp of the edit that produced this version (0 for the original).
+    pub ts: u64,
+    pub content: String,
+}
+
 /// In-memory edit stack per file (path relative to repo root).
 #[derive(Debug, Default)]
 struct HistoryInner {
@@ -185,6 +197,54 @@ impl EditHistory {
 
         Ok(Some(old_content))
     }
+
+    /// All retained versions of a file, oldest first. Empty if the file was never edited.
+    pub async fn versions(&self, file_path: &Path) -> Result<Vec<FileVersion>> {
+        let rel = path_relative_to(&self.repo_root, file_path)?;
+        let rel_key = rel.to_string_lossy().to_string();
+
+        let guard = self.inner.read().await;
+        let Some(stack) = guard.stacks.get(&rel_key).filter(|s| !s.is_empty()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut versions = Vec::with_capacity(stack.len() + 1);
+        versions.push(FileVersion {
+            version: 0,
+            ts: 0,
+            content: stack[0].old.clone(),
+        });
+        for (i, entry) in stack.iter().enumerate() {
+            versions.push(FileVersion {
+                version: i + 1,
+                ts: entry.ts,
+                content: entry.new.clone(),
+            });
+        }
+        Ok(versions)
+    }
+
+    /// Restore a file to a captured version. The restore is itself recorded as an edit,
+    /// so `undo` reverts it. Returns `(previous_content, restored_content)`, or None if
+    /// the version does not exist.
+    pub async fn restore(
+        &self,
+        file_path: &Path,
+        version: usize,
+    ) -> Result<Option<(String, String)>> {
+        let versions = self.versions(file_path).await?;
+        let Some(target) = versions.into_iter().find(|v| v.version == version) else {
+            return Ok(None);
+        };
+
+        let current = tokio::fs::read_to_string(file_path)
+            .await
+            .unwrap_or_default();
+        tokio::fs::write(file_path, &target.content).await?;
+        self.record(file_path, &current, &target.content).await?;
+
+        Ok(Some((current, target.content)))
+    }
 }
 
 fn path_relative_to(repo_root: &Path, path: &Path) -> Result<PathBuf> {
@@ -221,4 +281,32 @@ mod tests {
         let content = std::fs::read_to_string(&file).unwrap();
         assert_eq!(content, "fn foo(){}");
     }
+
+    #[tokio::test]
+    async fn versions_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_path_buf();
+        let history = EditHistory::load_blocking(repo.clone());
+
+        let file = repo.join("notes.txt");
+        std::fs::write(&file, "v2").unwrap();
+        history.record(&file, "v0", "v1").await.unwrap();
+        history.record(&file, "v1", "v2").await.unwrap();
+
+        let versions = history.versions(&file).await.unwrap();
+        let contents: Vec<&str> = versions.iter().map(|v| v.content.as_str()).collect();
+        assert_eq!(contents, vec!["v0", "v1", "v2"]);
+
+        let (previous, restored) = history.restore(&file, 0).await.unwrap().unwrap();
+        assert_eq!(previous, "v2");
+        assert_eq!(restored, "v0");
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v0");
+
+        // The restore is a new version, and undo reverts it.
+        assert_eq!(history.versions(&file).await.unwrap().len(), 4);
+        history.undo(&file).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+
+        assert!(history.restore(&file, 99).await.unwrap().is_none());
+    }
 }
@@ -1,86 +1,464 @@
 pub mod acp;
+pub mod command_rules;
+pub mod conflict;
+pub mod execution;
+pub mod features;
 pub mod history;
+pub mod lsp;
 pub mod mcp;
+pub mod permission;
+pub mod plugins;
+pub mod policy;
+pub mod protected_paths;
+pub mod recovery;
+pub mod result_cache;
+pub mod scripts;
+#[cfg(feature = "semantic")]
+pub mod semantic;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod tool_search;
 pub mod tools;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 
 use anyhow::{Result, anyhow};
+use locus_core::McpPromptInfo;
 use serde_json::Value as JsonValue;
 
-pub use history::EditHistory;
+pub use command_rules::{CommandDenied, CommandRules, CommandVerdict};
+pub use conflict::{
+    ConflictDecision, ConflictGuard, ConflictRequest, ConflictResolver, ConflictResponse,
+};
+pub use execution::{
+    DevcontainerBackend, DockerBackend, ExecutionBackend, ExecutionError, SshBackend,
+};
+pub use features::{Capability, CapabilityKind, capabilities};
+pub use history::{EditHistory, FileVersion};
+pub use lsp::{LspBridge, LspConfig, LspError, LspServerConfig};
+pub use permission::{
+    ApprovalDecision, ApprovalPreview, ApprovalRequest, ApprovalResponse, Approver, Permission,
+    PermissionDefaults, PermissionRule,
+};
+pub use plugins::{PluginError, PluginHost, PluginTool};
+pub use policy::{
+    CANCEL_GRACE, EffectiveToolPolicy, TOOL_POLICY_FILE, ToolInterrupted, ToolPolicies, ToolPolicy,
+    ToolPolicyError, TruncationStrategy,
+};
+pub use protected_paths::{
+    BUILTIN_PROTECTED, ProtectedPathDenied, ProtectedPathMatcher, ProtectedPaths,
+};
+pub use recovery::FailureKind;
+pub use result_cache::{CacheScope, ResultCache};
+pub use scripts::{ScriptError, ScriptHost, ScriptTool};
+#[cfg(feature = "semantic")]
+pub use semantic::{Embedder, SemanticError, SemanticIndex};
+pub use tool_search::{ToolMatch, ToolSearch};
+pub use tools::{
+    AcceptanceCriterion, Bash, BashArgs, BashError, BashExecutor, BranchAction, CreateFile,
+    CreateFileArgs, CreateFileError, DependencyNode, Deps, DepsAction, DepsArgs, DepsError,
+    DepsResult, DirectoryStats, DocumentFormat, Ecosystem, EditFile, EditFileArgs, EditFileError,
+    FileHistory, FileHistoryAction, FileHistoryArgs, FileHistoryError, FileStats, Finder,
+    FinderArgs, FinderError, FinderResult, FormatCode, FormatCodeArgs, FormatCodeError,
+    FormatCodeResult, FormatMode, FormattedFile, Formatter, GitBranch, GitBranchArgs, GitCommit,
+    GitCommitArgs, GitDiff, GitDiffArgs, GitError, GitLog, GitLogArgs, GitStatus, GitStatusArgs,
+    Glob, GlobArgs, GlobError, GlobResult, Grep, GrepArgs, GrepError, GrepMatch, GrepResult,
+    Handoff, HandoffArgs, HandoffError, JsonQuery, JsonQueryArgs, JsonQueryError, JsonQueryResult,
+    LanguageStats, LineCounts, Lint, LintArgs, LintCounts, LintDiagnostic, LintError, LintResult,
+    LintSeverity, Linter, LspDiagnostics, LspDiagnosticsArgs, LspHover, LspHoverArgs, LspRename,
+    LspRenameArgs, NoteSummary, Notes, NotesAction, NotesArgs, NotesError, PathError, ProgressSink,
+    Read, ReadArgs, ReadError, RepoStats, RepoStatsArgs, RepoStatsError, RepoStatsResult,
+    SearchMatch, SqliteQuery, SqliteQueryArgs, SqliteQueryError, SqliteQueryResult,
+    TASK_TOOL_DESCRIPTION, TaskItem, TaskList, TaskListAction, TaskListArgs, TaskListError,
+    TaskStatus, TodoItem, TodoScan, TodoScanArgs, TodoScanError, TodoScanResult, TodoSort, Tool,
+    ToolContext, ToolOutput, ToolResult, Tree, TreeArgs, TreeError, TreeNode, TreeNodeKind,
+    TreeResult, UndoEdit, UndoEditArgs, UndoEditError, VulnCounts, VulnSeverity, Vulnerability,
+    WriteSink, default_timeout, meta_tool_definitions, task_tool_definition,
+};
+#[cfg(feature = "syntax")]
 pub use tools::{
-    Bash, BashArgs, BashError, BashExecutor, CreateFile, CreateFileArgs, CreateFileError, EditFile,
-    EditFileArgs, EditFileError, Finder, FinderArgs, FinderError, FinderResult, Glob, GlobArgs,
-    GlobError, GlobResult, Grep, GrepArgs, GrepError, GrepMatch, GrepResult, Handoff, HandoffArgs,
-    HandoffError, Read, ReadArgs, ReadError, SearchMatch, TaskItem, TaskList, TaskListAction,
-    TaskListArgs, TaskListError, TaskStatus, Tool, ToolOutput, ToolResult, UndoEdit, UndoEditArgs,
-    UndoEditError, WebAutomation, WebAutomationArgs, WebAutomationError, default_timeout,
-    meta_tool_definitions, task_tool_definition,
+    AstMatch, AstSearch, AstSearchArgs, AstSearchError, AstSearchResult, CodeLocation, CodeNav,
+    CodeNavAction, CodeNavArgs, CodeNavError, CodeNavResult,
+};
+#[cfg(feature = "semantic")]
+pub use tools::{SemanticSearch, SemanticSearchArgs};
+#[cfg(feature = "web")]
+pub use tools::{
+    WebAutomation, WebAutomationArgs, WebAutomationError, WebFetch, WebFetchArgs, WebFetchError,
 };
 
 pub struct ToolBus {
     repo_root: PathBuf,
-    tools: HashMap<String, Arc<dyn Tool>>,
+    /// Where bash/handoff (and optionally file writes) run.
+    backend: ExecutionBackend,
+    /// Behind a lock so MCP tools can come and go on a shared bus.
+    tools: RwLock<HashMap<String, Arc<dyn Tool>>>,
+    /// Prompt templates of the running MCP servers, run as `/mcp.<server>.<prompt>`.
+    prompts: RwLock<HashMap<String, Arc<mcp::McpPromptWrapper>>>,
+    policies: ToolPolicies,
+    /// Answers `ask` permission rules; None means `ask` behaves like `allow`.
+    approver: RwLock<Option<Arc<dyn Approver>>>,
+    /// Tools the user chose "always allow" for during this session.
+    always_allowed: Mutex<HashSet<String>>,
+    /// File baselines and the resolver for edits that meet the user's changes.
+    conflicts: ConflictGuard,
+    /// Ranks tools for the `tool_search` meta-tool.
+    search: ToolSearch,
+    /// Results of read-only tools, reused while their files are unchanged.
+    cache: Arc<ResultCache>,
 }
 
 impl ToolBus {
     pub fn new(repo_root: PathBuf) -> Self {
+        Self::with_backend(repo_root, ExecutionBackend::default())
+    }
+
+    /// Bus whose `bash` and `handoff` run through `backend` (see [execution]).
+    pub fn with_backend(repo_root: PathBuf, backend: ExecutionBackend) -> Self {
+        let policies = ToolPolicies::load(&repo_root).unwrap_or_else(|e| {
+            tracing::warn!("{} ({}); using default tool limits", e, TOOL_POLICY_FILE);
+            ToolPolicies::default()
+        });
         let mut bus = Self {
+            cache: Arc::new(ResultCache::new(repo_root.clone())),
             repo_root,
-            tools: HashMap::new(),
+            backend,
+            tools: RwLock::new(HashMap::new()),
+            prompts: RwLock::new(HashMap::new()),
+            policies,
+            approver: RwLock::new(None),
+            always_allowed: Mutex::new(HashSet::new()),
+            conflicts: ConflictGuard::new(),
+            search: ToolSearch::from_env(),
         };
         bus.register_defaults();
         bus
     }
 
+    pub fn with_policies(mut self, policies: ToolPolicies) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Override the policy for one tool.
+    pub fn set_policy(&mut self, tool_name: impl Into<String>, policy: ToolPolicy) {
+        self.policies.tools.insert(tool_name.into(), policy);
+    }
+
+    pub fn policies(&self) -> &ToolPolicies {
+        &self.policies
+    }
+
+    pub fn with_approver(self, approver: Arc<dyn Approver>) -> Self {
+        self.set_approver(approver);
+        self
+    }
+
+    /// Set the approver for `ask` rules. Takes `&self` so a shared bus can be wired
+    /// to a UI after it is built.
+    pub fn set_approver(&self, approver: Arc<dyn Approver>) {
+        if let Ok(mut slot) = self.approver.write() {
+            *slot = Some(approver);
+        }
+    }
+
+    pub fn with_conflict_resolver(self, resolver: Arc<dyn ConflictResolver>) -> Self {
+        self.set_conflict_resolver(resolver);
+        self
+    }
+
+    /// Set who decides when an edit targets a file the user changed since the agent
+    /// last saw it (see [conflict]). Takes `&self` like [ToolBus::set_approver].
+    pub fn set_conflict_resolver(&self, resolver: Arc<dyn ConflictResolver>) {
+        self.conflicts.set_resolver(resolver);
+    }
+
+    /// Check the permission rule for a call and ask the approver when it says `ask`.
+    /// Shell commands are matched against `[commands]` first: a deny pattern fails
+    /// the call with [CommandDenied], an allow pattern skips the approver.
+    async fn authorize(&self, tool_name: &str, tool: &dyn Tool, args: &JsonValue) -> Result<()> {
+        let permission = tool.permission();
+        let rule = self.policies.permission_rule(tool_name, permission);
+        if rule != PermissionRule::Deny
+            && permission == Permission::Execute
+            && let Some(command) = args.get("command").and_then(|c| c.as_str())
+        {
+            match self.policies.commands.evaluate(command) {
+                CommandVerdict::Deny(denied) => {
+                    tracing::info!("Tool {} blocked: {}", tool_name, denied);
+                    return Err(denied.into());
+                }
+                CommandVerdict::Allow => return Ok(()),
+                CommandVerdict::Unmatched => {}
+            }
+        }
+        match rule {
+            PermissionRule::Allow => Ok(()),
+            PermissionRule::Deny => Err(anyhow!(
+                "Tool '{}' is denied by tool policy ({} permission)",
+                tool_name,
+                permission
+            )),
+            PermissionRule::Ask => {
+                if self
+                    .always_allowed
+                    .lock()
+                    .is_ok_and(|allowed| allowed.contains(tool_name))
+                {
+                    return Ok(());
+                }
+                let approver = self.approver.read().ok().and_then(|a| a.clone());
+                let Some(approver) = approver else {
+                    tracing::debug!(
+                        "Tool {} needs {} approval but no approver is set; allowing",
+                        tool_name,
+                        permission
+                    );
+                    return Ok(());
+                };
+                let mut request = ApprovalRequest::new(tool_name, permission, args.clone());
+                if let Some(preview) = conflict::preview_change(&self.repo_root, tool_name, args) {
+                    request = request.with_preview(preview);
+                }
+                let response = approver.approve(request).await;
+                match response.decision {
+                    ApprovalDecision::Allow => Ok(()),
+                    ApprovalDecision::AllowAlways => {
+                        if let Ok(mut allowed) = self.always_allowed.lock() {
+                            allowed.insert(tool_name.to_string());
+                        }
+                        Ok(())
+                    }
+                    ApprovalDecision::Deny => {
+                        let mut error = format!(
+                            "Tool '{}' was denied by the user ({} permission)",
+                            tool_name, permission
+                        );
+                        if let Some(message) = response.message.filter(|m| !m.trim().is_empty()) {
+                            error.push_str(&format!(". The user said: {}", message.trim()));
+                        }
+                        Err(anyhow!(error))
+                    }
+                }
+            }
+        }
+    }
+
     fn register_defaults(&mut self) {
-        let bash = Bash::new().with_working_dir(self.repo_root.to_string_lossy());
+        let bash = Bash::new()
+            .with_working_dir(self.repo_root.to_string_lossy())
+            .with_backend(self.backend.clone());
         self.register(bash);
 
-        let create_file = CreateFile::new(self.repo_root.clone());
+        let create_file =
+            CreateFile::new(self.repo_root.clone()).with_backend(self.backend.clone());
         self.register(create_file);
 
         let history = Arc::new(EditHistory::load_blocking(self.repo_root.clone()));
-        let edit_file = EditFile::new(self.repo_root.clone(), Arc::clone(&history));
+        let edit_file = EditFile::new(self.repo_root.clone(), Arc::clone(&history))
+            .with_backend(self.backend.clone());
         self.register(edit_file);
 
-        let undo_edit = UndoEdit::new(self.repo_root.clone(), history);
+        let undo_edit = UndoEdit::new(self.repo_root.clone(), Arc::clone(&history));
         self.register(undo_edit);
 
-        let glob = Glob::new(self.repo_root.clone());
+        let protected = self.policies.protected_paths.matcher();
+
+        let file_history = FileHistory::new(self.repo_root.clone(), Arc::clone(&history))
+            .with_protected_paths(protected.clone());
+        self.register(file_history);
+
+        let glob = Glob::new(self.repo_root.clone()).with_protected_paths(protected.clone());
         self.register(glob);
 
-        let grep = Grep::new(self.repo_root.clone());
+        let grep = Grep::new(self.repo_root.clone()).with_protected_paths(protected.clone());
         self.register(grep);
 
-        let finder = Finder::new(self.repo_root.clone());
+        let finder = Finder::new(self.repo_root.clone()).with_protected_paths(protected.clone());
         self.register(finder);
 
-        let read = Read::new(self.repo_root.clone());
+        let tree = Tree::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+        self.register(tree);
+
+        let todo_scan =
+            TodoScan::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+        self.register(todo_scan);
+
+        let repo_stats =
+            RepoStats::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+        self.register(repo_stats);
+
+        let json_query =
+            JsonQuery::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+        self.register(json_query);
+
+        let sqlite_query =
+            SqliteQuery::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+        self.register(sqlite_query);
+
+        #[cfg(feature = "syntax")]
+        {
+            let code_nav =
+                CodeNav::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+            self.register(code_nav);
+
+            let ast_search =
+                AstSearch::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+            self.register(ast_search);
+        }
+
+        let read = Read::new(self.repo_root.clone()).with_protected_paths(protected.clone());
         self.register(read);
 
-        let task_list = TaskList::new(self.repo_root.clone());
+        #[cfg(feature = "semantic")]
+        {
+            let index =
+                SemanticIndex::new(self.repo_root.clone()).with_protected_paths(protected.clone());
+            let semantic_search =
+                SemanticSearch::with_index(self.repo_root.clone(), Arc::new(index));
+            self.register(semantic_search);
+        }
+
+        self.register(GitStatus::new(self.repo_root.clone()));
+        self.register(GitDiff::new(self.repo_root.clone()).with_protected_paths(protected.clone()));
+        self.register(GitLog::new(self.repo_root.clone()));
+        self.register(GitCommit::new(self.repo_root.clone()));
+        self.register(GitBranch::new(self.repo_root.clone()));
+
+        let lsp = Arc::new(LspBridge::new(self.repo_root.clone()));
+        self.register(LspDiagnostics::new(Arc::clone(&lsp)));
+        self.register(LspHover::new(Arc::clone(&lsp)).with_protected_paths(protected));
+        self.register(LspRename::new(lsp, Arc::clone(&history)));
+
+        let task_list =
+            TaskList::new(self.repo_root.clone()).with_strict(TaskList::strict_from_env());
         self.register(task_list);
 
-        let handoff = Handoff::new(self.repo_root.clone());
+        let notes = Notes::new(self.repo_root.clone());
+        self.register(notes);
+
+        let handoff = Handoff::new(self.repo_root.clone()).with_backend(self.backend.clone());
         self.register(handoff);
 
-        let web_automation = WebAutomation::new();
-        self.register(web_automation);
+        let lint = Lint::new(self.repo_root.clone()).with_backend(self.backend.clone());
+        self.register(lint);
+
+        let format_code =
+            FormatCode::new(self.repo_root.clone(), history).with_backend(self.backend.clone());
+        self.register(format_code);
+
+        let deps = Deps::new(self.repo_root.clone()).with_backend(self.backend.clone());
+        self.register(deps);
+
+        #[cfg(feature = "web")]
+        {
+            let web_automation = WebAutomation::new();
+            self.register(web_automation);
+
+            self.register(WebFetch::new());
+        }
+
+        let scripts = ScriptHost::new(self.repo_root.clone())
+            .with_backend(self.backend.clone())
+            .with_protected_paths(self.policies.protected_paths.matcher())
+            .with_command_rules(self.policies.commands.clone());
+        for script in scripts.load() {
+            self.register(script);
+        }
+    }
+
+    /// Register the project's WASM plugins (see [plugins]). Each one is run
+    /// once to describe itself, so this is separate from building the bus.
+    pub async fn load_extensions(&self) {
+        let host = PluginHost::new(self.repo_root.clone())
+            .with_permissions(self.policies.plugins.clone())
+            .with_protected_paths(self.policies.protected_paths.matcher());
+        for plugin in host.load().await {
+            self.add_tool(Arc::new(plugin));
+        }
     }
 
     pub fn register<T: Tool + 'static>(&mut self, tool: T) {
-        self.tools.insert(tool.name().to_string(), Arc::new(tool));
+        self.add_tool(Arc::new(tool));
+    }
+
+    /// Register a tool on a bus that is already shared, replacing any tool with the
+    /// same name. Used for MCP servers started during a session.
+    pub fn add_tool(&self, tool: Arc<dyn Tool>) {
+        if let Ok(mut tools) = self.tools.write() {
+            tools.insert(tool.name().to_string(), tool);
+        }
+    }
+
+    /// Unregister a tool. Returns false when no tool had that name.
+    pub fn remove_tool(&self, tool_name: &str) -> bool {
+        self.tools
+            .write()
+            .is_ok_and(|mut tools| tools.remove(tool_name).is_some())
+    }
+
+    fn tool(&self, tool_name: &str) -> Option<Arc<dyn Tool>> {
+        self.tools.read().ok()?.get(tool_name).cloned()
+    }
+
+    /// Register an MCP server's prompt, replacing any with the same name.
+    pub fn add_prompt(&self, prompt: Arc<mcp::McpPromptWrapper>) {
+        if let Ok(mut prompts) = self.prompts.write() {
+            prompts.insert(prompt.namespaced_name().to_string(), prompt);
+        }
+    }
+
+    /// Unregister the prompts whose names start with `prefix`.
+    pub fn remove_prompts(&self, prefix: &str) {
+        if let Ok(mut prompts) = self.prompts.write() {
+            prompts.retain(|name, _| !name.starts_with(prefix));
+        }
+    }
+
+    /// The MCP prompt named `mcp.<server>.<prompt>`.
+    pub fn prompt(&self, name: &str) -> Option<Arc<mcp::McpPromptWrapper>> {
+        self.prompts.read().ok()?.get(name).cloned()
+    }
+
+    /// Registered MCP prompts, sorted by name.
+    pub fn list_prompts(&self) -> Vec<McpPromptInfo> {
+        let mut prompts: Vec<McpPromptInfo> = self
+            .prompts
+            .read()
+            .map(|prompts| {
+                prompts
+                    .values()
+                    .map(|p| McpPromptInfo {
+                        name: p.namespaced_name().to_string(),
+                        usage: p.usage(),
+                        description: p.description().to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        prompts
     }
 
     pub async fn call(&self, tool_name: &str, args: JsonValue) -> Result<(JsonValue, u64)> {
+        self.call_with_context(tool_name, args, ToolContext::new())
+            .await
+    }
+
+    /// [ToolBus::call] with the tool's progress lines sent to `ctx.progress`;
+    /// cancelling `ctx.cancel` stops the call with [ToolInterrupted::Cancelled].
+    pub async fn call_with_context(
+        &self,
+        tool_name: &str,
+        args: JsonValue,
+        ctx: ToolContext,
+    ) -> Result<(JsonValue, u64)> {
         // Catch truncated/unparseable arguments from streaming LLM responses
         if let Some(parse_error) = args.get("__parse_error") {
             let raw_len = args
@@ -98,8 +476,7 @@ impl ToolBus {
         }
 
         let tool = self
-            .tools
-            .get(tool_name)
+            .tool(tool_name)
             .ok_or_else(|| anyhow!("Tool not found: {}", tool_name))?;
 
         let args_pretty =
@@ -109,9 +486,75 @@ impl ToolBus {
             message = %format!("ToolBus call\n  tool={}\n  args:\n{}", tool_name, args_pretty)
         );
 
+        self.authorize(tool_name, tool.as_ref(), &args).await?;
+        let (args, resolution) = self
+            .conflicts
+            .check(&self.repo_root, tool_name, args)
+            .await?;
+        let path = args
+            .get("path")
+            .and_then(|p| p.as_str())
+            .map(str::to_string);
+        let action = args
+            .get("action")
+            .and_then(|a| a.as_str())
+            .map(str::to_string);
+
+        let policy = self.policies.effective(tool_name);
+        let session_id = ctx.session_id.clone();
         let start = Instant::now();
-        let result = tool.execute(args).await;
+        let cache_key = if policy.cache && tool.cacheable() {
+            self.cache_fingerprint(&args, tool.cache_scope(&args))
+                .await
+                .map(|fingerprint| (ResultCache::key(tool_name, &args), fingerprint))
+        } else {
+            None
+        };
+        let cached = cache_key
+            .as_ref()
+            .and_then(|(key, fingerprint)| self.cache.get(key, *fingerprint));
+        let result = match cached {
+            Some(output) => {
+                tracing::debug!("ToolBus reused cached {} result", tool_name);
+                Ok(output)
+            }
+            None => {
+                let result =
+                    recovery::run_with_retries(tool.as_ref(), tool_name, args, ctx, &policy).await;
+                if let (Ok(output), Some((key, fingerprint))) = (&result, cache_key) {
+                    self.cache.insert(key, fingerprint, output.clone());
+                }
+                result
+            }
+        };
+        if tool.permission() != Permission::Read {
+            self.cache.clear();
+        }
         let duration_ms = start.elapsed().as_millis() as u64;
+        let result = result.map(|mut output| {
+            let removed = policy.limit_result(&mut output);
+            if removed > 0 {
+                tracing::info!(
+                    "ToolBus truncated {} result by {} bytes (limit {} bytes)",
+                    tool_name,
+                    removed,
+                    policy.max_result_bytes
+                );
+            }
+            if let Some(resolution) = resolution {
+                output["conflict"] = resolution.to_json();
+            }
+            output
+        });
+        if result.is_ok() {
+            self.conflicts.observe(
+                &self.repo_root,
+                tool_name,
+                tool.permission(),
+                path.as_deref(),
+                action.as_deref(),
+            );
+        }
 
         match &result {
             Ok(output) => {
@@ -130,23 +573,151 @@ impl ToolBus {
             }
         }
 
+        self.record_usage(tool_name, session_id, duration_ms, &result);
         result.map(|r| (r, duration_ms))
     }
 
+    /// The tool result for a call to `tool_name` with `args` that failed with
+    /// `error`: the error, plus a `hint` when one can help the model's next try
+    /// (see [recovery]).
+    pub fn explain_failure(
+        &self,
+        tool_name: &str,
+        args: &JsonValue,
+        error: &anyhow::Error,
+    ) -> JsonValue {
+        let mut output = serde_json::json!({ "error": error.to_string() });
+        let tool = self.tool(tool_name);
+        let hint = if tool.is_none() {
+            let tools = self.tools.read().ok();
+            let similar = tools
+                .as_ref()
+                .map(|tools| recovery::similar_names(tool_name, tools.keys().map(String::as_str)))
+                .unwrap_or_default();
+            let hint = match similar.as_slice() {
+                [] => "There is no such tool. Use tool_search to find one.".to_string(),
+                _ => format!(
+                    "There is no such tool. Did you mean {}?",
+                    similar.join(" or ")
+                ),
+            };
+            Some(serde_json::json!({ "hint": hint, "similar_tools": similar }))
+        } else if recovery::classify(error) == FailureKind::Transient {
+            let hint = if tool.is_some_and(|tool| tool.idempotent(args)) {
+                format!(
+                    "This looks temporary (network trouble or a timeout) and failed again \
+                     after {} retries. Try again later, or get what you need another way.",
+                    self.policies.effective(tool_name).retries
+                )
+            } else {
+                "This looks temporary (network trouble or a timeout). It was not retried \
+                 because repeating it may do its work twice: check whether it took effect \
+                 before trying again."
+                    .to_string()
+            };
+            Some(serde_json::json!({ "transient": true, "hint": hint }))
+        } else if error
+            .chain()
+            .any(|cause| cause.downcast_ref::<serde_json::Error>().is_some())
+        {
+            Some(serde_json::json!({
+                "hint": format!(
+                    "The arguments do not match {}'s parameters. Call tool_explain with \
+                     tool_id \"{}\" for its schema.",
+                    tool_name, tool_name
+                ),
+            }))
+        } else {
+            let protected = self.policies.protected_paths.matcher();
+            recovery::file_hint(&self.repo_root, &protected, args, error)
+        };
+        if let (Some(JsonValue::Object(hint)), Some(output)) = (hint, output.as_object_mut()) {
+            output.extend(hint);
+        }
+        output
+    }
+
+    /// Fingerprint of the files a cacheable call reads (see [result_cache]).
+    async fn cache_fingerprint(&self, args: &JsonValue, scope: CacheScope) -> Option<u64> {
+        let cache = self.cache.clone();
+        let args = args.clone();
+        tokio::task::spawn_blocking(move || cache.fingerprint(&args, scope))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Append a finished call to the project DB's tool_usage table (read by
+    /// `locus toolbus stats`). The write runs on a blocking thread and failures
+    /// are only logged.
+    fn record_usage(
+        &self,
+        tool_name: &str,
+        session_id: Option<String>,
+        duration_ms: u64,
+        result: &Result<JsonValue>,
+    ) {
+        let bytes = match result {
+            Ok(output) => serde_json::to_vec(output).map_or(0, |b| b.len() as u64),
+            Err(_) => 0,
+        };
+        let record = locus_core::db::ToolCallRecord {
+            tool: tool_name.to_string(),
+            session_id,
+            ts: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            duration_ms,
+            success: result.is_ok(),
+            bytes,
+        };
+        let repo_root = self.repo_root.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = locus_core::db::record_tool_call(&repo_root, &record) {
+                tracing::warn!("ToolBus: failed to record {} usage: {}", record.tool, e);
+            }
+        });
+    }
+
+    /// Permission a registered tool declares, or None for unknown tools.
+    pub fn permission_of(&self, tool_name: &str) -> Option<Permission> {
+        self.tool(tool_name).map(|t| t.permission())
+    }
+
+    /// Registered tools, sorted by name so prompts built from them are stable.
     pub fn list_tools(&self) -> Vec<ToolInfo> {
-        self.tools
-            .values()
-            .map(|t| ToolInfo {
-                name: t.name().to_string(),
-                description: t.description().to_string(),
-                parameters: t.parameters_schema(),
+        let mut tools: Vec<ToolInfo> = self
+            .tools
+            .read()
+            .map(|tools| {
+                tools
+                    .values()
+                    .map(|t| ToolInfo {
+                        name: t.name().to_string(),
+                        description: t.description().to_string(),
+                        parameters: t.parameters_schema(),
+                    })
+                    .collect()
             })
-            .collect()
+            .unwrap_or_default();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Registered tools (MCP tools included) best matching `query`, for
+    /// `tool_search`. See [tool_search].
+    pub async fn search_tools(&self, query: &str, limit: usize) -> Vec<ToolMatch> {
+        self.search.search(&self.list_tools(), query, limit).await
     }
 
     pub fn repo_root(&self) -> &PathBuf {
         &self.repo_root
     }
+
+    pub fn execution_backend(&self) -> &ExecutionBackend {
+        &self.backend
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,466 @@
+//! JSON-RPC client for one language server over stdio.
+//!
+//! A background task reads the server's messages: responses complete the matching
+//! request, `textDocument/publishDiagnostics` notifications are kept per document,
+//! and requests from the server (configuration, progress, capability registration)
+//! get a minimal answer so servers that wait on them keep going.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use serde_json::{Value as JsonValue, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{Mutex, Notify, oneshot};
+use tokio::task::JoinHandle;
+
+use super::config::LspServerConfig;
+use super::error::{LspError, LspResult};
+
+type Writer = Arc<Mutex<Box<dyn AsyncWrite + Send + Unpin>>>;
+type Pending = Arc<StdMutex<HashMap<u64, oneshot::Sender<Result<JsonValue, String>>>>>;
+
+/// Diagnostics last published for a document.
+#[derive(Debug, Clone, Default)]
+pub struct PublishedDiagnostics {
+    /// Increases with every publish, across documents.
+    pub generation: u64,
+    pub diagnostics: Vec<JsonValue>,
+}
+
+#[derive(Default)]
+struct Shared {
+    diagnostics: StdMutex<HashMap<String, PublishedDiagnostics>>,
+    generation: AtomicU64,
+    published: Notify,
+    closed: AtomicBool,
+}
+
+/// An open document: the version and text last sent to the server.
+struct OpenDocument {
+    version: i64,
+    text: String,
+}
+
+pub struct LspClient {
+    server_id: String,
+    writer: Writer,
+    next_id: AtomicU64,
+    pending: Pending,
+    shared: Arc<Shared>,
+    documents: Mutex<HashMap<String, OpenDocument>>,
+    timeout: Duration,
+    reader: JoinHandle<()>,
+    /// Killed when the client is dropped.
+    _child: Option<Child>,
+}
+
+impl LspClient {
+    /// Start the configured server in `root` and complete the initialize handshake.
+    pub async fn spawn(config: &LspServerConfig, root: &Path) -> LspResult<Self> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| LspError::StartFailed {
+                server: config.id.clone(),
+                message: format!("{}: {}", config.command, e),
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(LspError::StartFailed {
+                server: config.id.clone(),
+                message: "could not capture stdio".to_string(),
+            });
+        };
+
+        let mut client = Self::connect(
+            &config.id,
+            stdout,
+            stdin,
+            Duration::from_secs(config.timeout_secs),
+        );
+        client._child = Some(child);
+        client
+            .initialize(root, config.initialization_options.clone())
+            .await?;
+        Ok(client)
+    }
+
+    /// Client over an already connected stream; call [`LspClient::initialize`] next.
+    pub fn connect(
+        server_id: &str,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+        writer: impl AsyncWrite + Send + Unpin + 'static,
+        timeout: Duration,
+    ) -> Self {
+        let writer: Writer = Arc::new(Mutex::new(Box::new(writer)));
+        let pending: Pending = Arc::default();
+        let shared = Arc::new(Shared::default());
+        let reader = tokio::spawn(read_loop(
+            BufReader::new(reader),
+            Arc::clone(&writer),
+            Arc::clone(&pending),
+            Arc::clone(&shared),
+        ));
+        Self {
+            server_id: server_id.to_string(),
+            writer,
+            next_id: AtomicU64::new(1),
+            pending,
+            shared,
+            documents: Mutex::new(HashMap::new()),
+            timeout,
+            reader,
+            _child: None,
+        }
+    }
+
+    pub fn server_id(&self) -> &str {
+        &self.server_id
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub async fn initialize(
+        &self,
+        root: &Path,
+        options: Option<JsonValue>,
+    ) -> LspResult<JsonValue> {
+        let root_uri = path_to_uri(root)?;
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut params = json!({
+            "processId": std::process::id(),
+            "rootUri": root_uri,
+            "workspaceFolders": [{ "uri": root_uri, "name": name }],
+            "clientInfo": { "name": "locus", "version": env!("CARGO_PKG_VERSION") },
+            "capabilities": {
+                "general": { "positionEncodings": ["utf-16"] },
+                "textDocument": {
+                    "synchronization": { "dynamicRegistration": false },
+                    "hover": { "contentFormat": ["markdown", "plaintext"] },
+                    "rename": { "prepareSupport": false },
+                    "publishDiagnostics": { "relatedInformation": false }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspaceFolders": true,
+                    "workspaceEdit": { "documentChanges": true }
+                }
+            }
+        });
+        if let Some(options) = options {
+            params["initializationOptions"] = options;
+        }
+        let result = self.request("initialize", params).await?;
+        self.notify("initialized", json!({})).await?;
+        Ok(result)
+    }
+
+    /// Send a request and wait for its result, up to the server timeout.
+    pub async fn request(&self, method: &str, params: JsonValue) -> LspResult<JsonValue> {
+        if self.shared.closed.load(Ordering::SeqCst) {
+            return Err(LspError::ServerExited(self.server_id.clone()));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.lock_pending().insert(id, tx);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(e) = write_message(&self.writer, &message).await {
+            self.lock_pending().remove(&id);
+            return Err(e);
+        }
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(message))) => Err(LspError::RequestFailed {
+                method: method.to_string(),
+                message,
+            }),
+            Ok(Err(_)) => Err(LspError::ServerExited(self.server_id.clone())),
+            Err(_) => {
+                self.lock_pending().remove(&id);
+                Err(LspError::Timeout {
+                    method: method.to_string(),
+                    timeout_ms: self.timeout.as_millis() as u64,
+                })
+            }
+        }
+    }
+
+    pub async fn notify(&self, method: &str, params: JsonValue) -> LspResult<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&self.writer, &message).await
+    }
+
+    /// Open the document, or send its new text when it changed since the last sync.
+    /// Returns whether anything was sent (and new diagnostics can be expected).
+    pub async fn sync_document(&self, uri: &str, language_id: &str, text: &str) -> LspResult<bool> {
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(uri) {
+            Some(doc) if doc.text == text => Ok(false),
+            Some(doc) => {
+                doc.version += 1;
+                doc.text = text.to_string();
+                let params = json!({
+                    "textDocument": { "uri": uri, "version": doc.version },
+                    "contentChanges": [{ "text": text }]
+                });
+                self.notify("textDocument/didChange", params).await?;
+                Ok(true)
+            }
+            None => {
+                let params = json!({
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": language_id,
+                        "version": 1,
+                        "text": text
+                    }
+                });
+                self.notify("textDocument/didOpen", params).await?;
+                documents.insert(
+                    uri.to_string(),
+                    OpenDocument {
+                        version: 1,
+                        text: text.to_string(),
+                    },
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    /// Current publish generation; pass it to [`LspClient::wait_for_diagnostics`].
+    pub fn generation(&self) -> u64 {
+        self.shared.generation.load(Ordering::SeqCst)
+    }
+
+    /// Diagnostics for `uri` published after generation `after`, waiting up to `wait`.
+    /// Returns the last known diagnostics and `false` when none arrived in time.
+    pub async fn wait_for_diagnostics(
+        &self,
+        uri: &str,
+        after: u64,
+        wait: Duration,
+    ) -> (Vec<JsonValue>, bool) {
+        let deadline = tokio::time::Instant::now() + wait;
+        loop {
+            let published = self.shared.published.notified();
+            let current = self.diagnostics(uri);
+            if let Some(current) = &current
+                && current.generation > after
+            {
+                return (current.diagnostics.clone(), true);
+            }
+            if self.shared.closed.load(Ordering::SeqCst)
+                || tokio::time::timeout_at(deadline, published).await.is_err()
+            {
+                return (current.map(|c| c.diagnostics).unwrap_or_default(), false);
+            }
+        }
+    }
+
+    /// Diagnostics last published for `uri`, if any.
+    pub fn diagnostics(&self, uri: &str) -> Option<PublishedDiagnostics> {
+        self.shared
+            .diagnostics
+            .lock()
+            .ok()
+            .and_then(|d| d.get(uri).cloned())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::SeqCst)
+    }
+
+    /// Ask the server to shut down and exit.
+    pub async fn shutdown(&self) {
+        if self.is_closed() {
+            return;
+        }
+        let _ = tokio::time::timeout(
+            Duration::from_secs(2),
+            self.request("shutdown", JsonValue::Null),
+        )
+        .await;
+        let _ = self.notify("exit", JsonValue::Null).await;
+    }
+
+    fn lock_pending(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<Result<JsonValue, String>>>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// `file://` URI for an absolute path.
+pub fn path_to_uri(path: &Path) -> LspResult<String> {
+    reqwest::Url::from_file_path(path)
+        .map(|url| url.to_string())
+        .map_err(|_| LspError::InvalidArgs(format!("not an absolute path: {}", path.display())))
+}
+
+/// Path for a `file://` URI.
+pub fn uri_to_path(uri: &str) -> Option<std::path::PathBuf> {
+    reqwest::Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+async fn write_message(writer: &Writer, message: &JsonValue) -> LspResult<()> {
+    let body = serde_json::to_string(message)?;
+    let mut writer = writer.lock().await;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(body.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read one framed message; `None` at end of stream.
+async fn read_message(
+    reader: &mut BufReader<impl AsyncRead + Unpin>,
+) -> LspResult<Option<JsonValue>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = Some(value.trim().parse::<usize>().map_err(|e| {
+                LspError::Protocol(format!("bad Content-Length {:?}: {}", value, e))
+            })?);
+        }
+    }
+    let mut body = vec![0u8; content_length.unwrap_or(0)];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+async fn read_loop(
+    mut reader: BufReader<impl AsyncRead + Unpin>,
+    writer: Writer,
+    pending: Pending,
+    shared: Arc<Shared>,
+) {
+    loop {
+        let message = match read_message(&mut reader).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!("LSP read failed: {}", e);
+                break;
+            }
+        };
+        let method = message.get("method").and_then(|m| m.as_str());
+        let id = message.get("id").filter(|id| !id.is_null());
+        match (method, id) {
+            (None, Some(id)) => {
+                let Some(sender) = id.as_u64().and_then(|id| pending.lock().ok()?.remove(&id))
+                else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(error
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("unknown error")
+                        .to_string()),
+                    None => Ok(message.get("result").cloned().unwrap_or(JsonValue::Null)),
+                };
+                let _ = sender.send(result);
+            }
+            (Some(method), Some(id)) => {
+                let reply = answer_server_request(method, message.get("params"), id.clone());
+                if let Err(e) = write_message(&writer, &reply).await {
+                    tracing::warn!("LSP reply to {} failed: {}", method, e);
+                }
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let params = message.get("params").cloned().unwrap_or_default();
+                let Some(uri) = params.get("uri").and_then(|u| u.as_str()) else {
+                    continue;
+                };
+                let diagnostics = params
+                    .get("diagnostics")
+                    .and_then(|d| d.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                let generation = shared.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                if let Ok(mut all) = shared.diagnostics.lock() {
+                    all.insert(
+                        uri.to_string(),
+                        PublishedDiagnostics {
+                            generation,
+                            diagnostics,
+                        },
+                    );
+                }
+                shared.published.notify_waiters();
+            }
+            _ => {}
+        }
+    }
+
+    shared.closed.store(true, Ordering::SeqCst);
+    if let Ok(mut pending) = pending.lock() {
+        pending.clear();
+    }
+    shared.published.notify_waiters();
+}
+
+/// Minimal answers to requests a server sends the client.
+fn answer_server_request(method: &str, params: Option<&JsonValue>, id: JsonValue) -> JsonValue {
+    let result = match method {
+        "workspace/configuration" => {
+            let items = params
+                .and_then(|p| p.get("items"))
+                .and_then(|i| i.as_array())
+                .map_or(0, |i| i.len());
+            JsonValue::Array(vec![JsonValue::Null; items])
+        }
+        "window/workDoneProgress/create"
+        | "client/registerCapability"
+        | "client/unregisterCapability"
+        | "window/showMessageRequest" => JsonValue::Null,
+        "workspace/workspaceFolders" => JsonValue::Array(Vec::new()),
+        // Edits only reach files through the rename tool, which applies them itself.
+        "workspace/applyEdit" => json!({ "applied": false }),
+        _ => {
+            return json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("unsupported: {}", method) }
+            });
+        }
+    };
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
@@ -0,0 +1,147 @@
+//! Language servers configured for a repo in `.locus/lsp.toml`.
+//!
+//! ```toml
+//! [[servers]]
+//! id = "rust"
+//! command = "rust-analyzer"
+//! extensions = ["rs"]
+//!
+//! [[servers]]
+//! id = "typescript"
+//! command = "typescript-language-server"
+//! args = ["--stdio"]
+//! extensions = ["ts", "tsx", "js", "jsx"]
+//! timeout_secs = 60
+//! initialization_options = { preferences = { includeInlayParameterNameHints = "none" } }
+//! ```
+//!
+//! A file is served by the first server listing its extension. Servers start on
+//! first use, in the repo root, and talk LSP over stdio.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::error::LspError;
+
+/// LSP config file, relative to the repo root.
+pub const LSP_CONFIG_FILE: &str = ".locus/lsp.toml";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// One language server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LspServerConfig {
+    pub id: String,
+    /// Executable, resolved on PATH.
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// File extensions (without the dot) this server handles.
+    pub extensions: Vec<String>,
+    /// `languageId` sent when opening files; guessed from the extension when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_id: Option<String>,
+    /// Sent as `initializationOptions` in the initialize request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initialization_options: Option<JsonValue>,
+    /// Per-request timeout, including the initialize handshake.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+impl LspServerConfig {
+    pub fn new(id: impl Into<String>, command: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            id: id.into(),
+            command: command.into(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            language_id: None,
+            initialization_options: None,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        }
+    }
+
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    pub fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+
+    /// `languageId` for a file this server handles.
+    pub fn language_id_for(&self, path: &Path) -> String {
+        if let Some(id) = &self.language_id {
+            return id.clone();
+        }
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match ext {
+            "rs" => "rust",
+            "py" | "pyi" => "python",
+            "ts" | "mts" | "cts" => "typescript",
+            "tsx" => "typescriptreact",
+            "js" | "mjs" | "cjs" => "javascript",
+            "jsx" => "javascriptreact",
+            "go" => "go",
+            "c" | "h" => "c",
+            "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+            "java" => "java",
+            "rb" => "ruby",
+            "sh" | "bash" => "shellscript",
+            other => other,
+        }
+        .to_string()
+    }
+}
+
+/// Contents of `.locus/lsp.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LspConfig {
+    #[serde(default)]
+    pub servers: Vec<LspServerConfig>,
+}
+
+impl LspConfig {
+    /// Load the repo's config; a missing file means no servers.
+    pub fn load(repo_root: &Path) -> Result<Self, LspError> {
+        let path = repo_root.join(LSP_CONFIG_FILE);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, LspError> {
+        let config: Self = toml::from_str(content).map_err(|e| LspError::Config(e.to_string()))?;
+        for server in &config.servers {
+            if server.command.trim().is_empty() || server.extensions.is_empty() {
+                return Err(LspError::Config(format!(
+                    "server '{}' needs a command and at least one extension",
+                    server.id
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// The first server that handles `path`.
+    pub fn server_for(&self, path: &Path) -> Option<&LspServerConfig> {
+        self.servers.iter().find(|s| s.handles(path))
+    }
+}
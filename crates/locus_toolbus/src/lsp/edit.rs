@@ -0,0 +1,170 @@
+//! LSP positions and text edits.
+//!
+//! LSP positions are 0-based lines and UTF-16 code unit offsets; tools take and
+//! report 1-based lines and character columns, as the rest of the toolbus does.
+
+use serde_json::Value as JsonValue;
+
+use super::error::{LspError, LspResult};
+
+/// A position as LSP sends it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl Position {
+    pub fn from_json(value: &JsonValue) -> Option<Self> {
+        Some(Self {
+            line: value.get("line")?.as_u64()? as u32,
+            character: value.get("character")?.as_u64()? as u32,
+        })
+    }
+}
+
+/// One `TextEdit`: replace `start..end` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: Position,
+    pub end: Position,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn from_json(value: &JsonValue) -> Option<Self> {
+        let range = value.get("range")?;
+        Some(Self {
+            start: Position::from_json(range.get("start")?)?,
+            end: Position::from_json(range.get("end")?)?,
+            new_text: value.get("newText")?.as_str()?.to_string(),
+        })
+    }
+}
+
+/// Text of 0-based `line` without its line ending.
+pub fn line_text(text: &str, line: u32) -> Option<&str> {
+    text.split('\n')
+        .nth(line as usize)
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+}
+
+/// UTF-16 offset of the 1-based character `column` in `line`.
+pub fn utf16_offset(line: &str, column: usize) -> u32 {
+    line.chars()
+        .take(column.saturating_sub(1))
+        .map(|c| c.len_utf16() as u32)
+        .sum()
+}
+
+/// 1-based character column of a UTF-16 offset in `line`.
+pub fn char_column(line: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    let mut column = 1;
+    for c in line.chars() {
+        if units >= utf16 {
+            break;
+        }
+        units += c.len_utf16() as u32;
+        column += 1;
+    }
+    column
+}
+
+/// Byte offset of `position` in `text`. Characters past the line end clamp to it,
+/// and lines past the last one to the end of the text.
+pub fn byte_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line = line_text(&text[line_start..], 0).unwrap_or("");
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= position.character {
+            return line_start + i;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line_start + line.len()
+}
+
+/// Apply edits that all refer to the original `text`, as LSP defines them.
+pub fn apply_edits(text: &str, edits: &[TextEdit]) -> LspResult<String> {
+    let mut spans = Vec::with_capacity(edits.len());
+    for edit in edits {
+        if edit.end < edit.start {
+            return Err(LspError::Protocol(format!(
+                "edit range {}:{}-{}:{} ends before it starts",
+                edit.start.line, edit.start.character, edit.end.line, edit.end.character
+            )));
+        }
+        spans.push((
+            byte_offset(text, edit.start),
+            byte_offset(text, edit.end),
+            &edit.new_text,
+        ));
+    }
+    spans.sort_by_key(|&(start, end, _)| (start, end));
+    if spans.windows(2).any(|w| w[0].1 > w[1].0) {
+        return Err(LspError::Protocol("overlapping edits".to_string()));
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    for (start, end, new_text) in spans {
+        out.push_str(&text[at..start]);
+        out.push_str(new_text);
+        at = end;
+    }
+    out.push_str(&text[at..]);
+    Ok(out)
+}
+
+/// Text edits per document URI in a `WorkspaceEdit`, in the order given.
+///
+/// Both `changes` and `documentChanges` are read. File create, rename and delete
+/// operations are refused: the rename tool only rewrites file contents.
+pub fn workspace_edit_files(edit: &JsonValue) -> LspResult<Vec<(String, Vec<TextEdit>)>> {
+    let mut files: Vec<(String, Vec<TextEdit>)> = Vec::new();
+    let mut add = |uri: &str, edits: &JsonValue| -> LspResult<()> {
+        let edits = edits
+            .as_array()
+            .ok_or_else(|| LspError::Protocol("edits must be an array".to_string()))?
+            .iter()
+            .map(|e| {
+                TextEdit::from_json(e)
+                    .ok_or_else(|| LspError::Protocol(format!("bad text edit: {}", e)))
+            })
+            .collect::<LspResult<Vec<_>>>()?;
+        match files.iter_mut().find(|(u, _)| u == uri) {
+            Some((_, existing)) => existing.extend(edits),
+            None => files.push((uri.to_string(), edits)),
+        }
+        Ok(())
+    };
+
+    if let Some(changes) = edit.get("documentChanges").and_then(|c| c.as_array()) {
+        for change in changes {
+            if let Some(kind) = change.get("kind").and_then(|k| k.as_str()) {
+                return Err(LspError::Protocol(format!(
+                    "the server wants to {} a file, which is not supported",
+                    kind
+                )));
+            }
+            let uri = change
+                .pointer("/textDocument/uri")
+                .and_then(|u| u.as_str())
+                .ok_or_else(|| LspError::Protocol("document change without a uri".to_string()))?;
+            add(uri, change.get("edits").unwrap_or(&JsonValue::Null))?;
+        }
+    } else if let Some(changes) = edit.get("changes").and_then(|c| c.as_object()) {
+        for (uri, edits) in changes {
+            add(uri, edits)?;
+        }
+    }
+    Ok(files)
+}
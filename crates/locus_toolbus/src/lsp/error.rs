@@ -0,0 +1,42 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LspError {
+    #[error("No language server configured for {0}; add one to .locus/lsp.toml")]
+    NoServer(String),
+
+    #[error("Failed to start language server '{server}': {message}")]
+    StartFailed { server: String, message: String },
+
+    #[error("Language server '{0}' exited")]
+    ServerExited(String),
+
+    #[error("{method} timed out after {timeout_ms}ms")]
+    Timeout { method: String, timeout_ms: u64 },
+
+    #[error("{method} failed: {message}")]
+    RequestFailed { method: String, message: String },
+
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    #[error("Invalid LSP config: {0}")]
+    Config(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("File not found: {0}")]
+    FileNotFound(String),
+
+    #[error("Path is outside the repository: {0}")]
+    OutsideRepo(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub type LspResult<T> = Result<T, LspError>;
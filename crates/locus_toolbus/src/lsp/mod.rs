@@ -0,0 +1,183 @@
+//! Language Server Protocol bridge for locus_toolbus.
+//!
+//! Gives tools type-aware answers (diagnostics, hover, rename) from the language
+//! servers a repo configures in `.locus/lsp.toml`, instead of the model re-reading
+//! headers and guessing. Servers are started lazily, one per config entry, and
+//! restarted on the next call if they exit.
+//!
+//! - **config**: `.locus/lsp.toml` server entries
+//! - **client**: JSON-RPC over stdio, document sync, published diagnostics
+//! - **edit**: LSP positions and applying `WorkspaceEdit`s
+//! - **error**: Error types for LSP operations
+//!
+//! The `lsp_diagnostics`, `lsp_hover` and `lsp_rename` tools are in `tools::lsp`.
+
+pub mod client;
+pub mod config;
+pub mod edit;
+pub mod error;
+
+pub use client::{LspClient, PublishedDiagnostics, path_to_uri, uri_to_path};
+pub use config::{LSP_CONFIG_FILE, LspConfig, LspServerConfig};
+pub use error::{LspError, LspResult};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+/// A file synced to its language server, ready for requests.
+pub struct OpenFile {
+    /// Canonical absolute path.
+    pub path: PathBuf,
+    /// Path relative to the repo root, `/`-separated.
+    pub relative: String,
+    pub uri: String,
+    pub text: String,
+    pub client: Arc<LspClient>,
+    /// Whether this call opened the file or sent new text.
+    pub synced: bool,
+    /// Diagnostics generation before the sync; newer publishes reflect this text.
+    pub generation_before: u64,
+}
+
+/// Language servers for one repo.
+pub struct LspBridge {
+    repo_root: PathBuf,
+    config: LspConfig,
+    clients: Mutex<HashMap<String, Arc<LspClient>>>,
+}
+
+impl LspBridge {
+    /// Bridge for the servers in the repo's `.locus/lsp.toml`.
+    pub fn new(repo_root: PathBuf) -> Self {
+        let config = LspConfig::load(&repo_root).unwrap_or_else(|e| {
+            tracing::warn!("{} ({}); LSP tools disabled", e, LSP_CONFIG_FILE);
+            LspConfig::default()
+        });
+        Self::with_config(repo_root, config)
+    }
+
+    pub fn with_config(repo_root: PathBuf, config: LspConfig) -> Self {
+        Self {
+            repo_root,
+            config,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &LspConfig {
+        &self.config
+    }
+
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// Canonical path of a repo file given relative to the root (or absolute inside it).
+    pub fn resolve(&self, path: &str) -> LspResult<PathBuf> {
+        let full = self.repo_root.join(path);
+        if !full.is_file() {
+            return Err(LspError::FileNotFound(path.to_string()));
+        }
+        let full = full.canonicalize()?;
+        self.check_inside(&full, path)?;
+        Ok(full)
+    }
+
+    /// Path relative to the repo root, `/`-separated; errors for paths outside it.
+    pub fn relative(&self, path: &Path) -> LspResult<String> {
+        let root = self.canonical_root();
+        path.strip_prefix(&root)
+            .map(|rel| {
+                rel.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .map_err(|_| LspError::OutsideRepo(path.display().to_string()))
+    }
+
+    fn check_inside(&self, path: &Path, shown: &str) -> LspResult<()> {
+        if path.starts_with(self.canonical_root()) {
+            Ok(())
+        } else {
+            Err(LspError::OutsideRepo(shown.to_string()))
+        }
+    }
+
+    fn canonical_root(&self) -> PathBuf {
+        self.repo_root
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_root.clone())
+    }
+
+    /// The running client for the server that handles `path`, started on first use.
+    pub async fn client_for(&self, path: &Path) -> LspResult<Arc<LspClient>> {
+        let server = self
+            .config
+            .server_for(path)
+            .ok_or_else(|| LspError::NoServer(path.display().to_string()))?;
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(&server.id)
+            && !client.is_closed()
+        {
+            return Ok(Arc::clone(client));
+        }
+        tracing::info!(
+            "Starting language server {} ({})",
+            server.id,
+            server.command
+        );
+        let client = Arc::new(LspClient::spawn(server, &self.canonical_root()).await?);
+        clients.insert(server.id.clone(), Arc::clone(&client));
+        Ok(client)
+    }
+
+    /// Use `client` for `server_id` instead of spawning the configured command.
+    pub async fn attach(&self, server_id: &str, client: LspClient) {
+        self.clients
+            .lock()
+            .await
+            .insert(server_id.to_string(), Arc::new(client));
+    }
+
+    /// Resolve `path`, read it and sync its current text to its server.
+    pub async fn open(&self, path: &str) -> LspResult<OpenFile> {
+        let full = self.resolve(path)?;
+        let text = tokio::fs::read_to_string(&full).await?;
+        let relative = self.relative(&full)?;
+        self.sync(full, relative, text).await
+    }
+
+    /// Sync `text` as the content of `path` (already resolved) to its server.
+    pub async fn sync(&self, path: PathBuf, relative: String, text: String) -> LspResult<OpenFile> {
+        let client = self.client_for(&path).await?;
+        let language_id = self
+            .config
+            .server_for(&path)
+            .map(|s| s.language_id_for(&path))
+            .unwrap_or_default();
+        let uri = path_to_uri(&path)?;
+        let generation_before = client.generation();
+        let synced = client.sync_document(&uri, &language_id, &text).await?;
+        Ok(OpenFile {
+            path,
+            relative,
+            uri,
+            text,
+            client,
+            synced,
+            generation_before,
+        })
+    }
+
+    /// Shut down every running server.
+    pub async fn shutdown(&self) {
+        let clients: Vec<_> = self.clients.lock().await.drain().collect();
+        for (_, client) in clients {
+            client.shutdown().await;
+        }
+    }
+}
@@ -8,15 +8,20 @@
 //! - Local servers use stdio transport (spawn process)
 //! - Remote servers use SSE transport (HTTP)
 
+use std::sync::Arc;
+
 use serde_json::Value as JsonValue;
 use tracing::{debug, info, warn};
 
 use crate::mcp::config::McpServerConfig;
 use crate::mcp::error::McpError;
+use crate::mcp::oauth::OAuthSession;
 use crate::mcp::protocol::{
-    CallToolRequest, CallToolResult, ClientCapabilities, Implementation, InitializeParams,
-    InitializeResult, ListToolsResult, Tool,
+    CallToolRequest, CallToolResult, ClientCapabilities, GetPromptRequest, GetPromptResult,
+    Implementation, InitializeParams, InitializeResult, ListPromptsResult, ListToolsResult, Prompt,
+    Tool,
 };
+use crate::mcp::token_store::TokenStore;
 use crate::mcp::transport::{SseTransport, StdioTransport, TransportEnum};
 
 /// MCP client for communicating with an MCP server.
@@ -86,11 +91,19 @@ impl McpClient {
     /// # Ok::<(), locus_toolbus::mcp::McpError>(())
     /// ```
     pub async fn connect(config: &McpServerConfig) -> Result<Self, McpError> {
+        Self::connect_with_tokens(config, &TokenStore::default()).await
+    }
+
+    /// Like [McpClient::connect], reading OAuth tokens from `tokens`.
+    pub async fn connect_with_tokens(
+        config: &McpServerConfig,
+        tokens: &TokenStore,
+    ) -> Result<Self, McpError> {
         info!("Connecting to MCP server: {} ({})", config.name, config.id);
 
         let (transport, is_remote) = if let Some(url) = &config.url {
             // Remote server via SSE
-            Self::create_sse_transport(config, url).await?
+            Self::create_sse_transport(config, url, tokens).await?
         } else if !config.command.is_empty() {
             // Local server via stdio
             Self::create_stdio_transport(config)?
@@ -112,9 +125,17 @@ impl McpClient {
     async fn create_sse_transport(
         config: &McpServerConfig,
         url: &str,
+        tokens: &TokenStore,
     ) -> Result<(TransportEnum, bool), McpError> {
         info!("[MCP:{}] Connecting to remote server: {}", config.id, url);
 
+        if config.auth.as_ref().is_some_and(|auth| auth.is_oauth()) {
+            let session = OAuthSession::new(config, tokens.clone())?;
+            let transport = SseTransport::new(url, reqwest::header::HeaderMap::new(), &config.id)
+                .with_oauth(Arc::new(session));
+            return Ok((TransportEnum::Sse(transport), true));
+        }
+
         let mut headers = reqwest::header::HeaderMap::new();
 
         // Add authentication headers if configured
@@ -255,6 +276,46 @@ impl McpClient {
         Ok(result)
     }
 
+    /// Lists the prompt templates the MCP server offers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has not been initialized or if
+    /// the server returns an error, as one without prompts support does.
+    pub async fn list_prompts(&mut self) -> Result<Vec<Prompt>, McpError> {
+        if !self.initialized {
+            return Err(McpError::Protocol("Server not initialized".to_string()));
+        }
+
+        let result: ListPromptsResult = self.transport.send_request("prompts/list", None).await?;
+
+        debug!(
+            "[MCP:{}] Found {} prompts",
+            self.server_id,
+            result.prompts.len()
+        );
+        Ok(result.prompts)
+    }
+
+    /// Fills in a prompt template with `arguments`, by name.
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Map<String, JsonValue>,
+    ) -> Result<GetPromptResult, McpError> {
+        if !self.initialized {
+            return Err(McpError::Protocol("Server not initialized".to_string()));
+        }
+
+        let request = GetPromptRequest {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        };
+        self.transport
+            .send_request("prompts/get", Some(serde_json::to_value(request)?))
+            .await
+    }
+
     /// Shuts down the MCP server gracefully.
     ///
     /// Sends a shutdown request and waits for the process to exit (stdio only).
@@ -30,15 +30,29 @@
 //! [servers.auth]
 //! auth_type = "bearer"
 //! token = "$API_KEY"
+//!
+//! # Remote MCP server behind OAuth 2.1 (log in with `locus mcp login hosted`)
+//! [[servers]]
+//! id = "hosted"
+//! name = "Hosted MCP Server"
+//! url = "https://mcp.example.com"
+//! transport = "sse"
+//!
+//! [servers.auth]
+//! auth_type = "oauth"
+//!
+//! [servers.auth.oauth]
+//! scopes = ["mcp:tools"]
 //! ```
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use super::oauth::OAuthConfig;
 use super::transport::TransportType;
 
 /// Errors that can occur during configuration operations.
@@ -89,19 +103,21 @@ impl Default for RestartPolicy {
 /// Authentication configuration for MCP servers.
 ///
 /// Supports various authentication methods including bearer tokens,
-/// basic authentication, and API keys. Tokens can reference environment
+/// basic authentication, API keys and OAuth 2.1. Tokens can reference environment
 /// variables using the `$VAR_NAME` syntax.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct McpAuthConfig {
     /// The type of authentication to use.
     ///
-    /// Common values: "bearer", "basic", "api_key"
+    /// Common values: "bearer", "basic", "api_key", "oauth"
     pub auth_type: String,
 
     /// The authentication token or credentials.
     ///
     /// Can reference environment variables using `$VAR_NAME` syntax.
     /// Use [`McpAuthConfig::resolve_token`] to get the actual value.
+    /// Unused for "oauth", whose tokens live in the OS keyring.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub token: String,
 
     /// Optional custom header name for the authentication.
@@ -112,6 +128,11 @@ pub struct McpAuthConfig {
     /// - "api_key" -> "X-API-Key"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub header: Option<String>,
+
+    /// OAuth client settings for "oauth"; endpoints not set here are discovered
+    /// from the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<OAuthConfig>,
 }
 
 impl McpAuthConfig {
@@ -121,6 +142,7 @@ impl McpAuthConfig {
             auth_type: auth_type.into(),
             token: token.into(),
             header: None,
+            oauth: None,
         }
     }
 
@@ -130,6 +152,7 @@ impl McpAuthConfig {
             auth_type: "bearer".to_string(),
             token: token.into(),
             header: Some("Authorization".to_string()),
+            oauth: None,
         }
     }
 
@@ -139,9 +162,25 @@ impl McpAuthConfig {
             auth_type: "api_key".to_string(),
             token: key.into(),
             header: Some("X-API-Key".to_string()),
+            oauth: None,
         }
     }
 
+    /// Creates an OAuth 2.1 authentication configuration.
+    pub fn oauth(config: OAuthConfig) -> Self {
+        Self {
+            auth_type: "oauth".to_string(),
+            token: String::new(),
+            header: Some("Authorization".to_string()),
+            oauth: Some(config),
+        }
+    }
+
+    /// Returns true if tokens come from the OAuth flow rather than `token`.
+    pub fn is_oauth(&self) -> bool {
+        self.auth_type == "oauth"
+    }
+
     /// Resolves the token value, expanding environment variable references.
     ///
     /// Environment variables are referenced using `$VAR_NAME` syntax.
@@ -187,7 +226,7 @@ impl McpAuthConfig {
         self.header
             .as_deref()
             .unwrap_or_else(|| match self.auth_type.as_str() {
-                "bearer" | "basic" => "Authorization",
+                "bearer" | "basic" | "oauth" => "Authorization",
                 "api_key" => "X-API-Key",
                 _ => "Authorization",
             })
@@ -439,6 +478,14 @@ impl McpServersConfig {
         Self::default()
     }
 
+    /// `~/.config/locus/mcp_servers.toml` (or the platform's config directory).
+    pub fn default_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("locus")
+            .join("mcp_servers.toml")
+    }
+
     /// Loads MCP server configuration from a TOML file.
     ///
     /// # Errors
@@ -4,16 +4,18 @@
 //! including configuration persistence, process lifecycle, and tool registration.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde_json::Value as JsonValue;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::mcp::client::McpClient;
 use crate::mcp::config::{McpServerConfig, McpServersConfig};
 use crate::mcp::error::McpError;
-use crate::mcp::protocol::Tool;
+use crate::mcp::oauth::OAuthSession;
+use crate::mcp::protocol::{GetPromptResult, Prompt, Tool};
+use crate::mcp::token_store::TokenStore;
 
 /// Result of testing an MCP server connection.
 #[derive(Debug, Clone)]
@@ -30,6 +32,30 @@ pub struct ServerTestResult {
     pub error: Option<String>,
 }
 
+/// What [McpManager::reload] changed. Server IDs in each list are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct McpReload {
+    /// Servers new in the configuration file
+    pub added: Vec<String>,
+    /// Servers no longer in the configuration file
+    pub removed: Vec<String>,
+    /// Servers whose configuration changed
+    pub changed: Vec<String>,
+    /// Running servers stopped because they were removed or changed
+    pub stopped: Vec<String>,
+    /// Servers started: new ones with `auto_start`, and changed ones restarted
+    pub started: Vec<String>,
+    /// Servers that failed to start, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+impl McpReload {
+    /// Whether the configuration file matched what was loaded.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
 /// MCP Manager for managing multiple MCP servers.
 ///
 /// The manager handles:
@@ -67,6 +93,8 @@ pub struct McpManager {
     configs: RwLock<HashMap<String, McpServerConfig>>,
     /// Running server clients
     running: RwLock<HashMap<String, McpClient>>,
+    /// OAuth tokens for remote servers
+    tokens: TokenStore,
 }
 
 impl McpManager {
@@ -76,9 +104,16 @@ impl McpManager {
             config_path,
             configs: RwLock::new(HashMap::new()),
             running: RwLock::new(HashMap::new()),
+            tokens: TokenStore::default(),
         }
     }
 
+    /// Uses `tokens` instead of the OS keyring for OAuth servers.
+    pub fn with_token_store(mut self, tokens: TokenStore) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
     /// Loads the MCP manager from the configuration file.
     ///
     /// If the file doesn't exist, an empty manager is created.
@@ -102,6 +137,95 @@ impl McpManager {
         Ok(manager)
     }
 
+    /// Path of the configuration file.
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Re-reads the configuration file and applies the difference to the
+    /// loaded configuration: removed servers are stopped, new `auto_start`
+    /// servers are started, and changed servers that were running (or are
+    /// `auto_start`) are restarted with their new configuration.
+    ///
+    /// A missing file means no servers. A file that fails to parse (for
+    /// example, while an editor is mid-save) leaves everything as it was.
+    pub async fn reload(&self) -> Result<McpReload, McpError> {
+        let servers = if self.config_path.exists() {
+            McpServersConfig::load(&self.config_path)
+                .map_err(|e| McpError::Config(e.to_string()))?
+                .servers
+        } else {
+            Vec::new()
+        };
+        let new: HashMap<String, McpServerConfig> =
+            servers.into_iter().map(|s| (s.id.clone(), s)).collect();
+        let old = self.configs.read().await.clone();
+
+        let mut reload = McpReload::default();
+        for (id, config) in &old {
+            match new.get(id) {
+                None => reload.removed.push(id.clone()),
+                Some(updated) if updated != config => reload.changed.push(id.clone()),
+                Some(_) => {}
+            }
+        }
+        reload.added = new
+            .keys()
+            .filter(|id| !old.contains_key(*id))
+            .cloned()
+            .collect();
+        reload.added.sort();
+        reload.removed.sort();
+        reload.changed.sort();
+        if reload.is_empty() {
+            return Ok(reload);
+        }
+
+        let running = self.list_running().await;
+        for id in reload.removed.iter().chain(&reload.changed) {
+            if running.contains(id) {
+                if let Err(e) = self.stop_server(id).await {
+                    warn!("MCP server '{}' did not shut down cleanly: {}", id, e);
+                }
+                reload.stopped.push(id.clone());
+            }
+        }
+
+        let to_start: Vec<String> = reload
+            .added
+            .iter()
+            .filter(|id| new[*id].auto_start)
+            .chain(
+                reload
+                    .changed
+                    .iter()
+                    .filter(|id| new[*id].auto_start || reload.stopped.contains(id)),
+            )
+            .cloned()
+            .collect();
+        *self.configs.write().await = new;
+
+        for id in to_start {
+            match self.start_server(&id).await {
+                Ok(()) => reload.started.push(id),
+                Err(e) => {
+                    error!("Failed to start MCP server '{}': {}", id, e);
+                    reload.failed.push((id, e.to_string()));
+                }
+            }
+        }
+        reload.stopped.sort();
+        reload.started.sort();
+
+        info!(
+            "Reloaded MCP configuration: {} added, {} removed, {} changed",
+            reload.added.len(),
+            reload.removed.len(),
+            reload.changed.len()
+        );
+        Ok(reload)
+    }
+
     /// Saves the current configuration to the configuration file.
     pub async fn save(&self) -> Result<(), McpError> {
         let configs = self.configs.read().await;
@@ -186,7 +310,7 @@ impl McpManager {
         info!("Starting MCP server: {} ({})", config.name, config.id);
 
         // Connect to server (local or remote)
-        let mut client = McpClient::connect(&config).await?;
+        let mut client = McpClient::connect_with_tokens(&config, &self.tokens).await?;
         client.initialize().await?;
 
         // Store running client
@@ -262,6 +386,15 @@ impl McpManager {
         configs.get(id).cloned()
     }
 
+    /// OAuth session for a server with `auth_type = "oauth"`, for logging in and out.
+    pub async fn oauth_session(&self, id: &str) -> Result<OAuthSession, McpError> {
+        let config = self
+            .get_config(id)
+            .await
+            .ok_or_else(|| McpError::ServerNotFound(id.to_string()))?;
+        OAuthSession::new(&config, self.tokens.clone())
+    }
+
     /// Tests an MCP server connection.
     ///
     /// This starts the server, initializes it, lists tools, and then shuts it down.
@@ -278,7 +411,7 @@ impl McpManager {
         info!("Testing MCP server: {} ({})", config.name, config.id);
 
         // Connect to server (local or remote)
-        let mut client = match McpClient::connect(&config).await {
+        let mut client = match McpClient::connect_with_tokens(&config, &self.tokens).await {
             Ok(c) => c,
             Err(e) => {
                 return Ok(ServerTestResult {
@@ -387,6 +520,34 @@ impl McpManager {
         client.list_tools().await
     }
 
+    /// Lists the prompt templates of a running MCP server.
+    pub async fn list_prompts(&self, server_id: &str) -> Result<Vec<Prompt>, McpError> {
+        let mut running = self.running.write().await;
+
+        let client = running
+            .get_mut(server_id)
+            .ok_or_else(|| McpError::ServerNotRunning(server_id.to_string()))?;
+
+        client.list_prompts().await
+    }
+
+    /// Fills in a prompt template of a running MCP server.
+    pub async fn get_prompt(
+        &self,
+        server_id: &str,
+        name: &str,
+        arguments: serde_json::Map<String, JsonValue>,
+    ) -> Result<GetPromptResult, McpError> {
+        let mut running = self.running.write().await;
+
+        let client = running
+            .get_mut(server_id)
+            .ok_or_else(|| McpError::ServerNotRunning(server_id.to_string()))?;
+
+        debug!("Getting prompt '{}' from server '{}'", name, server_id);
+        client.get_prompt(name, arguments).await
+    }
+
     /// Lists all tools from all running servers.
     ///
     /// Returns a map of server ID to tools.
@@ -426,3 +587,67 @@ impl Drop for McpManager {
         // The caller should call stop_all() before dropping
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{mcp_server, serve};
+
+    fn write_config(path: &Path, servers: &[McpServerConfig]) {
+        let mut config = McpServersConfig::new();
+        for server in servers {
+            config.add_server(server.clone());
+        }
+        config.save(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reload_starts_stops_and_restarts_servers() {
+        let url = serve(mcp_server);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mcp_servers.toml");
+        let manager = McpManager::new(path.clone());
+
+        // A missing file is an empty configuration.
+        assert!(manager.reload().await.unwrap().is_empty());
+
+        let docs = McpServerConfig::remote("docs", &url);
+        let manual = McpServerConfig::remote("manual", &url).with_auto_start(false);
+        write_config(&path, &[docs.clone(), manual.clone()]);
+        let reload = manager.reload().await.unwrap();
+        assert_eq!(reload.added, vec!["docs", "manual"]);
+        assert_eq!(reload.started, vec!["docs"]);
+        assert_eq!(manager.list_running().await, vec!["docs"]);
+        assert!(manager.reload().await.unwrap().is_empty());
+
+        // Changing a running server restarts it; removing one stops it.
+        write_config(&path, &[docs.clone().with_name("Docs v2")]);
+        let reload = manager.reload().await.unwrap();
+        assert_eq!(reload.changed, vec!["docs"]);
+        assert_eq!(reload.removed, vec!["manual"]);
+        assert_eq!(reload.stopped, vec!["docs"]);
+        assert_eq!(reload.started, vec!["docs"]);
+        assert_eq!(manager.get_config("docs").await.unwrap().name, "Docs v2");
+
+        write_config(&path, &[]);
+        let reload = manager.reload().await.unwrap();
+        assert_eq!(reload.removed, vec!["docs"]);
+        assert_eq!(reload.stopped, vec!["docs"]);
+        assert!(manager.list_running().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_state_on_unparseable_file() {
+        let url = serve(mcp_server);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mcp_servers.toml");
+        let manager = McpManager::new(path.clone());
+        write_config(&path, &[McpServerConfig::remote("docs", &url)]);
+        manager.reload().await.unwrap();
+
+        std::fs::write(&path, "[[servers]\nid = ").unwrap();
+        assert!(manager.reload().await.is_err());
+        assert_eq!(manager.list_running().await, vec!["docs"]);
+        assert_eq!(manager.list_servers().await.len(), 1);
+    }
+}
@@ -13,28 +13,46 @@
 //! - **protocol**: JSON-RPC types for MCP communication
 //! - **transport**: Transport abstraction (stdio, SSE)
 //! - **config**: Server configuration and persistence
+//! - **oauth**: OAuth 2.1 login and token refresh for remote servers
+//! - **token_store**: OAuth tokens in the OS keyring
 //! - **client**: MCP client for communicating with servers
 //! - **manager**: Server lifecycle and registry management
-//! - **toolbus_integration**: Adapter for registering MCP tools with ToolBus
+//! - **toolbus_integration**: Adapter for registering MCP tools and prompts with ToolBus
+//! - **watcher**: Hot reload of the configuration file into a live ToolBus
+//! - **server**: locus's own tools served to MCP clients (`locus mcp serve`)
 //! - **error**: Error types for MCP operations
 
 pub mod client;
 pub mod config;
 pub mod error;
 pub mod manager;
+pub mod oauth;
 pub mod protocol;
+pub mod server;
+pub mod token_store;
 pub mod toolbus_integration;
 pub mod transport;
+pub mod watcher;
+
+#[cfg(test)]
+mod test_server;
 
 // Re-export commonly used types for convenience
 pub use client::McpClient;
 pub use config::{McpAuthConfig, McpServerConfig, McpServersConfig, RestartPolicy};
 pub use error::{McpError, McpResult};
-pub use manager::{McpManager, ServerTestResult};
+pub use manager::{McpManager, McpReload, ServerTestResult};
+pub use oauth::{AuthPrompt, OAuthConfig, OAuthFlow, OAuthSession, OAuthTokens};
 pub use protocol::{
     CallToolRequest, CallToolResult, ClientCapabilities, Content, Implementation, InitializeParams,
     InitializeResult, JsonRpcError, JsonRpcRequest, JsonRpcResponse, ListToolsRequest,
-    ListToolsResult, ServerCapabilities, Tool,
+    ListToolsResult, Prompt, PromptArgument, ServerCapabilities, Tool,
+};
+pub use server::McpServer;
+pub use token_store::{StoredCredentials, TokenStore};
+pub use toolbus_integration::{
+    McpPromptWrapper, McpToolInfo, McpToolWrapper, register_mcp_prompts, register_mcp_tools,
+    sync_mcp_tools, unregister_mcp_tools,
 };
-pub use toolbus_integration::{McpToolInfo, McpToolWrapper, register_mcp_tools};
 pub use transport::{SseTransport, StdioTransport, TransportEnum, TransportType};
+pub use watcher::{apply_mcp_config, watch_mcp_config};
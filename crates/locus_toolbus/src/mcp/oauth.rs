@@ -0,0 +1,1056 @@
+//! OAuth 2.1 for remote MCP servers.
+//!
+//! - Endpoints come from `[servers.auth.oauth]` when set, else from the server's
+//!   protected resource metadata (RFC 9728) and its authorization server metadata
+//!   (RFC 8414), else the MCP defaults `/authorize`, `/token` and `/register` on
+//!   the server's origin
+//! - Without a `client_id` the client registers itself (RFC 7591) on each login
+//! - `browser` flow: authorization code with PKCE (S256) and a loopback redirect;
+//!   `device` flow: device authorization grant (RFC 8628) for machines without a
+//!   browser
+//! - Tokens live in the [TokenStore] (OS keyring) and [OAuthSession] refreshes them
+//!   for the SSE transport: before they expire and after a 401
+//!
+//! ```toml
+//! [servers.auth]
+//! auth_type = "oauth"
+//!
+//! [servers.auth.oauth]
+//! client_id = "locus"          # optional; registered dynamically when unset
+//! scopes = ["mcp:tools"]
+//! flow = "device"              # "browser" (default) or "device"
+//! ```
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::{Client as HttpClient, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, OnceCell};
+use tracing::{debug, info};
+
+use crate::mcp::config::McpServerConfig;
+use crate::mcp::error::McpError;
+use crate::mcp::token_store::{StoredCredentials, TokenStore};
+
+/// Refresh access tokens this long before they expire.
+const REFRESH_MARGIN_SECS: u64 = 60;
+/// How long a login waits for the user to finish in the browser.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+/// Device flow poll interval when the server does not give one.
+const DEFAULT_POLL_SECS: u64 = 5;
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const CALLBACK_PATH: &str = "/callback";
+/// A connection to the redirect listener that sends nothing is dropped after this.
+const CALLBACK_READ_TIMEOUT: Duration = Duration::from_secs(30);
+const CLIENT_NAME: &str = "locus";
+
+/// How `locus mcp login` gets the user's consent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthFlow {
+    /// Authorization code with PKCE; the browser redirects to a local listener.
+    #[default]
+    Browser,
+    /// Device authorization grant; the user enters a code on another device.
+    Device,
+}
+
+/// `[servers.auth.oauth]`: the OAuth client and any endpoints discovery should not pick.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthConfig {
+    /// Registered client ID; when unset the client registers itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// For confidential clients. `$VAR_NAME` reads an environment variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub flow: OAuthFlow,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_authorization_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_endpoint: Option<String>,
+    /// Loopback port for the browser redirect; any free port when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_port: Option<u16>,
+}
+
+impl OAuthConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    pub fn with_flow(mut self, flow: OAuthFlow) -> Self {
+        self.flow = flow;
+        self
+    }
+
+    fn resolve_client_secret(&self) -> Result<Option<String>, McpError> {
+        match self.client_secret.as_deref() {
+            Some(secret) if secret.starts_with('$') => {
+                std::env::var(&secret[1..]).map(Some).map_err(|_| {
+                    McpError::Config(format!("Environment variable not found: {}", &secret[1..]))
+                })
+            }
+            secret => Ok(secret.map(str::to_string)),
+        }
+    }
+}
+
+/// The authorization server endpoints a session uses.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct AuthServerMetadata {
+    #[serde(default)]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default)]
+    pub token_endpoint: Option<String>,
+    #[serde(default)]
+    pub device_authorization_endpoint: Option<String>,
+    #[serde(default)]
+    pub registration_endpoint: Option<String>,
+}
+
+/// Access and refresh tokens for one server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Unix seconds; None when the server gave no lifetime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl OAuthTokens {
+    /// Whether the access token is expired or expires within a minute of `now`.
+    pub fn expires_soon(&self, now: u64) -> bool {
+        self.expires_at
+            .is_some_and(|at| at <= now.saturating_add(REFRESH_MARGIN_SECS))
+    }
+}
+
+/// What the user has to do to finish a login.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthPrompt {
+    /// Open `url` in a browser; it redirects back to a local listener.
+    OpenBrowser { url: String },
+    /// Visit `verification_uri` and enter `user_code`.
+    EnterCode {
+        verification_uri: String,
+        user_code: String,
+        verification_uri_complete: Option<String>,
+    },
+}
+
+/// A token endpoint answer: tokens, or an OAuth error code.
+enum TokenReply {
+    Tokens(OAuthTokens),
+    Error {
+        code: String,
+        description: Option<String>,
+    },
+}
+
+/// OAuth state for one server: endpoints, the stored tokens, and refreshing them.
+/// Shared by the SSE transport, which asks it for a token on every request.
+pub struct OAuthSession {
+    server_id: String,
+    server_url: String,
+    config: OAuthConfig,
+    store: TokenStore,
+    http: HttpClient,
+    metadata: OnceCell<AuthServerMetadata>,
+    /// Loaded from the store on first use; the lock also serializes refreshes.
+    credentials: Mutex<Option<StoredCredentials>>,
+}
+
+impl OAuthSession {
+    /// Session for `server`, a remote server with `auth_type = "oauth"`.
+    pub fn new(server: &McpServerConfig, store: TokenStore) -> Result<Self, McpError> {
+        let url = server.url.clone().ok_or_else(|| {
+            McpError::Config(format!("OAuth needs a remote server URL ({})", server.id))
+        })?;
+        let config = server
+            .auth
+            .as_ref()
+            .filter(|auth| auth.is_oauth())
+            .ok_or_else(|| {
+                McpError::Config(format!(
+                    "Server '{}' is not configured for OAuth",
+                    server.id
+                ))
+            })?
+            .oauth
+            .clone()
+            .unwrap_or_default();
+        Ok(Self {
+            server_id: server.id.clone(),
+            server_url: url.trim_end_matches('/').to_string(),
+            config,
+            store,
+            http: HttpClient::new(),
+            metadata: OnceCell::new(),
+            credentials: Mutex::new(None),
+        })
+    }
+
+    /// The configured consent flow.
+    pub fn flow(&self) -> OAuthFlow {
+        self.config.flow
+    }
+
+    /// Stored tokens for this server, if the user has logged in.
+    pub async fn tokens(&self) -> Option<OAuthTokens> {
+        let mut credentials = self.credentials.lock().await;
+        self.load_into(&mut credentials).await;
+        credentials.as_ref().map(|c| c.tokens.clone())
+    }
+
+    /// A current access token, refreshed first when it is about to expire.
+    pub async fn access_token(&self) -> Result<String, McpError> {
+        let mut credentials = self.credentials.lock().await;
+        self.load_into(&mut credentials).await;
+        let Some(current) = credentials.as_mut() else {
+            return Err(self.login_needed("not logged in"));
+        };
+        if current.tokens.expires_soon(now_secs()) {
+            debug!("[MCP:{}] Access token expiring; refreshing", self.server_id);
+            self.refresh(current).await?;
+        }
+        Ok(current.tokens.access_token.clone())
+    }
+
+    /// A new access token after the server rejected `rejected`, unless another
+    /// request already replaced it.
+    pub async fn refresh_after_rejection(&self, rejected: &str) -> Result<String, McpError> {
+        let mut credentials = self.credentials.lock().await;
+        self.load_into(&mut credentials).await;
+        let Some(current) = credentials.as_mut() else {
+            return Err(self.login_needed("not logged in"));
+        };
+        if current.tokens.access_token == rejected {
+            self.refresh(current).await?;
+        }
+        Ok(current.tokens.access_token.clone())
+    }
+
+    /// Run `flow`, calling `prompt` with what the user has to do, and store the tokens.
+    pub async fn login(
+        &self,
+        flow: OAuthFlow,
+        prompt: &(dyn Fn(AuthPrompt) + Send + Sync),
+    ) -> Result<OAuthTokens, McpError> {
+        let credentials = match flow {
+            OAuthFlow::Browser => self.login_browser(prompt).await?,
+            OAuthFlow::Device => self.login_device(prompt).await?,
+        };
+        self.store.save(&self.server_id, &credentials).await?;
+        info!("[MCP:{}] Logged in", self.server_id);
+        let tokens = credentials.tokens.clone();
+        *self.credentials.lock().await = Some(credentials);
+        Ok(tokens)
+    }
+
+    /// Forget the stored tokens. Returns false when there were none.
+    pub async fn logout(&self) -> Result<bool, McpError> {
+        *self.credentials.lock().await = None;
+        self.store.delete(&self.server_id).await
+    }
+
+    async fn load_into(&self, credentials: &mut Option<StoredCredentials>) {
+        if credentials.is_none() {
+            // Tokens issued for another URL are useless here.
+            *credentials = self
+                .store
+                .load(&self.server_id)
+                .await
+                .filter(|c| c.server_url == self.server_url);
+        }
+    }
+
+    fn login_needed(&self, reason: &str) -> McpError {
+        McpError::AuthFailed(format!(
+            "'{}' {}; run `locus mcp login {}`",
+            self.server_id, reason, self.server_id
+        ))
+    }
+
+    async fn refresh(&self, credentials: &mut StoredCredentials) -> Result<(), McpError> {
+        let Some(refresh_token) = credentials.tokens.refresh_token.clone() else {
+            return Err(self.login_needed("access token expired and cannot be refreshed"));
+        };
+        let endpoint = self.endpoint(|m| m.token_endpoint.clone(), "token").await?;
+        let mut form = vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.clone()),
+            ("client_id", credentials.client_id.clone()),
+            ("resource", self.server_url.clone()),
+        ];
+        if let Some(secret) = &credentials.client_secret {
+            form.push(("client_secret", secret.clone()));
+        }
+        match self.token_request(&endpoint, &form).await? {
+            TokenReply::Tokens(mut tokens) => {
+                // Servers that do not rotate refresh tokens leave them out.
+                tokens.refresh_token.get_or_insert(refresh_token);
+                credentials.tokens = tokens;
+                self.store.save(&self.server_id, credentials).await?;
+                info!("[MCP:{}] Refreshed access token", self.server_id);
+                Ok(())
+            }
+            TokenReply::Error { code, description } => {
+                if code == "invalid_grant" {
+                    self.store.delete(&self.server_id).await?;
+                }
+                Err(self.login_needed(&format!(
+                    "refresh was refused ({})",
+                    description.unwrap_or(code)
+                )))
+            }
+        }
+    }
+
+    /// Authorization server metadata, discovered once per session.
+    async fn metadata(&self) -> &AuthServerMetadata {
+        self.metadata
+            .get_or_init(|| async {
+                let config = &self.config;
+                let configured = AuthServerMetadata {
+                    authorization_endpoint: config.authorization_endpoint.clone(),
+                    token_endpoint: config.token_endpoint.clone(),
+                    device_authorization_endpoint: config.device_authorization_endpoint.clone(),
+                    registration_endpoint: config.registration_endpoint.clone(),
+                };
+                if configured.authorization_endpoint.is_some()
+                    && configured.token_endpoint.is_some()
+                {
+                    return configured;
+                }
+                let discovered = discover(&self.http, &self.server_url).await;
+                AuthServerMetadata {
+                    authorization_endpoint: configured
+                        .authorization_endpoint
+                        .or(discovered.authorization_endpoint),
+                    token_endpoint: configured.token_endpoint.or(discovered.token_endpoint),
+                    device_authorization_endpoint: configured
+                        .device_authorization_endpoint
+                        .or(discovered.device_authorization_endpoint),
+                    registration_endpoint: configured
+                        .registration_endpoint
+                        .or(discovered.registration_endpoint),
+                }
+            })
+            .await
+    }
+
+    async fn endpoint(
+        &self,
+        pick: impl Fn(&AuthServerMetadata) -> Option<String>,
+        name: &str,
+    ) -> Result<String, McpError> {
+        pick(self.metadata().await).ok_or_else(|| {
+            McpError::AuthFailed(format!(
+                "'{}' has no OAuth {} endpoint; set it in [servers.auth.oauth]",
+                self.server_id, name
+            ))
+        })
+    }
+
+    /// The configured client, or one registered for `redirect_uri` / `grant`.
+    async fn client(
+        &self,
+        redirect_uri: Option<&str>,
+        grant: &str,
+    ) -> Result<(String, Option<String>), McpError> {
+        if let Some(client_id) = &self.config.client_id {
+            return Ok((client_id.clone(), self.config.resolve_client_secret()?));
+        }
+        let endpoint = self
+            .endpoint(|m| m.registration_endpoint.clone(), "registration")
+            .await
+            .map_err(|_| {
+                McpError::AuthFailed(format!(
+                    "'{}' does not support client registration; set client_id in [servers.auth.oauth]",
+                    self.server_id
+                ))
+            })?;
+        let body = serde_json::json!({
+            "client_name": CLIENT_NAME,
+            "redirect_uris": redirect_uri.into_iter().collect::<Vec<_>>(),
+            "grant_types": [grant, "refresh_token"],
+            "response_types": if redirect_uri.is_some() { vec!["code"] } else { Vec::new() },
+            "token_endpoint_auth_method": "none",
+        });
+        let response = self
+            .http
+            .post(&endpoint)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| McpError::AuthFailed(format!("Client registration failed: {}", e)))?;
+        let status = response.status();
+        let reply: JsonValue = response.json().await.unwrap_or_default();
+        match reply["client_id"].as_str() {
+            Some(client_id) if status.is_success() => {
+                debug!("[MCP:{}] Registered client {}", self.server_id, client_id);
+                Ok((
+                    client_id.to_string(),
+                    reply["client_secret"].as_str().map(str::to_string),
+                ))
+            }
+            _ => Err(McpError::AuthFailed(format!(
+                "Client registration failed ({}): {}",
+                status, reply
+            ))),
+        }
+    }
+
+    async fn login_browser(
+        &self,
+        prompt: &(dyn Fn(AuthPrompt) + Send + Sync),
+    ) -> Result<StoredCredentials, McpError> {
+        let listener =
+            TcpListener::bind(("127.0.0.1", self.config.redirect_port.unwrap_or(0))).await?;
+        let redirect_uri = format!(
+            "http://127.0.0.1:{}{}",
+            listener.local_addr()?.port(),
+            CALLBACK_PATH
+        );
+        let (client_id, client_secret) = self
+            .client(Some(&redirect_uri), "authorization_code")
+            .await?;
+        let authorize = self
+            .endpoint(|m| m.authorization_endpoint.clone(), "authorization")
+            .await?;
+
+        let verifier = random_token();
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let mut url = Url::parse(&authorize)
+            .map_err(|e| McpError::Config(format!("Invalid authorization endpoint: {}", e)))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &client_id)
+                .append_pair("redirect_uri", &redirect_uri)
+                .append_pair("code_challenge", &pkce_challenge(&verifier))
+                .append_pair("code_challenge_method", "S256")
+                .append_pair("state", &state)
+                .append_pair("resource", &self.server_url);
+            if !self.config.scopes.is_empty() {
+                query.append_pair("scope", &self.config.scopes.join(" "));
+            }
+        }
+        prompt(AuthPrompt::OpenBrowser {
+            url: url.to_string(),
+        });
+
+        let code = tokio::time::timeout(LOGIN_TIMEOUT, wait_for_callback(&listener, &state))
+            .await
+            .map_err(|_| McpError::AuthFailed("Timed out waiting for the browser".to_string()))??;
+
+        let token = self.endpoint(|m| m.token_endpoint.clone(), "token").await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", client_id.clone()),
+            ("code_verifier", verifier),
+            ("resource", self.server_url.clone()),
+        ];
+        if let Some(secret) = &client_secret {
+            form.push(("client_secret", secret.clone()));
+        }
+        match self.token_request(&token, &form).await? {
+            TokenReply::Tokens(tokens) => Ok(self.credentials(client_id, client_secret, tokens)),
+            TokenReply::Error { code, description } => Err(McpError::AuthFailed(format!(
+                "Authorization code exchange failed: {}",
+                description.unwrap_or(code)
+            ))),
+        }
+    }
+
+    async fn login_device(
+        &self,
+        prompt: &(dyn Fn(AuthPrompt) + Send + Sync),
+    ) -> Result<StoredCredentials, McpError> {
+        let (client_id, client_secret) = self.client(None, DEVICE_CODE_GRANT).await?;
+        let device = self
+            .endpoint(
+                |m| m.device_authorization_endpoint.clone(),
+                "device authorization",
+            )
+            .await?;
+        let mut form = vec![
+            ("client_id", client_id.clone()),
+            ("resource", self.server_url.clone()),
+        ];
+        if !self.config.scopes.is_empty() {
+            form.push(("scope", self.config.scopes.join(" ")));
+        }
+        let response = self
+            .http
+            .post(&device)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| McpError::AuthFailed(format!("Device authorization failed: {}", e)))?;
+        let reply: JsonValue = response.json().await.unwrap_or_default();
+        let (Some(device_code), Some(user_code), Some(verification_uri)) = (
+            reply["device_code"].as_str(),
+            reply["user_code"].as_str(),
+            reply["verification_uri"].as_str(),
+        ) else {
+            return Err(McpError::AuthFailed(format!(
+                "Device authorization failed: {}",
+                reply
+            )));
+        };
+        prompt(AuthPrompt::EnterCode {
+            verification_uri: verification_uri.to_string(),
+            user_code: user_code.to_string(),
+            verification_uri_complete: reply["verification_uri_complete"]
+                .as_str()
+                .map(str::to_string),
+        });
+
+        let token = self.endpoint(|m| m.token_endpoint.clone(), "token").await?;
+        let mut form = vec![
+            ("grant_type", DEVICE_CODE_GRANT.to_string()),
+            ("device_code", device_code.to_string()),
+            ("client_id", client_id.clone()),
+        ];
+        if let Some(secret) = &client_secret {
+            form.push(("client_secret", secret.clone()));
+        }
+        let mut interval = reply["interval"].as_u64().unwrap_or(DEFAULT_POLL_SECS);
+        let deadline = tokio::time::Instant::now()
+            + reply["expires_in"]
+                .as_u64()
+                .map_or(LOGIN_TIMEOUT, Duration::from_secs);
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            if tokio::time::Instant::now() > deadline {
+                return Err(McpError::AuthFailed("Device code expired".to_string()));
+            }
+            match self.token_request(&token, &form).await? {
+                TokenReply::Tokens(tokens) => {
+                    return Ok(self.credentials(client_id, client_secret, tokens));
+                }
+                TokenReply::Error { code, .. } if code == "authorization_pending" => {}
+                TokenReply::Error { code, .. } if code == "slow_down" => interval += 5,
+                TokenReply::Error { code, description } => {
+                    return Err(McpError::AuthFailed(format!(
+                        "Device login failed: {}",
+                        description.unwrap_or(code)
+                    )));
+                }
+            }
+        }
+    }
+
+    fn credentials(
+        &self,
+        client_id: String,
+        client_secret: Option<String>,
+        tokens: OAuthTokens,
+    ) -> StoredCredentials {
+        StoredCredentials {
+            server_url: self.server_url.clone(),
+            client_id,
+            // A configured secret is read from config each time; only keep registered ones.
+            client_secret: client_secret.filter(|_| self.config.client_id.is_none()),
+            tokens,
+        }
+    }
+
+    async fn token_request(
+        &self,
+        endpoint: &str,
+        form: &[(&str, String)],
+    ) -> Result<TokenReply, McpError> {
+        let response = self
+            .http
+            .post(endpoint)
+            .header("Accept", "application/json")
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| McpError::AuthFailed(format!("Token request failed: {}", e)))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let reply: JsonValue = serde_json::from_str(&text).unwrap_or_default();
+        if let Some(access_token) = reply["access_token"]
+            .as_str()
+            .filter(|_| status.is_success())
+        {
+            return Ok(TokenReply::Tokens(OAuthTokens {
+                access_token: access_token.to_string(),
+                refresh_token: reply["refresh_token"].as_str().map(str::to_string),
+                expires_at: reply["expires_in"]
+                    .as_u64()
+                    .map(|secs| now_secs().saturating_add(secs)),
+                scope: reply["scope"].as_str().map(str::to_string),
+            }));
+        }
+        match reply["error"].as_str() {
+            Some(code) => Ok(TokenReply::Error {
+                code: code.to_string(),
+                description: reply["error_description"].as_str().map(str::to_string),
+            }),
+            None => Err(McpError::AuthFailed(format!(
+                "Token request failed ({}): {}",
+                status, text
+            ))),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// 64 random characters from the PKCE verifier alphabet.
+fn random_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// S256 code challenge for `verifier` (RFC 7636).
+pub(crate) fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// `https://host/.well-known/<name>` with the URL's path appended (RFC 8414).
+fn well_known(url: &Url, name: &str) -> String {
+    let path = url.path().trim_end_matches('/');
+    format!(
+        "{}/.well-known/{}{}",
+        url.origin().ascii_serialization(),
+        name,
+        path
+    )
+}
+
+async fn fetch_json(http: &HttpClient, url: &str) -> Option<JsonValue> {
+    let response = http.get(url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Find the authorization server for the MCP server at `server_url`: its protected
+/// resource metadata names the issuer, whose metadata lists the endpoints. Falls
+/// back to the MCP default paths on the server's origin.
+async fn discover(http: &HttpClient, server_url: &str) -> AuthServerMetadata {
+    let Ok(server) = Url::parse(server_url) else {
+        return AuthServerMetadata::default();
+    };
+    let origin = server.origin().ascii_serialization();
+    let mut resource = fetch_json(http, &well_known(&server, "oauth-protected-resource")).await;
+    if resource.is_none() && server.path() != "/" {
+        resource = fetch_json(
+            http,
+            &format!("{}/.well-known/oauth-protected-resource", origin),
+        )
+        .await;
+    }
+    let issuer = resource
+        .as_ref()
+        .and_then(|r| r["authorization_servers"][0].as_str())
+        .and_then(|issuer| Url::parse(issuer).ok())
+        .unwrap_or_else(|| Url::parse(&origin).unwrap_or(server.clone()));
+    for name in ["oauth-authorization-server", "openid-configuration"] {
+        if let Some(metadata) = fetch_json(http, &well_known(&issuer, name))
+            .await
+            .and_then(|m| serde_json::from_value::<AuthServerMetadata>(m).ok())
+            .filter(|m| m.token_endpoint.is_some())
+        {
+            debug!("Discovered OAuth metadata for {}", server_url);
+            return metadata;
+        }
+    }
+    let issuer_origin = issuer.origin().ascii_serialization();
+    AuthServerMetadata {
+        authorization_endpoint: Some(format!("{}/authorize", issuer_origin)),
+        token_endpoint: Some(format!("{}/token", issuer_origin)),
+        device_authorization_endpoint: None,
+        registration_endpoint: Some(format!("{}/register", issuer_origin)),
+    }
+}
+
+/// Query parameters of a request line's target when it is the OAuth callback.
+fn callback_params(target: &str) -> Option<HashMap<String, String>> {
+    let url = Url::parse(&format!("http://127.0.0.1{}", target)).ok()?;
+    (url.path() == CALLBACK_PATH).then(|| url.query_pairs().into_owned().collect())
+}
+
+/// Serve the loopback redirect until the callback arrives; returns the code.
+/// Connections are handled concurrently: browsers open idle preconnects.
+async fn wait_for_callback(listener: &TcpListener, state: &str) -> Result<String, McpError> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let (tx, state) = (tx.clone(), state.to_string());
+                tokio::spawn(async move {
+                    if let Some(outcome) = answer_callback(stream, &state).await {
+                        let _ = tx.send(outcome).await;
+                    }
+                });
+            }
+            Some(outcome) = rx.recv() => return outcome,
+        }
+    }
+}
+
+/// Answer one request on the redirect listener; None unless it was the callback.
+async fn answer_callback(
+    mut stream: tokio::net::TcpStream,
+    state: &str,
+) -> Option<Result<String, McpError>> {
+    let mut buf = vec![0u8; 8192];
+    let n = tokio::time::timeout(CALLBACK_READ_TIMEOUT, stream.read(&mut buf))
+        .await
+        .ok()?
+        .ok()?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let target = request.split_whitespace().nth(1).unwrap_or("/");
+    let Some(params) = callback_params(target) else {
+        // Favicon and the like.
+        let _ = stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await;
+        return None;
+    };
+    let outcome = if let Some(error) = params.get("error") {
+        Err(McpError::AuthFailed(format!(
+            "Authorization denied: {}",
+            params.get("error_description").unwrap_or(error)
+        )))
+    } else if params.get("state").map(String::as_str) != Some(state) {
+        Err(McpError::AuthFailed(
+            "Authorization callback had the wrong state".to_string(),
+        ))
+    } else {
+        params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| McpError::AuthFailed("Callback without a code".to_string()))
+    };
+    let page = match &outcome {
+        Ok(_) => "Logged in to the MCP server. You can close this tab and return to locus.",
+        Err(_) => "Login failed. Check the terminal for details.",
+    };
+    let _ = stream
+        .write_all(
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )
+            .as_bytes(),
+        )
+        .await;
+    Some(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::config::McpAuthConfig;
+    use crate::mcp::test_server::serve;
+    use crate::mcp::transport::{SseTransport, TransportEnum};
+    use std::io::{Read, Write};
+    use std::sync::Arc;
+
+    /// Token endpoint that swaps refresh token `r1` for access token `a2`, and an
+    /// MCP endpoint that only accepts `a2`.
+    fn refreshing_server(target: &str, authorization: &str, body: &str) -> (u16, String) {
+        match target {
+            "POST /token" if body.contains("grant_type=refresh_token&refresh_token=r1") => (
+                200,
+                r#"{"access_token":"a2","token_type":"Bearer","expires_in":3600}"#.to_string(),
+            ),
+            "POST /token" => (400, r#"{"error":"invalid_grant"}"#.to_string()),
+            "POST /message" if authorization == "Bearer a2" => (
+                200,
+                r#"{"jsonrpc":"2.0","id":1,"result":{"ok":true}}"#.to_string(),
+            ),
+            _ => (401, String::new()),
+        }
+    }
+
+    fn oauth_server(url: &str, config: OAuthConfig) -> McpServerConfig {
+        McpServerConfig::remote("hosted", url).with_auth(McpAuthConfig::oauth(config))
+    }
+
+    fn stored(url: &str, access_token: &str, expires_at: Option<u64>) -> StoredCredentials {
+        StoredCredentials {
+            server_url: url.to_string(),
+            client_id: "locus".to_string(),
+            client_secret: None,
+            tokens: OAuthTokens {
+                access_token: access_token.to_string(),
+                refresh_token: Some("r1".to_string()),
+                expires_at,
+                scope: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_pkce_challenge_matches_rfc_7636() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        let verifier = random_token();
+        assert_eq!(verifier.len(), 64);
+        assert_ne!(verifier, random_token());
+    }
+
+    #[test]
+    fn test_well_known_and_callback_urls() {
+        let url = Url::parse("https://mcp.example.com/v1/").unwrap();
+        assert_eq!(
+            well_known(&url, "oauth-protected-resource"),
+            "https://mcp.example.com/.well-known/oauth-protected-resource/v1"
+        );
+        let root = Url::parse("https://auth.example.com").unwrap();
+        assert_eq!(
+            well_known(&root, "oauth-authorization-server"),
+            "https://auth.example.com/.well-known/oauth-authorization-server"
+        );
+
+        let params = callback_params("/callback?code=abc&state=s%201").unwrap();
+        assert_eq!(params["code"], "abc");
+        assert_eq!(params["state"], "s 1");
+        assert!(callback_params("/favicon.ico").is_none());
+    }
+
+    #[test]
+    fn test_tokens_expire_with_margin() {
+        let tokens = stored("https://x", "a", Some(1_000)).tokens;
+        assert!(tokens.expires_soon(1_000));
+        assert!(tokens.expires_soon(1_000 - REFRESH_MARGIN_SECS));
+        assert!(!tokens.expires_soon(1_000 - REFRESH_MARGIN_SECS - 1));
+        assert!(!stored("https://x", "a", None).tokens.expires_soon(u64::MAX));
+    }
+
+    #[test]
+    fn test_oauth_config_from_toml() {
+        let auth: McpAuthConfig = toml::from_str(
+            "auth_type = \"oauth\"\n[oauth]\nscopes = [\"mcp:tools\"]\nflow = \"device\"\n",
+        )
+        .unwrap();
+        assert!(auth.is_oauth());
+        assert!(auth.token.is_empty());
+        let oauth = auth.oauth.unwrap();
+        assert_eq!(oauth.flow, OAuthFlow::Device);
+        assert_eq!(oauth.scopes, vec!["mcp:tools"]);
+    }
+
+    #[tokio::test]
+    async fn test_transport_refreshes_expired_and_rejected_tokens() {
+        let base = serve(refreshing_server);
+        let config = OAuthConfig {
+            authorization_endpoint: Some(format!("{}/authorize", base)),
+            token_endpoint: Some(format!("{}/token", base)),
+            ..OAuthConfig::default()
+        };
+        let server = oauth_server(&base, config);
+
+        // Expired before the request: refreshed up front.
+        // Still valid but rejected with a 401: refreshed and retried.
+        for expires_at in [Some(1), None] {
+            let dir = tempfile::TempDir::new().unwrap();
+            let store = TokenStore::file_only(dir.path().to_path_buf());
+            store
+                .save("hosted", &stored(&base, "a1", expires_at))
+                .await
+                .unwrap();
+            let session = Arc::new(OAuthSession::new(&server, store.clone()).unwrap());
+            let mut transport = TransportEnum::Sse(
+                SseTransport::new(&base, reqwest::header::HeaderMap::new(), "hosted")
+                    .with_oauth(session),
+            );
+
+            let result: JsonValue = transport.send_request("ping", None).await.unwrap();
+            assert_eq!(result["ok"], true);
+            let saved = store.load("hosted").await.unwrap();
+            assert_eq!(saved.tokens.access_token, "a2");
+            assert_eq!(saved.tokens.refresh_token.as_deref(), Some("r1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_foreign_tokens_need_login() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = TokenStore::file_only(dir.path().to_path_buf());
+        let server = oauth_server("https://mcp.example.com", OAuthConfig::default());
+        let session = OAuthSession::new(&server, store.clone()).unwrap();
+        let err = session.access_token().await.unwrap_err();
+        assert!(err.to_string().contains("locus mcp login hosted"));
+
+        store
+            .save("hosted", &stored("https://other.example.com", "a1", None))
+            .await
+            .unwrap();
+        let session = OAuthSession::new(&server, store).unwrap();
+        assert!(session.tokens().await.is_none());
+    }
+
+    fn device_server(target: &str, _authorization: &str, body: &str) -> (u16, String) {
+        static POLLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        match target {
+            "POST /register" => (201, r#"{"client_id":"registered"}"#.to_string()),
+            "POST /device" if body.contains("client_id=registered") => (
+                200,
+                r#"{"device_code":"d1","user_code":"ABCD-1234","verification_uri":"https://example.com/device","interval":0}"#
+                    .to_string(),
+            ),
+            "POST /token" if body.contains("device_code=d1") => {
+                if POLLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    (400, r#"{"error":"authorization_pending"}"#.to_string())
+                } else {
+                    (
+                        200,
+                        r#"{"access_token":"a1","refresh_token":"r1","expires_in":3600}"#.to_string(),
+                    )
+                }
+            }
+            _ => (404, r#"{"error":"not_found"}"#.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_device_login_registers_client_and_stores_tokens() {
+        let base = serve(device_server);
+        let config = OAuthConfig {
+            authorization_endpoint: Some(format!("{}/authorize", base)),
+            token_endpoint: Some(format!("{}/token", base)),
+            device_authorization_endpoint: Some(format!("{}/device", base)),
+            registration_endpoint: Some(format!("{}/register", base)),
+            ..OAuthConfig::default()
+        };
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = TokenStore::file_only(dir.path().to_path_buf());
+        let session = OAuthSession::new(&oauth_server(&base, config), store.clone()).unwrap();
+
+        let prompts = std::sync::Mutex::new(Vec::new());
+        let tokens = session
+            .login(OAuthFlow::Device, &|p| prompts.lock().unwrap().push(p))
+            .await
+            .unwrap();
+        assert_eq!(tokens.access_token, "a1");
+        assert!(matches!(
+            &prompts.lock().unwrap()[..],
+            [AuthPrompt::EnterCode { user_code, .. }] if user_code == "ABCD-1234"
+        ));
+        let saved = store.load("hosted").await.unwrap();
+        assert_eq!(saved.client_id, "registered");
+        assert_eq!(saved.server_url, base);
+        assert_eq!(session.access_token().await.unwrap(), "a1");
+
+        assert!(session.logout().await.unwrap());
+        assert!(store.load("hosted").await.is_none());
+    }
+
+    fn code_server(target: &str, _authorization: &str, body: &str) -> (u16, String) {
+        match target {
+            "POST /token"
+                if body.contains("grant_type=authorization_code")
+                    && body.contains("code=c1")
+                    && body.contains("code_verifier=") =>
+            {
+                (200, r#"{"access_token":"a1"}"#.to_string())
+            }
+            _ => (400, r#"{"error":"invalid_request"}"#.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_browser_login_exchanges_the_callback_code() {
+        let base = serve(code_server);
+        let config = OAuthConfig {
+            client_id: Some("locus".to_string()),
+            authorization_endpoint: Some(format!("{}/authorize", base)),
+            token_endpoint: Some(format!("{}/token", base)),
+            scopes: vec!["mcp:tools".to_string()],
+            ..OAuthConfig::default()
+        };
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = TokenStore::file_only(dir.path().to_path_buf());
+        let session = OAuthSession::new(&oauth_server(&base, config), store).unwrap();
+
+        // Play the browser: follow the redirect with a code and the given state.
+        let open = |prompt: AuthPrompt| {
+            let AuthPrompt::OpenBrowser { url } = prompt else {
+                panic!("expected a browser prompt");
+            };
+            let url = Url::parse(&url).unwrap();
+            let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+            assert_eq!(params["code_challenge_method"], "S256");
+            assert_eq!(params["scope"], "mcp:tools");
+            let redirect = Url::parse(&params["redirect_uri"]).unwrap();
+            let state = params["state"].clone();
+            std::thread::spawn(move || {
+                // An idle preconnect must not hold up the callback.
+                let _idle = std::net::TcpStream::connect((
+                    redirect.host_str().unwrap(),
+                    redirect.port().unwrap(),
+                ))
+                .unwrap();
+                let mut stream = std::net::TcpStream::connect((
+                    redirect.host_str().unwrap(),
+                    redirect.port().unwrap(),
+                ))
+                .unwrap();
+                write!(
+                    stream,
+                    "GET {}?code=c1&state={} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                    redirect.path(),
+                    state
+                )
+                .unwrap();
+                let mut reply = String::new();
+                let _ = stream.read_to_string(&mut reply);
+            });
+        };
+        let tokens = session.login(OAuthFlow::Browser, &open).await.unwrap();
+        assert_eq!(tokens.access_token, "a1");
+    }
+}
@@ -481,6 +481,68 @@ impl Content {
     }
 }
 
+// =============================================================================
+// MCP Prompt Types
+// =============================================================================
+
+/// A prompt template a server offers, filled in by [GetPromptRequest].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prompt {
+    /// The unique name of the prompt
+    pub name: String,
+    /// What the prompt is for
+    #[serde(default)]
+    pub description: String,
+    /// The arguments the template takes
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// One argument of a [Prompt].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the prompt can't be filled in without it
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Result of listing available prompts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPromptsResult {
+    pub prompts: Vec<Prompt>,
+    /// Optional cursor for pagination (if more prompts available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Request to fill in a prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptRequest {
+    pub name: String,
+    /// Argument values by name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<serde_json::Map<String, Value>>,
+}
+
+/// A filled-in prompt: the messages to start a conversation with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// One message of a [GetPromptResult].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMessage {
+    /// `user` or `assistant`
+    pub role: String,
+    pub content: Content,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
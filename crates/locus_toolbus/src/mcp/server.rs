@@ -0,0 +1,417 @@
+//! locus as an MCP server.
+//!
+//! [McpServer] answers MCP requests from another agent or an editor with the
+//! tools on a [ToolBus], so they get locus's implementations: protected paths,
+//! command rules, edit history and conflict checks all apply as they do in a
+//! session. `locus mcp serve` runs it over stdio, one JSON-RPC message per line.
+//!
+//! Calls run concurrently; `notifications/cancelled` stops one. The bus has no
+//! approver here, so `ask` rules allow the call: the client is expected to ask
+//! its user before calling a tool.
+//!
+//! Responses use the field names of the MCP specification (`protocolVersion`,
+//! `inputSchema`, `isError`), which is what clients send and expect.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::mcp::error::McpError;
+use crate::mcp::protocol::{JsonRpcErrorObject, JsonRpcResponse, error_codes};
+use crate::{CommandDenied, ProtectedPathDenied, ToolBus, ToolContext, ToolInfo, ToolInterrupted};
+
+/// The protocol version answered when the client does not ask for one we know.
+pub const SERVER_PROTOCOL_VERSION: &str = "2025-03-26";
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// Serves the tools of a [ToolBus] to an MCP client.
+pub struct McpServer {
+    toolbus: Arc<ToolBus>,
+    /// Only these tools are listed and callable; None serves every tool.
+    allowed: Option<HashSet<String>>,
+    /// Cancellation for calls still running, by request ID.
+    running: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl McpServer {
+    pub fn new(toolbus: Arc<ToolBus>) -> Self {
+        Self {
+            toolbus,
+            allowed: None,
+            running: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Serve only the named tools.
+    pub fn with_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// The tools a client sees, sorted by name.
+    pub fn tools(&self) -> Vec<ToolInfo> {
+        self.toolbus
+            .list_tools()
+            .into_iter()
+            .filter(|tool| self.serves(&tool.name))
+            .collect()
+    }
+
+    fn serves(&self, tool_name: &str) -> bool {
+        self.allowed
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(tool_name))
+    }
+
+    /// Serve on stdin and stdout until stdin closes.
+    pub async fn serve_stdio(self) -> Result<(), McpError> {
+        let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+        Arc::new(self).serve(stdin, tokio::io::stdout()).await
+    }
+
+    /// Read one message per line from `reader` and write responses to `writer`
+    /// until the reader ends and the calls in flight have answered.
+    pub async fn serve<R, W>(self: Arc<Self>, reader: R, mut writer: W) -> Result<(), McpError>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        let write_task = tokio::spawn(async move {
+            while let Some(line) = rx.recv().await {
+                writer.write_all(line.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+            Ok::<(), std::io::Error>(())
+        });
+
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let server = Arc::clone(&self);
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let Some(response) = server.handle(&line).await {
+                    let _ = tx.send(response);
+                }
+            });
+        }
+
+        drop(tx);
+        write_task
+            .await
+            .map_err(|e| McpError::Io(std::io::Error::other(e)))??;
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC message. Returns the response line, or None for a
+    /// notification.
+    pub async fn handle(&self, message: &str) -> Option<String> {
+        let request: Value = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    error_codes::PARSE_ERROR,
+                    format!("Parse error: {}", e),
+                ));
+            }
+        };
+        let method = request.get("method").and_then(Value::as_str);
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let Some(id) = request.get("id").cloned() else {
+            if let Some(method) = method {
+                self.notify(method, &params);
+            }
+            return None;
+        };
+        let Some(method) = method else {
+            return Some(error_response(
+                id,
+                error_codes::INVALID_REQUEST,
+                "Request has no method",
+            ));
+        };
+
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(self.list_result()),
+            "tools/call" => self.call(&id, &params).await,
+            _ => Err(JsonRpcErrorObject::new(
+                error_codes::METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        };
+        let response = match result {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(error) => JsonRpcResponse::error(id, error),
+        };
+        serde_json::to_string(&response).ok()
+    }
+
+    fn notify(&self, method: &str, params: &Value) {
+        match method {
+            "notifications/cancelled" => {
+                let Some(id) = params.get("requestId") else {
+                    return;
+                };
+                if let Some(cancel) = self
+                    .running
+                    .lock()
+                    .ok()
+                    .and_then(|mut running| running.remove(&id.to_string()))
+                {
+                    cancel.cancel();
+                }
+            }
+            _ => debug!("Ignoring MCP notification {}", method),
+        }
+    }
+
+    fn list_result(&self) -> Value {
+        let tools: Vec<Value> = self
+            .tools()
+            .into_iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "inputSchema": tool.parameters,
+                })
+            })
+            .collect();
+        json!({ "tools": tools })
+    }
+
+    async fn call(&self, id: &Value, params: &Value) -> Result<Value, JsonRpcErrorObject> {
+        let name = params.get("name").and_then(Value::as_str).ok_or_else(|| {
+            JsonRpcErrorObject::new(error_codes::INVALID_PARAMS, "tools/call needs a name")
+        })?;
+        if !self.serves(name) || self.toolbus.permission_of(name).is_none() {
+            return Err(JsonRpcErrorObject::new(
+                error_codes::INVALID_PARAMS,
+                format!("Unknown tool: {}", name),
+            ));
+        }
+        let args = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let key = id.to_string();
+        let cancel = CancellationToken::new();
+        if let Ok(mut running) = self.running.lock() {
+            running.insert(key.clone(), cancel.clone());
+        }
+        let ctx = ToolContext::new().with_cancel(cancel);
+        let outcome = self.toolbus.call_with_context(name, args, ctx).await;
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&key);
+        }
+
+        Ok(match outcome {
+            Ok((output, _duration_ms)) => tool_result(&output, false),
+            // The call ran and failed: a result the model can read, not a protocol error.
+            Err(e) => tool_result(&error_output(&e), true),
+        })
+    }
+}
+
+/// Rule violations and interrupted calls keep their structured form, as in a session.
+fn error_output(e: &anyhow::Error) -> Value {
+    if let Some(denied) = e.downcast_ref::<CommandDenied>() {
+        denied.to_json()
+    } else if let Some(denied) = e.downcast_ref::<ProtectedPathDenied>() {
+        denied.to_json()
+    } else if let Some(interrupted) = e.downcast_ref::<ToolInterrupted>() {
+        interrupted.to_json()
+    } else {
+        json!({ "error": e.to_string() })
+    }
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(SERVER_PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": {
+            "name": "locus",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// Tool output as MCP text content; strings are sent as they are, anything else
+/// as pretty JSON.
+fn tool_result(output: &Value, is_error: bool) -> Value {
+    let text = match output {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    };
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn error_response(id: Value, code: i32, message: impl Into<String>) -> String {
+    let response = JsonRpcResponse::<Value>::error(id, JsonRpcErrorObject::new(code, message));
+    serde_json::to_string(&response).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, BufReader};
+
+    fn server(dir: &tempfile::TempDir) -> McpServer {
+        McpServer::new(Arc::new(ToolBus::new(dir.path().to_path_buf())))
+    }
+
+    async fn request(server: &McpServer, message: Value) -> Value {
+        let line = server.handle(&message.to_string()).await.unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let server = server(&dir).with_tools(["read", "grep", "edit_file", "bash"]);
+
+        let init = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize",
+                   "params": {"protocolVersion": "2024-11-05", "capabilities": {},
+                              "clientInfo": {"name": "editor", "version": "1"}}}),
+        )
+        .await;
+        assert_eq!(init["id"], 1);
+        assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(init["result"]["serverInfo"]["name"], "locus");
+        assert!(init["result"]["capabilities"]["tools"].is_object());
+
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(server.handle(&initialized.to_string()).await.is_none());
+
+        let list = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": "2", "method": "tools/list"}),
+        )
+        .await;
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["bash", "edit_file", "grep", "read"]);
+        assert!(list["result"]["tools"][0]["inputSchema"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_call_tool() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hello from locus\n").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=secret\n").unwrap();
+        let server = server(&dir).with_tools(["read"]);
+
+        let read = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                   "params": {"name": "read", "arguments": {"path": "notes.txt"}}}),
+        )
+        .await;
+        assert_eq!(read["result"]["isError"], false);
+        let text = read["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("hello from locus"));
+
+        let denied = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                   "params": {"name": "read", "arguments": {"path": ".env"}}}),
+        )
+        .await;
+        assert_eq!(denied["result"]["isError"], true);
+        let text = denied["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("protected_path"));
+        assert!(!text.contains("TOKEN=secret"));
+
+        let hidden = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 5, "method": "tools/call",
+                   "params": {"name": "bash", "arguments": {"command": "echo hi"}}}),
+        )
+        .await;
+        assert_eq!(hidden["error"]["code"], error_codes::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let server = server(&dir);
+
+        let parse: Value =
+            serde_json::from_str(&server.handle("{not json").await.unwrap()).unwrap();
+        assert_eq!(parse["error"]["code"], error_codes::PARSE_ERROR);
+        assert_eq!(parse["id"], Value::Null);
+
+        let unknown = request(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 6, "method": "resources/list"}),
+        )
+        .await;
+        assert_eq!(unknown["error"]["code"], error_codes::METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_serve_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let server = Arc::new(server(&dir).with_tools(["bash"]));
+        let input = [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                   "params": {"name": "bash", "arguments": {"command": "echo served"}}}),
+        ]
+        .iter()
+        .map(|m| format!("{}\n", m))
+        .collect::<String>();
+
+        let (mut client, server_end) = tokio::io::duplex(64 * 1024);
+        server
+            .serve(BufReader::new(input.as_bytes()), server_end)
+            .await
+            .unwrap();
+
+        let mut output = String::new();
+        client.read_to_string(&mut output).await.unwrap();
+        let responses: HashMap<i64, Value> = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .map(|r| (r["id"].as_i64().unwrap(), r))
+            .collect();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[&1]["result"], json!({}));
+        let text = responses[&2]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap();
+        assert!(text.contains("served"));
+    }
+}
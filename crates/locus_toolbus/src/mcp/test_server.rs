@@ -0,0 +1,100 @@
+//! A throwaway HTTP server for transport, OAuth and reload tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Answer requests with `handler(method path, authorization, body)` until the
+/// test ends; returns the base URL.
+pub(crate) fn serve(handler: fn(&str, &str, &str) -> (u16, String)) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let (mut length, mut authorization) = (0, String::new());
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => length = value.trim().parse().unwrap(),
+                    "authorization" => authorization = value.trim().to_string(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let target: Vec<&str> = request_line.split_whitespace().take(2).collect();
+            let (status, reply) = handler(
+                &target.join(" "),
+                &authorization,
+                &String::from_utf8_lossy(&body),
+            );
+            let _ = stream.write_all(
+                format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    reply.len(),
+                    reply
+                )
+                .as_bytes(),
+            );
+        }
+    });
+    format!("http://{}", addr)
+}
+
+/// A remote MCP server with one tool, `echo`, and one prompt, `review`, at
+/// `/message`. Field names follow [crate::mcp::protocol].
+pub(crate) fn mcp_server(target: &str, _authorization: &str, body: &str) -> (u16, String) {
+    let request: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+    let result = match request["method"].as_str() {
+        _ if target != "POST /message" => return (404, String::new()),
+        // Notifications get no answer.
+        _ if request.get("id").is_none() => return (202, String::new()),
+        Some("initialize") => serde_json::json!({
+            "protocol_version": "2024-11-05",
+            "capabilities": {},
+            "server_info": { "name": "mock", "version": "1.0" },
+        }),
+        Some("tools/list") => serde_json::json!({
+            "tools": [{
+                "name": "echo",
+                "description": "Echo the input",
+                "input_schema": { "type": "object" },
+            }],
+        }),
+        Some("prompts/list") => serde_json::json!({
+            "prompts": [{
+                "name": "review",
+                "description": "Review a file",
+                "arguments": [
+                    { "name": "path", "required": true },
+                    { "name": "focus" },
+                ],
+            }],
+        }),
+        Some("prompts/get") => {
+            let args = &request["params"]["arguments"];
+            let focus = args["focus"].as_str().unwrap_or("bugs");
+            serde_json::json!({
+                "messages": [{
+                    "role": "user",
+                    "content": {
+                        "type": "text",
+                        "text": format!("Review {} for {}.", args["path"].as_str().unwrap_or("?"), focus),
+                    },
+                }],
+            })
+        }
+        _ => serde_json::json!({}),
+    };
+    let reply = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+    (200, reply.to_string())
+}
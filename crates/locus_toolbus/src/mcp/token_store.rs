@@ -0,0 +1,212 @@
+//! Where OAuth tokens for MCP servers are kept.
+//!
+//! The OS keyring (macOS Keychain, Windows Credential Manager, Secret Service on
+//! Linux) under the service `locus-mcp`, one entry per server ID. Where no keyring
+//! is available (headless Linux, containers) tokens fall back to
+//! `~/.config/locus/mcp_tokens/<server_id>.json`, readable only by the user.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::mcp::error::McpError;
+use crate::mcp::oauth::OAuthTokens;
+
+const KEYRING_SERVICE: &str = "locus-mcp";
+
+/// What a login leaves behind for one server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCredentials {
+    /// The server the tokens were issued for; a changed URL needs a new login.
+    pub server_url: String,
+    pub client_id: String,
+    /// Secret from dynamic client registration, if the server issued one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub tokens: OAuthTokens,
+}
+
+/// Keyring-backed token storage with a file fallback.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    dir: PathBuf,
+    keyring: bool,
+}
+
+impl Default for TokenStore {
+    fn default() -> Self {
+        let dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("locus")
+            .join("mcp_tokens");
+        Self::new(dir)
+    }
+}
+
+impl TokenStore {
+    /// Keyring first, files in `dir` when the keyring is unavailable.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, keyring: true }
+    }
+
+    /// Files in `dir` only, never the keyring (tests, CI).
+    pub fn file_only(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            keyring: false,
+        }
+    }
+
+    pub async fn load(&self, server_id: &str) -> Option<StoredCredentials> {
+        let store = self.clone();
+        let server_id = server_id.to_string();
+        tokio::task::spawn_blocking(move || store.load_blocking(&server_id))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    pub async fn save(
+        &self,
+        server_id: &str,
+        credentials: &StoredCredentials,
+    ) -> Result<(), McpError> {
+        let store = self.clone();
+        let server_id = server_id.to_string();
+        let json = serde_json::to_string(credentials)?;
+        tokio::task::spawn_blocking(move || store.save_blocking(&server_id, &json))
+            .await
+            .map_err(|e| McpError::Io(std::io::Error::other(e)))?
+    }
+
+    /// Remove a server's tokens. Returns false when there were none.
+    pub async fn delete(&self, server_id: &str) -> Result<bool, McpError> {
+        let store = self.clone();
+        let server_id = server_id.to_string();
+        tokio::task::spawn_blocking(move || store.delete_blocking(&server_id))
+            .await
+            .map_err(|e| McpError::Io(std::io::Error::other(e)))?
+    }
+
+    fn entry(&self, server_id: &str) -> Option<keyring::Entry> {
+        if !self.keyring {
+            return None;
+        }
+        keyring::Entry::new(KEYRING_SERVICE, server_id)
+            .inspect_err(|e| debug!("Keyring unavailable: {}", e))
+            .ok()
+    }
+
+    fn path(&self, server_id: &str) -> PathBuf {
+        let name: String = server_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", name))
+    }
+
+    fn load_blocking(&self, server_id: &str) -> Option<StoredCredentials> {
+        let json = self
+            .entry(server_id)
+            .and_then(|entry| entry.get_password().ok())
+            .or_else(|| std::fs::read_to_string(self.path(server_id)).ok())?;
+        serde_json::from_str(&json)
+            .inspect_err(|e| debug!("Ignoring unreadable MCP tokens for {}: {}", server_id, e))
+            .ok()
+    }
+
+    fn save_blocking(&self, server_id: &str, json: &str) -> Result<(), McpError> {
+        let path = self.path(server_id);
+        if let Some(entry) = self.entry(server_id) {
+            match entry.set_password(json) {
+                Ok(()) => {
+                    // Don't leave an older copy on disk.
+                    let _ = std::fs::remove_file(&path);
+                    return Ok(());
+                }
+                Err(e) => debug!(
+                    "Keyring unavailable ({}); storing MCP tokens in {}",
+                    e,
+                    path.display()
+                ),
+            }
+        }
+        write_private(&path, json)?;
+        Ok(())
+    }
+
+    fn delete_blocking(&self, server_id: &str) -> Result<bool, McpError> {
+        let in_keyring = self
+            .entry(server_id)
+            .is_some_and(|entry| entry.delete_credential().is_ok());
+        let path = self.path(server_id);
+        let on_disk = path.exists();
+        if on_disk {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(in_keyring || on_disk)
+    }
+}
+
+/// Write `contents` to `path`, readable and writable by the owner only.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(path)?, contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials() -> StoredCredentials {
+        StoredCredentials {
+            server_url: "https://mcp.example.com".to_string(),
+            client_id: "locus".to_string(),
+            client_secret: None,
+            tokens: OAuthTokens {
+                access_token: "a1".to_string(),
+                refresh_token: Some("r1".to_string()),
+                expires_at: Some(1_700_000_000),
+                scope: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_store_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = TokenStore::file_only(dir.path().to_path_buf());
+        assert!(store.load("hosted/prod").await.is_none());
+
+        store.save("hosted/prod", &credentials()).await.unwrap();
+        let path = dir.path().join("hosted_prod.json");
+        assert!(path.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(store.load("hosted/prod").await, Some(credentials()));
+
+        assert!(store.delete("hosted/prod").await.unwrap());
+        assert!(!store.delete("hosted/prod").await.unwrap());
+        assert!(store.load("hosted/prod").await.is_none());
+    }
+}
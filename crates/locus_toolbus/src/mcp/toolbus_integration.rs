@@ -1,6 +1,8 @@
 //! ToolBus Integration for MCP Tools
 //!
-//! This module provides the [`McpToolWrapper`] for exposing MCP tools as ToolBus tools.
+//! This module provides the [`McpToolWrapper`] for exposing MCP tools as ToolBus tools,
+//! and the [`McpPromptWrapper`] for the prompt templates servers offer, which the
+//! chat runs as `/mcp.{server_id}.{prompt}` commands.
 
 use std::sync::Arc;
 
@@ -8,8 +10,8 @@ use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
 use crate::ToolResult;
-use crate::mcp::manager::McpManager;
-use crate::mcp::protocol::Tool;
+use crate::mcp::manager::{McpManager, McpReload};
+use crate::mcp::protocol::{Prompt, Tool};
 
 /// Wrapper that exposes an MCP tool as a ToolBus tool.
 ///
@@ -26,6 +28,9 @@ pub struct McpToolWrapper {
     manager: Arc<McpManager>,
     /// Cached namespaced name
     namespaced_name: String,
+    /// Leaked once for [crate::Tool::name] and [crate::Tool::description]
+    static_name: &'static str,
+    static_description: &'static str,
 }
 
 impl McpToolWrapper {
@@ -38,11 +43,17 @@ impl McpToolWrapper {
     /// * `manager` - Reference to the MCP manager
     pub fn new(server_id: String, tool: Tool, manager: Arc<McpManager>) -> Self {
         let namespaced_name = format!("mcp.{}.{}", server_id, tool.name);
+        // Tools need 'static strings. Leak them here rather than on every call, since
+        // a hot-reloaded server registers new wrappers and the bus lists tools often.
+        let static_name = Box::leak(namespaced_name.clone().into_boxed_str());
+        let static_description = Box::leak(tool.description.clone().into_boxed_str());
         Self {
             server_id,
             tool,
             manager,
             namespaced_name,
+            static_name,
+            static_description,
         }
     }
 
@@ -67,14 +78,11 @@ impl McpToolWrapper {
 #[async_trait]
 impl crate::Tool for McpToolWrapper {
     fn name(&self) -> &'static str {
-        // Leak the string to get a 'static lifetime
-        // This is acceptable because tools are typically created once and live for the program's lifetime
-        Box::leak(self.namespaced_name.clone().into_boxed_str())
+        self.static_name
     }
 
     fn description(&self) -> &'static str {
-        // Leak the string to get a 'static lifetime
-        Box::leak(self.tool.description.clone().into_boxed_str())
+        self.static_description
     }
 
     fn parameters_schema(&self) -> JsonValue {
@@ -82,11 +90,18 @@ impl crate::Tool for McpToolWrapper {
     }
 
     async fn execute(&self, args: serde_json::Value) -> ToolResult {
-        let result = self
-            .manager
-            .call_tool(&self.server_id, &self.tool.name, args)
-            .await
-            .map_err(|e| anyhow::anyhow!("MCP tool error: {}", e))?;
+        // The stdio transport reads responses with blocking I/O. Run the call on a
+        // blocking thread so a hung server cannot stall the runtime and the ToolBus
+        // timeout can fire; an abandoned call ends when the server answers or exits.
+        let manager = Arc::clone(&self.manager);
+        let (server_id, tool_name) = (self.server_id.clone(), self.tool.name.clone());
+        let handle = tokio::runtime::Handle::current();
+        let result = tokio::task::spawn_blocking(move || {
+            handle.block_on(manager.call_tool(&server_id, &tool_name, args))
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("MCP tool task failed: {}", e))?
+        .map_err(|e| anyhow::anyhow!("MCP tool error: {}", e))?;
 
         // Check if the result indicates an error
         let is_error = result
@@ -111,6 +126,121 @@ impl crate::Tool for McpToolWrapper {
     }
 }
 
+/// A prompt template of an MCP server, registered on the ToolBus as
+/// `mcp.{server_id}.{prompt}`.
+pub struct McpPromptWrapper {
+    server_id: String,
+    prompt: Prompt,
+    manager: Arc<McpManager>,
+    namespaced_name: String,
+}
+
+impl McpPromptWrapper {
+    pub fn new(server_id: String, prompt: Prompt, manager: Arc<McpManager>) -> Self {
+        let namespaced_name = format!("mcp.{}.{}", server_id, prompt.name);
+        Self {
+            server_id,
+            prompt,
+            manager,
+            namespaced_name,
+        }
+    }
+
+    /// Returns the namespaced prompt name.
+    ///
+    /// Format: `mcp.{server_id}.{prompt_name}`
+    pub fn namespaced_name(&self) -> &str {
+        &self.namespaced_name
+    }
+
+    /// What the prompt is for, as the server describes it.
+    pub fn description(&self) -> &str {
+        &self.prompt.description
+    }
+
+    /// The arguments it takes, e.g. `<path> [focus]`; required ones in angle brackets.
+    pub fn usage(&self) -> String {
+        self.prompt
+            .arguments
+            .iter()
+            .map(|a| {
+                if a.required {
+                    format!("<{}>", a.name)
+                } else {
+                    format!("[{}]", a.name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Fill in the prompt with what followed its name in the chat and return its
+    /// text: the messages' text content, in order, separated by blank lines.
+    ///
+    /// `name=value` words set that argument; the rest of the text goes to the
+    /// first argument not set that way.
+    pub async fn render(&self, args: &str) -> Result<String, anyhow::Error> {
+        let arguments = prompt_arguments(&self.prompt, args).map_err(anyhow::Error::msg)?;
+        // Blocking stdio reads, as in [McpToolWrapper]'s calls.
+        let manager = Arc::clone(&self.manager);
+        let (server_id, prompt_name) = (self.server_id.clone(), self.prompt.name.clone());
+        let handle = tokio::runtime::Handle::current();
+        let result = tokio::task::spawn_blocking(move || {
+            handle.block_on(manager.get_prompt(&server_id, &prompt_name, arguments))
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("MCP prompt task failed: {}", e))?
+        .map_err(|e| anyhow::anyhow!("MCP prompt error: {}", e))?;
+
+        let text = result
+            .messages
+            .iter()
+            .filter(|m| m.content.content_type == "text")
+            .filter_map(|m| m.content.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if text.trim().is_empty() {
+            return Err(anyhow::anyhow!("{} returned no text", self.namespaced_name));
+        }
+        Ok(text)
+    }
+}
+
+/// The argument values for `prompt` from the text typed after its name.
+fn prompt_arguments(
+    prompt: &Prompt,
+    args: &str,
+) -> Result<serde_json::Map<String, JsonValue>, String> {
+    let mut values = serde_json::Map::new();
+    let mut rest = Vec::new();
+    for word in args.split_whitespace() {
+        match word.split_once('=') {
+            Some((name, value)) if prompt.arguments.iter().any(|a| a.name == name) => {
+                values.insert(name.to_string(), JsonValue::from(value));
+            }
+            _ => rest.push(word),
+        }
+    }
+    if !rest.is_empty() {
+        let Some(free) = prompt
+            .arguments
+            .iter()
+            .find(|a| !values.contains_key(&a.name))
+        else {
+            return Err(format!("{} takes no more arguments", prompt.name));
+        };
+        values.insert(free.name.clone(), JsonValue::from(rest.join(" ")));
+    }
+    if let Some(missing) = prompt
+        .arguments
+        .iter()
+        .find(|a| a.required && !values.contains_key(&a.name))
+    {
+        return Err(format!("{} needs <{}>", prompt.name, missing.name));
+    }
+    Ok(values)
+}
+
 /// Registers all tools from a running MCP server with the ToolBus.
 ///
 /// # Arguments
@@ -133,14 +263,14 @@ impl crate::Tool for McpToolWrapper {
 ///     
 ///     manager.start_server("filesystem").await?;
 ///     
-///     let mut toolbus = ToolBus::new(std::env::current_dir()?);
-///     register_mcp_tools(&mut toolbus, manager.clone(), "filesystem").await?;
+///     let toolbus = ToolBus::new(std::env::current_dir()?);
+///     register_mcp_tools(&toolbus, manager.clone(), "filesystem").await?;
 ///     
 ///     Ok(())
 /// }
 /// ```
 pub async fn register_mcp_tools(
-    toolbus: &mut crate::ToolBus,
+    toolbus: &crate::ToolBus,
     manager: Arc<McpManager>,
     server_id: &str,
 ) -> Result<(), anyhow::Error> {
@@ -156,12 +286,65 @@ pub async fn register_mcp_tools(
         let namespaced_name = wrapper.namespaced_name();
         tracing::info!("Registering MCP tool: {}", namespaced_name);
 
-        toolbus.register(wrapper);
+        toolbus.add_tool(Arc::new(wrapper));
     }
 
     Ok(())
 }
 
+/// Removes a server's `mcp.{server_id}.*` tools from the ToolBus.
+///
+/// Returns the number of tools removed.
+pub fn unregister_mcp_tools(toolbus: &crate::ToolBus, server_id: &str) -> usize {
+    let prefix = format!("mcp.{}.", server_id);
+    toolbus
+        .list_tools()
+        .iter()
+        .filter(|t| t.name.starts_with(&prefix) && toolbus.remove_tool(&t.name))
+        .count()
+}
+
+/// Registers the prompts of a running MCP server with the ToolBus. A server
+/// without prompts support registers none.
+pub async fn register_mcp_prompts(
+    toolbus: &crate::ToolBus,
+    manager: Arc<McpManager>,
+    server_id: &str,
+) {
+    let prompts = match manager.list_prompts(server_id).await {
+        Ok(prompts) => prompts,
+        Err(e) => {
+            tracing::debug!("No MCP prompts from {}: {}", server_id, e);
+            return;
+        }
+    };
+    for prompt in prompts {
+        let wrapper = McpPromptWrapper::new(server_id.to_string(), prompt, Arc::clone(&manager));
+        tracing::info!("Registering MCP prompt: {}", wrapper.namespaced_name());
+        toolbus.add_prompt(Arc::new(wrapper));
+    }
+}
+
+/// Brings the ToolBus in line with a [McpManager::reload]: drops the tools and
+/// prompts of stopped servers, then registers those of started ones.
+pub async fn sync_mcp_tools(
+    toolbus: &crate::ToolBus,
+    manager: Arc<McpManager>,
+    reload: &McpReload,
+) {
+    for server_id in &reload.stopped {
+        let removed = unregister_mcp_tools(toolbus, server_id);
+        tracing::info!("Unregistered {} MCP tools of {}", removed, server_id);
+        toolbus.remove_prompts(&format!("mcp.{}.", server_id));
+    }
+    for server_id in &reload.started {
+        if let Err(e) = register_mcp_tools(toolbus, Arc::clone(&manager), server_id).await {
+            tracing::warn!("Failed to register MCP tools of {}: {}", server_id, e);
+        }
+        register_mcp_prompts(toolbus, Arc::clone(&manager), server_id).await;
+    }
+}
+
 /// Tool information for display purposes.
 #[derive(Debug, Clone)]
 pub struct McpToolInfo {
@@ -193,6 +376,74 @@ impl McpToolInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp::config::{McpServerConfig, McpServersConfig};
+    use crate::mcp::protocol::PromptArgument;
+    use crate::mcp::test_server::{mcp_server, serve};
+
+    fn argument(name: &str, required: bool) -> PromptArgument {
+        PromptArgument {
+            name: name.to_string(),
+            description: None,
+            required,
+        }
+    }
+
+    #[test]
+    fn test_prompt_arguments() {
+        let prompt = Prompt {
+            name: "review".to_string(),
+            description: String::new(),
+            arguments: vec![argument("path", true), argument("focus", false)],
+        };
+        let args = prompt_arguments(&prompt, "focus=tests src/lib.rs").unwrap();
+        assert_eq!(args["path"], "src/lib.rs");
+        assert_eq!(args["focus"], "tests");
+        let args = prompt_arguments(&prompt, "src/a b.rs").unwrap();
+        assert_eq!(args["path"], "src/a b.rs");
+        assert!(!args.contains_key("focus"));
+        assert_eq!(
+            prompt_arguments(&prompt, "focus=tests").unwrap_err(),
+            "review needs <path>"
+        );
+
+        let bare = Prompt {
+            arguments: Vec::new(),
+            ..prompt
+        };
+        assert!(prompt_arguments(&bare, "").unwrap().is_empty());
+        assert!(prompt_arguments(&bare, "extra").is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sync_registers_and_renders_prompts() {
+        let url = serve(mcp_server);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mcp_servers.toml");
+        let mut config = McpServersConfig::new();
+        config.add_server(McpServerConfig::remote("docs", &url));
+        config.save(&path).unwrap();
+        let manager = Arc::new(McpManager::new(path.clone()));
+        let toolbus = crate::ToolBus::new(dir.path().to_path_buf());
+
+        let reload = manager.reload().await.unwrap();
+        sync_mcp_tools(&toolbus, Arc::clone(&manager), &reload).await;
+        let listed = toolbus.list_prompts();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "mcp.docs.review");
+        assert_eq!(listed[0].usage, "<path> [focus]");
+        assert_eq!(listed[0].description, "Review a file");
+
+        let prompt = toolbus.prompt("mcp.docs.review").unwrap();
+        assert_eq!(
+            prompt.render("src/lib.rs").await.unwrap(),
+            "Review src/lib.rs for bugs."
+        );
+
+        McpServersConfig::new().save(&path).unwrap();
+        let reload = manager.reload().await.unwrap();
+        sync_mcp_tools(&toolbus, Arc::clone(&manager), &reload).await;
+        assert!(toolbus.list_prompts().is_empty());
+    }
 
     #[test]
     fn test_namespaced_name() {
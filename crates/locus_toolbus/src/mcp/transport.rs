@@ -19,6 +19,7 @@ use serde_json::Value as JsonValue;
 use tracing::{debug, info};
 
 use crate::mcp::error::McpError;
+use crate::mcp::oauth::OAuthSession;
 use crate::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
 
 /// Transport trait for MCP communication (internal).
@@ -280,6 +281,8 @@ pub struct SseTransport {
     request_id: Arc<AtomicU64>,
     server_id: String,
     message_endpoint: Option<String>,
+    /// Bearer tokens for OAuth servers, refreshed as needed.
+    oauth: Option<Arc<OAuthSession>>,
 }
 
 impl SseTransport {
@@ -292,38 +295,64 @@ impl SseTransport {
             request_id: Arc::new(AtomicU64::new(1)),
             server_id: server_id.to_string(),
             message_endpoint: None,
+            oauth: None,
         }
     }
 
+    /// Authenticates every request with an access token from `session`.
+    pub fn with_oauth(mut self, session: Arc<OAuthSession>) -> Self {
+        self.oauth = Some(session);
+        self
+    }
+
     /// Sets the message endpoint (discovered during initialization).
     pub fn set_message_endpoint(&mut self, endpoint: String) {
         self.message_endpoint = Some(endpoint);
     }
 
     /// Builds a request with the configured headers.
-    fn build_request(&self, url: &str) -> reqwest::RequestBuilder {
+    fn build_request(&self, url: &str, access_token: Option<&str>) -> reqwest::RequestBuilder {
         let mut builder = self.http_client.post(url);
         for (key, value) in &self.headers {
             builder = builder.header(key, value);
         }
+        if let Some(token) = access_token {
+            builder = builder.bearer_auth(token);
+        }
         builder.header("Content-Type", "application/json")
     }
 
-    /// Sends an HTTP request and parses the response.
-    async fn http_send<T: DeserializeOwned + Send + 'static>(
+    async fn post(
         &self,
         url: &str,
-        body: JsonValue,
-    ) -> Result<T, McpError> {
+        body: &JsonValue,
+        access_token: Option<&str>,
+    ) -> Result<reqwest::Response, McpError> {
+        self.build_request(url, access_token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| McpError::Protocol(format!("HTTP request failed: {}", e)))
+    }
+
+    /// Sends an HTTP request and returns the response body.
+    async fn http_send(&self, url: &str, body: JsonValue) -> Result<String, McpError> {
         debug!("[MCP:{}] HTTP POST to: {}", self.server_id, url);
         debug!("[MCP:{}] Body: {}", self.server_id, body);
 
-        let response = self
-            .build_request(url)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| McpError::Protocol(format!("HTTP request failed: {}", e)))?;
+        let access_token = match &self.oauth {
+            Some(session) => Some(session.access_token().await?),
+            None => None,
+        };
+        let mut response = self.post(url, &body, access_token.as_deref()).await?;
+        // A token can be revoked or expire early: refresh once and retry.
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && let (Some(session), Some(rejected)) = (&self.oauth, &access_token)
+        {
+            debug!("[MCP:{}] Access token rejected; refreshing", self.server_id);
+            let token = session.refresh_after_rejection(rejected).await?;
+            response = self.post(url, &body, Some(&token)).await?;
+        }
 
         let status = response.status();
         if !status.is_success() {
@@ -341,11 +370,7 @@ impl SseTransport {
 
         debug!("[MCP:{}] Response: {}", self.server_id, response_text);
 
-        let response: JsonRpcResponse<T> = serde_json::from_str(&response_text)?;
-
-        response
-            .into_result()
-            .map_err(|e| McpError::JsonRpc(e.message))
+        Ok(response_text)
     }
 }
 
@@ -370,7 +395,10 @@ impl TransportInner for SseTransport {
             "params": params
         });
 
-        self.http_send(&url, body).await
+        // The caller unwraps the JSON-RPC envelope, as for stdio.
+        let response = self.http_send(&url, body).await?;
+        serde_json::from_str(&response)
+            .map_err(|e| McpError::Protocol(format!("Invalid JSON response: {}", e)))
     }
 
     async fn send_notification(
@@ -390,7 +418,8 @@ impl TransportInner for SseTransport {
             "params": params
         });
 
-        let _: JsonValue = self.http_send(&url, body).await?;
+        // Servers answer notifications with an empty 202.
+        self.http_send(&url, body).await?;
         Ok(())
     }
 
@@ -0,0 +1,190 @@
+//! Hot reload of the MCP servers configuration.
+//!
+//! [watch_mcp_config] applies the configuration file to a [McpManager] and then
+//! watches it: each save is diffed with [McpManager::reload] (new servers start,
+//! removed ones stop, changed ones restart) and the `mcp.*` tools on the shared
+//! [ToolBus] follow. A long session picks up a server added with `locus mcp add`
+//! or a hand edit without restarting.
+//!
+//! The watch ends when the ToolBus is dropped.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::ToolBus;
+use crate::mcp::error::McpError;
+use crate::mcp::manager::{McpManager, McpReload};
+use crate::mcp::toolbus_integration::sync_mcp_tools;
+
+/// Editors save in several steps (truncate, write, rename); wait for the last.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+/// How often an idle watch checks whether the ToolBus is gone.
+const LIVENESS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Reload `manager` from its configuration file and sync the ToolBus tools.
+pub async fn apply_mcp_config(
+    manager: &Arc<McpManager>,
+    toolbus: &ToolBus,
+) -> Result<McpReload, McpError> {
+    let reload = manager.reload().await?;
+    sync_mcp_tools(toolbus, Arc::clone(manager), &reload).await;
+    for (id, error) in &reload.failed {
+        warn!("MCP server '{}' failed to start: {}", id, error);
+    }
+    Ok(reload)
+}
+
+/// Apply `manager`'s configuration file to `toolbus` now and after every change.
+///
+/// Watches the file's directory, so the file may be created later or replaced by
+/// an atomic save. A file that does not parse is logged and skipped until the
+/// next save.
+pub fn watch_mcp_config(
+    manager: Arc<McpManager>,
+    toolbus: &Arc<ToolBus>,
+) -> Result<JoinHandle<()>, McpError> {
+    let path = manager.config_path().to_path_buf();
+    let dir = watched_dir(&path);
+    std::fs::create_dir_all(&dir)?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let file_name: Option<OsString> = path.file_name().map(OsString::from);
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && !event.kind.is_access()
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == file_name.as_deref())
+        {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(watch_error)?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+
+    let toolbus: Weak<ToolBus> = Arc::downgrade(toolbus);
+    Ok(tokio::spawn(async move {
+        // Dropping the watcher ends the watch, so the task owns it.
+        let _watcher = watcher;
+        let mut changed = true;
+        loop {
+            let Some(bus) = toolbus.upgrade() else {
+                break;
+            };
+            if changed {
+                match apply_mcp_config(&manager, &bus).await {
+                    Ok(reload) if !reload.is_empty() => info!(
+                        "MCP servers reloaded from {}: started {:?}, stopped {:?}",
+                        path.display(),
+                        reload.started,
+                        reload.stopped
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!("Ignoring {}: {}", path.display(), e),
+                }
+            }
+            drop(bus);
+
+            changed = match tokio::time::timeout(LIVENESS_INTERVAL, rx.recv()).await {
+                Ok(Some(())) => {
+                    tokio::time::sleep(DEBOUNCE).await;
+                    while rx.try_recv().is_ok() {}
+                    true
+                }
+                Ok(None) => break,
+                Err(_) => false,
+            };
+        }
+        if let Err(e) = manager.stop_all().await {
+            warn!("Failed to stop MCP servers: {}", e);
+        }
+    }))
+}
+
+fn watched_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn watch_error(e: notify::Error) -> McpError {
+    McpError::Config(format!("Cannot watch MCP configuration: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::config::{McpServerConfig, McpServersConfig};
+    use crate::mcp::test_server::{mcp_server, serve};
+
+    fn write_config(path: &Path, servers: &[McpServerConfig]) {
+        let mut config = McpServersConfig::new();
+        for server in servers {
+            config.add_server(server.clone());
+        }
+        config.save(path).unwrap();
+    }
+
+    async fn wait_for(toolbus: &ToolBus, present: bool) {
+        for _ in 0..100 {
+            if toolbus.permission_of("mcp.docs.echo").is_some() == present {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("mcp.docs.echo present should be {}", present);
+    }
+
+    #[tokio::test]
+    async fn test_watch_registers_and_removes_tools() {
+        let url = serve(mcp_server);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("locus").join("mcp_servers.toml");
+        let manager = Arc::new(McpManager::new(path.clone()));
+        let toolbus = Arc::new(ToolBus::new(dir.path().to_path_buf()));
+
+        let handle = watch_mcp_config(Arc::clone(&manager), &toolbus).unwrap();
+        write_config(&path, &[McpServerConfig::remote("docs", &url)]);
+        wait_for(&toolbus, true).await;
+        assert_eq!(manager.list_running().await, vec!["docs"]);
+
+        write_config(&path, &[]);
+        wait_for(&toolbus, false).await;
+        assert!(manager.list_running().await.is_empty());
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_apply_mcp_config_syncs_tools() {
+        let url = serve(mcp_server);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mcp_servers.toml");
+        write_config(
+            &path,
+            &[
+                McpServerConfig::remote("docs", &url),
+                McpServerConfig::remote("broken", "http://127.0.0.1:1"),
+            ],
+        );
+        let manager = Arc::new(McpManager::new(path));
+        let toolbus = ToolBus::new(dir.path().to_path_buf());
+
+        let reload = apply_mcp_config(&manager, &toolbus).await.unwrap();
+        assert_eq!(reload.started, vec!["docs"]);
+        assert_eq!(reload.failed.len(), 1);
+        assert_eq!(reload.failed[0].0, "broken");
+        assert!(toolbus.permission_of("mcp.docs.echo").is_some());
+    }
+}
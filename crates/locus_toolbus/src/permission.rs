@@ -0,0 +1,125 @@
+//! Permission gate for [crate::ToolBus::call].
+//!
+//! - Every tool declares the [Permission] it needs ([crate::Tool::permission])
+//! - Each permission maps to a [PermissionRule]: `allow`, `ask` or `deny`
+//! - Rules come from `[permissions]` in `.locus/tools.toml`, overridden per tool
+//!   with `permission = "..."` under `[tools.<name>]`
+//! - Built-in rules: reads are allowed; writes, commands, network and git
+//!   changes ask
+//! - `ask` goes to the [Approver] set on the ToolBus (the TUI confirmation dialog);
+//!   without one, `ask` is treated as `allow` so headless runs keep working.
+//!   `edit_file` and `create_file` requests carry an [ApprovalPreview] of the change
+//!
+//! ```toml
+//! [permissions]
+//! write = "allow"
+//! network = "deny"
+//!
+//! [tools.bash]
+//! permission = "allow"
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub use locus_core::{
+    ApprovalDecision, ApprovalPreview, ApprovalRequest, ApprovalResponse, Permission,
+};
+
+/// What to do when a tool needing a given permission is called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionRule {
+    Allow,
+    Ask,
+    Deny,
+}
+
+/// Rule per permission level. Unset levels use the built-ins: read is
+/// allowed; write, execute, network and git_write ask.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PermissionDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read: Option<PermissionRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write: Option<PermissionRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execute: Option<PermissionRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<PermissionRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_write: Option<PermissionRule>,
+}
+
+impl PermissionDefaults {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, permission: Permission, rule: PermissionRule) -> Self {
+        *self.slot(permission) = Some(rule);
+        self
+    }
+
+    fn slot(&mut self, permission: Permission) -> &mut Option<PermissionRule> {
+        match permission {
+            Permission::Read => &mut self.read,
+            Permission::Write => &mut self.write,
+            Permission::Execute => &mut self.execute,
+            Permission::Network => &mut self.network,
+            Permission::GitWrite => &mut self.git_write,
+        }
+    }
+
+    /// Configured rule for `permission`, or the built-in default.
+    pub fn rule(&self, permission: Permission) -> PermissionRule {
+        let configured = match permission {
+            Permission::Read => self.read,
+            Permission::Write => self.write,
+            Permission::Execute => self.execute,
+            Permission::Network => self.network,
+            Permission::GitWrite => self.git_write,
+        };
+        configured.unwrap_or(match permission {
+            Permission::Read => PermissionRule::Allow,
+            Permission::Write
+            | Permission::Execute
+            | Permission::Network
+            | Permission::GitWrite => PermissionRule::Ask,
+        })
+    }
+}
+
+/// Answers approval requests for tool calls whose rule is `ask`. A denial's
+/// message is passed to the model with the error.
+#[async_trait]
+pub trait Approver: Send + Sync {
+    async fn approve(&self, request: ApprovalRequest) -> ApprovalResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_rules() {
+        let defaults = PermissionDefaults::default();
+        assert_eq!(defaults.rule(Permission::Read), PermissionRule::Allow);
+        assert_eq!(defaults.rule(Permission::Write), PermissionRule::Ask);
+        assert_eq!(defaults.rule(Permission::Execute), PermissionRule::Ask);
+        assert_eq!(defaults.rule(Permission::Network), PermissionRule::Ask);
+        assert_eq!(defaults.rule(Permission::GitWrite), PermissionRule::Ask);
+    }
+
+    #[test]
+    fn test_configured_rules_override_builtins() {
+        let defaults: PermissionDefaults =
+            toml::from_str("write = \"allow\"\nexecute = \"allow\"\ngit_write = \"deny\"").unwrap();
+        assert_eq!(defaults.rule(Permission::GitWrite), PermissionRule::Deny);
+        assert_eq!(defaults.rule(Permission::Write), PermissionRule::Allow);
+        assert_eq!(defaults.rule(Permission::Execute), PermissionRule::Allow);
+
+        let defaults = defaults.with_rule(Permission::Network, PermissionRule::Deny);
+        assert_eq!(defaults.rule(Permission::Network), PermissionRule::Deny);
+    }
+}
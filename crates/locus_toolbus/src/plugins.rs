@@ -0,0 +1,626 @@
+//! Project tools compiled to WebAssembly, from `.locus/plugins/*.wasm`.
+//!
+//! Each plugin is a WASI command module run by a WASI runtime on PATH
+//! (`wasmtime`, or the program in `LOCUS_WASM_RUNTIME`). The contract:
+//!
+//! - `plugin.wasm --describe` prints `{"description": ..., "parameters": {...}}`.
+//!   [ToolBus::load_extensions](crate::ToolBus::load_extensions) runs this once,
+//!   with nothing mounted, and registers the plugin as `plugin.<file stem>`
+//! - A call gets its arguments as JSON on stdin and prints its result as JSON on
+//!   stdout. A non-zero exit is an error, with the end of stderr as the message
+//!
+//! What a plugin may do is set by the project, not by the plugin: its level
+//! comes from `[plugins]` in `.locus/tools.toml` (default `execute`), and its
+//! calls ask for approval unless `[tools."plugin.<name>"]` sets a `permission`.
+//!
+//! ```toml
+//! [plugins]
+//! count_lines = "read"
+//! codegen = "write"
+//! ```
+//!
+//! The sandbox: one directory, mounted at `/repo`, no environment variables
+//! and no sockets. A `write` plugin gets the repo itself; any other gets a
+//! copy of it (gitignored and protected files left out), so nothing it writes
+//! reaches the repo. The copy is made on the first such call and brought back
+//! in line with the repo before each later one, copying only the files that
+//! changed. Cancelling the call or its policy timeout kills the runtime.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::permission::Permission;
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::lint::stderr_tail;
+use crate::tools::{Tool, ToolContext, ToolResult};
+
+/// Plugin directory, relative to the repo root.
+pub const PLUGIN_DIR: &str = ".locus/plugins";
+/// Overrides the WASI runtime used to run plugins.
+pub const WASM_RUNTIME_ENV: &str = "LOCUS_WASM_RUNTIME";
+pub const DEFAULT_WASM_RUNTIME: &str = "wasmtime";
+/// Where the repo is mounted inside the sandbox.
+pub const GUEST_REPO_DIR: &str = "/repo";
+/// Prefix of plugin tool names.
+pub const PLUGIN_PREFIX: &str = "plugin.";
+
+/// How long `--describe` may take before the plugin is skipped.
+const DESCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error(
+        "Failed to start {runtime} for {plugin}: {message} (install wasmtime or set LOCUS_WASM_RUNTIME)"
+    )]
+    SpawnFailed {
+        runtime: String,
+        plugin: String,
+        message: String,
+    },
+
+    #[error("{plugin} --describe took longer than {}s", DESCRIBE_TIMEOUT.as_secs())]
+    DescribeTimeout { plugin: String },
+
+    #[error("{plugin} exited with code {exit_code}: {stderr}")]
+    Failed {
+        plugin: String,
+        exit_code: i32,
+        stderr: String,
+    },
+
+    #[error("{plugin} printed invalid JSON: {message}")]
+    InvalidOutput { plugin: String, message: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// What `--describe` prints. Anything else in it (such as a `permission`
+/// from older plugins) is ignored.
+#[derive(Debug, Clone, Deserialize)]
+struct PluginDescription {
+    description: String,
+    #[serde(default = "empty_schema")]
+    parameters: JsonValue,
+}
+
+fn empty_schema() -> JsonValue {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+/// Finds and describes the plugins of one repo.
+#[derive(Debug, Clone)]
+pub struct PluginHost {
+    repo_root: PathBuf,
+    runtime: String,
+    /// Level of each plugin by file stem, from `[plugins]`.
+    permissions: HashMap<String, Permission>,
+    /// Files left out of the copy a plugin that may not write gets.
+    protected: ProtectedPathMatcher,
+    /// That copy, shared by the host's plugins; one call holds it at a time.
+    copy: Arc<Mutex<RepoCopy>>,
+}
+
+impl PluginHost {
+    /// Host using `LOCUS_WASM_RUNTIME`, or `wasmtime`.
+    pub fn new(repo_root: PathBuf) -> Self {
+        let runtime = std::env::var(WASM_RUNTIME_ENV)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_WASM_RUNTIME.to_string());
+        Self {
+            repo_root,
+            runtime,
+            permissions: HashMap::new(),
+            protected: ProtectedPathMatcher::default(),
+            copy: Arc::new(Mutex::new(RepoCopy::new())),
+        }
+    }
+
+    /// Run plugins with this program instead (takes wasmtime's `run` arguments).
+    pub fn with_runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.runtime = runtime.into();
+        self
+    }
+
+    /// Each plugin's level, by file stem (`[plugins]` in `.locus/tools.toml`).
+    /// Plugins not listed get `execute`.
+    pub fn with_permissions(mut self, permissions: HashMap<String, Permission>) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Leave the files `protected` matches out of plugins' copies of the repo.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// `.wasm` files in the plugin directory, sorted; empty when there is none.
+    pub fn plugin_files(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(self.repo_root.join(PLUGIN_DIR)) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Describe every plugin. Plugins that fail to describe themselves are
+    /// logged and left out, so one broken plugin does not take the bus down.
+    pub async fn load(&self) -> Vec<PluginTool> {
+        let mut tools = Vec::new();
+        for wasm in self.plugin_files() {
+            match self.describe(&wasm).await {
+                Ok(tool) => tools.push(tool),
+                Err(e) => tracing::warn!("Skipping plugin {}: {}", wasm.display(), e),
+            }
+        }
+        tools
+    }
+
+    /// Run `wasm --describe` and build its tool.
+    pub async fn describe(&self, wasm: &Path) -> Result<PluginTool, PluginError> {
+        let plugin = plugin_label(wasm);
+        let child = tokio::process::Command::new(&self.runtime)
+            .args(self.run_args(wasm, None))
+            .arg("--describe")
+            .current_dir(&self.repo_root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| self.spawn_failed(&plugin, e))?;
+        // Timing out drops the child, which kills it (kill_on_drop).
+        let output = tokio::time::timeout(DESCRIBE_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| PluginError::DescribeTimeout {
+                plugin: plugin.clone(),
+            })??;
+        if !output.status.success() {
+            return Err(PluginError::Failed {
+                plugin,
+                exit_code: output.status.code().unwrap_or(-1),
+                stderr: stderr_tail(&String::from_utf8_lossy(&output.stderr)),
+            });
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let description: PluginDescription =
+            serde_json::from_str(stdout.trim()).map_err(|e| PluginError::InvalidOutput {
+                plugin: plugin.clone(),
+                message: e.to_string(),
+            })?;
+        Ok(PluginTool::new(
+            self.clone(),
+            wasm.to_path_buf(),
+            description,
+        ))
+    }
+
+    /// `run [--dir <mount>::/repo] <wasm>`: `mount` is the only preopened directory.
+    fn run_args(&self, wasm: &Path, mount: Option<&Path>) -> Vec<String> {
+        let mut args = vec!["run".to_string()];
+        if let Some(mount) = mount {
+            args.push("--dir".to_string());
+            args.push(format!("{}::{}", mount.display(), GUEST_REPO_DIR));
+        }
+        args.push(wasm.to_string_lossy().to_string());
+        args
+    }
+
+    /// The plugins' copy of the repo, up to date and held until the guard drops.
+    async fn synced_copy(&self) -> Result<OwnedMutexGuard<RepoCopy>, PluginError> {
+        let mut copy = self.copy.clone().lock_owned().await;
+        let (root, protected) = (self.repo_root.clone(), self.protected.clone());
+        let copy = tokio::task::spawn_blocking(move || {
+            copy.sync(&root, &protected)?;
+            Ok::<_, std::io::Error>(copy)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        Ok(copy)
+    }
+
+    fn spawn_failed(&self, plugin: &str, e: std::io::Error) -> PluginError {
+        PluginError::SpawnFailed {
+            runtime: self.runtime.clone(),
+            plugin: plugin.to_string(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// `count_lines.wasm` for messages.
+fn plugin_label(wasm: &Path) -> String {
+    wasm.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| wasm.display().to_string())
+}
+
+/// Length and modification time, to tell whether a file changed.
+type FileStamp = (u64, Option<SystemTime>);
+
+fn file_stamp(path: &Path) -> std::io::Result<FileStamp> {
+    let meta = std::fs::metadata(path)?;
+    Ok((meta.len(), meta.modified().ok()))
+}
+
+/// The copy of the repo that plugins which may not write run in, deleted
+/// when dropped.
+#[derive(Debug)]
+struct RepoCopy {
+    dir: PathBuf,
+    /// Each copied file's stamp in the repo and in the copy, by relative path.
+    files: HashMap<PathBuf, (FileStamp, FileStamp)>,
+}
+
+impl RepoCopy {
+    /// An empty copy; the directory is made by the first [RepoCopy::sync].
+    fn new() -> Self {
+        Self {
+            dir: std::env::temp_dir().join(format!("locus-plugin-{}", uuid::Uuid::new_v4())),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Make the copy hold the files of `repo_root` that are neither gitignored
+    /// nor protected, and nothing else. Files whose stamp matches on both sides
+    /// are left alone; what a plugin added is removed. Symlinks are left out.
+    fn sync(&mut self, repo_root: &Path, protected: &ProtectedPathMatcher) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let (walk_root, protected) = (repo_root.to_path_buf(), protected.clone());
+        let walker = ignore::WalkBuilder::new(repo_root)
+            .hidden(false)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !protected.is_protected(&walk_root, e.path())
+            })
+            .build();
+        let (mut dirs, mut sources) = (HashSet::new(), Vec::new());
+        for entry in walker.flatten() {
+            let Ok(relative) = entry.path().strip_prefix(repo_root) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            match entry.file_type() {
+                Some(kind) if kind.is_dir() => {
+                    dirs.insert(relative.to_path_buf());
+                }
+                Some(kind) if kind.is_file() => {
+                    sources.push((relative.to_path_buf(), file_stamp(entry.path())?));
+                }
+                _ => {}
+            }
+        }
+        let keep: HashSet<&Path> = sources.iter().map(|(path, _)| path.as_path()).collect();
+        self.prune(&self.dir, &dirs, &keep)?;
+        for dir in &dirs {
+            std::fs::create_dir_all(self.dir.join(dir))?;
+        }
+        let mut files = HashMap::with_capacity(sources.len());
+        for (relative, source) in sources {
+            let target = self.dir.join(&relative);
+            let copied = match self.files.get(&relative) {
+                Some(&(was, copied))
+                    if was == source && file_stamp(&target).ok() == Some(copied) =>
+                {
+                    copied
+                }
+                _ => {
+                    // A plugin may have left it read-only.
+                    let _ = std::fs::remove_file(&target);
+                    std::fs::copy(repo_root.join(&relative), &target)?;
+                    file_stamp(&target)?
+                }
+            };
+            files.insert(relative, (source, copied));
+        }
+        self.files = files;
+        Ok(())
+    }
+
+    /// Remove what is under `dir` in the copy but not in `dirs` or `files`.
+    fn prune(
+        &self,
+        dir: &Path,
+        dirs: &HashSet<PathBuf>,
+        files: &HashSet<&Path>,
+    ) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let relative = path.strip_prefix(&self.dir).unwrap_or(&path);
+            let kind = entry.file_type()?;
+            if kind.is_dir() && dirs.contains(relative) {
+                self.prune(&path, dirs, files)?;
+            } else if kind.is_dir() {
+                std::fs::remove_dir_all(&path)?;
+            } else if !(kind.is_file() && files.contains(relative)) {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for RepoCopy {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A plugin registered on the bus as `plugin.<file stem>`.
+pub struct PluginTool {
+    host: PluginHost,
+    wasm: PathBuf,
+    parameters: JsonValue,
+    permission: Permission,
+    /// Leaked once for [Tool::name] and [Tool::description], as for MCP tools.
+    name: &'static str,
+    description: &'static str,
+}
+
+impl PluginTool {
+    fn new(host: PluginHost, wasm: PathBuf, description: PluginDescription) -> Self {
+        let stem = wasm
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let permission = host
+            .permissions
+            .get(&stem)
+            .copied()
+            .unwrap_or(Permission::Execute);
+        Self {
+            host,
+            wasm,
+            parameters: description.parameters,
+            permission,
+            name: Box::leak(format!("{}{}", PLUGIN_PREFIX, stem).into_boxed_str()),
+            description: Box::leak(description.description.into_boxed_str()),
+        }
+    }
+
+    pub fn wasm_path(&self) -> &Path {
+        &self.wasm
+    }
+
+    async fn run(&self, args: &JsonValue) -> Result<JsonValue, PluginError> {
+        let plugin = plugin_label(&self.wasm);
+        // Held until the plugin exits, so no other call sees what it writes.
+        let copy = if self.permission == Permission::Write {
+            None
+        } else {
+            Some(self.host.synced_copy().await?)
+        };
+        let mount = copy
+            .as_ref()
+            .map_or(self.host.repo_root.as_path(), |copy| copy.dir.as_path());
+        let mut child = tokio::process::Command::new(&self.host.runtime)
+            .args(self.host.run_args(&self.wasm, Some(mount)))
+            .current_dir(&self.host.repo_root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| self.host.spawn_failed(&plugin, e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(args.to_string().as_bytes()).await?;
+            // Closing stdin ends the input for plugins that read to EOF.
+            drop(stdin);
+        }
+        let output = child.wait_with_output().await?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            return Err(PluginError::Failed {
+                plugin,
+                exit_code: output.status.code().unwrap_or(-1),
+                stderr: stderr_tail(&String::from_utf8_lossy(&output.stderr)),
+            });
+        }
+        serde_json::from_str(stdout.trim()).map_err(|e| PluginError::InvalidOutput {
+            plugin,
+            message: e.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for PluginTool {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        self.parameters.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        self.permission
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        // Dropping the run kills the runtime (kill_on_drop).
+        tokio::select! {
+            result = self.run(&args) => Ok(result?),
+            _ = ctx.cancel.cancelled() => Err(anyhow::anyhow!("{} cancelled", self.name)),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// A stand-in runtime: `--describe` prints `<plugin>.describe.json` next to the
+    /// module, and fails when anything is mounted; a call echoes its stdin and the
+    /// mount, says whether `.env` and `written.txt` are in it and then writes
+    /// `written.txt` to it, or fails when the arguments ask it to.
+    fn fake_runtime(dir: &Path) -> String {
+        let path = dir.join("fake-wasmtime");
+        std::fs::write(
+            &path,
+            concat!(
+                "#!/bin/sh\n",
+                "[ \"$1\" = run ] || exit 64\n",
+                "shift; mount=\"\"\n",
+                "if [ \"$1\" = --dir ]; then mount=\"$2\"; shift 2; fi\n",
+                "wasm=\"$1\"\n",
+                "if [ \"$2\" = --describe ]; then [ -z \"$mount\" ] || exit 65; cat \"${wasm%.wasm}.describe.json\"; exit $?; fi\n",
+                "input=$(cat)\n",
+                "case \"$input\" in *fail*) echo 'plugin panicked' >&2; exit 3;; esac\n",
+                "dir=\"${mount%::/repo}\"\n",
+                "env=false; [ -e \"$dir/.env\" ] && env=true\n",
+                "written=false; [ -e \"$dir/written.txt\" ] && written=true\n",
+                "echo changed > \"$dir/written.txt\"\n",
+                "printf '{\"input\": %s, \"mount\": \"%s\", \"env\": %s, \"written\": %s}' \"$input\" \"$mount\" \"$env\" \"$written\"\n",
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    fn add_plugin(repo: &Path, name: &str, describe: &str) {
+        let dir = repo.join(PLUGIN_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{}.wasm", name)), b"\0asm").unwrap();
+        std::fs::write(dir.join(format!("{}.describe.json", name)), describe).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_plugins_register_as_described() {
+        let repo = TempDir::new().unwrap();
+        let bin = TempDir::new().unwrap();
+        add_plugin(
+            repo.path(),
+            "count_lines",
+            r#"{"description": "Count lines", "parameters": {"type": "object", "properties": {"path": {"type": "string"}}}}"#,
+        );
+        add_plugin(repo.path(), "broken", "not json");
+        add_plugin(
+            repo.path(),
+            "deploy",
+            r#"{"description": "Deploy", "permission": "read"}"#,
+        );
+
+        let host = PluginHost::new(repo.path().to_path_buf())
+            .with_runtime(fake_runtime(bin.path()))
+            .with_permissions(HashMap::from([(
+                "count_lines".to_string(),
+                Permission::Read,
+            )]));
+        let tools = host.load().await;
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, ["plugin.count_lines", "plugin.deploy"]);
+        assert_eq!(tools[0].description(), "Count lines");
+        assert_eq!(tools[0].permission(), Permission::Read);
+        assert_eq!(
+            tools[0].parameters_schema()["properties"]["path"]["type"],
+            "string"
+        );
+        // A plugin does not choose its own level: unlisted ones run arbitrary code.
+        assert_eq!(tools[1].permission(), Permission::Execute);
+        assert_eq!(tools[1].parameters_schema(), empty_schema());
+
+        let err = host
+            .describe(&repo.path().join(PLUGIN_DIR).join("broken.wasm"))
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, PluginError::InvalidOutput { .. }),
+            "got: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_write_plugins_can_change_the_repo() {
+        let repo = TempDir::new().unwrap();
+        let bin = TempDir::new().unwrap();
+        std::fs::write(repo.path().join(".env"), "TOKEN=x\n").unwrap();
+        add_plugin(repo.path(), "echo", r#"{"description": "Echo"}"#);
+        add_plugin(repo.path(), "codegen", r#"{"description": "Codegen"}"#);
+        let host = PluginHost::new(repo.path().to_path_buf())
+            .with_runtime(fake_runtime(bin.path()))
+            .with_permissions(HashMap::from([("codegen".to_string(), Permission::Write)]));
+        let mut tools = host.load().await;
+        let (codegen, echo) = (tools.remove(0), tools.remove(0));
+
+        let result = echo.execute(json!({"path": "src/lib.rs"})).await.unwrap();
+        assert_eq!(result["input"], json!({"path": "src/lib.rs"}));
+        let mount = result["mount"].as_str().unwrap().to_string();
+        assert!(mount.ends_with("::/repo"), "{}", mount);
+        assert_ne!(mount, format!("{}::/repo", repo.path().display()));
+        assert_eq!(result["env"], false);
+        assert!(!repo.path().join("written.txt").exists());
+
+        // Later calls reuse the copy, without what earlier ones wrote and with
+        // what changed in the repo since.
+        std::fs::write(repo.path().join("new.rs"), "fn main() {}\n").unwrap();
+        let again = echo.execute(json!({})).await.unwrap();
+        assert_eq!(again["mount"], mount);
+        assert_eq!(again["written"], false);
+        let copy = PathBuf::from(mount.trim_end_matches("::/repo"));
+        assert!(copy.join("new.rs").exists());
+
+        let result = codegen.execute(json!({})).await.unwrap();
+        assert_eq!(result["mount"], format!("{}::/repo", repo.path().display()));
+        assert!(repo.path().join("written.txt").exists());
+
+        let err = echo.execute(json!({"mode": "fail"})).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "echo.wasm exited with code 3: plugin panicked"
+        );
+
+        drop((codegen, echo, host));
+        assert!(!copy.exists());
+    }
+
+    #[tokio::test]
+    async fn test_missing_runtime_is_reported() {
+        let repo = TempDir::new().unwrap();
+        add_plugin(repo.path(), "echo", r#"{"description": "Echo"}"#);
+        let host = PluginHost::new(repo.path().to_path_buf()).with_runtime("/nonexistent/wasmtime");
+        assert!(host.load().await.is_empty());
+        let err = host
+            .describe(&repo.path().join(PLUGIN_DIR).join("echo.wasm"))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("install wasmtime"), "got: {}", err);
+    }
+}
@@ -0,0 +1,639 @@
+//! Per-tool execution limits enforced by [crate::ToolBus::call].
+//!
+//! - Loaded from `<repo_root>/.locus/tools.toml` when present; built-in defaults otherwise
+//! - `timeout_secs` bounds wall-clock time for any tool, on top of a tool's own timeout;
+//!   a call that runs over is cancelled (see [crate::tools::ToolContext]) and fails
+//!   with [ToolInterrupted::TimedOut]
+//! - `max_result_bytes` bounds the serialized JSON result; long strings are cut first
+//!   (keeping head, tail or both), then long arrays, so the result keeps its shape
+//! - `permission` and `[permissions]` control the approval gate (see [crate::permission])
+//! - `[commands]` allows or denies shell commands by pattern (see [crate::command_rules])
+//! - `[plugins]` sets the level of each WASM plugin; their calls ask unless
+//!   their own `permission` says otherwise (see [crate::plugins])
+//! - `[protected_paths]` lists files the read and search tools refuse (see
+//!   [crate::protected_paths]); it is read when the bus registers its tools
+//! - `cache = false` stops the bus reusing results of cacheable tools (see
+//!   [crate::result_cache])
+//! - `retries` bounds how often a transient failure (network, request timeout)
+//!   of a read or network tool is retried, with backoff (see [crate::recovery])
+//!
+//! ```toml
+//! [defaults]
+//! timeout_secs = 600
+//! max_result_bytes = 102400
+//! truncation = "head_tail"
+//!
+//! [permissions]
+//! execute = "ask"
+//!
+//! [commands]
+//! allow = ["npm test"]
+//! deny = ["terraform apply*"]
+//!
+//! [protected_paths]
+//! deny = ["secrets/", "*.key"]
+//!
+//! [tools.bash]
+//! timeout_secs = 300
+//! truncation = "tail"
+//! permission = "allow"
+//!
+//! [tools.grep]
+//! cache = false
+//!
+//! [tools.web_fetch]
+//! retries = 4
+//! ```
+
+use crate::command_rules::CommandRules;
+use crate::permission::{Permission, PermissionDefaults, PermissionRule};
+use crate::plugins::PLUGIN_PREFIX;
+use crate::protected_paths::ProtectedPaths;
+use crate::tools::{Tool, ToolContext};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Policy file, relative to the repo root.
+pub const TOOL_POLICY_FILE: &str = ".locus/tools.toml";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+const DEFAULT_MAX_RESULT_BYTES: usize = 100 * 1024;
+const DEFAULT_RETRIES: u32 = 2;
+/// How long a cancelled tool gets to wind down before the bus stops waiting for it.
+pub const CANCEL_GRACE: Duration = Duration::from_secs(2);
+/// Strings at or below this size are never cut; they carry structure, not bulk output.
+const MIN_STRING_BYTES: usize = 256;
+
+/// Which part of an oversized string or array to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    Head,
+    Tail,
+    #[default]
+    HeadTail,
+}
+
+/// Limits for one tool. Unset fields fall back to `[defaults]`, then built-ins.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_result_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<TruncationStrategy>,
+    /// Overrides the `[permissions]` rule for this tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<PermissionRule>,
+    /// `false` turns off result caching; tools that aren't cacheable never cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<bool>,
+    /// Retries of a transient failure; `0` turns them off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+}
+
+impl ToolPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_timeout_secs(mut self, secs: u64) -> Self {
+        self.timeout_secs = Some(secs);
+        self
+    }
+
+    pub fn with_max_result_bytes(mut self, bytes: usize) -> Self {
+        self.max_result_bytes = Some(bytes);
+        self
+    }
+
+    pub fn with_truncation(mut self, strategy: TruncationStrategy) -> Self {
+        self.truncation = Some(strategy);
+        self
+    }
+
+    pub fn with_permission(mut self, rule: PermissionRule) -> Self {
+        self.permission = Some(rule);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: bool) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+}
+
+/// Fully resolved limits for a single call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveToolPolicy {
+    pub timeout: Duration,
+    pub max_result_bytes: usize,
+    pub truncation: TruncationStrategy,
+    /// Results of cacheable tools may be reused.
+    pub cache: bool,
+    /// Times a transient failure is retried.
+    pub retries: u32,
+}
+
+/// Default policy plus per-tool overrides, keyed by tool name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolPolicies {
+    #[serde(default)]
+    pub defaults: ToolPolicy,
+    #[serde(default)]
+    pub tools: HashMap<String, ToolPolicy>,
+    #[serde(default)]
+    pub permissions: PermissionDefaults,
+    #[serde(default)]
+    pub commands: CommandRules,
+    #[serde(default)]
+    pub protected_paths: ProtectedPaths,
+    /// Level of each WASM plugin, by file stem.
+    #[serde(default)]
+    pub plugins: HashMap<String, Permission>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ToolPolicyError {
+    #[error("Failed to read tool policy file: {0}")]
+    Read(#[from] std::io::Error),
+
+    #[error("Failed to parse tool policy file: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// A call that was stopped before it finished, either by its policy timeout
+/// or by the caller (the user pressed Esc).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ToolInterrupted {
+    #[error("Tool '{tool}' timed out after {timeout_secs}s (tool policy limit)")]
+    TimedOut { tool: String, timeout_secs: u64 },
+
+    #[error("Tool '{tool}' was cancelled")]
+    Cancelled { tool: String },
+}
+
+impl ToolInterrupted {
+    /// Tool result for the model, with what happened and how to go on.
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            Self::TimedOut { tool, timeout_secs } => serde_json::json!({
+                "error": self.to_string(),
+                "timed_out": true,
+                "timeout_secs": timeout_secs,
+                "hint": format!(
+                    "'{}' did not finish within {}s and was cancelled; it may be hung. \
+                     Retry with a smaller scope or a different approach, or ask the user \
+                     to raise [tools.{}] timeout_secs in .locus/tools.toml.",
+                    tool, timeout_secs, tool
+                ),
+            }),
+            Self::Cancelled { .. } => serde_json::json!({
+                "error": self.to_string(),
+                "cancelled": true,
+                "hint": "The user stopped this call. Do not retry it unless they ask.",
+            }),
+        }
+    }
+}
+
+impl ToolPolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `.locus/tools.toml` under `repo_root`. A missing file yields the defaults.
+    pub fn load(repo_root: &Path) -> Result<Self, ToolPolicyError> {
+        let path = repo_root.join(TOOL_POLICY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn with_tool(mut self, name: impl Into<String>, policy: ToolPolicy) -> Self {
+        self.tools.insert(name.into(), policy);
+        self
+    }
+
+    /// Approval rule for `tool_name`: its own `permission`, then the rule for the
+    /// permission level it needs. Plugins ask unless they have their own.
+    pub fn permission_rule(&self, tool_name: &str, permission: Permission) -> PermissionRule {
+        self.tools
+            .get(tool_name)
+            .and_then(|p| p.permission)
+            .unwrap_or_else(|| {
+                if tool_name.starts_with(PLUGIN_PREFIX) {
+                    PermissionRule::Ask
+                } else {
+                    self.permissions.rule(permission)
+                }
+            })
+    }
+
+    /// Resolve the limits for `tool_name`: tool entry, then `[defaults]`, then built-ins.
+    pub fn effective(&self, tool_name: &str) -> EffectiveToolPolicy {
+        let tool = self.tools.get(tool_name);
+
+        EffectiveToolPolicy {
+            timeout: Duration::from_secs(
+                tool.and_then(|p| p.timeout_secs)
+                    .or(self.defaults.timeout_secs)
+                    .unwrap_or(DEFAULT_TIMEOUT_SECS),
+            ),
+            max_result_bytes: tool
+                .and_then(|p| p.max_result_bytes)
+                .or(self.defaults.max_result_bytes)
+                .unwrap_or(DEFAULT_MAX_RESULT_BYTES),
+            truncation: tool
+                .and_then(|p| p.truncation)
+                .or(self.defaults.truncation)
+                .unwrap_or_default(),
+            cache: tool
+                .and_then(|p| p.cache)
+                .or(self.defaults.cache)
+                .unwrap_or(true),
+            retries: tool
+                .and_then(|p| p.retries)
+                .or(self.defaults.retries)
+                .unwrap_or(DEFAULT_RETRIES),
+        }
+    }
+}
+
+impl EffectiveToolPolicy {
+    /// Shrink `value` until its serialized form fits `max_result_bytes`.
+    /// Returns the number of bytes removed (0 when it already fit).
+    pub fn limit_result(&self, value: &mut JsonValue) -> usize {
+        let original = json_len(value);
+        if original <= self.max_result_bytes {
+            return 0;
+        }
+
+        // Pass 1: cut the longest string, one at a time.
+        loop {
+            let size = json_len(value);
+            if size <= self.max_result_bytes {
+                return original - size;
+            }
+            let Some(longest) = longest_string(value) else {
+                break;
+            };
+            if longest.len() <= MIN_STRING_BYTES {
+                break;
+            }
+            let keep = longest
+                .len()
+                .saturating_sub(size - self.max_result_bytes)
+                .max(MIN_STRING_BYTES);
+            *longest = cut_string(longest, keep, self.truncation);
+        }
+
+        // Pass 2: halve the longest array until it fits.
+        loop {
+            let size = json_len(value);
+            if size <= self.max_result_bytes {
+                return original - size;
+            }
+            let Some(longest) = longest_array(value) else {
+                break;
+            };
+            // Two items would become one item plus the marker: no progress.
+            if longest.len() <= 2 {
+                break;
+            }
+            let keep = longest.len() / 2;
+            cut_array(longest, keep, self.truncation);
+        }
+
+        // Last resort: replace the result with a cut-down copy of its text.
+        let text = value.to_string();
+        let cut = cut_string(&text, self.max_result_bytes, self.truncation);
+        *value = serde_json::json!({ "truncated_output": cut });
+        original.saturating_sub(json_len(value))
+    }
+}
+
+/// Run `tool` until it finishes, `timeout` passes or the caller cancels
+/// `ctx.cancel`. An interrupted tool has its token cancelled and gets
+/// [CANCEL_GRACE] to wind down before it is dropped.
+pub(crate) async fn run_bounded(
+    tool: &dyn Tool,
+    tool_name: &str,
+    args: JsonValue,
+    ctx: ToolContext,
+    timeout: Duration,
+) -> anyhow::Result<JsonValue> {
+    // The tool gets a child token, so a timeout does not cancel the caller's.
+    let caller = ctx.cancel.clone();
+    let cancel = caller.child_token();
+    let run = tool.execute_with_context(args, ctx.with_cancel(cancel.clone()));
+    tokio::pin!(run);
+    // Caller first: a tool that stops on the (inherited) cancellation reports
+    // its own error, which should not hide why it stopped.
+    let interrupted = tokio::select! {
+        biased;
+        _ = caller.cancelled() => ToolInterrupted::Cancelled {
+            tool: tool_name.to_string(),
+        },
+        result = &mut run => return result,
+        _ = tokio::time::sleep(timeout) => ToolInterrupted::TimedOut {
+            tool: tool_name.to_string(),
+            timeout_secs: timeout.as_secs(),
+        },
+    };
+    cancel.cancel();
+    if tokio::time::timeout(CANCEL_GRACE, &mut run).await.is_err() {
+        tracing::warn!(
+            "Tool '{}' did not stop within {}s of being cancelled",
+            tool_name,
+            CANCEL_GRACE.as_secs()
+        );
+    }
+    Err(interrupted.into())
+}
+
+fn json_len(value: &JsonValue) -> usize {
+    serde_json::to_string(value).map(|s| s.len()).unwrap_or(0)
+}
+
+fn longest_string(value: &mut JsonValue) -> Option<&mut String> {
+    match value {
+        JsonValue::String(s) => Some(s),
+        JsonValue::Array(items) => items
+            .iter_mut()
+            .filter_map(longest_string)
+            .max_by_key(|s| s.len()),
+        JsonValue::Object(map) => map
+            .values_mut()
+            .filter_map(longest_string)
+            .max_by_key(|s| s.len()),
+        _ => None,
+    }
+}
+
+fn longest_array(value: &mut JsonValue) -> Option<&mut Vec<JsonValue>> {
+    match value {
+        JsonValue::Array(_) => {
+            // Prefer a longer nested array over this one.
+            let own_len = value.as_array().map(Vec::len).unwrap_or(0);
+            let nested_len = value
+                .as_array_mut()
+                .and_then(|items| {
+                    items
+                        .iter_mut()
+                        .filter_map(longest_array)
+                        .map(|a| a.len())
+                        .max()
+                })
+                .unwrap_or(0);
+            if nested_len > own_len {
+                value
+                    .as_array_mut()?
+                    .iter_mut()
+                    .filter_map(longest_array)
+                    .max_by_key(|a| a.len())
+            } else {
+                value.as_array_mut()
+            }
+        }
+        JsonValue::Object(map) => map
+            .values_mut()
+            .filter_map(longest_array)
+            .max_by_key(|a| a.len()),
+        _ => None,
+    }
+}
+
+/// Keep about `keep` bytes of `s` per `strategy`, marking what was dropped.
+fn cut_string(s: &str, keep: usize, strategy: TruncationStrategy) -> String {
+    if s.len() <= keep {
+        return s.to_string();
+    }
+    let marker = format!("\n… [truncated {} bytes] …\n", s.len() - keep);
+    let budget = keep.saturating_sub(marker.len());
+    let head_end = |n: usize| {
+        let mut end = n.min(s.len());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        end
+    };
+    let tail_start = |n: usize| {
+        let mut start = s.len().saturating_sub(n);
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+        start
+    };
+    match strategy {
+        TruncationStrategy::Head => format!("{}{}", &s[..head_end(budget)], marker),
+        TruncationStrategy::Tail => format!("{}{}", marker, &s[tail_start(budget)..]),
+        TruncationStrategy::HeadTail => {
+            let half = budget / 2;
+            format!(
+                "{}{}{}",
+                &s[..head_end(half)],
+                marker,
+                &s[tail_start(budget - half)..]
+            )
+        }
+    }
+}
+
+/// Keep `keep` items of `items` per `strategy`, with a marker string where items were dropped.
+fn cut_array(items: &mut Vec<JsonValue>, keep: usize, strategy: TruncationStrategy) {
+    let dropped = items.len() - keep;
+    let marker = JsonValue::String(format!("… [truncated {} items] …", dropped));
+    match strategy {
+        TruncationStrategy::Head => {
+            items.truncate(keep);
+            items.push(marker);
+        }
+        TruncationStrategy::Tail => {
+            items.drain(..dropped);
+            items.insert(0, marker);
+        }
+        TruncationStrategy::HeadTail => {
+            let head = keep / 2;
+            items.drain(head..head + dropped);
+            items.insert(head, marker);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn limited(max_result_bytes: usize, truncation: TruncationStrategy) -> EffectiveToolPolicy {
+        EffectiveToolPolicy {
+            timeout: Duration::from_secs(1),
+            max_result_bytes,
+            truncation,
+            cache: true,
+            retries: 0,
+        }
+    }
+
+    #[test]
+    fn test_effective_policy_precedence() {
+        let policies: ToolPolicies = toml::from_str(
+            r#"
+            [defaults]
+            max_result_bytes = 2048
+
+            [tools.bash]
+            timeout_secs = 30
+            truncation = "tail"
+
+            [tools.grep]
+            cache = false
+            retries = 0
+            "#,
+        )
+        .unwrap();
+
+        let bash = policies.effective("bash");
+        assert_eq!(bash.timeout, Duration::from_secs(30));
+        assert_eq!(bash.max_result_bytes, 2048);
+        assert_eq!(bash.truncation, TruncationStrategy::Tail);
+
+        let grep = policies.effective("grep");
+        assert_eq!(grep.timeout, Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+        assert_eq!(grep.truncation, TruncationStrategy::HeadTail);
+        assert!(!grep.cache);
+        assert_eq!(grep.retries, 0);
+        assert!(policies.effective("read").cache);
+        assert_eq!(policies.effective("read").retries, DEFAULT_RETRIES);
+    }
+
+    #[test]
+    fn test_load_missing_and_present_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            ToolPolicies::load(dir.path()).unwrap(),
+            ToolPolicies::default()
+        );
+
+        std::fs::create_dir(dir.path().join(".locus")).unwrap();
+        std::fs::write(
+            dir.path().join(TOOL_POLICY_FILE),
+            "[tools.grep]\nmax_result_bytes = 512\n",
+        )
+        .unwrap();
+        let policies = ToolPolicies::load(dir.path()).unwrap();
+        assert_eq!(policies.effective("grep").max_result_bytes, 512);
+
+        std::fs::write(
+            dir.path().join(TOOL_POLICY_FILE),
+            "[permissions]\nwrite = \"ask\"\n\n[commands]\ndeny = [\"make deploy\"]\n\n[tools.bash]\npermission = \"deny\"\n",
+        )
+        .unwrap();
+        let policies = ToolPolicies::load(dir.path()).unwrap();
+        assert_eq!(policies.commands.deny, vec!["make deploy".to_string()]);
+        assert!(policies.commands.builtin);
+        assert_eq!(
+            policies.permission_rule("bash", Permission::Execute),
+            PermissionRule::Deny
+        );
+        assert_eq!(
+            policies.permission_rule("edit_file", Permission::Write),
+            PermissionRule::Ask
+        );
+        assert_eq!(
+            policies.permission_rule("grep", Permission::Read),
+            PermissionRule::Allow
+        );
+
+        std::fs::write(
+            dir.path().join(TOOL_POLICY_FILE),
+            "[permissions]\nread = \"allow\"\n\n[plugins]\ncount_lines = \"read\"\n\n[tools.\"plugin.lint\"]\npermission = \"allow\"\n",
+        )
+        .unwrap();
+        let policies = ToolPolicies::load(dir.path()).unwrap();
+        assert_eq!(policies.plugins["count_lines"], Permission::Read);
+        assert_eq!(
+            policies.permission_rule("plugin.count_lines", Permission::Read),
+            PermissionRule::Ask
+        );
+        assert_eq!(
+            policies.permission_rule("plugin.lint", Permission::Execute),
+            PermissionRule::Allow
+        );
+
+        std::fs::write(dir.path().join(TOOL_POLICY_FILE), "[tools.grep\n").unwrap();
+        assert!(matches!(
+            ToolPolicies::load(dir.path()),
+            Err(ToolPolicyError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_small_result_untouched() {
+        let mut value = json!({ "stdout": "ok", "exit_code": 0 });
+        assert_eq!(
+            limited(1024, TruncationStrategy::Head).limit_result(&mut value),
+            0
+        );
+        assert_eq!(value["stdout"], "ok");
+    }
+
+    #[test]
+    fn test_long_string_cut_per_strategy() {
+        let output = format!("{}{}", "a".repeat(5000), "z".repeat(5000));
+        for (strategy, keeps_a, keeps_z) in [
+            (TruncationStrategy::Head, true, false),
+            (TruncationStrategy::Tail, false, true),
+            (TruncationStrategy::HeadTail, true, true),
+        ] {
+            let mut value = json!({ "stdout": output, "exit_code": 0 });
+            let removed = limited(1024, strategy).limit_result(&mut value);
+            let stdout = value["stdout"].as_str().unwrap();
+
+            assert!(removed > 0);
+            assert!(json_len(&value) <= 1024);
+            assert!(stdout.contains("[truncated"));
+            assert_eq!(stdout.starts_with('a'), keeps_a);
+            assert_eq!(stdout.ends_with('z'), keeps_z);
+            assert_eq!(value["exit_code"], 0);
+        }
+    }
+
+    #[test]
+    fn test_long_array_halved() {
+        let matches: Vec<_> = (0..500).map(|i| json!({ "line": i })).collect();
+        let mut value = json!({ "matches": matches });
+        limited(1024, TruncationStrategy::Head).limit_result(&mut value);
+
+        let kept = value["matches"].as_array().unwrap();
+        assert!(json_len(&value) <= 1024);
+        assert_eq!(kept[0]["line"], 0);
+        assert!(kept.last().unwrap().as_str().unwrap().contains("truncated"));
+    }
+
+    #[test]
+    fn test_cut_string_respects_char_boundaries() {
+        let s = "é".repeat(1000);
+        for strategy in [
+            TruncationStrategy::Head,
+            TruncationStrategy::Tail,
+            TruncationStrategy::HeadTail,
+        ] {
+            let cut = cut_string(&s, 301, strategy);
+            assert!(cut.len() <= 301);
+        }
+    }
+}
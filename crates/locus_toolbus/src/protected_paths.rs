@@ -0,0 +1,320 @@
+//! Files the tools that read the repo never open, whatever the model asks for.
+//!
+//! - A pattern without `/` matches a file or directory name at any depth
+//!   (`*.pem` matches `certs/server.pem`)
+//! - A pattern with `/` matches trailing path components (`.aws/credentials`
+//!   matches `home/.aws/credentials`); a leading `/` anchors it at the repo root
+//! - A protected directory protects everything under it
+//! - `*`, `?` and `[...]` work within one component; matching ignores case
+//! - Symlinks are resolved, so a link to a protected file is protected too
+//! - Built-in patterns apply unless `builtin = false`
+//!
+//! An explicit protected path is refused with [ProtectedPathDenied]: tools that
+//! take one resolve it with `tools::resolve_start` (or `resolve_dir` /
+//! `resolve_file`), which checks it here. Walks (`grep`, `glob`, `finder`,
+//! `tree`, `todo_scan`, `code_nav`, `ast_search`, the semantic index) and whole
+//! `git_diff`s leave protected files out of their results.
+//!
+//! ```toml
+//! [protected_paths]
+//! deny = ["secrets/", "*.key", "/config/prod.toml"]
+//! ```
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::path::{Component, Path};
+
+/// Never read: env files, private keys and cloud credentials.
+pub const BUILTIN_PROTECTED: &[&str] = &[".env", "*.pem", "id_rsa", ".aws/credentials"];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// `[protected_paths]` in `.locus/tools.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtectedPaths {
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Also apply [BUILTIN_PROTECTED].
+    #[serde(default = "default_builtin")]
+    pub builtin: bool,
+}
+
+fn default_builtin() -> bool {
+    true
+}
+
+impl Default for ProtectedPaths {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            builtin: true,
+        }
+    }
+}
+
+impl ProtectedPaths {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deny(mut self, pattern: impl Into<String>) -> Self {
+        self.deny.push(pattern.into());
+        self
+    }
+
+    pub fn with_builtin(mut self, builtin: bool) -> Self {
+        self.builtin = builtin;
+        self
+    }
+
+    /// Compile the patterns once, for tools that check many paths.
+    pub fn matcher(&self) -> ProtectedPathMatcher {
+        let builtin = if self.builtin { BUILTIN_PROTECTED } else { &[] };
+        let rules = builtin
+            .iter()
+            .copied()
+            .chain(self.deny.iter().map(String::as_str))
+            .filter_map(PathRule::parse)
+            .collect();
+        ProtectedPathMatcher { rules }
+    }
+}
+
+/// One compiled pattern: its components, and whether it starts at the repo root.
+#[derive(Debug, Clone)]
+struct PathRule {
+    pattern: String,
+    components: Vec<Pattern>,
+    anchored: bool,
+}
+
+impl PathRule {
+    fn parse(pattern: &str) -> Option<Self> {
+        let trimmed = pattern.trim();
+        let anchored = trimmed.starts_with('/');
+        let components = trimmed
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .map(Pattern::new)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        if components.is_empty() {
+            return None;
+        }
+        Some(Self {
+            pattern: pattern.to_string(),
+            components,
+            anchored,
+        })
+    }
+
+    /// Whether `path` (components from the repo root, or from `/` when `rooted`
+    /// is false) or one of its ancestors matches.
+    fn matches(&self, path: &[String], rooted: bool) -> bool {
+        let n = self.components.len();
+        if self.anchored {
+            return rooted && path.len() >= n && self.matches_at(&path[..n]);
+        }
+        (n..=path.len()).any(|end| self.matches_at(&path[end - n..end]))
+    }
+
+    fn matches_at(&self, window: &[String]) -> bool {
+        self.components
+            .iter()
+            .zip(window)
+            .all(|(pattern, component)| pattern.matches_with(component, MATCH_OPTIONS))
+    }
+}
+
+/// Compiled [ProtectedPaths], held by the tools that enforce them.
+#[derive(Debug, Clone)]
+pub struct ProtectedPathMatcher {
+    rules: Vec<PathRule>,
+}
+
+impl Default for ProtectedPathMatcher {
+    fn default() -> Self {
+        ProtectedPaths::default().matcher()
+    }
+}
+
+impl ProtectedPathMatcher {
+    /// The pattern protecting `path` (absolute, or relative to `repo_root`), if any.
+    /// Checks the path as given and, when it exists, where its symlinks lead.
+    pub fn matching_pattern(&self, repo_root: &Path, path: &Path) -> Option<&str> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let full = repo_root.join(path);
+        let given = full.strip_prefix(repo_root).ok().map(components);
+        let resolved = full.canonicalize().ok().map(|real| {
+            match repo_root
+                .canonicalize()
+                .ok()
+                .and_then(|root| real.strip_prefix(root).ok().map(components))
+            {
+                Some(inside) => (inside, true),
+                None => (components(&real), false),
+            }
+        });
+        let candidates = given
+            .map(|c| (c, true))
+            .into_iter()
+            .chain(resolved)
+            .collect::<Vec<_>>();
+        self.rules
+            .iter()
+            .find(|rule| {
+                candidates
+                    .iter()
+                    .any(|(path, rooted)| rule.matches(path, *rooted))
+            })
+            .map(|rule| rule.pattern.as_str())
+    }
+
+    pub fn is_protected(&self, repo_root: &Path, path: &Path) -> bool {
+        self.matching_pattern(repo_root, path).is_some()
+    }
+
+    /// Fail with [ProtectedPathDenied] when `path` is protected. `shown` is the
+    /// path as the model wrote it.
+    pub fn check(
+        &self,
+        repo_root: &Path,
+        path: &Path,
+        shown: &str,
+    ) -> Result<(), ProtectedPathDenied> {
+        match self.matching_pattern(repo_root, path) {
+            Some(pattern) => Err(ProtectedPathDenied {
+                path: shown.to_string(),
+                pattern: pattern.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A read of a protected path. Reported to the model as [ProtectedPathDenied::to_json].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[error("Path is protected by rule `{pattern}`: {path}")]
+pub struct ProtectedPathDenied {
+    pub path: String,
+    pub pattern: String,
+}
+
+impl ProtectedPathDenied {
+    pub fn to_json(&self) -> JsonValue {
+        serde_json::json!({
+            "error": self.to_string(),
+            "protected_path": {
+                "path": self.path,
+                "pattern": self.pattern,
+            },
+            "hint": "This file may hold secrets and is protected by the project's rules \
+                     (.locus/tools.toml). Do not try to read it another way; ask the user \
+                     for the specific non-secret values you need.",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn protected_by(matcher: &ProtectedPathMatcher, path: &str) -> Option<String> {
+        matcher
+            .matching_pattern(Path::new("/nonexistent/repo"), Path::new(path))
+            .map(str::to_string)
+    }
+
+    #[test]
+    fn test_builtin_patterns() {
+        let matcher = ProtectedPathMatcher::default();
+        assert_eq!(protected_by(&matcher, ".env").as_deref(), Some(".env"));
+        assert_eq!(protected_by(&matcher, "api/.ENV").as_deref(), Some(".env"));
+        assert_eq!(
+            protected_by(&matcher, "certs/server.pem").as_deref(),
+            Some("*.pem")
+        );
+        assert_eq!(
+            protected_by(&matcher, "home/.ssh/id_rsa").as_deref(),
+            Some("id_rsa")
+        );
+        assert_eq!(
+            protected_by(&matcher, "home/.aws/credentials").as_deref(),
+            Some(".aws/credentials")
+        );
+        assert_eq!(protected_by(&matcher, "credentials"), None);
+        assert_eq!(protected_by(&matcher, ".env.example"), None);
+        assert_eq!(protected_by(&matcher, "id_rsa.pub"), None);
+        assert_eq!(protected_by(&matcher, "src/env.rs"), None);
+    }
+
+    #[test]
+    fn test_configured_patterns() {
+        let paths: ProtectedPaths =
+            toml::from_str("deny = [\"secrets/\", \"/config/prod.toml\"]\nbuiltin = false")
+                .unwrap();
+        let matcher = paths.matcher();
+        assert_eq!(
+            protected_by(&matcher, "secrets/db/password.txt").as_deref(),
+            Some("secrets/")
+        );
+        assert_eq!(
+            protected_by(&matcher, "config/prod.toml").as_deref(),
+            Some("/config/prod.toml")
+        );
+        assert_eq!(protected_by(&matcher, "app/config/prod.toml"), None);
+        assert_eq!(protected_by(&matcher, ".env"), None);
+    }
+
+    #[test]
+    fn test_absolute_paths_and_symlinks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::write(root.join(".env"), "TOKEN=x").unwrap();
+        let matcher = ProtectedPathMatcher::default();
+
+        assert!(matcher.is_protected(&root, &root.join(".env")));
+        assert!(!matcher.is_protected(&root, &PathBuf::from("README.md")));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join(".env"), root.join("settings.txt")).unwrap();
+            assert_eq!(
+                matcher.matching_pattern(&root, Path::new("settings.txt")),
+                Some(".env")
+            );
+        }
+    }
+
+    #[test]
+    fn test_denied_json_is_structured() {
+        let err = ProtectedPathMatcher::default()
+            .check(
+                Path::new("/repo"),
+                Path::new("deploy/key.pem"),
+                "deploy/key.pem",
+            )
+            .unwrap_err();
+        let json = err.to_json();
+        assert_eq!(json["protected_path"]["pattern"], "*.pem");
+        assert_eq!(json["protected_path"]["path"], "deploy/key.pem");
+        assert!(json["error"].as_str().unwrap().contains("deploy/key.pem"));
+    }
+}
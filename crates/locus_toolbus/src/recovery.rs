@@ -0,0 +1,424 @@
+//! What the bus does with a failed call before the model sees it.
+//!
+//! - **Transient** failures (a dropped connection, a request timeout, HTTP 429
+//!   or 5xx) of calls that are safe to repeat ([crate::Tool::idempotent]: read
+//!   tools, and `web_fetch` GETs) are retried by [crate::ToolBus::call] up to
+//!   the tool's `retries` (see [crate::policy]), waiting [backoff] between tries.
+//!   Calls stopped by their policy timeout or by the user are not retried.
+//! - **Deterministic** failures go back to the model as they are, with a hint
+//!   that makes the next attempt likelier to work: the closest text in the file
+//!   for an `old_string` that was not found, the lines of an ambiguous match,
+//!   files with the same name for a path that does not exist, the nearest tool
+//!   names for an unknown tool. See [crate::ToolBus::explain_failure]. Files
+//!   covered by `[protected_paths]` are never quoted or suggested.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::{Value as JsonValue, json};
+
+use crate::policy::{EffectiveToolPolicy, ToolInterrupted};
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{EditFileError, ReadError, Tool, ToolContext};
+
+/// Wait before the first retry; doubled for each one after.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+/// Files larger than this are not searched for a closest match.
+const MAX_HINT_FILE_BYTES: usize = 512 * 1024;
+/// Lowest similarity (0..1) for text or a name to be suggested.
+const MIN_SIMILARITY: f64 = 0.5;
+/// Characters of the closest match quoted in a hint.
+const MAX_MATCH_CHARS: usize = 2_000;
+/// Suggestions listed for a missing path or tool.
+const MAX_SUGGESTIONS: usize = 5;
+/// Directory entries walked looking for a missing file's name.
+const MAX_WALK_ENTRIES: usize = 20_000;
+
+/// Whether trying the same call again can work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Network trouble or a timeout; the same call may succeed later.
+    Transient,
+    /// The call itself is wrong; repeating it fails the same way.
+    Deterministic,
+}
+
+/// Classify `error` by the errors in its chain.
+pub fn classify(error: &anyhow::Error) -> FailureKind {
+    let transient = error.chain().any(|cause| {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::TimedOut
+                    | ErrorKind::Interrupted
+            );
+        }
+        #[cfg(feature = "web")]
+        if let Some(web) = cause.downcast_ref::<crate::tools::WebFetchError>() {
+            use crate::tools::WebFetchError;
+            return match web {
+                WebFetchError::Timeout(_) | WebFetchError::RequestFailed(_) => true,
+                WebFetchError::Status { status, .. } => {
+                    matches!(status, 408 | 425 | 429 | 500 | 502 | 503 | 504)
+                }
+                _ => false,
+            };
+        }
+        false
+    });
+    if transient {
+        FailureKind::Transient
+    } else {
+        FailureKind::Deterministic
+    }
+}
+
+/// Whether a call that failed with `error` is retried: a transient failure of
+/// an `idempotent` call (see [Tool::idempotent]).
+pub fn should_retry(idempotent: bool, error: &anyhow::Error) -> bool {
+    idempotent && classify(error) == FailureKind::Transient
+}
+
+/// Wait before retry `attempt` (1-based): 0.5s, 1s, 2s, ...
+pub fn backoff(attempt: u32) -> Duration {
+    RETRY_BACKOFF * 2u32.saturating_pow(attempt.saturating_sub(1).min(6))
+}
+
+/// Run `tool` under `policy` (see [crate::policy::run_bounded]), retrying
+/// transient failures with [backoff]. Each retry is reported to `ctx.progress`;
+/// cancelling `ctx.cancel` during the wait stops the call.
+pub(crate) async fn run_with_retries(
+    tool: &dyn Tool,
+    tool_name: &str,
+    args: JsonValue,
+    ctx: ToolContext,
+    policy: &EffectiveToolPolicy,
+) -> anyhow::Result<JsonValue> {
+    let mut attempt = 0;
+    loop {
+        let result =
+            crate::policy::run_bounded(tool, tool_name, args.clone(), ctx.clone(), policy.timeout)
+                .await;
+        let error = match result {
+            Err(e) if attempt < policy.retries && should_retry(tool.idempotent(&args), &e) => e,
+            result => return result,
+        };
+        attempt += 1;
+        let wait = backoff(attempt);
+        tracing::info!(
+            "ToolBus retrying {} in {:?} ({} of {}): {}",
+            tool_name,
+            wait,
+            attempt,
+            policy.retries,
+            error
+        );
+        ctx.report(format!(
+            "{} — retrying ({} of {})",
+            error, attempt, policy.retries
+        ));
+        tokio::select! {
+            _ = ctx.cancel.cancelled() => {
+                return Err(ToolInterrupted::Cancelled { tool: tool_name.to_string() }.into());
+            }
+            _ = tokio::time::sleep(wait) => {}
+        }
+    }
+}
+
+/// Hint fields for an `edit_file` or `read` failure: the closest match for an
+/// `old_string` that was not found, where an ambiguous one matched, files
+/// named like a missing path. None for other errors, and for protected files.
+pub(crate) fn file_hint(
+    repo_root: &Path,
+    protected: &ProtectedPathMatcher,
+    args: &JsonValue,
+    error: &anyhow::Error,
+) -> Option<JsonValue> {
+    let path = args.get("path").and_then(JsonValue::as_str)?;
+    if protected.is_protected(repo_root, Path::new(path)) {
+        return None;
+    }
+    if let Some(edit) = error.downcast_ref::<EditFileError>() {
+        let old_string = |edit_number: Option<usize>| {
+            let old = match edit_number {
+                Some(n) => args.get("edits")?.get(n.checked_sub(1)?)?.get("old_string"),
+                None => args.get("old_string"),
+            };
+            old.and_then(JsonValue::as_str)
+        };
+        return match edit {
+            EditFileError::OldStringNotFound => {
+                closest_match_hint(repo_root, path, old_string(None)?)
+            }
+            EditFileError::MultieditStringNotFound { edit_number } => {
+                closest_match_hint(repo_root, path, old_string(Some(*edit_number))?)
+            }
+            EditFileError::MultipleMatches => {
+                multiple_matches_hint(repo_root, path, old_string(None)?)
+            }
+            EditFileError::MultieditMultipleMatches { edit_number } => {
+                multiple_matches_hint(repo_root, path, old_string(Some(*edit_number))?)
+            }
+            EditFileError::FileNotFound(_) => Some(missing_path_hint(repo_root, protected, path)),
+            _ => None,
+        };
+    }
+    match error.downcast_ref::<ReadError>() {
+        Some(ReadError::NotFound(_)) => Some(missing_path_hint(repo_root, protected, path)),
+        _ => None,
+    }
+}
+
+/// The registered tool names closest to `name`, best first.
+pub(crate) fn similar_names<'a>(name: &str, names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut scored: Vec<(f64, &str)> = names
+        .map(|candidate| {
+            let score = if candidate.contains(name) || name.contains(candidate) {
+                1.0
+            } else {
+                similarity(name, candidate)
+            };
+            (score, candidate)
+        })
+        .filter(|(score, _)| *score >= MIN_SIMILARITY)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn read_for_hint(repo_root: &Path, path: &str) -> Option<String> {
+    let path = repo_root.join(path);
+    let size = std::fs::metadata(&path).ok()?.len();
+    if size as usize > MAX_HINT_FILE_BYTES {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+fn closest_match_hint(repo_root: &Path, path: &str, old_string: &str) -> Option<JsonValue> {
+    let content = read_for_hint(repo_root, path)?;
+    let Some((line, text, score)) = closest_match(&content, old_string) else {
+        return Some(json!({
+            "hint": format!(
+                "Nothing in {} is close to old_string; the file may have changed. \
+                 Read it again before editing.",
+                path
+            ),
+        }));
+    };
+    let hint = if normalize(&text) == normalize(old_string) {
+        format!(
+            "old_string differs from the text at line {} of {} only in whitespace or \
+             indentation. Copy it exactly as it is in closest_match.",
+            line, path
+        )
+    } else {
+        format!(
+            "old_string must match the file exactly. The closest text is at line {} of {} \
+             ({:.0}% similar); check it against closest_match, or read the file again.",
+            line,
+            path,
+            score * 100.0
+        )
+    };
+    let quoted: String = text.chars().take(MAX_MATCH_CHARS).collect();
+    Some(json!({
+        "hint": hint,
+        "closest_match": { "line": line, "text": quoted },
+    }))
+}
+
+fn multiple_matches_hint(repo_root: &Path, path: &str, old_string: &str) -> Option<JsonValue> {
+    if old_string.is_empty() {
+        return None;
+    }
+    let content = read_for_hint(repo_root, path)?;
+    let lines: Vec<usize> = content
+        .match_indices(old_string)
+        .map(|(offset, _)| content[..offset].matches('\n').count() + 1)
+        .collect();
+    let listed: Vec<String> = lines.iter().map(usize::to_string).collect();
+    Some(json!({
+        "hint": format!(
+            "old_string occurs {} times in {} (lines {}). Add surrounding lines to make it \
+             unique, or set replace_all to change every occurrence.",
+            lines.len(),
+            path,
+            listed.join(", ")
+        ),
+        "match_lines": lines,
+    }))
+}
+
+fn missing_path_hint(repo_root: &Path, protected: &ProtectedPathMatcher, path: &str) -> JsonValue {
+    let similar = files_named_like(repo_root, protected, path);
+    if similar.is_empty() {
+        return json!({
+            "hint": format!(
+                "{} does not exist. Use glob or finder to locate the file.",
+                path
+            ),
+        });
+    }
+    json!({
+        "hint": format!("{} does not exist. Did you mean {}?", path, similar.join(" or ")),
+        "similar_paths": similar,
+    })
+}
+
+/// Repo-relative files with the same name as `path`'s, skipping ignored and
+/// protected ones.
+fn files_named_like(repo_root: &Path, protected: &ProtectedPathMatcher, path: &str) -> Vec<String> {
+    let Some(name) = Path::new(path).file_name() else {
+        return Vec::new();
+    };
+    let walker = ignore::WalkBuilder::new(repo_root)
+        .hidden(true)
+        .git_ignore(true)
+        .require_git(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    walker
+        .flatten()
+        .take(MAX_WALK_ENTRIES)
+        .filter(|entry| entry.file_name() == name && entry.file_type().is_some_and(|t| t.is_file()))
+        .filter(|entry| !protected.is_protected(repo_root, entry.path()))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(repo_root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// The run of lines in `content` most like `needle`, as its 1-based first
+/// line, text and similarity; None when nothing reaches [MIN_SIMILARITY].
+fn closest_match(content: &str, needle: &str) -> Option<(usize, String, f64)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let width = needle.lines().count().max(1);
+    if lines.is_empty() {
+        return None;
+    }
+    let target = normalize(needle);
+    let mut best: Option<(usize, f64)> = None;
+    for start in 0..=lines.len().saturating_sub(width) {
+        let end = (start + width).min(lines.len());
+        let score = similarity(&target, &normalize(&lines[start..end].join("\n")));
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((start, score));
+        }
+    }
+    let (start, score) = best.filter(|(_, score)| *score >= MIN_SIMILARITY)?;
+    let end = (start + width).min(lines.len());
+    Some((start + 1, lines[start..end].join("\n"), score))
+}
+
+/// `text` with runs of whitespace made one space.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Dice coefficient of the character pairs in `a` and `b`.
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let pairs = |s: &str| {
+        let chars: Vec<char> = s.chars().collect();
+        let mut counts: HashMap<(char, char), usize> = HashMap::new();
+        for pair in chars.windows(2) {
+            *counts.entry((pair[0], pair[1])).or_default() += 1;
+        }
+        counts
+    };
+    let (a, b) = (pairs(a), pairs(b));
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a
+        .iter()
+        .map(|(pair, n)| (*n).min(b.get(pair).copied().unwrap_or(0)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_network_errors_as_transient() {
+        let reset = anyhow::Error::from(std::io::Error::from(ErrorKind::ConnectionReset));
+        assert_eq!(classify(&reset), FailureKind::Transient);
+        assert!(should_retry(true, &reset));
+        assert!(!should_retry(false, &reset));
+
+        let missing = anyhow::Error::from(EditFileError::OldStringNotFound);
+        assert_eq!(classify(&missing), FailureKind::Deterministic);
+        assert!(!should_retry(true, &missing));
+
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(3), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn never_quotes_protected_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "API_KEY=sk-live-123\n").unwrap();
+        std::fs::create_dir(dir.path().join("config")).unwrap();
+        std::fs::write(dir.path().join("config/id_rsa"), "KEY").unwrap();
+        let protected = ProtectedPathMatcher::default();
+        let not_found = anyhow::Error::from(EditFileError::OldStringNotFound);
+
+        let args = json!({"path": ".env", "old_string": "API_KEY=sk-live-12"});
+        assert!(file_hint(dir.path(), &protected, &args, &not_found).is_none());
+        let args = json!({"path": "README.md", "old_string": "API_KEY=sk-live-12"});
+        assert!(file_hint(dir.path(), &protected, &args, &not_found).is_none());
+
+        let missing = anyhow::Error::from(ReadError::NotFound("id_rsa".to_string()));
+        let hint = file_hint(dir.path(), &protected, &json!({"path": "id_rsa"}), &missing);
+        assert!(hint.is_none_or(|hint| hint.get("similar_paths").is_none()));
+    }
+
+    #[test]
+    fn finds_the_closest_lines() {
+        let content =
+            "fn main() {\n    let total = price * quantity;\n    println!(\"{}\", total);\n}\n";
+        let (line, text, score) = closest_match(
+            content,
+            "let total = price * qty;\nprintln!(\"{}\", total);",
+        )
+        .unwrap();
+        assert_eq!(line, 2);
+        assert_eq!(
+            text,
+            "    let total = price * quantity;\n    println!(\"{}\", total);"
+        );
+        assert!(score > 0.8);
+        assert!(closest_match(content, "SELECT * FROM users WHERE id = 1").is_none());
+    }
+
+    #[test]
+    fn suggests_similar_tool_names() {
+        let names = ["read", "edit_file", "create_file", "grep", "glob"];
+        assert_eq!(
+            similar_names("edit", names.iter().copied()),
+            ["edit_file".to_string()]
+        );
+        assert_eq!(
+            similar_names("crate_file", names.iter().copied())[0],
+            "create_file"
+        );
+    }
+}
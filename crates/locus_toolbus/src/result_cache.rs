@@ -0,0 +1,283 @@
+//! Results of idempotent read-only tools (read, grep, glob, tree), reused by
+//! [crate::ToolBus::call] while the files they looked at are unchanged.
+//!
+//! - Tools opt in with [crate::tools::Tool::cacheable]; `cache = false` in
+//!   `.locus/tools.toml` turns it off for one tool or, under `[defaults]`, for all
+//! - Entries are keyed on the tool name and its arguments, and hold a fingerprint
+//!   of the call's `path` (default: the repo root): the names of everything under
+//!   it that the tool would look at (its [CacheScope]: gitignored files for
+//!   `tree`, `target/` and dot-directories for `grep` and `glob` are left out),
+//!   minus `.git` and `.locus`, and the size and mtime of every file. A hit
+//!   needs the current fingerprint to match, so edits by the user or by git are
+//!   noticed
+//! - Paths with more than [MAX_FINGERPRINT_ENTRIES] entries are not cached:
+//!   fingerprinting them would cost about as much as the call
+//! - Calls that may change files (write or execute permission) clear the cache
+//! - At most [MAX_ENTRIES] results are kept; the least recently used goes first
+
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Results kept at once.
+pub const MAX_ENTRIES: usize = 128;
+/// Largest directory tree (files and directories) a cached call may cover.
+pub const MAX_FINGERPRINT_ENTRIES: usize = 20_000;
+/// Directories the fingerprint skips: git internals, and the project DB the bus
+/// writes on every call.
+const SKIPPED_DIRS: &[&str] = &[".git", ".locus"];
+
+/// What a cached call can read under its `path`, so the fingerprint walks that
+/// and no more. See [crate::tools::Tool::cache_scope].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheScope {
+    /// Everything.
+    #[default]
+    All,
+    /// What an `ignore` walk yields: without gitignored entries when
+    /// `gitignore`, without dotfiles and dot-directories when `hidden`.
+    Ignore { gitignore: bool, hidden: bool },
+    /// Everything but dot-directories and directories with these names.
+    SkipDirs(&'static [&'static str]),
+}
+
+struct Entry {
+    fingerprint: u64,
+    output: JsonValue,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<String, Entry>,
+    clock: u64,
+}
+
+/// Cached tool results for one bus.
+pub struct ResultCache {
+    repo_root: PathBuf,
+    entries: Mutex<Entries>,
+}
+
+impl ResultCache {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Cache key for a call.
+    pub fn key(tool_name: &str, args: &JsonValue) -> String {
+        format!("{}\n{}", tool_name, args)
+    }
+
+    /// Fingerprint of the files in `scope` under the call's `path`, or None when
+    /// there are too many to track. Walks the file system; call it off the
+    /// async runtime.
+    pub fn fingerprint(&self, args: &JsonValue, scope: CacheScope) -> Option<u64> {
+        let path = args.get("path").and_then(|p| p.as_str()).unwrap_or(".");
+        fingerprint(&self.repo_root.join(path), scope)
+    }
+
+    /// The result stored for `key`, if it was stored with `fingerprint`.
+    pub fn get(&self, key: &str, fingerprint: u64) -> Option<JsonValue> {
+        let mut entries = self.entries.lock().ok()?;
+        entries.clock += 1;
+        let clock = entries.clock;
+        let entry = entries.by_key.get_mut(key)?;
+        if entry.fingerprint != fingerprint {
+            return None;
+        }
+        entry.last_used = clock;
+        Some(entry.output.clone())
+    }
+
+    pub fn insert(&self, key: String, fingerprint: u64, output: JsonValue) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        entries.clock += 1;
+        let last_used = entries.clock;
+        if entries.by_key.len() >= MAX_ENTRIES && !entries.by_key.contains_key(&key) {
+            let oldest = entries
+                .by_key
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.by_key.remove(&oldest);
+            }
+        }
+        entries.by_key.insert(
+            key,
+            Entry {
+                fingerprint,
+                output,
+                last_used,
+            },
+        );
+    }
+
+    /// Drop every result.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.by_key.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |e| e.by_key.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Hash of the paths in `scope` under `root` and the size and mtime of its files.
+fn fingerprint(root: &Path, scope: CacheScope) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    let (gitignore, hidden) = match scope {
+        CacheScope::Ignore { gitignore, hidden } => (gitignore, hidden),
+        _ => (false, false),
+    };
+    let walker = ignore::WalkBuilder::new(root)
+        .standard_filters(false)
+        .hidden(hidden)
+        .git_ignore(gitignore)
+        .git_exclude(gitignore)
+        .ignore(gitignore)
+        .parents(gitignore)
+        .require_git(false)
+        .filter_entry(move |e| {
+            if e.depth() == 0 {
+                return true;
+            }
+            let Some(name) = e.file_name().to_str() else {
+                return true;
+            };
+            let skipped_dir = match scope {
+                CacheScope::SkipDirs(dirs) => {
+                    (name.starts_with('.') || dirs.contains(&name))
+                        && e.file_type().is_some_and(|t| t.is_dir())
+                }
+                _ => false,
+            };
+            !SKIPPED_DIRS.contains(&name) && !skipped_dir
+        })
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    let mut count = 0;
+    for entry in walker {
+        let Ok(entry) = entry else {
+            // Missing root or unreadable entry: hash the error so it still differs
+            // from the readable state.
+            "error".hash(&mut hasher);
+            continue;
+        };
+        count += 1;
+        if count > MAX_FINGERPRINT_ENTRIES {
+            return None;
+        }
+        entry.path().hash(&mut hasher);
+        // A directory's own mtime only says its entries changed, and those are
+        // walked anyway; it would also move whenever `.locus` is created.
+        if let Some(metadata) = entry.metadata().ok().filter(|m| !m.is_dir()) {
+            metadata.len().hash(&mut hasher);
+            if let Ok(modified) = metadata.modified() {
+                modified
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos()
+                    .hash(&mut hasher);
+            }
+        }
+    }
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fingerprint_tracks_edits_and_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().to_path_buf());
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").unwrap();
+        let src = json!({"path": "src"});
+        let root = json!({});
+
+        let before = cache.fingerprint(&src, CacheScope::All).unwrap();
+        let root_before = cache.fingerprint(&root, CacheScope::All).unwrap();
+        assert_eq!(cache.fingerprint(&src, CacheScope::All), Some(before));
+
+        // The bus's own DB writes don't count.
+        std::fs::create_dir(dir.path().join(".locus")).unwrap();
+        std::fs::write(dir.path().join(".locus/locus.db"), "x").unwrap();
+        assert_eq!(cache.fingerprint(&root, CacheScope::All), Some(root_before));
+
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() { b() }").unwrap();
+        let edited = cache.fingerprint(&src, CacheScope::All).unwrap();
+        assert_ne!(edited, before);
+        std::fs::write(dir.path().join("src/b.rs"), "").unwrap();
+        assert_ne!(cache.fingerprint(&src, CacheScope::All).unwrap(), edited);
+        // A sibling doesn't touch `src`.
+        let src_now = cache.fingerprint(&src, CacheScope::All);
+        std::fs::write(dir.path().join("README.md"), "hi").unwrap();
+        assert_eq!(cache.fingerprint(&src, CacheScope::All), src_now);
+    }
+
+    #[test]
+    fn fingerprint_skips_what_the_scope_leaves_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().to_path_buf());
+        std::fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        let root = json!({});
+        let tree = CacheScope::Ignore {
+            gitignore: true,
+            hidden: true,
+        };
+        let grep = CacheScope::SkipDirs(&["target"]);
+        let before = |scope| cache.fingerprint(&root, scope).unwrap();
+        let (all, tree_before, grep_before) = (before(CacheScope::All), before(tree), before(grep));
+
+        for dir_name in ["build", "target", ".cache"] {
+            std::fs::create_dir(dir.path().join(dir_name)).unwrap();
+            std::fs::write(dir.path().join(dir_name).join("out"), "x").unwrap();
+        }
+        assert_ne!(cache.fingerprint(&root, CacheScope::All), Some(all));
+        // build/ is gitignored, .cache hidden; target/ is neither.
+        assert_ne!(cache.fingerprint(&root, tree), Some(tree_before));
+        std::fs::remove_dir_all(dir.path().join("target")).unwrap();
+        assert_eq!(cache.fingerprint(&root, tree), Some(tree_before));
+        // grep and glob walk build/ but not target/ or dot-directories.
+        assert_ne!(cache.fingerprint(&root, grep), Some(grep_before));
+        std::fs::remove_dir_all(dir.path().join("build")).unwrap();
+        assert_eq!(cache.fingerprint(&root, grep), Some(grep_before));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ResultCache::new(PathBuf::from("/tmp"));
+        for i in 0..MAX_ENTRIES {
+            cache.insert(format!("k{}", i), 1, json!(i));
+        }
+        assert_eq!(cache.get("k0", 1), Some(json!(0)));
+        assert_eq!(cache.get("k0", 2), None);
+        cache.insert("new".to_string(), 1, json!("new"));
+        assert_eq!(cache.len(), MAX_ENTRIES);
+        assert!(cache.get("k0", 1).is_some());
+        assert!(cache.get("k1", 1).is_none());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,632 @@
+//! Project tools written as [Rhai](https://rhai.rs) scripts, from
+//! `.locus/tools/*.rhai`.
+//!
+//! A script declares its tool with top-level constants and an `execute`
+//! function:
+//!
+//! ```text
+//! const DESCRIPTION = "Count TODO comments in a file";
+//! const PARAMETERS = #{
+//!     type: "object",
+//!     properties: #{ path: #{ type: "string" } },
+//!     required: ["path"],
+//! };
+//! const PERMISSION = "read";   // optional: read (default), write, execute, network
+//!
+//! fn execute(args) {
+//!     let count = 0;
+//!     for line in read_file(args.path).split("\n") {
+//!         if line.contains("TODO") { count += 1; }
+//!     }
+//!     #{ count: count }
+//! }
+//! ```
+//!
+//! The bus registers it as `script.<file stem>`; `execute` gets the call's
+//! arguments as a map and its return value is the tool result. `throw` fails
+//! the call with the thrown message. The constants must be literals; functions
+//! read them as `global::NAME`.
+//!
+//! Scripts get Rhai's standard library (without `eval` or `import`) and these
+//! functions:
+//!
+//! - `read_file(path)`: a file in the repo as a string. Paths outside the repo
+//!   and protected paths (`[protected_paths]` in `.locus/tools.toml`) fail
+//! - `run_command(command)`: run a shell command in the repo root through the
+//!   bus's execution backend and return `#{ exit_code, stdout, stderr }`. Only
+//!   scripts declaring `PERMISSION = "execute"` may call it, and `[commands]`
+//!   deny patterns apply
+//! - `print(value)`: send a progress line
+//!
+//! A call runs at most [MAX_OPERATIONS] operations, nests at most
+//! [MAX_CALL_LEVELS] function calls, builds no string longer than
+//! [MAX_STRING_SIZE] bytes, and stops when it is cancelled or times out.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::packages::{Package, StandardPackage};
+use rhai::{AST, CallFnOptions, Dynamic, Engine, EvalAltResult, Scope};
+use serde_json::Value as JsonValue;
+use thiserror::Error;
+use tokio::runtime::Handle;
+use tokio_util::sync::CancellationToken;
+
+use crate::command_rules::{CommandRules, CommandVerdict};
+use crate::execution::ExecutionBackend;
+use crate::permission::Permission;
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::lint::shell_and_arg;
+use crate::tools::{Tool, ToolContext, ToolResult};
+
+/// Script directory, relative to the repo root.
+pub const SCRIPT_DIR: &str = ".locus/tools";
+pub const SCRIPT_EXTENSION: &str = "rhai";
+
+/// Largest file `read_file` returns.
+const MAX_READ_BYTES: u64 = 1024 * 1024;
+/// Operations one call may run.
+pub const MAX_OPERATIONS: u64 = 1_000_000;
+/// Depth of nested function calls.
+pub const MAX_CALL_LEVELS: usize = 64;
+/// Longest string a script may build, in bytes.
+pub const MAX_STRING_SIZE: usize = 4 * 1024 * 1024;
+/// Most items in one array or map.
+const MAX_COLLECTION_SIZE: usize = 100_000;
+
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    #[error("{script}: {message}")]
+    Parse { script: String, message: String },
+
+    #[error("{script}: {message}")]
+    Invalid { script: String, message: String },
+
+    #[error("{script} failed: {message}")]
+    Failed { script: String, message: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Finds and compiles the script tools of one repo, and provides the functions
+/// they call.
+#[derive(Debug, Clone)]
+pub struct ScriptHost {
+    repo_root: PathBuf,
+    backend: ExecutionBackend,
+    protected: ProtectedPathMatcher,
+    commands: CommandRules,
+}
+
+impl ScriptHost {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            backend: ExecutionBackend::default(),
+            protected: ProtectedPathMatcher::default(),
+            commands: CommandRules::default(),
+        }
+    }
+
+    /// Run `run_command` through `backend` (see [crate::execution]).
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Refuse `read_file` on paths matching `protected`.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Refuse `run_command` on commands `rules` deny.
+    pub fn with_command_rules(mut self, rules: CommandRules) -> Self {
+        self.commands = rules;
+        self
+    }
+
+    /// `.rhai` files in the script directory, sorted; empty when there is none.
+    pub fn script_files(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(self.repo_root.join(SCRIPT_DIR)) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.is_file() && path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION)
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Compile every script. Scripts that fail to compile are logged and left
+    /// out, so one broken script does not take the bus down.
+    pub fn load(&self) -> Vec<ScriptTool> {
+        let host = Arc::new(self.clone());
+        self.script_files()
+            .into_iter()
+            .filter_map(|path| match ScriptTool::compile(Arc::clone(&host), &path) {
+                Ok(tool) => Some(tool),
+                Err(e) => {
+                    tracing::warn!("Skipping script tool {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// `path` inside the repo, with symlinks resolved.
+    fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let root = self
+            .repo_root
+            .canonicalize()
+            .map_err(|e| format!("read_file(): {}", e))?;
+        let full = root
+            .join(path)
+            .canonicalize()
+            .map_err(|e| format!("read_file({:?}): {}", path, e))?;
+        if !full.starts_with(&root) {
+            return Err(format!("read_file({:?}): path is outside the repo", path));
+        }
+        Ok(full)
+    }
+}
+
+/// An engine with Rhai's standard library and nothing more: no modules, no
+/// `eval`, and the limits of one call.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new_raw();
+    engine.register_global_module(StandardPackage::new().as_shared_module());
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE);
+    engine
+}
+
+/// A script registered on the bus as `script.<file stem>`.
+pub struct ScriptTool {
+    host: Arc<ScriptHost>,
+    path: PathBuf,
+    ast: Arc<AST>,
+    parameters: JsonValue,
+    permission: Permission,
+    /// Leaked once for [Tool::name] and [Tool::description], as for MCP tools.
+    name: &'static str,
+    description: &'static str,
+}
+
+impl ScriptTool {
+    fn compile(host: Arc<ScriptHost>, path: &Path) -> Result<Self, ScriptError> {
+        let script = script_label(path);
+        let source = std::fs::read_to_string(path)?;
+        let ast = sandboxed_engine()
+            .compile(&source)
+            .map_err(|e| ScriptError::Parse {
+                script: script.clone(),
+                message: e.to_string(),
+            })?;
+        let invalid = |message: String| ScriptError::Invalid {
+            script: script.clone(),
+            message,
+        };
+        // Only literal constants are read, so nothing runs while loading.
+        let constants: HashMap<String, JsonValue> = ast
+            .iter_literal_variables(true, false)
+            .filter_map(|(name, _, value)| {
+                Some((name.to_string(), rhai::serde::from_dynamic(&value).ok()?))
+            })
+            .collect();
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == "execute" && f.params.len() == 1)
+        {
+            return Err(invalid("missing `fn execute(args)`".to_string()));
+        }
+        let description = match constants.get("DESCRIPTION") {
+            Some(JsonValue::String(text)) => text.clone(),
+            _ => {
+                return Err(invalid(
+                    "missing `const DESCRIPTION = \"...\";`".to_string(),
+                ));
+            }
+        };
+        let parameters = match constants.get("PARAMETERS") {
+            Some(schema @ JsonValue::Object(_)) => schema.clone(),
+            None => serde_json::json!({ "type": "object", "properties": {} }),
+            Some(_) => return Err(invalid("PARAMETERS must be a map (`#{ ... }`)".to_string())),
+        };
+        let permission = match constants.get("PERMISSION") {
+            None => Permission::Read,
+            Some(value) => serde_json::from_value(value.clone())
+                .map_err(|_| invalid(format!("unknown PERMISSION {}", value)))?,
+        };
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(Self {
+            host,
+            path: path.to_path_buf(),
+            ast: Arc::new(ast),
+            parameters,
+            permission,
+            name: Box::leak(format!("script.{}", stem).into_boxed_str()),
+            description: Box::leak(description.into_boxed_str()),
+        })
+    }
+
+    pub fn script_path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn run(&self, args: JsonValue, ctx: &ToolContext) -> Result<JsonValue, ScriptError> {
+        // Timeouts drop this future; the guard then stops the script as well.
+        let cancel = ctx.cancel.child_token();
+        let _stop_on_drop = cancel.clone().drop_guard();
+        let builtins = Arc::new(Builtins {
+            host: Arc::clone(&self.host),
+            permission: self.permission,
+            cancel,
+            runtime: Handle::current(),
+        });
+        let progress = ctx.progress.clone();
+        let ast = Arc::clone(&self.ast);
+        let result = tokio::task::spawn_blocking(move || {
+            let mut engine = sandboxed_engine();
+            builtins.register(&mut engine);
+            engine.on_print(move |text| progress.report(text));
+            // Evaluating the top level first defines the `global::` constants.
+            let value: Dynamic = engine.call_fn_with_options(
+                CallFnOptions::new().eval_ast(true),
+                &mut Scope::new(),
+                &ast,
+                "execute",
+                (rhai::serde::to_dynamic(&args)?,),
+            )?;
+            rhai::serde::from_dynamic::<JsonValue>(&value)
+        })
+        .await
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+        result.map_err(|e| ScriptError::Failed {
+            script: script_label(&self.path),
+            message: error_message(&e),
+        })
+    }
+}
+
+/// `todo_count.rhai` for messages.
+fn script_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// What went wrong, for the model: a thrown value or a host function's error
+/// as it is, other errors as Rhai words them, with the line either way.
+fn error_message(error: &EvalAltResult) -> String {
+    match error {
+        EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => error_message(inner),
+        EvalAltResult::ErrorTerminated(..) => "the script was cancelled".to_string(),
+        EvalAltResult::ErrorRuntime(value, position) => match position.line() {
+            Some(line) => format!("{} (line {})", value, line),
+            None => value.to_string(),
+        },
+        _ => error.to_string(),
+    }
+}
+
+/// The host functions of one call. Runs on a blocking thread.
+struct Builtins {
+    host: Arc<ScriptHost>,
+    permission: Permission,
+    cancel: CancellationToken,
+    runtime: Handle,
+}
+
+impl Builtins {
+    /// Give `engine` the host functions, and stop its script on cancel.
+    fn register(self: &Arc<Self>, engine: &mut Engine) {
+        let builtins = Arc::clone(self);
+        engine.register_fn("read_file", move |path: &str| {
+            builtins.read_file(path).map_err(Box::<EvalAltResult>::from)
+        });
+        let builtins = Arc::clone(self);
+        engine.register_fn("run_command", move |command: &str| {
+            let output = builtins
+                .run_command(command)
+                .map_err(Box::<EvalAltResult>::from)?;
+            rhai::serde::to_dynamic(output)
+        });
+        let cancel = self.cancel.clone();
+        engine.on_progress(move |_| cancel.is_cancelled().then_some(Dynamic::UNIT));
+    }
+
+    fn read_file(&self, path: &str) -> Result<String, String> {
+        let full = self.host.resolve(path)?;
+        self.host
+            .protected
+            .check(&self.host.repo_root, &full, path)
+            .map_err(|e| e.to_string())?;
+        let size = std::fs::metadata(&full)
+            .map_err(|e| format!("read_file({:?}): {}", path, e))?
+            .len();
+        if size > MAX_READ_BYTES {
+            return Err(format!(
+                "read_file({:?}): file is {} bytes, more than the {} byte limit",
+                path, size, MAX_READ_BYTES
+            ));
+        }
+        std::fs::read_to_string(&full).map_err(|e| format!("read_file({:?}): {}", path, e))
+    }
+
+    fn run_command(&self, command: &str) -> Result<JsonValue, String> {
+        if self.permission != Permission::Execute {
+            return Err(
+                "run_command() needs `const PERMISSION = \"execute\";` in the script".to_string(),
+            );
+        }
+        if let CommandVerdict::Deny(denied) = self.host.commands.evaluate(command) {
+            return Err(denied.to_string());
+        }
+        let host = &self.host;
+        let mut prepared = host
+            .backend
+            .command(
+                &host.repo_root,
+                Some(&host.repo_root),
+                shell_and_arg(),
+                command,
+            )
+            .map_err(|e| e.to_string())?;
+        prepared.command.kill_on_drop(true);
+        let output = self.runtime.block_on(async {
+            tokio::select! {
+                output = prepared.command.output() => Some(output),
+                _ = self.cancel.cancelled() => None,
+            }
+        });
+        let Some(output) = output else {
+            self.runtime
+                .block_on(host.backend.cleanup(prepared.container.as_deref()));
+            return Err("the script was cancelled".to_string());
+        };
+        let output = output.map_err(|e| format!("run_command({:?}): {}", command, e))?;
+        Ok(serde_json::json!({
+            "exit_code": output.status.code().unwrap_or(-1),
+            "stdout": String::from_utf8_lossy(&output.stdout),
+            "stderr": String::from_utf8_lossy(&output.stderr),
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for ScriptTool {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        self.parameters.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        self.permission
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        Ok(self.run(args, &ctx).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protected_paths::ProtectedPaths;
+    use crate::tools::ProgressSink;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn add_script(repo: &Path, name: &str, source: &str) {
+        let dir = repo.join(SCRIPT_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{}.rhai", name)), source).unwrap();
+    }
+
+    const TODO_COUNT: &str = r#"
+        const DESCRIPTION = "Count TODO comments in a file";
+        const PARAMETERS = #{
+            type: "object",
+            properties: #{ path: #{ type: "string" } },
+            required: ["path"],
+        };
+
+        fn execute(args) {
+            let count = 0;
+            for line in read_file(args.path).split("\n") {
+                if line.contains("TODO") { count += 1; }
+            }
+            print(`${count} in ${args.path}`);
+            #{ path: args.path, count: count }
+        }
+    "#;
+
+    #[test]
+    fn test_scripts_register_as_declared() {
+        let repo = TempDir::new().unwrap();
+        add_script(repo.path(), "todo_count", TODO_COUNT);
+        add_script(repo.path(), "broken", "fn execute(args) { let }");
+        add_script(repo.path(), "no_description", "fn execute(args) { 1 }");
+        add_script(
+            repo.path(),
+            "test_runner",
+            "const DESCRIPTION = \"Run tests\";\nconst PERMISSION = \"execute\";\nfn execute(args) { () }",
+        );
+
+        let tools = ScriptHost::new(repo.path().to_path_buf()).load();
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, ["script.test_runner", "script.todo_count"]);
+        assert_eq!(tools[0].permission(), Permission::Execute);
+        assert_eq!(
+            tools[0].parameters_schema(),
+            json!({"type": "object", "properties": {}})
+        );
+        assert_eq!(tools[1].description(), "Count TODO comments in a file");
+        assert_eq!(tools[1].permission(), Permission::Read);
+        assert_eq!(tools[1].parameters_schema()["required"], json!(["path"]));
+
+        let host = Arc::new(ScriptHost::new(repo.path().to_path_buf()));
+        let err = ScriptTool::compile(
+            Arc::clone(&host),
+            &repo.path().join(SCRIPT_DIR).join("no_description.rhai"),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "no_description.rhai: missing `const DESCRIPTION = \"...\";`"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_file_stays_in_the_repo() {
+        let repo = TempDir::new().unwrap();
+        std::fs::write(repo.path().join("notes.txt"), "TODO one\nfine\nTODO two\n").unwrap();
+        std::fs::write(repo.path().join(".env"), "TOKEN=secret TODO\n").unwrap();
+        add_script(repo.path(), "todo_count", TODO_COUNT);
+        let tool = ScriptHost::new(repo.path().to_path_buf())
+            .with_protected_paths(ProtectedPaths::default().matcher())
+            .load()
+            .pop()
+            .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let result = tool
+            .execute_with_context(
+                json!({"path": "notes.txt"}),
+                ToolContext::new().with_progress(ProgressSink::new(tx)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"path": "notes.txt", "count": 2}));
+        assert_eq!(rx.recv().await.unwrap(), "2 in notes.txt");
+
+        let err = tool
+            .execute(json!({"path": "../outside.txt"}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("todo_count.rhai failed: read_file("),
+            "got: {}",
+            err
+        );
+        let err = tool.execute(json!({"path": ".env"})).await.unwrap_err();
+        assert!(
+            err.to_string().contains("Path is protected"),
+            "got: {}",
+            err
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_needs_execute_permission_and_obeys_rules() {
+        let repo = TempDir::new().unwrap();
+        let script = |permission: &str| {
+            format!(
+                "const DESCRIPTION = \"Run\";\n{}\nfn execute(args) {{\n  let out = run_command(args.command);\n  let err = out.stderr;\n  err.trim();\n  if out.exit_code != 0 {{ throw err; }}\n  let text = out.stdout;\n  text.trim();\n  text\n}}\n",
+                permission
+            )
+        };
+        add_script(repo.path(), "reader", &script(""));
+        add_script(
+            repo.path(),
+            "runner",
+            &script("const PERMISSION = \"execute\";"),
+        );
+        let rules: CommandRules = toml::from_str("deny = [\"rm *\"]").unwrap();
+        let tools = ScriptHost::new(repo.path().to_path_buf())
+            .with_command_rules(rules)
+            .load();
+        let (reader, runner) = (&tools[0], &tools[1]);
+
+        let err = reader
+            .execute(json!({"command": "echo hi"}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("needs `const PERMISSION"),
+            "got: {}",
+            err
+        );
+
+        let out = runner
+            .execute(json!({"command": "pwd && echo hi"}))
+            .await
+            .unwrap();
+        let canonical = repo.path().canonicalize().unwrap();
+        assert!(
+            out == json!(format!("{}\nhi", repo.path().display()))
+                || out == json!(format!("{}\nhi", canonical.display())),
+            "got: {}",
+            out
+        );
+        let err = runner
+            .execute(json!({"command": "echo oops >&2; exit 1"}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "runner.rhai failed: oops (line 7)");
+        let err = runner
+            .execute(json!({"command": "rm -rf target"}))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Command denied by rule `rm *`"),
+            "got: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scripts_are_sandboxed() {
+        let repo = TempDir::new().unwrap();
+        let tool = |source: &str| {
+            add_script(repo.path(), "probe", source);
+            ScriptHost::new(repo.path().to_path_buf()).load().pop()
+        };
+        let spin = tool("const DESCRIPTION = \"Spin\";\nfn execute(args) { loop {} }").unwrap();
+        let err = spin.execute(json!({})).await.unwrap_err();
+        assert!(
+            err.to_string().contains("Too many operations"),
+            "got: {}",
+            err
+        );
+
+        let import =
+            tool("const DESCRIPTION = \"Import\";\nfn execute(args) { import \"os\" as os; 1 }")
+                .unwrap();
+        let err = import.execute(json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("Module not found"), "got: {}", err);
+
+        assert!(tool("const DESCRIPTION = \"Eval\";\nfn execute(args) { eval(\"1\") }").is_none());
+    }
+}
@@ -0,0 +1,112 @@
+//! Split files into the chunks that get embedded.
+//!
+//! Files with a bundled grammar are split at top-level items, with leading
+//! comments and attributes kept on the item they describe, so a hit points at a
+//! whole function or type. Other text files, and items longer than
+//! [`MAX_CHUNK_LINES`], are cut into fixed line windows.
+
+use tree_sitter::{Node, Parser};
+
+use crate::tools::syntax::language_for;
+
+/// Longest chunk; longer items are split into windows of this many lines.
+pub const MAX_CHUNK_LINES: usize = 60;
+
+/// A span of one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// 1-based, inclusive.
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Name of the item the chunk covers, when it covers one.
+    pub symbol: Option<String>,
+    pub text: String,
+}
+
+/// Chunks of `text`, the content of repo file `path`.
+pub fn chunk_file(path: &str, text: &str) -> Vec<Chunk> {
+    let lines: Vec<&str> = text.lines().collect();
+    let spans = language_for(path)
+        .and_then(|language| {
+            let mut parser = Parser::new();
+            parser.set_language(&language).ok()?;
+            let tree = parser.parse(text, None)?;
+            Some(item_spans(tree.root_node(), text))
+        })
+        .unwrap_or_else(|| vec![(0, lines.len().saturating_sub(1), None)]);
+
+    let mut chunks = Vec::new();
+    for (start, end, symbol) in spans {
+        let mut at = start;
+        while at <= end && at < lines.len() {
+            let stop = (at + MAX_CHUNK_LINES - 1).min(end).min(lines.len() - 1);
+            let body = lines[at..=stop].join("\n");
+            if !body.trim().is_empty() {
+                chunks.push(Chunk {
+                    start_line: at + 1,
+                    end_line: stop + 1,
+                    symbol: symbol.clone(),
+                    text: body,
+                });
+            }
+            at = stop + 1;
+        }
+    }
+    chunks
+}
+
+/// 0-based line spans of top-level items, each with the comments and attributes
+/// right above it; stray nodes between items form their own spans.
+fn item_spans(root: Node, text: &str) -> Vec<(usize, usize, Option<String>)> {
+    let mut spans: Vec<(usize, usize, Option<String>)> = Vec::new();
+    let mut pending: Option<usize> = None;
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        let start = node.start_position().row;
+        let end = node.end_position().row;
+        if is_preamble(node.kind()) {
+            pending.get_or_insert(start);
+            continue;
+        }
+        let symbol = item_name(&node, text);
+        let start = pending.take().unwrap_or(start);
+        // Imports and other one-liners merge into the previous unnamed span.
+        if symbol.is_none()
+            && let Some(last) = spans.last_mut()
+            && last.2.is_none()
+            && start <= last.1 + 1
+        {
+            last.1 = end;
+            continue;
+        }
+        spans.push((start, end, symbol));
+    }
+    if let Some(start) = pending {
+        spans.push((start, root.end_position().row, None));
+    }
+    spans
+}
+
+fn is_preamble(kind: &str) -> bool {
+    matches!(
+        kind,
+        "line_comment" | "block_comment" | "comment" | "attribute_item" | "decorator"
+    )
+}
+
+/// Name of an item: its `name` field, or the type of a Rust `impl`.
+fn item_name(node: &Node, text: &str) -> Option<String> {
+    let mut node = *node;
+    // `export function f` and `@decorated def f` wrap the definition.
+    if matches!(node.kind(), "export_statement" | "decorated_definition")
+        && let Some(inner) = node
+            .child_by_field_name("declaration")
+            .or_else(|| node.child_by_field_name("definition"))
+    {
+        node = inner;
+    }
+    let name = node
+        .child_by_field_name("name")
+        .or_else(|| node.child_by_field_name("type"))?;
+    name.utf8_text(text.as_bytes()).ok().map(str::to_string)
+}
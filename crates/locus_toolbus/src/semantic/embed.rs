@@ -0,0 +1,249 @@
+//! Text embedders for the semantic index.
+//!
+//! - [`LexicalEmbedder`]: local and instant, and lexical, not semantic. Splits
+//!   identifiers (`refreshJwtToken` → refresh, jwt, token), drops stop words,
+//!   folds plurals and hashes words and their trigrams into a fixed-size vector.
+//!   It matches wording across naming styles; synonyms and paraphrases miss.
+//! - [`ApiEmbedder`]: any OpenAI-compatible `/embeddings` endpoint, the only
+//!   source of semantic matches. Set `LOCUS_EMBEDDINGS_URL` (e.g. `https://api.openai.com/v1`)
+//!   and `LOCUS_EMBEDDINGS_API_KEY`, and optionally `LOCUS_EMBEDDINGS_MODEL`.
+//!
+//! The index remembers which embedder built it and re-embeds everything when it
+//! changes.
+
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use sha2::{Digest, Sha256};
+
+use super::error::{SemanticError, SemanticResult};
+
+pub const EMBEDDINGS_URL_ENV: &str = "LOCUS_EMBEDDINGS_URL";
+pub const EMBEDDINGS_API_KEY_ENV: &str = "LOCUS_EMBEDDINGS_API_KEY";
+pub const EMBEDDINGS_MODEL_ENV: &str = "LOCUS_EMBEDDINGS_MODEL";
+
+const DEFAULT_API_MODEL: &str = "text-embedding-3-small";
+const DEFAULT_LEXICAL_DIMENSIONS: usize = 512;
+
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Identifies the model and settings; vectors from different ids don't mix.
+    fn id(&self) -> String;
+
+    /// One vector per input, in order.
+    async fn embed(&self, texts: &[String]) -> SemanticResult<Vec<Vec<f32>>>;
+}
+
+/// The API embedder when its env vars are set, the lexical one otherwise.
+pub fn embedder_from_env() -> Box<dyn Embedder> {
+    match (
+        std::env::var(EMBEDDINGS_URL_ENV),
+        std::env::var(EMBEDDINGS_API_KEY_ENV),
+    ) {
+        (Ok(url), Ok(key)) if !url.is_empty() && !key.is_empty() => {
+            let model = std::env::var(EMBEDDINGS_MODEL_ENV)
+                .unwrap_or_else(|_| DEFAULT_API_MODEL.to_string());
+            Box::new(ApiEmbedder::new(url, key, model))
+        }
+        _ => Box::new(LexicalEmbedder::new()),
+    }
+}
+
+/// Words that say nothing about where code is.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "code", "do", "does", "for", "from", "how",
+    "i", "in", "is", "it", "of", "on", "or", "the", "this", "to", "we", "what", "where", "which",
+    "who", "why", "with",
+];
+
+/// Lowercase words of `text`, with identifiers split at `_`, case changes and digits.
+pub fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for token in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut word = String::new();
+        let mut prev: Option<char> = None;
+        for c in token.chars() {
+            let boundary = prev.is_some_and(|p| {
+                (p.is_lowercase() && c.is_uppercase()) || (p.is_alphabetic() != c.is_alphabetic())
+            });
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+            prev = Some(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+        .into_iter()
+        .filter(|w| w.len() > 1 && !w.chars().all(|c| c.is_ascii_digit()))
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+        .map(|w| fold(&w))
+        .collect()
+}
+
+/// Fold common English endings so `tokens`, `refreshing` and `refreshed` meet
+/// `token` and `refresh`.
+fn fold(word: &str) -> String {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(stem) = word.strip_suffix(suffix)
+            && stem.len() >= 3
+            && !stem.ends_with('s')
+        {
+            return stem.to_string();
+        }
+    }
+    word.to_string()
+}
+
+/// Word and trigram hashing embedder; see the module docs.
+pub struct LexicalEmbedder {
+    dimensions: usize,
+}
+
+impl LexicalEmbedder {
+    pub fn new() -> Self {
+        Self::with_dimensions(DEFAULT_LEXICAL_DIMENSIONS)
+    }
+
+    pub fn with_dimensions(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(16),
+        }
+    }
+
+    pub fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0f32; self.dimensions];
+        let mut add = |feature: &str, weight: f32| {
+            let digest = Sha256::digest(feature.as_bytes());
+            let bucket = u64::from_le_bytes(digest[..8].try_into().expect("8 bytes"));
+            let sign = if digest[8] & 1 == 0 { 1.0 } else { -1.0 };
+            vector[(bucket % self.dimensions as u64) as usize] += sign * weight;
+        };
+        for word in words(text) {
+            add(&word, 1.0);
+            let chars: Vec<char> = format!("^{}$", word).chars().collect();
+            for gram in chars.windows(3) {
+                add(&gram.iter().collect::<String>(), 0.25);
+            }
+        }
+        normalize(&mut vector);
+        vector
+    }
+}
+
+impl Default for LexicalEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Embedder for LexicalEmbedder {
+    fn id(&self) -> String {
+        format!("lexical-{}", self.dimensions)
+    }
+
+    async fn embed(&self, texts: &[String]) -> SemanticResult<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.embed_one(t)).collect())
+    }
+}
+
+/// OpenAI-compatible embeddings API client.
+pub struct ApiEmbedder {
+    base_url: String,
+    api_key: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl ApiEmbedder {
+    pub fn new(
+        base_url: impl Into<String>,
+        api_key: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: api_key.into(),
+            model: model.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for ApiEmbedder {
+    fn id(&self) -> String {
+        format!("api-{}", self.model)
+    }
+
+    async fn embed(&self, texts: &[String]) -> SemanticResult<Vec<Vec<f32>>> {
+        let url = format!("{}/embeddings", self.base_url.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&json!({ "model": self.model, "input": texts }))
+            .send()
+            .await
+            .map_err(|e| SemanticError::Embedding(e.to_string()))?;
+        let status = response.status();
+        let body: JsonValue = response
+            .json()
+            .await
+            .map_err(|e| SemanticError::Embedding(e.to_string()))?;
+        if !status.is_success() {
+            let message = body["error"]["message"].as_str().unwrap_or("no message");
+            return Err(SemanticError::Embedding(format!("{}: {}", status, message)));
+        }
+        parse_embeddings(&body, texts.len())
+    }
+}
+
+/// Vectors from an `/embeddings` response, ordered by `index`.
+pub fn parse_embeddings(body: &JsonValue, expected: usize) -> SemanticResult<Vec<Vec<f32>>> {
+    let data = body["data"]
+        .as_array()
+        .ok_or_else(|| SemanticError::Embedding("response has no data array".to_string()))?;
+    let mut vectors = vec![Vec::new(); data.len()];
+    for (position, item) in data.iter().enumerate() {
+        let index = item["index"].as_u64().map_or(position, |i| i as usize);
+        let mut vector: Vec<f32> = item["embedding"]
+            .as_array()
+            .ok_or_else(|| SemanticError::Embedding("item has no embedding".to_string()))?
+            .iter()
+            .map(|v| v.as_f64().unwrap_or(0.0) as f32)
+            .collect();
+        normalize(&mut vector);
+        match vectors.get_mut(index) {
+            Some(slot) => *slot = vector,
+            None => {
+                return Err(SemanticError::Embedding(format!(
+                    "index {} out of range",
+                    index
+                )));
+            }
+        }
+    }
+    if vectors.len() != expected {
+        return Err(SemanticError::EmbeddingCount {
+            expected,
+            got: vectors.len(),
+        });
+    }
+    Ok(vectors)
+}
+
+/// Scale to unit length, so similarity is a dot product.
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
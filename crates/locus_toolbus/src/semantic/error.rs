@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SemanticError {
+    #[error("Embedding request failed: {0}")]
+    Embedding(String),
+
+    #[error("Embedder returned {got} vectors for {expected} inputs")]
+    EmbeddingCount { expected: usize, got: usize },
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("Index database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Index task failed: {0}")]
+    Task(String),
+}
+
+impl From<ignore::Error> for SemanticError {
+    fn from(err: ignore::Error) -> Self {
+        SemanticError::Walk(err.to_string())
+    }
+}
+
+pub type SemanticResult<T> = Result<T, SemanticError>;
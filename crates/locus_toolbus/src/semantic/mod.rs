@@ -0,0 +1,313 @@
+//! Embedding-based code search over the repo. The vectors are semantic only
+//! with an embeddings API configured; the built-in embedder is lexical (see
+//! [embed]).
+//!
+//! Files are split into chunks (top-level items where a grammar is bundled, line
+//! windows elsewhere), embedded, and stored in `.locus/index.db`. Every search
+//! first refreshes the index: files whose size or mtime changed are re-hashed,
+//! and only those whose content changed are re-chunked and re-embedded, so edits
+//! made during a session are searchable on the next call.
+//!
+//! - **chunk**: Splitting files into chunks
+//! - **embed**: The [`Embedder`] trait, local lexical and OpenAI-compatible API embedders
+//! - **store**: SQLite storage of files, chunks and vectors
+//! - **error**: Error types for indexing and search
+//!
+//! The `semantic_search` tool is in `tools::semantic_search`.
+
+pub mod chunk;
+pub mod embed;
+pub mod error;
+pub mod store;
+
+pub use chunk::{Chunk, chunk_file};
+pub use embed::{ApiEmbedder, Embedder, LexicalEmbedder, embedder_from_env};
+pub use error::{SemanticError, SemanticResult};
+pub use store::INDEX_DB_FILE;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use embed::dot;
+use store::{FileStamp, Store};
+
+/// Files above this size are not indexed.
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Stop walking after this many files.
+const MAX_FILES: usize = 20_000;
+/// Chunks per embedder call.
+const EMBED_BATCH: usize = 64;
+
+/// Extensions of the source and doc files that get indexed.
+const INDEXED_EXTENSIONS: &[&str] = &[
+    "rs", "py", "pyi", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", "go", "java", "kt",
+    "swift", "c", "h", "cc", "cpp", "hpp", "cs", "rb", "php", "scala", "sh", "sql", "md", "toml",
+    "yaml", "yml",
+];
+
+/// What a refresh changed, and the index size after it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RefreshStats {
+    pub files: usize,
+    pub chunks: usize,
+    /// Files embedded this time (new or changed).
+    pub updated: usize,
+    pub removed: usize,
+}
+
+/// One search result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticHit {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub symbol: Option<String>,
+    pub score: f32,
+    pub text: String,
+}
+
+/// A file that needs (re-)embedding.
+struct PendingFile {
+    path: String,
+    stamp: FileStamp,
+    chunks: Vec<Chunk>,
+}
+
+/// The semantic index of one repo.
+pub struct SemanticIndex {
+    repo_root: PathBuf,
+    embedder: Arc<dyn Embedder>,
+    /// Files never indexed, so never embedded or returned.
+    protected: ProtectedPathMatcher,
+    /// Serializes refreshes so two searches don't embed the same files.
+    refresh_lock: Mutex<()>,
+}
+
+impl SemanticIndex {
+    /// Index using the embedder configured in the environment.
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self::with_embedder(repo_root, Arc::from(embedder_from_env()))
+    }
+
+    pub fn with_embedder(repo_root: PathBuf, embedder: Arc<dyn Embedder>) -> Self {
+        Self {
+            repo_root,
+            embedder,
+            protected: ProtectedPathMatcher::default(),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+
+    /// Leave files matching `protected` out instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    pub fn protected_paths(&self) -> &ProtectedPathMatcher {
+        &self.protected
+    }
+
+    pub fn embedder_id(&self) -> String {
+        self.embedder.id()
+    }
+
+    /// Bring the index up to date with the files on disk.
+    pub async fn refresh(&self) -> SemanticResult<RefreshStats> {
+        let _guard = self.refresh_lock.lock().await;
+        let root = self.repo_root.clone();
+        let embedder_id = self.embedder.id();
+        let protected = self.protected.clone();
+        let (pending, removed) = blocking(move || scan(&root, &embedder_id, &protected)).await?;
+
+        let mut embedded = Vec::with_capacity(pending.len());
+        for file in pending {
+            let mut vectors = Vec::with_capacity(file.chunks.len());
+            for batch in file.chunks.chunks(EMBED_BATCH) {
+                let texts: Vec<String> = batch.iter().map(embed_text(&file.path)).collect();
+                let batch_vectors = self.embedder.embed(&texts).await?;
+                if batch_vectors.len() != texts.len() {
+                    return Err(SemanticError::EmbeddingCount {
+                        expected: texts.len(),
+                        got: batch_vectors.len(),
+                    });
+                }
+                vectors.extend(batch_vectors);
+            }
+            embedded.push((file, vectors));
+        }
+
+        let root = self.repo_root.clone();
+        blocking(move || {
+            let mut store = Store::open(&root)?;
+            let updated = embedded.len();
+            for (file, vectors) in embedded {
+                let chunks: Vec<_> = file.chunks.into_iter().zip(vectors).collect();
+                store.replace_file(&file.path, &file.stamp, &chunks)?;
+            }
+            let (files, chunks) = store.counts()?;
+            Ok(RefreshStats {
+                files,
+                chunks,
+                updated,
+                removed,
+            })
+        })
+        .await
+    }
+
+    /// The `limit` chunks under `path` (repo-relative; empty for all) closest to `query`.
+    /// Does not refresh; call [`SemanticIndex::refresh`] first.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        path: &str,
+    ) -> SemanticResult<Vec<SemanticHit>> {
+        let query_vector = self
+            .embedder
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .ok_or(SemanticError::EmbeddingCount {
+                expected: 1,
+                got: 0,
+            })?;
+        let root = self.repo_root.clone();
+        let prefix = path.trim_start_matches("./").to_string();
+        let stored = blocking(move || Store::open(&root)?.chunks(&prefix)).await?;
+
+        let mut hits: Vec<SemanticHit> = stored
+            .into_iter()
+            .map(|s| SemanticHit {
+                score: dot(&query_vector, &s.vector),
+                path: s.path,
+                start_line: s.chunk.start_line,
+                end_line: s.chunk.end_line,
+                symbol: s.chunk.symbol,
+                text: s.chunk.text,
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+}
+
+/// The text embedded for a chunk: its path and symbol give context the body may lack.
+fn embed_text(path: &str) -> impl Fn(&Chunk) -> String + '_ {
+    move |chunk| match &chunk.symbol {
+        Some(symbol) => format!("{} {}\n{}", path, symbol, chunk.text),
+        None => format!("{}\n{}", path, chunk.text),
+    }
+}
+
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> SemanticResult<T> + Send + 'static,
+) -> SemanticResult<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| SemanticError::Task(e.to_string()))?
+}
+
+/// Compare the files on disk with the index. Unchanged content gets its new stamp,
+/// deleted and protected files are dropped, and new or changed files are returned chunked.
+fn scan(
+    root: &Path,
+    embedder_id: &str,
+    protected: &ProtectedPathMatcher,
+) -> SemanticResult<(Vec<PendingFile>, usize)> {
+    let mut store = Store::open(root)?;
+    if store.embedder_id()?.as_deref() != Some(embedder_id) {
+        store.reset(embedder_id)?;
+    }
+    let known = store.files()?;
+
+    let (walk_root, protected) = (root.to_path_buf(), protected.clone());
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .git_exclude(true)
+        .git_global(false)
+        .require_git(false)
+        .filter_entry(move |e| {
+            e.file_name() != ".git" && !protected.is_protected(&walk_root, e.path())
+        })
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let mut seen = HashSet::new();
+    let mut pending = Vec::new();
+    for entry in walker {
+        let entry = entry?;
+        if seen.len() >= MAX_FILES {
+            break;
+        }
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let indexed = entry
+            .path()
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| INDEXED_EXTENSIONS.contains(&e));
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !indexed || metadata.len() > MAX_FILE_BYTES {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        seen.insert(rel.clone());
+
+        let mtime_ns = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos() as i64);
+        let size = metadata.len() as i64;
+        let previous = known.get(&rel);
+        if previous.is_some_and(|p| p.mtime_ns == mtime_ns && p.size == size) {
+            continue;
+        }
+        // Binary and non-UTF-8 files are skipped.
+        let Ok(content) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let stamp = FileStamp {
+            mtime_ns,
+            size,
+            hash: format!("{:x}", Sha256::digest(content.as_bytes())),
+        };
+        if previous.is_some_and(|p| p.hash == stamp.hash) {
+            store.touch_file(&rel, &stamp)?;
+            continue;
+        }
+        pending.push(PendingFile {
+            chunks: chunk_file(&rel, &content),
+            path: rel,
+            stamp,
+        });
+    }
+
+    let mut removed = 0;
+    for path in known.keys().filter(|p| !seen.contains(*p)) {
+        store.remove_file(path)?;
+        removed += 1;
+    }
+    Ok((pending, removed))
+}
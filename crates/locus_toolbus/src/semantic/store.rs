@@ -0,0 +1,186 @@
+//! SQLite storage for the semantic index in `.locus/index.db`.
+//!
+//! Kept apart from `locus.db` so the index can be deleted or rebuilt freely.
+//! Vectors are stored as little-endian `f32` blobs.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use super::chunk::Chunk;
+use super::error::SemanticResult;
+
+/// Index database, relative to the repo root.
+pub const INDEX_DB_FILE: &str = ".locus/index.db";
+
+/// What the index knows about a file when it was last embedded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    pub mtime_ns: i64,
+    pub size: i64,
+    pub hash: String,
+}
+
+/// A stored chunk with its vector.
+#[derive(Debug, Clone)]
+pub struct StoredChunk {
+    pub path: String,
+    pub chunk: Chunk,
+    pub vector: Vec<f32>,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(repo_root: &Path) -> SemanticResult<Self> {
+        let path = repo_root.join(INDEX_DB_FILE);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;
+             CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             CREATE TABLE IF NOT EXISTS files (
+                 path TEXT PRIMARY KEY, mtime_ns INTEGER NOT NULL,
+                 size INTEGER NOT NULL, hash TEXT NOT NULL);
+             CREATE TABLE IF NOT EXISTS chunks (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT, path TEXT NOT NULL,
+                 start_line INTEGER NOT NULL, end_line INTEGER NOT NULL,
+                 symbol TEXT, text TEXT NOT NULL, vector BLOB NOT NULL);
+             CREATE INDEX IF NOT EXISTS chunks_path ON chunks(path);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Embedder the stored vectors came from.
+    pub fn embedder_id(&self) -> SemanticResult<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = 'embedder'", [], |r| {
+                r.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Drop everything and start over for `embedder_id`.
+    pub fn reset(&mut self, embedder_id: &str) -> SemanticResult<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM chunks", [])?;
+        tx.execute("DELETE FROM files", [])?;
+        tx.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('embedder', ?1)",
+            [embedder_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn files(&self) -> SemanticResult<HashMap<String, FileStamp>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, mtime_ns, size, hash FROM files")?;
+        let rows = stmt.query_map([], |r| {
+            Ok((
+                r.get::<_, String>(0)?,
+                FileStamp {
+                    mtime_ns: r.get(1)?,
+                    size: r.get(2)?,
+                    hash: r.get(3)?,
+                },
+            ))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Record a new stamp for a file whose content did not change.
+    pub fn touch_file(&self, path: &str, stamp: &FileStamp) -> SemanticResult<()> {
+        self.conn.execute(
+            "UPDATE files SET mtime_ns = ?2, size = ?3 WHERE path = ?1",
+            params![path, stamp.mtime_ns, stamp.size],
+        )?;
+        Ok(())
+    }
+
+    /// Replace a file's chunks.
+    pub fn replace_file(
+        &mut self,
+        path: &str,
+        stamp: &FileStamp,
+        chunks: &[(Chunk, Vec<f32>)],
+    ) -> SemanticResult<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM chunks WHERE path = ?1", [path])?;
+        tx.execute(
+            "INSERT OR REPLACE INTO files (path, mtime_ns, size, hash) VALUES (?1, ?2, ?3, ?4)",
+            params![path, stamp.mtime_ns, stamp.size, stamp.hash],
+        )?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO chunks (path, start_line, end_line, symbol, text, vector)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (chunk, vector) in chunks {
+                let blob: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+                insert.execute(params![
+                    path,
+                    chunk.start_line as i64,
+                    chunk.end_line as i64,
+                    chunk.symbol,
+                    chunk.text,
+                    blob,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn remove_file(&self, path: &str) -> SemanticResult<()> {
+        self.conn
+            .execute("DELETE FROM chunks WHERE path = ?1", [path])?;
+        self.conn
+            .execute("DELETE FROM files WHERE path = ?1", [path])?;
+        Ok(())
+    }
+
+    /// Chunks of files under `prefix` (every file when empty).
+    pub fn chunks(&self, prefix: &str) -> SemanticResult<Vec<StoredChunk>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path, start_line, end_line, symbol, text, vector FROM chunks
+             WHERE ?1 = '' OR path = ?1 OR substr(path, 1, length(?1) + 1) = ?1 || '/'
+             ORDER BY path, start_line",
+        )?;
+        let rows = stmt.query_map([prefix.trim_end_matches('/')], |r| {
+            let blob: Vec<u8> = r.get(5)?;
+            Ok(StoredChunk {
+                path: r.get(0)?,
+                chunk: Chunk {
+                    start_line: r.get::<_, i64>(1)? as usize,
+                    end_line: r.get::<_, i64>(2)? as usize,
+                    symbol: r.get(3)?,
+                    text: r.get(4)?,
+                },
+                vector: blob
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect(),
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Number of indexed files and chunks.
+    pub fn counts(&self) -> SemanticResult<(usize, usize)> {
+        let files: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM files", [], |r| r.get(0))?;
+        let chunks: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM chunks", [], |r| r.get(0))?;
+        Ok((files as usize, chunks as usize))
+    }
+}
@@ -0,0 +1,266 @@
+//! Helpers for testing tools: schema checks, temporary workspaces and golden results.
+//!
+//! Built into this crate's own tests and, for plugin authors, behind the `testing`
+//! feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! locus-toolbus = { path = "../locus_toolbus", features = ["testing"] }
+//! ```
+//!
+//! A typical tool test builds a [`TestWorkspace`], checks the schema against the args
+//! struct with [`assert_args_round_trip`], runs the tool and compares the result with a
+//! checked-in file using [`assert_golden`]. Set `LOCUS_UPDATE_GOLDEN=1` to (re)write
+//! golden files after an intended change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+use crate::tools::Tool;
+
+/// Environment variable that makes [`assert_golden`] write the actual result instead of comparing.
+pub const UPDATE_GOLDEN_ENV: &str = "LOCUS_UPDATE_GOLDEN";
+
+/// Placeholder [`TestWorkspace::normalize`] puts where the workspace path was.
+pub const ROOT_PLACEHOLDER: &str = "<root>";
+
+/// A temporary repo root, removed when dropped.
+pub struct TestWorkspace {
+    dir: TempDir,
+}
+
+impl TestWorkspace {
+    pub fn new() -> Self {
+        Self {
+            dir: TempDir::new().expect("create temp workspace"),
+        }
+    }
+
+    /// Add a file (parent directories are created).
+    pub fn with_file(self, path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> Self {
+        self.write(path, content);
+        self
+    }
+
+    /// Add an empty directory.
+    pub fn with_dir(self, path: impl AsRef<Path>) -> Self {
+        fs::create_dir_all(self.path().join(path)).expect("create workspace dir");
+        self
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>, content: impl AsRef<[u8]>) {
+        let path = self.path().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("create workspace dir");
+        }
+        fs::write(path, content).expect("write workspace file");
+    }
+
+    /// Contents of a workspace file; panics when it is missing or not UTF-8.
+    pub fn read(&self, path: impl AsRef<Path>) -> String {
+        let path = self.path().join(path);
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+    }
+
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        self.path().join(path).exists()
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Owned root, as tool constructors take it.
+    pub fn root(&self) -> PathBuf {
+        self.dir.path().to_path_buf()
+    }
+
+    /// Replace the workspace path in every string of `value` with [`ROOT_PLACEHOLDER`],
+    /// so results with absolute paths can be compared with golden files.
+    pub fn normalize(&self, value: &mut JsonValue) {
+        let root = self.path().to_string_lossy().to_string();
+        replace_in_strings(value, &root, ROOT_PLACEHOLDER);
+    }
+}
+
+impl Default for TestWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check the shape of a tool's name and parameters schema: a snake_case name, a
+/// description, an object schema whose properties all have a type and description,
+/// and `required` keys that exist in `properties`.
+pub fn assert_schema_valid(tool: &dyn Tool) {
+    let name = tool.name();
+    assert!(
+        !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+        "tool name {:?} must be snake_case",
+        name
+    );
+    assert!(
+        !tool.description().trim().is_empty(),
+        "{}: description is empty",
+        name
+    );
+
+    let schema = tool.parameters_schema();
+    assert_eq!(
+        schema["type"], "object",
+        "{}: parameters schema must be an object",
+        name
+    );
+    let properties = schema["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("{}: parameters schema has no properties object", name));
+    for (key, property) in properties {
+        assert!(
+            property.get("type").is_some() || property.get("enum").is_some(),
+            "{}: property {:?} has no type",
+            name,
+            key
+        );
+        assert!(
+            property["description"]
+                .as_str()
+                .is_some_and(|d| !d.trim().is_empty()),
+            "{}: property {:?} has no description",
+            name,
+            key
+        );
+    }
+    if let Some(required) = schema.get("required") {
+        let required = required
+            .as_array()
+            .unwrap_or_else(|| panic!("{}: required must be an array", name));
+        for key in required {
+            let key = key
+                .as_str()
+                .unwrap_or_else(|| panic!("{}: required entries must be strings", name));
+            assert!(
+                properties.contains_key(key),
+                "{}: required key {:?} is not in properties",
+                name,
+                key
+            );
+        }
+    }
+}
+
+/// Check that `example` deserializes into the tool's args type `A`, serializes back
+/// to the same value, and only uses keys the schema declares. Also runs
+/// [`assert_schema_valid`].
+pub fn assert_args_round_trip<A>(tool: &dyn Tool, example: JsonValue)
+where
+    A: DeserializeOwned + Serialize,
+{
+    assert_schema_valid(tool);
+    let name = tool.name();
+
+    let args: A = serde_json::from_value(example.clone())
+        .unwrap_or_else(|e| panic!("{}: example args do not deserialize: {}", name, e));
+    let serialized = serde_json::to_value(&args).expect("args serialize");
+    let again: A = serde_json::from_value(serialized.clone())
+        .unwrap_or_else(|e| panic!("{}: serialized args do not deserialize: {}", name, e));
+    assert_eq!(
+        serialized,
+        serde_json::to_value(&again).expect("args serialize"),
+        "{}: args change on a serde round trip",
+        name
+    );
+
+    let schema = tool.parameters_schema();
+    let properties = schema["properties"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    for value in [&example, &serialized] {
+        for key in value.as_object().into_iter().flat_map(|o| o.keys()) {
+            assert!(
+                properties.contains_key(key),
+                "{}: args key {:?} is not in the schema",
+                name,
+                key
+            );
+        }
+    }
+    for (key, value) in example.as_object().into_iter().flatten() {
+        assert_eq!(
+            serialized.get(key),
+            Some(value),
+            "{}: args key {:?} does not round-trip",
+            name,
+            key
+        );
+    }
+}
+
+/// Compare `actual` with the pretty-printed JSON in `golden_path`.
+///
+/// A missing file is created, and with `LOCUS_UPDATE_GOLDEN=1` the file is always
+/// rewritten. Redact volatile fields first with [`redact_keys`] and workspace paths
+/// with [`TestWorkspace::normalize`].
+pub fn assert_golden(actual: &JsonValue, golden_path: impl AsRef<Path>) {
+    let path = golden_path.as_ref();
+    let rendered = format!(
+        "{}\n",
+        serde_json::to_string_pretty(actual).expect("result serializes")
+    );
+    let update = std::env::var(UPDATE_GOLDEN_ENV).is_ok_and(|v| v == "1");
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("create golden dir");
+        }
+        fs::write(path, &rendered).expect("write golden file");
+        return;
+    }
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("read golden {}: {}", path.display(), e));
+    assert_eq!(
+        rendered,
+        expected,
+        "result differs from {} (rerun with {}=1 to update)",
+        path.display(),
+        UPDATE_GOLDEN_ENV
+    );
+}
+
+/// Replace the value of every `keys` field, at any depth, with `"[redacted]"`.
+pub fn redact_keys(value: &mut JsonValue, keys: &[&str]) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if keys.contains(&key.as_str()) {
+                    *field = JsonValue::String("[redacted]".to_string());
+                } else {
+                    redact_keys(field, keys);
+                }
+            }
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(|v| redact_keys(v, keys)),
+        _ => {}
+    }
+}
+
+/// Replace `from` with `to` in every string of `value`, at any depth.
+pub fn replace_in_strings(value: &mut JsonValue, from: &str, to: &str) {
+    match value {
+        JsonValue::String(s) if s.contains(from) => *s = s.replace(from, to),
+        JsonValue::Object(map) => map
+            .values_mut()
+            .for_each(|v| replace_in_strings(v, from, to)),
+        JsonValue::Array(items) => items
+            .iter_mut()
+            .for_each(|v| replace_in_strings(v, from, to)),
+        _ => {}
+    }
+}
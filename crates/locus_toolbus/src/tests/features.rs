@@ -0,0 +1,49 @@
+use crate::features::{Capability, CapabilityKind, capabilities, find_program};
+use crate::testing::TestWorkspace;
+
+#[test]
+#[cfg(all(feature = "syntax", feature = "semantic", feature = "web"))]
+fn test_default_build_reports_all_features() {
+    let workspace = TestWorkspace::new();
+    let report = capabilities(&workspace.root());
+    let features: Vec<&Capability> = report
+        .iter()
+        .filter(|c| c.kind == CapabilityKind::Feature)
+        .collect();
+    let names: Vec<&str> = features.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["syntax", "semantic", "web"]);
+    assert!(features.iter().all(|c| c.compiled && c.available));
+    assert!(features[0].detail.contains("rust, python"));
+
+    let bus = crate::ToolBus::new(workspace.root());
+    let tools: Vec<String> = bus.list_tools().into_iter().map(|t| t.name).collect();
+    for tool in ["code_nav", "ast_search", "semantic_search", "web_fetch"] {
+        assert!(tools.iter().any(|t| t == tool), "{} not registered", tool);
+    }
+}
+
+#[test]
+fn test_missing_feature_and_program() {
+    let feature = Capability::feature("otel", false, "OTLP export");
+    assert!(!feature.available);
+    assert_eq!(feature.detail, "not in this build");
+
+    let program = Capability::program("locus-no-such-program", "nothing");
+    assert!(program.compiled && !program.available);
+    assert_eq!(program.detail, "nothing (not found on PATH)");
+    assert!(find_program("locus-no-such-program").is_none());
+}
+
+#[test]
+fn test_language_servers_are_checked() {
+    let workspace = TestWorkspace::new().with_file(
+        ".locus/lsp.toml",
+        "[[servers]]\nid = \"rust\"\ncommand = \"locus-missing-analyzer\"\nextensions = [\"rs\"]\n",
+    );
+    let report = capabilities(&workspace.root());
+    let server = report.last().unwrap();
+    assert_eq!(server.name, "locus-missing-analyzer");
+    assert_eq!(server.kind, CapabilityKind::Program);
+    assert!(!server.available);
+    assert!(server.detail.starts_with("rust language server"));
+}
@@ -1,4 +1,11 @@
 #[cfg(test)]
+mod features;
+// The golden workflow is shown with code_nav.
+#[cfg(all(test, feature = "syntax"))]
+mod testing;
+#[cfg(test)]
 mod tool_bus;
 #[cfg(test)]
+mod tool_search;
+#[cfg(test)]
 mod tools;
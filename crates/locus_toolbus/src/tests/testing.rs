@@ -0,0 +1,83 @@
+use crate::ToolBus;
+use crate::testing::{
+    TestWorkspace, assert_args_round_trip, assert_golden, assert_schema_valid, redact_keys,
+};
+use crate::tools::{AstSearch, AstSearchArgs, CodeNav, CodeNavArgs, TodoScan, TodoScanArgs, Tool};
+use serde_json::json;
+
+fn golden(name: &str) -> String {
+    format!("{}/src/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_builtin_tool_schemas_are_valid() {
+    let workspace = TestWorkspace::new();
+    let bus = ToolBus::new(workspace.root());
+    for tool in bus.tools.read().unwrap().values() {
+        assert_schema_valid(tool.as_ref());
+    }
+}
+
+#[test]
+fn test_args_round_trip() {
+    let root = TestWorkspace::new().root();
+    assert_args_round_trip::<CodeNavArgs>(
+        &CodeNav::new(root.clone()),
+        json!({"action": "references", "symbol": "parse", "path": "src", "max_results": 5}),
+    );
+    assert_args_round_trip::<AstSearchArgs>(
+        &AstSearch::new(root.clone()),
+        json!({"pattern": "$X.unwrap()", "language": "rust"}),
+    );
+    assert_args_round_trip::<TodoScanArgs>(
+        &TodoScan::new(root),
+        json!({"tags": ["TODO"], "sort": "oldest", "blame": false}),
+    );
+}
+
+#[test]
+#[should_panic(expected = "args key \"symbol_name\" is not in the schema")]
+fn test_args_round_trip_rejects_unknown_keys() {
+    assert_args_round_trip::<serde_json::Value>(
+        &CodeNav::new(TestWorkspace::new().root()),
+        json!({"action": "definition", "symbol_name": "parse"}),
+    );
+}
+
+#[tokio::test]
+async fn test_golden_code_nav_definition() {
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "src/lib.rs",
+            "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n",
+        )
+        .with_file("src/main.rs", "fn main() {\n    parse(\"x\");\n}\n");
+    let tool = CodeNav::new(workspace.root());
+
+    let mut result = tool
+        .execute(json!({"action": "definition", "symbol": "parse"}))
+        .await
+        .unwrap();
+    workspace.normalize(&mut result);
+    assert_golden(&result, golden("code_nav_definition.json"));
+}
+
+#[test]
+fn test_workspace_and_redaction_helpers() {
+    let workspace = TestWorkspace::new()
+        .with_file("a/b.txt", "hello")
+        .with_dir("empty");
+    assert_eq!(workspace.read("a/b.txt"), "hello");
+    assert!(workspace.exists("empty"));
+
+    let mut value = json!({
+        "path": format!("{}/a/b.txt", workspace.path().display()),
+        "runs": [{"duration_ms": 12, "ok": true}]
+    });
+    workspace.normalize(&mut value);
+    redact_keys(&mut value, &["duration_ms"]);
+    assert_eq!(
+        value,
+        json!({"path": "<root>/a/b.txt", "runs": [{"duration_ms": "[redacted]", "ok": true}]})
+    );
+}
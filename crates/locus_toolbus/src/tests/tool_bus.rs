@@ -1,4 +1,4 @@
-use crate::{Tool, ToolBus};
+use crate::{Tool, ToolBus, ToolContext, ToolInterrupted};
 use async_trait::async_trait;
 use serde_json::{Value as JsonValue, json};
 use std::path::PathBuf;
@@ -116,6 +116,43 @@ fn test_tool_bus_register_custom_tool() {
     });
 }
 
+#[test]
+fn test_tool_bus_add_and_remove_tool_on_shared_bus() {
+    struct LiveTool;
+
+    #[async_trait]
+    impl Tool for LiveTool {
+        fn name(&self) -> &'static str {
+            "mcp.live.ping"
+        }
+
+        fn description(&self) -> &'static str {
+            "Added after the bus was shared"
+        }
+
+        fn parameters_schema(&self) -> JsonValue {
+            json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: JsonValue) -> crate::ToolResult {
+            Ok(json!({"pong": true}))
+        }
+    }
+
+    let rt = runtime();
+    rt.block_on(async {
+        let bus = std::sync::Arc::new(ToolBus::new(temp_repo_root()));
+        bus.add_tool(std::sync::Arc::new(LiveTool));
+        let (result, _) = bus.call("mcp.live.ping", json!({})).await.unwrap();
+        assert_eq!(result["pong"], true);
+
+        assert!(bus.remove_tool("mcp.live.ping"));
+        assert!(!bus.remove_tool("mcp.live.ping"));
+        assert!(bus.permission_of("mcp.live.ping").is_none());
+        assert!(bus.call("mcp.live.ping", json!({})).await.is_err());
+    });
+}
+
 #[test]
 fn test_tool_bus_call_returns_duration() {
     let rt = runtime();
@@ -287,3 +324,731 @@ fn test_tool_bus_thread_safety() {
         assert_eq!(counter.load(Ordering::SeqCst), 5);
     });
 }
+
+#[test]
+fn test_tool_bus_policy_timeout() {
+    use crate::ToolPolicy;
+
+    let rt = runtime();
+    rt.block_on(async {
+        let mut bus = ToolBus::new(temp_repo_root());
+        bus.set_policy("bash", ToolPolicy::new().with_timeout_secs(1));
+
+        let result = bus
+            .call("bash", json!({"command": "sleep 5", "timeout": 30}))
+            .await;
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("timed out after 1s"));
+        assert!(matches!(
+            err.downcast_ref::<ToolInterrupted>(),
+            Some(ToolInterrupted::TimedOut {
+                timeout_secs: 1,
+                ..
+            })
+        ));
+    });
+}
+
+/// Waits until its call is cancelled, then records that it noticed.
+struct HangingTool {
+    noticed: std::sync::Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Tool for HangingTool {
+    fn name(&self) -> &'static str {
+        "hang"
+    }
+    fn description(&self) -> &'static str {
+        "Never finishes"
+    }
+    fn parameters_schema(&self) -> JsonValue {
+        json!({})
+    }
+    async fn execute(&self, _args: JsonValue) -> crate::ToolResult {
+        std::future::pending().await
+    }
+    async fn execute_with_context(&self, _args: JsonValue, ctx: ToolContext) -> crate::ToolResult {
+        ctx.cancel.cancelled().await;
+        self.noticed.fetch_add(1, Ordering::SeqCst);
+        Err(anyhow::anyhow!("stopped"))
+    }
+}
+
+#[test]
+fn test_tool_bus_timeout_cancels_tool() {
+    use crate::ToolPolicy;
+
+    let rt = runtime();
+    rt.block_on(async {
+        let noticed = std::sync::Arc::new(AtomicUsize::new(0));
+        let mut bus = ToolBus::new(temp_repo_root());
+        bus.register(HangingTool {
+            noticed: noticed.clone(),
+        });
+        bus.set_policy("hang", ToolPolicy::new().with_timeout_secs(1));
+
+        let err = bus.call("hang", json!({})).await.unwrap_err();
+        let interrupted = err.downcast_ref::<ToolInterrupted>().unwrap();
+        assert_eq!(
+            interrupted,
+            &ToolInterrupted::TimedOut {
+                tool: "hang".into(),
+                timeout_secs: 1
+            }
+        );
+        assert_eq!(interrupted.to_json()["timed_out"], true);
+        assert_eq!(noticed.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_caller_cancels_tool() {
+    let rt = runtime();
+    rt.block_on(async {
+        let noticed = std::sync::Arc::new(AtomicUsize::new(0));
+        let mut bus = ToolBus::new(temp_repo_root());
+        bus.register(HangingTool {
+            noticed: noticed.clone(),
+        });
+
+        let ctx = ToolContext::new();
+        let cancel = ctx.cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        let err = bus
+            .call_with_context("hang", json!({}), ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ToolInterrupted>(),
+            Some(ToolInterrupted::Cancelled { .. })
+        ));
+        assert_eq!(noticed.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_policy_limits_result() {
+    use crate::{ToolPolicies, ToolPolicy, TruncationStrategy};
+
+    let rt = runtime();
+    rt.block_on(async {
+        let policies = ToolPolicies::new().with_tool(
+            "bash",
+            ToolPolicy::new()
+                .with_max_result_bytes(2048)
+                .with_truncation(TruncationStrategy::Tail),
+        );
+        let bus = ToolBus::new(temp_repo_root()).with_policies(policies);
+
+        let (result, _) = bus
+            .call("bash", json!({"command": "seq 1 5000"}))
+            .await
+            .unwrap();
+
+        let stdout = result["stdout"].as_str().unwrap();
+        assert!(serde_json::to_string(&result).unwrap().len() <= 2048);
+        assert!(stdout.contains("[truncated"));
+        assert!(stdout.trim_end().ends_with("5000"));
+        assert_eq!(result["exit_code"], 0);
+    });
+}
+
+/// Approver that records how often it was asked and always gives `decision`.
+struct FixedApprover {
+    decision: crate::ApprovalDecision,
+    asked: AtomicUsize,
+}
+
+#[async_trait]
+impl crate::Approver for FixedApprover {
+    async fn approve(&self, request: crate::ApprovalRequest) -> crate::ApprovalResponse {
+        assert_eq!(request.permission, crate::Permission::Execute);
+        self.asked.fetch_add(1, Ordering::SeqCst);
+        crate::ApprovalResponse::new(request.id, self.decision)
+    }
+}
+
+/// Approver that keeps the requests it saw and denies them with a message.
+#[derive(Default)]
+struct ExplainingApprover {
+    requests: std::sync::Mutex<Vec<crate::ApprovalRequest>>,
+}
+
+#[async_trait]
+impl crate::Approver for ExplainingApprover {
+    async fn approve(&self, request: crate::ApprovalRequest) -> crate::ApprovalResponse {
+        let response = crate::ApprovalResponse::deny_with_message(&request.id, "keep it a minus");
+        self.requests.lock().unwrap().push(request);
+        response
+    }
+}
+
+fn approver(decision: crate::ApprovalDecision) -> std::sync::Arc<FixedApprover> {
+    std::sync::Arc::new(FixedApprover {
+        decision,
+        asked: AtomicUsize::new(0),
+    })
+}
+
+#[test]
+fn test_tool_bus_permission_deny_rule() {
+    use crate::{PermissionRule, ToolPolicy};
+
+    let rt = runtime();
+    rt.block_on(async {
+        let mut bus = ToolBus::new(temp_repo_root());
+        bus.set_policy(
+            "bash",
+            ToolPolicy::new().with_permission(PermissionRule::Deny),
+        );
+
+        let err = bus
+            .call("bash", json!({"command": "echo hi"}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("denied by tool policy"));
+
+        // Read tools are unaffected
+        assert!(bus.call("glob", json!({"pattern": "*.none"})).await.is_ok());
+    });
+}
+
+#[test]
+fn test_tool_bus_approver_deny() {
+    let rt = runtime();
+    rt.block_on(async {
+        let approver = approver(crate::ApprovalDecision::Deny);
+        let bus = ToolBus::new(temp_repo_root()).with_approver(approver.clone());
+
+        let err = bus
+            .call("bash", json!({"command": "echo hi"}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("denied by the user"));
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_write_approval_previews_the_change() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::write(root.join("lib.rs"), "a - b\n").unwrap();
+        let approver = std::sync::Arc::new(ExplainingApprover::default());
+        let bus = ToolBus::new(root.clone()).with_approver(approver.clone());
+
+        let err = bus
+            .call(
+                "edit_file",
+                json!({"path": "lib.rs", "old_string": "a - b", "new_string": "a + b"}),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("denied by the user"));
+        assert!(err.contains("keep it a minus"));
+        assert_eq!(
+            std::fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "a - b\n"
+        );
+
+        let requests = approver.requests.lock().unwrap();
+        let preview = requests[0].preview.as_ref().unwrap();
+        assert_eq!(requests[0].permission, crate::Permission::Write);
+        assert_eq!(preview.path, "lib.rs");
+        assert_eq!(preview.before, "a - b\n");
+        assert_eq!(preview.after, "a + b\n");
+    });
+}
+
+#[test]
+fn test_tool_bus_approver_allow_always() {
+    let rt = runtime();
+    rt.block_on(async {
+        let approver = approver(crate::ApprovalDecision::AllowAlways);
+        let bus = ToolBus::new(temp_repo_root());
+        bus.set_approver(approver.clone());
+
+        for _ in 0..3 {
+            let (result, _) = bus
+                .call("bash", json!({"command": "echo hi"}))
+                .await
+                .unwrap();
+            assert_eq!(result["exit_code"], 0);
+        }
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_command_rules() {
+    use crate::{CommandDenied, CommandRules, ToolPolicies};
+
+    let rt = runtime();
+    rt.block_on(async {
+        let approver = approver(crate::ApprovalDecision::Deny);
+        let policies = ToolPolicies {
+            commands: CommandRules::new().with_deny("echo forbidden*"),
+            ..ToolPolicies::default()
+        };
+        let bus = ToolBus::new(temp_repo_root())
+            .with_policies(policies)
+            .with_approver(approver.clone());
+
+        // Allowed by a built-in pattern: runs without asking.
+        let (result, _) = bus.call("bash", json!({"command": "pwd"})).await.unwrap();
+        assert_eq!(result["exit_code"], 0);
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 0);
+
+        // Denied before execution, with the matched rule attached.
+        let err = bus
+            .call("bash", json!({"command": "echo forbidden word"}))
+            .await
+            .unwrap_err();
+        let denied = err.downcast_ref::<CommandDenied>().unwrap();
+        assert_eq!(denied.pattern, "echo forbidden*");
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 0);
+
+        // Unmatched commands still go to the approver.
+        assert!(
+            bus.call("bash", json!({"command": "echo hi"}))
+                .await
+                .is_err()
+        );
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_protected_paths_from_policy_file() {
+    use crate::{ProtectedPathDenied, TOOL_POLICY_FILE};
+
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".locus")).unwrap();
+        std::fs::write(
+            dir.path().join(TOOL_POLICY_FILE),
+            "[protected_paths]\ndeny = [\"secrets/\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("secrets")).unwrap();
+        std::fs::write(dir.path().join("secrets/db.txt"), "password").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=x").unwrap();
+        let bus = ToolBus::new(dir.path().to_path_buf());
+
+        for path in ["secrets/db.txt", ".env"] {
+            let err = bus.call("read", json!({ "path": path })).await.unwrap_err();
+            assert!(err.downcast_ref::<ProtectedPathDenied>().is_some());
+        }
+    });
+}
+
+/// Resolver that keeps the requests it gets and always gives `decision`.
+struct FixedResolver {
+    decision: crate::ConflictDecision,
+    requests: std::sync::Mutex<Vec<crate::ConflictRequest>>,
+}
+
+#[async_trait]
+impl crate::ConflictResolver for FixedResolver {
+    async fn resolve(&self, request: crate::ConflictRequest) -> crate::ConflictDecision {
+        self.requests.lock().unwrap().push(request);
+        self.decision
+    }
+}
+
+const CONFLICT_BASE: &str = "one\ntwo\nthree\nfour\n";
+
+/// A repo with `notes.txt` read through the bus, then changed by the user.
+async fn bus_with_user_change(user: &str) -> (tempfile::TempDir, ToolBus) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.txt"), CONFLICT_BASE).unwrap();
+    let bus = ToolBus::new(dir.path().to_path_buf());
+    bus.call("read", json!({"path": "notes.txt"}))
+        .await
+        .unwrap();
+    std::fs::write(dir.path().join("notes.txt"), user).unwrap();
+    (dir, bus)
+}
+
+#[test]
+fn test_tool_bus_rebases_edit_onto_user_changes() {
+    let rt = runtime();
+    rt.block_on(async {
+        let (dir, bus) = bus_with_user_change("ONE\ntwo\nthree\nfour\n").await;
+
+        let (result, _) = bus
+            .call(
+                "edit_file",
+                json!({"path": "notes.txt", "old_string": "four", "new_string": "FOUR"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["conflict"]["resolution"], "rebase");
+        assert_eq!(result["conflict"]["conflicts"], 0);
+        let content = std::fs::read_to_string(dir.path().join("notes.txt")).unwrap();
+        assert_eq!(content, "ONE\ntwo\nthree\nFOUR\n");
+
+        // The written file is the new baseline: the next edit applies normally.
+        let (result, _) = bus
+            .call(
+                "edit_file",
+                json!({"path": "notes.txt", "old_string": "two", "new_string": "TWO"}),
+            )
+            .await
+            .unwrap();
+        assert!(result.get("conflict").is_none());
+    });
+}
+
+#[test]
+fn test_tool_bus_aborts_clashing_edit_without_resolver() {
+    let rt = runtime();
+    rt.block_on(async {
+        let user = "one\ntwo\nthree\nfour (user)\n";
+        let (dir, bus) = bus_with_user_change(user).await;
+
+        let err = bus
+            .call(
+                "create_file",
+                json!({"path": "notes.txt", "content": "one\ntwo\nthree\nfour (agent)\n"}),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("notes.txt was changed outside the agent")
+        );
+        let content = std::fs::read_to_string(dir.path().join("notes.txt")).unwrap();
+        assert_eq!(content, user);
+    });
+}
+
+#[test]
+fn test_tool_bus_conflict_resolver_decides() {
+    let rt = runtime();
+    rt.block_on(async {
+        let (dir, bus) = bus_with_user_change("one\ntwo\nthree\nfour (user)\n").await;
+        let resolver = std::sync::Arc::new(FixedResolver {
+            decision: crate::ConflictDecision::Overwrite,
+            requests: std::sync::Mutex::new(Vec::new()),
+        });
+        bus.set_conflict_resolver(resolver.clone());
+
+        let (result, _) = bus
+            .call(
+                "edit_file",
+                json!({"path": "notes.txt", "old_string": "four", "new_string": "four (agent)"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["conflict"]["resolution"], "overwrite");
+        let content = std::fs::read_to_string(dir.path().join("notes.txt")).unwrap();
+        assert_eq!(content, "one\ntwo\nthree\nfour (agent)\n");
+
+        let requests = resolver.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "notes.txt");
+        assert_eq!(requests[0].base, CONFLICT_BASE);
+        assert_eq!(requests[0].user, "one\ntwo\nthree\nfour (user)\n");
+        assert_eq!(requests[0].agent, "one\ntwo\nthree\nfour (agent)\n");
+        assert_eq!(requests[0].conflicts, 1);
+    });
+}
+
+#[test]
+fn test_tool_bus_commands_refresh_baselines() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), CONFLICT_BASE).unwrap();
+        let resolver = std::sync::Arc::new(FixedResolver {
+            decision: crate::ConflictDecision::Abort,
+            requests: std::sync::Mutex::new(Vec::new()),
+        });
+        let bus = ToolBus::new(dir.path().to_path_buf()).with_conflict_resolver(resolver.clone());
+        bus.call("read", json!({"path": "notes.txt"}))
+            .await
+            .unwrap();
+
+        // The agent's own command changes the file: not a user change.
+        bus.call("bash", json!({"command": "echo five >> notes.txt"}))
+            .await
+            .unwrap();
+        bus.call(
+            "edit_file",
+            json!({"path": "notes.txt", "old_string": "five", "new_string": "FIVE"}),
+        )
+        .await
+        .unwrap();
+        assert!(resolver.requests.lock().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_tool_bus_records_usage() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hello\n").unwrap();
+        let bus = ToolBus::new(dir.path().to_path_buf());
+
+        bus.call_with_context(
+            "read",
+            json!({"path": "notes.txt"}),
+            ToolContext::new().with_session("s1"),
+        )
+        .await
+        .unwrap();
+        assert!(
+            bus.call("read", json!({"path": "missing.txt"}))
+                .await
+                .is_err()
+        );
+        // Unknown tools never ran: not recorded.
+        assert!(bus.call("nope", json!({})).await.is_err());
+
+        // Records are written in the background.
+        let mut stats = Vec::new();
+        for _ in 0..100 {
+            stats = locus_core::db::tool_stats(dir.path(), 0).unwrap();
+            if stats.first().is_some_and(|s| s.calls == 2) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].tool, "read");
+        assert_eq!((stats[0].calls, stats[0].failures), (2, 1));
+        assert!(stats[0].avg_bytes > 0);
+    });
+}
+
+/// Cacheable tool returning the file at `path`, counting real runs.
+struct CachedReader {
+    repo_root: PathBuf,
+    runs: std::sync::Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Tool for CachedReader {
+    fn name(&self) -> &'static str {
+        "cached_read"
+    }
+    fn description(&self) -> &'static str {
+        "Reads a file"
+    }
+    fn parameters_schema(&self) -> JsonValue {
+        json!({})
+    }
+    fn cacheable(&self) -> bool {
+        true
+    }
+    async fn execute(&self, args: JsonValue) -> crate::ToolResult {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        let path = self.repo_root.join(args["path"].as_str().unwrap());
+        Ok(json!({"content": std::fs::read_to_string(path)?}))
+    }
+}
+
+#[test]
+fn test_tool_bus_caches_read_only_results() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, "one\n").unwrap();
+        let runs = std::sync::Arc::new(AtomicUsize::new(0));
+        let mut bus = ToolBus::new(dir.path().to_path_buf());
+        bus.register(CachedReader {
+            repo_root: dir.path().to_path_buf(),
+            runs: runs.clone(),
+        });
+        let read = || bus.call("cached_read", json!({"path": "notes.txt"}));
+
+        assert_eq!(read().await.unwrap().0["content"], "one\n");
+        assert_eq!(read().await.unwrap().0["content"], "one\n");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Changed outside the bus: the fingerprint no longer matches.
+        std::fs::write(&notes, "one\ntwo\n").unwrap();
+        assert_eq!(read().await.unwrap().0["content"], "one\ntwo\n");
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Commands through the bus drop cached results.
+        read().await.unwrap();
+        bus.call("bash", json!({"command": "true"})).await.unwrap();
+        read().await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[test]
+fn test_tool_bus_cache_policy_off() {
+    use crate::ToolPolicy;
+
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "one\n").unwrap();
+        let runs = std::sync::Arc::new(AtomicUsize::new(0));
+        let mut bus = ToolBus::new(dir.path().to_path_buf());
+        bus.set_policy("cached_read", ToolPolicy::new().with_cache(false));
+        bus.register(CachedReader {
+            repo_root: dir.path().to_path_buf(),
+            runs: runs.clone(),
+        });
+        for _ in 0..2 {
+            bus.call("cached_read", json!({"path": "notes.txt"}))
+                .await
+                .unwrap();
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    });
+}
+
+/// Fails with a dropped connection until it has run `failures` times.
+struct FlakyTool {
+    runs: std::sync::Arc<AtomicUsize>,
+    failures: usize,
+    permission: crate::Permission,
+}
+
+#[async_trait]
+impl Tool for FlakyTool {
+    fn name(&self) -> &'static str {
+        "flaky"
+    }
+    fn description(&self) -> &'static str {
+        "Fails, then works"
+    }
+    fn parameters_schema(&self) -> JsonValue {
+        json!({})
+    }
+    fn permission(&self) -> crate::Permission {
+        self.permission
+    }
+    async fn execute(&self, _args: JsonValue) -> crate::ToolResult {
+        if self.runs.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Err(std::io::Error::from(std::io::ErrorKind::ConnectionReset).into());
+        }
+        Ok(json!({"ok": true}))
+    }
+}
+
+#[test]
+fn test_tool_bus_retries_transient_failures() {
+    use crate::{FailureKind, Permission, ToolPolicy, recovery};
+
+    let rt = runtime();
+    rt.block_on(async {
+        let flaky = |failures, permission| {
+            let runs = std::sync::Arc::new(AtomicUsize::new(0));
+            let mut bus = ToolBus::new(temp_repo_root());
+            bus.set_policy("flaky", ToolPolicy::new().with_retries(1));
+            bus.register(FlakyTool {
+                runs: runs.clone(),
+                failures,
+                permission,
+            });
+            (bus, runs)
+        };
+
+        let (bus, runs) = flaky(1, Permission::Read);
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let ctx = ToolContext::new().with_progress(crate::ProgressSink::new(progress_tx));
+        let (result, _) = bus
+            .call_with_context("flaky", json!({}), ctx)
+            .await
+            .unwrap();
+        assert_eq!(result["ok"], true);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(
+            progress_rx
+                .recv()
+                .await
+                .unwrap()
+                .contains("retrying (1 of 1)")
+        );
+
+        // Out of retries: the model is told the failure looks temporary.
+        let (bus, runs) = flaky(2, Permission::Read);
+        let err = bus.call("flaky", json!({})).await.unwrap_err();
+        assert_eq!(recovery::classify(&err), FailureKind::Transient);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        let output = bus.explain_failure("flaky", &json!({}), &err);
+        assert_eq!(output["transient"], true);
+        assert!(output["hint"].as_str().unwrap().contains("after 1 retries"));
+
+        // Calls that may have side effects are never repeated.
+        for permission in [Permission::Network, Permission::Execute] {
+            let (bus, runs) = flaky(1, permission);
+            let err = bus.call("flaky", json!({})).await.unwrap_err();
+            assert_eq!(runs.load(Ordering::SeqCst), 1);
+            let output = bus.explain_failure("flaky", &json!({}), &err);
+            assert!(output["hint"].as_str().unwrap().contains("not retried"));
+        }
+    });
+}
+
+#[test]
+fn test_tool_bus_explains_failed_edits() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/billing")).unwrap();
+        std::fs::write(
+            dir.path().join("src/billing/invoice.rs"),
+            "fn total(items: &[Item]) -> u64 {\n    items.iter().map(|i| i.price).sum()\n}\n\nfn tax() {}\nfn tax() {}\n",
+        )
+        .unwrap();
+        let bus = ToolBus::new(dir.path().to_path_buf());
+        let explain = async |args: JsonValue| {
+            let err = bus.call("edit_file", args.clone()).await.unwrap_err();
+            bus.explain_failure("edit_file", &args, &err)
+        };
+
+        let output = explain(json!({
+            "path": "src/billing/invoice.rs",
+            "old_string": "items.iter().map(|item| item.price).sum()",
+            "new_string": "items.iter().map(Item::price).sum()",
+        }))
+        .await;
+        assert_eq!(output["error"], "Old string not found in file");
+        assert_eq!(output["closest_match"]["line"], 2);
+        assert_eq!(
+            output["closest_match"]["text"],
+            "    items.iter().map(|i| i.price).sum()"
+        );
+
+        let output = explain(json!({
+            "path": "src/billing/invoice.rs",
+            "old_string": "fn tax() {}",
+            "new_string": "fn tax() -> u64 { 0 }",
+        }))
+        .await;
+        assert_eq!(output["match_lines"], json!([5, 6]));
+
+        let output = explain(json!({
+            "path": "src/invoice.rs",
+            "old_string": "fn tax() {}",
+            "new_string": "",
+        }))
+        .await;
+        assert_eq!(output["similar_paths"], json!(["src/billing/invoice.rs"]));
+
+        let err = bus.call("edit", json!({})).await.unwrap_err();
+        let output = bus.explain_failure("edit", &json!({}), &err);
+        assert_eq!(output["similar_tools"][0], "edit_file");
+    });
+}
@@ -0,0 +1,182 @@
+use crate::{Tool, ToolBus, ToolInfo, ToolSearch};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+
+fn info(name: &str, description: &str) -> ToolInfo {
+    ToolInfo {
+        name: name.to_string(),
+        description: description.to_string(),
+        parameters: json!({"type": "object", "properties": {}}),
+    }
+}
+
+/// A few real-looking tools among a hundred MCP tools that match nothing.
+fn catalog() -> Vec<ToolInfo> {
+    let mut tools = vec![
+        info(
+            "mcp.github.create_pull_request",
+            "Create a new pull request in a GitHub repository. Needs head and base branches.",
+        ),
+        info(
+            "mcp.github.create_issue",
+            "Open an issue in a GitHub repository",
+        ),
+        info(
+            "mcp.slack.post_message",
+            "Post a message to a Slack channel",
+        ),
+        info("git_commit", "Commit staged changes with a message"),
+        info("read", "Read a file from the repository"),
+    ];
+    for i in 0..100 {
+        tools.push(info(
+            &format!("mcp.filler.tool_{}", i),
+            &format!("Placeholder endpoint number {} for padding", i),
+        ));
+    }
+    tools
+}
+
+fn names(matches: &[crate::ToolMatch]) -> Vec<&str> {
+    matches.iter().map(|m| m.name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_fuzzy_ranks_the_intended_tool_first() {
+    let search = ToolSearch::fuzzy();
+    let tools = catalog();
+
+    let matches = search
+        .search(&tools, "create a GitHub pull request", 5)
+        .await;
+    assert_eq!(matches[0].name, "mcp.github.create_pull_request");
+    assert_eq!(matches[1].name, "mcp.github.create_issue");
+    assert!(matches[0].score > matches[1].score);
+    assert_eq!(
+        matches[0].summary,
+        "Create a new pull request in a GitHub repository."
+    );
+
+    // Typos and partial words still find it.
+    let matches = search.search(&tools, "pul requst", 5).await;
+    assert_eq!(matches[0].name, "mcp.github.create_pull_request");
+    let matches = search.search(&tools, "slack msg post", 5).await;
+    assert_eq!(matches[0].name, "mcp.slack.post_message");
+
+    // A query naming the tool outright ranks it first.
+    let matches = search.search(&tools, "git_commit", 5).await;
+    assert_eq!(matches[0].name, "git_commit");
+    assert_eq!(matches[0].score, 1.0);
+
+    assert!(search.search(&tools, "kubernetes", 5).await.is_empty());
+    assert_eq!(search.search(&tools, "repository", 2).await.len(), 2);
+}
+
+#[cfg(feature = "semantic")]
+mod semantic {
+    use super::*;
+    use crate::semantic::{Embedder, SemanticError, SemanticResult};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Puts texts about shipping code on one axis and everything else on the other.
+    #[derive(Default)]
+    struct TopicEmbedder {
+        embedded: AtomicUsize,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl Embedder for TopicEmbedder {
+        fn id(&self) -> String {
+            "topic".to_string()
+        }
+
+        async fn embed(&self, texts: &[String]) -> SemanticResult<Vec<Vec<f32>>> {
+            if self.fail {
+                return Err(SemanticError::Embedding("offline".to_string()));
+            }
+            self.embedded.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    if ["deploy", "ship", "release"]
+                        .iter()
+                        .any(|w| text.contains(w))
+                    {
+                        vec![1.0, 0.0]
+                    } else {
+                        vec![0.0, 1.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_find_tools_without_shared_words() {
+        let embedder = Arc::new(TopicEmbedder::default());
+        let search = ToolSearch::with_embedder(embedder.clone());
+        let mut tools = catalog();
+        tools.push(info(
+            "mcp.ci.deploy",
+            "Roll the current build out to production",
+        ));
+
+        let matches = search.search(&tools, "ship it", 3).await;
+        assert_eq!(names(&matches), ["mcp.ci.deploy"]);
+        assert_eq!(embedder.embedded.load(Ordering::SeqCst), tools.len() + 1);
+
+        // Tool vectors are cached; only the query is embedded again...
+        search.search(&tools, "ship it", 3).await;
+        assert_eq!(embedder.embedded.load(Ordering::SeqCst), tools.len() + 2);
+        // ...until a description changes.
+        tools[0].description = "Open a pull request".to_string();
+        search.search(&tools, "ship it", 3).await;
+        assert_eq!(embedder.embedded.load(Ordering::SeqCst), tools.len() + 4);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_failure_falls_back_to_fuzzy() {
+        let search = ToolSearch::with_embedder(Arc::new(TopicEmbedder {
+            fail: true,
+            ..Default::default()
+        }));
+        let matches = search.search(&catalog(), "open an issue", 1).await;
+        assert_eq!(names(&matches), ["mcp.github.create_issue"]);
+    }
+}
+
+#[tokio::test]
+async fn test_bus_search_includes_tools_added_later() {
+    struct LiveTool;
+
+    #[async_trait]
+    impl Tool for LiveTool {
+        fn name(&self) -> &'static str {
+            "mcp.jira.transition_ticket"
+        }
+
+        fn description(&self) -> &'static str {
+            "Move a Jira ticket to another workflow state"
+        }
+
+        fn parameters_schema(&self) -> JsonValue {
+            json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: JsonValue) -> crate::ToolResult {
+            Ok(json!({}))
+        }
+    }
+
+    let bus = ToolBus::new(PathBuf::from("/tmp"));
+    let before = bus.search_tools("move jira ticket", 5).await;
+    assert!(!names(&before).contains(&"mcp.jira.transition_ticket"));
+
+    bus.add_tool(std::sync::Arc::new(LiveTool));
+    let after = bus.search_tools("move jira ticket", 5).await;
+    assert_eq!(after[0].name, "mcp.jira.transition_ticket");
+}
@@ -0,0 +1,169 @@
+use crate::tools::{AstSearch, Tool};
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_test_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(
+        dir.path().join("src/lib.rs"),
+        r#"pub fn load(path: &str) -> Result<String> {
+    let text = std::fs::read_to_string(path).unwrap();
+    Ok(text)
+}
+
+async fn save(path: &str, data: &[u8]) -> Result<()> {
+    // a.unwrap() in a comment is not code
+    write(path, data).unwrap();
+    Ok(())
+}
+
+fn count(items: &[u8]) -> usize {
+    items.len()
+}
+
+fn same(a: u32) -> u32 {
+    a + a
+}
+
+fn different(a: u32, b: u32) -> u32 {
+    a + b
+}
+"#,
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("web")).unwrap();
+    fs::write(
+        dir.path().join("web/app.js"),
+        "console.log('start');\nconsole.log(user, id, 42);\nconsole.error('boom');\n",
+    )
+    .unwrap();
+
+    dir
+}
+
+fn matches(result: &Value) -> &Vec<Value> {
+    result["matches"].as_array().unwrap()
+}
+
+#[test]
+fn test_ast_search_tool_name() {
+    let tool = AstSearch::new(".".into());
+    assert_eq!(tool.name(), "ast_search");
+    assert!(tool.description().contains("$$$NAME"));
+}
+
+#[tokio::test]
+async fn test_ast_search_function_signature_without_body() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({
+            "pattern": "fn $NAME($$$ARGS) -> Result<$T>",
+            "language": "rust"
+        }))
+        .await
+        .unwrap();
+    let found = matches(&result);
+    assert_eq!(found.len(), 2);
+    // `pub` and `async` are ignored because the pattern leaves them out.
+    assert_eq!(found[0]["captures"]["NAME"], "load");
+    assert_eq!(found[0]["captures"]["ARGS"], "path: &str");
+    assert_eq!(found[0]["captures"]["T"], "String");
+    assert_eq!(found[0]["line"], 1);
+    assert_eq!(found[0]["end_line"], 4);
+    assert_eq!(found[1]["captures"]["NAME"], "save");
+    assert_eq!(found[1]["captures"]["ARGS"], "path: &str, data: &[u8]");
+    assert_eq!(found[1]["captures"]["T"], "()");
+    assert_eq!(result["languages"], json!(["rust"]));
+}
+
+#[tokio::test]
+async fn test_ast_search_method_calls_skip_comments() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"pattern": "$X.unwrap()", "path": "src"}))
+        .await
+        .unwrap();
+    let found = matches(&result);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0]["captures"]["X"], "std::fs::read_to_string(path)");
+    assert_eq!(found[1]["captures"]["X"], "write(path, data)");
+    assert_eq!(found[1]["line"], 8);
+}
+
+#[tokio::test]
+async fn test_ast_search_repeated_metavariable_must_match_same_text() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"pattern": "$A + $A", "language": "rust"}))
+        .await
+        .unwrap();
+    let found = matches(&result);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0]["line"], 17);
+}
+
+#[tokio::test]
+async fn test_ast_search_variadic_arguments_in_javascript() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"pattern": "console.log($$$ARGS)", "language": "javascript"}))
+        .await
+        .unwrap();
+    let found = matches(&result);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0]["captures"]["ARGS"], "'start'");
+    assert_eq!(found[1]["captures"]["ARGS"], "user, id, 42");
+    assert_eq!(found[1]["path"], "web/app.js");
+}
+
+#[tokio::test]
+async fn test_ast_search_truncates_to_max_results() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"pattern": "fn $F($$$A) -> $R", "language": "rust", "max_results": 2}))
+        .await
+        .unwrap();
+    assert_eq!(matches(&result).len(), 2);
+    assert_eq!(result["total"], 5);
+    assert_eq!(result["truncated"], true);
+}
+
+#[tokio::test]
+async fn test_ast_search_rejects_bad_input() {
+    let dir = create_test_repo();
+    let tool = AstSearch::new(dir.path().to_path_buf());
+
+    let err = tool.execute(json!({"pattern": " "})).await.unwrap_err();
+    assert!(err.to_string().contains("Empty pattern"));
+
+    let err = tool
+        .execute(json!({"pattern": "$X", "language": "rust"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("only a metavariable"));
+
+    let err = tool
+        .execute(json!({"pattern": "foo()", "language": "cobol"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unsupported language"));
+
+    let err = tool
+        .execute(json!({"pattern": "fn (((", "language": "rust"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not parse as rust"));
+}
@@ -1,3 +1,4 @@
+use crate::execution::{DevcontainerBackend, DockerBackend, ExecutionBackend};
 use crate::tools::{Bash, BashArgs, BashExecutor, Tool, ToolOutput};
 use serde_json::json;
 
@@ -156,6 +157,40 @@ fn test_executor_with_working_dir() {
     });
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_cancel_kills_the_command_and_its_children() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let rt = runtime();
+    rt.block_on(async {
+        let executor = BashExecutor::new().with_working_dir(dir.path().to_string_lossy());
+        let args = BashArgs {
+            command: "sleep 30 & echo $! > child.pid; wait".to_string(),
+            timeout: 60,
+            working_dir: None,
+        };
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = executor.run_with_cancel(&args, &cancel).await;
+        assert!(matches!(result, Err(crate::tools::BashError::Cancelled)));
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+        let pid = std::fs::read_to_string(dir.path().join("child.pid")).unwrap();
+        // The background sleep went down with the shell (gone, or a zombie
+        // waiting to be reaped).
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()));
+        let alive = stat.is_ok_and(|stat| !stat.contains(") Z "));
+        assert!(!alive, "child {} still running", pid.trim());
+    });
+}
+
 #[test]
 fn test_bash_default() {
     let bash = Bash::default();
@@ -176,3 +211,114 @@ fn test_parameters_schema() {
             .contains(&json!("command"))
     );
 }
+
+/// A stand-in `docker` that prints its arguments, one per line.
+#[cfg(unix)]
+fn fake_docker(dir: &std::path::Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let path = dir.join("fake-docker");
+    std::fs::write(&path, "#!/bin/sh\nprintf '%s\\n' \"$@\"\n").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_docker_backend_runs_in_container() {
+    let repo = tempfile::TempDir::new().unwrap();
+    let bin = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(repo.path().join("sub")).unwrap();
+    let backend = ExecutionBackend::Docker(
+        DockerBackend::new("rust:1.85")
+            .with_docker(fake_docker(bin.path()))
+            .with_user("1000:1000"),
+    );
+    let bash = Bash::new()
+        .with_working_dir(repo.path().to_string_lossy())
+        .with_backend(backend);
+
+    let rt = runtime();
+    let result = rt
+        .block_on(bash.execute(json!({
+            "command": "cargo test",
+            "working_dir": repo.path().join("sub").to_string_lossy()
+        })))
+        .unwrap();
+    let args: Vec<&str> = result["stdout"].as_str().unwrap().lines().collect();
+    let mount = format!("{}:/workspace", repo.path().display());
+    assert_eq!(&args[..2], ["run", "--rm"]);
+    assert!(args.contains(&mount.as_str()));
+    assert!(args.windows(2).any(|w| w == ["-w", "/workspace/sub"]));
+    assert!(args.windows(2).any(|w| w == ["--network", "none"]));
+    assert_eq!(
+        &args[args.len() - 4..],
+        ["rust:1.85", "/bin/sh", "-c", "cargo test"]
+    );
+
+    let err = rt
+        .block_on(bash.execute(json!({ "command": "ls", "working_dir": "/etc" })))
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("outside the repository"),
+        "got: {}",
+        err
+    );
+}
+
+/// A stand-in `devcontainer` whose `up` reports a running container, like the real CLI.
+#[cfg(unix)]
+fn fake_devcontainer(dir: &std::path::Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let path = dir.join("fake-devcontainer");
+    std::fs::write(
+        &path,
+        concat!(
+            "#!/bin/sh\n",
+            "echo 'starting container' >&2\n",
+            r#"echo '{"outcome":"success","containerId":"c0ffee","remoteUser":"vscode","remoteWorkspaceFolder":"/workspaces/repo"}'"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_devcontainer_backend_execs_in_workspace_folder() {
+    let repo = tempfile::TempDir::new().unwrap();
+    let bin = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(repo.path().join("sub")).unwrap();
+    let backend = ExecutionBackend::Devcontainer(
+        DevcontainerBackend::new()
+            .with_devcontainer(fake_devcontainer(bin.path()))
+            .with_docker(fake_docker(bin.path())),
+    );
+    let bash = Bash::new()
+        .with_working_dir(repo.path().to_string_lossy())
+        .with_backend(backend);
+
+    let rt = runtime();
+    let result = rt
+        .block_on(bash.execute(json!({
+            "command": "cargo fmt --check",
+            "working_dir": repo.path().join("sub").to_string_lossy()
+        })))
+        .unwrap();
+    let args: Vec<&str> = result["stdout"].as_str().unwrap().lines().collect();
+    assert_eq!(
+        args,
+        [
+            "exec",
+            "-u",
+            "vscode",
+            "-w",
+            "/workspaces/repo/sub",
+            "c0ffee",
+            "/bin/sh",
+            "-c",
+            "cargo fmt --check"
+        ]
+    );
+}
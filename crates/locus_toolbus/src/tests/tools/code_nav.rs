@@ -0,0 +1,144 @@
+use crate::tools::{CodeNav, Tool};
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_test_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(
+        dir.path().join("src/store.rs"),
+        "pub struct Store {\n    data: Vec<String>,\n}\n\nimpl Store {\n    pub fn get(&self, i: usize) -> &str {\n        &self.data[i]\n    }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("src/main.rs"),
+        "use crate::store::Store;\n\n// Store is documented here, not used.\nfn main() {\n    let s = Store { data: vec![] };\n    let msg = \"Store\";\n    println!(\"{}\", s.get(0));\n    let getter = 1;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        "class Store:\n    def get(self, key):\n        return key\n\nstore = Store()\nstore.get(1)\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("notes.md"), "Store get Store\n").unwrap();
+
+    dir
+}
+
+fn locations(result: &Value) -> Vec<(String, u64)> {
+    result["locations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| {
+            (
+                l["path"].as_str().unwrap().to_string(),
+                l["line"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_code_nav_tool_name() {
+    let tool = CodeNav::new(".".into());
+    assert_eq!(tool.name(), "code_nav");
+    assert!(tool.description().contains("tree-sitter"));
+}
+
+#[tokio::test]
+async fn test_code_nav_finds_definitions_across_languages() {
+    let dir = create_test_repo();
+    let tool = CodeNav::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"action": "definition", "symbol": "Store"}))
+        .await
+        .unwrap();
+    assert_eq!(
+        locations(&result),
+        vec![("app.py".to_string(), 1), ("src/store.rs".to_string(), 1)]
+    );
+    assert_eq!(result["locations"][0]["kind"], "class");
+    assert_eq!(result["locations"][1]["kind"], "struct");
+    assert_eq!(result["locations"][1]["text"], "pub struct Store {");
+    assert_eq!(result["files_parsed"], 3);
+}
+
+#[tokio::test]
+async fn test_code_nav_qualified_symbol_resolves_method_with_scope() {
+    let dir = create_test_repo();
+    let tool = CodeNav::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"action": "definition", "symbol": "Store::get", "path": "src"}))
+        .await
+        .unwrap();
+    assert_eq!(result["symbol"], "get");
+    assert_eq!(locations(&result), vec![("src/store.rs".to_string(), 6)]);
+    assert_eq!(result["locations"][0]["kind"], "function");
+    assert_eq!(result["locations"][0]["scope"], "Store");
+    assert_eq!(result["locations"][0]["column"], 12);
+}
+
+#[tokio::test]
+async fn test_code_nav_references_skip_comments_strings_and_definitions() {
+    let dir = create_test_repo();
+    let tool = CodeNav::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"action": "references", "symbol": "Store"}))
+        .await
+        .unwrap();
+    assert_eq!(
+        locations(&result),
+        vec![
+            ("app.py".to_string(), 5),
+            ("src/main.rs".to_string(), 1),
+            ("src/main.rs".to_string(), 5),
+            ("src/store.rs".to_string(), 5),
+        ]
+    );
+    assert_eq!(result["locations"][2]["scope"], "main");
+
+    // Field and method references, but not `getter`.
+    let result = tool
+        .execute(json!({"action": "references", "symbol": "get", "path": "src/main.rs"}))
+        .await
+        .unwrap();
+    assert_eq!(locations(&result), vec![("src/main.rs".to_string(), 7)]);
+}
+
+#[tokio::test]
+async fn test_code_nav_truncates_to_max_results() {
+    let dir = create_test_repo();
+    let tool = CodeNav::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({"action": "references", "symbol": "Store", "max_results": 1}))
+        .await
+        .unwrap();
+    assert_eq!(result["locations"].as_array().unwrap().len(), 1);
+    assert_eq!(result["total"], 4);
+    assert_eq!(result["truncated"], true);
+}
+
+#[tokio::test]
+async fn test_code_nav_rejects_empty_symbol_and_outside_paths() {
+    let dir = create_test_repo();
+    let tool = CodeNav::new(dir.path().to_path_buf());
+
+    let err = tool
+        .execute(json!({"action": "definition", "symbol": "  "}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Empty symbol"));
+
+    let err = tool
+        .execute(json!({"action": "definition", "symbol": "Store", "path": "../"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("outside repository"));
+}
@@ -0,0 +1,205 @@
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::deps::parse::{
+    parse_cargo_audit, parse_cargo_outdated, parse_cargo_tree, parse_npm_audit, parse_npm_outdated,
+    parse_npm_tree, parse_pip_audit, parse_pip_outdated, parse_pip_tree,
+};
+use crate::tools::{Deps, DepsAction, DepsArgs, Ecosystem, Tool, VulnSeverity};
+use serde_json::json;
+
+const CARGO_AUDIT: &str = r#"{"database":{"advisory-count":600},"lockfile":{"dependency-count":120},
+"vulnerabilities":{"found":true,"count":1,"list":[{"advisory":{"id":"RUSTSEC-2023-0071","package":"rsa",
+"title":"Marvin Attack: potential key recovery through timing sidechannels","aliases":["CVE-2023-49092"],
+"url":"https://github.com/RustCrypto/RSA/issues/19","cvss":"CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N"},
+"versions":{"patched":[],"unaffected":[]},"package":{"name":"rsa","version":"0.9.6"}}]},"warnings":{}}"#;
+
+const NPM_AUDIT: &str = r#"{"auditReportVersion":2,"vulnerabilities":{
+"lodash":{"name":"lodash","severity":"high","via":[
+  {"source":1096305,"name":"lodash","title":"Prototype Pollution in lodash","url":"https://github.com/advisories/GHSA-jf85-cpcp-j695","severity":"critical","range":"<4.17.12"},
+  {"source":1096305,"name":"lodash","title":"Prototype Pollution in lodash","url":"https://github.com/advisories/GHSA-jf85-cpcp-j695","severity":"critical","range":"<4.17.12"},
+  {"source":1094499,"name":"lodash","title":"Regular Expression Denial of Service","url":"https://example.com/1094499","severity":"moderate","range":"<4.17.21"}],
+  "fixAvailable":{"name":"lodash","version":"4.17.21","isSemVerMajor":false}},
+"lodash-wrapper":{"name":"lodash-wrapper","severity":"high","via":["lodash"],"fixAvailable":true}},
+"metadata":{"vulnerabilities":{"total":2}}}"#;
+
+#[test]
+fn test_deps_tool_name_and_args() {
+    let tool = Deps::new(".".into());
+    assert_eq!(tool.name(), "deps");
+    assert!(tool.description().contains("vulnerabilities"));
+    assert_args_round_trip::<DepsArgs>(
+        &tool,
+        json!({ "action": "tree", "ecosystem": "npm", "path": "web", "depth": 2, "max_results": 50 }),
+    );
+    let args = DepsArgs::new(DepsAction::Audit);
+    assert_eq!((args.depth, args.max_results), (1, 200));
+}
+
+#[test]
+fn test_parse_audits() {
+    let cargo = parse_cargo_audit(CARGO_AUDIT).unwrap();
+    assert_eq!(cargo.len(), 1);
+    assert_eq!(cargo[0].package, "rsa");
+    assert_eq!(cargo[0].version.as_deref(), Some("0.9.6"));
+    assert_eq!(cargo[0].id, "RUSTSEC-2023-0071");
+    assert_eq!(cargo[0].aliases, ["CVE-2023-49092"]);
+    assert_eq!(cargo[0].severity, VulnSeverity::Unknown);
+    assert!(cargo[0].fixed_versions.is_empty());
+
+    let npm = parse_npm_audit(NPM_AUDIT).unwrap();
+    assert_eq!(npm.len(), 2, "duplicate advisories and string vias dropped");
+    assert_eq!(npm[0].id, "GHSA-jf85-cpcp-j695");
+    assert_eq!(npm[0].severity, VulnSeverity::Critical);
+    assert_eq!(npm[0].fixed_versions, ["lodash@4.17.21"]);
+    assert_eq!(npm[1].id, "1094499");
+    assert_eq!(npm[1].severity, VulnSeverity::Medium);
+
+    let pip = r#"{"dependencies":[{"name":"flask","version":"0.5","vulns":[{"id":"PYSEC-2019-179",
+        "fix_versions":["1.0"],"aliases":["CVE-2019-1010083"],"description":"The Pallets Project Flask before 1.0 is affected by: unexpected memory usage.\nMore text."}]},
+        {"name":"requests","version":"2.31.0","vulns":[]}],"fixes":[]}"#;
+    let pip = parse_pip_audit(pip).unwrap();
+    assert_eq!(pip.len(), 1);
+    assert_eq!(pip[0].package, "flask");
+    assert_eq!(pip[0].fixed_versions, ["1.0"]);
+    assert_eq!(
+        pip[0].title.as_deref(),
+        Some("The Pallets Project Flask before 1.0 is affected by: unexpected memory usage.")
+    );
+
+    assert!(parse_cargo_audit("error: couldn't fetch advisory database").is_err());
+    assert!(parse_npm_audit("{}").is_err());
+}
+
+#[test]
+fn test_parse_outdated() {
+    let cargo = r#"{"crate_name":"a","dependencies":[{"name":"serde","project":"1.0.100","compat":"1.0.200","latest":"1.0.200","kind":"Normal","platform":null},{"name":"rand","project":"0.7.3","compat":"---","latest":"0.8.5","kind":"Normal","platform":null}]}
+{"crate_name":"b","dependencies":[{"name":"rand","project":"0.7.3","compat":"---","latest":"0.8.5","kind":"Normal","platform":null}]}"#;
+    let cargo = parse_cargo_outdated(cargo).unwrap();
+    assert_eq!(cargo.len(), 2, "shared dependencies listed once");
+    assert_eq!(cargo[1].name, "rand");
+    assert_eq!(cargo[1].wanted, None);
+    assert_eq!(cargo[1].latest, "0.8.5");
+    assert!(parse_cargo_outdated("").is_err());
+
+    let npm = r#"{"react":{"current":"17.0.2","wanted":"17.0.2","latest":"18.3.1","location":"node_modules/react"},
+        "typescript":[{"current":"4.9.5","wanted":"4.9.5","latest":"5.6.3"},{"current":"4.8.0","wanted":"4.9.5","latest":"5.6.3"}]}"#;
+    let npm = parse_npm_outdated(npm).unwrap();
+    assert_eq!(npm.len(), 2);
+    assert_eq!(npm[1].current.as_deref(), Some("4.9.5"));
+    assert!(parse_npm_outdated("").unwrap().is_empty());
+
+    let pip = r#"[{"name":"requests","version":"2.25.0","latest_version":"2.32.3","latest_filetype":"wheel"}]"#;
+    let pip = parse_pip_outdated(pip).unwrap();
+    assert_eq!(
+        (pip[0].name.as_str(), pip[0].latest.as_str()),
+        ("requests", "2.32.3")
+    );
+}
+
+#[test]
+fn test_parse_trees() {
+    let cargo = "0demo v0.1.0 (/repo)\n1serde v1.0.200\n2serde_derive v1.0.200 (proc-macro)\n1rand v0.8.5 (*)\n\n0other v0.1.0 (/repo/other)\n";
+    let cargo = parse_cargo_tree(cargo).unwrap();
+    assert_eq!(cargo.len(), 5);
+    assert_eq!(cargo[2].name, "serde_derive");
+    assert_eq!(cargo[2].version.as_deref(), Some("1.0.200"));
+    assert_eq!(cargo[2].depth, 2);
+    assert!(parse_cargo_tree("error: could not find Cargo.toml").is_err());
+
+    let npm = r#"{"name":"web","version":"1.0.0","dependencies":{"react":{"version":"18.3.1","dependencies":{"loose-envify":{"version":"1.4.0"}}}}}"#;
+    let npm = parse_npm_tree(npm).unwrap();
+    assert_eq!(
+        npm.iter()
+            .map(|n| (n.name.as_str(), n.depth))
+            .collect::<Vec<_>>(),
+        [("web", 0), ("react", 1), ("loose-envify", 2)]
+    );
+
+    let pip = r#"[{"key":"flask","package_name":"Flask","installed_version":"3.0.0","dependencies":[
+        {"key":"click","package_name":"click","installed_version":"8.1.7","dependencies":[{"key":"colorama","package_name":"colorama","installed_version":"0.4.6","dependencies":[]}]}]}]"#;
+    let pip = parse_pip_tree(pip, 2).unwrap();
+    assert_eq!(pip.len(), 2, "cut at depth 2");
+    assert_eq!((pip[1].name.as_str(), pip[1].depth), ("click", 2));
+}
+
+#[test]
+fn test_deps_detects_and_builds_commands() {
+    let workspace = TestWorkspace::new()
+        .with_file("Cargo.toml", "[package]\nname = \"demo\"\n")
+        .with_file("web/package.json", "{}")
+        .with_file("api/requirements.txt", "flask==0.5\n");
+    let root = workspace.root();
+    assert_eq!(Deps::detect(&root), [Ecosystem::Cargo]);
+    assert_eq!(Deps::detect(&root.join("web")), [Ecosystem::Npm]);
+    assert_eq!(Deps::detect(&root.join("api")), [Ecosystem::Pip]);
+
+    assert_eq!(
+        Deps::command(Ecosystem::Cargo, DepsAction::Tree, 2, &root),
+        "cargo tree --workspace --prefix depth --depth 2"
+    );
+    assert_eq!(
+        Deps::command(Ecosystem::Npm, DepsAction::Tree, 1, &root),
+        "npm ls --json --depth 0"
+    );
+    assert_eq!(
+        Deps::command(Ecosystem::Pip, DepsAction::Audit, 1, &root.join("api")),
+        "pip-audit -r requirements.txt -f json"
+    );
+    assert_eq!(
+        Deps::command(Ecosystem::Pip, DepsAction::Audit, 1, &root),
+        "pip-audit -f json"
+    );
+}
+
+#[tokio::test]
+async fn test_deps_argument_errors() {
+    let workspace = TestWorkspace::new().with_file("notes.txt", "hi\n");
+    let tool = Deps::new(workspace.root());
+
+    let err = tool
+        .execute(json!({ "action": "audit" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("No dependency manifest"), "got: {}", err);
+
+    let err = tool
+        .execute(json!({ "action": "tree", "path": "missing" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("does not exist"), "got: {}", err);
+
+    assert!(tool.execute(json!({ "action": "upgrade" })).await.is_err());
+}
+
+#[tokio::test]
+async fn test_deps_runs_cargo_tree() {
+    if crate::features::find_program("cargo").is_none() {
+        eprintln!("cargo not available; skipping");
+        return;
+    }
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "Cargo.toml",
+            "[package]\nname = \"deps-demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nhelper = { path = \"helper\" }\n",
+        )
+        .with_file("src/lib.rs", "")
+        .with_file(
+            "helper/Cargo.toml",
+            "[package]\nname = \"helper\"\nversion = \"0.2.0\"\nedition = \"2021\"\n",
+        )
+        .with_file("helper/src/lib.rs", "");
+    let tool = Deps::new(workspace.root());
+
+    let result = tool.execute(json!({ "action": "tree" })).await.unwrap();
+    assert_eq!(result["ecosystems"], json!(["cargo"]));
+    assert_eq!(
+        result["dependencies"],
+        json!([
+            { "ecosystem": "cargo", "name": "deps-demo", "version": "0.1.0", "depth": 0 },
+            { "ecosystem": "cargo", "name": "helper", "version": "0.2.0", "depth": 1 },
+        ])
+    );
+    assert_eq!(result["total"], 2);
+    assert!(result.get("vulnerabilities").is_none());
+}
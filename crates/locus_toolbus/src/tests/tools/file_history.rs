@@ -0,0 +1,141 @@
+use crate::history::EditHistory;
+use crate::tools::{CreateFile, EditFile, FileHistory, FileHistoryAction, FileHistoryArgs, Tool};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Create `f.txt` with "one", then edit it to "two" and "three".
+async fn edited_repo() -> (TempDir, Arc<EditHistory>) {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let history = Arc::new(EditHistory::load_blocking(root.clone()));
+
+    CreateFile::new(root.clone())
+        .execute(json!({ "path": "f.txt", "content": "one\n" }))
+        .await
+        .unwrap();
+
+    let edit = EditFile::new(root, Arc::clone(&history));
+    for (old, new) in [("one", "two"), ("two", "three")] {
+        edit.execute(json!({ "path": "f.txt", "old_string": old, "new_string": new }))
+            .await
+            .unwrap();
+    }
+
+    (temp_dir, history)
+}
+
+#[test]
+fn test_file_history_args_parsing() {
+    let args: FileHistoryArgs = serde_json::from_value(json!({ "path": "src/main.rs" })).unwrap();
+    assert_eq!(args.path, "src/main.rs");
+    assert_eq!(args.action, FileHistoryAction::List);
+    assert!(args.version.is_none());
+
+    let args: FileHistoryArgs =
+        serde_json::from_value(json!({ "path": "a.rs", "action": "restore", "version": 2 }))
+            .unwrap();
+    assert_eq!(args.action, FileHistoryAction::Restore);
+    assert_eq!(args.version, Some(2));
+}
+
+#[test]
+fn test_file_history_tool_name() {
+    let root = PathBuf::from("/tmp");
+    let tool = FileHistory::new(root.clone(), Arc::new(EditHistory::load_blocking(root)));
+    assert_eq!(tool.name(), "file_history");
+}
+
+#[tokio::test]
+async fn test_file_history_list() {
+    let (dir, history) = edited_repo().await;
+    let tool = FileHistory::new(dir.path().to_path_buf(), history);
+
+    let result = tool.execute(json!({ "path": "f.txt" })).await.unwrap();
+    let versions = result["versions"].as_array().unwrap();
+
+    assert_eq!(versions.len(), 3);
+    assert_eq!(result["latest"], 2);
+    assert_eq!(versions[0]["version"], 0);
+    assert_eq!(versions[1]["added"], 1);
+    assert_eq!(versions[1]["removed"], 1);
+}
+
+#[tokio::test]
+async fn test_file_history_diff() {
+    let (dir, history) = edited_repo().await;
+    let tool = FileHistory::new(dir.path().to_path_buf(), history);
+
+    let result = tool
+        .execute(json!({ "path": "f.txt", "action": "diff", "version": 2 }))
+        .await
+        .unwrap();
+
+    assert_eq!(result["old_content"], "two\n");
+    assert_eq!(result["new_content"], "three\n");
+
+    let original = tool
+        .execute(json!({ "path": "f.txt", "action": "diff", "version": 0 }))
+        .await
+        .unwrap();
+    assert_eq!(original["old_content"], "");
+    assert_eq!(original["new_content"], "one\n");
+}
+
+#[tokio::test]
+async fn test_file_history_restore() {
+    let (dir, history) = edited_repo().await;
+    let root = dir.path().to_path_buf();
+    let tool = FileHistory::new(root.clone(), history);
+
+    let result = tool
+        .execute(json!({ "path": "f.txt", "action": "restore", "version": 0 }))
+        .await
+        .unwrap();
+    assert!(result["success"].as_bool().unwrap());
+    assert_eq!(result["old_content"], "three\n");
+
+    let content = tokio::fs::read_to_string(root.join("f.txt")).await.unwrap();
+    assert_eq!(content, "one\n");
+
+    let listed = tool.execute(json!({ "path": "f.txt" })).await.unwrap();
+    assert_eq!(listed["latest"], 3, "restore is recorded as a new version");
+}
+
+#[tokio::test]
+async fn test_file_history_errors() {
+    let (dir, history) = edited_repo().await;
+    let tool = FileHistory::new(dir.path().to_path_buf(), history);
+
+    let missing_version = tool
+        .execute(json!({ "path": "f.txt", "action": "diff" }))
+        .await;
+    assert!(
+        missing_version
+            .unwrap_err()
+            .to_string()
+            .contains("Missing version")
+    );
+
+    let out_of_range = tool
+        .execute(json!({ "path": "f.txt", "action": "restore", "version": 9 }))
+        .await;
+    assert!(out_of_range.unwrap_err().to_string().contains("not found"));
+
+    let untracked = tool.execute(json!({ "path": "other.txt" })).await;
+    assert!(
+        untracked
+            .unwrap_err()
+            .to_string()
+            .contains("No edit history")
+    );
+
+    let outside = tool.execute(json!({ "path": "../../etc/passwd" })).await;
+    assert!(
+        outside
+            .unwrap_err()
+            .to_string()
+            .contains("outside workspace")
+    );
+}
@@ -352,3 +352,21 @@ async fn test_search_result_structure() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_protected_paths_are_not_searched() {
+    let dir = create_test_repo();
+    fs::create_dir(dir.path().join("secrets")).unwrap();
+    fs::write(dir.path().join("secrets/prod.txt"), "fn hidden() {}\n").unwrap();
+    let finder = Finder::new(dir.path().to_path_buf())
+        .with_protected_paths(crate::ProtectedPaths::new().with_deny("secrets/").matcher());
+
+    let result = finder.execute(json!({ "query": "fn " })).await.unwrap();
+    let matches = result["matches"].as_array().unwrap();
+    assert!(!matches.is_empty());
+    assert!(
+        matches
+            .iter()
+            .all(|m| !m["file"].as_str().unwrap().starts_with("secrets"))
+    );
+}
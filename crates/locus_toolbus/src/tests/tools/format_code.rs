@@ -0,0 +1,189 @@
+use crate::history::EditHistory;
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::format_code::{rust_edition, unformatted_files};
+use crate::tools::{
+    FormatCode, FormatCodeArgs, FormatMode, Formatter, Tool, ToolContext, UndoEdit, WriteSink,
+};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+fn format_tool(workspace: &TestWorkspace) -> (FormatCode, Arc<EditHistory>) {
+    let history = Arc::new(EditHistory::load_blocking(workspace.root()));
+    (
+        FormatCode::new(workspace.root(), Arc::clone(&history)),
+        history,
+    )
+}
+
+#[test]
+fn test_format_code_tool_name_and_args() {
+    let workspace = TestWorkspace::new();
+    let (tool, _) = format_tool(&workspace);
+    assert_eq!(tool.name(), "format_code");
+    assert!(tool.description().contains("undo_edit"));
+    assert_args_round_trip::<FormatCodeArgs>(
+        &tool,
+        json!({ "mode": "apply", "paths": ["src", "web/app.ts"], "formatter": "prettier" }),
+    );
+    assert_eq!(FormatCodeArgs::default().mode, FormatMode::Check);
+}
+
+#[test]
+fn test_format_code_detects_and_builds_commands() {
+    let workspace = TestWorkspace::new()
+        .with_file("Cargo.toml", "[package]\nname = \"demo\"\n")
+        .with_file("package.json", "{\"prettier\": {\"semi\": false}}")
+        .with_file("pyproject.toml", "[tool.black]\nline-length = 100\n");
+    assert_eq!(
+        FormatCode::detect(&workspace.root()),
+        [Formatter::Rustfmt, Formatter::Prettier, Formatter::Black]
+    );
+    assert!(FormatCode::detect(&TestWorkspace::new().root()).is_empty());
+
+    let targets = ["web".to_string(), "my app".to_string()];
+    assert_eq!(
+        FormatCode::command(Formatter::Prettier, FormatMode::Check, &targets),
+        "npx --no-install prettier --list-different web 'my app'"
+    );
+    assert_eq!(
+        FormatCode::command(Formatter::Black, FormatMode::Apply, &targets),
+        "black -q web 'my app'"
+    );
+    assert_eq!(
+        FormatCode::rustfmt_command("2024", FormatMode::Check, &["src/lib.rs".to_string()]),
+        "rustfmt --edition 2024 --check -l src/lib.rs"
+    );
+}
+
+#[test]
+fn test_unformatted_files_per_formatter() {
+    let root = Path::new("/repo");
+    assert_eq!(
+        unformatted_files(
+            root,
+            Formatter::Rustfmt,
+            "/repo/src/main.rs\n/repo/src/a.rs\n/elsewhere/x.rs\n",
+            ""
+        ),
+        ["src/main.rs", "src/a.rs"]
+    );
+    assert_eq!(
+        unformatted_files(root, Formatter::Prettier, "web/app.ts\n./web/b.css\n", ""),
+        ["web/app.ts", "web/b.css"]
+    );
+    let black = "would reformat /repo/app/main.py\nwould reformat app/util.py\n\nOh no! 💥 💔 💥\n2 files would be reformatted.\n";
+    assert_eq!(
+        unformatted_files(root, Formatter::Black, "", black),
+        ["app/main.py", "app/util.py"]
+    );
+}
+
+#[test]
+fn test_rust_edition_follows_manifests() {
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nedition = \"2021\"\n",
+        )
+        .with_file(
+            "crates/own/Cargo.toml",
+            "[package]\nname = \"own\"\nedition = \"2024\"\n",
+        )
+        .with_file(
+            "crates/inherits/Cargo.toml",
+            "[package]\nname = \"inherits\"\nedition.workspace = true\n",
+        )
+        .with_file("crates/old/Cargo.toml", "[package]\nname = \"old\"\n");
+    let root = workspace.root();
+    assert_eq!(rust_edition(&root, "crates/own/src/lib.rs"), "2024");
+    assert_eq!(
+        rust_edition(&root, "crates/inherits/src/deep/mod.rs"),
+        "2021"
+    );
+    assert_eq!(rust_edition(&root, "crates/old/src/lib.rs"), "2015");
+    assert_eq!(rust_edition(&root, "build.rs"), "2015");
+}
+
+#[tokio::test]
+async fn test_format_code_argument_errors() {
+    let workspace = TestWorkspace::new().with_file("notes.txt", "hi\n");
+    let (tool, _) = format_tool(&workspace);
+
+    let err = tool.execute(json!({})).await.unwrap_err().to_string();
+    assert!(err.contains("No formatter found"), "got: {}", err);
+
+    let err = tool
+        .execute(json!({ "formatter": "black", "paths": ["missing"] }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("does not exist"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_format_code_runs_rustfmt_and_records_history() {
+    if crate::features::find_program("rustfmt").is_none() {
+        eprintln!("rustfmt not available; skipping");
+        return;
+    }
+    let messy = "mod util;\nfn main(){let x=1;println!(\"{}\",x);}\n";
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "Cargo.toml",
+            "[package]\nname = \"fmt-demo\"\nedition = \"2021\"\n",
+        )
+        .with_file("src/main.rs", messy)
+        .with_file("src/util.rs", "pub fn twice( x:i32 )->i32{x*2}\n")
+        .with_file("src/tidy.rs", "pub fn tidy() {}\n");
+    let (tool, history) = format_tool(&workspace);
+
+    let result = tool.execute(json!({})).await.unwrap();
+    assert_eq!(result["mode"], "check");
+    assert_eq!(result["clean"], false);
+    assert_eq!(
+        result["files"],
+        json!([
+            { "path": "src/main.rs", "formatter": "rustfmt" },
+            { "path": "src/util.rs", "formatter": "rustfmt" },
+        ])
+    );
+    assert_eq!(
+        workspace.read("src/main.rs"),
+        messy,
+        "check changes nothing"
+    );
+
+    // Formats the files the check listed (util.rs through main.rs's `mod util;`),
+    // reporting each with its content before it is written.
+    let (writes_tx, mut writes_rx) = tokio::sync::mpsc::unbounded_channel();
+    let ctx = ToolContext::new().with_writes(WriteSink::new(writes_tx));
+    let result = tool
+        .execute_with_context(json!({ "mode": "apply", "paths": ["src"] }), ctx)
+        .await
+        .unwrap();
+    assert_eq!(result["files"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        writes_rx.try_recv().unwrap(),
+        ("src/main.rs".to_string(), messy.to_string())
+    );
+    assert_eq!(writes_rx.try_recv().unwrap().0, "src/util.rs");
+    assert!(writes_rx.try_recv().is_err());
+    assert_eq!(
+        workspace.read("src/main.rs"),
+        "mod util;\nfn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n"
+    );
+    assert_eq!(
+        workspace.read("src/util.rs"),
+        "pub fn twice(x: i32) -> i32 {\n    x * 2\n}\n"
+    );
+
+    let result = tool.execute(json!({ "mode": "check" })).await.unwrap();
+    assert_eq!(result["clean"], true);
+
+    let undo = UndoEdit::new(workspace.root(), history);
+    undo.execute(json!({ "path": "src/main.rs" }))
+        .await
+        .unwrap();
+    assert_eq!(workspace.read("src/main.rs"), messy);
+}
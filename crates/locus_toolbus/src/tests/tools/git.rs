@@ -0,0 +1,216 @@
+use crate::permission::Permission;
+use crate::tools::{GitBranch, GitCommit, GitDiff, GitLog, GitStatus, Tool};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Repo with one commit on `main`, or None when git is unavailable.
+fn create_test_repo() -> Option<TempDir> {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("lib.rs"), "fn a() {}\n").unwrap();
+    let ok = git(dir.path(), &["init", "-q", "-b", "main"])
+        && git(dir.path(), &["config", "user.name", "Ada"])
+        && git(dir.path(), &["config", "user.email", "ada@example.com"])
+        && git(dir.path(), &["add", "-A"])
+        && git(dir.path(), &["commit", "-q", "-m", "Initial commit"]);
+    ok.then_some(dir)
+}
+
+#[test]
+fn test_git_tool_permissions() {
+    let root = std::path::PathBuf::from(".");
+    assert_eq!(GitStatus::new(root.clone()).permission(), Permission::Read);
+    assert_eq!(GitDiff::new(root.clone()).permission(), Permission::Read);
+    assert_eq!(GitLog::new(root.clone()).permission(), Permission::Read);
+    assert_eq!(
+        GitCommit::new(root.clone()).permission(),
+        Permission::GitWrite
+    );
+    assert_eq!(GitBranch::new(root).permission(), Permission::GitWrite);
+}
+
+#[tokio::test]
+async fn test_git_status_parses_changes() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+    fs::write(dir.path().join("new file.rs"), "").unwrap();
+    fs::write(dir.path().join("staged.rs"), "x\n").unwrap();
+    assert!(git(dir.path(), &["add", "staged.rs"]));
+
+    let result = GitStatus::new(dir.path().to_path_buf())
+        .execute(json!({}))
+        .await
+        .unwrap();
+    assert_eq!(result["branch"], "main");
+    assert_eq!(result["clean"], false);
+    assert_eq!(
+        result["staged"],
+        json!([{ "path": "staged.rs", "status": "added" }])
+    );
+    assert_eq!(
+        result["unstaged"],
+        json!([{ "path": "lib.rs", "status": "modified" }])
+    );
+    assert_eq!(result["untracked"], json!(["new file.rs"]));
+}
+
+#[tokio::test]
+async fn test_git_diff_counts_lines() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    fs::write(dir.path().join("lib.rs"), "fn b() {}\nfn c() {}\n").unwrap();
+
+    let tool = GitDiff::new(dir.path().to_path_buf());
+    let result = tool.execute(json!({})).await.unwrap();
+    assert_eq!(result["files_changed"], 1);
+    assert_eq!(result["additions"], 2);
+    assert_eq!(result["deletions"], 1);
+    assert!(result["diff"].as_str().unwrap().contains("+fn c() {}"));
+
+    let staged = tool
+        .execute(json!({ "staged": true, "stat_only": true }))
+        .await
+        .unwrap();
+    assert_eq!(staged["files_changed"], 0);
+    assert!(staged.get("diff").is_none());
+}
+
+#[tokio::test]
+async fn test_git_diff_leaves_out_protected_files() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    fs::write(dir.path().join("prod.pem"), "old key\n").unwrap();
+    assert!(git(dir.path(), &["add", "prod.pem"]));
+    assert!(git(dir.path(), &["commit", "-q", "-m", "Add key"]));
+    fs::write(dir.path().join("prod.pem"), "new key\n").unwrap();
+    fs::write(dir.path().join("lib.rs"), "fn b() {}\n").unwrap();
+
+    let tool = GitDiff::new(dir.path().to_path_buf());
+    let err = tool
+        .execute(json!({ "path": "prod.pem" }))
+        .await
+        .unwrap_err();
+    assert!(err.downcast_ref::<crate::ProtectedPathDenied>().is_some());
+
+    let result = tool.execute(json!({})).await.unwrap();
+    assert_eq!(
+        result["files"],
+        json!([{ "path": "lib.rs", "additions": 1, "deletions": 1 }])
+    );
+    assert_eq!(result["additions"], 1);
+    let diff = result["diff"].as_str().unwrap();
+    assert!(diff.contains("+fn b() {}"));
+    assert!(!diff.contains("key"), "{}", diff);
+}
+
+#[tokio::test]
+async fn test_git_diff_rejects_option_like_base() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    let result = GitDiff::new(dir.path().to_path_buf())
+        .execute(json!({ "base": "--output=/tmp/x" }))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_git_commit_and_log() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    let commit = GitCommit::new(dir.path().to_path_buf());
+
+    let empty = commit.execute(json!({ "message": "Nothing" })).await;
+    assert!(empty.unwrap_err().to_string().contains("Nothing to commit"));
+
+    fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+    let result = commit
+        .execute(json!({ "message": "Add main\n\nEntry point.", "paths": ["main.rs"] }))
+        .await
+        .unwrap();
+    assert_eq!(result["subject"], "Add main");
+    assert_eq!(result["branch"], "main");
+    assert_eq!(
+        result["files"],
+        json!([{ "path": "main.rs", "additions": 1, "deletions": 0 }])
+    );
+
+    let log = GitLog::new(dir.path().to_path_buf())
+        .execute(json!({ "max_count": 5 }))
+        .await
+        .unwrap();
+    assert_eq!(log["count"], 2);
+    assert_eq!(log["commits"][0]["hash"], result["hash"]);
+    assert_eq!(log["commits"][0]["body"], "Entry point.");
+    assert_eq!(log["commits"][1]["subject"], "Initial commit");
+    assert_eq!(log["commits"][1]["author"], "Ada");
+}
+
+#[tokio::test]
+async fn test_git_branch_create_list_switch_delete() {
+    let Some(dir) = create_test_repo() else {
+        return;
+    };
+    let tool = GitBranch::new(dir.path().to_path_buf());
+
+    let created = tool
+        .execute(json!({ "action": "create", "name": "feature/x", "switch": true }))
+        .await
+        .unwrap();
+    assert_eq!(created["current"], "feature/x");
+
+    let list = tool.execute(json!({})).await.unwrap();
+    let names: Vec<&str> = list["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["feature/x", "main"]);
+    assert_eq!(list["branches"][0]["current"], true);
+
+    tool.execute(json!({ "action": "switch", "name": "main" }))
+        .await
+        .unwrap();
+    let deleted = tool
+        .execute(json!({ "action": "delete", "name": "feature/x" }))
+        .await
+        .unwrap();
+    assert_eq!(deleted["current"], "main");
+
+    assert!(tool.execute(json!({ "action": "switch" })).await.is_err());
+    assert!(
+        tool.execute(json!({ "action": "switch", "name": "-f" }))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_git_status_outside_repo() {
+    let dir = TempDir::new().unwrap();
+    let result = GitStatus::new(dir.path().to_path_buf())
+        .execute(json!({}))
+        .await;
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Not a git repository")
+    );
+}
@@ -214,3 +214,30 @@ async fn test_glob_invalid_pattern() {
         "Should return error for invalid glob pattern"
     );
 }
+
+#[tokio::test]
+async fn test_glob_leaves_out_protected_paths() {
+    let dir = create_test_repo();
+    fs::write(dir.path().join(".env"), "TOKEN=x").unwrap();
+    fs::create_dir(dir.path().join("certs")).unwrap();
+    fs::write(dir.path().join("certs/server.pem"), "key").unwrap();
+    fs::write(dir.path().join("certs/README.md"), "certs").unwrap();
+    let glob = Glob::new(dir.path().to_path_buf());
+
+    let result = glob.execute(json!({ "pattern": "**/*" })).await.unwrap();
+    let files: Vec<&str> = result["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f.as_str().unwrap())
+        .collect();
+    assert!(files.contains(&"certs/README.md"));
+    assert!(!files.contains(&".env"));
+    assert!(!files.contains(&"certs/server.pem"));
+
+    let result = glob
+        .execute(json!({ "pattern": "*", "path": ".env" }))
+        .await
+        .unwrap();
+    assert_eq!(result["files"].as_array().unwrap().len(), 0);
+}
@@ -295,3 +295,26 @@ async fn test_grep_result_structure() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_grep_skips_protected_paths() {
+    let dir = create_test_repo();
+    fs::write(dir.path().join(".env"), "debug=secret\n").unwrap();
+    fs::write(dir.path().join("src/server.pem"), "debug key\n").unwrap();
+    let grep = Grep::new(dir.path().to_path_buf());
+
+    let result = grep.execute(json!({ "pattern": "debug" })).await.unwrap();
+    let files: Vec<&str> = result["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, vec!["config.json"]);
+
+    let err = grep
+        .execute(json!({ "pattern": "debug", "path": ".env" }))
+        .await
+        .unwrap_err();
+    assert!(err.downcast_ref::<crate::ProtectedPathDenied>().is_some());
+}
@@ -0,0 +1,384 @@
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::json_query::MAX_COUNTED;
+use crate::tools::json_query::query::{Query, parse};
+use crate::tools::json_query::yaml::parse_yaml;
+use crate::tools::{JsonQuery, JsonQueryArgs, Tool};
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
+
+fn eval(query: &Query, input: &Value) -> Result<Vec<Value>, String> {
+    let mut out = Vec::new();
+    query.run(input, Instant::now() + Duration::from_secs(10), |value| {
+        out.push(value);
+        true
+    })?;
+    Ok(out)
+}
+
+fn run(query: &str, input: &Value) -> Vec<Value> {
+    let compiled = parse(query).unwrap_or_else(|e| panic!("{}: {}", query, e));
+    eval(&compiled, input).unwrap_or_else(|e| panic!("{}: {}", query, e))
+}
+
+const MANIFEST: &str = r#"# Deployment and service
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: api   # trailing comment
+  labels: {app: api, tier: "backend"}
+spec:
+  replicas: 3
+  template:
+    spec:
+      containers:
+        - name: api
+          image: "registry.example.com/api:1.4.2"
+          ports:
+          - containerPort: 8080
+          args: [--port, "8080",
+                 --verbose]
+        - name: sidecar
+          image: envoy:v1.30
+          command:
+            - /bin/sh
+            - -c
+            - |
+              echo start
+              exec envoy
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: api
+  annotations:
+    note: >-
+      folded
+      text
+spec:
+  ports:
+    - port: 80
+      targetPort: 8080
+"#;
+
+#[test]
+fn test_json_query_tool_name_and_args() {
+    let tool = JsonQuery::new(".".into());
+    assert_eq!(tool.name(), "json_query");
+    assert!(tool.description().contains("JSONPath"));
+    assert_args_round_trip::<JsonQueryArgs>(
+        &tool,
+        json!({ "query": ".a", "path": "x.yaml", "format": "yaml", "max_results": 5, "max_bytes": 100 }),
+    );
+    let args = JsonQueryArgs::new(".");
+    assert_eq!((args.max_results, args.max_bytes), (100, 32 * 1024));
+}
+
+#[test]
+fn test_jq_paths_and_builtins() {
+    let doc = json!({
+        "name": "web",
+        "scripts": { "build": "vite build", "test": "vitest" },
+        "deps": [
+            { "name": "react", "version": "18.3.1", "dev": false },
+            { "name": "vitest", "version": "1.6.0", "dev": true },
+            { "name": "react-dom", "version": "18.3.1", "dev": false }
+        ],
+        "key with space": 1
+    });
+    assert_eq!(run(".name", &doc), [json!("web")]);
+    assert_eq!(run(".missing", &doc), [Value::Null]);
+    assert_eq!(run(".missing.deeper?", &doc), Vec::<Value>::new());
+    assert_eq!(run(".\"key with space\"", &doc), [json!(1)]);
+    assert_eq!(run(".[\"scripts\"].test", &doc), [json!("vitest")]);
+    assert_eq!(run(".scripts | keys", &doc), [json!(["build", "test"])]);
+    assert_eq!(run(".deps[-1].name", &doc), [json!("react-dom")]);
+    assert_eq!(
+        run(".deps[1:][] | .name", &doc),
+        [json!("vitest"), json!("react-dom")]
+    );
+    assert_eq!(
+        run(
+            ".deps[] | select(.dev == false and (.name | startswith(\"react\"))) | .name",
+            &doc
+        ),
+        [json!("react"), json!("react-dom")]
+    );
+    assert_eq!(
+        run("[.deps[] | .version] | unique", &doc),
+        [json!(["1.6.0", "18.3.1"])]
+    );
+    assert_eq!(
+        run(".deps | map({name, dev}) | first", &doc),
+        [json!({ "name": "react", "dev": false })]
+    );
+    assert_eq!(
+        run("(.deps | length), (.scripts | length)", &doc),
+        [json!(3), json!(2)]
+    );
+    assert_eq!(
+        run("[.. | .version? | select(. != null)] | length", &doc),
+        [json!(3)]
+    );
+    assert_eq!(
+        run(".deps[] | select(.name | test(\"^vi\")) | .version", &doc),
+        [json!("1.6.0")]
+    );
+    assert_eq!(run(".name.first?", &doc), Vec::<Value>::new());
+
+    let err = eval(&parse(".name.first").unwrap(), &doc).unwrap_err();
+    assert!(err.contains("cannot use \"web\""), "got: {}", err);
+    assert!(parse(".deps[").is_err());
+    assert_eq!(
+        parse("frobnicate").err().unwrap(),
+        "unknown filter `frobnicate`"
+    );
+    for outside in ["env", "$ENV", "halt", "\"x\" | stderr"] {
+        assert!(parse(outside).is_err(), "{} compiled", outside);
+    }
+    let err = eval(&parse("\"bye\" | halt_error").unwrap(), &doc).unwrap_err();
+    assert!(err.contains("not available"), "got: {}", err);
+}
+
+#[test]
+fn test_loops_without_results_stop_at_the_deadline() {
+    for endless in [
+        "first(repeat(1) | select(. == 2))",
+        "until(false; .)",
+        "def f: f; f",
+        "reduce range(1; infinite) as $x (0; .)",
+    ] {
+        let query = parse(endless).unwrap();
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let err = query.run(&json!(1), deadline, |_| true).unwrap_err();
+        assert!(err.contains("time limit"), "{}: {}", endless, err);
+    }
+    // Definitions still work as paths.
+    assert_eq!(
+        run("def f: .a; f |= 5", &json!({ "a": 1 })),
+        [json!({ "a": 5 })]
+    );
+}
+
+#[test]
+fn test_jsonpath_queries() {
+    let doc = json!({
+        "store": {
+            "book": [
+                { "title": "A", "price": 8, "tags": ["x"] },
+                { "title": "B", "price": 12 },
+                { "title": "C", "price": 5, "isbn": "123" }
+            ],
+            "bicycle": { "color": "red", "price": 20 }
+        }
+    });
+    assert_eq!(run("$.store.bicycle.color", &doc), [json!("red")]);
+    assert_eq!(run("$.store.book[1].title", &doc), [json!("B")]);
+    assert_eq!(
+        run("$.store.book[*].title", &doc),
+        [json!("A"), json!("B"), json!("C")]
+    );
+    assert_eq!(run("$..price", &doc).len(), 4);
+    assert_eq!(run("$.store.missing", &doc), Vec::<Value>::new());
+    assert_eq!(
+        run("$.store.book[?(@.price < 10)].title", &doc),
+        [json!("A"), json!("C")]
+    );
+    assert_eq!(run("$.store.book[?(@.isbn)].title", &doc), [json!("C")]);
+    assert_eq!(
+        run("$.store.book[0,2]['title']", &doc),
+        [json!("A"), json!("C")]
+    );
+    assert_eq!(
+        run("$.store.book[?(@.title == 'B' || @.price > 7)].price", &doc),
+        [json!(8), json!(12)]
+    );
+    assert_eq!(run("$.store.book[:1].title", &doc), Vec::<Value>::new());
+    assert_eq!(run("$.store.book[:1][*].title", &doc), [json!("A")]);
+}
+
+#[test]
+fn test_parse_yaml_manifest() {
+    let documents = parse_yaml(MANIFEST).unwrap();
+    assert_eq!(documents.len(), 2);
+    let deployment = &documents[0];
+    assert_eq!(deployment["metadata"]["name"], "api");
+    assert_eq!(
+        deployment["metadata"]["labels"],
+        json!({ "app": "api", "tier": "backend" })
+    );
+    assert_eq!(deployment["spec"]["replicas"], 3);
+    let containers = &deployment["spec"]["template"]["spec"]["containers"];
+    assert_eq!(containers[0]["image"], "registry.example.com/api:1.4.2");
+    assert_eq!(containers[0]["ports"], json!([{ "containerPort": 8080 }]));
+    assert_eq!(
+        containers[0]["args"],
+        json!(["--port", "8080", "--verbose"])
+    );
+    assert_eq!(
+        containers[1]["command"],
+        json!(["/bin/sh", "-c", "echo start\nexec envoy\n"])
+    );
+    assert_eq!(
+        documents[1]["metadata"]["annotations"]["note"],
+        "folded text"
+    );
+    assert_eq!(documents[1]["spec"]["ports"][0]["targetPort"], 8080);
+}
+
+#[test]
+fn test_parse_yaml_scalars_anchors_and_errors() {
+    let yaml = "defaults: &defaults\n  retries: 3\n  timeout: 1.5\nprod:\n  <<: *defaults\n  timeout: 10\nflags: [yes, ~, true, 0x1F, '3.10', 'it''s']\nempty:\nlist:\n- a\n- b: 1\n  c: 2\n- - nested\n";
+    let doc = &parse_yaml(yaml).unwrap()[0];
+    assert_eq!(doc["prod"], json!({ "retries": 3, "timeout": 10 }));
+    assert_eq!(doc["defaults"]["timeout"], 1.5);
+    assert_eq!(doc["flags"], json!(["yes", null, true, 31, "3.10", "it's"]));
+    assert_eq!(doc["empty"], Value::Null);
+    assert_eq!(doc["list"], json!(["a", { "b": 1, "c": 2 }, ["nested"]]));
+
+    let err = parse_yaml("a: 1\n   b: 2\n").unwrap_err();
+    assert!(err.contains("line 2"), "got: {}", err);
+    assert!(parse_yaml("a: *nowhere\n").is_err());
+}
+
+#[test]
+fn test_parse_yaml_refuses_alias_bombs() {
+    let mut yaml = "a0: &a0 [lol, lol, lol, lol, lol, lol, lol, lol, lol]\n".to_string();
+    for level in 1..10 {
+        let previous = format!("*a{}", level - 1);
+        yaml.push_str(&format!(
+            "a{}: &a{} [{}]\n",
+            level,
+            level,
+            [previous.as_str(); 9].join(", ")
+        ));
+    }
+    let err = parse_yaml(&yaml).unwrap_err();
+    assert!(err.contains("repetition limit exceeded"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_json_query_files_and_inline_content() {
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "package.json",
+            r#"{"name": "web", "dependencies": {"react": "^18.3.1", "vite": "^5.0.0"}}"#,
+        )
+        .with_file("k8s/app.yaml", MANIFEST)
+        .with_file(
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n",
+        );
+    let tool = JsonQuery::new(workspace.root());
+
+    let result = tool
+        .execute(json!({ "query": ".dependencies | keys", "path": "package.json" }))
+        .await
+        .unwrap();
+    assert_eq!(result["format"], "json");
+    assert_eq!(result["results"], json!([["react", "vite"]]));
+
+    let result = tool
+        .execute(json!({ "query": "$..image", "path": "k8s/app.yaml" }))
+        .await
+        .unwrap();
+    assert_eq!(result["format"], "yaml");
+    assert_eq!(result["documents"], 2);
+    assert_eq!(
+        result["results"],
+        json!(["registry.example.com/api:1.4.2", "envoy:v1.30"])
+    );
+
+    let result = tool
+        .execute(json!({ "query": ".dependencies.serde.features[0]", "path": "Cargo.toml" }))
+        .await
+        .unwrap();
+    assert_eq!(result["results"], json!(["derive"]));
+
+    let result = tool
+        .execute(json!({ "query": ".items[]", "content": "items: [1, 2, 3, 4]", "max_results": 2 }))
+        .await
+        .unwrap();
+    assert_eq!(result["source"], "inline");
+    assert_eq!(result["format"], "yaml", "not JSON, so read as YAML");
+    assert_eq!(result["results"], json!([1, 2]));
+    assert_eq!(
+        (result["total"].as_u64(), result["truncated"].as_bool()),
+        (Some(4), Some(true))
+    );
+
+    let result = tool
+        .execute(json!({ "query": ".", "path": "package.json", "max_bytes": 10 }))
+        .await
+        .unwrap();
+    assert_eq!(result["results"], json!([]));
+    assert_eq!(result["truncated"], true);
+
+    let result = tool
+        .execute(json!({ "query": "range(1; infinite)", "content": "null", "max_results": 2 }))
+        .await
+        .unwrap();
+    assert_eq!(result["results"], json!([1, 2]));
+    assert_eq!(
+        (result["total"].as_u64(), result["truncated"].as_bool()),
+        (Some(MAX_COUNTED as u64), Some(true))
+    );
+}
+
+#[tokio::test]
+async fn test_json_query_errors() {
+    let workspace = TestWorkspace::new().with_file("bad.json", "{\"a\": ");
+    let tool = JsonQuery::new(workspace.root());
+    let error = |args: Value| {
+        let tool = &tool;
+        async move { tool.execute(args).await.unwrap_err().to_string() }
+    };
+
+    assert!(
+        error(json!({ "query": "." }))
+            .await
+            .contains("either path or content")
+    );
+    assert!(
+        error(json!({ "query": ".", "path": "bad.json", "content": "{}" }))
+            .await
+            .contains("not both")
+    );
+    assert!(
+        error(json!({ "query": ".", "path": "missing.json" }))
+            .await
+            .contains("does not exist")
+    );
+    assert!(
+        error(json!({ "query": ".", "path": "bad.json" }))
+            .await
+            .contains("as JSON")
+    );
+    assert!(
+        error(json!({ "query": ".[", "content": "{}" }))
+            .await
+            .contains("Invalid query")
+    );
+    assert!(
+        error(json!({ "query": ".a[]", "content": "{\"a\": 1}" }))
+            .await
+            .contains("cannot use 1 as iterable")
+    );
+}
+
+#[tokio::test]
+async fn test_json_query_refuses_protected_paths() {
+    let workspace = TestWorkspace::new()
+        .with_file(".env", "TOKEN=secret\n")
+        .with_file("secrets/db.json", "{\"password\": \"hunter2\"}");
+    let tool = JsonQuery::new(workspace.root())
+        .with_protected_paths(crate::ProtectedPaths::new().with_deny("secrets/").matcher());
+
+    for path in [".env", "secrets/db.json", "./secrets/../secrets/db.json"] {
+        let err = tool
+            .execute(json!({ "query": ".", "path": path }))
+            .await
+            .unwrap_err();
+        let denied = err.downcast_ref::<crate::ProtectedPathDenied>().unwrap();
+        assert_eq!(denied.path, path);
+    }
+}
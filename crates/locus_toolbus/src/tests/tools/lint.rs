@@ -0,0 +1,151 @@
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::lint::parse::{parse_clippy, parse_eslint, parse_ruff};
+use crate::tools::{Lint, LintArgs, LintSeverity, Linter, Tool};
+use serde_json::json;
+use std::path::Path;
+
+const CLIPPY: &str = r#"{"reason":"compiler-artifact","target":{"name":"demo"}}
+{"reason":"compiler-message","message":{"level":"warning","message":"unneeded `return` statement","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true,"suggested_replacement":null}],"children":[{"level":"note","message":"`#[warn(clippy::needless_return)]` on by default","spans":[]},{"level":"help","message":"remove `return`","spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true,"suggested_replacement":"x"}]}]}}
+{"reason":"compiler-message","message":{"level":"warning","message":"unneeded `return` statement","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true}],"children":[{"level":"help","message":"remove `return`","spans":[{"file_name":"src/lib.rs","suggested_replacement":"x"}]}]}}
+{"reason":"compiler-message","message":{"level":"error","message":"cannot find value `y` in this scope","code":{"code":"E0425","explanation":"..."},"spans":[{"file_name":"src/main.rs","line_start":7,"column_start":13,"is_primary":true}],"children":[]}}
+{"reason":"compiler-message","message":{"level":"warning","message":"2 warnings emitted","code":null,"spans":[],"children":[]}}
+{"reason":"build-finished","success":false}"#;
+
+#[test]
+fn test_lint_tool_name_and_args() {
+    let tool = Lint::new(".".into());
+    assert_eq!(tool.name(), "lint");
+    assert!(tool.description().contains("clippy"));
+    assert_args_round_trip::<LintArgs>(
+        &tool,
+        json!({ "linter": "ruff", "path": "app", "max_results": 50 }),
+    );
+    assert_eq!(LintArgs::new().max_results, 200);
+}
+
+#[test]
+fn test_parse_clippy_keeps_primary_spans_once() {
+    let diagnostics = parse_clippy(Path::new("/repo"), CLIPPY);
+    assert_eq!(
+        diagnostics.len(),
+        2,
+        "duplicate and span-less messages dropped"
+    );
+
+    let lint = &diagnostics[0];
+    assert_eq!(
+        (lint.path.as_str(), lint.line, lint.column),
+        ("src/lib.rs", 3, 5)
+    );
+    assert_eq!(lint.severity, LintSeverity::Warning);
+    assert_eq!(lint.code.as_deref(), Some("clippy::needless_return"));
+    assert_eq!(lint.help.as_deref(), Some("remove `return`: `x`"));
+    assert_eq!(lint.linter, Linter::Clippy);
+
+    assert_eq!(diagnostics[1].severity, LintSeverity::Error);
+    assert_eq!(diagnostics[1].code.as_deref(), Some("E0425"));
+}
+
+#[test]
+fn test_parse_eslint_and_ruff() {
+    let eslint = r#"[{"filePath":"/repo/web/app.js","messages":[
+        {"ruleId":"no-unused-vars","severity":1,"message":"'x' is assigned a value but never used.","line":2,"column":7},
+        {"ruleId":"eqeqeq","severity":2,"message":"Expected '===' and instead saw '=='.","line":4,"column":9,"fix":{"range":[40,42],"text":"==="}},
+        {"ruleId":null,"fatal":true,"severity":2,"message":"Parsing error: Unexpected token"}
+    ]},{"filePath":"/elsewhere/lib.js","messages":[{"ruleId":"semi","severity":2,"message":"Missing semicolon.","line":1,"column":1}]}]"#;
+    let diagnostics = parse_eslint(Path::new("/repo"), eslint).unwrap();
+    assert_eq!(diagnostics.len(), 3, "files outside the repo dropped");
+    assert_eq!(diagnostics[0].path, "web/app.js");
+    assert_eq!(diagnostics[0].severity, LintSeverity::Warning);
+    assert_eq!(
+        diagnostics[1].help.as_deref(),
+        Some("Fixable with eslint --fix")
+    );
+    assert_eq!(
+        (diagnostics[2].line, diagnostics[2].code.clone()),
+        (1, None)
+    );
+    assert_eq!(diagnostics[2].severity, LintSeverity::Error);
+
+    let ruff = r#"[
+        {"code":"F401","message":"`os` imported but unused","filename":"/repo/app/main.py","location":{"row":1,"column":8},"end_location":{"row":1,"column":10},"fix":{"message":"Remove unused import: `os`","applicability":"safe","edits":[]}},
+        {"code":null,"message":"SyntaxError: Expected an expression","filename":"/repo/app/broken.py","location":{"row":3,"column":1},"fix":null}
+    ]"#;
+    let diagnostics = parse_ruff(Path::new("/repo"), ruff).unwrap();
+    assert_eq!(diagnostics[0].path, "app/main.py");
+    assert_eq!(diagnostics[0].severity, LintSeverity::Warning);
+    assert_eq!(
+        diagnostics[0].help.as_deref(),
+        Some("Remove unused import: `os`")
+    );
+    assert_eq!(diagnostics[1].severity, LintSeverity::Error);
+
+    assert!(parse_ruff(Path::new("/repo"), "error: unknown option").is_err());
+}
+
+#[test]
+fn test_lint_detects_configured_linters() {
+    let workspace = TestWorkspace::new()
+        .with_file("Cargo.toml", "[package]\nname = \"demo\"\n")
+        .with_file("package.json", "{\"eslintConfig\": {}}")
+        .with_file("pyproject.toml", "[tool.ruff]\nline-length = 100\n");
+    assert_eq!(
+        Lint::detect(&workspace.root()),
+        [Linter::Clippy, Linter::Eslint, Linter::Ruff]
+    );
+    assert!(Lint::detect(&TestWorkspace::new().root()).is_empty());
+
+    assert_eq!(
+        Lint::command(Linter::Ruff, Some("app dir")),
+        "ruff check --output-format json --exit-zero 'app dir'"
+    );
+    assert_eq!(
+        Lint::command(Linter::Eslint, None),
+        "npx --no-install eslint -f json ."
+    );
+}
+
+#[tokio::test]
+async fn test_lint_argument_errors() {
+    let workspace = TestWorkspace::new().with_file("notes.txt", "hi\n");
+    let tool = Lint::new(workspace.root());
+
+    let err = tool.execute(json!({})).await.unwrap_err().to_string();
+    assert!(err.contains("No linter found"), "got: {}", err);
+
+    let err = tool
+        .execute(json!({ "linter": "ruff", "path": "missing" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("does not exist"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_lint_runs_clippy() {
+    if crate::features::find_program("cargo-clippy").is_none() {
+        eprintln!("clippy not available; skipping");
+        return;
+    }
+    let workspace = TestWorkspace::new()
+        .with_file(
+            "Cargo.toml",
+            "[package]\nname = \"lint-demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+        )
+        .with_file(
+            "src/lib.rs",
+            "pub fn double(x: i32) -> i32 {\n    return x * 2;\n}\n",
+        );
+    let tool = Lint::new(workspace.root());
+
+    let result = tool.execute(json!({})).await.unwrap();
+    assert_eq!(result["linters"], json!(["clippy"]));
+    assert_eq!(result["counts"]["warning"], 1);
+    let diagnostic = &result["diagnostics"][0];
+    assert_eq!(diagnostic["path"], "src/lib.rs");
+    assert_eq!(diagnostic["line"], 2);
+    assert_eq!(diagnostic["code"], "clippy::needless_return");
+
+    let result = tool.execute(json!({ "path": "Cargo.toml" })).await.unwrap();
+    assert_eq!(result["total"], 0);
+}
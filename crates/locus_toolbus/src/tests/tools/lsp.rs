@@ -0,0 +1,484 @@
+use crate::history::EditHistory;
+use crate::lsp::edit::{Position, TextEdit, apply_edits, workspace_edit_files};
+use crate::lsp::{LspBridge, LspClient, LspConfig, LspError, LspServerConfig, uri_to_path};
+use crate::permission::Permission;
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::{
+    LspDiagnostics, LspDiagnosticsArgs, LspHover, LspHoverArgs, LspRename, LspRenameArgs, Tool,
+};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+const LIB: &str = "pub fn greet(name: &str) -> String {\n    format!(\"hi {}\", name)\n}\n";
+const MAIN: &str = "fn main() {\n    let s = greet(\"ada\");\n    bad(s);\n}\n";
+
+async fn read_frame(reader: &mut BufReader<impl AsyncRead + Unpin>) -> Option<Value> {
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse().ok()?;
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await.ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+async fn write_frame(writer: &mut (impl AsyncWrite + Unpin), message: Value) {
+    let body = message.to_string();
+    let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let _ = writer.write_all(frame.as_bytes()).await;
+}
+
+/// Identifier under a UTF-16 position (fixtures are ASCII) and its start column.
+fn word_at(text: &str, line: u64, character: u64) -> Option<(String, usize)> {
+    let line = text.lines().nth(line as usize)?;
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let at = character as usize;
+    if !line[at..].starts_with(is_ident) {
+        return None;
+    }
+    let start = line[..at].rfind(|c| !is_ident(c)).map_or(0, |i| i + 1);
+    let end = line[at..]
+        .find(|c| !is_ident(c))
+        .map_or(line.len(), |i| at + i);
+    Some((line[start..end].to_string(), start))
+}
+
+fn range(line: usize, start: usize, end: usize) -> Value {
+    json!({
+        "start": { "line": line, "character": start },
+        "end": { "line": line, "character": end },
+    })
+}
+
+/// Whole-word occurrences of `word` in `text` as (line, start column).
+fn occurrences(text: &str, word: &str) -> Vec<(usize, usize)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut found = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        for (at, _) in line.match_indices(word) {
+            let before = line[..at].chars().next_back();
+            let after = line[at + word.len()..].chars().next();
+            if !before.is_some_and(is_ident) && !after.is_some_and(is_ident) {
+                found.push((n, at));
+            }
+        }
+    }
+    found
+}
+
+/// A small language server: reports every `bad` as an unknown name, hovers any
+/// identifier, and renames whole words across the `.rs` files in `src/`.
+async fn fake_server(reader: impl AsyncRead + Unpin, mut writer: impl AsyncWrite + Unpin) {
+    let mut reader = BufReader::new(reader);
+    let mut documents: HashMap<String, String> = HashMap::new();
+    let mut root = std::path::PathBuf::new();
+    while let Some(message) = read_frame(&mut reader).await {
+        let params = &message["params"];
+        let method = message["method"].as_str().unwrap_or("");
+        let result = match method {
+            "initialize" => {
+                root = uri_to_path(params["rootUri"].as_str().unwrap()).unwrap();
+                json!({ "capabilities": { "hoverProvider": true, "renameProvider": true } })
+            }
+            "textDocument/didOpen" | "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap().to_string();
+                let text = if method == "textDocument/didOpen" {
+                    params["textDocument"]["text"].as_str().unwrap()
+                } else {
+                    params["contentChanges"][0]["text"].as_str().unwrap()
+                };
+                let diagnostics: Vec<Value> = occurrences(text, "bad")
+                    .into_iter()
+                    .map(|(line, at)| {
+                        json!({
+                            "range": range(line, at, at + 3),
+                            "severity": 1,
+                            "code": 425,
+                            "source": "fake",
+                            "message": "cannot find function `bad` in this scope",
+                        })
+                    })
+                    .collect();
+                documents.insert(uri.clone(), text.to_string());
+                write_frame(
+                    &mut writer,
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "textDocument/publishDiagnostics",
+                        "params": { "uri": uri, "diagnostics": diagnostics },
+                    }),
+                )
+                .await;
+                continue;
+            }
+            "textDocument/hover" => {
+                let text = &documents[params["textDocument"]["uri"].as_str().unwrap()];
+                let line = params["position"]["line"].as_u64().unwrap();
+                let character = params["position"]["character"].as_u64().unwrap();
+                match word_at(text, line, character) {
+                    Some((word, start)) => json!({
+                        "contents": { "kind": "markdown", "value": format!("```rust\nfn {}(name: &str) -> String\n```", word) },
+                        "range": range(line as usize, start, start + word.len()),
+                    }),
+                    None => Value::Null,
+                }
+            }
+            "textDocument/rename" => {
+                let text = &documents[params["textDocument"]["uri"].as_str().unwrap()];
+                let line = params["position"]["line"].as_u64().unwrap();
+                let character = params["position"]["character"].as_u64().unwrap();
+                match word_at(text, line, character) {
+                    Some((word, _)) => {
+                        let mut changes = serde_json::Map::new();
+                        let mut paths: Vec<_> = std::fs::read_dir(root.join("src"))
+                            .unwrap()
+                            .map(|e| e.unwrap().path())
+                            .collect();
+                        paths.sort();
+                        for path in paths {
+                            let content = std::fs::read_to_string(&path).unwrap();
+                            let edits: Vec<Value> = occurrences(&content, &word)
+                                .into_iter()
+                                .map(|(line, at)| {
+                                    json!({
+                                        "range": range(line, at, at + word.len()),
+                                        "newText": params["newName"],
+                                    })
+                                })
+                                .collect();
+                            if !edits.is_empty() {
+                                let uri = reqwest::Url::from_file_path(&path).unwrap().to_string();
+                                changes.insert(uri, json!(edits));
+                            }
+                        }
+                        json!({ "changes": changes })
+                    }
+                    None => Value::Null,
+                }
+            }
+            _ => Value::Null,
+        };
+        if !message["id"].is_null() {
+            write_frame(
+                &mut writer,
+                json!({ "jsonrpc": "2.0", "id": message["id"], "result": result }),
+            )
+            .await;
+        }
+    }
+}
+
+fn config() -> LspConfig {
+    LspConfig {
+        servers: vec![LspServerConfig::new("fake", "fake-ls", &["rs"])],
+    }
+}
+
+/// Workspace with a bridge whose `rs` server is the in-process fake.
+async fn setup() -> (TestWorkspace, Arc<LspBridge>) {
+    let workspace = TestWorkspace::new()
+        .with_file("src/lib.rs", LIB)
+        .with_file("src/main.rs", MAIN)
+        .with_file("README.md", "# demo\n");
+    let bridge = LspBridge::with_config(workspace.root(), config());
+
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    let (client_read, client_write) = tokio::io::split(client_io);
+    let (server_read, server_write) = tokio::io::split(server_io);
+    tokio::spawn(fake_server(server_read, server_write));
+    let client = LspClient::connect("fake", client_read, client_write, Duration::from_secs(5));
+    let root = workspace.root().canonicalize().unwrap();
+    client.initialize(&root, None).await.unwrap();
+    bridge.attach("fake", client).await;
+    (workspace, Arc::new(bridge))
+}
+
+#[test]
+fn test_lsp_config_parse() {
+    let config = LspConfig::parse(
+        r#"
+[[servers]]
+id = "rust"
+command = "rust-analyzer"
+extensions = ["rs"]
+
+[[servers]]
+id = "ts"
+command = "typescript-language-server"
+args = ["--stdio"]
+extensions = ["ts", "tsx"]
+timeout_secs = 60
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.servers.len(), 2);
+    assert_eq!(config.servers[0].timeout_secs, 30);
+    assert_eq!(config.servers[1].args, vec!["--stdio"]);
+
+    let server = config.server_for(Path::new("web/app.tsx")).unwrap();
+    assert_eq!(server.id, "ts");
+    assert_eq!(
+        server.language_id_for(Path::new("web/app.tsx")),
+        "typescriptreact"
+    );
+    assert!(config.server_for(Path::new("README.md")).is_none());
+
+    let err = LspConfig::parse("[[servers]]\nid = \"x\"\ncommand = \"\"\nextensions = []\n");
+    assert!(matches!(err, Err(LspError::Config(_))));
+}
+
+#[test]
+fn test_lsp_config_missing_file_is_empty() {
+    let workspace = TestWorkspace::new();
+    assert_eq!(
+        LspConfig::load(&workspace.root()).unwrap(),
+        LspConfig::default()
+    );
+
+    workspace.write(
+        ".locus/lsp.toml",
+        "[[servers]]\nid = \"py\"\ncommand = \"pyright-langserver\"\nextensions = [\"py\"]\n",
+    );
+    let config = LspConfig::load(&workspace.root()).unwrap();
+    assert_eq!(config.servers[0].id, "py");
+}
+
+#[test]
+fn test_apply_edits_in_reverse_order_and_utf16() {
+    let text = "let é = 1;\nlet x = é + é;\n";
+    let edit = |line, start, end, new_text: &str| TextEdit {
+        start: Position {
+            line,
+            character: start,
+        },
+        end: Position {
+            line,
+            character: end,
+        },
+        new_text: new_text.to_string(),
+    };
+    // `é` is one UTF-16 unit; the edits come unordered, as servers send them.
+    let edits = vec![edit(1, 12, 13, "e"), edit(0, 4, 5, "e"), edit(1, 8, 9, "e")];
+    assert_eq!(
+        apply_edits(text, &edits).unwrap(),
+        "let e = 1;\nlet x = e + e;\n"
+    );
+
+    let overlapping = vec![edit(0, 0, 5, ""), edit(0, 3, 6, "")];
+    assert!(matches!(
+        apply_edits(text, &overlapping),
+        Err(LspError::Protocol(_))
+    ));
+}
+
+#[test]
+fn test_workspace_edit_refuses_file_operations() {
+    let edit = json!({
+        "documentChanges": [
+            { "kind": "rename", "oldUri": "file:///a.rs", "newUri": "file:///b.rs" }
+        ]
+    });
+    assert!(matches!(
+        workspace_edit_files(&edit),
+        Err(LspError::Protocol(_))
+    ));
+
+    let edit = json!({
+        "documentChanges": [{
+            "textDocument": { "uri": "file:///a.rs", "version": 1 },
+            "edits": [{ "range": range(0, 0, 1), "newText": "b" }]
+        }]
+    });
+    let files = workspace_edit_files(&edit).unwrap();
+    assert_eq!(files[0].0, "file:///a.rs");
+    assert_eq!(files[0].1.len(), 1);
+}
+
+#[test]
+fn test_lsp_tool_permissions_and_args() {
+    let workspace = TestWorkspace::new();
+    let root = workspace.root();
+    let bridge = Arc::new(LspBridge::with_config(root.clone(), config()));
+    let history = Arc::new(EditHistory::load_blocking(root));
+    let diagnostics = LspDiagnostics::new(Arc::clone(&bridge));
+    let hover = LspHover::new(Arc::clone(&bridge));
+    let rename = LspRename::new(bridge, history);
+    assert_eq!(diagnostics.permission(), Permission::Read);
+    assert_eq!(hover.permission(), Permission::Read);
+    assert_eq!(rename.permission(), Permission::Write);
+
+    assert_args_round_trip::<LspDiagnosticsArgs>(
+        &diagnostics,
+        json!({"path": "src/lib.rs", "wait_ms": 100}),
+    );
+    assert_args_round_trip::<LspHoverArgs>(
+        &hover,
+        json!({"path": "src/lib.rs", "line": 1, "symbol": "greet"}),
+    );
+    assert_args_round_trip::<LspRenameArgs>(
+        &rename,
+        json!({"path": "src/lib.rs", "line": 1, "column": 8, "new_name": "welcome", "dry_run": true}),
+    );
+}
+
+#[tokio::test]
+async fn test_lsp_no_server_for_file() {
+    let (_workspace, bridge) = setup().await;
+    let tool = LspDiagnostics::new(bridge);
+    let err = tool
+        .execute(json!({"path": "README.md"}))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("No language server configured"), "{}", err);
+    assert!(err.contains(".locus/lsp.toml"), "{}", err);
+
+    let outside = tempfile::TempDir::new().unwrap();
+    let path = outside.path().join("outside.rs");
+    std::fs::write(&path, "fn x() {}\n").unwrap();
+    let err = tool
+        .execute(json!({"path": path.to_string_lossy()}))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("outside the repository"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_lsp_diagnostics() {
+    let (workspace, bridge) = setup().await;
+    let tool = LspDiagnostics::new(bridge);
+
+    let result = tool.execute(json!({"path": "src/main.rs"})).await.unwrap();
+    assert_eq!(result["server"], "fake");
+    assert_eq!(result["complete"], true);
+    assert_eq!(result["counts"]["error"], 1);
+    assert_eq!(
+        result["diagnostics"][0],
+        json!({
+            "line": 3,
+            "column": 5,
+            "end_line": 3,
+            "end_column": 8,
+            "severity": "error",
+            "code": "425",
+            "source": "fake",
+            "message": "cannot find function `bad` in this scope",
+        })
+    );
+
+    // Unchanged files answer from what was already published.
+    let again = tool.execute(json!({"path": "src/main.rs"})).await.unwrap();
+    assert_eq!(again["diagnostics"], result["diagnostics"]);
+
+    // Edits on disk are sent before asking.
+    workspace.write("src/main.rs", MAIN.replace("bad(s)", "drop(s)"));
+    let fixed = tool.execute(json!({"path": "src/main.rs"})).await.unwrap();
+    assert_eq!(fixed["complete"], true);
+    assert_eq!(fixed["counts"]["error"], 0);
+    assert_eq!(fixed["diagnostics"], json!([]));
+}
+
+#[tokio::test]
+async fn test_lsp_hover() {
+    let (_workspace, bridge) = setup().await;
+    let tool = LspHover::new(bridge);
+
+    let result = tool
+        .execute(json!({"path": "src/main.rs", "line": 2, "symbol": "greet"}))
+        .await
+        .unwrap();
+    assert_eq!(result["found"], true);
+    assert_eq!(
+        result["contents"],
+        "```rust\nfn greet(name: &str) -> String\n```"
+    );
+    assert_eq!(
+        result["range"],
+        json!({"line": 2, "column": 13, "end_line": 2, "end_column": 18})
+    );
+
+    // Without a symbol or column the first non-blank character is used.
+    let result = tool
+        .execute(json!({"path": "src/main.rs", "line": 2}))
+        .await
+        .unwrap();
+    assert!(result["contents"].as_str().unwrap().contains("fn let"));
+
+    let err = tool
+        .execute(json!({"path": "src/main.rs", "line": 2, "symbol": "missing"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not appear on line 2"));
+}
+
+#[tokio::test]
+async fn test_lsp_rename_dry_run_and_apply() {
+    let (workspace, bridge) = setup().await;
+    let root = workspace.root();
+    let history = Arc::new(EditHistory::load_blocking(root.clone()));
+    let tool = LspRename::new(bridge, Arc::clone(&history));
+    let args = json!({
+        "path": "src/lib.rs",
+        "line": 1,
+        "symbol": "greet",
+        "new_name": "welcome",
+        "dry_run": true,
+    });
+
+    let result = tool.execute(args.clone()).await.unwrap();
+    assert_eq!(result["applied"], false);
+    assert_eq!(result["total_edits"], 2);
+    assert_eq!(
+        result["files"],
+        json!([
+            {"path": "src/lib.rs", "edits": 1, "lines": [1]},
+            {"path": "src/main.rs", "edits": 1, "lines": [2]},
+        ])
+    );
+    assert_eq!(workspace.read("src/lib.rs"), LIB);
+
+    let mut args = args;
+    args["dry_run"] = json!(false);
+    let result = tool.execute(args).await.unwrap();
+    assert_eq!(result["applied"], true);
+    assert!(
+        workspace
+            .read("src/lib.rs")
+            .starts_with("pub fn welcome(name")
+    );
+    assert!(
+        workspace
+            .read("src/main.rs")
+            .contains("let s = welcome(\"ada\");")
+    );
+
+    // Each file's rename can be undone like any other edit.
+    let undone = history.undo(&root.join("src/main.rs")).await.unwrap();
+    assert_eq!(undone.as_deref(), Some(MAIN));
+
+    let err = tool
+        .execute(
+            json!({"path": "src/lib.rs", "line": 1, "symbol": "welcome", "new_name": "two words"}),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("new_name"));
+}
@@ -1,11 +1,32 @@
+#[cfg(feature = "syntax")]
+mod ast_search;
 mod bash;
+#[cfg(feature = "syntax")]
+mod code_nav;
 mod create_file;
+mod deps;
 mod edit_file;
+mod file_history;
 mod finder;
+mod format_code;
+mod git;
 mod glob;
 mod grep;
 mod handoff;
+mod json_query;
+mod lint;
+mod lsp;
+mod notes;
 mod read;
+mod repo_stats;
+#[cfg(feature = "semantic")]
+mod semantic_search;
+mod sqlite_query;
 mod task_list;
+mod todo_scan;
+mod tree;
 mod undo_edit;
+#[cfg(feature = "web")]
 mod web_automation;
+#[cfg(feature = "web")]
+mod web_fetch;
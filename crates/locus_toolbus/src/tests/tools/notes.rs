@@ -0,0 +1,187 @@
+use crate::permission::Permission;
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::{Notes, NotesAction, NotesArgs, Tool, ToolContext};
+use serde_json::{Value, json};
+
+fn session(id: &str) -> ToolContext {
+    ToolContext::new().with_session(id)
+}
+
+async fn call(tool: &Notes, args: Value, ctx: ToolContext) -> Value {
+    tool.execute_with_context(args, ctx).await.unwrap()
+}
+
+async fn error(tool: &Notes, args: Value) -> String {
+    tool.execute_with_context(args, session("s1"))
+        .await
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn test_notes_tool_name_and_args() {
+    let tool = Notes::new(".".into());
+    assert_eq!(tool.name(), "notes");
+    assert_eq!(tool.permission(), Permission::Write);
+    assert_args_round_trip::<NotesArgs>(
+        &tool,
+        json!({ "action": "append", "name": "plan", "content": "- step", "session": "abc" }),
+    );
+    let args = NotesArgs::new(NotesAction::Write)
+        .with_name("plan")
+        .with_content("x");
+    assert_eq!(args.name.as_deref(), Some("plan"));
+}
+
+#[tokio::test]
+async fn test_notes_write_append_read() {
+    let workspace = TestWorkspace::new();
+    let tool = Notes::new(workspace.root());
+
+    let result = call(
+        &tool,
+        json!({ "action": "write", "name": "plan", "content": "# Plan\n1. parser" }),
+        session("s1"),
+    )
+    .await;
+    assert_eq!(result["path"], ".locus/notes/s1/plan.md");
+    assert_eq!(result["bytes"], 16);
+
+    call(
+        &tool,
+        json!({ "action": "append", "name": "plan.md", "content": "2. tests\n" }),
+        session("s1"),
+    )
+    .await;
+    assert_eq!(
+        workspace.read(".locus/notes/s1/plan.md"),
+        "# Plan\n1. parser\n2. tests\n"
+    );
+
+    let result = call(
+        &tool,
+        json!({ "action": "read", "name": "plan" }),
+        session("s1"),
+    )
+    .await;
+    assert_eq!(result["content"], "# Plan\n1. parser\n2. tests\n");
+
+    call(
+        &tool,
+        json!({ "action": "write", "name": "plan", "content": "done" }),
+        session("s1"),
+    )
+    .await;
+    assert_eq!(workspace.read(".locus/notes/s1/plan.md"), "done");
+
+    call(
+        &tool,
+        json!({ "action": "append", "name": "new", "content": "first" }),
+        session("s1"),
+    )
+    .await;
+    assert_eq!(workspace.read(".locus/notes/s1/new.md"), "first");
+}
+
+#[tokio::test]
+async fn test_notes_are_namespaced_per_session() {
+    let workspace = TestWorkspace::new();
+    let tool = Notes::new(workspace.root());
+
+    call(
+        &tool,
+        json!({ "action": "write", "name": "plan", "content": "old plan" }),
+        session("s1"),
+    )
+    .await;
+    call(
+        &tool,
+        json!({ "action": "write", "name": "findings", "content": "\n  cache is stale\nmore" }),
+        session("s2"),
+    )
+    .await;
+    call(
+        &tool,
+        json!({ "action": "write", "name": "b", "content": "x" }),
+        session("s2"),
+    )
+    .await;
+
+    let result = call(&tool, json!({ "action": "list" }), session("s2")).await;
+    assert_eq!(result["session"], "s2");
+    let notes = result["notes"].as_array().unwrap();
+    let names: Vec<&str> = notes.iter().map(|n| n["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["b", "findings"]);
+    assert_eq!(notes[1]["preview"], "cache is stale");
+    assert!(notes[1]["modified"].as_u64().unwrap() > 0);
+
+    assert!(
+        tool.execute_with_context(json!({ "action": "read", "name": "plan" }), session("s2"))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Note not found: plan")
+    );
+    let result = call(
+        &tool,
+        json!({ "action": "read", "name": "plan", "session": "s1" }),
+        session("s2"),
+    )
+    .await;
+    assert_eq!(result["content"], "old plan");
+
+    let result = tool.execute(json!({ "action": "list" })).await.unwrap();
+    assert_eq!(result["session"], "default");
+    assert_eq!(result["notes"], json!([]));
+}
+
+#[tokio::test]
+async fn test_notes_errors() {
+    let workspace = TestWorkspace::new();
+    let tool = Notes::new(workspace.root());
+
+    assert!(
+        error(&tool, json!({ "action": "read" }))
+            .await
+            .contains("name required")
+    );
+    assert!(
+        error(&tool, json!({ "action": "write", "name": "plan" }))
+            .await
+            .contains("content required")
+    );
+    for name in ["../escape", "a/b", ".hidden", ""] {
+        assert!(
+            error(
+                &tool,
+                json!({ "action": "write", "name": name, "content": "x" })
+            )
+            .await
+            .contains("Invalid note name"),
+            "{}",
+            name
+        );
+    }
+    assert!(
+        error(&tool, json!({ "action": "list", "session": "../.." }))
+            .await
+            .contains("Invalid session")
+    );
+
+    let big = "x".repeat(200 * 1024);
+    call(
+        &tool,
+        json!({ "action": "write", "name": "big", "content": big }),
+        session("s1"),
+    )
+    .await;
+    assert!(
+        error(
+            &tool,
+            json!({ "action": "append", "name": "big", "content": big })
+        )
+        .await
+        .contains("would exceed")
+    );
+    assert!(!workspace.root().join("../escape.md").exists());
+}
@@ -156,3 +156,29 @@ fn test_read_tool_bus_integration() {
         assert_eq!(result["content"], "from bus");
     });
 }
+
+#[test]
+fn test_read_protected_path() {
+    let rt = runtime();
+    rt.block_on(async {
+        let temp_dir = TempDir::new().unwrap();
+        tokio::fs::write(temp_dir.path().join(".env"), "API_KEY=secret")
+            .await
+            .unwrap();
+        tokio::fs::write(temp_dir.path().join("deploy.key"), "-----BEGIN")
+            .await
+            .unwrap();
+
+        let tool = Read::new(temp_dir.path().to_path_buf());
+        let err = tool.execute(json!({ "path": "./.env" })).await.unwrap_err();
+        let denied = err.downcast_ref::<crate::ProtectedPathDenied>().unwrap();
+        assert_eq!(denied.pattern, ".env");
+        assert_eq!(denied.path, "./.env");
+
+        // Configured patterns come from [protected_paths].
+        let tool = Read::new(temp_dir.path().to_path_buf())
+            .with_protected_paths(crate::ProtectedPaths::new().with_deny("*.key").matcher());
+        assert!(tool.execute(json!({ "path": "deploy.key" })).await.is_err());
+        assert!(tool.execute(json!({ "path": ".env" })).await.is_err());
+    });
+}
@@ -0,0 +1,136 @@
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::repo_stats::languages::{count_lines, language_for};
+use crate::tools::{LineCounts, RepoStats, RepoStatsArgs, Tool};
+use serde_json::json;
+
+#[test]
+fn test_repo_stats_tool_name_and_args() {
+    let tool = RepoStats::new(".".into());
+    assert_eq!(tool.name(), "repo_stats");
+    assert!(tool.description().contains("tokei"));
+    assert_args_round_trip::<RepoStatsArgs>(
+        &tool,
+        json!({ "path": "crates", "depth": 2, "largest": 5 }),
+    );
+    let args = RepoStatsArgs::new();
+    assert_eq!((args.depth, args.largest), (1, 10));
+}
+
+#[test]
+fn test_language_for_file_names() {
+    let name = |file: &str| language_for(file).map(|l| l.name);
+    assert_eq!(name("main.rs"), Some("Rust"));
+    assert_eq!(name("App.TSX"), Some("TypeScript"));
+    assert_eq!(name("Dockerfile"), Some("Dockerfile"));
+    assert_eq!(name("Makefile"), Some("Makefile"));
+    assert_eq!(name("package.json"), Some("JSON"));
+    assert_eq!(name("package-lock.json"), None, "lockfiles skipped");
+    assert_eq!(name("Cargo.lock"), None);
+    assert_eq!(name("LICENSE"), None);
+}
+
+#[test]
+fn test_count_lines_splits_code_comments_blanks() {
+    let rust = language_for("lib.rs").unwrap();
+    let source = "//! Crate docs.\n\nfn main() {\n    /* one-line block */\n    let x = 1; // trailing\n    /*\n     * multi-line\n     */\n    let y = 2; /* opens\n    still comment */\n}\n";
+    assert_eq!(
+        count_lines(rust, source),
+        LineCounts {
+            code: 4,
+            comment: 6,
+            blank: 1
+        }
+    );
+
+    let lua = language_for("init.lua").unwrap();
+    let source = "--[[\nblock\n]]\n-- line\nprint(1)\n";
+    assert_eq!(
+        count_lines(lua, source),
+        LineCounts {
+            code: 1,
+            comment: 4,
+            blank: 0
+        }
+    );
+
+    let python = language_for("app.py").unwrap();
+    let source = "#!/usr/bin/env python3\nimport os  # why\n\n\nprint(os.name)\n";
+    assert_eq!(
+        count_lines(python, source),
+        LineCounts {
+            code: 2,
+            comment: 1,
+            blank: 2
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_repo_stats_counts_languages_directories_and_largest() {
+    let workspace = TestWorkspace::new()
+        .with_file(".gitignore", "target/\n")
+        .with_file("README.md", "# Demo\n\nSome text.\n")
+        .with_file("Cargo.lock", "# generated\n")
+        .with_file(
+            "src/main.rs",
+            "// entry\nfn main() {\n    demo::run();\n}\n",
+        )
+        .with_file("src/lib.rs", "pub fn run() {}\n")
+        .with_file(
+            "src/util/mod.rs",
+            "pub fn helper() {}\n\npub fn other() {}\n",
+        )
+        .with_file("web/app.ts", "export const a = 1;\nexport const b = 2;\n")
+        .with_file("target/debug/gen.rs", "fn ignored() {}\n");
+    let tool = RepoStats::new(workspace.root());
+
+    let result = tool.execute(json!({})).await.unwrap();
+    assert_eq!(result["path"], ".");
+    assert_eq!(
+        result["files"], 6,
+        "hidden files and gitignored target/ not walked"
+    );
+    assert_eq!(result["source_files"], 5, "Cargo.lock not counted");
+    assert_eq!(
+        result["lines"],
+        json!({ "code": 10, "comment": 1, "blank": 2 })
+    );
+    assert_eq!(
+        result["languages"],
+        json!([
+            { "language": "Rust", "files": 3, "code": 6, "comment": 1, "blank": 1 },
+            { "language": "Markdown", "files": 1, "code": 2, "comment": 0, "blank": 1 },
+            { "language": "TypeScript", "files": 1, "code": 2, "comment": 0, "blank": 0 },
+        ])
+    );
+    let directories: Vec<(&str, u64)> = result["directories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| (d["path"].as_str().unwrap(), d["code"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(directories, [("src", 6), (".", 2), ("web", 2)]);
+    assert_eq!(result["largest_files"][0]["path"], "src/main.rs");
+    assert_eq!(result["largest_files"][0]["lines"], 4);
+
+    let result = tool
+        .execute(json!({ "path": "src", "depth": 2, "largest": 1 }))
+        .await
+        .unwrap();
+    assert_eq!(result["path"], "src");
+    let directories: Vec<&str> = result["directories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(directories, ["src", "src/util"]);
+    assert_eq!(result["largest_files"].as_array().unwrap().len(), 1);
+
+    let err = tool
+        .execute(json!({ "path": "missing" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("does not exist"), "got: {}", err);
+}
@@ -0,0 +1,217 @@
+use crate::semantic::embed::{parse_embeddings, words};
+use crate::semantic::{INDEX_DB_FILE, LexicalEmbedder, SemanticError, SemanticIndex, chunk_file};
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::{SemanticSearch, SemanticSearchArgs, Tool};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+const AUTH: &str = r#"use std::time::Duration;
+use crate::http::Client;
+
+/// Exchange the refresh token for a new access token before it expires.
+pub async fn refresh_jwt_token(client: &Client, refresh_token: &str) -> Result<String> {
+    let response = client.post("/oauth/token", refresh_token).await?;
+    Ok(response.access_token)
+}
+
+pub struct Session {
+    pub user_id: u64,
+    pub expires_in: Duration,
+}
+"#;
+
+const DB: &str = r#"/// Open a pooled connection to the Postgres database.
+pub fn open_connection(url: &str) -> Pool {
+    Pool::connect(url)
+}
+"#;
+
+fn workspace() -> TestWorkspace {
+    TestWorkspace::new()
+        .with_file("src/auth.rs", AUTH)
+        .with_file("src/db.rs", DB)
+        .with_file(
+            "README.md",
+            "# Billing service\n\nCharges customers every month and emails invoices.\n",
+        )
+}
+
+fn tool(workspace: &TestWorkspace) -> SemanticSearch {
+    let root = workspace.root();
+    let index = SemanticIndex::with_embedder(root.clone(), Arc::new(LexicalEmbedder::new()));
+    SemanticSearch::with_index(root, Arc::new(index))
+}
+
+fn results(value: &Value) -> &Vec<Value> {
+    value["results"].as_array().unwrap()
+}
+
+#[test]
+fn test_semantic_search_tool_name() {
+    let workspace = TestWorkspace::new();
+    let tool = tool(&workspace);
+    assert_eq!(tool.name(), "semantic_search");
+    assert_args_round_trip::<SemanticSearchArgs>(
+        &tool,
+        json!({"query": "where do we refresh JWTs", "path": "src", "max_results": 5}),
+    );
+}
+
+#[test]
+fn test_chunk_file_keeps_docs_with_items() {
+    let chunks = chunk_file("src/auth.rs", AUTH);
+    let summary: Vec<_> = chunks
+        .iter()
+        .map(|c| (c.start_line, c.end_line, c.symbol.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, 2, None),
+            (4, 8, Some("refresh_jwt_token")),
+            (10, 13, Some("Session")),
+        ]
+    );
+    assert!(chunks[1].text.starts_with("/// Exchange the refresh token"));
+}
+
+#[test]
+fn test_chunk_file_splits_long_text_into_windows() {
+    let text: String = (1..=130).map(|n| format!("line {}\n", n)).collect();
+    let chunks = chunk_file("notes.md", &text);
+    let spans: Vec<_> = chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+    assert_eq!(spans, vec![(1, 60), (61, 120), (121, 130)]);
+    assert!(chunks.iter().all(|c| c.symbol.is_none()));
+}
+
+#[test]
+fn test_words_split_identifiers_and_fold_plurals() {
+    assert_eq!(words("refreshJwtToken"), vec!["refresh", "jwt", "token"]);
+    assert_eq!(words("where do we refresh JWTs?"), vec!["refresh", "jwt"]);
+    assert_eq!(
+        words("open_connection(url) -> sha256"),
+        vec!["open", "connection", "url", "sha"]
+    );
+}
+
+#[test]
+fn test_parse_embeddings_orders_by_index() {
+    let body = json!({
+        "data": [
+            {"index": 1, "embedding": [0.0, 2.0]},
+            {"index": 0, "embedding": [3.0, 4.0]},
+        ]
+    });
+    let vectors = parse_embeddings(&body, 2).unwrap();
+    assert_eq!(vectors, vec![vec![0.6, 0.8], vec![0.0, 1.0]]);
+    assert!(matches!(
+        parse_embeddings(&body, 3),
+        Err(SemanticError::EmbeddingCount {
+            expected: 3,
+            got: 2
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_semantic_search_finds_code_without_exact_names() {
+    let workspace = workspace();
+    let tool = tool(&workspace);
+
+    let result = tool
+        .execute(json!({"query": "where do we refresh JWTs"}))
+        .await
+        .unwrap();
+    let top = &results(&result)[0];
+    assert_eq!(top["path"], "src/auth.rs");
+    assert_eq!(top["symbol"], "refresh_jwt_token");
+    assert_eq!(top["start_line"], 4);
+    assert!(
+        top["snippet"]
+            .as_str()
+            .unwrap()
+            .contains("refresh_jwt_token")
+    );
+    assert_eq!(result["embedder"], "lexical-512");
+    assert_eq!(
+        result["index"],
+        json!({"files": 3, "chunks": 5, "updated": 3, "removed": 0})
+    );
+    assert!(workspace.exists(INDEX_DB_FILE));
+
+    let result = tool
+        .execute(json!({"query": "database connection pool", "max_results": 1}))
+        .await
+        .unwrap();
+    assert_eq!(results(&result).len(), 1);
+    assert_eq!(results(&result)[0]["symbol"], "open_connection");
+}
+
+#[tokio::test]
+async fn test_semantic_search_reindexes_only_changed_files() {
+    let workspace = workspace();
+    let tool = tool(&workspace);
+    let query = json!({"query": "send invoice emails"});
+
+    let first = tool.execute(query.clone()).await.unwrap();
+    assert_eq!(first["index"]["updated"], 3);
+
+    let again = tool.execute(query.clone()).await.unwrap();
+    assert_eq!(again["index"]["updated"], 0);
+
+    workspace.write(
+        "src/db.rs",
+        "/// Send the monthly invoice emails.\npub fn send_invoice_emails() {}\n",
+    );
+    std::fs::remove_file(workspace.path().join("README.md")).unwrap();
+    let changed = tool.execute(query).await.unwrap();
+    assert_eq!(
+        changed["index"],
+        json!({"files": 2, "chunks": 4, "updated": 1, "removed": 1})
+    );
+    assert_eq!(results(&changed)[0]["symbol"], "send_invoice_emails");
+}
+
+#[tokio::test]
+async fn test_semantic_search_rebuilds_for_a_new_embedder() {
+    let workspace = workspace();
+    tool(&workspace)
+        .execute(json!({"query": "session"}))
+        .await
+        .unwrap();
+
+    let root = workspace.root();
+    let index =
+        SemanticIndex::with_embedder(root.clone(), Arc::new(LexicalEmbedder::with_dimensions(64)));
+    let result = SemanticSearch::with_index(root, Arc::new(index))
+        .execute(json!({"query": "session"}))
+        .await
+        .unwrap();
+    assert_eq!(result["embedder"], "lexical-64");
+    assert_eq!(result["index"]["updated"], 3);
+}
+
+#[tokio::test]
+async fn test_semantic_search_path_filter_and_errors() {
+    let workspace = workspace();
+    let tool = tool(&workspace);
+
+    let result = tool
+        .execute(json!({"query": "billing customers", "path": "src"}))
+        .await
+        .unwrap();
+    assert!(
+        results(&result)
+            .iter()
+            .all(|r| r["path"].as_str().unwrap().starts_with("src/"))
+    );
+
+    let err = tool
+        .execute(json!({"query": "x", "path": "missing"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Path does not exist"));
+
+    let err = tool.execute(json!({"query": "  "})).await.unwrap_err();
+    assert!(err.to_string().contains("query must not be empty"));
+}
@@ -0,0 +1,224 @@
+use crate::testing::{TestWorkspace, assert_args_round_trip};
+use crate::tools::{SqliteQuery, SqliteQueryArgs, Tool};
+use serde_json::{Value, json};
+
+/// A workspace with `fixtures/app.db`: a `users` table of three rows.
+fn workspace() -> TestWorkspace {
+    let workspace = TestWorkspace::new().with_file("notes.txt", "not a database\n");
+    std::fs::create_dir_all(workspace.root().join("fixtures")).unwrap();
+    let conn = rusqlite::Connection::open(workspace.root().join("fixtures/app.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL, avatar BLOB);
+         INSERT INTO users VALUES (1, 'ada', 9.5, x'89504e47');
+         INSERT INTO users VALUES (2, 'linus', NULL, NULL);
+         INSERT INTO users VALUES (3, 'grace', 7.25, NULL);",
+    )
+    .unwrap();
+    workspace
+}
+
+async fn error(tool: &SqliteQuery, args: Value) -> String {
+    tool.execute(args).await.unwrap_err().to_string()
+}
+
+#[test]
+fn test_sqlite_query_tool_name_and_args() {
+    let tool = SqliteQuery::new(".".into());
+    assert_eq!(tool.name(), "sqlite_query");
+    assert!(tool.description().contains("read-only"));
+    assert_args_round_trip::<SqliteQueryArgs>(
+        &tool,
+        json!({ "path": "app.db", "query": "SELECT ?", "params": [1, "a"], "max_rows": 5, "max_bytes": 100 }),
+    );
+    let args = SqliteQueryArgs::new("app.db", "SELECT 1");
+    assert_eq!((args.max_rows, args.max_bytes), (100, 32 * 1024));
+}
+
+#[tokio::test]
+async fn test_sqlite_query_returns_rows() {
+    let workspace = workspace();
+    let tool = SqliteQuery::new(workspace.root());
+
+    let result = tool
+        .execute(json!({
+            "path": "fixtures/app.db",
+            "query": "SELECT id, name, score, avatar FROM users ORDER BY id",
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result["columns"], json!(["id", "name", "score", "avatar"]));
+    assert_eq!(
+        result["rows"],
+        json!([
+            [1, "ada", 9.5, "x'89504e47' (4 bytes)"],
+            [2, "linus", null, null],
+            [3, "grace", 7.25, null]
+        ])
+    );
+    assert_eq!(result["truncated"], false);
+
+    let result = tool
+        .execute(json!({
+            "path": "./fixtures/app.db",
+            "query": "SELECT name FROM users WHERE score > ? AND name <> ?",
+            "params": [8, "linus"],
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result["rows"], json!([["ada"]]));
+
+    let result = tool
+        .execute(json!({
+            "path": "fixtures/app.db",
+            "query": "SELECT name FROM sqlite_schema WHERE type = 'table'",
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result["rows"], json!([["users"]]));
+
+    let result = tool
+        .execute(json!({ "path": "fixtures/app.db", "query": "PRAGMA table_info(users)" }))
+        .await
+        .unwrap();
+    assert_eq!(result["rows"].as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_sqlite_query_limits() {
+    let workspace = workspace();
+    let tool = SqliteQuery::new(workspace.root());
+
+    let result = tool
+        .execute(json!({
+            "path": "fixtures/app.db",
+            "query": "SELECT id FROM users ORDER BY id",
+            "max_rows": 2,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result["rows"], json!([[1], [2]]));
+    assert_eq!(result["truncated"], true);
+
+    let result = tool
+        .execute(json!({
+            "path": "fixtures/app.db",
+            "query": "SELECT id FROM users ORDER BY id",
+            "max_bytes": 8,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(
+        result["rows"],
+        json!([[1], [2]]),
+        "each row is 3 bytes of JSON"
+    );
+    assert_eq!(result["truncated"], true);
+
+    let result = tool
+        .execute(json!({ "path": "fixtures/app.db", "query": "SELECT hex(zeroblob(3000))" }))
+        .await
+        .unwrap();
+    let text = result["rows"][0][0].as_str().unwrap();
+    assert_eq!(text.chars().count(), 2001);
+    assert!(text.ends_with('…'));
+}
+
+#[tokio::test]
+async fn test_sqlite_query_is_read_only() {
+    let workspace = workspace();
+    let tool = SqliteQuery::new(workspace.root());
+
+    for query in [
+        "DELETE FROM users",
+        "INSERT INTO users (name) VALUES ('eve')",
+        "CREATE TABLE t (x)",
+        "PRAGMA user_version = 7",
+    ] {
+        let message = error(&tool, json!({ "path": "fixtures/app.db", "query": query })).await;
+        assert!(
+            message.contains("read-only") || message.contains("readonly"),
+            "{}: {}",
+            query,
+            message
+        );
+    }
+    assert!(
+        error(
+            &tool,
+            json!({ "path": "fixtures/app.db", "query": "SELECT 1; DELETE FROM users" })
+        )
+        .await
+        .contains("one statement")
+    );
+    assert!(
+        error(
+            &tool,
+            json!({ "path": "fixtures/app.db", "query": "ATTACH DATABASE 'other.db' AS other" })
+        )
+        .await
+        .contains("SQLite error")
+    );
+
+    let conn = rusqlite::Connection::open(workspace.root().join("fixtures/app.db")).unwrap();
+    let count: i64 = conn
+        .query_row("SELECT count(*) FROM users", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 3);
+}
+
+#[tokio::test]
+async fn test_sqlite_query_errors() {
+    let workspace = workspace();
+    let tool = SqliteQuery::new(workspace.root());
+
+    assert!(
+        error(&tool, json!({ "path": "missing.db", "query": "SELECT 1" }))
+            .await
+            .contains("does not exist")
+    );
+    assert!(
+        error(
+            &tool,
+            json!({ "path": "notes.txt", "query": "SELECT * FROM t" })
+        )
+        .await
+        .contains("Not a SQLite database")
+    );
+    assert!(
+        error(
+            &tool,
+            json!({ "path": "fixtures/app.db", "query": "  -- nothing\n" })
+        )
+        .await
+        .contains("empty")
+    );
+    assert!(
+        error(
+            &tool,
+            json!({ "path": "fixtures/app.db", "query": "SELECT * FROM nope" })
+        )
+        .await
+        .contains("no such table")
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_query_reads_wal_database_in_use() {
+    let workspace = TestWorkspace::new();
+    std::fs::create_dir_all(workspace.root().join(".locus")).unwrap();
+    let writer = rusqlite::Connection::open(workspace.root().join(".locus/locus.db")).unwrap();
+    writer
+        .execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE config (key TEXT PRIMARY KEY, value TEXT);
+             INSERT INTO config VALUES ('theme', 'dark');",
+        )
+        .unwrap();
+    let tool = SqliteQuery::new(workspace.root());
+
+    let result = tool
+        .execute(json!({ "path": ".locus/locus.db", "query": "SELECT value FROM config" }))
+        .await
+        .unwrap();
+    assert_eq!(result["rows"], json!([["dark"]]));
+}
@@ -92,6 +92,44 @@ fn test_task_list_create_and_list() {
     });
 }
 
+#[test]
+fn test_task_list_survives_restart() {
+    let rt = runtime();
+    rt.block_on(async {
+        let (dir, tool) = temp_tool();
+        tool.execute(json!({
+            "action": "create",
+            "plan_id": "issue-7",
+            "tasks": [{ "title": "Write parser" }, { "title": "Add tests" }]
+        }))
+        .await
+        .unwrap();
+        tool.execute(json!({
+            "action": "update",
+            "plan_id": "issue-7",
+            "task_id": "t1",
+            "status": "done"
+        }))
+        .await
+        .unwrap();
+        drop(tool);
+
+        // A new process opens the same .locus/locus.db.
+        let tool = TaskList::new(dir.path().to_path_buf());
+        let list = tool
+            .execute(json!({ "action": "list", "plan_id": "issue-7" }))
+            .await
+            .unwrap();
+        assert_eq!(list["tasks"][0]["status"], "done");
+        assert_eq!(list["tasks"][1]["title"], "Add tests");
+        assert!(dir.path().join(".locus").join("locus.db").is_file());
+
+        let plans = locus_core::db::plans(dir.path()).unwrap();
+        assert_eq!(plans.len(), 1);
+        assert_eq!((plans[0].tasks, plans[0].done), (2, 1));
+    });
+}
+
 #[test]
 fn test_task_list_get_update_remove() {
     let rt = runtime();
@@ -209,3 +247,133 @@ fn test_task_list_tool_bus_integration() {
         assert_eq!(result["tasks"].as_array().unwrap().len(), 1);
     });
 }
+
+#[test]
+fn test_task_list_check_criterion_with_evidence() {
+    let rt = runtime();
+    rt.block_on(async {
+        let (_dir, tool) = temp_tool();
+        let created = tool
+            .execute(json!({
+                "action": "create",
+                "tasks": [{
+                    "title": "Fix parser",
+                    "criteria": [{ "text": "tests pass" }, { "text": "no new warnings" }]
+                }]
+            }))
+            .await
+            .unwrap();
+        let task = &created["tasks"][0];
+        let task_id = task["id"].as_str().unwrap();
+        assert_eq!(task["criteria"][0]["id"], "c1");
+        assert_eq!(task["criteria"][1]["id"], "c2");
+        assert_eq!(task["criteria"][0]["met"], false);
+
+        let missing_evidence = tool
+            .execute(json!({ "action": "check", "task_id": task_id, "criterion_id": "c1" }))
+            .await;
+        assert!(
+            missing_evidence
+                .unwrap_err()
+                .to_string()
+                .contains("evidence")
+        );
+
+        let checked = tool
+            .execute(json!({
+                "action": "check",
+                "task_id": task_id,
+                "criterion_id": "c1",
+                "evidence": ["tool:call_42", "cargo test: 12 passed"]
+            }))
+            .await
+            .unwrap();
+        assert_eq!(checked["criteria"][0]["met"], true);
+        assert_eq!(checked["criteria"][0]["evidence"][0], "tool:call_42");
+        assert_eq!(checked["criteria"][1]["met"], false);
+
+        let unknown = tool
+            .execute(json!({
+                "action": "check",
+                "task_id": task_id,
+                "criterion_id": "c9",
+                "evidence": ["x"]
+            }))
+            .await;
+        assert!(
+            unknown
+                .unwrap_err()
+                .to_string()
+                .contains("Criterion not found")
+        );
+    });
+}
+
+#[test]
+fn test_task_list_strict_mode_requires_met_criteria() {
+    let rt = runtime();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = TaskList::new(dir.path().to_path_buf()).with_strict(true);
+        tool.execute(json!({
+            "action": "create",
+            "tasks": [
+                { "id": "a", "title": "No checklist" },
+                { "id": "b", "title": "Checked", "criteria": [{ "text": "builds" }] }
+            ]
+        }))
+        .await
+        .unwrap();
+
+        let no_criteria = tool
+            .execute(json!({ "action": "update", "task_id": "a", "status": "done" }))
+            .await;
+        assert!(
+            no_criteria
+                .unwrap_err()
+                .to_string()
+                .contains("no acceptance criteria")
+        );
+
+        let unmet = tool
+            .execute(json!({ "action": "update", "task_id": "b", "status": "done" }))
+            .await;
+        assert!(
+            unmet
+                .unwrap_err()
+                .to_string()
+                .contains("unmet acceptance criteria: c1")
+        );
+
+        tool.execute(json!({
+            "action": "check",
+            "task_id": "b",
+            "criterion_id": "c1",
+            "evidence": ["cargo build ok"]
+        }))
+        .await
+        .unwrap();
+        let done = tool
+            .execute(json!({ "action": "update", "task_id": "b", "status": "done" }))
+            .await
+            .unwrap();
+        assert_eq!(done["status"], "done");
+    });
+}
+
+#[test]
+fn test_task_list_non_strict_allows_done_without_criteria() {
+    let rt = runtime();
+    rt.block_on(async {
+        let (_dir, tool) = temp_tool();
+        tool.execute(json!({ "action": "create", "tasks": [{ "id": "a", "title": "A" }] }))
+            .await
+            .unwrap();
+        let done = tool
+            .execute(json!({ "action": "update", "task_id": "a", "status": "done" }))
+            .await
+            .unwrap();
+        assert_eq!(done["status"], "done");
+        assert!(done.get("criteria").is_none());
+    });
+}
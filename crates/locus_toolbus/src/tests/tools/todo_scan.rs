@@ -0,0 +1,175 @@
+use crate::tools::{TaskList, TodoScan, Tool};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn create_test_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+
+    fs::create_dir_all(dir.path().join("src/payments")).unwrap();
+    fs::write(
+        dir.path().join("src/payments/charge.rs"),
+        "fn charge() {\n    // FIXME(ana): retry on timeout\n    let todo = \"TODO list\";\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("src/lib.rs"),
+        "// TODO: split this module\n/* HACK: remove after v2 */\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("build.sh"), "# TODO cache artifacts\n").unwrap();
+    fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+    fs::create_dir(dir.path().join("target")).unwrap();
+    fs::write(dir.path().join("target/gen.rs"), "// TODO: generated\n").unwrap();
+
+    dir
+}
+
+/// Commit everything in `dir` with the given author date (unix seconds).
+fn git_commit(dir: &Path, message: &str, date: i64) -> bool {
+    let date = format!("@{} +0000", date);
+    let run = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Ada")
+            .env("GIT_AUTHOR_EMAIL", "ada@example.com")
+            .env("GIT_COMMITTER_NAME", "Ada")
+            .env("GIT_COMMITTER_EMAIL", "ada@example.com")
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_DATE", &date)
+            .output()
+            .is_ok_and(|o| o.status.success())
+    };
+    (dir.join(".git").exists() || run(&["init", "-q"]))
+        && run(&["add", "-A"])
+        && run(&["commit", "-q", "-m", message])
+}
+
+fn items(result: &Value) -> &Vec<Value> {
+    result["items"].as_array().unwrap()
+}
+
+#[test]
+fn test_todo_scan_tool_name() {
+    let tool = TodoScan::new(".".into());
+    assert_eq!(tool.name(), "todo_scan");
+    assert!(tool.description().contains("TODO/FIXME/HACK"));
+}
+
+#[tokio::test]
+async fn test_todo_scan_collects_tagged_comments() {
+    let dir = create_test_repo();
+    let tool = TodoScan::new(dir.path().to_path_buf());
+
+    let result = tool.execute(json!({ "blame": false })).await.unwrap();
+    let found = items(&result);
+
+    assert_eq!(
+        result["total"], 4,
+        "gitignored and non-comment matches skipped"
+    );
+    assert_eq!(result["by_tag"]["TODO"], 2);
+    assert_eq!(result["by_tag"]["FIXME"], 1);
+    assert_eq!(result["by_tag"]["HACK"], 1);
+    assert_eq!(result["blamed"], false);
+
+    let fixme = found.iter().find(|i| i["tag"] == "FIXME").unwrap();
+    assert_eq!(fixme["path"], "src/payments/charge.rs");
+    assert_eq!(fixme["line"], 2);
+    assert_eq!(fixme["owner"], "ana");
+    assert_eq!(fixme["text"], "retry on timeout");
+
+    let hack = found.iter().find(|i| i["tag"] == "HACK").unwrap();
+    assert_eq!(hack["text"], "remove after v2");
+}
+
+#[tokio::test]
+async fn test_todo_scan_path_and_tags_filter() {
+    let dir = create_test_repo();
+    let tool = TodoScan::new(dir.path().to_path_buf());
+
+    let result = tool
+        .execute(json!({ "path": "src/payments", "tags": ["FIXME"], "blame": false }))
+        .await
+        .unwrap();
+    assert_eq!(result["total"], 1);
+
+    let err = tool
+        .execute(json!({ "path": "../" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("outside repository"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_todo_scan_blame_sorts_oldest_first() {
+    let dir = create_test_repo();
+    if !git_commit(dir.path(), "init", 1_600_000_000) {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    fs::write(
+        dir.path().join("src/payments/refund.rs"),
+        "// FIXME: refunds are not idempotent\n",
+    )
+    .unwrap();
+    assert!(git_commit(dir.path(), "refunds", 1_700_000_000));
+    fs::write(dir.path().join("src/wip.rs"), "// TODO: uncommitted\n").unwrap();
+
+    let tool = TodoScan::new(dir.path().to_path_buf());
+    let result = tool
+        .execute(json!({ "tags": ["FIXME"], "sort": "newest" }))
+        .await
+        .unwrap();
+    let found = items(&result);
+    assert_eq!(result["blamed"], true);
+    assert_eq!(found[0]["path"], "src/payments/refund.rs");
+    assert_eq!(found[0]["author"], "Ada");
+    assert_eq!(found[0]["author_time"], 1_700_000_000);
+
+    let result = tool
+        .execute(json!({ "sort": "oldest", "max_results": 10 }))
+        .await
+        .unwrap();
+    let found = items(&result);
+    assert_eq!(found[0]["author_time"], 1_600_000_000);
+    assert!(found[0]["age_days"].as_u64().unwrap() > 365);
+    let last = found.last().unwrap();
+    assert_eq!(last["path"], "src/wip.rs");
+    assert!(
+        last.get("author").is_none(),
+        "uncommitted lines have no author"
+    );
+}
+
+#[tokio::test]
+async fn test_todo_scan_feeds_task_list() {
+    let dir = create_test_repo();
+    let tool = TodoScan::new(dir.path().to_path_buf());
+
+    let args = json!({ "path": "src", "blame": false, "plan_id": "triage" });
+    let result = tool.execute(args.clone()).await.unwrap();
+    assert_eq!(result["tasks_added"].as_array().unwrap().len(), 3);
+
+    let again = tool.execute(args).await.unwrap();
+    assert!(
+        again.get("tasks_added").is_none(),
+        "existing tasks are not duplicated"
+    );
+
+    let tasks = TaskList::new(dir.path().to_path_buf())
+        .execute(json!({ "action": "list", "plan_id": "triage" }))
+        .await
+        .unwrap();
+    let tasks = tasks["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert!(tasks.iter().any(
+        |t| t["title"] == "FIXME src/payments/charge.rs:2: retry on timeout"
+            && t["status"] == "pending"
+            && t["description"] == "owner ana"
+    ));
+}
@@ -0,0 +1,179 @@
+use crate::tools::{Tool, Tree};
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_test_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+
+    fs::write(dir.path().join("README.md"), "# test\n").unwrap();
+    fs::write(dir.path().join(".gitignore"), "target/\n*.log\n").unwrap();
+    fs::write(dir.path().join("debug.log"), "noise").unwrap();
+
+    fs::create_dir_all(dir.path().join("src/utils/deep")).unwrap();
+    fs::write(dir.path().join("src/lib.rs"), "pub fn add() {}").unwrap();
+    fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(dir.path().join("src/utils/helper.rs"), "pub fn greet() {}").unwrap();
+    fs::write(dir.path().join("src/utils/deep/inner.rs"), "// inner").unwrap();
+
+    fs::create_dir(dir.path().join("target")).unwrap();
+    fs::write(dir.path().join("target/app.bin"), "binary").unwrap();
+
+    dir
+}
+
+fn child<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node["children"]
+        .as_array()?
+        .iter()
+        .find(|c| c["name"] == name)
+}
+
+#[tokio::test]
+async fn test_tree_basic_structure() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({})).await.unwrap();
+    let root = &result["root"];
+
+    assert_eq!(root["type"], "dir");
+    assert_eq!(root["path"], ".");
+
+    let src = child(root, "src").expect("src dir should be listed");
+    assert_eq!(src["type"], "dir");
+    assert_eq!(src["file_count"], 2);
+    assert_eq!(src["dir_count"], 1);
+
+    let main_rs = child(src, "main.rs").unwrap();
+    assert_eq!(main_rs["type"], "file");
+    assert_eq!(main_rs["path"], "src/main.rs");
+    assert_eq!(main_rs["size_bytes"], 13);
+}
+
+#[tokio::test]
+async fn test_tree_respects_gitignore() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({})).await.unwrap();
+    let root = &result["root"];
+
+    assert!(child(root, "target").is_none(), "target/ is gitignored");
+    assert!(child(root, "debug.log").is_none(), "*.log is gitignored");
+    assert!(child(root, ".gitignore").is_none(), "hidden files skipped");
+
+    let result = tree
+        .execute(json!({ "respect_gitignore": false, "include_hidden": true }))
+        .await
+        .unwrap();
+    let root = &result["root"];
+
+    assert!(child(root, "target").is_some());
+    assert!(child(root, "debug.log").is_some());
+    assert!(child(root, ".gitignore").is_some());
+}
+
+#[tokio::test]
+async fn test_tree_max_depth_collapses_with_counts() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "max_depth": 1 })).await.unwrap();
+    let src = child(&result["root"], "src").unwrap();
+
+    assert!(src.get("children").is_none(), "src should be collapsed");
+    assert_eq!(src["file_count"], 2);
+    assert_eq!(src["dir_count"], 1);
+    assert_eq!(result["max_depth"], 1);
+}
+
+#[tokio::test]
+async fn test_tree_directory_sizes_roll_up() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "path": "src/utils" })).await.unwrap();
+    let root = &result["root"];
+
+    assert_eq!(root["path"], "src/utils");
+    // helper.rs (17) + deep/inner.rs (8)
+    assert_eq!(root["size_bytes"], 25);
+    assert_eq!(result["total_size_bytes"], 25);
+    assert_eq!(result["total_files"], 2);
+    assert_eq!(result["total_dirs"], 1);
+
+    let deep = child(root, "deep").unwrap();
+    assert_eq!(deep["path"], "src/utils/deep");
+    assert_eq!(deep["size_bytes"], 8);
+}
+
+#[tokio::test]
+async fn test_tree_dirs_listed_before_files() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "path": "src" })).await.unwrap();
+    let names: Vec<&str> = result["root"]["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(names, vec!["utils", "lib.rs", "main.rs"]);
+}
+
+#[tokio::test]
+async fn test_tree_max_entries_truncates() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "max_entries": 2 })).await.unwrap();
+
+    assert_eq!(result["truncated"], true);
+    assert_eq!(
+        result["total_files"].as_u64().unwrap() + result["total_dirs"].as_u64().unwrap(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_tree_path_not_found() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "path": "missing" })).await;
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Path does not exist")
+    );
+}
+
+#[tokio::test]
+async fn test_tree_rejects_file_path() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().to_path_buf());
+
+    let result = tree.execute(json!({ "path": "README.md" })).await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Not a directory"));
+}
+
+#[tokio::test]
+async fn test_tree_rejects_path_outside_repo() {
+    let dir = create_test_repo();
+    let tree = Tree::new(dir.path().join("src"));
+
+    let result = tree.execute(json!({ "path": ".." })).await;
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("outside repository")
+    );
+}
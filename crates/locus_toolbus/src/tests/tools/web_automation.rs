@@ -1,5 +1,45 @@
-use crate::tools::{Tool, WebAutomation, WebAutomationArgs};
+use crate::tools::web_automation::progress_message;
+use crate::tools::{ProgressSink, Tool, ToolContext, WebAutomation, WebAutomationArgs};
 use serde_json::json;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+/// Serve one run-sse request, writing `events` as separate chunks; returns the
+/// base URL.
+fn serve_sse(events: &'static [&'static str]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end().to_ascii_lowercase();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        stream
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n",
+            )
+            .unwrap();
+        for event in events {
+            stream.write_all(event.as_bytes()).unwrap();
+            stream.flush().unwrap();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    });
+    format!("http://{}", addr)
+}
 
 #[test]
 fn test_web_automation_tool_name() {
@@ -97,3 +137,60 @@ fn test_web_automation_tool_bus_integration() {
     let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
     assert!(names.contains(&"web_automation"));
 }
+
+#[test]
+fn test_web_automation_progress_messages() {
+    let started = json!({"type": "STARTED", "runId": "r1"});
+    assert_eq!(
+        progress_message(&started).as_deref(),
+        Some("Browser session started")
+    );
+    let live = json!({"type": "STREAMING_URL", "streamingUrl": "https://live/r1"});
+    assert_eq!(
+        progress_message(&live).as_deref(),
+        Some("Live browser view: https://live/r1")
+    );
+    let step = json!({"type": "PROGRESS", "purpose": "Filling the search form"});
+    assert_eq!(
+        progress_message(&step).as_deref(),
+        Some("Filling the search form")
+    );
+    assert_eq!(progress_message(&json!({"type": "HEARTBEAT"})), None);
+    assert_eq!(progress_message(&json!({"type": "PROGRESS"})), None);
+}
+
+#[tokio::test]
+async fn test_web_automation_streams_progress_until_complete() {
+    let base = serve_sse(&[
+        "data: {\"type\":\"STARTED\",\"runId\":\"r1\"}\r\n\r\n",
+        "data: {\"type\":\"PROGRESS\",\"purpose\":\"Navigating to example.com\"}\n\n",
+        "data: {\"type\":\"HEARTBEAT\"}\n\ndata: {\"type\":\"PROG",
+        "RESS\",\"purpose\":\"Reading the page title\"}\n\n",
+        "data: {\"type\":\"COMPLETE\",\"status\":\"COMPLETED\",\"resultJson\":{\"title\":\"Example\"}}\n\n",
+    ]);
+    let tool = WebAutomation::new()
+        .with_base_url(base)
+        .with_api_key("test-key");
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let result = tool
+        .execute_with_context(
+            json!({"url": "https://example.com", "goal": "Get title"}),
+            ToolContext::new().with_progress(ProgressSink::new(tx)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, json!({"title": "Example"}));
+
+    let mut messages = Vec::new();
+    while let Ok(message) = rx.try_recv() {
+        messages.push(message);
+    }
+    assert_eq!(
+        messages,
+        [
+            "Browser session started",
+            "Navigating to example.com",
+            "Reading the page title"
+        ]
+    );
+}
@@ -0,0 +1,254 @@
+use crate::testing::assert_args_round_trip;
+use crate::tools::web_fetch::html_to_markdown;
+use crate::tools::{FetchMethod, Tool, WebFetch, WebFetchArgs};
+use reqwest::Url;
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+const ARTICLE: &str = r#"<!doctype html>
+<html>
+<head><title>Release notes | Widgets</title><script>var tracking = 1;</script></head>
+<body>
+  <nav class="top-nav"><a href="/">Home</a> <a href="/pricing">Pricing</a></nav>
+  <div class="sidebar"><p>Related posts, more posts, and even more posts to read later.</p></div>
+  <div id="content" class="post">
+    <h1>Widgets 2.0</h1>
+    <p>Widgets 2.0 adds streaming, retries, and a <a href="/docs/config">new config format</a>.</p>
+    <p>Upgrading is simple, fast, and mostly automatic for <strong>most</strong> users.</p>
+    <ul><li>Streaming</li><li>Retries with <code>backoff</code></li></ul>
+    <pre><code class="language-toml">[widgets]
+retries = 3</code></pre>
+  </div>
+  <footer>Copyright Widgets Inc.</footer>
+</body>
+</html>"#;
+
+struct Request {
+    line: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// Serve every connection with `respond`, one request per connection.
+fn serve(respond: fn(&Request) -> String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap();
+                headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+            }
+            let length = headers
+                .iter()
+                .find(|(name, _)| name == "content-length")
+                .map_or(0, |(_, value)| value.parse().unwrap());
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let request = Request {
+                line: line.trim_end().to_string(),
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            };
+            let _ = stream.write_all(respond(&request).as_bytes());
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn routes(request: &Request) -> String {
+    let path = request.line.split(' ').nth(1).unwrap_or("/");
+    match path {
+        "/article" => response("200 OK", "text/html; charset=utf-8", ARTICLE),
+        "/old" => "HTTP/1.1 301 Moved Permanently\r\nLocation: /article\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        "/echo" => {
+            let token = request
+                .headers
+                .iter()
+                .find(|(name, _)| name == "x-token")
+                .map_or("", |(_, value)| value.as_str());
+            let body = json!({"request": request.line, "token": token, "body": request.body});
+            response("200 OK", "application/json", &body.to_string())
+        }
+        "/long" => {
+            let text: String = (1..=400).map(|n| format!("line {}\n", n)).collect();
+            response("200 OK", "text/plain", &text)
+        }
+        "/slow" => {
+            std::thread::sleep(Duration::from_secs(3));
+            response("200 OK", "text/plain", "late")
+        }
+        "/image" => response("200 OK", "image/png", "PNG"),
+        _ => response("404 Not Found", "text/plain", "no such page"),
+    }
+}
+
+async fn fetch(args: Value) -> anyhow::Result<Value> {
+    WebFetch::new().execute(args).await
+}
+
+#[test]
+fn test_web_fetch_tool_name_and_args() {
+    let tool = WebFetch::new();
+    assert_eq!(tool.name(), "web_fetch");
+    assert!(tool.description().contains("markdown"));
+    assert_args_round_trip::<WebFetchArgs>(
+        &tool,
+        json!({
+            "url": "https://example.com/api",
+            "method": "POST",
+            "headers": {"Accept": "application/json"},
+            "body": "{}",
+            "timeout_secs": 10,
+            "max_bytes": 2000,
+            "raw": true
+        }),
+    );
+    let args = WebFetchArgs::new("https://example.com");
+    assert_eq!(args.method, FetchMethod::Get);
+    assert_eq!((args.timeout_secs, args.max_bytes), (30, 50_000));
+}
+
+#[test]
+fn test_html_to_markdown_keeps_main_content() {
+    let base = Url::parse("https://widgets.dev/blog/2.0").unwrap();
+    let page = html_to_markdown(ARTICLE, Some(&base));
+    assert_eq!(page.title.as_deref(), Some("Release notes | Widgets"));
+    assert_eq!(
+        page.markdown,
+        "# Widgets 2.0\n\n\
+         Widgets 2.0 adds streaming, retries, and a [new config format](https://widgets.dev/docs/config).\n\n\
+         Upgrading is simple, fast, and mostly automatic for **most** users.\n\n\
+         - Streaming\n\
+         - Retries with `backoff`\n\n\
+         ```toml\n[widgets]\nretries = 3\n```"
+    );
+}
+
+#[test]
+fn test_html_to_markdown_renders_tables_quotes_and_nested_lists() {
+    let html = r#"<body><main>
+        <blockquote><p>Quoted <em>text</em></p></blockquote>
+        <ol start="3"><li>Three<ul><li>Nested</li></ul></li><li>Four</li></ol>
+        <table><tr><th>Name</th><th>Size</th></tr><tr><td>a|b</td><td>1</td></tr></table>
+        <p style="display: none">Hidden</p>
+        <img src="/logo.png" alt="Logo">
+        </main></body>"#;
+    let page = html_to_markdown(html, None);
+    assert_eq!(
+        page.markdown,
+        "> Quoted _text_\n\n\
+         3. Three\n\n   - Nested\n4. Four\n\n\
+         | Name | Size |\n| --- | --- |\n| a\\|b | 1 |\n\n\
+         ![Logo](/logo.png)"
+    );
+}
+
+#[tokio::test]
+async fn test_web_fetch_converts_html_and_follows_redirects() {
+    let base = serve(routes);
+    let result = fetch(json!({"url": format!("{}/old", base)}))
+        .await
+        .unwrap();
+    assert_eq!(result["status"], 200);
+    assert_eq!(result["final_url"], format!("{}/article", base));
+    assert_eq!(result["title"], "Release notes | Widgets");
+    assert_eq!(result["truncated"], false);
+    let content = result["content"].as_str().unwrap();
+    assert!(content.starts_with("# Widgets 2.0"));
+    assert!(content.contains(&format!("({}/docs/config)", base)));
+    assert!(!content.contains("Pricing"));
+    assert!(!content.contains("Related posts"));
+    assert!(!content.contains("tracking"));
+
+    let raw = fetch(json!({"url": format!("{}/article", base), "raw": true}))
+        .await
+        .unwrap();
+    assert!(raw["content"].as_str().unwrap().contains("<nav"));
+    assert_eq!(raw["title"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_web_fetch_posts_body_and_headers() {
+    let base = serve(routes);
+    let result = fetch(json!({
+        "url": format!("{}/echo", base),
+        "method": "POST",
+        "headers": {"X-Token": "secret"},
+        "body": "name=locus"
+    }))
+    .await
+    .unwrap();
+    let echoed: Value = serde_json::from_str(result["content"].as_str().unwrap()).unwrap();
+    assert_eq!(echoed["request"], "POST /echo HTTP/1.1");
+    assert_eq!(echoed["token"], "secret");
+    assert_eq!(echoed["body"], "name=locus");
+    assert!(result["content"].as_str().unwrap().contains("\n  \""));
+}
+
+#[tokio::test]
+async fn test_web_fetch_truncates_at_a_line_break() {
+    let base = serve(routes);
+    let result = fetch(json!({"url": format!("{}/long", base), "max_bytes": 1000}))
+        .await
+        .unwrap();
+    let content = result["content"].as_str().unwrap();
+    assert_eq!(result["truncated"], true);
+    assert_eq!(result["bytes"], 3492);
+    assert!(content.len() <= 1000);
+    assert!(content.ends_with("line 123"));
+}
+
+#[tokio::test]
+async fn test_web_fetch_errors() {
+    let base = serve(routes);
+
+    let err = fetch(json!({"url": format!("{}/missing", base)}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("HTTP 404"));
+    assert!(err.to_string().contains("no such page"));
+
+    let err = fetch(json!({"url": format!("{}/slow", base), "timeout_secs": 1}))
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Request timed out after 1s");
+
+    let err = fetch(json!({"url": format!("{}/image", base)}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'image/png'"));
+
+    let err = fetch(json!({"url": "file:///etc/passwd"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unsupported URL scheme 'file'"));
+
+    let err = fetch(json!({"url": format!("{}/echo", base), "body": "x"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("only sent with method POST"));
+}
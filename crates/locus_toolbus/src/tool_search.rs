@@ -0,0 +1,292 @@
+//! Ranked search over the bus's tools, for the `tool_search` meta-tool.
+//!
+//! Each tool is scored on two signals:
+//!
+//! - **fuzzy**: every query word is matched against the words of the tool's name
+//!   and description (exact, prefix, or a typo away); name matches count more.
+//!   A query that appears in the name as written scores full marks
+//! - **semantic** (feature `semantic`): cosine similarity between embeddings of
+//!   the query and of `name: description`, from [embedder_from_env]. Tool vectors
+//!   are cached by name and recomputed when the description changes, so MCP
+//!   servers coming and going cost one embedding call for their new tools
+//!
+//! When embedding fails (API down) the search falls back to fuzzy scores.
+
+use serde::Serialize;
+
+use crate::ToolInfo;
+#[cfg(feature = "semantic")]
+use crate::semantic::{Embedder, embedder_from_env};
+#[cfg(feature = "semantic")]
+use std::collections::HashMap;
+#[cfg(feature = "semantic")]
+use std::sync::{Arc, Mutex};
+
+/// Weight of the fuzzy score when an embedder is available.
+const FUZZY_WEIGHT: f32 = 0.6;
+/// Description words count this much of a name word.
+const DESCRIPTION_WEIGHT: f32 = 0.6;
+/// Tools scoring below this are not returned.
+const MIN_SCORE: f32 = 0.1;
+/// Longest summary returned per tool.
+const MAX_SUMMARY_CHARS: usize = 200;
+
+/// Words that say nothing about what a tool does.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "can", "do", "for", "from", "how", "i", "in",
+    "into", "is", "it", "me", "my", "of", "on", "or", "some", "the", "this", "to", "use", "want",
+    "we", "what", "which", "with",
+];
+
+/// One ranked result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolMatch {
+    pub name: String,
+    /// First sentence of the description.
+    pub summary: String,
+    /// Combined score, 0 to 1.
+    pub score: f32,
+}
+
+/// Scores tools against a query; holds the tool embedding cache.
+pub struct ToolSearch {
+    #[cfg(feature = "semantic")]
+    embedder: Option<Arc<dyn Embedder>>,
+    /// Tool name → (embedded text, vector).
+    #[cfg(feature = "semantic")]
+    vectors: Mutex<HashMap<String, (String, Vec<f32>)>>,
+}
+
+impl ToolSearch {
+    /// Fuzzy matching only.
+    pub fn fuzzy() -> Self {
+        Self {
+            #[cfg(feature = "semantic")]
+            embedder: None,
+            #[cfg(feature = "semantic")]
+            vectors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fuzzy matching plus the embedder from the environment (see
+    /// [embedder_from_env]); fuzzy only without the `semantic` feature.
+    pub fn from_env() -> Self {
+        #[cfg(feature = "semantic")]
+        {
+            Self::with_embedder(Arc::from(embedder_from_env()))
+        }
+        #[cfg(not(feature = "semantic"))]
+        {
+            Self::fuzzy()
+        }
+    }
+
+    #[cfg(feature = "semantic")]
+    pub fn with_embedder(embedder: Arc<dyn Embedder>) -> Self {
+        Self {
+            embedder: Some(embedder),
+            vectors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The `limit` best matches for `query` among `tools`, best first.
+    pub async fn search(&self, tools: &[ToolInfo], query: &str, limit: usize) -> Vec<ToolMatch> {
+        let query_words = words(query);
+        let phrase = query.trim().to_lowercase();
+        let fuzzy: Vec<f32> = tools
+            .iter()
+            .map(|tool| fuzzy_score(tool, &query_words, &phrase))
+            .collect();
+        let scores = match self.semantic_scores(tools, query).await {
+            Some(semantic) => fuzzy
+                .iter()
+                .zip(semantic)
+                .map(|(f, s)| FUZZY_WEIGHT * f + (1.0 - FUZZY_WEIGHT) * s.max(0.0))
+                .collect(),
+            None => fuzzy,
+        };
+
+        let mut matches: Vec<ToolMatch> = tools
+            .iter()
+            .zip(scores)
+            .filter(|(_, score)| *score >= MIN_SCORE)
+            .map(|(tool, score)| ToolMatch {
+                name: tool.name.clone(),
+                summary: summary(&tool.description),
+                score: (score * 1000.0).round() / 1000.0,
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.name.cmp(&b.name)));
+        matches.truncate(limit);
+        matches
+    }
+
+    #[cfg(not(feature = "semantic"))]
+    async fn semantic_scores(&self, _tools: &[ToolInfo], _query: &str) -> Option<Vec<f32>> {
+        None
+    }
+
+    /// Similarity of each tool to `query`, or None without an embedder or when
+    /// embedding fails.
+    #[cfg(feature = "semantic")]
+    async fn semantic_scores(&self, tools: &[ToolInfo], query: &str) -> Option<Vec<f32>> {
+        let embedder = self.embedder.as_ref()?;
+        let texts: Vec<String> = tools
+            .iter()
+            .map(|tool| format!("{}: {}", tool.name, tool.description))
+            .collect();
+        let stale: Vec<usize> = {
+            let vectors = self.vectors.lock().ok()?;
+            texts
+                .iter()
+                .enumerate()
+                .filter(|(i, text)| {
+                    vectors
+                        .get(&tools[*i].name)
+                        .is_none_or(|(cached, _)| cached != *text)
+                })
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let mut inputs: Vec<String> = stale.iter().map(|i| texts[*i].clone()).collect();
+        inputs.push(query.to_string());
+        let mut embedded = match embedder.embed(&inputs).await {
+            Ok(vectors) if vectors.len() == inputs.len() => vectors,
+            Ok(_) => return None,
+            Err(e) => {
+                tracing::warn!("tool_search: embedding failed, using fuzzy matching: {}", e);
+                return None;
+            }
+        };
+        let query_vector = embedded.pop()?;
+
+        let mut vectors = self.vectors.lock().ok()?;
+        for (i, vector) in stale.into_iter().zip(embedded) {
+            vectors.insert(tools[i].name.clone(), (texts[i].clone(), vector));
+        }
+        Some(
+            tools
+                .iter()
+                .map(|tool| {
+                    vectors
+                        .get(&tool.name)
+                        .map_or(0.0, |(_, vector)| cosine(vector, &query_vector))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Default for ToolSearch {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+#[cfg(feature = "semantic")]
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let (na, nb) = (norm(a), norm(b));
+    if na == 0.0 || nb == 0.0 {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>() / (na * nb)
+}
+
+/// Lowercase words of `text`, split at anything but letters and digits and at
+/// case changes (`gitCommit` → git, commit), without stop words.
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for token in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut word = String::new();
+        let mut prev: Option<char> = None;
+        for c in token.chars() {
+            if prev.is_some_and(|p| p.is_lowercase() && c.is_uppercase()) && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+            prev = Some(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words.retain(|w| !STOP_WORDS.contains(&w.as_str()));
+    words
+}
+
+/// How well one query word matches one tool word, 0 to 1.
+fn word_match(query: &str, word: &str) -> f32 {
+    if query == word {
+        return 1.0;
+    }
+    let shorter = query.len().min(word.len());
+    if shorter >= 3 && (word.starts_with(query) || query.starts_with(word)) {
+        return 0.8;
+    }
+    let allowed = match shorter {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    };
+    if allowed > 0 && edit_distance(query, word) <= allowed {
+        return 0.6;
+    }
+    0.0
+}
+
+fn fuzzy_score(tool: &ToolInfo, query_words: &[String], phrase: &str) -> f32 {
+    if !phrase.is_empty() && tool.name.to_lowercase().contains(phrase) {
+        return 1.0;
+    }
+    if query_words.is_empty() {
+        return 0.0;
+    }
+    let name_words = words(&tool.name);
+    let description_words = words(&tool.description);
+    let total: f32 = query_words
+        .iter()
+        .map(|q| {
+            let in_name = name_words
+                .iter()
+                .map(|w| word_match(q, w))
+                .fold(0.0, f32::max);
+            let in_description = description_words
+                .iter()
+                .map(|w| word_match(q, w) * DESCRIPTION_WEIGHT)
+                .fold(0.0, f32::max);
+            in_name.max(in_description)
+        })
+        .sum();
+    total / query_words.len() as f32
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The description's first sentence (or line), cut to [MAX_SUMMARY_CHARS].
+fn summary(description: &str) -> String {
+    let first_line = description.trim().lines().next().unwrap_or_default();
+    let sentence = match first_line.find(". ") {
+        Some(end) => &first_line[..=end],
+        None => first_line,
+    };
+    if sentence.chars().count() <= MAX_SUMMARY_CHARS {
+        return sentence.to_string();
+    }
+    let cut: String = sentence.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AstSearchArgs {
+    /// Code pattern with `$NAME` (one node) and `$$$NAME` (any number of nodes) metavariables
+    pub pattern: String,
+
+    /// Language of the pattern; defaults to every language the pattern parses in
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// File or directory to search (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Maximum number of matches returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    50
+}
+
+impl AstSearchArgs {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            language: None,
+            path: None,
+            max_results: default_max_results(),
+        }
+    }
+
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+/// One node matching the pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AstMatch {
+    pub path: String,
+    /// 1-based first and last line of the matched node.
+    pub line: usize,
+    pub end_line: usize,
+    /// 1-based column where the match starts.
+    pub column: usize,
+    /// Source of the matched node, cut off after a few hundred characters.
+    pub text: String,
+    /// Text bound to each named metavariable (`$$$` captures span all their nodes).
+    pub captures: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AstSearchResult {
+    pub pattern: String,
+    /// Languages the pattern was searched in.
+    pub languages: Vec<String>,
+    pub matches: Vec<AstMatch>,
+    /// Matches found before `max_results` was applied.
+    pub total: usize,
+    pub truncated: bool,
+    pub files_parsed: usize,
+}
+
+impl AstSearchResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AstSearchError {
+    #[error("Empty pattern provided")]
+    EmptyPattern,
+
+    #[error("Unsupported language: {0} (supported: {1})")]
+    UnsupportedLanguage(String, String),
+
+    #[error("Invalid pattern: {0}")]
+    InvalidPattern(String),
+
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl From<ignore::Error> for AstSearchError {
+    fn from(err: ignore::Error) -> Self {
+        AstSearchError::Walk(err.to_string())
+    }
+}
@@ -0,0 +1,426 @@
+mod args;
+mod error;
+
+pub use args::{AstMatch, AstSearchArgs, AstSearchResult};
+pub use error::AstSearchError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::syntax::{LANGUAGES, grammar, language_name};
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_start};
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Files larger than this are skipped (generated code, fixtures, bundles).
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Longest matched source returned per match.
+const MAX_TEXT_CHARS: usize = 300;
+
+/// Identifiers metavariables are rewritten to, so the pattern parses as ordinary code.
+const SINGLE_PREFIX: &str = "__locus_mv_";
+const MULTI_PREFIX: &str = "__locus_mvs_";
+
+/// Appended to patterns that do not parse on their own; whatever the suffix adds
+/// (a body, a statement terminator) matches anything.
+const PATTERN_SUFFIXES: &[&str] = &["", " {}", ";", "\n    pass"];
+
+pub struct AstSearch {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+/// Metavariable bindings: name → matched text.
+type Captures = HashMap<String, String>;
+
+/// A pattern parsed in one language.
+struct Pattern {
+    language: &'static str,
+    source: String,
+    tree: Tree,
+    /// Length of the pattern before a suffix was appended.
+    len: usize,
+    /// Named-child indices from the tree root down to the node being matched.
+    root_path: Vec<usize>,
+}
+
+fn metavariable_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\$\$\$([A-Z_][A-Z0-9_]*)?|\$([A-Z_][A-Z0-9_]*)").expect("valid regex")
+    })
+}
+
+/// Rewrite `$NAME` / `$$$NAME` to placeholder identifiers.
+fn rewrite_metavariables(pattern: &str) -> String {
+    metavariable_regex()
+        .replace_all(pattern, |caps: &regex::Captures| match caps.get(2) {
+            Some(name) => format!("{}{}", SINGLE_PREFIX, name.as_str()),
+            None => format!("{}{}", MULTI_PREFIX, caps.get(1).map_or("", |m| m.as_str())),
+        })
+        .into_owned()
+}
+
+/// `(is_multi, name)` when the whole of `node` is a metavariable placeholder.
+fn metavariable<'a>(node: &Node, source: &'a [u8]) -> Option<(bool, &'a str)> {
+    let text = node.utf8_text(source).ok()?;
+    let (multi, name) = match text.strip_prefix(MULTI_PREFIX) {
+        Some(name) => (true, name),
+        None => (false, text.strip_prefix(SINGLE_PREFIX)?),
+    };
+    name.bytes()
+        .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_')
+        .then_some((multi, name))
+}
+
+/// Children that take part in matching; comments and other extras are ignored.
+fn children<'t>(node: &Node<'t>) -> Vec<Node<'t>> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|c| !c.is_extra())
+        .collect()
+}
+
+/// Code nodes a pattern may leave out: punctuation such as trailing commas,
+/// keywords, and modifiers like `pub` or `async`.
+fn skippable(node: &Node) -> bool {
+    !node.is_named() || node.kind().ends_with("modifier") || node.kind().ends_with("modifiers")
+}
+
+/// Record `text` for `name`; a name seen before must match the same text.
+/// `$_`, `$$$` and names starting with `_` match without capturing.
+fn bind(captures: &mut Captures, name: &str, text: &str) -> bool {
+    if name.is_empty() || name.starts_with('_') {
+        return true;
+    }
+    match captures.get(name) {
+        Some(previous) => previous == text,
+        None => {
+            captures.insert(name.to_string(), text.to_string());
+            true
+        }
+    }
+}
+
+impl Pattern {
+    /// Parse `pattern` as `language`, trying the suffixes until one parses cleanly.
+    fn compile(language: &'static str, pattern: &str) -> Result<Self, String> {
+        let text = rewrite_metavariables(pattern.trim());
+        let core_len = text
+            .trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+            .len();
+        let mut parser = Parser::new();
+        let grammar = grammar(language).ok_or_else(|| format!("no grammar for {}", language))?;
+        parser.set_language(&grammar).map_err(|e| e.to_string())?;
+
+        for suffix in PATTERN_SUFFIXES {
+            let source = format!("{}{}", text, suffix);
+            let Some(tree) = parser.parse(&source, None) else {
+                continue;
+            };
+            if tree.root_node().has_error() {
+                continue;
+            }
+
+            // Descend through wrappers (file, expression statement) that cover the whole pattern.
+            let mut node = tree.root_node();
+            let mut root_path = Vec::new();
+            loop {
+                let named: Vec<(usize, Node)> = (0..node.named_child_count())
+                    .filter_map(|i| node.named_child(i).map(|c| (i, c)))
+                    .filter(|(_, c)| !c.is_extra())
+                    .collect();
+                let [(index, child)] = named[..] else {
+                    break;
+                };
+                if child.start_byte() != node.start_byte() || child.end_byte() < core_len {
+                    break;
+                }
+                root_path.push(index);
+                node = child;
+            }
+            if node.parent().is_none() && node.named_child_count() > 1 {
+                return Err("pattern must be a single expression, statement or item".to_string());
+            }
+            if metavariable(&node, source.as_bytes()).is_some() {
+                return Err("pattern cannot be only a metavariable".to_string());
+            }
+            return Ok(Self {
+                language,
+                len: text.len(),
+                source,
+                tree,
+                root_path,
+            });
+        }
+        Err(format!("does not parse as {}", language))
+    }
+
+    fn root(&self) -> Node<'_> {
+        let mut node = self.tree.root_node();
+        for &index in &self.root_path {
+            node = node.named_child(index).expect("root path is valid");
+        }
+        node
+    }
+
+    fn match_node(&self, p: Node, c: Node, source: &[u8], captures: &mut Captures) -> bool {
+        if p.start_byte() >= self.len {
+            return true;
+        }
+        if let Some((_, name)) = metavariable(&p, self.source.as_bytes()) {
+            return c.is_named()
+                && c.utf8_text(source)
+                    .is_ok_and(|text| bind(captures, name, text));
+        }
+        if p.kind_id() != c.kind_id() {
+            return false;
+        }
+        let (pc, cc) = (children(&p), children(&c));
+        if pc.is_empty() {
+            return p.utf8_text(self.source.as_bytes()).ok() == c.utf8_text(source).ok();
+        }
+        self.match_list(&pc, &cc, source, captures)
+    }
+
+    fn match_list(&self, p: &[Node], c: &[Node], source: &[u8], captures: &mut Captures) -> bool {
+        let Some((head, rest)) = p.split_first() else {
+            return c.iter().all(skippable);
+        };
+
+        if let Some((true, name)) = metavariable(head, self.source.as_bytes()) {
+            for taken in 0..=c.len() {
+                let saved = captures.clone();
+                let text = match c[..taken] {
+                    [] => "",
+                    [first, .., last] => {
+                        std::str::from_utf8(&source[first.start_byte()..last.end_byte()])
+                            .unwrap_or("")
+                    }
+                    [only] => only.utf8_text(source).unwrap_or(""),
+                };
+                if bind(captures, name, text)
+                    && self.match_list(rest, &c[taken..], source, captures)
+                {
+                    return true;
+                }
+                *captures = saved;
+            }
+            return false;
+        }
+
+        let Some((first, others)) = c.split_first() else {
+            // Suffix nodes may be missing from the code entirely.
+            return head.start_byte() >= self.len && self.match_list(rest, c, source, captures);
+        };
+        let saved = captures.clone();
+        if self.match_node(*head, *first, source, captures)
+            && self.match_list(rest, others, source, captures)
+        {
+            return true;
+        }
+        *captures = saved;
+        skippable(first) && self.match_list(p, others, source, captures)
+    }
+
+    /// Every node in `tree` the pattern matches, outermost first.
+    fn find_all(&self, tree: &Tree, source: &str, rel: &str, out: &mut Vec<AstMatch>) {
+        let root = self.root();
+        let bytes = source.as_bytes();
+        let mut cursor = tree.walk();
+        loop {
+            let node = cursor.node();
+            let mut captures = Captures::new();
+            if node.kind_id() == root.kind_id() && self.match_node(root, node, bytes, &mut captures)
+            {
+                let text = node.utf8_text(bytes).unwrap_or("");
+                let mut short: String = text.chars().take(MAX_TEXT_CHARS).collect();
+                if short.len() < text.len() {
+                    short.push('…');
+                }
+                out.push(AstMatch {
+                    path: rel.to_string(),
+                    line: node.start_position().row + 1,
+                    end_line: node.end_position().row + 1,
+                    column: node.start_position().column + 1,
+                    text: short,
+                    captures: captures.into_iter().collect::<BTreeMap<_, _>>(),
+                });
+            }
+
+            if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl AstSearch {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path`, and leave protected files out of the walk, by
+    /// `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Parse the pattern in `language`, or in every language it is valid code in.
+    fn compile(pattern: &str, language: Option<&str>) -> Result<Vec<Pattern>, AstSearchError> {
+        if pattern.trim().is_empty() {
+            return Err(AstSearchError::EmptyPattern);
+        }
+        let Some(language) = language else {
+            let patterns: Vec<Pattern> = LANGUAGES
+                .iter()
+                .filter_map(|lang| Pattern::compile(lang, pattern).ok())
+                .collect();
+            if patterns.is_empty() {
+                return Err(AstSearchError::InvalidPattern(
+                    "does not parse as code in any supported language".to_string(),
+                ));
+            }
+            return Ok(patterns);
+        };
+        let Some(&language) = LANGUAGES.iter().find(|l| **l == language) else {
+            return Err(AstSearchError::UnsupportedLanguage(
+                language.to_string(),
+                LANGUAGES.join(", "),
+            ));
+        };
+        Pattern::compile(language, pattern)
+            .map(|p| vec![p])
+            .map_err(AstSearchError::InvalidPattern)
+    }
+
+    /// Walk `start` and match the patterns against every file of their language.
+    /// Blocking; call from `spawn_blocking`.
+    fn search(
+        repo_root: &Path,
+        start: &Path,
+        patterns: &[Pattern],
+        protected: &ProtectedPathMatcher,
+    ) -> Result<(Vec<AstMatch>, usize), AstSearchError> {
+        let (root, protected) = (repo_root.to_path_buf(), protected.clone());
+        let walker = ignore::WalkBuilder::new(start)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !protected.is_protected(&root, e.path())
+            })
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut parser = Parser::new();
+        let mut matches = Vec::new();
+        let mut files_parsed = 0;
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(repo_root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string();
+            let Some(pattern) =
+                language_name(&rel).and_then(|lang| patterns.iter().find(|p| p.language == lang))
+            else {
+                continue;
+            };
+            // Binary and non-UTF-8 files are skipped.
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            let Some(language) = grammar(pattern.language) else {
+                continue;
+            };
+            if parser.set_language(&language).is_err() {
+                continue;
+            }
+            let Some(tree) = parser.parse(&content, None) else {
+                continue;
+            };
+            files_parsed += 1;
+            pattern.find_all(&tree, &content, &rel, &mut matches);
+        }
+        Ok((matches, files_parsed))
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for AstSearch {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let search_args: AstSearchArgs = serde_json::from_value(args)?;
+        let start = resolve_start(
+            &self.repo_root,
+            search_args.path.as_deref(),
+            &self.protected,
+        )?;
+        let repo_root = self.repo_root.clone();
+        let pattern = search_args.pattern.clone();
+        let language = search_args.language.clone();
+        let protected = self.protected.clone();
+
+        let (languages, mut matches, files_parsed) = tokio::task::spawn_blocking(move || {
+            let patterns = Self::compile(&pattern, language.as_deref())?;
+            let (matches, files_parsed) = Self::search(&repo_root, &start, &patterns, &protected)?;
+            let languages: Vec<String> = patterns.iter().map(|p| p.language.to_string()).collect();
+            Ok::<_, AstSearchError>((languages, matches, files_parsed))
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("ast_search task failed: {}", e))??;
+
+        let total = matches.len();
+        matches.truncate(search_args.max_results);
+
+        Ok(AstSearchResult {
+            pattern: search_args.pattern,
+            languages,
+            truncated: total > matches.len(),
+            matches,
+            total,
+            files_parsed,
+        }
+        .to_json())
+    }
+}
@@ -5,6 +5,9 @@ pub enum BashError {
     #[error("Command timed out after {0} seconds")]
     Timeout(u64),
 
+    #[error("Command cancelled")]
+    Cancelled,
+
     #[error("Command not found: {0}")]
     CommandNotFound(String),
 
@@ -17,6 +20,9 @@ pub enum BashError {
     #[error("UTF-8 decode error: {0}")]
     Utf8Error(#[from] std::string::FromUtf8Error),
 
+    #[error(transparent)]
+    Execution(#[from] crate::execution::ExecutionError),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
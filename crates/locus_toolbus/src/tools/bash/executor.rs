@@ -1,12 +1,16 @@
 use super::args::BashArgs;
 use super::error::BashError;
+use crate::execution::ExecutionBackend;
 use crate::tools::ToolOutput;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::time::{Duration, Instant};
-use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Default)]
 pub struct BashExecutor {
     working_dir: Option<String>,
+    backend: ExecutionBackend,
 }
 
 impl BashExecutor {
@@ -23,25 +27,73 @@ impl BashExecutor {
         self
     }
 
+    /// Run commands through `backend`; the working dir is the repo root it mounts.
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
     pub async fn run(&self, args: &BashArgs) -> Result<ToolOutput, BashError> {
+        self.run_with_cancel(args, &CancellationToken::new()).await
+    }
+
+    /// Run a command until it exits, times out or `cancel` fires. The command
+    /// runs in its own process group, and a timed-out or cancelled command is
+    /// killed with everything it started.
+    pub async fn run_with_cancel(
+        &self,
+        args: &BashArgs,
+        cancel: &CancellationToken,
+    ) -> Result<ToolOutput, BashError> {
         let timeout = Duration::from_secs(args.timeout);
         let start = Instant::now();
 
-        let shell = self.get_shell();
-        let mut cmd = Command::new(shell);
-
-        cmd.arg("-c").arg(&args.command).kill_on_drop(true);
-
-        if let Some(ref dir) = args.working_dir {
-            cmd.current_dir(dir);
-        } else if let Some(ref dir) = self.working_dir {
-            cmd.current_dir(dir);
-        }
+        let repo_root = match &self.working_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => std::env::current_dir()?,
+        };
+        let working_dir = args
+            .working_dir
+            .as_deref()
+            .or(self.working_dir.as_deref())
+            .map(Path::new);
+        let mut prepared = self.backend.command(
+            &repo_root,
+            working_dir,
+            (self.get_shell(), "-c"),
+            &args.command,
+        )?;
+        prepared
+            .command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(unix)]
+        prepared.command.process_group(0);
 
-        let output = tokio::time::timeout(timeout, cmd.output())
-            .await
-            .map_err(|_| BashError::Timeout(args.timeout))?
+        let child = prepared
+            .command
+            .spawn()
             .map_err(|e| BashError::SpawnFailed(e.to_string()))?;
+        let group = ProcessGroup(child.id());
+        let output = tokio::select! {
+            output = tokio::time::timeout(timeout, child.wait_with_output()) => match output {
+                Ok(output) => output.map_err(|e| BashError::WaitFailed(e.to_string()))?,
+                Err(_) => {
+                    drop(group);
+                    self.backend.cleanup(prepared.container.as_deref()).await;
+                    return Err(BashError::Timeout(args.timeout));
+                }
+            },
+            _ = cancel.cancelled() => {
+                drop(group);
+                self.backend.cleanup(prepared.container.as_deref()).await;
+                return Err(BashError::Cancelled);
+            }
+        };
+        // Exited on its own: leave anything it started in the background alone.
+        group.release();
 
         let stdout = String::from_utf8(output.stdout)?;
         let stderr = String::from_utf8(output.stderr)?;
@@ -66,3 +118,24 @@ impl BashExecutor {
         "cmd"
     }
 }
+
+/// A command's process group, killed when dropped unless released.
+struct ProcessGroup(Option<u32>);
+
+impl ProcessGroup {
+    fn release(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(pgid) = self.0 {
+            // SAFETY: killpg only sends a signal; the group is the command's own.
+            unsafe {
+                libc::killpg(pgid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+    }
+}
@@ -6,7 +6,9 @@ pub use args::BashArgs;
 pub use error::BashError;
 pub use executor::BashExecutor;
 
-use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use crate::execution::ExecutionBackend;
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 use std::sync::OnceLock;
@@ -36,6 +38,11 @@ impl Bash {
         self.executor = self.executor.with_working_dir(working_dir);
         self
     }
+
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.executor = self.executor.with_backend(backend);
+        self
+    }
 }
 
 impl Default for Bash {
@@ -58,9 +65,22 @@ impl Tool for Bash {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let bash_args: BashArgs = serde_json::from_value(args)?;
         let output = self.executor.run(&bash_args).await?;
         Ok(output.to_json())
     }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let bash_args: BashArgs = serde_json::from_value(args)?;
+        let output = self
+            .executor
+            .run_with_cancel(&bash_args, &ctx.cancel)
+            .await?;
+        Ok(output.to_json())
+    }
 }
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CodeNavArgs {
+    pub action: CodeNavAction,
+
+    /// Symbol to resolve; `Type::method` and `obj.method` resolve the last segment
+    pub symbol: String,
+
+    /// File or directory to search (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Maximum number of locations returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    100
+}
+
+impl CodeNavArgs {
+    pub fn new(action: CodeNavAction, symbol: impl Into<String>) -> Self {
+        Self {
+            action,
+            symbol: symbol.into(),
+            path: None,
+            max_results: default_max_results(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeNavAction {
+    /// Where the symbol is defined (functions, types, constants, fields, ...)
+    Definition,
+    /// Where the symbol is used, excluding its definitions
+    References,
+}
+
+/// One definition or reference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeLocation {
+    pub path: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column of the symbol.
+    pub column: usize,
+    /// What is defined (`function`, `struct`, `class`, ...); definitions only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// The source line, trimmed.
+    pub text: String,
+    /// Name of the enclosing definition, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeNavResult {
+    pub action: CodeNavAction,
+    pub symbol: String,
+    pub locations: Vec<CodeLocation>,
+    /// Locations found before `max_results` was applied.
+    pub total: usize,
+    pub truncated: bool,
+    /// Files with a supported grammar that mention the symbol and were parsed.
+    pub files_parsed: usize,
+}
+
+impl CodeNavResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CodeNavError {
+    #[error("Empty symbol provided")]
+    EmptySymbol,
+
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl From<ignore::Error> for CodeNavError {
+    fn from(err: ignore::Error) -> Self {
+        CodeNavError::Walk(err.to_string())
+    }
+}
@@ -0,0 +1,285 @@
+mod args;
+mod error;
+
+pub use args::{CodeLocation, CodeNavAction, CodeNavArgs, CodeNavResult};
+pub use error::CodeNavError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::syntax::language_for;
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_start};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tree_sitter::{Node, Parser};
+
+/// Files larger than this are skipped (generated code, fixtures, bundles).
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Longest source line returned in a location.
+const MAX_TEXT_CHARS: usize = 200;
+
+pub struct CodeNav {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+/// What a node defines, for node kinds that carry a `name` field, across the supported grammars.
+fn definition_kind(node: &Node) -> Option<&'static str> {
+    let kind = match node.kind() {
+        "function_item" | "function_signature_item" | "function_definition" => "function",
+        "function_declaration" | "generator_function_declaration" => "function",
+        "method_definition" | "method_declaration" => "method",
+        "struct_item" => "struct",
+        "enum_item" | "enum_declaration" => "enum",
+        "union_item" => "union",
+        "enum_variant" => "variant",
+        "trait_item" => "trait",
+        "interface_declaration" => "interface",
+        "class_definition" | "class_declaration" | "abstract_class_declaration" => "class",
+        "type_item" | "type_alias_declaration" | "type_spec" => "type",
+        "const_item" | "const_spec" => "const",
+        "static_item" => "static",
+        "mod_item" => "module",
+        "macro_definition" => "macro",
+        "field_declaration" | "public_field_definition" => "field",
+        "variable_declarator" | "var_spec" => "variable",
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Whether a definition can enclose others and so names the scope of what is inside it.
+fn is_scope(kind: &str) -> bool {
+    !matches!(
+        kind,
+        "variable" | "field" | "const" | "static" | "variant" | "type"
+    )
+}
+
+/// `name` children of a definition node (Go specs can declare several names at once).
+fn definition_names<'a>(node: &Node<'a>) -> Vec<Node<'a>> {
+    let mut cursor = node.walk();
+    node.children_by_field_name("name", &mut cursor).collect()
+}
+
+/// Name of the closest definition enclosing `node`; `impl Foo` blocks count as `Foo`.
+fn scope_of(node: &Node, source: &[u8]) -> Option<String> {
+    let mut parent = node.parent();
+    while let Some(p) = parent {
+        let name = if p.kind() == "impl_item" {
+            p.child_by_field_name("type")
+        } else if definition_kind(&p).is_some_and(is_scope) {
+            definition_names(&p).into_iter().next()
+        } else {
+            None
+        };
+        if let Some(text) = name.and_then(|n| n.utf8_text(source).ok()) {
+            return Some(text.to_string());
+        }
+        parent = p.parent();
+    }
+    None
+}
+
+/// Last segment of a qualified symbol: `Store::get`, `store.get` and `get()` all resolve `get`.
+fn normalize_symbol(symbol: &str) -> &str {
+    let symbol = symbol.trim().trim_end_matches("()");
+    symbol.rsplit([':', '.']).next().unwrap_or(symbol)
+}
+
+/// Find definitions of or references to `symbol` in one parsed file.
+fn search_tree(
+    root: Node,
+    source: &str,
+    rel: &str,
+    symbol: &str,
+    action: CodeNavAction,
+    out: &mut Vec<CodeLocation>,
+) {
+    let bytes = source.as_bytes();
+    let lines: Vec<&str> = source.lines().collect();
+    let location = |node: &Node, kind: Option<&str>, scope_node: &Node| {
+        let pos = node.start_position();
+        let text = lines.get(pos.row).map_or("", |l| l.trim());
+        CodeLocation {
+            path: rel.to_string(),
+            line: pos.row + 1,
+            column: pos.column + 1,
+            kind: kind.map(String::from),
+            text: text.chars().take(MAX_TEXT_CHARS).collect(),
+            scope: scope_of(scope_node, bytes),
+        }
+    };
+
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        match action {
+            CodeNavAction::Definition => {
+                if let Some(kind) = definition_kind(&node) {
+                    for name in definition_names(&node) {
+                        if name.utf8_text(bytes).is_ok_and(|t| t == symbol) {
+                            out.push(location(&name, Some(kind), &node));
+                        }
+                    }
+                }
+            }
+            CodeNavAction::References => {
+                let is_match = node.child_count() == 0
+                    && node.kind().ends_with("identifier")
+                    && node.utf8_text(bytes).is_ok_and(|t| t == symbol);
+                let is_definition_name = node.parent().is_some_and(|p| {
+                    definition_kind(&p).is_some()
+                        && definition_names(&p).iter().any(|n| n.id() == node.id())
+                });
+                if is_match && !is_definition_name {
+                    out.push(location(&node, None, &node));
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return;
+            }
+        }
+    }
+}
+
+impl CodeNav {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path`, and leave protected files out of the walk, by
+    /// `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Walk `start` and parse every supported file that mentions `symbol`.
+    /// Blocking; call from `spawn_blocking`.
+    fn search(
+        repo_root: &Path,
+        start: &Path,
+        symbol: &str,
+        action: CodeNavAction,
+        protected: &ProtectedPathMatcher,
+    ) -> Result<(Vec<CodeLocation>, usize), CodeNavError> {
+        let (root, protected) = (repo_root.to_path_buf(), protected.clone());
+        let walker = ignore::WalkBuilder::new(start)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !protected.is_protected(&root, e.path())
+            })
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut parser = Parser::new();
+        let mut locations = Vec::new();
+        let mut files_parsed = 0;
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(repo_root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string();
+            let Some(language) = language_for(&rel) else {
+                continue;
+            };
+            // Binary and non-UTF-8 files are skipped, and so are files that
+            // cannot contain the symbol, which keeps most of the repo unparsed.
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            if !content.contains(symbol) || parser.set_language(&language).is_err() {
+                continue;
+            }
+            let Some(tree) = parser.parse(&content, None) else {
+                continue;
+            };
+            files_parsed += 1;
+            search_tree(
+                tree.root_node(),
+                &content,
+                &rel,
+                symbol,
+                action,
+                &mut locations,
+            );
+        }
+        Ok((locations, files_parsed))
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for CodeNav {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let nav_args: CodeNavArgs = serde_json::from_value(args)?;
+        let symbol = normalize_symbol(&nav_args.symbol).to_string();
+        if symbol.is_empty() {
+            return Err(CodeNavError::EmptySymbol.into());
+        }
+        let start = resolve_start(&self.repo_root, nav_args.path.as_deref(), &self.protected)?;
+        let repo_root = self.repo_root.clone();
+        let action = nav_args.action;
+        let protected = self.protected.clone();
+
+        let query = symbol.clone();
+        let (mut locations, files_parsed) = tokio::task::spawn_blocking(move || {
+            Self::search(&repo_root, &start, &query, action, &protected)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("code_nav search task failed: {}", e))??;
+
+        let total = locations.len();
+        locations.truncate(nav_args.max_results);
+
+        Ok(CodeNavResult {
+            action,
+            symbol,
+            truncated: total > locations.len(),
+            locations,
+            total,
+            files_parsed,
+        }
+        .to_json())
+    }
+}
@@ -4,6 +4,8 @@ mod error;
 pub use args::CreateFileArgs;
 pub use error::CreateFileError;
 
+use crate::execution::ExecutionBackend;
+use crate::permission::Permission;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
@@ -12,11 +14,21 @@ use std::sync::OnceLock;
 
 pub struct CreateFile {
     workspace_root: PathBuf,
+    backend: ExecutionBackend,
 }
 
 impl CreateFile {
     pub fn new(workspace_root: PathBuf) -> Self {
-        Self { workspace_root }
+        Self {
+            workspace_root,
+            backend: ExecutionBackend::default(),
+        }
+    }
+
+    /// Write through `backend` (only changes anything for Docker with `write_tools`).
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
     }
 
     fn validate_path(&self, path: &str) -> Result<PathBuf, CreateFileError> {
@@ -99,6 +111,10 @@ impl Tool for CreateFile {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let tool_args: CreateFileArgs = serde_json::from_value(args)?;
 
@@ -114,7 +130,12 @@ impl Tool for CreateFile {
         }
 
         // Write the file
-        tokio::fs::write(&file_path, &tool_args.content)
+        self.backend
+            .write_file(
+                &normalize_path(&self.workspace_root),
+                &file_path,
+                tool_args.content.as_bytes(),
+            )
             .await
             .map_err(|e| CreateFileError::WriteFailed(e.to_string()))?;
 
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DepsArgs {
+    /// `audit` for known vulnerabilities, `outdated` for newer versions, `tree` for the graph
+    pub action: DepsAction,
+
+    /// Package manager to ask; every one the project uses when omitted
+    #[serde(default)]
+    pub ecosystem: Option<Ecosystem>,
+
+    /// Project directory holding the manifest, relative to repo root (defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Tree levels below the project to list (1 = direct dependencies)
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// Maximum number of entries returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_depth() -> usize {
+    1
+}
+
+fn default_max_results() -> usize {
+    200
+}
+
+impl DepsArgs {
+    pub fn new(action: DepsAction) -> Self {
+        Self {
+            action,
+            ecosystem: None,
+            path: None,
+            depth: default_depth(),
+            max_results: default_max_results(),
+        }
+    }
+
+    pub fn with_ecosystem(mut self, ecosystem: Ecosystem) -> Self {
+        self.ecosystem = Some(ecosystem);
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepsAction {
+    /// Known vulnerabilities (cargo audit, npm audit, pip-audit)
+    Audit,
+    /// Dependencies with newer releases (cargo outdated, npm outdated, pip list --outdated)
+    Outdated,
+    /// The dependency graph (cargo tree, npm ls, pipdeptree)
+    Tree,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Pip,
+}
+
+impl Ecosystem {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Npm => "npm",
+            Self::Pip => "pip",
+        }
+    }
+}
+
+/// Advisory severity; npm's `moderate` is `medium`. Advisories without one
+/// (RustSec, PyPI) are `unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VulnSeverity {
+    Critical,
+    High,
+    Medium,
+    Low,
+    Unknown,
+}
+
+impl VulnSeverity {
+    pub fn parse(severity: &str) -> Self {
+        match severity.to_ascii_lowercase().as_str() {
+            "critical" => Self::Critical,
+            "high" => Self::High,
+            "moderate" | "medium" => Self::Medium,
+            "low" | "info" => Self::Low,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// One advisory affecting one package, normalized across auditors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vulnerability {
+    pub ecosystem: Ecosystem,
+    pub package: String,
+    /// Installed version, when the auditor reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Advisory id (`RUSTSEC-2023-0071`, `GHSA-...`, `PYSEC-...`).
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    pub severity: VulnSeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Versions (or ranges) that fix it; empty when there is no fix.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_versions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Vulnerabilities per severity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VulnCounts {
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub unknown: usize,
+}
+
+impl VulnCounts {
+    pub fn add(&mut self, severity: VulnSeverity) {
+        match severity {
+            VulnSeverity::Critical => self.critical += 1,
+            VulnSeverity::High => self.high += 1,
+            VulnSeverity::Medium => self.medium += 1,
+            VulnSeverity::Low => self.low += 1,
+            VulnSeverity::Unknown => self.unknown += 1,
+        }
+    }
+}
+
+/// A dependency with a newer release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutdatedPackage {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    /// Version in use; missing when the package is not installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+    /// Newest version the manifest's requirement allows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wanted: Option<String>,
+    pub latest: String,
+}
+
+/// One node of the dependency tree, in depth-first order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyNode {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 0 for the project itself (each workspace member), 1 for direct dependencies.
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepsResult {
+    pub action: DepsAction,
+    /// Ecosystems asked, in order.
+    pub ecosystems: Vec<Ecosystem>,
+    /// Shell commands run, one per ecosystem.
+    pub commands: Vec<String>,
+    /// Audit: vulnerabilities per severity, before `max_results`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counts: Option<VulnCounts>,
+    /// Audit: most severe first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<Vec<Vulnerability>>,
+    /// Outdated: by ecosystem and name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outdated: Option<Vec<OutdatedPackage>>,
+    /// Tree: depth-first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<DependencyNode>>,
+    /// Entries found before `max_results` was applied.
+    pub total: usize,
+    pub truncated: bool,
+}
+
+impl DepsResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,41 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DepsError {
+    #[error(
+        "No dependency manifest found (looked for Cargo.toml, package.json, and pyproject.toml, requirements.txt, setup.py or Pipfile); pass path or ecosystem explicitly"
+    )]
+    NoManifest,
+
+    #[error("Path does not exist: {0}")]
+    PathNotFound(String),
+
+    #[error("Path is outside repository: {0}")]
+    PathOutsideRepo(String),
+
+    #[error("{program} is not installed or not on PATH ({command}); install it with `{install}`")]
+    NotInstalled {
+        program: String,
+        command: String,
+        install: String,
+    },
+
+    #[error("{command} failed (exit code {exit_code}): {stderr}")]
+    CommandFailed {
+        command: String,
+        exit_code: i32,
+        stderr: String,
+    },
+
+    #[error("Could not parse {command} output: {reason}")]
+    BadOutput { command: String, reason: String },
+
+    #[error("Failed to run command: {0}")]
+    SpawnFailed(String),
+
+    #[error(transparent)]
+    Execution(#[from] crate::execution::ExecutionError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
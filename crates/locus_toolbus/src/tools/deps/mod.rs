@@ -0,0 +1,316 @@
+mod args;
+mod error;
+pub(crate) mod parse;
+
+pub use args::{
+    DependencyNode, DepsAction, DepsArgs, DepsResult, Ecosystem, OutdatedPackage, VulnCounts,
+    VulnSeverity, Vulnerability,
+};
+pub use error::DepsError;
+
+use crate::execution::ExecutionBackend;
+use crate::permission::Permission;
+use crate::tools::lint::{EXIT_NOT_FOUND, shell_and_arg, stderr_tail};
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const PYTHON_MANIFESTS: &[&str] = &["pyproject.toml", "requirements.txt", "setup.py", "Pipfile"];
+
+/// What one command found, by action.
+enum Found {
+    Vulnerabilities(Vec<Vulnerability>),
+    Outdated(Vec<OutdatedPackage>),
+    Tree(Vec<DependencyNode>),
+}
+
+pub struct Deps {
+    repo_root: PathBuf,
+    backend: ExecutionBackend,
+}
+
+impl Deps {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            backend: ExecutionBackend::default(),
+        }
+    }
+
+    /// Run package managers through `backend`, like `bash`.
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Ecosystems the project in `dir` uses: cargo for a Cargo.toml, npm for a
+    /// package.json, pip for Python project or requirements files.
+    pub fn detect(dir: &Path) -> Vec<Ecosystem> {
+        let mut ecosystems = Vec::new();
+        if dir.join("Cargo.toml").is_file() {
+            ecosystems.push(Ecosystem::Cargo);
+        }
+        if dir.join("package.json").is_file() {
+            ecosystems.push(Ecosystem::Npm);
+        }
+        if PYTHON_MANIFESTS.iter().any(|f| dir.join(f).is_file()) {
+            ecosystems.push(Ecosystem::Pip);
+        }
+        ecosystems
+    }
+
+    /// The project directory: `path` relative to the repo root, checked to be a
+    /// directory inside it; the repo root when None.
+    fn resolve_dir(&self, path: Option<&str>) -> Result<PathBuf, DepsError> {
+        let path = match path.map(|p| p.trim().trim_start_matches("./")) {
+            Some(p) if !p.is_empty() && p != "." => p,
+            _ => return Ok(self.repo_root.clone()),
+        };
+        let full = self.repo_root.join(path);
+        if !full.is_dir() {
+            return Err(DepsError::PathNotFound(path.to_string()));
+        }
+        let canonical_root = self
+            .repo_root
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_root.clone());
+        if !full.canonicalize()?.starts_with(&canonical_root) {
+            return Err(DepsError::PathOutsideRepo(path.to_string()));
+        }
+        Ok(full)
+    }
+
+    /// Shell command for `action` in `ecosystem`, run in the project directory
+    /// `dir`. `depth` counts levels below the project (1 = direct dependencies);
+    /// pip-audit checks `requirements.txt` when `dir` has one, the active
+    /// environment otherwise.
+    pub fn command(ecosystem: Ecosystem, action: DepsAction, depth: usize, dir: &Path) -> String {
+        match (ecosystem, action) {
+            (Ecosystem::Cargo, DepsAction::Audit) => "cargo audit --json".to_string(),
+            (Ecosystem::Cargo, DepsAction::Outdated) => {
+                "cargo outdated --workspace --root-deps-only --format json".to_string()
+            }
+            (Ecosystem::Cargo, DepsAction::Tree) => {
+                format!("cargo tree --workspace --prefix depth --depth {}", depth)
+            }
+            (Ecosystem::Npm, DepsAction::Audit) => "npm audit --json".to_string(),
+            (Ecosystem::Npm, DepsAction::Outdated) => "npm outdated --json".to_string(),
+            (Ecosystem::Npm, DepsAction::Tree) => {
+                format!("npm ls --json --depth {}", depth.saturating_sub(1))
+            }
+            (Ecosystem::Pip, DepsAction::Audit) if dir.join("requirements.txt").is_file() => {
+                "pip-audit -r requirements.txt -f json".to_string()
+            }
+            (Ecosystem::Pip, DepsAction::Audit) => "pip-audit -f json".to_string(),
+            (Ecosystem::Pip, DepsAction::Outdated) => {
+                "python3 -m pip list --outdated --format json".to_string()
+            }
+            (Ecosystem::Pip, DepsAction::Tree) => "pipdeptree --json-tree".to_string(),
+        }
+    }
+
+    /// Program behind `command`, and how to install it.
+    fn program(ecosystem: Ecosystem, action: DepsAction) -> (&'static str, &'static str) {
+        match (ecosystem, action) {
+            (Ecosystem::Cargo, DepsAction::Audit) => ("cargo-audit", "cargo install cargo-audit"),
+            (Ecosystem::Cargo, DepsAction::Outdated) => {
+                ("cargo-outdated", "cargo install cargo-outdated")
+            }
+            (Ecosystem::Cargo, DepsAction::Tree) => ("cargo", "rustup"),
+            (Ecosystem::Npm, _) => ("npm", "a Node.js install"),
+            (Ecosystem::Pip, DepsAction::Audit) => ("pip-audit", "pip install pip-audit"),
+            (Ecosystem::Pip, DepsAction::Outdated) => ("pip", "a Python install"),
+            (Ecosystem::Pip, DepsAction::Tree) => ("pipdeptree", "pip install pipdeptree"),
+        }
+    }
+
+    async fn run(
+        &self,
+        ecosystem: Ecosystem,
+        action: DepsAction,
+        depth: usize,
+        dir: &Path,
+        command: &str,
+    ) -> Result<Found, DepsError> {
+        let mut prepared =
+            self.backend
+                .command(&self.repo_root, Some(dir), shell_and_arg(), command)?;
+        prepared.command.kill_on_drop(true);
+        let output = prepared
+            .command
+            .output()
+            .await
+            .map_err(|e| DepsError::SpawnFailed(e.to_string()))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let exit_code = output.status.code().unwrap_or(-1);
+        // cargo reports a missing subcommand itself; the shell exits 127.
+        if exit_code == EXIT_NOT_FOUND || stderr.contains("no such command") {
+            let (program, install) = Self::program(ecosystem, action);
+            return Err(DepsError::NotInstalled {
+                program: program.to_string(),
+                command: command.to_string(),
+                install: install.to_string(),
+            });
+        }
+
+        let found = match (ecosystem, action) {
+            (Ecosystem::Cargo, DepsAction::Audit) => {
+                parse::parse_cargo_audit(&stdout).map(Found::Vulnerabilities)
+            }
+            (Ecosystem::Npm, DepsAction::Audit) => {
+                parse::parse_npm_audit(&stdout).map(Found::Vulnerabilities)
+            }
+            (Ecosystem::Pip, DepsAction::Audit) => {
+                parse::parse_pip_audit(&stdout).map(Found::Vulnerabilities)
+            }
+            (Ecosystem::Cargo, DepsAction::Outdated) => {
+                parse::parse_cargo_outdated(&stdout).map(Found::Outdated)
+            }
+            (Ecosystem::Npm, DepsAction::Outdated) => {
+                parse::parse_npm_outdated(&stdout).map(Found::Outdated)
+            }
+            (Ecosystem::Pip, DepsAction::Outdated) => {
+                parse::parse_pip_outdated(&stdout).map(Found::Outdated)
+            }
+            (Ecosystem::Cargo, DepsAction::Tree) if exit_code == 0 => {
+                parse::parse_cargo_tree(&stdout).map(Found::Tree)
+            }
+            (Ecosystem::Cargo, DepsAction::Tree) => Err("cargo tree failed".to_string()),
+            (Ecosystem::Npm, DepsAction::Tree) => parse::parse_npm_tree(&stdout).map(Found::Tree),
+            (Ecosystem::Pip, DepsAction::Tree) => {
+                parse::parse_pip_tree(&stdout, depth).map(Found::Tree)
+            }
+        };
+        // Audits exit 1 when they find something, `npm outdated` when anything
+        // is outdated and `npm ls` on extraneous packages; their JSON still holds.
+        found.map_err(|reason| match exit_code {
+            0 => DepsError::BadOutput {
+                command: command.to_string(),
+                reason,
+            },
+            _ => DepsError::CommandFailed {
+                command: command.to_string(),
+                exit_code,
+                stderr: stderr_tail(&stderr),
+            },
+        })
+    }
+
+    async fn deps(&self, args: DepsArgs, ctx: &ToolContext) -> Result<DepsResult, DepsError> {
+        let dir = self.resolve_dir(args.path.as_deref())?;
+        let ecosystems = match args.ecosystem {
+            Some(ecosystem) => vec![ecosystem],
+            None => Self::detect(&dir),
+        };
+        if ecosystems.is_empty() {
+            return Err(DepsError::NoManifest);
+        }
+        let depth = args.depth.max(1);
+
+        let mut commands = Vec::new();
+        let mut vulnerabilities = Vec::new();
+        let mut outdated = Vec::new();
+        let mut dependencies = Vec::new();
+        for &ecosystem in &ecosystems {
+            let command = Self::command(ecosystem, args.action, depth, &dir);
+            ctx.report(format!("Running {}", command));
+            match self
+                .run(ecosystem, args.action, depth, &dir, &command)
+                .await?
+            {
+                Found::Vulnerabilities(found) => vulnerabilities.extend(found),
+                Found::Outdated(found) => outdated.extend(found),
+                Found::Tree(found) => dependencies.extend(found),
+            }
+            commands.push(command);
+        }
+
+        let mut result = DepsResult {
+            action: args.action,
+            ecosystems,
+            commands,
+            counts: None,
+            vulnerabilities: None,
+            outdated: None,
+            dependencies: None,
+            total: 0,
+            truncated: false,
+        };
+        match args.action {
+            DepsAction::Audit => {
+                vulnerabilities.sort_by(|a, b| {
+                    (a.severity, a.ecosystem, &a.package, &a.id).cmp(&(
+                        b.severity,
+                        b.ecosystem,
+                        &b.package,
+                        &b.id,
+                    ))
+                });
+                let mut counts = VulnCounts::default();
+                for vulnerability in &vulnerabilities {
+                    counts.add(vulnerability.severity);
+                }
+                result.total = vulnerabilities.len();
+                vulnerabilities.truncate(args.max_results);
+                result.truncated = vulnerabilities.len() < result.total;
+                result.counts = Some(counts);
+                result.vulnerabilities = Some(vulnerabilities);
+            }
+            DepsAction::Outdated => {
+                outdated.sort_by(|a, b| (a.ecosystem, &a.name).cmp(&(b.ecosystem, &b.name)));
+                result.total = outdated.len();
+                outdated.truncate(args.max_results);
+                result.truncated = outdated.len() < result.total;
+                result.outdated = Some(outdated);
+            }
+            DepsAction::Tree => {
+                result.total = dependencies.len();
+                dependencies.truncate(args.max_results);
+                result.truncated = dependencies.len() < result.total;
+                result.dependencies = Some(dependencies);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for Deps {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    /// Runs package managers, which fetch advisories and registry data.
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: DepsArgs = serde_json::from_value(args)?;
+        // Dropping the run kills the command (kill_on_drop).
+        tokio::select! {
+            result = self.deps(args, &ctx) => Ok(result?.to_json()),
+            _ = ctx.cancel.cancelled() => Err(anyhow::anyhow!("Dependency check cancelled")),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Parsers for package manager output, each into the normalized [super::args] types.
+//!
+//! Errors are the reason the output could not be read; the caller decides
+//! whether that means the command failed.
+
+use std::collections::HashSet;
+
+use serde_json::Value as JsonValue;
+
+use super::args::{DependencyNode, Ecosystem, OutdatedPackage, VulnSeverity, Vulnerability};
+
+/// Longest advisory title kept, for pip-audit's free-text descriptions.
+const MAX_TITLE_CHARS: usize = 200;
+
+fn json(text: &str) -> Result<JsonValue, String> {
+    serde_json::from_str(text.trim()).map_err(|e| e.to_string())
+}
+
+fn string(value: &JsonValue) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn strings(value: &JsonValue) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(string)
+        .collect()
+}
+
+/// First line of `text`, cut to [MAX_TITLE_CHARS].
+fn title(text: &str) -> Option<String> {
+    let line = text.trim().lines().next()?.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let mut title: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    if line.chars().count() > MAX_TITLE_CHARS {
+        title.push('…');
+    }
+    Some(title)
+}
+
+/// `cargo audit --json`: `vulnerabilities.list[]` with the advisory, the
+/// installed package and the patched versions.
+pub(crate) fn parse_cargo_audit(text: &str) -> Result<Vec<Vulnerability>, String> {
+    let report = json(text)?;
+    let list = report["vulnerabilities"]["list"]
+        .as_array()
+        .ok_or("no vulnerabilities.list")?;
+    Ok(list
+        .iter()
+        .filter_map(|entry| {
+            let advisory = &entry["advisory"];
+            Some(Vulnerability {
+                ecosystem: Ecosystem::Cargo,
+                package: string(&entry["package"]["name"])
+                    .or_else(|| string(&advisory["package"]))?,
+                version: string(&entry["package"]["version"]),
+                id: string(&advisory["id"])?,
+                aliases: strings(&advisory["aliases"]),
+                // RustSec gives a CVSS vector, not a severity word.
+                severity: VulnSeverity::Unknown,
+                title: advisory["title"].as_str().and_then(title),
+                fixed_versions: strings(&entry["versions"]["patched"]),
+                url: string(&advisory["url"]),
+            })
+        })
+        .collect())
+}
+
+/// `npm audit --json` (npm 7+): `vulnerabilities.<package>.via[]`, where object
+/// entries are advisories and strings point at the vulnerable dependency.
+pub(crate) fn parse_npm_audit(text: &str) -> Result<Vec<Vulnerability>, String> {
+    let report = json(text)?;
+    let packages = report["vulnerabilities"]
+        .as_object()
+        .ok_or("no vulnerabilities object")?;
+    let mut seen = HashSet::new();
+    let mut vulnerabilities = Vec::new();
+    for (name, entry) in packages {
+        let fixed = match &entry["fixAvailable"] {
+            JsonValue::Object(fix) => fix
+                .get("version")
+                .and_then(string)
+                .map(|v| {
+                    format!(
+                        "{}@{}",
+                        fix.get("name").and_then(string).unwrap_or_default(),
+                        v
+                    )
+                })
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        };
+        for via in entry["via"].as_array().into_iter().flatten() {
+            if !via.is_object() {
+                continue;
+            }
+            let package = string(&via["name"]).unwrap_or_else(|| name.clone());
+            let url = string(&via["url"]);
+            // GitHub advisory URLs end in the GHSA id.
+            let id = url
+                .as_deref()
+                .and_then(|u| u.rsplit('/').next())
+                .filter(|s| s.starts_with("GHSA-"))
+                .map(str::to_string)
+                .or_else(|| via["source"].as_u64().map(|n| n.to_string()))
+                .unwrap_or_else(|| "unknown".to_string());
+            if !seen.insert((package.clone(), id.clone())) {
+                continue;
+            }
+            vulnerabilities.push(Vulnerability {
+                ecosystem: Ecosystem::Npm,
+                package,
+                version: None,
+                id,
+                aliases: Vec::new(),
+                severity: via["severity"]
+                    .as_str()
+                    .map_or(VulnSeverity::Unknown, VulnSeverity::parse),
+                title: via["title"].as_str().and_then(title),
+                fixed_versions: fixed.clone(),
+                url,
+            });
+        }
+    }
+    Ok(vulnerabilities)
+}
+
+/// `pip-audit -f json`: `{"dependencies": [...]}`, or a bare list from older
+/// versions; each dependency with its `vulns`.
+pub(crate) fn parse_pip_audit(text: &str) -> Result<Vec<Vulnerability>, String> {
+    let report = json(text)?;
+    let dependencies = report["dependencies"]
+        .as_array()
+        .or_else(|| report.as_array())
+        .ok_or("no dependencies list")?;
+    let mut vulnerabilities = Vec::new();
+    for dependency in dependencies {
+        let Some(package) = string(&dependency["name"]) else {
+            continue;
+        };
+        for vuln in dependency["vulns"].as_array().into_iter().flatten() {
+            let Some(id) = string(&vuln["id"]) else {
+                continue;
+            };
+            vulnerabilities.push(Vulnerability {
+                ecosystem: Ecosystem::Pip,
+                package: package.clone(),
+                version: string(&dependency["version"]),
+                id,
+                aliases: strings(&vuln["aliases"]),
+                severity: VulnSeverity::Unknown,
+                title: vuln["description"].as_str().and_then(title),
+                fixed_versions: strings(&vuln["fix_versions"]),
+                url: None,
+            });
+        }
+    }
+    Ok(vulnerabilities)
+}
+
+/// A version, or None for cargo outdated's `---` (no such version).
+fn version(value: &JsonValue) -> Option<String> {
+    string(value).filter(|v| v != "---")
+}
+
+/// `cargo outdated --format json`: one JSON object per workspace member, each
+/// with its `dependencies`. A dependency listed by several members appears once.
+pub(crate) fn parse_cargo_outdated(text: &str) -> Result<Vec<OutdatedPackage>, String> {
+    let members: Vec<&str> = text
+        .lines()
+        .filter(|l| l.trim_start().starts_with('{'))
+        .collect();
+    if members.is_empty() {
+        return Err("no JSON lines".to_string());
+    }
+    let mut seen = HashSet::new();
+    let mut outdated = Vec::new();
+    for line in members {
+        let member = json(line)?;
+        for dependency in member["dependencies"].as_array().into_iter().flatten() {
+            let Some(name) = string(&dependency["name"]) else {
+                continue;
+            };
+            let current = version(&dependency["project"]);
+            let wanted = version(&dependency["compat"]);
+            let Some(latest) = version(&dependency["latest"]).or_else(|| wanted.clone()) else {
+                continue;
+            };
+            if seen.insert((name.clone(), current.clone(), latest.clone())) {
+                outdated.push(OutdatedPackage {
+                    ecosystem: Ecosystem::Cargo,
+                    name,
+                    current,
+                    wanted,
+                    latest,
+                });
+            }
+        }
+    }
+    Ok(outdated)
+}
+
+/// `npm outdated --json`: an object keyed by package; empty output when
+/// everything is current.
+pub(crate) fn parse_npm_outdated(text: &str) -> Result<Vec<OutdatedPackage>, String> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let report = json(text)?;
+    let packages = report.as_object().ok_or("not an object")?;
+    Ok(packages
+        .iter()
+        .filter_map(|(name, entry)| {
+            // Packages installed under several dependents come as a list.
+            let entry = entry.as_array().and_then(|a| a.first()).unwrap_or(entry);
+            Some(OutdatedPackage {
+                ecosystem: Ecosystem::Npm,
+                name: name.clone(),
+                current: string(&entry["current"]),
+                wanted: string(&entry["wanted"]),
+                latest: string(&entry["latest"])?,
+            })
+        })
+        .collect())
+}
+
+/// `pip list --outdated --format json`: `[{name, version, latest_version}]`.
+pub(crate) fn parse_pip_outdated(text: &str) -> Result<Vec<OutdatedPackage>, String> {
+    let report = json(text)?;
+    let packages = report.as_array().ok_or("not a list")?;
+    Ok(packages
+        .iter()
+        .filter_map(|entry| {
+            Some(OutdatedPackage {
+                ecosystem: Ecosystem::Pip,
+                name: string(&entry["name"])?,
+                current: string(&entry["version"]),
+                wanted: None,
+                latest: string(&entry["latest_version"])?,
+            })
+        })
+        .collect())
+}
+
+/// `cargo tree --prefix depth`: `<depth><name> v<version> [(path)] [(*)]` per
+/// line; blank lines separate workspace members.
+pub(crate) fn parse_cargo_tree(text: &str) -> Result<Vec<DependencyNode>, String> {
+    let mut nodes = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        let depth = line[..digits]
+            .parse()
+            .map_err(|_| format!("no depth prefix: {}", line))?;
+        let mut parts = line[digits..].split_whitespace();
+        let Some(name) = parts.next() else {
+            continue;
+        };
+        let version = parts.next().map(|v| v.trim_start_matches('v').to_string());
+        nodes.push(DependencyNode {
+            ecosystem: Ecosystem::Cargo,
+            name: name.to_string(),
+            version,
+            depth,
+        });
+    }
+    Ok(nodes)
+}
+
+/// `npm ls --json`: the project with nested `dependencies` objects.
+pub(crate) fn parse_npm_tree(text: &str) -> Result<Vec<DependencyNode>, String> {
+    fn walk(name: &str, node: &JsonValue, depth: usize, nodes: &mut Vec<DependencyNode>) {
+        nodes.push(DependencyNode {
+            ecosystem: Ecosystem::Npm,
+            name: name.to_string(),
+            version: string(&node["version"]),
+            depth,
+        });
+        for (child, value) in node["dependencies"].as_object().into_iter().flatten() {
+            walk(child, value, depth + 1, nodes);
+        }
+    }
+    let report = json(text)?;
+    if !report.is_object() {
+        return Err("not an object".to_string());
+    }
+    let mut nodes = Vec::new();
+    let name = string(&report["name"]).unwrap_or_else(|| "(project)".to_string());
+    walk(&name, &report, 0, &mut nodes);
+    Ok(nodes)
+}
+
+/// `pipdeptree --json-tree`: top-level packages with nested `dependencies`,
+/// cut at `max_depth` (pipdeptree has no depth flag).
+pub(crate) fn parse_pip_tree(text: &str, max_depth: usize) -> Result<Vec<DependencyNode>, String> {
+    fn walk(node: &JsonValue, depth: usize, max_depth: usize, nodes: &mut Vec<DependencyNode>) {
+        let Some(name) = string(&node["package_name"]).or_else(|| string(&node["key"])) else {
+            return;
+        };
+        nodes.push(DependencyNode {
+            ecosystem: Ecosystem::Pip,
+            name,
+            version: string(&node["installed_version"]),
+            depth,
+        });
+        if depth < max_depth {
+            for child in node["dependencies"].as_array().into_iter().flatten() {
+                walk(child, depth + 1, max_depth, nodes);
+            }
+        }
+    }
+    let report = json(text)?;
+    let roots = report.as_array().ok_or("not a list")?;
+    let mut nodes = Vec::new();
+    for root in roots {
+        walk(root, 1, max_depth, &mut nodes);
+    }
+    Ok(nodes)
+}
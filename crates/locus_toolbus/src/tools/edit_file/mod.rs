@@ -4,7 +4,9 @@ mod error;
 pub use args::{EditFileArgs, EditOperation};
 pub use error::EditFileError;
 
+use crate::execution::ExecutionBackend;
 use crate::history::EditHistory;
+use crate::permission::Permission;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
@@ -15,6 +17,7 @@ use std::sync::OnceLock;
 pub struct EditFile {
     workspace_root: PathBuf,
     history: Arc<EditHistory>,
+    backend: ExecutionBackend,
 }
 
 impl EditFile {
@@ -22,9 +25,27 @@ impl EditFile {
         Self {
             workspace_root,
             history,
+            backend: ExecutionBackend::default(),
         }
     }
 
+    /// Write through `backend` (only changes anything for Docker with `write_tools`).
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    async fn write(&self, file_path: &Path, content: &str) -> Result<(), EditFileError> {
+        self.backend
+            .write_file(
+                &normalize_path(&self.workspace_root),
+                file_path,
+                content.as_bytes(),
+            )
+            .await
+            .map_err(|e| EditFileError::WriteFailed(e.to_string()))
+    }
+
     fn validate_path(&self, path: &str) -> Result<PathBuf, EditFileError> {
         let path = Path::new(path);
 
@@ -108,6 +129,10 @@ impl Tool for EditFile {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let tool_args: EditFileArgs = serde_json::from_value(args)?;
 
@@ -149,9 +174,7 @@ impl EditFile {
                 .unwrap_or_default();
 
             // Write the file
-            tokio::fs::write(&file_path, new_string)
-                .await
-                .map_err(|e| EditFileError::WriteFailed(e.to_string()))?;
+            self.write(&file_path, new_string).await?;
 
             // Record to history for undo
             let _ = self
@@ -196,30 +219,16 @@ impl EditFile {
         let old_string = tool_args.old_string.as_ref().unwrap();
         let new_string = tool_args.new_string.as_ref().unwrap();
 
-        // Count occurrences
-        let match_count = content.matches(old_string).count();
-
-        if match_count == 0 {
-            return Err(EditFileError::OldStringNotFound.into());
-        }
-
-        // Check for multiple matches when replace_all is false
-        if match_count > 1 && !tool_args.replace_all {
-            return Err(EditFileError::MultipleMatches.into());
-        }
-
-        // Perform the replacement
-        let new_content = if tool_args.replace_all {
-            content.replace(old_string, new_string)
-        } else {
-            // Only replace first occurrence
-            content.replacen(old_string, new_string, 1)
-        };
+        let (new_content, match_count) =
+            replace_matches(&content, old_string, new_string, tool_args.replace_all).map_err(
+                |e| match e {
+                    MatchError::NotFound => EditFileError::OldStringNotFound,
+                    MatchError::Multiple => EditFileError::MultipleMatches,
+                },
+            )?;
 
         // Write the file
-        tokio::fs::write(&file_path, &new_content)
-            .await
-            .map_err(|e| EditFileError::WriteFailed(e.to_string()))?;
+        self.write(&file_path, &new_content).await?;
 
         // Record to history for undo
         let _ = self
@@ -255,57 +264,11 @@ impl EditFile {
             .await
             .map_err(|e| EditFileError::ReadFailed(e.to_string()))?;
 
-        let mut content = original_content.clone();
-        let mut total_matches = 0;
-        let mut total_replaced = 0;
-
-        // Apply each edit in sequence
-        for (index, edit) in edits.iter().enumerate() {
-            let edit_num = index + 1;
-
-            // Check if old_string is empty (overwrite in multiedit is not allowed)
-            if edit.old_string.is_empty() {
-                return Err(EditFileError::InvalidArgs(format!(
-                    "Edit {}: old_string cannot be empty in multiedit mode",
-                    edit_num
-                ))
-                .into());
-            }
-
-            // Count occurrences
-            let match_count = content.matches(&edit.old_string).count();
-
-            if match_count == 0 {
-                return Err(EditFileError::MultieditStringNotFound {
-                    edit_number: edit_num,
-                }
-                .into());
-            }
-
-            // Check for multiple matches when replace_all is false
-            if match_count > 1 && !edit.replace_all {
-                return Err(EditFileError::MultieditMultipleMatches {
-                    edit_number: edit_num,
-                }
-                .into());
-            }
-
-            // Perform the replacement
-            content = if edit.replace_all {
-                total_matches += match_count;
-                total_replaced += match_count;
-                content.replace(&edit.old_string, &edit.new_string)
-            } else {
-                total_matches += 1;
-                total_replaced += 1;
-                content.replacen(&edit.old_string, &edit.new_string, 1)
-            };
-        }
+        let (content, total_matches) = apply_multiedit(&original_content, edits)?;
+        let total_replaced = total_matches;
 
         // Write the file
-        tokio::fs::write(&file_path, &content)
-            .await
-            .map_err(|e| EditFileError::WriteFailed(e.to_string()))?;
+        self.write(&file_path, &content).await?;
 
         // Record to history for undo
         let _ = self
@@ -332,3 +295,89 @@ impl EditFile {
 
 /// Max total old+new content size to include in result for TUI diff (bytes).
 const MAX_DIFF_CONTENT_LEN: usize = 4096;
+
+/// The content `args` turns `content` into, without touching the file; what
+/// `edit_file` would write if the file held `content`. Overwrites ignore it.
+pub fn apply_edits(content: &str, args: &EditFileArgs) -> Result<String, EditFileError> {
+    if let Some(edits) = &args.edits {
+        return apply_multiedit(content, edits).map(|(content, _)| content);
+    }
+    let new_string = args.new_string.as_deref().ok_or_else(|| {
+        EditFileError::InvalidArgs("new_string is required when not using edits array".to_string())
+    })?;
+    match args.old_string.as_deref() {
+        None | Some("") => Ok(new_string.to_string()),
+        Some(old_string) => replace_matches(content, old_string, new_string, args.replace_all)
+            .map(|(content, _)| content)
+            .map_err(|e| match e {
+                MatchError::NotFound => EditFileError::OldStringNotFound,
+                MatchError::Multiple => EditFileError::MultipleMatches,
+            }),
+    }
+}
+
+/// Apply `edits` in sequence; returns the content and the number of matches replaced.
+fn apply_multiedit(
+    content: &str,
+    edits: &[EditOperation],
+) -> Result<(String, usize), EditFileError> {
+    let mut content = content.to_string();
+    let mut total_matches = 0;
+    for (index, edit) in edits.iter().enumerate() {
+        let edit_num = index + 1;
+
+        // Check if old_string is empty (overwrite in multiedit is not allowed)
+        if edit.old_string.is_empty() {
+            return Err(EditFileError::InvalidArgs(format!(
+                "Edit {}: old_string cannot be empty in multiedit mode",
+                edit_num
+            )));
+        }
+
+        let (next, replaced) = replace_matches(
+            &content,
+            &edit.old_string,
+            &edit.new_string,
+            edit.replace_all,
+        )
+        .map_err(|e| match e {
+            MatchError::NotFound => EditFileError::MultieditStringNotFound {
+                edit_number: edit_num,
+            },
+            MatchError::Multiple => EditFileError::MultieditMultipleMatches {
+                edit_number: edit_num,
+            },
+        })?;
+        content = next;
+        total_matches += if edit.replace_all { replaced } else { 1 };
+    }
+    Ok((content, total_matches))
+}
+
+enum MatchError {
+    NotFound,
+    Multiple,
+}
+
+/// Replace the only match of `old` (or every match with `replace_all`).
+/// Returns the new content and how many matches `content` had.
+fn replace_matches(
+    content: &str,
+    old: &str,
+    new: &str,
+    replace_all: bool,
+) -> Result<(String, usize), MatchError> {
+    let match_count = content.matches(old).count();
+    if match_count == 0 {
+        return Err(MatchError::NotFound);
+    }
+    if match_count > 1 && !replace_all {
+        return Err(MatchError::Multiple);
+    }
+    let replaced = if replace_all {
+        content.replace(old, new)
+    } else {
+        content.replacen(old, new, 1)
+    };
+    Ok((replaced, match_count))
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+/// Action to perform on a file's edit history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileHistoryAction {
+    List,
+    Diff,
+    Restore,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileHistoryArgs {
+    /// Path to the file (relative to workspace root). Required.
+    pub path: String,
+
+    /// Defaults to `list`.
+    #[serde(default = "default_action")]
+    pub action: FileHistoryAction,
+
+    /// Version number for `diff` and `restore` (0 = before the first edit).
+    #[serde(default)]
+    pub version: Option<usize>,
+}
+
+fn default_action() -> FileHistoryAction {
+    FileHistoryAction::List
+}
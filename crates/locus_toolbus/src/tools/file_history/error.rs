@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FileHistoryError {
+    #[error("Path is outside workspace: {0}")]
+    PathOutsideWorkspace(String),
+
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+
+    #[error("No edit history for this file: {0}")]
+    NoHistory(String),
+
+    #[error("Missing version for action '{0}'")]
+    MissingVersion(String),
+
+    #[error("Version {version} not found (available: 0..={latest})")]
+    VersionNotFound { version: usize, latest: usize },
+}
@@ -0,0 +1,212 @@
+mod args;
+mod error;
+
+pub use args::{FileHistoryAction, FileHistoryArgs};
+pub use error::FileHistoryError;
+
+use crate::history::{EditHistory, FileVersion};
+use crate::permission::Permission;
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+pub struct FileHistory {
+    workspace_root: PathBuf,
+    history: Arc<EditHistory>,
+    protected: ProtectedPathMatcher,
+}
+
+impl FileHistory {
+    pub fn new(workspace_root: PathBuf, history: Arc<EditHistory>) -> Self {
+        Self {
+            workspace_root,
+            history,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path` by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    fn validate_path(&self, path: &str) -> Result<PathBuf, FileHistoryError> {
+        let path = Path::new(path);
+
+        if path.as_os_str().is_empty() {
+            return Err(FileHistoryError::InvalidPath(
+                "Path cannot be empty".to_string(),
+            ));
+        }
+
+        let full_path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.workspace_root.join(path)
+        };
+
+        let normalized_path = normalize_path(&full_path);
+        let normalized_workspace = normalize_path(&self.workspace_root);
+
+        if !normalized_path.starts_with(&normalized_workspace) {
+            return Err(FileHistoryError::PathOutsideWorkspace(
+                full_path.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(normalized_path)
+    }
+
+    fn list(path: &str, versions: &[FileVersion]) -> JsonValue {
+        let entries: Vec<JsonValue> = versions
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let (added, removed) = match i {
+                    0 => (0, 0),
+                    _ => line_delta(&versions[i - 1].content, &v.content),
+                };
+                serde_json::json!({
+                    "version": v.version,
+                    "ts": v.ts,
+                    "lines": v.content.lines().count(),
+                    "bytes": v.content.len(),
+                    "added": added,
+                    "removed": removed,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "path": path,
+            "versions": entries,
+            "latest": versions.len().saturating_sub(1),
+        })
+    }
+}
+
+/// Approximate added/removed line counts: strip the common prefix and suffix, then
+/// count what is left on each side. Cheap and good enough for a history overview.
+fn line_delta(old: &str, new: &str) -> (usize, usize) {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    (
+        new_lines.len() - prefix - suffix,
+        old_lines.len() - prefix - suffix,
+    )
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component.as_os_str());
+                }
+            }
+            _ => normalized.push(component.as_os_str()),
+        }
+    }
+    normalized
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for FileHistory {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let tool_args: FileHistoryArgs = serde_json::from_value(args)?;
+        let file_path = self.validate_path(&tool_args.path)?;
+        // The file may be gone (restoring it is the point), so no resolve_file
+        self.protected
+            .check(&self.workspace_root, &file_path, &tool_args.path)?;
+
+        let versions = self.history.versions(&file_path).await?;
+        if versions.is_empty() {
+            return Err(FileHistoryError::NoHistory(tool_args.path).into());
+        }
+        let latest = versions.len() - 1;
+        let require_version = |action: &str| -> Result<usize, FileHistoryError> {
+            let version = tool_args
+                .version
+                .ok_or_else(|| FileHistoryError::MissingVersion(action.to_string()))?;
+            if version > latest {
+                return Err(FileHistoryError::VersionNotFound { version, latest });
+            }
+            Ok(version)
+        };
+
+        match tool_args.action {
+            FileHistoryAction::List => Ok(Self::list(&tool_args.path, &versions)),
+            FileHistoryAction::Diff => {
+                let version = require_version("diff")?;
+                let old = match version {
+                    0 => "",
+                    v => versions[v - 1].content.as_str(),
+                };
+                Ok(serde_json::json!({
+                    "path": tool_args.path,
+                    "version": version,
+                    "ts": versions[version].ts,
+                    "old_content": old,
+                    "new_content": versions[version].content,
+                }))
+            }
+            FileHistoryAction::Restore => {
+                let version = require_version("restore")?;
+                let (old, new) = self
+                    .history
+                    .restore(&file_path, version)
+                    .await?
+                    .ok_or(FileHistoryError::VersionNotFound { version, latest })?;
+                Ok(serde_json::json!({
+                    "success": true,
+                    "path": tool_args.path,
+                    "version": version,
+                    "old_content": old,
+                    "new_content": new,
+                    "message": format!("Restored to version {}", version)
+                }))
+            }
+        }
+    }
+}
@@ -4,29 +4,16 @@ mod error;
 pub use args::{FinderArgs, FinderResult, SearchMatch};
 pub use error::FinderError;
 
+use crate::protected_paths::ProtectedPathMatcher;
 use crate::tools::{Glob, Grep, GrepArgs, Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
-// TODO: Future Enhancements for Intelligent Search
-// ================================================
-// 1. Semantic Search: Add embedding-based search using vector databases (e.g., LanceDB, Qdrant)
-//    - Convert code to embeddings for natural language queries
-//    - Allow queries like "find authentication code" without exact text match
-//    - Consider models: VoyageCode3, OpenAI text-embedding-3, Jina Code Embeddings V2
-//
-// 2. Symbol Search: AST-based code intelligence using tree-sitter
-//    - Find functions, classes, structs, enums, traits by name
-//    - Support cross-file symbol resolution
-//    - Add "go-to-definition" and "find-references" capabilities
-//
-// 3. LSP Integration: Leverage Language Server Protocol for deep code understanding
-//    - Real-time symbol indexing
-//    - Type-aware search
-//    - Rename refactoring support
-//    - Hover documentation
+// Natural-language queries are served by `semantic_search`, symbol lookups by
+// `code_nav`, and type-aware answers by the `lsp_*` tools; Finder stays a fast
+// text and filename search.
 
 pub struct Finder {
     repo_root: Arc<std::path::PathBuf>,
@@ -44,6 +31,13 @@ impl Finder {
         }
     }
 
+    /// Leave out paths matching `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.glob = self.glob.with_protected_paths(protected.clone());
+        self.grep = self.grep.with_protected_paths(protected);
+        self
+    }
+
     fn get_file_extensions(&self, file_type: &str) -> Vec<&'static str> {
         match file_type.to_lowercase().as_str() {
             "rust" | "rs" => vec![".rs"],
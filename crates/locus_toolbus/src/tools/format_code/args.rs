@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FormatCodeArgs {
+    /// `check` lists files that need formatting; `apply` formats them
+    #[serde(default)]
+    pub mode: FormatMode,
+
+    /// Files or directories to format, relative to repo root (defaults to repo root)
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Formatter to run; every formatter the repo is set up for when omitted
+    #[serde(default)]
+    pub formatter: Option<Formatter>,
+}
+
+impl FormatCodeArgs {
+    pub fn new(mode: FormatMode) -> Self {
+        Self {
+            mode,
+            paths: Vec::new(),
+            formatter: None,
+        }
+    }
+
+    pub fn with_formatter(mut self, formatter: Formatter) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+}
+
+impl Default for FormatCodeArgs {
+    fn default() -> Self {
+        Self::new(FormatMode::Check)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormatMode {
+    /// Report files that are not formatted; change nothing
+    #[default]
+    Check,
+    /// Format the files and record each change in the edit history
+    Apply,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Formatter {
+    /// `rustfmt`, with each crate's edition
+    Rustfmt,
+    /// `prettier`, through `npx`
+    Prettier,
+    /// `black`
+    Black,
+}
+
+impl Formatter {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rustfmt => "rustfmt",
+            Self::Prettier => "prettier",
+            Self::Black => "black",
+        }
+    }
+}
+
+/// A file that needs (check) or got (apply) formatting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormattedFile {
+    /// Path relative to the repo root.
+    pub path: String,
+    pub formatter: Formatter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatCodeResult {
+    pub mode: FormatMode,
+    /// Formatters that ran, in order.
+    pub formatters: Vec<Formatter>,
+    /// Shell commands run, in order.
+    pub commands: Vec<String>,
+    /// Check: files that are not formatted. Apply: files that changed.
+    pub files: Vec<FormattedFile>,
+    /// Every file in scope was already formatted.
+    pub clean: bool,
+}
+
+impl FormatCodeResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FormatCodeError {
+    #[error(
+        "No formatter found for this repo (looked for Cargo.toml, a Prettier config, and black in pyproject.toml); pass formatter explicitly"
+    )]
+    NoFormatter,
+
+    #[error("Path does not exist: {0}")]
+    PathNotFound(String),
+
+    #[error("Path is outside repository: {0}")]
+    PathOutsideRepo(String),
+
+    #[error("{formatter} is not installed or not on PATH ({command})")]
+    NotInstalled { formatter: String, command: String },
+
+    #[error("{command} failed (exit code {exit_code}): {stderr}")]
+    CommandFailed {
+        command: String,
+        exit_code: i32,
+        stderr: String,
+    },
+
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("Failed to run formatter: {0}")]
+    SpawnFailed(String),
+
+    #[error(transparent)]
+    Execution(#[from] crate::execution::ExecutionError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl From<ignore::Error> for FormatCodeError {
+    fn from(err: ignore::Error) -> Self {
+        FormatCodeError::Walk(err.to_string())
+    }
+}
@@ -0,0 +1,409 @@
+mod args;
+mod error;
+
+pub use args::{FormatCodeArgs, FormatCodeResult, FormatMode, FormattedFile, Formatter};
+pub use error::FormatCodeError;
+
+use crate::execution::{ExecutionBackend, shell_quote};
+use crate::history::EditHistory;
+use crate::permission::Permission;
+use crate::tools::lint::parse::repo_relative;
+use crate::tools::lint::{EXIT_NOT_FOUND, shell_and_arg, stderr_tail};
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+const PRETTIER_CONFIGS: &[&str] = &[
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.json5",
+    ".prettierrc.yml",
+    ".prettierrc.yaml",
+    ".prettierrc.toml",
+    ".prettierrc.js",
+    ".prettierrc.cjs",
+    ".prettierrc.mjs",
+    "prettier.config.js",
+    "prettier.config.cjs",
+    "prettier.config.mjs",
+];
+
+/// Edition rustfmt assumes, like cargo, when a crate sets none.
+const DEFAULT_EDITION: &str = "2015";
+
+pub struct FormatCode {
+    repo_root: PathBuf,
+    history: Arc<EditHistory>,
+    backend: ExecutionBackend,
+}
+
+impl FormatCode {
+    pub fn new(repo_root: PathBuf, history: Arc<EditHistory>) -> Self {
+        Self {
+            repo_root,
+            history,
+            backend: ExecutionBackend::default(),
+        }
+    }
+
+    /// Run formatters through `backend`, like `bash`.
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Formatters `repo_root` is set up for: rustfmt for a Cargo.toml, prettier
+    /// for a Prettier config (or `prettier` in package.json), black for
+    /// `[tool.black]` in pyproject.toml.
+    pub fn detect(repo_root: &Path) -> Vec<Formatter> {
+        let contains = |file: &str, needle: &str| {
+            std::fs::read_to_string(repo_root.join(file)).is_ok_and(|c| c.contains(needle))
+        };
+        let mut formatters = Vec::new();
+        if repo_root.join("Cargo.toml").is_file() {
+            formatters.push(Formatter::Rustfmt);
+        }
+        if PRETTIER_CONFIGS.iter().any(|f| repo_root.join(f).is_file())
+            || contains("package.json", "\"prettier\"")
+        {
+            formatters.push(Formatter::Prettier);
+        }
+        if contains("pyproject.toml", "[tool.black") {
+            formatters.push(Formatter::Black);
+        }
+        formatters
+    }
+
+    /// Each path relative to the repo root, checked to exist inside it; the repo
+    /// root alone when none are given.
+    fn resolve_paths(&self, paths: &[String]) -> Result<Vec<String>, FormatCodeError> {
+        let canonical_root = self
+            .repo_root
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_root.clone());
+        let mut resolved = Vec::new();
+        for path in paths {
+            let path = match path.trim().trim_start_matches("./") {
+                "" | "." => ".",
+                p => p,
+            };
+            let full = self.repo_root.join(path);
+            if !full.exists() {
+                return Err(FormatCodeError::PathNotFound(path.to_string()));
+            }
+            if !full.canonicalize()?.starts_with(&canonical_root) {
+                return Err(FormatCodeError::PathOutsideRepo(path.to_string()));
+            }
+            resolved.push(path.to_string());
+        }
+        if resolved.is_empty() {
+            resolved.push(".".to_string());
+        }
+        Ok(resolved)
+    }
+
+    /// Shell command for `formatter` over `targets`: listing the files it would
+    /// change (`check`) or rewriting them. Prettier and black take directories;
+    /// rustfmt takes `.rs` files and is run once per edition (this gives the
+    /// default edition, see [Self::rustfmt_command]).
+    pub fn command(formatter: Formatter, mode: FormatMode, targets: &[String]) -> String {
+        let targets: Vec<String> = targets.iter().map(|t| shell_quote(t)).collect();
+        let targets = targets.join(" ");
+        match (formatter, mode) {
+            (Formatter::Rustfmt, _) => rustfmt_line(DEFAULT_EDITION, mode, &targets),
+            (Formatter::Prettier, FormatMode::Check) => {
+                format!("npx --no-install prettier --list-different {}", targets)
+            }
+            (Formatter::Prettier, FormatMode::Apply) => {
+                format!("npx --no-install prettier --write {}", targets)
+            }
+            (Formatter::Black, FormatMode::Check) => format!("black --check {}", targets),
+            (Formatter::Black, FormatMode::Apply) => format!("black -q {}", targets),
+        }
+    }
+
+    /// rustfmt command for `files` of one `edition`.
+    pub fn rustfmt_command(edition: &str, mode: FormatMode, files: &[String]) -> String {
+        let files: Vec<String> = files.iter().map(|f| shell_quote(f)).collect();
+        rustfmt_line(edition, mode, &files.join(" "))
+    }
+
+    /// Commands that check (or format) `targets`, one per rustfmt edition.
+    fn commands(
+        &self,
+        formatter: Formatter,
+        mode: FormatMode,
+        targets: &[String],
+    ) -> Result<Vec<String>, FormatCodeError> {
+        if formatter != Formatter::Rustfmt {
+            return Ok(vec![Self::command(formatter, mode, targets)]);
+        }
+        let mut by_edition: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for file in rust_files(&self.repo_root, targets)? {
+            by_edition
+                .entry(rust_edition(&self.repo_root, &file))
+                .or_default()
+                .push(file);
+        }
+        Ok(by_edition
+            .iter()
+            .map(|(edition, files)| Self::rustfmt_command(edition, mode, files))
+            .collect())
+    }
+
+    /// Run one command; returns the files it lists as not formatted (check) or
+    /// nothing (apply).
+    async fn run(
+        &self,
+        formatter: Formatter,
+        mode: FormatMode,
+        command: &str,
+    ) -> Result<Vec<String>, FormatCodeError> {
+        let mut prepared = self.backend.command(
+            &self.repo_root,
+            Some(&self.repo_root),
+            shell_and_arg(),
+            command,
+        )?;
+        prepared.command.kill_on_drop(true);
+        let output = prepared
+            .command
+            .output()
+            .await
+            .map_err(|e| FormatCodeError::SpawnFailed(e.to_string()))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let exit_code = output.status.code().unwrap_or(-1);
+        if exit_code == EXIT_NOT_FOUND {
+            return Err(FormatCodeError::NotInstalled {
+                formatter: formatter.name().to_string(),
+                command: command.to_string(),
+            });
+        }
+
+        let listed = match mode {
+            FormatMode::Check => unformatted_files(&self.repo_root, formatter, &stdout, &stderr),
+            FormatMode::Apply => Vec::new(),
+        };
+        // Check modes exit 1 when they list files; any other failure is an error
+        // (syntax errors, a missing formatter behind npx).
+        if exit_code != 0 && listed.is_empty() {
+            return Err(FormatCodeError::CommandFailed {
+                command: command.to_string(),
+                exit_code,
+                stderr: stderr_tail(if stderr.trim().is_empty() {
+                    &stdout
+                } else {
+                    &stderr
+                }),
+            });
+        }
+        Ok(listed)
+    }
+
+    async fn format(
+        &self,
+        args: FormatCodeArgs,
+        ctx: &ToolContext,
+    ) -> Result<FormatCodeResult, FormatCodeError> {
+        let targets = self.resolve_paths(&args.paths)?;
+        let formatters = match args.formatter {
+            Some(formatter) => vec![formatter],
+            None => Self::detect(&self.repo_root),
+        };
+        if formatters.is_empty() {
+            return Err(FormatCodeError::NoFormatter);
+        }
+
+        let mut commands = Vec::new();
+        let mut files = Vec::new();
+        for &formatter in &formatters {
+            let mut unformatted = Vec::new();
+            for command in self.commands(formatter, FormatMode::Check, &targets)? {
+                ctx.report(format!("Running {}", command));
+                unformatted.extend(self.run(formatter, FormatMode::Check, &command).await?);
+                commands.push(command);
+            }
+            unformatted.sort();
+            unformatted.dedup();
+            if args.mode == FormatMode::Check || unformatted.is_empty() {
+                files.extend(
+                    unformatted
+                        .into_iter()
+                        .map(|path| FormattedFile { path, formatter }),
+                );
+                continue;
+            }
+
+            // Format exactly the listed files, so every change can be recorded.
+            let mut before = Vec::new();
+            for path in &unformatted {
+                let content = tokio::fs::read_to_string(self.repo_root.join(path)).await?;
+                ctx.writes.before_write(path, &content);
+                before.push(content);
+            }
+            for command in self.commands(formatter, FormatMode::Apply, &unformatted)? {
+                ctx.report(format!("Running {}", command));
+                self.run(formatter, FormatMode::Apply, &command).await?;
+                commands.push(command);
+            }
+            for (path, old_content) in unformatted.into_iter().zip(before) {
+                let full = self.repo_root.join(&path);
+                let new_content = tokio::fs::read_to_string(&full).await?;
+                if new_content == old_content {
+                    continue;
+                }
+                let _ = self.history.record(&full, &old_content, &new_content).await;
+                files.push(FormattedFile { path, formatter });
+            }
+        }
+
+        Ok(FormatCodeResult {
+            mode: args.mode,
+            formatters,
+            commands,
+            clean: files.is_empty(),
+            files,
+        })
+    }
+}
+
+fn rustfmt_line(edition: &str, mode: FormatMode, files: &str) -> String {
+    match mode {
+        FormatMode::Check => format!("rustfmt --edition {} --check -l {}", edition, files),
+        FormatMode::Apply => format!("rustfmt --edition {} {}", edition, files),
+    }
+}
+
+/// Files a check command listed, relative to the repo root: rustfmt and
+/// prettier print one path per line, black a "would reformat <path>" line on
+/// stderr.
+pub(crate) fn unformatted_files(
+    repo_root: &Path,
+    formatter: Formatter,
+    stdout: &str,
+    stderr: &str,
+) -> Vec<String> {
+    let paths: Vec<&str> = match formatter {
+        Formatter::Rustfmt | Formatter::Prettier => stdout.lines().collect(),
+        Formatter::Black => stderr
+            .lines()
+            .filter_map(|line| line.strip_prefix("would reformat "))
+            .collect(),
+    };
+    paths
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .filter_map(|p| repo_relative(repo_root, p))
+        .collect()
+}
+
+/// `.rs` files under `targets` (relative to `repo_root`), skipping ignored and
+/// hidden files; a target that is a file is taken as is.
+fn rust_files(repo_root: &Path, targets: &[String]) -> Result<Vec<String>, FormatCodeError> {
+    let mut files = Vec::new();
+    for target in targets {
+        let start = repo_root.join(target);
+        if start.is_file() {
+            files.push(target.trim_start_matches("./").to_string());
+            continue;
+        }
+        let walker = ignore::WalkBuilder::new(&start)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        for entry in walker {
+            let entry = entry?;
+            let is_rust = entry.file_type().is_some_and(|t| t.is_file())
+                && entry.path().extension().is_some_and(|e| e == "rs");
+            if let (true, Ok(rel)) = (is_rust, entry.path().strip_prefix(repo_root)) {
+                files.push(rel.to_string_lossy().to_string());
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Edition of the crate holding `file`: `package.edition` of the nearest
+/// Cargo.toml up to the repo root, or `workspace.package.edition` for members
+/// that inherit it.
+pub(crate) fn rust_edition(repo_root: &Path, file: &str) -> String {
+    let file = repo_root.join(file);
+    let mut inherits = false;
+    for dir in file.ancestors().skip(1) {
+        if !dir.starts_with(repo_root) {
+            break;
+        }
+        let Some(manifest) = std::fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|m| m.parse::<toml::Table>().ok())
+        else {
+            continue;
+        };
+        if !inherits && let Some(package) = manifest.get("package") {
+            match package.get("edition") {
+                Some(toml::Value::String(edition)) => return edition.clone(),
+                // `edition.workspace = true`
+                Some(_) => inherits = true,
+                None => return DEFAULT_EDITION.to_string(),
+            }
+        }
+        let workspace = manifest
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("edition"));
+        if let (true, Some(toml::Value::String(edition))) = (inherits, workspace) {
+            return edition.clone();
+        }
+    }
+    DEFAULT_EDITION.to_string()
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for FormatCode {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    /// Formatters are external programs, and apply rewrites files.
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: FormatCodeArgs = serde_json::from_value(args)?;
+        // Dropping the run kills the formatter (kill_on_drop).
+        tokio::select! {
+            result = self.format(args, &ctx) => Ok(result?.to_json()),
+            _ = ctx.cancel.cancelled() => Err(anyhow::anyhow!("Formatting cancelled")),
+        }
+    }
+}
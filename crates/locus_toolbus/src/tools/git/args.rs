@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitStatusArgs {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitDiffArgs {
+    /// Limit the diff to this path (relative to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Diff the index instead of the working tree
+    #[serde(default)]
+    pub staged: bool,
+
+    /// Commit or branch to diff against
+    #[serde(default)]
+    pub base: Option<String>,
+
+    #[serde(default = "default_context_lines")]
+    pub context_lines: u32,
+
+    /// Skip the patch text
+    #[serde(default)]
+    pub stat_only: bool,
+}
+
+fn default_context_lines() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLogArgs {
+    /// Revision or range (default: HEAD)
+    #[serde(default)]
+    pub rev: Option<String>,
+
+    #[serde(default)]
+    pub path: Option<String>,
+
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// Any date git understands (`2024-05-01`, `2 weeks ago`)
+    #[serde(default)]
+    pub since: Option<String>,
+
+    /// Message pattern
+    #[serde(default)]
+    pub grep: Option<String>,
+
+    #[serde(default = "default_max_count")]
+    pub max_count: usize,
+}
+
+fn default_max_count() -> usize {
+    20
+}
+
+/// Upper bound for `max_count`.
+pub const MAX_LOG_COUNT: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitCommitArgs {
+    pub message: String,
+
+    /// Paths to stage before committing
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Stage every change (`git add -A`) before committing
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchAction {
+    #[default]
+    List,
+    Create,
+    Switch,
+    Delete,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitBranchArgs {
+    #[serde(default)]
+    pub action: BranchAction,
+
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Where a new branch starts (default: HEAD)
+    #[serde(default)]
+    pub start_point: Option<String>,
+
+    /// Switch to the branch after creating it
+    #[serde(default)]
+    pub switch: bool,
+
+    /// Delete unmerged branches too (`-D`)
+    #[serde(default)]
+    pub force: bool,
+}
@@ -0,0 +1,108 @@
+use super::args::{BranchAction, GitBranchArgs};
+use super::{GitError, check_arg, current_branch, run_git};
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/git_branch.json")))
+}
+
+const BRANCH_FORMAT: &str =
+    "--format=%(refname:short)%1f%(objectname:short)%1f%(upstream:short)%1f%(HEAD)";
+
+pub struct GitBranch {
+    repo_root: PathBuf,
+}
+
+impl GitBranch {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+
+    async fn list(&self) -> ToolResult {
+        let output = run_git(
+            &self.repo_root,
+            &["for-each-ref", BRANCH_FORMAT, "refs/heads"],
+        )
+        .await?;
+        let branches: Vec<JsonValue> = output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\x1f').collect();
+                let [name, commit, upstream, head] = fields[..] else {
+                    return None;
+                };
+                Some(json!({
+                    "name": name,
+                    "commit": commit,
+                    "upstream": (!upstream.is_empty()).then_some(upstream),
+                    "current": head == "*",
+                }))
+            })
+            .collect();
+        Ok(json!({
+            "current": current_branch(&self.repo_root).await.ok().flatten(),
+            "branches": branches,
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for GitBranch {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::GitWrite
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: GitBranchArgs = serde_json::from_value(args)?;
+        if args.action == BranchAction::List {
+            return self.list().await;
+        }
+
+        let name = args.name.as_deref().ok_or_else(|| {
+            GitError::InvalidArgs(format!("name is required for {:?}", args.action))
+        })?;
+        let name = check_arg("name", name)?;
+        let start_point = args
+            .start_point
+            .as_deref()
+            .map(|s| check_arg("start_point", s))
+            .transpose()?;
+
+        let mut git_args = match args.action {
+            BranchAction::Create if args.switch => vec!["switch", "-c", name],
+            BranchAction::Create => vec!["branch", name],
+            BranchAction::Switch => vec!["switch", name],
+            BranchAction::Delete if args.force => vec!["branch", "-D", name],
+            BranchAction::Delete => vec!["branch", "-d", name],
+            BranchAction::List => unreachable!(),
+        };
+        if args.action == BranchAction::Create {
+            git_args.extend(start_point);
+        }
+        run_git(&self.repo_root, &git_args).await?;
+
+        Ok(json!({
+            "action": args.action,
+            "name": name,
+            "current": current_branch(&self.repo_root).await.ok().flatten(),
+        }))
+    }
+}
@@ -0,0 +1,103 @@
+use super::args::GitCommitArgs;
+use super::{GitError, current_branch, parse_numstat, run_git};
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::process::Command;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/git_commit.json")))
+}
+
+pub struct GitCommit {
+    repo_root: PathBuf,
+}
+
+impl GitCommit {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+
+    /// True when the index differs from HEAD (or HEAD does not exist yet).
+    async fn has_staged_changes(&self) -> Result<bool, GitError> {
+        let status = Command::new("git")
+            .args(["diff", "--cached", "--quiet"])
+            .current_dir(&self.repo_root)
+            .kill_on_drop(true)
+            .status()
+            .await
+            .map_err(|e| GitError::SpawnFailed(e.to_string()))?;
+        Ok(!status.success())
+    }
+}
+
+#[async_trait]
+impl Tool for GitCommit {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::GitWrite
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: GitCommitArgs = serde_json::from_value(args)?;
+        let message = args.message.trim();
+        if message.is_empty() {
+            return Err(GitError::InvalidArgs("message must not be empty".to_string()).into());
+        }
+
+        if args.all {
+            run_git(&self.repo_root, &["add", "-A"]).await?;
+        } else if !args.paths.is_empty() {
+            let mut add = vec!["add", "--"];
+            add.extend(args.paths.iter().map(String::as_str));
+            run_git(&self.repo_root, &add).await?;
+        }
+        if !self.has_staged_changes().await? {
+            return Err(GitError::NothingToCommit.into());
+        }
+
+        run_git(&self.repo_root, &["commit", "--quiet", "-m", message]).await?;
+        let hash = run_git(&self.repo_root, &["rev-parse", "HEAD"]).await?;
+        let hash = hash.trim();
+        let numstat = run_git(
+            &self.repo_root,
+            &[
+                "diff-tree",
+                "--root",
+                "--no-commit-id",
+                "--numstat",
+                "-z",
+                "-r",
+                "HEAD",
+            ],
+        )
+        .await?;
+        let (files, additions, deletions) = parse_numstat(&numstat);
+
+        Ok(json!({
+            "hash": hash,
+            "short_hash": &hash[..hash.len().min(7)],
+            "branch": current_branch(&self.repo_root).await?,
+            "subject": message.lines().next().unwrap_or_default(),
+            "files": files,
+            "files_changed": files.len(),
+            "additions": additions,
+            "deletions": deletions,
+        }))
+    }
+}
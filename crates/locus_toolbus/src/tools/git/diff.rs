@@ -0,0 +1,117 @@
+use super::args::GitDiffArgs;
+use super::{check_arg, parse_numstat, run_git};
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/git_diff.json")))
+}
+
+pub struct GitDiff {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+impl GitDiff {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path`, and leave protected files out of whole diffs,
+    /// by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Whether a `--numstat` entry touches a protected file, under its new or old name.
+    fn is_protected(&self, file: &JsonValue) -> bool {
+        ["path", "old_path"]
+            .iter()
+            .filter_map(|key| file[key].as_str())
+            .any(|path| {
+                self.protected
+                    .is_protected(&self.repo_root, Path::new(path))
+            })
+    }
+}
+
+#[async_trait]
+impl Tool for GitDiff {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: GitDiffArgs = serde_json::from_value(args)?;
+        let unified = format!("--unified={}", args.context_lines);
+        let mut common = vec!["diff"];
+        if args.staged {
+            common.push("--cached");
+        }
+        if let Some(base) = &args.base {
+            common.push(check_arg("base", base)?);
+        }
+        let mut pathspec = vec!["--".to_string()];
+        if let Some(path) = args.path.as_deref().filter(|p| !p.is_empty()) {
+            self.protected
+                .check(&self.repo_root, Path::new(path), path)?;
+            pathspec.push(path.to_string());
+        }
+
+        let numstat_args: Vec<&str> = common
+            .iter()
+            .copied()
+            .chain(["--numstat", "-z"])
+            .chain(pathspec.iter().map(String::as_str))
+            .collect();
+        let numstat = run_git(&self.repo_root, &numstat_args).await?;
+        let (files, mut additions, mut deletions) = parse_numstat(&numstat);
+
+        // Protected files stay out of the listing, the totals and the patch
+        let (hidden, files): (Vec<JsonValue>, Vec<JsonValue>) =
+            files.into_iter().partition(|file| self.is_protected(file));
+        for file in &hidden {
+            additions -= file["additions"].as_u64().unwrap_or(0);
+            deletions -= file["deletions"].as_u64().unwrap_or(0);
+            for key in ["path", "old_path"] {
+                if let Some(path) = file[key].as_str() {
+                    pathspec.push(format!(":(exclude,literal){}", path));
+                }
+            }
+        }
+
+        let mut result = json!({
+            "files": files,
+            "files_changed": files.len(),
+            "additions": additions,
+            "deletions": deletions,
+        });
+        if !args.stat_only {
+            let patch_args: Vec<&str> = common
+                .iter()
+                .copied()
+                .chain([unified.as_str()])
+                .chain(pathspec.iter().map(String::as_str))
+                .collect();
+            result["diff"] = run_git(&self.repo_root, &patch_args).await?.into();
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("Not a git repository: {0}")]
+    NotARepository(String),
+
+    #[error("{command} failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("Nothing to commit: no staged changes")]
+    NothingToCommit,
+
+    #[error("Failed to run git: {0}")]
+    SpawnFailed(String),
+}
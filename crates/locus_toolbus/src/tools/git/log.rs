@@ -0,0 +1,96 @@
+use super::args::{GitLogArgs, MAX_LOG_COUNT};
+use super::{check_arg, run_git};
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/git_log.json")))
+}
+
+/// Fields separated by 0x1f, records by 0x1e.
+const LOG_FORMAT: &str = "--format=%H%x1f%h%x1f%an%x1f%ae%x1f%at%x1f%s%x1f%b%x1e";
+
+pub struct GitLog {
+    repo_root: PathBuf,
+}
+
+impl GitLog {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+}
+
+pub(crate) fn parse_log(output: &str) -> Vec<JsonValue> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_start_matches('\n').splitn(7, '\x1f').collect();
+            let [hash, short, author, email, timestamp, subject, body] = fields[..] else {
+                return None;
+            };
+            Some(json!({
+                "hash": hash,
+                "short_hash": short,
+                "author": author,
+                "email": email,
+                "timestamp": timestamp.parse::<i64>().unwrap_or(0),
+                "subject": subject,
+                "body": body.trim(),
+            }))
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for GitLog {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: GitLogArgs = serde_json::from_value(args)?;
+        let max_count = format!("--max-count={}", args.max_count.clamp(1, MAX_LOG_COUNT));
+        let author = args.author.as_ref().map(|a| format!("--author={}", a));
+        let since = args.since.as_ref().map(|s| format!("--since={}", s));
+        let grep = args.grep.as_ref().map(|g| format!("--grep={}", g));
+
+        let mut git_args = vec!["log", LOG_FORMAT, max_count.as_str()];
+        git_args.extend(
+            [&author, &since, &grep]
+                .into_iter()
+                .filter_map(|a| a.as_deref()),
+        );
+        if let Some(rev) = &args.rev {
+            git_args.push(check_arg("rev", rev)?);
+        }
+        git_args.push("--");
+        if let Some(path) = args.path.as_deref().filter(|p| !p.is_empty()) {
+            git_args.push(path);
+        }
+
+        let output = match run_git(&self.repo_root, &git_args).await {
+            Ok(output) => output,
+            // A repository without commits has no history rather than an error.
+            Err(super::GitError::CommandFailed { stderr, .. })
+                if stderr.contains("does not have any commits") =>
+            {
+                String::new()
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let commits = parse_log(&output);
+        Ok(json!({ "count": commits.len(), "commits": commits }))
+    }
+}
@@ -0,0 +1,111 @@
+//! Structured git tools: `git_status`, `git_diff`, `git_log` (read) and
+//! `git_commit`, `git_branch` (git_write permission).
+//!
+//! Each tool runs the git CLI in the repo root with machine-readable output
+//! (`--porcelain=v2 -z`, `--numstat -z`, unit-separated log formats) and returns
+//! parsed JSON. User-supplied revisions and names may not start with `-`, and
+//! paths are passed after `--`, so arguments cannot turn into git options.
+
+mod args;
+mod branch;
+mod commit;
+mod diff;
+mod error;
+mod log;
+mod status;
+
+pub use args::{
+    BranchAction, GitBranchArgs, GitCommitArgs, GitDiffArgs, GitLogArgs, GitStatusArgs,
+};
+pub use branch::GitBranch;
+pub use commit::GitCommit;
+pub use diff::GitDiff;
+pub use error::GitError;
+pub use log::GitLog;
+pub use status::GitStatus;
+
+use serde_json::Value as JsonValue;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Run git in `repo_root` and return stdout.
+pub(crate) async fn run_git(repo_root: &Path, args: &[&str]) -> Result<String, GitError> {
+    let output = Command::new("git")
+        .args(["-c", "core.quotepath=off"])
+        .args(args)
+        .current_dir(repo_root)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| GitError::SpawnFailed(e.to_string()))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.contains("not a git repository") {
+        return Err(GitError::NotARepository(repo_root.display().to_string()));
+    }
+    Err(GitError::CommandFailed {
+        command: format!("git {}", args.join(" ")),
+        stderr,
+    })
+}
+
+/// Reject revisions, branch names and patterns that git would read as options.
+pub(crate) fn check_arg<'a>(name: &str, value: &'a str) -> Result<&'a str, GitError> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('-') {
+        return Err(GitError::InvalidArgs(format!(
+            "{} must be non-empty and must not start with '-': {:?}",
+            name, value
+        )));
+    }
+    Ok(value)
+}
+
+/// Current branch name, or None when HEAD is detached.
+pub(crate) async fn current_branch(repo_root: &Path) -> Result<Option<String>, GitError> {
+    let name = run_git(repo_root, &["rev-parse", "--abbrev-ref", "HEAD"]).await?;
+    let name = name.trim();
+    Ok((name != "HEAD").then(|| name.to_string()))
+}
+
+/// Parse `--numstat -z` output into per-file entries and totals.
+pub(crate) fn parse_numstat(output: &str) -> (Vec<JsonValue>, u64, u64) {
+    let mut files = Vec::new();
+    let (mut additions, mut deletions) = (0, 0);
+    let mut fields = output.split('\0');
+    while let Some(record) = fields.next() {
+        let mut parts = record.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        // Renames leave the path empty and put old and new paths in the next fields.
+        let (path, old_path) = if path.is_empty() {
+            let old = fields.next().unwrap_or_default();
+            let new = fields.next().unwrap_or_default();
+            (new.to_string(), Some(old.to_string()))
+        } else {
+            (path.to_string(), None)
+        };
+        let binary = added == "-" || deleted == "-";
+        let added: u64 = added.parse().unwrap_or(0);
+        let deleted: u64 = deleted.parse().unwrap_or(0);
+        additions += added;
+        deletions += deleted;
+        let mut file = serde_json::json!({
+            "path": path,
+            "additions": added,
+            "deletions": deleted,
+        });
+        if let Some(old_path) = old_path {
+            file["old_path"] = old_path.into();
+        }
+        if binary {
+            file["binary"] = true.into();
+        }
+        files.push(file);
+    }
+    (files, additions, deletions)
+}
@@ -0,0 +1,136 @@
+use super::args::GitStatusArgs;
+use super::run_git;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/git_status.json")))
+}
+
+pub struct GitStatus {
+    repo_root: PathBuf,
+}
+
+impl GitStatus {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+}
+
+/// Word for a porcelain status letter.
+fn change_kind(code: char) -> &'static str {
+    match code {
+        'M' => "modified",
+        'T' => "type_changed",
+        'A' => "added",
+        'D' => "deleted",
+        'R' => "renamed",
+        'C' => "copied",
+        _ => "changed",
+    }
+}
+
+/// Parse `git status --porcelain=v2 --branch -z`.
+pub(crate) fn parse_status(output: &str) -> JsonValue {
+    let mut branch = JsonValue::Null;
+    let mut upstream = JsonValue::Null;
+    let (mut ahead, mut behind) = (0i64, 0i64);
+    let (mut staged, mut unstaged) = (Vec::new(), Vec::new());
+    let (mut untracked, mut conflicted) = (Vec::new(), Vec::new());
+
+    let mut records = output.split('\0').filter(|r| !r.is_empty());
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.head" if value != "(detached)" => branch = value.into(),
+                "branch.upstream" => upstream = value.into(),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let kind = record.chars().next().unwrap_or(' ');
+        match kind {
+            '?' => untracked.push(record[2..].to_string()),
+            'u' => {
+                if let Some(path) = record.splitn(11, ' ').nth(10) {
+                    conflicted.push(path.to_string());
+                }
+            }
+            '1' | '2' => {
+                // `1 XY sub mH mI mW hH hI path`; type 2 adds a score field and
+                // is followed by the original path as its own record.
+                let fields = if kind == '1' { 9 } else { 10 };
+                let parts: Vec<&str> = record.splitn(fields, ' ').collect();
+                let Some(path) = parts.get(fields - 1) else {
+                    continue;
+                };
+                let old_path = if kind == '2' { records.next() } else { None };
+                let xy: Vec<char> = parts[1].chars().collect();
+                for (code, list) in [(xy[0], &mut staged), (xy[1], &mut unstaged)] {
+                    if code == '.' {
+                        continue;
+                    }
+                    let mut entry = json!({ "path": path, "status": change_kind(code) });
+                    if let Some(old_path) = old_path.filter(|_| matches!(code, 'R' | 'C')) {
+                        entry["old_path"] = old_path.into();
+                    }
+                    list.push(entry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let clean =
+        staged.is_empty() && unstaged.is_empty() && untracked.is_empty() && conflicted.is_empty();
+    json!({
+        "branch": branch,
+        "upstream": upstream,
+        "ahead": ahead,
+        "behind": behind,
+        "staged": staged,
+        "unstaged": unstaged,
+        "untracked": untracked,
+        "conflicted": conflicted,
+        "clean": clean,
+    })
+}
+
+#[async_trait]
+impl Tool for GitStatus {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let _: GitStatusArgs = serde_json::from_value(args)?;
+        let output = run_git(
+            &self.repo_root,
+            &["status", "--porcelain=v2", "--branch", "-z"],
+        )
+        .await?;
+        Ok(parse_status(&output))
+    }
+}
@@ -4,7 +4,9 @@ mod error;
 pub use args::{GlobArgs, GlobResult};
 pub use error::GlobError;
 
-use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::result_cache::CacheScope;
+use crate::tools::{Tool, ToolResult, UNSEARCHED_DIRS, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 use std::path::Path;
@@ -14,16 +16,28 @@ use tokio::fs;
 
 pub struct Glob {
     repo_root: Arc<std::path::PathBuf>,
+    protected: ProtectedPathMatcher,
 }
 
 impl Glob {
     pub fn new(repo_root: std::path::PathBuf) -> Self {
         Self {
             repo_root: Arc::new(repo_root),
+            protected: ProtectedPathMatcher::default(),
         }
     }
 
+    /// Leave out paths matching `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
     fn should_include(&self, path: &Path, args: &GlobArgs) -> bool {
+        if self.protected.is_protected(&self.repo_root, path) {
+            return false;
+        }
+
         let path_str = path.to_string_lossy();
         let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
@@ -62,10 +76,9 @@ impl Glob {
                 let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
                 if dir_name.starts_with('.')
-                    || dir_name == "target"
-                    || dir_name == "node_modules"
-                    || dir_name == "vendor"
+                    || UNSEARCHED_DIRS.contains(&dir_name)
                     || args.exclude.iter().any(|e| dir_name == e)
+                    || self.protected.is_protected(&self.repo_root, &path)
                 {
                     continue;
                 }
@@ -106,6 +119,14 @@ impl Tool for Glob {
         schema().2.clone()
     }
 
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    fn cache_scope(&self, _args: &JsonValue) -> CacheScope {
+        CacheScope::SkipDirs(UNSEARCHED_DIRS)
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let glob_args: GlobArgs = serde_json::from_value(args)?;
 
@@ -129,7 +150,7 @@ impl Tool for Glob {
                 .strip_prefix(&*self.repo_root)
                 .unwrap_or(&search_path)
                 .to_string_lossy();
-            if pattern.matches(&rel_path) {
+            if pattern.matches(&rel_path) && self.should_include(&search_path, &glob_args) {
                 result.add_file(rel_path.to_string());
             }
         } else {
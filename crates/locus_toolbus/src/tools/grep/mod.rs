@@ -4,7 +4,9 @@ mod error;
 pub use args::{GrepArgs, GrepMatch, GrepResult};
 pub use error::GrepError;
 
-use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::result_cache::CacheScope;
+use crate::tools::{Tool, ToolResult, UNSEARCHED_DIRS, parse_tool_schema};
 use async_trait::async_trait;
 use regex::RegexBuilder;
 use serde_json::Value as JsonValue;
@@ -15,15 +17,23 @@ use tokio::fs;
 
 pub struct Grep {
     repo_root: Arc<std::path::PathBuf>,
+    protected: ProtectedPathMatcher,
 }
 
 impl Grep {
     pub fn new(repo_root: std::path::PathBuf) -> Self {
         Self {
             repo_root: Arc::new(repo_root),
+            protected: ProtectedPathMatcher::default(),
         }
     }
 
+    /// Skip paths matching `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
     fn build_regex(&self, args: &GrepArgs) -> Result<regex::Regex, GrepError> {
         if args.pattern.is_empty() {
             return Err(GrepError::EmptyPattern);
@@ -134,7 +144,7 @@ impl Grep {
                 return Ok(());
             }
 
-            if !file_path.is_file() {
+            if !file_path.is_file() || self.protected.is_protected(&self.repo_root, file_path) {
                 continue;
             }
 
@@ -196,15 +206,14 @@ impl Grep {
                 return Ok(());
             }
 
+            if self.protected.is_protected(&self.repo_root, &path) {
+                continue;
+            }
+
             if path.is_dir() {
                 let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-                if dir_name.starts_with('.')
-                    || dir_name == "target"
-                    || dir_name == "node_modules"
-                    || dir_name == "vendor"
-                    || dir_name == ".git"
-                {
+                if dir_name.starts_with('.') || UNSEARCHED_DIRS.contains(&dir_name) {
                     continue;
                 }
 
@@ -256,6 +265,14 @@ impl Tool for Grep {
         schema().2.clone()
     }
 
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    fn cache_scope(&self, _args: &JsonValue) -> CacheScope {
+        CacheScope::SkipDirs(UNSEARCHED_DIRS)
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let grep_args: GrepArgs = serde_json::from_value(args)?;
 
@@ -266,6 +283,7 @@ impl Tool for Grep {
             if !full_path.exists() {
                 return Err(anyhow::anyhow!("Path does not exist: {}", path));
             }
+            self.protected.check(&self.repo_root, &full_path, path)?;
             full_path
         } else {
             self.repo_root.as_path().to_path_buf()
@@ -4,4 +4,7 @@ use thiserror::Error;
 pub enum HandoffError {
     #[error("Failed to spawn process: {0}")]
     SpawnFailed(String),
+
+    #[error(transparent)]
+    Execution(#[from] crate::execution::ExecutionError),
 }
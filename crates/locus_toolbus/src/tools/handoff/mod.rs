@@ -4,23 +4,33 @@ mod error;
 pub use args::HandoffArgs;
 pub use error::HandoffError;
 
+use crate::execution::ExecutionBackend;
+use crate::permission::Permission;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::process::Command;
 
 static NEXT_HANDOFF_ID: AtomicU64 = AtomicU64::new(1);
 
 pub struct Handoff {
     working_dir: PathBuf,
+    backend: ExecutionBackend,
 }
 
 impl Handoff {
     pub fn new(working_dir: PathBuf) -> Self {
-        Self { working_dir }
+        Self {
+            working_dir,
+            backend: ExecutionBackend::default(),
+        }
+    }
+
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
     }
 
     #[cfg(unix)]
@@ -36,13 +46,16 @@ impl Handoff {
     async fn spawn_background(
         &self,
         command: &str,
-        working_dir: &PathBuf,
+        working_dir: &Path,
     ) -> Result<u64, HandoffError> {
-        let (shell, flag) = self.shell_and_arg();
-        let mut child = Command::new(shell)
-            .arg(flag)
-            .arg(command)
-            .current_dir(working_dir)
+        let mut prepared = self.backend.command(
+            &self.working_dir,
+            Some(working_dir),
+            self.shell_and_arg(),
+            command,
+        )?;
+        let mut child = prepared
+            .command
             .kill_on_drop(false)
             .spawn()
             .map_err(|e| HandoffError::SpawnFailed(e.to_string()))?;
@@ -76,6 +89,10 @@ impl Tool for Handoff {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let a: HandoffArgs = serde_json::from_value(args)?;
         let working_dir = a
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JsonQueryArgs {
+    /// jq expression (`.dependencies | keys`), or JSONPath starting with `$`
+    pub query: String,
+
+    /// File to query, relative to repo root
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Inline document to query instead of a file
+    #[serde(default)]
+    pub content: Option<String>,
+
+    /// Document format; from the file extension when omitted, else JSON then YAML
+    #[serde(default)]
+    pub format: Option<DocumentFormat>,
+
+    /// Maximum number of results returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+
+    /// Maximum size of the returned results, in bytes of JSON
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+}
+
+fn default_max_results() -> usize {
+    100
+}
+
+fn default_max_bytes() -> usize {
+    32 * 1024
+}
+
+impl JsonQueryArgs {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            path: None,
+            content: None,
+            format: None,
+            max_results: default_max_results(),
+            max_bytes: default_max_bytes(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentFormat {
+    Json,
+    /// Multi-document files are queried one document at a time, like yq.
+    Yaml,
+    Toml,
+}
+
+impl DocumentFormat {
+    /// Format for a file name's extension, if it names one.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, ext) = path.rsplit_once('.')?;
+        match ext.to_ascii_lowercase().as_str() {
+            "json" | "jsonc" | "geojson" | "har" | "ipynb" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonQueryResult {
+    /// The file queried, or `inline`.
+    pub source: String,
+    pub format: DocumentFormat,
+    /// Documents in the source (more than one for multi-document YAML).
+    pub documents: usize,
+    pub results: Vec<serde_json::Value>,
+    /// Results before `max_results` and `max_bytes` were applied, counted up
+    /// to [super::MAX_COUNTED].
+    pub total: usize,
+    pub truncated: bool,
+}
+
+impl JsonQueryResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JsonQueryError {
+    #[error("Give either path or content to query")]
+    NoInput,
+
+    #[error("Give path or content, not both")]
+    BothInputs,
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("Could not parse {source_name} as {format}: {reason}")]
+    Parse {
+        source_name: String,
+        format: &'static str,
+        reason: String,
+    },
+
+    #[error("Query failed: {0}")]
+    Eval(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
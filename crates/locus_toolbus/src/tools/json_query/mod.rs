@@ -0,0 +1,183 @@
+mod args;
+mod error;
+pub(crate) mod query;
+pub(crate) mod yaml;
+
+pub use args::{DocumentFormat, JsonQueryArgs, JsonQueryResult};
+pub use error::JsonQueryError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_file};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Results counted before a query is stopped.
+pub const MAX_COUNTED: usize = 10_000;
+
+/// How long a query may run over all the documents before it is stopped.
+pub const QUERY_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+pub struct JsonQuery {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+impl JsonQuery {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path` by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Parse `text` into its documents. Without a format, JSON is tried first
+    /// and YAML (a superset in practice) second.
+    pub fn parse_documents(
+        text: &str,
+        format: Option<DocumentFormat>,
+        source_name: &str,
+    ) -> Result<(DocumentFormat, Vec<JsonValue>), JsonQueryError> {
+        let error = |format: DocumentFormat, reason: String| JsonQueryError::Parse {
+            source_name: source_name.to_string(),
+            format: match format {
+                DocumentFormat::Json => "JSON",
+                DocumentFormat::Yaml => "YAML",
+                DocumentFormat::Toml => "TOML",
+            },
+            reason,
+        };
+        match format {
+            Some(DocumentFormat::Json) => serde_json::from_str(text)
+                .map(|value| (DocumentFormat::Json, vec![value]))
+                .map_err(|e| error(DocumentFormat::Json, e.to_string())),
+            Some(DocumentFormat::Yaml) => yaml::parse_yaml(text)
+                .map(|documents| (DocumentFormat::Yaml, documents))
+                .map_err(|e| error(DocumentFormat::Yaml, e)),
+            Some(DocumentFormat::Toml) => toml::from_str::<toml::Value>(text)
+                .map(|value| (DocumentFormat::Toml, vec![toml_to_json(value)]))
+                .map_err(|e| error(DocumentFormat::Toml, e.message().to_string())),
+            None => Self::parse_documents(text, Some(DocumentFormat::Json), source_name)
+                .or_else(|_| Self::parse_documents(text, Some(DocumentFormat::Yaml), source_name)),
+        }
+    }
+
+    /// The file to read for `args`: the resolved `path`, or None for inline content.
+    fn input_file(&self, args: &JsonQueryArgs) -> anyhow::Result<Option<PathBuf>> {
+        match (args.path.as_deref(), args.content.is_some()) {
+            (Some(_), true) => Err(JsonQueryError::BothInputs.into()),
+            (None, false) => Err(JsonQueryError::NoInput.into()),
+            (Some(path), false) => Ok(Some(resolve_file(&self.repo_root, path, &self.protected)?)),
+            (None, true) => Ok(None),
+        }
+    }
+
+    /// Read `file` (or take the inline content), parse and query. Blocking; call
+    /// from `spawn_blocking`.
+    fn run(args: JsonQueryArgs, file: Option<PathBuf>) -> Result<JsonQueryResult, JsonQueryError> {
+        let query = query::parse(&args.query).map_err(JsonQueryError::InvalidQuery)?;
+        let (source, text, format) = match (file, args.path, args.content) {
+            (Some(file), Some(path), _) => {
+                let text = std::fs::read_to_string(file)?;
+                let format = args.format.or_else(|| DocumentFormat::from_path(&path));
+                (path, text, format)
+            }
+            (_, _, Some(content)) => ("inline".to_string(), content, args.format),
+            _ => return Err(JsonQueryError::NoInput),
+        };
+        let (format, documents) = Self::parse_documents(&text, format, &source)?;
+
+        // Results are kept up to the limits and counted up to MAX_COUNTED; the
+        // query is not run further, so an endless one stops there. One that
+        // loops without results stops at QUERY_TIME_LIMIT.
+        let deadline = Instant::now() + QUERY_TIME_LIMIT;
+        let mut results = Vec::new();
+        let (mut total, mut bytes, mut truncated) = (0, 0, false);
+        for document in &documents {
+            query
+                .run(document, deadline, |value| {
+                    total += 1;
+                    if !truncated {
+                        bytes += value.to_string().len();
+                        truncated = results.len() >= args.max_results || bytes > args.max_bytes;
+                        if !truncated {
+                            results.push(value);
+                        }
+                    }
+                    total < MAX_COUNTED
+                })
+                .map_err(JsonQueryError::Eval)?;
+            if total >= MAX_COUNTED {
+                truncated = true;
+                break;
+            }
+        }
+        Ok(JsonQueryResult {
+            source,
+            format,
+            documents: documents.len(),
+            truncated,
+            results,
+            total,
+        })
+    }
+}
+
+/// TOML as JSON; datetimes become their TOML text.
+fn toml_to_json(value: toml::Value) -> JsonValue {
+    match value {
+        toml::Value::String(s) => JsonValue::String(s),
+        toml::Value::Integer(n) => JsonValue::from(n),
+        toml::Value::Float(f) => {
+            serde_json::Number::from_f64(f).map_or(JsonValue::Null, JsonValue::Number)
+        }
+        toml::Value::Boolean(b) => JsonValue::Bool(b),
+        toml::Value::Datetime(dt) => JsonValue::String(dt.to_string()),
+        toml::Value::Array(items) => {
+            JsonValue::Array(items.into_iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => JsonValue::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for JsonQuery {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: JsonQueryArgs = serde_json::from_value(args)?;
+        let file = self.input_file(&args)?;
+        let result = tokio::task::spawn_blocking(move || Self::run(args, file))
+            .await
+            .map_err(|e| anyhow::anyhow!("json_query task failed: {}", e))??;
+        Ok(result.to_json())
+    }
+}
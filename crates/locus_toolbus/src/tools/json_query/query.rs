@@ -0,0 +1,460 @@
+//! jq queries run by [jaq](https://github.com/01mf02/jaq), and JSONPath
+//! translated to jq.
+//!
+//! jq: the jaq dialect with its standard library, minus what reaches outside
+//! the document (`env`, `halt`, `halt_error`, `stderr`). Unlike jq, indexing
+//! `null` is an error; `?` skips it. JSONPath (a query starting with `$`):
+//! `.name`, `['name']`, `[0,1]`, `[1:3]`, `*`, `..` and `[?(@.x < 1)]`
+//! filters, which take jq syntax with `@` for the current node.
+//!
+//! Every definition, the standard library's included, starts with a check of
+//! the run's deadline, as does every number `range` yields: a loop can only
+//! go on by calling a definition again or by reading a range, so one that
+//! never produces output still stops at the deadline.
+
+use std::cell::Cell;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use jaq_core::box_iter::box_once;
+use jaq_core::load::lex::Token;
+use jaq_core::load::parse::{Def, Pattern, Term};
+use jaq_core::load::{self, Arena, File, Lexer, Loader, Parser};
+use jaq_core::path::Part;
+use jaq_core::{Compiler, Ctx, Error, Exn, FilterT, Native, RcIter};
+use jaq_json::Val;
+use serde_json::Value as JsonValue;
+
+/// Builtins left out: they read the environment or write to the terminal.
+const UNAVAILABLE: &[&str] = &["env", "halt", "stderr"];
+
+/// JSONPath children: the member if the input has it, nothing otherwise.
+const JSONPATH_DEFS: &str = "def _member($k): if type == \"object\" and has($k) then .[$k] else empty end; \
+     def _index($i): if type == \"array\" and $i < length and $i >= -length then .[$i] else empty end; ";
+
+/// Native run at the start of every definition; users can't name it.
+const STEP: &str = "!step";
+
+/// Error of a query stopped at its deadline.
+const TIMED_OUT: &str = "the query ran past its time limit and was stopped";
+
+/// Definition holding the query itself, called by the main program.
+const QUERY_DEF: &str = "_query";
+
+thread_local! {
+    /// When the query running on this thread has to stop.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The standard `range/3`, called by the one that checks the deadline.
+static RANGE: OnceLock<Native<Val>> = OnceLock::new();
+
+/// A compiled query.
+pub(crate) struct Query(jaq_core::Filter<Native<Val>>);
+
+/// Compile a jq expression, or a JSONPath one when it starts with `$`.
+pub(crate) fn parse(query: &str) -> Result<Query, String> {
+    let query = query.trim();
+    let code = if query.starts_with('$') {
+        format!("{}{}", JSONPATH_DEFS, jsonpath_to_jq(query)?)
+    } else {
+        query.to_string()
+    };
+    let tokens = Lexer::new(&code)
+        .lex()
+        .map_err(|errors| load_error(load::Error::Lex(errors)))?;
+    let mut term = Parser::new(&tokens).parse(|p| p.term()).map_err(|errors| {
+        let errors = errors
+            .into_iter()
+            .map(|(expect, found)| (expect, Token::opt_as_str(found, &code)))
+            .collect();
+        load_error(load::Error::Parse(errors))
+    })?;
+    meter(&mut term);
+    let query = Def {
+        name: QUERY_DEF,
+        args: Vec::new(),
+        body: term,
+    };
+    let prelude = jaq_std::defs()
+        .chain(jaq_json::defs())
+        .map(|mut def| {
+            meter_def(&mut def);
+            def
+        })
+        .chain([query]);
+
+    let arena = Arena::default();
+    let modules = Loader::new(prelude)
+        .load(
+            &arena,
+            File {
+                code: QUERY_DEF,
+                path: (),
+            },
+        )
+        .map_err(|errors| {
+            let (_, error) = errors.into_iter().next().expect("a load error");
+            load_error(error)
+        })?;
+    let step =
+        Native::new(|_, cv| box_once(check_deadline().map(|()| cv.1))).with_update(|_, cv, f| {
+            match check_deadline() {
+                Ok(()) => f(cv.1),
+                Err(e) => box_once(Err(e)),
+            }
+        });
+    let funs = jaq_std::funs()
+        .chain(jaq_json::funs())
+        .filter(|(name, _, _)| !UNAVAILABLE.contains(name))
+        .map(|(name, args, native)| match name {
+            // Kept for the standard library's `halt_error/0`, which calls it.
+            "halt_error" => (name, args, Native::new(|_, _| unavailable("halt_error"))),
+            "range" => {
+                RANGE.get_or_init(|| native);
+                let range = Native::new(|lut, cv| {
+                    let range = RANGE.get().expect("the standard range");
+                    Box::new(range.run(lut, cv).map(|y| check_deadline().and(y)))
+                });
+                (name, args, range)
+            }
+            _ => (name, args, native),
+        })
+        .chain([(STEP, jaq_std::v(0), step)]);
+    let filter = Compiler::default()
+        .with_funs(funs)
+        .compile(modules)
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .flat_map(|(_, undefined)| undefined)
+                .map(|(name, kind)| format!("unknown {} `{}`", kind.as_str(), name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })?;
+    Ok(Query(filter))
+}
+
+/// Err once the deadline of the query running on this thread has passed.
+fn check_deadline<'a>() -> Result<(), Exn<'a, Val>> {
+    match DEADLINE.get() {
+        Some(deadline) if Instant::now() >= deadline => Err(Exn::from(Error::str(TIMED_OUT))),
+        _ => Ok(()),
+    }
+}
+
+/// Make every definition in `term` check the deadline first.
+fn meter(term: &mut Term<&str>) {
+    match term {
+        Term::Id | Term::Recurse | Term::Num(_) | Term::Break(_) | Term::Var(_) => {}
+        Term::Str(_, parts) => {
+            for part in parts {
+                if let load::lex::StrPart::Term(term) = part {
+                    meter(term);
+                }
+            }
+        }
+        Term::Arr(term) => term.iter_mut().for_each(|term| meter(term)),
+        Term::Obj(entries) => {
+            for (key, value) in entries {
+                meter(key);
+                value.iter_mut().for_each(meter);
+            }
+        }
+        Term::Neg(term) | Term::Label(_, term) => meter(term),
+        Term::Pipe(l, pattern, r) => {
+            meter(l);
+            pattern.iter_mut().for_each(meter_pattern);
+            meter(r);
+        }
+        Term::BinOp(l, _, r) => {
+            meter(l);
+            meter(r);
+        }
+        Term::Fold(_, xs, pattern, args) => {
+            meter(xs);
+            meter_pattern(pattern);
+            args.iter_mut().for_each(meter);
+        }
+        Term::TryCatch(body, catch) => {
+            meter(body);
+            catch.iter_mut().for_each(|term| meter(term));
+        }
+        Term::IfThenElse(branches, otherwise) => {
+            for (cond, then) in branches {
+                meter(cond);
+                meter(then);
+            }
+            otherwise.iter_mut().for_each(|term| meter(term));
+        }
+        Term::Def(defs, rest) => {
+            defs.iter_mut().for_each(meter_def);
+            meter(rest);
+        }
+        Term::Call(_, args) => args.iter_mut().for_each(meter),
+        Term::Path(term, path) => {
+            meter(term);
+            for (part, _) in &mut path.0 {
+                match part {
+                    Part::Index(index) => meter(index),
+                    Part::Range(from, to) => {
+                        from.iter_mut().chain(to.iter_mut()).for_each(meter);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn meter_pattern(pattern: &mut Pattern<&str>) {
+    match pattern {
+        Pattern::Var(_) => {}
+        Pattern::Arr(patterns) => patterns.iter_mut().for_each(meter_pattern),
+        Pattern::Obj(entries) => {
+            for (key, pattern) in entries {
+                meter(key);
+                meter_pattern(pattern);
+            }
+        }
+    }
+}
+
+/// `def f: body;` as `def f: !step | body;`, with `body` metered too.
+fn meter_def(def: &mut Def<&str>) {
+    meter(&mut def.body);
+    let body = std::mem::take(&mut def.body);
+    def.body = Term::Pipe(Box::new(Term::Call(STEP, Vec::new())), None, Box::new(body));
+}
+
+fn unavailable<'a>(name: &str) -> jaq_core::ValXs<'a, Val> {
+    box_once(Err(Exn::from(Error::str(format!(
+        "{} is not available",
+        name
+    )))))
+}
+
+/// The first lex or parse error of a query, with where it happened.
+fn load_error(error: load::Error<&str>) -> String {
+    let near = |found: &str| match found.chars().take(20).collect::<String>() {
+        rest if rest.is_empty() => "at the end".to_string(),
+        rest => format!("at `{}`", rest),
+    };
+    match error {
+        load::Error::Io(errors) => errors
+            .into_iter()
+            .map(|(path, error)| format!("{}: {}", path, error))
+            .collect::<Vec<_>>()
+            .join(", "),
+        load::Error::Lex(errors) => errors
+            .first()
+            .map(|(expect, found)| format!("expected {} {}", expect.as_str(), near(found)))
+            .unwrap_or_default(),
+        load::Error::Parse(errors) => errors
+            .first()
+            .map(|(expect, found)| format!("expected {} {}", expect.as_str(), near(found)))
+            .unwrap_or_default(),
+    }
+}
+
+impl Query {
+    /// Run on `input`, handing each output to `each` until it returns false.
+    /// The first error stops the run, as in jq, and so does `deadline`.
+    pub(crate) fn run(
+        &self,
+        input: &JsonValue,
+        deadline: Instant,
+        each: impl FnMut(JsonValue) -> bool,
+    ) -> Result<(), String> {
+        DEADLINE.set(Some(deadline));
+        let result = self.run_outputs(input, each);
+        DEADLINE.set(None);
+        result
+    }
+
+    fn run_outputs(
+        &self,
+        input: &JsonValue,
+        mut each: impl FnMut(JsonValue) -> bool,
+    ) -> Result<(), String> {
+        let inputs = RcIter::new(core::iter::empty());
+        for output in self
+            .0
+            .run((Ctx::new([], &inputs), Val::from(input.clone())))
+        {
+            // A query that caught the deadline's error doesn't get to go on.
+            if check_deadline().is_err() {
+                return Err(TIMED_OUT.to_string());
+            }
+            let value = output.map_err(|e| e.to_string())?;
+            if !each(JsonValue::from(value)) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A JSONPath query (`$` first) as a jq pipeline.
+fn jsonpath_to_jq(query: &str) -> Result<String, String> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut steps = vec![".".to_string()];
+    let mut i = 1;
+    let name_end = |mut i: usize| {
+        while i < chars.len()
+            && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+        {
+            i += 1;
+        }
+        i
+    };
+    while i < chars.len() {
+        match chars[i] {
+            '.' if chars.get(i + 1) == Some(&'.') => {
+                steps.push("..".to_string());
+                i += 2;
+                // `..name` / `..*` / `..[...]`
+                if chars.get(i).is_some_and(|c| *c != '[') {
+                    i -= 1;
+                }
+            }
+            '.' if chars.get(i + 1) == Some(&'*') => {
+                steps.push(".[]?".to_string());
+                i += 2;
+            }
+            '.' => {
+                let end = name_end(i + 1);
+                if end == i + 1 {
+                    return Err(format!("expected a member name at column {}", i + 2));
+                }
+                let name: String = chars[i + 1..end].iter().collect();
+                steps.push(format!("_member({})", JsonValue::from(name)));
+                i = end;
+            }
+            '[' => {
+                let end = closing_bracket(&chars, i)
+                    .ok_or_else(|| format!("unclosed `[` at column {}", i + 1))?;
+                let inner: String = chars[i + 1..end].iter().collect();
+                steps.push(jsonpath_bracket(inner.trim())?);
+                i = end + 1;
+            }
+            c => return Err(format!("unexpected `{}` at column {}", c, i + 1)),
+        }
+    }
+    Ok(steps.join(" | "))
+}
+
+/// Index of the `]` matching the `[` at `open`, skipping quotes and nesting.
+fn closing_bracket(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, &c) in chars.iter().enumerate().skip(open) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[' | '(') => depth += 1,
+            (None, ']' | ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn jsonpath_bracket(inner: &str) -> Result<String, String> {
+    if inner == "*" {
+        return Ok(".[]?".to_string());
+    }
+    if let Some(filter) = inner.strip_prefix('?') {
+        let filter = filter.trim();
+        let filter = filter
+            .strip_prefix('(')
+            .and_then(|f| f.strip_suffix(')'))
+            .unwrap_or(filter);
+        return Ok(format!(
+            ".[]? | select(({})?)",
+            jsonpath_filter_to_jq(filter)
+        ));
+    }
+    if let Some((from, to)) = inner.split_once(':') {
+        let bound = |s: &str| -> Result<String, String> {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(String::new());
+            }
+            s.parse::<i64>()
+                .map(|n| n.to_string())
+                .map_err(|_| format!("bad slice bound `{}`", s))
+        };
+        return Ok(format!(".[{}:{}]?", bound(from)?, bound(to)?));
+    }
+    if inner.is_empty() {
+        return Err("empty `[]`".to_string());
+    }
+    // `[0]`, `['a']`, or a union `[0,2]` / `['a','b']`.
+    let members = inner
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            if let Some(name) = part
+                .strip_prefix('\'')
+                .and_then(|p| p.strip_suffix('\''))
+                .or_else(|| part.strip_prefix('"').and_then(|p| p.strip_suffix('"')))
+            {
+                Ok(format!("_member({})", JsonValue::from(name)))
+            } else {
+                part.parse::<i64>()
+                    .map(|index| format!("_index({})", index))
+                    .map_err(|_| format!("bad member `{}` in `[]`", part))
+            }
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(format!("({})", members.join(", ")))
+}
+
+/// `@.price < 10 && @.tag == 'x'` as jq: `.price < 10 and .tag == "x"`.
+fn jsonpath_filter_to_jq(filter: &str) -> String {
+    let mut out = String::new();
+    let mut chars = filter.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                out.push('"');
+                for c in chars.by_ref() {
+                    match c {
+                        '\'' => break,
+                        '"' => out.push_str("\\\""),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            '"' => {
+                out.push('"');
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            // `@.name` is `.name`; `@` alone and `@[0]` need the dot.
+            '@' if chars.peek() == Some(&'.') => {}
+            '@' => out.push('.'),
+            '&' if chars.peek() == Some(&'&') => {
+                chars.next();
+                out.push_str(" and ");
+            }
+            '|' if chars.peek() == Some(&'|') => {
+                chars.next();
+                out.push_str(" or ");
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,57 @@
+//! YAML documents as JSON values, read with `serde_yaml`.
+//!
+//! `<<` merge keys are applied, tags dropped and non-string keys written as
+//! their JSON text. Aliases are expanded by the parser, which refuses a
+//! document whose aliases repeat it too many times over (a "billion laughs"
+//! file) instead of building it.
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+
+/// Parse every `---` separated document in `text`.
+pub(crate) fn parse_yaml(text: &str) -> Result<Vec<JsonValue>, String> {
+    // The iterator repeats an error forever, so stop at the first one.
+    serde_yaml::Deserializer::from_str(text)
+        .map(|document| {
+            let mut value = YamlValue::deserialize(document).map_err(|e| e.to_string())?;
+            value.apply_merge().map_err(|e| e.to_string())?;
+            Ok(yaml_to_json(value))
+        })
+        .collect()
+}
+
+fn yaml_to_json(value: YamlValue) -> JsonValue {
+    match value {
+        YamlValue::Null => JsonValue::Null,
+        YamlValue::Bool(b) => JsonValue::Bool(b),
+        YamlValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                JsonValue::from(i)
+            } else if let Some(u) = n.as_u64() {
+                JsonValue::from(u)
+            } else {
+                n.as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map_or(JsonValue::Null, JsonValue::Number)
+            }
+        }
+        YamlValue::String(s) => JsonValue::String(s),
+        YamlValue::Sequence(items) => {
+            JsonValue::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        YamlValue::Mapping(mapping) => JsonValue::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        JsonValue::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        YamlValue::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
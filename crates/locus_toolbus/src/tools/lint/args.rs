@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LintArgs {
+    /// Linter to run; every linter the repo is set up for when omitted
+    #[serde(default)]
+    pub linter: Option<Linter>,
+
+    /// File or directory to lint, relative to repo root (defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Maximum number of diagnostics returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    200
+}
+
+impl LintArgs {
+    pub fn new() -> Self {
+        Self {
+            linter: None,
+            path: None,
+            max_results: default_max_results(),
+        }
+    }
+
+    pub fn with_linter(mut self, linter: Linter) -> Self {
+        self.linter = Some(linter);
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl Default for LintArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Linter {
+    /// `cargo clippy --message-format=json`
+    Clippy,
+    /// `eslint -f json`, through `npx`
+    Eslint,
+    /// `ruff check --output-format json`
+    Ruff,
+}
+
+impl Linter {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Clippy => "clippy",
+            Self::Eslint => "eslint",
+            Self::Ruff => "ruff",
+        }
+    }
+}
+
+/// Severity words shared with `lsp_diagnostics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// One diagnostic, normalized across linters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintDiagnostic {
+    /// Path relative to the repo root.
+    pub path: String,
+    /// 1-based line.
+    pub line: usize,
+    /// 1-based column.
+    pub column: usize,
+    pub severity: LintSeverity,
+    /// Rule or lint name (`clippy::needless_return`, `no-unused-vars`, `F401`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    /// Suggested fix, when the linter gives one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    pub linter: Linter,
+}
+
+/// Diagnostics per severity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintCounts {
+    pub error: usize,
+    pub warning: usize,
+    pub information: usize,
+    pub hint: usize,
+}
+
+impl LintCounts {
+    pub fn add(&mut self, severity: LintSeverity) {
+        match severity {
+            LintSeverity::Error => self.error += 1,
+            LintSeverity::Warning => self.warning += 1,
+            LintSeverity::Information => self.information += 1,
+            LintSeverity::Hint => self.hint += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintResult {
+    /// Linters that ran, in order.
+    pub linters: Vec<Linter>,
+    /// Shell commands run, one per linter.
+    pub commands: Vec<String>,
+    pub counts: LintCounts,
+    /// Errors first, then by path and line.
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// Diagnostics found before `max_results` was applied.
+    pub total: usize,
+    pub truncated: bool,
+}
+
+impl LintResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
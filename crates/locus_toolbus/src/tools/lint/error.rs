@@ -0,0 +1,37 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LintError {
+    #[error(
+        "No linter found for this repo (looked for Cargo.toml, an ESLint config, and ruff or Python project files); pass linter explicitly"
+    )]
+    NoLinter,
+
+    #[error("Path does not exist: {0}")]
+    PathNotFound(String),
+
+    #[error("Path is outside repository: {0}")]
+    PathOutsideRepo(String),
+
+    #[error("{linter} is not installed or not on PATH ({command})")]
+    NotInstalled { linter: String, command: String },
+
+    #[error("{command} failed (exit code {exit_code}): {stderr}")]
+    CommandFailed {
+        command: String,
+        exit_code: i32,
+        stderr: String,
+    },
+
+    #[error("Could not parse {linter} output: {reason}")]
+    BadOutput { linter: String, reason: String },
+
+    #[error("Failed to run linter: {0}")]
+    SpawnFailed(String),
+
+    #[error(transparent)]
+    Execution(#[from] crate::execution::ExecutionError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
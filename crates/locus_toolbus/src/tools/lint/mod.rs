@@ -0,0 +1,268 @@
+mod args;
+mod error;
+pub(crate) mod parse;
+
+pub use args::{LintArgs, LintCounts, LintDiagnostic, LintResult, LintSeverity, Linter};
+pub use error::LintError;
+
+use crate::execution::{ExecutionBackend, shell_quote};
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Lines of stderr quoted when a linter fails.
+const STDERR_TAIL_LINES: usize = 20;
+/// Exit code of a shell whose command was not found.
+pub(crate) const EXIT_NOT_FOUND: i32 = 127;
+
+const ESLINT_CONFIGS: &[&str] = &[
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    ".eslintrc",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.json",
+    ".eslintrc.yml",
+    ".eslintrc.yaml",
+];
+
+pub struct Lint {
+    repo_root: PathBuf,
+    backend: ExecutionBackend,
+}
+
+impl Lint {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            backend: ExecutionBackend::default(),
+        }
+    }
+
+    /// Run linters through `backend`, like `bash`.
+    pub fn with_backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Linters `repo_root` is set up for: clippy for a Cargo.toml, eslint for an
+    /// ESLint config (or `eslintConfig` in package.json), ruff for a ruff config
+    /// (or `[tool.ruff]` in pyproject.toml).
+    pub fn detect(repo_root: &Path) -> Vec<Linter> {
+        let contains = |file: &str, needle: &str| {
+            std::fs::read_to_string(repo_root.join(file)).is_ok_and(|c| c.contains(needle))
+        };
+        let mut linters = Vec::new();
+        if repo_root.join("Cargo.toml").is_file() {
+            linters.push(Linter::Clippy);
+        }
+        if ESLINT_CONFIGS.iter().any(|f| repo_root.join(f).is_file())
+            || contains("package.json", "\"eslintConfig\"")
+        {
+            linters.push(Linter::Eslint);
+        }
+        if repo_root.join("ruff.toml").is_file()
+            || repo_root.join(".ruff.toml").is_file()
+            || contains("pyproject.toml", "[tool.ruff")
+        {
+            linters.push(Linter::Ruff);
+        }
+        linters
+    }
+
+    /// `path` relative to the repo root, checked to exist inside it; None for the root.
+    fn resolve_path(&self, path: Option<&str>) -> Result<Option<String>, LintError> {
+        let path = match path.map(|p| p.trim().trim_start_matches("./")) {
+            Some(p) if !p.is_empty() && p != "." => p,
+            _ => return Ok(None),
+        };
+        let full = self.repo_root.join(path);
+        if !full.exists() {
+            return Err(LintError::PathNotFound(path.to_string()));
+        }
+        let canonical_root = self
+            .repo_root
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_root.clone());
+        if !full.canonicalize()?.starts_with(&canonical_root) {
+            return Err(LintError::PathOutsideRepo(path.to_string()));
+        }
+        Ok(Some(path.to_string()))
+    }
+
+    /// Shell command for `linter`. Clippy checks the whole workspace; its
+    /// diagnostics are filtered to `path` afterwards.
+    pub fn command(linter: Linter, path: Option<&str>) -> String {
+        let target = shell_quote(path.unwrap_or("."));
+        match linter {
+            Linter::Clippy => {
+                "cargo clippy --workspace --all-targets --message-format=json".to_string()
+            }
+            Linter::Eslint => format!("npx --no-install eslint -f json {}", target),
+            Linter::Ruff => format!("ruff check --output-format json --exit-zero {}", target),
+        }
+    }
+
+    async fn run(
+        &self,
+        linter: Linter,
+        path: Option<&str>,
+        command: &str,
+    ) -> Result<Vec<LintDiagnostic>, LintError> {
+        let mut prepared = self.backend.command(
+            &self.repo_root,
+            Some(&self.repo_root),
+            shell_and_arg(),
+            command,
+        )?;
+        prepared.command.kill_on_drop(true);
+        let output = prepared
+            .command
+            .output()
+            .await
+            .map_err(|e| LintError::SpawnFailed(e.to_string()))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let exit_code = output.status.code().unwrap_or(-1);
+        let failed = || LintError::CommandFailed {
+            command: command.to_string(),
+            exit_code,
+            stderr: stderr_tail(&stderr),
+        };
+        if exit_code == EXIT_NOT_FOUND {
+            return Err(LintError::NotInstalled {
+                linter: linter.name().to_string(),
+                command: command.to_string(),
+            });
+        }
+
+        match linter {
+            Linter::Clippy => {
+                let mut diagnostics = parse::parse_clippy(&self.repo_root, &stdout);
+                // A failed build with no errors to show (bad manifest, missing toolchain).
+                if exit_code != 0
+                    && !diagnostics
+                        .iter()
+                        .any(|d| d.severity == LintSeverity::Error)
+                {
+                    return Err(failed());
+                }
+                if let Some(path) = path {
+                    diagnostics.retain(|d| Path::new(&d.path).starts_with(path));
+                }
+                Ok(diagnostics)
+            }
+            Linter::Eslint | Linter::Ruff => {
+                let parsed = match linter {
+                    Linter::Eslint => parse::parse_eslint(&self.repo_root, &stdout),
+                    _ => parse::parse_ruff(&self.repo_root, &stdout),
+                };
+                // ESLint exits 1 when it found problems; its output is still valid.
+                parsed.map_err(|reason| match exit_code {
+                    0 => LintError::BadOutput {
+                        linter: linter.name().to_string(),
+                        reason,
+                    },
+                    _ => failed(),
+                })
+            }
+        }
+    }
+
+    async fn lint(&self, args: LintArgs, ctx: &ToolContext) -> Result<LintResult, LintError> {
+        let path = self.resolve_path(args.path.as_deref())?;
+        let linters = match args.linter {
+            Some(linter) => vec![linter],
+            None => Self::detect(&self.repo_root),
+        };
+        if linters.is_empty() {
+            return Err(LintError::NoLinter);
+        }
+
+        let mut commands = Vec::new();
+        let mut diagnostics = Vec::new();
+        for &linter in &linters {
+            let command = Self::command(linter, path.as_deref());
+            ctx.report(format!("Running {}", command));
+            diagnostics.extend(self.run(linter, path.as_deref(), &command).await?);
+            commands.push(command);
+        }
+
+        diagnostics.sort_by(|a, b| {
+            (a.severity, &a.path, a.line, a.column).cmp(&(b.severity, &b.path, b.line, b.column))
+        });
+        let mut counts = LintCounts::default();
+        for diagnostic in &diagnostics {
+            counts.add(diagnostic.severity);
+        }
+        let total = diagnostics.len();
+        diagnostics.truncate(args.max_results);
+        Ok(LintResult {
+            linters,
+            commands,
+            counts,
+            truncated: diagnostics.len() < total,
+            diagnostics,
+            total,
+        })
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn shell_and_arg() -> (&'static str, &'static str) {
+    ("/bin/sh", "-c")
+}
+
+#[cfg(windows)]
+pub(crate) fn shell_and_arg() -> (&'static str, &'static str) {
+    ("cmd", "/c")
+}
+
+/// Last lines of `stderr`, where build and config errors end up.
+pub(crate) fn stderr_tail(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.trim_end().lines().collect();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for Lint {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    /// Linters build the code, which runs build scripts.
+    fn permission(&self) -> Permission {
+        Permission::Execute
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: LintArgs = serde_json::from_value(args)?;
+        // Dropping the run kills the linter (kill_on_drop).
+        tokio::select! {
+            result = self.lint(args, &ctx) => Ok(result?.to_json()),
+            _ = ctx.cancel.cancelled() => Err(anyhow::anyhow!("Lint cancelled")),
+        }
+    }
+}
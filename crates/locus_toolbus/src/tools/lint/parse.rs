@@ -0,0 +1,169 @@
+//! Linter JSON output to [LintDiagnostic]s.
+
+use super::args::{LintDiagnostic, LintSeverity, Linter};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// `cargo clippy --message-format=json`: one JSON object per line; compiler
+/// messages with a primary span in the repo become diagnostics. The same
+/// message reported for several targets (lib and tests) is kept once.
+pub(crate) fn parse_clippy(repo_root: &Path, stdout: &str) -> Vec<LintDiagnostic> {
+    let mut diagnostics: Vec<LintDiagnostic> = Vec::new();
+    for line in stdout.lines() {
+        let Ok(record) = serde_json::from_str::<JsonValue>(line) else {
+            continue;
+        };
+        if record["reason"] != "compiler-message" {
+            continue;
+        }
+        let message = &record["message"];
+        let Some(span) = message["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|s| s["is_primary"] == true))
+        else {
+            continue;
+        };
+        let Some(path) = span["file_name"]
+            .as_str()
+            .and_then(|p| repo_relative(repo_root, p))
+        else {
+            continue;
+        };
+        let severity = match message["level"].as_str().unwrap_or("") {
+            level if level.starts_with("error") => LintSeverity::Error,
+            "warning" => LintSeverity::Warning,
+            "help" => LintSeverity::Hint,
+            _ => LintSeverity::Information,
+        };
+        let diagnostic = LintDiagnostic {
+            path,
+            line: index(&span["line_start"]),
+            column: index(&span["column_start"]),
+            severity,
+            code: message["code"]["code"].as_str().map(str::to_string),
+            message: message["message"].as_str().unwrap_or("").to_string(),
+            help: clippy_help(message),
+            linter: Linter::Clippy,
+        };
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// First `help` child, with its suggested replacement when there is one.
+fn clippy_help(message: &JsonValue) -> Option<String> {
+    let help = message["children"]
+        .as_array()?
+        .iter()
+        .find(|c| c["level"] == "help")?;
+    let text = help["message"].as_str()?;
+    let replacement = help["spans"]
+        .as_array()
+        .and_then(|spans| {
+            spans
+                .iter()
+                .find_map(|s| s["suggested_replacement"].as_str())
+        })
+        .filter(|r| !r.is_empty() && !r.contains('\n'));
+    Some(match replacement {
+        Some(replacement) => format!("{}: `{}`", text, replacement),
+        None => text.to_string(),
+    })
+}
+
+/// `eslint -f json`: an array of files, each with its messages.
+pub(crate) fn parse_eslint(repo_root: &Path, stdout: &str) -> Result<Vec<LintDiagnostic>, String> {
+    let files: Vec<JsonValue> =
+        serde_json::from_str(json_start(stdout, '[')).map_err(|e| e.to_string())?;
+    let mut diagnostics = Vec::new();
+    for file in &files {
+        let Some(path) = file["filePath"]
+            .as_str()
+            .and_then(|p| repo_relative(repo_root, p))
+        else {
+            continue;
+        };
+        for message in file["messages"].as_array().into_iter().flatten() {
+            let help = message["suggestions"][0]["desc"]
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| {
+                    message
+                        .get("fix")
+                        .map(|_| "Fixable with eslint --fix".to_string())
+                });
+            diagnostics.push(LintDiagnostic {
+                path: path.clone(),
+                line: index(&message["line"]),
+                column: index(&message["column"]),
+                severity: match message["severity"].as_u64() {
+                    Some(1) => LintSeverity::Warning,
+                    _ => LintSeverity::Error,
+                },
+                code: message["ruleId"].as_str().map(str::to_string),
+                message: message["message"].as_str().unwrap_or("").to_string(),
+                help,
+                linter: Linter::Eslint,
+            });
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// `ruff check --output-format json`: an array of violations. Ruff has no
+/// severities; syntax errors (no code, or `E999`) are errors, the rest warnings.
+pub(crate) fn parse_ruff(repo_root: &Path, stdout: &str) -> Result<Vec<LintDiagnostic>, String> {
+    let violations: Vec<JsonValue> =
+        serde_json::from_str(json_start(stdout, '[')).map_err(|e| e.to_string())?;
+    let mut diagnostics = Vec::new();
+    for violation in &violations {
+        let Some(path) = violation["filename"]
+            .as_str()
+            .and_then(|p| repo_relative(repo_root, p))
+        else {
+            continue;
+        };
+        let code = violation["code"].as_str().map(str::to_string);
+        let severity = match code.as_deref() {
+            None | Some("E999") => LintSeverity::Error,
+            Some(_) => LintSeverity::Warning,
+        };
+        diagnostics.push(LintDiagnostic {
+            path,
+            line: index(&violation["location"]["row"]),
+            column: index(&violation["location"]["column"]),
+            severity,
+            code,
+            message: violation["message"].as_str().unwrap_or("").to_string(),
+            help: violation["fix"]["message"].as_str().map(str::to_string),
+            linter: Linter::Ruff,
+        });
+    }
+    Ok(diagnostics)
+}
+
+/// `path` relative to `repo_root`; None for absolute paths outside it.
+pub(crate) fn repo_relative(repo_root: &Path, path: &str) -> Option<String> {
+    let path = Path::new(path);
+    if path.is_relative() {
+        return Some(path.to_string_lossy().trim_start_matches("./").to_string());
+    }
+    let canonical_root = repo_root.canonicalize().ok();
+    [Some(repo_root), canonical_root.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// A 1-based position field; missing positions (file-level messages) are 1.
+fn index(value: &JsonValue) -> usize {
+    value.as_u64().map_or(1, |n| n.max(1) as usize)
+}
+
+/// `text` from the first `open` on, skipping anything a wrapper printed first.
+fn json_start(text: &str, open: char) -> &str {
+    text.find(open).map_or(text, |i| &text[i..])
+}
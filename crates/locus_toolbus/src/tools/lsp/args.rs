@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LspDiagnosticsArgs {
+    /// File to check (relative to repo root)
+    pub path: String,
+
+    /// How long to wait for the server to publish diagnostics
+    #[serde(default = "default_wait_ms")]
+    pub wait_ms: u64,
+}
+
+fn default_wait_ms() -> u64 {
+    5000
+}
+
+impl LspDiagnosticsArgs {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            wait_ms: default_wait_ms(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LspHoverArgs {
+    /// File containing the symbol (relative to repo root)
+    pub path: String,
+
+    /// 1-based line
+    pub line: usize,
+
+    /// Symbol on the line to hover; its first whole-word occurrence is used
+    #[serde(default)]
+    pub symbol: Option<String>,
+
+    /// 1-based character column, when no symbol is given
+    #[serde(default)]
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LspRenameArgs {
+    /// File containing the symbol (relative to repo root)
+    pub path: String,
+
+    /// 1-based line
+    pub line: usize,
+
+    /// Symbol on the line to rename; its first whole-word occurrence is used
+    #[serde(default)]
+    pub symbol: Option<String>,
+
+    /// 1-based character column, when no symbol is given
+    #[serde(default)]
+    pub column: Option<usize>,
+
+    pub new_name: String,
+
+    /// Report the edits without writing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
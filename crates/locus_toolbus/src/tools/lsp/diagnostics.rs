@@ -0,0 +1,109 @@
+use super::args::LspDiagnosticsArgs;
+use super::range_json;
+use crate::lsp::LspBridge;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+const MAX_WAIT_MS: u64 = 60_000;
+const MAX_DIAGNOSTICS: usize = 200;
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/lsp_diagnostics.json")))
+}
+
+pub struct LspDiagnostics {
+    bridge: Arc<LspBridge>,
+}
+
+impl LspDiagnostics {
+    pub fn new(bridge: Arc<LspBridge>) -> Self {
+        Self { bridge }
+    }
+}
+
+/// LSP `DiagnosticSeverity` as a word; servers may omit it, which means error.
+fn severity_name(severity: Option<u64>) -> &'static str {
+    match severity {
+        Some(2) => "warning",
+        Some(3) => "information",
+        Some(4) => "hint",
+        _ => "error",
+    }
+}
+
+/// One LSP `Diagnostic` with 1-based positions.
+pub(crate) fn diagnostic_json(text: &str, diagnostic: &JsonValue) -> JsonValue {
+    let mut out = range_json(text, &diagnostic["range"]);
+    if !out.is_object() {
+        out = json!({});
+    }
+    out["severity"] = json!(severity_name(diagnostic["severity"].as_u64()));
+    out["message"] = diagnostic["message"].clone();
+    out["code"] = match &diagnostic["code"] {
+        JsonValue::Number(n) => json!(n.to_string()),
+        other => other.clone(),
+    };
+    out["source"] = diagnostic["source"].clone();
+    out
+}
+
+#[async_trait]
+impl Tool for LspDiagnostics {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: LspDiagnosticsArgs = serde_json::from_value(args)?;
+        let file = self.bridge.open(&args.path).await?;
+        let wait = Duration::from_millis(args.wait_ms.min(MAX_WAIT_MS));
+
+        // Unchanged text that already has diagnostics needs no new publish.
+        let (raw, complete) = match file.client.diagnostics(&file.uri) {
+            Some(published) if !file.synced => (published.diagnostics, true),
+            _ => {
+                file.client
+                    .wait_for_diagnostics(&file.uri, file.generation_before, wait)
+                    .await
+            }
+        };
+
+        let mut diagnostics: Vec<JsonValue> =
+            raw.iter().map(|d| diagnostic_json(&file.text, d)).collect();
+        diagnostics.sort_by_key(|d| {
+            (
+                d["line"].as_u64().unwrap_or(0),
+                d["column"].as_u64().unwrap_or(0),
+            )
+        });
+        let mut counts = json!({ "error": 0, "warning": 0, "information": 0, "hint": 0 });
+        for diagnostic in &diagnostics {
+            if let Some(severity) = diagnostic["severity"].as_str() {
+                counts[severity] = json!(counts[severity].as_u64().unwrap_or(0) + 1);
+            }
+        }
+        let truncated = diagnostics.len() > MAX_DIAGNOSTICS;
+        diagnostics.truncate(MAX_DIAGNOSTICS);
+
+        Ok(json!({
+            "path": file.relative,
+            "server": file.client.server_id(),
+            "complete": complete,
+            "counts": counts,
+            "diagnostics": diagnostics,
+            "truncated": truncated,
+        }))
+    }
+}
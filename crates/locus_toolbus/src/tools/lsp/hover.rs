@@ -0,0 +1,101 @@
+use super::args::LspHoverArgs;
+use super::{range_json, resolve_position};
+use crate::lsp::LspBridge;
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/lsp_hover.json")))
+}
+
+pub struct LspHover {
+    bridge: Arc<LspBridge>,
+    protected: ProtectedPathMatcher,
+}
+
+impl LspHover {
+    pub fn new(bridge: Arc<LspBridge>) -> Self {
+        Self {
+            bridge,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path` by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+}
+
+/// Hover `contents` as text: `MarkupContent`, a `MarkedString` or an array of them.
+pub(crate) fn hover_text(contents: &JsonValue) -> String {
+    match contents {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Array(items) => items
+            .iter()
+            .map(hover_text)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        JsonValue::Object(object) => {
+            let value = object.get("value").and_then(|v| v.as_str()).unwrap_or("");
+            match object.get("language").and_then(|l| l.as_str()) {
+                Some(language) => format!("```{}\n{}\n```", language, value),
+                None => value.to_string(),
+            }
+        }
+        _ => String::new(),
+    }
+    .trim()
+    .to_string()
+}
+
+#[async_trait]
+impl Tool for LspHover {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: LspHoverArgs = serde_json::from_value(args)?;
+        self.protected
+            .check(self.bridge.repo_root(), Path::new(&args.path), &args.path)?;
+        let file = self.bridge.open(&args.path).await?;
+        let position =
+            resolve_position(&file.text, args.line, args.symbol.as_deref(), args.column)?;
+
+        let result = file
+            .client
+            .request(
+                "textDocument/hover",
+                json!({
+                    "textDocument": { "uri": file.uri },
+                    "position": { "line": position.line, "character": position.character },
+                }),
+            )
+            .await?;
+
+        let contents = hover_text(&result["contents"]);
+        Ok(json!({
+            "path": file.relative,
+            "line": args.line,
+            "found": !contents.is_empty(),
+            "contents": contents,
+            "range": range_json(&file.text, &result["range"]),
+        }))
+    }
+}
@@ -0,0 +1,101 @@
+//! Language server tools: `lsp_diagnostics`, `lsp_hover` (read) and `lsp_rename`
+//! (write).
+//!
+//! All three go through one shared [`LspBridge`](crate::lsp::LspBridge), so a
+//! server started for one call is reused by the next. Positions are 1-based
+//! lines and character columns; a symbol name on the line can stand in for the
+//! column, since models rarely count columns right.
+
+mod args;
+mod diagnostics;
+mod hover;
+mod rename;
+
+pub use args::{LspDiagnosticsArgs, LspHoverArgs, LspRenameArgs};
+pub use diagnostics::LspDiagnostics;
+pub use hover::LspHover;
+pub use rename::LspRename;
+
+use serde_json::{Value as JsonValue, json};
+
+use crate::lsp::edit::{Position, char_column, line_text, utf16_offset};
+use crate::lsp::{LspError, LspResult};
+
+/// LSP position of a 1-based `line` and either `symbol` (first whole-word match on
+/// the line) or a 1-based `column` (default: first non-blank character).
+pub(crate) fn resolve_position(
+    text: &str,
+    line: usize,
+    symbol: Option<&str>,
+    column: Option<usize>,
+) -> LspResult<Position> {
+    let index = line
+        .checked_sub(1)
+        .ok_or_else(|| LspError::InvalidArgs("line is 1-based".to_string()))?;
+    let line_str = line_text(text, index as u32).ok_or_else(|| {
+        LspError::InvalidArgs(format!(
+            "line {} is past the end of the file ({} lines)",
+            line,
+            text.lines().count()
+        ))
+    })?;
+    let column = match (symbol, column) {
+        (Some(symbol), _) => find_symbol(line_str, symbol).ok_or_else(|| {
+            LspError::InvalidArgs(format!("'{}' does not appear on line {}", symbol, line))
+        })?,
+        (None, Some(0)) => {
+            return Err(LspError::InvalidArgs("column is 1-based".to_string()));
+        }
+        (None, Some(column)) => column,
+        (None, None) => line_str
+            .chars()
+            .position(|c| !c.is_whitespace())
+            .map_or(1, |i| i + 1),
+    };
+    Ok(Position {
+        line: index as u32,
+        character: utf16_offset(line_str, column),
+    })
+}
+
+/// 1-based column of the first occurrence of `symbol` in `line` that is not part
+/// of a longer identifier, falling back to the first occurrence at all.
+fn find_symbol(line: &str, symbol: &str) -> Option<usize> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        return None;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let column_of = |byte: usize| line[..byte].chars().count() + 1;
+    let mut first = None;
+    for (byte, _) in line.match_indices(symbol) {
+        first.get_or_insert(byte);
+        let before = line[..byte].chars().next_back();
+        let after = line[byte + symbol.len()..].chars().next();
+        if !before.is_some_and(is_ident) && !after.is_some_and(is_ident) {
+            return Some(column_of(byte));
+        }
+    }
+    first.map(column_of)
+}
+
+/// An LSP `Range` in `text` as 1-based lines and character columns.
+pub(crate) fn range_json(text: &str, range: &JsonValue) -> JsonValue {
+    let point = |key: &str| {
+        let position = range.get(key).and_then(Position::from_json)?;
+        let line = line_text(text, position.line).unwrap_or("");
+        Some((
+            position.line as usize + 1,
+            char_column(line, position.character),
+        ))
+    };
+    match (point("start"), point("end")) {
+        (Some((line, column)), Some((end_line, end_column))) => json!({
+            "line": line,
+            "column": column,
+            "end_line": end_line,
+            "end_column": end_column,
+        }),
+        _ => JsonValue::Null,
+    }
+}
@@ -0,0 +1,163 @@
+use super::args::LspRenameArgs;
+use super::resolve_position;
+use crate::history::EditHistory;
+use crate::lsp::edit::{apply_edits, workspace_edit_files};
+use crate::lsp::{LspBridge, LspError, uri_to_path};
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schemas/lsp_rename.json")))
+}
+
+pub struct LspRename {
+    bridge: Arc<LspBridge>,
+    history: Arc<EditHistory>,
+}
+
+impl LspRename {
+    pub fn new(bridge: Arc<LspBridge>, history: Arc<EditHistory>) -> Self {
+        Self { bridge, history }
+    }
+}
+
+/// One file a rename rewrites.
+struct FileChange {
+    path: PathBuf,
+    relative: String,
+    old_content: String,
+    new_content: String,
+    edits: usize,
+    lines: Vec<usize>,
+}
+
+#[async_trait]
+impl Tool for LspRename {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    /// Each file the rename rewrites is reported to `ctx.writes` before it is written.
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: LspRenameArgs = serde_json::from_value(args)?;
+        let new_name = args.new_name.trim();
+        if new_name.is_empty() || new_name.chars().any(char::is_whitespace) {
+            return Err(LspError::InvalidArgs(format!(
+                "new_name must be a single non-empty identifier: {:?}",
+                args.new_name
+            ))
+            .into());
+        }
+
+        let file = self.bridge.open(&args.path).await?;
+        let position =
+            resolve_position(&file.text, args.line, args.symbol.as_deref(), args.column)?;
+        let result = file
+            .client
+            .request(
+                "textDocument/rename",
+                json!({
+                    "textDocument": { "uri": file.uri },
+                    "position": { "line": position.line, "character": position.character },
+                    "newName": new_name,
+                }),
+            )
+            .await?;
+        if result.is_null() {
+            return Err(LspError::InvalidArgs(format!(
+                "nothing to rename at line {} of {}",
+                args.line, file.relative
+            ))
+            .into());
+        }
+
+        // Work out every file's new content before writing any, so a rename that
+        // reaches outside the repo changes nothing.
+        let mut changes = Vec::new();
+        for (uri, edits) in workspace_edit_files(&result)? {
+            let path = uri_to_path(&uri)
+                .ok_or_else(|| LspError::Protocol(format!("not a file uri: {}", uri)))?;
+            let path = path
+                .canonicalize()
+                .map_err(|_| LspError::FileNotFound(path.display().to_string()))?;
+            let relative = self.bridge.relative(&path)?;
+            let old_content = if path == file.path {
+                file.text.clone()
+            } else {
+                tokio::fs::read_to_string(&path).await?
+            };
+            let new_content = apply_edits(&old_content, &edits)?;
+            let mut lines: Vec<usize> = edits.iter().map(|e| e.start.line as usize + 1).collect();
+            lines.sort_unstable();
+            lines.dedup();
+            changes.push(FileChange {
+                path,
+                relative,
+                old_content,
+                new_content,
+                edits: edits.len(),
+                lines,
+            });
+        }
+
+        if !args.dry_run {
+            for change in &changes {
+                ctx.writes
+                    .before_write(&change.relative, &change.old_content);
+            }
+            for change in &changes {
+                tokio::fs::write(&change.path, &change.new_content).await?;
+                let _ = self
+                    .history
+                    .record(&change.path, &change.old_content, &change.new_content)
+                    .await;
+            }
+            for change in &changes {
+                let synced = self
+                    .bridge
+                    .sync(
+                        change.path.clone(),
+                        change.relative.clone(),
+                        change.new_content.clone(),
+                    )
+                    .await;
+                if let Err(e) = synced {
+                    tracing::debug!("lsp_rename: not re-synced {}: {}", change.relative, e);
+                }
+            }
+        }
+
+        let total_edits: usize = changes.iter().map(|c| c.edits).sum();
+        let files: Vec<JsonValue> = changes
+            .iter()
+            .map(|c| json!({ "path": c.relative, "edits": c.edits, "lines": c.lines }))
+            .collect();
+        Ok(json!({
+            "new_name": new_name,
+            "applied": !args.dry_run,
+            "files": files,
+            "total_edits": total_edits,
+        }))
+    }
+}
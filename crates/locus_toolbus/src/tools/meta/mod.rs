@@ -45,11 +45,14 @@ pub fn meta_tool_definitions() -> Vec<ToolInfo> {
     ]
 }
 
+/// What the `task` tool tells the model it is for.
+pub const TASK_TOOL_DESCRIPTION: &str = "Run a sub-task in a separate agent. Use for independent, parallelizable work. Multiple task calls in the same response run in parallel. Do NOT use for simple single-file edits.";
+
 /// Task tool definition for the LLM.
 pub fn task_tool_definition() -> ToolInfo {
     ToolInfo {
         name: "task".to_string(),
-        description: "Run a sub-task in a separate agent. Use for independent, parallelizable work. Multiple task calls in the same response run in parallel. Do NOT use for simple single-file edits.".to_string(),
+        description: TASK_TOOL_DESCRIPTION.to_string(),
         parameters: serde_json::json!({
             "type": "object",
             "properties": {
@@ -60,6 +63,10 @@ pub fn task_tool_definition() -> ToolInfo {
                 "description": {
                     "type": "string",
                     "description": "Short label for this task (shown in UI)"
+                },
+                "profile": {
+                    "type": "string",
+                    "description": "Optional agent profile; the sub-agent gets only that profile's tools. Omit for a full agent. searcher: read-only, cheap model; tester: bash and reading files; reviewer: reading files and git history"
                 }
             },
             "required": ["prompt", "description"]
@@ -1,20 +1,48 @@
+#[cfg(feature = "syntax")]
+pub mod ast_search;
 pub mod bash;
+#[cfg(feature = "syntax")]
+pub mod code_nav;
 pub mod create_file;
+pub mod deps;
 pub mod edit_file;
+pub mod file_history;
 pub mod finder;
+pub mod format_code;
+pub mod git;
 pub mod glob;
 pub mod grep;
 pub mod handoff;
+pub mod json_query;
+pub mod lint;
+pub mod lsp;
 pub mod meta;
+pub mod notes;
 pub mod read;
+pub mod repo_stats;
+#[cfg(feature = "semantic")]
+pub mod semantic_search;
+pub mod sqlite_query;
+pub mod syntax;
 pub mod task_list;
+pub mod todo_scan;
+pub mod tree;
 pub mod undo_edit;
+#[cfg(feature = "web")]
 pub mod web_automation;
+#[cfg(feature = "web")]
+pub mod web_fetch;
 
+use crate::permission::Permission;
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::result_cache::CacheScope;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Deserialize)]
 pub(crate) struct ToolSchemaJson {
@@ -35,18 +63,138 @@ pub(crate) fn parse_tool_schema(json: &'static str) -> (&'static str, &'static s
     )
 }
 
+/// Directories `grep` and `glob` never enter, besides dot-directories.
+pub(crate) const UNSEARCHED_DIRS: &[&str] = &["target", "node_modules", "vendor"];
+
+/// A path argument that doesn't lead to something the tool can use.
+#[derive(Debug, thiserror::Error)]
+pub enum PathError {
+    #[error("Path does not exist: {0}")]
+    NotFound(String),
+
+    #[error("Path is outside repository: {0}")]
+    OutsideRepo(String),
+
+    #[error("Not a directory: {0}")]
+    NotADirectory(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Where a tool that walks the repo starts: `path` under `repo_root`, or the
+/// root itself when `path` is missing, empty or `.`. Fails with [PathError]
+/// when it does not exist or leads outside the repo (symlinks included), and
+/// with [crate::ProtectedPathDenied] when `protected` covers it.
+///
+/// Every tool that reads files resolves its path arguments here, or through
+/// [resolve_dir] / [resolve_file]; walks leave out what `protected` covers.
+pub(crate) fn resolve_start(
+    repo_root: &Path,
+    path: Option<&str>,
+    protected: &ProtectedPathMatcher,
+) -> anyhow::Result<PathBuf> {
+    let shown = path.unwrap_or(".");
+    let start = match path {
+        Some(p) if !p.is_empty() && p != "." => repo_root.join(p),
+        _ => repo_root.to_path_buf(),
+    };
+    if !start.exists() {
+        return Err(PathError::NotFound(shown.to_string()).into());
+    }
+    let canonical_root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.to_path_buf());
+    if !start
+        .canonicalize()
+        .map_err(PathError::from)?
+        .starts_with(&canonical_root)
+    {
+        return Err(PathError::OutsideRepo(start.to_string_lossy().to_string()).into());
+    }
+    protected.check(repo_root, &start, shown)?;
+    Ok(start)
+}
+
+/// [resolve_start] for tools that need a directory.
+pub(crate) fn resolve_dir(
+    repo_root: &Path,
+    path: Option<&str>,
+    protected: &ProtectedPathMatcher,
+) -> anyhow::Result<PathBuf> {
+    let start = resolve_start(repo_root, path, protected)?;
+    if !start.is_dir() {
+        return Err(PathError::NotADirectory(path.unwrap_or(".").to_string()).into());
+    }
+    Ok(start)
+}
+
+/// [resolve_start] for tools that read one file.
+pub(crate) fn resolve_file(
+    repo_root: &Path,
+    path: &str,
+    protected: &ProtectedPathMatcher,
+) -> anyhow::Result<PathBuf> {
+    if !repo_root.join(path).is_file() {
+        return Err(PathError::NotFound(path.to_string()).into());
+    }
+    resolve_start(repo_root, Some(path), protected)
+}
+
+#[cfg(feature = "syntax")]
+pub use ast_search::{AstMatch, AstSearch, AstSearchArgs, AstSearchError, AstSearchResult};
 pub use bash::{Bash, BashArgs, BashError, BashExecutor};
+#[cfg(feature = "syntax")]
+pub use code_nav::{
+    CodeLocation, CodeNav, CodeNavAction, CodeNavArgs, CodeNavError, CodeNavResult,
+};
 pub use create_file::{CreateFile, CreateFileArgs, CreateFileError};
+pub use deps::{
+    DependencyNode, Deps, DepsAction, DepsArgs, DepsError, DepsResult, Ecosystem, OutdatedPackage,
+    VulnCounts, VulnSeverity, Vulnerability,
+};
 pub use edit_file::{EditFile, EditFileArgs, EditFileError, EditOperation};
+pub use file_history::{FileHistory, FileHistoryAction, FileHistoryArgs, FileHistoryError};
 pub use finder::{Finder, FinderArgs, FinderError, FinderResult, SearchMatch};
+pub use format_code::{
+    FormatCode, FormatCodeArgs, FormatCodeError, FormatCodeResult, FormatMode, FormattedFile,
+    Formatter,
+};
+pub use git::{
+    BranchAction, GitBranch, GitBranchArgs, GitCommit, GitCommitArgs, GitDiff, GitDiffArgs,
+    GitError, GitLog, GitLogArgs, GitStatus, GitStatusArgs,
+};
 pub use glob::{Glob, GlobArgs, GlobError, GlobResult};
 pub use grep::{Grep, GrepArgs, GrepError, GrepMatch, GrepResult};
 pub use handoff::{Handoff, HandoffArgs, HandoffError};
-pub use meta::{meta_tool_definitions, task_tool_definition};
+pub use json_query::{DocumentFormat, JsonQuery, JsonQueryArgs, JsonQueryError, JsonQueryResult};
+pub use lint::{
+    Lint, LintArgs, LintCounts, LintDiagnostic, LintError, LintResult, LintSeverity, Linter,
+};
+pub use lsp::{
+    LspDiagnostics, LspDiagnosticsArgs, LspHover, LspHoverArgs, LspRename, LspRenameArgs,
+};
+pub use meta::{TASK_TOOL_DESCRIPTION, meta_tool_definitions, task_tool_definition};
+pub use notes::{NoteSummary, Notes, NotesAction, NotesArgs, NotesError};
 pub use read::{Read, ReadArgs, ReadError};
-pub use task_list::{TaskItem, TaskList, TaskListAction, TaskListArgs, TaskListError, TaskStatus};
+pub use repo_stats::{
+    DirectoryStats, FileStats, LanguageStats, LineCounts, RepoStats, RepoStatsArgs, RepoStatsError,
+    RepoStatsResult,
+};
+#[cfg(feature = "semantic")]
+pub use semantic_search::{SemanticSearch, SemanticSearchArgs};
+pub use sqlite_query::{SqliteQuery, SqliteQueryArgs, SqliteQueryError, SqliteQueryResult};
+pub use task_list::{
+    AcceptanceCriterion, STRICT_TASKS_ENV, TaskItem, TaskList, TaskListAction, TaskListArgs,
+    TaskListError, TaskStatus,
+};
+pub use todo_scan::{TodoItem, TodoScan, TodoScanArgs, TodoScanError, TodoScanResult, TodoSort};
+pub use tree::{Tree, TreeArgs, TreeError, TreeNode, TreeNodeKind, TreeResult};
 pub use undo_edit::{UndoEdit, UndoEditArgs, UndoEditError};
+#[cfg(feature = "web")]
 pub use web_automation::{ProxyConfig, WebAutomation, WebAutomationArgs, WebAutomationError};
+#[cfg(feature = "web")]
+pub use web_fetch::{FetchMethod, WebFetch, WebFetchArgs, WebFetchError};
 
 pub type ToolResult = anyhow::Result<JsonValue>;
 
@@ -55,7 +203,129 @@ pub trait Tool: Send + Sync {
     fn name(&self) -> &'static str;
     fn description(&self) -> &'static str;
     fn parameters_schema(&self) -> JsonValue;
+    /// What the tool can do; decides whether a call needs approval.
+    fn permission(&self) -> Permission {
+        Permission::Read
+    }
+    /// Whether the bus may answer a repeated call from [crate::result_cache]
+    /// while the files under its `path` are unchanged. Only for read-only tools
+    /// whose result depends on nothing else.
+    fn cacheable(&self) -> bool {
+        false
+    }
+    /// The entries under `path` a cacheable call can read; the cache's
+    /// fingerprint leaves out the rest. Everything by default.
+    fn cache_scope(&self, args: &JsonValue) -> CacheScope {
+        let _ = args;
+        CacheScope::All
+    }
+    /// Whether making this call twice does no more than making it once, so the
+    /// bus may retry it after a transient failure (see [crate::recovery]).
+    /// Read tools by default.
+    fn idempotent(&self, args: &JsonValue) -> bool {
+        let _ = args;
+        self.permission() == Permission::Read
+    }
     async fn execute(&self, args: JsonValue) -> ToolResult;
+    /// [Tool::execute] with a [ToolContext]: report what the tool is doing and
+    /// stop early when the call is cancelled. Tools that finish quickly keep the
+    /// default, which ignores both.
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let _ = ctx;
+        self.execute(args).await
+    }
+}
+
+/// What a running tool gets besides its arguments.
+///
+/// `cancel` fires when the call hits its policy timeout or the caller stops it.
+/// Tools holding resources (a remote run, a child process) watch it to clean
+/// up; the bus stops waiting after [crate::policy::CANCEL_GRACE] either way.
+/// `session_id` is the agent session making the call, for tools that keep
+/// per-session state (`notes`); None outside a session. `writes` hears about
+/// the files a tool finds it has to change only while it runs.
+#[derive(Debug, Clone, Default)]
+pub struct ToolContext {
+    pub progress: ProgressSink,
+    pub cancel: CancellationToken,
+    pub session_id: Option<String>,
+    pub writes: WriteSink,
+}
+
+impl ToolContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_progress(mut self, progress: ProgressSink) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_writes(mut self, writes: WriteSink) -> Self {
+        self.writes = writes;
+        self
+    }
+
+    pub fn report(&self, message: impl Into<String>) {
+        self.progress.report(message);
+    }
+}
+
+/// Where a running tool sends short progress lines ("navigating to example.com").
+/// Lines are dropped when nobody listens.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressSink {
+    tx: Option<mpsc::UnboundedSender<String>>,
+}
+
+impl ProgressSink {
+    pub fn new(tx: mpsc::UnboundedSender<String>) -> Self {
+        Self { tx: Some(tx) }
+    }
+
+    /// A sink nobody listens to.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&self, message: impl Into<String>) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(message.into());
+        }
+    }
+}
+
+/// Where a tool that works out which files it changes while it runs
+/// (`lsp_rename`, `format_code`) reports each one before writing it: its path
+/// relative to the repo root and its content before the write. Callers that
+/// guard or record edits snapshot them from here, since the call's arguments
+/// don't name them all. Reports are dropped when nobody listens.
+#[derive(Debug, Clone, Default)]
+pub struct WriteSink {
+    tx: Option<mpsc::UnboundedSender<(String, String)>>,
+}
+
+impl WriteSink {
+    pub fn new(tx: mpsc::UnboundedSender<(String, String)>) -> Self {
+        Self { tx: Some(tx) }
+    }
+
+    pub fn before_write(&self, path: impl Into<String>, content: impl Into<String>) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send((path.into(), content.into()));
+        }
+    }
 }
 
 pub struct ToolOutput {
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Action to perform on the notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotesAction {
+    /// Create or replace a note.
+    Write,
+    /// Add to the end of a note, creating it if missing.
+    Append,
+    Read,
+    List,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotesArgs {
+    pub action: NotesAction,
+
+    /// For write, append, read: note name (letters, digits, `.`, `_`, `-`).
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// For write, append: the text.
+    #[serde(default)]
+    pub content: Option<String>,
+
+    /// Session whose notes to use; the current session when omitted.
+    #[serde(default)]
+    pub session: Option<String>,
+}
+
+impl NotesArgs {
+    pub fn new(action: NotesAction) -> Self {
+        Self {
+            action,
+            name: None,
+            content: None,
+            session: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+}
+
+/// One note in a `list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteSummary {
+    pub name: String,
+    pub bytes: u64,
+    /// Last change, seconds since the Unix epoch.
+    pub modified: u64,
+    /// First non-empty line.
+    pub preview: String,
+}
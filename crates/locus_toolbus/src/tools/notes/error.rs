@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NotesError {
+    #[error("Missing required field for action: {0}")]
+    MissingField(String),
+
+    #[error("Invalid note name {0:?}: use letters, digits, '.', '_' and '-' (max 64)")]
+    InvalidName(String),
+
+    #[error("Invalid session {0:?}")]
+    InvalidSession(String),
+
+    #[error("Note not found: {0}")]
+    NoteNotFound(String),
+
+    #[error("Note {name} would exceed {limit} bytes; split it or write a summary")]
+    TooLarge { name: String, limit: usize },
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
@@ -0,0 +1,225 @@
+mod args;
+mod error;
+
+pub use args::{NoteSummary, NotesAction, NotesArgs};
+pub use error::NotesError;
+
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+/// Notes live in `<repo>/.locus/notes/<session>/<name>.md`.
+pub const NOTES_DIR: &str = ".locus/notes";
+/// Namespace for calls made outside an agent session.
+pub const DEFAULT_SESSION: &str = "default";
+/// Largest note kept, so a runaway append cannot fill the disk.
+const MAX_NOTE_BYTES: usize = 256 * 1024;
+const MAX_NAME_CHARS: usize = 64;
+const MAX_PREVIEW_CHARS: usize = 80;
+
+pub struct Notes {
+    repo_root: PathBuf,
+}
+
+impl Notes {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+
+    /// `name` without a trailing `.md`, checked to be a plain file name.
+    fn note_name(name: &str) -> Result<String, NotesError> {
+        let trimmed = name.trim();
+        let stem = trimmed.strip_suffix(".md").unwrap_or(trimmed);
+        if !is_safe_name(stem) {
+            return Err(NotesError::InvalidName(name.to_string()));
+        }
+        Ok(stem.to_string())
+    }
+
+    /// Directory for `session`, or the context's session, or [DEFAULT_SESSION].
+    fn session_dir(
+        &self,
+        session: Option<&str>,
+        ctx: &ToolContext,
+    ) -> Result<(String, PathBuf), NotesError> {
+        let session = session
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .or(ctx.session_id.as_deref())
+            .unwrap_or(DEFAULT_SESSION);
+        if !is_safe_name(session) {
+            return Err(NotesError::InvalidSession(session.to_string()));
+        }
+        let dir = self.repo_root.join(NOTES_DIR).join(session);
+        Ok((session.to_string(), dir))
+    }
+
+    async fn save(
+        dir: &Path,
+        name: &str,
+        content: &str,
+        append: bool,
+    ) -> Result<usize, NotesError> {
+        let path = dir.join(format!("{}.md", name));
+        let mut text = if append {
+            match tokio::fs::read_to_string(&path).await {
+                Ok(existing) => existing,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            String::new()
+        };
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(content);
+        if text.len() > MAX_NOTE_BYTES {
+            return Err(NotesError::TooLarge {
+                name: name.to_string(),
+                limit: MAX_NOTE_BYTES,
+            });
+        }
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&path, &text).await?;
+        Ok(text.len())
+    }
+
+    async fn list(dir: &Path) -> Result<Vec<NoteSummary>, NotesError> {
+        let mut entries = match tokio::fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut notes = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".md"))
+            else {
+                continue;
+            };
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let text = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            let preview = text
+                .lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .unwrap_or("")
+                .chars()
+                .take(MAX_PREVIEW_CHARS)
+                .collect();
+            notes.push(NoteSummary {
+                name: name.to_string(),
+                bytes: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs()),
+                preview,
+            });
+        }
+        notes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(notes)
+    }
+
+    async fn run(&self, args: NotesArgs, ctx: &ToolContext) -> Result<JsonValue, NotesError> {
+        let (session, dir) = self.session_dir(args.session.as_deref(), ctx)?;
+        let name = || {
+            let name = args.name.as_deref().ok_or_else(|| {
+                NotesError::MissingField("name required for write, append and read".to_string())
+            })?;
+            Self::note_name(name)
+        };
+        match args.action {
+            NotesAction::Write | NotesAction::Append => {
+                let name = name()?;
+                let content = args.content.as_deref().ok_or_else(|| {
+                    NotesError::MissingField("content required for write and append".to_string())
+                })?;
+                let bytes =
+                    Self::save(&dir, &name, content, args.action == NotesAction::Append).await?;
+                Ok(json!({
+                    "session": session,
+                    "name": name,
+                    "path": format!("{}/{}/{}.md", NOTES_DIR, session, name),
+                    "bytes": bytes,
+                }))
+            }
+            NotesAction::Read => {
+                let name = name()?;
+                let content =
+                    match tokio::fs::read_to_string(dir.join(format!("{}.md", name))).await {
+                        Ok(content) => content,
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                            return Err(NotesError::NoteNotFound(name));
+                        }
+                        Err(e) => return Err(e.into()),
+                    };
+                Ok(json!({
+                    "session": session,
+                    "name": name,
+                    "bytes": content.len(),
+                    "content": content,
+                }))
+            }
+            NotesAction::List => Ok(json!({
+                "session": session,
+                "notes": Self::list(&dir).await?,
+            })),
+        }
+    }
+}
+
+/// A plain file name: `[A-Za-z0-9._-]`, not starting with `.`.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for Notes {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: NotesArgs = serde_json::from_value(args)?;
+        Ok(self.run(args, &ctx).await?)
+    }
+}
@@ -4,6 +4,7 @@ mod error;
 pub use args::ReadArgs;
 pub use error::ReadError;
 
+use crate::protected_paths::ProtectedPathMatcher;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
@@ -13,6 +14,7 @@ use tokio::fs;
 
 pub struct Read {
     repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
 }
 
 fn normalize_path(path: &Path) -> PathBuf {
@@ -37,7 +39,16 @@ fn is_within_directory(path: &Path, dir: &Path) -> bool {
 
 impl Read {
     pub fn new(repo_root: PathBuf) -> Self {
-        Self { repo_root }
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse paths matching `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
     }
 
     fn validate_path(&self, path: &str) -> Result<PathBuf, ReadError> {
@@ -85,9 +96,15 @@ impl Tool for Read {
         schema().2.clone()
     }
 
+    fn cacheable(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let tool_args: ReadArgs = serde_json::from_value(args)?;
         let full_path = self.validate_path(&tool_args.path)?;
+        self.protected
+            .check(&self.repo_root, &full_path, &tool_args.path)?;
 
         let metadata = fs::metadata(&full_path)
             .await
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RepoStatsArgs {
+    /// Directory to count (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Directory levels below `path` that lines are grouped by (1 = top-level directories)
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// Number of largest files returned
+    #[serde(default = "default_largest")]
+    pub largest: usize,
+}
+
+fn default_depth() -> usize {
+    1
+}
+
+fn default_largest() -> usize {
+    10
+}
+
+impl RepoStatsArgs {
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            depth: default_depth(),
+            largest: default_largest(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl Default for RepoStatsArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lines of one file, split like tokei: code, comment-only and blank lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCounts {
+    pub code: usize,
+    pub comment: usize,
+    pub blank: usize,
+}
+
+impl LineCounts {
+    pub fn total(&self) -> usize {
+        self.code + self.comment + self.blank
+    }
+
+    pub fn add(&mut self, other: LineCounts) {
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+/// Files and lines of one language.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: usize,
+    #[serde(flatten)]
+    pub lines: LineCounts,
+}
+
+/// Files and lines under one directory (`.` for files directly in `path`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryStats {
+    pub path: String,
+    pub files: usize,
+    #[serde(flatten)]
+    pub lines: LineCounts,
+}
+
+/// One of the largest source files, by lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStats {
+    pub path: String,
+    pub language: String,
+    pub lines: usize,
+    pub code: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoStatsResult {
+    /// Directory counted, relative to repo root.
+    pub path: String,
+    /// Every file walked, including ones in no known language.
+    pub files: usize,
+    /// Files in a known language, whose lines are counted.
+    pub source_files: usize,
+    pub bytes: u64,
+    /// Lines of all source files.
+    pub lines: LineCounts,
+    /// Most code first.
+    pub languages: Vec<LanguageStats>,
+    /// Most code first.
+    pub directories: Vec<DirectoryStats>,
+    pub largest_files: Vec<FileStats>,
+    /// Source files too large to count (bundles, generated code); in `files` only.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+impl RepoStatsResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
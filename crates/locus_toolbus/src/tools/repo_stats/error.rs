@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RepoStatsError {
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl From<ignore::Error> for RepoStatsError {
+    fn from(err: ignore::Error) -> Self {
+        RepoStatsError::Walk(err.to_string())
+    }
+}
@@ -0,0 +1,156 @@
+//! Languages recognized by repo_stats, and tokei-style line counting.
+
+use super::args::LineCounts;
+
+/// One language: how its files are named and how its comments look.
+pub(crate) struct Language {
+    pub name: &'static str,
+    extensions: &'static [&'static str],
+    filenames: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+}
+
+const C_BLOCK: Option<(&str, &str)> = Some(("/*", "*/"));
+const HTML_BLOCK: Option<(&str, &str)> = Some(("<!--", "-->"));
+
+const fn lang(
+    name: &'static str,
+    extensions: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+) -> Language {
+    Language {
+        name,
+        extensions,
+        filenames: &[],
+        line_comments,
+        block_comment,
+    }
+}
+
+const LANGUAGES: &[Language] = &[
+    lang("Rust", &["rs"], &["//"], C_BLOCK),
+    lang("Python", &["py", "pyi", "pyw"], &["#"], None),
+    lang("JavaScript", &["js", "mjs", "cjs", "jsx"], &["//"], C_BLOCK),
+    lang("TypeScript", &["ts", "mts", "cts", "tsx"], &["//"], C_BLOCK),
+    lang("Go", &["go"], &["//"], C_BLOCK),
+    lang("C", &["c", "h"], &["//"], C_BLOCK),
+    lang(
+        "C++",
+        &["cc", "cpp", "cxx", "hh", "hpp", "hxx"],
+        &["//"],
+        C_BLOCK,
+    ),
+    lang("C#", &["cs"], &["//"], C_BLOCK),
+    lang("Java", &["java"], &["//"], C_BLOCK),
+    lang("Kotlin", &["kt", "kts"], &["//"], C_BLOCK),
+    lang("Scala", &["scala", "sc"], &["//"], C_BLOCK),
+    lang("Swift", &["swift"], &["//"], C_BLOCK),
+    lang("Dart", &["dart"], &["//"], C_BLOCK),
+    lang("Zig", &["zig"], &["//"], None),
+    lang("Ruby", &["rb", "rake"], &["#"], Some(("=begin", "=end"))),
+    lang("PHP", &["php"], &["//", "#"], C_BLOCK),
+    lang("Elixir", &["ex", "exs"], &["#"], None),
+    lang("Haskell", &["hs"], &["--"], Some(("{-", "-}"))),
+    lang("Lua", &["lua"], &["--"], Some(("--[[", "]]"))),
+    lang("Shell", &["sh", "bash", "zsh", "fish"], &["#"], None),
+    lang("SQL", &["sql"], &["--"], C_BLOCK),
+    lang("Protobuf", &["proto"], &["//"], C_BLOCK),
+    lang("GraphQL", &["graphql", "gql"], &["#"], None),
+    lang("Nix", &["nix"], &["#"], C_BLOCK),
+    lang("HTML", &["html", "htm"], &[], HTML_BLOCK),
+    lang("Vue", &["vue"], &["//"], HTML_BLOCK),
+    lang("Svelte", &["svelte"], &["//"], HTML_BLOCK),
+    lang("CSS", &["css"], &[], C_BLOCK),
+    lang("SCSS", &["scss", "sass", "less"], &["//"], C_BLOCK),
+    lang("Markdown", &["md", "markdown", "mdx"], &[], HTML_BLOCK),
+    lang("TOML", &["toml"], &["#"], None),
+    lang("YAML", &["yml", "yaml"], &["#"], None),
+    lang("JSON", &["json", "jsonc"], &[], None),
+    Language {
+        name: "Dockerfile",
+        extensions: &["dockerfile"],
+        filenames: &["Dockerfile", "Containerfile"],
+        line_comments: &["#"],
+        block_comment: None,
+    },
+    Language {
+        name: "Makefile",
+        extensions: &["mk"],
+        filenames: &["Makefile", "makefile", "GNUmakefile"],
+        line_comments: &["#"],
+        block_comment: None,
+    },
+];
+
+/// Lockfiles are generated; counting them would bury the code.
+const LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "pnpm-lock.yaml",
+    "composer.lock",
+];
+
+/// Language of a file by its name, or None for unknown and lock files.
+pub(crate) fn language_for(file_name: &str) -> Option<&'static Language> {
+    if LOCKFILES.contains(&file_name) {
+        return None;
+    }
+    if let Some(language) = LANGUAGES.iter().find(|l| l.filenames.contains(&file_name)) {
+        return Some(language);
+    }
+    let (_, ext) = file_name.rsplit_once('.')?;
+    let ext = ext.to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|l| l.extensions.contains(&ext.as_str()))
+}
+
+/// Count code, comment and blank lines. A line is a comment when it only holds
+/// a comment (or sits inside a block comment); comment markers inside strings
+/// are not told apart, as in tokei's fast path.
+pub(crate) fn count_lines(language: &Language, content: &str) -> LineCounts {
+    let mut counts = LineCounts::default();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if in_block {
+            counts.comment += 1;
+            if let Some((_, end)) = language.block_comment
+                && line.contains(end)
+            {
+                in_block = false;
+            }
+            continue;
+        }
+        if line.is_empty() {
+            counts.blank += 1;
+            continue;
+        }
+        // Before line comments: Lua's `--[[` also starts with `--`.
+        if let Some((start, end)) = language.block_comment
+            && let Some(rest) = line.strip_prefix(start)
+        {
+            counts.comment += 1;
+            in_block = !rest.contains(end);
+            continue;
+        }
+        if language.line_comments.iter().any(|c| line.starts_with(c)) {
+            counts.comment += 1;
+            continue;
+        }
+        counts.code += 1;
+        // Code that opens a block comment it does not close (ahead of any line comment).
+        if let Some((start, end)) = language.block_comment
+            && let Some(idx) = line.find(start)
+            && !language
+                .line_comments
+                .iter()
+                .any(|c| line[..idx].contains(c))
+        {
+            in_block = !line[idx + start.len()..].contains(end);
+        }
+    }
+    counts
+}
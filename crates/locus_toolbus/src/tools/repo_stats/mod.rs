@@ -0,0 +1,199 @@
+mod args;
+mod error;
+pub(crate) mod languages;
+
+pub use args::{
+    DirectoryStats, FileStats, LanguageStats, LineCounts, RepoStatsArgs, RepoStatsResult,
+};
+pub use error::RepoStatsError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_dir};
+use async_trait::async_trait;
+use languages::{count_lines, language_for};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Files larger than this are not line-counted (bundles, generated code).
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Directories returned, the ones with the most code.
+const MAX_DIRECTORIES: usize = 50;
+
+pub struct RepoStats {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+impl RepoStats {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path` by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Walk `start` (honouring .gitignore) and count every file. Blocking; call
+    /// from `spawn_blocking`.
+    pub fn collect(
+        repo_root: &Path,
+        start: &Path,
+        args: &RepoStatsArgs,
+    ) -> Result<RepoStatsResult, RepoStatsError> {
+        let walker = ignore::WalkBuilder::new(start)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        let relative = |path: &Path| {
+            let rel = path.strip_prefix(repo_root).unwrap_or(path);
+            match rel.to_string_lossy().as_ref() {
+                "" => ".".to_string(),
+                rel => rel.to_string(),
+            }
+        };
+        let start_rel = relative(start);
+
+        let mut result = RepoStatsResult {
+            path: start_rel.clone(),
+            files: 0,
+            source_files: 0,
+            bytes: 0,
+            lines: LineCounts::default(),
+            languages: Vec::new(),
+            directories: Vec::new(),
+            largest_files: Vec::new(),
+            skipped: Vec::new(),
+        };
+        let mut languages: HashMap<&'static str, LanguageStats> = HashMap::new();
+        let mut directories: HashMap<String, DirectoryStats> = HashMap::new();
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            result.files += 1;
+            result.bytes += bytes;
+            let Some(language) = language_for(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            let path = relative(entry.path());
+            if bytes > MAX_FILE_BYTES {
+                result.skipped.push(path);
+                continue;
+            }
+            // Binary and non-UTF-8 files are not counted.
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            let lines = count_lines(language, &content);
+            result.source_files += 1;
+            result.lines.add(lines);
+
+            let stats = languages
+                .entry(language.name)
+                .or_insert_with(|| LanguageStats {
+                    language: language.name.to_string(),
+                    files: 0,
+                    lines: LineCounts::default(),
+                });
+            stats.files += 1;
+            stats.lines.add(lines);
+
+            let dir = directory_of(start, entry.path(), args.depth.max(1), &start_rel);
+            let stats = directories
+                .entry(dir.clone())
+                .or_insert_with(|| DirectoryStats {
+                    path: dir,
+                    files: 0,
+                    lines: LineCounts::default(),
+                });
+            stats.files += 1;
+            stats.lines.add(lines);
+
+            files.push(FileStats {
+                path,
+                language: language.name.to_string(),
+                lines: lines.total(),
+                code: lines.code,
+                bytes,
+            });
+        }
+
+        result.languages = languages.into_values().collect();
+        result
+            .languages
+            .sort_by(|a, b| (b.lines.code, &a.language).cmp(&(a.lines.code, &b.language)));
+        result.directories = directories.into_values().collect();
+        result
+            .directories
+            .sort_by(|a, b| (b.lines.code, &a.path).cmp(&(a.lines.code, &b.path)));
+        result.directories.truncate(MAX_DIRECTORIES);
+        files.sort_by(|a, b| (b.lines, &a.path).cmp(&(a.lines, &b.path)));
+        files.truncate(args.largest);
+        result.largest_files = files;
+        Ok(result)
+    }
+}
+
+/// Directory `file` is grouped under: its first `depth` directories below
+/// `start`, as a repo-relative path; `start_rel` for files directly in `start`.
+fn directory_of(start: &Path, file: &Path, depth: usize, start_rel: &str) -> String {
+    let rel = file.strip_prefix(start).unwrap_or(file);
+    let dirs: Vec<_> = rel
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.components())
+        .take(depth)
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    match (start_rel, dirs.is_empty()) {
+        (_, true) => start_rel.to_string(),
+        (".", false) => dirs.join("/"),
+        (_, false) => format!("{}/{}", start_rel, dirs.join("/")),
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for RepoStats {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: RepoStatsArgs = serde_json::from_value(args)?;
+        let start = resolve_dir(&self.repo_root, args.path.as_deref(), &self.protected)?;
+        let repo_root = self.repo_root.clone();
+        let result = tokio::task::spawn_blocking(move || Self::collect(&repo_root, &start, &args))
+            .await
+            .map_err(|e| anyhow::anyhow!("repo_stats walk task failed: {}", e))??;
+        Ok(result.to_json())
+    }
+}
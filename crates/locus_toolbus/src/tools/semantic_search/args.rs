@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SemanticSearchArgs {
+    /// What the code does, in plain words
+    pub query: String,
+
+    /// Directory or file to search (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Maximum number of results
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    10
+}
+
+impl SemanticSearchArgs {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            path: None,
+            max_results: default_max_results(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
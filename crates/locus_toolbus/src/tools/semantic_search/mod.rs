@@ -0,0 +1,96 @@
+mod args;
+
+pub use args::SemanticSearchArgs;
+
+use crate::semantic::{SemanticError, SemanticIndex};
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_start};
+use async_trait::async_trait;
+use serde_json::{Value as JsonValue, json};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+const MAX_RESULTS: usize = 50;
+const SNIPPET_LINES: usize = 12;
+
+pub struct SemanticSearch {
+    repo_root: PathBuf,
+    index: Arc<SemanticIndex>,
+}
+
+impl SemanticSearch {
+    /// Search with the embedder configured in the environment.
+    pub fn new(repo_root: PathBuf) -> Self {
+        let index = Arc::new(SemanticIndex::new(repo_root.clone()));
+        Self::with_index(repo_root, index)
+    }
+
+    pub fn with_index(repo_root: PathBuf, index: Arc<SemanticIndex>) -> Self {
+        Self { repo_root, index }
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+/// The first lines of a chunk, marked when cut.
+fn snippet(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() <= SNIPPET_LINES {
+        return text.to_string();
+    }
+    format!("{}\n…", lines[..SNIPPET_LINES].join("\n"))
+}
+
+#[async_trait]
+impl Tool for SemanticSearch {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let args: SemanticSearchArgs = serde_json::from_value(args)?;
+        let query = args.query.trim();
+        if query.is_empty() {
+            return Err(SemanticError::InvalidArgs("query must not be empty".to_string()).into());
+        }
+        let path = args.path.as_deref().unwrap_or("").trim_matches('/');
+        resolve_start(&self.repo_root, Some(path), self.index.protected_paths())?;
+        let path = if path == "." { "" } else { path };
+
+        let stats = self.index.refresh().await?;
+        let hits = self
+            .index
+            .search(query, args.max_results.clamp(1, MAX_RESULTS), path)
+            .await?;
+        let results: Vec<JsonValue> = hits
+            .iter()
+            .map(|hit| {
+                json!({
+                    "path": hit.path,
+                    "start_line": hit.start_line,
+                    "end_line": hit.end_line,
+                    "symbol": hit.symbol,
+                    "score": (hit.score * 1000.0).round() / 1000.0,
+                    "snippet": snippet(&hit.text),
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "query": query,
+            "results": results,
+            "index": stats,
+            "embedder": self.index.embedder_id(),
+        }))
+    }
+}
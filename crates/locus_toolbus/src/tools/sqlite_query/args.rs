@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SqliteQueryArgs {
+    /// Database file, relative to repo root (e.g. `.locus/locus.db`)
+    pub path: String,
+
+    /// One read-only statement: SELECT, WITH, EXPLAIN or a read PRAGMA
+    pub query: String,
+
+    /// Values bound to `?` placeholders, in order
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
+
+    /// Maximum number of rows returned
+    #[serde(default = "default_max_rows")]
+    pub max_rows: usize,
+
+    /// Maximum size of the returned rows, in bytes of JSON
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+}
+
+fn default_max_rows() -> usize {
+    100
+}
+
+fn default_max_bytes() -> usize {
+    32 * 1024
+}
+
+impl SqliteQueryArgs {
+    pub fn new(path: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            query: query.into(),
+            params: Vec::new(),
+            max_rows: default_max_rows(),
+            max_bytes: default_max_bytes(),
+        }
+    }
+
+    pub fn with_params(mut self, params: Vec<serde_json::Value>) -> Self {
+        self.params = params;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqliteQueryResult {
+    pub path: String,
+    pub columns: Vec<String>,
+    /// One array per row, values in `columns` order.
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// True when the query had more rows than were returned.
+    pub truncated: bool,
+}
+
+impl SqliteQueryResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
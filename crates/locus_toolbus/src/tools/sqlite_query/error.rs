@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SqliteQueryError {
+    #[error("Not a SQLite database: {0}")]
+    NotADatabase(String),
+
+    #[error("Query is empty")]
+    EmptyQuery,
+
+    #[error("Give one statement per query")]
+    MultipleStatements,
+
+    #[error("Only read-only statements (SELECT, WITH, EXPLAIN, read PRAGMAs) can run")]
+    NotReadOnly,
+
+    #[error("Query did not finish within {0}s")]
+    Timeout(u64),
+
+    #[error("SQLite error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
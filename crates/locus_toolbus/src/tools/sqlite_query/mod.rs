@@ -0,0 +1,212 @@
+mod args;
+mod error;
+
+pub use args::{SqliteQueryArgs, SqliteQueryResult};
+pub use error::SqliteQueryError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema, resolve_file};
+use async_trait::async_trait;
+use rusqlite::limits::Limit;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{Batch, Connection, ErrorCode, OpenFlags, params_from_iter};
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Queries still running after this are interrupted.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long to wait on a database another process is writing (e.g. locus.db).
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest text value returned; longer values end in `…`.
+const MAX_TEXT_CHARS: usize = 2000;
+/// Leading bytes of a blob shown as hex.
+const BLOB_PREFIX_BYTES: usize = 32;
+
+pub struct SqliteQuery {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+impl SqliteQuery {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path` by `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Open `path` read-only, with ATTACH disabled so a query cannot reach
+    /// databases outside the repo.
+    fn open(path: &Path) -> Result<Connection, SqliteQueryError> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0);
+        Ok(conn)
+    }
+
+    /// Prepare and run the single statement in `args.query`. Blocking; call
+    /// from `spawn_blocking`.
+    fn run(
+        conn: &Connection,
+        args: &SqliteQueryArgs,
+    ) -> Result<SqliteQueryResult, SqliteQueryError> {
+        let mut batch = Batch::new(conn, &args.query);
+        let mut stmt = batch.next()?.ok_or(SqliteQueryError::EmptyQuery)?;
+        if batch.next()?.is_some() {
+            return Err(SqliteQueryError::MultipleStatements);
+        }
+        if !stmt.readonly() {
+            return Err(SqliteQueryError::NotReadOnly);
+        }
+
+        let columns: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let mut rows = stmt.query(params_from_iter(args.params.iter().map(json_to_sql)))?;
+        let mut result = SqliteQueryResult {
+            path: args.path.clone(),
+            columns,
+            rows: Vec::new(),
+            truncated: false,
+        };
+        let mut bytes = 0;
+        while let Some(row) = rows.next()? {
+            if result.rows.len() >= args.max_rows {
+                result.truncated = true;
+                break;
+            }
+            let values = (0..result.columns.len())
+                .map(|i| row.get_ref(i).map(sql_to_json))
+                .collect::<Result<Vec<_>, _>>()?;
+            bytes += JsonValue::from(values.clone()).to_string().len();
+            if bytes > args.max_bytes {
+                result.truncated = true;
+                break;
+            }
+            result.rows.push(values);
+        }
+        Ok(result)
+    }
+}
+
+/// SQLite's "file is not a database" as [SqliteQueryError::NotADatabase].
+fn not_a_database(error: SqliteQueryError, path: &str) -> SqliteQueryError {
+    match error {
+        SqliteQueryError::Database(rusqlite::Error::SqliteFailure(err, _))
+            if err.code == ErrorCode::NotADatabase =>
+        {
+            SqliteQueryError::NotADatabase(path.to_string())
+        }
+        error => error,
+    }
+}
+
+/// A JSON parameter as a SQLite value: booleans as 0/1, arrays and objects as
+/// JSON text (for SQLite's json functions).
+fn json_to_sql(value: &JsonValue) -> SqlValue {
+    match value {
+        JsonValue::Null => SqlValue::Null,
+        JsonValue::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        JsonValue::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// A column value as JSON. Text is cut at [MAX_TEXT_CHARS]; blobs become
+/// `x'<hex prefix>…' (N bytes)`.
+fn sql_to_json(value: ValueRef<'_>) -> JsonValue {
+    match value {
+        ValueRef::Null => JsonValue::Null,
+        ValueRef::Integer(i) => JsonValue::from(i),
+        ValueRef::Real(f) => {
+            serde_json::Number::from_f64(f).map_or(JsonValue::Null, JsonValue::Number)
+        }
+        ValueRef::Text(bytes) => {
+            let text = String::from_utf8_lossy(bytes);
+            let mut cut: String = text.chars().take(MAX_TEXT_CHARS).collect();
+            if cut.len() < text.len() {
+                cut.push('…');
+            }
+            JsonValue::String(cut)
+        }
+        ValueRef::Blob(bytes) => {
+            let hex: String = bytes
+                .iter()
+                .take(BLOB_PREFIX_BYTES)
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let more = if bytes.len() > BLOB_PREFIX_BYTES {
+                "…"
+            } else {
+                ""
+            };
+            JsonValue::String(format!("x'{}{}' ({} bytes)", hex, more, bytes.len()))
+        }
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for SqliteQuery {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        self.execute_with_context(args, ToolContext::new()).await
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let args: SqliteQueryArgs = serde_json::from_value(args)?;
+        let path = resolve_file(&self.repo_root, &args.path, &self.protected)?;
+        let conn = Self::open(&path)?;
+        let interrupt = conn.get_interrupt_handle();
+        let task = tokio::task::spawn_blocking(move || {
+            Self::run(&conn, &args).map_err(|e| not_a_database(e, &args.path))
+        });
+        // Interrupting makes the running statement fail and frees the task.
+        tokio::select! {
+            result = task => {
+                let result = result.map_err(|e| anyhow::anyhow!("sqlite_query task failed: {}", e))?;
+                Ok(result?.to_json())
+            }
+            _ = ctx.cancel.cancelled() => {
+                interrupt.interrupt();
+                Err(anyhow::anyhow!("SQLite query cancelled"))
+            }
+            _ = tokio::time::sleep(QUERY_TIMEOUT) => {
+                interrupt.interrupt();
+                Err(SqliteQueryError::Timeout(QUERY_TIMEOUT.as_secs()).into())
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Tree-sitter grammars shared by the syntax-aware tools (`code_nav`, `ast_search`).
+//!
+//! The grammars are behind the `syntax` feature; without it no language has one,
+//! so callers fall back to plain text.
+
+use tree_sitter::Language;
+
+/// Languages with a bundled grammar, by the name tools accept in their arguments.
+#[cfg(feature = "syntax")]
+pub const LANGUAGES: &[&str] = &["rust", "python", "javascript", "typescript", "tsx", "go"];
+#[cfg(not(feature = "syntax"))]
+pub const LANGUAGES: &[&str] = &[];
+
+/// Language name for a file path, by extension.
+pub fn language_name(path: &str) -> Option<&'static str> {
+    let ext = path.rsplit_once('.')?.1;
+    let name = match ext {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Grammar for a language name from [`LANGUAGES`].
+#[cfg(feature = "syntax")]
+pub fn grammar(name: &str) -> Option<Language> {
+    let language = match name {
+        "rust" => tree_sitter_rust::LANGUAGE,
+        "python" => tree_sitter_python::LANGUAGE,
+        "javascript" => tree_sitter_javascript::LANGUAGE,
+        "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+        "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+        "go" => tree_sitter_go::LANGUAGE,
+        _ => return None,
+    };
+    Some(language.into())
+}
+
+/// Grammar for a language name from [`LANGUAGES`]; none in this build.
+#[cfg(not(feature = "syntax"))]
+pub fn grammar(_name: &str) -> Option<Language> {
+    None
+}
+
+/// Grammar for a file path, by extension. `None` for languages without a bundled grammar.
+pub fn language_for(path: &str) -> Option<Language> {
+    grammar(language_name(path)?)
+}
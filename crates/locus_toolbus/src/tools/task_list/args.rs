@@ -1,7 +1,7 @@
 use serde::Deserialize;
 
 // Re-export task types from core (single source of truth for storage and API).
-pub use locus_core::db::{TaskItem, TaskStatus};
+pub use locus_core::db::{AcceptanceCriterion, TaskItem, TaskStatus};
 
 /// Action to perform on the task list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, serde::Serialize)]
@@ -14,6 +14,8 @@ pub enum TaskListAction {
     Add,
     Remove,
     Reorder,
+    /// Check off one acceptance criterion with evidence.
+    Check,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +49,18 @@ pub struct TaskListArgs {
     /// For reorder: ordered list of task_ids.
     #[serde(default)]
     pub order: Vec<String>,
+
+    /// For update: replaces the task's acceptance criteria.
+    #[serde(default)]
+    pub criteria: Option<Vec<AcceptanceCriterion>>,
+
+    /// For check: criterion id within the task.
+    #[serde(default)]
+    pub criterion_id: Option<String>,
+
+    /// For check: evidence links (tool call ids, diffs, test runs).
+    #[serde(default)]
+    pub evidence: Vec<String>,
 }
 
 fn default_plan_id() -> String {
@@ -14,6 +14,12 @@ pub enum TaskListError {
     #[error("Invalid order: {0}")]
     InvalidOrder(String),
 
+    #[error("Criterion not found: {0}")]
+    CriterionNotFound(String),
+
+    #[error("Cannot mark task {task_id} done in strict mode: {reason}")]
+    CriteriaUnmet { task_id: String, reason: String },
+
     #[error("Database error: {0}")]
     Db(#[from] anyhow::Error),
 }
@@ -1,9 +1,10 @@
 mod args;
 mod error;
 
-pub use args::{TaskItem, TaskListAction, TaskListArgs, TaskStatus};
+pub use args::{AcceptanceCriterion, TaskItem, TaskListAction, TaskListArgs, TaskStatus};
 pub use error::TaskListError;
 
+use crate::permission::Permission;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use locus_core::db;
@@ -11,13 +12,33 @@ use serde_json::Value as JsonValue;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
+/// Env var that turns on strict mode for the default tool bus.
+pub const STRICT_TASKS_ENV: &str = "LOCUS_STRICT_TASKS";
+
 pub struct TaskList {
     repo_root: PathBuf,
+    /// When set, a task can only be marked done once it has acceptance criteria and all are met.
+    strict: bool,
 }
 
 impl TaskList {
     pub fn new(repo_root: PathBuf) -> Self {
-        Self { repo_root }
+        Self {
+            repo_root,
+            strict: false,
+        }
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Strict mode from [STRICT_TASKS_ENV] (`1` or `true`).
+    pub fn strict_from_env() -> bool {
+        std::env::var(STRICT_TASKS_ENV)
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true"))
+            .unwrap_or(false)
     }
 
     async fn run_create(&self, plan_id: &str, tasks: Vec<TaskItem>) -> ToolResult {
@@ -58,12 +79,28 @@ impl TaskList {
         status: Option<TaskStatus>,
         title: Option<String>,
         description: Option<String>,
+        criteria: Option<Vec<AcceptanceCriterion>>,
     ) -> Result<JsonValue, TaskListError> {
+        if self.strict && status == Some(TaskStatus::Done) {
+            let criteria = match criteria.clone() {
+                Some(c) => c,
+                None => self.load_task(plan_id, task_id).await?.criteria,
+            };
+            check_done_allowed(task_id, &criteria)?;
+        }
         let repo = self.repo_root.clone();
         let plan_id = plan_id.to_string();
         let task_id_clone = task_id.to_string();
         let out = tokio::task::spawn_blocking(move || {
-            db::update(&repo, &plan_id, &task_id_clone, status, title, description)
+            db::update(
+                &repo,
+                &plan_id,
+                &task_id_clone,
+                status,
+                title,
+                description,
+                criteria,
+            )
         })
         .await
         .map_err(|e| TaskListError::MissingField(format!("spawn_blocking: {}", e)))??;
@@ -71,6 +108,38 @@ impl TaskList {
         serde_json::to_value(task).map_err(|e| TaskListError::MissingField(e.to_string()))
     }
 
+    async fn load_task(&self, plan_id: &str, task_id: &str) -> Result<TaskItem, TaskListError> {
+        let repo = self.repo_root.clone();
+        let plan_id = plan_id.to_string();
+        let task_id_clone = task_id.to_string();
+        let out = tokio::task::spawn_blocking(move || db::get(&repo, &plan_id, &task_id_clone))
+            .await
+            .map_err(|e| TaskListError::MissingField(format!("spawn_blocking: {}", e)))??;
+        out.ok_or_else(|| TaskListError::TaskNotFound(task_id.to_string()))
+    }
+
+    async fn run_check(
+        &self,
+        plan_id: &str,
+        task_id: &str,
+        criterion_id: &str,
+        evidence: Vec<String>,
+    ) -> Result<JsonValue, TaskListError> {
+        let mut criteria = self.load_task(plan_id, task_id).await?.criteria;
+        let criterion = criteria
+            .iter_mut()
+            .find(|c| c.id.as_deref() == Some(criterion_id))
+            .ok_or_else(|| TaskListError::CriterionNotFound(criterion_id.to_string()))?;
+        criterion.met = true;
+        for link in evidence {
+            if !criterion.evidence.contains(&link) {
+                criterion.evidence.push(link);
+            }
+        }
+        self.run_update(plan_id, task_id, None, None, None, Some(criteria))
+            .await
+    }
+
     async fn run_add(&self, plan_id: &str, new_tasks: Vec<TaskItem>) -> ToolResult {
         let repo = self.repo_root.clone();
         let plan_id = plan_id.to_string();
@@ -110,6 +179,29 @@ impl TaskList {
     }
 }
 
+/// Strict mode: done requires at least one criterion, all of them met.
+fn check_done_allowed(
+    task_id: &str,
+    criteria: &[AcceptanceCriterion],
+) -> Result<(), TaskListError> {
+    let unmet: Vec<&str> = criteria
+        .iter()
+        .filter(|c| !c.met)
+        .map(|c| c.id.as_deref().unwrap_or(c.text.as_str()))
+        .collect();
+    let reason = if criteria.is_empty() {
+        "no acceptance criteria defined".to_string()
+    } else if !unmet.is_empty() {
+        format!("unmet acceptance criteria: {}", unmet.join(", "))
+    } else {
+        return Ok(());
+    };
+    Err(TaskListError::CriteriaUnmet {
+        task_id: task_id.to_string(),
+        reason,
+    })
+}
+
 impl Default for TaskList {
     fn default() -> Self {
         Self::new(PathBuf::from("."))
@@ -135,6 +227,10 @@ impl Tool for TaskList {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let a: TaskListArgs = serde_json::from_value(args)?;
         let plan_id = &a.plan_id;
@@ -152,9 +248,16 @@ impl Tool for TaskList {
                 let task_id = a.task_id.as_deref().ok_or_else(|| {
                     TaskListError::MissingField("task_id required for update".to_string())
                 })?;
-                self.run_update(plan_id, task_id, a.status, a.title, a.description)
-                    .await
-                    .map_err(Into::into)
+                self.run_update(
+                    plan_id,
+                    task_id,
+                    a.status,
+                    a.title,
+                    a.description,
+                    a.criteria,
+                )
+                .await
+                .map_err(Into::into)
             }
             TaskListAction::Add => Ok(self.run_add(plan_id, a.tasks).await?),
             TaskListAction::Remove => {
@@ -164,6 +267,28 @@ impl Tool for TaskList {
                 self.run_remove(plan_id, task_id).await.map_err(Into::into)
             }
             TaskListAction::Reorder => self.run_reorder(plan_id, a.order).await.map_err(Into::into),
+            TaskListAction::Check => {
+                let task_id = a.task_id.as_deref().ok_or_else(|| {
+                    TaskListError::MissingField("task_id required for check".to_string())
+                })?;
+                let criterion_id = a.criterion_id.as_deref().ok_or_else(|| {
+                    TaskListError::MissingField("criterion_id required for check".to_string())
+                })?;
+                if a.evidence.iter().all(|e| e.trim().is_empty()) {
+                    return Err(TaskListError::MissingField(
+                        "evidence required for check".to_string(),
+                    )
+                    .into());
+                }
+                let evidence = a
+                    .evidence
+                    .into_iter()
+                    .filter(|e| !e.trim().is_empty())
+                    .collect();
+                self.run_check(plan_id, task_id, criterion_id, evidence)
+                    .await
+                    .map_err(Into::into)
+            }
         }
     }
 }
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TodoScanArgs {
+    /// Directory to scan (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Comment tags to collect
+    #[serde(default = "default_tags")]
+    pub tags: Vec<String>,
+
+    /// Look up author and age of each comment with `git blame`
+    #[serde(default = "default_blame")]
+    pub blame: bool,
+
+    /// Order of the returned items
+    #[serde(default)]
+    pub sort: TodoSort,
+
+    /// Maximum number of items returned
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+
+    /// When set, add the returned items as pending tasks to this task_list plan
+    #[serde(default)]
+    pub plan_id: Option<String>,
+}
+
+fn default_tags() -> Vec<String> {
+    vec!["TODO".to_string(), "FIXME".to_string(), "HACK".to_string()]
+}
+
+fn default_blame() -> bool {
+    true
+}
+
+fn default_max_results() -> usize {
+    200
+}
+
+impl TodoScanArgs {
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            tags: default_tags(),
+            blame: true,
+            sort: TodoSort::default(),
+            max_results: default_max_results(),
+            plan_id: None,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_sort(mut self, sort: TodoSort) -> Self {
+        self.sort = sort;
+        self
+    }
+}
+
+impl Default for TodoScanArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Order of scan results. `oldest` / `newest` use the blame time; items
+/// without one (uncommitted lines, no git) sort last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoSort {
+    #[default]
+    Path,
+    Oldest,
+    Newest,
+}
+
+/// One TODO/FIXME/HACK comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub path: String,
+    /// 1-based line number.
+    pub line: usize,
+    pub tag: String,
+    /// Comment text after the tag (owner in `TODO(name):` is kept in `owner`).
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Unix seconds of the commit that last touched the line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl TodoItem {
+    /// Task title used when feeding items into task_list.
+    pub fn task_title(&self) -> String {
+        let text: String = self.text.chars().take(80).collect();
+        format!("{} {}:{}: {}", self.tag, self.path, self.line, text)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoScanResult {
+    pub items: Vec<TodoItem>,
+    /// Matches found before `max_results` was applied.
+    pub total: usize,
+    pub truncated: bool,
+    pub by_tag: BTreeMap<String, usize>,
+    /// Whether blame info was attached (false outside a git repo).
+    pub blamed: bool,
+    /// Task ids added to `plan_id`, if one was given.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tasks_added: Vec<String>,
+}
+
+impl TodoScanResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TodoScanError {
+    #[error("No tags given")]
+    NoTags,
+
+    #[error("Walk error: {0}")]
+    Walk(String),
+
+    #[error("Failed to add tasks: {0}")]
+    TaskList(#[from] anyhow::Error),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl From<ignore::Error> for TodoScanError {
+    fn from(err: ignore::Error) -> Self {
+        TodoScanError::Walk(err.to_string())
+    }
+}
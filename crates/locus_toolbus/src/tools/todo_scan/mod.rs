@@ -0,0 +1,361 @@
+mod args;
+mod error;
+
+pub use args::{TodoItem, TodoScanArgs, TodoScanResult, TodoSort};
+pub use error::TodoScanError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_start};
+use async_trait::async_trait;
+use locus_core::db::{self, TaskItem, TaskStatus};
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::process::Command;
+
+/// Files larger than this are skipped (generated code, fixtures, bundles).
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+pub struct TodoScan {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+/// Blame info for one line.
+struct BlameLine {
+    commit: String,
+    author: String,
+    author_time: i64,
+}
+
+impl TodoScan {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path`, and leave protected files out of the walk, by
+    /// `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Matches a comment marker followed by one of `tags`, an optional `(owner)`,
+    /// an optional colon and the comment text.
+    fn tag_regex(tags: &[String]) -> Result<Regex, TodoScanError> {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .map(regex::escape)
+            .collect();
+        if tags.is_empty() {
+            return Err(TodoScanError::NoTags);
+        }
+        let pattern = format!(
+            r"(?://+!?|#+|/\*+|\*|--|;+|<!--)\s*\b({})\b(?:\(([^)]*)\))?:?\s*(.*)",
+            tags.join("|")
+        );
+        Ok(Regex::new(&pattern).expect("escaped tag regex is valid"))
+    }
+
+    /// Walk `start` and collect tagged comments. Blocking; call from `spawn_blocking`.
+    fn scan(
+        repo_root: &Path,
+        start: &Path,
+        re: &Regex,
+        protected: &ProtectedPathMatcher,
+    ) -> Result<Vec<TodoItem>, TodoScanError> {
+        let (root, protected) = (repo_root.to_path_buf(), protected.clone());
+        let walker = ignore::WalkBuilder::new(start)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(false)
+            .require_git(false)
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !protected.is_protected(&root, e.path())
+            })
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut items = Vec::new();
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+                continue;
+            }
+            // Binary and non-UTF-8 files are skipped.
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            let rel = entry
+                .path()
+                .strip_prefix(repo_root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string();
+
+            for (idx, line) in content.lines().enumerate() {
+                let Some(caps) = re.captures(line) else {
+                    continue;
+                };
+                let text = caps.get(3).map_or("", |m| m.as_str());
+                let text = text
+                    .trim_end()
+                    .trim_end_matches("-->")
+                    .trim_end_matches("*/")
+                    .trim_end();
+                items.push(TodoItem {
+                    path: rel.clone(),
+                    line: idx + 1,
+                    tag: caps[1].to_string(),
+                    text: text.to_string(),
+                    owner: caps
+                        .get(2)
+                        .map(|m| m.as_str().trim().to_string())
+                        .filter(|o| !o.is_empty()),
+                    author: None,
+                    author_time: None,
+                    age_days: None,
+                    commit: None,
+                });
+            }
+        }
+        Ok(items)
+    }
+
+    async fn is_git_repo(&self) -> bool {
+        Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(&self.repo_root)
+            .output()
+            .await
+            .is_ok_and(|out| out.status.success())
+    }
+
+    /// Run `git blame --line-porcelain` on `path` and map final line numbers to blame info.
+    async fn blame_file(&self, path: &str) -> Option<HashMap<usize, BlameLine>> {
+        let output = Command::new("git")
+            .args(["blame", "--line-porcelain", "--", path])
+            .current_dir(&self.repo_root)
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            tracing::debug!(
+                "git blame failed for {}: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return None;
+        }
+        Some(parse_line_porcelain(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Attach blame info to `items`, one `git blame` per file.
+    async fn attach_blame(&self, items: &mut [TodoItem]) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let mut blamed: HashMap<String, Option<HashMap<usize, BlameLine>>> = HashMap::new();
+        for item in items.iter_mut() {
+            if !blamed.contains_key(&item.path) {
+                let lines = self.blame_file(&item.path).await;
+                blamed.insert(item.path.clone(), lines);
+            }
+            let Some(Some(lines)) = blamed.get(&item.path) else {
+                continue;
+            };
+            let Some(blame) = lines.get(&item.line) else {
+                continue;
+            };
+            // Lines that are not committed yet have an all-zero commit id.
+            if blame.commit.bytes().all(|b| b == b'0') {
+                continue;
+            }
+            item.author = Some(blame.author.clone());
+            item.author_time = Some(blame.author_time);
+            item.age_days = Some((now - blame.author_time).max(0) as u64 / 86_400);
+            item.commit = Some(blame.commit.chars().take(12).collect());
+        }
+    }
+
+    /// Add `items` to `plan_id` as pending tasks, skipping ones already on the plan.
+    async fn add_tasks(
+        &self,
+        plan_id: &str,
+        items: &[TodoItem],
+    ) -> Result<Vec<String>, TodoScanError> {
+        let new_tasks: Vec<TaskItem> = items
+            .iter()
+            .map(|item| {
+                let mut origin = Vec::new();
+                if let Some(owner) = &item.owner {
+                    origin.push(format!("owner {}", owner));
+                }
+                if let Some(author) = &item.author {
+                    origin.push(format!("last touched by {}", author));
+                }
+                if let Some(days) = item.age_days {
+                    origin.push(format!("{} days ago", days));
+                }
+                TaskItem {
+                    id: None,
+                    title: item.task_title(),
+                    status: TaskStatus::Pending,
+                    description: (!origin.is_empty()).then(|| origin.join(", ")),
+                    criteria: Vec::new(),
+                }
+            })
+            .collect();
+
+        let repo = self.repo_root.clone();
+        let plan_id = plan_id.to_string();
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<String>> {
+            let existing = db::list(&repo, &plan_id)?;
+            let existing_titles: HashSet<&str> = existing["tasks"]
+                .as_array()
+                .map(|tasks| tasks.iter().filter_map(|t| t["title"].as_str()).collect())
+                .unwrap_or_default();
+            let new_tasks: Vec<TaskItem> = new_tasks
+                .into_iter()
+                .filter(|t| !existing_titles.contains(t.title.as_str()))
+                .collect();
+            if new_tasks.is_empty() {
+                return Ok(Vec::new());
+            }
+            let added = new_tasks.len();
+            let out = db::add(&repo, &plan_id, new_tasks)?;
+            let tasks = out["tasks"].as_array().cloned().unwrap_or_default();
+            Ok(tasks[tasks.len().saturating_sub(added)..]
+                .iter()
+                .filter_map(|t| t["id"].as_str().map(String::from))
+                .collect())
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("todo_scan spawn_blocking: {}", e))?
+        .map_err(TodoScanError::from)
+    }
+}
+
+/// Parse `git blame --line-porcelain` output: every line carries a full header
+/// (`<sha> <orig> <final> [<count>]`, `author ...`, `author-time ...`) followed by
+/// the tab-prefixed content line.
+fn parse_line_porcelain(output: &str) -> HashMap<usize, BlameLine> {
+    let mut lines = HashMap::new();
+    let mut commit = String::new();
+    let mut final_line = 0usize;
+    let mut author = String::new();
+    let mut author_time = 0i64;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            lines.insert(
+                final_line,
+                BlameLine {
+                    commit: commit.clone(),
+                    author: author.clone(),
+                    author_time,
+                },
+            );
+        } else if let Some(name) = line.strip_prefix("author ") {
+            author = name.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            author_time = time.trim().parse().unwrap_or(0);
+        } else {
+            let mut parts = line.split(' ');
+            let sha = parts.next().unwrap_or("");
+            if sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+                commit = sha.to_string();
+                final_line = parts.nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+            }
+        }
+    }
+    lines
+}
+
+fn sort_items(items: &mut [TodoItem], sort: TodoSort) {
+    match sort {
+        TodoSort::Path => {}
+        TodoSort::Oldest => items.sort_by_key(|i| i.author_time.unwrap_or(i64::MAX)),
+        TodoSort::Newest => items.sort_by_key(|i| std::cmp::Reverse(i.author_time.unwrap_or(0))),
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for TodoScan {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let scan_args: TodoScanArgs = serde_json::from_value(args)?;
+        let start = resolve_start(&self.repo_root, scan_args.path.as_deref(), &self.protected)?;
+        let re = Self::tag_regex(&scan_args.tags)?;
+        let repo_root = self.repo_root.clone();
+        let protected = self.protected.clone();
+
+        let mut items =
+            tokio::task::spawn_blocking(move || Self::scan(&repo_root, &start, &re, &protected))
+                .await
+                .map_err(|e| anyhow::anyhow!("todo_scan walk task failed: {}", e))??;
+
+        let total = items.len();
+        let mut by_tag = BTreeMap::new();
+        for item in &items {
+            *by_tag.entry(item.tag.clone()).or_insert(0) += 1;
+        }
+
+        let blamed = scan_args.blame && !items.is_empty() && self.is_git_repo().await;
+        // Path order doesn't need blame to pick the top items, so only blame what is returned.
+        if scan_args.sort == TodoSort::Path {
+            items.truncate(scan_args.max_results);
+        }
+        if blamed {
+            self.attach_blame(&mut items).await;
+        }
+        sort_items(&mut items, scan_args.sort);
+        items.truncate(scan_args.max_results);
+
+        let tasks_added = match scan_args.plan_id.as_deref() {
+            Some(plan_id) if !items.is_empty() => self.add_tasks(plan_id, &items).await?,
+            _ => Vec::new(),
+        };
+
+        Ok(TodoScanResult {
+            truncated: total > items.len(),
+            items,
+            total,
+            by_tag,
+            blamed,
+            tasks_added,
+        }
+        .to_json())
+    }
+}
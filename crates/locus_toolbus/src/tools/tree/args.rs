@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TreeArgs {
+    /// Directory to start from (relative to repo root, defaults to repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// How many directory levels below `path` to expand
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+
+    /// Maximum number of nodes returned before the walk stops
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+
+    /// Include dotfiles and dot-directories (`.git` is always skipped)
+    #[serde(default)]
+    pub include_hidden: bool,
+
+    /// Honour `.gitignore` / `.ignore` files
+    #[serde(default = "default_respect_gitignore")]
+    pub respect_gitignore: bool,
+}
+
+fn default_max_depth() -> usize {
+    3
+}
+
+fn default_max_entries() -> usize {
+    500
+}
+
+fn default_respect_gitignore() -> bool {
+    true
+}
+
+impl TreeArgs {
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            max_depth: default_max_depth(),
+            max_entries: default_max_entries(),
+            include_hidden: false,
+            respect_gitignore: true,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+}
+
+impl Default for TreeArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Kind of a tree node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TreeNodeKind {
+    File,
+    Dir,
+}
+
+/// One file or directory in the tree.
+///
+/// For directories, `size_bytes` is the sum of the files seen beneath it and
+/// `file_count` / `dir_count` are direct child counts, including children that
+/// sit past `max_depth` and therefore have no `children` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "type")]
+    pub kind: TreeNodeKind,
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<TreeNode>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeResult {
+    pub root: TreeNode,
+    pub total_files: usize,
+    pub total_dirs: usize,
+    pub total_size_bytes: u64,
+    pub max_depth: usize,
+    pub truncated: bool,
+}
+
+impl TreeResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::json!({ "error": "serialization failed" }))
+    }
+}
@@ -0,0 +1,290 @@
+mod args;
+mod error;
+
+pub use args::{TreeArgs, TreeNode, TreeNodeKind, TreeResult};
+pub use error::TreeError;
+
+use crate::protected_paths::ProtectedPathMatcher;
+use crate::result_cache::CacheScope;
+use crate::tools::{Tool, ToolResult, parse_tool_schema, resolve_dir};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub struct Tree {
+    repo_root: PathBuf,
+    protected: ProtectedPathMatcher,
+}
+
+/// Per-directory counters collected while walking.
+#[derive(Debug, Default)]
+struct DirStats {
+    file_count: usize,
+    dir_count: usize,
+    size_bytes: u64,
+}
+
+/// A visible node collected while walking (children are linked afterwards).
+struct WalkedNode {
+    rel: PathBuf,
+    kind: TreeNodeKind,
+    size_bytes: u64,
+    depth: usize,
+}
+
+impl Tree {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            protected: ProtectedPathMatcher::default(),
+        }
+    }
+
+    /// Refuse a protected `path`, and leave protected files out of the walk, by
+    /// `protected` instead of the built-in patterns.
+    pub fn with_protected_paths(mut self, protected: ProtectedPathMatcher) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// Walk `start` and build the nested tree. Blocking; call from `spawn_blocking`.
+    fn build(
+        repo_root: &Path,
+        start: &Path,
+        args: &TreeArgs,
+        protected: &ProtectedPathMatcher,
+    ) -> Result<TreeResult, TreeError> {
+        let (root, protected) = (repo_root.to_path_buf(), protected.clone());
+        let walker = ignore::WalkBuilder::new(start)
+            .hidden(!args.include_hidden)
+            .git_ignore(args.respect_gitignore)
+            .git_exclude(args.respect_gitignore)
+            .ignore(args.respect_gitignore)
+            .parents(args.respect_gitignore)
+            .git_global(false)
+            .require_git(false)
+            // One level past max_depth so collapsed directories still get entry counts.
+            .max_depth(Some(args.max_depth + 1))
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !protected.is_protected(&root, e.path())
+            })
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut stats: HashMap<PathBuf, DirStats> = HashMap::new();
+        let mut nodes: Vec<WalkedNode> = Vec::new();
+        let mut truncated = false;
+
+        for entry in walker {
+            let entry = entry?;
+            let depth = entry.depth();
+            if depth == 0 {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(start)
+                .unwrap_or(entry.path())
+                .to_path_buf();
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            let size_bytes = if is_dir {
+                0
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+
+            let parent = rel.parent().map(Path::to_path_buf).unwrap_or_default();
+            let parent_stats = stats.entry(parent).or_default();
+            if is_dir {
+                parent_stats.dir_count += 1;
+            } else {
+                parent_stats.file_count += 1;
+                for ancestor in rel.ancestors().skip(1) {
+                    stats.entry(ancestor.to_path_buf()).or_default().size_bytes += size_bytes;
+                }
+            }
+
+            if depth > args.max_depth {
+                continue;
+            }
+            if nodes.len() >= args.max_entries {
+                truncated = true;
+                continue;
+            }
+            nodes.push(WalkedNode {
+                rel,
+                kind: if is_dir {
+                    TreeNodeKind::Dir
+                } else {
+                    TreeNodeKind::File
+                },
+                size_bytes,
+                depth,
+            });
+        }
+
+        let total_files = nodes
+            .iter()
+            .filter(|n| n.kind == TreeNodeKind::File)
+            .count();
+        let total_dirs = nodes.len() - total_files;
+
+        let mut children: HashMap<PathBuf, Vec<WalkedNode>> = HashMap::new();
+        for node in nodes {
+            let parent = node.rel.parent().map(Path::to_path_buf).unwrap_or_default();
+            children.entry(parent).or_default().push(node);
+        }
+
+        let root_rel = PathBuf::new();
+        let root_name = start
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+        let root_path = start
+            .strip_prefix(repo_root)
+            .ok()
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        let root_stats = stats.get(&root_rel);
+        let root = TreeNode {
+            name: root_name,
+            path: root_path.clone(),
+            kind: TreeNodeKind::Dir,
+            size_bytes: root_stats.map(|s| s.size_bytes).unwrap_or(0),
+            file_count: Some(root_stats.map(|s| s.file_count).unwrap_or(0)),
+            dir_count: Some(root_stats.map(|s| s.dir_count).unwrap_or(0)),
+            children: Some(Self::link_children(
+                &root_rel,
+                &root_path,
+                &mut children,
+                &stats,
+                args.max_depth,
+            )),
+        };
+
+        Ok(TreeResult {
+            total_size_bytes: root.size_bytes,
+            root,
+            total_files,
+            total_dirs,
+            max_depth: args.max_depth,
+            truncated,
+        })
+    }
+
+    fn link_children(
+        dir: &Path,
+        root_path: &str,
+        children: &mut HashMap<PathBuf, Vec<WalkedNode>>,
+        stats: &HashMap<PathBuf, DirStats>,
+        max_depth: usize,
+    ) -> Vec<TreeNode> {
+        let mut walked = children.remove(dir).unwrap_or_default();
+        walked.sort_by(|a, b| {
+            (a.kind != TreeNodeKind::Dir)
+                .cmp(&(b.kind != TreeNodeKind::Dir))
+                .then_with(|| a.rel.cmp(&b.rel))
+        });
+
+        walked
+            .into_iter()
+            .map(|node| {
+                let name = node
+                    .rel
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let path = if root_path == "." {
+                    node.rel.to_string_lossy().to_string()
+                } else {
+                    Path::new(root_path)
+                        .join(&node.rel)
+                        .to_string_lossy()
+                        .to_string()
+                };
+                match node.kind {
+                    TreeNodeKind::File => TreeNode {
+                        name,
+                        path,
+                        kind: TreeNodeKind::File,
+                        size_bytes: node.size_bytes,
+                        file_count: None,
+                        dir_count: None,
+                        children: None,
+                    },
+                    TreeNodeKind::Dir => {
+                        let dir_stats = stats.get(&node.rel);
+                        let nested = if node.depth < max_depth {
+                            Some(Self::link_children(
+                                &node.rel, root_path, children, stats, max_depth,
+                            ))
+                        } else {
+                            None
+                        };
+                        TreeNode {
+                            name,
+                            path,
+                            kind: TreeNodeKind::Dir,
+                            size_bytes: dir_stats.map(|s| s.size_bytes).unwrap_or(0),
+                            file_count: Some(dir_stats.map(|s| s.file_count).unwrap_or(0)),
+                            dir_count: Some(dir_stats.map(|s| s.dir_count).unwrap_or(0)),
+                            children: nested,
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for Tree {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    fn cache_scope(&self, args: &JsonValue) -> CacheScope {
+        match serde_json::from_value::<TreeArgs>(args.clone()) {
+            Ok(args) => CacheScope::Ignore {
+                gitignore: args.respect_gitignore,
+                hidden: !args.include_hidden,
+            },
+            Err(_) => CacheScope::All,
+        }
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let tree_args: TreeArgs = serde_json::from_value(args)?;
+        let start = resolve_dir(&self.repo_root, tree_args.path.as_deref(), &self.protected)?;
+        let repo_root = self.repo_root.clone();
+        let protected = self.protected.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            Self::build(&repo_root, &start, &tree_args, &protected)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("tree walk task failed: {}", e))??;
+
+        Ok(result.to_json())
+    }
+}
@@ -5,6 +5,7 @@ pub use args::UndoEditArgs;
 pub use error::UndoEditError;
 
 use crate::history::EditHistory;
+use crate::permission::Permission;
 use crate::tools::{Tool, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
@@ -88,6 +89,10 @@ impl Tool for UndoEdit {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let tool_args: UndoEditArgs = serde_json::from_value(args)?;
 
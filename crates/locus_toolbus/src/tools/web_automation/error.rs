@@ -10,4 +10,7 @@ pub enum WebAutomationError {
 
     #[error("API error: {code} - {message}")]
     ApiError { code: String, message: String },
+
+    #[error("Automation run cancelled")]
+    Cancelled,
 }
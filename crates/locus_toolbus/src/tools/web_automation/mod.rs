@@ -8,24 +8,31 @@ pub use error::WebAutomationError;
 pub use stream::SseRunner;
 pub use types::{AutomationRequest, AutomationResult, SseEvent};
 
-use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use stream::{SseBuffer, event_data};
+
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolContext, ToolResult, parse_tool_schema};
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use serde_json::Value as JsonValue;
 use std::sync::OnceLock;
 
 const DEFAULT_BASE_URL: &str = "https://agent.tinyfish.ai";
-const ENV_API_KEY: &str = "TINYFISH_API_KEY";
+pub(crate) const ENV_API_KEY: &str = "TINYFISH_API_KEY";
 /// TinyFish docs use run-sse; response is SSE with final event type "COMPLETE" and resultJson.
 const AUTOMATION_PATH: &str = "/v1/automation/run-sse";
 
 pub struct WebAutomation {
     base_url: String,
+    /// Used instead of `TINYFISH_API_KEY` when set.
+    api_key: Option<String>,
 }
 
 impl WebAutomation {
     pub fn new() -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
         }
     }
 
@@ -34,13 +41,22 @@ impl WebAutomation {
         self
     }
 
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
     fn api_key(&self) -> Result<String, WebAutomationError> {
-        std::env::var(ENV_API_KEY).map_err(|_| WebAutomationError::MissingApiKey)
+        match &self.api_key {
+            Some(key) => Ok(key.clone()),
+            None => std::env::var(ENV_API_KEY).map_err(|_| WebAutomationError::MissingApiKey),
+        }
     }
 
     async fn run_automation(
         &self,
         args: &WebAutomationArgs,
+        ctx: &ToolContext,
     ) -> Result<JsonValue, WebAutomationError> {
         let api_key = self.api_key()?;
 
@@ -62,18 +78,18 @@ impl WebAutomation {
             .post(&url)
             .header("X-API-Key", api_key)
             .header("Content-Type", "application/json")
+            .header("Accept", "text/event-stream")
             .json(&body)
             .send()
             .await
             .map_err(|e| WebAutomationError::RequestFailed(e.to_string()))?;
 
         let status = response.status();
-        let text = response
-            .text()
-            .await
-            .map_err(|e| WebAutomationError::RequestFailed(e.to_string()))?;
-
         if !status.is_success() {
+            let text = response
+                .text()
+                .await
+                .map_err(|e| WebAutomationError::RequestFailed(e.to_string()))?;
             let err_json: Result<serde_json::Value, _> = serde_json::from_str(&text);
             if let Ok(js) = err_json {
                 if let Some(err) = js.get("error") {
@@ -96,26 +112,61 @@ impl WebAutomation {
             });
         }
 
-        // Parse SSE: lines "data: {...}"; final event is type "COMPLETE" with resultJson.
-        let mut result_json = None;
-        for line in text.lines() {
-            let line = line.trim();
-            if line.starts_with("data: ") {
-                let payload = line.strip_prefix("data: ").unwrap_or(line);
-                if let Ok(ev) = serde_json::from_str::<JsonValue>(payload) {
-                    if ev.get("type").and_then(|t| t.as_str()) == Some("COMPLETE") {
-                        if let Some(rj) = ev.get("resultJson") {
-                            result_json = Some(rj.clone());
-                            break;
-                        }
-                        result_json = Some(ev);
-                        break;
-                    }
+        // Read SSE events as they arrive, reporting progress until the final
+        // event of type "COMPLETE" with resultJson.
+        let mut stream = response.bytes_stream();
+        let mut buffer = SseBuffer::default();
+        let mut text = String::new();
+        loop {
+            // Dropping the stream closes the connection, which ends the run.
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = ctx.cancel.cancelled() => return Err(WebAutomationError::Cancelled),
+            };
+            let Some(chunk) = chunk else { break };
+            let chunk = chunk.map_err(|e| WebAutomationError::RequestFailed(e.to_string()))?;
+            text.push_str(&String::from_utf8_lossy(&chunk));
+            for event in buffer.push(&chunk) {
+                if let Some(result) = handle_event(&event, ctx) {
+                    return Ok(result);
                 }
             }
         }
-        let result = result_json.unwrap_or_else(|| serde_json::json!({ "raw": text }));
-        Ok(result)
+        if let Some(result) = buffer.finish().and_then(|e| handle_event(&e, ctx)) {
+            return Ok(result);
+        }
+        Ok(serde_json::json!({ "raw": text }))
+    }
+}
+
+/// Report an intermediate event as progress; returns the result once the
+/// COMPLETE event arrives.
+fn handle_event(event: &str, ctx: &ToolContext) -> Option<JsonValue> {
+    let ev: JsonValue = serde_json::from_str(&event_data(event)?).ok()?;
+    if ev.get("type").and_then(|t| t.as_str()) == Some("COMPLETE") {
+        return Some(ev.get("resultJson").cloned().unwrap_or(ev));
+    }
+    if let Some(message) = progress_message(&ev) {
+        ctx.report(message);
+    }
+    None
+}
+
+/// One line for the user about an intermediate SSE event, if it says anything.
+pub(crate) fn progress_message(event: &JsonValue) -> Option<String> {
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| event.get(*name).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    match event.get("type").and_then(|t| t.as_str())? {
+        "STARTED" => Some("Browser session started".to_string()),
+        "STREAMING_URL" => field(&["streamingUrl", "streaming_url"])
+            .map(|url| format!("Live browser view: {}", url)),
+        "PROGRESS" => field(&["purpose", "message"]).map(str::to_string),
+        _ => None,
     }
 }
 
@@ -144,8 +195,19 @@ impl Tool for WebAutomation {
         schema().2.clone()
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Network
+    }
+
     async fn execute(&self, args: JsonValue) -> ToolResult {
         let a: WebAutomationArgs = serde_json::from_value(args)?;
-        self.run_automation(&a).await.map_err(Into::into)
+        self.run_automation(&a, &ToolContext::new())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn execute_with_context(&self, args: JsonValue, ctx: ToolContext) -> ToolResult {
+        let a: WebAutomationArgs = serde_json::from_value(args)?;
+        self.run_automation(&a, &ctx).await.map_err(Into::into)
     }
 }
@@ -81,19 +81,14 @@ impl SseRunner {
 
         // Stream SSE events
         let mut stream = response.bytes_stream();
-        let mut buffer = String::new();
+        let mut buffer = SseBuffer::default();
         let mut final_result: Option<serde_json::Value> = None;
 
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.map_err(|e| WebAutomationError::RequestFailed(e.to_string()))?;
-            let chunk_str = String::from_utf8_lossy(&chunk);
-            buffer.push_str(&chunk_str);
 
             // Process complete SSE events in buffer
-            while let Some(pos) = buffer.find("\n\n") {
-                let event_text = buffer[..pos].to_string();
-                buffer = buffer[pos + 2..].to_string();
-
+            for event_text in buffer.push(&chunk) {
                 // Parse SSE event
                 if let Some(event) = parse_sse_event(&event_text) {
                     let is_complete = event.is_complete();
@@ -139,14 +134,42 @@ impl SseRunner {
     }
 }
 
-/// Parse SSE event from text (format: "data: {...}\n" or "data: {...}\nid: ...\n")
-fn parse_sse_event(text: &str) -> Option<SseEvent> {
-    for line in text.lines() {
-        let line = line.trim();
-        if line.starts_with("data: ") {
-            let json_str = line.strip_prefix("data: ")?;
-            return serde_json::from_str(json_str).ok();
+/// Splits an SSE byte stream into event blocks, which end at a blank line.
+#[derive(Default)]
+pub(crate) struct SseBuffer {
+    pending: Vec<u8>,
+}
+
+impl SseBuffer {
+    /// Append `chunk`; returns the events it completed, oldest first.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend(chunk.iter().filter(|&&b| b != b'\r'));
+        let mut events = Vec::new();
+        while let Some(pos) = self.pending.windows(2).position(|w| w == b"\n\n") {
+            let event: Vec<u8> = self.pending.drain(..pos + 2).collect();
+            events.push(String::from_utf8_lossy(&event[..pos]).into_owned());
         }
+        events
+    }
+
+    /// The last event when the stream ends without a blank line after it.
+    pub(crate) fn finish(self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&self.pending).trim().to_string();
+        (!rest.is_empty()).then_some(rest)
     }
-    None
+}
+
+/// The payload of an event block's `data:` lines, joined by line breaks.
+pub(crate) fn event_data(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Parse SSE event from text (format: "data: {...}\n" or "data: {...}\nid: ...\n")
+fn parse_sse_event(text: &str) -> Option<SseEvent> {
+    serde_json::from_str(&event_data(text)?).ok()
 }
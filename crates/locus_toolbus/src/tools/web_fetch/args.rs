@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebFetchArgs {
+    /// http:// or https:// URL to fetch
+    pub url: String,
+
+    #[serde(default)]
+    pub method: FetchMethod,
+
+    /// Extra request headers
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// Request body (POST only)
+    #[serde(default)]
+    pub body: Option<String>,
+
+    /// Seconds before the request is abandoned
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Largest content returned, in bytes
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+
+    /// Return HTML as fetched instead of the extracted main content
+    #[serde(default)]
+    pub raw: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FetchMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_bytes() -> usize {
+    50_000
+}
+
+impl WebFetchArgs {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            method: FetchMethod::Get,
+            headers: BTreeMap::new(),
+            body: None,
+            timeout_secs: default_timeout_secs(),
+            max_bytes: default_max_bytes(),
+            raw: false,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WebFetchError {
+    #[error("Invalid URL {0}")]
+    InvalidUrl(String),
+
+    #[error("Unsupported URL scheme '{0}': only http and https can be fetched")]
+    UnsupportedScheme(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("Request timed out after {0}s")]
+    Timeout(u64),
+
+    #[error("Request failed: {0}")]
+    RequestFailed(String),
+
+    #[error("HTTP {status} from {url}: {body}")]
+    Status {
+        status: u16,
+        url: String,
+        body: String,
+    },
+
+    #[error("Unsupported content type '{0}': web_fetch returns text, HTML and JSON only")]
+    UnsupportedContent(String),
+}
@@ -0,0 +1,260 @@
+mod args;
+mod error;
+pub mod readability;
+
+pub use args::{FetchMethod, WebFetchArgs};
+pub use error::WebFetchError;
+pub use readability::{Page, html_to_markdown};
+
+use crate::permission::Permission;
+use crate::tools::{Tool, ToolResult, parse_tool_schema};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Url, redirect};
+use serde_json::{Value as JsonValue, json};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const MAX_REDIRECTS: usize = 10;
+const MAX_TIMEOUT_SECS: u64 = 120;
+const MIN_MAX_BYTES: usize = 1_000;
+const MAX_MAX_BYTES: usize = 200_000;
+/// Bodies are read up to this size; anything past it is dropped before conversion.
+const MAX_DOWNLOAD_BYTES: usize = 5 * 1024 * 1024;
+/// Characters of an error response body quoted in the error.
+const ERROR_BODY_CHARS: usize = 500;
+
+pub struct WebFetch {
+    client: reqwest::Client,
+}
+
+impl WebFetch {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .redirect(redirect::Policy::limited(MAX_REDIRECTS))
+            .user_agent(concat!("locus.codes/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+
+    async fn fetch(&self, args: &WebFetchArgs) -> Result<JsonValue, WebFetchError> {
+        let url = Url::parse(args.url.trim())
+            .map_err(|e| WebFetchError::InvalidUrl(format!("'{}': {}", args.url, e)))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(WebFetchError::UnsupportedScheme(url.scheme().to_string()));
+        }
+        if args.body.is_some() && args.method != FetchMethod::Post {
+            return Err(WebFetchError::InvalidArgs(
+                "body is only sent with method POST".to_string(),
+            ));
+        }
+        let headers = header_map(&args.headers)?;
+        let timeout_secs = args.timeout_secs.clamp(1, MAX_TIMEOUT_SECS);
+
+        let request = match args.method {
+            FetchMethod::Get => self.client.get(url.clone()),
+            FetchMethod::Post => self
+                .client
+                .post(url.clone())
+                .body(args.body.clone().unwrap_or_default()),
+        };
+        let request = request
+            .headers(headers)
+            .timeout(Duration::from_secs(timeout_secs));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| request_error(e, timeout_secs))?;
+        let status = response.status();
+        let final_url = response.url().clone();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+        let bytes = read_body(response, timeout_secs).await?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        if !status.is_success() {
+            return Err(WebFetchError::Status {
+                status: status.as_u16(),
+                url: final_url.to_string(),
+                body: snippet(&text),
+            });
+        }
+
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_ascii_lowercase();
+        let (title, content) = match ContentKind::of(&mime, &text) {
+            ContentKind::Html if args.raw => (None, text.into_owned()),
+            ContentKind::Html => {
+                let page = html_to_markdown(&text, Some(&final_url));
+                (page.title, page.markdown)
+            }
+            ContentKind::Json => {
+                let pretty = serde_json::from_str::<JsonValue>(&text)
+                    .ok()
+                    .and_then(|v| serde_json::to_string_pretty(&v).ok());
+                (None, pretty.unwrap_or_else(|| text.into_owned()))
+            }
+            ContentKind::Text => (None, text.into_owned()),
+            ContentKind::Other => return Err(WebFetchError::UnsupportedContent(mime)),
+        };
+
+        let max_bytes = args.max_bytes.clamp(MIN_MAX_BYTES, MAX_MAX_BYTES);
+        let bytes = content.len();
+        let (content, truncated) = truncate(&content, max_bytes);
+        Ok(json!({
+            "url": url.as_str(),
+            "final_url": final_url.as_str(),
+            "status": status.as_u16(),
+            "content_type": content_type,
+            "title": title,
+            "content": content,
+            "bytes": bytes,
+            "truncated": truncated,
+        }))
+    }
+}
+
+impl Default for WebFetch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum ContentKind {
+    Html,
+    Json,
+    Text,
+    Other,
+}
+
+impl ContentKind {
+    /// Kind of a response from its MIME type, sniffing the body when none was sent.
+    fn of(mime: &str, body: &str) -> Self {
+        match mime {
+            "text/html" | "application/xhtml+xml" => Self::Html,
+            "application/json" => Self::Json,
+            m if m.ends_with("+json") => Self::Json,
+            "" if body.trim_start().starts_with('<') => Self::Html,
+            "" => Self::Text,
+            m if m.starts_with("text/")
+                || m.ends_with("+xml")
+                || m.ends_with("/xml")
+                || m.contains("javascript") =>
+            {
+                Self::Text
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+fn header_map(headers: &BTreeMap<String, String>) -> Result<HeaderMap, WebFetchError> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| WebFetchError::InvalidArgs(format!("invalid header name '{}'", name)))?;
+        let value = HeaderValue::from_str(value).map_err(|_| {
+            WebFetchError::InvalidArgs(format!("invalid value for header '{}'", name))
+        })?;
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+fn request_error(error: reqwest::Error, timeout_secs: u64) -> WebFetchError {
+    if error.is_timeout() {
+        WebFetchError::Timeout(timeout_secs)
+    } else {
+        WebFetchError::RequestFailed(error.to_string())
+    }
+}
+
+async fn read_body(
+    response: reqwest::Response,
+    timeout_secs: u64,
+) -> Result<Vec<u8>, WebFetchError> {
+    let mut body = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| request_error(e, timeout_secs))?;
+        let room = MAX_DOWNLOAD_BYTES - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        if body.len() >= MAX_DOWNLOAD_BYTES {
+            break;
+        }
+    }
+    Ok(body)
+}
+
+fn snippet(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(ERROR_BODY_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Cut `content` to at most `max_bytes`, at the last line break when one is
+/// in the second half of the budget, otherwise at a char boundary.
+fn truncate(content: &str, max_bytes: usize) -> (String, bool) {
+    if content.len() <= max_bytes {
+        return (content.to_string(), false);
+    }
+    let mut end = max_bytes;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some(newline) = content[..end].rfind('\n')
+        && newline >= max_bytes / 2
+    {
+        end = newline;
+    }
+    (content[..end].to_string(), true)
+}
+
+fn schema() -> &'static (&'static str, &'static str, JsonValue) {
+    static SCHEMA: OnceLock<(&'static str, &'static str, JsonValue)> = OnceLock::new();
+    SCHEMA.get_or_init(|| parse_tool_schema(include_str!("schema.json")))
+}
+
+#[async_trait]
+impl Tool for WebFetch {
+    fn name(&self) -> &'static str {
+        schema().0
+    }
+
+    fn description(&self) -> &'static str {
+        schema().1
+    }
+
+    fn parameters_schema(&self) -> JsonValue {
+        schema().2.clone()
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Network
+    }
+
+    /// GETs only: a POST may have done its work before the connection dropped.
+    fn idempotent(&self, args: &JsonValue) -> bool {
+        serde_json::from_value::<WebFetchArgs>(args.clone())
+            .is_ok_and(|a| a.method == FetchMethod::Get)
+    }
+
+    async fn execute(&self, args: JsonValue) -> ToolResult {
+        let a: WebFetchArgs = serde_json::from_value(args)?;
+        self.fetch(&a).await.map_err(Into::into)
+    }
+}
@@ -0,0 +1,470 @@
+//! Readability pass: find the main content of an HTML page and render it as markdown.
+//!
+//! The content root is the page's `<main>` or `<article>` when it has real text,
+//! otherwise the element whose paragraphs score highest (text length and commas,
+//! weighted by class names like `content` or `sidebar` and by link density).
+//! Scripts, forms, navigation and elements named like boilerplate (`nav`,
+//! `footer`, `cookie`, `share`…) are dropped while rendering.
+
+use std::collections::HashMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// `<main>` or `<article>` with less text than this falls back to scoring.
+const MIN_MAIN_CHARS: usize = 200;
+/// Paragraphs shorter than this do not count towards a candidate's score.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+/// Marks list and quote indentation until the final pass, so whitespace
+/// cleanup of the enclosing block does not strip it.
+const INDENT: char = '\u{1}';
+
+/// Elements that never carry readable content.
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "object", "form",
+    "button", "input", "select", "textarea", "nav", "aside", "footer", "head",
+];
+
+/// Class or id words of page chrome rather than content.
+const BOILERPLATE_WORDS: &[&str] = &[
+    "nav",
+    "navbar",
+    "navigation",
+    "menu",
+    "sidebar",
+    "footer",
+    "header",
+    "masthead",
+    "breadcrumb",
+    "breadcrumbs",
+    "toc",
+    "comment",
+    "comments",
+    "cookie",
+    "cookies",
+    "consent",
+    "banner",
+    "share",
+    "social",
+    "related",
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "promo",
+    "newsletter",
+    "popup",
+    "modal",
+];
+
+/// Class or id words of the main content.
+const CONTENT_WORDS: &[&str] = &[
+    "article",
+    "content",
+    "main",
+    "post",
+    "entry",
+    "body",
+    "text",
+    "doc",
+    "docs",
+    "documentation",
+    "markdown",
+    "prose",
+];
+
+/// A page reduced to its readable content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+/// Extract the main content of `html` as markdown. Relative links and images
+/// are resolved against `base`.
+pub fn html_to_markdown(html: &str, base: Option<&Url>) -> Page {
+    let document = Html::parse_document(html);
+    let root = main_content(&document);
+    Page {
+        title: page_title(&document),
+        markdown: normalize(&render_children(root, base), true),
+    }
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+fn page_title(document: &Html) -> Option<String> {
+    let text = |el: ElementRef| el.text().collect::<Vec<_>>().join(" ");
+    document
+        .select(&selector("title"))
+        .map(text)
+        .chain(
+            document
+                .select(&selector("meta[property='og:title']"))
+                .filter_map(|m| m.attr("content").map(str::to_string)),
+        )
+        .chain(document.select(&selector("h1")).map(text))
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|t| !t.is_empty())
+}
+
+fn text_len(el: ElementRef) -> usize {
+    el.text().map(|t| t.trim().len()).sum()
+}
+
+fn link_density(el: ElementRef) -> f64 {
+    let total = text_len(el);
+    if total == 0 {
+        return 0.0;
+    }
+    let links: usize = el.select(&selector("a")).map(text_len).sum();
+    links as f64 / total as f64
+}
+
+/// Lowercase words of an element's class and id.
+fn name_words(el: ElementRef) -> Vec<String> {
+    let value = el.value();
+    [value.attr("class"), value.id()]
+        .into_iter()
+        .flatten()
+        .flat_map(|s| s.split(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|w| !w.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+fn class_weight(el: ElementRef) -> f64 {
+    let words = name_words(el);
+    let mut weight = 0.0;
+    if words.iter().any(|w| CONTENT_WORDS.contains(&w.as_str())) {
+        weight += 25.0;
+    }
+    if words
+        .iter()
+        .any(|w| BOILERPLATE_WORDS.contains(&w.as_str()))
+    {
+        weight -= 25.0;
+    }
+    weight
+}
+
+/// The element holding the page's main content; `<body>` when nothing stands out.
+fn main_content(document: &Html) -> ElementRef<'_> {
+    let body = document
+        .select(&selector("body"))
+        .next()
+        .unwrap_or_else(|| document.root_element());
+    for css in ["main", "article", "[role=main]"] {
+        if let Some(el) = document.select(&selector(css)).max_by_key(|e| text_len(*e))
+            && text_len(el) >= MIN_MAIN_CHARS
+        {
+            return el;
+        }
+    }
+
+    let mut scores: HashMap<_, f64> = HashMap::new();
+    for paragraph in document.select(&selector("p, pre, td")) {
+        let len = text_len(paragraph);
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let commas: usize = paragraph.text().map(|t| t.matches(',').count()).sum();
+        let score = 1.0 + commas as f64 + (len as f64 / 100.0).min(3.0);
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        for share in [1.0, 0.5] {
+            let Some(ancestor) = ancestors.next() else {
+                break;
+            };
+            *scores
+                .entry(ancestor.id())
+                .or_insert_with(|| class_weight(ancestor)) += score * share;
+        }
+    }
+    scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let el = document.tree.get(id).and_then(ElementRef::wrap)?;
+            Some((el, score * (1.0 - link_density(el))))
+        })
+        // Highest score; the earlier element on ties.
+        .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.id().cmp(&a.0.id())))
+        .map(|(el, _)| el)
+        .unwrap_or(body)
+}
+
+fn is_hidden(el: ElementRef) -> bool {
+    let value = el.value();
+    value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value.attr("style").is_some_and(|s| {
+            s.replace(' ', "")
+                .to_ascii_lowercase()
+                .contains("display:none")
+        })
+}
+
+fn is_boilerplate(el: ElementRef) -> bool {
+    name_words(el)
+        .iter()
+        .any(|w| BOILERPLATE_WORDS.contains(&w.as_str()))
+}
+
+fn render_children(el: ElementRef, base: Option<&Url>) -> String {
+    el.children()
+        .map(|child| match child.value() {
+            Node::Text(text) => collapse_whitespace(text),
+            Node::Element(_) => ElementRef::wrap(child)
+                .map(|el| render_element(el, base))
+                .unwrap_or_default(),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+/// Runs of whitespace become one space, keeping a space at either end.
+fn collapse_whitespace(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return if text.is_empty() { "" } else { " " }.to_string();
+    }
+    let mut out = String::new();
+    if text.starts_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(&words.join(" "));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out
+}
+
+/// Children rendered on one line.
+fn inline(el: ElementRef, base: Option<&Url>) -> String {
+    render_children(el, base)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn block(content: &str) -> String {
+    format!("\n\n{}\n\n", content)
+}
+
+fn render_element(el: ElementRef, base: Option<&Url>) -> String {
+    let name = el.value().name();
+    if SKIPPED_TAGS.contains(&name) || is_hidden(el) || is_boilerplate(el) {
+        return String::new();
+    }
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let text = inline(el, base);
+            if text.is_empty() {
+                return String::new();
+            }
+            let level = name[1..].parse().unwrap_or(1);
+            block(&format!("{} {}", "#".repeat(level), text))
+        }
+        "p" | "div" | "section" | "article" | "main" | "header" | "figure" | "figcaption"
+        | "dl" | "dt" | "dd" | "details" | "summary" | "address" => {
+            block(&render_children(el, base))
+        }
+        "br" => "\n".to_string(),
+        "hr" => block("---"),
+        "pre" => code_block(el),
+        "code" | "kbd" | "samp" => inline_code(&el.text().collect::<String>()),
+        "strong" | "b" => wrap(&render_children(el, base), "**"),
+        "em" | "i" => wrap(&render_children(el, base), "_"),
+        "del" | "s" => wrap(&render_children(el, base), "~~"),
+        "a" => link(el, base),
+        "img" => image(el, base),
+        "ul" | "ol" => list(el, base, name == "ol"),
+        "blockquote" => {
+            let inner = normalize(&render_children(el, base), false);
+            let quoted: Vec<String> = inner
+                .lines()
+                .map(|line| {
+                    if line.is_empty() {
+                        ">".to_string()
+                    } else {
+                        format!("> {}", line)
+                    }
+                })
+                .collect();
+            block(&quoted.join("\n"))
+        }
+        "table" => table(el, base),
+        _ => render_children(el, base),
+    }
+}
+
+/// `mark` around the trimmed text, keeping the spaces around it.
+fn wrap(text: &str, mark: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let before = if text.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let after = if text.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}{}{}", before, mark, trimmed, mark, after)
+}
+
+fn inline_code(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        String::new()
+    } else if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+fn code_block(pre: ElementRef) -> String {
+    let code = pre.text().collect::<String>();
+    let language = std::iter::once(pre)
+        .chain(pre.select(&selector("code")))
+        .filter_map(|el| el.attr("class"))
+        .flat_map(str::split_whitespace)
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or("");
+    block(&format!(
+        "```{}\n{}\n```",
+        language,
+        code.trim_matches('\n').trim_end()
+    ))
+}
+
+fn resolve(url: &str, base: Option<&Url>) -> String {
+    base.and_then(|b| b.join(url).ok())
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn link(el: ElementRef, base: Option<&Url>) -> String {
+    let text = inline(el, base);
+    let href = el.attr("href").unwrap_or("").trim();
+    if text.is_empty()
+        || href.is_empty()
+        || href.starts_with('#')
+        || href.starts_with("javascript:")
+    {
+        return text;
+    }
+    format!("[{}]({})", text, resolve(href, base))
+}
+
+fn image(el: ElementRef, base: Option<&Url>) -> String {
+    let alt = el.attr("alt").unwrap_or("").trim();
+    match el.attr("src") {
+        Some(src) if !alt.is_empty() => format!("![{}]({})", alt, resolve(src, base)),
+        _ => String::new(),
+    }
+}
+
+fn list(el: ElementRef, base: Option<&Url>, ordered: bool) -> String {
+    let start: usize = el.attr("start").and_then(|s| s.parse().ok()).unwrap_or(1);
+    let mut lines = Vec::new();
+    let items = el
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() == "li");
+    for (i, item) in items.enumerate() {
+        let marker = if ordered {
+            format!("{}. ", start + i)
+        } else {
+            "- ".to_string()
+        };
+        let content = normalize(&render_children(item, base), false);
+        let indent: String = std::iter::repeat_n(INDENT, marker.len()).collect();
+        for (n, line) in content.lines().enumerate() {
+            if n == 0 {
+                lines.push(format!("{}{}", marker, line));
+            } else if line.is_empty() {
+                lines.push(String::new());
+            } else {
+                lines.push(format!("{}{}", indent, line));
+            }
+        }
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    block(&lines.join("\n"))
+}
+
+fn table(el: ElementRef, base: Option<&Url>) -> String {
+    let rows: Vec<Vec<String>> = el
+        .select(&selector("tr"))
+        .map(|row| {
+            row.children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| inline(cell, base).replace('|', "\\|"))
+                .collect::<Vec<_>>()
+        })
+        .filter(|cells| !cells.is_empty())
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    let line = |cells: &[String]| {
+        let mut padded = cells.to_vec();
+        padded.resize(columns, String::new());
+        format!("| {} |", padded.join(" | "))
+    };
+    let mut lines = vec![line(&rows[0]), line(&vec!["---".to_string(); columns])];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    block(&lines.join("\n"))
+}
+
+/// Tidy rendered markdown: collapse spaces and blank lines outside code fences
+/// and trim the ends. `finish` also turns indentation marks into spaces.
+fn normalize(text: &str, finish: bool) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for line in text.split('\n') {
+        let body = line.trim_start_matches(INDENT);
+        let indent = &line[..line.len() - body.len()];
+        if body.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(format!("{}{}", indent, body.trim()));
+            continue;
+        }
+        if in_fence {
+            lines.push(line.trim_end().to_string());
+            continue;
+        }
+        let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+        if body.is_empty() {
+            if lines.last().is_some_and(|l| !l.is_empty()) {
+                lines.push(String::new());
+            }
+            continue;
+        }
+        lines.push(format!("{}{}", indent, body));
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    let out = lines.join("\n");
+    if finish {
+        out.replace(INDENT, " ")
+    } else {
+        out
+    }
+}
@@ -0,0 +1,13 @@
+//! Agents screen.
+//!
+//! The sub-agents `task` calls started this session, updated live from
+//! `SessionEvent::SubAgents`: each one's description, state, time, the tool
+//! it is running, the last line it streamed and the tokens it used. Enter
+//! opens the selected agent's transcript (`SessionEvent::SubAgentOutput`).
+//! Access via Ctrl+T from the main chat.
+
+pub mod state;
+pub mod view;
+
+pub use state::{AgentsState, TranscriptEntry};
+pub use view::draw_agents;
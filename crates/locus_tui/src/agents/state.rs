@@ -0,0 +1,251 @@
+//! Agents view state: the sub-agents started this session, where each is,
+//! and the transcript of what each streamed and called.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use locus_core::{SessionEvent, SubAgentProgress, SubAgentState};
+
+/// Transcript entries kept per sub-agent; older ones are dropped.
+pub const MAX_TRANSCRIPT_ENTRIES: usize = 500;
+
+/// One thing a sub-agent said or did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptEntry {
+    /// Text it streamed, run together until a tool call or error.
+    Text(String),
+    /// A tool call; `ok` is None while it runs.
+    Tool {
+        id: String,
+        name: String,
+        ok: Option<bool>,
+        duration_ms: u64,
+    },
+    Error(String),
+}
+
+/// Sub-agents of this session, oldest first, their transcripts, and what is
+/// on screen.
+#[derive(Debug, Clone, Default)]
+pub struct AgentsState {
+    pub agents: Vec<SubAgentProgress>,
+    /// When the runtime last sent the board, to tick running agents' time.
+    pub updated_at: Option<Instant>,
+    /// Transcripts by tool_use_id.
+    pub transcripts: HashMap<String, Vec<TranscriptEntry>>,
+    /// Index into agents of the selected one.
+    pub selected: usize,
+    /// Showing the selected agent's transcript instead of the list.
+    pub open: bool,
+    /// Scroll offset for the list or transcript (lines from top).
+    pub scroll: usize,
+}
+
+impl AgentsState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take in a board from the runtime. Agents of earlier `task` batches
+    /// stay listed; ones already seen are replaced.
+    pub fn update(&mut self, agents: Vec<SubAgentProgress>) {
+        for agent in agents {
+            match self
+                .agents
+                .iter()
+                .position(|seen| seen.tool_use_id == agent.tool_use_id)
+            {
+                Some(index) => self.agents[index] = agent,
+                None => self.agents.push(agent),
+            }
+        }
+        self.updated_at = Some(Instant::now());
+    }
+
+    /// Add what a sub-agent streamed or called to its transcript.
+    pub fn record(&mut self, tool_use_id: &str, event: SessionEvent) {
+        let transcript = self.transcripts.entry(tool_use_id.to_string()).or_default();
+        match event {
+            SessionEvent::TextDelta { text } => match transcript.last_mut() {
+                Some(TranscriptEntry::Text(last)) => last.push_str(&text),
+                _ => transcript.push(TranscriptEntry::Text(text)),
+            },
+            SessionEvent::ToolStart { tool_use } => transcript.push(TranscriptEntry::Tool {
+                id: tool_use.id,
+                name: tool_use.name,
+                ok: None,
+                duration_ms: 0,
+            }),
+            SessionEvent::ToolDone {
+                tool_use_id,
+                result,
+            } => {
+                let call = transcript.iter_mut().rev().find_map(|entry| match entry {
+                    TranscriptEntry::Tool {
+                        id,
+                        ok,
+                        duration_ms,
+                        ..
+                    } if *id == tool_use_id => Some((ok, duration_ms)),
+                    _ => None,
+                });
+                if let Some((ok, duration_ms)) = call {
+                    *ok = Some(!result.is_error);
+                    *duration_ms = result.duration_ms;
+                }
+            }
+            SessionEvent::Error { error } => transcript.push(TranscriptEntry::Error(error)),
+            _ => {}
+        }
+        if transcript.len() > MAX_TRANSCRIPT_ENTRIES {
+            transcript.remove(0);
+        }
+    }
+
+    /// The selected agent, if any was started.
+    pub fn current(&self) -> Option<&SubAgentProgress> {
+        self.agents.get(self.selected)
+    }
+
+    /// The selected agent's transcript so far.
+    pub fn transcript(&self) -> &[TranscriptEntry] {
+        self.current()
+            .and_then(|agent| self.transcripts.get(&agent.tool_use_id))
+            .map_or(&[][..], Vec::as_slice)
+    }
+
+    /// How long `agent` has run, ticking on between boards while it runs.
+    pub fn elapsed(&self, agent: &SubAgentProgress) -> Duration {
+        let mut elapsed = Duration::from_millis(agent.elapsed_ms);
+        if agent.state == SubAgentState::Running {
+            elapsed += self.updated_at.map_or(Duration::ZERO, |at| at.elapsed());
+        }
+        elapsed
+    }
+
+    /// Select an earlier (`delta < 0`) or later agent.
+    pub fn step(&mut self, delta: isize) {
+        let last = self.agents.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        if self.open {
+            self.scroll = 0;
+        }
+    }
+
+    /// Show the selected agent's transcript.
+    pub fn open_transcript(&mut self) {
+        if self.current().is_some() {
+            self.open = true;
+            self.scroll = 0;
+        }
+    }
+
+    /// Back from a transcript to the list; false when the list was shown.
+    pub fn close_transcript(&mut self) -> bool {
+        let was_open = std::mem::take(&mut self.open);
+        if was_open {
+            self.scroll = 0;
+        }
+        was_open
+    }
+
+    pub fn scroll_up(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_sub(delta);
+    }
+
+    pub fn scroll_down(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_add(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::{ToolResultData, ToolUse};
+
+    fn agent(id: &str, state: SubAgentState) -> SubAgentProgress {
+        SubAgentProgress {
+            tool_use_id: id.to_string(),
+            description: format!("task {}", id),
+            state,
+            activity: String::new(),
+            elapsed_ms: 1000,
+            tool: None,
+            last_line: String::new(),
+            tokens: 0,
+        }
+    }
+
+    #[test]
+    fn boards_of_later_batches_add_to_the_list() {
+        let mut agents = AgentsState::new();
+        assert!(agents.current().is_none());
+
+        agents.update(vec![
+            agent("a", SubAgentState::Running),
+            agent("b", SubAgentState::Queued),
+        ]);
+        agents.update(vec![
+            agent("a", SubAgentState::Done),
+            agent("b", SubAgentState::Running),
+        ]);
+        agents.update(vec![agent("c", SubAgentState::Queued)]);
+        let ids: Vec<&str> = agents
+            .agents
+            .iter()
+            .map(|a| a.tool_use_id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(agents.agents[0].state, SubAgentState::Done);
+
+        // Only running agents tick on.
+        assert_eq!(
+            agents.elapsed(&agents.agents[0]),
+            Duration::from_millis(1000)
+        );
+        assert!(agents.elapsed(&agents.agents[1]) >= Duration::from_millis(1000));
+
+        agents.step(5);
+        assert_eq!(agents.current().unwrap().tool_use_id, "c");
+        agents.step(-1);
+        assert_eq!(agents.current().unwrap().tool_use_id, "b");
+    }
+
+    #[test]
+    fn transcript_joins_text_and_marks_finished_tools() {
+        let mut agents = AgentsState::new();
+        agents.update(vec![agent("a", SubAgentState::Running)]);
+        agents.record("a", SessionEvent::text_delta("Reading "));
+        agents.record("a", SessionEvent::text_delta("lib.rs"));
+        agents.record(
+            "a",
+            SessionEvent::tool_start(ToolUse::new("t1", "read", serde_json::json!({}))),
+        );
+        agents.record(
+            "a",
+            SessionEvent::tool_done("t1", ToolResultData::success(serde_json::json!({}), 12)),
+        );
+        agents.record("a", SessionEvent::error("rate limited"));
+        agents.record("b", SessionEvent::text_delta("elsewhere"));
+
+        assert_eq!(
+            agents.transcript(),
+            [
+                TranscriptEntry::Text("Reading lib.rs".into()),
+                TranscriptEntry::Tool {
+                    id: "t1".into(),
+                    name: "read".into(),
+                    ok: Some(true),
+                    duration_ms: 12,
+                },
+                TranscriptEntry::Error("rate limited".into()),
+            ]
+        );
+
+        assert!(!agents.close_transcript());
+        agents.open_transcript();
+        assert!(agents.open);
+        assert!(agents.close_transcript());
+        assert!(!agents.open);
+    }
+}
@@ -0,0 +1,369 @@
+//! View rendering for the agents screen.
+
+use std::time::Duration;
+
+use locus_core::{SubAgentProgress, SubAgentState};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::agents::state::{AgentsState, TranscriptEntry};
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, danger_style, render_header, success_style,
+    text_muted_style, text_style,
+};
+use crate::runtime_events::format_token_count;
+use crate::theme::LocusPalette;
+use crate::utils::{LEFT_PADDING, format_duration};
+
+/// Marker in front of the selected agent.
+const SELECTED_MARKER: &str = "▶ ";
+
+/// Draw the agents screen: every sub-agent with where it is, or the selected
+/// one's transcript.
+pub fn draw_agents(frame: &mut Frame, state: &mut AgentsState, area: Rect, palette: &LocusPalette) {
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(6),    // Agents or transcript
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let running = state
+        .agents
+        .iter()
+        .filter(|agent| agent.state == SubAgentState::Running)
+        .count();
+    let status = match state.current() {
+        None => t("agents.empty_status").to_string(),
+        Some(agent) if state.open => agent.description.clone(),
+        Some(_) => tf(
+            "agents.position",
+            &[("running", &running), ("total", &state.agents.len())],
+        ),
+    };
+    render_header(
+        frame,
+        chunks[0],
+        palette,
+        t(HEADER_TITLE),
+        t("agents.section"),
+        status.as_str(),
+        running > 0,
+        false,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+
+    let lines = if state.agents.is_empty() {
+        empty_lines(palette)
+    } else if state.open {
+        transcript_lines(state.transcript(), palette)
+    } else {
+        let mut lines = Vec::new();
+        for (index, agent) in state.agents.iter().enumerate() {
+            lines.extend(card_lines(
+                agent,
+                state.elapsed(agent),
+                index == state.selected,
+                palette,
+            ));
+        }
+        lines
+    };
+    let height = inner.height as usize;
+    state.scroll = state.scroll.min(lines.len().saturating_sub(height));
+    let visible: Vec<Line> = lines.into_iter().skip(state.scroll).collect();
+    frame.render_widget(Paragraph::new(visible).wrap(Wrap { trim: false }), inner);
+
+    draw_shortcuts(frame, chunks[2], state.open, palette);
+}
+
+fn empty_lines(palette: &LocusPalette) -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled("● ".to_string(), text_style(palette.accent)),
+            Span::styled(t("agents.empty_title"), text_style(palette.text)),
+        ]),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled(
+                format!("  {}", t("agents.empty_hint")),
+                text_muted_style(palette.text_muted),
+            ),
+        ]),
+    ]
+}
+
+/// An agent card: state glyph, description, state, time and tokens; then
+/// the tool it runs (or its last status) and the last line it streamed;
+/// then a blank line.
+pub fn card_lines(
+    agent: &SubAgentProgress,
+    elapsed: Duration,
+    selected: bool,
+    palette: &LocusPalette,
+) -> Vec<Line<'static>> {
+    let (glyph, color) = match agent.state {
+        SubAgentState::Queued => ("○", palette.text_muted),
+        SubAgentState::Running => ("●", palette.accent),
+        SubAgentState::Throttled => ("⏸", palette.warning),
+        SubAgentState::Done => ("✓", palette.success),
+        SubAgentState::Failed | SubAgentState::TimedOut => ("✗", palette.danger),
+        SubAgentState::Cancelled => ("✗", palette.text_disabled),
+    };
+    let marker = if selected { SELECTED_MARKER } else { "  " };
+    let mut facts = vec![state_label(agent.state).to_string()];
+    if agent.state != SubAgentState::Queued {
+        facts.push(elapsed_label(elapsed));
+    }
+    if agent.tokens > 0 {
+        facts.push(tf(
+            "agents.tokens",
+            &[("tokens", &format_token_count(agent.tokens))],
+        ));
+    }
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!(" {}", marker), text_style(palette.accent)),
+        Span::styled(format!("{} ", glyph), text_style(color)),
+        Span::styled(agent.description.clone(), text_style(palette.text)),
+        Span::styled(
+            format!("  {}", facts.join(" · ")),
+            text_muted_style(palette.text_muted),
+        ),
+    ])];
+
+    let activity = match &agent.tool {
+        Some(tool) => Some(tf("agents.running_tool", &[("tool", tool)])),
+        None => (!agent.activity.is_empty() && !agent.state.is_finished())
+            .then(|| agent.activity.clone()),
+    };
+    if let Some(activity) = activity {
+        lines.push(Line::from(Span::styled(
+            format!("     {}", activity),
+            text_style(palette.text),
+        )));
+    }
+    if !agent.last_line.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("     {}", agent.last_line),
+            text_muted_style(palette.text_muted),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+fn state_label(state: SubAgentState) -> &'static str {
+    match state {
+        SubAgentState::Queued => t("agents.queued"),
+        SubAgentState::Running => t("agents.running"),
+        SubAgentState::Throttled => t("agents.throttled"),
+        SubAgentState::Done => t("agents.done"),
+        SubAgentState::Failed => t("agents.failed"),
+        SubAgentState::TimedOut => t("agents.timed_out"),
+        SubAgentState::Cancelled => t("agents.cancelled"),
+    }
+}
+
+/// Whole seconds, then minutes and seconds past a minute.
+fn elapsed_label(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+/// A sub-agent's transcript: its text, each tool call with how it went, and
+/// its errors.
+pub fn transcript_lines(
+    transcript: &[TranscriptEntry],
+    palette: &LocusPalette,
+) -> Vec<Line<'static>> {
+    if transcript.is_empty() {
+        return vec![Line::from(Span::styled(
+            format!(" {}", t("agents.no_output")),
+            text_muted_style(palette.text_disabled),
+        ))];
+    }
+    let mut lines = Vec::new();
+    for entry in transcript {
+        match entry {
+            TranscriptEntry::Text(text) => {
+                lines.extend(text.trim().lines().map(|line| {
+                    Line::from(Span::styled(format!(" {}", line), text_style(palette.text)))
+                }));
+            }
+            TranscriptEntry::Tool {
+                name,
+                ok,
+                duration_ms,
+                ..
+            } => {
+                let outcome = match ok {
+                    None => Span::styled(
+                        t("agents.running").to_string(),
+                        text_muted_style(palette.text_muted),
+                    ),
+                    Some(true) => Span::styled(
+                        format!("✓ {}", format_duration(Duration::from_millis(*duration_ms))),
+                        success_style(palette.success),
+                    ),
+                    Some(false) => Span::styled(
+                        format!("✗ {}", format_duration(Duration::from_millis(*duration_ms))),
+                        danger_style(palette.danger),
+                    ),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(" ⚙ ".to_string(), text_muted_style(palette.text_muted)),
+                    Span::styled(format!("{}  ", name), text_style(palette.accent)),
+                    outcome,
+                ]));
+            }
+            TranscriptEntry::Error(error) => lines.push(Line::from(Span::styled(
+                format!(" ✗ {}", error),
+                danger_style(palette.danger),
+            ))),
+        }
+    }
+    lines
+}
+
+fn draw_shortcuts(frame: &mut Frame, area: Rect, open: bool, palette: &LocusPalette) {
+    let shortcuts = if open {
+        [
+            ("Esc", t("agents.shortcut.list")),
+            ("←→", t("agents.shortcut.agents")),
+            ("↑↓/jk", t("shortcut.scroll")),
+            ("g/G", t("shortcut.top_bottom")),
+        ]
+    } else {
+        [
+            ("Esc", t("shortcut.back")),
+            ("←→", t("agents.shortcut.agents")),
+            ("Enter", t("agents.shortcut.transcript")),
+            ("↑↓/jk", t("shortcut.scroll")),
+        ]
+    };
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn card_shows_state_time_tokens_tool_and_last_line() {
+        let palette = LocusPalette::locus_dark();
+        let agent = SubAgentProgress {
+            tool_use_id: "t1".into(),
+            description: "Read lib.rs".into(),
+            state: SubAgentState::Running,
+            activity: "read".into(),
+            elapsed_ms: 0,
+            tool: Some("read".into()),
+            last_line: "Looking at add".into(),
+            tokens: 1200,
+        };
+        assert_eq!(
+            text(&card_lines(&agent, Duration::from_secs(75), true, &palette)),
+            [
+                " ▶ ● Read lib.rs  running · 1m 15s · 1,200 tokens",
+                "     running read",
+                "     Looking at add",
+                ""
+            ]
+        );
+
+        let agent = SubAgentProgress {
+            state: SubAgentState::Queued,
+            tool: None,
+            last_line: String::new(),
+            tokens: 0,
+            ..agent
+        };
+        assert_eq!(
+            text(&card_lines(&agent, Duration::ZERO, false, &palette)),
+            ["   ○ Read lib.rs  queued", "     read", ""]
+        );
+
+        let agent = SubAgentProgress {
+            state: SubAgentState::Throttled,
+            activity: "3% of requests left".into(),
+            ..agent
+        };
+        assert_eq!(
+            text(&card_lines(&agent, Duration::from_secs(2), false, &palette)),
+            [
+                "   ⏸ Read lib.rs  throttled · 2s",
+                "     3% of requests left",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn transcript_shows_text_tools_and_errors() {
+        let palette = LocusPalette::locus_dark();
+        let transcript = [
+            TranscriptEntry::Text("Reading.\nThen grep.\n".into()),
+            TranscriptEntry::Tool {
+                id: "t1".into(),
+                name: "grep".into(),
+                ok: Some(false),
+                duration_ms: 40,
+            },
+            TranscriptEntry::Error("rate limited".into()),
+        ];
+        assert_eq!(
+            text(&transcript_lines(&transcript, &palette)),
+            [
+                " Reading.",
+                " Then grep.",
+                " ⚙ grep  ✗ 40ms",
+                " ✗ rate limited"
+            ]
+        );
+    }
+}
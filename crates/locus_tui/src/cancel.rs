@@ -0,0 +1,73 @@
+//! Stopping the run in progress: Ctrl+C, Esc, or `:cancel` in the chat input.
+//!
+//! The runtime is busy for the whole run, so the request goes on the cancel
+//! channel rather than as a chat message. The runtime aborts the LLM stream,
+//! kills running commands and skips pending sub-agents, then ends the session
+//! `Cancelled` with a marker turn so the chat can carry on from there.
+
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::t;
+use crate::state::TuiState;
+
+/// Chat command to stop the run in progress.
+pub const CANCEL_COMMAND: &str = ":cancel";
+
+/// Ask the runtime to stop the run in progress. False when there is nothing
+/// to cancel or no runtime to ask.
+pub fn cancel_run(state: &mut TuiState, cancel_tx: Option<&tokio_mpsc::Sender<()>>) -> bool {
+    if !state.is_streaming {
+        return false;
+    }
+    let Some(tx) = cancel_tx else {
+        return false;
+    };
+    let _ = tx.try_send(());
+    set_status(state, t("status.cancelling").to_string());
+    true
+}
+
+/// Handle `line` if it is the cancel command: stop the run, or say there is
+/// none. Returns false for any other input.
+pub fn request_cancel(
+    state: &mut TuiState,
+    line: &str,
+    cancel_tx: Option<&tokio_mpsc::Sender<()>>,
+) -> bool {
+    if line.trim() != CANCEL_COMMAND {
+        return false;
+    }
+    if !cancel_run(state, cancel_tx) {
+        set_status(state, t("status.nothing_to_cancel").to_string());
+    }
+    true
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_command_stops_only_a_running_turn() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+
+        assert!(!request_cancel(&mut state, "cancel the build", Some(&tx)));
+        assert!(request_cancel(&mut state, ":cancel", Some(&tx)));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(state.status, t("status.nothing_to_cancel"));
+
+        state.is_streaming = true;
+        assert!(request_cancel(&mut state, " :cancel ", Some(&tx)));
+        assert!(rx.try_recv().is_ok());
+        assert_eq!(state.status, t("status.cancelling"));
+        assert!(!cancel_run(&mut state, None));
+    }
+}
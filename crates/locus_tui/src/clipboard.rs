@@ -0,0 +1,275 @@
+//! Copying from the chat. Text goes to the terminal's clipboard with an
+//! OSC 52 escape (which reaches the local clipboard even over SSH) and to the
+//! system clipboard when one is reachable.
+//!
+//! Ctrl+Y copies the last reply, Alt+C the last code block of the replies
+//! and Alt+O the output of the last tool. Alt+V selects chat items: j/k
+//! (↑↓) move, Enter copies the item, c its last code block, Esc is done.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use base64::Engine;
+
+use crate::i18n::t;
+use crate::state::{ChatItem, TuiState};
+
+/// Tool outputs kept for copying, newest last.
+pub const MAX_TOOL_OUTPUTS: usize = 100;
+
+/// OSC 52 payloads past this size are left to the system clipboard; many
+/// terminals drop them.
+const OSC52_MAX_BYTES: usize = 100_000;
+
+/// Outputs of the tools run this session by tool use id, for copying.
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutputs(VecDeque<(String, String)>);
+
+impl ToolOutputs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the output of `tool_use_id`, dropping the oldest past the cap.
+    pub fn record(&mut self, tool_use_id: &str, output: &serde_json::Value) {
+        self.0
+            .push_back((tool_use_id.to_string(), output_text(output)));
+        if self.0.len() > MAX_TOOL_OUTPUTS {
+            self.0.pop_front();
+        }
+    }
+
+    pub fn get(&self, tool_use_id: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(id, _)| id == tool_use_id)
+            .map(|(_, text)| text.as_str())
+    }
+
+    pub fn last(&self) -> Option<&str> {
+        self.0.back().map(|(_, text)| text.as_str())
+    }
+}
+
+/// A tool's output as text: a string as it is, the stdout/stderr/output-like
+/// fields of an object, else pretty JSON.
+pub fn output_text(output: &serde_json::Value) -> String {
+    if let Some(text) = output.as_str() {
+        return text.to_string();
+    }
+    let fields: Vec<&str> = ["output", "stdout", "stderr", "content", "text"]
+        .iter()
+        .filter_map(|key| output.get(key).and_then(|v| v.as_str()))
+        .filter(|text| !text.is_empty())
+        .collect();
+    if fields.is_empty() {
+        serde_json::to_string_pretty(output).unwrap_or_default()
+    } else {
+        fields.join("\n")
+    }
+}
+
+/// The last fenced code block in `text`, without its fences.
+pub fn last_code_block(text: &str) -> Option<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(lines) => blocks.push(lines.join("\n")),
+                None => current = Some(Vec::new()),
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    blocks.pop()
+}
+
+/// What copying a chat item gives, None for items with nothing to copy.
+pub fn item_text(item: &ChatItem, outputs: &ToolOutputs) -> Option<String> {
+    let tool_text = |tool: &crate::messages::tools::ToolCallMessage| {
+        tool.id
+            .as_deref()
+            .and_then(|id| outputs.get(id))
+            .map(str::to_string)
+            .or_else(|| tool.summary.clone())
+    };
+    match item {
+        ChatItem::User(m) => Some(m.text.clone()),
+        ChatItem::Ai(m) => Some(m.text.clone()),
+        ChatItem::Think(m) => Some(m.text.clone()),
+        ChatItem::Error(m) => Some(m.text.clone()),
+        ChatItem::Tool(tool) => tool_text(tool),
+        ChatItem::ToolGroup(tools) => {
+            let texts: Vec<String> = tools.iter().filter_map(tool_text).collect();
+            (!texts.is_empty()).then(|| texts.join("\n\n"))
+        }
+        ChatItem::EditDiff(d) => Some(d.new_content.clone()),
+        ChatItem::Insights(m) => Some(format!("{}\n\n{}", m.insight, m.recommendation)),
+        ChatItem::MetaTool(_) | ChatItem::Memory(_) | ChatItem::Separator(_) => None,
+    }
+}
+
+/// Put `text` on the clipboard: OSC 52 to the terminal and the system
+/// clipboard; fine when either takes it.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let osc52 = if text.len() <= OSC52_MAX_BYTES {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", encoded).and_then(|_| stdout.flush())
+    } else {
+        Err(io::Error::other("too large for OSC 52"))
+    };
+    let system = cli_clipboard::set_contents(text.to_string());
+    match (osc52, system) {
+        (Ok(()), _) | (_, Ok(())) => Ok(()),
+        (Err(e), Err(_)) => Err(e),
+    }
+}
+
+/// Copy `text` and say so in the status line; `empty_key` says what was
+/// missing when there is nothing to copy.
+pub fn copy_with_status(state: &mut TuiState, text: Option<String>, empty_key: &'static str) {
+    state.status = match text.filter(|text| !text.is_empty()) {
+        Some(text) => match copy_to_clipboard(&text) {
+            Ok(()) => t("status.copied").to_string(),
+            Err(_) => t("status.copy_failed").to_string(),
+        },
+        None => t(empty_key).to_string(),
+    };
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = state.chat_selection.is_some();
+    state.needs_redraw = true;
+}
+
+/// Ctrl+Y: the last reply.
+pub fn copy_last_reply(state: &mut TuiState) {
+    let text = state.messages.iter().rev().find_map(|item| match item {
+        ChatItem::Ai(m) => Some(m.text.clone()),
+        _ => None,
+    });
+    copy_with_status(state, text, "status.no_reply");
+}
+
+/// Alt+C: the last code block of the replies.
+pub fn copy_last_code_block(state: &mut TuiState) {
+    let text = state.messages.iter().rev().find_map(|item| match item {
+        ChatItem::Ai(m) => last_code_block(&m.text),
+        _ => None,
+    });
+    copy_with_status(state, text, "status.no_code_block");
+}
+
+/// Alt+O: the output of the last tool run.
+pub fn copy_last_tool_output(state: &mut TuiState) {
+    let text = state.tool_outputs.last().map(str::to_string);
+    copy_with_status(state, text, "status.no_tool_output");
+}
+
+/// Alt+V: select the newest item that has something to copy.
+pub fn begin_selection(state: &mut TuiState) {
+    state.chat_selection = (0..state.messages.len())
+        .rev()
+        .find(|&i| item_text(&state.messages[i], &state.tool_outputs).is_some());
+    state.status = if state.chat_selection.is_some() {
+        state.auto_scroll = false;
+        t("status.selecting").to_string()
+    } else {
+        t("status.nothing_to_select").to_string()
+    };
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = state.chat_selection.is_some();
+    state.cache_dirty = true;
+    state.needs_redraw = true;
+}
+
+/// j/k: select the next newer (`delta > 0`) or older item with something to
+/// copy; stays put at either end.
+pub fn step_selection(state: &mut TuiState, delta: isize) {
+    let Some(current) = state.chat_selection else {
+        return;
+    };
+    let copyable = |i: &usize| item_text(&state.messages[*i], &state.tool_outputs).is_some();
+    let next = if delta < 0 {
+        (0..current).rev().find(copyable)
+    } else {
+        (current + 1..state.messages.len()).find(copyable)
+    };
+    if let Some(next) = next {
+        state.chat_selection = Some(next);
+        state.cache_dirty = true;
+        state.needs_redraw = true;
+    }
+}
+
+/// Enter: copy the selected item; `c`: its last code block.
+pub fn copy_selected(state: &mut TuiState, code_only: bool) {
+    let Some(item) = state.chat_selection.and_then(|i| state.messages.get(i)) else {
+        return;
+    };
+    let text = item_text(item, &state.tool_outputs);
+    if code_only {
+        let code = text.as_deref().and_then(last_code_block);
+        copy_with_status(state, code, "status.no_code_block");
+    } else {
+        copy_with_status(state, text, "status.nothing_to_select");
+    }
+}
+
+/// Esc: stop selecting and follow the chat again.
+pub fn end_selection(state: &mut TuiState) {
+    state.chat_selection = None;
+    state.selection_lines = None;
+    state.status.clear();
+    state.status_permanent = false;
+    state.auto_scroll = true;
+    state.scroll = 0;
+    state.cache_dirty = true;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_last_code_block() {
+        let text = "Run:\n```sh\ncargo test\n```\nthen\n```rust\nfn main() {}\n```\n";
+        assert_eq!(last_code_block(text).as_deref(), Some("fn main() {}"));
+        assert_eq!(last_code_block("no code"), None);
+        // An unclosed fence is not a block
+        assert_eq!(last_code_block("```\nopen"), None);
+    }
+
+    #[test]
+    fn tool_outputs_read_as_text() {
+        let mut outputs = ToolOutputs::new();
+        outputs.record("t1", &serde_json::json!({"stdout": "ok", "exit_code": 0}));
+        outputs.record("t2", &serde_json::json!("plain"));
+        outputs.record("t3", &serde_json::json!({"files": ["a.rs"]}));
+        assert_eq!(outputs.get("t1"), Some("ok"));
+        assert_eq!(outputs.get("t2"), Some("plain"));
+        assert!(outputs.last().unwrap().contains("\"a.rs\""));
+    }
+
+    #[test]
+    fn selection_walks_copyable_items() {
+        let mut state = TuiState::new();
+        state.push_user("fix the add test".to_string(), None);
+        state.push_separator("New session".to_string());
+        state.push_ai("Done.".to_string(), None);
+        begin_selection(&mut state);
+        assert_eq!(state.chat_selection, Some(2));
+        step_selection(&mut state, -1);
+        assert_eq!(state.chat_selection, Some(0));
+        step_selection(&mut state, -1);
+        assert_eq!(state.chat_selection, Some(0));
+        step_selection(&mut state, 1);
+        assert_eq!(state.chat_selection, Some(2));
+        end_selection(&mut state);
+        assert_eq!(state.chat_selection, None);
+        assert!(state.auto_scroll);
+    }
+}
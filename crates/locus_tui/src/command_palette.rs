@@ -0,0 +1,373 @@
+//! Command palette (Ctrl+X): every action of the TUI — the screens, session
+//! commands, the theme and the built-in `:` commands — in one list that
+//! fuzzy-matches what is typed. ↑↓ pick an action, Enter runs it, Esc closes.
+//! Actions run recently come first.
+//!
+//! Most actions do what their key does on the chat screen; [run_selected]
+//! hands that key back to the run loop to handle as if it was pressed.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::i18n::t;
+use crate::layouts::{background_style, text_muted_style, text_style};
+use crate::slash::BUILTIN_COMMANDS;
+use crate::state::{Screen, TuiState};
+use crate::theme::{Appearance, LocusPalette};
+use crate::utils::{fuzzy_filter, truncate_ellipsis};
+
+/// Actions remembered as recently run.
+const MAX_RECENT: usize = 10;
+
+/// Rows of actions shown at once.
+const VISIBLE_ROWS: usize = 12;
+
+/// What an entry does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    /// Show a screen.
+    Show(Screen),
+    /// Do what the key does on the chat screen.
+    Key(KeyCode, KeyModifiers),
+    /// Put a built-in command in the prompt; one without arguments is sent.
+    Command(&'static str, &'static str),
+    /// Switch between the dark and light themes.
+    ToggleTheme,
+}
+
+/// An entry of the palette: its label (an i18n key, or the help text of a
+/// built-in command), the key that does the same, and the action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: &'static str,
+    pub shortcut: &'static str,
+    pub action: PaletteAction,
+}
+
+const fn ctrl(c: char) -> PaletteAction {
+    PaletteAction::Key(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+/// Screens, session and prompt actions, in the order shown before any is run.
+const ACTIONS: &[(&str, &str, PaletteAction)] = &[
+    (
+        "palette.action.chat",
+        "Esc",
+        PaletteAction::Show(Screen::Main),
+    ),
+    (
+        "palette.action.logs",
+        "Ctrl+D",
+        PaletteAction::Show(Screen::DebugTraces),
+    ),
+    (
+        "palette.action.agents",
+        "Ctrl+T",
+        PaletteAction::Show(Screen::Agents),
+    ),
+    (
+        "palette.action.diff_review",
+        "Ctrl+F",
+        PaletteAction::Show(Screen::DiffReview),
+    ),
+    ("palette.action.plan", "Ctrl+L", ctrl('l')),
+    (
+        "palette.action.memory_trace",
+        "Ctrl+G",
+        PaletteAction::Show(Screen::MemoryTrace),
+    ),
+    (
+        "palette.action.task_board",
+        "Ctrl+B",
+        PaletteAction::Show(Screen::TaskBoard),
+    ),
+    (
+        "palette.action.provider_calls",
+        "Ctrl+P",
+        PaletteAction::Show(Screen::ProviderCalls),
+    ),
+    (
+        "palette.action.web_automation",
+        "Ctrl+W",
+        PaletteAction::Show(Screen::WebAutomation),
+    ),
+    ("palette.action.new_session", "Ctrl+N", ctrl('n')),
+    ("palette.action.switch_session", "Ctrl+S", ctrl('s')),
+    ("palette.action.similar_work", "Ctrl+O", ctrl('o')),
+    ("palette.action.annotate", "Ctrl+A", ctrl('a')),
+    ("palette.action.copy_reply", "Ctrl+Y", ctrl('y')),
+    ("palette.action.editor", "Ctrl+E", ctrl('e')),
+    ("palette.action.search_history", "Ctrl+R", ctrl('r')),
+    ("palette.action.toggle_mouse", "Ctrl+M", ctrl('m')),
+    (
+        "palette.action.toggle_theme",
+        "",
+        PaletteAction::ToggleTheme,
+    ),
+];
+
+/// Every entry: the actions above, then the built-in commands.
+pub fn palette_entries() -> Vec<PaletteEntry> {
+    let actions = ACTIONS
+        .iter()
+        .map(|&(label, shortcut, action)| PaletteEntry {
+            label: t(label),
+            shortcut,
+            action,
+        });
+    let commands = BUILTIN_COMMANDS
+        .iter()
+        .map(|&(command, usage, help)| PaletteEntry {
+            label: t(help),
+            shortcut: command,
+            action: PaletteAction::Command(command, usage),
+        });
+    actions.chain(commands).collect()
+}
+
+/// The open palette: what was typed and the entries matching it.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub matches: Vec<PaletteEntry>,
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// Every entry, those in `recent` (labels, most recent first) leading.
+    pub fn new(recent: &[&'static str]) -> Self {
+        let mut palette = Self::default();
+        palette.refresh(recent);
+        palette
+    }
+
+    pub fn insert(&mut self, c: char, recent: &[&'static str]) {
+        self.query.push(c);
+        self.refresh(recent);
+    }
+
+    pub fn backspace(&mut self, recent: &[&'static str]) {
+        self.query.pop();
+        self.refresh(recent);
+    }
+
+    /// Select an earlier (`delta < 0`) or later entry.
+    pub fn step(&mut self, delta: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Match the entries against the query; recent ones win ties.
+    fn refresh(&mut self, recent: &[&'static str]) {
+        let mut entries = palette_entries();
+        entries.sort_by_key(|e| {
+            recent
+                .iter()
+                .position(|r| *r == e.label)
+                .unwrap_or(usize::MAX)
+        });
+        let labels: Vec<&str> = entries.iter().map(|e| e.label).collect();
+        self.matches = fuzzy_filter(labels.iter().copied(), &self.query, entries.len())
+            .into_iter()
+            .filter_map(|label| entries.iter().find(|e| e.label == label).cloned())
+            .collect();
+        self.selected = 0;
+    }
+}
+
+/// Ctrl+X: open the palette, or close it when open.
+pub fn toggle_palette(state: &mut TuiState) {
+    state.command_palette = match state.command_palette {
+        Some(_) => None,
+        None => Some(CommandPalette::new(&state.recent_actions)),
+    };
+    state.needs_redraw = true;
+}
+
+/// Enter: close the palette and run the selected action. Returns the key to
+/// handle next on the chat screen when the action is one.
+pub fn run_selected(state: &mut TuiState) -> Option<(KeyCode, KeyModifiers)> {
+    let palette = state.command_palette.take()?;
+    state.needs_redraw = true;
+    let entry = palette.matches.get(palette.selected)?;
+    state.recent_actions.retain(|label| *label != entry.label);
+    state.recent_actions.insert(0, entry.label);
+    state.recent_actions.truncate(MAX_RECENT);
+    match entry.action {
+        PaletteAction::Show(screen) => {
+            state.screen = screen;
+            None
+        }
+        PaletteAction::Key(code, modifiers) => {
+            state.screen = Screen::Main;
+            Some((code, modifiers))
+        }
+        PaletteAction::Command(command, usage) => {
+            state.screen = Screen::Main;
+            state.input_buffer = if usage.is_empty() {
+                command.to_string()
+            } else {
+                format!("{} ", command)
+            };
+            state.input_cursor = state.input_buffer.len();
+            usage
+                .is_empty()
+                .then_some((KeyCode::Enter, KeyModifiers::NONE))
+        }
+        PaletteAction::ToggleTheme => {
+            let appearance = if state.palette == LocusPalette::locus_dark() {
+                Appearance::Light
+            } else {
+                Appearance::Dark
+            };
+            state.palette = LocusPalette::for_appearance(appearance);
+            state.follow_terminal = false;
+            state.cache_dirty = true;
+            None
+        }
+    }
+}
+
+/// Draw the palette centred over the screen: the query, then the matching
+/// actions with their keys.
+pub fn draw_command_palette(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let Some(palette) = state.command_palette.as_ref() else {
+        return;
+    };
+    let colors = &state.palette;
+    let muted = text_muted_style(colors.text_muted);
+    let width = area.width.saturating_sub(4).min(70);
+    let inner_width = width.saturating_sub(2) as usize;
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("› ".to_string(), text_style(colors.accent)),
+            Span::styled(palette.query.clone(), text_style(colors.text)),
+            Span::styled("▏".to_string(), text_style(colors.accent)),
+        ]),
+        Line::from(""),
+    ];
+    if palette.matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t("palette.no_match")),
+            muted,
+        )));
+    }
+    let first = palette.selected.saturating_sub(VISIBLE_ROWS - 1);
+    for (i, entry) in palette
+        .matches
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(VISIBLE_ROWS)
+    {
+        let selected = i == palette.selected;
+        let style = if selected {
+            text_style(colors.accent)
+        } else {
+            text_style(colors.text)
+        };
+        let shortcut_width = entry.shortcut.chars().count();
+        let label = truncate_ellipsis(entry.label, inner_width.saturating_sub(shortcut_width + 4));
+        let gap = inner_width.saturating_sub(2 + label.chars().count() + shortcut_width);
+        lines.push(Line::from(vec![
+            Span::styled(if selected { "› " } else { "  " }.to_string(), style),
+            Span::styled(label, style),
+            Span::raw(" ".repeat(gap)),
+            Span::styled(entry.shortcut.to_string(), muted),
+        ]));
+    }
+
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 3,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("palette.title")))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {} ", t("palette.hint")),
+            muted,
+        )))
+        .border_style(text_style(colors.accent))
+        .style(background_style(colors.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(state: &mut TuiState, text: &str) {
+        toggle_palette(state);
+        for c in text.chars() {
+            let recent = state.recent_actions.clone();
+            state.command_palette.as_mut().unwrap().insert(c, &recent);
+        }
+    }
+
+    #[test]
+    fn fuzzy_matches_actions_and_runs_them() {
+        let mut state = TuiState::new();
+        typed(&mut state, "logs");
+        assert_eq!(
+            state.command_palette.as_ref().unwrap().matches[0].label,
+            t("palette.action.logs")
+        );
+        assert_eq!(run_selected(&mut state), None);
+        assert_eq!(state.screen, Screen::DebugTraces);
+        assert!(state.command_palette.is_none());
+
+        typed(&mut state, "new sess");
+        assert_eq!(
+            run_selected(&mut state),
+            Some((KeyCode::Char('n'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(state.screen, Screen::Main);
+
+        typed(&mut state, "theme");
+        run_selected(&mut state);
+        assert_eq!(state.palette, LocusPalette::locus_light());
+
+        typed(&mut state, "stop the run");
+        assert_eq!(
+            run_selected(&mut state),
+            Some((KeyCode::Enter, KeyModifiers::NONE))
+        );
+        assert_eq!(state.input_buffer, ":cancel");
+    }
+
+    #[test]
+    fn recently_run_actions_come_first() {
+        let mut state = TuiState::new();
+        toggle_palette(&mut state);
+        let first = state.command_palette.as_ref().unwrap().matches[0].label;
+        assert_eq!(first, t("palette.action.chat"));
+        toggle_palette(&mut state);
+
+        typed(&mut state, "agents");
+        run_selected(&mut state);
+        typed(&mut state, "diff");
+        run_selected(&mut state);
+        toggle_palette(&mut state);
+        let labels: Vec<&str> = state.command_palette.as_ref().unwrap().matches[..2]
+            .iter()
+            .map(|e| e.label)
+            .collect();
+        assert_eq!(
+            labels,
+            [t("palette.action.diff_review"), t("palette.action.agents")]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Diff review screen.
+//!
+//! The files each agent turn changed, as hunks, from
+//! `SessionEvent::TurnDiff` at the end of the turn. Walk the hunks, approve
+//! the ones to keep and reject the others: rejecting sends `:reject-hunk` to
+//! the runtime, which puts the hunk's old lines back and reports
+//! `SessionEvent::HunkReverted`. Unified or side-by-side. Access via Ctrl+F
+//! from the main chat.
+
+pub mod state;
+pub mod view;
+
+pub use state::{DiffReviewState, HunkDecision, TurnReview};
+pub use view::draw_diff_review;
+
+use locus_core::diff_review::REJECT_HUNK_COMMAND;
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::t;
+use crate::state::TuiState;
+
+/// Approve the selected hunk and move on to the next one.
+pub fn approve_selected(state: &mut TuiState) {
+    state.diff_review.decide(HunkDecision::Approved);
+    state.diff_review.step_hunk(1);
+    state.needs_redraw = true;
+}
+
+/// Ask the runtime to revert the selected hunk, or say why not. Not while a
+/// turn is running: it may be editing the same file.
+pub fn reject_selected(state: &mut TuiState, user_msg_tx: Option<&tokio_mpsc::Sender<String>>) {
+    let Some(selected) = state.diff_review.selected() else {
+        return;
+    };
+    if matches!(
+        selected.decision,
+        HunkDecision::Rejecting | HunkDecision::Rejected
+    ) {
+        return;
+    }
+    let command = format!(
+        "{} {} {} {}",
+        REJECT_HUNK_COMMAND, selected.turn, selected.index, selected.file.path
+    );
+    state.status = if state.is_streaming {
+        t("status.reject_busy").to_string()
+    } else {
+        match user_msg_tx {
+            Some(tx) if tx.try_send(command).is_ok() => {
+                state.diff_review.decide(HunkDecision::Rejecting);
+                state.diff_review.step_hunk(1);
+                t("status.rejecting").to_string()
+            }
+            _ => t("status.reject_unavailable").to_string(),
+        }
+    };
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::FileDiff;
+    use locus_core::diff_review::hunks;
+
+    #[test]
+    fn rejects_go_to_the_runtime_when_idle() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        state.diff_review.record(
+            3,
+            vec![FileDiff {
+                path: "src/my lib.rs".into(),
+                created: false,
+                deleted: false,
+                hunks: hunks("a\n", "b\n"),
+            }],
+        );
+
+        state.is_streaming = true;
+        reject_selected(&mut state, Some(&tx));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            state.diff_review.selected().unwrap().decision,
+            HunkDecision::Pending
+        );
+
+        state.is_streaming = false;
+        reject_selected(&mut state, Some(&tx));
+        assert_eq!(rx.try_recv().unwrap(), ":reject-hunk 3 0 src/my lib.rs");
+        assert_eq!(
+            state.diff_review.selected().unwrap().decision,
+            HunkDecision::Rejecting
+        );
+
+        // Sent once
+        reject_selected(&mut state, Some(&tx));
+        assert!(rx.try_recv().is_err());
+    }
+}
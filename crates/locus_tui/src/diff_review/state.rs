@@ -0,0 +1,285 @@
+//! Diff review state: the hunks of each turn, what the user decided about
+//! each one and which one is selected.
+
+use locus_core::{FileDiff, Hunk};
+
+/// Turns kept for review; older ones are dropped.
+pub const MAX_TURNS: usize = 50;
+
+/// What the user decided about a hunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkDecision {
+    Pending,
+    Approved,
+    /// Sent to the runtime to revert; not confirmed yet.
+    Rejecting,
+    /// Reverted in the working tree.
+    Rejected,
+}
+
+/// The files one turn changed and a decision per hunk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnReview {
+    pub turn: u32,
+    pub files: Vec<FileDiff>,
+    /// Decisions by file, then by hunk.
+    pub decisions: Vec<Vec<HunkDecision>>,
+}
+
+impl TurnReview {
+    pub fn new(turn: u32, files: Vec<FileDiff>) -> Self {
+        let decisions = files
+            .iter()
+            .map(|file| vec![HunkDecision::Pending; file.hunks.len()])
+            .collect();
+        Self {
+            turn,
+            files,
+            decisions,
+        }
+    }
+
+    /// Hunks across all files.
+    pub fn hunk_count(&self) -> usize {
+        self.files.iter().map(|file| file.hunks.len()).sum()
+    }
+
+    /// File and hunk index of the `index`-th hunk across all files.
+    pub fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        let mut rest = index;
+        for (f, file) in self.files.iter().enumerate() {
+            if rest < file.hunks.len() {
+                return Some((f, rest));
+            }
+            rest -= file.hunks.len();
+        }
+        None
+    }
+
+    /// How many hunks have `decision`.
+    pub fn count(&self, decision: HunkDecision) -> usize {
+        self.decisions
+            .iter()
+            .flatten()
+            .filter(|d| **d == decision)
+            .count()
+    }
+}
+
+/// The hunk selected on the diff review screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectedHunk<'a> {
+    pub turn: u32,
+    pub file: &'a FileDiff,
+    /// Index of the hunk within its file, as `:reject-hunk` takes it.
+    pub index: usize,
+    pub hunk: &'a Hunk,
+    pub decision: HunkDecision,
+}
+
+/// What the diff review screen shows.
+#[derive(Debug, Clone, Default)]
+pub struct DiffReviewState {
+    /// Turns with changes, oldest first.
+    pub turns: Vec<TurnReview>,
+    /// Index into `turns` of the turn shown.
+    pub turn: usize,
+    /// Index of the selected hunk across the shown turn's files.
+    pub hunk: usize,
+    /// Old and new side by side instead of one unified column.
+    pub side_by_side: bool,
+    /// Scroll offset (lines from top).
+    pub scroll: usize,
+    /// Scroll the selected hunk into view on the next draw.
+    pub reveal: bool,
+    /// A turn was recorded since the end of the last run was reported.
+    pub unseen: bool,
+}
+
+impl DiffReviewState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the diff of `turn` and show it. Turns numbered `turn` or later are
+    /// replaced: their changes were rewound or belong to an earlier session.
+    pub fn record(&mut self, turn: u32, files: Vec<FileDiff>) {
+        self.turns.retain(|review| review.turn < turn);
+        if self.turns.len() >= MAX_TURNS {
+            self.turns.remove(0);
+        }
+        self.turns.push(TurnReview::new(turn, files));
+        self.turn = self.turns.len() - 1;
+        self.hunk = 0;
+        self.scroll = 0;
+        self.reveal = true;
+        self.unseen = true;
+    }
+
+    /// How many files the newest turn changed, once after it was recorded.
+    pub fn take_unseen(&mut self) -> Option<usize> {
+        if !std::mem::take(&mut self.unseen) {
+            return None;
+        }
+        self.turns.last().map(|review| review.files.len())
+    }
+
+    /// The turn shown.
+    pub fn current(&self) -> Option<&TurnReview> {
+        self.turns.get(self.turn)
+    }
+
+    /// The selected hunk of the turn shown.
+    pub fn selected(&self) -> Option<SelectedHunk<'_>> {
+        let review = self.current()?;
+        let (f, h) = review.locate(self.hunk)?;
+        Some(SelectedHunk {
+            turn: review.turn,
+            file: &review.files[f],
+            index: h,
+            hunk: &review.files[f].hunks[h],
+            decision: review.decisions[f][h],
+        })
+    }
+
+    /// Show the previous (`delta` < 0) or next turn.
+    pub fn step_turn(&mut self, delta: isize) {
+        let last = self.turns.len().saturating_sub(1);
+        let turn = self.turn.saturating_add_signed(delta).min(last);
+        if turn != self.turn {
+            self.turn = turn;
+            self.hunk = 0;
+            self.scroll = 0;
+            self.reveal = true;
+        }
+    }
+
+    /// Select the previous (`delta` < 0) or next hunk of the turn shown.
+    pub fn step_hunk(&mut self, delta: isize) {
+        let count = self.current().map_or(0, TurnReview::hunk_count);
+        self.hunk = self
+            .hunk
+            .saturating_add_signed(delta)
+            .min(count.saturating_sub(1));
+        self.reveal = true;
+    }
+
+    /// Set the decision of the selected hunk. Rejected hunks stay rejected.
+    pub fn decide(&mut self, decision: HunkDecision) {
+        let Some((f, h)) = self.current().and_then(|review| review.locate(self.hunk)) else {
+            return;
+        };
+        let current = &mut self.turns[self.turn].decisions[f][h];
+        if *current != HunkDecision::Rejected {
+            *current = decision;
+        }
+    }
+
+    /// The runtime reverted hunk `hunk` of `path` in `turn`.
+    pub fn mark_reverted(&mut self, turn: u32, path: &str, hunk: usize) {
+        let Some(review) = self.turns.iter_mut().find(|review| review.turn == turn) else {
+            return;
+        };
+        if let Some(f) = review.files.iter().position(|file| file.path == path)
+            && let Some(decision) = review.decisions[f].get_mut(hunk)
+        {
+            *decision = HunkDecision::Rejected;
+        }
+    }
+
+    /// Put hunks still being rejected back to pending, e.g. when the revert failed.
+    pub fn reset_rejecting(&mut self) {
+        for decision in self
+            .turns
+            .iter_mut()
+            .flat_map(|review| review.decisions.iter_mut().flatten())
+        {
+            if *decision == HunkDecision::Rejecting {
+                *decision = HunkDecision::Pending;
+            }
+        }
+    }
+
+    pub fn scroll_up(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_sub(delta);
+    }
+
+    pub fn scroll_down(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_add(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::diff_review::hunks;
+
+    fn file(path: &str, before: &str, after: &str) -> FileDiff {
+        FileDiff {
+            path: path.to_string(),
+            created: false,
+            deleted: false,
+            hunks: hunks(before, after),
+        }
+    }
+
+    fn two_files() -> Vec<FileDiff> {
+        let before: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+        vec![file("a.rs", &before, &after), file("b.rs", "x\n", "y\n")]
+    }
+
+    #[test]
+    fn hunks_are_walked_across_files() {
+        let mut review = DiffReviewState::new();
+        review.record(1, two_files());
+        assert_eq!(review.current().unwrap().hunk_count(), 3);
+
+        review.step_hunk(2);
+        let selected = review.selected().unwrap();
+        assert_eq!(selected.file.path, "b.rs");
+        assert_eq!(selected.index, 0);
+
+        review.step_hunk(5);
+        assert_eq!(review.hunk, 2);
+        review.step_hunk(-1);
+        let selected = review.selected().unwrap();
+        assert_eq!((selected.file.path.as_str(), selected.index), ("a.rs", 1));
+    }
+
+    #[test]
+    fn decisions_follow_the_runtime() {
+        let mut review = DiffReviewState::new();
+        review.record(1, two_files());
+        review.decide(HunkDecision::Approved);
+        review.step_hunk(1);
+        review.decide(HunkDecision::Rejecting);
+        review.reset_rejecting();
+        assert_eq!(review.selected().unwrap().decision, HunkDecision::Pending);
+
+        review.decide(HunkDecision::Rejecting);
+        review.mark_reverted(1, "a.rs", 1);
+        review.decide(HunkDecision::Approved);
+        let turn = review.current().unwrap();
+        assert_eq!(turn.count(HunkDecision::Approved), 1);
+        assert_eq!(turn.count(HunkDecision::Rejected), 1);
+    }
+
+    #[test]
+    fn a_new_turn_replaces_later_ones() {
+        let mut review = DiffReviewState::new();
+        review.record(1, two_files());
+        review.record(2, two_files());
+        review.record(3, two_files());
+        review.step_turn(-1);
+        assert_eq!(review.current().unwrap().turn, 2);
+
+        // Turns 2 and 3 were rewound
+        review.record(2, vec![file("c.rs", "", "c\n")]);
+        let turns: Vec<u32> = review.turns.iter().map(|r| r.turn).collect();
+        assert_eq!(turns, [1, 2]);
+        assert_eq!(review.selected().unwrap().file.path, "c.rs");
+    }
+}
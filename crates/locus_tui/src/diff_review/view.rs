@@ -0,0 +1,399 @@
+//! View rendering for the diff review screen.
+
+use locus_core::{FileDiff, Hunk, HunkLineKind};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::diff_review::state::{DiffReviewState, HunkDecision, TurnReview};
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, danger_style, render_header, success_style,
+    text_muted_style, text_style,
+};
+use crate::theme::LocusPalette;
+use crate::utils::LEFT_PADDING;
+
+/// Marker in front of the selected hunk's header.
+const SELECTED_MARKER: &str = "▶ ";
+/// Between the old and new columns in side-by-side mode.
+const COLUMN_DIVIDER: &str = " │ ";
+
+/// Draw the diff review screen: the turn shown, then each file it changed
+/// as hunks, the selected one marked, with what was decided about each.
+pub fn draw_diff_review(
+    frame: &mut Frame,
+    state: &mut DiffReviewState,
+    area: Rect,
+    palette: &LocusPalette,
+) {
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(6),    // Hunks
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let status = match state.current() {
+        None => t("diff_review.empty_status").to_string(),
+        Some(review) => tf(
+            "diff_review.position",
+            &[
+                ("turn", &review.turn),
+                ("index", &(state.turn + 1)),
+                ("turns", &state.turns.len()),
+            ],
+        ),
+    };
+    render_header(
+        frame,
+        chunks[0],
+        palette,
+        t(HEADER_TITLE),
+        t("diff_review.section"),
+        status.as_str(),
+        false,
+        false,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+    let (lines, selected_at) = match state.current() {
+        Some(review) => review_lines(
+            review,
+            state.hunk,
+            state.side_by_side,
+            inner.width as usize,
+            palette,
+        ),
+        None => (empty_lines(palette), 0),
+    };
+    let height = inner.height as usize;
+    if state.reveal {
+        state.scroll = selected_at;
+        state.reveal = false;
+    }
+    state.scroll = state.scroll.min(lines.len().saturating_sub(height));
+    let visible: Vec<Line> = lines.into_iter().skip(state.scroll).collect();
+    frame.render_widget(Paragraph::new(visible), inner);
+
+    draw_shortcuts(frame, chunks[2], palette);
+}
+
+fn empty_lines(palette: &LocusPalette) -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled("● ".to_string(), text_style(palette.accent)),
+            Span::styled(t("diff_review.empty_title"), text_style(palette.text)),
+        ]),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled(
+                format!("  {}", t("diff_review.empty_hint")),
+                text_muted_style(palette.text_muted),
+            ),
+        ]),
+    ]
+}
+
+/// Lines of `review`: a summary, then each file and its hunks, unified or
+/// side by side in `width` columns. Also returns the line the `selected`
+/// hunk (index across files) starts at.
+pub fn review_lines(
+    review: &TurnReview,
+    selected: usize,
+    side_by_side: bool,
+    width: usize,
+    palette: &LocusPalette,
+) -> (Vec<Line<'static>>, usize) {
+    let summary = tf(
+        "diff_review.summary",
+        &[
+            ("files", &review.files.len()),
+            ("hunks", &review.hunk_count()),
+            ("approved", &review.count(HunkDecision::Approved)),
+            ("rejected", &review.count(HunkDecision::Rejected)),
+        ],
+    );
+    let mut lines = vec![Line::from(Span::styled(
+        format!(" {}", summary),
+        text_muted_style(palette.text_muted),
+    ))];
+    let mut selected_at = 0;
+    let mut index = 0;
+    for (file, decisions) in review.files.iter().zip(&review.decisions) {
+        lines.push(Line::from(""));
+        lines.push(file_line(file, palette));
+        for (hunk, decision) in file.hunks.iter().zip(decisions) {
+            if index == selected {
+                selected_at = lines.len();
+            }
+            lines.push(hunk_header(hunk, *decision, index == selected, palette));
+            if side_by_side {
+                lines.extend(side_by_side_lines(hunk, width, palette));
+            } else {
+                lines.extend(unified_lines(hunk, palette));
+            }
+            index += 1;
+        }
+    }
+    (lines, selected_at)
+}
+
+fn file_line(file: &FileDiff, palette: &LocusPalette) -> Line<'static> {
+    let mut spans = vec![
+        Span::raw(" "),
+        Span::styled("● ".to_string(), text_style(palette.accent)),
+        Span::styled(file.path.clone(), text_style(palette.text)),
+    ];
+    let note = if file.created {
+        Some(t("diff_review.created"))
+    } else if file.deleted {
+        Some(t("diff_review.deleted"))
+    } else {
+        None
+    };
+    if let Some(note) = note {
+        spans.push(Span::styled(
+            format!("  {}", note),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// `@@ -old +new @@` with the decision, marked when selected. Lines are
+/// 1-based, as in `git diff`.
+fn hunk_header(
+    hunk: &Hunk,
+    decision: HunkDecision,
+    selected: bool,
+    palette: &LocusPalette,
+) -> Line<'static> {
+    let range = |start: usize, len: usize| format!("{},{}", start + 1, len);
+    let header = format!(
+        "@@ -{} +{} @@",
+        range(hunk.old_start, hunk.old_lines().len()),
+        range(hunk.new_start, hunk.new_lines().len())
+    );
+    // A symbol as well as a color, so the decision reads without telling colors apart
+    let (symbol, label, label_style) = match decision {
+        HunkDecision::Pending => (
+            "·",
+            t("diff_review.pending"),
+            text_muted_style(palette.text_muted),
+        ),
+        HunkDecision::Approved => (
+            "✓",
+            t("diff_review.approved"),
+            success_style(palette.success),
+        ),
+        HunkDecision::Rejecting => ("…", t("diff_review.rejecting"), text_style(palette.warning)),
+        HunkDecision::Rejected => ("✗", t("diff_review.rejected"), danger_style(palette.danger)),
+    };
+    let (marker, header_style) = if selected {
+        (SELECTED_MARKER, text_style(palette.accent))
+    } else {
+        ("  ", text_muted_style(palette.text_muted))
+    };
+    Line::from(vec![
+        Span::raw(" "),
+        Span::styled(marker.to_string(), text_style(palette.accent)),
+        Span::styled(header, header_style),
+        Span::raw("  "),
+        Span::styled(format!("{} {}", symbol, label), label_style),
+    ])
+}
+
+fn line_style(kind: HunkLineKind, palette: &LocusPalette) -> Style {
+    match kind {
+        HunkLineKind::Context => text_muted_style(palette.text_muted),
+        HunkLineKind::Removed => danger_style(palette.danger),
+        HunkLineKind::Added => success_style(palette.success),
+    }
+}
+
+/// `-` for removed lines, `+` for added ones.
+fn sign(kind: HunkLineKind) -> char {
+    match kind {
+        HunkLineKind::Context => ' ',
+        HunkLineKind::Removed => '-',
+        HunkLineKind::Added => '+',
+    }
+}
+
+/// The hunk as one column: `-` for removed lines, `+` for added ones.
+pub fn unified_lines(hunk: &Hunk, palette: &LocusPalette) -> Vec<Line<'static>> {
+    hunk.lines
+        .iter()
+        .map(|line| {
+            let sign = sign(line.kind);
+            Line::from(vec![
+                Span::raw("     "),
+                Span::styled(
+                    format!("{} {}", sign, line.text),
+                    line_style(line.kind, palette),
+                ),
+            ])
+        })
+        .collect()
+}
+
+/// The hunk as two columns, old on the left and new on the right; removed
+/// lines are paired with the added lines that replaced them. Lines keep their
+/// `-`/`+` sign.
+pub fn side_by_side_lines(hunk: &Hunk, width: usize, palette: &LocusPalette) -> Vec<Line<'static>> {
+    let column = width.saturating_sub(5 + COLUMN_DIVIDER.chars().count()) / 2;
+    let cell = |line: Option<&locus_core::HunkLine>| match line {
+        Some(line) => Span::styled(
+            fit(&format!("{} {}", sign(line.kind), line.text), column),
+            line_style(line.kind, palette),
+        ),
+        None => Span::raw(" ".repeat(column)),
+    };
+    let mut rows = Vec::new();
+    let mut k = 0;
+    while k < hunk.lines.len() {
+        let line = &hunk.lines[k];
+        if line.kind == HunkLineKind::Context {
+            rows.push((Some(line), Some(line)));
+            k += 1;
+            continue;
+        }
+        let removed: Vec<_> = hunk.lines[k..]
+            .iter()
+            .take_while(|l| l.kind == HunkLineKind::Removed)
+            .collect();
+        let added: Vec<_> = hunk.lines[k + removed.len()..]
+            .iter()
+            .take_while(|l| l.kind == HunkLineKind::Added)
+            .collect();
+        for r in 0..removed.len().max(added.len()) {
+            rows.push((removed.get(r).copied(), added.get(r).copied()));
+        }
+        k += removed.len() + added.len();
+    }
+    rows.into_iter()
+        .map(|(old, new)| {
+            Line::from(vec![
+                Span::raw("     "),
+                cell(old),
+                Span::styled(
+                    COLUMN_DIVIDER.to_string(),
+                    text_muted_style(palette.border_variant),
+                ),
+                cell(new),
+            ])
+        })
+        .collect()
+}
+
+/// `text` cut or padded to exactly `width` characters.
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count > width {
+        let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            cut.push('…');
+        }
+        cut
+    } else {
+        format!("{}{}", text, " ".repeat(width - count))
+    }
+}
+
+fn draw_shortcuts(frame: &mut Frame, area: Rect, palette: &LocusPalette) {
+    let shortcuts = [
+        ("Esc", t("shortcut.back")),
+        ("←→", t("diff_review.shortcut.turns")),
+        ("n/p", t("diff_review.shortcut.hunks")),
+        ("a", t("diff_review.shortcut.approve")),
+        ("r", t("diff_review.shortcut.reject")),
+        ("v", t("diff_review.shortcut.layout")),
+        ("↑↓/jk", t("shortcut.scroll")),
+    ];
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::diff_review::hunks;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn hunks_render_unified_and_side_by_side() {
+        let palette = LocusPalette::locus_dark();
+        let hunk = &hunks("a\nb\nc\n", "a\nB\nc\nd\n")[0];
+        assert_eq!(
+            text(&unified_lines(hunk, &palette)),
+            ["       a", "     - b", "     + B", "       c", "     + d"]
+        );
+        assert_eq!(
+            text(&side_by_side_lines(hunk, 17, &palette)),
+            [
+                "       a  │   a ",
+                "     - b  │ + B ",
+                "       c  │   c ",
+                "          │ + d "
+            ]
+        );
+    }
+
+    #[test]
+    fn the_selected_hunk_is_marked() {
+        let palette = LocusPalette::locus_dark();
+        let mut review = TurnReview::new(
+            2,
+            vec![FileDiff {
+                path: "src/lib.rs".into(),
+                created: false,
+                deleted: false,
+                hunks: hunks("a\n", "b\n"),
+            }],
+        );
+        review.decisions[0][0] = HunkDecision::Approved;
+        let (lines, selected_at) = review_lines(&review, 0, false, 80, &palette);
+        let lines = text(&lines);
+        assert_eq!(lines[2], " ● src/lib.rs");
+        assert_eq!(selected_at, 3);
+        assert_eq!(lines[3], " ▶ @@ -1,1 +1,1 @@  ✓ approved");
+    }
+}
@@ -0,0 +1,115 @@
+//! Ctrl+E in the prompt: edit the draft in `$VISUAL` / `$EDITOR`, or with an
+//! empty prompt the last message sent, and put the result back in the prompt
+//! to send with Enter. The terminal is handed to the editor meanwhile (see
+//! `run.rs`).
+
+use std::io;
+use std::process::Command;
+
+use crate::i18n::tf;
+use crate::state::{ChatItem, TuiState};
+
+/// Used when neither `$VISUAL` nor `$EDITOR` is set.
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// The editor to run and its arguments, e.g. `code --wait`.
+pub fn editor_command() -> Vec<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|command| !command.is_empty())
+        .unwrap_or_else(|| vec![DEFAULT_EDITOR.to_string()])
+}
+
+/// Write `text` to a temporary file, wait for the editor to close it, and
+/// return what it holds then. Fails when the editor cannot start or exits
+/// unsuccessfully.
+pub fn open_in_editor(text: &str) -> io::Result<String> {
+    let path = std::env::temp_dir().join(format!("locus-prompt-{}.md", std::process::id()));
+    std::fs::write(&path, text)?;
+    let command = editor_command();
+    let status = Command::new(&command[0])
+        .args(&command[1..])
+        .arg(&path)
+        .status();
+    let edited = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(&path),
+        Ok(status) => Err(io::Error::other(format!(
+            "{} exited with {}",
+            command[0], status
+        ))),
+        Err(e) => Err(io::Error::new(e.kind(), format!("{}: {}", command[0], e))),
+    };
+    let _ = std::fs::remove_file(&path);
+    edited
+}
+
+/// Text Ctrl+E opens: the draft, or with none the last message sent.
+pub fn text_to_edit(state: &TuiState) -> String {
+    if !state.input_buffer.is_empty() {
+        return state.input_buffer.clone();
+    }
+    state
+        .messages
+        .iter()
+        .rev()
+        .find_map(|item| match item {
+            ChatItem::User(message) => Some(message.text.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Edit the prompt with `edit` (normally [open_in_editor]) and put the result
+/// in the input, cursor at the end. On failure the draft stays as it was and
+/// the status says why.
+pub fn edit_prompt(state: &mut TuiState, edit: impl FnOnce(&str) -> io::Result<String>) {
+    match edit(&text_to_edit(state)) {
+        Ok(edited) => {
+            state.input_buffer = edited.trim_end().to_string();
+            state.input_cursor = state.input_buffer.len();
+        }
+        Err(e) => {
+            state.status = tf("status.editor_failed", &[("error", &e)]);
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
+    }
+    state.cache_dirty = true;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_the_draft_or_else_the_last_message() {
+        let mut state = TuiState::new();
+        state.push_user("fix the add test".to_string(), None);
+        state.push_ai("Done.".to_string(), None);
+
+        edit_prompt(&mut state, |text| {
+            assert_eq!(text, "fix the add test");
+            Ok(format!("{}, then run cargo test\n", text))
+        });
+        assert_eq!(state.input_buffer, "fix the add test, then run cargo test");
+        assert_eq!(state.input_cursor, state.input_buffer.len());
+
+        edit_prompt(&mut state, |text| {
+            assert_eq!(text, "fix the add test, then run cargo test");
+            Err(io::Error::other("vi exited with exit status: 1"))
+        });
+        assert_eq!(state.input_buffer, "fix the add test, then run cargo test");
+        assert!(state.status.contains("exit status: 1"));
+    }
+}
@@ -0,0 +1,538 @@
+//! `:export [md|html|json]` in the chat input: write the chat of the session
+//! to a file under `.locus/exports/`, to attach an agent run to a PR or an
+//! issue. Messages are kept as they are, tool calls with their output folded
+//! away (`<details>` in Markdown and HTML) and file edits as unified diffs.
+//! Memory and meta-tool events of the chat are left out.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use locus_core::HunkLineKind;
+use locus_core::diff_review::hunks;
+use serde::Serialize;
+
+use crate::clipboard::{ToolOutputs, item_text};
+use crate::i18n::{t, tf};
+use crate::messages::tools::{ToolCallMessage, ToolCallStatus};
+use crate::state::{ChatItem, TuiState};
+
+/// Chat command to export the session.
+pub const EXPORT_COMMAND: &str = ":export";
+
+/// Where exports go, relative to the repo root.
+pub const EXPORTS_DIR: &str = ".locus/exports";
+
+/// Tool outputs longer than this are cut in the export.
+const MAX_OUTPUT_CHARS: usize = 20_000;
+
+/// The file format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Parse `:export [md|html|json]`; Markdown when no format is given. None
+/// for other input, Err with the usage for an unknown format.
+pub fn parse_export_command(line: &str) -> Option<Result<ExportFormat, String>> {
+    let rest = line.trim().strip_prefix(EXPORT_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(match rest.trim() {
+        "" | "md" | "markdown" => Ok(ExportFormat::Markdown),
+        "html" => Ok(ExportFormat::Html),
+        "json" => Ok(ExportFormat::Json),
+        _ => Err(t("status.export_usage").to_string()),
+    })
+}
+
+/// One entry of an export, in chat order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportEntry {
+    User {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timestamp: Option<String>,
+    },
+    Assistant {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timestamp: Option<String>,
+    },
+    Thinking {
+        text: String,
+    },
+    Tool {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        /// `running`, `ok` or `failed`.
+        status: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+    },
+    Diff {
+        path: String,
+        /// Unified diff of the edit.
+        diff: String,
+    },
+    Insights {
+        insight: String,
+        recommendation: String,
+    },
+    Error {
+        text: String,
+    },
+    Separator {
+        text: String,
+    },
+}
+
+/// The chat as export entries.
+pub fn export_entries(messages: &[ChatItem], outputs: &ToolOutputs) -> Vec<ExportEntry> {
+    let mut entries = Vec::new();
+    for item in messages {
+        match item {
+            ChatItem::User(m) => entries.push(ExportEntry::User {
+                text: m.text.clone(),
+                timestamp: m.timestamp.clone(),
+            }),
+            ChatItem::Ai(m) => entries.push(ExportEntry::Assistant {
+                text: m.text.clone(),
+                timestamp: m.timestamp.clone(),
+            }),
+            ChatItem::Think(m) => entries.push(ExportEntry::Thinking {
+                text: m.text.clone(),
+            }),
+            ChatItem::Tool(tool) => entries.push(tool_entry(tool, outputs)),
+            ChatItem::ToolGroup(tools) => {
+                entries.extend(tools.iter().map(|tool| tool_entry(tool, outputs)))
+            }
+            ChatItem::EditDiff(d) => entries.push(ExportEntry::Diff {
+                path: d.path.clone(),
+                diff: unified_diff(&d.old_content, &d.new_content),
+            }),
+            ChatItem::Insights(m) => entries.push(ExportEntry::Insights {
+                insight: m.insight.clone(),
+                recommendation: m.recommendation.clone(),
+            }),
+            ChatItem::Error(m) => entries.push(ExportEntry::Error {
+                text: m.text.clone(),
+            }),
+            ChatItem::Separator(text) => {
+                entries.push(ExportEntry::Separator { text: text.clone() })
+            }
+            ChatItem::MetaTool(_) | ChatItem::Memory(_) => {}
+        }
+    }
+    entries
+}
+
+fn tool_entry(tool: &ToolCallMessage, outputs: &ToolOutputs) -> ExportEntry {
+    let (status, duration_ms) = match &tool.status {
+        ToolCallStatus::Running => ("running", None),
+        ToolCallStatus::Done {
+            duration_ms,
+            success,
+        } => (if *success { "ok" } else { "failed" }, Some(*duration_ms)),
+        ToolCallStatus::Error { .. } => ("failed", None),
+    };
+    let output = match &tool.status {
+        ToolCallStatus::Error { message } => Some(message.clone()),
+        _ => tool.id.as_deref().and_then(|id| outputs.get(id)).map(cut),
+    };
+    ExportEntry::Tool {
+        name: tool.tool_name.clone(),
+        summary: tool.summary.clone(),
+        status,
+        duration_ms,
+        output,
+    }
+}
+
+/// `text` cut to [MAX_OUTPUT_CHARS], saying so.
+fn cut(text: &str) -> String {
+    match text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((end, _)) => format!("{}\n… (cut, {} bytes in all)", &text[..end], text.len()),
+        None => text.to_string(),
+    }
+}
+
+/// `before` → `after` as unified diff hunks.
+pub fn unified_diff(before: &str, after: &str) -> String {
+    let mut diff = String::new();
+    for hunk in hunks(before, after) {
+        let _ = writeln!(
+            diff,
+            "@@ -{},{} +{},{} @@",
+            hunk.old_start + 1,
+            hunk.old_lines().len(),
+            hunk.new_start + 1,
+            hunk.new_lines().len()
+        );
+        for line in &hunk.lines {
+            let marker = match line.kind {
+                HunkLineKind::Context => ' ',
+                HunkLineKind::Removed => '-',
+                HunkLineKind::Added => '+',
+            };
+            let _ = writeln!(diff, "{}{}", marker, line.text);
+        }
+    }
+    diff
+}
+
+fn tool_title(name: &str, summary: Option<&str>, status: &str, duration_ms: Option<u64>) -> String {
+    let mut title = name.to_string();
+    if let Some(summary) = summary {
+        let _ = write!(title, " · {}", summary);
+    }
+    let _ = match duration_ms {
+        Some(ms) => write!(title, " ({}, {:.1}s)", status, ms as f64 / 1000.0),
+        None => write!(title, " ({})", status),
+    };
+    title
+}
+
+/// A code fence longer than any run of backticks in `text`.
+fn fence(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// The entries as a Markdown document.
+pub fn to_markdown(entries: &[ExportEntry], title: &str) -> String {
+    let mut out = format!("# {}\n", title);
+    for entry in entries {
+        out.push('\n');
+        match entry {
+            ExportEntry::User { text, timestamp } | ExportEntry::Assistant { text, timestamp } => {
+                let who = if matches!(entry, ExportEntry::User { .. }) {
+                    "You"
+                } else {
+                    "Assistant"
+                };
+                match timestamp {
+                    Some(at) => out.push_str(&format!("### {} · {}\n\n", who, at)),
+                    None => out.push_str(&format!("### {}\n\n", who)),
+                }
+                out.push_str(text.trim_end());
+                out.push('\n');
+            }
+            ExportEntry::Thinking { text } => {
+                out.push_str("<details>\n<summary>Thinking</summary>\n\n");
+                out.push_str(text.trim_end());
+                out.push_str("\n\n</details>\n");
+            }
+            ExportEntry::Tool {
+                name,
+                summary,
+                status,
+                duration_ms,
+                output,
+            } => {
+                let title = tool_title(name, summary.as_deref(), status, *duration_ms);
+                match output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    Some(output) => {
+                        let fence = fence(output);
+                        out.push_str(&format!(
+                            "<details>\n<summary>🔧 {}</summary>\n\n{}\n{}\n{}\n\n</details>\n",
+                            title,
+                            fence,
+                            output.trim_end(),
+                            fence
+                        ));
+                    }
+                    None => out.push_str(&format!("🔧 {}\n", title)),
+                }
+            }
+            ExportEntry::Diff { path, diff } => {
+                let fence = fence(diff);
+                out.push_str(&format!(
+                    "**{}**\n\n{}diff\n{}{}\n",
+                    path, fence, diff, fence
+                ));
+            }
+            ExportEntry::Insights {
+                insight,
+                recommendation,
+            } => out.push_str(&format!("> {}\n>\n> {}\n", insight, recommendation)),
+            ExportEntry::Error { text } => out.push_str(&format!("> **Error:** {}\n", text)),
+            ExportEntry::Separator { text } => out.push_str(&format!("---\n\n_{}_\n", text)),
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;white-space:pre-wrap}\
+.msg{margin:1.25rem 0}.who{font-weight:600;color:#555}.user .text{background:#eef4ff;padding:.5rem .75rem;border-radius:6px}\
+.text{white-space:pre-wrap}details{margin:.5rem 0}summary{cursor:pointer;color:#555}\
+.add{color:#1a7f37}.del{color:#cf222e}.error{color:#cf222e}hr{margin:2rem 0}";
+
+/// The entries as a standalone HTML page.
+pub fn to_html(entries: &[ExportEntry], title: &str) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>{1}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title),
+        HTML_STYLE
+    );
+    for entry in entries {
+        match entry {
+            ExportEntry::User { text, timestamp } | ExportEntry::Assistant { text, timestamp } => {
+                let (class, who) = if matches!(entry, ExportEntry::User { .. }) {
+                    ("user", "You")
+                } else {
+                    ("assistant", "Assistant")
+                };
+                let at = timestamp
+                    .as_deref()
+                    .map(|at| format!(" · {}", escape_html(at)))
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "<div class=\"msg {}\"><div class=\"who\">{}{}</div><div class=\"text\">{}</div></div>",
+                    class,
+                    who,
+                    at,
+                    escape_html(text.trim_end())
+                );
+            }
+            ExportEntry::Thinking { text } => {
+                let _ = writeln!(
+                    out,
+                    "<details><summary>Thinking</summary><div class=\"text\">{}</div></details>",
+                    escape_html(text.trim_end())
+                );
+            }
+            ExportEntry::Tool {
+                name,
+                summary,
+                status,
+                duration_ms,
+                output,
+            } => {
+                let title =
+                    escape_html(&tool_title(name, summary.as_deref(), status, *duration_ms));
+                match output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    Some(output) => {
+                        let _ = writeln!(
+                            out,
+                            "<details><summary>🔧 {}</summary><pre>{}</pre></details>",
+                            title,
+                            escape_html(output.trim_end())
+                        );
+                    }
+                    None => {
+                        let _ = writeln!(out, "<p>🔧 {}</p>", title);
+                    }
+                }
+            }
+            ExportEntry::Diff { path, diff } => {
+                let lines: Vec<String> = diff
+                    .lines()
+                    .map(|line| match line.chars().next() {
+                        Some('+') => format!("<span class=\"add\">{}</span>", escape_html(line)),
+                        Some('-') => format!("<span class=\"del\">{}</span>", escape_html(line)),
+                        _ => escape_html(line),
+                    })
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "<p><strong>{}</strong></p><pre>{}</pre>",
+                    escape_html(path),
+                    lines.join("\n")
+                );
+            }
+            ExportEntry::Insights {
+                insight,
+                recommendation,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "<blockquote><p>{}</p><p>{}</p></blockquote>",
+                    escape_html(insight),
+                    escape_html(recommendation)
+                );
+            }
+            ExportEntry::Error { text } => {
+                let _ = writeln!(
+                    out,
+                    "<p class=\"error\"><strong>Error:</strong> {}</p>",
+                    escape_html(text)
+                );
+            }
+            ExportEntry::Separator { text } => {
+                let _ = writeln!(out, "<hr><p><em>{}</em></p>", escape_html(text));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// The entries as JSON: `{"title", "exported_at", "entries": [...]}`.
+pub fn to_json(entries: &[ExportEntry], title: &str) -> String {
+    let document = serde_json::json!({
+        "title": title,
+        "exported_at": chrono::Local::now().to_rfc3339(),
+        "entries": entries,
+    });
+    serde_json::to_string_pretty(&document).unwrap_or_default() + "\n"
+}
+
+/// Write the chat of `state` as `format` to a new file under [EXPORTS_DIR],
+/// named by time. Returns its path relative to `repo_root`.
+pub fn export_session(
+    state: &TuiState,
+    repo_root: &Path,
+    format: ExportFormat,
+) -> std::io::Result<String> {
+    let now = chrono::Local::now();
+    let title = format!("locus session · {}", now.format("%Y-%m-%d %H:%M"));
+    let entries = export_entries(&state.messages, &state.tool_outputs);
+    let content = match format {
+        ExportFormat::Markdown => to_markdown(&entries, &title),
+        ExportFormat::Html => to_html(&entries, &title),
+        ExportFormat::Json => to_json(&entries, &title),
+    };
+    let dir = repo_root.join(EXPORTS_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let stamp = now.format("%Y%m%d-%H%M%S");
+    let mut name = format!("{}-session.{}", stamp, format.extension());
+    let mut n = 2;
+    while dir.join(&name).exists() {
+        name = format!("{}-session-{}.{}", stamp, n, format.extension());
+        n += 1;
+    }
+    std::fs::write(dir.join(&name), content)?;
+    Ok(format!("{}/{}", EXPORTS_DIR, name))
+}
+
+/// Handle `line` if it is the export command: write the export and say where,
+/// or why not. Returns false for any other input.
+pub fn request_export(state: &mut TuiState, line: &str) -> bool {
+    let Some(parsed) = parse_export_command(line) else {
+        return false;
+    };
+    let copyable = state
+        .messages
+        .iter()
+        .any(|item| item_text(item, &state.tool_outputs).is_some());
+    let status = match parsed {
+        Err(usage) => usage,
+        Ok(_) if !copyable => t("status.nothing_to_export").to_string(),
+        Ok(format) => {
+            let repo_root = state
+                .file_finder
+                .root
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("."));
+            match export_session(state, &repo_root, format) {
+                Ok(path) => tf("status.exported", &[("path", &path)]),
+                Err(e) => tf("status.export_failed", &[("error", &e)]),
+            }
+        }
+    };
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_export_command() {
+        assert_eq!(
+            parse_export_command(":export"),
+            Some(Ok(ExportFormat::Markdown))
+        );
+        assert_eq!(
+            parse_export_command(" :export html "),
+            Some(Ok(ExportFormat::Html))
+        );
+        assert_eq!(
+            parse_export_command(":export json"),
+            Some(Ok(ExportFormat::Json))
+        );
+        assert!(matches!(parse_export_command(":export pdf"), Some(Err(_))));
+        assert_eq!(parse_export_command(":exports"), None);
+        assert_eq!(parse_export_command("export the docs"), None);
+    }
+
+    #[test]
+    fn exports_messages_tools_and_diffs() {
+        let mut state = TuiState::new();
+        state.push_user("fix the add test".to_string(), None);
+        state.messages.push(ChatItem::Tool(ToolCallMessage::done(
+            Some("t1".to_string()),
+            "bash",
+            1200,
+            false,
+            Some("cargo test".to_string()),
+            None,
+        )));
+        state.tool_outputs.record(
+            "t1",
+            &serde_json::json!({"stdout": "test add ... FAILED\n```"}),
+        );
+        state.messages.push(ChatItem::EditDiff(
+            crate::messages::tools::EditDiffMessage {
+                path: "src/lib.rs".to_string(),
+                old_content: "fn add() {\n    1 - 1\n}\n".to_string(),
+                new_content: "fn add() {\n    1 + 1\n}\n".to_string(),
+                tool_id: None,
+            },
+        ));
+        state.push_ai("Fixed <it>.".to_string(), None);
+
+        let entries = export_entries(&state.messages, &state.tool_outputs);
+        let markdown = to_markdown(&entries, "session");
+        assert!(markdown.contains("### You\n\nfix the add test\n"));
+        assert!(markdown.contains("<summary>🔧 bash · cargo test (failed, 1.2s)</summary>"));
+        // A fence the output's own backticks can't close
+        assert!(markdown.contains("````\ntest add ... FAILED\n```\n````"));
+        assert!(markdown.contains("@@ -1,3 +1,3 @@\n fn add() {\n-    1 - 1\n+    1 + 1\n }\n"));
+
+        let html = to_html(&entries, "session");
+        assert!(html.contains("Fixed &lt;it&gt;."));
+        assert!(html.contains("<span class=\"add\">+    1 + 1</span>"));
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&entries, "session")).unwrap();
+        assert_eq!(json["entries"][1]["kind"], "tool");
+        assert_eq!(json["entries"][1]["status"], "failed");
+        assert_eq!(json["entries"][3]["kind"], "assistant");
+
+        let root = std::env::temp_dir().join(format!("locus-export-{}", std::process::id()));
+        let path = export_session(&state, &root, ExportFormat::Html).unwrap();
+        assert!(path.starts_with(".locus/exports/") && path.ends_with("-session.html"));
+        assert!(root.join(&path).exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,335 @@
+//! File finder in the prompt: typing `@` or a path-like word (one with a
+//! `/`) pops up the repo's files that fuzzy-match it; ↑↓ pick one, Tab puts
+//! its relative path in the prompt and Esc closes the popup. Files are listed
+//! once per session by a gitignore-aware walk of the repo.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::i18n::t;
+use crate::layouts::{background_style, border_style, text_muted_style, text_style};
+use crate::state::TuiState;
+use crate::utils::fuzzy_filter;
+
+/// Paths listed at most; the walk stops there in huge repos.
+pub const MAX_FILES: usize = 20_000;
+
+/// Matches shown in the popup.
+const MAX_MATCHES: usize = 8;
+
+/// The popup over the prompt: the word it completes and the files matching it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinderPopup {
+    /// Byte offset of the word in the input.
+    pub start: usize,
+    /// The word is an `@` mention; the `@` is kept when completing.
+    pub mention: bool,
+    pub query: String,
+    pub matches: Vec<String>,
+    pub selected: usize,
+}
+
+/// Files of the repo, listed on first use, and the open popup.
+#[derive(Debug, Clone, Default)]
+pub struct FileFinder {
+    /// Repo to list; None for the current directory.
+    pub root: Option<PathBuf>,
+    files: Option<Vec<String>>,
+    pub popup: Option<FinderPopup>,
+    /// Start of the word whose popup Esc closed, so typing on doesn't reopen it.
+    dismissed: Option<usize>,
+}
+
+impl FileFinder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finder over the files of `root`.
+    pub fn with_root(root: PathBuf) -> Self {
+        Self {
+            root: Some(root),
+            ..Self::default()
+        }
+    }
+
+    /// Finder over `files` instead of a walk (preview, tests).
+    pub fn with_files(files: Vec<String>) -> Self {
+        Self {
+            files: Some(files),
+            ..Self::default()
+        }
+    }
+
+    fn files(&mut self) -> &[String] {
+        if self.files.is_none() {
+            let root = self
+                .root
+                .clone()
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_else(|| PathBuf::from("."));
+            self.files = Some(list_files(&root));
+        }
+        self.files.as_deref().unwrap_or_default()
+    }
+
+    /// Open, refresh or close the popup for the word before the cursor.
+    pub fn update(&mut self, input: &str, cursor: usize) {
+        let Some((start, mention, query)) = path_word(input, cursor) else {
+            self.popup = None;
+            self.dismissed = None;
+            return;
+        };
+        if self.dismissed == Some(start) {
+            return;
+        }
+        if let Some(popup) = &self.popup
+            && popup.start == start
+            && popup.query == query
+        {
+            return;
+        }
+        let matches: Vec<String> =
+            fuzzy_filter(self.files().iter().map(String::as_str), query, MAX_MATCHES)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+        self.popup = (!matches.is_empty()).then(|| FinderPopup {
+            start,
+            mention,
+            query: query.to_string(),
+            matches,
+            selected: 0,
+        });
+    }
+
+    /// Select an earlier (`delta < 0`) or later match.
+    pub fn step(&mut self, delta: isize) {
+        if let Some(popup) = self.popup.as_mut() {
+            let last = popup.matches.len().saturating_sub(1);
+            popup.selected = popup.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Close the popup until the word changes.
+    pub fn dismiss(&mut self) {
+        if let Some(popup) = self.popup.take() {
+            self.dismissed = Some(popup.start);
+        }
+    }
+}
+
+/// The word ending at the cursor when it names a path: `@` and what follows
+/// (maybe nothing yet), or a word with a `/` not at its start. Returns its
+/// start, whether it is a mention, and the text to match.
+pub fn path_word(input: &str, cursor: usize) -> Option<(usize, bool, &str)> {
+    let after = input.get(cursor..)?;
+    if after.chars().next().is_some_and(|c| !c.is_whitespace()) {
+        return None;
+    }
+    let before = &input[..cursor];
+    let start = before.rfind(char::is_whitespace).map_or(0, |i| {
+        i + before[i..].chars().next().map_or(1, char::len_utf8)
+    });
+    let word = &before[start..];
+    if let Some(query) = word.strip_prefix('@') {
+        return (!query.contains('@')).then_some((start, true, query));
+    }
+    (word.contains('/') && !word.starts_with('/') && !word.contains("://"))
+        .then_some((start, false, word))
+}
+
+/// Relative paths under `root`, folders ending in `/`, skipping what
+/// `.gitignore` and hidden-file rules leave out; sorted, at most [MAX_FILES].
+pub fn list_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    for entry in ignore::WalkBuilder::new(root)
+        .require_git(false)
+        .build()
+        .flatten()
+    {
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let mut path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            path.push('/');
+        }
+        files.push(path);
+        if files.len() >= MAX_FILES {
+            break;
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Tab with the popup open: replace the word with the selected path (a
+/// space after files, so typing goes on; none after folders, to go deeper).
+/// Returns false when no popup is open.
+pub fn complete_file(state: &mut TuiState) -> bool {
+    let Some(popup) = state.file_finder.popup.take() else {
+        return false;
+    };
+    let path = &popup.matches[popup.selected];
+    let mut completed = if popup.mention {
+        format!("@{}", path)
+    } else {
+        path.clone()
+    };
+    if !path.ends_with('/') {
+        completed.push(' ');
+    }
+    state
+        .input_buffer
+        .replace_range(popup.start..state.input_cursor, &completed);
+    state.input_cursor = popup.start + completed.len();
+    state
+        .file_finder
+        .update(&state.input_buffer, state.input_cursor);
+    state.needs_redraw = true;
+    true
+}
+
+/// Draw the popup just above the prompt, `footer_height` rows from the bottom.
+pub fn draw_file_finder(frame: &mut Frame, state: &TuiState, area: Rect, footer_height: u16) {
+    let Some(popup) = state.file_finder.popup.as_ref() else {
+        return;
+    };
+    let palette = &state.palette;
+    let lines: Vec<Line<'static>> = popup
+        .matches
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let selected = i == popup.selected;
+            let style = if selected {
+                text_style(palette.accent)
+            } else {
+                text_style(palette.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { "› " } else { "  " }.to_string(), style),
+                Span::styled(path.clone(), style),
+            ])
+        })
+        .collect();
+
+    let width = popup
+        .matches
+        .iter()
+        .map(|p| p.len() as u16 + 6)
+        .max()
+        .unwrap_or(0)
+        .clamp(30, area.width.saturating_sub(4).max(1));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(footer_height));
+    let rect = Rect {
+        x: area.x + 2,
+        y: (area.y + area.height)
+            .saturating_sub(footer_height)
+            .saturating_sub(height),
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("file_finder.title")))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {} ", t("file_finder.hint")),
+            text_muted_style(palette.text_muted),
+        )))
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_words_are_mentions_or_contain_a_slash() {
+        assert_eq!(path_word("look at @src/li", 15), Some((8, true, "src/li")));
+        assert_eq!(path_word("@", 1), Some((0, true, "")));
+        assert_eq!(
+            path_word("open crates/tui", 15),
+            Some((5, false, "crates/tui"))
+        );
+        assert_eq!(path_word("/review", 7), None);
+        assert_eq!(path_word("see https://x.io/a", 18), None);
+        assert_eq!(path_word("mail me@host", 12), None);
+        // The cursor must end the word.
+        assert_eq!(path_word("@src more", 3), None);
+    }
+
+    #[test]
+    fn tab_puts_the_selected_path_in_the_prompt() {
+        let mut state = TuiState::new();
+        state.file_finder = FileFinder::with_files(vec![
+            "docs/".into(),
+            "docs/style.md".into(),
+            "src/lib.rs".into(),
+            "src/main.rs".into(),
+        ]);
+        state.input_buffer = "explain @srcli".into();
+        state.input_cursor = state.input_buffer.len();
+        state
+            .file_finder
+            .update(&state.input_buffer, state.input_cursor);
+        let popup = state.file_finder.popup.as_ref().unwrap();
+        assert_eq!(popup.matches, ["src/lib.rs"]);
+
+        assert!(complete_file(&mut state));
+        assert_eq!(state.input_buffer, "explain @src/lib.rs ");
+        assert_eq!(state.input_cursor, state.input_buffer.len());
+        assert!(state.file_finder.popup.is_none());
+        assert!(!complete_file(&mut state));
+
+        // Folders keep the popup going inside them.
+        state.input_buffer = "see do".into();
+        state.input_cursor = 6;
+        state.file_finder.update("see do", 6);
+        assert!(state.file_finder.popup.is_none());
+        state.input_buffer = "see @do".into();
+        state.input_cursor = 7;
+        state.file_finder.update("see @do", 7);
+        complete_file(&mut state);
+        assert_eq!(state.input_buffer, "see @docs/");
+        assert_eq!(
+            state.file_finder.popup.as_ref().unwrap().matches,
+            ["docs/", "docs/style.md"]
+        );
+
+        state.file_finder.dismiss();
+        state.file_finder.update("see @docs/s", 11);
+        assert!(state.file_finder.popup.is_none());
+    }
+
+    #[test]
+    fn list_files_skips_ignored_paths() {
+        let dir = std::env::temp_dir().join(format!("locus-files-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        std::fs::write(dir.join("target/out"), "").unwrap();
+        std::fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        assert_eq!(list_files(&dir), ["src/", "src/lib.rs"]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
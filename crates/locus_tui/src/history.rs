@@ -0,0 +1,292 @@
+//! Prompt history: Up/Down in the prompt step through what was sent before,
+//! like a shell, and Ctrl+R searches it backwards. Prompts are kept in the
+//! project's `.locus/locus.db` (see `locus_core::db::record_prompt`), so they
+//! carry over to later sessions.
+
+use std::path::{Path, PathBuf};
+
+use locus_core::db::{self, MAX_PROMPT_HISTORY};
+use ratatui::text::{Line, Span};
+
+use crate::i18n::t;
+use crate::layouts::{INPUT_ICON, text_muted_style, text_style};
+use crate::logs::state::contains_ignore_case;
+use crate::state::TuiState;
+use crate::theme::LocusPalette;
+
+/// An open Ctrl+R search.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistorySearch {
+    pub query: String,
+    /// Index into the history of the match shown; None when nothing matches.
+    pub matched: Option<usize>,
+}
+
+/// Prompts sent, oldest first, and where Up/Down or Ctrl+R are in them.
+#[derive(Debug, Clone, Default)]
+pub struct PromptHistory {
+    pub entries: Vec<String>,
+    /// Project whose DB the history is saved to; None keeps it in memory.
+    repo_root: Option<PathBuf>,
+    /// Entry recalled with Up/Down; None when not browsing.
+    index: Option<usize>,
+    /// What was in the prompt before browsing, given back past the newest entry.
+    draft: String,
+    pub search: Option<HistorySearch>,
+}
+
+impl PromptHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// History saved for `repo_root`, kept up to date as prompts are sent.
+    /// Starts empty when the DB can't be read.
+    pub fn load(repo_root: &Path) -> Self {
+        let entries = db::recent_prompts(repo_root, MAX_PROMPT_HISTORY).unwrap_or_else(|e| {
+            tracing::warn!("prompt history unavailable: {}", e);
+            Vec::new()
+        });
+        Self {
+            entries,
+            repo_root: Some(repo_root.to_path_buf()),
+            ..Self::default()
+        }
+    }
+
+    /// Add a sent prompt (not a repeat of the last) and stop browsing.
+    pub fn record(&mut self, prompt: &str) {
+        self.index = None;
+        self.draft.clear();
+        if prompt.is_empty() || self.entries.last().is_some_and(|last| last == prompt) {
+            return;
+        }
+        self.entries.push(prompt.to_string());
+        if self.entries.len() > MAX_PROMPT_HISTORY {
+            self.entries.remove(0);
+        }
+        if let Some(root) = &self.repo_root {
+            let ts = chrono::Utc::now().timestamp();
+            if let Err(e) = db::record_prompt(root, prompt, ts) {
+                tracing::warn!("could not save prompt history: {}", e);
+            }
+        }
+    }
+
+    /// The entry before the one shown (Up); `current` is kept to come back to.
+    /// None when there is nothing older.
+    pub fn older(&mut self, current: &str) -> Option<String> {
+        let index = match self.index {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(index) => index.checked_sub(1)?,
+        };
+        self.index = Some(index);
+        Some(self.entries[index].clone())
+    }
+
+    /// The entry after the one shown (Down), or the draft past the newest.
+    /// None when not browsing.
+    pub fn newer(&mut self) -> Option<String> {
+        let index = self.index?;
+        if index + 1 < self.entries.len() {
+            self.index = Some(index + 1);
+            Some(self.entries[index + 1].clone())
+        } else {
+            self.index = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Open a Ctrl+R search, or when one is open look for an older match.
+    pub fn search_older(&mut self) {
+        match self.search.take() {
+            None => self.search = Some(HistorySearch::default()),
+            Some(search) => {
+                let before = search.matched.unwrap_or(self.entries.len());
+                let matched = self.find(&search.query, before).or(search.matched);
+                self.search = Some(HistorySearch { matched, ..search });
+            }
+        }
+    }
+
+    pub fn search_insert(&mut self, c: char) {
+        if let Some(search) = self.search.as_mut() {
+            search.query.push(c);
+        }
+        self.refresh_search();
+    }
+
+    pub fn search_backspace(&mut self) {
+        if let Some(search) = self.search.as_mut() {
+            search.query.pop();
+        }
+        self.refresh_search();
+    }
+
+    /// Close the search and return the match to put in the prompt.
+    pub fn search_accept(&mut self) -> Option<String> {
+        let search = self.search.take()?;
+        self.index = None;
+        search.matched.map(|index| self.entries[index].clone())
+    }
+
+    /// Close the search, leaving the prompt as it was.
+    pub fn search_cancel(&mut self) {
+        self.search = None;
+    }
+
+    /// The newest match of the query after it changed.
+    fn refresh_search(&mut self) {
+        let Some(query) = self.search.as_ref().map(|s| s.query.clone()) else {
+            return;
+        };
+        let matched = if query.is_empty() {
+            None
+        } else {
+            self.find(&query, self.entries.len())
+        };
+        if let Some(search) = self.search.as_mut() {
+            search.matched = matched;
+        }
+    }
+
+    /// Newest entry before `before` containing `query`, ignoring case.
+    fn find(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|entry| contains_ignore_case(entry, query))
+    }
+}
+
+/// Up: put the prompt sent before the one shown in the input.
+pub fn recall_previous(state: &mut TuiState) {
+    if let Some(prompt) = state.history.older(&state.input_buffer) {
+        set_input(state, prompt);
+    }
+}
+
+/// Down: put the prompt sent after the one shown in the input, or the draft.
+pub fn recall_next(state: &mut TuiState) {
+    if let Some(prompt) = state.history.newer() {
+        set_input(state, prompt);
+    }
+}
+
+/// Enter during Ctrl+R: put the match in the input to edit or send.
+pub fn accept_search(state: &mut TuiState) {
+    if let Some(prompt) = state.history.search_accept() {
+        set_input(state, prompt);
+    }
+    state.needs_redraw = true;
+}
+
+fn set_input(state: &mut TuiState, text: String) {
+    state.input_buffer = text;
+    state.input_cursor = state.input_buffer.len();
+    state.needs_redraw = true;
+}
+
+/// The prompt line during a Ctrl+R search: the query, then the match.
+pub fn search_line(
+    history: &PromptHistory,
+    search: &HistorySearch,
+    palette: &LocusPalette,
+) -> Line<'static> {
+    let found = match search.matched {
+        Some(index) => Span::styled(
+            history.entries[index].replace('\n', " "),
+            text_style(palette.text),
+        ),
+        None if search.query.is_empty() => Span::styled(
+            t("history.search_hint").to_string(),
+            text_muted_style(palette.text_placeholder),
+        ),
+        None => Span::styled(
+            t("history.no_match").to_string(),
+            text_muted_style(palette.text_placeholder),
+        ),
+    };
+    Line::from(vec![
+        Span::styled(INPUT_ICON.to_string(), text_style(palette.accent)),
+        Span::styled(
+            format!("{} ", t("history.search")),
+            text_muted_style(palette.text_muted),
+        ),
+        Span::styled(search.query.clone(), text_style(palette.accent)),
+        Span::styled("▏ ".to_string(), text_style(palette.accent)),
+        found,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(entries: &[&str]) -> PromptHistory {
+        PromptHistory {
+            entries: entries.iter().map(|e| e.to_string()).collect(),
+            ..PromptHistory::new()
+        }
+    }
+
+    #[test]
+    fn up_and_down_walk_the_history_and_back_to_the_draft() {
+        let mut history = history(&["fix the add test", "run cargo test"]);
+        assert_eq!(
+            history.older("half typed").as_deref(),
+            Some("run cargo test")
+        );
+        assert_eq!(
+            history.older("run cargo test").as_deref(),
+            Some("fix the add test")
+        );
+        assert_eq!(history.older("fix the add test"), None);
+        assert_eq!(history.newer().as_deref(), Some("run cargo test"));
+        assert_eq!(history.newer().as_deref(), Some("half typed"));
+        assert_eq!(history.newer(), None);
+
+        history.record("run cargo test");
+        history.record("explain lib.rs");
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.older("").as_deref(), Some("explain lib.rs"));
+        assert!(PromptHistory::new().older("x").is_none());
+    }
+
+    #[test]
+    fn ctrl_r_finds_older_matches() {
+        let mut history = history(&["fix the add test", "run cargo test", "Fix clippy"]);
+        history.search_older();
+        for c in "fix".chars() {
+            history.search_insert(c);
+        }
+        assert_eq!(history.search.as_ref().unwrap().matched, Some(2));
+        history.search_older();
+        assert_eq!(history.search.as_ref().unwrap().matched, Some(0));
+        // No older match: stays on the oldest.
+        history.search_older();
+        assert_eq!(history.search.as_ref().unwrap().matched, Some(0));
+        assert_eq!(history.search_accept().as_deref(), Some("fix the add test"));
+        assert!(history.search.is_none());
+
+        history.search_older();
+        history.search_insert('z');
+        assert_eq!(history.search.as_ref().unwrap().matched, None);
+        history.search_cancel();
+        assert!(history.search.is_none());
+    }
+
+    #[test]
+    fn recalled_prompts_go_to_the_input() {
+        let mut state = TuiState::new();
+        state.history = history(&["fix the add test"]);
+        state.input_buffer = "dr".into();
+        recall_previous(&mut state);
+        assert_eq!(state.input_buffer, "fix the add test");
+        assert_eq!(state.input_cursor, state.input_buffer.len());
+        recall_next(&mut state);
+        assert_eq!(state.input_buffer, "dr");
+    }
+}
@@ -0,0 +1,295 @@
+//! Message catalog for user-facing TUI copy.
+//!
+//! Every header, shortcut hint, popup and status message is looked up by key
+//! with [t] (or [tf] for `{placeholder}` arguments) instead of being written inline.
+//!
+//! - Catalogs are flat JSON objects: `{ "shortcut.send": "send", ... }`
+//! - `en` is built in and is the fallback for any key a locale does not define
+//! - The locale comes from `LOCUS_LOCALE` (set with `locus config locale <code>`,
+//!   stored in the config table like the other `LOCUS_*` keys)
+//! - Translations live in `src/i18n/<code>.json` and are listed in [BUILTIN_LOCALES];
+//!   `~/.locus/locales/<code>.json` is layered on top, so a translation can be
+//!   tried without rebuilding
+//! - The `keys` pseudo-locale renders every key as itself, to spot untranslated
+//!   copy and to write tests that do not depend on wording
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Config / env key holding the locale code.
+pub const LOCALE_ENV: &str = "LOCUS_LOCALE";
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Pseudo-locale that shows message keys instead of copy.
+pub const KEYS_LOCALE: &str = "keys";
+
+/// Locales compiled into the binary, as (code, catalog JSON).
+pub const BUILTIN_LOCALES: &[(&str, &str)] = &[("en", include_str!("en.json"))];
+
+/// Messages for one locale, with `en` filled in for missing keys.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, &'static str>,
+}
+
+impl Catalog {
+    /// Catalog for `locale`: built-in `en`, then the built-in catalog for `locale`,
+    /// then `<user_dir>/<locale>.json` when `user_dir` is given and the file exists.
+    pub fn load(locale: &str, user_dir: Option<&Path>) -> Self {
+        let locale = normalize_locale(locale);
+        let mut messages = HashMap::new();
+        merge(&mut messages, builtin(DEFAULT_LOCALE).unwrap_or("{}"));
+        if locale != DEFAULT_LOCALE {
+            if let Some(json) = builtin(&locale) {
+                merge(&mut messages, json);
+            }
+            if let Some(json) = user_dir
+                .map(|dir| dir.join(format!("{}.json", locale)))
+                .and_then(|path| std::fs::read_to_string(path).ok())
+            {
+                merge(&mut messages, &json);
+            }
+        }
+        Self { locale, messages }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Message for `key`; the key itself when no catalog defines it (or in the `keys` locale).
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        if self.locale == KEYS_LOCALE {
+            return key;
+        }
+        self.messages.get(key).copied().unwrap_or(key)
+    }
+}
+
+fn builtin(locale: &str) -> Option<&'static str> {
+    BUILTIN_LOCALES
+        .iter()
+        .find(|(code, _)| *code == locale)
+        .map(|(_, json)| *json)
+}
+
+/// Add the string entries of a flat JSON catalog; malformed files are logged and skipped.
+fn merge(messages: &mut HashMap<String, &'static str>, json: &str) {
+    match serde_json::from_str::<HashMap<String, serde_json::Value>>(json) {
+        Ok(entries) => {
+            for (key, value) in entries {
+                if let serde_json::Value::String(text) = value {
+                    // Catalogs live for the whole process; leaking gives `'static` spans.
+                    messages.insert(key, Box::leak(text.into_boxed_str()));
+                }
+            }
+        }
+        Err(e) => eprintln!("locus: ignoring invalid message catalog: {}", e),
+    }
+}
+
+/// `de_DE.UTF-8` → `de-de`; empty → `en`.
+fn normalize_locale(locale: &str) -> String {
+    let code = locale
+        .split('.')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .replace('_', "-")
+        .to_lowercase();
+    if code.is_empty() {
+        DEFAULT_LOCALE.to_string()
+    } else if builtin(&code).is_none() && code != KEYS_LOCALE {
+        // `pt-br` falls back to a built-in `pt` when there is no exact match.
+        let language = code.split('-').next().unwrap_or(&code);
+        if builtin(language).is_some() {
+            language.to_string()
+        } else {
+            code
+        }
+    } else {
+        code
+    }
+}
+
+/// Directory for user-provided catalogs: `~/.locus/locales`.
+pub fn user_locales_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".locus").join("locales"))
+}
+
+/// Whether `locale` has a built-in or user catalog (or is the `keys` pseudo-locale).
+pub fn is_available(locale: &str) -> bool {
+    let code = normalize_locale(locale);
+    code == KEYS_LOCALE
+        || builtin(&code).is_some()
+        || user_locales_dir().is_some_and(|dir| dir.join(format!("{}.json", code)).exists())
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Select the locale for this process. Only the first call has an effect; lookups
+/// before any call use `LOCUS_LOCALE`.
+pub fn init(locale: &str) {
+    let _ = CATALOG.set(Catalog::load(locale, user_locales_dir().as_deref()));
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        let locale = std::env::var(LOCALE_ENV).unwrap_or_default();
+        Catalog::load(&locale, user_locales_dir().as_deref())
+    })
+}
+
+/// Locale in use.
+pub fn current_locale() -> &'static str {
+    catalog().locale()
+}
+
+/// Look up `key` in the active catalog.
+pub fn t(key: &'static str) -> &'static str {
+    catalog().get(key)
+}
+
+/// Look up `key` and replace `{name}` placeholders with `args`.
+pub fn tf(key: &'static str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    fill(t(key), args)
+}
+
+fn fill(template: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog_keys(json: &str) -> Vec<String> {
+        serde_json::from_str::<HashMap<String, String>>(json)
+            .expect("catalog must be a flat object of strings")
+            .into_keys()
+            .collect()
+    }
+
+    #[test]
+    fn builtin_catalogs_are_flat_and_cover_en() {
+        let en = catalog_keys(builtin(DEFAULT_LOCALE).unwrap());
+        assert!(!en.is_empty());
+        for (code, json) in BUILTIN_LOCALES {
+            for key in catalog_keys(json) {
+                assert!(en.contains(&key), "{}: key {} is not in en.json", code, key);
+            }
+        }
+    }
+
+    /// Every `t("...")` / `tf("...")` in the crate must name a key in en.json.
+    #[test]
+    fn every_key_used_in_source_exists() {
+        let en = catalog_keys(builtin(DEFAULT_LOCALE).unwrap());
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut missing = Vec::new();
+        let mut dirs = vec![src];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension().is_none_or(|e| e != "rs") {
+                    continue;
+                }
+                let content = std::fs::read_to_string(&path).unwrap();
+                for key in used_keys(&content) {
+                    if !en.contains(&key) {
+                        missing.push(format!("{}: {}", path.display(), key));
+                    }
+                }
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "missing catalog keys:\n{}",
+            missing.join("\n")
+        );
+    }
+
+    /// Keys held in constants rather than passed as literals.
+    #[test]
+    fn constant_keys_exist() {
+        use crate::layouts::{HEADER_STATUS_READY, HEADER_TAGLINE, HEADER_TITLE};
+        let catalog = Catalog::load(DEFAULT_LOCALE, None);
+        for key in [HEADER_TITLE, HEADER_STATUS_READY, HEADER_TAGLINE] {
+            assert_ne!(catalog.get(key), key);
+        }
+        for cursor in 0..2 {
+            assert!(!crate::setup::graph_choice_label(cursor).starts_with("setup."));
+        }
+    }
+
+    /// Keys passed as string literals to `t(` / `tf(`.
+    fn used_keys(content: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        for call in ["t(\"", "tf(\""] {
+            let mut rest = content;
+            while let Some(pos) = rest.find(call) {
+                let before = rest[..pos].chars().next_back();
+                rest = &rest[pos + call.len()..];
+                if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                if let Some(end) = rest.find('"') {
+                    let key = &rest[..end];
+                    if key.contains('.')
+                        && key
+                            .split('.')
+                            .all(|part| !part.is_empty() && !part.contains(' '))
+                    {
+                        keys.push(key.to_string());
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn missing_keys_fall_back_to_en_then_key() {
+        let dir = std::env::temp_dir().join(format!("locus-i18n-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("xx.json"), r#"{"shortcut.send": "senden"}"#).unwrap();
+
+        let catalog = Catalog::load("xx_XX.UTF-8", Some(&dir));
+        assert_eq!(catalog.locale(), "xx-xx");
+        // Region-specific code without a file: only en.
+        assert_eq!(catalog.get("shortcut.send"), "send");
+
+        let catalog = Catalog::load("xx", Some(&dir));
+        assert_eq!(catalog.get("shortcut.send"), "senden");
+        assert_eq!(catalog.get("shortcut.quit"), "quit");
+        assert_eq!(catalog.get("no.such.key"), "no.such.key");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn keys_locale_shows_keys() {
+        let catalog = Catalog::load(KEYS_LOCALE, None);
+        assert_eq!(catalog.get("shortcut.send"), "shortcut.send");
+        assert_eq!(Catalog::load("", None).locale(), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn fill_replaces_placeholders() {
+        assert_eq!(
+            fill("call {n}/{total}", &[("n", &2), ("total", &"5")]),
+            "call 2/5"
+        );
+        assert_eq!(fill("no args", &[]), "no args");
+    }
+}
@@ -14,6 +14,7 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 use super::style::{background_style, border_style, text_muted_style, text_style};
+use crate::i18n::t;
 use crate::theme::LocusPalette;
 use crate::utils::horizontal_padding;
 
@@ -34,8 +35,8 @@ impl HeadLayout {
     }
 }
 
-/// Short descriptor shown beside the app title in the header.
-pub const HEADER_TAGLINE: &str = "terminal workspace";
+/// Catalog key for the short descriptor shown beside the app title in the header.
+pub const HEADER_TAGLINE: &str = "header.tagline";
 
 /// Build the top header line: app title, accent dot, and muted tagline.
 pub fn header_title_line(title: &str, palette: &LocusPalette, width: u16) -> Line<'static> {
@@ -43,7 +44,8 @@ pub fn header_title_line(title: &str, palette: &LocusPalette, width: u16) -> Lin
     let accent_style = text_style(palette.accent);
     let tagline_style = text_muted_style(palette.text_muted);
     let title_width = UnicodeWidthStr::width(title);
-    let tagline_width = UnicodeWidthStr::width(HEADER_TAGLINE);
+    let tagline = t(HEADER_TAGLINE);
+    let tagline_width = UnicodeWidthStr::width(tagline);
     let can_show_tagline = width as usize > title_width + tagline_width + 8;
 
     let mut spans = vec![
@@ -54,7 +56,7 @@ pub fn header_title_line(title: &str, palette: &LocusPalette, width: u16) -> Lin
 
     if can_show_tagline {
         spans.push(Span::styled("  ·  ".to_string(), tagline_style));
-        spans.push(Span::styled(HEADER_TAGLINE.to_string(), tagline_style));
+        spans.push(Span::styled(tagline, tagline_style));
     }
 
     Line::from(spans)
@@ -86,7 +88,15 @@ pub fn header_status_line(
 ) -> Line<'static> {
     let left_style = text_muted_style(palette.text_muted);
     let left = section.to_string();
-    let badge_text = format!(" ● {} ", status);
+    // The symbol tells the state apart without relying on its color
+    let symbol = if has_error {
+        "✗"
+    } else if is_streaming {
+        "●"
+    } else {
+        "✓"
+    };
+    let badge_text = format!(" {} {} ", symbol, status);
     let left_width = UnicodeWidthStr::width(left.as_str());
     let badge_width = UnicodeWidthStr::width(badge_text.as_str());
     let gap = (width as usize).saturating_sub(left_width + badge_width);
@@ -109,11 +119,11 @@ pub fn block_for_head(_layout: &HeadLayout, palette: &LocusPalette) -> Block<'st
         .style(background_style(palette.status_bar_background))
 }
 
-/// Default title shown in the header.
-pub const HEADER_TITLE: &str = "locus.codes";
+/// Catalog key for the default title shown in the header.
+pub const HEADER_TITLE: &str = "header.title";
 
-/// Default status when none is set.
-pub const HEADER_STATUS_READY: &str = "Ready";
+/// Catalog key for the default status when none is set.
+pub const HEADER_STATUS_READY: &str = "header.status.ready";
 
 /// Draw the header: two-line block (title line, then border), status with colored dot.
 pub fn render_header(
@@ -176,7 +186,7 @@ mod tests {
         assert!(
             line.spans
                 .iter()
-                .any(|s| s.content.contains("terminal workspace"))
+                .any(|s| s.content.contains(t(HEADER_TAGLINE)))
         );
     }
 
@@ -189,6 +199,8 @@ mod tests {
                 .iter()
                 .any(|s| s.content.contains("main workspace"))
         );
-        assert!(line.spans.iter().any(|s| s.content.contains("Ready")));
+        assert!(line.spans.iter().any(|s| s.content.contains("✓ Ready")));
+        let line = header_status_line("main workspace", "Error", false, true, &palette, 80);
+        assert!(line.spans.iter().any(|s| s.content.contains("✗ Error")));
     }
 }
@@ -9,9 +9,10 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
+use crate::i18n::{t, tf};
 use crate::layouts::{
-    background_style, border_focused_style, border_style, danger_style, success_style,
-    text_muted_style, text_style,
+    HEADER_TITLE, background_style, border_focused_style, border_style, danger_style,
+    success_style, text_muted_style, text_style,
 };
 use crate::setup::{
     PROVIDERS, footer_hints, graph_choice_label, mask_for_input, mask_preview,
@@ -69,7 +70,7 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &TuiState) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let left = "locus.codes";
+    let left = t(HEADER_TITLE);
     let progress = progress_text(state.setup.step);
     let gap = inner.width.saturating_sub(
         (UnicodeWidthStr::width(left) + UnicodeWidthStr::width(progress.as_str())) as u16,
@@ -177,7 +178,7 @@ fn draw_welcome(frame: &mut Frame, area: Rect, state: &TuiState) {
                 Span::styled("▐█▌".to_string(), text_style(palette.accent)),
                 Span::raw("  "),
                 Span::styled(
-                    "locus.codes".to_string(),
+                    t(HEADER_TITLE).to_string(),
                     text_style(palette.text).add_modifier(Modifier::BOLD),
                 ),
             ],
@@ -185,18 +186,18 @@ fn draw_welcome(frame: &mut Frame, area: Rect, state: &TuiState) {
         ),
         centered_line(
             vec![Span::styled(
-                "Terminal-native coding agent with memory".to_string(),
+                t("setup.welcome.tagline").to_string(),
                 text_muted_style(palette.text_muted),
             )],
             area.width,
         ),
         Line::from(""),
         Line::from(vec![Span::styled(
-            "This wizard sets up the minimum config to get started.",
+            t("setup.welcome.intro"),
             text_style(palette.text),
         )]),
         Line::from(vec![Span::styled(
-            "You'll need an API key for at least one LLM provider.",
+            t("setup.welcome.requirement"),
             text_muted_style(palette.text_muted),
         )]),
         error_line(state),
@@ -217,8 +218,8 @@ fn draw_provider_select(frame: &mut Frame, area: Rect, state: &TuiState) {
     draw_step_header(
         frame,
         sections[0],
-        "Choose your LLM provider",
-        "Which provider's models should the agent use?",
+        t("setup.provider.title"),
+        t("setup.provider.description"),
     );
 
     for idx in 0..PROVIDERS.len() {
@@ -259,8 +260,8 @@ fn draw_api_key_step(frame: &mut Frame, area: Rect, state: &TuiState) {
     draw_step_header(
         frame,
         sections[0],
-        &format!("Enter your {} API key", label),
-        "Paste your key - it stays local in ~/.locus.",
+        &tf("setup.api_key.title", &[("provider", &label)]),
+        t("setup.api_key.description"),
     );
     draw_input_field(
         frame,
@@ -287,15 +288,19 @@ fn draw_graph_choice(frame: &mut Frame, area: Rect, state: &TuiState) {
     draw_step_header(
         frame,
         sections[0],
-        "Memory",
-        "LocusGraph gives the agent memory across sessions.",
+        t("setup.graph.title"),
+        t("setup.graph.description"),
     );
 
     for idx in 0..2 {
         draw_selection_row(
             frame,
             sections[1 + idx],
-            if idx == 0 { "Yes" } else { "Skip" },
+            if idx == 0 {
+                t("setup.graph.yes")
+            } else {
+                t("setup.graph.skip")
+            },
             graph_choice_label(idx),
             idx == state.setup.graph_choice_cursor,
             state,
@@ -318,22 +323,22 @@ fn draw_graph_input(frame: &mut Frame, area: Rect, state: &TuiState, step: Setup
 
     let (title, description, label, value, secret) = match step {
         SetupStep::LocusGraphUrl => (
-            "LocusGraph server URL",
-            "The gRPC endpoint for your LocusGraph instance.",
+            t("setup.graph_url.title"),
+            t("setup.graph_url.description"),
             "LOCUSGRAPH_SERVER_URL",
             state.setup.graph_url.as_str(),
             false,
         ),
         SetupStep::LocusGraphSecret => (
-            "LocusGraph secret",
-            "Your agent authentication secret.",
+            t("setup.graph_secret.title"),
+            t("setup.graph_secret.description"),
             "LOCUSGRAPH_AGENT_SECRET",
             state.setup.graph_secret.as_str(),
             true,
         ),
         SetupStep::LocusGraphId => (
-            "Graph ID",
-            "Namespace for this agent's memory.",
+            t("setup.graph_id.title"),
+            t("setup.graph_id.description"),
             "LOCUSGRAPH_GRAPH_ID",
             state.setup.graph_id.as_str(),
             false,
@@ -366,13 +371,13 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
     draw_step_header(
         frame,
         sections[0],
-        "Review your configuration",
-        "Everything looks good?",
+        t("setup.confirm.title"),
+        t("setup.confirm.description"),
     );
 
     let card = Block::default()
         .title(Span::styled(
-            " Configuration ".to_string(),
+            format!(" {} ", t("setup.confirm.card")),
             text_style(palette.text).add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
@@ -386,11 +391,11 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
         .selected_provider
         .as_deref()
         .map(provider_label)
-        .unwrap_or("Not selected");
+        .unwrap_or(t("setup.confirm.not_selected"));
     let graph_status = if state.setup.configure_graph {
-        "Configured"
+        t("setup.confirm.configured")
     } else {
-        "Skipped"
+        t("setup.confirm.skipped")
     };
     let graph_style = if state.setup.configure_graph {
         success_style(palette.success)
@@ -398,10 +403,10 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
         text_muted_style(palette.text_muted)
     };
     let lines = vec![
-        summary_line(inner.width, "Provider", provider, state),
+        summary_line(inner.width, t("setup.confirm.provider"), provider, state),
         summary_line(
             inner.width,
-            "API Key",
+            t("setup.confirm.api_key"),
             &mask_preview(&state.setup.api_key),
             state,
         ),
@@ -414,7 +419,7 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
         ]),
         summary_line(
             inner.width,
-            "URL",
+            t("setup.confirm.url"),
             if state.setup.configure_graph {
                 &state.setup.graph_url
             } else {
@@ -424,7 +429,7 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
         ),
         summary_line(
             inner.width,
-            "Graph ID",
+            t("setup.graph_id.title"),
             if state.setup.configure_graph {
                 &state.setup.graph_id
             } else {
@@ -435,7 +440,7 @@ fn draw_confirm(frame: &mut Frame, area: Rect, state: &TuiState) {
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "Saved to ".to_string(),
+                format!("{} ", t("setup.confirm.saved_to")),
                 text_muted_style(palette.text_muted),
             ),
             Span::styled("~/.locus/locus.db".to_string(), text_style(palette.text)),
@@ -471,13 +476,13 @@ fn draw_done(frame: &mut Frame, area: Rect, state: &TuiState) {
 
     let saved_line = if let Some(shimmer) = &state.setup.done_shimmer {
         centered_line(
-            shimmer.styled_spans_with_palette("Configuration saved.", palette),
+            shimmer.styled_spans_with_palette(t("setup.done.saved"), palette),
             area.width,
         )
     } else {
         centered_line(
             vec![Span::styled(
-                "Configuration saved.".to_string(),
+                t("setup.done.saved").to_string(),
                 success_style(palette.success),
             )],
             area.width,
@@ -487,7 +492,7 @@ fn draw_done(frame: &mut Frame, area: Rect, state: &TuiState) {
     frame.render_widget(
         Paragraph::new(centered_line(
             vec![Span::styled(
-                "Press Enter to start chatting.",
+                t("setup.done.start"),
                 text_style(palette.text),
             )],
             area.width,
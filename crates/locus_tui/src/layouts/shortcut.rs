@@ -5,6 +5,7 @@ use ratatui::text::{Line, Span};
 
 use super::input::INPUT_PADDING_H;
 use super::style::{text_muted_style, text_style};
+use crate::i18n::t;
 use crate::theme::LocusPalette;
 
 /// Horizontal inset so shortcut aligns with input content (input border + input padding).
@@ -24,6 +25,7 @@ pub fn shortcut_inner_rect(area: Rect) -> Rect {
 
 /// Build the shortcut line for the footer. Dynamic based on state:
 /// - When active: "<phase>  Ctrl+C: cancel  Ctrl+D: logs"
+/// - When annotating a tool result: "Enter: attach note  Esc: cancel"
 /// - When input has text: "Enter: send  Ctrl+U: clear  Ctrl+K: kill  Ctrl+C: quit"
 /// - When input empty: "↑↓: scroll  PgUp/PgDn: faster  Ctrl+N: new  Ctrl+D: logs"
 pub fn shortcut_line(
@@ -31,6 +33,7 @@ pub fn shortcut_line(
     active_label: Option<&str>,
     active_glyph: Option<&str>,
     input_has_text: bool,
+    annotating: bool,
     has_diff_pager: bool,
     has_ai_history: bool,
 ) -> Line<'static> {
@@ -48,31 +51,135 @@ pub fn shortcut_line(
         ));
         spans.push(Span::styled(label.to_string(), streaming_style));
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+C", "cancel", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+C",
+            t("shortcut.cancel"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+D", "logs", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+D",
+            t("shortcut.logs"),
+            key_style,
+            desc_style,
+        );
+    } else if annotating {
+        push_shortcut(
+            &mut spans,
+            "Enter",
+            t("shortcut.attach_note"),
+            key_style,
+            desc_style,
+        );
+        spans.push(Span::styled("  ·  ".to_string(), sep_style));
+        push_shortcut(
+            &mut spans,
+            "Esc",
+            t("shortcut.cancel"),
+            key_style,
+            desc_style,
+        );
     } else if input_has_text {
-        push_shortcut(&mut spans, "Enter", "send", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Enter",
+            t("shortcut.send"),
+            key_style,
+            desc_style,
+        );
+        spans.push(Span::styled("  ·  ".to_string(), sep_style));
+        push_shortcut(
+            &mut spans,
+            "Ctrl+U",
+            t("shortcut.clear"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+U", "clear", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+K",
+            t("shortcut.kill"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+K", "kill", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+E",
+            t("shortcut.editor"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+C", "quit", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+C",
+            t("shortcut.quit"),
+            key_style,
+            desc_style,
+        );
     } else {
-        push_shortcut(&mut spans, "↑↓", "scroll", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "↑↓",
+            t("shortcut.history"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "PgUp/PgDn", "faster", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "PgUp/PgDn",
+            t("shortcut.scroll"),
+            key_style,
+            desc_style,
+        );
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
         if has_diff_pager {
-            push_shortcut(&mut spans, "d", "next diff", key_style, desc_style);
+            push_shortcut(
+                &mut spans,
+                "d",
+                t("shortcut.next_diff"),
+                key_style,
+                desc_style,
+            );
         } else if has_ai_history {
-            push_shortcut(&mut spans, "Ctrl+Y", "copy reply", key_style, desc_style);
+            push_shortcut(
+                &mut spans,
+                "Ctrl+Y",
+                t("shortcut.copy_reply"),
+                key_style,
+                desc_style,
+            );
         } else {
-            push_shortcut(&mut spans, "Ctrl+N", "new", key_style, desc_style);
+            push_shortcut(
+                &mut spans,
+                "Ctrl+N",
+                t("shortcut.new"),
+                key_style,
+                desc_style,
+            );
         }
         spans.push(Span::styled("  ·  ".to_string(), sep_style));
-        push_shortcut(&mut spans, "Ctrl+D", "logs", key_style, desc_style);
+        push_shortcut(
+            &mut spans,
+            "Ctrl+D",
+            t("shortcut.logs"),
+            key_style,
+            desc_style,
+        );
+        spans.push(Span::styled("  ·  ".to_string(), sep_style));
+        push_shortcut(
+            &mut spans,
+            "Ctrl+X",
+            t("shortcut.palette"),
+            key_style,
+            desc_style,
+        );
     }
 
     Line::from(spans)
@@ -110,22 +217,47 @@ mod tests {
     #[test]
     fn shortcut_line_streaming() {
         let palette = LocusPalette::locus_dark();
-        let line = shortcut_line(&palette, Some("Responding"), Some("◔"), false, false, false);
+        let line = shortcut_line(
+            &palette,
+            Some("Responding"),
+            Some("◔"),
+            false,
+            false,
+            false,
+            false,
+        );
         assert!(line.spans.iter().any(|s| s.content.contains("Responding")));
     }
 
     #[test]
     fn shortcut_line_typing() {
         let palette = LocusPalette::locus_dark();
-        let line = shortcut_line(&palette, None, None, true, false, false);
+        let line = shortcut_line(&palette, None, None, true, false, false, false);
         assert!(line.spans.iter().any(|s| s.content.contains("Enter")));
         assert!(line.spans.iter().any(|s| s.content.contains("Ctrl+K")));
+        assert!(line.spans.iter().any(|s| s.content.contains("Ctrl+E")));
     }
 
     #[test]
     fn shortcut_line_idle() {
         let palette = LocusPalette::locus_dark();
-        let line = shortcut_line(&palette, None, None, false, false, true);
-        assert!(line.spans.iter().any(|s| s.content.contains("copy reply")));
+        let line = shortcut_line(&palette, None, None, false, false, false, true);
+        assert!(
+            line.spans
+                .iter()
+                .any(|s| s.content.contains(t("shortcut.copy_reply")))
+        );
+    }
+
+    #[test]
+    fn shortcut_line_annotating() {
+        let palette = LocusPalette::locus_dark();
+        let line = shortcut_line(&palette, None, None, true, true, false, false);
+        assert!(
+            line.spans
+                .iter()
+                .any(|s| s.content.contains(t("shortcut.attach_note")))
+        );
+        assert!(line.spans.iter().any(|s| s.content.contains("Esc")));
     }
 }
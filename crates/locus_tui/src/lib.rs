@@ -1,22 +1,49 @@
 //! locus-tui — TUI for locus.codes.
 //!
 //! Theming in `theme`; layout in `layouts`; messages in `messages`; state and view in [state] and [view].
+//! User-facing copy is looked up in the [i18n] message catalog; [transcript] mirrors the chat to a text file.
 //! Run with [run_tui].
 
+pub mod agents;
 pub mod animation;
+pub mod cancel;
+pub mod clipboard;
+pub mod command_palette;
 pub mod diff;
+pub mod diff_review;
+pub mod editor;
+pub mod export;
+pub mod file_finder;
+pub mod history;
+pub mod i18n;
 pub mod layouts;
+pub mod logs;
+pub mod memory_trace;
 pub mod messages;
+pub mod model_picker;
+pub mod paste;
+pub mod pin;
+pub mod plan;
 pub mod preview;
+pub mod rewind;
 pub mod run;
 pub mod runtime_events;
+pub mod scroll;
+pub mod session_picker;
 pub mod setup;
+pub mod slash;
 pub mod state;
+pub mod status_bar;
+pub mod task_board;
 pub mod theme;
+pub mod theme_picker;
+pub mod transcript;
 pub mod utils;
 pub mod view;
 pub mod web_automation;
 
 pub use run::{run_tui, run_tui_preview, run_tui_with_runtime};
+pub use session_picker::SessionSwitch;
 pub use state::{ChatItem, Screen, TuiState};
+pub use transcript::TranscriptMirror;
 pub use view::draw as draw_view;
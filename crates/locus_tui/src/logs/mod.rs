@@ -0,0 +1,12 @@
+//! Logs screen.
+//!
+//! The tracing layer and the TUI write to a shared `LogBuffer`; this screen
+//! shows it newest at the bottom, narrowed by severity, target and a
+//! case-insensitive search whose matches are highlighted. `y` copies the
+//! lines shown. Access via Ctrl+D from the main chat.
+
+pub mod state;
+pub mod view;
+
+pub use state::{LevelFilter, LogsInput, LogsState, MAX_LOG_LINES};
+pub use view::draw_logs;
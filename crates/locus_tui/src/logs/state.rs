@@ -0,0 +1,311 @@
+//! Logs state: the shared log buffer, the filters applied to it, and where
+//! the view is scrolled.
+
+use locusgraph_observability::{LogBuffer, LogRecord};
+use tracing::Level;
+
+/// Log records kept; older ones are dropped.
+pub const MAX_LOG_LINES: usize = 2000;
+
+/// Lowest severity shown, `l` cycles through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelFilter {
+    #[default]
+    All,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LevelFilter {
+    /// The next filter, back to All after Error.
+    pub fn next(self) -> Self {
+        match self {
+            LevelFilter::All => LevelFilter::Debug,
+            LevelFilter::Debug => LevelFilter::Info,
+            LevelFilter::Info => LevelFilter::Warn,
+            LevelFilter::Warn => LevelFilter::Error,
+            LevelFilter::Error => LevelFilter::All,
+        }
+    }
+
+    /// Whether a record at `level` is shown.
+    pub fn allows(self, level: Level) -> bool {
+        // More verbose levels compare greater.
+        match self {
+            LevelFilter::All => true,
+            LevelFilter::Debug => level <= Level::DEBUG,
+            LevelFilter::Info => level <= Level::INFO,
+            LevelFilter::Warn => level <= Level::WARN,
+            LevelFilter::Error => level <= Level::ERROR,
+        }
+    }
+}
+
+/// Which filter the keys are typed into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogsInput {
+    Search,
+    Target,
+}
+
+/// What the logs screen shows.
+#[derive(Debug, Clone)]
+pub struct LogsState {
+    /// Records from the tracing layer and the TUI itself.
+    pub buffer: LogBuffer,
+    /// Records taken in from the buffer so far.
+    seen: u64,
+    pub level: LevelFilter,
+    /// Shown when the target contains this (case-insensitive); empty for all.
+    pub target: String,
+    /// Shown when the line contains this (case-insensitive) and highlighted.
+    pub query: String,
+    /// Set while a filter is being typed.
+    pub input: Option<LogsInput>,
+    /// Lines scrolled up from the bottom.
+    pub scroll: usize,
+    /// Show new lines as they come (follow-tail); off once scrolled up, back
+    /// on at the bottom.
+    pub follow: bool,
+}
+
+impl Default for LogsState {
+    fn default() -> Self {
+        Self::with_buffer(LogBuffer::new(MAX_LOG_LINES))
+    }
+}
+
+impl LogsState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Logs read from `buffer`, which the tracing layer also writes to.
+    pub fn with_buffer(buffer: LogBuffer) -> Self {
+        Self {
+            buffer,
+            seen: 0,
+            level: LevelFilter::All,
+            target: String::new(),
+            query: String::new(),
+            input: None,
+            scroll: 0,
+            follow: true,
+        }
+    }
+
+    /// Add a line of the TUI's own, "[tag] message" with the tag as target.
+    pub fn push_line(&mut self, line: &str) {
+        let (target, message) = match line.strip_prefix('[').and_then(|l| l.split_once("] ")) {
+            Some((tag, message)) => (tag, message),
+            None => ("locus_tui", line),
+        };
+        self.buffer
+            .push(LogRecord::new(Level::INFO, target, message.to_string()));
+    }
+
+    /// Take in records added since the last call. Returns whether any came;
+    /// unless following, the view stays on the lines it shows.
+    pub fn sync(&mut self) -> bool {
+        let (new, seen) = self.buffer.since(self.seen);
+        self.seen = seen;
+        if !self.follow {
+            let shown = new.iter().filter(|record| self.matches(record)).count();
+            self.scroll = self.scroll.saturating_add(shown);
+        }
+        !new.is_empty()
+    }
+
+    /// Whether `record` passes the level, target and search filters.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        self.level.allows(record.level)
+            && contains_ignore_case(&record.target, &self.target)
+            && contains_ignore_case(&record.line(), &self.query)
+    }
+
+    /// Records the filters let through, oldest first.
+    pub fn visible(&self) -> Vec<LogRecord> {
+        self.buffer
+            .records()
+            .into_iter()
+            .filter(|record| self.matches(record))
+            .collect()
+    }
+
+    /// Whether any filter is set.
+    pub fn is_filtered(&self) -> bool {
+        self.level != LevelFilter::All || !self.target.is_empty() || !self.query.is_empty()
+    }
+
+    pub fn cycle_level(&mut self) {
+        self.level = self.level.next();
+        self.follow_latest();
+    }
+
+    /// Start typing into the search or target filter.
+    pub fn begin_input(&mut self, input: LogsInput) {
+        self.input = Some(input);
+    }
+
+    /// Stop typing; the filter stays.
+    pub fn end_input(&mut self) {
+        self.input = None;
+    }
+
+    pub fn input_insert(&mut self, c: char) {
+        if let Some(field) = self.input_field() {
+            field.push(c);
+            self.follow_latest();
+        }
+    }
+
+    pub fn input_backspace(&mut self) {
+        if let Some(field) = self.input_field() {
+            field.pop();
+            self.follow_latest();
+        }
+    }
+
+    fn input_field(&mut self) -> Option<&mut String> {
+        match self.input? {
+            LogsInput::Search => Some(&mut self.query),
+            LogsInput::Target => Some(&mut self.target),
+        }
+    }
+
+    /// Drop every filter.
+    pub fn clear_filters(&mut self) {
+        self.level = LevelFilter::All;
+        self.target.clear();
+        self.query.clear();
+        self.input = None;
+        self.follow_latest();
+    }
+
+    fn follow_latest(&mut self) {
+        self.scroll = 0;
+        self.follow = true;
+    }
+
+    pub fn scroll_up(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_add(delta);
+        self.follow = false;
+    }
+
+    pub fn scroll_down(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_sub(delta);
+        self.follow = self.scroll == 0;
+    }
+}
+
+/// Whether `text` contains `needle`, ignoring ASCII case; true for an empty
+/// needle.
+pub fn contains_ignore_case(text: &str, needle: &str) -> bool {
+    needle.is_empty()
+        || text
+            .to_ascii_lowercase()
+            .contains(&needle.to_ascii_lowercase())
+}
+
+/// Byte ranges of `needle` in `text`, ignoring ASCII case, not overlapping.
+pub fn match_ranges(text: &str, needle: &str) -> Vec<(usize, usize)> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let haystack = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, found)| (start, start + found.len()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: Level, target: &str, message: &str) -> LogRecord {
+        LogRecord::new(level, target, message)
+    }
+
+    #[test]
+    fn filters_by_level_target_and_search() {
+        let mut logs = LogsState::new();
+        logs.buffer.push(record(
+            Level::DEBUG,
+            "locus_runtime::llm",
+            "Streaming LLM response",
+        ));
+        logs.buffer.push(record(
+            Level::WARN,
+            "locus_graph::client",
+            "LocusGraph offline",
+        ));
+        logs.buffer
+            .push(record(Level::ERROR, "locus_runtime::tools", "bash failed"));
+        let messages = |logs: &LogsState| {
+            logs.visible()
+                .into_iter()
+                .map(|r| r.message)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(messages(&logs).len(), 3);
+
+        logs.cycle_level();
+        logs.cycle_level();
+        assert_eq!(logs.level, LevelFilter::Info);
+        assert_eq!(messages(&logs), ["LocusGraph offline", "bash failed"]);
+
+        logs.begin_input(LogsInput::Target);
+        for c in "RUNTIME".chars() {
+            logs.input_insert(c);
+        }
+        logs.end_input();
+        assert_eq!(messages(&logs), ["bash failed"]);
+
+        logs.clear_filters();
+        logs.begin_input(LogsInput::Search);
+        for c in "llm".chars() {
+            logs.input_insert(c);
+        }
+        assert_eq!(messages(&logs), ["Streaming LLM response"]);
+        logs.input_backspace();
+        logs.input_backspace();
+        logs.input_backspace();
+        assert!(!logs.is_filtered());
+    }
+
+    #[test]
+    fn sync_holds_the_view_on_matching_lines_only() {
+        let mut logs = LogsState::new();
+        logs.query = "tool".into();
+        logs.buffer.push(record(Level::INFO, "a", "tool started"));
+        assert!(logs.sync());
+        assert!(!logs.sync());
+
+        logs.scroll_up(1);
+        logs.buffer.push(record(Level::INFO, "a", "tool done"));
+        logs.buffer.push(record(Level::INFO, "a", "llm call"));
+        assert!(logs.sync());
+        assert_eq!(logs.scroll, 2);
+    }
+
+    #[test]
+    fn tui_lines_use_their_tag_as_target() {
+        let mut logs = LogsState::new();
+        logs.push_line("[preview] TUI preview loaded");
+        logs.push_line("no tag");
+        let records = logs.buffer.records();
+        assert_eq!(records[0].target, "preview");
+        assert_eq!(records[0].message, "TUI preview loaded");
+        assert_eq!(records[1].target, "locus_tui");
+    }
+
+    #[test]
+    fn match_ranges_ignore_case() {
+        assert_eq!(match_ranges("Tool call TOOL", "tool"), [(0, 4), (10, 14)]);
+        assert!(match_ranges("anything", "").is_empty());
+    }
+}
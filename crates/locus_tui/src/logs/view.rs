@@ -0,0 +1,254 @@
+//! View rendering for the logs screen.
+
+use locusgraph_observability::LogRecord;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use tracing::Level;
+
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, danger_style, render_header, rgb_to_color,
+    text_muted_style, text_style, warning_style,
+};
+use crate::logs::state::{LevelFilter, LogsInput, LogsState, match_ranges};
+use crate::theme::LocusPalette;
+
+/// Draw the logs screen: the filters, then the records they let through,
+/// newest at the bottom, search matches highlighted.
+pub fn draw_logs(frame: &mut Frame, state: &mut LogsState, area: Rect, palette: &LocusPalette) {
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(1), // Filters
+            Constraint::Min(8),    // Lines
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let records = state.visible();
+    let total = state.buffer.len();
+    let status = if total == 0 {
+        t("logs.empty_status").to_string()
+    } else {
+        let follow = if state.follow {
+            t("logs.following")
+        } else {
+            t("logs.paused")
+        };
+        let count = if state.is_filtered() {
+            tf(
+                "logs.filtered_count",
+                &[("shown", &records.len()), ("count", &total)],
+            )
+        } else {
+            tf("logs.line_count", &[("count", &total)])
+        };
+        format!("{} · {}", count, follow)
+    };
+    render_header(
+        frame,
+        chunks[0],
+        palette,
+        t(HEADER_TITLE),
+        t("logs.section"),
+        status.as_str(),
+        false,
+        false,
+    );
+
+    frame.render_widget(Paragraph::new(filter_line(state, palette)), chunks[1]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(chunks[2]);
+    frame.render_widget(block, chunks[2]);
+
+    let viewport_height = inner.height as usize;
+    let max_scroll = records.len().saturating_sub(viewport_height);
+    state.scroll = state.scroll.min(max_scroll);
+    let offset_from_top = max_scroll.saturating_sub(state.scroll);
+
+    let lines: Vec<Line> = if total == 0 {
+        vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("● ".to_string(), text_style(palette.accent)),
+                Span::styled(t("logs.empty_title"), text_style(palette.text)),
+            ]),
+            Line::from(vec![Span::styled(
+                format!("  {}", t("logs.empty_hint")),
+                text_muted_style(palette.text_muted),
+            )]),
+        ]
+    } else if records.is_empty() {
+        vec![Line::from(Span::styled(
+            format!("  {}", t("logs.no_matches")),
+            text_muted_style(palette.text_disabled),
+        ))]
+    } else {
+        records
+            .iter()
+            .skip(offset_from_top)
+            .take(viewport_height)
+            .map(|record| record_line(record, &state.query, palette))
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+
+    draw_shortcuts(frame, chunks[3], state.input.is_some(), palette);
+}
+
+/// "Level: … · Target: … · Search: …", with a cursor on the one being typed.
+fn filter_line(state: &LogsState, palette: &LocusPalette) -> Line<'static> {
+    let level = match state.level {
+        LevelFilter::All => t("logs.level_all"),
+        LevelFilter::Debug => "debug+",
+        LevelFilter::Info => "info+",
+        LevelFilter::Warn => "warn+",
+        LevelFilter::Error => "error",
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!(" {}: ", t("logs.level")),
+            text_muted_style(palette.text_muted),
+        ),
+        Span::styled(level.to_string(), text_style(palette.text)),
+    ];
+    let fields = [
+        (LogsInput::Target, t("logs.target"), &state.target),
+        (LogsInput::Search, t("logs.search"), &state.query),
+    ];
+    for (input, label, value) in fields {
+        spans.push(Span::styled(
+            format!("  ·  {}: ", label),
+            text_muted_style(palette.text_muted),
+        ));
+        let typing = state.input == Some(input);
+        if value.is_empty() && !typing {
+            spans.push(Span::styled(
+                t("logs.any").to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        } else {
+            spans.push(Span::styled(value.clone(), text_style(palette.text)));
+        }
+        if typing {
+            spans.push(Span::styled("▏".to_string(), text_style(palette.accent)));
+        }
+    }
+    Line::from(spans)
+}
+
+/// A record as "│ [LEVEL] target: message", coloured by level, with the
+/// parts matching `query` highlighted.
+pub fn record_line(record: &LogRecord, query: &str, palette: &LocusPalette) -> Line<'static> {
+    let style = match record.level {
+        Level::ERROR => danger_style(palette.danger),
+        Level::WARN => warning_style(palette.warning),
+        _ => text_muted_style(palette.text_muted),
+    };
+    let highlight = Style::default()
+        .fg(rgb_to_color(palette.text))
+        .bg(rgb_to_color(palette.element_selected));
+    let text = record.line();
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled("│ ".to_string(), text_muted_style(palette.border_variant)),
+    ];
+    let mut at = 0;
+    for (start, end) in match_ranges(&text, query) {
+        if start > at {
+            spans.push(Span::styled(text[at..start].to_string(), style));
+        }
+        spans.push(Span::styled(text[start..end].to_string(), highlight));
+        at = end;
+    }
+    if at < text.len() {
+        spans.push(Span::styled(text[at..].to_string(), style));
+    }
+    Line::from(spans)
+}
+
+fn draw_shortcuts(frame: &mut Frame, area: Rect, typing: bool, palette: &LocusPalette) {
+    let shortcuts: &[(&str, &str)] = if typing {
+        &[
+            ("Enter/Esc", t("logs.shortcut.done")),
+            ("↑↓", t("shortcut.scroll")),
+        ]
+    } else {
+        &[
+            ("Esc", t("shortcut.back")),
+            ("/", t("logs.shortcut.search")),
+            ("t", t("logs.shortcut.target")),
+            ("l", t("logs.shortcut.level")),
+            ("x", t("logs.shortcut.clear")),
+            ("y", t("logs.shortcut.copy")),
+            ("↑↓/jk", t("shortcut.scroll")),
+            ("g/G", t("shortcut.top_bottom_follow")),
+        ]
+    };
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_line_highlights_search_matches() {
+        let palette = LocusPalette::locus_dark();
+        let record = LogRecord::new(
+            Level::WARN,
+            "locus_graph",
+            "Graph offline, graph cache used",
+        );
+        let line = record_line(&record, "GRAPH", &palette);
+        let parts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            parts,
+            [
+                "  ",
+                "│ ",
+                "[WARN] locus_",
+                "graph",
+                ": ",
+                "Graph",
+                " offline, ",
+                "graph",
+                " cache used"
+            ]
+        );
+        assert_eq!(
+            line.spans[3].style.bg,
+            Some(rgb_to_color(palette.element_selected))
+        );
+        assert_eq!(line.spans[2].style, warning_style(palette.warning));
+    }
+}
@@ -0,0 +1,83 @@
+//! Memory trace screen.
+//!
+//! What LocusGraph remembers about the repo: type a query and see the
+//! memories recall would find for it, most relevant first, as reported by
+//! `SessionEvent::MemorySearch`; below that, a per-turn timeline of the
+//! retrievals made before each LLM call (`SessionEvent::MemoriesRetrieved`)
+//! with each memory's relevance and whether it made it into the prompt.
+//! Access via Ctrl+G from the main chat, or with `:memory <query>` in the
+//! chat input.
+
+pub mod state;
+pub mod view;
+
+pub use state::{MemorySearch, MemoryTraceState, Retrieval};
+pub use view::draw_memory_trace;
+
+use locus_core::memory_search::MEMORY_COMMAND;
+use locus_core::parse_memory_command;
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::t;
+use crate::state::{Screen, TuiState};
+
+/// Handle `line` if it is `:memory <query>`: ask the runtime to search and
+/// show the memory trace screen, or say why not. A search sent during a run
+/// is taken up once the run ends. Returns false for any other input.
+pub fn request_memory_search(
+    state: &mut TuiState,
+    line: &str,
+    user_msg_tx: Option<&tokio_mpsc::Sender<String>>,
+) -> bool {
+    let Some(parsed) = parse_memory_command(line) else {
+        return false;
+    };
+    match parsed {
+        Err(usage) => state.status = usage,
+        Ok(query) => match user_msg_tx {
+            Some(tx) if tx.try_send(format!("{} {}", MEMORY_COMMAND, query)).is_ok() => {
+                state.memory_trace.pending = Some(query);
+                state.screen = Screen::MemoryTrace;
+                state.needs_redraw = true;
+                return true;
+            }
+            _ => state.status = t("status.memory_unavailable").to_string(),
+        },
+    }
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_searches_go_to_the_runtime() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        assert!(request_memory_search(
+            &mut state,
+            ":memory error handling",
+            Some(&tx)
+        ));
+        assert_eq!(rx.try_recv().unwrap(), ":memory error handling");
+        assert_eq!(state.screen, Screen::MemoryTrace);
+        assert_eq!(
+            state.memory_trace.pending.as_deref(),
+            Some("error handling")
+        );
+
+        let mut state = TuiState::new();
+        assert!(request_memory_search(&mut state, ":memory", Some(&tx)));
+        assert!(state.status.starts_with("Usage"));
+        assert_eq!(state.screen, Screen::Main);
+        assert!(!request_memory_search(
+            &mut state,
+            "remember this",
+            Some(&tx)
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+//! Memory trace state: the retrievals before each LLM call, the last memory
+//! search and the one still running.
+
+use locus_core::RetrievedMemory;
+
+/// Retrievals kept for the timeline; older ones are dropped.
+pub const MAX_RETRIEVALS: usize = 200;
+
+/// One memory retrieval before an LLM call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Retrieval {
+    /// User turn it was made for.
+    pub turn: usize,
+    pub query: String,
+    /// Memories recalled, most relevant first.
+    pub memories: Vec<RetrievedMemory>,
+    pub duration_ms: u64,
+    /// True when LocusGraph could not be searched.
+    pub degraded: bool,
+}
+
+/// Results of one `:memory` search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySearch {
+    pub query: String,
+    /// Memories found, most relevant first.
+    pub memories: Vec<String>,
+    /// True when LocusGraph could not be searched.
+    pub degraded: bool,
+}
+
+/// What the memory trace screen shows.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTraceState {
+    /// Retrievals of this session, oldest first.
+    pub retrievals: Vec<Retrieval>,
+    /// The last search the runtime answered.
+    pub search: Option<MemorySearch>,
+    /// Query sent to the runtime and not answered yet.
+    pub pending: Option<String>,
+    /// Scroll offset for the results (lines from top).
+    pub scroll: usize,
+}
+
+impl MemoryTraceState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a retrieval to the timeline.
+    pub fn record(&mut self, retrieval: Retrieval) {
+        if self.retrievals.len() >= MAX_RETRIEVALS {
+            self.retrievals.remove(0);
+        }
+        self.retrievals.push(retrieval);
+    }
+
+    /// The retrievals grouped by turn, newest turn first, each turn's in the
+    /// order they were made.
+    pub fn turns(&self) -> Vec<(usize, Vec<&Retrieval>)> {
+        let mut turns: Vec<(usize, Vec<&Retrieval>)> = Vec::new();
+        for retrieval in &self.retrievals {
+            match turns.last_mut() {
+                Some((turn, list)) if *turn == retrieval.turn => list.push(retrieval),
+                _ => turns.push((retrieval.turn, vec![retrieval])),
+            }
+        }
+        turns.reverse();
+        turns
+    }
+
+    /// Show the results of a search.
+    pub fn show_search(&mut self, query: String, memories: Vec<String>, degraded: bool) {
+        if self.pending.as_ref() == Some(&query) {
+            self.pending = None;
+        }
+        self.search = Some(MemorySearch {
+            query,
+            memories,
+            degraded,
+        });
+        self.scroll = 0;
+    }
+
+    pub fn scroll_up(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_sub(delta);
+    }
+
+    pub fn scroll_down(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_add(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retrieval(turn: usize, query: &str) -> Retrieval {
+        Retrieval {
+            turn,
+            query: query.to_string(),
+            memories: Vec::new(),
+            duration_ms: 5,
+            degraded: false,
+        }
+    }
+
+    #[test]
+    fn retrievals_group_by_turn_newest_first() {
+        let mut state = MemoryTraceState::new();
+        state.record(retrieval(1, "fix add"));
+        state.record(retrieval(1, "tool results"));
+        state.record(retrieval(2, "now subtract"));
+
+        let turns = state.turns();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].0, 2);
+        assert_eq!(turns[1].0, 1);
+        let queries: Vec<_> = turns[1].1.iter().map(|r| r.query.as_str()).collect();
+        assert_eq!(queries, ["fix add", "tool results"]);
+
+        for _ in 0..MAX_RETRIEVALS {
+            state.record(retrieval(3, "again"));
+        }
+        assert_eq!(state.retrievals.len(), MAX_RETRIEVALS);
+        assert_eq!(state.turns().len(), 1);
+    }
+}
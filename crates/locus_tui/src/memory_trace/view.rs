@@ -0,0 +1,347 @@
+//! View rendering for the memory trace screen.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, render_header, text_muted_style, text_style,
+};
+use crate::memory_trace::state::{MemorySearch, MemoryTraceState, Retrieval};
+use crate::theme::LocusPalette;
+use crate::utils::LEFT_PADDING;
+
+/// Draw the memory trace screen: the query being typed, the memories the last
+/// search found, then the retrievals of each turn.
+pub fn draw_memory_trace(
+    frame: &mut Frame,
+    state: &mut MemoryTraceState,
+    input: &str,
+    area: Rect,
+    palette: &LocusPalette,
+) {
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(3), // Query
+            Constraint::Min(6),    // Results
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let status = match (&state.pending, &state.search) {
+        (Some(query), _) => tf("memory_trace.searching", &[("query", query)]),
+        (None, Some(search)) => tf(
+            "memory_trace.found",
+            &[("count", &search.memories.len()), ("query", &search.query)],
+        ),
+        (None, None) if state.retrievals.is_empty() => t("memory_trace.empty_status").to_string(),
+        (None, None) => tf(
+            "memory_trace.retrievals",
+            &[("count", &state.retrievals.len())],
+        ),
+    };
+    render_header(
+        frame,
+        chunks[0],
+        palette,
+        t(HEADER_TITLE),
+        t("memory_trace.section"),
+        status.as_str(),
+        state.pending.is_some(),
+        state.search.as_ref().is_some_and(|s| s.degraded),
+    );
+
+    draw_query(frame, chunks[1], input, palette);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(chunks[2]);
+    frame.render_widget(block, chunks[2]);
+    let mut lines = match &state.search {
+        Some(search) => result_lines(search, palette),
+        None if state.retrievals.is_empty() => empty_lines(palette),
+        None => Vec::new(),
+    };
+    lines.extend(timeline_lines(state, palette));
+    let height = inner.height as usize;
+    state.scroll = state.scroll.min(lines.len().saturating_sub(height));
+    let visible: Vec<Line> = lines.into_iter().skip(state.scroll).collect();
+    frame.render_widget(Paragraph::new(visible).wrap(Wrap { trim: false }), inner);
+
+    draw_shortcuts(frame, chunks[3], palette);
+}
+
+fn draw_query(frame: &mut Frame, area: Rect, input: &str, palette: &LocusPalette) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border_focused))
+        .title(Span::styled(
+            format!(" {} ", t("memory_trace.query")),
+            text_style(palette.text),
+        ))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let line = if input.is_empty() {
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(
+                t("memory_trace.query_hint").to_string(),
+                text_muted_style(palette.text_disabled),
+            ),
+        ])
+    } else {
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(input.to_string(), text_style(palette.text)),
+            Span::styled("▏".to_string(), text_style(palette.accent)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(line), inner);
+}
+
+fn empty_lines(palette: &LocusPalette) -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled("● ".to_string(), text_style(palette.accent)),
+            Span::styled(t("memory_trace.empty_title"), text_style(palette.text)),
+        ]),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled(
+                format!("  {}", t("memory_trace.empty_hint")),
+                text_muted_style(palette.text_muted),
+            ),
+        ]),
+    ]
+}
+
+/// The memories of `search`, numbered by rank, each followed by a blank line.
+pub fn result_lines(search: &MemorySearch, palette: &LocusPalette) -> Vec<Line<'static>> {
+    if search.degraded {
+        return vec![Line::from(Span::styled(
+            format!(" {}", t("memory_trace.degraded")),
+            text_style(palette.danger),
+        ))];
+    }
+    if search.memories.is_empty() {
+        return vec![Line::from(Span::styled(
+            format!(" {}", t("memory_trace.no_results")),
+            text_muted_style(palette.text_disabled),
+        ))];
+    }
+    let mut lines = Vec::new();
+    for (rank, memory) in search.memories.iter().enumerate() {
+        let memory = memory.trim_start_matches(['-', '*', '+']).trim_start();
+        for (i, text) in memory.lines().enumerate() {
+            let prefix = if i == 0 {
+                Span::styled(
+                    format!(" {:>2}. ", rank + 1),
+                    text_muted_style(palette.text_muted),
+                )
+            } else {
+                Span::raw("     ")
+            };
+            lines.push(Line::from(vec![
+                prefix,
+                Span::styled(text.trim().to_string(), text_style(palette.text)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// The retrievals of each turn, newest turn first: a line per retrieval with
+/// its query, count and latency, then its memories with their relevance, ✓ for
+/// those that made it into the prompt.
+pub fn timeline_lines(state: &MemoryTraceState, palette: &LocusPalette) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (turn, retrievals) in state.turns() {
+        lines.push(Line::from(Span::styled(
+            format!(" {}", tf("memory_trace.turn", &[("turn", &turn)])),
+            text_style(palette.accent),
+        )));
+        for retrieval in retrievals {
+            lines.extend(retrieval_lines(retrieval, palette));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+fn retrieval_lines(retrieval: &Retrieval, palette: &LocusPalette) -> Vec<Line<'static>> {
+    let summary = if retrieval.degraded {
+        t("memory_trace.degraded").to_string()
+    } else {
+        tf(
+            "memory_trace.retrieved",
+            &[
+                ("count", &retrieval.memories.len()),
+                ("ms", &retrieval.duration_ms),
+            ],
+        )
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw("   "),
+        Span::styled(
+            format!(
+                "\"{}\"",
+                retrieval.query.lines().next().unwrap_or("").trim()
+            ),
+            text_style(palette.text),
+        ),
+        Span::styled(
+            format!(" · {}", summary),
+            if retrieval.degraded {
+                text_style(palette.danger)
+            } else {
+                text_muted_style(palette.text_muted)
+            },
+        ),
+    ])];
+    for memory in &retrieval.memories {
+        let (mark, mark_style) = if memory.included {
+            ("✓", text_style(palette.success))
+        } else {
+            ("✗", text_muted_style(palette.text_disabled))
+        };
+        let relevance = match memory.relevance {
+            Some(score) => format!("{:.2}", score),
+            None => "  — ".to_string(),
+        };
+        let summary = memory
+            .summary
+            .trim_start_matches(['-', '*', '+'])
+            .trim_start();
+        lines.push(Line::from(vec![
+            Span::raw("     "),
+            Span::styled(mark.to_string(), mark_style),
+            Span::styled(
+                format!(" {} ", relevance),
+                text_muted_style(palette.text_muted),
+            ),
+            Span::styled(
+                summary.to_string(),
+                if memory.included {
+                    text_style(palette.text)
+                } else {
+                    text_muted_style(palette.text_disabled)
+                },
+            ),
+        ]));
+    }
+    lines
+}
+
+fn draw_shortcuts(frame: &mut Frame, area: Rect, palette: &LocusPalette) {
+    let shortcuts = [
+        ("Esc", t("shortcut.back")),
+        ("Enter", t("memory_trace.shortcut.search")),
+        ("↑↓", t("shortcut.scroll")),
+        ("Home/End", t("shortcut.top_bottom")),
+    ];
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn results_are_numbered_by_rank() {
+        let palette = LocusPalette::locus_dark();
+        let search = MemorySearch {
+            query: "errors".into(),
+            memories: vec![
+                "- [fact:errors] errors go through AppError\n  in src/error.rs".into(),
+                "- [decision:retries] retry reads twice".into(),
+            ],
+            degraded: false,
+        };
+        assert_eq!(
+            text(&result_lines(&search, &palette)),
+            [
+                "  1. [fact:errors] errors go through AppError",
+                "     in src/error.rs",
+                "",
+                "  2. [decision:retries] retry reads twice",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn timeline_shows_relevance_and_inclusion() {
+        use locus_core::RetrievedMemory;
+
+        let palette = LocusPalette::locus_dark();
+        let mut state = MemoryTraceState::new();
+        state.record(Retrieval {
+            turn: 1,
+            query: "fix add".into(),
+            memories: vec![
+                RetrievedMemory {
+                    summary: "- [fact:math] add lives in src/math.rs".into(),
+                    relevance: Some(0.8234),
+                    tokens: 12,
+                    included: true,
+                },
+                RetrievedMemory {
+                    summary: "- [fact:old] unrelated".into(),
+                    relevance: None,
+                    tokens: 400,
+                    included: false,
+                },
+            ],
+            duration_ms: 42,
+            degraded: false,
+        });
+        assert_eq!(
+            text(&timeline_lines(&state, &palette)),
+            [
+                " Turn 1",
+                "   \"fix add\" · 2 memories · 42 ms",
+                "     ✓ 0.82 [fact:math] add lives in src/math.rs",
+                "     ✗   —  [fact:old] unrelated",
+                "",
+            ]
+        );
+    }
+}
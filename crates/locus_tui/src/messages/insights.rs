@@ -0,0 +1,141 @@
+//! "What I remember about this project": LocusGraph's insights from past
+//! sessions, shown as the first item of a session's chat.
+//!
+//! Layout: a header line with the confidence, then the wrapped insight and
+//! recommendation on a left rail. Collapsed (key `i`), only the header is
+//! shown, with how many lines are hidden.
+
+use ratatui::text::{Line, Span};
+
+use crate::i18n::{t, tf};
+use crate::layouts::{text_muted_style, text_style};
+use crate::theme::LocusPalette;
+use crate::utils::wrap_lines;
+
+/// Left rail for the insights block.
+const INSIGHTS_LEFT_BORDER: &str = "│ ";
+/// Icon in front of the header.
+const INSIGHTS_ICON: &str = "◈";
+
+/// Project insights for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectInsightsMessage {
+    /// Common failure patterns and conventions, as LocusGraph put them.
+    pub insight: String,
+    /// What to keep in mind when working on the project.
+    pub recommendation: String,
+    /// From 0 to 1.
+    pub confidence: f64,
+    /// When true, show only the header line.
+    pub collapsed: bool,
+}
+
+impl ProjectInsightsMessage {
+    pub fn new(
+        insight: impl Into<String>,
+        recommendation: impl Into<String>,
+        confidence: f64,
+    ) -> Self {
+        Self {
+            insight: insight.into(),
+            recommendation: recommendation.into(),
+            confidence,
+            collapsed: false,
+        }
+    }
+}
+
+/// Lines of the insights block: header, then (unless collapsed) the insight
+/// and the recommendation wrapped to `width`.
+pub fn insights_lines(
+    msg: &ProjectInsightsMessage,
+    palette: &LocusPalette,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let rail = Span::styled(
+        INSIGHTS_LEFT_BORDER.to_string(),
+        text_muted_style(palette.border_variant),
+    );
+    let muted = text_muted_style(palette.text_muted);
+    let wrap_width = width.saturating_sub(INSIGHTS_LEFT_BORDER.len()).max(1);
+
+    let mut body = wrap_paragraphs(&msg.insight, wrap_width);
+    let recommendation = msg.recommendation.trim();
+    if !recommendation.is_empty() {
+        body.push(String::new());
+        body.extend(wrap_paragraphs(
+            &tf("chat.insights_recommendation", &[("text", &recommendation)]),
+            wrap_width,
+        ));
+    }
+
+    let confidence = format!("{:.0}%", msg.confidence * 100.0);
+    let mut header = vec![
+        rail.clone(),
+        Span::styled(format!("{} ", INSIGHTS_ICON), text_style(palette.info)),
+        Span::styled(
+            t("chat.insights_title").to_string(),
+            text_style(palette.text),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            tf("chat.insights_confidence", &[("confidence", &confidence)]),
+            muted,
+        ),
+    ];
+    if msg.collapsed {
+        let count = body.len().to_string();
+        header.push(Span::raw("  "));
+        header.push(Span::styled(
+            tf("chat.insights_collapsed", &[("count", &count)]),
+            muted,
+        ));
+        return vec![Line::from(header)];
+    }
+
+    let mut lines = vec![Line::from(header)];
+    lines.extend(
+        body.into_iter()
+            .map(|text| Line::from(vec![rail.clone(), Span::styled(text, muted)])),
+    );
+    lines
+}
+
+/// Wrap each line of `text` on its own, so lists keep one item per line.
+fn wrap_paragraphs(text: &str, width: usize) -> Vec<String> {
+    text.trim()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .flat_map(|line| wrap_lines(line, width))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn insights_show_body_until_collapsed() {
+        let palette = LocusPalette::locus_dark();
+        let mut msg = ProjectInsightsMessage::new(
+            "cargo test fails without DATABASE_URL",
+            "export DATABASE_URL before running tests",
+            0.72,
+        );
+        let lines = insights_lines(&msg, &palette, 80);
+        assert!(text(&lines[0]).contains("What I remember about this project"));
+        assert!(text(&lines[0]).contains("72%"));
+        assert_eq!(lines.len(), 4);
+        assert!(text(&lines[1]).contains("DATABASE_URL"));
+        assert!(text(&lines[3]).contains("export DATABASE_URL"));
+
+        msg.collapsed = true;
+        let lines = insights_lines(&msg, &palette, 80);
+        assert_eq!(lines.len(), 1);
+        assert!(text(&lines[0]).contains("3 lines"));
+    }
+}
@@ -3,6 +3,8 @@
 //! Types are TUI-only (no dependency on locus_runtime). The runtime maps
 //! memory events to [MemoryMessage] for display. Colors from [crate::theme] only.
 
+use locus_core::MemoryUsage;
+use locus_core::db::{SessionLogEntry, SimilarWork};
 use ratatui::text::{Line, Span};
 
 use crate::layouts::{text_muted_style, text_style};
@@ -15,8 +17,12 @@ const MEMORY_LEFT_BORDER: &str = "· ";
 pub enum MemoryKind {
     /// Recalled memories from LocusGraph.
     Recall,
+    /// Recalled memories measured against the prompt's token budget.
+    Budget,
     /// Stored a new memory to LocusGraph.
     Store,
+    /// A past session handled a similar request.
+    Similar,
 }
 
 impl MemoryKind {
@@ -24,7 +30,9 @@ impl MemoryKind {
     pub fn label(self) -> &'static str {
         match self {
             MemoryKind::Recall => "Memory recall",
+            MemoryKind::Budget => "Memory budget",
             MemoryKind::Store => "Memory store",
+            MemoryKind::Similar => "Similar work",
         }
     }
 
@@ -32,7 +40,9 @@ impl MemoryKind {
     pub fn icon(self) -> &'static str {
         match self {
             MemoryKind::Recall => "◎",
+            MemoryKind::Budget => "◔",
             MemoryKind::Store => "◉",
+            MemoryKind::Similar => "≈",
         }
     }
 }
@@ -60,6 +70,27 @@ impl MemoryMessage {
         }
     }
 
+    /// Create a memory budget message: tokens spent on memories and how many made it in.
+    pub fn budget(budget_tokens: u64, used_tokens: u64, items: &[MemoryUsage]) -> Self {
+        let included = items.iter().filter(|i| i.included).count();
+        let context = if budget_tokens == 0 {
+            format!("{} tokens", used_tokens)
+        } else {
+            format!("{}/{} tokens", used_tokens, budget_tokens)
+        };
+        let left_out: Vec<String> = items
+            .iter()
+            .filter(|i| !i.included)
+            .map(|i| format!("{} ({} tokens)", i.summary, i.tokens))
+            .collect();
+        Self {
+            kind: MemoryKind::Budget,
+            context,
+            detail: format!("{} of {} memories", included, items.len()),
+            summary: (!left_out.is_empty()).then(|| format!("left out: {}", left_out.join(", "))),
+        }
+    }
+
     /// Create a memory store message.
     pub fn store(
         context_id: impl Into<String>,
@@ -80,6 +111,59 @@ impl MemoryMessage {
     }
 }
 
+impl MemoryMessage {
+    /// Create a message pointing at a past session that did similar work.
+    pub fn similar(work: &SimilarWork) -> Self {
+        Self {
+            kind: MemoryKind::Similar,
+            context: session_label(&work.matched).to_string(),
+            detail: format!(
+                "{} · {} files",
+                format_day(work.matched.ts),
+                work.files_modified.len()
+            ),
+            summary: Some(work.matched.title.clone()),
+        }
+    }
+}
+
+/// Session slug, or the start of the session id for unnamed sessions.
+pub(crate) fn session_label(entry: &SessionLogEntry) -> &str {
+    if entry.session_slug.is_empty() {
+        entry.session_id.get(..8).unwrap_or(&entry.session_id)
+    } else {
+        &entry.session_slug
+    }
+}
+
+/// Local calendar day of a unix timestamp, e.g. "March 3".
+pub(crate) fn format_day(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|d| d.with_timezone(&chrono::Local).format("%B %-d").to_string())
+        .unwrap_or_default()
+}
+
+/// Markdown summary of a past session: each turn's request and outcome, then the files it changed.
+pub fn similar_work_summary(work: &SimilarWork) -> String {
+    let mut out = format!(
+        "**Session {}** · {}\n\n",
+        session_label(&work.matched),
+        format_day(work.matched.ts)
+    );
+    for turn in &work.turns {
+        out.push_str(&format!("- {} → {}\n", turn.title, turn.outcome));
+    }
+    if !work.files_modified.is_empty() {
+        let files: Vec<String> = work
+            .files_modified
+            .iter()
+            .map(|f| format!("`{}`", f))
+            .collect();
+        out.push_str(&format!("\nFiles changed: {}", files.join(", ")));
+    }
+    out
+}
+
 /// Build a single [Line] for a memory event.
 pub fn memory_line(msg: &MemoryMessage, palette: &LocusPalette) -> Line<'static> {
     let mut spans = vec![Span::styled(
@@ -161,6 +245,31 @@ mod tests {
         assert_eq!(msg.detail, "3 memories");
     }
 
+    #[test]
+    fn memory_budget_message() {
+        let usage = |summary: &str, tokens, included| MemoryUsage {
+            summary: summary.into(),
+            tokens,
+            included,
+        };
+        let items = [
+            usage("JWT refresh lives in src/auth.rs", 9, true),
+            usage("HTTP client rewrite", 900, false),
+        ];
+        let msg = MemoryMessage::budget(500, 9, &items);
+        assert_eq!(msg.kind, MemoryKind::Budget);
+        assert_eq!(msg.context, "9/500 tokens");
+        assert_eq!(msg.detail, "1 of 2 memories");
+        assert_eq!(
+            msg.summary.as_deref(),
+            Some("left out: HTTP client rewrite (900 tokens)")
+        );
+
+        let msg = MemoryMessage::budget(0, 9, &items[..1]);
+        assert_eq!(msg.context, "9 tokens");
+        assert!(msg.summary.is_none());
+    }
+
     #[test]
     fn memory_store_message() {
         let msg = MemoryMessage::store(
@@ -181,6 +290,44 @@ mod tests {
         assert!(msg.summary.is_none());
     }
 
+    fn similar_work() -> SimilarWork {
+        let turn = |title: &str, files: &[&str]| SessionLogEntry {
+            session_id: "0123456789abcdef".into(),
+            session_slug: String::new(),
+            ts: 1_700_000_000,
+            title: title.into(),
+            outcome: "Done".into(),
+            files_modified: files.iter().map(|f| f.to_string()).collect(),
+            tool_calls: 1,
+        };
+        SimilarWork {
+            score: 0.8,
+            matched: turn("add retries", &["src/http.rs"]),
+            files_modified: vec!["src/http.rs".into(), "tests/http.rs".into()],
+            turns: vec![
+                turn("add retries", &["src/http.rs"]),
+                turn("cover timeouts", &["tests/http.rs"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn memory_similar_message() {
+        let msg = MemoryMessage::similar(&similar_work());
+        assert_eq!(msg.kind, MemoryKind::Similar);
+        assert_eq!(msg.context, "01234567");
+        assert!(msg.detail.ends_with("2 files"));
+        assert_eq!(msg.summary.as_deref(), Some("add retries"));
+    }
+
+    #[test]
+    fn similar_work_summary_lists_turns_and_files() {
+        let summary = similar_work_summary(&similar_work());
+        assert!(summary.starts_with("**Session 01234567**"));
+        assert!(summary.contains("- add retries → Done\n- cover timeouts → Done"));
+        assert!(summary.ends_with("Files changed: `src/http.rs`, `tests/http.rs`"));
+    }
+
     #[test]
     fn memory_line_builds() {
         let msg = MemoryMessage::recall("search query", 5);
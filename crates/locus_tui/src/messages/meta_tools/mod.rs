@@ -10,11 +10,11 @@ mod explain;
 mod search;
 mod tasks;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ratatui::text::{Line, Span};
 
-use crate::layouts::{danger_style, success_style, text_muted_style, text_style};
+use crate::layouts::{danger_style, success_style, text_muted_style, text_style, warning_style};
 use crate::theme::LocusPalette;
 use crate::utils::{LEFT_PADDING, format_duration};
 
@@ -59,6 +59,7 @@ impl MetaToolKind {
 #[derive(Debug, Clone)]
 pub enum MetaToolStatus {
     Running,
+    Throttled { until: Instant, reason: String },
     Done { duration_ms: u64, success: bool },
     Error { message: String },
 }
@@ -140,6 +141,7 @@ const META_LABEL_WIDTH: usize = 13;
 const META_RUNNING_INDICATOR: &str = "⠋";
 const META_SUCCESS_INDICATOR: &str = "✓";
 const META_FAILURE_INDICATOR: &str = "✗";
+const META_THROTTLED_INDICATOR: &str = "⏸";
 
 fn push_meta_label(spans: &mut Vec<Span<'static>>, msg: &MetaToolMessage, palette: &LocusPalette) {
     spans.push(Span::styled(
@@ -228,6 +230,38 @@ pub fn meta_tool_lines(
 
             vec![Line::from(spans)]
         }
+        MetaToolStatus::Throttled { until, reason } => {
+            let remaining = until.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let running = MetaToolMessage {
+                    status: MetaToolStatus::Running,
+                    ..msg.clone()
+                };
+                return meta_tool_lines(&running, palette, running_indicator);
+            }
+            let throttled_style = warning_style(palette.warning);
+            spans.push(Span::styled(
+                format!("{} ", META_THROTTLED_INDICATOR),
+                throttled_style,
+            ));
+            push_meta_label(&mut spans, msg, palette);
+            if let Some(d) = &msg.detail {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    d.clone(),
+                    text_muted_style(palette.text_muted),
+                ));
+            }
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                format!("throttled {}", format_duration(remaining)),
+                throttled_style,
+            ));
+            vec![
+                Line::from(spans),
+                meta_tool_detail_line(palette, reason.clone(), false),
+            ]
+        }
         MetaToolStatus::Done {
             duration_ms,
             success,
@@ -348,6 +382,32 @@ mod tests {
         assert!(lines[0].spans.iter().any(|s| s.content.contains("⠋")));
     }
 
+    #[test]
+    fn meta_tool_throttled_shows_wait_then_runs() {
+        let palette = LocusPalette::locus_dark();
+        let mut msg = MetaToolMessage::running(MetaToolKind::Task, Some("Add tests".into()));
+        msg.status = MetaToolStatus::Throttled {
+            until: Instant::now() + Duration::from_secs(30),
+            reason: "4% of rate-limit budget left".into(),
+        };
+        let lines = meta_tool_lines(&msg, &palette, None);
+        assert!(
+            lines[0]
+                .spans
+                .iter()
+                .any(|s| s.content.contains("throttled"))
+        );
+        assert!(lines[1].spans.iter().any(|s| s.content.contains("4%")));
+
+        msg.status = MetaToolStatus::Throttled {
+            until: Instant::now(),
+            reason: "done waiting".into(),
+        };
+        let lines = meta_tool_lines(&msg, &palette, Some("⠋"));
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].spans.iter().any(|s| s.content.contains("⠋")));
+    }
+
     #[test]
     fn meta_tool_info_line_builds() {
         let info = MetaToolInfo {
@@ -6,12 +6,14 @@
 //! - **tools** — Tool list, tool call status, and per-tool rendering modules.
 //! - **meta_tools** — Meta-tools tool_search, tool_explain, task with rendering.
 //! - **memory** — Memory recall/store events from LocusGraph.
+//! - **insights** — What LocusGraph remembers about the project, at session start.
 
 pub mod ai_message;
 pub mod ai_think_message;
 pub mod common;
 pub mod edit_diff;
 pub mod error;
+pub mod insights;
 pub mod markdown;
 pub mod memory;
 pub mod meta_tools;
@@ -0,0 +1,83 @@
+//! ast_search tool TUI rendering — structural code search.
+//!
+//! One line only: `fn $NAME($$$ARGS)  12 in 4 files`. No preview — matches go to LLM.
+
+use std::collections::HashSet;
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Longest pattern shown before it is cut off with `…`.
+const PATTERN_MAX_CHARS: usize = 40;
+
+/// Build status line spans for ast_search: `pattern  N in M files`, with
+/// ` (truncated)` when `max_results` cut the list.
+pub fn ast_search_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let pattern = args.get("pattern").and_then(|v| v.as_str()).unwrap_or("");
+    let pattern = pattern.split_whitespace().collect::<Vec<_>>().join(" ");
+    let muted = text_muted_style(palette.text_muted);
+
+    let shown = if pattern.chars().count() > PATTERN_MAX_CHARS {
+        let cut: String = pattern.chars().take(PATTERN_MAX_CHARS - 1).collect();
+        format!("{}…", cut)
+    } else {
+        pattern
+    };
+    let mut spans = vec![Span::styled(shown, muted)];
+
+    if let Some(matches) = result.get("matches").and_then(|v| v.as_array()) {
+        let total = result
+            .get("total")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(matches.len() as u64);
+        let files: HashSet<&str> = matches.iter().filter_map(|m| m["path"].as_str()).collect();
+        let summary = match (total, files.len()) {
+            (0, _) => "none found".to_string(),
+            (n, 1) => format!("{} in 1 file", n),
+            (n, f) => format!("{} in {} files", n, f),
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(summary, muted));
+        if result.get("truncated").and_then(|v| v.as_bool()) == Some(true) {
+            spans.push(Span::styled(" (truncated)", muted));
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_shows_pattern_and_counts() {
+        let args = serde_json::json!({"pattern": "fn $NAME($$$ARGS)\n    -> Result<$T>"});
+        let result = serde_json::json!({
+            "matches": [{"path": "a.rs"}, {"path": "b.rs"}],
+            "total": 2
+        });
+        let palette = LocusPalette::locus_dark();
+        let spans = ast_search_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, "fn $NAME($$$ARGS) -> Result<$T>");
+        assert!(spans.iter().any(|s| s.content == "2 in 2 files"));
+        assert!(!spans.iter().any(|s| s.content == " (truncated)"));
+    }
+
+    #[test]
+    fn status_cuts_long_patterns() {
+        let args = serde_json::json!({"pattern": "x".repeat(60)});
+        let result = serde_json::json!({"matches": [], "total": 0});
+        let palette = LocusPalette::locus_dark();
+        let spans = ast_search_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content.chars().count(), PATTERN_MAX_CHARS);
+        assert!(spans[0].content.ends_with('…'));
+        assert!(spans.iter().any(|s| s.content == "none found"));
+    }
+}
@@ -0,0 +1,82 @@
+//! code_nav tool TUI rendering — go-to-definition and find-references.
+//!
+//! One line only: `definition Store  2 in 2 files`. No preview — locations go to LLM.
+
+use std::collections::HashSet;
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Build status line spans for code_nav: `action symbol  N in M files`, with
+/// ` (truncated)` when `max_results` cut the list.
+pub fn code_nav_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let action = args.get("action").and_then(|v| v.as_str()).unwrap_or("");
+    let symbol = result
+        .get("symbol")
+        .or_else(|| args.get("symbol"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let muted = text_muted_style(palette.text_muted);
+
+    let mut spans = vec![Span::styled(format!("{} {}", action, symbol), muted)];
+
+    if let Some(locations) = result.get("locations").and_then(|v| v.as_array()) {
+        let total = result
+            .get("total")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(locations.len() as u64);
+        let files: HashSet<&str> = locations
+            .iter()
+            .filter_map(|l| l["path"].as_str())
+            .collect();
+        let summary = match (total, files.len()) {
+            (0, _) => "none found".to_string(),
+            (n, 1) => format!("{} in 1 file", n),
+            (n, f) => format!("{} in {} files", n, f),
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(summary, muted));
+        if result.get("truncated").and_then(|v| v.as_bool()) == Some(true) {
+            spans.push(Span::styled(" (truncated)", muted));
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_shows_action_symbol_and_counts() {
+        let args = serde_json::json!({"action": "references", "symbol": "Store::get"});
+        let result = serde_json::json!({
+            "symbol": "get",
+            "locations": [{"path": "a.rs"}, {"path": "a.rs"}, {"path": "b.rs"}],
+            "total": 7,
+            "truncated": true
+        });
+        let palette = LocusPalette::locus_dark();
+        let spans = code_nav_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, "references get");
+        assert!(spans.iter().any(|s| s.content == "7 in 2 files"));
+        assert!(spans.iter().any(|s| s.content == " (truncated)"));
+    }
+
+    #[test]
+    fn status_reports_no_locations() {
+        let args = serde_json::json!({"action": "definition", "symbol": "Missing"});
+        let result = serde_json::json!({"locations": [], "total": 0});
+        let palette = LocusPalette::locus_dark();
+        let spans = code_nav_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, "definition Missing");
+        assert!(spans.iter().any(|s| s.content == "none found"));
+    }
+}
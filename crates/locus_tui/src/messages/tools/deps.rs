@@ -0,0 +1,116 @@
+//! deps TUI rendering.
+//!
+//! One line: the action and ecosystems, then vulnerability counts by severity
+//! (audit), how many dependencies are outdated, or how many packages the tree
+//! lists. No preview — the model reads the full list.
+
+use ratatui::text::Span;
+
+use crate::layouts::{danger_style, text_muted_style, warning_style};
+use crate::theme::LocusPalette;
+
+/// Build status line spans for deps: `audit (cargo, npm)  2 high, 1 medium`,
+/// `outdated (npm)  3 outdated`, or `tree (cargo)  42 packages`.
+pub fn deps_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let action = args["action"].as_str().unwrap_or("audit");
+    let ecosystems: Vec<&str> = result["ecosystems"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.as_str())
+        .collect();
+    let label = match (ecosystems.is_empty(), args["ecosystem"].as_str()) {
+        (false, _) => format!("{} ({})", action, ecosystems.join(", ")),
+        (true, Some(ecosystem)) => format!("{} ({})", action, ecosystem),
+        (true, None) => action.to_string(),
+    };
+    let mut spans = vec![Span::styled(label, muted)];
+    let Some(total) = result["total"].as_u64() else {
+        return spans;
+    };
+    spans.push(Span::raw("  "));
+    match action {
+        "audit" => spans.extend(vulnerability_count_spans(&result["counts"], palette)),
+        "outdated" if total == 0 => spans.push(Span::styled("up to date".to_string(), muted)),
+        "outdated" => spans.push(Span::styled(
+            format!("{} outdated", total),
+            warning_style(palette.warning),
+        )),
+        _ => spans.push(Span::styled(
+            format!("{} package{}", total, if total == 1 { "" } else { "s" }),
+            muted,
+        )),
+    }
+    spans
+}
+
+/// `1 critical, 2 high, 1 medium`: critical and high in danger, medium in
+/// warning, the rest muted.
+fn vulnerability_count_spans(
+    counts: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let severities = [
+        ("critical", danger_style(palette.danger)),
+        ("high", danger_style(palette.danger)),
+        ("medium", warning_style(palette.warning)),
+        ("low", muted),
+        ("unknown", muted),
+    ];
+    let mut spans = Vec::new();
+    for (key, style) in severities {
+        let n = counts[key].as_u64().unwrap_or(0);
+        if n == 0 {
+            continue;
+        }
+        let sep = if spans.is_empty() { "" } else { ", " };
+        spans.push(Span::styled(format!("{}{} {}", sep, n, key), style));
+    }
+    if spans.is_empty() {
+        spans.push(Span::styled("no known vulnerabilities".to_string(), muted));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_action_and_findings() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"action": "audit"});
+        let result = serde_json::json!({
+            "action": "audit",
+            "ecosystems": ["cargo", "npm"],
+            "counts": {"critical": 0, "high": 2, "medium": 1, "low": 0, "unknown": 0},
+            "total": 3
+        });
+        let spans = deps_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "audit (cargo, npm)  2 high, 1 medium");
+
+        let clean = serde_json::json!({"ecosystems": ["pip"], "counts": {}, "total": 0});
+        let spans = deps_status_summary(&args, &clean, &palette);
+        assert_eq!(text(&spans), "audit (pip)  no known vulnerabilities");
+
+        let args = serde_json::json!({"action": "outdated", "ecosystem": "npm"});
+        let result = serde_json::json!({"ecosystems": ["npm"], "total": 3});
+        let spans = deps_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "outdated (npm)  3 outdated");
+
+        let args = serde_json::json!({"action": "tree"});
+        let result = serde_json::json!({"ecosystems": ["cargo"], "total": 1});
+        let spans = deps_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "tree (cargo)  1 package");
+    }
+}
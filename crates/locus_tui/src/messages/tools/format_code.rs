@@ -0,0 +1,98 @@
+//! format_code TUI rendering.
+//!
+//! One line: the paths and formatters, then how many files need (check) or got
+//! (apply) formatting. No preview — apply edits are in the edit history.
+
+use ratatui::text::Span;
+
+use crate::layouts::{success_style, text_muted_style, warning_style};
+use crate::theme::LocusPalette;
+
+/// Build status line spans for format_code: `src (rustfmt)  2 files to format`,
+/// `2 files formatted` after apply, or `clean`.
+pub fn format_code_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let paths: Vec<&str> = args["paths"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.as_str())
+        .collect();
+    let paths = if paths.is_empty() {
+        ".".to_string()
+    } else {
+        paths.join(", ")
+    };
+    let formatters: Vec<&str> = result["formatters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|f| f.as_str())
+        .collect();
+    let label = match (formatters.is_empty(), args["formatter"].as_str()) {
+        (false, _) => format!("{} ({})", paths, formatters.join(", ")),
+        (true, Some(formatter)) => format!("{} ({})", paths, formatter),
+        (true, None) => paths,
+    };
+    let mut spans = vec![Span::styled(label, muted)];
+    let Some(files) = result["files"].as_array() else {
+        return spans;
+    };
+    spans.push(Span::raw("  "));
+    let n = files.len();
+    let files_word = if n == 1 { "file" } else { "files" };
+    spans.push(match (n, result["mode"].as_str()) {
+        (0, _) => Span::styled("clean".to_string(), muted),
+        (_, Some("apply")) => Span::styled(
+            format!("{} {} formatted", n, files_word),
+            success_style(palette.success),
+        ),
+        _ => Span::styled(
+            format!("{} {} to format", n, files_word),
+            warning_style(palette.warning),
+        ),
+    });
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_formatters_and_files() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"paths": ["src", "web"]});
+        let result = serde_json::json!({
+            "mode": "check",
+            "formatters": ["rustfmt", "prettier"],
+            "files": [{"path": "src/main.rs", "formatter": "rustfmt"}, {"path": "web/a.ts", "formatter": "prettier"}],
+            "clean": false
+        });
+        let spans = format_code_status_summary(&args, &result, &palette);
+        assert_eq!(
+            text(&spans),
+            "src, web (rustfmt, prettier)  2 files to format"
+        );
+
+        let applied = serde_json::json!({
+            "mode": "apply",
+            "formatters": ["black"],
+            "files": [{"path": "app.py", "formatter": "black"}]
+        });
+        let spans = format_code_status_summary(&serde_json::json!({}), &applied, &palette);
+        assert_eq!(text(&spans), ". (black)  1 file formatted");
+
+        let clean = serde_json::json!({"mode": "check", "formatters": ["black"], "files": []});
+        let spans = format_code_status_summary(&serde_json::json!({}), &clean, &palette);
+        assert_eq!(text(&spans), ". (black)  clean");
+    }
+}
@@ -0,0 +1,205 @@
+//! git_status / git_diff / git_log / git_commit / git_branch TUI rendering.
+//!
+//! One line each: branch and change counts, `+A -D` totals, commit subjects,
+//! branch actions. No preview — diffs and logs go to the LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+fn array_len(value: &serde_json::Value, key: &str) -> usize {
+    value
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map_or(0, Vec::len)
+}
+
+fn totals(result: &serde_json::Value) -> String {
+    format!(
+        "{} files  +{} -{}",
+        result["files_changed"].as_u64().unwrap_or(0),
+        result["additions"].as_u64().unwrap_or(0),
+        result["deletions"].as_u64().unwrap_or(0)
+    )
+}
+
+/// Build status line spans for git_status: `main  2 staged, 1 unstaged, 3 untracked`
+/// (or `clean`), plus `↑A ↓B` against upstream.
+pub fn git_status_status_summary(
+    _args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let branch = result["branch"].as_str().unwrap_or("detached");
+    let mut spans = vec![Span::styled(branch.to_string(), muted)];
+
+    let counts: Vec<String> = ["staged", "unstaged", "untracked", "conflicted"]
+        .iter()
+        .filter_map(|key| {
+            let n = array_len(result, key);
+            (n > 0).then(|| format!("{} {}", n, key))
+        })
+        .collect();
+    if result.get("clean").is_some() {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            if counts.is_empty() {
+                "clean".to_string()
+            } else {
+                counts.join(", ")
+            },
+            muted,
+        ));
+    }
+
+    let ahead = result["ahead"].as_u64().unwrap_or(0);
+    let behind = result["behind"].as_u64().unwrap_or(0);
+    if ahead > 0 || behind > 0 {
+        spans.push(Span::styled(format!("  ↑{} ↓{}", ahead, behind), muted));
+    }
+    spans
+}
+
+/// Build status line spans for git_diff: `path|staged|base  N files  +A -D`.
+pub fn git_diff_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let mut target = args["path"].as_str().unwrap_or(".").to_string();
+    if args["staged"].as_bool().unwrap_or(false) {
+        target.push_str(" (staged)");
+    }
+    if let Some(base) = args["base"].as_str() {
+        target.push_str(&format!(" vs {}", base));
+    }
+    let mut spans = vec![Span::styled(target, muted)];
+    if result.get("files_changed").is_some() {
+        spans.push(Span::styled(format!("  {}", totals(result)), muted));
+    }
+    spans
+}
+
+/// Build status line spans for git_log: `rev  N commits  latest subject`.
+pub fn git_log_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let rev = args["rev"]
+        .as_str()
+        .or(args["path"].as_str())
+        .unwrap_or("HEAD");
+    let mut spans = vec![Span::styled(rev.to_string(), muted)];
+    if let Some(count) = result["count"].as_u64() {
+        spans.push(Span::styled(format!("  {} commits", count), muted));
+    }
+    if let Some(subject) = result["commits"][0]["subject"].as_str() {
+        spans.push(Span::styled(format!("  {}", subject), muted));
+    }
+    spans
+}
+
+/// Build status line spans for git_commit: `abc1234 subject  N files  +A -D`.
+pub fn git_commit_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let subject = result["subject"]
+        .as_str()
+        .or_else(|| args["message"].as_str().and_then(|m| m.lines().next()))
+        .unwrap_or_default();
+    let mut spans = Vec::new();
+    if let Some(short) = result["short_hash"].as_str() {
+        spans.push(Span::styled(format!("{} ", short), muted));
+    }
+    spans.push(Span::styled(subject.to_string(), muted));
+    if result.get("files_changed").is_some() {
+        spans.push(Span::styled(format!("  {}", totals(result)), muted));
+    }
+    spans
+}
+
+/// Build status line spans for git_branch: `N branches  on main` for list,
+/// `create name → on name` for changes.
+pub fn git_branch_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let action = args["action"].as_str().unwrap_or("list");
+    let mut spans = if action == "list" {
+        vec![Span::styled(
+            format!("{} branches", array_len(result, "branches")),
+            muted,
+        )]
+    } else {
+        vec![Span::styled(
+            format!("{} {}", action, args["name"].as_str().unwrap_or("")),
+            muted,
+        )]
+    };
+    if let Some(current) = result["current"].as_str() {
+        spans.push(Span::styled(format!("  on {}", current), muted));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_branch_counts_and_divergence() {
+        let result = serde_json::json!({
+            "branch": "main", "ahead": 2, "behind": 0, "clean": false,
+            "staged": [{}], "unstaged": [{}, {}], "untracked": [], "conflicted": []
+        });
+        let palette = LocusPalette::locus_dark();
+        let spans = git_status_status_summary(&serde_json::json!({}), &result, &palette);
+        assert_eq!(text(&spans), "main  1 staged, 2 unstaged  ↑2 ↓0");
+
+        let clean = serde_json::json!({"branch": "main", "clean": true});
+        let spans = git_status_status_summary(&serde_json::json!({}), &clean, &palette);
+        assert_eq!(text(&spans), "main  clean");
+    }
+
+    #[test]
+    fn diff_and_commit_show_totals() {
+        let palette = LocusPalette::locus_dark();
+        let totals = serde_json::json!({"files_changed": 2, "additions": 10, "deletions": 3});
+        let args = serde_json::json!({"staged": true});
+        let spans = git_diff_status_summary(&args, &totals, &palette);
+        assert_eq!(text(&spans), ". (staged)  2 files  +10 -3");
+
+        let mut commit = totals.clone();
+        commit["short_hash"] = "abc1234".into();
+        commit["subject"] = "Fix login".into();
+        let spans = git_commit_status_summary(&serde_json::json!({}), &commit, &palette);
+        assert_eq!(text(&spans), "abc1234 Fix login  2 files  +10 -3");
+    }
+
+    #[test]
+    fn log_and_branch_summaries() {
+        let palette = LocusPalette::locus_dark();
+        let log = serde_json::json!({"count": 3, "commits": [{"subject": "Add main"}]});
+        let spans = git_log_status_summary(&serde_json::json!({}), &log, &palette);
+        assert_eq!(text(&spans), "HEAD  3 commits  Add main");
+
+        let args = serde_json::json!({"action": "create", "name": "feature/x"});
+        let result = serde_json::json!({"current": "feature/x"});
+        let spans = git_branch_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "create feature/x  on feature/x");
+    }
+}
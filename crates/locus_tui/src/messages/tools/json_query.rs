@@ -0,0 +1,82 @@
+//! json_query tool TUI rendering — jq/JSONPath queries over JSON, YAML and TOML.
+//!
+//! One line only: `package.json  .dependencies | keys  3 results`. No preview —
+//! the values go to the LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::{text_muted_style, text_style, warning_style};
+use crate::theme::LocusPalette;
+
+/// Longest query shown before it is cut with `…`.
+const MAX_QUERY_CHARS: usize = 48;
+
+/// Build status line spans for json_query: source, query and result count,
+/// with `(truncated)` when results were cut.
+pub fn json_query_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let source = result["source"]
+        .as_str()
+        .or_else(|| args["path"].as_str())
+        .unwrap_or("inline");
+    let mut spans = vec![Span::styled(source.to_string(), muted)];
+
+    if let Some(query) = args["query"].as_str() {
+        let mut shown: String = query.chars().take(MAX_QUERY_CHARS).collect();
+        if query.chars().count() > MAX_QUERY_CHARS {
+            shown.push('…');
+        }
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(shown, text_style(palette.text)));
+    }
+
+    if let Some(total) = result["total"].as_u64() {
+        spans.push(Span::styled(
+            format!("  {} result{}", total, if total == 1 { "" } else { "s" }),
+            muted,
+        ));
+        if result["truncated"].as_bool() == Some(true) {
+            spans.push(Span::styled(" (truncated)", warning_style(palette.warning)));
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_source_query_and_count() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"query": ".dependencies | keys", "path": "package.json"});
+        let result = serde_json::json!({"source": "package.json", "total": 1, "truncated": false});
+        assert_eq!(
+            text(&json_query_status_summary(&args, &result, &palette)),
+            "package.json  .dependencies | keys  1 result"
+        );
+    }
+
+    #[test]
+    fn status_marks_truncation_and_cuts_long_queries() {
+        let palette = LocusPalette::locus_dark();
+        let query = format!(".items[] | select(.name == \"{}\")", "x".repeat(40));
+        let args = serde_json::json!({"query": query, "content": "{}"});
+        let result = serde_json::json!({"source": "inline", "total": 250, "truncated": true});
+        let line = text(&json_query_status_summary(&args, &result, &palette));
+        assert!(
+            line.starts_with("inline  .items[] | select(.name == \""),
+            "{}",
+            line
+        );
+        assert!(line.ends_with("…  250 results (truncated)"), "{}", line);
+    }
+}
@@ -0,0 +1,68 @@
+//! lint TUI rendering.
+//!
+//! One line: the linted path, the linters that ran and the diagnostic counts.
+//! No preview — the diagnostics go to the LLM.
+
+use ratatui::text::Span;
+
+use super::lsp::severity_count_spans;
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Build status line spans for lint: `src (clippy, ruff)  2 errors, 1 warning` (or `clean`).
+pub fn lint_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let path = args["path"].as_str().unwrap_or(".");
+    let linters: Vec<&str> = result["linters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| l.as_str())
+        .collect();
+    let label = match (linters.is_empty(), args["linter"].as_str()) {
+        (false, _) => format!("{} ({})", path, linters.join(", ")),
+        (true, Some(linter)) => format!("{} ({})", path, linter),
+        (true, None) => path.to_string(),
+    };
+    let mut spans = vec![Span::styled(label, muted)];
+    let Some(counts) = result.get("counts") else {
+        return spans;
+    };
+    spans.push(Span::raw("  "));
+    spans.extend(severity_count_spans(counts, palette));
+    if result["truncated"] == true {
+        spans.push(Span::styled(" (truncated)".to_string(), muted));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_linters_and_counts() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"path": "src"});
+        let result = serde_json::json!({
+            "linters": ["clippy", "ruff"],
+            "counts": {"error": 0, "warning": 3, "information": 0, "hint": 0},
+            "truncated": true
+        });
+        let spans = lint_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "src (clippy, ruff)  3 warnings (truncated)");
+
+        let clean =
+            serde_json::json!({"linters": ["eslint"], "counts": {"error": 0, "warning": 0}});
+        let spans = lint_status_summary(&serde_json::json!({}), &clean, &palette);
+        assert_eq!(text(&spans), ". (eslint)  clean");
+    }
+}
@@ -0,0 +1,175 @@
+//! lsp_diagnostics / lsp_hover / lsp_rename TUI rendering.
+//!
+//! One line each: diagnostic counts, the first line of the hover text, files
+//! and edits touched by a rename. No preview — the details go to the LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::{danger_style, text_muted_style};
+use crate::theme::LocusPalette;
+
+/// Build status line spans for lsp_diagnostics: `path  2 errors, 1 warning` (or `clean`).
+pub fn lsp_diagnostics_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let mut spans = vec![Span::styled(
+        args["path"].as_str().unwrap_or_default().to_string(),
+        muted,
+    )];
+    let Some(counts) = result.get("counts") else {
+        return spans;
+    };
+    spans.push(Span::raw("  "));
+    spans.extend(severity_count_spans(counts, palette));
+    if result["complete"] == false {
+        spans.push(Span::styled(" (still checking)".to_string(), muted));
+    }
+    spans
+}
+
+/// `2 errors, 1 warning` (errors in danger color) or `clean`, from a
+/// `counts` object keyed by severity; shared with the lint tool.
+pub(super) fn severity_count_spans(
+    counts: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let count = |key: &str| counts[key].as_u64().unwrap_or(0);
+    let plural = |n: u64, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+    let errors = count("error");
+    let warnings = count("warning");
+    if errors == 0 && warnings == 0 {
+        return vec![Span::styled("clean".to_string(), muted)];
+    }
+    let mut spans = Vec::new();
+    if errors > 0 {
+        spans.push(Span::styled(
+            plural(errors, "error"),
+            danger_style(palette.danger),
+        ));
+    }
+    if warnings > 0 {
+        let sep = if errors > 0 { ", " } else { "" };
+        spans.push(Span::styled(
+            format!("{}{}", sep, plural(warnings, "warning")),
+            muted,
+        ));
+    }
+    spans
+}
+
+/// Build status line spans for lsp_hover: `path:line  first line of the hover`.
+pub fn lsp_hover_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let mut spans = vec![Span::styled(location(args), muted)];
+    if let Some(contents) = result["contents"].as_str() {
+        let first = contents
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with("```"))
+            .unwrap_or("no info");
+        spans.push(Span::styled(format!("  {}", truncate(first, 60)), muted));
+    }
+    spans
+}
+
+/// Build status line spans for lsp_rename: `symbol → new  N edits in M files`.
+pub fn lsp_rename_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let from = args["symbol"]
+        .as_str()
+        .map_or_else(|| location(args), str::to_string);
+    let mut spans = vec![Span::styled(
+        format!(
+            "{} → {}",
+            from,
+            args["new_name"].as_str().unwrap_or_default()
+        ),
+        muted,
+    )];
+    if let Some(total) = result["total_edits"].as_u64() {
+        let files = result["files"].as_array().map_or(0, Vec::len);
+        spans.push(Span::styled(
+            format!("  {} edits in {} files", total, files),
+            muted,
+        ));
+        if result["applied"] == false {
+            spans.push(Span::styled(" (dry run)".to_string(), muted));
+        }
+    }
+    spans
+}
+
+fn location(args: &serde_json::Value) -> String {
+    format!(
+        "{}:{}",
+        args["path"].as_str().unwrap_or_default(),
+        args["line"].as_u64().unwrap_or(0)
+    )
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max - 1).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn diagnostics_show_counts_or_clean() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"path": "src/main.rs"});
+        let result = serde_json::json!({
+            "complete": true,
+            "counts": {"error": 2, "warning": 1, "information": 0, "hint": 0}
+        });
+        let spans = lsp_diagnostics_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "src/main.rs  2 errors, 1 warning");
+
+        let clean = serde_json::json!({"complete": false, "counts": {"error": 0, "warning": 0}});
+        let spans = lsp_diagnostics_status_summary(&args, &clean, &palette);
+        assert_eq!(text(&spans), "src/main.rs  clean (still checking)");
+    }
+
+    #[test]
+    fn hover_shows_first_line_of_contents() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"path": "src/lib.rs", "line": 4, "symbol": "greet"});
+        let result =
+            serde_json::json!({"contents": "```rust\nfn greet(name: &str) -> String\n```"});
+        let spans = lsp_hover_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "src/lib.rs:4  fn greet(name: &str) -> String");
+    }
+
+    #[test]
+    fn rename_shows_edit_totals() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"path": "src/lib.rs", "line": 1, "symbol": "greet", "new_name": "welcome", "dry_run": true});
+        let result = serde_json::json!({"applied": false, "total_edits": 3, "files": [{}, {}]});
+        let spans = lsp_rename_status_summary(&args, &result, &palette);
+        assert_eq!(
+            text(&spans),
+            "greet → welcome  3 edits in 2 files (dry run)"
+        );
+    }
+}
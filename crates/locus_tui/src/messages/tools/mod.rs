@@ -6,15 +6,29 @@
 //!
 //! Per-tool rendering modules handle summary extraction and preview lines.
 
+mod ast_search;
 mod bash;
+mod code_nav;
 mod create_file;
+mod deps;
 mod edit_file;
 mod finder;
+mod format_code;
+mod git;
 mod glob;
 mod grep;
 mod handoff;
+mod json_query;
+mod lint;
+mod lsp;
+mod notes;
 mod read;
+mod repo_stats;
+mod semantic_search;
+mod sqlite_query;
 mod task_list;
+mod todo_scan;
+mod tree;
 mod undo_edit;
 mod web_automation;
 
@@ -30,15 +44,34 @@ use crate::theme::LocusPalette;
 use crate::utils::{LEFT_PADDING, format_duration};
 
 // Re-export types
+pub use ast_search::ast_search_status_summary;
 pub use bash::{bash_preview_lines, bash_status_summary, bash_summary};
+pub use code_nav::code_nav_status_summary;
 pub use create_file::create_file_status_summary;
+pub use deps::deps_status_summary;
 pub use edit_file::{edit_file_diff_lines, edit_file_status_summary};
 pub use finder::finder_status_summary;
+pub use format_code::format_code_status_summary;
+pub use git::{
+    git_branch_status_summary, git_commit_status_summary, git_diff_status_summary,
+    git_log_status_summary, git_status_status_summary,
+};
 pub use glob::{glob_preview_lines, glob_status_summary};
 pub use grep::{grep_preview_lines, grep_status_summary};
 pub use handoff::{handoff_preview_line, handoff_status_summary};
+pub use json_query::json_query_status_summary;
+pub use lint::lint_status_summary;
+pub use lsp::{
+    lsp_diagnostics_status_summary, lsp_hover_status_summary, lsp_rename_status_summary,
+};
+pub use notes::notes_status_summary;
 pub use read::{read_dir_status_summary, read_file_status_summary};
-pub use task_list::task_list_status_summary;
+pub use repo_stats::{repo_stats_preview_lines, repo_stats_status_summary};
+pub use semantic_search::semantic_search_status_summary;
+pub use sqlite_query::sqlite_query_status_summary;
+pub use task_list::{task_list_preview_lines, task_list_status_summary};
+pub use todo_scan::todo_scan_status_summary;
+pub use tree::tree_status_summary;
 pub use undo_edit::undo_edit_status_summary;
 pub use web_automation::{web_fetch_status_summary, web_search_status_summary};
 
@@ -94,6 +127,8 @@ pub struct ToolCallMessage {
     pub args: Option<serde_json::Value>,
     /// Optional result JSON for per-tool rendering.
     pub result: Option<serde_json::Value>,
+    /// While running, the latest progress line the tool reported.
+    pub progress: Option<String>,
 }
 
 impl ToolCallMessage {
@@ -115,6 +150,7 @@ impl ToolCallMessage {
             edit_diff: None,
             args: None,
             result: None,
+            progress: None,
         }
     }
 
@@ -138,6 +174,7 @@ impl ToolCallMessage {
             edit_diff,
             args: None,
             result: None,
+            progress: None,
         }
     }
 
@@ -158,6 +195,7 @@ impl ToolCallMessage {
             edit_diff: None,
             args: None,
             result: None,
+            progress: None,
         }
     }
 }
@@ -170,17 +208,38 @@ const TOOL_SUCCESS_INDICATOR: &str = "✓";
 const TOOL_FAILURE_INDICATOR: &str = "✗";
 
 /// Format tool name for display: edit_file → Edit, create_file → Create, etc.
-fn format_tool_name(name: &str) -> String {
+pub(crate) fn format_tool_name(name: &str) -> String {
     match name {
         "edit_file" => "Edit",
         "create_file" => "Create",
         "undo_edit" => "Undo",
+        "file_history" => "History",
         "bash" => "Bash",
         "read" | "view" => "Read",
         "glob" => "Glob",
         "grep" => "Grep",
         "finder" => "Finder",
+        "tree" => "Tree",
+        "todo_scan" => "TODOs",
+        "repo_stats" => "Repo stats",
+        "json_query" => "Query",
+        "sqlite_query" => "SQLite",
+        "notes" => "Notes",
+        "code_nav" => "Code nav",
+        "ast_search" => "AST search",
+        "semantic_search" => "Semantic",
+        "git_status" => "Git status",
+        "git_diff" => "Git diff",
+        "git_log" => "Git log",
+        "git_commit" => "Commit",
+        "git_branch" => "Branch",
+        "lsp_diagnostics" => "Diagnostics",
+        "lsp_hover" => "Hover",
+        "lsp_rename" => "Rename",
         "handoff" => "Handoff",
+        "lint" => "Lint",
+        "format_code" => "Format",
+        "deps" => "Deps",
         "task_list" => "Tasks",
         "web_fetch" | "fetch" => "Fetch",
         "web_search" => "Search",
@@ -346,7 +405,16 @@ pub fn tool_call_lines(
                     text_muted_style(palette.text_muted),
                 ));
             }
-            vec![Line::from(line1)]
+            let mut lines = vec![Line::from(line1)];
+            if let Some(progress) = &msg.progress {
+                let prefix = if in_group {
+                    format!("{}    ", LEFT_PADDING)
+                } else {
+                    LEFT_PADDING.to_string()
+                };
+                lines.push(tool_detail_line(&prefix, palette, progress.clone(), false));
+            }
+            lines
         }
         ToolCallStatus::Done {
             duration_ms,
@@ -451,8 +519,28 @@ fn get_tool_status_spans(msg: &ToolCallMessage, palette: &LocusPalette) -> Vec<S
         "glob" => glob_status_summary(args, result, palette),
         "grep" => grep_status_summary(args, result, palette),
         "finder" => finder_status_summary(args, result, palette),
+        "tree" => tree_status_summary(args, result, palette),
+        "todo_scan" => todo_scan_status_summary(args, result, palette),
+        "repo_stats" => repo_stats_status_summary(args, result, palette),
+        "json_query" => json_query_status_summary(args, result, palette),
+        "sqlite_query" => sqlite_query_status_summary(args, result, palette),
+        "code_nav" => code_nav_status_summary(args, result, palette),
+        "ast_search" => ast_search_status_summary(args, result, palette),
+        "semantic_search" => semantic_search_status_summary(args, result, palette),
+        "git_status" => git_status_status_summary(args, result, palette),
+        "git_diff" => git_diff_status_summary(args, result, palette),
+        "git_log" => git_log_status_summary(args, result, palette),
+        "git_commit" => git_commit_status_summary(args, result, palette),
+        "git_branch" => git_branch_status_summary(args, result, palette),
+        "lsp_diagnostics" => lsp_diagnostics_status_summary(args, result, palette),
+        "lint" => lint_status_summary(args, result, palette),
+        "format_code" => format_code_status_summary(args, result, palette),
+        "deps" => deps_status_summary(args, result, palette),
+        "lsp_hover" => lsp_hover_status_summary(args, result, palette),
+        "lsp_rename" => lsp_rename_status_summary(args, result, palette),
         "handoff" => handoff_status_summary(args, result, palette),
         "task_list" => task_list_status_summary(args, result, palette),
+        "notes" => notes_status_summary(args, result, palette),
         "web_fetch" | "fetch" => web_fetch_status_summary(args, result, palette),
         "web_search" => web_search_status_summary(args, result, palette),
         _ => vec![],
@@ -475,6 +563,8 @@ fn get_tool_preview_lines(
         "glob" => glob_preview_lines(result, palette),
         "grep" => grep_preview_lines(result, palette),
         "handoff" => handoff_preview_line(result, palette).into_iter().collect(),
+        "task_list" => task_list_preview_lines(result, palette),
+        "repo_stats" => repo_stats_preview_lines(result, palette),
         _ => vec![],
     }
 }
@@ -528,6 +618,21 @@ mod tests {
         assert!(!line.spans.is_empty());
     }
 
+    #[test]
+    fn tool_call_running_shows_progress() {
+        let mut msg = ToolCallMessage::running("t1", "web_automation", None);
+        let palette = LocusPalette::locus_dark();
+        assert_eq!(
+            tool_call_lines(&msg, &palette, None, None, None, false, true).len(),
+            1
+        );
+        msg.progress = Some("Filling the search form".into());
+        let lines = tool_call_lines(&msg, &palette, None, None, None, false, true);
+        assert_eq!(lines.len(), 2);
+        let text: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.ends_with("Filling the search form"));
+    }
+
     #[test]
     fn tool_info_line_builds() {
         let info = ToolInfo {
@@ -0,0 +1,76 @@
+//! notes tool TUI rendering — session scratch notes.
+//!
+//! One line only: `append plan  1.2 KB` or `list  3 notes`. No preview — note
+//! text stays out of the transcript, which is the point of the tool.
+
+use ratatui::text::Span;
+
+use crate::layouts::{text_muted_style, text_style};
+use crate::theme::LocusPalette;
+
+/// `1234` bytes as `1.2 KB`, small sizes as `N B`.
+fn size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Build status line spans for notes: action and note name, then the note's
+/// size (write/append/read) or the number of notes (list).
+pub fn notes_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let action = args["action"].as_str().unwrap_or("");
+    let mut spans = vec![Span::styled(action.to_string(), muted)];
+
+    if let Some(name) = result["name"].as_str().or_else(|| args["name"].as_str()) {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(name.to_string(), text_style(palette.text)));
+    }
+    if let Some(notes) = result["notes"].as_array() {
+        let n = notes.len();
+        spans.push(Span::styled(
+            format!("  {} note{}", n, if n == 1 { "" } else { "s" }),
+            muted,
+        ));
+    } else if let Some(bytes) = result["bytes"].as_u64() {
+        spans.push(Span::styled(format!("  {}", size(bytes)), muted));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_action_name_and_size() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"action": "append", "name": "plan.md", "content": "- x"});
+        let result = serde_json::json!({"session": "s1", "name": "plan", "bytes": 1300});
+        assert_eq!(
+            text(&notes_status_summary(&args, &result, &palette)),
+            "append plan  1.3 KB"
+        );
+    }
+
+    #[test]
+    fn status_counts_listed_notes() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"action": "list"});
+        let result = serde_json::json!({"session": "s1", "notes": [{"name": "plan"}]});
+        assert_eq!(
+            text(&notes_status_summary(&args, &result, &palette)),
+            "list  1 note"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! repo_stats tool TUI rendering — codebase statistics.
+//!
+//! One line: `path  N files, M lines of code  Rust 72%, TypeScript 20%`.
+//! Preview is a small repo context block: the directories with the most code
+//! and the largest files.
+
+use ratatui::text::{Line, Span};
+
+use crate::layouts::{text_muted_style, text_style};
+use crate::theme::LocusPalette;
+use crate::utils::LEFT_PADDING;
+
+const PREVIEW_INDENT: &str = "    ";
+const MAX_SUMMARY_LANGUAGES: usize = 3;
+const MAX_PREVIEW_DIRECTORIES: usize = 4;
+const MAX_PREVIEW_FILES: usize = 3;
+
+/// `12345` as `12.3k`, `987` as-is.
+fn compact(n: u64) -> String {
+    match n {
+        0..1_000 => n.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+/// Build status line spans for repo_stats: path, file and code line counts, and
+/// the share of code of the top languages.
+pub fn repo_stats_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let path = args["path"].as_str().unwrap_or(".");
+    let mut spans = vec![Span::styled(path.to_string(), muted)];
+    let Some(files) = result["files"].as_u64() else {
+        return spans;
+    };
+    let code = result["lines"]["code"].as_u64().unwrap_or(0);
+    spans.push(Span::raw("  "));
+    spans.push(Span::styled(
+        format!(
+            "{} file{}, {} lines of code",
+            files,
+            if files == 1 { "" } else { "s" },
+            compact(code)
+        ),
+        text_style(palette.text),
+    ));
+
+    let shares: Vec<String> = result["languages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .take(MAX_SUMMARY_LANGUAGES)
+        .filter_map(|l| {
+            let share = l["code"].as_u64()? * 100 / code.max(1);
+            Some(format!("{} {}%", l["language"].as_str()?, share))
+        })
+        .collect();
+    if !shares.is_empty() {
+        spans.push(Span::styled(format!("  {}", shares.join(", ")), muted));
+    }
+    spans
+}
+
+/// Build repo context preview lines: top directories by code, then the largest files.
+pub fn repo_stats_preview_lines(
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Line<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let row = |label: &str, name: &str, detail: String| {
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::raw(PREVIEW_INDENT),
+            Span::styled(format!("{:<8}", label), muted),
+            Span::styled(name.to_string(), text_style(palette.text)),
+            Span::styled(format!("  {}", detail), muted),
+        ])
+    };
+    let mut lines = Vec::new();
+    for dir in result["directories"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .take(MAX_PREVIEW_DIRECTORIES)
+    {
+        let (Some(path), Some(code)) = (dir["path"].as_str(), dir["code"].as_u64()) else {
+            continue;
+        };
+        lines.push(row("dir", path, format!("{} code", compact(code))));
+    }
+    for file in result["largest_files"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .take(MAX_PREVIEW_FILES)
+    {
+        let (Some(path), Some(total)) = (file["path"].as_str(), file["lines"].as_u64()) else {
+            continue;
+        };
+        lines.push(row("largest", path, format!("{} lines", compact(total))));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn sample() -> serde_json::Value {
+        serde_json::json!({
+            "path": ".",
+            "files": 120,
+            "lines": {"code": 12345, "comment": 800, "blank": 1500},
+            "languages": [
+                {"language": "Rust", "files": 80, "code": 9000},
+                {"language": "TypeScript", "files": 30, "code": 3000},
+                {"language": "TOML", "files": 5, "code": 300},
+                {"language": "Markdown", "files": 5, "code": 45}
+            ],
+            "directories": [{"path": "crates", "files": 90, "code": 11000}, {"path": "web", "files": 20, "code": 1345}],
+            "largest_files": [{"path": "crates/core/src/lib.rs", "language": "Rust", "lines": 2400, "code": 2000, "bytes": 90000}]
+        })
+    }
+
+    #[test]
+    fn status_shows_size_and_language_shares() {
+        let palette = LocusPalette::locus_dark();
+        let spans = repo_stats_status_summary(&serde_json::json!({}), &sample(), &palette);
+        assert_eq!(
+            text(&spans),
+            ".  120 files, 12.3k lines of code  Rust 72%, TypeScript 24%, TOML 2%"
+        );
+    }
+
+    #[test]
+    fn preview_lists_directories_and_largest_files() {
+        let palette = LocusPalette::locus_dark();
+        let lines = repo_stats_preview_lines(&sample(), &palette);
+        let rows: Vec<String> = lines
+            .iter()
+            .map(|l| text(&l.spans).trim().to_string())
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "dir     crates  11.0k code",
+                "dir     web  1.3k code",
+                "largest crates/core/src/lib.rs  2.4k lines",
+            ]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! semantic_search tool TUI rendering — embedding-based code search.
+//!
+//! One line only: `"refresh JWTs"  → src/auth.rs:4  8 results  (3 reindexed)`.
+//! No preview — snippets go to the LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Longest query shown before it is cut off with `…`.
+const QUERY_MAX_CHARS: usize = 40;
+
+/// Build status line spans for semantic_search: the query, the top hit, the
+/// result count and how many files the index re-embedded for this call.
+pub fn semantic_search_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let query = args["query"].as_str().unwrap_or("").trim();
+    let shown = if query.chars().count() > QUERY_MAX_CHARS {
+        let cut: String = query.chars().take(QUERY_MAX_CHARS - 1).collect();
+        format!("{}…", cut)
+    } else {
+        query.to_string()
+    };
+    let mut spans = vec![Span::styled(format!("\"{}\"", shown), muted)];
+
+    let Some(results) = result["results"].as_array() else {
+        return spans;
+    };
+    spans.push(Span::raw("  "));
+    match results.first() {
+        Some(top) => spans.push(Span::styled(
+            format!(
+                "→ {}:{}  {} results",
+                top["path"].as_str().unwrap_or(""),
+                top["start_line"].as_u64().unwrap_or(0),
+                results.len()
+            ),
+            muted,
+        )),
+        None => spans.push(Span::styled("none found".to_string(), muted)),
+    }
+    let updated = result["index"]["updated"].as_u64().unwrap_or(0);
+    if updated > 0 {
+        spans.push(Span::styled(format!("  ({} reindexed)", updated), muted));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn summary_shows_top_hit_and_reindexed_files() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"query": "refresh JWTs"});
+        let result = serde_json::json!({
+            "results": [{"path": "src/auth.rs", "start_line": 4}, {"path": "src/db.rs", "start_line": 1}],
+            "index": {"files": 3, "chunks": 5, "updated": 3, "removed": 0}
+        });
+        let spans = semantic_search_status_summary(&args, &result, &palette);
+        assert_eq!(
+            text(&spans),
+            "\"refresh JWTs\"  → src/auth.rs:4  2 results  (3 reindexed)"
+        );
+    }
+
+    #[test]
+    fn summary_without_results() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"query": "payroll"});
+        let result = serde_json::json!({"results": [], "index": {"updated": 0}});
+        let spans = semantic_search_status_summary(&args, &result, &palette);
+        assert_eq!(text(&spans), "\"payroll\"  none found");
+    }
+}
@@ -0,0 +1,79 @@
+//! sqlite_query tool TUI rendering — read-only SQL over a workspace database.
+//!
+//! One line only: `fixtures/app.db  SELECT name FROM users  3 rows`. No preview —
+//! the rows go to the LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::{text_muted_style, text_style, warning_style};
+use crate::theme::LocusPalette;
+
+/// Longest query shown before it is cut with `…`.
+const MAX_QUERY_CHARS: usize = 48;
+
+/// Build status line spans for sqlite_query: database, query (whitespace
+/// collapsed) and row count, with `(truncated)` when rows were cut.
+pub fn sqlite_query_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let path = args["path"].as_str().unwrap_or("");
+    let mut spans = vec![Span::styled(path.to_string(), muted)];
+
+    if let Some(query) = args["query"].as_str() {
+        let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut shown: String = query.chars().take(MAX_QUERY_CHARS).collect();
+        if query.chars().count() > MAX_QUERY_CHARS {
+            shown.push('…');
+        }
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(shown, text_style(palette.text)));
+    }
+
+    if let Some(rows) = result["rows"].as_array() {
+        let n = rows.len();
+        spans.push(Span::styled(
+            format!("  {} row{}", n, if n == 1 { "" } else { "s" }),
+            muted,
+        ));
+        if result["truncated"].as_bool() == Some(true) {
+            spans.push(Span::styled(" (truncated)", warning_style(palette.warning)));
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn status_shows_database_query_and_rows() {
+        let palette = LocusPalette::locus_dark();
+        let args =
+            serde_json::json!({"path": "fixtures/app.db", "query": "SELECT name\n  FROM users"});
+        let result =
+            serde_json::json!({"columns": ["name"], "rows": [["ada"]], "truncated": false});
+        assert_eq!(
+            text(&sqlite_query_status_summary(&args, &result, &palette)),
+            "fixtures/app.db  SELECT name FROM users  1 row"
+        );
+    }
+
+    #[test]
+    fn status_marks_truncation() {
+        let palette = LocusPalette::locus_dark();
+        let args = serde_json::json!({"path": ".locus/locus.db", "query": "SELECT * FROM edit_history WHERE path LIKE 'src/%' ORDER BY ts DESC"});
+        let result = serde_json::json!({"rows": [[1], [2]], "truncated": true});
+        assert_eq!(
+            text(&sqlite_query_status_summary(&args, &result, &palette)),
+            ".locus/locus.db  SELECT * FROM edit_history WHERE path LIKE 'src/…  2 rows (truncated)"
+        );
+    }
+}
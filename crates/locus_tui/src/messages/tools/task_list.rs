@@ -1,11 +1,52 @@
 //! task_list tool TUI rendering — task management.
 //!
-//! One line: `action  N tasks (M active)`. Content goes to LLM.
+//! One line: `action  N tasks (M active)`, plus `criteria M/N` when tasks carry
+//! acceptance criteria. Preview is a small task board: one line per task and a
+//! ✓/○ line per criterion with its evidence.
 
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 
-use crate::layouts::text_muted_style;
+use crate::layouts::{success_style, text_muted_style, text_style};
 use crate::theme::LocusPalette;
+use crate::utils::LEFT_PADDING;
+
+const PREVIEW_INDENT: &str = "    ";
+const MAX_PREVIEW_TASKS: usize = 8;
+const MAX_CRITERIA_PER_TASK: usize = 5;
+const MAX_EVIDENCE_CHARS: usize = 60;
+
+/// Tasks in a result: `tasks` array (create/list/add) or the task itself (get/update/check).
+fn result_tasks(result: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match result.get("tasks").and_then(|v| v.as_array()) {
+        Some(tasks) => tasks.iter().collect(),
+        None if result.get("title").is_some() => vec![result],
+        None => vec![],
+    }
+}
+
+fn criteria_of(task: &serde_json::Value) -> &[serde_json::Value] {
+    task.get("criteria")
+        .and_then(|v| v.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or(&[])
+}
+
+fn is_met(criterion: &serde_json::Value) -> bool {
+    criterion
+        .get("met")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// (met, total) acceptance criteria across the tasks in a result.
+fn criteria_progress(result: &serde_json::Value) -> (usize, usize) {
+    result_tasks(result)
+        .into_iter()
+        .flat_map(criteria_of)
+        .fold((0, 0), |(met, total), c| {
+            (met + usize::from(is_met(c)), total + 1)
+        })
+}
 
 /// Build status line spans for task_list: `action  N tasks (M active)`.
 pub fn task_list_status_summary(
@@ -56,9 +97,105 @@ pub fn task_list_status_summary(
         }
     }
 
+    let (met, total) = criteria_progress(result);
+    if total > 0 {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("criteria {}/{}", met, total),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+
     spans
 }
 
+/// Build task board preview lines: status icon + title per task, then each
+/// acceptance criterion with ✓/○ and its evidence links.
+pub fn task_list_preview_lines(
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Line<'static>> {
+    let tasks = result_tasks(result);
+    let muted = text_muted_style(palette.text_muted);
+    let mut lines = Vec::new();
+
+    for task in tasks.iter().take(MAX_PREVIEW_TASKS) {
+        let status = task.get("status").and_then(|v| v.as_str()).unwrap_or("");
+        let icon = match status {
+            "done" => "✓",
+            "in_progress" => "◐",
+            "cancelled" => "✗",
+            _ => "○",
+        };
+        let title = task.get("title").and_then(|v| v.as_str()).unwrap_or("");
+        let id = task.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let title_style = if status == "done" {
+            success_style(palette.success)
+        } else {
+            text_style(palette.text)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::raw(PREVIEW_INDENT),
+            Span::styled(format!("{} ", icon), title_style),
+            Span::styled(title.to_string(), title_style),
+            Span::styled(format!("  {}", id), muted),
+        ]));
+
+        let criteria = criteria_of(task);
+        for c in criteria.iter().take(MAX_CRITERIA_PER_TASK) {
+            let met = is_met(c);
+            let text = c.get("text").and_then(|v| v.as_str()).unwrap_or("");
+            let mut spans = vec![
+                Span::raw(LEFT_PADDING),
+                Span::raw(PREVIEW_INDENT),
+                Span::raw("  "),
+                if met {
+                    Span::styled("✓ ", success_style(palette.success))
+                } else {
+                    Span::styled("○ ", muted)
+                },
+                Span::styled(text.to_string(), muted),
+            ];
+            let evidence: Vec<&str> = c
+                .get("evidence")
+                .and_then(|v| v.as_array())
+                .map(|e| e.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+            if !evidence.is_empty() {
+                let joined = evidence.join(", ");
+                let joined = if joined.chars().count() > MAX_EVIDENCE_CHARS {
+                    let cut: String = joined.chars().take(MAX_EVIDENCE_CHARS).collect();
+                    format!("{}…", cut)
+                } else {
+                    joined
+                };
+                spans.push(Span::styled(format!("  ← {}", joined), muted));
+            }
+            lines.push(Line::from(spans));
+        }
+        let hidden = criteria.len().saturating_sub(MAX_CRITERIA_PER_TASK);
+        if hidden > 0 {
+            lines.push(Line::from(vec![
+                Span::raw(LEFT_PADDING),
+                Span::raw(PREVIEW_INDENT),
+                Span::styled(format!("  +{} more criteria", hidden), muted),
+            ]));
+        }
+    }
+
+    let remaining = tasks.len().saturating_sub(MAX_PREVIEW_TASKS);
+    if remaining > 0 {
+        lines.push(Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::raw(PREVIEW_INDENT),
+            Span::styled(format!("+{} more", remaining), muted),
+        ]));
+    }
+
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +210,40 @@ mod tests {
         assert!(spans.iter().any(|s| s.content.contains("3 tasks")));
         assert!(spans.iter().any(|s| s.content.contains("(1 active)")));
     }
+
+    #[test]
+    fn status_shows_criteria_progress() {
+        let args = serde_json::json!({"action": "check"});
+        let result = serde_json::json!({
+            "id": "t1",
+            "title": "Fix parser",
+            "status": "in_progress",
+            "criteria": [{"id": "c1", "text": "tests pass", "met": true}, {"id": "c2", "text": "docs"}]
+        });
+        let palette = LocusPalette::locus_dark();
+        let spans = task_list_status_summary(&args, &result, &palette);
+        assert!(spans.iter().any(|s| s.content.contains("criteria 1/2")));
+    }
+
+    #[test]
+    fn preview_shows_tasks_criteria_and_evidence() {
+        let result = serde_json::json!({"tasks": [
+            {"id": "t1", "title": "Fix parser", "status": "in_progress", "criteria": [
+                {"id": "c1", "text": "tests pass", "met": true, "evidence": ["call_42"]},
+                {"id": "c2", "text": "docs updated"}
+            ]},
+            {"id": "t2", "title": "Release", "status": "pending"}
+        ]});
+        let palette = LocusPalette::locus_dark();
+        let lines = task_list_preview_lines(&result, &palette);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(text[0].contains("◐ Fix parser"));
+        assert!(text[1].contains("✓ tests pass") && text[1].contains("call_42"));
+        assert!(text[2].contains("○ docs updated"));
+        assert!(text[3].contains("○ Release"));
+    }
 }
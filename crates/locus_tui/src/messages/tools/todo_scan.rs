@@ -0,0 +1,83 @@
+//! todo_scan tool TUI rendering — TODO/FIXME/HACK comments.
+//!
+//! One line only: `path  2 FIXME, 5 TODO  oldest 420d`. No preview — items go to LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Build status line spans for todo_scan: `path  counts by tag  oldest Nd` plus
+/// `→ N tasks` when items were added to a plan.
+pub fn todo_scan_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+    let muted = text_muted_style(palette.text_muted);
+
+    let mut spans = vec![Span::styled(path.to_string(), muted)];
+
+    if let Some(by_tag) = result.get("by_tag").and_then(|v| v.as_object()) {
+        let counts: Vec<String> = by_tag
+            .iter()
+            .filter_map(|(tag, n)| n.as_u64().map(|n| format!("{} {}", n, tag)))
+            .collect();
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            if counts.is_empty() {
+                "none found".to_string()
+            } else {
+                counts.join(", ")
+            },
+            muted,
+        ));
+    }
+
+    let oldest = result
+        .get("items")
+        .and_then(|v| v.as_array())
+        .and_then(|items| items.iter().filter_map(|i| i["age_days"].as_u64()).max());
+    if let Some(days) = oldest {
+        spans.push(Span::styled(format!("  oldest {}d", days), muted));
+    }
+
+    if let Some(added) = result.get("tasks_added").and_then(|v| v.as_array()) {
+        spans.push(Span::styled(format!("  → {} tasks", added.len()), muted));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_shows_counts_age_and_tasks() {
+        let args = serde_json::json!({"path": "src/payments"});
+        let result = serde_json::json!({
+            "by_tag": {"FIXME": 2, "TODO": 5},
+            "items": [{"age_days": 12}, {"age_days": 420}, {}],
+            "tasks_added": ["t1", "t2"]
+        });
+        let palette = LocusPalette::locus_dark();
+        let spans = todo_scan_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, "src/payments");
+        assert!(spans.iter().any(|s| s.content == "2 FIXME, 5 TODO"));
+        assert!(spans.iter().any(|s| s.content.contains("oldest 420d")));
+        assert!(spans.iter().any(|s| s.content.contains("→ 2 tasks")));
+    }
+
+    #[test]
+    fn status_reports_empty_scan() {
+        let args = serde_json::json!({});
+        let result = serde_json::json!({"by_tag": {}, "items": []});
+        let palette = LocusPalette::locus_dark();
+        let spans = todo_scan_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, ".");
+        assert!(spans.iter().any(|s| s.content == "none found"));
+        assert!(!spans.iter().any(|s| s.content.contains("oldest")));
+    }
+}
@@ -0,0 +1,67 @@
+//! tree tool TUI rendering — directory layout.
+//!
+//! One line only: `path  N files, M dirs`. No preview — the tree goes to LLM.
+
+use ratatui::text::Span;
+
+use crate::layouts::text_muted_style;
+use crate::theme::LocusPalette;
+
+/// Build status line spans for tree: `path  N files, M dirs` (`(truncated)` when capped).
+pub fn tree_status_summary(
+    args: &serde_json::Value,
+    result: &serde_json::Value,
+    palette: &LocusPalette,
+) -> Vec<Span<'static>> {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+
+    let mut spans = vec![Span::styled(
+        path.to_string(),
+        text_muted_style(palette.text_muted),
+    )];
+
+    let files = result.get("total_files").and_then(|v| v.as_u64());
+    let dirs = result.get("total_dirs").and_then(|v| v.as_u64());
+    if let (Some(files), Some(dirs)) = (files, dirs) {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{} files, {} dirs", files, dirs),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+
+    if result.get("truncated").and_then(|v| v.as_bool()) == Some(true) {
+        spans.push(Span::styled(
+            " (truncated)",
+            text_muted_style(palette.text_muted),
+        ));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_shows_path_and_counts() {
+        let args = serde_json::json!({"path": "crates"});
+        let result = serde_json::json!({"total_files": 42, "total_dirs": 7, "truncated": false});
+        let palette = LocusPalette::locus_dark();
+        let spans = tree_status_summary(&args, &result, &palette);
+        assert!(spans.iter().any(|s| s.content.contains("crates")));
+        assert!(spans.iter().any(|s| s.content.contains("42 files, 7 dirs")));
+        assert!(!spans.iter().any(|s| s.content.contains("truncated")));
+    }
+
+    #[test]
+    fn status_defaults_to_repo_root_and_marks_truncation() {
+        let args = serde_json::json!({});
+        let result = serde_json::json!({"total_files": 500, "total_dirs": 0, "truncated": true});
+        let palette = LocusPalette::locus_dark();
+        let spans = tree_status_summary(&args, &result, &palette);
+        assert_eq!(spans[0].content, ".");
+        assert!(spans.iter().any(|s| s.content.contains("(truncated)")));
+    }
+}
@@ -1,6 +1,7 @@
 //! web_automation tool TUI rendering — web browsing and search.
 //!
-//! URL fetch: `domain/path  duration`. Protocol stripped.
+//! URL fetch: `domain/path  duration`. Protocol stripped; `truncated` when the
+//! content was cut to `max_bytes`.
 //! Web search: `"query"  N results  duration`.
 //! One line each — content goes to LLM.
 
@@ -9,10 +10,11 @@ use ratatui::text::Span;
 use crate::layouts::text_muted_style;
 use crate::theme::LocusPalette;
 
-/// Build status line spans for web fetch: `domain/path` (protocol stripped).
+/// Build status line spans for web fetch: `domain/path` (protocol stripped),
+/// then `truncated` when the result was cut.
 pub fn web_fetch_status_summary(
     args: &serde_json::Value,
-    _result: &serde_json::Value,
+    result: &serde_json::Value,
     palette: &LocusPalette,
 ) -> Vec<Span<'static>> {
     let url = args.get("url").and_then(|v| v.as_str()).unwrap_or("");
@@ -23,10 +25,20 @@ pub fn web_fetch_status_summary(
         .or_else(|| url.strip_prefix("http://"))
         .unwrap_or(url);
 
-    vec![Span::styled(
+    let mut spans = vec![Span::styled(
         display_url.to_string(),
         text_muted_style(palette.text_muted),
-    )]
+    )];
+
+    if result.get("truncated").and_then(|v| v.as_bool()) == Some(true) {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            "truncated",
+            text_muted_style(palette.text_muted),
+        ));
+    }
+
+    spans
 }
 
 /// Build status line spans for web search: `"query"  N results`.
@@ -78,6 +90,18 @@ mod tests {
         assert!(!spans.iter().any(|s| s.content.contains("https://")));
     }
 
+    #[test]
+    fn fetch_marks_truncated_content() {
+        let args = serde_json::json!({"url": "https://docs.rs/tokio"});
+        let palette = LocusPalette::locus_dark();
+        let result = serde_json::json!({"status": 200, "truncated": true});
+        let spans = web_fetch_status_summary(&args, &result, &palette);
+        assert!(spans.iter().any(|s| s.content == "truncated"));
+        let result = serde_json::json!({"status": 200, "truncated": false});
+        let spans = web_fetch_status_summary(&args, &result, &palette);
+        assert!(!spans.iter().any(|s| s.content == "truncated"));
+    }
+
     #[test]
     fn search_shows_query_and_count() {
         let args = serde_json::json!({"query": "rust async channels"});
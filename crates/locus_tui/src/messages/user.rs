@@ -3,15 +3,18 @@
 //! Layout:
 //! - left padding aligned with tool/channel grid
 //! - primary-colored body text per line
+//! - `@path` mentions highlighted in the info color
 //! - timestamps kept but hidden
 
+use locus_core::find_mentions;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
-use crate::layouts::text_style;
+use crate::layouts::{info_style, text_style};
 use crate::theme::LocusPalette;
 use crate::utils::{LEFT_PADDING, wrap_lines};
 
-use super::common::push_wrapped_plain_continuations;
+use super::common::continuation_padding;
 
 /// User message for display. No dependency on other crates.
 #[derive(Debug, Clone)]
@@ -36,20 +39,47 @@ pub fn user_message_lines(
         return vec![Line::from(Span::raw(LEFT_PADDING.to_string()))];
     }
 
-    let mut lines = Vec::with_capacity(wrapped.len());
-    let first = &wrapped[0];
+    let mention_style = info_style(palette.info).add_modifier(Modifier::BOLD);
     let indent_span = Span::raw(LEFT_PADDING);
-    let first_line = vec![indent_span.clone(), Span::styled(first.clone(), body_style)];
-    lines.push(Line::from(first_line));
+    wrapped
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let mut spans = vec![indent_span.clone()];
+            if i > 0 {
+                spans.push(continuation_padding(indent_len));
+            }
+            spans.extend(highlight_mentions(segment, body_style, mention_style));
+            Line::from(spans)
+        })
+        .collect()
+}
 
-    push_wrapped_plain_continuations(
-        &mut lines,
-        &indent_span,
-        LEFT_PADDING.len(),
-        &wrapped,
-        body_style,
-    );
-    lines
+/// `segment` as spans, its `@path` mentions in `mention_style`.
+fn highlight_mentions(
+    segment: &str,
+    body_style: Style,
+    mention_style: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut at = 0;
+    for mention in find_mentions(segment) {
+        if mention.range.start > at {
+            spans.push(Span::styled(
+                segment[at..mention.range.start].to_string(),
+                body_style,
+            ));
+        }
+        spans.push(Span::styled(
+            segment[mention.range.clone()].to_string(),
+            mention_style,
+        ));
+        at = mention.range.end;
+    }
+    if at < segment.len() || spans.is_empty() {
+        spans.push(Span::styled(segment[at..].to_string(), body_style));
+    }
+    spans
 }
 
 #[cfg(test)]
@@ -74,6 +104,22 @@ mod tests {
         assert!(!lines[0].spans.iter().any(|s| s.content.as_ref() == "you"));
     }
 
+    #[test]
+    fn user_message_highlights_mentions() {
+        let msg = UserMessage {
+            text: "why does @src/lib.rs fail?".into(),
+            timestamp: None,
+        };
+        let palette = LocusPalette::locus_dark();
+        let lines = user_message_lines(&msg, &palette, 80);
+        let spans: Vec<&str> = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(spans, [LEFT_PADDING, "why does ", "@src/lib.rs", " fail?"]);
+        assert_eq!(
+            lines[0].spans[2].style,
+            info_style(palette.info).add_modifier(Modifier::BOLD)
+        );
+    }
+
     #[test]
     fn user_message_lines_wraps_long_text() {
         let msg = UserMessage {
@@ -0,0 +1,241 @@
+//! `:model` in the chat input: ask the runtime for the models of each provider
+//! with an API key and pick one in a popup, or switch directly with
+//! `:model <provider>/<model>`. The runtime uses it from the next turn on.
+
+use locus_core::model::MODEL_COMMAND;
+use locus_core::{ModelCommand, ProviderModels, parse_model_command};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::{t, tf};
+use crate::layouts::{background_style, text_muted_style, text_style};
+use crate::state::TuiState;
+
+/// Rows of models shown at once in the picker.
+const VISIBLE_ROWS: usize = 14;
+
+/// The model picker: every provider's models, one row each.
+#[derive(Debug, Clone)]
+pub struct ModelPicker {
+    /// (provider, model) rows, grouped by provider.
+    pub rows: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+impl ModelPicker {
+    /// Rows for `providers`, with the model in use (`current`) highlighted.
+    pub fn new(providers: Vec<ProviderModels>, current: (&str, &str)) -> Self {
+        let rows: Vec<(String, String)> = providers
+            .into_iter()
+            .flat_map(|p| {
+                let provider = p.provider;
+                p.models
+                    .into_iter()
+                    .map(move |model| (provider.clone(), model))
+            })
+            .collect();
+        let selected = rows
+            .iter()
+            .position(|(p, m)| p == current.0 && m == current.1)
+            .unwrap_or(0);
+        Self { rows, selected }
+    }
+
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&(String, String)> {
+        self.rows.get(self.selected)
+    }
+}
+
+/// Handle `line` if it is a model command: send it to the runtime, or show why
+/// not. A command sent during a run is taken up once the run ends. Returns
+/// false for any other input.
+pub fn request_model(
+    state: &mut TuiState,
+    line: &str,
+    user_msg_tx: Option<&tokio_mpsc::Sender<String>>,
+) -> bool {
+    let Some(parsed) = parse_model_command(line) else {
+        return false;
+    };
+    let status = match parsed {
+        Err(usage) => usage,
+        Ok(command) => match user_msg_tx {
+            Some(tx) if tx.try_send(line.to_string()).is_ok() => match command {
+                ModelCommand::List => t("status.listing_models").to_string(),
+                ModelCommand::Switch { provider, model } => switching(&provider, &model),
+            },
+            _ => t("status.model_unavailable").to_string(),
+        },
+    };
+    set_status(state, status);
+    true
+}
+
+/// The runtime listed the models: open the picker on the one in use.
+pub fn show_models(state: &mut TuiState, providers: Vec<ProviderModels>) {
+    let picker = ModelPicker::new(providers, (&state.usage.provider, &state.usage.model));
+    if picker.rows.is_empty() {
+        set_status(state, t("status.no_models").to_string());
+        return;
+    }
+    state.model_picker = Some(picker);
+    state.status.clear();
+    state.needs_redraw = true;
+}
+
+/// Switch to the highlighted model. Closes the picker.
+pub fn switch_selected(state: &mut TuiState, user_msg_tx: Option<&tokio_mpsc::Sender<String>>) {
+    let Some((provider, model)) = state
+        .model_picker
+        .take()
+        .and_then(|picker| picker.selected().cloned())
+    else {
+        return;
+    };
+    let line = format!("{} {}/{}", MODEL_COMMAND, provider, model);
+    request_model(state, &line, user_msg_tx);
+}
+
+fn switching(provider: &str, model: &str) -> String {
+    tf(
+        "status.switching_model",
+        &[("model", &format!("{}/{}", provider, model))],
+    )
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+/// The picker popup, drawn over the chat.
+pub fn draw_model_picker(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let Some(picker) = state.model_picker.as_ref() else {
+        return;
+    };
+    let palette = &state.palette;
+    let muted = text_muted_style(palette.text_muted);
+
+    // Keep the highlighted row in view.
+    let first = picker.selected.saturating_sub(VISIBLE_ROWS - 1);
+    let mut lines: Vec<Line<'static>> = picker
+        .rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(VISIBLE_ROWS)
+        .map(|(i, (provider, model))| {
+            let selected = i == picker.selected;
+            let style = if selected {
+                text_style(palette.accent)
+            } else {
+                text_style(palette.text)
+            };
+            let mut spans = vec![
+                Span::styled(if selected { "› " } else { "  " }.to_string(), style),
+                Span::styled(format!("{}/", provider), muted),
+                Span::styled(model.clone(), style),
+            ];
+            if *provider == state.usage.provider && *model == state.usage.model {
+                spans.push(Span::styled(
+                    format!("  {}", t("model_picker.current")),
+                    muted,
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    lines.push(Line::from(""));
+    let key = |k: &str| Span::styled(k.to_string(), text_style(palette.accent));
+    lines.push(Line::from(vec![
+        key("↑↓"),
+        Span::styled(format!(" {}  ", t("model_picker.select")), muted),
+        key("Enter"),
+        Span::styled(format!(" {}  ", t("model_picker.switch")), muted),
+        key("Esc"),
+        Span::styled(format!(" {}", t("shortcut.cancel")), muted),
+    ]));
+
+    let width = area.width.saturating_sub(4).min(70);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("model_picker.title")))
+        .border_style(text_style(palette.accent))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers() -> Vec<ProviderModels> {
+        vec![
+            ProviderModels {
+                provider: "anthropic".to_string(),
+                models: vec!["claude-opus-4-1".to_string(), "claude-sonnet-4".to_string()],
+            },
+            ProviderModels {
+                provider: "zai".to_string(),
+                models: vec!["glm-4.5".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn picker_opens_on_the_model_in_use_and_switches() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        assert!(!request_model(&mut state, "model this", Some(&tx)));
+
+        assert!(request_model(&mut state, ":model", Some(&tx)));
+        assert_eq!(rx.try_recv().unwrap(), ":model");
+
+        state
+            .usage
+            .set_model("anthropic".to_string(), "claude-sonnet-4".to_string(), 0);
+        show_models(&mut state, providers());
+        assert_eq!(state.model_picker.as_ref().unwrap().selected, 1);
+
+        state.model_picker.as_mut().unwrap().down();
+        switch_selected(&mut state, Some(&tx));
+        assert!(state.model_picker.is_none());
+        assert_eq!(rx.try_recv().unwrap(), ":model zai/glm-4.5");
+        assert!(state.status.contains("zai/glm-4.5"));
+
+        assert!(request_model(&mut state, ":model glm-4.5", Some(&tx)));
+        assert!(state.status.starts_with("Usage"));
+        assert!(rx.try_recv().is_err());
+
+        show_models(&mut state, Vec::new());
+        assert!(state.model_picker.is_none());
+        assert_eq!(state.status, t("status.no_models"));
+    }
+}
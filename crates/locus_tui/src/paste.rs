@@ -0,0 +1,221 @@
+//! Pasting into the prompt. Bracketed paste hands the TUI a paste in one
+//! event, so it goes into the prompt at once, newlines and all, instead of
+//! key by key (where each newline would send the prompt). A large paste is
+//! put in the prompt as a `[pasted 3.2KB]` placeholder: Tab right after it
+//! expands it in place, Backspace drops it, and when the prompt is sent the
+//! placeholder is replaced by the pasted text as a context block.
+
+use crate::setup::handle_setup_char;
+use crate::state::{Screen, TuiState};
+
+/// Pastes from this size on become a placeholder.
+pub const LARGE_PASTE_BYTES: usize = 2048;
+
+/// Pastes with more lines than this become a placeholder too.
+pub const LARGE_PASTE_LINES: usize = 40;
+
+/// Start of the context block a pasted text is sent in.
+pub const PASTE_PREFIX: &str = "[Pasted text";
+
+/// A large paste waiting in the prompt behind its placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastedBlock {
+    /// The placeholder in the prompt, e.g. `[pasted 3.2KB]`.
+    pub label: String,
+    pub text: String,
+}
+
+/// Whether `text` is large enough to be put behind a placeholder.
+pub fn is_large(text: &str) -> bool {
+    text.len() >= LARGE_PASTE_BYTES || text.lines().count() > LARGE_PASTE_LINES
+}
+
+/// "3.2KB", "812B", "1.4MB".
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{}B", bytes),
+        1024..1_048_576 => format!("{:.1}KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1}MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// A paste: into the prompt at the cursor, or behind a placeholder when large.
+/// Line endings are made `\n`.
+pub fn paste(state: &mut TuiState, text: &str) {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let inserted = if is_large(&text) {
+        let mut label = format!("[pasted {}]", format_size(text.len()));
+        let mut n = 1;
+        while state.pasted.iter().any(|block| block.label == label) {
+            n += 1;
+            label = format!("[pasted {} #{}]", format_size(text.len()), n);
+        }
+        state.pasted.push(PastedBlock {
+            label: label.clone(),
+            text,
+        });
+        label
+    } else {
+        text
+    };
+    state.input_buffer.insert_str(state.input_cursor, &inserted);
+    state.input_cursor += inserted.len();
+    state.needs_redraw = true;
+}
+
+/// A paste event: into the prompt on the chat screen, else into whatever
+/// single-line field has the keys (newlines made spaces).
+pub fn handle_paste(state: &mut TuiState, text: &str) {
+    let line = || {
+        text.chars()
+            .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+    };
+    if let Some(palette) = state.command_palette.as_mut() {
+        for c in line() {
+            palette.insert(c, &state.recent_actions);
+        }
+    } else if state.screen == Screen::Setup {
+        // Keys and URLs: a stray newline or space would break them
+        for c in line().filter(|c| *c != ' ') {
+            handle_setup_char(state, c);
+        }
+    } else if state.screen == Screen::DebugTraces {
+        for c in line() {
+            state.logs.input_insert(c);
+        }
+    } else if state.screen == Screen::MemoryTrace {
+        for c in line() {
+            state.input_insert(c);
+        }
+    } else if state.screen == Screen::Main {
+        if state.history.search.is_some() {
+            for c in line() {
+                state.history.search_insert(c);
+            }
+        } else {
+            paste(state, text);
+            state
+                .file_finder
+                .update(&state.input_buffer, state.input_cursor);
+        }
+    }
+    state.needs_redraw = true;
+}
+
+/// The pasted block whose placeholder ends at the cursor.
+fn block_before_cursor(state: &TuiState) -> Option<usize> {
+    let before = &state.input_buffer[..state.input_cursor];
+    state
+        .pasted
+        .iter()
+        .position(|block| before.ends_with(&block.label))
+}
+
+/// Tab after a placeholder: put the text it stands for in the prompt to edit.
+/// Returns false when the cursor is not after one.
+pub fn expand_paste(state: &mut TuiState) -> bool {
+    let Some(index) = block_before_cursor(state) else {
+        return false;
+    };
+    let block = state.pasted.remove(index);
+    let start = state.input_cursor - block.label.len();
+    state
+        .input_buffer
+        .replace_range(start..state.input_cursor, &block.text);
+    state.input_cursor = start + block.text.len();
+    state.needs_redraw = true;
+    true
+}
+
+/// Backspace after a placeholder: drop it and the text it stands for.
+/// Returns false when the cursor is not after one.
+pub fn remove_paste(state: &mut TuiState) -> bool {
+    let Some(index) = block_before_cursor(state) else {
+        return false;
+    };
+    let block = state.pasted.remove(index);
+    let start = state.input_cursor - block.label.len();
+    state.input_buffer.drain(start..state.input_cursor);
+    state.input_cursor = start;
+    state.needs_redraw = true;
+    true
+}
+
+/// The prompt to send for `line`: each placeholder replaced by its text as a
+/// context block. The pasted blocks are used up.
+pub fn take_pastes(state: &mut TuiState, line: &str) -> String {
+    let mut sent = line.to_string();
+    for block in std::mem::take(&mut state.pasted) {
+        let context = format!(
+            "\n{}, {}]\n{}\n[End of pasted text]\n",
+            PASTE_PREFIX,
+            format_size(block.text.len()),
+            block.text
+        );
+        sent = sent.replacen(&block.label, &context, 1);
+    }
+    sent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(lines: usize) -> String {
+        (0..lines)
+            .map(|i| format!("line {}: error[E0308]: mismatched types", i))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn small_pastes_go_in_whole() {
+        let mut state = TuiState::new();
+        state.input_buffer = "fix ".into();
+        state.input_cursor = 4;
+        paste(&mut state, "a\r\nb");
+        assert_eq!(state.input_buffer, "fix a\nb");
+        assert_eq!(state.input_cursor, state.input_buffer.len());
+        assert!(state.pasted.is_empty());
+        assert_eq!(take_pastes(&mut state, "fix a\nb"), "fix a\nb");
+    }
+
+    #[test]
+    fn large_pastes_become_placeholders() {
+        let mut state = TuiState::new();
+        state.input_buffer = "why: ".into();
+        state.input_cursor = 5;
+        let text = log(100);
+        paste(&mut state, &text);
+        let label = format!("[pasted {}]", format_size(text.len()));
+        assert_eq!(state.input_buffer, format!("why: {}", label));
+        paste(&mut state, &text);
+        assert_eq!(
+            state.pasted[1].label,
+            format!("[pasted {} #2]", format_size(text.len()))
+        );
+
+        assert!(remove_paste(&mut state));
+        assert_eq!(state.input_buffer, format!("why: {}", label));
+        assert_eq!(state.pasted.len(), 1);
+
+        let line = format!("{} fix it", state.input_buffer);
+        let sent = take_pastes(&mut state, &line);
+        assert!(sent.starts_with("why: \n[Pasted text, "));
+        assert!(sent.contains(&text));
+        assert!(sent.ends_with("[End of pasted text]\n fix it"));
+        assert!(state.pasted.is_empty());
+
+        paste(&mut state, &text);
+        assert!(expand_paste(&mut state));
+        assert!(state.input_buffer.ends_with(&text));
+        assert!(!expand_paste(&mut state));
+    }
+
+    #[test]
+    fn sizes_read_as_bytes_kb_or_mb() {
+        assert_eq!(format_size(812), "812B");
+        assert_eq!(format_size(3277), "3.2KB");
+        assert_eq!(format_size(3 * 1_048_576), "3.0MB");
+    }
+}
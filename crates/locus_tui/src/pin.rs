@@ -0,0 +1,81 @@
+//! `:pin <path>` / `:unpin <path>` in the chat input: ask the runtime to keep a
+//! file in every request's context, or to stop; `:pin` alone lists them.
+
+use locus_core::{PinCommand, parse_pin_command};
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::{t, tf};
+use crate::state::TuiState;
+
+/// Handle `line` if it is a pin command: send it to the runtime, or show why
+/// not. A command sent during a run is taken up once the run ends. Returns
+/// false for any other input.
+pub fn request_pin(
+    state: &mut TuiState,
+    line: &str,
+    user_msg_tx: Option<&tokio_mpsc::Sender<String>>,
+) -> bool {
+    let Some(parsed) = parse_pin_command(line) else {
+        return false;
+    };
+    let status = match parsed {
+        Err(usage) => usage,
+        Ok(command) => match user_msg_tx {
+            Some(tx) if tx.try_send(line.to_string()).is_ok() => match command {
+                PinCommand::Pin(path) => tf("status.pinning", &[("path", &path)]),
+                PinCommand::Unpin(path) => tf("status.unpinning", &[("path", &path)]),
+                PinCommand::List => return true,
+            },
+            _ => t("status.pin_unavailable").to_string(),
+        },
+    };
+    set_status(state, status);
+    true
+}
+
+/// Say which files are pinned now.
+pub fn show_pinned(state: &mut TuiState, files: &[String]) {
+    let status = if files.is_empty() {
+        t("status.no_pinned").to_string()
+    } else {
+        tf("status.pinned", &[("files", &files.join(", "))])
+    };
+    set_status(state, status);
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_commands_go_to_the_runtime() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        assert!(!request_pin(&mut state, "pin the docs", Some(&tx)));
+
+        state.is_streaming = true;
+        assert!(request_pin(&mut state, ":pin docs/STYLE.md", Some(&tx)));
+        assert_eq!(rx.try_recv().unwrap(), ":pin docs/STYLE.md");
+        assert!(state.status.contains("docs/STYLE.md"));
+
+        assert!(request_pin(&mut state, ":unpin", Some(&tx)));
+        assert!(state.status.starts_with("Usage"));
+        assert!(rx.try_recv().is_err());
+
+        assert!(request_pin(&mut state, ":pin", None));
+        assert_eq!(state.status, t("status.pin_unavailable"));
+
+        show_pinned(
+            &mut state,
+            &["docs/STYLE.md".to_string(), "api.yaml".to_string()],
+        );
+        assert!(state.status.contains("docs/STYLE.md, api.yaml"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Plan screen (Ctrl+L): the plan from the last `:plan` run, with an action to
+//! carry it out.
+//!
+//! The runtime saves each plan under `.locus/plans/` and sends
+//! `SessionEvent::PlanReady`; Enter on this screen sends `:execute-plan <path>`,
+//! which runs the plan with every tool and the plan passed to the model.
+
+use locus_core::Plan;
+use locus_core::plan::EXECUTE_PLAN_COMMAND;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, render_header, text_muted_style, text_style,
+};
+use crate::messages::markdown::{parse_blocks, render_blocks_to_lines};
+use crate::state::{Screen, TuiState};
+
+/// Keep a new plan and open the plan screen on it.
+pub fn show_plan(state: &mut TuiState, plan: Plan) {
+    state.push_separator(tf("chat.plan_saved", &[("path", &plan.path)]));
+    state.plan = Some(plan);
+    state.plan_scroll = 0;
+    state.screen = Screen::Plan;
+    state.needs_redraw = true;
+}
+
+/// Ctrl+L: open the plan screen, or say how to make a plan when there is none.
+pub fn toggle_plan_screen(state: &mut TuiState) {
+    match state.screen {
+        Screen::Plan => state.screen = Screen::Main,
+        Screen::Main if state.plan.is_some() => state.screen = Screen::Plan,
+        Screen::Main => set_status(state, t("status.no_plan").to_string()),
+        _ => {}
+    }
+    state.needs_redraw = true;
+}
+
+/// Ask the runtime to carry out the plan on screen and go back to the chat.
+pub fn execute_plan(state: &mut TuiState, user_msg_tx: Option<&tokio_mpsc::Sender<String>>) {
+    let Some(plan) = state.plan.as_ref() else {
+        return;
+    };
+    if state.is_streaming {
+        set_status(state, t("status.plan_busy").to_string());
+        return;
+    }
+    let command = format!("{} {}", EXECUTE_PLAN_COMMAND, plan.path);
+    if user_msg_tx.is_none_or(|tx| tx.try_send(command).is_err()) {
+        set_status(state, t("status.plan_unavailable").to_string());
+        return;
+    }
+    let label = tf("chat.plan_executing", &[("task", &plan.task)]);
+    if let Some(mirror) = state.transcript.as_mut() {
+        mirror.record_separator(&label);
+    }
+    state.push_separator(label);
+    state.screen = Screen::Main;
+    state.auto_scroll = true;
+    state.needs_redraw = true;
+}
+
+pub fn plan_scroll_up(state: &mut TuiState, lines: usize) {
+    state.plan_scroll = state.plan_scroll.saturating_sub(lines);
+    state.needs_redraw = true;
+}
+
+pub fn plan_scroll_down(state: &mut TuiState, lines: usize) {
+    state.plan_scroll = state.plan_scroll.saturating_add(lines);
+    state.needs_redraw = true;
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+/// Draw the plan screen: the plan as markdown, then the shortcuts.
+pub fn draw_plan(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let palette = state.palette.clone();
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Plan
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let Some(plan) = state.plan.as_ref() else {
+        return;
+    };
+    render_header(
+        frame,
+        chunks[0],
+        &palette,
+        t(HEADER_TITLE),
+        t("plan.section"),
+        plan.path.as_str(),
+        state.is_streaming,
+        false,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .title(Span::styled(
+            format!(" {} ", plan.task),
+            text_style(palette.text),
+        ))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+
+    let lines = render_blocks_to_lines(
+        &parse_blocks(&plan.text),
+        &palette,
+        inner.width as usize,
+        2,
+        &Span::raw(""),
+        None,
+    );
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    state.plan_scroll = state.plan_scroll.min(max_scroll);
+    let visible: Vec<Line> = lines.into_iter().skip(state.plan_scroll).collect();
+    frame.render_widget(Paragraph::new(visible), inner);
+
+    let shortcuts = [
+        ("Esc", t("shortcut.back")),
+        ("Enter", t("plan.shortcut.execute")),
+        ("↑↓/jk", t("shortcut.scroll")),
+        ("g/G", t("shortcut.top_bottom")),
+    ];
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ChatItem;
+
+    fn plan() -> Plan {
+        Plan {
+            path: ".locus/plans/20261016-120000-fix-add.md".to_string(),
+            task: "Fix add".to_string(),
+            text: "1. Change `a - b` to `a + b`.".to_string(),
+        }
+    }
+
+    #[test]
+    fn new_plan_opens_the_plan_screen() {
+        let mut state = TuiState::new();
+        toggle_plan_screen(&mut state);
+        assert_eq!(state.screen, Screen::Main);
+        assert!(state.status.contains(":plan"));
+
+        show_plan(&mut state, plan());
+        assert_eq!(state.screen, Screen::Plan);
+        assert!(
+            matches!(state.messages.last(), Some(ChatItem::Separator(s)) if s.contains("fix-add.md"))
+        );
+        toggle_plan_screen(&mut state);
+        assert_eq!(state.screen, Screen::Main);
+    }
+
+    #[test]
+    fn execute_sends_the_plan_path_when_idle() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        show_plan(&mut state, plan());
+
+        state.is_streaming = true;
+        execute_plan(&mut state, Some(&tx));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(state.screen, Screen::Plan);
+
+        state.is_streaming = false;
+        execute_plan(&mut state, Some(&tx));
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            ":execute-plan .locus/plans/20261016-120000-fix-add.md"
+        );
+        assert_eq!(state.screen, Screen::Main);
+        assert!(
+            matches!(state.messages.last(), Some(ChatItem::Separator(s)) if s.contains("Fix add"))
+        );
+    }
+}
@@ -440,7 +440,7 @@ mod tests {
     fn preview_state_seeds_messages_and_input() {
         let state = preview_state(false, Appearance::Dark);
         assert!(!state.messages.is_empty());
-        assert!(!state.trace_lines.is_empty());
+        assert!(!state.logs.buffer.is_empty());
         assert!(!state.input_buffer.is_empty());
         assert!(state.is_streaming);
         assert!(!state.current_think_text.is_empty());
@@ -0,0 +1,118 @@
+//! `:rewind N` in the chat input: ask the runtime to drop the last N turns and
+//! restore their files, then cut the same turns from the chat when it reports back.
+
+use locus_core::{RewindSummary, parse_rewind};
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::{t, tf};
+use crate::state::{ChatItem, TuiState};
+
+/// Handle `line` if it is the rewind command: send it to the runtime, or show
+/// why not. Returns false for any other input.
+pub fn request_rewind(
+    state: &mut TuiState,
+    line: &str,
+    user_msg_tx: Option<&tokio_mpsc::Sender<String>>,
+) -> bool {
+    let Some(parsed) = parse_rewind(line) else {
+        return false;
+    };
+    let status = match parsed {
+        Err(usage) => usage,
+        Ok(_) if state.is_streaming => t("status.rewind_busy").to_string(),
+        Ok(_) => match user_msg_tx {
+            Some(tx) if tx.try_send(line.to_string()).is_ok() => t("status.rewinding").to_string(),
+            _ => t("status.rewind_unavailable").to_string(),
+        },
+    };
+    set_status(state, status);
+    true
+}
+
+/// Drop the rewound turns from the chat and say which files were put back.
+pub fn apply_rewound(state: &mut TuiState, summary: &RewindSummary) {
+    let users: Vec<usize> = state
+        .messages
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| matches!(item, ChatItem::User(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if let Some(&cut) = users.get(users.len().saturating_sub(summary.turns as usize)) {
+        state.messages.truncate(cut);
+    }
+    state.current_ai_text.clear();
+    state.current_think_text.clear();
+    state.diff_page_message_index = None;
+    state.annotation_target = None;
+
+    state.push_separator(tf("chat.rewound", &[("count", &summary.turns)]));
+    if !summary.kept.is_empty() {
+        state.push_error(
+            tf("chat.rewind_kept", &[("files", &summary.kept.join(", "))]),
+            None,
+        );
+    }
+    set_status(
+        state,
+        tf(
+            "status.rewound",
+            &[
+                ("restored", &summary.restored.len()),
+                ("removed", &summary.removed.len()),
+            ],
+        ),
+    );
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewind_is_sent_only_when_idle() {
+        let (tx, mut rx) = tokio_mpsc::channel(4);
+        let mut state = TuiState::new();
+        assert!(!request_rewind(&mut state, "rewind please", Some(&tx)));
+
+        state.is_streaming = true;
+        assert!(request_rewind(&mut state, ":rewind 2", Some(&tx)));
+        assert!(rx.try_recv().is_err());
+
+        state.is_streaming = false;
+        assert!(request_rewind(&mut state, ":rewind x", Some(&tx)));
+        assert!(state.status.starts_with("Usage"));
+        assert!(request_rewind(&mut state, ":rewind 2", Some(&tx)));
+        assert_eq!(rx.try_recv().unwrap(), ":rewind 2");
+    }
+
+    #[test]
+    fn rewound_turns_leave_the_chat() {
+        let mut state = TuiState::new();
+        for turn in ["one", "two", "three"] {
+            state.push_user(turn.to_string(), None);
+            state.push_ai(format!("answer {}", turn), None);
+        }
+        let summary = RewindSummary {
+            turns: 2,
+            restored: vec!["src/lib.rs".to_string()],
+            removed: Vec::new(),
+            kept: vec!["notes.md".to_string()],
+        };
+
+        apply_rewound(&mut state, &summary);
+
+        assert!(matches!(&state.messages[0], ChatItem::User(m) if m.text == "one"));
+        assert!(matches!(&state.messages[1], ChatItem::Ai(_)));
+        assert!(matches!(&state.messages[2], ChatItem::Separator(_)));
+        assert!(matches!(&state.messages[3], ChatItem::Error(e) if e.text.contains("notes.md")));
+        assert_eq!(state.messages.len(), 4);
+    }
+}
@@ -4,29 +4,65 @@
 //! input; this keeps the UI responsive when the stream hangs or the terminal is slow.
 
 use std::io;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
 use std::time::Duration;
 
 use crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
     MouseEventKind,
 };
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
-use locus_core::SessionEvent;
+use locus_core::memory_search::MEMORY_COMMAND;
+use locus_core::{
+    ApprovalDecision, ApprovalResponse, ConflictDecision, ConflictResponse, CustomCommand,
+    SessionEvent, ToolAnnotation,
+};
+use locusgraph_observability::LogBuffer;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use tokio::sync::mpsc as tokio_mpsc;
 
+use crate::cancel::{cancel_run, request_cancel};
+use crate::clipboard::{
+    begin_selection, copy_last_code_block, copy_last_reply, copy_last_tool_output, copy_selected,
+    copy_to_clipboard, end_selection, step_selection,
+};
+use crate::command_palette::{run_selected, toggle_palette};
+use crate::diff_review::{approve_selected, reject_selected};
+use crate::editor::{edit_prompt, open_in_editor};
+use crate::export::request_export;
+use crate::file_finder::{FileFinder, complete_file};
+use crate::history::{PromptHistory, accept_search, recall_next, recall_previous};
+use crate::i18n::{t, tf};
+use crate::logs::{LogsInput, LogsState};
+use crate::memory_trace::request_memory_search;
+use crate::messages::memory::similar_work_summary;
+use crate::model_picker::{request_model, switch_selected};
+use crate::paste::{expand_paste, handle_paste, remove_paste, take_pastes};
+use crate::pin::request_pin;
+use crate::plan::{execute_plan, toggle_plan_screen};
+use crate::rewind::request_rewind;
 use crate::runtime_events::apply_session_event;
+use crate::scroll::{Scroll, WHEEL, scroll_key, scroll_view, scrolls};
+use crate::session_picker::{SessionSwitch, open_session_picker, resume_selected};
 use crate::setup::{
     handle_setup_back, handle_setup_backspace, handle_setup_char, handle_setup_down,
     handle_setup_enter, handle_setup_up, tick_setup_animation,
 };
+use crate::slash::{complete_from_menu, complete_slash_command, update_slash_menu};
 use crate::state::{ChatItem, Screen, TuiState};
-use crate::theme::Appearance;
+use crate::status_bar::refresh_git;
+use crate::theme::{Appearance, detect_term};
+use crate::theme_picker::{
+    apply_selected, apply_startup_theme, cancel_theme_picker, follow_terminal_appearance,
+    preview_selected, request_theme,
+};
+use crate::transcript::TranscriptMirror;
 use crate::view;
 
 /// Toggle collapsed state of the last thinking block (key `t` when input empty).
@@ -44,7 +80,12 @@ fn toggle_last_think_collapsed(state: &mut TuiState) {
 fn run_tui_from_state(mut state: TuiState) -> anyhow::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -56,11 +97,15 @@ fn run_tui_from_state(mut state: TuiState) -> anyhow::Result<()> {
         None,
         None,
         None,
+        None,
+        None,
+        None,
         true,
     );
 
     execute!(
         terminal.backend_mut(),
+        DisableBracketedPaste,
         DisableMouseCapture,
         LeaveAlternateScreen
     )?;
@@ -86,28 +131,63 @@ pub fn run_tui_preview(show_onboarding: bool, appearance: Appearance) -> anyhow:
 
 /// Run the TUI with runtime: receive [SessionEvent] on `event_rx`, send user messages on Enter via `user_msg_tx`.
 /// If `show_onboarding` is true, show the setup wizard first.
-/// If `log_rx` is provided, runtime log lines (tracing) are pushed to the debug traces screen (Ctrl+D).
+/// If `logs` is provided, the logs screen (Ctrl+D) shows the runtime log records (tracing) it is given.
 /// If `new_session_tx` is provided, Ctrl+N sends a signal to start a new session (next message uses fresh runtime).
 /// If `cancel_tx` is provided, first Ctrl+C during streaming sends cancel (halts run); second Ctrl+C exits TUI.
+/// Esc and `:cancel` during streaming send cancel too (see [crate::cancel]).
+/// If `annotation_tx` is provided, Ctrl+A attaches the next input as a note on the last finished tool result.
+/// If `approval_tx` is provided, answers from the tool approval dialog (y/a/n) are sent on it.
+/// If `conflict_tx` is provided, answers from the edit conflict dialog (r/o/x) are sent on it.
+/// If `transcript` is provided, the chat is mirrored to it as plain text (see [crate::transcript]).
+/// If `session_switch` is provided, Ctrl+S lists saved sessions and resumes the chosen one, and
+/// prompts sent are kept in its project DB for ↑↓ recall and Ctrl+R search (see [crate::history]).
+/// `custom_commands` are the project's `.locus/commands` templates, offered in the `/` command popup.
+/// `appearance` picks the light or dark palette (see [crate::theme::detect_term]); it follows the
+/// terminal when it regains focus, until a theme is chosen (see [crate::theme_picker]).
+#[allow(clippy::too_many_arguments)]
 pub fn run_tui_with_runtime(
     mut event_rx: tokio_mpsc::Receiver<SessionEvent>,
     user_msg_tx: tokio_mpsc::Sender<String>,
-    log_rx: Option<tokio_mpsc::Receiver<String>>,
+    logs: Option<LogBuffer>,
     new_session_tx: Option<tokio_mpsc::Sender<()>>,
     cancel_tx: Option<tokio_mpsc::Sender<()>>,
+    annotation_tx: Option<tokio_mpsc::Sender<ToolAnnotation>>,
+    approval_tx: Option<tokio_mpsc::Sender<ApprovalResponse>>,
+    conflict_tx: Option<tokio_mpsc::Sender<ConflictResponse>>,
+    transcript: Option<TranscriptMirror>,
+    session_switch: Option<SessionSwitch>,
+    custom_commands: Vec<CustomCommand>,
     appearance: Appearance,
     show_onboarding: bool,
 ) -> anyhow::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste,
+        EnableFocusChange
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut state = TuiState::with_appearance(appearance);
+    state.follow_terminal = true;
     if show_onboarding {
         state.screen = Screen::Setup;
     }
+    state.transcript = transcript;
+    state.custom_commands = custom_commands;
+    if let Some(switch) = &session_switch {
+        state.history = PromptHistory::load(&switch.repo_root);
+        state.file_finder = FileFinder::with_root(switch.repo_root.clone());
+        refresh_git(&mut state);
+    }
+    apply_startup_theme(&mut state);
+    if let Some(logs) = logs {
+        state.logs = LogsState::with_buffer(logs);
+    }
     state.push_trace_line(
         "[log] TUI started with runtime. Runtime logs (Ctrl+D) show tracing output.".to_string(),
     );
@@ -116,14 +196,19 @@ pub fn run_tui_with_runtime(
         &mut state,
         Some(&mut event_rx),
         Some(&user_msg_tx),
-        log_rx,
         new_session_tx.as_ref(),
         cancel_tx.as_ref(),
+        annotation_tx.as_ref(),
+        approval_tx.as_ref(),
+        conflict_tx.as_ref(),
+        session_switch.as_ref(),
         true,
     );
 
     execute!(
         terminal.backend_mut(),
+        DisableFocusChange,
+        DisableBracketedPaste,
         DisableMouseCapture,
         LeaveAlternateScreen
     )?;
@@ -135,19 +220,30 @@ pub fn run_tui_with_runtime(
 
 const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
 
+#[allow(clippy::too_many_arguments)]
 fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut TuiState,
     mut event_rx: Option<&mut tokio_mpsc::Receiver<SessionEvent>>,
     user_msg_tx: Option<&tokio_mpsc::Sender<String>>,
-    mut log_rx: Option<tokio_mpsc::Receiver<String>>,
     new_session_tx: Option<&tokio_mpsc::Sender<()>>,
     cancel_tx: Option<&tokio_mpsc::Sender<()>>,
+    annotation_tx: Option<&tokio_mpsc::Sender<ToolAnnotation>>,
+    approval_tx: Option<&tokio_mpsc::Sender<ApprovalResponse>>,
+    conflict_tx: Option<&tokio_mpsc::Sender<ConflictResponse>>,
+    session_switch: Option<&SessionSwitch>,
     mut mouse_enabled: bool,
 ) -> anyhow::Result<()> {
     let (key_tx, key_rx) = mpsc::channel();
+    // Set while an external editor has the terminal, so its keys stay its own
+    let reader_paused = Arc::new(AtomicBool::new(false));
+    let paused = Arc::clone(&reader_paused);
     let _reader = std::thread::spawn(move || {
         loop {
+            if paused.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            }
             if event::poll(Duration::from_millis(50)).unwrap_or(false)
                 && let Ok(ev) = event::read()
             {
@@ -156,14 +252,12 @@ fn run_loop(
         }
     });
 
+    // Key a palette action stands for, handled as if pressed next
+    let mut replay: Option<Event> = None;
     loop {
-        // Drain runtime log lines into debug traces (multi-line logs split into separate lines)
-        if let Some(ref mut rx) = log_rx {
-            while let Ok(line) = rx.try_recv() {
-                for l in line.split('\n') {
-                    state.push_trace_line(l.to_string());
-                }
-            }
+        // Take in runtime log records kept since the last pass
+        if state.logs.sync() {
+            state.needs_redraw = true;
         }
         // Drain session events from runtime
         if let Some(ref mut rx) = event_rx {
@@ -201,12 +295,22 @@ fn run_loop(
             state.needs_redraw = false;
         }
 
-        if let Ok(ev) = key_rx.try_recv() {
+        if let Some(ev) = replay.take().or_else(|| key_rx.try_recv().ok()) {
             match ev {
                 Event::Key(e) => {
                     if e.kind != KeyEventKind::Press {
                         continue;
                     }
+                    let typing_filter = state.screen == Screen::DebugTraces
+                        && state.logs.input.is_some()
+                        && matches!(e.code, KeyCode::Char(_));
+                    if let Some(scroll) = scroll_key(e.code, e.modifiers, state.screen)
+                        && !typing_filter
+                        && state.command_palette.is_none()
+                    {
+                        scroll_view(state, scroll);
+                        continue;
+                    }
                     match e.code {
                         // Onboarding: Enter -> continue to chat, Q -> quit
                         KeyCode::Enter if state.screen == Screen::Onboarding => {
@@ -232,6 +336,363 @@ fn run_loop(
                         KeyCode::Char(c) if state.screen == Screen::Setup => {
                             handle_setup_char(state, c);
                         }
+                        // Ctrl+X: command palette; typing filters it, ↑↓ pick an action,
+                        // Enter runs it, Esc closes
+                        KeyCode::Char('x')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen != Screen::Onboarding =>
+                        {
+                            toggle_palette(state)
+                        }
+                        KeyCode::Esc if state.command_palette.is_some() => {
+                            state.command_palette = None;
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Up if state.command_palette.is_some() => {
+                            if let Some(palette) = state.command_palette.as_mut() {
+                                palette.step(-1);
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Down if state.command_palette.is_some() => {
+                            if let Some(palette) = state.command_palette.as_mut() {
+                                palette.step(1);
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter if state.command_palette.is_some() => {
+                            if let Some((code, modifiers)) = run_selected(state) {
+                                replay = Some(Event::Key(KeyEvent::new(code, modifiers)));
+                            }
+                        }
+                        KeyCode::Backspace if state.command_palette.is_some() => {
+                            if let Some(palette) = state.command_palette.as_mut() {
+                                palette.backspace(&state.recent_actions);
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(c)
+                            if state.command_palette.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            if let Some(palette) = state.command_palette.as_mut() {
+                                palette.insert(c, &state.recent_actions);
+                            }
+                            state.needs_redraw = true;
+                        }
+                        _ if state.command_palette.is_some()
+                            && !e.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        // Tool approval dialog: y allow, a always allow, n/Esc deny,
+                        // m deny with a message typed into the input line (Enter sends)
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.approval_denying =>
+                        {
+                            if let Some(response) = state.deny_with_message()
+                                && let Some(tx) = approval_tx
+                            {
+                                let _ = tx.try_send(response);
+                            }
+                        }
+                        KeyCode::Esc if state.screen == Screen::Main && state.approval_denying => {
+                            state.cancel_deny_message()
+                        }
+                        KeyCode::Char('m')
+                            if state.screen == Screen::Main
+                                && !state.pending_approvals.is_empty()
+                                && !state.approval_denying
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            state.begin_deny_message()
+                        }
+                        KeyCode::Char(c @ ('y' | 'a' | 'n'))
+                            if state.screen == Screen::Main
+                                && !state.pending_approvals.is_empty()
+                                && !state.approval_denying
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            let decision = match c {
+                                'y' => ApprovalDecision::Allow,
+                                'a' => ApprovalDecision::AllowAlways,
+                                _ => ApprovalDecision::Deny,
+                            };
+                            answer_approval(state, approval_tx, decision);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main
+                                && !state.pending_approvals.is_empty() =>
+                        {
+                            answer_approval(state, approval_tx, ApprovalDecision::Deny);
+                        }
+                        // Edit conflict dialog (after approvals): r rebase, o overwrite, x/Esc abort
+                        KeyCode::Char(c @ ('r' | 'o' | 'x'))
+                            if state.screen == Screen::Main
+                                && state.pending_approvals.is_empty()
+                                && !state.pending_conflicts.is_empty()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            let decision = match c {
+                                'r' => ConflictDecision::Rebase,
+                                'o' => ConflictDecision::Overwrite,
+                                _ => ConflictDecision::Abort,
+                            };
+                            answer_conflict(state, conflict_tx, decision);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main
+                                && state.pending_approvals.is_empty()
+                                && !state.pending_conflicts.is_empty() =>
+                        {
+                            answer_conflict(state, conflict_tx, ConflictDecision::Abort);
+                        }
+                        // Copying from the chat: Alt+C last code block, Alt+O last tool
+                        // output, Alt+V select an item (j/k move, Enter copy, c its
+                        // last code block, Esc done)
+                        KeyCode::Char('c')
+                            if e.modifiers.contains(KeyModifiers::ALT)
+                                && state.screen == Screen::Main =>
+                        {
+                            copy_last_code_block(state)
+                        }
+                        KeyCode::Char('o')
+                            if e.modifiers.contains(KeyModifiers::ALT)
+                                && state.screen == Screen::Main =>
+                        {
+                            copy_last_tool_output(state)
+                        }
+                        KeyCode::Char('v')
+                            if e.modifiers.contains(KeyModifiers::ALT)
+                                && state.screen == Screen::Main =>
+                        {
+                            begin_selection(state)
+                        }
+                        KeyCode::Char('j') | KeyCode::Down
+                            if state.screen == Screen::Main
+                                && state.chat_selection.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            step_selection(state, 1)
+                        }
+                        KeyCode::Char('k') | KeyCode::Up
+                            if state.screen == Screen::Main
+                                && state.chat_selection.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            step_selection(state, -1)
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.chat_selection.is_some() =>
+                        {
+                            copy_selected(state, false)
+                        }
+                        KeyCode::Char('c')
+                            if state.screen == Screen::Main
+                                && state.chat_selection.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            copy_selected(state, true)
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.chat_selection.is_some() =>
+                        {
+                            end_selection(state)
+                        }
+                        KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Tab
+                            if state.screen == Screen::Main
+                                && state.chat_selection.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        // Session switcher: ↑↓ select, Enter resume, Esc close
+                        KeyCode::Up
+                            if state.screen == Screen::Main && state.session_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.session_picker.as_mut() {
+                                picker.up();
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main && state.session_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.session_picker.as_mut() {
+                                picker.down();
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.session_picker.is_some() =>
+                        {
+                            if let Some(switch) = session_switch {
+                                resume_selected(state, switch);
+                            }
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.session_picker.is_some() =>
+                        {
+                            state.session_picker = None;
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(_)
+                            if state.screen == Screen::Main
+                                && state.session_picker.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        // Model picker: ↑↓ select, Enter switch, Esc close
+                        KeyCode::Up
+                            if state.screen == Screen::Main && state.model_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.model_picker.as_mut() {
+                                picker.up();
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main && state.model_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.model_picker.as_mut() {
+                                picker.down();
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.model_picker.is_some() =>
+                        {
+                            switch_selected(state, user_msg_tx);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.model_picker.is_some() =>
+                        {
+                            state.model_picker = None;
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(_)
+                            if state.screen == Screen::Main
+                                && state.model_picker.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        // Theme gallery: ↑↓ preview, Enter apply, Esc restore
+                        KeyCode::Up
+                            if state.screen == Screen::Main && state.theme_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.theme_picker.as_mut() {
+                                picker.up();
+                            }
+                            preview_selected(state);
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main && state.theme_picker.is_some() =>
+                        {
+                            if let Some(picker) = state.theme_picker.as_mut() {
+                                picker.down();
+                            }
+                            preview_selected(state);
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.theme_picker.is_some() =>
+                        {
+                            apply_selected(state);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.theme_picker.is_some() =>
+                        {
+                            cancel_theme_picker(state);
+                        }
+                        KeyCode::Char(_)
+                            if state.screen == Screen::Main
+                                && state.theme_picker.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        // Command popup: ↑↓ pick a command, Tab inserts it, Esc closes
+                        KeyCode::Up
+                            if state.screen == Screen::Main && state.slash_menu.popup.is_some() =>
+                        {
+                            state.slash_menu.step(-1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main && state.slash_menu.popup.is_some() =>
+                        {
+                            state.slash_menu.step(1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Tab
+                            if state.screen == Screen::Main && state.slash_menu.popup.is_some() =>
+                        {
+                            complete_from_menu(state);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.slash_menu.popup.is_some() =>
+                        {
+                            state.slash_menu.dismiss();
+                            state.needs_redraw = true;
+                        }
+                        // File finder popup: ↑↓ pick a path, Tab inserts it, Esc closes
+                        KeyCode::Up
+                            if state.screen == Screen::Main
+                                && state.file_finder.popup.is_some() =>
+                        {
+                            state.file_finder.step(-1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main
+                                && state.file_finder.popup.is_some() =>
+                        {
+                            state.file_finder.step(1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Tab
+                            if state.screen == Screen::Main
+                                && state.file_finder.popup.is_some() =>
+                        {
+                            complete_file(state);
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main
+                                && state.file_finder.popup.is_some() =>
+                        {
+                            state.file_finder.dismiss();
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+R: search the prompt history, again for an older match;
+                        // Enter puts the match in the prompt, Esc leaves it as it was
+                        KeyCode::Char('r')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen == Screen::Main =>
+                        {
+                            state.history.search_older();
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main && state.history.search.is_some() =>
+                        {
+                            accept_search(state)
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main && state.history.search.is_some() =>
+                        {
+                            state.history.search_cancel();
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Backspace
+                            if state.screen == Screen::Main && state.history.search.is_some() =>
+                        {
+                            state.history.search_backspace();
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(c)
+                            if state.screen == Screen::Main
+                                && state.history.search.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            state.history.search_insert(c);
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+S: Open the session switcher (not while a turn is running)
+                        KeyCode::Char('s')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen == Screen::Main
+                                && !state.is_streaming =>
+                        {
+                            if let Some(switch) = session_switch {
+                                open_session_picker(state, switch);
+                            }
+                        }
                         // Ctrl+D: Toggle debug traces
                         KeyCode::Char('d') if e.modifiers.contains(KeyModifiers::CONTROL) => {
                             state.screen = match state.screen {
@@ -239,7 +700,30 @@ fn run_loop(
                                     Screen::DebugTraces
                                 }
                                 Screen::DebugTraces => Screen::Main,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
                                 Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::DiffReview => Screen::DiffReview,
+                                Screen::Agents => Screen::Agents,
+                            };
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+P: Toggle provider call inspection
+                        KeyCode::Char('p') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.screen = match state.screen {
+                                Screen::Main | Screen::Onboarding | Screen::Setup => {
+                                    Screen::ProviderCalls
+                                }
+                                Screen::ProviderCalls => Screen::Main,
+                                Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::DiffReview => Screen::DiffReview,
+                                Screen::Agents => Screen::Agents,
                             };
                             state.needs_redraw = true;
                         }
@@ -251,28 +735,208 @@ fn run_loop(
                                 }
                                 Screen::WebAutomation => Screen::Main,
                                 Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::DiffReview => Screen::DiffReview,
+                                Screen::Agents => Screen::Agents,
                             };
                             state.needs_redraw = true;
                         }
-                        // WebAutomation screen specific keys
-                        KeyCode::Esc if state.screen == Screen::WebAutomation => {
+                        // Ctrl+B: Toggle task board
+                        KeyCode::Char('b') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.screen = match state.screen {
+                                Screen::Main | Screen::Onboarding | Screen::Setup => {
+                                    Screen::TaskBoard
+                                }
+                                Screen::TaskBoard => Screen::Main,
+                                Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
+                                Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::DiffReview => Screen::DiffReview,
+                                Screen::Agents => Screen::Agents,
+                            };
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+G: Toggle the memory trace
+                        KeyCode::Char('g') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.screen = match state.screen {
+                                Screen::Main | Screen::Onboarding | Screen::Setup => {
+                                    Screen::MemoryTrace
+                                }
+                                Screen::MemoryTrace => Screen::Main,
+                                Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
+                                Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::DiffReview => Screen::DiffReview,
+                                Screen::Agents => Screen::Agents,
+                            };
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+F: Toggle the diff review
+                        KeyCode::Char('f') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.screen = match state.screen {
+                                Screen::Main | Screen::Onboarding | Screen::Setup => {
+                                    Screen::DiffReview
+                                }
+                                Screen::DiffReview => Screen::Main,
+                                Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
+                                Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::Agents => Screen::Agents,
+                            };
+                            state.needs_redraw = true;
+                        }
+                        // Ctrl+T: Toggle the agents view
+                        KeyCode::Char('t') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.screen = match state.screen {
+                                Screen::Main | Screen::Onboarding | Screen::Setup => Screen::Agents,
+                                Screen::Agents => Screen::Main,
+                                Screen::DebugTraces => Screen::DebugTraces,
+                                Screen::ProviderCalls => Screen::ProviderCalls,
+                                Screen::WebAutomation => Screen::WebAutomation,
+                                Screen::TaskBoard => Screen::TaskBoard,
+                                Screen::Plan => Screen::Plan,
+                                Screen::MemoryTrace => Screen::MemoryTrace,
+                                Screen::DiffReview => Screen::DiffReview,
+                            };
+                            state.needs_redraw = true;
+                        }
+                        // Agents screen: pick an agent, Enter opens its transcript,
+                        // Esc goes back to the list and then to the chat
+                        KeyCode::Esc if state.screen == Screen::Agents => {
+                            if !state.agents.close_transcript() {
+                                state.screen = Screen::Main;
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Left if state.screen == Screen::Agents => {
+                            state.agents.step(-1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Right if state.screen == Screen::Agents => {
+                            state.agents.step(1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter if state.screen == Screen::Agents => {
+                            state.agents.open_transcript();
+                            state.needs_redraw = true;
+                        }
+                        // Logs screen: while a filter is typed keys go to it, Enter or
+                        // Esc ends it; otherwise / t l pick filters, x clears, y copies
+                        KeyCode::Enter | KeyCode::Esc
+                            if state.screen == Screen::DebugTraces
+                                && state.logs.input.is_some() =>
+                        {
+                            state.logs.end_input();
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Backspace
+                            if state.screen == Screen::DebugTraces
+                                && state.logs.input.is_some() =>
+                        {
+                            state.logs.input_backspace();
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(c)
+                            if state.screen == Screen::DebugTraces
+                                && state.logs.input.is_some()
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            state.logs.input_insert(c);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char(c @ ('/' | 't' | 'l' | 'x' | 'y'))
+                            if state.screen == Screen::DebugTraces
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            match c {
+                                '/' => state.logs.begin_input(LogsInput::Search),
+                                't' => state.logs.begin_input(LogsInput::Target),
+                                'l' => state.logs.cycle_level(),
+                                'x' => state.logs.clear_filters(),
+                                _ => copy_logs_to_clipboard(state),
+                            }
+                            state.needs_redraw = true;
+                        }
+                        // Diff review screen: walk turns and hunks, approve or reject
+                        KeyCode::Esc if state.screen == Screen::DiffReview => {
                             state.screen = Screen::Main;
                             state.needs_redraw = true;
                         }
-                        KeyCode::Up if state.screen == Screen::WebAutomation => {
-                            state.web_automation.scroll_up(1);
+                        KeyCode::Left if state.screen == Screen::DiffReview => {
+                            state.diff_review.step_turn(-1);
                             state.needs_redraw = true;
                         }
-                        KeyCode::Down if state.screen == Screen::WebAutomation => {
-                            state.web_automation.scroll_down(1);
+                        KeyCode::Right if state.screen == Screen::DiffReview => {
+                            state.diff_review.step_turn(1);
                             state.needs_redraw = true;
                         }
-                        KeyCode::PageUp if state.screen == Screen::WebAutomation => {
-                            state.web_automation.scroll_up(10);
+                        KeyCode::Char('n') | KeyCode::Tab if state.screen == Screen::DiffReview => {
+                            state.diff_review.step_hunk(1);
                             state.needs_redraw = true;
                         }
-                        KeyCode::PageDown if state.screen == Screen::WebAutomation => {
-                            state.web_automation.scroll_down(10);
+                        KeyCode::Char('p') | KeyCode::BackTab
+                            if state.screen == Screen::DiffReview =>
+                        {
+                            state.diff_review.step_hunk(-1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char('a')
+                            if state.screen == Screen::DiffReview
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            approve_selected(state)
+                        }
+                        KeyCode::Char('r') if state.screen == Screen::DiffReview => {
+                            reject_selected(state, user_msg_tx)
+                        }
+                        KeyCode::Char('v') if state.screen == Screen::DiffReview => {
+                            state.diff_review.side_by_side = !state.diff_review.side_by_side;
+                            state.diff_review.reveal = true;
+                            state.needs_redraw = true;
+                        }
+                        // Memory trace screen: type a query, Enter searches, Esc goes back
+                        KeyCode::Esc if state.screen == Screen::MemoryTrace => {
+                            state.screen = Screen::Main;
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Enter if state.screen == Screen::MemoryTrace => {
+                            let query = state.input_take();
+                            if !query.trim().is_empty() {
+                                let line = format!("{} {}", MEMORY_COMMAND, query.trim());
+                                request_memory_search(state, &line, user_msg_tx);
+                            }
+                        }
+                        KeyCode::Backspace if state.screen == Screen::MemoryTrace => {
+                            state.input_backspace()
+                        }
+                        KeyCode::Char(c)
+                            if state.screen == Screen::MemoryTrace
+                                && !e.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            state.input_insert(c)
+                        }
+                        // Ctrl+L: Toggle the plan from the last `:plan` run
+                        KeyCode::Char('l') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            toggle_plan_screen(state)
+                        }
+                        // Plan screen: Enter carries the plan out, Esc goes back
+                        KeyCode::Esc if state.screen == Screen::Plan => toggle_plan_screen(state),
+                        KeyCode::Enter if state.screen == Screen::Plan => {
+                            execute_plan(state, user_msg_tx)
+                        }
+                        // WebAutomation screen specific keys
+                        KeyCode::Esc if state.screen == Screen::WebAutomation => {
+                            state.screen = Screen::Main;
                             state.needs_redraw = true;
                         }
                         KeyCode::Enter if state.screen == Screen::WebAutomation => {
@@ -300,9 +964,11 @@ fn run_loop(
                         {
                             if let Some(tx) = new_session_tx {
                                 let _ = tx.try_send(());
-                                state.push_separator("New session".to_string());
-                                state.status =
-                                    "New session — next message starts fresh".to_string();
+                                if let Some(mirror) = state.transcript.as_mut() {
+                                    mirror.record_separator("New session");
+                                }
+                                state.push_separator(t("status.new_session").to_string());
+                                state.status = t("status.new_session_started").to_string();
                                 state.status_set_at = Some(std::time::Instant::now());
                                 state.status_permanent = false;
                                 state.needs_redraw = true;
@@ -315,64 +981,162 @@ fn run_loop(
                             mouse_enabled = !mouse_enabled;
                             if mouse_enabled {
                                 let _ = execute!(terminal.backend_mut(), EnableMouseCapture);
-                                state.status = "Mouse enabled (scroll with mouse)".to_string();
+                                state.status = t("status.mouse_enabled").to_string();
                             } else {
                                 let _ = execute!(terminal.backend_mut(), DisableMouseCapture);
-                                state.status = "Mouse disabled (select text to copy)".to_string();
+                                state.status = t("status.mouse_disabled").to_string();
                             }
                             state.status_set_at = Some(std::time::Instant::now());
                             state.status_permanent = false;
                             state.needs_redraw = true;
                         }
-                        KeyCode::Char('c') if e.modifiers.contains(KeyModifiers::CONTROL) => {
-                            if state.is_streaming {
-                                if let Some(tx) = cancel_tx {
-                                    let _ = tx.try_send(());
-                                    state.status = "Cancelling… (Ctrl+C again to quit)".to_string();
-                                    state.status_set_at = Some(std::time::Instant::now());
-                                    state.needs_redraw = true;
-                                } else {
-                                    break;
-                                }
+                        // Ctrl+O: Show the summary of the past session flagged as similar work
+                        KeyCode::Char('o')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen == Screen::Main =>
+                        {
+                            if let Some(work) = state.similar_work.as_ref() {
+                                let summary = similar_work_summary(work);
+                                state.push_ai(summary, None);
                             } else {
+                                state.status = t("status.no_similar_work").to_string();
+                                state.status_set_at = Some(std::time::Instant::now());
+                                state.status_permanent = false;
+                            }
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Char('c') if e.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Stops a run; with nothing to stop, quits
+                            let stopped = cancel_run(state, cancel_tx);
+                            if !stopped {
                                 break;
                             }
                         }
-                        KeyCode::Char('q') if state.input_buffer.is_empty() => break,
+                        KeyCode::Char('q')
+                            if state.input_buffer.is_empty()
+                                && state.annotation_target.is_none() =>
+                        {
+                            break;
+                        }
                         KeyCode::Esc if state.screen == Screen::DebugTraces => {
                             state.screen = Screen::Main;
                             state.needs_redraw = true;
                         }
-                        KeyCode::Up if state.screen == Screen::DebugTraces => {
-                            state.trace_scroll_up(1)
+                        KeyCode::Esc if state.screen == Screen::ProviderCalls => {
+                            state.screen = Screen::Main;
+                            state.needs_redraw = true;
                         }
-                        KeyCode::Down if state.screen == Screen::DebugTraces => {
-                            state.trace_scroll_down(1)
+                        KeyCode::Left if state.screen == Screen::ProviderCalls => {
+                            state.provider_call_step(-1)
                         }
-                        KeyCode::PageUp if state.screen == Screen::DebugTraces => {
-                            state.trace_scroll_up(10)
+                        KeyCode::Right if state.screen == Screen::ProviderCalls => {
+                            state.provider_call_step(1)
                         }
-                        KeyCode::PageDown if state.screen == Screen::DebugTraces => {
-                            state.trace_scroll_down(10)
+                        KeyCode::Esc if state.screen == Screen::TaskBoard => {
+                            state.screen = Screen::Main;
+                            state.needs_redraw = true;
                         }
-                        KeyCode::Up if state.screen == Screen::Main => state.scroll_up(1),
-                        KeyCode::Down if state.screen == Screen::Main => state.scroll_down(1),
+                        KeyCode::Left if state.screen == Screen::TaskBoard => {
+                            state.task_board.step(-1);
+                            state.needs_redraw = true;
+                        }
+                        KeyCode::Right if state.screen == Screen::TaskBoard => {
+                            state.task_board.step(1);
+                            state.needs_redraw = true;
+                        }
+                        // ↑↓ recall earlier prompts; Shift+↑↓ scroll the chat
+                        KeyCode::Up
+                            if state.screen == Screen::Main
+                                && e.modifiers.contains(KeyModifiers::SHIFT) =>
+                        {
+                            state.scroll_up(1)
+                        }
+                        KeyCode::Down
+                            if state.screen == Screen::Main
+                                && e.modifiers.contains(KeyModifiers::SHIFT) =>
+                        {
+                            state.scroll_down(1)
+                        }
+                        KeyCode::Up if state.screen == Screen::Main => recall_previous(state),
+                        KeyCode::Down if state.screen == Screen::Main => recall_next(state),
                         KeyCode::PageUp if state.screen == Screen::Main => state.scroll_up(5),
                         KeyCode::PageDown if state.screen == Screen::Main => state.scroll_down(5),
+                        KeyCode::Char('a')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen == Screen::Main =>
+                        {
+                            state.status = if state.begin_annotation() {
+                                t("status.annotating").to_string()
+                            } else {
+                                t("status.nothing_to_annotate").to_string()
+                            };
+                            state.status_set_at = Some(std::time::Instant::now());
+                            state.status_permanent = false;
+                        }
+                        KeyCode::Esc
+                            if state.screen == Screen::Main
+                                && state.annotation_target.is_some() =>
+                        {
+                            state.cancel_annotation()
+                        }
+                        KeyCode::Enter
+                            if state.screen == Screen::Main
+                                && state.annotation_target.is_some() =>
+                        {
+                            if let Some(annotation) = state.take_annotation() {
+                                let sent =
+                                    annotation_tx.is_some_and(|tx| tx.try_send(annotation).is_ok());
+                                state.status = if sent {
+                                    t("status.note_attached").to_string()
+                                } else {
+                                    t("status.note_not_sent").to_string()
+                                };
+                                state.status_set_at = Some(std::time::Instant::now());
+                                state.status_permanent = false;
+                            }
+                        }
+                        // Esc while a run is going (and no dialog is open): stop it
+                        KeyCode::Esc if state.screen == Screen::Main && state.is_streaming => {
+                            cancel_run(state, cancel_tx);
+                        }
                         KeyCode::Enter if state.screen == Screen::Main => {
                             let line = state.input_take();
+                            // Large pastes go in as context blocks; the chat keeps placeholders
+                            let sent = take_pastes(state, &line);
                             let trimmed = line.trim();
-                            if !trimmed.is_empty() {
+                            state.history.record(sent.trim());
+                            if request_cancel(state, trimmed, cancel_tx) {
+                                // Stopping the run; the runtime reports back with SessionEnd.
+                            } else if request_rewind(state, trimmed, user_msg_tx) {
+                                // Sent to the runtime; the chat is cut when it reports back.
+                            } else if request_pin(state, trimmed, user_msg_tx) {
+                                // Sent to the runtime; it reports the pinned files back.
+                            } else if request_memory_search(state, trimmed, user_msg_tx) {
+                                // Sent to the runtime; the memory trace shows the results.
+                            } else if request_model(state, trimmed, user_msg_tx) {
+                                // Sent to the runtime; it lists the models or switches.
+                            } else if request_theme(state, trimmed) {
+                                // Opens the theme gallery, or applies the named theme.
+                            } else if request_export(state, trimmed) {
+                                // Written under .locus/exports/; the status line says where.
+                            } else if !trimmed.is_empty() {
+                                if let Some(mirror) = state.transcript.as_mut() {
+                                    mirror.record_user(sent.trim());
+                                }
                                 state.push_user(trimmed.to_string(), None);
                                 if let Some(tx) = user_msg_tx {
-                                    let _ = tx.try_send(trimmed.to_string());
+                                    let _ = tx.try_send(sent.trim().to_string());
                                 } else {
-                                    state.push_ai(format!("You said: {}", trimmed), None);
+                                    state.push_ai(tf("status.echo", &[("text", &trimmed)]), None);
                                 }
                             }
                         }
                         KeyCode::Backspace if state.screen == Screen::Main => {
-                            state.input_backspace()
+                            // A placeholder right before the cursor goes as a whole
+                            let removed = remove_paste(state);
+                            if !removed {
+                                state.input_backspace()
+                            }
                         }
                         KeyCode::Char('u')
                             if e.modifiers.contains(KeyModifiers::CONTROL)
@@ -387,12 +1151,21 @@ fn run_loop(
                             state.input_kill_to_end()
                         }
                         KeyCode::Char('t')
-                            if state.input_buffer.is_empty() && state.screen == Screen::Main =>
+                            if state.input_buffer.is_empty()
+                                && state.annotation_target.is_none()
+                                && state.screen == Screen::Main =>
                         {
                             toggle_last_think_collapsed(state)
                         }
+                        KeyCode::Char('i')
+                            if state.input_buffer.is_empty()
+                                && state.annotation_target.is_none()
+                                && state.screen == Screen::Main
+                                && state.toggle_project_insights() => {}
                         KeyCode::Char('d')
-                            if state.input_buffer.is_empty() && state.screen == Screen::Main =>
+                            if state.input_buffer.is_empty()
+                                && state.annotation_target.is_none()
+                                && state.screen == Screen::Main =>
                         {
                             state.diff_show_next_page()
                         }
@@ -401,44 +1174,70 @@ fn run_loop(
                                 && state.input_buffer.is_empty()
                                 && state.screen == Screen::Main =>
                         {
-                            copy_last_ai_to_clipboard(state);
+                            copy_last_reply(state);
+                        }
+                        // Ctrl+E: edit the draft (or the last message sent) in $EDITOR
+                        KeyCode::Char('e')
+                            if e.modifiers.contains(KeyModifiers::CONTROL)
+                                && state.screen == Screen::Main
+                                && state.annotation_target.is_none() =>
+                        {
+                            edit_prompt(state, |text| {
+                                run_editor(terminal, &reader_paused, mouse_enabled, text)
+                            });
                         }
                         KeyCode::Char(c) if state.screen == Screen::Main => state.input_insert(c),
                         KeyCode::Left if state.screen == Screen::Main => state.input_cursor_left(),
                         KeyCode::Right if state.screen == Screen::Main => {
                             state.input_cursor_right()
                         }
+                        KeyCode::Tab if state.screen == Screen::Main => {
+                            // Tab on a paste placeholder shows the paste in full
+                            let expanded = expand_paste(state);
+                            if !expanded {
+                                complete_slash_command(state);
+                            }
+                        }
                         KeyCode::Home if state.screen == Screen::Main => state.input_cursor_home(),
                         KeyCode::End if state.screen == Screen::Main => state.input_cursor_end(),
                         KeyCode::Delete if state.screen == Screen::Main => state.input_delete(),
                         _ => {}
                     }
+                    if state.screen == Screen::Main {
+                        state
+                            .file_finder
+                            .update(&state.input_buffer, state.input_cursor);
+                        update_slash_menu(state);
+                    }
+                }
+                // Bracketed paste: the whole paste at once, newlines kept
+                Event::Paste(text) => {
+                    handle_paste(state, &text);
+                    update_slash_menu(state);
+                }
+                // Back in the terminal: it may have switched between light and dark
+                Event::FocusGained if state.follow_terminal => {
+                    if let Some(appearance) = detect_paused(&reader_paused) {
+                        follow_terminal_appearance(state, appearance);
+                    }
                 }
                 Event::Resize(_, _) => {
                     state.cache_dirty = true;
                     state.needs_redraw = true;
                 }
                 Event::Mouse(me) if mouse_enabled => match me.kind {
+                    MouseEventKind::ScrollUp if scrolls(state.screen) => {
+                        scroll_view(state, Scroll::Up(WHEEL))
+                    }
+                    MouseEventKind::ScrollDown if scrolls(state.screen) => {
+                        scroll_view(state, Scroll::Down(WHEEL))
+                    }
                     MouseEventKind::ScrollUp => {
-                        match state.screen {
-                            Screen::DebugTraces => state.trace_scroll_up(3),
-                            Screen::WebAutomation => {
-                                state.web_automation.scroll_up(3);
-                            }
-                            Screen::Main | Screen::Onboarding | Screen::Setup => state.scroll_up(3),
-                        }
+                        state.scroll_up(WHEEL);
                         state.needs_redraw = true;
                     }
                     MouseEventKind::ScrollDown => {
-                        match state.screen {
-                            Screen::DebugTraces => state.trace_scroll_down(3),
-                            Screen::WebAutomation => {
-                                state.web_automation.scroll_down(3);
-                            }
-                            Screen::Main | Screen::Onboarding | Screen::Setup => {
-                                state.scroll_down(3)
-                            }
-                        }
+                        state.scroll_down(WHEEL);
                         state.needs_redraw = true;
                     }
                     _ => {}
@@ -452,25 +1251,90 @@ fn run_loop(
     Ok(())
 }
 
-/// Copy last AI message to system clipboard (Ctrl+Y when input empty).
-fn copy_last_ai_to_clipboard(state: &mut TuiState) {
-    let text = state
-        .messages
-        .iter()
-        .rev()
-        .find_map(|m| {
-            if let ChatItem::Ai(ai) = m {
-                Some(ai.text.clone())
-            } else {
-                None
-            }
-        })
-        .unwrap_or_default();
-    if text.is_empty() {
+/// Hand the terminal to [open_in_editor] on `text` and take it back after,
+/// with the key reader paused meanwhile.
+fn run_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    reader_paused: &AtomicBool,
+    mouse_enabled: bool,
+    text: &str,
+) -> io::Result<String> {
+    reader_paused.store(true, Ordering::Relaxed);
+    // Let a poll already under way finish before the editor reads keys
+    std::thread::sleep(Duration::from_millis(60));
+    let edited = execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )
+    .and_then(|_| disable_raw_mode())
+    .and_then(|_| open_in_editor(text));
+    reader_paused.store(false, Ordering::Relaxed);
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste
+    )?;
+    if mouse_enabled {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    edited
+}
+
+/// Ask the terminal for its background again (see [detect_term]), with the key
+/// reader paused so the answer isn't read as key presses.
+fn detect_paused(reader_paused: &AtomicBool) -> Option<Appearance> {
+    reader_paused.store(true, Ordering::Relaxed);
+    // Let a poll already under way finish before the query is sent
+    std::thread::sleep(Duration::from_millis(60));
+    let appearance = detect_term();
+    reader_paused.store(false, Ordering::Relaxed);
+    appearance
+}
+
+/// Answer the approval shown in the dialog and send the decision to the runtime.
+fn answer_approval(
+    state: &mut TuiState,
+    approval_tx: Option<&tokio_mpsc::Sender<ApprovalResponse>>,
+    decision: ApprovalDecision,
+) {
+    if let Some(response) = state.answer_approval(decision)
+        && let Some(tx) = approval_tx
+    {
+        let _ = tx.try_send(response);
+    }
+}
+
+/// Answer the conflict shown in the dialog and send the decision to the runtime.
+fn answer_conflict(
+    state: &mut TuiState,
+    conflict_tx: Option<&tokio_mpsc::Sender<ConflictResponse>>,
+    decision: ConflictDecision,
+) {
+    if let Some(response) = state.answer_conflict(decision)
+        && let Some(tx) = conflict_tx
+    {
+        let _ = tx.try_send(response);
+    }
+}
+
+/// Copy the log lines the filters let through, one per line.
+fn copy_logs_to_clipboard(state: &mut TuiState) {
+    let records = state.logs.visible();
+    if records.is_empty() {
         return;
     }
-    if cli_clipboard::set_contents(text).is_ok() {
-        state.status = "Copied to clipboard".to_string();
+    let text = records
+        .iter()
+        .map(|record| record.line())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if copy_to_clipboard(&text).is_ok() {
+        state.status = tf("logs.copied", &[("count", &records.len())]);
         state.status_set_at = Some(std::time::Instant::now());
         state.status_permanent = false;
         state.needs_redraw = true;
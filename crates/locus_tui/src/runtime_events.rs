@@ -1,16 +1,27 @@
 //! Map [locus_core::SessionEvent] to [TuiState] updates.
 
-use locus_core::{Role, SessionEvent, ToolResultData, ToolUse};
+use locus_core::{Role, SessionEvent, SubAgentState, ToolResultData, ToolUse};
 
-use crate::messages::memory::MemoryMessage;
-use crate::messages::meta_tools::{MetaToolKind, MetaToolMessage};
+use crate::i18n::{t, tf};
+use crate::memory_trace::Retrieval;
+use crate::messages::insights::ProjectInsightsMessage;
+use crate::messages::memory::{MemoryMessage, format_day, session_label};
+use crate::messages::meta_tools::{MetaToolKind, MetaToolMessage, MetaToolStatus};
 use crate::messages::tools::{EditDiff, EditDiffMessage, ToolCallMessage};
+use crate::model_picker::show_models;
+use crate::pin::show_pinned;
+use crate::plan::show_plan;
+use crate::rewind::apply_rewound;
 use crate::state::{ChatItem, TuiState};
+use crate::status_bar::refresh_git;
 
 /// Apply a session event to TUI state (accumulate or push items).
 /// Runtime logs are shown in the debug traces screen (Ctrl+D), not session events.
 pub fn apply_session_event(state: &mut TuiState, event: SessionEvent) {
     state.needs_redraw = true;
+    if let Some(mirror) = state.transcript.as_mut() {
+        mirror.record_event(&event);
+    }
     match event {
         SessionEvent::TurnStart { role } => {
             if role == Role::Assistant {
@@ -54,6 +65,7 @@ pub fn apply_session_event(state: &mut TuiState, event: SessionEvent) {
             result,
         } => {
             state.cache_dirty = true;
+            state.tool_outputs.record(&tool_use_id, &result.output);
             let edit_diff = extract_edit_diff(&result);
             // Update tool status without attaching inline diff; push a dedicated EditDiff block when present.
             let updated =
@@ -82,17 +94,183 @@ pub fn apply_session_event(state: &mut TuiState, event: SessionEvent) {
                 }
             }
         }
+        SessionEvent::ToolProgress {
+            tool_use_id,
+            message,
+        } => {
+            state.set_tool_progress(&tool_use_id, message);
+        }
+        SessionEvent::ToolTimedOut {
+            tool_use_id,
+            tool_name,
+            timeout_secs,
+        } => {
+            let secs = timeout_secs.to_string();
+            state.fail_tool_by_id(&tool_use_id, tf("tool.timed_out", &[("secs", &secs)]));
+            state.status = tf(
+                "status.tool_timed_out",
+                &[("tool", &tool_name), ("secs", &secs)],
+            );
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
         SessionEvent::Status { message } => {
             state.status = message;
             state.status_set_at = Some(std::time::Instant::now());
             state.status_permanent = false;
         }
+        SessionEvent::Throttled {
+            wait_ms, reason, ..
+        } => {
+            let until = std::time::Instant::now() + std::time::Duration::from_millis(wait_ms);
+            let task = state.messages.iter_mut().rev().find_map(|item| match item {
+                ChatItem::MetaTool(m)
+                    if m.kind == MetaToolKind::Task
+                        && matches!(m.status, MetaToolStatus::Running) =>
+                {
+                    Some(m)
+                }
+                _ => None,
+            });
+            if let Some(m) = task {
+                m.status = MetaToolStatus::Throttled {
+                    until,
+                    reason: reason.clone(),
+                };
+                state.cache_dirty = true;
+            }
+            state.status = tf("status.throttled", &[("reason", &reason)]);
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
+        SessionEvent::SubAgents { agents } => {
+            let count = |f: fn(SubAgentState) -> bool| {
+                agents
+                    .iter()
+                    .filter(|agent| f(agent.state))
+                    .count()
+                    .to_string()
+            };
+            state.status = tf(
+                "status.sub_agents",
+                &[
+                    ("running", &count(|s| s == SubAgentState::Running)),
+                    ("queued", &count(|s| s == SubAgentState::Queued)),
+                    ("finished", &count(SubAgentState::is_finished)),
+                ],
+            );
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+            state.agents.update(agents);
+        }
+        SessionEvent::SubAgentOutput { tool_use_id, event } => {
+            state.agents.record(&tool_use_id, *event);
+        }
+        SessionEvent::TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_prompt_tokens,
+            total_completion_tokens,
+        } => state.usage.record(
+            prompt_tokens,
+            completion_tokens,
+            total_prompt_tokens,
+            total_completion_tokens,
+        ),
+        SessionEvent::ModelInUse {
+            provider,
+            model,
+            context_limit,
+        } => state.usage.set_model(provider, model, context_limit),
+        SessionEvent::ProviderCall { call, keep } => {
+            state.push_provider_call(*call, keep);
+        }
+        SessionEvent::ApprovalRequested { request } => {
+            state.push_approval(request);
+        }
+        SessionEvent::ConflictDetected { request } => {
+            state.push_conflict(request);
+        }
+        SessionEvent::GuardrailViolation {
+            violations,
+            reverted,
+        } => {
+            let key = if reverted {
+                "chat.guardrail_reverted"
+            } else {
+                "chat.guardrail_blocked"
+            };
+            let text = tf(key, &[("violations", &violations.join("; "))]);
+            state.status = text.clone();
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+            state.push_error(text, None);
+        }
+        SessionEvent::SecretsRedacted { secrets } => {
+            let listed: Vec<String> = secrets
+                .iter()
+                .map(|s| format!("{} → {}", s.kind, s.placeholder))
+                .collect();
+            state.status = tf(
+                "status.secrets_redacted",
+                &[("secrets", &listed.join(", "))],
+            );
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
+        SessionEvent::InstructionsLoaded { files } => {
+            state.status = tf(
+                "status.instructions_loaded",
+                &[("files", &files.join(", "))],
+            );
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
+        SessionEvent::SimilarWork { work } => {
+            let files = if work.files_modified.len() > 3 {
+                format!(
+                    "{} and {} more",
+                    work.files_modified[..3].join(", "),
+                    work.files_modified.len() - 3
+                )
+            } else {
+                work.files_modified.join(", ")
+            };
+            state.status = tf(
+                "chat.similar_work",
+                &[
+                    ("date", &format_day(work.matched.ts)),
+                    ("session", &session_label(&work.matched)),
+                    ("files", &files),
+                ],
+            );
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+            state.push_memory(MemoryMessage::similar(&work));
+            state.similar_work = Some(*work);
+        }
+        SessionEvent::TaskListUpdated { plan_id, tasks } => {
+            state.task_board.update(plan_id, tasks);
+        }
+        SessionEvent::Rewound { summary } => apply_rewound(state, &summary),
+        SessionEvent::TurnDiff { turn, files } => state.diff_review.record(turn, files),
+        SessionEvent::HunkReverted { turn, path, hunk } => {
+            state.diff_review.mark_reverted(turn, &path, hunk);
+            state.status = tf("status.hunk_reverted", &[("path", &path)]);
+            state.status_set_at = Some(std::time::Instant::now());
+            state.status_permanent = false;
+        }
+        SessionEvent::PinnedFiles { files } => show_pinned(state, &files),
+        SessionEvent::ModelList { providers } => show_models(state, providers),
+        SessionEvent::McpPrompts { prompts } => state.mcp_prompts = prompts,
+        SessionEvent::PlanReady { plan } => show_plan(state, plan),
         SessionEvent::TurnEnd => {
             state.is_streaming = false;
             state.flush_turn();
         }
         SessionEvent::Error { error } => {
             state.is_streaming = false;
+            state.diff_review.reset_rejecting();
             state.status = error.clone();
             state.status_set_at = Some(std::time::Instant::now());
             state.status_permanent = false;
@@ -105,25 +283,78 @@ pub fn apply_session_event(state: &mut TuiState, event: SessionEvent) {
         } => {
             state.is_streaming = false;
             state.flush_turn();
+            // The runtime stops waiting for answers once the session ends.
+            state.pending_approvals.clear();
+            state.approval_denying = false;
+            state.pending_conflicts.clear();
+            // The run may have changed files or switched branches
+            refresh_git(state);
             let total = prompt_tokens + completion_tokens;
             let sep_label = if total > 0 {
-                format!(
-                    "Turn complete · {} tokens ({}↑ {}↓)",
-                    format_token_count(total),
-                    format_token_count(prompt_tokens),
-                    format_token_count(completion_tokens),
+                tf(
+                    "status.turn_complete_tokens",
+                    &[
+                        ("total", &format_token_count(total)),
+                        ("prompt", &format_token_count(prompt_tokens)),
+                        ("completion", &format_token_count(completion_tokens)),
+                    ],
                 )
             } else {
-                "Turn complete".to_string()
+                t("status.turn_complete").to_string()
             };
             state.push_separator(sep_label);
-            state.status = "Send message to continue · Ctrl+N new session".to_string();
+            state.status = match state.diff_review.take_unseen() {
+                Some(files) => tf("status.review_changes", &[("files", &files)]),
+                None => t("status.continue_hint").to_string(),
+            };
             state.status_permanent = true;
             state.status_set_at = None;
         }
         SessionEvent::MemoryRecall { query, items_found } => {
             state.push_memory(MemoryMessage::recall(query, items_found));
         }
+        SessionEvent::MemoryBudget {
+            budget_tokens,
+            used_tokens,
+            items,
+        } => {
+            state.push_memory(MemoryMessage::budget(budget_tokens, used_tokens, &items));
+        }
+        SessionEvent::MemorySearch {
+            query,
+            memories,
+            degraded,
+        } => {
+            state.memory_trace.show_search(query, memories, degraded);
+            state.needs_redraw = true;
+        }
+        SessionEvent::ProjectInsights {
+            insight,
+            recommendation,
+            confidence,
+        } => {
+            state.push_project_insights(ProjectInsightsMessage::new(
+                insight,
+                recommendation,
+                confidence,
+            ));
+        }
+        SessionEvent::MemoriesRetrieved {
+            turn,
+            query,
+            memories,
+            duration_ms,
+            degraded,
+        } => {
+            state.memory_trace.record(Retrieval {
+                turn,
+                query,
+                memories,
+                duration_ms,
+                degraded,
+            });
+            state.needs_redraw = true;
+        }
         SessionEvent::MemoryStore {
             context_id,
             event_kind,
@@ -135,7 +366,7 @@ pub fn apply_session_event(state: &mut TuiState, event: SessionEvent) {
 }
 
 /// Format token count for display: "1,234" or "12.3k" for large numbers.
-fn format_token_count(n: u64) -> String {
+pub(crate) fn format_token_count(n: u64) -> String {
     if n >= 1_000_000 {
         format!("{:.1}M", n as f64 / 1_000_000.0)
     } else if n >= 10_000 {
@@ -167,7 +398,7 @@ fn extract_edit_diff(result: &ToolResultData) -> Option<EditDiff> {
     })
 }
 
-fn tool_summary(tool: &ToolUse) -> Option<String> {
+pub(crate) fn tool_summary(tool: &ToolUse) -> Option<String> {
     tool.args
         .get("path")
         .or(tool.args.get("file_path"))
@@ -182,7 +413,7 @@ fn tool_summary(tool: &ToolUse) -> Option<String> {
         })
 }
 
-fn tool_detail(tool: &ToolUse) -> Option<String> {
+pub(crate) fn tool_detail(tool: &ToolUse) -> Option<String> {
     tool.args
         .get("query")
         .or(tool.args.get("tool_id"))
@@ -0,0 +1,196 @@
+//! Scrolling in the full-screen views: task board, logs, plan, provider calls,
+//! web automation, memory trace, diff review and agents.
+//!
+//! Every view keeps its own scroll offset; [scroll_key] maps a key to a
+//! [Scroll] for the current screen and [scroll_view] applies it there. Keys:
+//! ↑/↓ and j/k by a line, PgUp/PgDn by a page, g/G (or Home/End) to the top
+//! or bottom. The memory trace has a query line, so letters type into it and
+//! only the arrows, PgUp/PgDn and Home/End scroll. Reaching the bottom of the
+//! logs follows new lines again; scrolling up holds the view in place.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::plan::{plan_scroll_down, plan_scroll_up};
+use crate::state::{Screen, TuiState};
+
+/// Lines PgUp/PgDn move.
+pub const PAGE: usize = 10;
+/// Lines one mouse-wheel step moves.
+pub const WHEEL: usize = 3;
+
+/// A scroll in the current view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scroll {
+    /// Towards the top by this many lines.
+    Up(usize),
+    /// Towards the bottom by this many lines.
+    Down(usize),
+    Top,
+    /// The end; in the logs, follows new lines.
+    Bottom,
+}
+
+/// The scroll `code` asks for on `screen`, if it scrolls there.
+pub fn scroll_key(code: KeyCode, modifiers: KeyModifiers, screen: Screen) -> Option<Scroll> {
+    if !scrolls(screen) || modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+    let letters = screen != Screen::MemoryTrace;
+    match code {
+        KeyCode::Up => Some(Scroll::Up(1)),
+        KeyCode::Down => Some(Scroll::Down(1)),
+        KeyCode::PageUp => Some(Scroll::Up(PAGE)),
+        KeyCode::PageDown => Some(Scroll::Down(PAGE)),
+        KeyCode::Home => Some(Scroll::Top),
+        KeyCode::End => Some(Scroll::Bottom),
+        KeyCode::Char('k') if letters => Some(Scroll::Up(1)),
+        KeyCode::Char('j') if letters => Some(Scroll::Down(1)),
+        KeyCode::Char('g') if letters => Some(Scroll::Top),
+        KeyCode::Char('G') if letters => Some(Scroll::Bottom),
+        _ => None,
+    }
+}
+
+/// Whether `screen` is a view [scroll_view] scrolls.
+pub fn scrolls(screen: Screen) -> bool {
+    matches!(
+        screen,
+        Screen::TaskBoard
+            | Screen::DebugTraces
+            | Screen::Plan
+            | Screen::ProviderCalls
+            | Screen::WebAutomation
+            | Screen::MemoryTrace
+            | Screen::DiffReview
+            | Screen::Agents
+    )
+}
+
+/// Apply `scroll` to the current view. Views clamp their offset when drawn,
+/// so the top and bottom of views scrolled from the top are reached by
+/// scrolling all the way.
+pub fn scroll_view(state: &mut TuiState, scroll: Scroll) {
+    match state.screen {
+        Screen::TaskBoard => {
+            let board = &mut state.task_board;
+            match scroll {
+                Scroll::Up(n) => board.scroll_up(n),
+                Scroll::Down(n) => board.scroll_down(n),
+                Scroll::Top => board.scroll = 0,
+                Scroll::Bottom => board.scroll = usize::MAX,
+            }
+        }
+        Screen::MemoryTrace => {
+            let trace = &mut state.memory_trace;
+            match scroll {
+                Scroll::Up(n) => trace.scroll_up(n),
+                Scroll::Down(n) => trace.scroll_down(n),
+                Scroll::Top => trace.scroll = 0,
+                Scroll::Bottom => trace.scroll = usize::MAX,
+            }
+        }
+        Screen::DiffReview => {
+            let review = &mut state.diff_review;
+            match scroll {
+                Scroll::Up(n) => review.scroll_up(n),
+                Scroll::Down(n) => review.scroll_down(n),
+                Scroll::Top => review.scroll = 0,
+                Scroll::Bottom => review.scroll = usize::MAX,
+            }
+        }
+        Screen::Agents => {
+            let agents = &mut state.agents;
+            match scroll {
+                Scroll::Up(n) => agents.scroll_up(n),
+                Scroll::Down(n) => agents.scroll_down(n),
+                Scroll::Top => agents.scroll = 0,
+                Scroll::Bottom => agents.scroll = usize::MAX,
+            }
+        }
+        Screen::Plan => match scroll {
+            Scroll::Up(n) => plan_scroll_up(state, n),
+            Scroll::Down(n) => plan_scroll_down(state, n),
+            Scroll::Top => state.plan_scroll = 0,
+            Scroll::Bottom => state.plan_scroll = usize::MAX,
+        },
+        Screen::ProviderCalls => match scroll {
+            Scroll::Up(n) => state.provider_call_scroll_up(n),
+            Scroll::Down(n) => state.provider_call_scroll_down(n),
+            Scroll::Top => state.provider_call_scroll = 0,
+            Scroll::Bottom => state.provider_call_scroll = usize::MAX,
+        },
+        // Scrolled from the bottom
+        Screen::WebAutomation => {
+            let web = &mut state.web_automation;
+            match scroll {
+                Scroll::Up(n) => web.scroll_up(n),
+                Scroll::Down(n) => web.scroll_down(n),
+                Scroll::Top => web.scroll = usize::MAX,
+                Scroll::Bottom => web.scroll = 0,
+            }
+        }
+        Screen::DebugTraces => match scroll {
+            Scroll::Up(n) => state.logs.scroll_up(n),
+            Scroll::Down(n) => state.logs.scroll_down(n),
+            Scroll::Top => state.logs.scroll_up(usize::MAX),
+            Scroll::Bottom => state.logs.scroll_down(usize::MAX),
+        },
+        Screen::Main | Screen::Onboarding | Screen::Setup => return,
+    }
+    state.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_scroll_except_in_the_memory_trace() {
+        let none = KeyModifiers::NONE;
+        assert_eq!(
+            scroll_key(KeyCode::Char('j'), none, Screen::TaskBoard),
+            Some(Scroll::Down(1))
+        );
+        assert_eq!(
+            scroll_key(KeyCode::Char('G'), KeyModifiers::SHIFT, Screen::DebugTraces),
+            Some(Scroll::Bottom)
+        );
+        assert_eq!(
+            scroll_key(KeyCode::PageUp, none, Screen::Plan),
+            Some(Scroll::Up(PAGE))
+        );
+        assert_eq!(
+            scroll_key(KeyCode::Char('g'), none, Screen::MemoryTrace),
+            None
+        );
+        assert_eq!(
+            scroll_key(KeyCode::End, none, Screen::MemoryTrace),
+            Some(Scroll::Bottom)
+        );
+        assert_eq!(scroll_key(KeyCode::Char('j'), none, Screen::Main), None);
+        assert_eq!(
+            scroll_key(KeyCode::Char('g'), KeyModifiers::CONTROL, Screen::TaskBoard),
+            None
+        );
+    }
+
+    #[test]
+    fn logs_follow_new_lines_until_scrolled_up() {
+        let mut state = TuiState::new();
+        state.screen = Screen::DebugTraces;
+        state.push_trace_line("one".into());
+        assert!(state.logs.follow);
+
+        scroll_view(&mut state, Scroll::Up(1));
+        assert!(!state.logs.follow);
+        state.push_trace_line("two".into());
+        // Held in place: one more line below the view
+        assert_eq!(state.logs.scroll, 2);
+
+        scroll_view(&mut state, Scroll::Bottom);
+        assert!(state.logs.follow);
+        assert_eq!(state.logs.scroll, 0);
+        state.push_trace_line("three".into());
+        assert_eq!(state.logs.scroll, 0);
+    }
+}
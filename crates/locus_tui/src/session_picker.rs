@@ -0,0 +1,336 @@
+//! Session switcher (Ctrl+S): pick a saved session, replay its transcript into the
+//! chat, and ask the runtime to resume it.
+//!
+//! Sessions are read from `.locus/locus.db` (see `locus_core::db::list_sessions`);
+//! the chosen session id is sent on [SessionSwitch::resume_tx] for the runtime.
+
+use std::path::PathBuf;
+
+use locus_core::db::{self, SessionRecord};
+use locus_core::mention::is_attachment;
+use locus_core::{ContentBlock, Role, Session, SessionEvent, ToolResultData};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::mpsc as tokio_mpsc;
+
+use crate::i18n::{t, tf};
+use crate::layouts::{background_style, text_muted_style, text_style};
+use crate::runtime_events::apply_session_event;
+use crate::state::TuiState;
+
+/// Rows of sessions shown at once in the switcher.
+const VISIBLE_ROWS: usize = 12;
+
+/// Where saved sessions live and where to send the one to resume.
+pub struct SessionSwitch {
+    pub repo_root: PathBuf,
+    pub resume_tx: tokio_mpsc::Sender<String>,
+}
+
+/// Open switcher: saved sessions, most recent first, and the highlighted row.
+#[derive(Debug, Clone, Default)]
+pub struct SessionPicker {
+    pub sessions: Vec<SessionRecord>,
+    pub selected: usize,
+}
+
+impl SessionPicker {
+    pub fn new(sessions: Vec<SessionRecord>) -> Self {
+        Self {
+            sessions,
+            selected: 0,
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selected + 1 < self.sessions.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&SessionRecord> {
+        self.sessions.get(self.selected)
+    }
+}
+
+/// Load the saved sessions and open the switcher; show a status instead when there
+/// are none or the DB can't be read.
+pub fn open_session_picker(state: &mut TuiState, switch: &SessionSwitch) {
+    match db::list_sessions(&switch.repo_root) {
+        Ok(sessions) if !sessions.is_empty() => {
+            state.session_picker = Some(SessionPicker::new(sessions));
+        }
+        Ok(_) => set_status(state, t("status.no_saved_sessions").to_string()),
+        Err(e) => set_status(
+            state,
+            tf("status.sessions_failed", &[("error", &e.to_string())]),
+        ),
+    }
+    state.needs_redraw = true;
+}
+
+/// Resume the highlighted session: replace the chat with its transcript and send
+/// its id to the runtime. Closes the switcher.
+pub fn resume_selected(state: &mut TuiState, switch: &SessionSwitch) {
+    let Some(record) = state
+        .session_picker
+        .take()
+        .and_then(|picker| picker.selected().cloned())
+    else {
+        return;
+    };
+    match db::load_session(&switch.repo_root, &record.id) {
+        Ok(Some(session)) => {
+            replay_session(state, &record, &session);
+            let _ = switch.resume_tx.try_send(record.id.clone());
+            set_status(
+                state,
+                tf("status.session_resumed", &[("session", &record.label())]),
+            );
+        }
+        Ok(None) => set_status(
+            state,
+            tf("status.sessions_failed", &[("error", &"session not found")]),
+        ),
+        Err(e) => set_status(
+            state,
+            tf("status.sessions_failed", &[("error", &e.to_string())]),
+        ),
+    }
+    state.needs_redraw = true;
+}
+
+/// Replace the chat with a saved session's messages and tool calls.
+pub fn replay_session(state: &mut TuiState, record: &SessionRecord, session: &Session) {
+    state.messages.clear();
+    state.current_ai_text.clear();
+    state.current_think_text.clear();
+    state.diff_page_message_index = None;
+    state.annotation_target = None;
+    state.similar_work = None;
+    state.scroll = 0;
+    state.auto_scroll = true;
+    // The live transcript mirrors what happens from here on, not the replayed history.
+    let mut transcript = state.transcript.take();
+    if let Some(mirror) = transcript.as_mut() {
+        mirror.record_separator(&format!("Resumed session {}", record.label()));
+    }
+
+    state.push_separator(tf("chat.session_resumed", &[("session", &record.label())]));
+    for turn in &session.turns {
+        let ts = turn
+            .timestamp
+            .with_timezone(&chrono::Local)
+            .format("%H:%M")
+            .to_string();
+        for block in &turn.blocks {
+            match (turn.role, block) {
+                (Role::User, ContentBlock::Text { text }) if !is_attachment(text) => {
+                    state.push_user(text.clone(), Some(ts.clone()))
+                }
+                (Role::Assistant, ContentBlock::Text { text }) if !text.trim().is_empty() => {
+                    state.push_ai(text.clone(), Some(ts.clone()))
+                }
+                (_, ContentBlock::Thinking { thinking, .. }) if !thinking.trim().is_empty() => {
+                    state.push_think(thinking.clone(), true)
+                }
+                (_, ContentBlock::ToolUse { tool_use }) => {
+                    apply_session_event(
+                        state,
+                        SessionEvent::ToolStart {
+                            tool_use: tool_use.clone(),
+                        },
+                    );
+                }
+                (_, ContentBlock::ToolResult { tool_result }) => {
+                    // Tool turns wrap each result with the id of the call it answers.
+                    let output = &tool_result.output;
+                    if let Some(id) = output.get("tool_use_id").and_then(|v| v.as_str()) {
+                        apply_session_event(
+                            state,
+                            SessionEvent::ToolDone {
+                                tool_use_id: id.to_string(),
+                                result: ToolResultData {
+                                    output: output.get("result").cloned().unwrap_or_default(),
+                                    duration_ms: tool_result.duration_ms,
+                                    is_error: tool_result.is_error,
+                                },
+                            },
+                        );
+                    }
+                }
+                (_, ContentBlock::Error { error }) => state.push_error(error.clone(), None),
+                _ => {}
+            }
+        }
+    }
+    state.transcript = transcript;
+    state.is_streaming = false;
+    state.cache_dirty = true;
+    state.needs_redraw = true;
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+}
+
+/// The switcher popup, drawn over the chat.
+pub fn draw_session_picker(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let Some(picker) = state.session_picker.as_ref() else {
+        return;
+    };
+    let palette = &state.palette;
+    let muted = text_muted_style(palette.text_muted);
+    let now = chrono::Utc::now().timestamp();
+
+    // Keep the highlighted row in view.
+    let first = picker.selected.saturating_sub(VISIBLE_ROWS - 1);
+    let mut lines: Vec<Line<'static>> = picker
+        .sessions
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(VISIBLE_ROWS)
+        .map(|(i, record)| {
+            let selected = i == picker.selected;
+            let style = if selected {
+                text_style(palette.accent)
+            } else {
+                text_style(palette.text)
+            };
+            let title = if record.title.is_empty() {
+                record.label().to_string()
+            } else {
+                record.title.clone()
+            };
+            Line::from(vec![
+                Span::styled(if selected { "› " } else { "  " }.to_string(), style),
+                Span::styled(title, style),
+                Span::styled(
+                    format!(
+                        "  {} · {}",
+                        format_age(now.saturating_sub(record.updated_at)),
+                        tf("session_picker.turns", &[("count", &record.turns)])
+                    ),
+                    muted,
+                ),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    let key = |k: &str| Span::styled(k.to_string(), text_style(palette.accent));
+    lines.push(Line::from(vec![
+        key("↑↓"),
+        Span::styled(format!(" {}  ", t("session_picker.select")), muted),
+        key("Enter"),
+        Span::styled(format!(" {}  ", t("session_picker.resume")), muted),
+        key("Esc"),
+        Span::styled(format!(" {}", t("shortcut.cancel")), muted),
+    ]));
+
+    let width = area.width.saturating_sub(4).min(90);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("session_picker.title")))
+        .border_style(text_style(palette.accent))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// "just now", "5m ago", "3h ago", "2d ago".
+fn format_age(secs: i64) -> String {
+    match secs.max(0) {
+        0..60 => "just now".to_string(),
+        s @ 60..3_600 => format!("{}m ago", s / 60),
+        s @ 3_600..86_400 => format!("{}h ago", s / 3_600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ChatItem;
+    use locus_core::{SessionConfig, ToolUse, Turn};
+    use serde_json::json;
+
+    fn record(id: &str) -> SessionRecord {
+        SessionRecord {
+            id: id.to_string(),
+            slug: format!("{}-slug", id),
+            title: String::new(),
+            created_at: 0,
+            updated_at: 0,
+            provider: "anthropic".to_string(),
+            model: "m".to_string(),
+            turns: 1,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+        }
+    }
+
+    #[test]
+    fn picker_moves_within_bounds() {
+        let mut picker = SessionPicker::new(vec![record("a"), record("b")]);
+        picker.up();
+        assert_eq!(picker.selected().unwrap().id, "a");
+        picker.down();
+        picker.down();
+        assert_eq!(picker.selected().unwrap().id, "b");
+    }
+
+    #[test]
+    fn replay_shows_messages_and_finished_tools() {
+        let mut session = Session::new(
+            std::path::PathBuf::from("/repo"),
+            SessionConfig::new("m", "anthropic"),
+        );
+        let tool_use = ToolUse::new("call-1", "read", json!({"path": "src/lib.rs"}));
+        session.add_turn(Turn::user().with_block(ContentBlock::text("Explain lib.rs")));
+        session.add_turn(
+            Turn::assistant()
+                .with_block(ContentBlock::text("Reading it."))
+                .with_block(ContentBlock::tool_use(tool_use)),
+        );
+        session.add_turn(Turn::new(Role::Tool).with_block(ContentBlock::tool_result(
+            ToolResultData::success(
+                json!({"tool_use_id": "call-1", "tool_name": "read", "result": {"content": "x"}}),
+                12,
+            ),
+        )));
+
+        let mut state = TuiState::new();
+        state.push_ai("stale".to_string(), None);
+        replay_session(&mut state, &record("s1"), &session);
+
+        assert!(matches!(&state.messages[0], ChatItem::Separator(_)));
+        assert!(matches!(&state.messages[1], ChatItem::User(m) if m.text == "Explain lib.rs"));
+        assert!(matches!(&state.messages[2], ChatItem::Ai(m) if m.text == "Reading it."));
+        assert!(matches!(
+            &state.messages[3],
+            ChatItem::Tool(t) if !matches!(t.status, crate::messages::tools::ToolCallStatus::Running)
+        ));
+        assert_eq!(state.messages.len(), 4);
+        assert!(!state.is_streaming);
+    }
+}
@@ -6,6 +6,7 @@ use std::time::{Duration, Instant};
 use anyhow::{Result, anyhow};
 
 use crate::animation::Shimmer;
+use crate::i18n::t;
 use crate::state::{Screen, SetupState, SetupStep, TuiState};
 
 pub const PROVIDERS: [(&str, &str, &str); 2] = [
@@ -17,7 +18,8 @@ pub const PROVIDERS: [(&str, &str, &str); 2] = [
     ("zai", "ZAI", "GLM models (glm-5, glm-4-plus)"),
 ];
 
-const GRAPH_CHOICES: [&str; 2] = ["Configure now", "Skip for now"];
+/// Catalog keys for the LocusGraph choice rows.
+const GRAPH_CHOICES: [&str; 2] = ["setup.graph.configure_now", "setup.graph.skip_for_now"];
 const DONE_SHIMMER_DURATION: Duration = Duration::from_secs(2);
 
 pub fn provider_id_for_cursor(cursor: usize) -> &'static str {
@@ -45,7 +47,7 @@ pub fn provider_env_var(provider: &str) -> Result<&'static str> {
 }
 
 pub fn graph_choice_label(cursor: usize) -> &'static str {
-    GRAPH_CHOICES[cursor.min(GRAPH_CHOICES.len().saturating_sub(1))]
+    t(GRAPH_CHOICES[cursor.min(GRAPH_CHOICES.len().saturating_sub(1))])
 }
 
 pub fn setup_progress(step: SetupStep) -> usize {
@@ -62,19 +64,21 @@ pub fn setup_progress(step: SetupStep) -> usize {
     }
 }
 
-pub fn footer_hints(step: SetupStep) -> &'static [(&'static str, &'static str)] {
+pub fn footer_hints(step: SetupStep) -> Vec<(&'static str, &'static str)> {
+    let select = ("Up/Down", t("setup.hint.select"));
+    let confirm = ("Enter", t("setup.hint.confirm"));
+    let proceed = ("Enter", t("setup.hint.continue"));
+    let back = ("Esc", t("shortcut.back"));
     match step {
-        SetupStep::Welcome => &[("Enter", "begin")],
-        SetupStep::SelectProvider => &[("Up/Down", "select"), ("Enter", "confirm")],
-        SetupStep::EnterApiKey => &[("Enter", "continue"), ("Esc", "back")],
-        SetupStep::LocusGraphChoice => {
-            &[("Up/Down", "select"), ("Enter", "confirm"), ("Esc", "back")]
-        }
+        SetupStep::Welcome => vec![("Enter", t("setup.hint.begin"))],
+        SetupStep::SelectProvider => vec![select, confirm],
+        SetupStep::EnterApiKey => vec![proceed, back],
+        SetupStep::LocusGraphChoice => vec![select, confirm, back],
         SetupStep::LocusGraphUrl | SetupStep::LocusGraphSecret | SetupStep::LocusGraphId => {
-            &[("Enter", "continue"), ("Esc", "back")]
+            vec![proceed, back]
         }
-        SetupStep::Confirm => &[("Enter", "save & start"), ("Esc", "back")],
-        SetupStep::Done => &[("Enter", "start chatting")],
+        SetupStep::Confirm => vec![("Enter", t("setup.hint.save_and_start")), back],
+        SetupStep::Done => vec![("Enter", t("setup.hint.start_chatting"))],
     }
 }
 
@@ -115,7 +119,7 @@ pub fn handle_setup_enter(state: &mut TuiState) {
         }
         SetupStep::EnterApiKey => {
             if state.setup.api_key.trim().is_empty() {
-                state.setup.error_message = Some("Enter an API key to continue.".to_string());
+                state.setup.error_message = Some(t("setup.error.api_key").to_string());
             } else {
                 state.setup.step = SetupStep::LocusGraphChoice;
             }
@@ -130,23 +134,21 @@ pub fn handle_setup_enter(state: &mut TuiState) {
         }
         SetupStep::LocusGraphUrl => {
             if !is_valid_graph_url(&state.setup.graph_url) {
-                state.setup.error_message =
-                    Some("Enter a valid URL like https://grpc-dev.locusgraph.com:443".to_string());
+                state.setup.error_message = Some(t("setup.error.graph_url").to_string());
             } else {
                 state.setup.step = SetupStep::LocusGraphSecret;
             }
         }
         SetupStep::LocusGraphSecret => {
             if state.setup.graph_secret.trim().is_empty() {
-                state.setup.error_message =
-                    Some("Enter a LocusGraph secret or go back and skip it.".to_string());
+                state.setup.error_message = Some(t("setup.error.graph_secret").to_string());
             } else {
                 state.setup.step = SetupStep::LocusGraphId;
             }
         }
         SetupStep::LocusGraphId => {
             if state.setup.graph_id.trim().is_empty() {
-                state.setup.error_message = Some("Graph ID cannot be empty.".to_string());
+                state.setup.error_message = Some(t("setup.error.graph_id").to_string());
             } else {
                 state.setup.step = SetupStep::Confirm;
             }
@@ -156,7 +158,7 @@ pub fn handle_setup_enter(state: &mut TuiState) {
                 state.setup.step = SetupStep::Done;
                 state.setup.done_shimmer = Some(Shimmer::new());
                 state.setup.done_started_at = Some(Instant::now());
-                state.status = "Configuration saved to ~/.locus/locus.db".to_string();
+                state.status = t("setup.status.saved").to_string();
                 state.status_set_at = Some(Instant::now());
                 state.status_permanent = false;
             }
@@ -316,7 +318,7 @@ fn save_setup_config(setup: &SetupState) -> Result<()> {
     Ok(())
 }
 
-fn global_locus_dir() -> Result<PathBuf> {
+pub(crate) fn global_locus_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
     let locus_dir = home.join(".locus");
     std::fs::create_dir_all(&locus_dir)?;
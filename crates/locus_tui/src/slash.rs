@@ -0,0 +1,437 @@
+//! Commands in the chat input. `/name` runs a custom command from
+//! `.locus/commands/` and `/mcp.<server>.<prompt>` a prompt of an MCP server
+//! (both expanded by the runtime); the built-ins start with `:`. Typing `/` or `:` at the start of the prompt pops up every command
+//! that fuzzy-matches what follows, with its arguments and what it does; ↑↓
+//! pick one, Tab puts it in the prompt and Esc closes the popup.
+
+use locus_core::diff_review::REJECT_HUNK_COMMAND;
+use locus_core::memory_search::MEMORY_COMMAND;
+use locus_core::model::MODEL_COMMAND;
+use locus_core::pin::{PIN_COMMAND, UNPIN_COMMAND};
+use locus_core::plan::{EXECUTE_PLAN_COMMAND, PLAN_COMMAND};
+use locus_core::rewind::REWIND_COMMAND;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::cancel::CANCEL_COMMAND;
+use crate::export::EXPORT_COMMAND;
+use crate::i18n::{t, tf};
+use crate::layouts::{background_style, border_style, text_muted_style, text_style};
+use crate::state::TuiState;
+use crate::theme_picker::THEME_COMMAND;
+use crate::utils::{fuzzy_filter, truncate_ellipsis};
+
+/// Built-in commands: the command, its arguments and the key of its help text.
+pub const BUILTIN_COMMANDS: &[(&str, &str, &str)] = &[
+    (CANCEL_COMMAND, "", "slash.help.cancel"),
+    (REWIND_COMMAND, "N", "slash.help.rewind"),
+    (PIN_COMMAND, "[path]", "slash.help.pin"),
+    (UNPIN_COMMAND, "<path>", "slash.help.unpin"),
+    (MEMORY_COMMAND, "<query>", "slash.help.memory"),
+    (PLAN_COMMAND, "<task>", "slash.help.plan"),
+    (EXECUTE_PLAN_COMMAND, "[path]", "slash.help.execute_plan"),
+    (
+        REJECT_HUNK_COMMAND,
+        "<turn> <hunk> <path>",
+        "slash.help.reject_hunk",
+    ),
+    (EXPORT_COMMAND, "[md|html|json]", "slash.help.export"),
+    (MODEL_COMMAND, "[provider/model]", "slash.help.model"),
+    (THEME_COMMAND, "[name]", "slash.help.theme"),
+];
+
+/// Entries shown in the popup.
+const MAX_MATCHES: usize = 8;
+
+/// A command the popup offers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandEntry {
+    /// What goes in the prompt: `:name` for built-ins, `/name` for custom
+    /// commands and MCP prompts.
+    pub command: String,
+    /// Its arguments, e.g. `<path>`; empty when it takes none.
+    pub usage: String,
+    pub help: String,
+}
+
+/// The popup over the prompt: the commands matching what was typed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlashPopup {
+    pub query: String,
+    pub matches: Vec<CommandEntry>,
+    pub selected: usize,
+}
+
+/// The open popup, if any.
+#[derive(Debug, Clone, Default)]
+pub struct SlashMenu {
+    pub popup: Option<SlashPopup>,
+    /// Esc closed the popup; it stays closed until the prompt no longer
+    /// starts a command.
+    dismissed: bool,
+}
+
+impl SlashMenu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select an earlier (`delta < 0`) or later entry.
+    pub fn step(&mut self, delta: isize) {
+        if let Some(popup) = self.popup.as_mut() {
+            let last = popup.matches.len().saturating_sub(1);
+            popup.selected = popup.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Close the popup until the command is typed anew.
+    pub fn dismiss(&mut self) {
+        if self.popup.take().is_some() {
+            self.dismissed = true;
+        }
+    }
+}
+
+/// Every command offered: the built-ins, the project's custom commands, then
+/// the MCP servers' prompts.
+pub fn command_entries(
+    custom: &[locus_core::CustomCommand],
+    prompts: &[locus_core::McpPromptInfo],
+) -> Vec<CommandEntry> {
+    let builtins = BUILTIN_COMMANDS
+        .iter()
+        .map(|&(command, usage, help)| CommandEntry {
+            command: command.to_string(),
+            usage: usage.to_string(),
+            help: t(help).to_string(),
+        });
+    let custom = custom.iter().map(|c| CommandEntry {
+        command: format!("/{}", c.name),
+        usage: String::new(),
+        help: c.description.clone(),
+    });
+    let prompts = prompts.iter().map(|p| CommandEntry {
+        command: format!("/{}", p.name),
+        usage: p.usage.clone(),
+        help: p.description.clone(),
+    });
+    builtins.chain(custom).chain(prompts).collect()
+}
+
+/// The command name being typed: the input is `/` or `:` and a word with the
+/// cursor at its end. Returns the word after the sigil.
+pub fn command_word(input: &str, cursor: usize) -> Option<&str> {
+    let word = input
+        .strip_prefix('/')
+        .or_else(|| input.strip_prefix(':'))?;
+    (cursor == input.len() && !word.contains(char::is_whitespace)).then_some(word)
+}
+
+/// Open, refresh or close the popup after the prompt changed.
+pub fn update_slash_menu(state: &mut TuiState) {
+    let Some(query) = command_word(&state.input_buffer, state.input_cursor) else {
+        state.slash_menu.popup = None;
+        state.slash_menu.dismissed = false;
+        return;
+    };
+    if state.slash_menu.dismissed {
+        return;
+    }
+    if let Some(popup) = &state.slash_menu.popup
+        && popup.query == query
+    {
+        return;
+    }
+    let entries = command_entries(&state.custom_commands, &state.mcp_prompts);
+    // Matched on the name alone, so `/` finds built-ins and `:` custom commands too
+    let names: Vec<&str> = entries.iter().map(|e| &e.command[1..]).collect();
+    let matches: Vec<CommandEntry> = fuzzy_filter(names.iter().copied(), query, MAX_MATCHES)
+        .into_iter()
+        .filter_map(|name| {
+            let at = names.iter().position(|n| std::ptr::eq(*n, name))?;
+            Some(entries[at].clone())
+        })
+        .collect();
+    state.slash_menu.popup = (!matches.is_empty()).then(|| SlashPopup {
+        query: query.to_string(),
+        matches,
+        selected: 0,
+    });
+}
+
+/// Tab with the popup open: put the selected command in the prompt, with a
+/// space when it takes arguments. Returns false when no popup is open.
+pub fn complete_from_menu(state: &mut TuiState) -> bool {
+    let Some(popup) = state.slash_menu.popup.take() else {
+        return false;
+    };
+    let entry = &popup.matches[popup.selected];
+    state.input_buffer = if entry.usage.is_empty() && entry.command.starts_with(':') {
+        entry.command.clone()
+    } else {
+        format!("{} ", entry.command)
+    };
+    state.input_cursor = state.input_buffer.len();
+    state.slash_menu.dismissed = true;
+    state.needs_redraw = true;
+    true
+}
+
+/// Complete the `/name` being typed to the custom commands and MCP prompts it
+/// starts: the whole name when one matches, else as far as they agree, listing
+/// them in the status line. Returns false when the input is not a `/name` prefix.
+pub fn complete_slash_command(state: &mut TuiState) -> bool {
+    let input = &state.input_buffer;
+    let Some(prefix) = input.strip_prefix('/') else {
+        return false;
+    };
+    if prefix.contains(char::is_whitespace) || state.input_cursor != input.len() {
+        return false;
+    }
+    let custom = state
+        .custom_commands
+        .iter()
+        .map(|c| (c.name.as_str(), c.description.as_str()));
+    let prompts = state
+        .mcp_prompts
+        .iter()
+        .map(|p| (p.name.as_str(), p.description.as_str()));
+    let matches: Vec<(&str, &str)> = custom
+        .chain(prompts)
+        .filter(|(name, _)| name.starts_with(prefix))
+        .collect();
+
+    let (completed, status) = match matches.as_slice() {
+        [] => (None, tf("status.slash_no_match", &[("prefix", &prefix)])),
+        [(name, description)] => (
+            Some(format!("/{} ", name)),
+            format!("/{} — {}", name, description),
+        ),
+        _ => {
+            let common = matches
+                .iter()
+                .skip(1)
+                .fold(matches[0].0, |common, (name, _)| {
+                    let len = common
+                        .char_indices()
+                        .zip(name.chars())
+                        .take_while(|((_, a), b)| a == b)
+                        .last()
+                        .map_or(0, |((i, a), _)| i + a.len_utf8());
+                    &common[..len]
+                });
+            let listed: Vec<String> = matches
+                .iter()
+                .map(|(name, description)| format!("/{} — {}", name, description))
+                .collect();
+            (Some(format!("/{}", common)), listed.join(" · "))
+        }
+    };
+    if let Some(completed) = completed {
+        state.input_cursor = completed.len();
+        state.input_buffer = completed;
+    }
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+    true
+}
+
+/// Draw the popup just above the prompt, `footer_height` rows from the bottom:
+/// each command with its arguments, and what it does.
+pub fn draw_slash_menu(frame: &mut Frame, state: &TuiState, area: Rect, footer_height: u16) {
+    let Some(popup) = state.slash_menu.popup.as_ref() else {
+        return;
+    };
+    let palette = &state.palette;
+    let width = area.width.saturating_sub(4).clamp(1, 80);
+    let label = |entry: &CommandEntry| {
+        if entry.usage.is_empty() {
+            entry.command.clone()
+        } else {
+            format!("{} {}", entry.command, entry.usage)
+        }
+    };
+    let label_width = popup
+        .matches
+        .iter()
+        .map(|e| label(e).chars().count())
+        .max()
+        .unwrap_or(0);
+    let help_width = (width as usize).saturating_sub(label_width + 8);
+    let lines: Vec<Line<'static>> = popup
+        .matches
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let selected = i == popup.selected;
+            let style = if selected {
+                text_style(palette.accent)
+            } else {
+                text_style(palette.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { "› " } else { "  " }.to_string(), style),
+                Span::styled(format!("{:<label_width$}  ", label(entry)), style),
+                Span::styled(
+                    truncate_ellipsis(&entry.help, help_width),
+                    text_muted_style(palette.text_muted),
+                ),
+            ])
+        })
+        .collect();
+
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(footer_height));
+    let rect = Rect {
+        x: area.x + 2,
+        y: (area.y + area.height)
+            .saturating_sub(footer_height)
+            .saturating_sub(height),
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("slash.title")))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {} ", t("slash.hint")),
+            text_muted_style(palette.text_muted),
+        )))
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::CustomCommand;
+
+    fn command(name: &str) -> CustomCommand {
+        CustomCommand {
+            name: name.to_string(),
+            description: format!("{} things", name),
+            template: String::new(),
+        }
+    }
+
+    fn typed(state: &mut TuiState, text: &str) {
+        state.input_buffer = text.to_string();
+        state.input_cursor = text.len();
+    }
+
+    #[test]
+    fn tab_completes_custom_command_names() {
+        let mut state = TuiState::new();
+        state.custom_commands = vec![command("review"), command("release"), command("test")];
+
+        typed(&mut state, "/t");
+        assert!(complete_slash_command(&mut state));
+        assert_eq!(state.input_buffer, "/test ");
+        assert_eq!(state.input_cursor, 6);
+
+        typed(&mut state, "/re");
+        assert!(complete_slash_command(&mut state));
+        assert_eq!(state.input_buffer, "/re");
+        assert!(state.status.contains("/review — review things"));
+        assert!(state.status.contains("/release"));
+
+        typed(&mut state, "/rev");
+        complete_slash_command(&mut state);
+        assert_eq!(state.input_buffer, "/review ");
+
+        typed(&mut state, "/deploy");
+        assert!(complete_slash_command(&mut state));
+        assert_eq!(state.input_buffer, "/deploy");
+        typed(&mut state, "/review src");
+        assert!(!complete_slash_command(&mut state));
+        typed(&mut state, "fix it");
+        assert!(!complete_slash_command(&mut state));
+    }
+
+    #[test]
+    fn popup_lists_built_in_and_custom_commands() {
+        let mut state = TuiState::new();
+        state.custom_commands = vec![command("review"), command("release")];
+
+        typed(&mut state, "/");
+        update_slash_menu(&mut state);
+        let popup = state.slash_menu.popup.as_ref().unwrap();
+        assert_eq!(popup.matches.len(), MAX_MATCHES);
+        assert_eq!(popup.matches[0].command, CANCEL_COMMAND);
+
+        typed(&mut state, "/re");
+        update_slash_menu(&mut state);
+        let commands: Vec<String> = state
+            .slash_menu
+            .popup
+            .as_ref()
+            .unwrap()
+            .matches
+            .iter()
+            .map(|e| e.command.clone())
+            .collect();
+        assert!(commands.contains(&"/review".to_string()));
+        assert!(commands.contains(&REWIND_COMMAND.to_string()));
+
+        state.slash_menu.step(1);
+        assert!(complete_from_menu(&mut state));
+        assert_eq!(state.input_buffer, format!("{} ", commands[1]));
+        assert!(state.slash_menu.popup.is_none());
+
+        typed(&mut state, ":canc");
+        update_slash_menu(&mut state);
+        let popup = state.slash_menu.popup.as_ref().unwrap();
+        assert_eq!(popup.matches[0].help, t("slash.help.cancel"));
+        complete_from_menu(&mut state);
+        assert_eq!(state.input_buffer, ":cancel");
+        update_slash_menu(&mut state);
+        assert!(state.slash_menu.popup.is_none());
+
+        typed(&mut state, "");
+        update_slash_menu(&mut state);
+        typed(&mut state, "/r");
+        update_slash_menu(&mut state);
+        assert!(state.slash_menu.popup.is_some());
+        state.slash_menu.dismiss();
+        typed(&mut state, "/re");
+        update_slash_menu(&mut state);
+        assert!(state.slash_menu.popup.is_none());
+        typed(&mut state, "fix it");
+        update_slash_menu(&mut state);
+        typed(&mut state, "/re");
+        update_slash_menu(&mut state);
+        assert!(state.slash_menu.popup.is_some());
+    }
+
+    #[test]
+    fn popup_and_tab_offer_mcp_prompts() {
+        let mut state = TuiState::new();
+        state.mcp_prompts = vec![locus_core::McpPromptInfo {
+            name: "mcp.docs.review".to_string(),
+            usage: "<path> [focus]".to_string(),
+            description: "Review a file".to_string(),
+        }];
+
+        typed(&mut state, "/docsrev");
+        update_slash_menu(&mut state);
+        let popup = state.slash_menu.popup.as_ref().unwrap();
+        assert_eq!(popup.matches[0].command, "/mcp.docs.review");
+        assert_eq!(popup.matches[0].usage, "<path> [focus]");
+        assert_eq!(popup.matches[0].help, "Review a file");
+        complete_from_menu(&mut state);
+        assert_eq!(state.input_buffer, "/mcp.docs.review ");
+
+        typed(&mut state, "/mcp.d");
+        assert!(complete_slash_command(&mut state));
+        assert_eq!(state.input_buffer, "/mcp.docs.review ");
+    }
+}
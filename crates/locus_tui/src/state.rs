@@ -3,21 +3,31 @@
 //! [TuiState] holds everything the view needs to render. [ChatItem] wraps
 //! message types from [crate::messages] so we can store a single list.
 
+use std::collections::VecDeque;
 use std::time::Instant;
 
+use locus_core::db::SimilarWork;
+use locus_core::{
+    ApprovalDecision, ApprovalRequest, ApprovalResponse, ConflictDecision, ConflictRequest,
+    ConflictResponse, ProviderCall, ToolAnnotation,
+};
+
 use crate::animation::Shimmer;
+use crate::i18n::tf;
 use crate::messages::{
     ai_message::AiMessage,
     ai_think_message::AiThinkMessage,
     error::ErrorMessage,
+    insights::ProjectInsightsMessage,
     memory::MemoryMessage,
     meta_tools::MetaToolMessage,
     tools::{EditDiff, EditDiffMessage, ToolCallMessage, ToolCallStatus},
     user::UserMessage,
 };
 use crate::theme::{Appearance, LocusPalette};
+use crate::transcript::TranscriptMirror;
 
-/// Which screen is currently shown (main chat, onboarding, debug traces, web automation).
+/// Which screen is currently shown (main chat, onboarding, debug traces, provider calls, web automation, task board, plan, memory trace, diff review).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
     Main,
@@ -26,7 +36,19 @@ pub enum Screen {
     /// Interactive first-run setup wizard.
     Setup,
     DebugTraces,
+    /// Recent provider requests and raw stream events (needs `LOCUS_INSPECT_CALLS`).
+    ProviderCalls,
     WebAutomation,
+    /// Kanban board of the task_list plans updated this session.
+    TaskBoard,
+    /// The plan from the last `:plan` run, with an action to carry it out.
+    Plan,
+    /// Memory searches (`:memory <query>`) and what they found.
+    MemoryTrace,
+    /// The files each turn changed, as hunks to approve or reject.
+    DiffReview,
+    /// Sub-agents started by the `task` tool, and their transcripts.
+    Agents,
 }
 
 /// Which step of the interactive setup wizard is active.
@@ -79,10 +101,14 @@ impl Default for SetupState {
     }
 }
 
-/// Max trace lines to keep (older lines dropped).
-const MAX_TRACE_LINES: usize = 2000;
+/// Finished tool result the user is attaching a note to (Ctrl+A).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationTarget {
+    pub tool_use_id: String,
+    pub tool_name: String,
+}
 
-/// One item in the chat: user, assistant, thinking, tool, tool group, edit-diff block, meta-tool, memory, or error.
+/// One item in the chat: user, assistant, thinking, tool, tool group, edit-diff block, meta-tool, memory, project insights, or error.
 #[derive(Debug, Clone)]
 pub enum ChatItem {
     User(UserMessage),
@@ -94,6 +120,8 @@ pub enum ChatItem {
     EditDiff(EditDiffMessage),
     MetaTool(MetaToolMessage),
     Memory(MemoryMessage),
+    /// What LocusGraph remembers about the project; first item of its session.
+    Insights(ProjectInsightsMessage),
     Error(ErrorMessage),
     Separator(String),
 }
@@ -113,6 +141,9 @@ pub struct TuiState {
     pub auto_scroll: bool,
     /// Theme palette (dark/light).
     pub palette: LocusPalette,
+    /// Switch between the light and dark palette with the terminal's background
+    /// (checked when the terminal regains focus); off once a theme is chosen.
+    pub follow_terminal: bool,
     /// Optional status text for header right side.
     pub status: String,
     /// Accumulated assistant text for current turn (pushed on TurnEnd).
@@ -141,10 +172,26 @@ pub struct TuiState {
     pub tool_shimmer: Option<crate::animation::Shimmer>,
     /// Current screen (main chat or debug traces).
     pub screen: Screen,
-    /// Debug trace lines (session events, etc.). Newest at end.
-    pub trace_lines: Vec<String>,
-    /// Scroll offset for debug trace view (lines scrolled up).
-    pub trace_scroll: usize,
+    /// Logs screen state: the shared log buffer, filters and scroll.
+    pub logs: crate::logs::LogsState,
+    /// Prompts sent, for Up/Down recall and Ctrl+R search.
+    pub history: crate::history::PromptHistory,
+    /// `@` / path completion popup over the prompt.
+    pub file_finder: crate::file_finder::FileFinder,
+    /// Model, context window, tokens and cost of the session, for the status bar.
+    pub usage: crate::status_bar::SessionUsage,
+    /// Branch and dirty state of the project's work tree, for the status bar.
+    pub git: Option<crate::status_bar::GitStatus>,
+    /// Chat item selected for copying (Alt+V), an index into messages.
+    pub chat_selection: Option<usize>,
+    /// Lines of the selected item in the chat, set when drawn.
+    pub selection_lines: Option<(usize, usize)>,
+    /// Outputs of the tools run, for copying them.
+    pub tool_outputs: crate::clipboard::ToolOutputs,
+    /// Large pastes behind their `[pasted …]` placeholders in the prompt.
+    pub pasted: Vec<crate::paste::PastedBlock>,
+    /// Command popup opened by `/` or `:` at the start of the prompt.
+    pub slash_menu: crate::slash::SlashMenu,
     /// First-run interactive setup wizard state.
     pub setup: SetupState,
     /// Web automation state.
@@ -153,6 +200,52 @@ pub struct TuiState {
     pub diff_page_message_index: Option<usize>,
     /// Line offset for the paged diff block (0, 12, 24, ...).
     pub diff_page_offset: usize,
+    /// When set, Enter sends the input as a note on this tool result instead of a new prompt.
+    pub annotation_target: Option<AnnotationTarget>,
+    /// Recorded provider calls for the inspection screen (Ctrl+P). Newest at end.
+    pub provider_calls: VecDeque<ProviderCall>,
+    /// Index into provider_calls of the call being inspected.
+    pub provider_call_index: usize,
+    /// Scroll offset for the inspection view (lines from top).
+    pub provider_call_scroll: usize,
+    /// Tool calls waiting for approval; the front one is shown in the confirmation dialog.
+    pub pending_approvals: VecDeque<ApprovalRequest>,
+    /// The input line is a message to send with a denial (`m` in the approval
+    /// dialog); Enter denies the shown call with it.
+    pub approval_denying: bool,
+    /// Edits that met the user's changes; the front one is shown in the conflict
+    /// dialog once no approval is waiting.
+    pub pending_conflicts: VecDeque<ConflictRequest>,
+    /// Plain-text transcript mirror (`.locus/live-transcript.txt`), when enabled.
+    pub transcript: Option<TranscriptMirror>,
+    /// Latest past session flagged as similar work; Ctrl+O shows its summary.
+    pub similar_work: Option<SimilarWork>,
+    /// Task plans reported by the runtime, for the task board screen (Ctrl+B).
+    pub task_board: crate::task_board::TaskBoardState,
+    /// Memory searches, for the memory trace screen (Ctrl+G).
+    pub memory_trace: crate::memory_trace::MemoryTraceState,
+    /// Per-turn diffs, for the diff review screen (Ctrl+F).
+    pub diff_review: crate::diff_review::DiffReviewState,
+    /// Sub-agents and their transcripts, for the agents screen (Ctrl+T).
+    pub agents: crate::agents::AgentsState,
+    /// Saved-session switcher (Ctrl+S), drawn over the chat while open.
+    pub session_picker: Option<crate::session_picker::SessionPicker>,
+    /// Model picker (`:model`), drawn over the chat while open.
+    pub model_picker: Option<crate::model_picker::ModelPicker>,
+    /// Theme gallery (`:theme`), drawn over the chat while open.
+    pub theme_picker: Option<crate::theme_picker::ThemePicker>,
+    /// Command palette (Ctrl+X), drawn over any screen while open.
+    pub command_palette: Option<crate::command_palette::CommandPalette>,
+    /// Palette actions run this session, most recent first.
+    pub recent_actions: Vec<&'static str>,
+    /// Plan from the last `:plan` run, for the plan screen (Ctrl+L).
+    pub plan: Option<locus_core::Plan>,
+    /// Lines scrolled down on the plan screen.
+    pub plan_scroll: usize,
+    /// `.locus/commands/*.md` templates, for the `/` command popup and Tab.
+    pub custom_commands: Vec<locus_core::CustomCommand>,
+    /// Prompts of the runtime's MCP servers, run as `/mcp.<server>.<prompt>`.
+    pub mcp_prompts: Vec<locus_core::McpPromptInfo>,
 }
 
 impl Default for TuiState {
@@ -164,6 +257,7 @@ impl Default for TuiState {
             scroll: 0,
             auto_scroll: true,
             palette: LocusPalette::locus_dark(),
+            follow_terminal: false,
             status: String::new(),
             current_ai_text: String::new(),
             current_think_text: String::new(),
@@ -178,12 +272,42 @@ impl Default for TuiState {
             status_permanent: false,
             tool_shimmer: None,
             screen: Screen::Main,
-            trace_lines: Vec::new(),
-            trace_scroll: 0,
+            logs: crate::logs::LogsState::new(),
+            history: crate::history::PromptHistory::new(),
+            file_finder: crate::file_finder::FileFinder::new(),
+            usage: crate::status_bar::SessionUsage::default(),
+            git: None,
+            chat_selection: None,
+            selection_lines: None,
+            tool_outputs: crate::clipboard::ToolOutputs::new(),
+            pasted: Vec::new(),
+            slash_menu: crate::slash::SlashMenu::new(),
             setup: SetupState::default(),
             web_automation: crate::web_automation::WebAutomationState::new(),
             diff_page_message_index: None,
             diff_page_offset: 0,
+            annotation_target: None,
+            provider_calls: VecDeque::new(),
+            provider_call_index: 0,
+            provider_call_scroll: 0,
+            pending_approvals: VecDeque::new(),
+            approval_denying: false,
+            pending_conflicts: VecDeque::new(),
+            transcript: None,
+            similar_work: None,
+            task_board: crate::task_board::TaskBoardState::new(),
+            memory_trace: crate::memory_trace::MemoryTraceState::new(),
+            diff_review: crate::diff_review::DiffReviewState::new(),
+            agents: crate::agents::AgentsState::new(),
+            session_picker: None,
+            model_picker: None,
+            theme_picker: None,
+            command_palette: None,
+            recent_actions: Vec::new(),
+            plan: None,
+            plan_scroll: 0,
+            custom_commands: Vec::new(),
+            mcp_prompts: Vec::new(),
         }
     }
 }
@@ -286,6 +410,46 @@ impl TuiState {
         }
     }
 
+    /// The tool with `tool_use_id`, alone or in a group; the latest if repeated.
+    fn tool_by_id_mut(&mut self, tool_use_id: &str) -> Option<&mut ToolCallMessage> {
+        self.messages.iter_mut().rev().find_map(|item| match item {
+            ChatItem::Tool(t) if t.id.as_deref() == Some(tool_use_id) => Some(t),
+            ChatItem::ToolGroup(group) => group
+                .iter_mut()
+                .find(|t| t.id.as_deref() == Some(tool_use_id)),
+            _ => None,
+        })
+    }
+
+    /// Show `message` under the running tool with `tool_use_id` (ToolProgress).
+    pub fn set_tool_progress(&mut self, tool_use_id: &str, message: String) -> bool {
+        match self.tool_by_id_mut(tool_use_id) {
+            Some(t) if matches!(t.status, ToolCallStatus::Running) => {
+                t.progress = Some(message);
+                self.cache_dirty = true;
+                self.needs_redraw = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark the tool with `tool_use_id` failed with `message` (ToolTimedOut).
+    pub fn fail_tool_by_id(&mut self, tool_use_id: &str, message: String) -> bool {
+        let Some(t) = self.tool_by_id_mut(tool_use_id) else {
+            return false;
+        };
+        *t = ToolCallMessage::error(
+            t.id.clone(),
+            t.tool_name.clone(),
+            message,
+            t.summary.clone(),
+        );
+        self.cache_dirty = true;
+        self.needs_redraw = true;
+        true
+    }
+
     /// Find a tool by id and update it (for ToolDone matching by tool_use_id).
     pub fn update_tool_by_id(
         &mut self,
@@ -369,6 +533,155 @@ impl TuiState {
         }
     }
 
+    /// Most recent tool call that has finished and carries a tool_use_id.
+    pub fn last_finished_tool(&self) -> Option<&ToolCallMessage> {
+        self.messages.iter().rev().find_map(|item| match item {
+            ChatItem::Tool(t) => Some(t).filter(|t| is_annotatable(t)),
+            ChatItem::ToolGroup(group) => group.iter().rev().find(|t| is_annotatable(t)),
+            _ => None,
+        })
+    }
+
+    /// Start annotating the last finished tool result (Ctrl+A). Returns false if there is none.
+    pub fn begin_annotation(&mut self) -> bool {
+        let target = self.last_finished_tool().map(|t| AnnotationTarget {
+            tool_use_id: t.id.clone().unwrap_or_default(),
+            tool_name: t.tool_name.clone(),
+        });
+        self.annotation_target = target;
+        self.needs_redraw = true;
+        self.annotation_target.is_some()
+    }
+
+    /// Leave annotation mode without sending (Esc).
+    pub fn cancel_annotation(&mut self) {
+        self.annotation_target = None;
+        self.needs_redraw = true;
+    }
+
+    /// Submit the input as a note on the annotation target: echoes it in the chat and
+    /// returns the annotation to send to the runtime. None if not annotating or input is empty.
+    pub fn take_annotation(&mut self) -> Option<ToolAnnotation> {
+        let target = self.annotation_target.take()?;
+        self.needs_redraw = true;
+        let line = self.input_take();
+        let note = line.trim();
+        if note.is_empty() {
+            return None;
+        }
+        if let Some(mirror) = self.transcript.as_mut() {
+            mirror.record_note(&target.tool_name, note);
+        }
+        self.push_user(
+            tf(
+                "status.note_on",
+                &[
+                    (
+                        "tool",
+                        &crate::messages::tools::format_tool_name(&target.tool_name),
+                    ),
+                    ("note", &note),
+                ],
+            ),
+            None,
+        );
+        Some(ToolAnnotation::new(
+            target.tool_use_id,
+            target.tool_name,
+            note,
+        ))
+    }
+
+    /// Queue a tool call for the confirmation dialog.
+    pub fn push_approval(&mut self, request: ApprovalRequest) {
+        self.pending_approvals.push_back(request);
+        self.needs_redraw = true;
+    }
+
+    /// Answer the approval shown in the dialog (y/a/n) and return the response
+    /// for the runtime. None if nothing is waiting.
+    pub fn answer_approval(&mut self, decision: ApprovalDecision) -> Option<ApprovalResponse> {
+        self.finish_approval(decision, None)
+    }
+
+    /// Start typing a message for the model to go with denying the shown call.
+    pub fn begin_deny_message(&mut self) {
+        self.approval_denying = !self.pending_approvals.is_empty();
+        self.needs_redraw = true;
+    }
+
+    /// Back to the approval keys without denying (Esc); the typed text stays.
+    pub fn cancel_deny_message(&mut self) {
+        self.approval_denying = false;
+        self.needs_redraw = true;
+    }
+
+    /// Deny the shown call with the input line as the message for the model.
+    pub fn deny_with_message(&mut self) -> Option<ApprovalResponse> {
+        let message = self.input_take();
+        let message = message.trim();
+        self.finish_approval(
+            ApprovalDecision::Deny,
+            (!message.is_empty()).then(|| message.to_string()),
+        )
+    }
+
+    fn finish_approval(
+        &mut self,
+        decision: ApprovalDecision,
+        message: Option<String>,
+    ) -> Option<ApprovalResponse> {
+        self.approval_denying = false;
+        let request = self.pending_approvals.pop_front()?;
+        self.needs_redraw = true;
+        let key = match decision {
+            ApprovalDecision::Allow => "status.approval.allowed",
+            ApprovalDecision::AllowAlways => "status.approval.always",
+            ApprovalDecision::Deny => "status.approval.denied",
+        };
+        self.status = tf(
+            key,
+            &[(
+                "tool",
+                &crate::messages::tools::format_tool_name(&request.tool_name),
+            )],
+        );
+        self.status_set_at = Some(Instant::now());
+        self.status_permanent = false;
+        if let Some(mirror) = self.transcript.as_mut() {
+            mirror.record_approval(&request, decision, message.as_deref());
+        }
+        Some(ApprovalResponse {
+            message,
+            ..ApprovalResponse::new(request.id, decision)
+        })
+    }
+
+    /// Queue an edit conflict for the conflict dialog.
+    pub fn push_conflict(&mut self, request: ConflictRequest) {
+        self.pending_conflicts.push_back(request);
+        self.needs_redraw = true;
+    }
+
+    /// Answer the conflict shown in the dialog (r/o/x) and return the response
+    /// for the runtime. None if nothing is waiting.
+    pub fn answer_conflict(&mut self, decision: ConflictDecision) -> Option<ConflictResponse> {
+        let request = self.pending_conflicts.pop_front()?;
+        self.needs_redraw = true;
+        let key = match decision {
+            ConflictDecision::Rebase => "status.conflict.rebased",
+            ConflictDecision::Overwrite => "status.conflict.overwritten",
+            ConflictDecision::Abort => "status.conflict.aborted",
+        };
+        self.status = tf(key, &[("path", &request.path)]);
+        self.status_set_at = Some(Instant::now());
+        self.status_permanent = false;
+        if let Some(mirror) = self.transcript.as_mut() {
+            mirror.record_conflict(&request, decision);
+        }
+        Some(ConflictResponse::new(request.id, decision))
+    }
+
     /// Push a meta-tool message.
     pub fn push_meta_tool(&mut self, msg: MetaToolMessage) {
         self.messages.push(ChatItem::MetaTool(msg));
@@ -400,6 +713,42 @@ impl TuiState {
         }
     }
 
+    /// Put the project insights first in the current session (after the last
+    /// separator), replacing any the session already shows.
+    pub fn push_project_insights(&mut self, msg: ProjectInsightsMessage) {
+        let start = self
+            .messages
+            .iter()
+            .rposition(|item| matches!(item, ChatItem::Separator(_)))
+            .map_or(0, |i| i + 1);
+        match self.messages[start..]
+            .iter()
+            .position(|item| matches!(item, ChatItem::Insights(_)))
+        {
+            Some(i) => self.messages[start + i] = ChatItem::Insights(msg),
+            None => self.messages.insert(start, ChatItem::Insights(msg)),
+        }
+        self.cache_dirty = true;
+        self.needs_redraw = true;
+    }
+
+    /// Collapse or expand the current session's project insights (key `i`).
+    /// Returns false when there are none.
+    pub fn toggle_project_insights(&mut self) -> bool {
+        let Some(ChatItem::Insights(msg)) = self
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|item| matches!(item, ChatItem::Insights(_)))
+        else {
+            return false;
+        };
+        msg.collapsed = !msg.collapsed;
+        self.cache_dirty = true;
+        self.needs_redraw = true;
+        true
+    }
+
     /// Push a session separator (e.g. "New session").
     pub fn push_separator(&mut self, label: String) {
         self.messages.push(ChatItem::Separator(label));
@@ -523,25 +872,49 @@ impl TuiState {
         self.needs_redraw = true;
     }
 
-    /// Append a line to the debug trace buffer (for Ctrl+D debug screen). Drops oldest if over capacity.
+    /// Append a line to the log buffer (for the Ctrl+D logs screen). Drops oldest if over capacity.
     pub fn push_trace_line(&mut self, line: String) {
-        self.trace_lines.push(line);
-        if self.trace_lines.len() > MAX_TRACE_LINES {
-            self.trace_lines
-                .drain(0..self.trace_lines.len() - MAX_TRACE_LINES);
+        self.logs.push_line(&line);
+        self.logs.sync();
+        self.needs_redraw = true;
+    }
+
+    /// Record a provider call, keeping at most `keep`. Follows the newest call unless
+    /// the user is looking at an older one.
+    pub fn push_provider_call(&mut self, call: ProviderCall, keep: usize) {
+        let following = self.provider_call_index + 1 >= self.provider_calls.len();
+        self.provider_calls.push_back(call);
+        while self.provider_calls.len() > keep.max(1) {
+            self.provider_calls.pop_front();
+            self.provider_call_index = self.provider_call_index.saturating_sub(1);
+        }
+        if following {
+            self.provider_call_index = self.provider_calls.len() - 1;
+            self.provider_call_scroll = 0;
         }
         self.needs_redraw = true;
     }
 
-    /// Scroll the trace view up.
-    pub fn trace_scroll_up(&mut self, delta: usize) {
-        self.trace_scroll = self.trace_scroll.saturating_add(delta);
+    /// Step to an older (`delta < 0`) or newer provider call in the inspection view.
+    pub fn provider_call_step(&mut self, delta: isize) {
+        let last = self.provider_calls.len().saturating_sub(1);
+        self.provider_call_index = self
+            .provider_call_index
+            .saturating_add_signed(delta)
+            .min(last);
+        self.provider_call_scroll = 0;
         self.needs_redraw = true;
     }
 
-    /// Scroll the trace view down.
-    pub fn trace_scroll_down(&mut self, delta: usize) {
-        self.trace_scroll = self.trace_scroll.saturating_sub(delta);
+    /// Scroll the inspection view up.
+    pub fn provider_call_scroll_up(&mut self, delta: usize) {
+        self.provider_call_scroll = self.provider_call_scroll.saturating_sub(delta);
+        self.needs_redraw = true;
+    }
+
+    /// Scroll the inspection view down.
+    pub fn provider_call_scroll_down(&mut self, delta: usize) {
+        self.provider_call_scroll = self.provider_call_scroll.saturating_add(delta);
         self.needs_redraw = true;
     }
 
@@ -562,6 +935,10 @@ impl TuiState {
     }
 }
 
+fn is_annotatable(tool: &ToolCallMessage) -> bool {
+    tool.id.is_some() && matches!(tool.status, ToolCallStatus::Done { .. })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -628,6 +1005,25 @@ mod tests {
         assert!(matches!(s.messages[1], ChatItem::Ai(_)));
     }
 
+    #[test]
+    fn project_insights_lead_the_current_session() {
+        let mut s = TuiState::new();
+        s.push_ai("old session".into(), None);
+        s.push_separator("New session".into());
+        s.push_user("fix the tests".into(), None);
+        s.push_project_insights(ProjectInsightsMessage::new("tests need a DB", "", 0.5));
+        assert!(matches!(s.messages[2], ChatItem::Insights(_)));
+        assert!(matches!(s.messages[3], ChatItem::User(_)));
+
+        s.push_project_insights(ProjectInsightsMessage::new("tests need Postgres", "", 0.6));
+        assert_eq!(s.messages.len(), 4);
+        assert!(s.toggle_project_insights());
+        assert!(matches!(
+            &s.messages[2],
+            ChatItem::Insights(m) if m.collapsed && m.insight == "tests need Postgres"
+        ));
+    }
+
     #[test]
     fn scroll_up_disables_auto_scroll() {
         let mut s = TuiState::new();
@@ -817,7 +1213,7 @@ mod tests {
         for i in 0..2500 {
             s.push_trace_line(format!("line {}", i));
         }
-        assert!(s.trace_lines.len() <= 2000);
+        assert!(s.logs.buffer.len() <= 2000);
     }
 
     #[test]
@@ -875,6 +1271,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_tool_progress_only_updates_running_tools() {
+        let mut s = TuiState::new();
+        s.push_tool_grouped(ToolCallMessage::running("t1", "bash", None));
+        s.push_tool_grouped(ToolCallMessage::running("t2", "web_automation", None));
+        assert!(s.set_tool_progress("t2", "Navigating to example.com".into()));
+        assert!(!s.set_tool_progress("t3", "unknown".into()));
+        if let ChatItem::ToolGroup(g) = &s.messages[0] {
+            assert_eq!(g[1].progress.as_deref(), Some("Navigating to example.com"));
+        } else {
+            panic!("expected ToolGroup");
+        }
+        s.update_tool_by_id("t2", 10, true, None);
+        assert!(!s.set_tool_progress("t2", "late".into()));
+    }
+
+    #[test]
+    fn fail_tool_by_id_shows_the_message() {
+        use crate::messages::tools::ToolCallStatus;
+        let mut s = TuiState::new();
+        s.push_tool_grouped(ToolCallMessage::running("t1", "bash", Some("sleep".into())));
+        s.update_tool_by_id("t1", 1000, false, None);
+        assert!(s.fail_tool_by_id("t1", "timed out after 1s".into()));
+        assert!(matches!(
+            &s.messages[0],
+            ChatItem::Tool(t) if t.summary.as_deref() == Some("sleep")
+                && matches!(&t.status, ToolCallStatus::Error { message } if message == "timed out after 1s")
+        ));
+        assert!(!s.fail_tool_by_id("t2", "missing".into()));
+    }
+
     #[test]
     fn non_consecutive_tools_are_separate() {
         let mut s = TuiState::new();
@@ -893,6 +1320,122 @@ mod tests {
         assert!(s.has_running_tools());
     }
 
+    #[test]
+    fn begin_annotation_targets_last_finished_tool() {
+        let mut s = TuiState::new();
+        assert!(!s.begin_annotation());
+        s.push_tool_grouped(ToolCallMessage::running("t1", "grep", None));
+        s.push_tool_grouped(ToolCallMessage::running("t2", "bash", None));
+        s.update_tool_by_id("t1", 10, true, None);
+        assert!(s.begin_annotation());
+        assert_eq!(
+            s.annotation_target,
+            Some(AnnotationTarget {
+                tool_use_id: "t1".to_string(),
+                tool_name: "grep".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn take_annotation_echoes_note_and_clears_mode() {
+        let mut s = TuiState::new();
+        s.push_tool(ToolCallMessage::running("t1", "grep", None));
+        s.update_tool_by_id("t1", 10, true, None);
+        s.begin_annotation();
+        s.input_buffer = " missed the generated dir ".to_string();
+        let annotation = s.take_annotation().unwrap();
+        assert_eq!(annotation.tool_use_id, "t1");
+        assert_eq!(annotation.note, "missed the generated dir");
+        assert!(s.annotation_target.is_none());
+        assert!(s.input_buffer.is_empty());
+        assert!(matches!(s.messages.last(), Some(ChatItem::User(u)) if u.text.contains("Grep")));
+    }
+
+    #[test]
+    fn take_annotation_empty_input_cancels() {
+        let mut s = TuiState::new();
+        s.push_tool(ToolCallMessage::running("t1", "grep", None));
+        s.update_tool_by_id("t1", 10, true, None);
+        s.begin_annotation();
+        assert!(s.take_annotation().is_none());
+        assert!(s.annotation_target.is_none());
+        assert_eq!(s.messages.len(), 1);
+    }
+
+    #[test]
+    fn answer_approval_pops_in_order() {
+        let mut s = TuiState::new();
+        assert!(s.answer_approval(ApprovalDecision::Allow).is_none());
+        let first = ApprovalRequest::new(
+            "bash",
+            locus_core::Permission::Execute,
+            serde_json::json!({"command": "ls"}),
+        );
+        let first_id = first.id.clone();
+        s.push_approval(first);
+        s.push_approval(ApprovalRequest::new(
+            "web_automation",
+            locus_core::Permission::Network,
+            serde_json::json!({}),
+        ));
+
+        let response = s.answer_approval(ApprovalDecision::Deny).unwrap();
+        assert_eq!(response.id, first_id);
+        assert_eq!(response.decision, ApprovalDecision::Deny);
+        assert_eq!(s.pending_approvals.len(), 1);
+        assert_eq!(s.status, tf("status.approval.denied", &[("tool", &"Bash")]));
+    }
+
+    #[test]
+    fn deny_with_message_sends_the_input() {
+        let mut s = TuiState::new();
+        s.begin_deny_message();
+        assert!(!s.approval_denying);
+        s.push_approval(ApprovalRequest::new(
+            "edit_file",
+            locus_core::Permission::Write,
+            serde_json::json!({"path": "src/lib.rs"}),
+        ));
+        s.begin_deny_message();
+        assert!(s.approval_denying);
+        for c in " use the builder ".chars() {
+            s.input_insert(c);
+        }
+
+        let response = s.deny_with_message().unwrap();
+        assert_eq!(response.decision, ApprovalDecision::Deny);
+        assert_eq!(response.message.as_deref(), Some("use the builder"));
+        assert!(!s.approval_denying);
+        assert!(s.input_buffer.is_empty());
+        assert!(s.pending_approvals.is_empty());
+    }
+
+    #[test]
+    fn answer_conflict_pops_in_order() {
+        let mut s = TuiState::new();
+        assert!(s.answer_conflict(ConflictDecision::Abort).is_none());
+        let first = ConflictRequest::new("edit_file", "src/lib.rs", "a\n", "b\n", "c\n");
+        let first_id = first.id.clone();
+        s.push_conflict(first);
+        s.push_conflict(ConflictRequest::new(
+            "create_file",
+            "notes.md",
+            "",
+            "x",
+            "y",
+        ));
+
+        let response = s.answer_conflict(ConflictDecision::Rebase).unwrap();
+        assert_eq!(response.id, first_id);
+        assert_eq!(response.decision, ConflictDecision::Rebase);
+        assert_eq!(s.pending_conflicts.len(), 1);
+        assert_eq!(
+            s.status,
+            tf("status.conflict.rebased", &[("path", &"src/lib.rs")])
+        );
+    }
+
     #[test]
     fn is_active_phase_includes_streaming_and_running_tools() {
         let mut s = TuiState::new();
@@ -904,4 +1447,25 @@ mod tests {
         assert!(s.has_running_tools());
         assert!(s.is_active_phase());
     }
+
+    #[test]
+    fn provider_calls_keep_limit_and_follow_newest() {
+        let call = |model: &str| ProviderCall::new("zai", model, serde_json::json!({}));
+        let mut s = TuiState::new();
+        for model in ["a", "b", "c"] {
+            s.push_provider_call(call(model), 2);
+        }
+        assert_eq!(s.provider_calls.len(), 2);
+        assert_eq!(s.provider_calls[s.provider_call_index].model, "c");
+
+        s.provider_call_step(-1);
+        s.provider_call_scroll_down(5);
+        s.push_provider_call(call("d"), 2);
+        assert_eq!(s.provider_calls[s.provider_call_index].model, "c");
+        assert_eq!(s.provider_call_scroll, 5);
+
+        s.provider_call_step(10);
+        assert_eq!(s.provider_calls[s.provider_call_index].model, "d");
+        assert_eq!(s.provider_call_scroll, 0);
+    }
 }
@@ -0,0 +1,279 @@
+//! Status bar under the prompt: how full the context window is (a bar going
+//! from green to red), what the session has cost so far, the model in use
+//! and the git branch with `*` when the work tree is dirty.
+//!
+//! The model and window come with `SessionEvent::ModelInUse` when a turn
+//! starts, the tokens with each `SessionEvent::TokenUsage`; the branch is
+//! read at startup and after each run. Parts that don't fit are left out,
+//! the branch first.
+
+use std::path::Path;
+use std::process::Command;
+
+use locus_core::model_price;
+use ratatui::text::{Line, Span};
+
+use crate::i18n::t;
+use crate::layouts::{text_muted_style, text_style};
+use crate::runtime_events::format_token_count;
+use crate::state::TuiState;
+use crate::theme::{LocusPalette, Rgb};
+
+/// Cells of the context gauge.
+const GAUGE_CELLS: usize = 8;
+
+/// Model, context window and token use of the session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionUsage {
+    pub provider: String,
+    pub model: String,
+    /// Tokens the model's context window holds (0 until known).
+    pub context_limit: u64,
+    /// Tokens in the context as of the last LLM call: its prompt and completion.
+    pub context_tokens: u64,
+    /// Prompt and completion tokens of the session so far.
+    pub total_tokens: u64,
+    /// Estimated USD cost of the session; None when a model used has no known price.
+    pub cost: Option<f64>,
+}
+
+impl SessionUsage {
+    /// A turn starts on `model`.
+    pub fn set_model(&mut self, provider: String, model: String, context_limit: u64) {
+        self.provider = provider;
+        self.model = model;
+        self.context_limit = context_limit;
+    }
+
+    /// An LLM call used these tokens; the totals are the session's. Totals
+    /// lower than before mean a new session, which starts the cost over.
+    pub fn record(
+        &mut self,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        total_prompt_tokens: u64,
+        total_completion_tokens: u64,
+    ) {
+        let total = total_prompt_tokens + total_completion_tokens;
+        let cost = if self.total_tokens == 0 || total < self.total_tokens {
+            Some(0.0)
+        } else {
+            self.cost
+        };
+        self.cost = cost
+            .zip(model_price(&self.provider, &self.model))
+            .map(|(cost, price)| cost + price.cost(prompt_tokens, completion_tokens));
+        self.context_tokens = prompt_tokens + completion_tokens;
+        self.total_tokens = total;
+    }
+
+    /// How full the context window is, 0 to 1; None until the window is known.
+    pub fn context_ratio(&self) -> Option<f64> {
+        (self.context_limit > 0)
+            .then(|| (self.context_tokens as f64 / self.context_limit as f64).min(1.0))
+    }
+}
+
+/// Branch of the work tree and whether it has uncommitted changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitStatus {
+    pub branch: String,
+    pub dirty: bool,
+}
+
+/// The git status of `root`; None outside a git repo or without git.
+pub fn git_status(root: &Path) -> Option<GitStatus> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["status", "--porcelain=v2", "--branch"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_git_status(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `git status --porcelain=v2 --branch`.
+fn parse_git_status(text: &str) -> Option<GitStatus> {
+    let mut branch = None;
+    let mut dirty = false;
+    for line in text.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            branch = Some(head.to_string());
+        } else if !line.starts_with('#') && !line.is_empty() {
+            dirty = true;
+        }
+    }
+    Some(GitStatus {
+        branch: branch?,
+        dirty,
+    })
+}
+
+/// Read the git status of the project again.
+pub fn refresh_git(state: &mut TuiState) {
+    if let Some(root) = state.file_finder.root.clone() {
+        state.git = git_status(&root);
+    }
+}
+
+/// `a` to `b` by `ratio` (0 to 1).
+fn blend(a: Rgb, b: Rgb, ratio: f64) -> Rgb {
+    let mix = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * ratio).round() as u8;
+    Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
+
+/// Color of the gauge: success when empty, warning half way, danger when full.
+pub fn gauge_color(palette: &LocusPalette, ratio: f64) -> Rgb {
+    if ratio < 0.5 {
+        blend(palette.success, palette.warning, ratio * 2.0)
+    } else {
+        blend(palette.warning, palette.danger, (ratio - 0.5) * 2.0)
+    }
+}
+
+/// The status bar for `width` columns.
+pub fn status_bar_line(state: &TuiState, width: usize) -> Line<'static> {
+    let palette = &state.palette;
+    let muted = text_muted_style(palette.text_muted);
+    let usage = &state.usage;
+    let mut parts: Vec<Vec<Span<'static>>> = Vec::new();
+
+    if let Some(ratio) = usage.context_ratio() {
+        let filled = (ratio * GAUGE_CELLS as f64).round() as usize;
+        let gauge = text_style(gauge_color(palette, ratio));
+        parts.push(vec![
+            Span::styled(format!("{} ", t("status_bar.context")), muted),
+            Span::styled("▰".repeat(filled), gauge),
+            Span::styled("▱".repeat(GAUGE_CELLS - filled), muted),
+            Span::styled(
+                format!(
+                    " {}/{} ",
+                    format_token_count(usage.context_tokens),
+                    format_token_count(usage.context_limit)
+                ),
+                text_style(palette.text),
+            ),
+            Span::styled(format!("{:.0}%", ratio * 100.0), gauge),
+        ]);
+    }
+    if usage.total_tokens > 0 {
+        let cost = match usage.cost {
+            Some(cost) => format!("${:.2}", cost),
+            None => t("status_bar.cost_unknown").to_string(),
+        };
+        parts.push(vec![Span::styled(cost, text_style(palette.text))]);
+    }
+    if !usage.model.is_empty() {
+        parts.push(vec![Span::styled(
+            format!("{}/{}", usage.provider, usage.model),
+            text_style(palette.text_accent),
+        )]);
+    }
+    if let Some(git) = &state.git {
+        let mut spans = vec![Span::styled(format!("⎇ {}", git.branch), muted)];
+        if git.dirty {
+            spans.push(Span::styled("*".to_string(), text_style(palette.warning)));
+        }
+        parts.push(spans);
+    }
+
+    let separator = "  ·  ";
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for part in parts {
+        let part_width: usize = part.iter().map(|s| s.content.chars().count()).sum();
+        let gap = if spans.is_empty() { 0 } else { separator.len() };
+        if used + gap + part_width > width {
+            break;
+        }
+        if gap > 0 {
+            spans.push(Span::styled(
+                separator.to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        used += gap + part_width;
+        spans.extend(part);
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn usage_adds_up_cost_and_context() {
+        let mut usage = SessionUsage::default();
+        usage.set_model(
+            "anthropic".to_string(),
+            "claude-sonnet-4-20250514".to_string(),
+            200_000,
+        );
+        usage.record(100_000, 2_000, 100_000, 2_000);
+        assert_eq!(usage.context_tokens, 102_000);
+        assert!((usage.context_ratio().unwrap() - 0.51).abs() < 1e-9);
+        usage.record(100_000, 2_000, 200_000, 4_000);
+        assert!((usage.cost.unwrap() - 0.66).abs() < 1e-9);
+
+        // A new session starts the cost over
+        usage.record(1_000, 0, 1_000, 0);
+        assert!((usage.cost.unwrap() - 0.003).abs() < 1e-9);
+
+        usage.set_model("anthropic".to_string(), "unreleased".to_string(), 200_000);
+        usage.record(1_000, 0, 2_000, 0);
+        assert_eq!(usage.cost, None);
+    }
+
+    #[test]
+    fn parses_git_branch_and_dirty_tree() {
+        let clean = "# branch.oid 14f188f\n# branch.head main\n# branch.upstream origin/main\n";
+        assert_eq!(
+            parse_git_status(clean),
+            Some(GitStatus {
+                branch: "main".to_string(),
+                dirty: false
+            })
+        );
+        let dirty = "# branch.head fix-paste\n1 .M N... 100644 100644 100644 a b src/run.rs\n";
+        assert!(parse_git_status(dirty).unwrap().dirty);
+        assert_eq!(parse_git_status(""), None);
+    }
+
+    #[test]
+    fn bar_drops_what_does_not_fit() {
+        let mut state = TuiState::new();
+        assert_eq!(text(&status_bar_line(&state, 80)), "");
+
+        state
+            .usage
+            .set_model("zai".to_string(), "glm-4.5".to_string(), 128_000);
+        state.usage.record(64_000, 0, 64_000, 0);
+        state.git = Some(GitStatus {
+            branch: "main".to_string(),
+            dirty: true,
+        });
+        let full = text(&status_bar_line(&state, 120));
+        assert_eq!(
+            full,
+            "ctx ▰▰▰▰▱▱▱▱ 64.0k/128.0k 50%  ·  $0.04  ·  zai/glm-4.5  ·  ⎇ main*"
+        );
+        let narrow = text(&status_bar_line(&state, 40));
+        assert_eq!(narrow, "ctx ▰▰▰▰▱▱▱▱ 64.0k/128.0k 50%  ·  $0.04");
+    }
+
+    #[test]
+    fn gauge_goes_from_success_to_danger() {
+        let palette = LocusPalette::locus_dark();
+        assert_eq!(gauge_color(&palette, 0.0), palette.success);
+        assert_eq!(gauge_color(&palette, 0.5), palette.warning);
+        assert_eq!(gauge_color(&palette, 1.0), palette.danger);
+    }
+}
@@ -0,0 +1,10 @@
+//! Task board screen.
+//!
+//! A kanban view of the plans the task_list tool maintains, updated live from
+//! `SessionEvent::TaskListUpdated`. Access via Ctrl+B from the main chat.
+
+pub mod state;
+pub mod view;
+
+pub use state::{TaskBoardState, TaskColumn, TaskPlan};
+pub use view::draw_task_board;
@@ -0,0 +1,171 @@
+//! Task board state: the plans seen this session and which one is shown.
+
+use locus_core::db::{TaskItem, TaskStatus};
+
+/// Board column a task sits in. Cancelled tasks go with the done ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskColumn {
+    Pending,
+    InProgress,
+    Done,
+}
+
+impl TaskColumn {
+    pub const ALL: [TaskColumn; 3] = [
+        TaskColumn::Pending,
+        TaskColumn::InProgress,
+        TaskColumn::Done,
+    ];
+
+    pub fn of(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::Pending => TaskColumn::Pending,
+            TaskStatus::InProgress => TaskColumn::InProgress,
+            TaskStatus::Done | TaskStatus::Cancelled => TaskColumn::Done,
+        }
+    }
+}
+
+/// One plan as last reported by the runtime.
+#[derive(Debug, Clone)]
+pub struct TaskPlan {
+    pub plan_id: String,
+    pub tasks: Vec<TaskItem>,
+}
+
+impl TaskPlan {
+    /// Tasks in `column`, in plan order.
+    pub fn column(&self, column: TaskColumn) -> Vec<&TaskItem> {
+        self.tasks
+            .iter()
+            .filter(|task| TaskColumn::of(task.status) == column)
+            .collect()
+    }
+
+    /// Tasks marked done (not cancelled).
+    pub fn done_count(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Done)
+            .count()
+    }
+}
+
+/// Plans updated this session, oldest first, and the one on screen.
+#[derive(Debug, Clone, Default)]
+pub struct TaskBoardState {
+    pub plans: Vec<TaskPlan>,
+    /// Index into plans of the plan shown.
+    pub index: usize,
+    /// Scroll offset for the columns (lines from top).
+    pub scroll: usize,
+}
+
+impl TaskBoardState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace (or add) a plan and show it, so the board follows the plan the
+    /// agent is working on.
+    pub fn update(&mut self, plan_id: String, tasks: Vec<TaskItem>) {
+        match self.plans.iter().position(|plan| plan.plan_id == plan_id) {
+            Some(index) => {
+                self.plans[index].tasks = tasks;
+                if index != self.index {
+                    self.index = index;
+                    self.scroll = 0;
+                }
+            }
+            None => {
+                self.plans.push(TaskPlan { plan_id, tasks });
+                self.index = self.plans.len() - 1;
+                self.scroll = 0;
+            }
+        }
+    }
+
+    /// The plan on screen, if any plan was seen.
+    pub fn current(&self) -> Option<&TaskPlan> {
+        self.plans.get(self.index)
+    }
+
+    /// Step to an earlier (`delta < 0`) or later plan.
+    pub fn step(&mut self, delta: isize) {
+        let last = self.plans.len().saturating_sub(1);
+        self.index = self.index.saturating_add_signed(delta).min(last);
+        self.scroll = 0;
+    }
+
+    pub fn scroll_up(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_sub(delta);
+    }
+
+    pub fn scroll_down(&mut self, delta: usize) {
+        self.scroll = self.scroll.saturating_add(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, status: TaskStatus) -> TaskItem {
+        TaskItem {
+            id: Some(id.to_string()),
+            title: format!("task {}", id),
+            status,
+            description: None,
+            criteria: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn columns_group_by_status_and_keep_order() {
+        let plan = TaskPlan {
+            plan_id: "default".into(),
+            tasks: vec![
+                task("1", TaskStatus::Done),
+                task("2", TaskStatus::Pending),
+                task("3", TaskStatus::InProgress),
+                task("4", TaskStatus::Cancelled),
+                task("5", TaskStatus::Pending),
+            ],
+        };
+        let ids = |column| {
+            plan.column(column)
+                .iter()
+                .map(|t| t.id.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(TaskColumn::Pending), ["2", "5"]);
+        assert_eq!(ids(TaskColumn::InProgress), ["3"]);
+        assert_eq!(ids(TaskColumn::Done), ["1", "4"]);
+        assert_eq!(plan.done_count(), 1);
+    }
+
+    #[test]
+    fn update_follows_the_changed_plan() {
+        let mut board = TaskBoardState::new();
+        assert!(board.current().is_none());
+
+        board.update("a".into(), vec![task("1", TaskStatus::Pending)]);
+        board.update("b".into(), vec![task("1", TaskStatus::Pending)]);
+        assert_eq!(board.current().unwrap().plan_id, "b");
+
+        board.scroll_down(4);
+        board.update("b".into(), vec![task("1", TaskStatus::Done)]);
+        assert_eq!(board.scroll, 4);
+        assert_eq!(board.current().unwrap().done_count(), 1);
+
+        board.update("a".into(), Vec::new());
+        assert_eq!(board.plans.len(), 2);
+        assert_eq!(board.current().unwrap().plan_id, "a");
+        assert_eq!(board.scroll, 0);
+
+        board.step(5);
+        assert_eq!(board.current().unwrap().plan_id, "b");
+        board.step(-5);
+        assert_eq!(board.current().unwrap().plan_id, "a");
+    }
+}
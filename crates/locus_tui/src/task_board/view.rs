@@ -0,0 +1,268 @@
+//! View rendering for the task board screen.
+
+use locus_core::db::{TaskItem, TaskStatus};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::i18n::{t, tf};
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, render_header, text_muted_style, text_style,
+};
+use crate::task_board::state::{TaskBoardState, TaskColumn, TaskPlan};
+use crate::theme::LocusPalette;
+use crate::utils::LEFT_PADDING;
+
+/// Draw the task board screen: one column per status for the plan on screen.
+pub fn draw_task_board(
+    frame: &mut Frame,
+    state: &mut TaskBoardState,
+    area: Rect,
+    palette: &LocusPalette,
+) {
+    frame.render_widget(
+        Block::default().style(background_style(palette.background)),
+        area,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Columns
+            Constraint::Length(1), // Shortcuts
+        ])
+        .split(area);
+
+    let status = match state.current() {
+        Some(plan) => tf(
+            "task_board.position",
+            &[
+                ("plan", &plan.plan_id),
+                ("done", &plan.done_count()),
+                ("total", &plan.tasks.len()),
+                ("index", &(state.index + 1)),
+                ("plans", &state.plans.len()),
+            ],
+        ),
+        None => t("task_board.empty_status").to_string(),
+    };
+    render_header(
+        frame,
+        chunks[0],
+        palette,
+        t(HEADER_TITLE),
+        t("task_board.section"),
+        status.as_str(),
+        false,
+        false,
+    );
+
+    match state.current().cloned() {
+        Some(plan) => draw_columns(frame, chunks[1], state, &plan, palette),
+        None => draw_empty(frame, chunks[1], palette),
+    }
+    draw_shortcuts(frame, chunks[2], palette);
+}
+
+fn draw_empty(frame: &mut Frame, area: Rect, palette: &LocusPalette) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style(palette.border))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled("● ".to_string(), text_style(palette.accent)),
+            Span::styled(t("task_board.empty_title"), text_style(palette.text)),
+        ]),
+        Line::from(vec![
+            Span::raw(LEFT_PADDING),
+            Span::styled(
+                format!("  {}", t("task_board.empty_hint")),
+                text_muted_style(palette.text_muted),
+            ),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn draw_columns(
+    frame: &mut Frame,
+    area: Rect,
+    state: &mut TaskBoardState,
+    plan: &TaskPlan,
+    palette: &LocusPalette,
+) {
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(area);
+
+    let columns: Vec<(TaskColumn, Vec<Line<'static>>)> = TaskColumn::ALL
+        .iter()
+        .map(|&column| {
+            let lines = plan
+                .column(column)
+                .into_iter()
+                .flat_map(|task| card_lines(task, palette))
+                .collect();
+            (column, lines)
+        })
+        .collect();
+
+    // One scroll offset for the board, kept within the longest column.
+    let height = area.height.saturating_sub(2) as usize;
+    let longest = columns.iter().map(|(_, l)| l.len()).max().unwrap_or(0);
+    state.scroll = state.scroll.min(longest.saturating_sub(height));
+
+    for ((column, lines), &column_area) in columns.into_iter().zip(areas.iter()) {
+        let count = plan.column(column).len();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style(
+                if column == TaskColumn::InProgress && count > 0 {
+                    palette.border_focused
+                } else {
+                    palette.border
+                },
+            ))
+            .title(Span::styled(
+                format!(" {} ({}) ", column_title(column), count),
+                text_style(palette.text),
+            ))
+            .style(background_style(palette.surface_background));
+        let inner = block.inner(column_area);
+        frame.render_widget(block, column_area);
+
+        let visible: Vec<Line> = if lines.is_empty() {
+            vec![Line::from(Span::styled(
+                format!(" {}", t("task_board.no_tasks")),
+                text_muted_style(palette.text_disabled),
+            ))]
+        } else {
+            lines.into_iter().skip(state.scroll).collect()
+        };
+        frame.render_widget(Paragraph::new(visible).wrap(Wrap { trim: false }), inner);
+    }
+}
+
+fn column_title(column: TaskColumn) -> &'static str {
+    match column {
+        TaskColumn::Pending => t("task_board.pending"),
+        TaskColumn::InProgress => t("task_board.in_progress"),
+        TaskColumn::Done => t("task_board.done"),
+    }
+}
+
+/// A task card: status glyph, id and title, then criteria progress when the
+/// task has a checklist, then a blank line.
+pub fn card_lines(task: &TaskItem, palette: &LocusPalette) -> Vec<Line<'static>> {
+    let (glyph, glyph_color, title_color) = match task.status {
+        TaskStatus::Pending => ("○", palette.text_muted, palette.text),
+        TaskStatus::InProgress => ("●", palette.accent, palette.text),
+        TaskStatus::Done => ("✓", palette.success, palette.text_muted),
+        TaskStatus::Cancelled => ("✗", palette.text_disabled, palette.text_disabled),
+    };
+    let mut title = vec![
+        Span::raw(" "),
+        Span::styled(format!("{} ", glyph), text_style(glyph_color)),
+    ];
+    if let Some(id) = &task.id {
+        title.push(Span::styled(
+            format!("{} ", id),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    title.push(Span::styled(task.title.clone(), text_style(title_color)));
+
+    let mut lines = vec![Line::from(title)];
+    if !task.criteria.is_empty() {
+        let met = task.criteria.iter().filter(|c| c.met).count();
+        lines.push(Line::from(Span::styled(
+            format!(
+                "   {}",
+                tf(
+                    "task_board.criteria",
+                    &[("met", &met), ("total", &task.criteria.len())],
+                )
+            ),
+            text_muted_style(palette.text_muted),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+fn draw_shortcuts(frame: &mut Frame, area: Rect, palette: &LocusPalette) {
+    let shortcuts = [
+        ("Esc", t("shortcut.back")),
+        ("←→", t("task_board.shortcut.plans")),
+        ("↑↓/jk", t("shortcut.scroll")),
+        ("g/G", t("shortcut.top_bottom")),
+    ];
+    let mut spans: Vec<Span> = Vec::new();
+    for (idx, (key, action)) in shortcuts.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(
+                "  ·  ".to_string(),
+                text_muted_style(palette.text_disabled),
+            ));
+        }
+        spans.push(Span::styled((*key).to_string(), text_style(palette.text)));
+        spans.push(Span::styled(
+            format!(": {}", action),
+            text_muted_style(palette.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::db::AcceptanceCriterion;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn card_shows_id_title_and_criteria_progress() {
+        let palette = LocusPalette::locus_dark();
+        let criterion = |met| AcceptanceCriterion {
+            id: None,
+            text: "tests pass".into(),
+            met,
+            evidence: Vec::new(),
+        };
+        let task = TaskItem {
+            id: Some("2".into()),
+            title: "Add retries".into(),
+            status: TaskStatus::InProgress,
+            description: None,
+            criteria: vec![criterion(true), criterion(false)],
+        };
+        assert_eq!(
+            text(&card_lines(&task, &palette)),
+            [" ● 2 Add retries", "   criteria 1/2", ""]
+        );
+
+        let task = TaskItem {
+            id: None,
+            status: TaskStatus::Cancelled,
+            criteria: Vec::new(),
+            ..task
+        };
+        assert_eq!(text(&card_lines(&task, &palette)), [" ✗ Add retries", ""]);
+    }
+}
@@ -0,0 +1,148 @@
+//! Light or dark terminal: ask the terminal for its background color (OSC 11),
+//! else read `COLORFGBG`.
+//!
+//! The query is followed by a device attributes request (DA1), which every
+//! terminal answers, so reading stops there even when OSC 11 goes unanswered.
+//! It is asked at startup, before the TUI reads input, and again when the
+//! terminal regains focus, with the TUI's event reader paused. Each read waits
+//! in `poll(2)`, so nothing is left reading the terminal afterwards.
+
+use super::Appearance;
+use super::rgb::Rgb;
+
+/// How long to wait for the terminal to answer.
+#[cfg(unix)]
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// The terminal's appearance, from its background color or `COLORFGBG`;
+/// None when neither says. Call while nothing else reads terminal events.
+pub fn detect_term() -> Option<Appearance> {
+    query_background()
+        .map(appearance_of)
+        .or_else(|| parse_colorfgbg(&std::env::var("COLORFGBG").ok()?))
+}
+
+/// Light when the background's relative luminance is above one half.
+pub fn appearance_of(background: Rgb) -> Appearance {
+    let luminance =
+        0.2126 * background.0 as f64 + 0.7152 * background.1 as f64 + 0.0722 * background.2 as f64;
+    if luminance > 127.5 {
+        Appearance::Light
+    } else {
+        Appearance::Dark
+    }
+}
+
+/// Ask the terminal for its background (OSC 11) on `/dev/tty`.
+#[cfg(unix)]
+fn query_background() -> Option<Rgb> {
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+    use std::io::Write;
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let raw = is_raw_mode_enabled().unwrap_or(false);
+    if !raw {
+        enable_raw_mode().ok()?;
+    }
+    let reply = tty
+        .write_all(b"\x1b]11;?\x07\x1b[c")
+        .and_then(|_| tty.flush())
+        .ok()
+        .and_then(|_| read_reply(&mut tty));
+    if !raw {
+        let _ = disable_raw_mode();
+    }
+    parse_osc11(&reply?)
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<Rgb> {
+    None
+}
+
+/// Bytes from `tty` up to the end of the DA1 answer, or None after [QUERY_TIMEOUT].
+/// Each read waits in `poll(2)`, so no read is left pending on the terminal.
+#[cfg(unix)]
+fn read_reply(tty: &mut std::fs::File) -> Option<String> {
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        let left = deadline.checked_duration_since(Instant::now())?;
+        let mut fd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut fd, 1, left.as_millis().max(1) as libc::c_int) };
+        if ready <= 0 || tty.read(&mut byte).ok()? != 1 {
+            return None;
+        }
+        reply.push(byte[0]);
+        // DA1 answers `ESC [ ? … c`
+        if byte[0] == b'c' && reply.windows(2).any(|w| w == b"\x1b[") {
+            return Some(String::from_utf8_lossy(&reply).into_owned());
+        }
+    }
+}
+
+/// The color in an OSC 11 answer: `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` ended by
+/// BEL or ST, with 1 to 4 hex digits per channel.
+fn parse_osc11(reply: &str) -> Option<Rgb> {
+    let start = reply.find("]11;rgb:")? + "]11;rgb:".len();
+    let rest = &reply[start..];
+    let end = rest.find(['\x07', '\x1b']).unwrap_or(rest.len());
+    let mut channels = rest[..end].split('/').map(|hex| {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = 16u32.checked_pow(hex.len() as u32)?.checked_sub(1)?;
+        (hex.len() <= 4 && max > 0).then(|| (value * 255 / max) as u8)
+    });
+    Some(Rgb(channels.next()??, channels.next()??, channels.next()??))
+}
+
+/// `COLORFGBG` is `fg;bg` (some terminals put more fields between); a
+/// background of 0–6 or 8 is dark, other colors light.
+fn parse_colorfgbg(value: &str) -> Option<Appearance> {
+    let background: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    Some(match background {
+        0..=6 | 8 => Appearance::Dark,
+        _ => Appearance::Light,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_background_answers() {
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?62;22c"),
+            Some(Rgb(255, 255, 255))
+        );
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:1e/1e/2e\x1b\\"),
+            Some(Rgb(30, 30, 46))
+        );
+        assert_eq!(parse_osc11("\x1b[?1;2c"), None);
+        assert_eq!(appearance_of(Rgb(253, 246, 227)), Appearance::Light);
+        assert_eq!(appearance_of(Rgb(30, 30, 46)), Appearance::Dark);
+    }
+
+    #[test]
+    fn colorfgbg_names_the_background() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Appearance::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Appearance::Light));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(Appearance::Light));
+        assert_eq!(parse_colorfgbg("default"), None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Theme gallery: the built-in palettes by name, and the project's own theme in
+//! `.locus/theme.toml`.
+//!
+//! The file starts from a built-in and overrides any palette role by its field
+//! name, as `#rrggbb`:
+//!
+//! ```toml
+//! base = "light"
+//!
+//! [colors]
+//! accent = "#b4008c"
+//! text_muted = "#5a5a6e"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::palette::{LocusPalette, PALETTE_ROLES};
+use super::rgb::Rgb;
+
+/// The project's theme file, relative to the repo root.
+pub const THEME_FILE: &str = ".locus/theme.toml";
+
+/// Name the project's theme file goes by in the gallery.
+pub const CUSTOM_THEME: &str = "custom";
+
+/// Built-in themes, in gallery order.
+pub const BUILTIN_THEMES: &[&str] = &[
+    "dark",
+    "light",
+    "solarized",
+    "high-contrast",
+    "colorblind",
+    "colorblind-light",
+];
+
+/// The built-in palette called `name`.
+pub fn builtin_palette(name: &str) -> Option<LocusPalette> {
+    match name {
+        "dark" => Some(LocusPalette::locus_dark()),
+        "light" => Some(LocusPalette::locus_light()),
+        "solarized" => Some(LocusPalette::solarized_dark()),
+        "high-contrast" => Some(LocusPalette::high_contrast()),
+        "colorblind" => Some(LocusPalette::colorblind_dark()),
+        "colorblind-light" => Some(LocusPalette::colorblind_light()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+/// Parse a theme file: its base palette with the colors it overrides.
+pub fn parse_theme(text: &str) -> Result<LocusPalette, String> {
+    let file: ThemeFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+    let base = file.base.as_deref().unwrap_or("dark");
+    let mut palette =
+        builtin_palette(base).ok_or_else(|| format!("unknown base theme \"{}\"", base))?;
+    for (role, value) in &file.colors {
+        let color = Rgb::from_hex(value)
+            .ok_or_else(|| format!("{}: \"{}\" is not a #rrggbb color", role, value))?;
+        let slot = palette.role_mut(role).ok_or_else(|| {
+            format!(
+                "unknown color role \"{}\" (one of {})",
+                role,
+                PALETTE_ROLES.join(", ")
+            )
+        })?;
+        *slot = color;
+    }
+    Ok(palette)
+}
+
+/// The project's theme from `<root>/.locus/theme.toml`; None when there is
+/// no such file.
+pub fn load_theme_file(root: &Path) -> Option<Result<LocusPalette, String>> {
+    let text = std::fs::read_to_string(root.join(THEME_FILE)).ok()?;
+    Some(parse_theme(&text).map_err(|e| format!("{}: {}", THEME_FILE, e)))
+}
+
+/// The theme called `name`: a built-in, or [CUSTOM_THEME] for the project's file.
+pub fn theme_palette(name: &str, root: Option<&Path>) -> Result<LocusPalette, String> {
+    if name == CUSTOM_THEME {
+        return root
+            .and_then(load_theme_file)
+            .unwrap_or_else(|| Err(format!("no {} in this project", THEME_FILE)));
+    }
+    builtin_palette(name).ok_or_else(|| {
+        format!(
+            "unknown theme \"{}\" (one of {}, {})",
+            name,
+            BUILTIN_THEMES.join(", "),
+            CUSTOM_THEME
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_file_overrides_roles_of_its_base() {
+        let palette = parse_theme(
+            "base = \"light\"\n[colors]\naccent = \"#b4008c\"\ntext_muted = \"5a5a6e\"\n",
+        )
+        .unwrap();
+        assert_eq!(palette.accent, Rgb(180, 0, 140));
+        assert_eq!(palette.text_muted, Rgb(90, 90, 110));
+        assert_eq!(palette.text, LocusPalette::locus_light().text);
+
+        assert_eq!(parse_theme("").unwrap(), LocusPalette::locus_dark());
+        assert!(parse_theme("[colors]\naccnet = \"#ffffff\"").is_err());
+        assert!(parse_theme("[colors]\naccent = \"blue\"").is_err());
+        assert!(parse_theme("base = \"neon\"").is_err());
+    }
+
+    #[test]
+    fn every_role_can_be_overridden() {
+        let mut palette = LocusPalette::locus_dark();
+        for role in PALETTE_ROLES {
+            assert!(palette.role_mut(role).is_some(), "{}", role);
+        }
+        for name in BUILTIN_THEMES {
+            assert!(theme_palette(name, None).is_ok());
+        }
+        assert!(theme_palette(CUSTOM_THEME, None).is_err());
+    }
+}
@@ -13,12 +13,25 @@
 //! let text = palette.text.tuple(); // (r, g, b) for ratatui
 //!
 //! let palette = LocusPalette::for_appearance(Appearance::Light);
+//!
+//! // Light or dark from the terminal's background
+//! let palette = LocusPalette::for_appearance(locus_tui::theme::detect_term().unwrap_or_default());
+//!
+//! // A built-in by name, or the project's `.locus/theme.toml` as "custom"
+//! let palette = locus_tui::theme::theme_palette("solarized", None).unwrap();
 //! ```
 
 mod appearance;
+mod detect;
+mod gallery;
 mod palette;
 mod rgb;
 
 pub use appearance::Appearance;
-pub use palette::LocusPalette;
+pub use detect::{appearance_of, detect_term};
+pub use gallery::{
+    BUILTIN_THEMES, CUSTOM_THEME, THEME_FILE, builtin_palette, load_theme_file, parse_theme,
+    theme_palette,
+};
+pub use palette::{LocusPalette, PALETTE_ROLES};
 pub use rgb::Rgb;
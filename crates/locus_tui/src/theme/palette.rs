@@ -7,6 +7,55 @@
 use super::Appearance;
 use super::rgb::Rgb;
 
+/// Names of the color roles, as used by [LocusPalette::role_mut] and `.locus/theme.toml`.
+pub const PALETTE_ROLES: &[&str] = &[
+    "background",
+    "surface_background",
+    "elevated_surface_background",
+    "border",
+    "border_variant",
+    "border_focused",
+    "border_selected",
+    "border_disabled",
+    "element_background",
+    "element_hover",
+    "element_active",
+    "element_selected",
+    "element_disabled",
+    "ghost_element_background",
+    "ghost_element_hover",
+    "ghost_element_selected",
+    "ghost_element_disabled",
+    "text",
+    "text_muted",
+    "text_placeholder",
+    "text_disabled",
+    "text_accent",
+    "icon",
+    "icon_muted",
+    "icon_disabled",
+    "icon_accent",
+    "accent",
+    "danger",
+    "success",
+    "warning",
+    "info",
+    "status_bar_background",
+    "tab_bar_background",
+    "tab_inactive_background",
+    "tab_active_background",
+    "panel_background",
+    "panel_focused_border",
+    "scrollbar_thumb_background",
+    "scrollbar_thumb_hover_background",
+    "scrollbar_thumb_active",
+    "scrollbar_track_background",
+    "pane_focused_border",
+    "editor_background",
+    "editor_foreground",
+    "editor_line_number",
+];
+
 /// One full palette for an appearance (dark or light). All colors are semantic roles.
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocusPalette {
@@ -178,6 +227,184 @@ impl LocusPalette {
         }
     }
 
+    /// Solarized (dark) by Ethan Schoonover.
+    pub fn solarized_dark() -> Self {
+        Self {
+            background: Rgb(0, 43, 54),
+            surface_background: Rgb(7, 54, 66),
+            elevated_surface_background: Rgb(10, 64, 78),
+            border: Rgb(22, 74, 88),
+            border_variant: Rgb(13, 62, 75),
+            border_focused: Rgb(38, 139, 210),
+            border_selected: Rgb(38, 139, 210),
+            border_disabled: Rgb(17, 62, 74),
+            element_background: Rgb(7, 54, 66),
+            element_hover: Rgb(14, 64, 77),
+            element_active: Rgb(20, 72, 86),
+            element_selected: Rgb(18, 70, 84),
+            element_disabled: Rgb(4, 48, 60),
+            ghost_element_background: Rgb(0, 43, 54),
+            ghost_element_hover: Rgb(5, 50, 62),
+            ghost_element_selected: Rgb(10, 58, 71),
+            ghost_element_disabled: Rgb(2, 46, 58),
+            text: Rgb(147, 161, 161),
+            text_muted: Rgb(88, 110, 117),
+            text_placeholder: Rgb(78, 98, 106),
+            text_disabled: Rgb(60, 82, 90),
+            text_accent: Rgb(38, 139, 210),
+            icon: Rgb(131, 148, 150),
+            icon_muted: Rgb(88, 110, 117),
+            icon_disabled: Rgb(60, 82, 90),
+            icon_accent: Rgb(38, 139, 210),
+            accent: Rgb(38, 139, 210),
+            danger: Rgb(220, 50, 47),
+            success: Rgb(133, 153, 0),
+            warning: Rgb(181, 137, 0),
+            info: Rgb(42, 161, 152),
+            status_bar_background: Rgb(7, 54, 66),
+            tab_bar_background: Rgb(7, 54, 66),
+            tab_inactive_background: Rgb(7, 54, 66),
+            tab_active_background: Rgb(0, 43, 54),
+            panel_background: Rgb(7, 54, 66),
+            panel_focused_border: Rgb(38, 139, 210),
+            scrollbar_thumb_background: Rgb(88, 110, 117),
+            scrollbar_thumb_hover_background: Rgb(101, 123, 131),
+            scrollbar_thumb_active: Rgb(131, 148, 150),
+            scrollbar_track_background: Rgb(3, 46, 58),
+            pane_focused_border: Rgb(38, 139, 210),
+            editor_background: Rgb(0, 43, 54),
+            editor_foreground: Rgb(131, 148, 150),
+            editor_line_number: Rgb(88, 110, 117),
+        }
+    }
+
+    /// Black background, white text and saturated accents, for low vision or glare.
+    pub fn high_contrast() -> Self {
+        Self {
+            background: Rgb(0, 0, 0),
+            surface_background: Rgb(0, 0, 0),
+            elevated_surface_background: Rgb(16, 16, 16),
+            border: Rgb(255, 255, 255),
+            border_variant: Rgb(200, 200, 200),
+            border_focused: Rgb(255, 230, 0),
+            border_selected: Rgb(255, 230, 0),
+            border_disabled: Rgb(128, 128, 128),
+            element_background: Rgb(0, 0, 0),
+            element_hover: Rgb(40, 40, 40),
+            element_active: Rgb(60, 60, 60),
+            element_selected: Rgb(0, 60, 120),
+            element_disabled: Rgb(24, 24, 24),
+            ghost_element_background: Rgb(0, 0, 0),
+            ghost_element_hover: Rgb(40, 40, 40),
+            ghost_element_selected: Rgb(0, 60, 120),
+            ghost_element_disabled: Rgb(0, 0, 0),
+            text: Rgb(255, 255, 255),
+            text_muted: Rgb(220, 220, 220),
+            text_placeholder: Rgb(190, 190, 190),
+            text_disabled: Rgb(150, 150, 150),
+            text_accent: Rgb(255, 230, 0),
+            icon: Rgb(255, 255, 255),
+            icon_muted: Rgb(220, 220, 220),
+            icon_disabled: Rgb(150, 150, 150),
+            icon_accent: Rgb(255, 230, 0),
+            accent: Rgb(255, 230, 0),
+            danger: Rgb(255, 90, 90),
+            success: Rgb(0, 255, 120),
+            warning: Rgb(255, 170, 0),
+            info: Rgb(0, 230, 255),
+            status_bar_background: Rgb(0, 0, 0),
+            tab_bar_background: Rgb(0, 0, 0),
+            tab_inactive_background: Rgb(0, 0, 0),
+            tab_active_background: Rgb(40, 40, 40),
+            panel_background: Rgb(0, 0, 0),
+            panel_focused_border: Rgb(255, 230, 0),
+            scrollbar_thumb_background: Rgb(200, 200, 200),
+            scrollbar_thumb_hover_background: Rgb(255, 255, 255),
+            scrollbar_thumb_active: Rgb(255, 230, 0),
+            scrollbar_track_background: Rgb(32, 32, 32),
+            pane_focused_border: Rgb(255, 230, 0),
+            editor_background: Rgb(0, 0, 0),
+            editor_foreground: Rgb(255, 255, 255),
+            editor_line_number: Rgb(200, 200, 200),
+        }
+    }
+
+    /// Dark palette safe for deuteranopia and protanopia: blue for success and
+    /// added lines, orange for danger and removed ones (Okabe–Ito colors).
+    pub fn colorblind_dark() -> Self {
+        Self {
+            danger: Rgb(230, 159, 0),
+            success: Rgb(86, 180, 233),
+            warning: Rgb(240, 228, 66),
+            info: Rgb(204, 121, 167),
+            ..Self::locus_dark()
+        }
+    }
+
+    /// Light counterpart of [LocusPalette::colorblind_dark].
+    pub fn colorblind_light() -> Self {
+        Self {
+            danger: Rgb(213, 94, 0),
+            success: Rgb(0, 114, 178),
+            warning: Rgb(160, 120, 0),
+            info: Rgb(170, 68, 136),
+            ..Self::locus_light()
+        }
+    }
+
+    /// The color of `role` (a field name such as `accent` or `text_muted`), to
+    /// override it; None for an unknown role.
+    pub fn role_mut(&mut self, role: &str) -> Option<&mut Rgb> {
+        Some(match role {
+            "background" => &mut self.background,
+            "surface_background" => &mut self.surface_background,
+            "elevated_surface_background" => &mut self.elevated_surface_background,
+            "border" => &mut self.border,
+            "border_variant" => &mut self.border_variant,
+            "border_focused" => &mut self.border_focused,
+            "border_selected" => &mut self.border_selected,
+            "border_disabled" => &mut self.border_disabled,
+            "element_background" => &mut self.element_background,
+            "element_hover" => &mut self.element_hover,
+            "element_active" => &mut self.element_active,
+            "element_selected" => &mut self.element_selected,
+            "element_disabled" => &mut self.element_disabled,
+            "ghost_element_background" => &mut self.ghost_element_background,
+            "ghost_element_hover" => &mut self.ghost_element_hover,
+            "ghost_element_selected" => &mut self.ghost_element_selected,
+            "ghost_element_disabled" => &mut self.ghost_element_disabled,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "text_placeholder" => &mut self.text_placeholder,
+            "text_disabled" => &mut self.text_disabled,
+            "text_accent" => &mut self.text_accent,
+            "icon" => &mut self.icon,
+            "icon_muted" => &mut self.icon_muted,
+            "icon_disabled" => &mut self.icon_disabled,
+            "icon_accent" => &mut self.icon_accent,
+            "accent" => &mut self.accent,
+            "danger" => &mut self.danger,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "info" => &mut self.info,
+            "status_bar_background" => &mut self.status_bar_background,
+            "tab_bar_background" => &mut self.tab_bar_background,
+            "tab_inactive_background" => &mut self.tab_inactive_background,
+            "tab_active_background" => &mut self.tab_active_background,
+            "panel_background" => &mut self.panel_background,
+            "panel_focused_border" => &mut self.panel_focused_border,
+            "scrollbar_thumb_background" => &mut self.scrollbar_thumb_background,
+            "scrollbar_thumb_hover_background" => &mut self.scrollbar_thumb_hover_background,
+            "scrollbar_thumb_active" => &mut self.scrollbar_thumb_active,
+            "scrollbar_track_background" => &mut self.scrollbar_track_background,
+            "pane_focused_border" => &mut self.pane_focused_border,
+            "editor_background" => &mut self.editor_background,
+            "editor_foreground" => &mut self.editor_foreground,
+            "editor_line_number" => &mut self.editor_line_number,
+            _ => return None,
+        })
+    }
+
     /// Palette for the given appearance.
     pub fn for_appearance(appearance: Appearance) -> Self {
         match appearance {
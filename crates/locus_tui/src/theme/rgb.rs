@@ -19,6 +19,16 @@ impl Rgb {
         self.2
     }
 
+    /// Parse `#rrggbb` (the `#` is optional).
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
     /// Tuple for ratatui/crossterm: `(r, g, b)`.
     pub fn tuple(self) -> (u8, u8, u8) {
         (self.0, self.1, self.2)
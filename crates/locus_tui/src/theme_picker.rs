@@ -0,0 +1,309 @@
+//! `:theme` in the chat input: pick a theme from the gallery with a live
+//! preview, or apply one by name with `:theme <name>`. The choice is kept in
+//! the global config table as `LOCUS_THEME` and used on the next start.
+//!
+//! The gallery holds the built-ins and, when the project has one,
+//! `.locus/theme.toml` as `custom` (see [crate::theme]). With no saved choice
+//! the project's theme file wins over the default palette; with neither, the
+//! light or dark palette follows the terminal's background.
+
+use std::path::PathBuf;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::i18n::{t, tf};
+use crate::layouts::{background_style, text_muted_style, text_style};
+use crate::state::TuiState;
+use crate::theme::{
+    Appearance, BUILTIN_THEMES, CUSTOM_THEME, LocusPalette, load_theme_file, theme_palette,
+};
+
+/// Chat command to open the theme gallery, or apply a theme by name.
+pub const THEME_COMMAND: &str = ":theme";
+
+/// Config / env key holding the chosen theme's name.
+pub const THEME_ENV: &str = "LOCUS_THEME";
+
+/// The theme gallery: each theme's name and palette, previewed as the
+/// selection moves.
+#[derive(Debug, Clone)]
+pub struct ThemePicker {
+    pub themes: Vec<(String, LocusPalette)>,
+    pub selected: usize,
+    /// Palette in use when the gallery opened, restored on Esc.
+    pub original: LocusPalette,
+}
+
+impl ThemePicker {
+    /// The gallery, with the theme in use (`current`) selected.
+    pub fn new(themes: Vec<(String, LocusPalette)>, current: LocusPalette) -> Self {
+        let selected = themes
+            .iter()
+            .position(|(_, palette)| *palette == current)
+            .unwrap_or(0);
+        Self {
+            themes,
+            selected,
+            original: current,
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selected + 1 < self.themes.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&(String, LocusPalette)> {
+        self.themes.get(self.selected)
+    }
+}
+
+/// Root of the project, where `.locus/theme.toml` is looked up.
+fn project_root(state: &TuiState) -> Option<PathBuf> {
+    state
+        .file_finder
+        .root
+        .clone()
+        .or_else(|| std::env::current_dir().ok())
+}
+
+/// Built-ins, then the project's theme file when it parses.
+fn gallery(state: &TuiState) -> Vec<(String, LocusPalette)> {
+    let mut themes: Vec<(String, LocusPalette)> = BUILTIN_THEMES
+        .iter()
+        .filter_map(|name| Some((name.to_string(), theme_palette(name, None).ok()?)))
+        .collect();
+    if let Some(Ok(palette)) = project_root(state).as_deref().and_then(load_theme_file) {
+        themes.push((CUSTOM_THEME.to_string(), palette));
+    }
+    themes
+}
+
+/// Use the saved theme, else the project's theme file, else keep the palette
+/// the TUI started with. A theme that can't be loaded is reported in the status line.
+pub fn apply_startup_theme(state: &mut TuiState) {
+    let root = project_root(state);
+    let loaded = match std::env::var(THEME_ENV) {
+        Ok(name) if !name.trim().is_empty() => Some(theme_palette(name.trim(), root.as_deref())),
+        _ => root.as_deref().and_then(load_theme_file),
+    };
+    match loaded {
+        Some(Ok(palette)) => {
+            state.follow_terminal = false;
+            set_palette(state, palette);
+        }
+        Some(Err(e)) => set_status(state, tf("status.theme_failed", &[("error", &e)])),
+        None => {}
+    }
+}
+
+/// The terminal regained focus and reports `appearance`: follow a change of
+/// its background between light and dark, unless a theme was chosen.
+pub fn follow_terminal_appearance(state: &mut TuiState, appearance: Appearance) {
+    if !state.follow_terminal {
+        return;
+    }
+    let palette = LocusPalette::for_appearance(appearance);
+    if palette != state.palette {
+        set_palette(state, palette);
+    }
+}
+
+/// Handle `line` if it is a theme command: open the gallery, or apply and
+/// save the named theme. Returns false for any other input.
+pub fn request_theme(state: &mut TuiState, line: &str) -> bool {
+    let Some(rest) = line.trim().strip_prefix(THEME_COMMAND) else {
+        return false;
+    };
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return false;
+    }
+    let name = rest.trim();
+    if name.is_empty() {
+        state.theme_picker = Some(ThemePicker::new(gallery(state), state.palette.clone()));
+        state.needs_redraw = true;
+        return true;
+    }
+    match theme_palette(name, project_root(state).as_deref()) {
+        Ok(palette) => {
+            state.follow_terminal = false;
+            set_palette(state, palette);
+            save_choice(state, name);
+        }
+        Err(e) => set_status(state, tf("status.theme_failed", &[("error", &e)])),
+    }
+    true
+}
+
+/// Preview the highlighted theme.
+pub fn preview_selected(state: &mut TuiState) {
+    if let Some((_, palette)) = state.theme_picker.as_ref().and_then(|p| p.selected()) {
+        let palette = palette.clone();
+        set_palette(state, palette);
+    }
+}
+
+/// Keep the highlighted theme and save it. Closes the gallery.
+pub fn apply_selected(state: &mut TuiState) {
+    let Some((name, palette)) = state
+        .theme_picker
+        .take()
+        .and_then(|picker| picker.selected().cloned())
+    else {
+        return;
+    };
+    state.follow_terminal = false;
+    set_palette(state, palette);
+    save_choice(state, &name);
+}
+
+/// Close the gallery and go back to the theme in use before it opened.
+pub fn cancel_theme_picker(state: &mut TuiState) {
+    if let Some(picker) = state.theme_picker.take() {
+        set_palette(state, picker.original);
+    }
+}
+
+fn set_palette(state: &mut TuiState, palette: LocusPalette) {
+    state.palette = palette;
+    state.cache_dirty = true;
+    state.needs_redraw = true;
+}
+
+/// Save `name` as `LOCUS_THEME` in the global config table.
+fn save_choice(state: &mut TuiState, name: &str) {
+    let saved = crate::setup::global_locus_dir().and_then(|locus_dir| {
+        let conn = locus_core::db::open_db_at(&locus_dir)?;
+        locus_core::db::set_config(&conn, THEME_ENV, name)?;
+        let config = locus_core::db::get_config(&conn)?;
+        locus_core::db::sync_env_file(&locus_dir, &config)?;
+        Ok(())
+    });
+    let status = match saved {
+        Ok(()) => tf("status.theme_set", &[("theme", &name)]),
+        Err(e) => tf("status.theme_failed", &[("error", &e.to_string())]),
+    };
+    set_status(state, status);
+}
+
+fn set_status(state: &mut TuiState, status: String) {
+    state.status = status;
+    state.status_set_at = Some(std::time::Instant::now());
+    state.status_permanent = false;
+    state.needs_redraw = true;
+}
+
+/// The gallery popup, drawn over the chat in the previewed theme.
+pub fn draw_theme_picker(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let Some(picker) = state.theme_picker.as_ref() else {
+        return;
+    };
+    let palette = &state.palette;
+    let muted = text_muted_style(palette.text_muted);
+
+    let mut lines: Vec<Line<'static>> = picker
+        .themes
+        .iter()
+        .enumerate()
+        .map(|(i, (name, theme))| {
+            let selected = i == picker.selected;
+            let style = if selected {
+                text_style(palette.accent)
+            } else {
+                text_style(palette.text)
+            };
+            // A swatch of the theme's own colors
+            let swatch = [theme.background, theme.accent, theme.success, theme.danger]
+                .into_iter()
+                .map(|color| Span::styled("█".to_string(), text_style(color)));
+            let mut spans = vec![Span::styled(
+                if selected { "› " } else { "  " }.to_string(),
+                style,
+            )];
+            spans.extend(swatch);
+            spans.push(Span::styled(format!(" {}", name), style));
+            if *theme == picker.original {
+                spans.push(Span::styled(
+                    format!("  {}", t("theme_picker.current")),
+                    muted,
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    lines.push(Line::from(""));
+    let key = |k: &str| Span::styled(k.to_string(), text_style(palette.accent));
+    lines.push(Line::from(vec![
+        key("↑↓"),
+        Span::styled(format!(" {}  ", t("theme_picker.preview")), muted),
+        key("Enter"),
+        Span::styled(format!(" {}  ", t("theme_picker.apply")), muted),
+        key("Esc"),
+        Span::styled(format!(" {}", t("shortcut.cancel")), muted),
+    ]));
+
+    let width = area.width.saturating_sub(4).min(50);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("theme_picker.title")))
+        .border_style(text_style(palette.accent))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gallery_previews_and_esc_restores() {
+        let mut state = TuiState::new();
+        assert!(!request_theme(&mut state, ":themes"));
+        assert!(request_theme(&mut state, ":theme"));
+        let picker = state.theme_picker.as_ref().unwrap();
+        assert_eq!(picker.selected().unwrap().0, "dark");
+
+        state.theme_picker.as_mut().unwrap().down();
+        preview_selected(&mut state);
+        assert_eq!(state.palette, LocusPalette::locus_light());
+
+        cancel_theme_picker(&mut state);
+        assert!(state.theme_picker.is_none());
+        assert_eq!(state.palette, LocusPalette::locus_dark());
+
+        assert!(request_theme(&mut state, ":theme neon"));
+        assert_eq!(state.palette, LocusPalette::locus_dark());
+        assert!(state.status.contains("neon"));
+    }
+
+    #[test]
+    fn follows_terminal_until_theme_chosen() {
+        let mut state = TuiState::new();
+        follow_terminal_appearance(&mut state, Appearance::Light);
+        assert_eq!(state.palette, LocusPalette::locus_dark());
+
+        state.follow_terminal = true;
+        follow_terminal_appearance(&mut state, Appearance::Light);
+        assert_eq!(state.palette, LocusPalette::locus_light());
+    }
+}
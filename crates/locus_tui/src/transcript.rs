@@ -0,0 +1,493 @@
+//! Plain-text mirror of the chat transcript (`.locus/live-transcript.txt`).
+//!
+//! Written while the session runs so screen readers, `tail -f` in another pane or
+//! stream overlays can follow along without parsing the TUI. The format is fixed
+//! and not localized, so tools can rely on it:
+//!
+//! ```text
+//! [14:03:10] You:
+//!   fix the failing test
+//!
+//! [14:03:12] Assistant:
+//!   Looking at the test output first.
+//!
+//! [14:03:12] Tool bash started: cargo test
+//!
+//! [14:03:14] Tool bash done in 1.2s
+//!
+//! [14:03:20] Turn complete: 1,234 tokens (800 in, 434 out)
+//!
+//! ```
+//!
+//! - Every entry starts with `[HH:MM:SS] ` at column 0 and ends with a blank line
+//! - Message bodies are indented two spaces; assistant text is appended as it streams
+//! - Thinking, status-bar messages and provider traces are not mirrored
+//! - The file is truncated when the TUI starts; write errors never interrupt the TUI
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use locus_core::{
+    ApprovalDecision, ApprovalRequest, ConflictDecision, ConflictRequest, SessionEvent,
+};
+
+use crate::messages::memory::session_label;
+use crate::runtime_events::{format_token_count, tool_detail, tool_summary};
+
+/// File name under `.locus/`.
+pub const TRANSCRIPT_FILE: &str = "live-transcript.txt";
+
+/// Config / env key that turns the mirror on for `locus tui` (`1` or `true`).
+pub const TRANSCRIPT_ENV: &str = "LOCUS_TRANSCRIPT";
+
+/// Appends transcript entries to a text file.
+#[derive(Debug)]
+pub struct TranscriptMirror {
+    path: PathBuf,
+    file: File,
+    /// An assistant entry is open and receiving text deltas.
+    streaming: bool,
+    /// Tool names by tool_use_id, for the "done" entry.
+    tools: HashMap<String, String>,
+}
+
+impl TranscriptMirror {
+    /// Create (or truncate) the transcript at `path`.
+    pub fn create(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(&path)?;
+        Ok(Self {
+            path,
+            file,
+            streaming: false,
+            tools: HashMap::new(),
+        })
+    }
+
+    /// Create the transcript at `<repo_root>/.locus/live-transcript.txt`.
+    pub fn for_repo(repo_root: &Path) -> io::Result<Self> {
+        Self::create(repo_root.join(".locus").join(TRANSCRIPT_FILE))
+    }
+
+    /// Whether `LOCUS_TRANSCRIPT` asks for the mirror.
+    pub fn enabled_by_env() -> bool {
+        std::env::var(TRANSCRIPT_ENV)
+            .map(|v| {
+                matches!(
+                    v.trim().to_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record_user(&mut self, text: &str) {
+        self.entry("You:", Some(text));
+    }
+
+    /// A note attached to a tool result (Ctrl+A).
+    pub fn record_note(&mut self, tool_name: &str, note: &str) {
+        self.entry(&format!("Note on {}:", tool_name), Some(note));
+    }
+
+    pub fn record_separator(&mut self, label: &str) {
+        self.entry(&format!("--- {} ---", label), None);
+    }
+
+    pub fn record_approval(
+        &mut self,
+        request: &ApprovalRequest,
+        decision: ApprovalDecision,
+        message: Option<&str>,
+    ) {
+        let verb = match decision {
+            ApprovalDecision::Allow => "allowed",
+            ApprovalDecision::AllowAlways => "always allowed",
+            ApprovalDecision::Deny => "denied",
+        };
+        self.entry(
+            &format!("Approval {} for {}", verb, request.tool_name),
+            message,
+        );
+    }
+
+    pub fn record_conflict(&mut self, request: &ConflictRequest, decision: ConflictDecision) {
+        let verb = match decision {
+            ConflictDecision::Rebase => "rebased onto user changes",
+            ConflictDecision::Overwrite => "overwrote user changes",
+            ConflictDecision::Abort => "aborted",
+        };
+        self.entry(&format!("Edit to {} {}", request.path, verb), None);
+    }
+
+    /// Mirror a runtime event. Call before the event is applied to the TUI state.
+    pub fn record_event(&mut self, event: &SessionEvent) {
+        match event {
+            SessionEvent::TextDelta { text } => self.stream(text),
+            SessionEvent::ToolStart { tool_use } => {
+                self.tools
+                    .insert(tool_use.id.clone(), tool_use.name.clone());
+                let line = match tool_summary(tool_use).or_else(|| tool_detail(tool_use)) {
+                    Some(summary) => {
+                        format!("Tool {} started: {}", tool_use.name, one_line(&summary))
+                    }
+                    None => format!("Tool {} started", tool_use.name),
+                };
+                self.entry(&line, None);
+            }
+            SessionEvent::ToolDone {
+                tool_use_id,
+                result,
+            } => {
+                let name = self
+                    .tools
+                    .remove(tool_use_id)
+                    .unwrap_or_else(|| "call".to_string());
+                let outcome = if result.is_error {
+                    "failed after"
+                } else {
+                    "done in"
+                };
+                self.entry(
+                    &format!(
+                        "Tool {} {} {}",
+                        name,
+                        outcome,
+                        format_duration(result.duration_ms)
+                    ),
+                    None,
+                );
+            }
+            SessionEvent::MemoryRecall { query, items_found } => self.entry(
+                &format!(
+                    "Memory recalled {} item(s): {}",
+                    items_found,
+                    one_line(query)
+                ),
+                None,
+            ),
+            SessionEvent::MemoryBudget {
+                budget_tokens,
+                used_tokens,
+                items,
+            } => {
+                let limit = if *budget_tokens == 0 {
+                    "no limit".to_string()
+                } else {
+                    format!("budget {}", budget_tokens)
+                };
+                let lines: Vec<String> = items
+                    .iter()
+                    .map(|i| {
+                        let mark = if i.included { "+" } else { "-" };
+                        format!("{} {:>5} tokens  {}", mark, i.tokens, one_line(&i.summary))
+                    })
+                    .collect();
+                self.entry(
+                    &format!("Memory used {} tokens ({}):", used_tokens, limit),
+                    Some(&lines.join("\n")),
+                );
+            }
+            SessionEvent::MemoryStore { summary, .. } => {
+                self.entry(&format!("Memory stored: {}", one_line(summary)), None)
+            }
+            SessionEvent::ToolTimedOut {
+                tool_name,
+                timeout_secs,
+                ..
+            } => self.entry(
+                &format!(
+                    "Tool timed out: {} after {}s (cancelled)",
+                    tool_name, timeout_secs
+                ),
+                None,
+            ),
+            SessionEvent::Throttled { reason, .. } => {
+                self.entry(&format!("Sub-agent throttled: {}", one_line(reason)), None)
+            }
+            SessionEvent::ApprovalRequested { request } => {
+                let subject = request
+                    .args
+                    .get("command")
+                    .or(request.args.get("path"))
+                    .or(request.args.get("url"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| format!(": {}", one_line(s)))
+                    .unwrap_or_default();
+                self.entry(
+                    &format!(
+                        "Approval needed for {} ({}){}",
+                        request.tool_name,
+                        request.permission.as_str(),
+                        subject
+                    ),
+                    None,
+                );
+            }
+            SessionEvent::ConflictDetected { request } => {
+                let clashes = match request.conflicts {
+                    0 => "merges cleanly".to_string(),
+                    1 => "1 clashing hunk".to_string(),
+                    n => format!("{} clashing hunks", n),
+                };
+                self.entry(
+                    &format!(
+                        "Edit conflict in {}: changed since the agent read it ({})",
+                        request.path, clashes
+                    ),
+                    None,
+                );
+            }
+            SessionEvent::GuardrailViolation {
+                violations,
+                reverted,
+            } => {
+                let outcome = if *reverted {
+                    "edits reverted"
+                } else {
+                    "run stopped"
+                };
+                self.entry(
+                    &format!("Guardrail violated ({}):", outcome),
+                    Some(&violations.join("\n")),
+                );
+            }
+            SessionEvent::InstructionsLoaded { files } => {
+                self.entry("Project instructions:", Some(&files.join("\n")));
+            }
+            SessionEvent::SecretsRedacted { secrets } => {
+                let lines: Vec<String> = secrets
+                    .iter()
+                    .map(|s| format!("{} {}", s.placeholder, s.kind))
+                    .collect();
+                self.entry("Secrets hidden from the model:", Some(&lines.join("\n")));
+            }
+            SessionEvent::SimilarWork { work } => {
+                let matched = &work.matched;
+                self.entry(
+                    &format!(
+                        "Similar past work: session {} asked \"{}\"",
+                        session_label(matched),
+                        one_line(&matched.title)
+                    ),
+                    Some(&work.files_modified.join("\n")),
+                );
+            }
+            SessionEvent::Rewound { summary } => {
+                let files: Vec<String> = summary
+                    .restored
+                    .iter()
+                    .map(|path| format!("restored {}", path))
+                    .chain(
+                        summary
+                            .removed
+                            .iter()
+                            .map(|path| format!("removed {}", path)),
+                    )
+                    .chain(summary.kept.iter().map(|path| format!("kept {}", path)))
+                    .collect();
+                self.entry(
+                    &format!("--- Rewound {} turns ---", summary.turns),
+                    (!files.is_empty()).then(|| files.join("\n")).as_deref(),
+                );
+            }
+            SessionEvent::PlanReady { plan } => {
+                self.entry(&format!("Plan saved to {}", plan.path), Some(&plan.text));
+            }
+            SessionEvent::TurnEnd => self.end_stream(),
+            SessionEvent::Error { error } => self.entry("Error:", Some(error)),
+            SessionEvent::SessionEnd {
+                prompt_tokens,
+                completion_tokens,
+                ..
+            } => {
+                let total = prompt_tokens + completion_tokens;
+                let line = if total > 0 {
+                    format!(
+                        "Turn complete: {} tokens ({} in, {} out)",
+                        format_token_count(total),
+                        format_token_count(*prompt_tokens),
+                        format_token_count(*completion_tokens)
+                    )
+                } else {
+                    "Turn complete".to_string()
+                };
+                self.entry(&line, None);
+            }
+            SessionEvent::TurnStart { .. }
+            | SessionEvent::ThinkingDelta { .. }
+            | SessionEvent::Status { .. }
+            | SessionEvent::ToolProgress { .. }
+            | SessionEvent::ProviderCall { .. }
+            | SessionEvent::TaskListUpdated { .. }
+            | SessionEvent::SubAgents { .. }
+            | SessionEvent::SubAgentOutput { .. }
+            | SessionEvent::TokenUsage { .. }
+            | SessionEvent::ModelInUse { .. }
+            | SessionEvent::ModelList { .. }
+            | SessionEvent::McpPrompts { .. }
+            | SessionEvent::PinnedFiles { .. }
+            | SessionEvent::MemorySearch { .. }
+            | SessionEvent::MemoriesRetrieved { .. }
+            | SessionEvent::ProjectInsights { .. }
+            | SessionEvent::TurnDiff { .. }
+            | SessionEvent::HunkReverted { .. } => {}
+        }
+    }
+
+    fn stream(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let mut out = String::new();
+        if !self.streaming {
+            self.streaming = true;
+            out.push_str(&format!("{} Assistant:\n  ", timestamp()));
+        }
+        out.push_str(&text.replace('\n', "\n  "));
+        self.write(&out);
+    }
+
+    fn end_stream(&mut self) {
+        if std::mem::take(&mut self.streaming) {
+            self.write("\n\n");
+        }
+    }
+
+    /// Write `[time] header`, then the indented `body` if any, then a blank line.
+    fn entry(&mut self, header: &str, body: Option<&str>) {
+        self.end_stream();
+        let mut out = format!("{} {}\n", timestamp(), header);
+        if let Some(body) = body {
+            for line in body.trim_end().lines() {
+                out.push_str("  ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+        self.write(&out);
+    }
+
+    fn write(&mut self, text: &str) {
+        // Best effort: a full disk or removed file must not take the TUI down.
+        let _ = self.file.write_all(text.as_bytes());
+        let _ = self.file.flush();
+    }
+}
+
+fn timestamp() -> String {
+    chrono::Local::now().format("[%H:%M:%S]").to_string()
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locus_core::{Permission, Role, ToolResultData, ToolUse};
+
+    /// Transcript contents with the `[HH:MM:SS]` stamps replaced by `[t]`.
+    fn contents(mirror: &TranscriptMirror) -> String {
+        let text = std::fs::read_to_string(mirror.path()).unwrap();
+        text.lines()
+            .map(|line| match line.strip_prefix('[') {
+                Some(rest) if rest.get(8..9) == Some("]") => format!("[t]{}", &rest[9..]),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn temp_mirror(name: &str) -> (PathBuf, TranscriptMirror) {
+        let dir =
+            std::env::temp_dir().join(format!("locus-transcript-{}-{}", name, std::process::id()));
+        let mirror = TranscriptMirror::for_repo(&dir).unwrap();
+        (dir, mirror)
+    }
+
+    #[test]
+    fn mirrors_a_turn_with_stable_formatting() {
+        let (dir, mut mirror) = temp_mirror("turn");
+        assert!(mirror.path().ends_with(".locus/live-transcript.txt"));
+
+        mirror.record_user("fix the test\nplease");
+        mirror.record_event(&SessionEvent::turn_start(Role::Assistant));
+        mirror.record_event(&SessionEvent::text_delta("Looking "));
+        mirror.record_event(&SessionEvent::text_delta("now.\nRunning tests."));
+        mirror.record_event(&SessionEvent::tool_start(ToolUse::new(
+            "t1",
+            "bash",
+            serde_json::json!({"command": "cargo   test"}),
+        )));
+        mirror.record_event(&SessionEvent::ToolDone {
+            tool_use_id: "t1".into(),
+            result: ToolResultData::success(serde_json::json!({}), 1200),
+        });
+        mirror.record_event(&SessionEvent::TurnEnd);
+        mirror.record_event(&SessionEvent::SessionEnd {
+            status: locus_core::SessionStatus::Completed,
+            prompt_tokens: 800,
+            completion_tokens: 434,
+        });
+
+        assert_eq!(
+            contents(&mirror),
+            "[t] You:\n  fix the test\n  please\n\n\
+             [t] Assistant:\n  Looking now.\n  Running tests.\n\n\
+             [t] Tool bash started: cargo test\n\n\
+             [t] Tool bash done in 1.2s\n\n\
+             [t] Turn complete: 1,234 tokens (800 in, 434 out)\n"
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn mirrors_approvals_and_errors() {
+        let (dir, mut mirror) = temp_mirror("approval");
+        let request = ApprovalRequest::new(
+            "bash",
+            Permission::Execute,
+            serde_json::json!({"command": "rm -rf build"}),
+        );
+        mirror.record_event(&SessionEvent::approval_requested(request.clone()));
+        mirror.record_approval(&request, ApprovalDecision::Deny, Some("build is needed"));
+        mirror.record_event(&SessionEvent::Error {
+            error: "provider timed out".into(),
+        });
+        mirror.record_separator("New session");
+        let conflict = ConflictRequest::new("edit_file", "src/lib.rs", "a\n", "b\n", "c\n");
+        mirror.record_event(&SessionEvent::conflict_detected(conflict.clone()));
+        mirror.record_conflict(&conflict, ConflictDecision::Abort);
+
+        assert_eq!(
+            contents(&mirror),
+            "[t] Approval needed for bash (execute): rm -rf build\n\n\
+             [t] Approval denied for bash\n  build is needed\n\n\
+             [t] Error:\n  provider timed out\n\n\
+             [t] --- New session ---\n\n\
+             [t] Edit conflict in src/lib.rs: changed since the agent read it (1 clashing hunk)\n\n\
+             [t] Edit to src/lib.rs aborted\n"
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,85 @@
+//! Fuzzy matching for pickers: the query's characters in order anywhere in
+//! the candidate, ignoring case, ranked so the expected pick comes first.
+
+/// Score of `candidate` for `query`, higher is better; None when the query's
+/// characters don't all appear in it in order. Runs of consecutive
+/// characters and matches at the start of a word or path segment count
+/// extra, and shorter candidates rank above longer ones.
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let query: Vec<char> = query.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let Some(&first) = query.first() else {
+        return Some(-(candidate.len() as i64));
+    };
+    // Best of the greedy matches from each place the first character matches
+    let best = (0..candidate.len())
+        .filter(|&start| candidate[start].to_ascii_lowercase() == first)
+        .filter_map(|start| greedy_score(&candidate, &query, start))
+        .max()?;
+    Some(best * 100 - candidate.len() as i64)
+}
+
+fn greedy_score(candidate: &[char], query: &[char], start: usize) -> Option<i64> {
+    let mut score = 0;
+    let mut wanted = query.iter().peekable();
+    let mut prev_matched = false;
+    for at in start..candidate.len() {
+        let Some(&&c) = wanted.peek() else {
+            break;
+        };
+        let matched = candidate[at].to_ascii_lowercase() == c;
+        if matched {
+            wanted.next();
+            score += 1;
+            if prev_matched {
+                score += 5;
+            }
+            if at == 0 || matches!(candidate[at - 1], '/' | '_' | '-' | '.' | ' ' | ':') {
+                score += 8;
+            }
+        }
+        prev_matched = matched;
+    }
+    wanted.peek().is_none().then_some(score)
+}
+
+/// `candidates` matching `query`, best first (ties keep their order), at
+/// most `limit`. An empty query keeps the first `limit` as they are.
+pub fn fuzzy_filter<'a>(
+    candidates: impl IntoIterator<Item = &'a str>,
+    query: &str,
+    limit: usize,
+) -> Vec<&'a str> {
+    let mut scored: Vec<(i64, usize, &str)> = candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, c)| fuzzy_score(c, query).map(|score| (score, i, c)))
+        .collect();
+    if !query.is_empty() {
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    }
+    scored.into_iter().take(limit).map(|(_, _, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_segment_starts_and_runs_first() {
+        assert!(fuzzy_score("src/lib.rs", "xyz").is_none());
+        assert!(fuzzy_score("src/lib.rs", "SLR").is_some());
+        let files = [
+            "crates/locus_tui/src/state.rs",
+            "src/lib.rs",
+            "docs/style.md",
+            "crates/locus_core/src/lib.rs",
+        ];
+        assert_eq!(
+            fuzzy_filter(files, "lib", 3),
+            ["src/lib.rs", "crates/locus_core/src/lib.rs"]
+        );
+        assert_eq!(fuzzy_filter(files, "st", 1), ["docs/style.md"]);
+        assert_eq!(fuzzy_filter(files, "", 2), &files[..2]);
+    }
+}
@@ -1,5 +1,6 @@
 //! TUI view: header (fixed top), scrollable chat body, shortcut + input (fixed bottom).
 
+use locus_core::{ApprovalRequest, ConflictRequest, ProviderCall};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -8,16 +9,17 @@ use ratatui::{
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use crate::i18n::{t, tf};
 use crate::layouts::{
     CHAT_MESSAGE_SPACING, ChatsLayout, HEADER_STATUS_READY, HEADER_TITLE, INPUT_ICON,
-    INPUT_PADDING_H, background_style, block_for_input_bordered, draw_setup,
+    INPUT_PADDING_H, background_style, block_for_input_bordered, danger_style, draw_setup,
     main_splits_with_padding_and_footer_height, render_header, shortcut_inner_rect, shortcut_line,
-    text_muted_style, text_style, vertical_split, warning_style,
+    success_style, text_muted_style, text_style, vertical_split, warning_style,
 };
 use crate::messages::edit_diff::DIFF_PAGE_SIZE;
 use crate::messages::tools::ToolCallStatus;
 use crate::messages::{
-    ai_message, ai_think_message, edit_diff, error, memory, meta_tools, tools, user,
+    ai_message, ai_think_message, edit_diff, error, insights, memory, meta_tools, tools, user,
 };
 use crate::state::{ChatItem, Screen, TuiState};
 use crate::utils::collapse_repeated_chars;
@@ -42,21 +44,21 @@ impl LivePhase {
 
     fn header_label(self) -> &'static str {
         match self {
-            Self::Ready => HEADER_STATUS_READY,
-            Self::Reviewing => "Reviewing",
-            Self::Preparing => "Preparing",
-            Self::Thinking => "Thinking",
-            Self::Responding => "Responding",
-            Self::Tooling => "Running tools",
+            Self::Ready => t(HEADER_STATUS_READY),
+            Self::Reviewing => t("phase.reviewing"),
+            Self::Preparing => t("phase.preparing"),
+            Self::Thinking => t("phase.thinking"),
+            Self::Responding => t("phase.responding"),
+            Self::Tooling => t("phase.tooling"),
         }
     }
 
     fn footer_label(self) -> Option<&'static str> {
         match self {
-            Self::Preparing => Some("Preparing"),
-            Self::Thinking => Some("Thinking"),
-            Self::Responding => Some("Responding"),
-            Self::Tooling => Some("Running tools"),
+            Self::Preparing => Some(t("phase.preparing")),
+            Self::Thinking => Some(t("phase.thinking")),
+            Self::Responding => Some(t("phase.responding")),
+            Self::Tooling => Some(t("phase.tooling")),
             Self::Ready | Self::Reviewing => None,
         }
     }
@@ -86,11 +88,11 @@ fn live_phase(state: &TuiState) -> LivePhase {
 
 fn header_section_label(state: &TuiState, phase: LivePhase) -> &'static str {
     if !state.auto_scroll {
-        "manual review"
+        t("header.section.review")
     } else if phase.is_active() {
-        "live session"
+        t("header.section.live")
     } else {
-        "main workspace"
+        t("header.section.main")
     }
 }
 
@@ -118,12 +120,12 @@ fn preparing_indicator_lines(
                 format!("{} ", crate::animation::spinner_frame(frame_count)),
                 warning,
             ),
-            Span::styled("preparing response".to_string(), text_style(palette.text)),
+            Span::styled(t("chat.preparing"), text_style(palette.text)),
         ]),
         Line::from(vec![
             rail,
             Span::raw("  "),
-            Span::styled("waiting for the first token", muted),
+            Span::styled(t("chat.waiting_first_token"), muted),
         ]),
     ]
 }
@@ -134,50 +136,60 @@ fn empty_state_lines(palette: &crate::theme::LocusPalette) -> Vec<Line<'static>>
         Line::from(vec![
             Span::styled("●".to_string(), text_style(palette.accent)),
             Span::raw(" "),
-            Span::styled("locus.codes".to_string(), text_style(palette.text)),
+            Span::styled(t(HEADER_TITLE), text_style(palette.text)),
         ]),
         Line::from(vec![Span::styled(
-            "quiet terminal workspace for code, tools, and memory".to_string(),
+            t("empty.tagline"),
             text_muted_style(palette.text_muted),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Try".to_string(), text_style(palette.accent)),
+            Span::styled(t("empty.try"), text_style(palette.accent)),
             Span::styled(
-                ": review the last changes".to_string(),
+                format!(": {}", t("empty.try.review")),
                 text_muted_style(palette.text_muted),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Try".to_string(), text_style(palette.accent)),
+            Span::styled(t("empty.try"), text_style(palette.accent)),
             Span::styled(
-                ": explain this crate".to_string(),
+                format!(": {}", t("empty.try.explain")),
                 text_muted_style(palette.text_muted),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Try".to_string(), text_style(palette.accent)),
+            Span::styled(t("empty.try"), text_style(palette.accent)),
             Span::styled(
-                ": patch the failing test".to_string(),
+                format!(": {}", t("empty.try.patch")),
                 text_muted_style(palette.text_muted),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Enter".to_string(), text_style(palette.text)),
-            Span::styled(": send", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!(": {}", t("shortcut.send")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled("  ·  ".to_string(), text_muted_style(palette.text_disabled)),
             Span::styled("Ctrl+D".to_string(), text_style(palette.text)),
-            Span::styled(": logs", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!(": {}", t("shortcut.logs")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled("  ·  ".to_string(), text_muted_style(palette.text_disabled)),
             Span::styled("Ctrl+N".to_string(), text_style(palette.text)),
-            Span::styled(": new session", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!(": {}", t("shortcut.new_session")),
+                text_muted_style(palette.text_muted),
+            ),
         ]),
     ]
 }
 
 const INPUT_BORDER_HEIGHT: u16 = 2;
 const INPUT_SHORTCUT_HEIGHT: u16 = 1;
+const STATUS_BAR_HEIGHT: u16 = 1;
 const INPUT_MAX_CONTENT_LINES: usize = 6;
 
 #[derive(Debug, Clone)]
@@ -275,7 +287,7 @@ fn input_footer_height(area_width: u16, buffer: &str, cursor_byte: usize) -> u16
         .saturating_sub(INPUT_PADDING_H.saturating_mul(2));
     let text_width = inner_width.saturating_sub(icon_width) as usize;
     let visual = input_visual_state(buffer, cursor_byte, text_width.max(1));
-    INPUT_BORDER_HEIGHT + visual.lines.len() as u16 + INPUT_SHORTCUT_HEIGHT
+    INPUT_BORDER_HEIGHT + visual.lines.len() as u16 + INPUT_SHORTCUT_HEIGHT + STATUS_BAR_HEIGHT
 }
 
 fn message_spacing_between(previous: &ChatItem, current: &ChatItem) -> usize {
@@ -306,12 +318,15 @@ fn separator_line(
     ])
 }
 
-/// Draw the full TUI: main chat, onboarding, debug traces, or web automation depending on state.screen.
+/// Draw the full TUI: main chat, onboarding, debug traces, provider calls, web automation, the task board, the plan, the memory trace, the diff review or the agents depending on state.screen.
 pub fn draw(frame: &mut Frame, state: &mut TuiState, area: Rect) {
     match state.screen {
         Screen::Onboarding => draw_onboarding(frame, state, area),
         Screen::Setup => draw_setup(frame, state, area),
-        Screen::DebugTraces => draw_debug_traces(frame, state, area),
+        Screen::DebugTraces => {
+            crate::logs::draw_logs(frame, &mut state.logs, area, &state.palette);
+        }
+        Screen::ProviderCalls => draw_provider_calls(frame, state, area),
         Screen::WebAutomation => {
             crate::web_automation::draw_web_automation(
                 frame,
@@ -320,8 +335,328 @@ pub fn draw(frame: &mut Frame, state: &mut TuiState, area: Rect) {
                 &state.palette,
             );
         }
-        Screen::Main => draw_main(frame, state, area),
+        Screen::TaskBoard => {
+            crate::task_board::draw_task_board(frame, &mut state.task_board, area, &state.palette);
+        }
+        Screen::Plan => crate::plan::draw_plan(frame, state, area),
+        Screen::MemoryTrace => crate::memory_trace::draw_memory_trace(
+            frame,
+            &mut state.memory_trace,
+            &state.input_buffer,
+            area,
+            &state.palette,
+        ),
+        Screen::DiffReview => crate::diff_review::draw_diff_review(
+            frame,
+            &mut state.diff_review,
+            area,
+            &state.palette,
+        ),
+        Screen::Agents => {
+            crate::agents::draw_agents(frame, &mut state.agents, area, &state.palette);
+        }
+        Screen::Main => {
+            draw_main(frame, state, area);
+            if !state.pending_approvals.is_empty() {
+                draw_approval_dialog(frame, state, area);
+            } else if !state.pending_conflicts.is_empty() {
+                draw_conflict_dialog(frame, state, area);
+            } else if state.session_picker.is_some() {
+                crate::session_picker::draw_session_picker(frame, state, area);
+            } else if state.model_picker.is_some() {
+                crate::model_picker::draw_model_picker(frame, state, area);
+            } else if state.theme_picker.is_some() {
+                crate::theme_picker::draw_theme_picker(frame, state, area);
+            } else if state.file_finder.popup.is_some() {
+                let footer_height =
+                    input_footer_height(area.width, &state.input_buffer, state.input_cursor);
+                crate::file_finder::draw_file_finder(frame, state, area, footer_height);
+            } else if state.slash_menu.popup.is_some() {
+                let footer_height =
+                    input_footer_height(area.width, &state.input_buffer, state.input_cursor);
+                crate::slash::draw_slash_menu(frame, state, area, footer_height);
+            }
+        }
+    }
+    crate::command_palette::draw_command_palette(frame, state, area);
+}
+
+/// Argument lines shown in the approval dialog; long JSON is cut.
+const APPROVAL_ARG_LINES: usize = 8;
+
+/// Body of the approval dialog: tool, permission, the change a write would make
+/// (or the arguments), and the key hints. `message` is the denial message being
+/// typed, when the user pressed `m`.
+fn approval_dialog_lines(
+    request: &ApprovalRequest,
+    queued: usize,
+    message: Option<&str>,
+    palette: &crate::theme::LocusPalette,
+) -> Vec<Line<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                tools::format_tool_name(&request.tool_name),
+                text_style(palette.text),
+            ),
+            Span::styled(
+                format!(
+                    "  {}",
+                    tf(
+                        "approval.needs_permission",
+                        &[("permission", &request.permission)]
+                    )
+                ),
+                warning_style(palette.warning),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    if let Some(preview) = &request.preview {
+        lines.push(Line::from(vec![
+            Span::styled("│ ".to_string(), text_muted_style(palette.border_variant)),
+            Span::styled(preview.path.clone(), text_style(palette.text)),
+        ]));
+        lines.extend(conflict_diff_lines(
+            &preview.before,
+            &preview.after,
+            palette,
+        ));
+    } else {
+        // Show the field a user decides on directly; fall back to the whole argument object.
+        let args = ["command", "path", "url"]
+            .iter()
+            .find_map(|key| request.args.get(*key).and_then(|v| v.as_str()))
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| {
+                serde_json::to_string_pretty(&request.args)
+                    .unwrap_or_else(|_| request.args.to_string())
+            });
+        let arg_lines: Vec<&str> = args.lines().collect();
+        for line in arg_lines.iter().take(APPROVAL_ARG_LINES) {
+            lines.push(Line::from(vec![
+                Span::styled("│ ".to_string(), text_muted_style(palette.border_variant)),
+                Span::styled(line.to_string(), text_style(palette.text)),
+            ]));
+        }
+        if arg_lines.len() > APPROVAL_ARG_LINES {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "│ … {}",
+                    tf(
+                        "approval.more_lines",
+                        &[("count", &(arg_lines.len() - APPROVAL_ARG_LINES))]
+                    )
+                ),
+                muted,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let key = |k: &str| Span::styled(k.to_string(), text_style(palette.accent));
+    if let Some(message) = message {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", t("approval.message")), muted),
+            Span::styled(format!("{}▏", message), text_style(palette.text)),
+        ]));
+        lines.push(Line::from(vec![
+            key("Enter"),
+            Span::styled(format!(" {}  ", t("approval.deny_with_message")), muted),
+            key("Esc"),
+            Span::styled(format!(" {}", t("approval.back")), muted),
+        ]));
+    } else {
+        lines.push(Line::from(vec![
+            key("y"),
+            Span::styled(format!(" {}  ", t("approval.allow")), muted),
+            key("a"),
+            Span::styled(format!(" {}  ", t("approval.allow_always")), muted),
+            key("n"),
+            Span::styled("/".to_string(), muted),
+            key("Esc"),
+            Span::styled(format!(" {}  ", t("approval.deny")), muted),
+            key("m"),
+            Span::styled(format!(" {}", t("approval.deny_with_message")), muted),
+        ]));
+    }
+    if queued > 0 {
+        lines.push(Line::from(Span::styled(
+            tf("approval.queued", &[("count", &queued)]),
+            muted,
+        )));
+    }
+    lines
+}
+
+/// Confirmation dialog for the front of [TuiState::pending_approvals], drawn over the chat.
+fn draw_approval_dialog(frame: &mut Frame, state: &TuiState, area: Rect) {
+    use ratatui::widgets::{Borders, Clear};
+
+    let Some(request) = state.pending_approvals.front() else {
+        return;
+    };
+    let palette = &state.palette;
+    let message = state
+        .approval_denying
+        .then_some(state.input_buffer.as_str());
+    let lines = approval_dialog_lines(request, state.pending_approvals.len() - 1, message, palette);
+
+    let width = area.width.saturating_sub(4).min(80);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("approval.title")))
+        .border_style(warning_style(palette.warning))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+/// Changed lines shown per side in the conflict dialog, and for a write in the
+/// approval dialog.
+const CONFLICT_DIFF_LINES: usize = 8;
+
+/// Changed lines from `old` to `new` as `-`/`+` rows, at most [CONFLICT_DIFF_LINES].
+fn conflict_diff_lines(
+    old: &str,
+    new: &str,
+    palette: &crate::theme::LocusPalette,
+) -> Vec<Line<'static>> {
+    use crate::diff::{ChangeType, line_diff_with_numbers};
+
+    let rows: Vec<_> = line_diff_with_numbers(old, new)
+        .into_iter()
+        .filter(|row| row.change != ChangeType::Unchanged)
+        .collect();
+    let mut lines: Vec<Line<'static>> = rows
+        .iter()
+        .take(CONFLICT_DIFF_LINES)
+        .map(|row| {
+            let (sign, style) = match row.change {
+                ChangeType::Removed => ("- ", danger_style(palette.danger)),
+                _ => ("+ ", success_style(palette.success)),
+            };
+            Line::from(Span::styled(format!("{}{}", sign, row.text), style))
+        })
+        .collect();
+    if rows.len() > CONFLICT_DIFF_LINES {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  … {}",
+                tf(
+                    "approval.more_lines",
+                    &[("count", &(rows.len() - CONFLICT_DIFF_LINES))]
+                )
+            ),
+            text_muted_style(palette.text_muted),
+        )));
     }
+    lines
+}
+
+/// Body of the conflict dialog: the user's and the agent's changes to the base,
+/// how they merge, and the key hints.
+fn conflict_dialog_lines(
+    request: &ConflictRequest,
+    queued: usize,
+    palette: &crate::theme::LocusPalette,
+) -> Vec<Line<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let heading = |text: &str| Line::from(Span::styled(text.to_string(), text_style(palette.text)));
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(request.path.clone(), text_style(palette.text)),
+            Span::styled(
+                format!(
+                    "  {}",
+                    tf(
+                        "conflict.changed",
+                        &[("tool", &tools::format_tool_name(&request.tool_name))]
+                    )
+                ),
+                warning_style(palette.warning),
+            ),
+        ]),
+        Line::from(""),
+        heading(t("conflict.user_changes")),
+    ];
+    lines.extend(conflict_diff_lines(&request.base, &request.user, palette));
+    lines.push(Line::from(""));
+    lines.push(heading(t("conflict.agent_changes")));
+    lines.extend(conflict_diff_lines(&request.base, &request.agent, palette));
+    lines.push(Line::from(""));
+    lines.push(match request.conflicts {
+        0 => Line::from(Span::styled(
+            t("conflict.merges_cleanly"),
+            success_style(palette.success),
+        )),
+        n => Line::from(Span::styled(
+            tf("conflict.clashing", &[("count", &n)]),
+            warning_style(palette.warning),
+        )),
+    });
+
+    lines.push(Line::from(""));
+    let key = |k: &str| Span::styled(k.to_string(), text_style(palette.accent));
+    lines.push(Line::from(vec![
+        key("r"),
+        Span::styled(format!(" {}  ", t("conflict.rebase")), muted),
+        key("o"),
+        Span::styled(format!(" {}  ", t("conflict.overwrite")), muted),
+        key("x"),
+        Span::styled("/".to_string(), muted),
+        key("Esc"),
+        Span::styled(format!(" {}", t("conflict.abort")), muted),
+    ]));
+    if queued > 0 {
+        lines.push(Line::from(Span::styled(
+            tf("approval.queued", &[("count", &queued)]),
+            muted,
+        )));
+    }
+    lines
+}
+
+/// Three-way view for the front of [TuiState::pending_conflicts], drawn over the chat.
+fn draw_conflict_dialog(frame: &mut Frame, state: &TuiState, area: Rect) {
+    use ratatui::widgets::{Borders, Clear};
+
+    let Some(request) = state.pending_conflicts.front() else {
+        return;
+    };
+    let palette = &state.palette;
+    let lines = conflict_dialog_lines(request, state.pending_conflicts.len() - 1, palette);
+
+    let width = area.width.saturating_sub(4).min(100);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t("conflict.title")))
+        .border_style(warning_style(palette.warning))
+        .style(background_style(palette.surface_background));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
 }
 
 /// Onboarding screen: configure API keys and related settings. Shown when no LLM key is set.
@@ -348,9 +683,9 @@ fn draw_onboarding(frame: &mut Frame, state: &mut TuiState, area: Rect) {
         frame,
         chunks[0],
         palette,
-        HEADER_TITLE,
-        "first run",
-        "Configuration",
+        t(HEADER_TITLE),
+        t("onboarding.section"),
+        t("onboarding.status"),
         false,
         false,
     );
@@ -370,74 +705,135 @@ fn draw_onboarding(frame: &mut Frame, state: &mut TuiState, area: Rect) {
     let lines = vec![
         Line::from(vec![
             Span::styled("● ".to_string(), accent),
-            Span::styled("configure at least one provider".to_string(), normal),
+            Span::styled(t("onboarding.configure_provider"), normal),
         ]),
         Line::from(vec![
             Span::raw("  "),
-            Span::styled(
-                "the agent needs an API key before the chat can start",
-                muted,
-            ),
+            Span::styled(t("onboarding.needs_api_key"), muted),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::raw("  "),
             Span::styled("locus config api".to_string(), normal),
-            Span::styled("  add anthropic, zai, or tinyfish", muted),
+            Span::styled(format!("  {}", t("onboarding.config_api")), muted),
         ]),
         Line::from(vec![
             Span::raw("  "),
             Span::styled("locus config graph".to_string(), normal),
-            Span::styled("  set LocusGraph URL and secret", muted),
+            Span::styled(format!("  {}", t("onboarding.config_graph")), muted),
         ]),
         Line::from(vec![
             Span::raw("  "),
             Span::styled("locus config --help".to_string(), normal),
-            Span::styled("  inspect all configuration options", muted),
+            Span::styled(format!("  {}", t("onboarding.config_help")), muted),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("● ".to_string(), accent),
-            Span::styled("config location".to_string(), normal),
+            Span::styled(t("onboarding.config_location"), normal),
         ]),
         Line::from(vec![
             Span::raw("  "),
             Span::styled("~/.locus/env".to_string(), normal),
-            Span::styled("  source it after updates, then restart the TUI", muted),
+            Span::styled(format!("  {}", t("onboarding.config_location_hint")), muted),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("● ".to_string(), accent),
-            Span::styled("next".to_string(), normal),
+            Span::styled(t("onboarding.next"), normal),
         ]),
         Line::from(vec![
             Span::raw("  "),
             Span::styled("Enter".to_string(), normal),
-            Span::styled("  continue to chat", muted),
+            Span::styled(format!("  {}", t("onboarding.continue")), muted),
             Span::styled("  ·  ".to_string(), disabled),
             Span::styled("q".to_string(), normal),
-            Span::styled("  quit", muted),
+            Span::styled(format!("  {}", t("shortcut.quit")), muted),
             Span::styled("  ·  ".to_string(), disabled),
             Span::styled("locus tui --onboarding".to_string(), normal),
-            Span::styled("  show this again", muted),
+            Span::styled(format!("  {}", t("onboarding.show_again")), muted),
         ]),
     ];
     frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
     frame.render_widget(
         Paragraph::new(Line::from(vec![
             Span::styled("Ctrl+D".to_string(), normal),
-            Span::styled(": logs", muted),
+            Span::styled(format!(": {}", t("shortcut.logs")), muted),
             Span::styled("  ·  ".to_string(), disabled),
             Span::styled("docs/prompts.md".to_string(), normal),
-            Span::styled(": prompt ideas", muted),
+            Span::styled(format!(": {}", t("onboarding.prompt_ideas")), muted),
         ])),
         chunks[2],
     );
 }
 
-/// Runtime logs screen: scrollable list of tracing output. Ctrl+D to close.
-fn draw_debug_traces(frame: &mut Frame, state: &mut TuiState, area: Rect) {
-    use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+/// Lines for one inspected provider call: summary, request body, then raw stream events.
+fn provider_call_lines(
+    call: &ProviderCall,
+    palette: &crate::theme::LocusPalette,
+) -> Vec<Line<'static>> {
+    let muted = text_muted_style(palette.text_muted);
+    let heading = |label: String| {
+        Line::from(vec![
+            Span::styled("● ".to_string(), text_style(palette.accent)),
+            Span::styled(label, text_style(palette.text)),
+        ])
+    };
+    let body = |text: &str| {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled("│ ".to_string(), text_muted_style(palette.border_variant)),
+            Span::styled(text.to_string(), muted),
+        ])
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{} · {}", call.provider, call.model),
+            text_style(palette.text),
+        ),
+        Span::styled(
+            format!(
+                "  {} · {}ms",
+                call.started_at.format("%H:%M:%S"),
+                call.duration_ms
+            ),
+            muted,
+        ),
+    ])];
+    if let Some(error) = &call.error {
+        lines.push(Line::from(Span::styled(
+            tf("inspect.error", &[("error", error)]),
+            danger_style(palette.danger),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading(t("inspect.request").to_string()));
+    let request = serde_json::to_string_pretty(&call.request).unwrap_or_default();
+    lines.extend(request.lines().map(body));
+
+    lines.push(Line::from(""));
+    lines.push(heading(tf(
+        "inspect.stream_events",
+        &[("count", &(call.events.len() + call.dropped_events))],
+    )));
+    lines.extend(call.events.iter().map(|e| body(e)));
+    if call.dropped_events > 0 {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  … {}",
+                tf("inspect.dropped_events", &[("count", &call.dropped_events)])
+            ),
+            muted,
+        )));
+    }
+    lines
+}
+
+/// Provider inspection screen: the last N request payloads and raw stream events. Ctrl+P to close.
+fn draw_provider_calls(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    use ratatui::widgets::{Borders, Wrap};
 
     let palette = &state.palette;
     frame.render_widget(
@@ -455,17 +851,23 @@ fn draw_debug_traces(frame: &mut Frame, state: &mut TuiState, area: Rect) {
         ])
         .split(area);
 
-    let status = if state.trace_lines.is_empty() {
-        "No logs".to_string()
-    } else {
-        format!("{} lines", state.trace_lines.len())
+    let call = state.provider_calls.get(state.provider_call_index);
+    let status = match call {
+        Some(_) => tf(
+            "inspect.call_position",
+            &[
+                ("index", &(state.provider_call_index + 1)),
+                ("total", &state.provider_calls.len()),
+            ],
+        ),
+        None => t("inspect.empty_status").to_string(),
     };
     render_header(
         frame,
         chunks[0],
         palette,
-        HEADER_TITLE,
-        "runtime logs",
+        t(HEADER_TITLE),
+        t("inspect.section"),
         status.as_str(),
         false,
         false,
@@ -478,53 +880,61 @@ fn draw_debug_traces(frame: &mut Frame, state: &mut TuiState, area: Rect) {
     let inner = block.inner(chunks[1]);
     frame.render_widget(block, chunks[1]);
 
-    let content_height = state.trace_lines.len();
-    let viewport_height = inner.height as usize;
-    let max_scroll = content_height.saturating_sub(viewport_height);
-    state.trace_scroll = state.trace_scroll.min(max_scroll);
-    let offset_from_top = max_scroll.saturating_sub(state.trace_scroll);
-
-    let lines: Vec<Line> = if state.trace_lines.is_empty() {
-        vec![
+    let lines = match call {
+        Some(call) => provider_call_lines(call, palette),
+        None => vec![
             Line::from(""),
             Line::from(vec![
                 Span::styled("● ".to_string(), text_style(palette.accent)),
-                Span::styled(
-                    "runtime tracing is quiet".to_string(),
-                    text_style(palette.text),
-                ),
+                Span::styled(t("inspect.empty_title"), text_style(palette.text)),
             ]),
             Line::from(vec![Span::styled(
-                "  logs from the runtime and event stream appear here while the session is active",
+                format!("  {}", t("inspect.empty_hint")),
                 text_muted_style(palette.text_muted),
             )]),
-        ]
-    } else {
-        state
-            .trace_lines
-            .iter()
-            .skip(offset_from_top)
-            .take(viewport_height)
-            .map(|line| {
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("│ ".to_string(), text_muted_style(palette.border_variant)),
-                    Span::styled(line.clone(), text_muted_style(palette.text_muted)),
-                ])
-            })
-            .collect()
+        ],
     };
-    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    state.provider_call_scroll = state.provider_call_scroll.min(max_scroll);
+    let visible: Vec<Line> = lines
+        .into_iter()
+        .skip(state.provider_call_scroll)
+        .take(inner.height as usize)
+        .collect();
+    frame.render_widget(Paragraph::new(visible).wrap(Wrap { trim: false }), inner);
+
+    let sep = || Span::styled("  ·  ".to_string(), text_muted_style(palette.text_disabled));
     frame.render_widget(
         Paragraph::new(Line::from(vec![
             Span::styled("Esc".to_string(), text_style(palette.text)),
-            Span::styled(": back", text_muted_style(palette.text_muted)),
-            Span::styled("  ·  ".to_string(), text_muted_style(palette.text_disabled)),
-            Span::styled("↑↓".to_string(), text_style(palette.text)),
-            Span::styled(": scroll", text_muted_style(palette.text_muted)),
-            Span::styled("  ·  ".to_string(), text_muted_style(palette.text_disabled)),
+            Span::styled(
+                format!(": {}", t("shortcut.back")),
+                text_muted_style(palette.text_muted),
+            ),
+            sep(),
+            Span::styled("←→".to_string(), text_style(palette.text)),
+            Span::styled(
+                format!(": {}", t("shortcut.older_newer")),
+                text_muted_style(palette.text_muted),
+            ),
+            sep(),
+            Span::styled("↑↓/jk".to_string(), text_style(palette.text)),
+            Span::styled(
+                format!(": {}", t("shortcut.scroll")),
+                text_muted_style(palette.text_muted),
+            ),
+            sep(),
             Span::styled("PgUp/PgDn".to_string(), text_style(palette.text)),
-            Span::styled(": faster", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!(": {}", t("shortcut.faster")),
+                text_muted_style(palette.text_muted),
+            ),
+            sep(),
+            Span::styled("g/G".to_string(), text_style(palette.text)),
+            Span::styled(
+                format!(": {}", t("shortcut.top_bottom")),
+                text_muted_style(palette.text_muted),
+            ),
         ])),
         chunks[2],
     );
@@ -545,7 +955,7 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
         frame,
         splits.header,
         palette,
-        HEADER_TITLE,
+        t(HEADER_TITLE),
         header_section_label(state, phase),
         header_status.as_str(),
         phase.is_active(),
@@ -578,12 +988,15 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
 
         let mut lines = Vec::new();
         let mut i = 0;
+        state.selection_lines = None;
         while i < state.messages.len() {
             if i > 0 {
                 for _ in 0..message_spacing_between(&state.messages[i - 1], &state.messages[i]) {
                     lines.push(spacer.clone());
                 }
             }
+            let item_start = lines.len();
+            let item_index = i;
             match &state.messages[i] {
                 ChatItem::Tool(t) => {
                     let elapsed = t
@@ -665,6 +1078,10 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
                     lines.push(memory::memory_line(m, palette));
                     i += 1;
                 }
+                ChatItem::Insights(m) => {
+                    lines.extend(insights::insights_lines(m, palette, width));
+                    i += 1;
+                }
                 ChatItem::Error(m) => {
                     lines.extend(error::error_message_lines(m, palette, width));
                     i += 1;
@@ -707,6 +1124,14 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
                     i += 1;
                 }
             }
+            // Item selected for copying (Alt+V): highlighted
+            if state.chat_selection == Some(item_index) {
+                let selected = background_style(palette.element_selected);
+                for line in &mut lines[item_start..] {
+                    line.style = line.style.patch(selected);
+                }
+                state.selection_lines = Some((item_start, lines.len()));
+            }
         }
         state.cached_lines = lines.clone();
         state.cache_dirty = false;
@@ -799,6 +1224,20 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
     // Scroll clamp: state.scroll is "lines scrolled UP from bottom" (0 = at bottom).
     let max_scroll = content_height.saturating_sub(viewport_height);
     state.scroll = state.scroll.min(max_scroll);
+    // Keep the item selected for copying in view
+    if state.chat_selection.is_some()
+        && let Some((start, end)) = state.selection_lines
+    {
+        let offset = max_scroll.saturating_sub(state.scroll);
+        let offset = if start < offset {
+            start
+        } else if end > offset + viewport_height {
+            end.saturating_sub(viewport_height).min(start)
+        } else {
+            offset
+        };
+        state.scroll = max_scroll.saturating_sub(offset.min(max_scroll));
+    }
     state.last_content_height = content_height;
     state.last_viewport_height = viewport_height;
 
@@ -864,19 +1303,32 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
         frame.render_widget(Paragraph::new(scrollbar_lines), scrollbar_rect);
     }
 
-    // ---- Footer: input block + shortcut ----
+    // ---- Footer: input block + shortcut + status bar ----
     frame.render_widget(
         Block::default().style(background_style(palette.status_bar_background)),
         splits.footer,
     );
-    let input_height = splits.footer.height.saturating_sub(INPUT_SHORTCUT_HEIGHT);
-    let (input_rect, shortcut_rect) = vertical_split(splits.footer, input_height);
+    let input_height = splits
+        .footer
+        .height
+        .saturating_sub(INPUT_SHORTCUT_HEIGHT + STATUS_BAR_HEIGHT);
+    let (input_rect, bottom_rect) = vertical_split(splits.footer, input_height);
+    let (shortcut_rect, status_bar_rect) = vertical_split(bottom_rect, INPUT_SHORTCUT_HEIGHT);
 
     let block = block_for_input_bordered(palette, true);
     let inner = block.inner(input_rect);
     frame.render_widget(block, input_rect);
 
-    let placeholder = "Ask anything…";
+    let placeholder = match &state.annotation_target {
+        Some(target) => tf(
+            "input.placeholder_note",
+            &[(
+                "tool",
+                &crate::messages::tools::format_tool_name(&target.tool_name),
+            )],
+        ),
+        None => t("input.placeholder").to_string(),
+    };
     let (icon_style, content_style) = if state.input_buffer.is_empty() {
         (
             text_style(palette.accent),
@@ -886,10 +1338,13 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
         (text_style(palette.success), text_style(palette.text))
     };
     let icon_width = INPUT_ICON.width();
-    if state.input_buffer.is_empty() {
+    if let Some(search) = &state.history.search {
+        let search_line = crate::history::search_line(&state.history, search, palette);
+        frame.render_widget(Paragraph::new(search_line), inner);
+    } else if state.input_buffer.is_empty() {
         let input_line = ratatui::text::Line::from(vec![
             ratatui::text::Span::styled(INPUT_ICON.to_string(), icon_style),
-            ratatui::text::Span::styled(placeholder.to_string(), content_style),
+            ratatui::text::Span::styled(placeholder, content_style),
         ]);
         frame.render_widget(Paragraph::new(input_line), inner);
         frame.set_cursor_position((inner.x + icon_width as u16, inner.y));
@@ -931,11 +1386,21 @@ fn draw_main(frame: &mut Frame, state: &mut TuiState, area: Rect) {
                 .footer_label()
                 .map(|_| crate::animation::spinner_frame(state.frame_count)),
             !state.input_buffer.is_empty(),
+            state.annotation_target.is_some(),
             state.diff_page_message_index.is_some(),
             has_ai_history(&state.messages),
         )),
         shortcut_inner,
     );
+
+    let status_bar_inner = shortcut_inner_rect(status_bar_rect);
+    frame.render_widget(
+        Paragraph::new(crate::status_bar::status_bar_line(
+            state,
+            status_bar_inner.width as usize,
+        )),
+        status_bar_inner,
+    );
 }
 
 #[cfg(test)]
@@ -947,6 +1412,101 @@ mod tests {
         user::UserMessage,
     };
 
+    #[test]
+    fn approval_dialog_shows_command_and_keys() {
+        let palette = crate::theme::LocusPalette::locus_dark();
+        let request = ApprovalRequest::new(
+            "bash",
+            locus_core::Permission::Execute,
+            serde_json::json!({ "command": "cargo test", "timeout": 60 }),
+        );
+
+        let text: Vec<String> = approval_dialog_lines(&request, 2, None, &palette)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert!(text[0].contains("needs execute permission"));
+        assert!(text.iter().any(|l| l == "│ cargo test"));
+        assert!(text.iter().any(|l| l.contains("always allow")));
+        assert_eq!(text.last().unwrap(), "2 more waiting");
+    }
+
+    #[test]
+    fn approval_dialog_previews_writes_and_shows_the_denial_message() {
+        let palette = crate::theme::LocusPalette::locus_dark();
+        let request = ApprovalRequest::new(
+            "edit_file",
+            locus_core::Permission::Write,
+            serde_json::json!({ "path": "src/lib.rs", "old_string": "a - b", "new_string": "a + b" }),
+        )
+        .with_preview(locus_core::ApprovalPreview {
+            path: "src/lib.rs".to_string(),
+            before: "a - b\n".to_string(),
+            after: "a + b\n".to_string(),
+        });
+
+        let text: Vec<String> = approval_dialog_lines(&request, 0, Some("keep minus"), &palette)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert!(text.iter().any(|l| l == "│ src/lib.rs"));
+        assert!(text.iter().any(|l| l == "- a - b"));
+        assert!(text.iter().any(|l| l == "+ a + b"));
+        assert!(text.iter().any(|l| l.contains("keep minus")));
+        assert!(!text.iter().any(|l| l.contains("always allow")));
+    }
+
+    #[test]
+    fn conflict_dialog_shows_both_changes_and_keys() {
+        let palette = crate::theme::LocusPalette::locus_dark();
+        let request = ConflictRequest::new(
+            "edit_file",
+            "src/lib.rs",
+            "let a = 1;\n\nlet b = 2;\n",
+            "let a = 10;\n\nlet b = 2;\n",
+            "let a = 1;\n\nlet b = 3;\n",
+        );
+
+        let text: Vec<String> = conflict_dialog_lines(&request, 0, &palette)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert!(text[0].starts_with("src/lib.rs"));
+        let user = text.iter().position(|l| l == "Your changes").unwrap();
+        let agent = text.iter().position(|l| l == "Agent's edit").unwrap();
+        assert_eq!(text[user + 1..user + 3], ["- let a = 1;", "+ let a = 10;"]);
+        assert_eq!(text[agent + 1..agent + 3], ["- let b = 2;", "+ let b = 3;"]);
+        assert!(text.iter().any(|l| l.contains("merge cleanly")));
+        assert!(text.last().unwrap().contains("rebase onto your changes"));
+    }
+
+    #[test]
+    fn provider_call_lines_show_request_and_events() {
+        let palette = crate::theme::LocusPalette::locus_dark();
+        let mut call = ProviderCall::new(
+            "anthropic",
+            "claude",
+            serde_json::json!({ "messages": [{ "role": "system" }] }),
+        );
+        call.push_event(r#"{"type":"text_delta","delta":"hi"}"#);
+        let call = call.finish(120, Some("overloaded".to_string()));
+
+        let text: Vec<String> = provider_call_lines(&call, &palette)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert!(text[0].contains("anthropic · claude"));
+        assert!(text[0].contains("120ms"));
+        assert!(text.iter().any(|l| l.contains("error: overloaded")));
+        assert!(text.iter().any(|l| l.contains(r#""role": "system""#)));
+        assert!(text.iter().any(|l| l.contains("stream events (1)")));
+        assert!(text.iter().any(|l| l.contains("text_delta")));
+    }
+
     #[test]
     fn tool_and_diff_stay_attached() {
         let tool = ChatItem::Tool(ToolCallMessage::running("t1", "edit_file", None));
@@ -8,7 +8,10 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use crate::layouts::{background_style, border_style, render_header, text_muted_style, text_style};
+use crate::i18n::t;
+use crate::layouts::{
+    HEADER_TITLE, background_style, border_style, render_header, text_muted_style, text_style,
+};
 use crate::theme::LocusPalette;
 use crate::utils::LEFT_PADDING;
 use crate::web_automation::state::{AutomationStatus, WebAutomationState};
@@ -43,11 +46,11 @@ pub fn draw_web_automation(
 
 fn draw_header(frame: &mut Frame, area: Rect, state: &WebAutomationState, palette: &LocusPalette) {
     let (status_text, active, has_error) = match state.status {
-        AutomationStatus::Idle => ("Idle".to_string(), false, false),
-        AutomationStatus::Starting => ("Preparing browser".to_string(), true, false),
-        AutomationStatus::Running => ("Running".to_string(), true, false),
-        AutomationStatus::Completed => ("Completed".to_string(), false, false),
-        AutomationStatus::Failed => ("Failed".to_string(), false, true),
+        AutomationStatus::Idle => (t("web.status.idle").to_string(), false, false),
+        AutomationStatus::Starting => (t("web.status.starting").to_string(), true, false),
+        AutomationStatus::Running => (t("web.status.running").to_string(), true, false),
+        AutomationStatus::Completed => (t("web.status.completed").to_string(), false, false),
+        AutomationStatus::Failed => (t("web.status.failed").to_string(), false, true),
     };
 
     let status = if state.is_running() {
@@ -62,8 +65,8 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &WebAutomationState, palett
         frame,
         area,
         palette,
-        "locus.codes",
-        "web automation",
+        t(HEADER_TITLE),
+        t("web.title"),
         status.as_str(),
         active,
         has_error,
@@ -84,12 +87,12 @@ fn draw_input_section(
     frame.render_widget(block, area);
 
     let url_text = if state.url.is_empty() {
-        "(not set)".to_string()
+        t("web.not_set").to_string()
     } else {
         state.url.clone()
     };
     let goal_text = if state.goal.is_empty() {
-        "(not set)".to_string()
+        t("web.not_set").to_string()
     } else if state.goal.len() > 72 {
         format!("{}…", &state.goal[..71])
     } else {
@@ -99,13 +102,16 @@ fn draw_input_section(
         .run_id
         .as_deref()
         .map(str::to_string)
-        .unwrap_or_else(|| "waiting".to_string());
+        .unwrap_or_else(|| t("web.waiting").to_string());
 
     let lines = vec![
         Line::from(vec![
             Span::raw(LEFT_PADDING),
             Span::styled("▏ ".to_string(), text_muted_style(palette.border_variant)),
-            Span::styled("url   ".to_string(), text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!("{:<6}", t("web.label.url")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled(
                 url_text,
                 if state.url.is_empty() {
@@ -118,7 +124,10 @@ fn draw_input_section(
         Line::from(vec![
             Span::raw(LEFT_PADDING),
             Span::styled("▏ ".to_string(), text_muted_style(palette.border_variant)),
-            Span::styled("goal  ".to_string(), text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!("{:<6}", t("web.label.goal")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled(
                 goal_text,
                 if state.goal.is_empty() {
@@ -131,7 +140,10 @@ fn draw_input_section(
         Line::from(vec![
             Span::raw(LEFT_PADDING),
             Span::styled("▏ ".to_string(), text_muted_style(palette.border_variant)),
-            Span::styled("run   ".to_string(), text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!("{:<6}", t("web.label.run")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled(run_text, text_muted_style(palette.text_muted)),
         ]),
     ];
@@ -161,26 +173,29 @@ fn draw_progress_section(
         lines.push(Line::from(vec![
             Span::raw(LEFT_PADDING),
             Span::styled("● ".to_string(), text_style(palette.accent)),
-            Span::styled(
-                "browser automation is idle".to_string(),
-                text_style(palette.text),
-            ),
+            Span::styled(t("web.idle").to_string(), text_style(palette.text)),
         ]));
         lines.push(Line::from(vec![
             Span::raw(LEFT_PADDING),
             Span::styled(
-                "  press Enter to launch a sample run",
+                format!("  {}", t("web.idle_hint")),
                 text_muted_style(palette.text_muted),
             ),
         ]));
         lines.push(Line::from(vec![
             Span::raw(LEFT_PADDING),
-            Span::styled("  url", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!("  {}", t("web.label.url")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled("  https://example.com", text_style(palette.text)),
         ]));
         lines.push(Line::from(vec![
             Span::raw(LEFT_PADDING),
-            Span::styled("  goal", text_muted_style(palette.text_muted)),
+            Span::styled(
+                format!("  {}", t("web.label.goal")),
+                text_muted_style(palette.text_muted),
+            ),
             Span::styled("  Extract the page title", text_style(palette.text)),
         ]));
     } else {
@@ -189,7 +204,10 @@ fn draw_progress_section(
             lines.push(Line::from(vec![
                 Span::raw(LEFT_PADDING),
                 Span::styled("▏ ".to_string(), text_style(palette.info)),
-                Span::styled("live browser  ".to_string(), text_style(palette.info)),
+                Span::styled(
+                    format!("{}  ", t("web.live_browser")),
+                    text_style(palette.info),
+                ),
                 Span::styled(url, text_style(palette.accent)),
             ]));
             lines.push(Line::from(""));
@@ -227,7 +245,7 @@ fn draw_progress_section(
                 Span::raw(LEFT_PADDING),
                 Span::styled("▏ ".to_string(), text_muted_style(palette.border_variant)),
                 Span::styled(
-                    "Result:",
+                    t("web.result"),
                     text_style(palette.text).add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -251,7 +269,7 @@ fn draw_progress_section(
                 Span::raw(LEFT_PADDING),
                 Span::styled("▏ ".to_string(), text_style(palette.danger)),
                 Span::styled(
-                    "Error:",
+                    t("web.error"),
                     text_style(palette.danger).add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -286,9 +304,17 @@ fn draw_shortcuts(
     palette: &LocusPalette,
 ) {
     let shortcuts = if state.is_running() {
-        vec![("Ctrl+W", "back"), ("Ctrl+C", "cancel"), ("↑↓", "scroll")]
+        vec![
+            ("Ctrl+W", t("shortcut.back")),
+            ("Ctrl+C", t("shortcut.cancel")),
+            ("↑↓", t("shortcut.scroll")),
+        ]
     } else {
-        vec![("Ctrl+W", "back"), ("Enter", "sample run"), ("r", "reset")]
+        vec![
+            ("Ctrl+W", t("shortcut.back")),
+            ("Enter", t("web.shortcut.sample_run")),
+            ("r", t("web.shortcut.reset")),
+        ]
     };
 
     let mut spans: Vec<Span> = Vec::new();
@@ -4,6 +4,8 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
+use crate::tui_log_layer::LogBuffer;
+
 /// Sink for runtime log lines (e.g. TUI debug traces). Called from the tracing layer; must not block.
 pub type LogSink = Arc<dyn Fn(String) + Send + Sync>;
 
@@ -31,10 +33,13 @@ pub struct ObservabilityConfig {
 
     /// Optional sink for each formatted log line (e.g. TUI debug traces). Not serialized.
     pub log_sink: Option<LogSink>,
+
+    /// Optional ring buffer each log record is kept in (e.g. TUI logs screen). Not serialized.
+    pub log_buffer: Option<LogBuffer>,
 }
 
 // Serde doesn't support Arc<dyn Fn>, so we don't derive Serialize/Deserialize for the whole struct.
-// We use a separate impl and skip log_sink.
+// We use a separate impl and skip log_sink and log_buffer.
 impl Serialize for ObservabilityConfig {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -81,6 +86,7 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
             log_level: de.log_level,
             resource_attributes: de.resource_attributes,
             log_sink: None,
+            log_buffer: None,
         })
     }
 }
@@ -95,6 +101,7 @@ impl Default for ObservabilityConfig {
             log_level: None,
             resource_attributes: Vec::new(),
             log_sink: None,
+            log_buffer: None,
         }
     }
 }
@@ -109,6 +116,10 @@ impl std::fmt::Debug for ObservabilityConfig {
             .field("log_level", &self.log_level)
             .field("resource_attributes", &self.resource_attributes)
             .field("log_sink", &self.log_sink.as_ref().map(|_| "Some(LogSink)"))
+            .field(
+                "log_buffer",
+                &self.log_buffer.as_ref().map(|_| "Some(LogBuffer)"),
+            )
             .finish()
     }
 }
@@ -162,6 +173,12 @@ impl ObservabilityConfig {
         self
     }
 
+    /// Ring buffer to keep each log record in (e.g. TUI logs screen).
+    pub fn with_log_buffer(mut self, buffer: LogBuffer) -> Self {
+        self.log_buffer = Some(buffer);
+        self
+    }
+
     /// Build from environment variables
     ///
     /// Reads:
@@ -195,6 +212,7 @@ impl ObservabilityConfig {
             log_level,
             resource_attributes: Vec::new(),
             log_sink: None,
+            log_buffer: None,
         }
     }
 }
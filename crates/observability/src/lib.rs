@@ -5,7 +5,7 @@
 //!
 //! # Features
 //!
-//! - OpenTelemetry integration with OTLP export
+//! - OpenTelemetry integration with OTLP export (`otel` cargo feature, on by default)
 //! - Configurable via environment variables or programmatic API
 //! - Reusable tracing utilities (HTTP, agent, storage spans)
 //! - Console logging integration
@@ -51,8 +51,9 @@ pub mod tui_log_layer;
 
 pub use config::ObservabilityConfig;
 pub use error::ObservabilityError;
-pub use telemetry::{init, init_from_env, shutdown};
+pub use telemetry::{init, init_from_env, shutdown, OTEL_ENABLED};
 pub use tracing::{record_duration, record_error};
+pub use tui_log_layer::{LogBuffer, LogRecord};
 
 // Macros are automatically exported via #[macro_export] and available
 // as locusgraph_observability::agent_span!(), etc.
@@ -1,16 +1,24 @@
 //! OpenTelemetry telemetry initialization for version 0.31.0
 //!
 //! Implements OTLP export to Tempo/Grafana with console logging fallback.
+//! OTLP export needs the `otel` feature; without it an endpoint is ignored with a
+//! warning and only console and TUI logging are set up.
 //!
 //! Based on OpenTelemetry Rust 0.31 API patterns from:
 //! https://github.com/open-telemetry/opentelemetry-rust
 //! https://docs.rs/opentelemetry-otlp/0.31.0/opentelemetry_otlp/
 
+#[cfg(feature = "otel")]
 use once_cell::sync::OnceCell;
+#[cfg(feature = "otel")]
 use opentelemetry::{global, trace::TracerProvider, KeyValue};
+#[cfg(feature = "otel")]
 use opentelemetry_otlp::WithExportConfig;
+#[cfg(feature = "otel")]
 use opentelemetry_sdk::trace::SdkTracerProvider;
+#[cfg(feature = "otel")]
 use opentelemetry_sdk::Resource;
+#[cfg(feature = "otel")]
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Registry};
 
@@ -18,7 +26,11 @@ use crate::config::ObservabilityConfig;
 use crate::error::ObservabilityError;
 use crate::tui_log_layer;
 
+/// Whether this build can export spans over OTLP (the `otel` feature).
+pub const OTEL_ENABLED: bool = cfg!(feature = "otel");
+
 // Store the tracer provider for proper shutdown
+#[cfg(feature = "otel")]
 static TRACER_PROVIDER: OnceCell<SdkTracerProvider> = OnceCell::new();
 
 /// Initialize OpenTelemetry tracing with the given configuration
@@ -42,6 +54,50 @@ pub fn init(config: ObservabilityConfig) -> Result<(), ObservabilityError> {
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))
         });
 
+    // Build layers first (build separately, then compose once to avoid type mismatch)
+    let fmt_layer = config
+        .enable_console
+        .then_some(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    let otel_layer = otlp_layer(&config);
+    #[cfg(not(feature = "otel"))]
+    let otel_layer = {
+        if let Some(endpoint) = &config.otlp_endpoint {
+            tracing::warn!(
+                service.name = %config.service_name,
+                endpoint = %endpoint,
+                "Built without the otel feature, OTLP endpoint ignored"
+            );
+        }
+        None::<tracing_subscriber::layer::Identity>
+    };
+
+    // Optional TUI log sink and buffer (runtime logs for the logs screen)
+    let tui_layer =
+        tui_log_layer::tui_log_layer(config.log_sink.clone(), config.log_buffer.clone());
+
+    // Compose subscriber once (no mutation, avoids type mismatch)
+    let subscriber = Registry::default()
+        .with(env_filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .with(tui_layer);
+
+    // Initialize subscriber
+    subscriber.init();
+
+    Ok(())
+}
+
+/// OTLP layer for `config`, or None without an endpoint or when the exporter fails.
+#[cfg(feature = "otel")]
+fn otlp_layer<S>(
+    config: &ObservabilityConfig,
+) -> Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::SdkTracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
     // Build resource using Resource::new() pattern (0.31 API)
     let mut attributes = vec![KeyValue::new("service.name", config.service_name.clone())];
 
@@ -58,13 +114,8 @@ pub fn init(config: ObservabilityConfig) -> Result<(), ObservabilityError> {
     // In 0.31, Resource constructors are private; ResourceBuilder is the public API
     let resource = Resource::builder().with_attributes(attributes).build();
 
-    // Build layers first (build separately, then compose once to avoid type mismatch)
-    let fmt_layer = config
-        .enable_console
-        .then_some(tracing_subscriber::fmt::layer());
-
     // Build OTLP layer if endpoint is configured
-    let otel_layer = if let Some(endpoint) = &config.otlp_endpoint {
+    if let Some(endpoint) = &config.otlp_endpoint {
         match build_otlp_tracer_provider(&config.service_name, endpoint, resource.clone()) {
             Ok((tracer, provider)) => {
                 // Set as global provider BEFORE creating layer (important ordering)
@@ -97,22 +148,7 @@ pub fn init(config: ObservabilityConfig) -> Result<(), ObservabilityError> {
             "Tracing initialized (console only, no OTLP endpoint configured)"
         );
         None
-    };
-
-    // Optional TUI log sink (runtime logs for debug traces screen)
-    let tui_layer = tui_log_layer::tui_log_layer(config.log_sink.clone());
-
-    // Compose subscriber once (no mutation, avoids type mismatch)
-    let subscriber = Registry::default()
-        .with(env_filter)
-        .with(fmt_layer)
-        .with(otel_layer)
-        .with(tui_layer);
-
-    // Initialize subscriber
-    subscriber.init();
-
-    Ok(())
+    }
 }
 
 /// Build OTLP tracer provider for OpenTelemetry 0.31
@@ -129,6 +165,7 @@ pub fn init(config: ObservabilityConfig) -> Result<(), ObservabilityError> {
 /// # Returns
 ///
 /// Returns (tracer, provider) on success, or an error if initialization fails
+#[cfg(feature = "otel")]
 fn build_otlp_tracer_provider(
     service_name: &str,
     endpoint: &str,
@@ -162,6 +199,7 @@ fn build_otlp_tracer_provider(
 /// Call this during graceful shutdown to ensure all traces are exported.
 /// Uses the correct 0.31 pattern: call shutdown() on the provider instance.
 pub fn shutdown() {
+    #[cfg(feature = "otel")]
     if let Some(provider) = TRACER_PROVIDER.get() {
         // In 0.31, shutdown() returns Result but errors are logged internally
         let _ = provider.shutdown();
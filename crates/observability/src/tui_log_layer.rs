@@ -1,8 +1,12 @@
-//! Tracing layer that forwards formatted log lines to a sink (e.g. TUI debug traces).
+//! Tracing layer that forwards log lines to a sink and keeps them in a
+//! [LogBuffer] (e.g. for the TUI logs screen).
 
+use std::collections::VecDeque;
 use std::fmt::Write;
+use std::sync::{Arc, Mutex};
 
 use tracing::field::Visit;
+use tracing::Level;
 use tracing_subscriber::layer::{Context, Layer};
 
 use crate::config::LogSink;
@@ -55,14 +59,110 @@ impl Visit for LineVisitor {
     }
 }
 
-/// Layer that sends each formatted event to the given sink when present. The sink must not block.
-pub(crate) fn tui_log_layer(sink: Option<LogSink>) -> TuiLogLayer {
-    TuiLogLayer { sink }
+/// One log event: its level, target and message with fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    pub fn new(level: Level, target: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            target: target.into(),
+            message: message.into(),
+        }
+    }
+
+    /// "[LEVEL] target: message", or "[LEVEL] target" without a message.
+    pub fn line(&self) -> String {
+        if self.message.is_empty() {
+            format!("[{}] {}", self.level, self.target)
+        } else {
+            format!("[{}] {}: {}", self.level, self.target, self.message)
+        }
+    }
+}
+
+/// The latest log records, shared between the tracing layer and whoever
+/// shows them; past `capacity` the oldest are dropped. Pushing never blocks
+/// on a reader for longer than a copy.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<LogRing>>,
+}
+
+#[derive(Debug)]
+struct LogRing {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    /// Records pushed since the buffer was made, dropped ones included.
+    pushed: u64,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogRing {
+                records: VecDeque::new(),
+                capacity: capacity.max(1),
+                pushed: 0,
+            })),
+        }
+    }
+
+    pub fn push(&self, record: LogRecord) {
+        let mut ring = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if ring.records.len() >= ring.capacity {
+            ring.records.pop_front();
+        }
+        ring.records.push_back(record);
+        ring.pushed += 1;
+    }
+
+    /// Records pushed after the first `seen` that are still kept, and the
+    /// count to pass next time.
+    pub fn since(&self, seen: u64) -> (Vec<LogRecord>, u64) {
+        let ring = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let new = ring
+            .pushed
+            .saturating_sub(seen)
+            .min(ring.records.len() as u64) as usize;
+        let skip = ring.records.len() - new;
+        (
+            ring.records.iter().skip(skip).cloned().collect(),
+            ring.pushed,
+        )
+    }
+
+    /// Every record kept, oldest first.
+    pub fn records(&self) -> Vec<LogRecord> {
+        let ring = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        ring.records.iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        let ring = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        ring.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Layer that sends each formatted event to the given sink and pushes it to
+/// the buffer, when present. The sink must not block.
+pub(crate) fn tui_log_layer(sink: Option<LogSink>, buffer: Option<LogBuffer>) -> TuiLogLayer {
+    TuiLogLayer { sink, buffer }
 }
 
 #[derive(Clone)]
 pub(crate) struct TuiLogLayer {
     sink: Option<LogSink>,
+    buffer: Option<LogBuffer>,
 }
 
 impl<S> Layer<S> for TuiLogLayer
@@ -70,25 +170,29 @@ where
     S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
 {
     fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if self.sink.is_none() && self.buffer.is_none() {
+            return;
+        }
         let level = *event.metadata().level();
         let target = event.metadata().target();
         let mut visitor = LineVisitor::new();
         event.record(&mut visitor);
-        let rest = visitor.finish();
-        let line = if rest.is_empty() {
-            format!("[{}] {}", level, target)
-        } else {
-            format!("[{}] {}: {}", level, target, rest)
-        };
+        let message = visitor.finish();
         const MAX_LEN: usize = 32_000;
-        let line = if line.len() > MAX_LEN {
-            let trunc: String = line.chars().take(MAX_LEN).collect();
-            format!("{}… ({} chars)", trunc, line.len())
+        let message = if message.len() > MAX_LEN {
+            let trunc: String = message.chars().take(MAX_LEN).collect();
+            format!("{}… ({} chars)", trunc, message.len())
         } else {
-            line
+            message
         };
         if let Some(ref sink) = self.sink {
-            sink(line);
+            sink(LogRecord::new(level, target, message.as_str()).line());
+        }
+        if let Some(ref buffer) = self.buffer {
+            // One record per line, so the logs screen can scroll and filter them
+            for line in message.split('\n') {
+                buffer.push(LogRecord::new(level, target, line));
+            }
         }
     }
 }
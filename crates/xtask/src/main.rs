@@ -0,0 +1,180 @@
+//! Repo maintenance tasks, run with `cargo xtask <task>` (alias in `.cargo/config.toml`).
+//!
+//! - `new-tool <name>` — scaffold a builtin tool in `locus_toolbus`: `src/tools/<name>/`
+//!   (`mod.rs`, `args.rs`, `error.rs`, `schema.json`) and `src/tests/tools/<name>.rs`
+//!   using `locus_toolbus::testing`, plus the `mod` / `pub use` lines. The generated tool
+//!   builds and its tests pass; registering it with the ToolBus is left to the author.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+const USAGE: &str = "usage: cargo xtask new-tool <snake_case_name>";
+
+/// Templates for `new-tool`: (template, path under `crates/locus_toolbus/src`).
+const TOOL_TEMPLATES: &[(&str, &str)] = &[
+    (
+        include_str!("../templates/new_tool/mod.rs.tmpl"),
+        "tools/{{name}}/mod.rs",
+    ),
+    (
+        include_str!("../templates/new_tool/args.rs.tmpl"),
+        "tools/{{name}}/args.rs",
+    ),
+    (
+        include_str!("../templates/new_tool/error.rs.tmpl"),
+        "tools/{{name}}/error.rs",
+    ),
+    (
+        include_str!("../templates/new_tool/schema.json.tmpl"),
+        "tools/{{name}}/schema.json",
+    ),
+    (
+        include_str!("../templates/new_tool/test.rs.tmpl"),
+        "tests/tools/{{name}}.rs",
+    ),
+];
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["new-tool", name] => new_tool(&workspace_root(), name),
+        _ => bail!(USAGE),
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("xtask lives in crates/xtask")
+        .to_path_buf()
+}
+
+/// `grep_files` → `GrepFiles`.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn render(template: &str, name: &str) -> String {
+    template
+        .replace("{{name}}", name)
+        .replace("{{Name}}", &pascal_case(name))
+}
+
+/// Insert `line` into the run of lines starting with `prefix`, keeping the run sorted.
+fn insert_sorted(source: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let first = lines
+        .iter()
+        .position(|l| l.starts_with(prefix))
+        .with_context(|| format!("no `{}` lines to extend", prefix.trim()))?;
+    // Feature-gated entries carry a `#[cfg(...)]` line; it belongs to the block.
+    let end = lines[first..]
+        .iter()
+        .position(|l| {
+            !l.starts_with(prefix) && !l.starts_with("    ") && *l != "};" && !l.starts_with("#[")
+        })
+        .map_or(lines.len(), |n| first + n);
+    let mut at = (first..end)
+        .find(|&i| lines[i].starts_with(prefix) && lines[i] > line)
+        .unwrap_or(end);
+    // Insert above the attributes of the entry that follows, not between them and it.
+    while at > first && lines[at - 1].starts_with("#[") {
+        at -= 1;
+    }
+    lines.insert(at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn new_tool(root: &Path, name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    if !valid {
+        bail!(
+            "tool name must be snake_case, e.g. `lint_report`\n{}",
+            USAGE
+        );
+    }
+
+    let src = root.join("crates/locus_toolbus/src");
+    let tool_dir = src.join("tools").join(name);
+    if tool_dir.exists() {
+        bail!("{} already exists", tool_dir.display());
+    }
+
+    for (template, path) in TOOL_TEMPLATES {
+        let path = src.join(render(path, name));
+        fs::create_dir_all(path.parent().expect("template paths have a parent"))?;
+        fs::write(&path, render(template, name))
+            .with_context(|| format!("write {}", path.display()))?;
+        println!(
+            "  created  {}",
+            path.strip_prefix(root).unwrap_or(&path).display()
+        );
+    }
+
+    let pascal = pascal_case(name);
+    let edits = [
+        ("tools/mod.rs", "pub mod ", format!("pub mod {};", name)),
+        (
+            "tools/mod.rs",
+            "pub use ",
+            format!(
+                "pub use {}::{{{}, {}Args, {}Error}};",
+                name, pascal, pascal, pascal
+            ),
+        ),
+        ("tests/tools/mod.rs", "mod ", format!("mod {};", name)),
+    ];
+    let mut updated: Vec<PathBuf> = Vec::new();
+    for (file, prefix, line) in edits {
+        let path = src.join(file);
+        let source =
+            fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+        fs::write(&path, insert_sorted(&source, prefix, &line)?)?;
+        if !updated.contains(&path) {
+            println!(
+                "  updated  {}",
+                path.strip_prefix(root).unwrap_or(&path).display()
+            );
+            updated.push(path);
+        }
+    }
+
+    // Long names can push generated lines past the width limit; let rustfmt settle them.
+    let generated = TOOL_TEMPLATES
+        .iter()
+        .map(|(_, path)| src.join(render(path, name)))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"));
+    let formatted = Command::new("rustfmt")
+        .args(["--edition", "2024"])
+        .args(generated.chain(updated))
+        .status();
+    if !formatted.is_ok_and(|status| status.success()) {
+        println!("  (rustfmt failed; run cargo fmt -p locus-toolbus)");
+    }
+
+    println!(
+        "\nNext:\n  \
+         1. Describe the tool in tools/{name}/schema.json and fill in args.rs and execute().\n  \
+         2. Register it in ToolBus::register_defaults and re-export it from locus_toolbus/src/lib.rs\n     \
+            (override Tool::permission if it writes, executes, or uses the network).\n  \
+         3. Give it a label and status line in locus_tui/src/messages/tools/mod.rs.\n  \
+         4. cargo test -p locus-toolbus {name}   (writes src/tests/golden/{name}.json on first run)",
+        name = name
+    );
+    Ok(())
+}